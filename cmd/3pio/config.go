@@ -0,0 +1,214 @@
+package main
+
+import (
+	"fmt"
+	"io"
+	"os"
+	"strconv"
+	"strings"
+	"text/tabwriter"
+	"time"
+
+	"github.com/zk/3pio/internal/config"
+	"github.com/zk/3pio/internal/report"
+	"github.com/zk/3pio/internal/runner/definitions"
+)
+
+// configOption is a run option that the config files and THREEPIO_<KEY> variables
+// can set. Its key is the flag without the leading dashes.
+type configOption struct {
+	flag  string                  // Flag taking the value, or for a Bool the flag for true
+	off   string                  // Flag for false (Bool) or 0 (Seconds), when the flag itself can't say it
+	also  []string                // Other flags on the command line that override the key
+	kind  config.Kind             // Type of the value
+	value func(runOptions) string // The option's value in opts, as TOML
+}
+
+// configOptions are the options the config can set, in the order of 3pio config --show
+var configOptions = []configOption{
+	{flag: "--runner", kind: config.String, value: func(o runOptions) string { return strconv.Quote(o.runner) }},
+	{flag: "--no-backtrace", kind: config.Bool, value: func(o runOptions) string { return strconv.FormatBool(o.noBacktrace) }},
+	{flag: "--timeout", kind: config.Seconds, value: func(o runOptions) string { return formatConfigSeconds(o.timeout) }},
+	{flag: "--inactivity-timeout", kind: config.Seconds, value: func(o runOptions) string { return formatConfigSeconds(o.inactivityTimeout) }},
+	{flag: "--rerun-failed", kind: config.Bool, value: func(o runOptions) string { return strconv.FormatBool(o.rerunFailed) }},
+	{flag: "--junit-xml", kind: config.String, value: func(o runOptions) string { return strconv.Quote(o.junitXMLPath) }},
+	{flag: "--summary-json", kind: config.String, value: func(o runOptions) string { return strconv.Quote(o.summaryJSON) }},
+	{flag: "--slow-threshold", kind: config.Seconds, value: func(o runOptions) string {
+		if o.slowThreshold == 0 {
+			return formatConfigSeconds(report.DefaultSlowThreshold)
+		}
+		return formatConfigSeconds(o.slowThreshold)
+	}},
+	{flag: "--gha-annotations", off: "--no-gha-annotations", kind: config.Bool, value: func(o runOptions) string { return strconv.FormatBool(o.ghaAnnotations) }},
+	{flag: "--no-snippets", kind: config.Bool, value: func(o runOptions) string { return strconv.FormatBool(o.noSnippets) }},
+	{flag: "--snippet-lines", kind: config.Int, value: func(o runOptions) string { return strconv.Itoa(o.snippetLines) }},
+	{flag: "--allow-no-tests", kind: config.Bool, value: func(o runOptions) string { return strconv.FormatBool(o.allowNoTests) }},
+	{flag: "--quarantine", kind: config.String, value: func(o runOptions) string { return strconv.Quote(o.quarantinePath) }},
+	{flag: "--retries", kind: config.Int, value: func(o runOptions) string { return strconv.Itoa(o.retries) }},
+	{flag: "--fail-on-flaky", kind: config.Bool, value: func(o runOptions) string { return strconv.FormatBool(o.failOnFlaky) }},
+	{flag: "--fail-on-xpass", kind: config.Bool, value: func(o runOptions) string { return strconv.FormatBool(o.failOnXPass) }},
+	{flag: "--fail-on-warnings", kind: config.Bool, value: func(o runOptions) string { return strconv.FormatBool(o.failOnWarnings) }},
+	{flag: "--strict-args", kind: config.Bool, value: func(o runOptions) string { return strconv.FormatBool(o.strictArgs) }},
+	{flag: "--progress", off: "--no-progress", kind: config.Bool, value: func(o runOptions) string { return strconv.FormatBool(o.progress) }},
+	{flag: "--preflight-list", off: "--no-preflight-list", kind: config.Bool, value: func(o runOptions) string { return strconv.FormatBool(o.preflightList) }},
+	{flag: "--redact", kind: config.Bool, value: func(o runOptions) string { return strconv.FormatBool(o.redact) }},
+	{flag: "--redact-raw", kind: config.Bool, value: func(o runOptions) string { return strconv.FormatBool(o.redactRaw) }},
+	{flag: "--heartbeat", off: "--no-heartbeat", kind: config.Seconds, value: func(o runOptions) string { return formatConfigSeconds(o.heartbeat) }},
+	{flag: "--quiet", also: []string{"-q", "-v", "--verbose"}, kind: config.Bool, value: func(o runOptions) string { return strconv.FormatBool(o.quiet) }},
+	{flag: "--verbose", also: []string{"-v", "-q", "--quiet"}, kind: config.Bool, value: func(o runOptions) string { return strconv.FormatBool(o.verbose) }},
+	{flag: "--max-line-length", kind: config.Size, value: func(o runOptions) string {
+		if o.maxLineLength == 0 {
+			return formatConfigSize(definitions.DefaultMaxLineLength)
+		}
+		return formatConfigSize(o.maxLineLength)
+	}},
+	{flag: "--color", kind: config.String, value: func(o runOptions) string { return strconv.Quote(string(o.color)) }},
+	{flag: "--ipc-transport", kind: config.String, value: func(o runOptions) string { return strconv.Quote(string(o.ipcTransport)) }},
+	{flag: "--output", kind: config.String, value: func(o runOptions) string { return strconv.Quote(o.output) }},
+}
+
+// key returns the option's key in the config files
+func (opt configOption) key() string {
+	return strings.TrimPrefix(opt.flag, "--")
+}
+
+// args returns the flags that give the option a config value
+func (opt configOption) args(value string) []string {
+	switch opt.kind {
+	case config.Bool:
+		if value == "true" {
+			return []string{opt.flag}
+		}
+		if opt.off != "" {
+			return []string{opt.off}
+		}
+		return nil // false is the default
+	case config.Seconds:
+		if value == "0" {
+			if opt.off != "" {
+				return []string{opt.off}
+			}
+			return nil // 0 is the default
+		}
+	}
+	return []string{opt.flag, value}
+}
+
+// overriddenBy reports whether the 3pio flags given on the command line set the option
+func (opt configOption) overriddenBy(given []string) bool {
+	flags := append([]string{opt.flag, opt.off}, opt.also...)
+	for _, arg := range given {
+		name, _, _ := strings.Cut(arg, "=")
+		for _, flag := range flags {
+			if flag != "" && name == flag {
+				return true
+			}
+		}
+	}
+	return false
+}
+
+// configKeys returns the keys of configOptions
+func configKeys() []config.Key {
+	keys := make([]config.Key, len(configOptions))
+	for i, opt := range configOptions {
+		keys[i] = config.Key{Name: opt.key(), Kind: opt.kind}
+	}
+	return keys
+}
+
+// loadConfig reads the user config file, the .3pio.toml of the repository and the
+// THREEPIO_<KEY> variables
+func loadConfig() (*config.Config, error) {
+	return config.Load(config.UserFile(), ".", configKeys())
+}
+
+// withConfig puts the flags for the config's settings before args, leaving out those
+// that the 3pio flags of args set, so the command line wins
+func withConfig(cfg *config.Config, args []string) ([]string, error) {
+	_, command, err := parseRunOptions(args)
+	if err != nil {
+		return nil, err
+	}
+	given := args[:len(args)-len(command)]
+
+	var configArgs []string
+	for _, opt := range configOptions {
+		setting, ok := cfg.Settings[opt.key()]
+		if !ok || opt.overriddenBy(given) {
+			continue
+		}
+		flags := opt.args(setting.Value)
+		if _, _, err := parseRunOptions(flags); err != nil {
+			return nil, fmt.Errorf("%s: %s: %v", setting.Source, setting.Key, err)
+		}
+		configArgs = append(configArgs, flags...)
+	}
+	return append(configArgs, args...), nil
+}
+
+// formatConfigSeconds formats a duration as TOML seconds: 600, 0.5
+func formatConfigSeconds(d time.Duration) string {
+	if d%time.Second == 0 {
+		return strconv.FormatInt(int64(d/time.Second), 10)
+	}
+	return strconv.FormatFloat(d.Seconds(), 'f', -1, 64)
+}
+
+// formatConfigSize formats a number of bytes the way --max-line-length takes it: "4M"
+func formatConfigSize(n int) string {
+	switch {
+	case n%(1024*1024) == 0:
+		return strconv.Quote(strconv.Itoa(n/(1024*1024)) + "M")
+	case n%1024 == 0:
+		return strconv.Quote(strconv.Itoa(n/1024) + "K")
+	}
+	return strconv.Itoa(n)
+}
+
+// runConfigCommand runs "3pio config" and exits with its exit code
+func runConfigCommand(args []string) error {
+	cfg, err := loadConfig()
+	exitCode := 1
+	if err == nil {
+		exitCode, err = runConfigShow(cfg, args, os.Stdout)
+	}
+	if err != nil {
+		fmt.Fprintf(os.Stderr, "Error: %v\n", err)
+	}
+	os.Exit(exitCode)
+	return nil // Never reached, but needed for signature
+}
+
+// runConfigShow prints the effective value of every option and where it comes from,
+// as TOML that can be copied into .3pio.toml: "3pio config --show"
+func runConfigShow(cfg *config.Config, args []string, w io.Writer) (int, error) {
+	if len(args) != 1 || args[0] != "--show" {
+		return 1, fmt.Errorf("usage: 3pio config --show")
+	}
+	configArgs, err := withConfig(cfg, nil)
+	if err != nil {
+		return 1, err
+	}
+	opts, _, err := parseRunOptions(configArgs)
+	if err != nil {
+		return 1, err
+	}
+
+	for _, warning := range cfg.Warnings {
+		fmt.Fprintf(w, "# Warning: %s\n", warning)
+	}
+	if len(cfg.Files) == 0 {
+		fmt.Fprintf(w, "# No config file; 3pio reads %s and the user config file\n", config.FileName)
+	}
+
+	table := tabwriter.NewWriter(w, 0, 0, 2, ' ', 0)
+	for _, opt := range configOptions {
+		source := "default"
+		if setting, ok := cfg.Settings[opt.key()]; ok {
+			source = setting.Source
+		}
+		fmt.Fprintf(table, "%s = %s\t# %s\n", opt.key(), opt.value(opts), source)
+	}
+	return 0, table.Flush()
+}
@@ -0,0 +1,85 @@
+package main
+
+import (
+	"bytes"
+	"fmt"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/internal/config"
+)
+
+// testConfig returns a config with the given key, value pairs, each on its own line
+// of .3pio.toml
+func testConfig(values ...string) *config.Config {
+	cfg := &config.Config{Settings: make(map[string]config.Setting)}
+	for i := 0; i+1 < len(values); i += 2 {
+		source := fmt.Sprintf(".3pio.toml:%d", i/2+1)
+		cfg.Settings[values[i]] = config.Setting{Key: values[i], Value: values[i+1], Source: source}
+	}
+	return cfg
+}
+
+func TestWithConfig(t *testing.T) {
+	cfg := testConfig("retries", "2", "progress", "false", "heartbeat", "0", "quiet", "true", "timeout", "600", "redact", "false")
+	testCases := []struct {
+		args     []string
+		expected string
+	}{
+		{[]string{"cargo", "test"}, "--timeout 600 --retries 2 --no-progress --no-heartbeat --quiet cargo test"},
+		// The command line wins, however it spells the flag
+		{[]string{"--retries=0", "-v", "--progress", "cargo", "test"}, "--timeout 600 --no-heartbeat --retries=0 -v --progress cargo test"},
+		{[]string{"--heartbeat", "30", "--timeout", "60", "cargo", "test", "--retries"}, "--retries 2 --no-progress --quiet --heartbeat 30 --timeout 60 cargo test --retries"},
+	}
+	for _, tc := range testCases {
+		args, err := withConfig(cfg, tc.args)
+		if err != nil {
+			t.Fatalf("Unexpected error for %v: %v", tc.args, err)
+		}
+		if strings.Join(args, " ") != tc.expected {
+			t.Errorf("Expected %q for %v, got %q", tc.expected, tc.args, strings.Join(args, " "))
+		}
+		if _, _, err := parseRunOptions(args); err != nil {
+			t.Errorf("Unexpected error parsing %v: %v", args, err)
+		}
+	}
+}
+
+func TestWithConfig_InvalidValue(t *testing.T) {
+	_, err := withConfig(testConfig("timeout", "0.5"), []string{"cargo", "test"})
+	expected := `.3pio.toml:1: timeout: --timeout requires a positive number of seconds, got "0.5"`
+	if err == nil || err.Error() != expected {
+		t.Errorf("Expected error %q, got %v", expected, err)
+	}
+}
+
+func TestRunConfigShow(t *testing.T) {
+	t.Setenv("GITHUB_ACTIONS", "")
+	cfg := testConfig("retries", "2", "slow-threshold", "0.5", "max-line-length", "16M")
+	cfg.Files = []string{".3pio.toml"}
+	cfg.Warnings = []string{`.3pio.toml:4: unknown key "parallel", ignored`}
+
+	var out bytes.Buffer
+	exitCode, err := runConfigShow(cfg, []string{"--show"}, &out)
+	if err != nil || exitCode != 0 {
+		t.Fatalf("Expected exit code 0, got %d: %v", exitCode, err)
+	}
+	for _, line := range []string{
+		`# Warning: .3pio.toml:4: unknown key "parallel", ignored`,
+		`retries = 2              # .3pio.toml:1`,
+		`slow-threshold = 0.5     # .3pio.toml:2`,
+		`max-line-length = "16M"  # .3pio.toml:3`,
+		`snippet-lines = 15       # default`,
+		`heartbeat = 60           # default`,
+		`gha-annotations = false  # default`,
+		`color = "auto"           # default`,
+	} {
+		if !strings.Contains(out.String(), line+"\n") {
+			t.Errorf("Expected line %q in:\n%s", line, out.String())
+		}
+	}
+
+	if exitCode, err := runConfigShow(cfg, nil, &out); err == nil || exitCode != 1 {
+		t.Errorf("Expected a usage error without --show, got %d: %v", exitCode, err)
+	}
+}
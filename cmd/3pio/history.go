@@ -0,0 +1,703 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"slices"
+	"sort"
+	"strconv"
+	"strings"
+	"text/tabwriter"
+	"time"
+
+	"github.com/zk/3pio/internal/ipc"
+	"github.com/zk/3pio/internal/report"
+	"github.com/zk/3pio/internal/runs"
+)
+
+const (
+	// defaultListLimit is how many runs 3pio list prints without --limit
+	defaultListLimit = 20
+
+	// maxShowFailures is how many failures 3pio show lists, as in test-run.md
+	maxShowFailures = 50
+
+	// failuresErrorExitCode is what 3pio failures exits with when the run can't be
+	// read, since 1 means the run had failures
+	failuresErrorExitCode = 2
+
+	// mergeErrorExitCode is what 3pio merge exits with when the runs can't be merged,
+	// since 1 means the merged run had failures
+	mergeErrorExitCode = 2
+
+	// importErrorExitCode is what 3pio import exits with when the files can't be
+	// imported, since 1 means the imported run had failures
+	importErrorExitCode = 2
+)
+
+// historyCommand browses the runs in runsDir, printing to w, and returns the exit code
+type historyCommand func(runsDir string, args []string, w io.Writer) (int, error)
+
+// historyCommands are the subcommands that work on past runs instead of running tests
+var historyCommands = map[string]historyCommand{
+	"list":     runList,
+	"show":     runShow,
+	"failures": runFailures,
+	"clean":    runClean,
+	"diff":     runDiff,
+	"merge":    runMerge,
+	"import":   runImport,
+}
+
+// runHistoryCommand runs a history subcommand on .3pio/runs and exits with its exit code
+func runHistoryCommand(command historyCommand, args []string) error {
+	exitCode, err := command(filepath.Join(".3pio", "runs"), args, os.Stdout)
+	if err != nil {
+		fmt.Fprintf(os.Stderr, "Error: %v\n", err)
+	}
+	os.Exit(exitCode)
+	return nil // Never reached, but needed for signature
+}
+
+// runList prints a table of the most recent runs: "3pio list [--limit <n>]"
+func runList(runsDir string, args []string, w io.Writer) (int, error) {
+	limit := defaultListLimit
+	for i := 0; i < len(args); i++ {
+		value, ok := strings.CutPrefix(args[i], "--limit=")
+		if !ok {
+			if args[i] != "--limit" || i+1 >= len(args) {
+				return 1, fmt.Errorf("usage: 3pio list [--limit <n>]")
+			}
+			i++
+			value = args[i]
+		}
+		n, err := strconv.Atoi(value)
+		if err != nil || n <= 0 {
+			return 1, fmt.Errorf("--limit requires a positive number of runs, got %q", value)
+		}
+		limit = n
+	}
+
+	recent, err := runs.Recent(runsDir, limit)
+	if err != nil {
+		return 1, err
+	}
+	if len(recent) == 0 {
+		fmt.Fprintf(w, "No runs in %s\n", runsDir)
+		return 0, nil
+	}
+
+	now := time.Now()
+	table := tabwriter.NewWriter(w, 0, 0, 2, ' ', 0)
+	fmt.Fprintln(table, "RUN\tAGE\tSTATUS\tRESULTS\tDURATION\tCOMMAND")
+	for _, run := range recent {
+		fmt.Fprintf(table, "%s\t%s\t%s\t%s\t%s\t%s\n", run.RunID, formatAge(run.Entry, now),
+			orDash(run.Status), formatCounts(run.Entry), formatRunDuration(run.Entry), orDash(run.Command))
+	}
+	return 0, table.Flush()
+}
+
+// runShow prints a run's summary and failure digest: "3pio show [<run-id>|latest]"
+func runShow(runsDir string, args []string, w io.Writer) (int, error) {
+	run, err := loadRun(runsDir, "show", args)
+	if err != nil {
+		return 1, err
+	}
+
+	fmt.Fprintf(w, "Run:       %s\n", run.RunID)
+	fmt.Fprintf(w, "Command:   %s\n", orDash(run.Command))
+	if run.Cargo != nil {
+		fmt.Fprintf(w, "Build:     %s\n", run.Cargo)
+	}
+	status := orDash(run.Status)
+	if run.Status != "" {
+		status += fmt.Sprintf(" (exit code %d)", run.ExitCode)
+	}
+	fmt.Fprintf(w, "Status:    %s\n", status)
+	if !run.StartTime.IsZero() {
+		fmt.Fprintf(w, "Started:   %s (%s)\n", run.StartTime.Local().Format("2006-01-02 15:04:05"), formatAge(run.Entry, time.Now()))
+	}
+	fmt.Fprintf(w, "Duration:  %s\n", formatRunDuration(run.Entry))
+	fmt.Fprintf(w, "Results:   %s\n", formatCounts(run.Entry))
+	fmt.Fprintf(w, "Report:    %s\n", filepath.Join(run.Dir, "test-run.md"))
+
+	if run.Summary == nil {
+		fmt.Fprintf(w, "\nThis run has no %s, it was made by an older 3pio version. See its test-run.md for the results.\n", report.SummaryFileName)
+		return 0, nil
+	}
+
+	failures := summaryFailures(run.Summary)
+	if len(failures) == 0 {
+		return 0, nil
+	}
+	fmt.Fprintf(w, "\nFailures (%d):\n", len(failures))
+	for i, failure := range failures {
+		if i >= maxShowFailures {
+			fmt.Fprintf(w, "- …and %d more\n", len(failures)-maxShowFailures)
+			break
+		}
+		fmt.Fprintf(w, "- %s", failure.name())
+		if message := firstLine(failure.message); message != "" {
+			fmt.Fprintf(w, ": %s", message)
+		}
+		if failure.report != "" {
+			fmt.Fprintf(w, " (%s)", filepath.Join(run.Dir, filepath.FromSlash(failure.report)))
+		}
+		fmt.Fprintln(w)
+	}
+	return 0, nil
+}
+
+// runFailures prints every failed test of a run with its full captured output:
+// "3pio failures [<run-id>|latest]". It exits with 1 if the run had failures.
+func runFailures(runsDir string, args []string, w io.Writer) (int, error) {
+	run, err := loadRun(runsDir, "failures", args)
+	if err != nil {
+		return failuresErrorExitCode, err
+	}
+
+	var failures []failure
+	if run.Summary != nil {
+		failures = summaryFailures(run.Summary)
+	} else {
+		// Older runs may still have failed-tests.json
+		failed, err := report.ReadFailedTests(run.Dir)
+		if err != nil {
+			return failuresErrorExitCode, fmt.Errorf("run %s has neither %s nor %s", run.RunID, report.SummaryFileName, ipc.FailedTestsFileName)
+		}
+		for _, test := range failed {
+			failures = append(failures, failure{path: test.ParentNames, test: test.Name})
+		}
+	}
+	if len(failures) == 0 {
+		fmt.Fprintf(w, "No failed tests in %s\n", run.RunID)
+		return 0, nil
+	}
+
+	// Test logs are only listed in index.json, which older runs don't have
+	testLogs := make(map[string]map[string]string)
+	if index, err := report.ReadPathIndex(run.Dir); err == nil {
+		for _, group := range index.Groups {
+			testLogs[strings.Join(group.Path, "\x00")] = group.TestLogs
+		}
+	}
+
+	for i, failure := range failures {
+		if i > 0 {
+			fmt.Fprintln(w)
+		}
+		fmt.Fprintf(w, "## %s\n", failure.name())
+		if failure.message != "" {
+			fmt.Fprintf(w, "\n%s\n", strings.TrimRight(failure.message, "\n"))
+		}
+		logPath := testLogs[strings.Join(failure.path, "\x00")][failure.test]
+		if logPath == "" {
+			continue
+		}
+		output, err := os.ReadFile(filepath.Join(run.Dir, filepath.FromSlash(logPath)))
+		if err != nil || len(output) == 0 {
+			continue
+		}
+		fence := codeFence(string(output))
+		fmt.Fprintf(w, "\n%s\n%s\n%s\n", fence, strings.TrimRight(string(output), "\n"), fence)
+	}
+	return 1, nil
+}
+
+// runClean removes old runs: "3pio clean [--keep <n>] [--keep-days <d>] [--keep-failed] [--dry-run]"
+func runClean(runsDir string, args []string, w io.Writer) (int, error) {
+	policy := runs.DefaultPolicy
+	dryRun := false
+	for i := 0; i < len(args); i++ {
+		if args[i] == "--keep-failed" {
+			policy.KeepFailed = true
+			continue
+		}
+		if args[i] == "--dry-run" {
+			dryRun = true
+			continue
+		}
+		flag, value, hasValue := strings.Cut(args[i], "=")
+		if flag != "--keep" && flag != "--keep-days" {
+			return 1, fmt.Errorf("usage: 3pio clean [--keep <n>] [--keep-days <d>] [--keep-failed] [--dry-run]")
+		}
+		if !hasValue {
+			if i+1 >= len(args) {
+				return 1, fmt.Errorf("%s requires a value", flag)
+			}
+			i++
+			value = args[i]
+		}
+		n, err := strconv.Atoi(value)
+		if err != nil || n < 0 {
+			return 1, fmt.Errorf("%s requires a number of at least 0, got %q", flag, value)
+		}
+		if flag == "--keep" {
+			policy.KeepLast = n
+		} else {
+			policy.KeepWithin = time.Duration(n) * 24 * time.Hour
+		}
+	}
+
+	removed, err := runs.Clean(runsDir, policy, dryRun, time.Now())
+	if err != nil {
+		return 1, err
+	}
+	if len(removed) == 0 {
+		fmt.Fprintln(w, "Nothing to clean")
+		return 0, nil
+	}
+
+	var total int64
+	for _, run := range removed {
+		total += run.Size
+	}
+	if dryRun {
+		fmt.Fprintf(w, "Would remove %d run(s) (%s):\n", len(removed), formatSize(total))
+		now := time.Now()
+		table := tabwriter.NewWriter(w, 0, 0, 2, ' ', 0)
+		for _, run := range removed {
+			fmt.Fprintf(table, "  %s\t%s\t%s\t%s\n", run.RunID, formatAge(run.Entry, now), orDash(run.Status), formatSize(run.Size))
+		}
+		return 0, table.Flush()
+	}
+	fmt.Fprintf(w, "Removed %d run(s) (%s)\n", len(removed), formatSize(total))
+	return 0, nil
+}
+
+// runDiff compares the tests of two runs: "3pio diff [<run-a> [<run-b>]] [--json]
+// [--force]". Without runs it compares the two most recent runs with a summary.json,
+// and with one it compares that run with the latest. Runs of cargo builds with another
+// profile, features or target are only compared with --force.
+func runDiff(runsDir string, args []string, w io.Writer) (int, error) {
+	asJSON, force := false, false
+	var refs []string
+	for _, arg := range args {
+		switch arg {
+		case "--json":
+			asJSON = true
+		case "--force":
+			force = true
+		default:
+			refs = append(refs, arg)
+		}
+	}
+	if len(refs) > 2 {
+		return 1, fmt.Errorf("usage: 3pio diff [<run-a> [<run-b>]] [--json] [--force]")
+	}
+	if len(refs) < 2 {
+		recent, err := recentSummaries(runsDir, 2)
+		if err != nil {
+			return 1, err
+		}
+		switch {
+		case len(refs) == 1 && len(recent) > 0:
+			refs = append(refs, recent[0])
+		case len(refs) == 0 && len(recent) == 2:
+			refs = []string{recent[1], recent[0]}
+		default:
+			return 1, fmt.Errorf("need two runs with a %s to compare in %s", report.SummaryFileName, runsDir)
+		}
+	}
+
+	var summaries [2]*report.RunSummary
+	for i, ref := range refs {
+		run, err := runs.Load(runsDir, ref)
+		if err != nil {
+			return 1, err
+		}
+		if run.Summary == nil {
+			return 1, fmt.Errorf("run %s has no %s, it was made by an older 3pio version", run.RunID, report.SummaryFileName)
+		}
+		summaries[i] = run.Summary
+	}
+	diff := runs.Diff(summaries[0], summaries[1])
+	if diff.BuildChanged != "" && !force {
+		return 1, fmt.Errorf("the runs were built differently, so their results may differ for that alone: %s. Use --force to compare them anyway", diff.BuildChanged)
+	}
+
+	if asJSON {
+		data, err := json.MarshalIndent(diff, "", "  ")
+		if err != nil {
+			return 1, err
+		}
+		_, err = fmt.Fprintln(w, string(data))
+		return 0, err
+	}
+
+	fmt.Fprintf(w, "Comparing %s with %s\n\n", diff.Before, diff.After)
+	if diff.BuildChanged != "" {
+		fmt.Fprintf(w, "Warning: the runs were built differently: %s\n\n", diff.BuildChanged)
+	}
+	if c := diff.Coverage; c != nil {
+		fmt.Fprintf(w, "Coverage: lines %s, functions %s\n\n", formatCoverageChange(c.BeforeLines, c.AfterLines), formatCoverageChange(c.BeforeFunctions, c.AfterFunctions))
+	}
+	if diff.Empty() {
+		fmt.Fprintln(w, "No changes")
+		return 0, nil
+	}
+	table := tabwriter.NewWriter(w, 0, 0, 2, ' ', 0)
+	fmt.Fprintln(table, "CHANGE\tTEST\tBEFORE\tAFTER")
+	sections := []struct {
+		name    string
+		changes []runs.TestChange
+	}{
+		{"newly failing", diff.NewlyFailing},
+		{"newly passing", diff.NewlyPassing},
+		{"still failing", diff.StillFailing},
+		{"added", diff.Added},
+		{"removed", diff.Removed},
+	}
+	var counts []string
+	for _, section := range sections {
+		for _, change := range section.changes {
+			name := change.FullName()
+			if change.RenamedFrom != nil {
+				name += fmt.Sprintf(" (was in %s)", strings.Join(change.RenamedFrom, " → "))
+			}
+			fmt.Fprintf(table, "%s\t%s\t%s\t%s\n", section.name, name, orDash(change.BeforeStatus), orDash(change.AfterStatus))
+		}
+		if len(section.changes) > 0 {
+			counts = append(counts, fmt.Sprintf("%d %s", len(section.changes), section.name))
+		}
+	}
+	for _, change := range diff.Slower {
+		fmt.Fprintf(table, "slower\t%s\t%s\t%s (%.1fx)\n", change.FullName(),
+			formatMs(change.BeforeMs), formatMs(change.AfterMs), change.AfterMs/change.BeforeMs)
+	}
+	if len(diff.Slower) > 0 {
+		counts = append(counts, fmt.Sprintf("%d slower", len(diff.Slower)))
+	}
+	if err := table.Flush(); err != nil {
+		return 1, err
+	}
+	fmt.Fprintf(w, "\n%s\n", strings.Join(counts, ", "))
+	return 0, nil
+}
+
+// runMerge combines the runs of a sharded test suite into one: "3pio merge <dir>...
+// --out <dir>". Each run is a run directory, or the ID of a run in .3pio/runs. It exits
+// with the merged run's exit code.
+func runMerge(runsDir string, args []string, w io.Writer) (int, error) {
+	usage := fmt.Errorf("usage: 3pio merge <run-dir>... --out <dir>")
+	var outDir string
+	var shards []runs.Shard
+	for i := 0; i < len(args); i++ {
+		if value, ok := strings.CutPrefix(args[i], "--out="); ok {
+			outDir = value
+			continue
+		}
+		if args[i] == "--out" {
+			if i+1 >= len(args) {
+				return mergeErrorExitCode, usage
+			}
+			i++
+			outDir = args[i]
+			continue
+		}
+		if strings.HasPrefix(args[i], "-") {
+			return mergeErrorExitCode, usage
+		}
+		dir := args[i]
+		if _, err := os.Stat(filepath.Join(dir, report.SummaryFileName)); err != nil && !strings.ContainsAny(dir, `/\`) {
+			dir = filepath.Join(runsDir, dir)
+		}
+		shard, err := runs.LoadShard(dir)
+		if err != nil {
+			return mergeErrorExitCode, err
+		}
+		shards = append(shards, shard)
+	}
+	if outDir == "" || len(shards) == 0 {
+		return mergeErrorExitCode, usage
+	}
+
+	merged, err := runs.Merge(filepath.Base(filepath.Clean(outDir)), shards)
+	if err != nil {
+		return mergeErrorExitCode, err
+	}
+	if err := runs.WriteMerged(outDir, shards, merged); err != nil {
+		return mergeErrorExitCode, err
+	}
+
+	fmt.Fprintf(w, "Merged %d runs into %s\n", len(shards), outDir)
+	fmt.Fprintf(w, "Results:   %s\n", formatCounts(runs.EntryFromSummary(merged)))
+	if missing := runs.MissingShardsText(merged); missing != "" {
+		fmt.Fprintf(w, "Missing:   %s, the merged run is incomplete\n", missing)
+	}
+	fmt.Fprintf(w, "Duration:  %s (longest run), %s across runs\n",
+		formatDuration(time.Duration(merged.Merge.WallMs*float64(time.Millisecond))),
+		formatDuration(time.Duration(merged.Merge.ShardMs*float64(time.Millisecond))))
+	fmt.Fprintf(w, "Report:    %s\n", filepath.Join(outDir, "test-run.md"))
+	if len(merged.Merge.Conflicts) > 0 {
+		fmt.Fprintf(w, "\nConflicting results (%d):\n", len(merged.Merge.Conflicts))
+		for _, conflict := range merged.Merge.Conflicts {
+			runIDs := make([]string, 0, len(conflict.Results))
+			for runID := range conflict.Results {
+				runIDs = append(runIDs, runID)
+			}
+			sort.Strings(runIDs)
+			var results []string
+			for _, runID := range runIDs {
+				results = append(results, fmt.Sprintf("%s on %s", conflict.Results[runID], runID))
+			}
+			name := failure{path: conflict.Path, test: conflict.Name}.name()
+			fmt.Fprintf(w, "- %s: %s\n", name, strings.Join(results, ", "))
+		}
+	}
+	return merged.ExitCode, nil
+}
+
+// runImport records the JUnit XML files of a run made without 3pio as a run in
+// runsDir: "3pio import --format junit <glob>...". The files of several globs, or of
+// a glob matching several, are merged as by 3pio merge. It exits with the imported
+// run's exit code.
+func runImport(runsDir string, args []string, w io.Writer) (int, error) {
+	usage := fmt.Errorf("usage: 3pio import --format junit <glob>...")
+	format := ""
+	var files []string
+	for i := 0; i < len(args); i++ {
+		if value, ok := strings.CutPrefix(args[i], "--format="); ok {
+			format = value
+			continue
+		}
+		if args[i] == "--format" {
+			if i+1 >= len(args) {
+				return importErrorExitCode, usage
+			}
+			i++
+			format = args[i]
+			continue
+		}
+		if strings.HasPrefix(args[i], "-") {
+			return importErrorExitCode, usage
+		}
+		matches, err := filepath.Glob(args[i])
+		if err != nil {
+			return importErrorExitCode, fmt.Errorf("bad pattern %q: %w", args[i], err)
+		}
+		if len(matches) == 0 {
+			return importErrorExitCode, fmt.Errorf("no files match %q", args[i])
+		}
+		for _, match := range matches {
+			if !slices.Contains(files, match) {
+				files = append(files, match)
+			}
+		}
+	}
+	if format == "" || len(files) == 0 {
+		return importErrorExitCode, usage
+	}
+	if format != "junit" {
+		return importErrorExitCode, fmt.Errorf("can't import --format %s, only junit", format)
+	}
+
+	if err := os.MkdirAll(runsDir, 0755); err != nil {
+		return importErrorExitCode, err
+	}
+	base := time.Now().Format("20060102T150405") + "-junit-import"
+	runID := base
+	for n := 2; ; n++ {
+		if _, err := os.Stat(filepath.Join(runsDir, runID)); os.IsNotExist(err) {
+			break
+		}
+		runID = fmt.Sprintf("%s-%d", base, n)
+	}
+	runDir := filepath.Join(runsDir, runID)
+	command := "3pio import --format junit " + strings.Join(files, " ")
+	summary, err := runs.ImportJUnit(runDir, files, command)
+	if err != nil {
+		_ = os.RemoveAll(runDir)
+		return importErrorExitCode, err
+	}
+	if err := runs.Record(runsDir, runs.EntryFromSummary(summary)); err != nil {
+		return importErrorExitCode, err
+	}
+
+	noun := "files"
+	if len(files) == 1 {
+		noun = "file"
+	}
+	fmt.Fprintf(w, "Imported %d JUnit XML %s as run %s\n", len(files), noun, runID)
+	fmt.Fprintf(w, "Results:   %s\n", formatCounts(runs.EntryFromSummary(summary)))
+	fmt.Fprintf(w, "Report:    %s\n", filepath.Join(runDir, "test-run.md"))
+	return summary.ExitCode, nil
+}
+
+// recentSummaries returns the IDs of up to n of the most recent runs that have a
+// summary.json, newest first
+func recentSummaries(runsDir string, n int) ([]string, error) {
+	runIDs, err := runs.List(runsDir)
+	if err != nil {
+		return nil, err
+	}
+	var recent []string
+	for i := len(runIDs) - 1; i >= 0 && len(recent) < n; i-- {
+		if _, err := os.Stat(filepath.Join(runsDir, runIDs[i], report.SummaryFileName)); err == nil {
+			recent = append(recent, runIDs[i])
+		}
+	}
+	return recent, nil
+}
+
+// formatMs formats a test duration in milliseconds, e.g. "850ms" or "2.40s"
+func formatMs(ms float64) string {
+	if ms < 1000 {
+		return fmt.Sprintf("%.0fms", ms)
+	}
+	return fmt.Sprintf("%.2fs", ms/1000)
+}
+
+// loadRun loads the run named by a subcommand's only argument, or the latest run
+func loadRun(runsDir, command string, args []string) (*runs.Run, error) {
+	if len(args) > 1 {
+		return nil, fmt.Errorf("usage: 3pio %s [<run-id>|latest]", command)
+	}
+	ref := runs.LatestRef
+	if len(args) == 1 {
+		ref = args[0]
+	}
+	return runs.Load(runsDir, ref)
+}
+
+// failure is a failed test, or a group that failed without a failing test, of a run
+type failure struct {
+	path    []string // Names of the groups from the root group down
+	test    string   // Test name; empty for a group failure
+	message string   // Failure message
+	report  string   // Report of the test's group, relative to the run directory
+}
+
+// name returns the failure's full name, e.g. "my_crate → tests → test_add"
+func (f failure) name() string {
+	names := append([]string(nil), f.path...)
+	if f.test != "" {
+		names = append(names, f.test)
+	}
+	return strings.Join(names, " → ")
+}
+
+// summaryFailures lists the failed tests and failed groups of a summary, depth-first
+func summaryFailures(summary *report.RunSummary) []failure {
+	var failures []failure
+	for _, group := range summary.Groups {
+		failedTests := 0
+		for _, tc := range group.TestCases {
+			if tc.Status != string(report.TestStatusFail) && tc.Status != string(report.TestStatusError) {
+				continue
+			}
+			failedTests++
+			failures = append(failures, failure{path: group.Path, test: tc.Name, message: tc.FailureMessage, report: tc.Report})
+		}
+		if failedTests == 0 && group.Error != "" {
+			failures = append(failures, failure{path: group.Path, message: group.Error, report: group.Report})
+		}
+	}
+	return failures
+}
+
+// formatCounts returns a run's test counts, e.g. "12 passed, 1 failed", or "-" when unknown
+func formatCounts(entry runs.Entry) string {
+	totals := entry.Totals
+	if totals.Total == 0 && entry.Status == "" {
+		return "-"
+	}
+	parts := []string{fmt.Sprintf("%d passed", totals.Passed)}
+	if totals.Failed > 0 {
+		parts = append(parts, fmt.Sprintf("%d failed", totals.Failed))
+	}
+	if totals.Skipped > 0 {
+		parts = append(parts, fmt.Sprintf("%d skipped", totals.Skipped))
+	}
+	return strings.Join(parts, ", ")
+}
+
+// formatRunDuration returns how long a run took, or "-" when unknown
+func formatRunDuration(entry runs.Entry) string {
+	if entry.StartTime.IsZero() || entry.EndTime.IsZero() {
+		return "-"
+	}
+	return formatDuration(entry.EndTime.Sub(entry.StartTime))
+}
+
+// formatAge returns how long ago a run finished (or started), e.g. "5m ago"
+func formatAge(entry runs.Entry, now time.Time) string {
+	at := entry.EndTime
+	if at.IsZero() {
+		at = entry.StartTime
+	}
+	if at.IsZero() {
+		return "-"
+	}
+	return formatDuration(now.Sub(at)) + " ago"
+}
+
+// formatDuration formats a duration for the history tables, e.g. "3.2s", "4m", "2h" or "3d"
+func formatDuration(d time.Duration) string {
+	switch {
+	case d < 0:
+		return "0s"
+	case d < time.Minute:
+		return fmt.Sprintf("%.1fs", d.Seconds())
+	case d < time.Hour:
+		return fmt.Sprintf("%dm", int(d.Minutes()))
+	case d < 24*time.Hour:
+		return fmt.Sprintf("%dh", int(d.Hours()))
+	default:
+		return fmt.Sprintf("%dd", int(d.Hours()/24))
+	}
+}
+
+// formatSize formats a number of bytes, e.g. "512 B", "3.4 MB" or "1.2 GB"
+func formatSize(size int64) string {
+	const unit = 1024
+	if size < unit {
+		return fmt.Sprintf("%d B", size)
+	}
+	value, prefix := float64(size)/unit, 0
+	for value >= unit && prefix < len("KMGT")-1 {
+		value /= unit
+		prefix++
+	}
+	return fmt.Sprintf("%.1f %cB", value, "KMGT"[prefix])
+}
+
+// formatCoverageChange formats a change of coverage, e.g. "71.2% → 74.0% (+2.8)"
+func formatCoverageChange(before, after float64) string {
+	return fmt.Sprintf("%.1f%% → %.1f%% (%+.1f)", before, after, after-before)
+}
+
+// orDash returns s, or "-" when it is empty
+func orDash(s string) string {
+	if s == "" {
+		return "-"
+	}
+	return s
+}
+
+// firstLine returns the first non-empty line of s
+func firstLine(s string) string {
+	for _, line := range strings.Split(s, "\n") {
+		if line = strings.TrimSpace(line); line != "" {
+			return line
+		}
+	}
+	return ""
+}
+
+// codeFence returns a markdown code fence longer than any backtick run in content
+func codeFence(content string) string {
+	longest, run := 0, 0
+	for _, r := range content {
+		if r == '`' {
+			run++
+			longest = max(longest, run)
+		} else {
+			run = 0
+		}
+	}
+	return strings.Repeat("`", max(3, longest+1))
+}
@@ -0,0 +1,448 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/zk/3pio/internal/coverage"
+	"github.com/zk/3pio/internal/report"
+	"github.com/zk/3pio/internal/runinfo"
+	"github.com/zk/3pio/internal/runs"
+)
+
+// writeHistoryFixture creates a runs directory with a run of an older 3pio version,
+// which left only test-run.md, and a current run with one failed test
+func writeHistoryFixture(t *testing.T) string {
+	t.Helper()
+	runsDir := t.TempDir()
+	write := func(path, content string) {
+		t.Helper()
+		path = filepath.Join(runsDir, filepath.FromSlash(path))
+		if err := os.MkdirAll(filepath.Dir(path), 0755); err != nil {
+			t.Fatalf("Failed to create directory: %v", err)
+		}
+		if err := os.WriteFile(path, []byte(content), 0644); err != nil {
+			t.Fatalf("Failed to write %s: %v", path, err)
+		}
+	}
+
+	write("20261013T090000-sleepy-kirk/test-run.md", "---\nstatus: COMPLETED\n---\n")
+
+	runID := "20261014T100000-cranky-spock"
+	start := time.Now().Add(-5*time.Minute - 3*time.Second).UTC()
+	summary := report.RunSummary{
+		SchemaVersion: report.SummarySchemaVersion,
+		RunID:         runID,
+		Command:       "cargo test",
+		StartTime:     start,
+		EndTime:       start.Add(3 * time.Second),
+		ExitCode:      101,
+		Status:        "COMPLETED",
+		Totals:        report.SummaryCounts{Total: 2, Passed: 1, Failed: 1},
+		Groups: []report.SummaryGroup{{
+			Name:   "tests",
+			Path:   []string{"my-crate", "tests"},
+			Status: "FAIL",
+			Report: "./reports/my_crate/tests/index.md",
+			TestCases: []report.SummaryTestCase{
+				{Name: "test_add", Status: "PASS"},
+				{Name: "test_sub", Status: "FAIL", FailureMessage: "assertion `left == right` failed\n  left: 1\n right: 2",
+					Report: "./reports/my_crate/tests/index.md"},
+			},
+		}},
+	}
+	data, _ := json.Marshal(summary)
+	write(runID+"/"+report.SummaryFileName, string(data))
+	index, _ := json.Marshal(report.PathIndex{SchemaVersion: 1, Groups: []report.PathIndexGroup{{
+		Path:     []string{"my-crate", "tests"},
+		Dir:      "reports/my_crate/tests",
+		Report:   "reports/my_crate/tests/index.md",
+		TestLogs: map[string]string{"test_sub": "reports/my_crate/tests/logs/test_sub.log"},
+	}}})
+	write(runID+"/"+report.PathIndexFileName, string(index))
+	write(runID+"/reports/my_crate/tests/logs/test_sub.log", "thread 'tests::test_sub' panicked at src/lib.rs:10:5:\n```\nfenced\n```\n")
+	if err := runs.Record(runsDir, runs.EntryFromSummary(&summary)); err != nil {
+		t.Fatalf("Record failed: %v", err)
+	}
+	return runsDir
+}
+
+func TestRunList(t *testing.T) {
+	runsDir := writeHistoryFixture(t)
+
+	var out bytes.Buffer
+	if exitCode, err := runList(runsDir, nil, &out); exitCode != 0 || err != nil {
+		t.Fatalf("Expected list to succeed, got %d (%v)", exitCode, err)
+	}
+	lines := strings.Split(strings.TrimSpace(out.String()), "\n")
+	if len(lines) != 3 || !strings.HasPrefix(lines[0], "RUN") {
+		t.Fatalf("Expected a header and two runs, got:\n%s", out.String())
+	}
+
+	// Newest first, with what the index knows about it
+	for _, want := range []string{"20261014T100000-cranky-spock", "5m ago", "COMPLETED", "1 passed, 1 failed", "3.0s", "cargo test"} {
+		if !strings.Contains(lines[1], want) {
+			t.Errorf("Expected %q in the latest run's row %q", want, lines[1])
+		}
+	}
+	// The older run only has its ID, which still gives its age
+	if !strings.HasPrefix(lines[2], "20261013T090000-sleepy-kirk") || !strings.Contains(lines[2], " ago") {
+		t.Errorf("Expected the older run with its age, got %q", lines[2])
+	}
+
+	out.Reset()
+	if _, err := runList(runsDir, []string{"--limit", "1"}, &out); err != nil || strings.Count(out.String(), "\n") != 2 {
+		t.Errorf("Expected one run with --limit 1, got:\n%s (%v)", out.String(), err)
+	}
+	if exitCode, err := runList(runsDir, []string{"--limit", "0"}, &out); exitCode != 1 || err == nil {
+		t.Errorf("Expected --limit 0 to be rejected, got %d (%v)", exitCode, err)
+	}
+}
+
+func TestRunShow(t *testing.T) {
+	runsDir := writeHistoryFixture(t)
+
+	var out bytes.Buffer
+	if exitCode, err := runShow(runsDir, []string{"latest"}, &out); exitCode != 0 || err != nil {
+		t.Fatalf("Expected show to succeed, got %d (%v)", exitCode, err)
+	}
+	for _, want := range []string{
+		"Run:       20261014T100000-cranky-spock",
+		"Status:    COMPLETED (exit code 101)",
+		"Results:   1 passed, 1 failed",
+		"Failures (1):",
+		"- my-crate → tests → test_sub: assertion `left == right` failed (",
+	} {
+		if !strings.Contains(out.String(), want) {
+			t.Errorf("Expected %q in:\n%s", want, out.String())
+		}
+	}
+
+	// A run without summary.json still shows what is known
+	out.Reset()
+	if exitCode, err := runShow(runsDir, []string{"20261013T090000-sleepy-kirk"}, &out); exitCode != 0 || err != nil {
+		t.Fatalf("Expected show to succeed for an older run, got %d (%v)", exitCode, err)
+	}
+	if !strings.Contains(out.String(), "older 3pio version") {
+		t.Errorf("Expected a note about the missing summary, got:\n%s", out.String())
+	}
+
+	if exitCode, err := runShow(runsDir, []string{"20261012T000000-missing"}, &out); exitCode != 1 || err == nil {
+		t.Errorf("Expected an unknown run to fail, got %d (%v)", exitCode, err)
+	}
+}
+
+func TestRunFailures(t *testing.T) {
+	runsDir := writeHistoryFixture(t)
+
+	var out bytes.Buffer
+	exitCode, err := runFailures(runsDir, []string{"20261014T100000-cranky-spock"}, &out)
+	if exitCode != 1 || err != nil {
+		t.Fatalf("Expected exit code 1 for a run with failures, got %d (%v)", exitCode, err)
+	}
+	for _, want := range []string{
+		"## my-crate → tests → test_sub\n",
+		"left: 1\n right: 2\n",
+		"````\nthread 'tests::test_sub' panicked at src/lib.rs:10:5:\n```\nfenced\n```\n````\n",
+	} {
+		if !strings.Contains(out.String(), want) {
+			t.Errorf("Expected %q in:\n%s", want, out.String())
+		}
+	}
+	if strings.Contains(out.String(), "test_add") {
+		t.Errorf("Expected only failed tests, got:\n%s", out.String())
+	}
+
+	// Neither summary.json nor failed-tests.json: the run can't be read
+	if exitCode, err := runFailures(runsDir, []string{"20261013T090000-sleepy-kirk"}, &out); exitCode != failuresErrorExitCode || err == nil {
+		t.Errorf("Expected exit code %d for an older run, got %d (%v)", failuresErrorExitCode, exitCode, err)
+	}
+}
+
+func TestRunClean(t *testing.T) {
+	runsDir := writeHistoryFixture(t)
+
+	var out bytes.Buffer
+	if exitCode, err := runClean(runsDir, []string{"--keep=0", "--keep-days", "0", "--dry-run"}, &out); exitCode != 0 || err != nil {
+		t.Fatalf("Expected a dry run to succeed, got %d (%v)", exitCode, err)
+	}
+	if !strings.HasPrefix(out.String(), "Would remove 2 run(s)") || !strings.Contains(out.String(), "20261013T090000-sleepy-kirk") {
+		t.Errorf("Expected both runs listed, got:\n%s", out.String())
+	}
+
+	out.Reset()
+	if exitCode, err := runClean(runsDir, []string{"--keep", "1", "--keep-days", "0"}, &out); exitCode != 0 || err != nil {
+		t.Fatalf("Expected clean to succeed, got %d (%v)", exitCode, err)
+	}
+	if !strings.HasPrefix(out.String(), "Removed 1 run(s)") {
+		t.Errorf("Expected one run removed, got:\n%s", out.String())
+	}
+	if _, err := os.Stat(filepath.Join(runsDir, "20261013T090000-sleepy-kirk")); !os.IsNotExist(err) {
+		t.Errorf("Expected the older run to be removed")
+	}
+
+	if exitCode, err := runClean(runsDir, []string{"--keep", "-1"}, &out); exitCode != 1 || err == nil {
+		t.Errorf("Expected a negative --keep to be rejected, got %d (%v)", exitCode, err)
+	}
+}
+
+func TestRunDiff(t *testing.T) {
+	runsDir := writeHistoryFixture(t)
+
+	var out bytes.Buffer
+	if exitCode, err := runDiff(runsDir, nil, &out); exitCode != 1 || err == nil {
+		t.Errorf("Expected an error with only one run that has a summary, got %d (%v)", exitCode, err)
+	}
+
+	runID := "20261014T110000-sulky-uhura"
+	summary := report.RunSummary{
+		SchemaVersion: report.SummarySchemaVersion,
+		RunID:         runID,
+		Groups: []report.SummaryGroup{{
+			Path: []string{"my-crate", "math", "tests"},
+			TestCases: []report.SummaryTestCase{
+				{Name: "test_add", Status: "FAIL"},
+				{Name: "test_sub", Status: "PASS"},
+			},
+		}},
+	}
+	data, _ := json.Marshal(summary)
+	if err := os.MkdirAll(filepath.Join(runsDir, runID), 0755); err != nil {
+		t.Fatalf("Failed to create run directory: %v", err)
+	}
+	if err := os.WriteFile(filepath.Join(runsDir, runID, report.SummaryFileName), data, 0644); err != nil {
+		t.Fatalf("Failed to write summary: %v", err)
+	}
+
+	out.Reset()
+	if exitCode, err := runDiff(runsDir, nil, &out); exitCode != 0 || err != nil {
+		t.Fatalf("Expected diff to succeed, got %d (%v)", exitCode, err)
+	}
+	for _, want := range []string{
+		"Comparing 20261014T100000-cranky-spock with " + runID,
+		"newly failing  my-crate → math → tests → test_add (was in my-crate → tests)  PASS    FAIL",
+		"newly passing  my-crate → math → tests → test_sub (was in my-crate → tests)  FAIL    PASS",
+		"1 newly failing, 1 newly passing",
+	} {
+		if !strings.Contains(out.String(), want) {
+			t.Errorf("Expected output to contain %q, got:\n%s", want, out.String())
+		}
+	}
+
+	out.Reset()
+	if exitCode, err := runDiff(runsDir, []string{runID, runID, "--json"}, &out); exitCode != 0 || err != nil {
+		t.Fatalf("Expected diff --json to succeed, got %d (%v)", exitCode, err)
+	}
+	var diff runs.RunDiff
+	if err := json.Unmarshal(out.Bytes(), &diff); err != nil {
+		t.Fatalf("Expected JSON output, got %v:\n%s", err, out.String())
+	}
+	if !diff.Empty() || diff.Before != runID {
+		t.Errorf("Expected no changes between a run and itself, got %+v", diff)
+	}
+
+	if exitCode, err := runDiff(runsDir, []string{"20261013T090000-sleepy-kirk"}, &out); exitCode != 1 || err == nil {
+		t.Errorf("Expected an error for a run without a summary, got %d (%v)", exitCode, err)
+	}
+}
+
+func TestRunDiff_CargoBuild(t *testing.T) {
+	runsDir := t.TempDir()
+	builds := map[string]*runinfo.Cargo{
+		"20261014T100000-cranky-spock": {Profile: "dev"},
+		"20261014T110000-sulky-uhura":  {Profile: "dev", Features: []string{"extra"}},
+	}
+	for runID, build := range builds {
+		summary := report.RunSummary{
+			SchemaVersion: report.SummarySchemaVersion,
+			RunID:         runID,
+			Cargo:         build,
+			Groups: []report.SummaryGroup{{
+				Path:      []string{"my-crate", "tests"},
+				TestCases: []report.SummaryTestCase{{Name: "test_add", Status: "PASS"}},
+			}},
+		}
+		data, _ := json.Marshal(summary)
+		if err := os.MkdirAll(filepath.Join(runsDir, runID), 0755); err != nil {
+			t.Fatalf("Failed to create run directory: %v", err)
+		}
+		if err := os.WriteFile(filepath.Join(runsDir, runID, report.SummaryFileName), data, 0644); err != nil {
+			t.Fatalf("Failed to write summary: %v", err)
+		}
+	}
+	refs := []string{"20261014T100000-cranky-spock", "20261014T110000-sulky-uhura"}
+
+	var out bytes.Buffer
+	exitCode, err := runDiff(runsDir, refs, &out)
+	if exitCode != 1 || err == nil || !strings.Contains(err.Error(), "20261014T110000-sulky-uhura with profile dev, features extra") {
+		t.Fatalf("Expected diff to refuse runs built with other features, got %d (%v)", exitCode, err)
+	}
+
+	if exitCode, err := runDiff(runsDir, append(refs, "--force"), &out); exitCode != 0 || err != nil {
+		t.Fatalf("Expected diff --force to compare the runs, got %d (%v)", exitCode, err)
+	}
+	if !strings.Contains(out.String(), "Warning: the runs were built differently: 20261014T100000-cranky-spock was built with profile dev;") {
+		t.Errorf("Expected a warning about the builds, got:\n%s", out.String())
+	}
+
+	out.Reset()
+	if exitCode, err := runDiff(runsDir, append(refs, "--force", "--json"), &out); exitCode != 0 || err != nil {
+		t.Fatalf("Expected diff --force --json to succeed, got %d (%v)", exitCode, err)
+	}
+	var diff runs.RunDiff
+	if err := json.Unmarshal(out.Bytes(), &diff); err != nil || diff.BuildChanged == "" {
+		t.Errorf("Expected the JSON to say how the builds differ, got %v:\n%s", err, out.String())
+	}
+}
+
+func TestRunDiff_Coverage(t *testing.T) {
+	runsDir := t.TempDir()
+	measured := map[string]*coverage.Summary{
+		"20261014T100000-cranky-spock": {Report: "cov.lcov", Lines: coverage.Counts{Covered: 712, Total: 1000, Percent: 71.2}, Functions: coverage.Counts{Covered: 80, Total: 100, Percent: 80}},
+		"20261014T110000-sulky-uhura":  {Report: "cov.lcov", Lines: coverage.Counts{Covered: 740, Total: 1000, Percent: 74}, Functions: coverage.Counts{Covered: 79, Total: 100, Percent: 79}},
+	}
+	for runID, cov := range measured {
+		summary := report.RunSummary{
+			SchemaVersion: report.SummarySchemaVersion,
+			RunID:         runID,
+			Coverage:      cov,
+			Groups: []report.SummaryGroup{{
+				Path:      []string{"my-crate", "tests"},
+				TestCases: []report.SummaryTestCase{{Name: "test_add", Status: "PASS"}},
+			}},
+		}
+		data, _ := json.Marshal(summary)
+		if err := os.MkdirAll(filepath.Join(runsDir, runID), 0755); err != nil {
+			t.Fatalf("Failed to create run directory: %v", err)
+		}
+		if err := os.WriteFile(filepath.Join(runsDir, runID, report.SummaryFileName), data, 0644); err != nil {
+			t.Fatalf("Failed to write summary: %v", err)
+		}
+	}
+	refs := []string{"20261014T100000-cranky-spock", "20261014T110000-sulky-uhura"}
+
+	var out bytes.Buffer
+	if exitCode, err := runDiff(runsDir, refs, &out); exitCode != 0 || err != nil {
+		t.Fatalf("Expected diff to succeed, got %d (%v)", exitCode, err)
+	}
+	if want := "Coverage: lines 71.2% → 74.0% (+2.8), functions 80.0% → 79.0% (-1.0)"; !strings.Contains(out.String(), want) {
+		t.Errorf("Expected output to contain %q, got:\n%s", want, out.String())
+	}
+
+	out.Reset()
+	if exitCode, err := runDiff(runsDir, append(refs, "--json"), &out); exitCode != 0 || err != nil {
+		t.Fatalf("Expected diff --json to succeed, got %d (%v)", exitCode, err)
+	}
+	var diff runs.RunDiff
+	if err := json.Unmarshal(out.Bytes(), &diff); err != nil || diff.Coverage == nil || diff.Coverage.AfterLines != 74 {
+		t.Errorf("Expected the JSON to carry the coverage of both runs, got %v:\n%s", err, out.String())
+	}
+}
+
+func TestRunMerge(t *testing.T) {
+	runsDir := writeHistoryFixture(t)
+
+	// A second shard, where test_sub passed
+	runID := "20261014T100500-jolly-sulu"
+	summary := report.RunSummary{
+		SchemaVersion: report.SummarySchemaVersion,
+		RunID:         runID,
+		Command:       "cargo test",
+		Status:        "COMPLETED",
+		ExitReason:    report.ExitReasonOK,
+		Groups: []report.SummaryGroup{{
+			Path: []string{"my-crate", "tests"},
+			TestCases: []report.SummaryTestCase{
+				{Name: "test_mul", Status: "PASS"},
+				{Name: "test_sub", Status: "PASS"},
+			},
+		}},
+	}
+	data, _ := json.Marshal(summary)
+	shardDir := filepath.Join(t.TempDir(), runID)
+	if err := os.MkdirAll(shardDir, 0755); err != nil {
+		t.Fatalf("Failed to create run directory: %v", err)
+	}
+	if err := os.WriteFile(filepath.Join(shardDir, report.SummaryFileName), data, 0644); err != nil {
+		t.Fatalf("Failed to write summary: %v", err)
+	}
+
+	// Runs are given by ID in the runs directory or by path
+	outDir := filepath.Join(t.TempDir(), "merged")
+	var out bytes.Buffer
+	exitCode, err := runMerge(runsDir, []string{"20261014T100000-cranky-spock", shardDir, "--out", outDir}, &out)
+	if exitCode != 1 || err != nil {
+		t.Fatalf("Expected merge to exit 1 for the failed test, got %d (%v)", exitCode, err)
+	}
+	for _, want := range []string{
+		"Merged 2 runs into " + outDir,
+		"Results:   2 passed, 1 failed",
+		"Conflicting results (1):",
+		"- my-crate → tests → test_sub: FAIL on 20261014T100000-cranky-spock, PASS on " + runID,
+	} {
+		if !strings.Contains(out.String(), want) {
+			t.Errorf("Expected output to contain %q, got:\n%s", want, out.String())
+		}
+	}
+	merged, err := report.ReadSummary(outDir)
+	if err != nil || merged.RunID != "merged" || len(merged.Merge.Shards) != 2 {
+		t.Errorf("Expected the merged summary.json, got %+v (%v)", merged, err)
+	}
+
+	for _, args := range [][]string{
+		{"20261014T100000-cranky-spock"},
+		{"--out", outDir},
+		{"20261013T090000-sleepy-kirk", "--out", filepath.Join(t.TempDir(), "merged")},
+	} {
+		if exitCode, err := runMerge(runsDir, args, &out); exitCode != mergeErrorExitCode || err == nil {
+			t.Errorf("%v: expected exit code %d with an error, got %d (%v)", args, mergeErrorExitCode, exitCode, err)
+		}
+	}
+}
+
+func TestRunImport(t *testing.T) {
+	runsDir := writeHistoryFixture(t)
+	dir := t.TempDir()
+	for name, doc := range map[string]string{
+		"unit.xml": `<testsuites><testsuite name="unit"><testcase name="adds"/></testsuite></testsuites>`,
+		"e2e.xml":  `<testsuite name="e2e"><testcase name="logs in"><failure message="timed out"/></testcase></testsuite>`,
+	} {
+		if err := os.WriteFile(filepath.Join(dir, name), []byte(doc), 0644); err != nil {
+			t.Fatal(err)
+		}
+	}
+
+	var out bytes.Buffer
+	exitCode, err := runImport(runsDir, []string{"--format", "junit", filepath.Join(dir, "*.xml")}, &out)
+	if exitCode != 1 || err != nil {
+		t.Fatalf("Expected import to exit 1 for the failed test, got %d (%v)", exitCode, err)
+	}
+	if !strings.Contains(out.String(), "Imported 2 JUnit XML files") || !strings.Contains(out.String(), "Results:   1 passed, 1 failed") {
+		t.Errorf("Expected the imported run's results, got:\n%s", out.String())
+	}
+
+	// The imported run is the latest, as if 3pio had run it
+	latest, err := runs.Latest(runsDir)
+	if err != nil || !strings.HasSuffix(latest, "-junit-import") {
+		t.Fatalf("Expected the imported run to be the latest, got %q (%v)", latest, err)
+	}
+	summary, err := report.ReadSummary(filepath.Join(runsDir, latest))
+	if err != nil || summary.Totals.Total != 2 || len(summary.Merge.Shards) != 2 {
+		t.Errorf("Expected the merged summary.json of both files, got %+v (%v)", summary, err)
+	}
+
+	for _, args := range [][]string{
+		{filepath.Join(dir, "*.xml")},
+		{"--format", "xunit", filepath.Join(dir, "*.xml")},
+		{"--format=junit", filepath.Join(dir, "*.json")},
+	} {
+		if exitCode, err := runImport(runsDir, args, &out); exitCode != importErrorExitCode || err == nil {
+			t.Errorf("%v: expected exit code %d with an error, got %d (%v)", args, importErrorExitCode, exitCode, err)
+		}
+	}
+}
@@ -2,12 +2,19 @@ package main
 
 import (
 	"fmt"
+	"io"
+	"math"
 	"os"
+	"strconv"
 	"strings"
+	"time"
 
 	"github.com/spf13/cobra"
-	"github.com/zk/3pio/internal/logger"
+	"github.com/zk/3pio/internal/console"
+	"github.com/zk/3pio/internal/ipc"
 	"github.com/zk/3pio/internal/orchestrator"
+	"github.com/zk/3pio/internal/runner/definitions"
+	"github.com/zk/3pio/pkg/threepio"
 )
 
 var (
@@ -34,11 +41,55 @@ Examples:
   3pio npx jest                    # Run Jest directly
   3pio npx vitest run              # Run Vitest
   3pio pytest                      # Run pytest
-  3pio cargo test                  # Run Rust tests`,
+  3pio cargo test                  # Run Rust tests
+  3pio --runner criterion cargo bench  # Choose the runner instead of detecting it
+  3pio --runner tap prove -v t/    # Read the TAP any test harness prints
+  3pio exec -- ./scripts/smoke.sh  # Run any command as one test that passes when it exits 0
+  3pio --no-backtrace cargo test   # Don't collect Rust backtraces for failed tests
+  3pio --timeout 600 cargo test    # Stop the run after 10 minutes, exiting with code 124
+  3pio --inactivity-timeout 300 cargo test  # Stop a hung run after 5 minutes without output
+  3pio --rerun-failed cargo test   # Run only the tests that failed last time
+  3pio --junit-xml report.xml cargo test  # Also write JUnit XML for CI
+  3pio --summary-json - cargo test  # Print the JSON summary after the console summary
+  3pio --gha-annotations cargo test  # Annotate failures on GitHub (default when GITHUB_ACTIONS=true)
+  3pio --slow-threshold 0.5 cargo test  # Sort group reports by duration once a test takes over 500ms
+  3pio --no-snippets cargo test    # Leave the source lines around failures out of group reports
+  3pio --snippet-lines 5 cargo test  # Print 5 lines of a group's first failure under it (default 15, 0 for none)
+  3pio --allow-no-tests cargo test my_filter  # Exit 0 instead of 5 when the filter matches no test
+  3pio --quarantine flaky.toml cargo test --no-fail-fast  # Known-flaky tests don't fail the run
+  3pio --retries 2 cargo test --no-fail-fast  # Re-run failed tests twice; those that pass are flaky
+  3pio --retries 2 --fail-on-flaky cargo test  # Report flaky tests, but still fail the run
+  3pio --fail-on-xpass pytest      # Fail the run when an xfail-marked test passes
+  3pio --fail-on-warnings pytest   # Fail the run when pytest recorded a warning
+  3pio --strict-args npx jest --watch  # Refuse watch flags instead of removing them with a warning
+  3pio --no-progress cargo test    # No live progress line, even in a terminal (--progress forces it)
+  3pio --no-preflight-list cargo test  # Don't list the tests as pending before running them
+  3pio --redact cargo test         # Replace the values of *TOKEN*, *SECRET*, *PASSWORD* and AWS_* variables in reports (--redact-raw: output.log too)
+  3pio --heartbeat 120 cargo test  # Without a progress line, say what runs after 2 minutes of silence (default 60, --no-heartbeat)
+  3pio -q cargo test               # Only the failed tests and the results line (--quiet)
+  3pio -v cargo test               # Also show every test as it completes (--verbose)
+  3pio --max-line-length 16M cargo test  # Parse output lines up to 16MB (default 4M); longer ones are cut, output.log keeps them
+  3pio --color=never cargo test    # No colors on the console (always, never, or auto: a terminal without NO_COLOR)
+  3pio --ipc-transport stdio npx jest  # Adapters send events over fd 3 (stderr on Windows) instead of a file
+  3pio --output json cargo test    # Stream the run's events as JSON lines on stdout instead of the console output
+
+Browsing past runs:
+  3pio list                        # Table of the 20 most recent runs (--limit <n> for more)
+  3pio show [<run-id>|latest]      # Summary and failures of a run
+  3pio failures [<run-id>|latest]  # Each failed test with its full output; exits 1 if there are any
+  3pio diff --json                 # Tests that changed between the last two runs
+  3pio clean --dry-run             # Runs that clean would remove (--keep 20, --keep-days 7, --keep-failed)
+  3pio merge shard-*/.3pio/runs/* --out merged  # Combine the runs of CI shards into one report
+  3pio import --format junit 'reports/*.xml'  # Record the JUnit XML of a run made without 3pio as a run
+
+Defaults for the flags above come from .3pio.toml at the repository root, the user
+config file (~/.config/3pio/config.toml) and THREEPIO_<FLAG> variables:
+  3pio config --show               # Each option's value and where it comes from`,
 		Version: fmt.Sprintf("%s (commit: %s, built: %s)", version, commit, date),
 	}
 
-	// No subcommands - 3pio works as a direct wrapper
+	// No cobra subcommands - 3pio works as a direct wrapper, and list, show and
+	// failures are dispatched from RunE
 
 	// Allow running without "run" subcommand
 	rootCmd.DisableFlagParsing = true
@@ -51,13 +102,21 @@ Examples:
 			if firstArg == "--help" || firstArg == "-h" || firstArg == "help" {
 				return cmd.Help()
 			}
-			// Check for version flags
-			if firstArg == "--version" || firstArg == "-v" || firstArg == "version" {
+			// Check for version flags; "-v" before a test command is --verbose
+			if firstArg == "--version" || (firstArg == "-v" && len(args) == 1) || firstArg == "version" {
 				fmt.Printf("3pio version %s\n", version)
 				fmt.Printf("Commit: %s\n", commit)
 				fmt.Printf("Built: %s\n", date)
 				return nil
 			}
+			// config --show prints the effective config
+			if firstArg == "config" {
+				return runConfigCommand(args[1:])
+			}
+			// list, show and failures browse past runs
+			if command, ok := historyCommands[firstArg]; ok {
+				return runHistoryCommand(command, args[1:])
+			}
 			// Otherwise, assume it's a test command
 			return runTests(args)
 		}
@@ -85,39 +144,96 @@ Flags:
 
 // runTestsCore contains the core logic for running tests (testable)
 func runTestsCore(args []string) (int, error) {
-	// Check for unsupported modes
-	if err := checkUnsupportedModes(args); err != nil {
+	// .3pio.toml, the user config file and THREEPIO_<KEY> variables give defaults for
+	// the options, which the command line overrides
+	cfg, err := loadConfig()
+	if err != nil {
+		fmt.Fprintf(os.Stderr, "Error: %v\n", err)
+		return 1, err
+	}
+	for _, warning := range cfg.Warnings {
+		fmt.Fprintf(os.Stderr, "Warning: %s\n", warning)
+	}
+	cliArgs := args
+	args, err = withConfig(cfg, args)
+	if err != nil {
 		fmt.Fprintf(os.Stderr, "Error: %v\n", err)
 		return 1, err
 	}
 
-	// Create file logger
-	fileLogger, err := logger.NewFileLogger()
+	// 3pio options such as --runner come before the test command
+	opts, args, err := parseRunOptions(args)
 	if err != nil {
-		fmt.Fprintf(os.Stderr, "Failed to create debug logger: %v\n", err)
+		fmt.Fprintf(os.Stderr, "Error: %v\n", err)
 		return 1, err
 	}
-	defer func() {
-		if err := fileLogger.Close(); err != nil {
-			fmt.Fprintf(os.Stderr, "Warning: failed to close debug log: %v\n", err)
+	if definitions.UsesMiri(args) {
+		applyMiriDefaults(&opts, cliArgs[:len(cliArgs)-len(args)])
+	}
+
+	// 3pio exec -- <command> runs any command, reporting its output and exit code
+	if len(args) > 0 && args[0] == "exec" {
+		args = args[1:]
+		if len(args) > 0 && args[0] == "--" {
+			args = args[1:]
+		}
+		if len(args) == 0 {
+			err := fmt.Errorf("usage: 3pio [options] exec -- <command...>")
+			fmt.Fprintf(os.Stderr, "Error: %v\n", err)
+			return 1, err
 		}
-	}()
+		opts.runner = "exec"
+	}
 
-	// Create orchestrator configuration
-	config := orchestrator.Config{
-		Command: args,
-		Logger:  fileLogger,
+	// Check for unsupported modes; a plain command's flags are its own
+	if opts.runner != "exec" {
+		if err := checkUnsupportedModes(args); err != nil {
+			fmt.Fprintf(os.Stderr, "Error: %v\n", err)
+			return 1, err
+		}
 	}
 
-	// Create and run orchestrator
-	orch, err := orchestrator.New(config)
-	if err != nil {
-		fmt.Fprintf(os.Stderr, "Failed to create orchestrator: %v\n", err)
-		return 1, err
+	// The run goes through the same library API that programs embedding 3pio use
+	config := threepio.RunConfig{
+		Command:           args,
+		Runner:            opts.runner,
+		NoBacktrace:       opts.noBacktrace,
+		Timeout:           opts.timeout,
+		InactivityTimeout: opts.inactivityTimeout,
+		RerunFailed:       opts.rerunFailed,
+		JUnitXMLPath:      opts.junitXMLPath,
+		SummaryJSON:       opts.summaryJSON,
+		SlowThreshold:     opts.slowThreshold,
+		GHAAnnotations:    opts.ghaAnnotations,
+		NoSnippets:        opts.noSnippets,
+		AllowNoTests:      opts.allowNoTests,
+		QuarantinePath:    opts.quarantinePath,
+		Retries:           opts.retries,
+		FailOnFlaky:       opts.failOnFlaky,
+		FailOnXPass:       opts.failOnXPass,
+		FailOnWarnings:    opts.failOnWarnings,
+		StrictArgs:        opts.strictArgs,
+		SnippetLines:      opts.snippetLines,
+		MaxLineLength:     opts.maxLineLength,
+		HeartbeatInterval: opts.heartbeat,
+		Progress:          opts.progress,
+		PreflightList:     opts.preflightList,
+		Redact:            opts.redact,
+		RedactRaw:         opts.redactRaw,
+		Verbosity:         opts.verbosity(),
+		Color:             opts.color,
+		IPCTransport:      opts.ipcTransport,
+	}
+	if opts.output == outputJSON {
+		// stdout only carries the event stream; errors still go to stderr
+		config.Console = io.Discard
+		config.Progress = false
+		config.EventStream = os.Stdout
 	}
 
 	// Run tests
-	if err := orch.Run(); err != nil {
+	result, err := threepio.Run(config)
+	if err != nil {
 		// Check if it's a test runner not found error
 		if strings.Contains(err.Error(), "no test runner detected") {
 			fmt.Fprintf(os.Stderr, "\nError: Could not detect test runner from command: %s\n", strings.Join(args, " "))
@@ -125,9 +241,13 @@ func runTestsCore(args []string) (int, error) {
 			fmt.Fprintf(os.Stderr, "\nTest Runners:\n")
 			fmt.Fprintf(os.Stderr, "  • Jest\n")
 			fmt.Fprintf(os.Stderr, "  • Vitest (requires v3.0+)\n")
+			fmt.Fprintf(os.Stderr, "  • Playwright Test\n")
 			fmt.Fprintf(os.Stderr, "  • pytest\n")
 			fmt.Fprintf(os.Stderr, "  • go test\n")
 			fmt.Fprintf(os.Stderr, "  • cargo test\n")
+			fmt.Fprintf(os.Stderr, "  • cargo bench (criterion)\n")
+			fmt.Fprintf(os.Stderr, "  • node --test\n")
+			fmt.Fprintf(os.Stderr, "  • bun test\n")
 			fmt.Fprintf(os.Stderr, "\nPackage Managers:\n")
 			fmt.Fprintf(os.Stderr, "  • npm\n")
 			fmt.Fprintf(os.Stderr, "  • yarn\n")
@@ -146,11 +266,413 @@ func runTestsCore(args []string) (int, error) {
 		}
 
 		fmt.Fprintf(os.Stderr, "Test execution failed: %v\n", err)
-		return orch.GetExitCode(), err
+		return result.ExitCode, err
 	}
 
 	// Return the exit code
-	return orch.GetExitCode(), nil
+	return result.ExitCode, nil
+}
+
+// runOptions holds the 3pio options given before the test command
+type runOptions struct {
+	runner            string            // Runner name to use instead of detecting it
+	noBacktrace       bool              // Don't set RUST_BACKTRACE=1 for cargo test
+	timeout           time.Duration     // Stop the run after this long in total; 0 waits forever
+	inactivityTimeout time.Duration     // Stop the run after this long without output; 0 waits forever
+	rerunFailed       bool              // Run only the tests that failed in the latest run
+	junitXMLPath      string            // Also write the results as JUnit XML to this path
+	summaryJSON       string            // Also copy summary.json to this path; "-" prints it to stdout
+	slowThreshold     time.Duration     // Sort group reports by duration once a test takes longer; 0 for the default
+	ghaAnnotations    bool              // Print GitHub Actions ::error commands, on by default under GitHub Actions
+	noSnippets        bool              // Don't embed source lines around failures in group reports
+	allowNoTests      bool              // Exit 0 when the test filter matched no test
+	quarantinePath    string            // Quarantine file to use instead of .3pio/quarantine.toml
+	retries           int               // Re-run failed tests up to this many times
+	failOnFlaky       bool              // Fail the run when a test only passed on a retry
+	failOnXPass       bool              // Fail the run when a test marked xfail passed
+	failOnWarnings    bool              // Fail the run when the runner recorded warnings
+	strictArgs        bool              // Refuse watch flags instead of removing them
+	snippetLines      int               // Print this many lines of a group's first failure on the console
+	maxLineLength     int               // Parse at most this many bytes of a line of runner output; 0 for the default
+	progress          bool              // Show a live progress line, on by default when stdout is a terminal
+	preflightList     bool              // List the tests before running them, on by default
+	redact            bool              // Replace the values of secret environment variables in the reports
+	redactRaw         bool              // Also replace them in output.log
+	heartbeat         time.Duration     // Without a progress line, print what is running after this long without output; 0 never
+	quiet             bool              // Print only the failed tests and the summary
+	verbose           bool              // Also print every test case as it completes
+	color             console.ColorMode // When the console uses colors: auto, always or never
+	ipcTransport      ipc.Transport     // How adapters send their events: file or stdio
+	output            string            // What goes to stdout: the console output or the JSON event stream
+}
+
+// Values of --output
+const (
+	outputConsole = "console" // The console output, by default
+	outputJSON    = "json"    // The run's events as JSON lines, with no console output
+)
+
+// verbosity returns the console verbosity chosen with -q/--quiet or -v/--verbose
+func (opts runOptions) verbosity() console.Verbosity {
+	switch {
+	case opts.quiet:
+		return console.Quiet
+	case opts.verbose:
+		return console.Verbose
+	default:
+		return console.Normal
+	}
+}
+
+// parseRunOptions extracts the 3pio options that come before the test command:
+// "--runner <name>" (or "--runner=<name>"), which selects the test runner instead
+// of detecting it, "--no-backtrace", "--timeout <secs>", "--inactivity-timeout <secs>",
+// "--rerun-failed", "--junit-xml <path>", "--summary-json <path|->",
+// "--slow-threshold <secs>", "--gha-annotations" (or "--no-gha-annotations"),
+// "--no-snippets", "--allow-no-tests", "--quarantine <path>", "--retries <n>",
+// "--fail-on-flaky", "--fail-on-xpass", "--fail-on-warnings", "--strict-args", "--snippet-lines <n>",
+// "--progress" (or "--no-progress"), "--preflight-list" (or "--no-preflight-list"),
+// "--redact", "--redact-raw",
+// "--heartbeat <secs>" (or "--no-heartbeat"), "-q/--quiet" or "-v/--verbose",
+// "--color <auto|always|never>", "--max-line-length <bytes>",
+// "--ipc-transport <file|stdio>", and "--output <console|json>"
+func parseRunOptions(args []string) (runOptions, []string, error) {
+	opts := runOptions{
+		ghaAnnotations: os.Getenv("GITHUB_ACTIONS") == "true",
+		progress:       console.IsTerminal(os.Stdout),
+		preflightList:  true,
+		color:          console.ColorAuto,
+		ipcTransport:   ipc.TransportFile,
+		snippetLines:   orchestrator.DefaultSnippetLines,
+		heartbeat:      orchestrator.DefaultHeartbeatInterval,
+		output:         outputConsole,
+	}
+	timeouts := map[string]*time.Duration{
+		"--timeout":            &opts.timeout,
+		"--inactivity-timeout": &opts.inactivityTimeout,
+		"--heartbeat":          &opts.heartbeat,
+	}
+	paths := map[string]*string{
+		"--junit-xml":    &opts.junitXMLPath,
+		"--summary-json": &opts.summaryJSON,
+		"--quarantine":   &opts.quarantinePath,
+	}
+	for len(args) > 0 {
+		if name, ok := strings.CutPrefix(args[0], "--runner="); ok {
+			if name == "" {
+				return opts, nil, fmt.Errorf("--runner requires a runner name")
+			}
+			opts.runner = name
+			args = args[1:]
+			continue
+		}
+		if flag, path, ok := strings.Cut(args[0], "="); ok && paths[flag] != nil {
+			if path == "" {
+				return opts, nil, fmt.Errorf("%s requires a file path", flag)
+			}
+			*paths[flag] = path
+			args = args[1:]
+			continue
+		}
+		if path := paths[args[0]]; path != nil {
+			if len(args) < 2 || args[1] == "" {
+				return opts, nil, fmt.Errorf("%s requires a file path", args[0])
+			}
+			*path = args[1]
+			args = args[2:]
+			continue
+		}
+		if n, ok := strings.CutPrefix(args[0], "--retries="); ok {
+			if err := parseRetries(n, &opts.retries); err != nil {
+				return opts, nil, err
+			}
+			args = args[1:]
+			continue
+		}
+		if n, ok := strings.CutPrefix(args[0], "--snippet-lines="); ok {
+			if err := parseSnippetLines(n, &opts.snippetLines); err != nil {
+				return opts, nil, err
+			}
+			args = args[1:]
+			continue
+		}
+		if size, ok := strings.CutPrefix(args[0], "--max-line-length="); ok {
+			if err := parseLineLength(size, &opts.maxLineLength); err != nil {
+				return opts, nil, err
+			}
+			args = args[1:]
+			continue
+		}
+		if value, ok := strings.CutPrefix(args[0], "--ipc-transport="); ok {
+			transport, err := ipc.ParseTransport(value)
+			if err != nil {
+				return opts, nil, err
+			}
+			opts.ipcTransport = transport
+			args = args[1:]
+			continue
+		}
+		if value, ok := strings.CutPrefix(args[0], "--output="); ok {
+			if err := parseOutput(value, &opts.output); err != nil {
+				return opts, nil, err
+			}
+			args = args[1:]
+			continue
+		}
+		if mode, ok := strings.CutPrefix(args[0], "--color="); ok {
+			color, err := console.ParseColorMode(mode)
+			if err != nil {
+				return opts, nil, err
+			}
+			opts.color = color
+			args = args[1:]
+			continue
+		}
+		if secs, ok := strings.CutPrefix(args[0], "--slow-threshold="); ok {
+			if err := parseThresholdSeconds(secs, &opts.slowThreshold); err != nil {
+				return opts, nil, err
+			}
+			args = args[1:]
+			continue
+		}
+		if flag, secs, ok := strings.Cut(args[0], "="); ok && timeouts[flag] != nil {
+			if err := parseTimeoutSeconds(flag, secs, timeouts[flag]); err != nil {
+				return opts, nil, err
+			}
+			args = args[1:]
+			continue
+		}
+		if timeout := timeouts[args[0]]; timeout != nil {
+			if len(args) < 2 {
+				return opts, nil, fmt.Errorf("%s requires a number of seconds", args[0])
+			}
+			if err := parseTimeoutSeconds(args[0], args[1], timeout); err != nil {
+				return opts, nil, err
+			}
+			args = args[2:]
+			continue
+		}
+
+		switch args[0] {
+		case "--runner":
+			if len(args) < 2 {
+				return opts, nil, fmt.Errorf("--runner requires a runner name")
+			}
+			opts.runner = args[1]
+			args = args[2:]
+		case "--slow-threshold":
+			if len(args) < 2 {
+				return opts, nil, fmt.Errorf("--slow-threshold requires a number of seconds")
+			}
+			if err := parseThresholdSeconds(args[1], &opts.slowThreshold); err != nil {
+				return opts, nil, err
+			}
+			args = args[2:]
+		case "--retries":
+			if len(args) < 2 {
+				return opts, nil, fmt.Errorf("--retries requires a number of retries")
+			}
+			if err := parseRetries(args[1], &opts.retries); err != nil {
+				return opts, nil, err
+			}
+			args = args[2:]
+		case "--snippet-lines":
+			if len(args) < 2 {
+				return opts, nil, fmt.Errorf("--snippet-lines requires a number of lines")
+			}
+			if err := parseSnippetLines(args[1], &opts.snippetLines); err != nil {
+				return opts, nil, err
+			}
+			args = args[2:]
+		case "--max-line-length":
+			if len(args) < 2 {
+				return opts, nil, fmt.Errorf("--max-line-length requires a number of bytes")
+			}
+			if err := parseLineLength(args[1], &opts.maxLineLength); err != nil {
+				return opts, nil, err
+			}
+			args = args[2:]
+		case "--color":
+			if len(args) < 2 {
+				return opts, nil, fmt.Errorf("--color requires auto, always or never")
+			}
+			color, err := console.ParseColorMode(args[1])
+			if err != nil {
+				return opts, nil, err
+			}
+			opts.color = color
+			args = args[2:]
+		case "--ipc-transport":
+			if len(args) < 2 {
+				return opts, nil, fmt.Errorf("--ipc-transport requires file or stdio")
+			}
+			transport, err := ipc.ParseTransport(args[1])
+			if err != nil {
+				return opts, nil, err
+			}
+			opts.ipcTransport = transport
+			args = args[2:]
+		case "--output":
+			if len(args) < 2 {
+				return opts, nil, fmt.Errorf("--output requires console or json")
+			}
+			if err := parseOutput(args[1], &opts.output); err != nil {
+				return opts, nil, err
+			}
+			args = args[2:]
+		case "--fail-on-flaky":
+			opts.failOnFlaky = true
+			args = args[1:]
+		case "--fail-on-xpass":
+			opts.failOnXPass = true
+			args = args[1:]
+		case "--fail-on-warnings":
+			opts.failOnWarnings = true
+			args = args[1:]
+		case "--strict-args":
+			opts.strictArgs = true
+			args = args[1:]
+		case "--no-backtrace":
+			opts.noBacktrace = true
+			args = args[1:]
+		case "--rerun-failed":
+			opts.rerunFailed = true
+			args = args[1:]
+		case "--no-snippets":
+			opts.noSnippets = true
+			args = args[1:]
+		case "--allow-no-tests":
+			opts.allowNoTests = true
+			args = args[1:]
+		case "--gha-annotations", "--no-gha-annotations":
+			opts.ghaAnnotations = args[0] == "--gha-annotations"
+			args = args[1:]
+		case "--progress", "--no-progress":
+			opts.progress = args[0] == "--progress"
+			args = args[1:]
+		case "--preflight-list", "--no-preflight-list":
+			opts.preflightList = args[0] == "--preflight-list"
+			args = args[1:]
+		case "--redact":
+			opts.redact = true
+			args = args[1:]
+		case "--redact-raw":
+			opts.redactRaw = true
+			args = args[1:]
+		case "--no-heartbeat":
+			opts.heartbeat = 0
+			args = args[1:]
+		case "-q", "--quiet":
+			opts.quiet = true
+			args = args[1:]
+		case "-v", "--verbose":
+			opts.verbose = true
+			args = args[1:]
+		default:
+			return opts, args, checkOptions(opts)
+		}
+	}
+	return opts, args, checkOptions(opts)
+}
+
+// parseSnippetLines parses the value of --snippet-lines, a number of lines that may be 0
+func parseSnippetLines(value string, lines *int) error {
+	n, err := strconv.Atoi(value)
+	if err != nil || n < 0 {
+		return fmt.Errorf("--snippet-lines requires a non-negative number of lines, got %q", value)
+	}
+	*lines = n
+	return nil
+}
+
+// parseLineLength parses the value of --max-line-length, a positive number of bytes
+// that may end in K or M for KiB or MiB
+func parseLineLength(value string, length *int) error {
+	number, unit := value, 1
+	if n, ok := strings.CutSuffix(value, "K"); ok {
+		number, unit = n, 1024
+	} else if n, ok := strings.CutSuffix(value, "M"); ok {
+		number, unit = n, 1024*1024
+	}
+	n, err := strconv.Atoi(number)
+	if err != nil || n <= 0 || n > math.MaxInt32/unit {
+		return fmt.Errorf("--max-line-length requires a positive number of bytes, such as 4M, got %q", value)
+	}
+	*length = n * unit
+	return nil
+}
+
+// checkOptions rejects -q/--quiet together with -v/--verbose, and --summary-json -
+// together with --output json, which both print to stdout
+func checkOptions(opts runOptions) error {
+	if opts.quiet && opts.verbose {
+		return fmt.Errorf("--quiet and --verbose can't be used together")
+	}
+	if opts.output == outputJSON && opts.summaryJSON == "-" {
+		return fmt.Errorf("--summary-json - can't be used with --output json, whose run_finished event holds the summary")
+	}
+	return nil
+}
+
+// miriSlowdown is how many times longer the heartbeat interval and the inactivity
+// timeout are by default under cargo miri test, which interprets the tests
+const miriSlowdown = 10
+
+// applyMiriDefaults stretches the heartbeat interval and the inactivity timeout that
+// the 3pio flags given on the command line don't set, the defaults and the config's
+// alike, as a test under miri can go minutes without printing
+func applyMiriDefaults(opts *runOptions, given []string) {
+	for _, option := range []struct {
+		flags configOption
+		value *time.Duration
+	}{
+		{configOption{flag: "--heartbeat", off: "--no-heartbeat"}, &opts.heartbeat},
+		{configOption{flag: "--inactivity-timeout"}, &opts.inactivityTimeout},
+	} {
+		if !option.flags.overriddenBy(given) {
+			*option.value *= miriSlowdown
+		}
+	}
+}
+
+// parseOutput parses the value of --output, console or json
+func parseOutput(value string, output *string) error {
+	if value != outputConsole && value != outputJSON {
+		return fmt.Errorf("--output requires console or json, got %q", value)
+	}
+	*output = value
+	return nil
+}
+
+// parseRetries parses the value of --retries, a number of retries that may be 0
+func parseRetries(value string, retries *int) error {
+	n, err := strconv.Atoi(value)
+	if err != nil || n < 0 {
+		return fmt.Errorf("--retries requires a non-negative number of retries, got %q", value)
+	}
+	*retries = n
+	return nil
+}
+
+// parseTimeoutSeconds parses a timeout flag's value, a positive number of seconds
+func parseTimeoutSeconds(flag, value string, timeout *time.Duration) error {
+	secs, err := strconv.Atoi(value)
+	if err != nil || secs <= 0 {
+		return fmt.Errorf("%s requires a positive number of seconds, got %q", flag, value)
+	}
+	*timeout = time.Duration(secs) * time.Second
+	return nil
+}
+
+// parseThresholdSeconds parses --slow-threshold, a positive number of seconds that
+// may have a fraction ("0.05" for 50ms)
+func parseThresholdSeconds(value string, threshold *time.Duration) error {
+	secs, err := strconv.ParseFloat(value, 64)
+	if err != nil || math.IsNaN(secs) || math.IsInf(secs, 0) || secs <= 0 {
+		return fmt.Errorf("--slow-threshold requires a positive number of seconds, got %q", value)
+	}
+	*threshold = time.Duration(secs * float64(time.Second))
+	return nil
 }
 
 func runTests(args []string) error {
@@ -159,51 +681,21 @@ func runTests(args []string) error {
 	return nil // Never reached, but needed for signature
 }
 
-// checkUnsupportedModes checks for watch mode and coverage mode
+// checkUnsupportedModes checks for watchers with no single-run mode to fall back to, and
+// coverage mode. The orchestrator removes the watch flags of the runners it supports.
 func checkUnsupportedModes(args []string) error {
 	// Join all args to check for flags
 	cmdStr := strings.Join(args, " ")
 
 	// Check for watch mode
 	watchPatterns := []string{
-		"--watch",
-		"--watchAll",
-		" -w ",  // Short form with spaces to avoid matching --workspace
-		" -w\t", // Short form with tab
 		"pytest-watch",
 		"ptw", // pytest-watch alias
 	}
 
-	// Also check for -w at the end or beginning of args
-	for _, arg := range args {
-		if arg == "-w" {
-			return fmt.Errorf("watch mode is not supported. Please run tests in single-run mode without -w flag")
-		}
-	}
-
-	// Special case: plain 'vitest' without 'run' defaults to watch mode
-	if len(args) > 0 && strings.Contains(cmdStr, "vitest") && !strings.Contains(cmdStr, "run") {
-		// Check if it's actually vitest command
-		for _, arg := range args {
-			if strings.HasSuffix(arg, "vitest") || arg == "vitest" {
-				// Check if 'run' is in subsequent args
-				hasRun := false
-				for _, a := range args {
-					if a == "run" {
-						hasRun = true
-						break
-					}
-				}
-				if !hasRun {
-					return fmt.Errorf("watch mode is not supported. Vitest defaults to watch mode. Please use 'vitest run' for single-run mode")
-				}
-			}
-		}
-	}
-
 	for _, pattern := range watchPatterns {
 		if strings.Contains(cmdStr, pattern) {
-			return fmt.Errorf("watch mode is not supported. Please run tests in single-run mode without %s flag", pattern)
+			return fmt.Errorf("watch mode is not supported. Please run tests in single-run mode without %s", pattern)
 		}
 	}
 
@@ -4,7 +4,10 @@ import (
 	"os"
 	"strings"
 	"testing"
+	"time"
 
+	"github.com/zk/3pio/internal/console"
+	"github.com/zk/3pio/internal/ipc"
 	"github.com/zk/3pio/internal/logger"
 	"github.com/zk/3pio/internal/orchestrator"
 )
@@ -79,6 +82,591 @@ func TestRunTestsCore_ValidCommands(t *testing.T) {
 	}
 }
 
+func TestParseRunOptions(t *testing.T) {
+	testCases := []struct {
+		args        []string
+		desc        string
+		runner      string
+		noBacktrace bool
+		command     []string
+		expectError bool
+	}{
+		{[]string{"cargo", "bench"}, "no options", "", false, []string{"cargo", "bench"}, false},
+		{[]string{"--runner", "criterion", "cargo", "bench"}, "runner flag with value", "criterion", false, []string{"cargo", "bench"}, false},
+		{[]string{"--runner=criterion", "cargo", "bench"}, "runner flag with equals", "criterion", false, []string{"cargo", "bench"}, false},
+		{[]string{"cargo", "bench", "--runner", "criterion"}, "runner flag after command belongs to the command", "", false, []string{"cargo", "bench", "--runner", "criterion"}, false},
+		{[]string{"--no-backtrace", "cargo", "test"}, "no-backtrace flag", "", true, []string{"cargo", "test"}, false},
+		{[]string{"--no-backtrace", "--runner", "cargo", "cargo", "test"}, "no-backtrace and runner flags", "cargo", true, []string{"cargo", "test"}, false},
+		{[]string{"cargo", "test", "--no-backtrace"}, "no-backtrace flag after command belongs to the command", "", false, []string{"cargo", "test", "--no-backtrace"}, false},
+		{[]string{"--runner"}, "runner flag without value", "", false, nil, true},
+		{[]string{"--runner=", "cargo", "bench"}, "runner flag with empty value", "", false, nil, true},
+	}
+
+	for _, tc := range testCases {
+		t.Run(tc.desc, func(t *testing.T) {
+			opts, command, err := parseRunOptions(tc.args)
+			if tc.expectError {
+				if err == nil {
+					t.Errorf("Expected error for %v, got nil", tc.args)
+				}
+				return
+			}
+			if err != nil {
+				t.Fatalf("Unexpected error for %v: %v", tc.args, err)
+			}
+			if opts.runner != tc.runner {
+				t.Errorf("Expected runner %q, got %q", tc.runner, opts.runner)
+			}
+			if opts.noBacktrace != tc.noBacktrace {
+				t.Errorf("Expected noBacktrace %v, got %v", tc.noBacktrace, opts.noBacktrace)
+			}
+			if strings.Join(command, " ") != strings.Join(tc.command, " ") {
+				t.Errorf("Expected command %v, got %v", tc.command, command)
+			}
+		})
+	}
+}
+
+func TestParseRunOptions_Timeouts(t *testing.T) {
+	testCases := []struct {
+		args              []string
+		desc              string
+		timeout           time.Duration
+		inactivityTimeout time.Duration
+		command           []string
+		expectError       bool
+	}{
+		{[]string{"cargo", "test"}, "no timeouts by default", 0, 0, []string{"cargo", "test"}, false},
+		{[]string{"--timeout", "600", "cargo", "test"}, "run timeout with value", 600 * time.Second, 0, []string{"cargo", "test"}, false},
+		{[]string{"--timeout=600", "cargo", "test"}, "run timeout with equals", 600 * time.Second, 0, []string{"cargo", "test"}, false},
+		{[]string{"--inactivity-timeout", "300", "cargo", "test"}, "inactivity timeout with value", 0, 300 * time.Second, []string{"cargo", "test"}, false},
+		{[]string{"--inactivity-timeout=30", "--no-backtrace", "cargo", "test"}, "inactivity timeout with equals", 0, 30 * time.Second, []string{"cargo", "test"}, false},
+		{[]string{"--timeout", "600", "--inactivity-timeout", "60", "cargo", "test"}, "both timeouts", 600 * time.Second, 60 * time.Second, []string{"cargo", "test"}, false},
+		{[]string{"cargo", "test", "--timeout", "600"}, "timeout after command belongs to the command", 0, 0, []string{"cargo", "test", "--timeout", "600"}, false},
+		{[]string{"--inactivity-timeout"}, "timeout without value", 0, 0, nil, true},
+		{[]string{"--timeout", "soon", "cargo", "test"}, "timeout that isn't a number", 0, 0, nil, true},
+		{[]string{"--inactivity-timeout=0", "cargo", "test"}, "zero timeout", 0, 0, nil, true},
+	}
+
+	for _, tc := range testCases {
+		t.Run(tc.desc, func(t *testing.T) {
+			opts, command, err := parseRunOptions(tc.args)
+			if tc.expectError {
+				if err == nil {
+					t.Errorf("Expected error for %v, got nil", tc.args)
+				}
+				return
+			}
+			if err != nil {
+				t.Fatalf("Unexpected error for %v: %v", tc.args, err)
+			}
+			if opts.timeout != tc.timeout {
+				t.Errorf("Expected timeout %v, got %v", tc.timeout, opts.timeout)
+			}
+			if opts.inactivityTimeout != tc.inactivityTimeout {
+				t.Errorf("Expected inactivity timeout %v, got %v", tc.inactivityTimeout, opts.inactivityTimeout)
+			}
+			if strings.Join(command, " ") != strings.Join(tc.command, " ") {
+				t.Errorf("Expected command %v, got %v", tc.command, command)
+			}
+		})
+	}
+}
+
+func TestParseRunOptions_Heartbeat(t *testing.T) {
+	for args, want := range map[string]time.Duration{
+		"cargo test":                 orchestrator.DefaultHeartbeatInterval,
+		"--heartbeat 120 cargo test": 120 * time.Second,
+		"--heartbeat=5 cargo test":   5 * time.Second,
+		"--no-heartbeat cargo test":  0,
+	} {
+		opts, command, err := parseRunOptions(strings.Fields(args))
+		if err != nil {
+			t.Fatalf("%s: unexpected error: %v", args, err)
+		}
+		if opts.heartbeat != want || strings.Join(command, " ") != "cargo test" {
+			t.Errorf("%s: got heartbeat %v and command %v, want %v", args, opts.heartbeat, command, want)
+		}
+	}
+	if _, _, err := parseRunOptions([]string{"--heartbeat", "0", "cargo", "test"}); err == nil {
+		t.Error("Expected an error for --heartbeat 0, which --no-heartbeat is for")
+	}
+}
+
+func TestApplyMiriDefaults(t *testing.T) {
+	testCases := []struct {
+		args              string
+		heartbeat         time.Duration
+		inactivityTimeout time.Duration
+	}{
+		{"cargo miri test", 10 * orchestrator.DefaultHeartbeatInterval, 0},
+		{"--inactivity-timeout 300 cargo miri test", 10 * orchestrator.DefaultHeartbeatInterval, 300 * time.Second},
+		{"--heartbeat=30 cargo miri test", 30 * time.Second, 0},
+		{"--no-heartbeat cargo miri test", 0, 0},
+	}
+	for _, tc := range testCases {
+		args := strings.Fields(tc.args)
+		opts, command, err := parseRunOptions(args)
+		if err != nil {
+			t.Fatalf("%s: unexpected error: %v", tc.args, err)
+		}
+		applyMiriDefaults(&opts, args[:len(args)-len(command)])
+		if opts.heartbeat != tc.heartbeat || opts.inactivityTimeout != tc.inactivityTimeout {
+			t.Errorf("%s: got heartbeat %v and inactivity timeout %v, want %v and %v", tc.args, opts.heartbeat, opts.inactivityTimeout, tc.heartbeat, tc.inactivityTimeout)
+		}
+	}
+
+	// The config's inactivity timeout is a default, meant for runs that aren't interpreted
+	opts, _, err := parseRunOptions(strings.Fields("--inactivity-timeout 300 cargo miri test"))
+	if err != nil {
+		t.Fatal(err)
+	}
+	applyMiriDefaults(&opts, nil)
+	if opts.inactivityTimeout != 3000*time.Second {
+		t.Errorf("Expected the config's inactivity timeout to be stretched, got %v", opts.inactivityTimeout)
+	}
+}
+
+func TestParseRunOptions_RerunFailed(t *testing.T) {
+	opts, command, err := parseRunOptions([]string{"--rerun-failed", "--no-backtrace", "cargo", "test"})
+	if err != nil {
+		t.Fatalf("Unexpected error: %v", err)
+	}
+	if !opts.rerunFailed || !opts.noBacktrace {
+		t.Errorf("Expected rerunFailed and noBacktrace, got %+v", opts)
+	}
+	if strings.Join(command, " ") != "cargo test" {
+		t.Errorf("Expected command [cargo test], got %v", command)
+	}
+
+	// After the command it's the command's own flag
+	opts, _, _ = parseRunOptions([]string{"cargo", "test", "--rerun-failed"})
+	if opts.rerunFailed {
+		t.Errorf("Expected --rerun-failed after the command to be left to the command")
+	}
+}
+
+func TestParseRunOptions_GHAAnnotations(t *testing.T) {
+	testCases := []struct {
+		githubActions string
+		args          []string
+		expected      bool
+	}{
+		{"", []string{"cargo", "test"}, false},
+		{"true", []string{"cargo", "test"}, true},
+		{"", []string{"--gha-annotations", "cargo", "test"}, true},
+		{"true", []string{"--no-gha-annotations", "cargo", "test"}, false},
+	}
+	for _, tc := range testCases {
+		t.Setenv("GITHUB_ACTIONS", tc.githubActions)
+		opts, command, err := parseRunOptions(tc.args)
+		if err != nil {
+			t.Fatalf("Unexpected error for %v: %v", tc.args, err)
+		}
+		if opts.ghaAnnotations != tc.expected {
+			t.Errorf("Expected ghaAnnotations %v for %v with GITHUB_ACTIONS=%q, got %v", tc.expected, tc.args, tc.githubActions, opts.ghaAnnotations)
+		}
+		if strings.Join(command, " ") != "cargo test" {
+			t.Errorf("Expected command [cargo test] for %v, got %v", tc.args, command)
+		}
+	}
+}
+
+func TestParseRunOptions_PreflightList(t *testing.T) {
+	testCases := []struct {
+		args     []string
+		expected bool
+	}{
+		{[]string{"cargo", "test"}, true},
+		{[]string{"--no-preflight-list", "cargo", "test"}, false},
+		{[]string{"--no-preflight-list", "--preflight-list", "cargo", "test"}, true},
+	}
+	for _, tc := range testCases {
+		opts, command, err := parseRunOptions(tc.args)
+		if err != nil {
+			t.Fatalf("Unexpected error for %v: %v", tc.args, err)
+		}
+		if opts.preflightList != tc.expected {
+			t.Errorf("Expected preflightList %v for %v, got %v", tc.expected, tc.args, opts.preflightList)
+		}
+		if strings.Join(command, " ") != "cargo test" {
+			t.Errorf("Expected command [cargo test] for %v, got %v", tc.args, command)
+		}
+	}
+}
+
+func TestParseRunOptions_Redact(t *testing.T) {
+	testCases := []struct {
+		args      []string
+		redact    bool
+		redactRaw bool
+	}{
+		{[]string{"cargo", "test"}, false, false},
+		{[]string{"--redact", "cargo", "test"}, true, false},
+		{[]string{"--redact-raw", "cargo", "test"}, false, true},
+	}
+	for _, tc := range testCases {
+		opts, command, err := parseRunOptions(tc.args)
+		if err != nil {
+			t.Fatalf("Unexpected error for %v: %v", tc.args, err)
+		}
+		if opts.redact != tc.redact || opts.redactRaw != tc.redactRaw {
+			t.Errorf("Expected redact %v and redactRaw %v for %v, got %v and %v", tc.redact, tc.redactRaw, tc.args, opts.redact, opts.redactRaw)
+		}
+		if strings.Join(command, " ") != "cargo test" {
+			t.Errorf("Expected command [cargo test] for %v, got %v", tc.args, command)
+		}
+	}
+}
+
+func TestParseRunOptions_JUnitXML(t *testing.T) {
+	for _, args := range [][]string{
+		{"--junit-xml", "target/junit.xml", "cargo", "test"},
+		{"--junit-xml=target/junit.xml", "cargo", "test"},
+	} {
+		opts, command, err := parseRunOptions(args)
+		if err != nil {
+			t.Fatalf("Unexpected error for %v: %v", args, err)
+		}
+		if opts.junitXMLPath != "target/junit.xml" {
+			t.Errorf("Expected junitXMLPath target/junit.xml for %v, got %q", args, opts.junitXMLPath)
+		}
+		if strings.Join(command, " ") != "cargo test" {
+			t.Errorf("Expected command [cargo test] for %v, got %v", args, command)
+		}
+	}
+
+	for _, args := range [][]string{{"--junit-xml"}, {"--junit-xml=", "cargo", "test"}} {
+		if _, _, err := parseRunOptions(args); err == nil {
+			t.Errorf("Expected an error for %v", args)
+		}
+	}
+}
+
+func TestParseRunOptions_SummaryJSON(t *testing.T) {
+	opts, command, err := parseRunOptions([]string{"--summary-json", "-", "--junit-xml=junit.xml", "cargo", "test"})
+	if err != nil {
+		t.Fatalf("Unexpected error: %v", err)
+	}
+	if opts.summaryJSON != "-" || opts.junitXMLPath != "junit.xml" {
+		t.Errorf("Expected summaryJSON - and junitXMLPath junit.xml, got %+v", opts)
+	}
+	if strings.Join(command, " ") != "cargo test" {
+		t.Errorf("Expected command [cargo test], got %v", command)
+	}
+
+	if _, _, err := parseRunOptions([]string{"--summary-json"}); err == nil {
+		t.Errorf("Expected an error for --summary-json without a path")
+	}
+}
+
+func TestParseRunOptions_SlowThreshold(t *testing.T) {
+	for _, args := range [][]string{
+		{"--slow-threshold", "0.05", "cargo", "test"},
+		{"--slow-threshold=0.05", "cargo", "test"},
+	} {
+		opts, command, err := parseRunOptions(args)
+		if err != nil {
+			t.Fatalf("Unexpected error for %v: %v", args, err)
+		}
+		if opts.slowThreshold != 50*time.Millisecond {
+			t.Errorf("Expected a 50ms slow threshold for %v, got %v", args, opts.slowThreshold)
+		}
+		if strings.Join(command, " ") != "cargo test" {
+			t.Errorf("Expected command [cargo test], got %v", command)
+		}
+	}
+
+	for _, args := range [][]string{{"--slow-threshold"}, {"--slow-threshold", "0", "cargo"}, {"--slow-threshold=fast"}, {"--slow-threshold", "NaN"}} {
+		if _, _, err := parseRunOptions(args); err == nil {
+			t.Errorf("Expected an error for %v", args)
+		}
+	}
+}
+
+func TestParseRunOptions_NoSnippetsAndAllowNoTests(t *testing.T) {
+	opts, command, err := parseRunOptions([]string{"--no-snippets", "--allow-no-tests", "cargo", "test"})
+	if err != nil {
+		t.Fatalf("Unexpected error: %v", err)
+	}
+	if !opts.noSnippets || !opts.allowNoTests {
+		t.Errorf("Expected noSnippets and allowNoTests, got %+v", opts)
+	}
+	if strings.Join(command, " ") != "cargo test" {
+		t.Errorf("Expected command [cargo test], got %v", command)
+	}
+}
+
+func TestParseRunOptions_Quarantine(t *testing.T) {
+	for _, args := range [][]string{
+		{"--quarantine", "ci/flaky.toml", "cargo", "test"},
+		{"--quarantine=ci/flaky.toml", "cargo", "test"},
+	} {
+		opts, command, err := parseRunOptions(args)
+		if err != nil {
+			t.Fatalf("%v: unexpected error: %v", args, err)
+		}
+		if opts.quarantinePath != "ci/flaky.toml" {
+			t.Errorf("%v: expected quarantinePath ci/flaky.toml, got %q", args, opts.quarantinePath)
+		}
+		if strings.Join(command, " ") != "cargo test" {
+			t.Errorf("%v: expected command [cargo test], got %v", args, command)
+		}
+	}
+	if _, _, err := parseRunOptions([]string{"--quarantine"}); err == nil {
+		t.Errorf("Expected an error for --quarantine without a path")
+	}
+}
+
+func TestParseRunOptions_Retries(t *testing.T) {
+	for _, args := range [][]string{
+		{"--retries", "2", "--fail-on-flaky", "cargo", "test"},
+		{"--fail-on-flaky", "--retries=2", "cargo", "test"},
+	} {
+		opts, command, err := parseRunOptions(args)
+		if err != nil {
+			t.Fatalf("%v: unexpected error: %v", args, err)
+		}
+		if opts.retries != 2 || !opts.failOnFlaky {
+			t.Errorf("%v: expected retries 2 and failOnFlaky, got %d and %v", args, opts.retries, opts.failOnFlaky)
+		}
+		if strings.Join(command, " ") != "cargo test" {
+			t.Errorf("%v: expected command [cargo test], got %v", args, command)
+		}
+	}
+	for _, args := range [][]string{
+		{"--retries"},
+		{"--retries", "-1", "cargo", "test"},
+		{"--retries=two", "cargo", "test"},
+	} {
+		if _, _, err := parseRunOptions(args); err == nil {
+			t.Errorf("%v: expected an error", args)
+		}
+	}
+}
+
+func TestParseRunOptions_FailOnXPass(t *testing.T) {
+	opts, command, err := parseRunOptions([]string{"--fail-on-xpass", "pytest", "-x"})
+	if err != nil {
+		t.Fatalf("Unexpected error: %v", err)
+	}
+	if !opts.failOnXPass {
+		t.Errorf("Expected failOnXPass, got %+v", opts)
+	}
+	if strings.Join(command, " ") != "pytest -x" {
+		t.Errorf("Expected command [pytest -x], got %v", command)
+	}
+}
+
+func TestParseRunOptions_FailOnWarnings(t *testing.T) {
+	opts, command, err := parseRunOptions([]string{"--fail-on-warnings", "pytest"})
+	if err != nil {
+		t.Fatalf("Unexpected error: %v", err)
+	}
+	if !opts.failOnWarnings || strings.Join(command, " ") != "pytest" {
+		t.Errorf("Expected failOnWarnings and command [pytest], got %+v and %v", opts, command)
+	}
+}
+
+func TestParseRunOptions_SnippetLines(t *testing.T) {
+	opts, _, err := parseRunOptions([]string{"cargo", "test"})
+	if err != nil || opts.snippetLines != orchestrator.DefaultSnippetLines {
+		t.Errorf("Expected %d snippet lines by default, got %d, %v", orchestrator.DefaultSnippetLines, opts.snippetLines, err)
+	}
+	for args, want := range map[string]int{
+		"--snippet-lines 5 cargo test": 5,
+		"--snippet-lines=0 cargo test": 0,
+	} {
+		opts, command, err := parseRunOptions(strings.Fields(args))
+		if err != nil {
+			t.Fatalf("%s: unexpected error: %v", args, err)
+		}
+		if opts.snippetLines != want || strings.Join(command, " ") != "cargo test" {
+			t.Errorf("%s: got %d lines and command %v", args, opts.snippetLines, command)
+		}
+	}
+	for _, args := range [][]string{
+		{"--snippet-lines"},
+		{"--snippet-lines", "-3", "cargo", "test"},
+		{"--snippet-lines=many", "cargo", "test"},
+	} {
+		if _, _, err := parseRunOptions(args); err == nil {
+			t.Errorf("%v: expected an error", args)
+		}
+	}
+}
+
+func TestParseRunOptions_Verbosity(t *testing.T) {
+	tests := []struct {
+		args []string
+		want console.Verbosity
+	}{
+		{[]string{"cargo", "test"}, console.Normal},
+		{[]string{"-q", "cargo", "test"}, console.Quiet},
+		{[]string{"--quiet", "cargo", "test"}, console.Quiet},
+		{[]string{"-v", "cargo", "test"}, console.Verbose},
+		{[]string{"--no-progress", "--verbose", "cargo", "test"}, console.Verbose},
+	}
+	for _, tt := range tests {
+		opts, command, err := parseRunOptions(tt.args)
+		if err != nil {
+			t.Fatalf("%v: unexpected error: %v", tt.args, err)
+		}
+		if got := opts.verbosity(); got != tt.want {
+			t.Errorf("%v: verbosity = %v, want %v", tt.args, got, tt.want)
+		}
+		if strings.Join(command, " ") != "cargo test" {
+			t.Errorf("%v: expected command [cargo test], got %v", tt.args, command)
+		}
+	}
+
+	// -v after the test command belongs to the test runner
+	opts, command, err := parseRunOptions([]string{"pytest", "-v"})
+	if err != nil || opts.verbose || strings.Join(command, " ") != "pytest -v" {
+		t.Errorf("Expected pytest -v to be left alone, got %v, %v, %v", opts.verbose, command, err)
+	}
+
+	if _, _, err := parseRunOptions([]string{"-q", "-v", "cargo", "test"}); err == nil {
+		t.Error("Expected an error for --quiet with --verbose")
+	}
+}
+
+func TestParseRunOptions_MaxLineLength(t *testing.T) {
+	tests := []struct {
+		args []string
+		want int
+	}{
+		{[]string{"cargo", "test"}, 0},
+		{[]string{"--max-line-length", "65536", "cargo", "test"}, 65536},
+		{[]string{"--max-line-length=64K", "cargo", "test"}, 64 * 1024},
+		{[]string{"--max-line-length", "16M", "cargo", "test"}, 16 * 1024 * 1024},
+	}
+	for _, tt := range tests {
+		opts, command, err := parseRunOptions(tt.args)
+		if err != nil {
+			t.Fatalf("%v: unexpected error: %v", tt.args, err)
+		}
+		if opts.maxLineLength != tt.want {
+			t.Errorf("%v: maxLineLength = %d, want %d", tt.args, opts.maxLineLength, tt.want)
+		}
+		if strings.Join(command, " ") != "cargo test" {
+			t.Errorf("%v: expected command [cargo test], got %v", tt.args, command)
+		}
+	}
+
+	for _, args := range [][]string{
+		{"--max-line-length", "0", "cargo", "test"},
+		{"--max-line-length=-1M", "cargo", "test"},
+		{"--max-line-length", "4G", "cargo", "test"},
+		{"--max-line-length", "99999M", "cargo", "test"},
+		{"--max-line-length"},
+	} {
+		if _, _, err := parseRunOptions(args); err == nil {
+			t.Errorf("%v: expected an error", args)
+		}
+	}
+}
+
+func TestParseRunOptions_Color(t *testing.T) {
+	tests := []struct {
+		args []string
+		want console.ColorMode
+	}{
+		{[]string{"cargo", "test"}, console.ColorAuto},
+		{[]string{"--color=always", "cargo", "test"}, console.ColorAlways},
+		{[]string{"--color", "never", "cargo", "test"}, console.ColorNever},
+		{[]string{"--color=never", "--color", "auto", "cargo", "test"}, console.ColorAuto},
+	}
+	for _, tt := range tests {
+		opts, command, err := parseRunOptions(tt.args)
+		if err != nil {
+			t.Fatalf("%v: unexpected error: %v", tt.args, err)
+		}
+		if opts.color != tt.want {
+			t.Errorf("%v: color = %q, want %q", tt.args, opts.color, tt.want)
+		}
+		if strings.Join(command, " ") != "cargo test" {
+			t.Errorf("%v: expected command [cargo test], got %v", tt.args, command)
+		}
+	}
+
+	for _, args := range [][]string{
+		{"--color=sometimes", "cargo", "test"},
+		{"--color"},
+		{"--color=", "cargo", "test"},
+	} {
+		if _, _, err := parseRunOptions(args); err == nil {
+			t.Errorf("%v: expected an error", args)
+		}
+	}
+}
+
+func TestParseRunOptions_Output(t *testing.T) {
+	tests := []struct {
+		args []string
+		want string
+	}{
+		{[]string{"cargo", "test"}, outputConsole},
+		{[]string{"--output", "json", "cargo", "test"}, outputJSON},
+		{[]string{"--output=json", "--summary-json", "summary.json", "cargo", "test"}, outputJSON},
+		{[]string{"--output=json", "--output", "console", "cargo", "test"}, outputConsole},
+	}
+	for _, tt := range tests {
+		opts, command, err := parseRunOptions(tt.args)
+		if err != nil {
+			t.Fatalf("%v: unexpected error: %v", tt.args, err)
+		}
+		if opts.output != tt.want {
+			t.Errorf("%v: output = %q, want %q", tt.args, opts.output, tt.want)
+		}
+		if strings.Join(command, " ") != "cargo test" {
+			t.Errorf("%v: expected command [cargo test], got %v", tt.args, command)
+		}
+	}
+
+	for _, args := range [][]string{
+		{"--output=yaml", "cargo", "test"},
+		{"--output"},
+		{"--output", "json", "--summary-json", "-", "cargo", "test"},
+	} {
+		if _, _, err := parseRunOptions(args); err == nil {
+			t.Errorf("%v: expected an error", args)
+		}
+	}
+}
+
+func TestParseRunOptions_IPCTransport(t *testing.T) {
+	tests := []struct {
+		args []string
+		want ipc.Transport
+	}{
+		{[]string{"npx", "jest"}, ipc.TransportFile},
+		{[]string{"--ipc-transport=stdio", "npx", "jest"}, ipc.TransportStdio},
+		{[]string{"--ipc-transport", "file", "npx", "jest"}, ipc.TransportFile},
+	}
+	for _, tt := range tests {
+		opts, command, err := parseRunOptions(tt.args)
+		if err != nil {
+			t.Fatalf("%v: unexpected error: %v", tt.args, err)
+		}
+		if opts.ipcTransport != tt.want {
+			t.Errorf("%v: ipcTransport = %q, want %q", tt.args, opts.ipcTransport, tt.want)
+		}
+		if strings.Join(command, " ") != "npx jest" {
+			t.Errorf("%v: expected command [npx jest], got %v", tt.args, command)
+		}
+	}
+
+	for _, args := range [][]string{
+		{"--ipc-transport=socket", "npx", "jest"},
+		{"--ipc-transport"},
+	} {
+		if _, _, err := parseRunOptions(args); err == nil {
+			t.Errorf("%v: expected an error", args)
+		}
+	}
+}
+
 func TestMain_Args(t *testing.T) {
 	// Save original args
 	oldArgs := os.Args
@@ -0,0 +1,96 @@
+//! The IPC events 3pio reads from the file in `THREEPIO_IPC_PATH`, one JSON object
+//! per line. See docs/architecture/test-runner-adapters.md for the full schema.
+
+use serde::Serialize;
+
+/// Event schema this crate writes, 3pio's `ipc.SchemaVersion`
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// An event, written as `{"eventType":"testCase","payload":{...},"schema":2}`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "eventType", content = "payload")]
+pub enum Event {
+    /// Sent first, so 3pio can stop a run whose reporter writes a schema it can't read
+    #[serde(rename = "adapterHandshake")]
+    AdapterHandshake(HandshakePayload),
+    #[serde(rename = "testGroupDiscovered")]
+    GroupDiscovered(GroupPayload),
+    #[serde(rename = "testGroupStart")]
+    GroupStart(GroupPayload),
+    #[serde(rename = "testCase")]
+    TestCase(TestCasePayload),
+    #[serde(rename = "testGroupResult")]
+    GroupResult(GroupResultPayload),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HandshakePayload {
+    pub adapter: String,
+    pub schema: u32,
+}
+
+/// Names a group by its place in the hierarchy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupPayload {
+    pub group_name: String,
+    /// Enclosing groups from the root down, empty for the root group
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub parent_names: Vec<String>,
+}
+
+/// Status of a test, or of a group once it finished
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Status {
+    Pass,
+    Fail,
+    Skip,
+    /// A group that ran no tests
+    NoTests,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestCasePayload {
+    pub test_name: String,
+    /// Groups the test belongs to, from the root down
+    pub parent_names: Vec<String>,
+    pub status: Status,
+    /// Duration in milliseconds
+    pub duration: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<TestError>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stdout: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stderr: Option<String>,
+    /// Why a skipped test didn't run, e.g. "ignored"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skip_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TestError {
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupResultPayload {
+    pub group_name: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub parent_names: Vec<String>,
+    pub status: Status,
+    /// Duration in milliseconds
+    pub duration: f64,
+    pub totals: Totals,
+}
+
+/// Tests finished in a group and the groups nested in it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Totals {
+    pub passed: u32,
+    pub failed: u32,
+    pub skipped: u32,
+}
@@ -0,0 +1,414 @@
+//! Reports the tests of a custom Rust test harness (`harness = false`) to 3pio.
+//!
+//! 3pio reads the JSON output of libtest, which harnesses such as libtest-mimic or
+//! cucumber don't print, so their tests would show up as one opaque target. A harness
+//! can send its groups and tests itself instead:
+//!
+//! ```no_run
+//! use threepio_reporter::{Outcome, ThreePioReporter};
+//!
+//! let reporter = ThreePioReporter::from_env(env!("CARGO_CRATE_NAME"));
+//! if let Some(reporter) = &reporter {
+//!     reporter.test_started(&["math"], "adds").ok();
+//!     reporter.test_finished(&["math"], "adds", Outcome::Passed).ok();
+//!     reporter.finish().ok();
+//! }
+//! ```
+//!
+//! Outside 3pio `from_env` returns `None` and the harness runs as usual.
+
+pub mod events;
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Instant;
+
+use events::{
+    Event, GroupPayload, GroupResultPayload, HandshakePayload, Status, TestCasePayload, TestError,
+    Totals, SCHEMA_VERSION,
+};
+
+/// Environment variable holding the IPC file 3pio reads the events from
+pub const IPC_PATH_ENV: &str = "THREEPIO_IPC_PATH";
+
+/// Adapter name sent in the handshake
+pub const ADAPTER_NAME: &str = "threepio-reporter";
+
+/// How a test finished
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Passed,
+    Failed { message: String },
+    Ignored,
+}
+
+/// Sends a harness's groups and tests to 3pio.
+///
+/// Groups are named by their path below the root group, which is the test target
+/// (`&["parser", "errors"]` is the group "errors" in "parser"); the empty path is the
+/// root. Groups start when first used and finish with [`finish_group`] or [`finish`].
+///
+/// The reporter can be shared between threads. Each event is one line, appended to
+/// the IPC file with a single write.
+///
+/// [`finish_group`]: ThreePioReporter::finish_group
+/// [`finish`]: ThreePioReporter::finish
+pub struct ThreePioReporter {
+    root: String,
+    inner: Mutex<Inner>,
+}
+
+struct Inner {
+    file: File,
+    groups: Vec<Group>, // In the order they started
+    running: HashMap<(Vec<String>, String), Instant>,
+}
+
+struct Group {
+    path: Vec<String>,
+    started: Instant,
+    totals: Totals,
+    finished: bool,
+}
+
+impl ThreePioReporter {
+    /// Opens the IPC file of the 3pio run, or returns `None` outside 3pio.
+    /// `target` names the root group, usually `env!("CARGO_CRATE_NAME")`.
+    pub fn from_env(target: &str) -> Option<Self> {
+        let path = std::env::var_os(IPC_PATH_ENV)?;
+        match Self::open(&path, target) {
+            Ok(reporter) => Some(reporter),
+            Err(err) => {
+                let path = Path::new(&path).display();
+                eprintln!("threepio-reporter: can't open {}: {}", path, err);
+                None
+            }
+        }
+    }
+
+    /// Opens the IPC file at path and starts the root group
+    pub fn open(path: impl AsRef<Path>, target: &str) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        // 3pio names cargo targets with hyphens
+        let reporter = ThreePioReporter {
+            root: target.replace('_', "-"),
+            inner: Mutex::new(Inner {
+                file,
+                groups: Vec::new(),
+                running: HashMap::new(),
+            }),
+        };
+        {
+            let mut inner = reporter.lock();
+            inner.send(&Event::AdapterHandshake(HandshakePayload {
+                adapter: ADAPTER_NAME.to_string(),
+                schema: SCHEMA_VERSION,
+            }))?;
+            inner.start_group(&reporter.root, &[])?;
+        }
+        Ok(reporter)
+    }
+
+    /// Sends an event as is
+    pub fn send(&self, event: &Event) -> io::Result<()> {
+        self.lock().send(event)
+    }
+
+    /// Starts a group and the groups enclosing it
+    pub fn start_group(&self, path: &[&str]) -> io::Result<()> {
+        self.lock().start_group(&self.root, &to_path(path))
+    }
+
+    /// Records the start of a test, which times it
+    pub fn test_started(&self, group: &[&str], name: &str) -> io::Result<()> {
+        let group = to_path(group);
+        let mut inner = self.lock();
+        inner.start_group(&self.root, &group)?;
+        inner.running.insert((group, name.to_string()), Instant::now());
+        Ok(())
+    }
+
+    /// Reports a finished test, timed from [`test_started`](Self::test_started)
+    pub fn test_finished(&self, group: &[&str], name: &str, outcome: Outcome) -> io::Result<()> {
+        let group = to_path(group);
+        let mut inner = self.lock();
+        inner.start_group(&self.root, &group)?;
+        let duration = inner
+            .running
+            .remove(&(group.clone(), name.to_string()))
+            .map_or(0.0, |started| started.elapsed().as_secs_f64() * 1000.0);
+
+        let (status, error, skip_reason) = match outcome {
+            Outcome::Passed => (Status::Pass, None, None),
+            Outcome::Failed { message } => (Status::Fail, Some(TestError { message }), None),
+            Outcome::Ignored => (Status::Skip, None, Some("ignored".to_string())),
+        };
+        // The test counts toward its group and every group enclosing it
+        for g in inner.groups.iter_mut() {
+            if !g.finished && group.starts_with(&g.path) {
+                match status {
+                    Status::Pass => g.totals.passed += 1,
+                    Status::Fail => g.totals.failed += 1,
+                    _ => g.totals.skipped += 1,
+                }
+            }
+        }
+
+        let mut parent_names = vec![self.root.clone()];
+        parent_names.extend(group);
+        inner.send(&Event::TestCase(TestCasePayload {
+            test_name: name.to_string(),
+            parent_names,
+            status,
+            duration,
+            error,
+            stdout: None,
+            stderr: None,
+            skip_reason,
+        }))
+    }
+
+    /// Finishes a group after the groups nested in it
+    pub fn finish_group(&self, path: &[&str]) -> io::Result<()> {
+        self.lock().finish_group(&self.root, &to_path(path))
+    }
+
+    /// Finishes every group still running, the root group last. Call it before the
+    /// harness exits: `std::process::exit` skips the finish on drop.
+    pub fn finish(&self) -> io::Result<()> {
+        self.finish_group(&[])
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        // A panic while holding the lock leaves nothing half written worth stopping for
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Drop for ThreePioReporter {
+    fn drop(&mut self) {
+        let _ = self.finish();
+    }
+}
+
+impl Inner {
+    fn start_group(&mut self, root: &str, path: &[String]) -> io::Result<()> {
+        if self.groups.iter().any(|g| g.path == path) {
+            return Ok(());
+        }
+        if let Some((_, parent)) = path.split_last() {
+            self.start_group(root, parent)?;
+        }
+        let payload = group_payload(root, path);
+        self.send(&Event::GroupDiscovered(payload.clone()))?;
+        self.send(&Event::GroupStart(payload))?;
+        self.groups.push(Group {
+            path: path.to_vec(),
+            started: Instant::now(),
+            totals: Totals::default(),
+            finished: false,
+        });
+        Ok(())
+    }
+
+    fn finish_group(&mut self, root: &str, path: &[String]) -> io::Result<()> {
+        self.start_group(root, path)?;
+        // Nested groups started after their parent, so finish them from the last
+        for i in (0..self.groups.len()).rev() {
+            let group = &self.groups[i];
+            if group.finished || !group.path.starts_with(path) {
+                continue;
+            }
+            let totals = group.totals;
+            let status = if totals.failed > 0 {
+                Status::Fail
+            } else if totals.passed > 0 {
+                Status::Pass
+            } else if totals.skipped > 0 {
+                Status::Skip
+            } else {
+                Status::NoTests
+            };
+            let payload = group_payload(root, &group.path);
+            let duration = group.started.elapsed().as_secs_f64() * 1000.0;
+            self.groups[i].finished = true;
+            self.send(&Event::GroupResult(GroupResultPayload {
+                group_name: payload.group_name,
+                parent_names: payload.parent_names,
+                status,
+                duration,
+                totals,
+            }))?;
+        }
+        Ok(())
+    }
+
+    fn send(&mut self, event: &Event) -> io::Result<()> {
+        let mut value = serde_json::to_value(event)?;
+        value["schema"] = SCHEMA_VERSION.into();
+        let mut line = serde_json::to_vec(&value)?;
+        line.push(b'\n');
+        // One write of the whole line to a file opened for appending, so lines from
+        // other threads and processes never land inside it
+        self.file.write_all(&line)
+    }
+}
+
+fn to_path(path: &[&str]) -> Vec<String> {
+    path.iter().map(|name| name.to_string()).collect()
+}
+
+fn group_payload(root: &str, path: &[String]) -> GroupPayload {
+    match path.split_last() {
+        None => GroupPayload {
+            group_name: root.to_string(),
+            parent_names: Vec::new(),
+        },
+        Some((name, parents)) => {
+            let mut parent_names = vec![root.to_string()];
+            parent_names.extend_from_slice(parents);
+            GroupPayload {
+                group_name: name.clone(),
+                parent_names,
+            }
+        }
+    }
+}
+
+/// Returns the command line arguments without the libtest JSON flags 3pio adds to
+/// every test binary of `cargo test` (`-Z unstable-options --format json --report-time`),
+/// which custom harnesses reject. Outside 3pio the arguments are returned unchanged.
+pub fn args() -> Vec<String> {
+    if std::env::var_os(IPC_PATH_ENV).is_none() {
+        return std::env::args().collect();
+    }
+    strip_libtest_json_args(std::env::args().collect())
+}
+
+fn strip_libtest_json_args(args: Vec<String>) -> Vec<String> {
+    let mut result = Vec::with_capacity(args.len());
+    let mut i = 0;
+    while i < args.len() {
+        match (args[i].as_str(), args.get(i + 1).map(String::as_str)) {
+            ("-Z", Some("unstable-options")) | ("--format", Some("json")) => i += 2,
+            ("-Zunstable-options" | "--format=json" | "--report-time", _) => i += 1,
+            _ => {
+                result.push(args[i].clone());
+                i += 1;
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::sync::Arc;
+    use std::thread;
+
+    fn temp_ipc_path(name: &str) -> std::path::PathBuf {
+        let file = format!("threepio-reporter-{}-{}.jsonl", name, std::process::id());
+        let path = std::env::temp_dir().join(file);
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn read_events(path: &Path) -> Vec<Value> {
+        let content = std::fs::read_to_string(path).unwrap();
+        content
+            .lines()
+            .map(|line| {
+                serde_json::from_str(line)
+                    .unwrap_or_else(|err| panic!("invalid line {:?}: {}", line, err))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reports_groups_and_tests() {
+        let path = temp_ipc_path("groups");
+        let reporter = ThreePioReporter::open(&path, "custom_harness").unwrap();
+        reporter.test_started(&["math"], "adds").unwrap();
+        reporter.test_finished(&["math"], "adds", Outcome::Passed).unwrap();
+        let failed = Outcome::Failed {
+            message: "expected 7".into(),
+        };
+        reporter
+            .test_finished(&["math", "division"], "by_zero", failed)
+            .unwrap();
+        reporter.test_finished(&[], "slow", Outcome::Ignored).unwrap();
+        reporter.finish().unwrap();
+        drop(reporter);
+
+        let events = read_events(&path);
+        assert_eq!(events[0]["eventType"], "adapterHandshake");
+        assert!(events.iter().all(|e| e["schema"] == 2));
+
+        let test = events.iter().find(|e| e["payload"]["testName"] == "by_zero").unwrap();
+        let parents = serde_json::json!(["custom-harness", "math", "division"]);
+        assert_eq!(test["payload"]["parentNames"], parents);
+        assert_eq!(test["payload"]["status"], "FAIL");
+        assert_eq!(test["payload"]["error"]["message"], "expected 7");
+
+        // Nested groups finish first, and count the tests of the groups they enclose
+        let results: Vec<&Value> = events
+            .iter()
+            .filter(|e| e["eventType"] == "testGroupResult")
+            .collect();
+        let names: Vec<&Value> = results.iter().map(|e| &e["payload"]["groupName"]).collect();
+        assert_eq!(names, ["division", "math", "custom-harness"]);
+        let totals = serde_json::json!({"passed": 1, "failed": 1, "skipped": 0});
+        assert_eq!(results[1]["payload"]["totals"], totals);
+        assert_eq!(results[2]["payload"]["status"], "FAIL");
+        assert_eq!(results[2]["payload"]["totals"]["skipped"], 1);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn writes_whole_lines_from_many_threads() {
+        let path = temp_ipc_path("threads");
+        let reporter = Arc::new(ThreePioReporter::open(&path, "threads").unwrap());
+        let message = "x".repeat(64 * 1024);
+        let handles: Vec<_> = (0..8)
+            .map(|t| {
+                let reporter = Arc::clone(&reporter);
+                let message = message.clone();
+                thread::spawn(move || {
+                    for i in 0..50 {
+                        let name = format!("test_{}_{}", t, i);
+                        let failed = Outcome::Failed {
+                            message: message.clone(),
+                        };
+                        reporter.test_finished(&["load"], &name, failed).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        reporter.finish().unwrap();
+
+        let tests = read_events(&path)
+            .into_iter()
+            .filter(|e| e["eventType"] == "testCase")
+            .count();
+        assert_eq!(tests, 8 * 50);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn strips_the_libtest_json_flags() {
+        let args = [
+            "harness", "parser", "-Z", "unstable-options", "--format", "json", "--report-time",
+            "--exact",
+        ];
+        let args = strip_libtest_json_args(args.iter().map(|a| a.to_string()).collect());
+        assert_eq!(args, ["harness", "parser", "--exact"]);
+    }
+}
@@ -12,6 +12,9 @@ const path = require('path');
 const IPC_PATH = /*__IPC_PATH__*/"WILL_BE_REPLACED"/*__IPC_PATH__*/;
 const LOG_LEVEL = /*__LOG_LEVEL__*/"WARN"/*__LOG_LEVEL__*/;
 
+// IPC event schema this adapter writes, checked by 3pio (ipc.SchemaVersion)
+const SCHEMA_VERSION = 2;
+
 function now() { return Date.now(); }
 
 function safeAppend(line) {
@@ -24,8 +27,39 @@ function safeAppend(line) {
   }
 }
 
+/**
+ * Write an event line over the transport 3pio chose in THREEPIO_IPC: a file
+ * descriptor ("fd:3"), stderr marked with the 3PIO_EVT: sentinel, or the IPC file.
+ * Returns false to write it to the IPC file.
+ */
+function writeEventToStdio(line) {
+  const transport = process.env.THREEPIO_IPC || '';
+  if (transport.startsWith('fd:')) {
+    try {
+      // Only 3pio's pipe: a launcher like npx may not pass the descriptor down,
+      // leaving the number to an unrelated file
+      const fd = Number(transport.slice(3));
+      if (fs.fstatSync(fd).isFIFO()) {
+        fs.writeSync(fd, line);
+        return true;
+      }
+    } catch (_) {
+      // The descriptor wasn't passed down; fall back to stderr
+    }
+  } else if (transport !== 'stderr') {
+    return false;
+  }
+  try {
+    fs.writeSync(2, '3PIO_EVT:' + line);
+  } catch (_) {
+    // Silent failure - adapters should not write to stdout/stderr otherwise
+  }
+  return true;
+}
+
 function sendEvent(event) {
-  safeAppend(JSON.stringify(event));
+  const line = JSON.stringify({ ...event, schema: SCHEMA_VERSION });
+  if (!writeEventToStdio(line + '\n')) safeAppend(line);
 }
 
 // Basic discovered/start trackers to avoid duplicates
@@ -110,6 +144,7 @@ function ThreePioCypressReporter(runner /*, options */) {
 
   runner.on('start', () => {
     startedAt = now();
+    sendEvent({ eventType: 'adapterHandshake', payload: { adapter: 'cypress', schema: SCHEMA_VERSION } });
   });
 
   runner.on('suite', (suite) => {
@@ -26,16 +26,20 @@ var (
 
 	//go:embed mocha.js
 	mochaAdapter []byte
+
+	//go:embed playwright.js
+	playwrightAdapter []byte
 )
 
-// GetAdapterPath returns the path to an extracted adapter with IPC path and log level injected
-func GetAdapterPath(name string, ipcPath string, runDir string, logLevel string) (string, error) {
+// GetAdapterPath returns the path to an extracted adapter with IPC path and log level
+// injected. projectDir holds the project's package.json; "" for the working directory.
+func GetAdapterPath(name string, ipcPath string, runDir string, logLevel string, projectDir string) (string, error) {
 	// No caching needed since each run gets its own adapter
-	return extractAdapter(name, ipcPath, runDir, logLevel)
+	return extractAdapter(name, ipcPath, runDir, logLevel, projectDir)
 }
 
 // extractAdapter extracts an embedded adapter with IPC path and log level injected
-func extractAdapter(name string, ipcPath string, runDir string, logLevel string) (string, error) {
+func extractAdapter(name string, ipcPath string, runDir string, logLevel string, projectDir string) (string, error) {
 	var content []byte
 	var filename string
 	var isESM bool
@@ -44,7 +48,7 @@ func extractAdapter(name string, ipcPath string, runDir string, logLevel string)
 	case "jest.js":
 		content = jestAdapter
 		// Check if target project is ES module
-		if isProjectESM() {
+		if isProjectESM(projectDir) {
 			filename = "jest.cjs" // Use .cjs extension for ES module projects
 		} else {
 			filename = "jest.js"
@@ -68,6 +72,15 @@ func extractAdapter(name string, ipcPath string, runDir string, logLevel string)
 		// Mocha reporter is CommonJS
 		filename = "mocha.js"
 		isESM = false
+	case "playwright.js":
+		content = playwrightAdapter
+		// Playwright reporter is CommonJS, loaded from a .cjs file in ES module projects
+		if isProjectESM(projectDir) {
+			filename = "playwright.cjs"
+		} else {
+			filename = "playwright.js"
+		}
+		isESM = false
 	default:
 		return "", fmt.Errorf("unknown adapter: %s", name)
 	}
@@ -76,7 +89,7 @@ func extractAdapter(name string, ipcPath string, runDir string, logLevel string)
 	contentStr := string(content)
 
 	// For JavaScript adapters, inject as single-quoted strings for ESLint consistency
-	if name == "vitest.js" || name == "jest.js" || name == "cypress.js" || name == "mocha.js" {
+	if name == "vitest.js" || name == "jest.js" || name == "cypress.js" || name == "mocha.js" || name == "playwright.js" {
 		// Quote using JSON, then convert to single-quoted JS literal
 		jsonQuoted := strconv.Quote(ipcPath)
 		if len(jsonQuoted) >= 2 {
@@ -103,7 +116,7 @@ func extractAdapter(name string, ipcPath string, runDir string, logLevel string)
 
 	// Inject log level into all adapters
 	// For JavaScript adapters, inject log level as single-quoted strings
-	if name == "vitest.js" || name == "jest.js" || name == "cypress.js" || name == "mocha.js" {
+	if name == "vitest.js" || name == "jest.js" || name == "cypress.js" || name == "mocha.js" || name == "playwright.js" {
 		jsonQuoted := strconv.Quote(logLevel)
 		if len(jsonQuoted) >= 2 {
 			jsonQuoted = jsonQuoted[1 : len(jsonQuoted)-1]
@@ -165,10 +178,10 @@ func extractAdapter(name string, ipcPath string, runDir string, logLevel string)
 	return absPath, nil
 }
 
-// isProjectESM checks if the current project is configured as an ES module
-func isProjectESM() bool {
+// isProjectESM checks if the project in dir is configured as an ES module
+func isProjectESM(dir string) bool {
 	// Check if package.json exists and has "type": "module"
-	packagePath := "package.json"
+	packagePath := filepath.Join(dir, "package.json")
 	if _, err := os.Stat(packagePath); err != nil {
 		return false // No package.json found
 	}
@@ -4,10 +4,14 @@ import (
 	"fmt"
 	"os"
 	"path/filepath"
+	"regexp"
 	"runtime"
+	"strconv"
 	"strings"
 	"testing"
 	"time"
+
+	"github.com/zk/3pio/internal/ipc"
 )
 
 func TestGetAdapterPath_IPCPathInjection(t *testing.T) {
@@ -128,7 +132,7 @@ func TestGetAdapterPath_IPCPathInjection(t *testing.T) {
 			defer func() { _ = os.RemoveAll(tt.runDir) }()
 
 			// Call GetAdapterPath with IPC path, run directory, and log level
-			path, err := GetAdapterPath(tt.adapterName, tt.ipcPath, tt.runDir, "WARN")
+			path, err := GetAdapterPath(tt.adapterName, tt.ipcPath, tt.runDir, "WARN", "")
 
 			// Check error expectation
 			if (err != nil) != tt.wantErr {
@@ -176,13 +180,13 @@ func TestGetAdapterPath_UniqueAdaptersPerRun(t *testing.T) {
 	defer func() { _ = os.RemoveAll(runDir2) }()
 
 	// Get adapter for first run
-	path1, err := GetAdapterPath("jest.js", ipcPath1, runDir1, "WARN")
+	path1, err := GetAdapterPath("jest.js", ipcPath1, runDir1, "WARN", "")
 	if err != nil {
 		t.Fatalf("Failed to get adapter for run1: %v", err)
 	}
 
 	// Get adapter for second run
-	path2, err := GetAdapterPath("jest.js", ipcPath2, runDir2, "WARN")
+	path2, err := GetAdapterPath("jest.js", ipcPath2, runDir2, "WARN", "")
 	if err != nil {
 		t.Fatalf("Failed to get adapter for run2: %v", err)
 	}
@@ -222,7 +226,7 @@ func TestGetAdapterPath_ESMHandling(t *testing.T) {
 	defer func() { _ = os.RemoveAll(runDir) }()
 
 	// Test Vitest adapter (ESM)
-	path, err := GetAdapterPath("vitest.js", ipcPath, runDir, "WARN")
+	path, err := GetAdapterPath("vitest.js", ipcPath, runDir, "WARN", "")
 	if err != nil {
 		t.Fatalf("Failed to get Vitest adapter: %v", err)
 	}
@@ -247,7 +251,7 @@ func TestGetAdapterPath_PythonExecutable(t *testing.T) {
 	defer func() { _ = os.RemoveAll(runDir) }()
 
 	// Test Python adapter
-	path, err := GetAdapterPath("pytest_adapter.py", ipcPath, runDir, "WARN")
+	path, err := GetAdapterPath("pytest_adapter.py", ipcPath, runDir, "WARN", "")
 	if err != nil {
 		t.Fatalf("Failed to get Python adapter: %v", err)
 	}
@@ -347,7 +351,7 @@ func TestGetAdapterPath_LogLevelInjection(t *testing.T) {
 			defer func() { _ = os.RemoveAll(runDir) }()
 
 			// Call GetAdapterPath with log level
-			path, err := GetAdapterPath(tt.adapterName, ipcPath, runDir, tt.logLevel)
+			path, err := GetAdapterPath(tt.adapterName, ipcPath, runDir, tt.logLevel, "")
 
 			// Check error expectation
 			if (err != nil) != tt.wantErr {
@@ -375,3 +379,27 @@ func TestGetAdapterPath_LogLevelInjection(t *testing.T) {
 		})
 	}
 }
+
+func TestAdapters_WriteCurrentSchema(t *testing.T) {
+	schemaPattern := regexp.MustCompile(`SCHEMA_VERSION = (\d+)`)
+	for name, content := range map[string][]byte{
+		"jest.js":           jestAdapter,
+		"vitest.js":         vitestAdapter,
+		"pytest_adapter.py": pytestAdapter,
+		"cypress.js":        cypressAdapter,
+		"mocha.js":          mochaAdapter,
+		"playwright.js":     playwrightAdapter,
+	} {
+		match := schemaPattern.FindSubmatch(content)
+		if match == nil {
+			t.Errorf("%s doesn't declare SCHEMA_VERSION", name)
+			continue
+		}
+		if schema, _ := strconv.Atoi(string(match[1])); schema != ipc.SchemaVersion {
+			t.Errorf("%s writes schema %d, want ipc.SchemaVersion %d", name, schema, ipc.SchemaVersion)
+		}
+		if !strings.Contains(string(content), "adapterHandshake") {
+			t.Errorf("%s doesn't send the adapter handshake", name)
+		}
+	}
+}
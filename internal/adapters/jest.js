@@ -12,11 +12,167 @@ const IPC_PATH = /*__IPC_PATH__*/"WILL_BE_REPLACED"/*__IPC_PATH__*/;
 // Log level will be replaced at runtime
 const LOG_LEVEL = /*__LOG_LEVEL__*/"WARN"/*__LOG_LEVEL__*/;
 
+// IPC event schema this adapter writes, checked by 3pio (ipc.SchemaVersion)
+const SCHEMA_VERSION = 2;
+
 // Track discovered groups to avoid duplicates
 const discoveredGroups = new Map();
 const groupStarts = new Map();
 const fileGroups = new Map();
 
+/**
+ * Path of a test file relative to the project, as reports and annotations show it
+ */
+function relativePath(filePath) {
+  return path.relative(process.cwd(), filePath).split(path.sep).join('/');
+}
+
+/**
+ * Source location of a test for its event: Jest's location of the test definition
+ * (from --testLocationInResults, null for tests it can't place, e.g. generated
+ * ones) and the file
+ */
+function testLocation(filePath, testCaseResult) {
+  const location = { file: relativePath(filePath) };
+  if (testCaseResult.location && testCaseResult.location.line) {
+    location.line = testCaseResult.location.line;
+    if (testCaseResult.location.column) location.column = testCaseResult.location.column;
+  }
+  return location;
+}
+
+/**
+ * Where a test failed: the first frame of a failure message's stack in the test file
+ */
+function failureLocation(message, filePath) {
+  const plain = message.replace(/\u001b\[[0-9;]*m/g, '');
+  for (const line of plain.split('\n')) {
+    const match = /^\s*at (?:.*? \()?(.+?):(\d+):(\d+)\)?$/.exec(line);
+    if (match && match[1] === filePath) {
+      return { line: Number(match[2]), column: Number(match[3]) };
+    }
+  }
+  return null;
+}
+
+/**
+ * Offset of the parenthesis closing the first one at or after from, skipping
+ * strings, template literals and comments, or -1
+ */
+function closingParen(source, from) {
+  let i = source.indexOf('(', from);
+  if (i < 0) return -1;
+  const stack = [];
+  for (; i < source.length; i++) {
+    const c = source[i];
+    if (stack[stack.length - 1] === '`') {
+      if (c === '\\') i++;
+      else if (c === '`') stack.pop();
+      else if (c === '$' && source[i + 1] === '{') { stack.push('{'); i++; }
+      continue;
+    }
+    if (c === '/' && source[i + 1] === '/') {
+      i = source.indexOf('\n', i);
+      if (i < 0) return -1;
+    } else if (c === '/' && source[i + 1] === '*') {
+      i = source.indexOf('*/', i + 2);
+      if (i < 0) return -1;
+      i++;
+    } else if (c === '\'' || c === '"') {
+      for (i++; i < source.length && source[i] !== c && source[i] !== '\n'; i++) {
+        if (source[i] === '\\') i++;
+      }
+    } else if (c === '`' || c === '(' || c === '[' || c === '{') {
+      stack.push(c);
+    } else if (c === ')' || c === ']' || c === '}') {
+      stack.pop();
+      if (stack.length === 0) return i;
+    }
+  }
+  return -1;
+}
+
+/**
+ * Lines each test of a file spans, from its location to the parenthesis closing
+ * the test call. Jest only says where a console call was made, not in which test.
+ */
+function testLineRanges(filePath, testResults) {
+  let source;
+  try {
+    source = fs.readFileSync(filePath, 'utf8');
+  } catch (_) {
+    return [];
+  }
+  const lineStarts = [0];
+  for (let i = 0; i < source.length; i++) {
+    if (source[i] === '\n') lineStarts.push(i + 1);
+  }
+
+  const ranges = [];
+  for (const testCase of testResults || []) {
+    const location = testCase.location;
+    if (!location || !location.line || location.line > lineStarts.length) continue;
+    const end = closingParen(source, lineStarts[location.line - 1] + Math.max((location.column || 1) - 1, 0));
+    if (end < 0) continue;
+    let last = location.line;
+    while (last < lineStarts.length && lineStarts[last] <= end) last++;
+    ranges.push({ testCase, first: location.line, last });
+  }
+  return ranges;
+}
+
+/**
+ * The test a console entry was logged by: the test whose lines hold the first frame
+ * of its stack in the test file that lies inside exactly one test. Null for output
+ * outside any test (module scope, beforeAll) and from tests sharing their lines,
+ * like those of test.each.
+ */
+function consoleOwner(origin, filePath, ranges) {
+  for (const line of String(origin || '').split('\n')) {
+    const match = /^\s*at (?:.*? \()?(.+?):(\d+):(\d+)\)?$/.exec(line);
+    if (!match || match[1] !== filePath) continue;
+    const lineNumber = Number(match[2]);
+    const owners = ranges.filter((r) => r.first <= lineNumber && lineNumber <= r.last);
+    if (owners.length === 1) return owners[0].testCase;
+    if (owners.length > 1) return null;
+  }
+  return null;
+}
+
+/**
+ * A console entry as Jest prints it without buffering (verbose, e.g. a run of one
+ * file): "  console.log\n    message\n\n      at ...". Returns its level and message,
+ * or null for other output.
+ */
+function parsePrintedConsole(chunk) {
+  const plain = chunk.replace(/\u001b\[[0-9;]*m/g, '');
+  const match = /^\s*console\.(\w+)\n([\s\S]*?)(?:\n+\s+at [\s\S]*)?\n*$/.exec(plain);
+  if (!match) return null;
+  return { level: match[1], message: match[2].replace(/^ {4}/gm, '') };
+}
+
+/**
+ * Send a console entry as output of the test that logged it, or of the file's
+ * setup when no test did. The level (log, warn, error, ...) is kept with it.
+ */
+function sendConsoleEntry(filePath, owner, level, message) {
+  const payload = { groupName: filePath, parentNames: [], level, chunk: `${message}\n` };
+  if (owner) {
+    const hierarchy = buildHierarchy(filePath, owner.ancestorTitles);
+    payload.groupName = hierarchy[hierarchy.length - 1];
+    payload.parentNames = hierarchy.slice(0, -1);
+    payload.testName = owner.title;
+  }
+  sendEvent({
+    eventType: level === 'error' ? 'groupStderr' : 'groupStdout',
+    payload
+  });
+}
+
+function caseId(filePath, testCase) {
+  return `${filePath}:${(testCase.ancestorTitles || []).join(':')}:${testCase.title}`;
+}
+
 /**
  * Build hierarchy from file path and ancestor titles
  */
@@ -35,16 +191,49 @@ function getGroupId(hierarchy) {
   return hierarchy.join(':');
 }
 
+/**
+ * Write an event line over the transport 3pio chose in THREEPIO_IPC: a file
+ * descriptor ("fd:3"), stderr marked with the 3PIO_EVT: sentinel, or the IPC file.
+ * Returns false to write it to the IPC file.
+ */
+function writeEventToStdio(line) {
+  const transport = process.env.THREEPIO_IPC || '';
+  if (transport.startsWith('fd:')) {
+    try {
+      // Only 3pio's pipe: a launcher like npx may not pass the descriptor down,
+      // leaving the number to an unrelated file
+      const fd = Number(transport.slice(3));
+      if (fs.fstatSync(fd).isFIFO()) {
+        fs.writeSync(fd, line);
+        return true;
+      }
+    } catch (_) {
+      // The descriptor wasn't passed down; fall back to stderr
+    }
+  } else if (transport !== 'stderr') {
+    return false;
+  }
+  try {
+    fs.writeSync(2, '3PIO_EVT:' + line);
+  } catch (_) {
+    // Silent failure - adapters should not write to stdout/stderr otherwise
+  }
+  return true;
+}
+
 /**
  * Send event to IPC file
  */
 function sendEvent(event) {
   try {
+    const line = JSON.stringify({ ...event, schema: SCHEMA_VERSION }) + '\n';
+    if (writeEventToStdio(line)) {
+      return;
+    }
     const dir = path.dirname(IPC_PATH);
     if (!fs.existsSync(dir)) {
       fs.mkdirSync(dir, { recursive: true });
     }
-    const line = JSON.stringify(event) + '\n';
     fs.appendFileSync(IPC_PATH, line);
   } catch (error) {
     // Silent failure - adapters should not write to stdout/stderr
@@ -128,6 +317,7 @@ class ThreePioJestReporter {
   currentTestFile = null;
   captureEnabled = false;
   testSuiteStats = new Map(); // Track stats per test suite
+  runningTests = new Map(); // id -> { path, ancestorTitles, title } between case start and result
 
   constructor() {
     this.originalStdoutWrite = process.stdout.write.bind(process.stdout);
@@ -135,6 +325,12 @@ class ThreePioJestReporter {
   }
 
   onRunStart() {
+    // Tell 3pio which schema this adapter writes
+    sendEvent({
+      eventType: 'adapterHandshake',
+      payload: { adapter: 'jest', schema: SCHEMA_VERSION }
+    });
+
     // Collection phase for Jest (Jest doesn't have separate collection)
     sendEvent({
       eventType: 'collectionStart',
@@ -163,6 +359,12 @@ class ThreePioJestReporter {
 
   onTestCaseStart(test, testCaseStartInfo) {
     if (testCaseStartInfo?.ancestorTitles && testCaseStartInfo?.title) {
+      this.runningTests.set(caseId(test.path, testCaseStartInfo), {
+        path: test.path,
+        ancestorTitles: testCaseStartInfo.ancestorTitles,
+        title: testCaseStartInfo.title
+      });
+
       // Ensure all parent groups are discovered
       ensureGroupsDiscovered(test.path, testCaseStartInfo.ancestorTitles);
       
@@ -178,6 +380,7 @@ class ThreePioJestReporter {
 
   onTestCaseResult(test, testCaseResult) {
     if (testCaseResult) {
+      this.runningTests.delete(caseId(test.path, testCaseResult));
       const parentNames = [test.path, ...(testCaseResult.ancestorTitles || [])];
       const testName = testCaseResult.title;
       
@@ -195,7 +398,8 @@ class ThreePioJestReporter {
         testName: testName,
         parentNames: parentNames,
         status: status,
-        duration: testCaseResult.duration
+        duration: testCaseResult.duration,
+        ...testLocation(test.path, testCaseResult)
       };
 
       // Only include error if it exists
@@ -203,6 +407,13 @@ class ThreePioJestReporter {
         payload.error = {
           message: error
         };
+        const failedAt = failureLocation(error, test.path);
+        if (failedAt) {
+          payload.error.file = payload.file;
+          payload.error.line = failedAt.line;
+          payload.error.column = failedAt.column;
+          payload.error.location = `${payload.file}:${failedAt.line}:${failedAt.column}`;
+        }
       }
 
       sendEvent({
@@ -225,18 +436,11 @@ class ThreePioJestReporter {
   onTestResult(test, testResult, aggregatedResult) {
     this.stopCapture();
     
-    // Send console output as group output
+    // Buffered console output, as output of the test that logged each entry
     if (testResult.console && testResult.console.length > 0) {
+      const ranges = testLineRanges(test.path, testResult.testResults);
       for (const log of testResult.console) {
-        const chunk = `${log.message}\n`;
-        sendEvent({
-          eventType: log.type === 'error' ? 'groupStderr' : 'groupStdout',
-          payload: {
-            groupName: test.path,
-            parentNames: [],
-            chunk: chunk
-          }
-        });
+        sendConsoleEntry(test.path, consoleOwner(log.origin, test.path, ranges), log.type, log.message);
       }
     }
     
@@ -280,7 +484,8 @@ class ThreePioJestReporter {
               testName: testCase.title,
               parentNames: parentNames,
               status: status,
-              duration: testCase.duration || 0
+              duration: testCase.duration || 0,
+              ...testLocation(test.path, testCase)
             }
           });
         }
@@ -389,6 +594,7 @@ class ThreePioJestReporter {
     
     process.stdout.write = (chunk, ...args) => {
       const chunkStr = chunk.toString();
+      if (this.sendRunningTestConsole(chunkStr)) return true;
       if (this.currentTestFile) {
         sendEvent({
           eventType: 'groupStdout',
@@ -404,6 +610,7 @@ class ThreePioJestReporter {
     
     process.stderr.write = (chunk, ...args) => {
       const chunkStr = chunk.toString();
+      if (this.sendRunningTestConsole(chunkStr)) return true;
       if (this.currentTestFile) {
         sendEvent({
           eventType: 'groupStderr',
@@ -418,6 +625,19 @@ class ThreePioJestReporter {
     };
   }
 
+  /**
+   * Console entries Jest prints as they happen (unbuffered, in band) belong to the
+   * running test when only one is. Returns whether the chunk was sent.
+   */
+  sendRunningTestConsole(chunk) {
+    if (this.runningTests.size !== 1) return false;
+    const entry = parsePrintedConsole(chunk);
+    if (!entry) return false;
+    const running = this.runningTests.values().next().value;
+    sendConsoleEntry(running.path, running, entry.level, entry.message);
+    return true;
+  }
+
   stopCapture() {
     if (!this.captureEnabled) return;
     this.captureEnabled = false;
@@ -1,17 +1,23 @@
 /**
  * 3pio Mocha Adapter (Custom Reporter)
- * Emits hierarchical group/test events to THREEPIO_IPC_PATH.
+ * Emits hierarchical group/test events to THREEPIO_IPC_PATH: each spec file and
+ * its describe blocks, with pending tests skipped and this.timeout() overruns
+ * TIMED_OUT.
  * Silent by design: no stdout/stderr logs.
  */
 
 /* eslint-disable */
 const fs = require('fs');
 const path = require('path');
+const util = require('util');
 
 // Runtime-injected values from Go embedder
 const IPC_PATH = /*__IPC_PATH__*/"WILL_BE_REPLACED"/*__IPC_PATH__*/;
 const LOG_LEVEL = /*__LOG_LEVEL__*/"WARN"/*__LOG_LEVEL__*/;
 
+// IPC event schema this adapter writes, checked by 3pio (ipc.SchemaVersion)
+const SCHEMA_VERSION = 2;
+
 function now() { return Date.now(); }
 
 function safeAppend(line) {
@@ -24,181 +30,198 @@ function safeAppend(line) {
   }
 }
 
-function sendEvent(event) {
-  safeAppend(JSON.stringify(event));
+/**
+ * Write an event line over the transport 3pio chose in THREEPIO_IPC: a file
+ * descriptor ("fd:3"), stderr marked with the 3PIO_EVT: sentinel, or the IPC file.
+ * Returns false to write it to the IPC file.
+ */
+function writeEventToStdio(line) {
+  const transport = process.env.THREEPIO_IPC || '';
+  if (transport.startsWith('fd:')) {
+    try {
+      // Only 3pio's pipe: a launcher like npx may not pass the descriptor down,
+      // leaving the number to an unrelated file
+      const fd = Number(transport.slice(3));
+      if (fs.fstatSync(fd).isFIFO()) {
+        fs.writeSync(fd, line);
+        return true;
+      }
+    } catch (_) {
+      // The descriptor wasn't passed down; fall back to stderr
+    }
+  } else if (transport !== 'stderr') {
+    return false;
+  }
+  try {
+    fs.writeSync(2, '3PIO_EVT:' + line);
+  } catch (_) {
+    // Silent failure - adapters should not write to stdout/stderr otherwise
+  }
+  return true;
 }
 
-// Basic discovered/start trackers to avoid duplicates
-const discovered = new Set();
-const started = new Set();
+function sendEvent(event) {
+  const line = JSON.stringify({ ...event, schema: SCHEMA_VERSION });
+  if (!writeEventToStdio(line + '\n')) safeAppend(line);
+}
 
 function groupId(hierarchy) { return hierarchy.join(':'); }
 
-function discoverHierarchy(filePath, suiteChain) {
-  const groups = [];
-  groups.push({ name: filePath, parentNames: [] });
-  if (suiteChain && suiteChain.length > 0) {
-    for (let i = 0; i < suiteChain.length; i++) {
-      const parent = [filePath, ...suiteChain.slice(0, i)];
-      groups.push({ name: suiteChain[i], parentNames: parent });
-    }
+// Spec file of a test or suite; tests outside any describe block belong to the root
+// suite, which has no file of its own
+function fileOf(node) {
+  for (let n = node; n; n = n.parent) {
+    if (n.file) return n.file;
   }
-  return groups;
+  return 'unknown.spec';
+}
+
+// Titles of the describe blocks around a test or suite, outermost first
+function suiteChain(node) {
+  const chain = [];
+  for (let n = node.parent; n && !n.root; n = n.parent) {
+    if (n.title) chain.unshift(n.title);
+  }
+  return chain;
+}
+
+// Mocha fails a test or hook that outlives this.timeout() with this error
+function isTimeout(err) {
+  return Boolean(err) && (err.code === 'ERR_MOCHA_TIMEOUT' || /^Timeout of \d+ms exceeded/.test(String(err.message || '')));
+}
+
+function valueText(value) {
+  return typeof value === 'string' ? value : util.inspect(value, { depth: 4 });
 }
 
-function ensureDiscovered(filePath, suiteChain) {
-  const groups = discoverHierarchy(filePath, suiteChain);
-  for (const g of groups) {
-    const id = groupId([...g.parentNames, g.name]);
-    if (!discovered.has(id)) {
+// Mocha reporter API
+function ThreePioMochaReporter(runner /*, options */) {
+  const discovered = new Set();
+  const groups = new Map(); // id -> { name, parentNames, startedAt, passed, failed, skipped }
+
+  function ensureDiscovered(hierarchy) {
+    for (let i = 0; i < hierarchy.length; i++) {
+      const id = groupId(hierarchy.slice(0, i + 1));
+      if (discovered.has(id)) continue;
       discovered.add(id);
       sendEvent({
         eventType: 'testGroupDiscovered',
-        payload: { groupName: g.name, parentNames: g.parentNames }
+        payload: { groupName: hierarchy[i], parentNames: hierarchy.slice(0, i) },
+      });
+    }
+  }
+
+  function ensureStarted(hierarchy) {
+    ensureDiscovered(hierarchy);
+    for (let i = 0; i < hierarchy.length; i++) {
+      const id = groupId(hierarchy.slice(0, i + 1));
+      if (groups.has(id)) continue;
+      groups.set(id, {
+        name: hierarchy[i],
+        parentNames: hierarchy.slice(0, i),
+        startedAt: now(),
+        passed: 0,
+        failed: 0,
+        skipped: 0,
+        finished: false,
+      });
+      sendEvent({
+        eventType: 'testGroupStart',
+        payload: { groupName: hierarchy[i], parentNames: hierarchy.slice(0, i) },
       });
     }
   }
-}
 
-function ensureStarted(filePath, suiteChain) {
-  const id = groupId([...(suiteChain ? [filePath, ...suiteChain] : [filePath])]);
-  if (!started.has(id)) {
-    started.add(id);
-    const name = suiteChain && suiteChain.length > 0 ? suiteChain[suiteChain.length - 1] : filePath;
-    const parentNames = suiteChain && suiteChain.length > 0 ? [filePath, ...suiteChain.slice(0, -1)] : [];
+  function finishGroup(g) {
+    if (g.finished) return;
+    g.finished = true;
+    const total = g.passed + g.failed + g.skipped;
+    const status = g.failed > 0 ? 'FAIL' : (g.passed > 0 || total === 0 ? 'PASS' : 'SKIP');
     sendEvent({
-      eventType: 'testGroupStart',
-      payload: { groupName: name, parentNames }
+      eventType: 'testGroupResult',
+      payload: {
+        groupName: g.name,
+        parentNames: g.parentNames,
+        status,
+        duration: now() - g.startedAt,
+        totals: { passed: g.passed, failed: g.failed, skipped: g.skipped, total },
+      },
     });
   }
-}
 
-// Mocha reporter API
-function ThreePioMochaReporter(runner /*, options */) {
-  // Track per-spec statistics
-  let specFile = null;
-  let startedAt = 0;
-  let passed = 0, failed = 0, skipped = 0;
-
-  function resolveSpecFrom(obj) {
-    if (!obj) return null;
-    // Mocha commonly exposes file on test/suite
-    if (obj.file) return obj.file;
-    if (obj.parent && obj.parent.file) return obj.parent.file;
-    // Fallbacks
-    if (obj.parent && obj.parent.root && obj.parent.suites && obj.parent.suites[0] && obj.parent.suites[0].file) {
-      return obj.parent.suites[0].file;
+  function testError(err) {
+    const timedOut = isTimeout(err);
+    const error = {
+      message: String(err && (err.message || err)) || 'Error',
+      stack: String((err && err.stack) || '').split('\n').filter((l) => /^\s+at /.test(l)).map((l) => l.trim()).join('\n'),
+      errorType: timedOut ? 'TIMED_OUT' : ((err && err.name) || 'Error'),
+    };
+    if (timedOut) error.failureKind = 'TIMED_OUT';
+    // Values of a failed assert or chai expect
+    if (err && err.showDiff !== false && err.expected !== undefined && err.actual !== undefined) {
+      error.expected = valueText(err.expected);
+      error.actual = valueText(err.actual);
     }
-    return null;
+    return error;
   }
 
-  function toChain(testOrSuite) {
-    const chain = [];
-    let node = testOrSuite.parent; // Exclude the test/suite itself
-    while (node && !node.root) {
-      if (node.title) chain.unshift(node.title);
-      node = node.parent;
-    }
-    return chain;
-  }
+  function emitTestCase(test, status, err) {
+    const hierarchy = [fileOf(test), ...suiteChain(test)];
+    ensureStarted(hierarchy);
 
-  function statusFrom(type) {
-    if (type === 'pass') return 'PASS';
-    if (type === 'fail') return 'FAIL';
-    if (type === 'pending') return 'SKIP';
-    return 'PENDING';
+    const payload = {
+      testName: test.title || 'Unnamed test',
+      parentNames: hierarchy,
+      status,
+      duration: typeof test.duration === 'number' ? test.duration : 0,
+    };
+    if (err) payload.error = testError(err);
+
+    for (let i = 0; i < hierarchy.length; i++) {
+      const g = groups.get(groupId(hierarchy.slice(0, i + 1)));
+      if (status === 'FAIL') g.failed++;
+      else if (status === 'SKIP') g.skipped++;
+      else g.passed++;
+    }
+    sendEvent({ eventType: 'testCase', payload });
   }
 
   runner.on('start', () => {
-    startedAt = now();
+    sendEvent({ eventType: 'adapterHandshake', payload: { adapter: 'mocha', schema: SCHEMA_VERSION } });
   });
 
   runner.on('suite', (suite) => {
-    // Ignore root suite (empty title)
+    // The root suite, which has no title, isn't a group
     if (!suite || !suite.title) return;
-    if (!specFile) specFile = resolveSpecFrom(suite) || specFile;
-    const chain = toChain(suite);
-    if (specFile) {
-      ensureDiscovered(specFile, chain.concat([suite.title]));
-      ensureStarted(specFile, chain.concat([suite.title]));
-    }
-  });
-
-  runner.on('test', (test) => {
-    if (!specFile) specFile = resolveSpecFrom(test) || specFile;
-    const chain = toChain(test); // excludes the test title itself
-    if (specFile) {
-      ensureDiscovered(specFile, chain);
-      ensureStarted(specFile, []); // ensure file-level started for RUNNING
-    }
+    ensureStarted([fileOf(suite), ...suiteChain(suite), suite.title]);
   });
 
-  runner.on('pass', (test) => {
-    passed++;
-    emitTestCase(test, 'pass');
-  });
-
-  runner.on('fail', (test, err) => {
-    failed++;
-    emitTestCase(test, 'fail', err);
+  runner.on('suite end', (suite) => {
+    if (!suite || !suite.title) return;
+    const g = groups.get(groupId([fileOf(suite), ...suiteChain(suite), suite.title]));
+    if (g) finishGroup(g);
   });
 
-  runner.on('pending', (test) => {
-    skipped++;
-    emitTestCase(test, 'pending');
+  runner.on('test', (test) => {
+    ensureStarted([fileOf(test), ...suiteChain(test)]);
   });
 
-  function emitTestCase(test, kind, err) {
-    if (!specFile) specFile = resolveSpecFrom(test) || 'unknown.spec';
-    const chain = toChain(test);
-    const duration = typeof test.duration === 'number' ? test.duration : 0;
+  runner.on('pass', (test) => emitTestCase(test, 'PASS'));
 
-    // Ensure discovery for all parent groups
-    ensureDiscovered(specFile, chain);
+  // Tests and hooks: a failed hook is reported as a test named after it, e.g.
+  // "before each" hook for "adds an item"
+  runner.on('fail', (test, err) => emitTestCase(test, 'FAIL', err));
 
-    const payload = {
-      testName: test.title || 'Unnamed test',
-      parentNames: [specFile, ...chain],
-      status: statusFrom(kind),
-      duration,
-    };
-    if (err) {
-      payload.error = {
-        message: String(err && (err.message || err)) || 'Error',
-        stack: (err && err.stack) || '',
-        errorType: (err && err.name) || 'Error',
-      };
-    }
-
-    sendEvent({ eventType: 'testCase', payload });
-  }
+  // it.skip, this.skip() and tests without a function
+  runner.on('pending', (test) => emitTestCase(test, 'SKIP'));
 
   runner.once('end', () => {
-    const endedAt = now();
-    const total = passed + failed + skipped;
-    const status = failed > 0 ? 'FAIL' : (passed > 0 && failed === 0 ? 'PASS' : (skipped > 0 ? 'SKIP' : 'PASS'));
-    const duration = endedAt - startedAt;
-
-    const file = specFile || 'unknown.spec';
-    // Ensure file group at least
-    ensureDiscovered(file, []);
-    ensureStarted(file, []);
-
-    sendEvent({
-      eventType: 'testGroupResult',
-      payload: {
-        groupName: file,
-        parentNames: [],
-        status,
-        duration,
-        totals: { passed, failed, skipped, total },
-      },
-    });
-
-    // End of run (optional, used by manager)
+    // The file groups, once all their describe blocks finished
+    for (const g of groups.values()) {
+      if (g.parentNames.length === 0) finishGroup(g);
+    }
     sendEvent({ eventType: 'runComplete', payload: {} });
   });
 }
 
 module.exports = ThreePioMochaReporter;
-
@@ -0,0 +1,336 @@
+/**
+ * 3pio Playwright Adapter (Playwright Test reporter)
+ * Emits hierarchical group/test events to THREEPIO_IPC_PATH: the project, then
+ * the spec file and its describe blocks. Retries are reported as the attempts of a
+ * single test case.
+ * Silent by design: Playwright keeps its own line reporter for the console.
+ */
+
+/* eslint-disable */
+const fs = require('fs');
+const path = require('path');
+
+// Runtime-injected values from Go embedder
+const IPC_PATH = /*__IPC_PATH__*/"WILL_BE_REPLACED"/*__IPC_PATH__*/;
+const LOG_LEVEL = /*__LOG_LEVEL__*/"WARN"/*__LOG_LEVEL__*/;
+
+// IPC event schema this adapter writes, checked by 3pio (ipc.SchemaVersion)
+const SCHEMA_VERSION = 2;
+
+function now() { return Date.now(); }
+
+function safeAppend(line) {
+  try {
+    const dir = path.dirname(IPC_PATH);
+    if (!fs.existsSync(dir)) fs.mkdirSync(dir, { recursive: true });
+    fs.appendFileSync(IPC_PATH, line + '\n');
+  } catch (_) {
+    // intentionally silent
+  }
+}
+
+/**
+ * Write an event line over the transport 3pio chose in THREEPIO_IPC: a file
+ * descriptor ("fd:3"), stderr marked with the 3PIO_EVT: sentinel, or the IPC file.
+ * Returns false to write it to the IPC file.
+ */
+function writeEventToStdio(line) {
+  const transport = process.env.THREEPIO_IPC || '';
+  if (transport.startsWith('fd:')) {
+    try {
+      // Only 3pio's pipe: a launcher like npx may not pass the descriptor down,
+      // leaving the number to an unrelated file
+      const fd = Number(transport.slice(3));
+      if (fs.fstatSync(fd).isFIFO()) {
+        fs.writeSync(fd, line);
+        return true;
+      }
+    } catch (_) {
+      // The descriptor wasn't passed down; fall back to stderr
+    }
+  } else if (transport !== 'stderr') {
+    return false;
+  }
+  try {
+    fs.writeSync(2, '3PIO_EVT:' + line);
+  } catch (_) {
+    // Silent failure - adapters should not write to stdout/stderr otherwise
+  }
+  return true;
+}
+
+function sendEvent(event) {
+  const line = JSON.stringify({ ...event, schema: SCHEMA_VERSION });
+  if (!writeEventToStdio(line + '\n')) safeAppend(line);
+}
+
+// Playwright colors its messages, snippets and diffs even when not on a terminal
+const ANSI_PATTERN = /\u001b\[[0-9;]*m/g;
+
+function stripAnsi(text) { return String(text || '').replace(ANSI_PATTERN, ''); }
+
+function groupId(hierarchy) { return hierarchy.join(':'); }
+
+// Status of a single run of a test
+function attemptStatus(result) {
+  switch (result.status) {
+    case 'passed': return 'PASS';
+    case 'failed':
+    case 'timedOut': return 'FAIL';
+    default: return 'SKIP'; // skipped, interrupted
+  }
+}
+
+function chunkText(chunk) {
+  return typeof chunk === 'string' ? chunk : Buffer.from(chunk).toString('utf8');
+}
+
+class ThreePioPlaywrightReporter {
+  constructor() {
+    this.discovered = new Set();
+    this.groups = new Map(); // id -> { name, parentNames, startedAt, passed, failed, skipped }
+    this.output = new Map(); // TestResult -> { stdout, stderr }
+    this.reported = new Set();
+    this.tests = [];
+  }
+
+  // Playwright adds its own line or dot reporter when no reporter prints, so the
+  // console keeps showing progress
+  printsToStdio() { return false; }
+
+  onBegin(config, suite) {
+    sendEvent({ eventType: 'adapterHandshake', payload: { adapter: 'playwright', schema: SCHEMA_VERSION } });
+    this.tests = suite.allTests();
+    for (const test of this.tests) this.ensureDiscovered(this.hierarchy(test));
+  }
+
+  onTestBegin(test) {
+    this.ensureStarted(this.hierarchy(test));
+  }
+
+  onStdOut(chunk, test, result) { this.appendOutput(result, 'stdout', chunk); }
+
+  onStdErr(chunk, test, result) { this.appendOutput(result, 'stderr', chunk); }
+
+  onTestEnd(test, result) {
+    // A failed attempt Playwright is about to retry isn't the test's result yet
+    if (result.status !== test.expectedStatus && result.status !== 'skipped' &&
+        result.status !== 'interrupted' && result.retry < test.retries) {
+      return;
+    }
+    this.reportTest(test);
+  }
+
+  onError(error) {
+    // Errors outside a test, e.g. a spec file that doesn't load
+    const file = error.location && error.location.file;
+    if (!file) return;
+    sendEvent({
+      eventType: 'testGroupError',
+      payload: {
+        groupName: file,
+        parentNames: [],
+        errorType: 'COLLECTION_FAILURE',
+        error: { message: stripAnsi(error.message || error.value || 'Error'), phase: 'collection' },
+      },
+    });
+  }
+
+  onEnd() {
+    // Tests the run stopped before their last attempt, e.g. after --max-failures
+    for (const test of this.tests) {
+      if (test.results.length > 0) this.reportTest(test);
+    }
+
+    // Innermost groups first, so describe blocks finish before their file
+    const groups = [...this.groups.values()].sort((a, b) => b.parentNames.length - a.parentNames.length);
+    for (const g of groups) {
+      const total = g.passed + g.failed + g.skipped;
+      const status = g.failed > 0 ? 'FAIL' : (g.passed > 0 ? 'PASS' : 'SKIP');
+      sendEvent({
+        eventType: 'testGroupResult',
+        payload: {
+          groupName: g.name,
+          parentNames: g.parentNames,
+          status,
+          duration: g.startedAt ? now() - g.startedAt : 0,
+          totals: { passed: g.passed, failed: g.failed, skipped: g.skipped, total },
+        },
+      });
+    }
+
+    sendEvent({ eventType: 'runComplete', payload: {} });
+  }
+
+  /**
+   * Groups of a test, outermost first: its project (when the config names one),
+   * its spec file and its describe blocks.
+   */
+  hierarchy(test) {
+    const describes = [];
+    let project = '';
+    for (let suite = test.parent; suite; suite = suite.parent) {
+      if (suite.type === 'describe') describes.unshift(suite.title);
+      if (suite.type === 'project') project = suite.title;
+    }
+    const groups = project ? [project] : [];
+    groups.push(test.location.file, ...describes);
+    return groups;
+  }
+
+  ensureDiscovered(hierarchy) {
+    for (let i = 0; i < hierarchy.length; i++) {
+      const id = groupId(hierarchy.slice(0, i + 1));
+      if (this.discovered.has(id)) continue;
+      this.discovered.add(id);
+      sendEvent({
+        eventType: 'testGroupDiscovered',
+        payload: { groupName: hierarchy[i], parentNames: hierarchy.slice(0, i) },
+      });
+    }
+  }
+
+  ensureStarted(hierarchy) {
+    this.ensureDiscovered(hierarchy);
+    for (let i = 0; i < hierarchy.length; i++) {
+      const id = groupId(hierarchy.slice(0, i + 1));
+      if (this.groups.has(id)) continue;
+      this.groups.set(id, {
+        name: hierarchy[i],
+        parentNames: hierarchy.slice(0, i),
+        startedAt: now(),
+        passed: 0,
+        failed: 0,
+        skipped: 0,
+      });
+      sendEvent({
+        eventType: 'testGroupStart',
+        payload: { groupName: hierarchy[i], parentNames: hierarchy.slice(0, i) },
+      });
+    }
+  }
+
+  appendOutput(result, stream, chunk) {
+    if (!result) return; // Output of the worker outside a test
+    const output = this.output.get(result) || { stdout: '', stderr: '' };
+    output[stream] += chunkText(chunk);
+    this.output.set(result, output);
+  }
+
+  reportTest(test) {
+    if (this.reported.has(test)) return;
+    this.reported.add(test);
+
+    const hierarchy = this.hierarchy(test);
+    this.ensureStarted(hierarchy);
+
+    const results = test.results;
+    const last = results[results.length - 1];
+    const output = this.output.get(last) || { stdout: '', stderr: '' };
+    const payload = {
+      testName: test.title,
+      parentNames: hierarchy,
+      status: 'PASS',
+      duration: results.reduce((sum, r) => sum + (r.duration || 0), 0),
+      file: path.relative(process.cwd(), test.location.file),
+      line: test.location.line,
+    };
+    if (output.stdout) payload.stdout = output.stdout;
+    if (output.stderr) payload.stderr = output.stderr;
+
+    switch (test.outcome()) {
+      case 'flaky':
+        payload.status = 'FLAKY';
+        break;
+      case 'skipped': {
+        payload.status = 'SKIP';
+        if (last.status === 'interrupted') payload.skipReason = 'not_run';
+        const annotation = test.annotations.find((a) => a.type === 'skip' || a.type === 'fixme');
+        if (annotation && annotation.description) payload.skipMessage = annotation.description;
+        break;
+      }
+      case 'expected':
+        if (last.status === 'failed') {
+          // test.fail(): failed as it said it would
+          payload.status = 'XFAIL';
+          const annotation = test.annotations.find((a) => a.type === 'fail');
+          if (annotation && annotation.description) payload.xfailReason = annotation.description;
+        }
+        break;
+      default:
+        payload.status = 'FAIL';
+        payload.error = this.testError(test, last);
+    }
+
+    if (results.length > 1) {
+      payload.attempts = results.map((r) => {
+        const attemptOutput = this.output.get(r) || {};
+        const attempt = { status: attemptStatus(r), duration: r.duration || 0 };
+        if (attemptOutput.stdout) attempt.stdout = attemptOutput.stdout;
+        if (attemptOutput.stderr) attempt.stderr = attemptOutput.stderr;
+        return attempt;
+      });
+    }
+
+    // Traces, screenshots and videos saved to disk, from every attempt
+    const attachments = [];
+    for (const r of results) {
+      for (const a of r.attachments || []) {
+        if (a.path) attachments.push({ name: a.name, path: a.path, contentType: a.contentType });
+      }
+    }
+    if (attachments.length > 0) payload.attachments = attachments;
+
+    this.countTest(hierarchy, payload.status);
+    sendEvent({ eventType: 'testCase', payload });
+  }
+
+  countTest(hierarchy, status) {
+    for (let i = 0; i < hierarchy.length; i++) {
+      const g = this.groups.get(groupId(hierarchy.slice(0, i + 1)));
+      if (!g) continue;
+      if (status === 'FAIL') g.failed++;
+      else if (status === 'SKIP') g.skipped++;
+      else g.passed++;
+    }
+  }
+
+  testError(test, result) {
+    const error = result.error || (result.errors && result.errors[0]);
+    if (result.status === 'passed') {
+      // test.fail() that passed
+      return { message: 'Expected to fail, but passed.', errorType: 'Error' };
+    }
+
+    const timedOut = result.status === 'timedOut';
+    let message = stripAnsi(error ? (error.message || error.value) : '') ||
+      (timedOut ? `Test timeout of ${test.timeout}ms exceeded.` : 'Error');
+    const snippet = error && error.snippet ? stripAnsi(error.snippet) : '';
+    if (snippet) message += '\n\n' + snippet;
+
+    const testError = {
+      message,
+      stack: error ? stripAnsi(error.stack || '').split('\n').filter((l) => /^\s+at /.test(l)).map((l) => l.trim()).join('\n') : '',
+      errorType: timedOut ? 'TIMED_OUT' : 'Error',
+    };
+    if (timedOut) testError.failureKind = 'TIMED_OUT';
+
+    // Values from the expect diff, e.g. "Expected: 80" and "Received: 90"
+    const expected = /^Expected(?: [\w ]+)?: (.*)$/m.exec(message);
+    const received = /^Received(?: [\w ]+)?: (.*)$/m.exec(message);
+    if (expected && received) {
+      testError.expected = expected[1];
+      testError.actual = received[1];
+    }
+
+    if (error && error.location && error.location.file) {
+      testError.file = path.relative(process.cwd(), error.location.file);
+      testError.line = error.location.line;
+      testError.column = error.location.column;
+      testError.location = `${testError.file}:${error.location.line}:${error.location.column}`;
+    }
+    return testError;
+  }
+}
+
+module.exports = ThreePioPlaywrightReporter;
@@ -7,6 +7,7 @@ specified by the THREEPIO_IPC_PATH environment variable.
 """
 
 import os
+import stat
 import sys
 import json
 import time
@@ -21,18 +22,30 @@ from _pytest.nodes import Item
 
 # Log level will be replaced at runtime
 LOG_LEVEL = #__LOG_LEVEL__#"WARN"#__LOG_LEVEL__#
+
+# IPC event schema this adapter writes, checked by 3pio (ipc.SchemaVersion)
+SCHEMA_VERSION = 2
 from _pytest.terminal import TerminalReporter
 
 
 # Global reporter instance
 _reporter: Optional['ThreepioReporter'] = None
 
+# Events a pytest-xdist worker sends itself. xdist forwards every report of a worker
+# to the controller, which sends the rest, so they would arrive twice.
+WORKER_EVENTS = ("testCaseStart", "groupStdout", "groupStderr")
+
+# The count of test_results each test status goes in
+STATUS_COUNTS = {"PASS": "passed", "FAIL": "failed", "ERROR": "errors", "SKIP": "skipped", "XFAIL": "xfailed", "XPASS": "xpassed"}
+
 
 class ThreepioReporter:
     """pytest reporter that sends test events via IPC."""
 
-    def __init__(self, ipc_path: str):
+    def __init__(self, ipc_path: str, worker_id: str = ""):
         self.ipc_path = ipc_path
+        self.worker_id = worker_id  # pytest-xdist worker, e.g. "gw1"; "" in the controller or without xdist
+        self.current_test = None  # (parent_names, test_name) of the test running in this process
         self.test_files = set()
         self.test_results = {}  # Track results per file
         self.current_test_file = None
@@ -46,26 +59,62 @@ class ThreepioReporter:
         self.group_starts = set()
         self.file_groups = {}
 
+        # Warnings of the run, sent together when the session finishes
+        self.warnings = []
+
+        # Last test result sent per worker ("" without xdist), as (nodeid, file_path, payload),
+        # for a teardown that fails after it
+        self.last_results = {}
+
         self._ensure_debug_log_dir()
         self._log_startup()
         
     def send_event(self, event_type: str, payload: Dict[str, Any]) -> None:
         """Send an event to the IPC file."""
+        if self.worker_id and event_type not in WORKER_EVENTS:
+            return
         event = {
             "eventType": event_type,
             "payload": payload,
+            "schema": SCHEMA_VERSION,
             "timestamp": time.time()
         }
         
         try:
+            line = json.dumps(event) + '\n'
+            if self._write_event_to_stdio(line):
+                return
             # Write to IPC file (append mode, create if doesn't exist)
             with open(self.ipc_path, 'a') as f:
-                f.write(json.dumps(event) + '\n')
+                f.write(line)
                 f.flush()  # Ensure immediate write
         except Exception as e:
             # Log error to debug log but stay silent in console
             self._log_error(f"Failed to send IPC event: {e}")
     
+    def _write_event_to_stdio(self, line: str) -> bool:
+        """Write an event line over the transport 3pio chose in THREEPIO_IPC: a file
+        descriptor ("fd:3"), stderr marked with the 3PIO_EVT: sentinel, or the IPC file.
+        Returns False to write it to the IPC file."""
+        transport = os.environ.get("THREEPIO_IPC", "")
+        if transport.startswith("fd:"):
+            try:
+                # Only 3pio's pipe: a launcher may not pass the descriptor down,
+                # leaving the number to an unrelated file
+                fd = int(transport[3:])
+                if stat.S_ISFIFO(os.fstat(fd).st_mode):
+                    os.write(fd, line.encode("utf-8"))
+                    return True
+            except (OSError, ValueError):
+                pass  # The descriptor wasn't passed down; fall back to stderr
+        elif transport != "stderr":
+            return False
+        try:
+            os.write(2, ("3PIO_EVT:" + line).encode("utf-8"))
+        except OSError:
+            pass
+        return True
+
     def _ensure_debug_log_dir(self) -> None:
         """Ensure the debug log directory exists."""
         try:
@@ -91,6 +140,8 @@ class ThreepioReporter:
         self._log("INFO", "Configuration:")
         self._log("INFO", f"  - IPC Path: {self.ipc_path}")
         self._log("INFO", f"  - Process ID: {os.getpid()}")
+        if self.worker_id:
+            self._log("INFO", f"  - xdist worker: {self.worker_id}")
         self._log("INFO", "==================================")
     
     def _log_info(self, message: str) -> None:
@@ -235,11 +286,14 @@ class ThreepioReporter:
                 self.newlines = getattr(original_stream, 'newlines', None)
             
             def write(self, text):
-                # Send to IPC if we have a current test file
-                if self.reporter.current_test_file and text:
-                    # Old stdout/stderr chunk events removed - using group events instead
-                    # Output is now captured by group events (groupStdout/groupStderr)
-                    pass
+                # Send the output of the running test, tagged with this process's worker
+                # so 3pio attributes it to the right test when workers run in parallel
+                if self.reporter.current_test and text:
+                    event_type = "groupStdout" if self.stream_type == "stdout" else "groupStderr"
+                    payload = {"chunk": text}
+                    if self.reporter.worker_id:
+                        payload["workerId"] = self.reporter.worker_id
+                    self.reporter.send_event(event_type, payload)
                 
                 # Return length of text to indicate success, but DON'T write to terminal
                 # This makes the adapter completely silent like Jest/Vitest
@@ -276,11 +330,33 @@ class ThreepioReporter:
             
         self.capture_enabled = False
         self.current_test_file = None
+        self.current_test = None
         
         # Don't restore original streams - we manage the entire test run
         # This prevents any buffered output from appearing after capture stops
 
 
+def _xdist_worker_id(config: Config) -> str:
+    """Returns the id of the pytest-xdist worker this process is, or "" if none."""
+    workerinput = getattr(config, "workerinput", None)
+    return workerinput.get("workerid", "") if workerinput else ""
+
+
+def _xdist_workers(config: Config) -> int:
+    """Returns how many pytest-xdist workers run the tests, 0 without xdist. xdist has
+    resolved -n auto to a number by the time pytest_configure runs."""
+    numprocesses = getattr(config.option, "numprocesses", None)
+    if not isinstance(numprocesses, int) or getattr(config.option, "dist", "no") == "no":
+        return 0
+    return numprocesses
+
+
+def _report_worker_id(report) -> str:
+    """Returns the pytest-xdist worker a report came from, "" for a test run in this process."""
+    gateway = getattr(getattr(report, "node", None), "gateway", None)
+    return str(getattr(gateway, "id", "") or "")
+
+
 def pytest_configure(config: Config) -> None:
     """Register the 3pio reporter if IPC path is set."""
     global _reporter
@@ -289,7 +365,7 @@ def pytest_configure(config: Config) -> None:
     
     if True:  # IPC path will always be present after injection
         # Create the reporter instance
-        _reporter = ThreepioReporter(ipc_path)
+        _reporter = ThreepioReporter(ipc_path, _xdist_worker_id(config))
         _reporter._log_info("Plugin initialized in pytest_configure")
         
         # Store it in config for access in other hooks
@@ -299,8 +375,14 @@ def pytest_configure(config: Config) -> None:
         # Use a special file path for collection phase
         _reporter.start_capture("__collection__")
         
+        # Tell 3pio which schema this adapter writes
+        _reporter.send_event("adapterHandshake", {"adapter": "pytest", "schema": SCHEMA_VERSION})
+
         # Send an event to indicate collection is starting
-        _reporter.send_event("collectionStart", {"phase": "collection"})
+        collection_start = {"phase": "collection"}
+        if _xdist_workers(config):
+            collection_start["workers"] = _xdist_workers(config)
+        _reporter.send_event("collectionStart", collection_start)
         
         # Note: Output capture is disabled via -s flag added by 3pio CLI
         # This ensures we can capture all print statements from tests
@@ -321,19 +403,35 @@ def pytest_collectreport(report: CollectReport) -> None:
     
     # Check if there was a collection error
     if report.failed:
-        # Extract the file path if available
-        file_path = str(report.nodeid) if report.nodeid else "__collection__"
-        
+        # Extract the file path if available, also for a class that failed to collect
+        file_path = str(report.nodeid).split("::")[0] if report.nodeid else "__collection__"
+        error = str(report.longrepr) if hasattr(report, 'longrepr') else "Collection failed"
+
         # Send collection error event
         payload = {
             "filePath": file_path,
-            "error": str(report.longrepr) if hasattr(report, 'longrepr') else "Collection failed",
+            "error": error,
             "phase": "collection"
         }
-        
+
         _reporter.send_event("collectionError", payload)
-        
-        # Collection error details are captured in the collectionError event
+
+        # The file becomes an errored group with the traceback, so its tests don't
+        # silently vanish from the report
+        _reporter.ensure_groups_discovered(file_path, [])
+        _reporter.ensure_group_started([file_path])
+        _reporter.send_event("testGroupError", {
+            "groupName": file_path,
+            "parentNames": [],
+            "errorType": "COLLECTION_ERROR",
+            "error": {"message": error, "phase": "collection"}
+        })
+        _reporter.send_event("testGroupResult", {
+            "groupName": file_path,
+            "parentNames": [],
+            "status": "FAIL",
+            "totals": {"total": 0, "passed": 0, "failed": 0, "skipped": 0, "setupFailed": True}
+        })
 
 
 def pytest_collection_finish(session) -> None:
@@ -363,7 +461,7 @@ def pytest_runtest_protocol(item: Item, nextitem: Optional[Item]) -> None:
         if file_path not in _reporter.test_files:
             _reporter.test_files.add(file_path)
             # File discovery and group management handled by group events
-            _reporter.test_results[file_path] = {"passed": 0, "failed": 0, "skipped": 0, "failed_tests": []}
+            _reporter.test_results[file_path] = {"passed": 0, "failed": 0, "errors": 0, "skipped": 0, "xfailed": 0, "xpassed": 0, "failed_tests": []}
 
             # Discover the file as a root group and start it
             _reporter.ensure_groups_discovered(file_path, [])
@@ -381,16 +479,104 @@ def pytest_runtest_protocol(item: Item, nextitem: Optional[Item]) -> None:
             # Switch to capturing for a different test file
             _reporter.current_test_file = file_path
 
+        # Output from now on belongs to this test, on this process's worker
+        file_path, suite_chain, test_name = _reporter.parse_test_hierarchy(item.nodeid)
+        parent_names = _reporter.build_hierarchy_from_file(file_path, suite_chain)
+        _reporter.current_test = (parent_names, test_name)
+        start = {"testName": test_name, "parentNames": parent_names}
+        if _reporter.worker_id:
+            start["workerId"] = _reporter.worker_id
+        _reporter.send_event("testCaseStart", start)
+
+
+def _skip_message(report: TestReport) -> str:
+    """Returns the reason a skipped test gave, from its (path, lineno, message) longrepr."""
+    longrepr = getattr(report, 'longrepr', None)
+    if not isinstance(longrepr, tuple) or len(longrepr) != 3:
+        return ""
+    message = str(longrepr[2])
+    if message.startswith("Skipped: "):
+        message = message[len("Skipped: "):]
+    return message
+
+
+def _split_param_id(test_name: str):
+    """Splits a parametrized test's name, e.g. test_divide[10-0-None], into its base name
+    and parameter id. Returns None for other tests. The base name is a Python identifier,
+    so the first "[" ends it even if the id has brackets of its own."""
+    if not test_name.endswith("]") or "[" not in test_name:
+        return None
+    base_name, param_id = test_name[:-1].split("[", 1)
+    return base_name, param_id
+
+
+def _error_payload(report: TestReport, phase: str = "") -> dict:
+    """Returns the error of a failed report: the exception line as the message, with the
+    full traceback as the stack. phase is "setup" or "teardown" if a fixture failed."""
+    longrepr = getattr(report, 'longrepr', None)
+    error = {"message": str(longrepr) if longrepr else ""}
+    crash = getattr(longrepr, 'reprcrash', None)
+    if crash is not None:
+        error["message"] = crash.message
+        error["stack"] = str(longrepr)
+    if phase:
+        error["phase"] = phase
+    return error
+
+
+def _report_teardown_error(report: TestReport) -> None:
+    """Reports a fixture that failed to tear down against the result of its test, which was
+    already sent. pytest counts a test that passed and then failed its teardown as an error,
+    a failed test keeps its failure and gets the teardown error too."""
+    worker_id = _report_worker_id(report)
+    last = _reporter.last_results.pop(worker_id, None)
+    if not last or last[0] != report.nodeid:
+        return
+    _, file_path, payload = last
+
+    error = _error_payload(report, "teardown")
+    if payload["status"] in ("FAIL", "ERROR"):
+        payload["teardownError"] = error
+    else:
+        results = _reporter.test_results[file_path]
+        results[STATUS_COUNTS[payload["status"]]] -= 1
+        results["errors"] += 1
+        results["failed_tests"].append({"name": payload["testName"], "duration": payload["duration"]})
+        payload["status"] = "ERROR"
+        payload["error"] = error
+        payload.pop("xfailReason", None)
+        payload.pop("skipMessage", None)
+
+    _reporter._log_debug(f"Sending teardown error of test case: {payload['testName']}")
+    _reporter.send_event("testCase", payload)
+
+
+def _xfail_reason(report: TestReport) -> str:
+    """Returns the reason of an xfail marker, or of a pytest.xfail() call."""
+    reason = str(report.wasxfail)
+    if reason.startswith("reason: "):
+        reason = reason[len("reason: "):]
+    return reason
+
 
 def pytest_runtest_logreport(report: TestReport) -> None:
     """Process test reports."""
     global _reporter
     
-    if not _reporter:
+    if not _reporter or _reporter.worker_id:
+        # xdist workers leave their results to the controller
         return
     
-    # Only process the 'call' phase (actual test execution)
-    if report.when != 'call':
+    # A fixture that failed to tear down changes the result already sent for its test
+    if report.when == 'teardown':
+        if report.failed:
+            _report_teardown_error(report)
+        return
+
+    # Only process the 'call' phase (actual test execution), and the skips and errors in
+    # the 'setup' phase (skip and skipif markers, xfail with run=False, fixtures that
+    # raised), which never get to it
+    if report.when != 'call' and not (report.when == 'setup' and (report.skipped or report.failed)):
         return
     
     # Parse the test hierarchy from nodeid
@@ -398,7 +584,13 @@ def pytest_runtest_logreport(report: TestReport) -> None:
 
     # Initialize results for file if needed
     if file_path not in _reporter.test_results:
-        _reporter.test_results[file_path] = {"passed": 0, "failed": 0, "skipped": 0, "xfailed": 0, "xpassed": 0, "failed_tests": []}
+        _reporter.test_results[file_path] = {"passed": 0, "failed": 0, "errors": 0, "skipped": 0, "xfailed": 0, "xpassed": 0, "failed_tests": []}
+
+    # Under xdist the controller runs no tests, so it first hears of a file here
+    worker_id = _report_worker_id(report)
+    if worker_id and file_path not in _reporter.test_files:
+        _reporter.test_files.add(file_path)
+        _reporter.file_groups[file_path] = {'start_time': time.time(), 'tests': []}
 
     # Determine test status
     has_xfail = hasattr(report, 'wasxfail')
@@ -414,6 +606,10 @@ def pytest_runtest_logreport(report: TestReport) -> None:
     elif report.passed:
         status = "PASS"
         _reporter.test_results[file_path]["passed"] += 1
+    elif report.failed and report.when == 'setup':
+        # pytest reports a fixture that raised as an error, apart from the failures
+        status = "ERROR"
+        _reporter.test_results[file_path]["errors"] += 1
     elif report.failed:
         status = "FAIL"
         _reporter.test_results[file_path]["failed"] += 1
@@ -441,15 +637,23 @@ def pytest_runtest_logreport(report: TestReport) -> None:
         "status": status,
         "duration": report.duration * 1000 if hasattr(report, 'duration') else 0  # Convert to milliseconds
     }
+    if worker_id:
+        payload["workerId"] = worker_id
 
-    # Add xfail reason if available
-    if has_xfail:
-        payload["xfailReason"] = str(report.wasxfail)
+    # 3pio groups the parameter sets of a test under their base test
+    param = _split_param_id(test_name)
+    if param:
+        payload["baseName"], payload["paramId"] = param
+
+    # Add the reason of the xfail or skip marker if available
+    if has_xfail and _xfail_reason(report):
+        payload["xfailReason"] = _xfail_reason(report)
+    elif status == "SKIP" and _skip_message(report):
+        payload["skipMessage"] = _skip_message(report)
 
-    # Add error information for failures
+    # Add error information for failures, and setup errors
     if report.failed:
-        if hasattr(report, 'longrepr') and report.longrepr:
-            payload["error"] = str(report.longrepr)
+        payload["error"] = _error_payload(report, "setup" if status == "ERROR" else "")
         # Track failed test for file result
         _reporter.test_results[file_path]["failed_tests"].append({
             "name": test_name,
@@ -468,6 +672,25 @@ def pytest_runtest_logreport(report: TestReport) -> None:
 
     # Send test case event with group hierarchy
     _reporter.send_event("testCase", payload)
+    _reporter.last_results[worker_id] = (report.nodeid, file_path, payload)
+
+
+def pytest_warning_recorded(warning_message, when: str, nodeid: str, location) -> None:
+    """Record a warning for the run's warnings list, as pytest's warnings summary shows it."""
+    global _reporter
+
+    if not _reporter:
+        return
+
+    warning = {
+        "category": warning_message.category.__name__,
+        "message": str(warning_message.message),
+        "file": str(warning_message.filename),
+        "line": warning_message.lineno,
+    }
+    if nodeid:
+        warning["nodeId"] = nodeid
+    _reporter.warnings.append(warning)
 
 
 def pytest_sessionfinish(session, exitstatus: int) -> None:
@@ -478,6 +701,9 @@ def pytest_sessionfinish(session, exitstatus: int) -> None:
         return
     
     _reporter._log_info(f"Session finished with exit status: {exitstatus}")
+
+    if _reporter.warnings:
+        _reporter.send_event("runWarnings", {"warnings": _reporter.warnings})
     
     # Send group result events for all test files
     for file_path in _reporter.test_files:
@@ -485,9 +711,10 @@ def pytest_sessionfinish(session, exitstatus: int) -> None:
         file_group = _reporter.file_groups.get(file_path, {})
 
         # Determine overall file status
-        if results.get("failed", 0) > 0:
+        if results.get("failed", 0) + results.get("errors", 0) > 0:
             status = "FAIL"
-        elif results.get("passed", 0) > 0:
+        elif results.get("passed", 0) + results.get("xfailed", 0) + results.get("xpassed", 0) > 0:
+            # Expected failures and unexpected passes don't fail the file
             status = "PASS"
         elif results.get("skipped", 0) > 0:
             status = "SKIP"
@@ -502,8 +729,8 @@ def pytest_sessionfinish(session, exitstatus: int) -> None:
         # Calculate totals for the file group
         totals = {
             'total': (results.get("passed", 0) + results.get("failed", 0) +
-                     results.get("skipped", 0) + results.get("xfailed", 0) +
-                     results.get("xpassed", 0)),
+                     results.get("errors", 0) + results.get("skipped", 0) +
+                     results.get("xfailed", 0) + results.get("xpassed", 0)),
             'passed': results.get("passed", 0),
             'failed': results.get("failed", 0),
             'skipped': results.get("skipped", 0),
@@ -90,6 +90,10 @@ const require_package = __commonJS({
     };
   },
 });
+
+// IPC event schema this adapter writes, checked by 3pio (ipc.SchemaVersion)
+const SCHEMA_VERSION = 2;
+
 const IPCSender = {
   /**
    * Send an event to the IPC file (used by adapters)
@@ -106,14 +110,44 @@ const IPCSender = {
     const ipcPath =
       process.env.THREEPIO_IPC_PATH || /* __IPC_PATH__ */ 'WILL_BE_REPLACED'; /* __IPC_PATH__ */
     try {
+      const line = `${JSON.stringify({ ...event, schema: SCHEMA_VERSION })}\n`;
+      if (this.writeToStdio(line)) {
+        return;
+      }
       const dir = path.dirname(ipcPath);
       if (!fs.existsSync(dir)) {
         fs.mkdirSync(dir, { recursive: true });
       }
-      const line = `${JSON.stringify(event)}\n`;
       fs.appendFileSync(ipcPath, line);
     } catch {}
   },
+  /**
+   * Write an event line over the transport 3pio chose in THREEPIO_IPC: a file
+   * descriptor ("fd:3"), stderr marked with the 3PIO_EVT: sentinel, or the IPC file.
+   * Returns false to write it to the IPC file.
+   */
+  writeToStdio(line) {
+    const transport = process.env.THREEPIO_IPC || '';
+    if (transport.startsWith('fd:')) {
+      try {
+        // Only 3pio's pipe: a launcher like npx may not pass the descriptor down,
+        // leaving the number to an unrelated file
+        const fd = Number(transport.slice(3));
+        if (fs.fstatSync(fd).isFIFO()) {
+          fs.writeSync(fd, line);
+          return true;
+        }
+      } catch {
+        // The descriptor wasn't passed down; fall back to stderr
+      }
+    } else if (transport !== 'stderr') {
+      return false;
+    }
+    try {
+      fs.writeSync(2, `3PIO_EVT:${line}`);
+    } catch {}
+    return true;
+  },
 };
 
 const Logger = class _Logger {
@@ -294,9 +328,9 @@ const ThreePioVitestReporter = class {
     const suites = [];
     let current = task;
 
-    // Walk up parent chain to collect suite names
+    // Walk up parent chain to collect suite names (a file is a suite too, but not one of them)
     while (current) {
-      if (current.type === 'suite' && current.name) {
+      if (current.type === 'suite' && current.name && current !== task.file) {
         suites.unshift(current.name);
       }
       current = current.parent || current.suite;
@@ -305,28 +339,43 @@ const ThreePioVitestReporter = class {
     return suites;
   }
 
-  buildHierarchyFromFile(filePath, suiteChain = []) {
-    const hierarchy = [filePath];
+  // Workspace projects are root groups above their files, so a file run by two
+  // projects is reported under each of them
+  projectRoot(projectName) {
+    return projectName ? [projectName] : [];
+  }
+
+  buildHierarchyFromFile(filePath, suiteChain = [], root = []) {
+    const hierarchy = [...root, filePath];
     if (suiteChain && suiteChain.length > 0) {
       hierarchy.push(...suiteChain);
     }
     return hierarchy;
   }
 
-  discoverGroups(filePath, suiteChain = []) {
+  discoverGroups(filePath, suiteChain = [], root = []) {
     const groups = [];
 
-    // First, the file itself is a group
+    // First, the project the file ran in, if any
+    for (let i = 0; i < root.length; i++) {
+      groups.push({
+        hierarchy: root.slice(0, i + 1),
+        name: root[i],
+        parentNames: root.slice(0, i),
+      });
+    }
+
+    // Then the file itself is a group
     groups.push({
-      hierarchy: [filePath],
+      hierarchy: [...root, filePath],
       name: filePath,
-      parentNames: [],
+      parentNames: [...root],
     });
 
     // Then each level of suites creates a nested group
     if (suiteChain && suiteChain.length > 0) {
       for (let i = 0; i < suiteChain.length; i++) {
-        const parentNames = [filePath, ...suiteChain.slice(0, i)];
+        const parentNames = [...root, filePath, ...suiteChain.slice(0, i)];
         const groupName = suiteChain[i];
         groups.push({
           hierarchy: [...parentNames, groupName],
@@ -339,8 +388,8 @@ const ThreePioVitestReporter = class {
     return groups;
   }
 
-  ensureGroupsDiscovered(filePath, suiteChain = []) {
-    const groups = this.discoverGroups(filePath, suiteChain);
+  ensureGroupsDiscovered(filePath, suiteChain = [], root = []) {
+    const groups = this.discoverGroups(filePath, suiteChain, root);
 
     for (const group of groups) {
       const groupId = this.getGroupId(group.hierarchy);
@@ -387,13 +436,27 @@ const ThreePioVitestReporter = class {
     }
   }
 
+  // Starts each group down to the one at hierarchy, outermost first
+  ensureHierarchyStarted(hierarchy) {
+    for (let i = 1; i <= hierarchy.length; i++) {
+      this.ensureGroupStarted(hierarchy.slice(0, i));
+    }
+  }
+
   onInit(ctx) {
+    this.ctx = ctx;
     this.logger.lifecycle('Test run initializing');
     const ipcPath =
       process.env.THREEPIO_IPC_PATH || /* __IPC_PATH__ */ 'WILL_BE_REPLACED'; /* __IPC_PATH__ */
     this.logger.info('IPC communication channel ready', { path: ipcPath });
     this.logger.initComplete({ ipcPath });
 
+    // Tell 3pio which schema this adapter writes
+    IPCSender.sendEvent({
+      eventType: 'adapterHandshake',
+      payload: { adapter: 'vitest', schema: SCHEMA_VERSION },
+    });
+
     // Send collection start event
     IPCSender.sendEvent({
       eventType: 'collectionStart',
@@ -444,10 +507,15 @@ const ThreePioVitestReporter = class {
       name: testModule?.name,
     });
 
-    // Discover the file as a root group
+    // Discover the file as a root group, or under its project's
     const filePath = testModule?.filepath || testModule?.moduleId;
     if (filePath) {
-      this.ensureGroupsDiscovered(filePath, []);
+      const root = this.projectRoot(testModule.project?.name);
+      this.ensureGroupsDiscovered(filePath, [], root);
+      const groupId = this.getGroupId([...root, filePath]);
+      if (!this.fileGroups.has(groupId)) {
+        this.fileGroups.set(groupId, { startTime: Date.now(), tests: [] });
+      }
       // testFileStart event removed - using group events instead
     }
   }
@@ -488,17 +556,13 @@ const ThreePioVitestReporter = class {
     // Send IPC event for test case result with group hierarchy
     if (result && filePath) {
       // Extract hierarchy for this test case
+      const root = this.projectRoot(testCase.project?.name);
       const suiteChain = this.extractHierarchyFromTask(testCase, filePath);
-      const parentNames = this.buildHierarchyFromFile(filePath, suiteChain);
+      const parentNames = this.buildHierarchyFromFile(filePath, suiteChain, root);
 
       // Ensure all parent groups are discovered and started
-      this.ensureGroupsDiscovered(filePath, suiteChain);
-
-      // Start all parent groups
-      for (let i = 0; i <= suiteChain.length; i++) {
-        const hierarchy = [filePath, ...suiteChain.slice(0, i)];
-        this.ensureGroupStarted(hierarchy);
-      }
+      this.ensureGroupsDiscovered(filePath, suiteChain, root);
+      this.ensureHierarchyStarted(parentNames);
 
       const status =
         result.state === 'passed'
@@ -508,6 +572,8 @@ const ThreePioVitestReporter = class {
             : result.state === 'skipped'
               ? 'SKIP'
               : 'UNKNOWN';
+      // test.todo() is a skipped test with nothing in it yet
+      const skipReason = testCase.options?.mode === 'todo' ? 'todo' : undefined;
 
       // Send test case event with group hierarchy
       this.logger.ipc('send', 'testCase', { testName: testCase.name, parentNames, status });
@@ -534,13 +600,15 @@ const ThreePioVitestReporter = class {
           status,
           duration: diagnostic?.duration,
           error: errorObj,
+          skipReason,
+          skipMessage: result.note,
         },
       }).catch((error) => {
         this.logger.error('Failed to send testCase event', error);
       });
 
       // Track test in file group
-      const fileGroup = this.fileGroups.get(filePath);
+      const fileGroup = this.fileGroups.get(this.getGroupId([...root, filePath]));
       if (fileGroup) {
         fileGroup.tests.push({
           name: testCase.name,
@@ -571,7 +639,8 @@ const ThreePioVitestReporter = class {
     // Send group result for the file when module completes
     const filePath = testModule?.filepath || testModule?.moduleId;
     if (filePath) {
-      const fileGroup = this.fileGroups.get(filePath);
+      const root = this.projectRoot(testModule.project?.name);
+      const fileGroup = this.fileGroups.get(this.getGroupId([...root, filePath]));
       if (fileGroup) {
         const fileDuration = fileGroup.startTime ? Date.now() - fileGroup.startTime : undefined;
 
@@ -595,7 +664,7 @@ const ThreePioVitestReporter = class {
           eventType: 'testGroupResult',
           payload: {
             groupName: filePath,
-            parentNames: [],
+            parentNames: root,
             status,
             duration: fileDuration,
             totals,
@@ -642,6 +711,35 @@ const ThreePioVitestReporter = class {
     });
   }
 
+  // Attributes console output to the innermost group of the test, suite or file
+  // that logged it
+  onUserConsoleLog(log) {
+    const task = log?.taskId ? this.ctx?.state?.idMap?.get(log.taskId) : undefined;
+    const file = task?.file || task;
+    const filePath = file?.filepath;
+    if (!filePath) {
+      this.logger.debug('Console output without a test file', { taskId: log?.taskId });
+      return;
+    }
+
+    const root = this.projectRoot(file.projectName);
+    const suiteChain = task === file ? [] : this.extractHierarchyFromTask(task, filePath);
+    const hierarchy = this.buildHierarchyFromFile(filePath, suiteChain, root);
+    this.ensureGroupsDiscovered(filePath, suiteChain, root);
+
+    const chunk = log.content.endsWith('\n') ? log.content : `${log.content}\n`;
+    IPCSender.sendEvent({
+      eventType: log.type === 'stderr' ? 'groupStderr' : 'groupStdout',
+      payload: {
+        groupName: hierarchy[hierarchy.length - 1],
+        parentNames: hierarchy.slice(0, -1),
+        chunk,
+      },
+    }).catch((error) => {
+      this.logger.error('Failed to send console output', error);
+    });
+  }
+
   onTestFileStart(file) {
     this.logger.testFlow('Starting test file', file.filepath);
     this.currentTestFile = file.filepath;
@@ -717,14 +815,15 @@ const ThreePioVitestReporter = class {
   // Simplified file processing for legacy Vitest compatibility
   processFileResults(file) {
     const filePath = file.filepath;
+    const root = this.projectRoot(file.projectName);
 
     // Ensure file group is discovered and started
-    this.ensureGroupsDiscovered(filePath, []);
-    this.ensureGroupStarted([filePath]);
+    this.ensureGroupsDiscovered(filePath, [], root);
+    this.ensureHierarchyStarted([...root, filePath]);
 
     // Process test cases if available
     if (file.tasks) {
-      this.processTasksSimple(filePath, file.tasks);
+      this.processTasksSimple(filePath, file.tasks, root);
     }
 
     // Send file result
@@ -747,7 +846,7 @@ const ThreePioVitestReporter = class {
       eventType: 'testGroupResult',
       payload: {
         groupName: filePath,
-        parentNames: [],
+        parentNames: root,
         status,
         duration: file.result?.duration || 0,
         totals,
@@ -757,13 +856,13 @@ const ThreePioVitestReporter = class {
     });
   }
 
-  processTasksSimple(filePath, tasks) {
+  processTasksSimple(filePath, tasks, root = []) {
     for (const task of tasks) {
       if (task.type === 'test') {
         const status =
           task.result?.state === 'fail'
             ? 'FAIL'
-            : task.result?.state === 'skip' || task.mode === 'skip'
+            : task.result?.state === 'skip' || task.mode === 'skip' || task.mode === 'todo'
               ? 'SKIP'
               : 'PASS';
 
@@ -785,14 +884,11 @@ const ThreePioVitestReporter = class {
 
         // Simple hierarchy - just file and test name
         const suiteChain = this.extractHierarchyFromTask(task, filePath);
-        const parentNames = this.buildHierarchyFromFile(filePath, suiteChain);
+        const parentNames = this.buildHierarchyFromFile(filePath, suiteChain, root);
 
         // Ensure groups are discovered and started
-        this.ensureGroupsDiscovered(filePath, suiteChain);
-        for (let i = 0; i <= suiteChain.length; i++) {
-          const hierarchy = [filePath, ...suiteChain.slice(0, i)];
-          this.ensureGroupStarted(hierarchy);
-        }
+        this.ensureGroupsDiscovered(filePath, suiteChain, root);
+        this.ensureHierarchyStarted(parentNames);
 
         this.logger.ipc('send', 'testCase', { testName: task.name, parentNames, status });
         IPCSender.sendEvent({
@@ -803,12 +899,13 @@ const ThreePioVitestReporter = class {
             status,
             duration: task.result?.duration,
             error,
+            skipReason: task.mode === 'todo' ? 'todo' : undefined,
           },
         }).catch((error_) => {
           this.logger.error('Failed to send testCase event', error_);
         });
       } else if (task.type === 'suite' && task.tasks) {
-        this.processTasksSimple(filePath, task.tasks);
+        this.processTasksSimple(filePath, task.tasks, root);
       }
     }
   }
@@ -852,7 +949,10 @@ const ThreePioVitestReporter = class {
   countSkippedTestsSimple(tasks) {
     let count = 0;
     for (const task of tasks) {
-      if (task.type === 'test' && (task.result?.state === 'skip' || task.mode === 'skip')) {
+      if (
+        task.type === 'test' &&
+        (task.result?.state === 'skip' || task.mode === 'skip' || task.mode === 'todo')
+      ) {
         count++;
       } else if (task.tasks) {
         count += this.countSkippedTestsSimple(task.tasks);
@@ -0,0 +1,359 @@
+// Package config reads persistent defaults for the 3pio options: a user-level
+// config file, a .3pio.toml at the repository root and THREEPIO_<KEY> environment
+// variables, each overriding the one before. Flags on the command line override
+// them all; the caller puts them last.
+package config
+
+import (
+	"fmt"
+	"math"
+	"os"
+	"path/filepath"
+	"sort"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// FileName is the config file 3pio reads from the repository root
+const FileName = ".3pio.toml"
+
+// EnvPrefix starts the environment variables that set an option, followed by its key
+// upper-cased with dashes turned into underscores, e.g. THREEPIO_JUNIT_XML
+const EnvPrefix = "THREEPIO_"
+
+// Kind is the type of an option's value
+type Kind int
+
+const (
+	Bool    Kind = iota // true or false
+	String              // A string
+	Int                 // An integer
+	Seconds             // A number of seconds, or a duration string such as "500ms" or "10m"
+	Size                // A number of bytes, or a string such as "16M"
+)
+
+// Key is an option the config can set
+type Key struct {
+	Name string // Key in the config files, e.g. "junit-xml"
+	Kind Kind
+}
+
+// EnvVar returns the environment variable that sets the key
+func (k Key) EnvVar() string {
+	return EnvPrefix + strings.ToUpper(strings.ReplaceAll(k.Name, "-", "_"))
+}
+
+// Setting is the value one layer of the config gives a key
+type Setting struct {
+	Key     string
+	Value   string // Normalized: "true" or "false", a number of seconds, or the string itself
+	Literal string // The value as TOML, for printing the config
+	Source  string // Where the value comes from: a file and line ("path:3") or an environment variable
+}
+
+// Config is the merged configuration
+type Config struct {
+	Settings map[string]Setting // Key -> the value of the last layer that sets it
+	Files    []string           // Config files read, in order
+	Warnings []string           // Unknown keys, which are ignored so older versions can read newer configs
+}
+
+// UserFile returns the user-level config file, e.g. ~/.config/3pio/config.toml on
+// Linux; "" when there is no user config directory
+func UserFile() string {
+	dir, err := os.UserConfigDir()
+	if err != nil {
+		return ""
+	}
+	return filepath.Join(dir, "3pio", "config.toml")
+}
+
+// FindFile returns the .3pio.toml of dir or of its closest parent that has one,
+// looking no further up than the repository root (the directory with .git); "" if
+// there is none
+func FindFile(dir string) string {
+	dir, err := filepath.Abs(dir)
+	if err != nil {
+		return ""
+	}
+	for {
+		path := filepath.Join(dir, FileName)
+		if _, err := os.Stat(path); err == nil {
+			return path
+		}
+		if _, err := os.Stat(filepath.Join(dir, ".git")); err == nil {
+			return ""
+		}
+		parent := filepath.Dir(dir)
+		if parent == dir {
+			return ""
+		}
+		dir = parent
+	}
+}
+
+// Load merges, in order, the user config file (skipped when empty or missing), the
+// .3pio.toml found from dir and the environment variables of keys
+func Load(userFile, dir string, keys []Key) (*Config, error) {
+	cfg := &Config{Settings: make(map[string]Setting)}
+	byName := make(map[string]Key)
+	for _, key := range keys {
+		byName[key.Name] = key
+	}
+
+	for _, path := range []string{userFile, FindFile(dir)} {
+		if path == "" {
+			continue
+		}
+		content, err := os.ReadFile(path)
+		if os.IsNotExist(err) {
+			continue
+		}
+		if err != nil {
+			return nil, err
+		}
+		if err := cfg.parseFile(path, string(content), byName); err != nil {
+			return nil, err
+		}
+		cfg.Files = append(cfg.Files, path)
+	}
+
+	for _, key := range keys {
+		raw, ok := os.LookupEnv(key.EnvVar())
+		if !ok {
+			continue
+		}
+		setting, err := parseEnvValue(key, raw)
+		if err != nil {
+			return nil, fmt.Errorf("%s: %v", key.EnvVar(), err)
+		}
+		setting.Source = key.EnvVar()
+		cfg.Settings[key.Name] = setting
+	}
+	return cfg, nil
+}
+
+// Sorted returns the settings ordered by key
+func (c *Config) Sorted() []Setting {
+	settings := make([]Setting, 0, len(c.Settings))
+	for _, setting := range c.Settings {
+		settings = append(settings, setting)
+	}
+	sort.Slice(settings, func(i, j int) bool { return settings[i].Key < settings[j].Key })
+	return settings
+}
+
+// parseFile reads the "key = value" lines of a config file into the settings
+func (c *Config) parseFile(path, content string, keys map[string]Key) error {
+	seen := make(map[string]bool)
+	table := ""
+	for i, line := range strings.Split(content, "\n") {
+		source := fmt.Sprintf("%s:%d", path, i+1)
+		line = strings.TrimSpace(line)
+		if line == "" || strings.HasPrefix(line, "#") {
+			continue
+		}
+
+		// Options aren't grouped in tables, so the keys of one are unknown
+		if strings.HasPrefix(line, "[") {
+			end := strings.Index(line, "]")
+			if end < 0 || !isComment(line[end+1:]) {
+				return fmt.Errorf("%s: expected a [table] header, got %q", source, line)
+			}
+			table = strings.TrimSpace(line[1:end])
+			continue
+		}
+
+		name, rest, ok := strings.Cut(line, "=")
+		name = strings.TrimSpace(name)
+		if !ok || name == "" {
+			return fmt.Errorf("%s: expected key = value, got %q", source, line)
+		}
+		if table != "" {
+			name = table + "." + name
+		}
+		if seen[name] {
+			return fmt.Errorf("%s: %s is set twice", source, name)
+		}
+		seen[name] = true
+
+		v, err := parseTOMLValue(strings.TrimSpace(rest))
+		if err != nil {
+			return fmt.Errorf("%s: %s: %v", source, name, err)
+		}
+		key, known := keys[name]
+		if !known {
+			c.Warnings = append(c.Warnings, fmt.Sprintf("%s: unknown key %q, ignored", source, name))
+			continue
+		}
+		setting, err := v.setting(key)
+		if err != nil {
+			return fmt.Errorf("%s: %s: %v", source, name, err)
+		}
+		setting.Source = source
+		c.Settings[name] = setting
+	}
+	return nil
+}
+
+// tomlValue is a parsed TOML scalar
+type tomlValue struct {
+	kind    Kind    // Bool, String, Int, or Seconds for a float
+	str     string  // String value
+	num     float64 // Int and float value
+	boolean bool
+	literal string // The value as written
+}
+
+// parseTOMLValue parses the value of a key, with an optional trailing comment:
+// a basic or literal string on one line, an integer, a float or a boolean
+func parseTOMLValue(s string) (tomlValue, error) {
+	switch {
+	case strings.HasPrefix(s, `"`):
+		end := closingQuote(s)
+		if end < 0 {
+			return tomlValue{}, fmt.Errorf("unterminated string %s", s)
+		}
+		str, err := strconv.Unquote(s[:end+1])
+		if err != nil {
+			return tomlValue{}, fmt.Errorf("invalid string %s", s[:end+1])
+		}
+		if !isComment(s[end+1:]) {
+			return tomlValue{}, fmt.Errorf("unexpected %q after the value", strings.TrimSpace(s[end+1:]))
+		}
+		return tomlValue{kind: String, str: str, literal: s[:end+1]}, nil
+	case strings.HasPrefix(s, "'"):
+		end := strings.Index(s[1:], "'")
+		if end < 0 {
+			return tomlValue{}, fmt.Errorf("unterminated string %s", s)
+		}
+		if !isComment(s[end+2:]) {
+			return tomlValue{}, fmt.Errorf("unexpected %q after the value", strings.TrimSpace(s[end+2:]))
+		}
+		return tomlValue{kind: String, str: s[1 : end+1], literal: s[:end+2]}, nil
+	}
+
+	literal := s
+	if i := strings.Index(s, "#"); i >= 0 {
+		literal = strings.TrimSpace(s[:i])
+	}
+	switch {
+	case literal == "":
+		return tomlValue{}, fmt.Errorf("missing value")
+	case literal == "true" || literal == "false":
+		return tomlValue{kind: Bool, boolean: literal == "true", literal: literal}, nil
+	case strings.HasPrefix(literal, "["):
+		return tomlValue{}, fmt.Errorf("arrays aren't supported")
+	}
+	digits := strings.ReplaceAll(literal, "_", "")
+	if n, err := strconv.ParseInt(digits, 10, 64); err == nil {
+		return tomlValue{kind: Int, num: float64(n), literal: literal}, nil
+	}
+	if f, err := strconv.ParseFloat(digits, 64); err == nil && !math.IsInf(f, 0) && !math.IsNaN(f) {
+		return tomlValue{kind: Seconds, num: f, literal: literal}, nil
+	}
+	return tomlValue{}, fmt.Errorf("invalid value %s; strings need quotes", literal)
+}
+
+// closingQuote returns the index of the quote that ends the basic string at the
+// start of s, -1 if it doesn't end on the line
+func closingQuote(s string) int {
+	for i := 1; i < len(s); i++ {
+		switch s[i] {
+		case '\\':
+			i++
+		case '"':
+			return i
+		}
+	}
+	return -1
+}
+
+// isComment reports whether the rest of a line is empty or a comment
+func isComment(rest string) bool {
+	rest = strings.TrimSpace(rest)
+	return rest == "" || strings.HasPrefix(rest, "#")
+}
+
+// setting converts a value from a config file to the key's kind
+func (v tomlValue) setting(key Key) (Setting, error) {
+	switch key.Kind {
+	case Bool:
+		if v.kind != Bool {
+			return Setting{}, fmt.Errorf("expected true or false, got %s", v.literal)
+		}
+		return Setting{Key: key.Name, Value: strconv.FormatBool(v.boolean), Literal: v.literal}, nil
+	case String:
+		if v.kind != String {
+			return Setting{}, fmt.Errorf("expected a string, got %s", v.literal)
+		}
+		return Setting{Key: key.Name, Value: v.str, Literal: v.literal}, nil
+	case Int:
+		if v.kind != Int {
+			return Setting{}, fmt.Errorf("expected an integer, got %s", v.literal)
+		}
+		return Setting{Key: key.Name, Value: strconv.FormatFloat(v.num, 'f', -1, 64), Literal: v.literal}, nil
+	case Seconds:
+		switch v.kind {
+		case Int, Seconds:
+			return Setting{Key: key.Name, Value: formatSeconds(v.num), Literal: v.literal}, nil
+		case String:
+			d, err := time.ParseDuration(v.str)
+			if err != nil {
+				return Setting{}, fmt.Errorf("expected a number of seconds or a duration such as \"500ms\", got %s", v.literal)
+			}
+			return Setting{Key: key.Name, Value: formatSeconds(d.Seconds()), Literal: v.literal}, nil
+		}
+		return Setting{}, fmt.Errorf("expected a number of seconds or a duration such as \"500ms\", got %s", v.literal)
+	case Size:
+		switch v.kind {
+		case Int:
+			return Setting{Key: key.Name, Value: strconv.FormatFloat(v.num, 'f', -1, 64), Literal: v.literal}, nil
+		case String:
+			return Setting{Key: key.Name, Value: v.str, Literal: v.literal}, nil
+		}
+		return Setting{}, fmt.Errorf("expected a number of bytes or a size such as \"16M\", got %s", v.literal)
+	}
+	return Setting{}, fmt.Errorf("unsupported option kind %d", key.Kind)
+}
+
+// parseEnvValue converts the value of an environment variable to the key's kind
+func parseEnvValue(key Key, raw string) (Setting, error) {
+	setting := Setting{Key: key.Name, Value: raw, Literal: strconv.Quote(raw)}
+	switch key.Kind {
+	case Bool:
+		switch strings.ToLower(raw) {
+		case "true", "1", "yes", "on":
+			setting.Value = "true"
+		case "false", "0", "no", "off", "":
+			setting.Value = "false"
+		default:
+			return Setting{}, fmt.Errorf("expected true or false, got %q", raw)
+		}
+		setting.Literal = setting.Value
+	case Int:
+		if _, err := strconv.Atoi(raw); err != nil {
+			return Setting{}, fmt.Errorf("expected an integer, got %q", raw)
+		}
+		setting.Literal = raw
+	case Seconds:
+		if secs, err := strconv.ParseFloat(raw, 64); err == nil && !math.IsInf(secs, 0) && !math.IsNaN(secs) {
+			setting.Value, setting.Literal = formatSeconds(secs), raw
+		} else if d, err := time.ParseDuration(raw); err == nil {
+			setting.Value = formatSeconds(d.Seconds())
+		} else {
+			return Setting{}, fmt.Errorf("expected a number of seconds or a duration such as \"500ms\", got %q", raw)
+		}
+	case Size:
+		if _, err := strconv.Atoi(raw); err == nil {
+			setting.Literal = raw
+		}
+	}
+	return setting, nil
+}
+
+// formatSeconds formats a number of seconds without a needless fraction: "600", "0.5"
+func formatSeconds(secs float64) string {
+	return strconv.FormatFloat(secs, 'f', -1, 64)
+}
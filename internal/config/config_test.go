@@ -0,0 +1,142 @@
+package config
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+var testKeys = []Key{
+	{Name: "runner", Kind: String},
+	{Name: "retries", Kind: Int},
+	{Name: "redact", Kind: Bool},
+	{Name: "timeout", Kind: Seconds},
+	{Name: "max-line-length", Kind: Size},
+}
+
+// writeRepo creates a repository whose .3pio.toml holds content, returning its directory
+func writeRepo(t *testing.T, content string) string {
+	t.Helper()
+	dir := t.TempDir()
+	if err := os.Mkdir(filepath.Join(dir, ".git"), 0755); err != nil {
+		t.Fatal(err)
+	}
+	if content != "" {
+		if err := os.WriteFile(filepath.Join(dir, FileName), []byte(content), 0644); err != nil {
+			t.Fatal(err)
+		}
+	}
+	return dir
+}
+
+func TestLoad_Layers(t *testing.T) {
+	userFile := filepath.Join(t.TempDir(), "config.toml")
+	if err := os.WriteFile(userFile, []byte("runner = \"pytest\"\nretries = 1\nredact = true\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	repo := writeRepo(t, "# Shared defaults\nretries = 2 # CI is flaky\ntimeout = \"10m\"\n")
+	sub := filepath.Join(repo, "crates", "core")
+	if err := os.MkdirAll(sub, 0755); err != nil {
+		t.Fatal(err)
+	}
+	t.Setenv("THREEPIO_REDACT", "no")
+
+	cfg, err := Load(userFile, sub, testKeys)
+	if err != nil {
+		t.Fatalf("Load failed: %v", err)
+	}
+
+	repoFile := filepath.Join(repo, FileName)
+	expected := map[string]Setting{
+		"runner":  {Key: "runner", Value: "pytest", Literal: `"pytest"`, Source: userFile + ":1"},
+		"retries": {Key: "retries", Value: "2", Literal: "2", Source: repoFile + ":2"},
+		"timeout": {Key: "timeout", Value: "600", Literal: `"10m"`, Source: repoFile + ":3"},
+		"redact":  {Key: "redact", Value: "false", Literal: "false", Source: "THREEPIO_REDACT"},
+	}
+	if len(cfg.Settings) != len(expected) {
+		t.Errorf("Expected %d settings, got %v", len(expected), cfg.Settings)
+	}
+	for name, want := range expected {
+		if got := cfg.Settings[name]; got != want {
+			t.Errorf("Expected %s to be %+v, got %+v", name, want, got)
+		}
+	}
+	if strings.Join(cfg.Files, ",") != userFile+","+repoFile {
+		t.Errorf("Expected files [%s %s], got %v", userFile, repoFile, cfg.Files)
+	}
+}
+
+func TestLoad_Errors(t *testing.T) {
+	testCases := []struct {
+		content string
+		err     string
+	}{
+		{"retries = 2\nretries = \"three\"\n", ":2: retries is set twice"},
+		{"runner = cargo\n", ":1: runner: invalid value cargo; strings need quotes"},
+		{"\nretries = 1.5\n", ":2: retries: expected an integer, got 1.5"},
+		{"redact = \"yes\"\n", ":1: redact: expected true or false, got \"yes\""},
+		{"timeout = \"soon\"\n", ":1: timeout: expected a number of seconds or a duration such as \"500ms\", got \"soon\""},
+		{"runner = \"cargo\" extra\n", ":1: runner: unexpected \"extra\" after the value"},
+		{"runner\n", ":1: expected key = value, got \"runner\""},
+	}
+	for _, tc := range testCases {
+		repo := writeRepo(t, tc.content)
+		_, err := Load("", repo, testKeys)
+		if err == nil || !strings.HasSuffix(err.Error(), FileName+tc.err) {
+			t.Errorf("Expected error ending in %q for %q, got %v", FileName+tc.err, tc.content, err)
+		}
+	}
+}
+
+func TestLoad_UnknownKeys(t *testing.T) {
+	repo := writeRepo(t, "retries = 1\nparallel = 4\n\n[runner]\nname = \"cargo\"\n")
+
+	cfg, err := Load("", repo, testKeys)
+	if err != nil {
+		t.Fatalf("Load failed: %v", err)
+	}
+	if cfg.Settings["retries"].Value != "1" {
+		t.Errorf("Expected retries 1, got %+v", cfg.Settings["retries"])
+	}
+	path := filepath.Join(repo, FileName)
+	expected := []string{
+		path + `:2: unknown key "parallel", ignored`,
+		path + `:5: unknown key "runner.name", ignored`,
+	}
+	if strings.Join(cfg.Warnings, "\n") != strings.Join(expected, "\n") {
+		t.Errorf("Expected warnings %q, got %q", expected, cfg.Warnings)
+	}
+}
+
+func TestLoad_EnvErrors(t *testing.T) {
+	repo := writeRepo(t, "")
+	t.Setenv("THREEPIO_RETRIES", "many")
+
+	_, err := Load("", repo, testKeys)
+	if err == nil || err.Error() != `THREEPIO_RETRIES: expected an integer, got "many"` {
+		t.Errorf("Expected an error naming THREEPIO_RETRIES, got %v", err)
+	}
+}
+
+func TestFindFile(t *testing.T) {
+	outer := writeRepo(t, "retries = 1\n")
+	inner := filepath.Join(outer, "vendor", "lib")
+	if err := os.MkdirAll(filepath.Join(inner, ".git"), 0755); err != nil {
+		t.Fatal(err)
+	}
+
+	// The search stops at the inner repository's root
+	if path := FindFile(inner); path != "" {
+		t.Errorf("Expected no config file for a nested repository, got %s", path)
+	}
+	if path := FindFile(filepath.Join(outer, "vendor")); path != filepath.Join(outer, FileName) {
+		t.Errorf("Expected %s, got %s", filepath.Join(outer, FileName), path)
+	}
+}
+
+func TestKey_EnvVar(t *testing.T) {
+	if name := (Key{Name: "max-line-length"}).EnvVar(); name != "THREEPIO_MAX_LINE_LENGTH" {
+		t.Errorf("Expected THREEPIO_MAX_LINE_LENGTH, got %s", name)
+	}
+}
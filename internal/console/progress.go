@@ -0,0 +1,243 @@
+// Package console renders 3pio's live progress line in interactive terminals
+package console
+
+import (
+	"fmt"
+	"io"
+	"os"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+	"unicode/utf8"
+)
+
+// defaultWidth is the terminal width assumed when it can't be read, e.g. when the
+// progress line is forced on with --progress while stdout is a pipe
+const defaultWidth = 80
+
+// redrawInterval is how often the progress line is redrawn to keep its elapsed time current
+const redrawInterval = 500 * time.Millisecond
+
+// ANSI sequences: return to the start of the line and erase it, and the colors of the tally
+const (
+	clearLine = "\r\x1b[K"
+	green     = "\x1b[32m"
+	red       = "\x1b[31m"
+	yellow    = "\x1b[33m"
+	reset     = "\x1b[0m"
+)
+
+// Progress is the state of the run shown on the progress line
+type Progress struct {
+	CompletedGroups int // Top-level groups that finished
+	TotalGroups     int // Top-level groups discovered so far
+	Passed          int
+	Failed          int
+	Skipped         int
+}
+
+// ProgressRenderer owns the bottom line of the terminal and keeps a status line there,
+// updated in place, while other output is printed above it. All output written while
+// the renderer runs must go through Suspend so lines never interleave mid-render.
+type ProgressRenderer struct {
+	mu       sync.Mutex
+	out      io.Writer
+	width    int
+	color    bool
+	start    time.Time
+	progress Progress
+	shown    bool // The progress line is on screen
+	started  bool
+	stopped  bool
+	stop     chan struct{}
+	done     chan struct{}
+}
+
+// NewProgressRenderer returns a renderer writing to out, a terminal width columns wide,
+// that colors the tally when color is true
+func NewProgressRenderer(out io.Writer, width int, color bool) *ProgressRenderer {
+	if width <= 0 {
+		width = defaultWidth
+	}
+	return &ProgressRenderer{
+		out:   out,
+		width: width,
+		color: color,
+		start: time.Now(),
+		stop:  make(chan struct{}),
+		done:  make(chan struct{}),
+	}
+}
+
+// Start draws the progress line and redraws it periodically until Stop
+func (r *ProgressRenderer) Start() {
+	if r == nil {
+		return
+	}
+	r.mu.Lock()
+	r.start = time.Now()
+	r.started = true
+	r.render()
+	r.mu.Unlock()
+
+	go func() {
+		defer close(r.done)
+		ticker := time.NewTicker(redrawInterval)
+		defer ticker.Stop()
+		for {
+			select {
+			case <-r.stop:
+				return
+			case <-ticker.C:
+				r.mu.Lock()
+				if !r.stopped {
+					r.render()
+				}
+				r.mu.Unlock()
+			}
+		}
+	}()
+}
+
+// Update replaces the progress shown and redraws the line
+func (r *ProgressRenderer) Update(progress Progress) {
+	if r == nil {
+		return
+	}
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	r.progress = progress
+	if !r.stopped {
+		r.render()
+	}
+}
+
+// Suspend clears the progress line, runs print, which writes output that belongs
+// above the line, and draws the line again below it. A nil or stopped renderer just
+// runs print.
+func (r *ProgressRenderer) Suspend(print func()) {
+	if r == nil {
+		print()
+		return
+	}
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	if r.stopped {
+		print()
+		return
+	}
+	r.clear()
+	print()
+	r.render()
+}
+
+// Stop clears the progress line for good, leaving the terminal as it was before Start.
+// It is safe to call more than once, and on a nil renderer.
+func (r *ProgressRenderer) Stop() {
+	if r == nil {
+		return
+	}
+	r.mu.Lock()
+	if r.stopped {
+		r.mu.Unlock()
+		return
+	}
+	r.stopped = true
+	r.clear()
+	started := r.started
+	r.mu.Unlock()
+
+	close(r.stop)
+	if started {
+		<-r.done
+	}
+}
+
+// render draws the progress line over the current one. Caller must hold r.mu.
+func (r *ProgressRenderer) render() {
+	_, _ = io.WriteString(r.out, clearLine+r.line(time.Since(r.start)))
+	r.shown = true
+}
+
+// clear erases the progress line. Caller must hold r.mu.
+func (r *ProgressRenderer) clear() {
+	if r.shown {
+		_, _ = io.WriteString(r.out, clearLine)
+		r.shown = false
+	}
+}
+
+// line returns the progress line after elapsed time, cut to the terminal width. The
+// last column is left free so the line never wraps. Caller must hold r.mu.
+func (r *ProgressRenderer) line(elapsed time.Duration) string {
+	p := r.progress
+	type part struct{ text, color string }
+	parts := []part{
+		{formatElapsed(elapsed), ""},
+		{fmt.Sprintf("%d/%d groups", p.CompletedGroups, p.TotalGroups), ""},
+		{fmt.Sprintf("%d passed", p.Passed), green},
+		{fmt.Sprintf("%d failed", p.Failed), red},
+		{fmt.Sprintf("%d skipped", p.Skipped), yellow},
+	}
+	if p.Failed == 0 {
+		parts[3].color = ""
+	}
+	if p.Skipped == 0 {
+		parts[4].color = ""
+	}
+
+	const separator = "  "
+	var sb strings.Builder
+	width := 0
+	for i, part := range parts {
+		next := utf8.RuneCountInString(part.text)
+		if i > 0 {
+			next += len(separator)
+		}
+		if width+next > r.width-1 {
+			break
+		}
+		if i > 0 {
+			sb.WriteString(separator)
+		}
+		if r.color && part.color != "" {
+			sb.WriteString(part.color + part.text + reset)
+		} else {
+			sb.WriteString(part.text)
+		}
+		width += next
+	}
+	return sb.String()
+}
+
+// formatElapsed formats the elapsed time as on the rest of the console, e.g. "[T+ 1m5s]"
+func formatElapsed(elapsed time.Duration) string {
+	total := int(elapsed.Seconds())
+	switch {
+	case total < 60:
+		return fmt.Sprintf("[T+ %ds]", total)
+	case total < 3600:
+		return fmt.Sprintf("[T+ %dm%ds]", total/60, total%60)
+	default:
+		return fmt.Sprintf("[T+ %dh%dm%ds]", total/3600, (total%3600)/60, total%60)
+	}
+}
+
+// Width returns the width of the terminal f is attached to. When it can't be read,
+// COLUMNS is used, and then a default of 80.
+func Width(f *os.File) int {
+	if width, ok := terminalWidth(f); ok && width > 0 {
+		return width
+	}
+	if columns, err := strconv.Atoi(os.Getenv("COLUMNS")); err == nil && columns > 0 {
+		return columns
+	}
+	return defaultWidth
+}
+
+// IsTerminal reports whether f is an interactive terminal
+func IsTerminal(f *os.File) bool {
+	_, ok := terminalWidth(f)
+	return ok
+}
@@ -0,0 +1,72 @@
+package console
+
+import (
+	"strings"
+	"testing"
+	"time"
+)
+
+func TestProgressRenderer_Line(t *testing.T) {
+	r := NewProgressRenderer(&strings.Builder{}, 120, false)
+	r.progress = Progress{CompletedGroups: 3, TotalGroups: 10, Passed: 25, Failed: 2, Skipped: 1}
+
+	if got, want := r.line(65*time.Second), "[T+ 1m5s]  3/10 groups  25 passed  2 failed  1 skipped"; got != want {
+		t.Errorf("line = %q, want %q", got, want)
+	}
+
+	// Parts that don't fit the terminal are dropped, leaving the last column free
+	r.width = 31
+	if got, want := r.line(time.Second), "[T+ 1s]  3/10 groups"; got != want {
+		t.Errorf("line = %q, want %q", got, want)
+	}
+}
+
+func TestProgressRenderer_Colors(t *testing.T) {
+	r := NewProgressRenderer(&strings.Builder{}, 120, true)
+	r.progress = Progress{Passed: 1, Failed: 1}
+
+	line := r.line(0)
+	if !strings.Contains(line, green+"1 passed"+reset) || !strings.Contains(line, red+"1 failed"+reset) {
+		t.Errorf("Expected a colored tally, got %q", line)
+	}
+	if !strings.Contains(line, "  0 skipped") {
+		t.Errorf("Expected no color for a zero count, got %q", line)
+	}
+}
+
+func TestProgressRenderer_SuspendAndStop(t *testing.T) {
+	var out strings.Builder
+	r := NewProgressRenderer(&out, 80, false)
+	r.Update(Progress{CompletedGroups: 1, TotalGroups: 2, Passed: 4})
+	r.Suspend(func() { out.WriteString("FAIL(1) $trun_dir/reports/a/index.md\n") })
+	r.Stop()
+	r.Stop()
+
+	// The printed line replaces the progress line, which is drawn again below it
+	// and erased on Stop
+	got := out.String()
+	want := clearLine + "FAIL(1) $trun_dir/reports/a/index.md\n" + clearLine + "[T+ 0s]  1/2 groups  4 passed  0 failed  0 skipped" + clearLine
+	if !strings.HasSuffix(got, want) {
+		t.Errorf("Output = %q, want it to end with %q", got, want)
+	}
+
+	// Output after Stop is written as is
+	out.Reset()
+	r.Suspend(func() { out.WriteString("done\n") })
+	r.Update(Progress{})
+	if out.String() != "done\n" {
+		t.Errorf("Output after Stop = %q, want %q", out.String(), "done\n")
+	}
+}
+
+func TestProgressRenderer_Nil(t *testing.T) {
+	var r *ProgressRenderer
+	printed := false
+	r.Start()
+	r.Update(Progress{})
+	r.Suspend(func() { printed = true })
+	r.Stop()
+	if !printed {
+		t.Error("Expected Suspend on a nil renderer to run print")
+	}
+}
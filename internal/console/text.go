@@ -0,0 +1,112 @@
+package console
+
+import (
+	"fmt"
+	"os"
+	"regexp"
+	"strings"
+	"unicode/utf8"
+)
+
+// dim is the ANSI sequence for faint text, used for output quoted from the tests
+const dim = "\x1b[2m"
+
+// ansiEscapeRegex matches ANSI escape sequences: CSI sequences such as colors and
+// cursor movement, OSC sequences such as hyperlinks (ended by BEL or ESC \), and the
+// other two- and three-byte escapes
+var ansiEscapeRegex = regexp.MustCompile("\x1b\\[[0-?]*[ -/]*[@-~]|\x1b\\][^\x07\x1b]*(?:\x07|\x1b\\\\)|\x1b[ -/]*[0-~]")
+
+// StripANSI removes ANSI escape sequences from s. The text of a hyperlink is kept.
+func StripANSI(s string) string {
+	return ansiEscapeRegex.ReplaceAllString(s, "")
+}
+
+// ValidUTF8 returns s with each byte that isn't part of valid UTF-8 replaced by U+FFFD,
+// as encoding/json does when it encodes or decodes a string
+func ValidUTF8(s string) string {
+	if utf8.ValidString(s) {
+		return s
+	}
+	var sb strings.Builder
+	sb.Grow(len(s) + 8)
+	for _, r := range s {
+		sb.WriteRune(r)
+	}
+	return sb.String()
+}
+
+// PlainText returns terminal output as it reads once displayed: escape sequences are
+// removed, and text after a bare carriage return overwrites the start of its line, as
+// progress bars do. Line endings of "\r\n" become "\n", and bytes that aren't valid
+// UTF-8 become U+FFFD.
+func PlainText(s string) string {
+	s = StripANSI(ValidUTF8(s))
+	if !strings.Contains(s, "\r") {
+		return s
+	}
+	lines := strings.Split(s, "\n")
+	for i, line := range lines {
+		if !strings.Contains(line, "\r") {
+			continue
+		}
+		var shown []rune
+		col := 0
+		for _, r := range line {
+			if r == '\r' {
+				col = 0
+				continue
+			}
+			if col < len(shown) {
+				shown[col] = r
+			} else {
+				shown = append(shown, r)
+			}
+			col++
+		}
+		lines[i] = string(shown)
+	}
+	return strings.Join(lines, "\n")
+}
+
+// Dim returns s as faint text when color is true, and unchanged otherwise
+func Dim(s string, color bool) string {
+	if !color || s == "" {
+		return s
+	}
+	return dim + s + reset
+}
+
+// ColorMode is the --color policy for 3pio's own console output
+type ColorMode string
+
+const (
+	// ColorAuto uses colors in a terminal, unless NO_COLOR is set
+	ColorAuto ColorMode = "auto"
+	// ColorAlways uses colors even when stdout is piped or NO_COLOR is set
+	ColorAlways ColorMode = "always"
+	// ColorNever never uses colors
+	ColorNever ColorMode = "never"
+)
+
+// ParseColorMode parses the value of --color
+func ParseColorMode(value string) (ColorMode, error) {
+	switch mode := ColorMode(value); mode {
+	case ColorAuto, ColorAlways, ColorNever:
+		return mode, nil
+	default:
+		return "", fmt.Errorf("--color must be auto, always or never, got %q", value)
+	}
+}
+
+// Enabled reports whether output to f uses colors under the policy. The empty mode
+// is ColorAuto.
+func (m ColorMode) Enabled(f *os.File) bool {
+	switch m {
+	case ColorAlways:
+		return true
+	case ColorNever:
+		return false
+	default:
+		return os.Getenv("NO_COLOR") == "" && IsTerminal(f)
+	}
+}
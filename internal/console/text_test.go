@@ -0,0 +1,120 @@
+package console
+
+import (
+	"os"
+	"testing"
+)
+
+func TestStripANSI(t *testing.T) {
+	tests := []struct {
+		name, input, want string
+	}{
+		{
+			"cargo test result",
+			"test tests::test_add ... \x1b[32mok\x1b[0m\ntest result: \x1b[31mFAILED\x1b[0m. 1 passed; 1 failed",
+			"test tests::test_add ... ok\ntest result: FAILED. 1 passed; 1 failed",
+		},
+		{
+			"cargo build error",
+			"\x1b[0m\x1b[1m\x1b[38;5;9merror[E0308]\x1b[0m\x1b[0m\x1b[1m: mismatched types\x1b[0m\n \x1b[0m\x1b[1m\x1b[38;5;12m--> \x1b[0m\x1b[0msrc/lib.rs:2:5\x1b[0m",
+			"error[E0308]: mismatched types\n --> src/lib.rs:2:5",
+		},
+		{
+			"jest assertion",
+			"\x1b[2mexpect(\x1b[22m\x1b[31mreceived\x1b[39m\x1b[2m).\x1b[22mtoBe\x1b[2m(\x1b[22m\x1b[32mexpected\x1b[39m\x1b[2m)\x1b[22m\n\nExpected: \x1b[32m5\x1b[39m\nReceived: \x1b[31m4\x1b[39m",
+			"expect(received).toBe(expected)\n\nExpected: 5\nReceived: 4",
+		},
+		{
+			"hyperlink ended by ESC backslash",
+			"see \x1b]8;;file:///src/lib.rs\x1b\\src/lib.rs\x1b]8;;\x1b\\ for details",
+			"see src/lib.rs for details",
+		},
+		{
+			"hyperlink ended by BEL",
+			"\x1b]8;;https://jestjs.io\x07docs\x1b]8;;\x07",
+			"docs",
+		},
+		{
+			"cursor movement and charset",
+			"\x1b[2K\x1b[1Gdone\x1b(B\x1b[?25h",
+			"done",
+		},
+		{"plain text", "no escapes here\n", "no escapes here\n"},
+	}
+	for _, tt := range tests {
+		if got := StripANSI(tt.input); got != tt.want {
+			t.Errorf("%s: StripANSI = %q, want %q", tt.name, got, tt.want)
+		}
+	}
+}
+
+func TestPlainText(t *testing.T) {
+	tests := []struct {
+		input, want string
+	}{
+		// A progress bar redrawn in place reads as its last frame
+		{"Compiling [=>  ] 1/4\rCompiling [===>] 4/4\nFinished\n", "Compiling [===>] 4/4\nFinished\n"},
+		// A shorter frame only overwrites the start of the line
+		{"downloading 100%\rdone", "doneloading 100%"},
+		{"\x1b[2K\r\x1b[32m✓\x1b[39m adds 1 + 2\r\n", "✓ adds 1 + 2\n"},
+		{"line\r\n", "line\n"},
+		{"no carriage return", "no carriage return"},
+	}
+	for _, tt := range tests {
+		if got := PlainText(tt.input); got != tt.want {
+			t.Errorf("PlainText(%q) = %q, want %q", tt.input, got, tt.want)
+		}
+	}
+}
+
+func TestValidUTF8(t *testing.T) {
+	tests := []struct {
+		input, want string
+	}{
+		{"valid ✓ text", "valid ✓ text"},
+		{"\xff\xfe header", "\uFFFD\uFFFD header"},
+		{"caf\xe9", "caf\uFFFD"},
+		// A character cut in half is one invalid byte per byte
+		{"\xe2\x9c", "\uFFFD\uFFFD"},
+	}
+	for _, tt := range tests {
+		if got := ValidUTF8(tt.input); got != tt.want {
+			t.Errorf("ValidUTF8(%q) = %q, want %q", tt.input, got, tt.want)
+		}
+	}
+	if got := PlainText("\x1b[31m\xff\xfe\x1b[0m\r\n"); got != "\uFFFD\uFFFD\n" {
+		t.Errorf("PlainText = %q, want invalid bytes replaced", got)
+	}
+}
+
+func TestParseColorMode(t *testing.T) {
+	for _, value := range []string{"auto", "always", "never"} {
+		if mode, err := ParseColorMode(value); err != nil || string(mode) != value {
+			t.Errorf("ParseColorMode(%q) = %q, %v", value, mode, err)
+		}
+	}
+	for _, value := range []string{"", "yes", "ALWAYS"} {
+		if _, err := ParseColorMode(value); err == nil {
+			t.Errorf("Expected an error for %q", value)
+		}
+	}
+}
+
+func TestColorMode_Enabled(t *testing.T) {
+	r, w, err := os.Pipe()
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer func() { _ = r.Close() }()
+	defer func() { _ = w.Close() }()
+
+	// always and never override NO_COLOR and the terminal check; auto needs a terminal
+	t.Setenv("NO_COLOR", "1")
+	if !ColorAlways.Enabled(w) {
+		t.Error("Expected colors with always, even under NO_COLOR on a pipe")
+	}
+	t.Setenv("NO_COLOR", "")
+	if ColorNever.Enabled(w) || ColorAuto.Enabled(w) || ColorMode("").Enabled(w) {
+		t.Error("Expected no colors with never, or with auto on a pipe")
+	}
+}
@@ -0,0 +1,14 @@
+package console
+
+// Verbosity is how much the console shows while the tests run. It never changes what
+// is written to the run directory.
+type Verbosity int
+
+const (
+	// Normal shows a block for each group with failures, then the summary
+	Normal Verbosity = iota
+	// Quiet (-q, --quiet) shows only the failed tests and the summary
+	Quiet
+	// Verbose (-v, --verbose) also shows every test case as it completes
+	Verbose
+)
@@ -0,0 +1,287 @@
+// Package coverage reads the code coverage reports the tools that measure it write,
+// lcov tracefiles and llvm-cov's JSON export, into the totals 3pio reports
+package coverage
+
+import (
+	"bufio"
+	"encoding/json"
+	"fmt"
+	"io"
+	"math"
+	"os"
+	"path/filepath"
+	"sort"
+	"strconv"
+	"strings"
+)
+
+// Report formats Read understands
+const (
+	FormatLCOV = "lcov" // lcov tracefile, e.g. cargo llvm-cov --lcov
+	FormatJSON = "json" // llvm-cov export JSON, e.g. cargo llvm-cov --json
+)
+
+// Summary is the code coverage a run measured
+type Summary struct {
+	Report    string `json:"report"` // File it was read from, as the command named it
+	Lines     Counts `json:"lines"`
+	Functions Counts `json:"functions"`
+	Files     []File `json:"-"` // Files under the root Read was given, relative to it with forward slashes
+}
+
+// Counts is how many of the lines or functions ran
+type Counts struct {
+	Covered int     `json:"covered"`
+	Total   int     `json:"total"`
+	Percent float64 `json:"percent"` // Rounded to 2 decimals, 0 when there is nothing to cover
+}
+
+// File is the coverage of one source file
+type File struct {
+	Path      string
+	Lines     Counts
+	Functions Counts
+}
+
+// newCounts returns the counts with their percentage
+func newCounts(covered, total int) Counts {
+	c := Counts{Covered: covered, Total: total}
+	if total > 0 {
+		c.Percent = math.Round(float64(covered)*10000/float64(total)) / 100
+	}
+	return c
+}
+
+// Read reads the coverage report at path in format. The totals are those of the whole
+// report; Files has only the files under root, as reports may also cover dependencies.
+func Read(path, format, root string) (*Summary, error) {
+	f, err := os.Open(path)
+	if err != nil {
+		return nil, err
+	}
+	defer func() { _ = f.Close() }()
+
+	var summary *Summary
+	switch format {
+	case FormatLCOV:
+		summary, err = parseLCOV(f, root)
+	case FormatJSON:
+		summary, err = parseLLVMCovJSON(f, root)
+	default:
+		return nil, fmt.Errorf("unknown coverage report format %q", format)
+	}
+	if err != nil {
+		return nil, fmt.Errorf("failed to read %s: %w", path, err)
+	}
+	summary.Report = path
+	return summary, nil
+}
+
+// fileCounts accumulates the counts of a file, which a tracefile may list more than once
+type fileCounts struct {
+	lines, coveredLines, functions, coveredFunctions int
+}
+
+// parseLCOV reads an lcov tracefile. Each record's LF/LH and FNF/FNH give its counts;
+// the DA and FNDA lines are counted when a tool leaves them out.
+//
+//	SF:/src/app/src/lib.rs
+//	FN:3,_RNvCs1_3app3add
+//	FNDA:1,_RNvCs1_3app3add
+//	FNF:1
+//	FNH:1
+//	DA:3,1
+//	DA:4,0
+//	LF:2
+//	LH:1
+//	end_of_record
+func parseLCOV(r io.Reader, root string) (*Summary, error) {
+	files := make(map[string]*fileCounts)
+	var order []string
+
+	var source string
+	var record, counted fileCounts
+	var haveLines, haveFunctions bool
+	finish := func() {
+		if source == "" {
+			return
+		}
+		if !haveLines {
+			record.lines, record.coveredLines = counted.lines, counted.coveredLines
+		}
+		if !haveFunctions {
+			record.functions, record.coveredFunctions = counted.functions, counted.coveredFunctions
+		}
+		total, ok := files[source]
+		if !ok {
+			total = &fileCounts{}
+			files[source] = total
+			order = append(order, source)
+		}
+		total.lines += record.lines
+		total.coveredLines += record.coveredLines
+		total.functions += record.functions
+		total.coveredFunctions += record.coveredFunctions
+		source, record, counted, haveLines, haveFunctions = "", fileCounts{}, fileCounts{}, false, false
+	}
+
+	scanner := bufio.NewScanner(r)
+	scanner.Buffer(make([]byte, 0, 64*1024), 16*1024*1024)
+	for scanner.Scan() {
+		line := strings.TrimSpace(scanner.Text())
+		key, value, _ := strings.Cut(line, ":")
+		switch key {
+		case "SF":
+			finish()
+			source = value
+		case "LF":
+			record.lines, _ = strconv.Atoi(value)
+			haveLines = true
+		case "LH":
+			record.coveredLines, _ = strconv.Atoi(value)
+			haveLines = true
+		case "FNF":
+			record.functions, _ = strconv.Atoi(value)
+			haveFunctions = true
+		case "FNH":
+			record.coveredFunctions, _ = strconv.Atoi(value)
+			haveFunctions = true
+		case "DA":
+			// DA:<line>,<hits>[,<checksum>]
+			counted.lines++
+			if fields := strings.Split(value, ","); len(fields) > 1 && fields[1] != "0" {
+				counted.coveredLines++
+			}
+		case "FNDA":
+			// FNDA:<hits>,<name>
+			counted.functions++
+			if hits, _, _ := strings.Cut(value, ","); hits != "0" {
+				counted.coveredFunctions++
+			}
+		default:
+			if line == "end_of_record" {
+				finish()
+			}
+		}
+	}
+	if err := scanner.Err(); err != nil {
+		return nil, err
+	}
+	finish()
+	if len(files) == 0 {
+		return nil, fmt.Errorf("no source files in the lcov report")
+	}
+
+	summary := &Summary{}
+	var totals fileCounts
+	for _, source := range order {
+		counts := files[source]
+		totals.lines += counts.lines
+		totals.coveredLines += counts.coveredLines
+		totals.functions += counts.functions
+		totals.coveredFunctions += counts.coveredFunctions
+		summary.addFile(source, root, counts)
+	}
+	summary.Lines = newCounts(totals.coveredLines, totals.lines)
+	summary.Functions = newCounts(totals.coveredFunctions, totals.functions)
+	return summary, nil
+}
+
+// llvm-cov export's JSON, as --summary-only writes it; without it each file also has
+// its segments, branches and expansions, which aren't needed for the totals
+type llvmCovExport struct {
+	Type string `json:"type"` // "llvm.coverage.json.export"
+	Data []struct {
+		Files []struct {
+			Filename string         `json:"filename"`
+			Summary  llvmCovSummary `json:"summary"`
+		} `json:"files"`
+		Totals llvmCovSummary `json:"totals"`
+	} `json:"data"`
+}
+
+type llvmCovSummary struct {
+	Lines     llvmCovCounts `json:"lines"`
+	Functions llvmCovCounts `json:"functions"`
+}
+
+type llvmCovCounts struct {
+	Count   int `json:"count"`
+	Covered int `json:"covered"`
+}
+
+// parseLLVMCovJSON reads the JSON llvm-cov export writes
+func parseLLVMCovJSON(r io.Reader, root string) (*Summary, error) {
+	var export llvmCovExport
+	if err := json.NewDecoder(r).Decode(&export); err != nil {
+		return nil, err
+	}
+	if len(export.Data) == 0 {
+		return nil, fmt.Errorf("no coverage data in the llvm-cov export (type %q)", export.Type)
+	}
+
+	summary := &Summary{}
+	var totals fileCounts
+	for _, data := range export.Data {
+		totals.lines += data.Totals.Lines.Count
+		totals.coveredLines += data.Totals.Lines.Covered
+		totals.functions += data.Totals.Functions.Count
+		totals.coveredFunctions += data.Totals.Functions.Covered
+		for _, file := range data.Files {
+			summary.addFile(file.Filename, root, &fileCounts{
+				lines:            file.Summary.Lines.Count,
+				coveredLines:     file.Summary.Lines.Covered,
+				functions:        file.Summary.Functions.Count,
+				coveredFunctions: file.Summary.Functions.Covered,
+			})
+		}
+	}
+	summary.Lines = newCounts(totals.coveredLines, totals.lines)
+	summary.Functions = newCounts(totals.coveredFunctions, totals.functions)
+	return summary, nil
+}
+
+// addFile adds a file of the report to Files when it is under root
+func (s *Summary) addFile(source, root string, counts *fileCounts) {
+	path := source
+	if root != "" {
+		if !filepath.IsAbs(path) {
+			path = filepath.Join(root, path)
+		}
+		rel, err := filepath.Rel(root, path)
+		if err != nil || rel == ".." || strings.HasPrefix(rel, ".."+string(filepath.Separator)) {
+			return
+		}
+		path = rel
+	}
+	s.Files = append(s.Files, File{
+		Path:      filepath.ToSlash(path),
+		Lines:     newCounts(counts.coveredLines, counts.lines),
+		Functions: newCounts(counts.coveredFunctions, counts.functions),
+	})
+}
+
+// LeastCovered returns up to n files with lines to cover, lowest line coverage first,
+// then the most uncovered lines
+func (s *Summary) LeastCovered(n int) []File {
+	var files []File
+	for _, file := range s.Files {
+		if file.Lines.Total > 0 {
+			files = append(files, file)
+		}
+	}
+	sort.SliceStable(files, func(i, j int) bool {
+		a, b := files[i].Lines, files[j].Lines
+		if a.Percent != b.Percent {
+			return a.Percent < b.Percent
+		}
+		if missedA, missedB := a.Total-a.Covered, b.Total-b.Covered; missedA != missedB {
+			return missedA > missedB
+		}
+		return files[i].Path < files[j].Path
+	})
+	if len(files) > n {
+		files = files[:n]
+	}
+	return files
+}
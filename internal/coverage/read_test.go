@@ -0,0 +1,95 @@
+package coverage
+
+import (
+	"os"
+	"path/filepath"
+	"reflect"
+	"testing"
+)
+
+func TestRead_LCOV(t *testing.T) {
+	// As cargo llvm-cov --lcov writes it: a workspace file, one without LF/LH, and a
+	// dependency outside the workspace
+	root := filepath.Join(t.TempDir(), "app")
+	lib, parser := filepath.Join(root, "src", "lib.rs"), filepath.Join(root, "src", "parser.rs")
+	dep := filepath.Join(filepath.Dir(root), "registry", "serde", "src", "de.rs")
+	tracefile := "SF:" + lib + "\n" +
+		"FN:3,_RNvCs1_3app3add\nFNDA:1,_RNvCs1_3app3add\nFNF:1\nFNH:1\n" +
+		"DA:3,1\nDA:4,1\nDA:5,0\nDA:6,1\nLF:4\nLH:3\nend_of_record\n" +
+		"SF:" + parser + "\n" +
+		"FNDA:0,_RNvCs1_3app5parse\nFNDA:2,_RNvCs1_3app4lex\n" +
+		"DA:1,0\nDA:2,0\nDA:3,4\nend_of_record\n" +
+		"SF:" + dep + "\n" +
+		"FNF:2\nFNH:0\nLF:10\nLH:0\nend_of_record\n"
+	path := filepath.Join(t.TempDir(), "cov.lcov")
+	if err := os.WriteFile(path, []byte(tracefile), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	summary, err := Read(path, FormatLCOV, root)
+	if err != nil {
+		t.Fatalf("Read failed: %v", err)
+	}
+	if expected := (Counts{Covered: 4, Total: 17, Percent: 23.53}); summary.Lines != expected {
+		t.Errorf("Expected line totals %+v, got %+v", expected, summary.Lines)
+	}
+	if expected := (Counts{Covered: 2, Total: 5, Percent: 40}); summary.Functions != expected {
+		t.Errorf("Expected function totals %+v, got %+v", expected, summary.Functions)
+	}
+	expected := []File{
+		{Path: "src/parser.rs", Lines: Counts{Covered: 1, Total: 3, Percent: 33.33}, Functions: Counts{Covered: 1, Total: 2, Percent: 50}},
+		{Path: "src/lib.rs", Lines: Counts{Covered: 3, Total: 4, Percent: 75}, Functions: Counts{Covered: 1, Total: 1, Percent: 100}},
+	}
+	if files := summary.LeastCovered(10); !reflect.DeepEqual(files, expected) {
+		t.Errorf("Expected the workspace files least covered first %+v, got %+v", expected, files)
+	}
+	if files := summary.LeastCovered(1); len(files) != 1 || files[0].Path != "src/parser.rs" {
+		t.Errorf("Expected only the least covered file, got %+v", files)
+	}
+}
+
+func TestRead_LLVMCovJSON(t *testing.T) {
+	root := filepath.Join(t.TempDir(), "app")
+	export := `{"data":[{"files":[` +
+		`{"filename":` + quote(filepath.Join(root, "src", "lib.rs")) + `,"summary":{"lines":{"count":20,"covered":18,"percent":90},"functions":{"count":4,"covered":4,"percent":100}}},` +
+		`{"filename":` + quote(filepath.Join(root, "src", "io.rs")) + `,"summary":{"lines":{"count":10,"covered":2,"percent":20},"functions":{"count":2,"covered":1,"percent":50}}}` +
+		`],"totals":{"lines":{"count":30,"covered":20,"percent":66.66666666666667},"functions":{"count":6,"covered":5,"percent":83.33333333333334}}}],` +
+		`"type":"llvm.coverage.json.export","version":"2.0.1"}`
+	path := filepath.Join(t.TempDir(), "cov.json")
+	if err := os.WriteFile(path, []byte(export), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	summary, err := Read(path, FormatJSON, root)
+	if err != nil {
+		t.Fatalf("Read failed: %v", err)
+	}
+	if summary.Report != path || summary.Lines.Percent != 66.67 || summary.Functions.Percent != 83.33 {
+		t.Errorf("Expected 66.67%% of lines and 83.33%% of functions from %s, got %+v", path, summary)
+	}
+	if files := summary.LeastCovered(10); len(files) != 2 || files[0].Path != "src/io.rs" {
+		t.Errorf("Expected src/io.rs least covered, got %+v", files)
+	}
+}
+
+func TestRead_Errors(t *testing.T) {
+	dir := t.TempDir()
+	if _, err := Read(filepath.Join(dir, "missing.lcov"), FormatLCOV, dir); !os.IsNotExist(err) {
+		t.Errorf("Expected a not exist error, got %v", err)
+	}
+	empty := filepath.Join(dir, "empty.lcov")
+	if err := os.WriteFile(empty, nil, 0644); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := Read(empty, FormatLCOV, dir); err == nil {
+		t.Error("Expected an error for a tracefile without source files")
+	}
+	if _, err := Read(empty, "cobertura", dir); err == nil {
+		t.Error("Expected an error for an unknown format")
+	}
+}
+
+// quote returns path as a JSON string, escaping the backslashes of Windows paths
+func quote(path string) string {
+	return `"` + filepath.ToSlash(path) + `"`
+}
@@ -11,6 +11,9 @@ const (
 	EventTypeCollectionStart  EventType = "collectionStart"
 	EventTypeCollectionError  EventType = "collectionError"
 	EventTypeCollectionFinish EventType = "collectionFinish"
+	EventTypeBuildError       EventType = "buildError"
+	EventTypeBinaryCrash      EventType = "binaryCrash"
+	EventTypeRunWarnings      EventType = "runWarnings"
 )
 
 // TestStatus represents the status of a test
@@ -25,6 +28,45 @@ const (
 	TestStatusNoTests TestStatus = "NO_TESTS"
 	TestStatusXFail   TestStatus = "XFAIL" // Test failed as expected
 	TestStatusXPass   TestStatus = "XPASS" // Test passed unexpectedly
+	TestStatusBench   TestStatus = "BENCH" // Benchmark result, not a pass/fail test
+	TestStatusWarn    TestStatus = "WARN"  // Group completed with a warning, e.g. a benchmark regressed
+	TestStatusFlaky   TestStatus = "FLAKY" // Test failed, then passed when retried
+	TestStatusError   TestStatus = "ERROR" // Test's setup or teardown failed rather than the test (pytest fixtures)
+)
+
+// Phases of a test's setup or teardown that raised, making it an ERROR test
+const (
+	ErrorPhaseSetup    = "setup"    // A fixture failed before the test ran, so it never did
+	ErrorPhaseTeardown = "teardown" // A fixture failed to clean up after the test
+)
+
+// Reasons a test or group was reported as SKIP
+const (
+	SkipReasonIgnored     = "ignored"       // Test is marked to not run by default (e.g., Rust #[ignore])
+	SkipReasonNotRun      = "not_run"       // Test never finished: its test binary crashed, or never ran (e.g., after a failing binary)
+	SkipReasonNoTestFiles = "no_test_files" // Group has no tests to run, e.g. a Go package without _test.go files
+	SkipReasonTodo        = "todo"          // Test is a placeholder with nothing to run yet (e.g., Vitest test.todo)
+)
+
+// Error types for tests that never finished on their own
+const (
+	ErrorTypeCrashed  = "CRASHED"   // Test was running when its test binary crashed
+	ErrorTypeTimedOut = "TIMED_OUT" // Test was running when the inactivity timeout or the runner's own timeout stopped the run
+)
+
+// Failure kinds tell why a test or group failed, since a failed assertion, a panic in
+// the code under test and a test binary killed by the OOM killer call for different fixes
+const (
+	FailureKindAssertionFailed   = "ASSERTION_FAILED"   // An assertion of the test didn't hold, or #[should_panic] wasn't met
+	FailureKindPanicked          = "PANICKED"           // The code panicked or threw outside an assertion (unwrap on None, index out of bounds)
+	FailureKindTimedOut          = "TIMED_OUT"          // The test was still running when a timeout stopped it
+	FailureKindCrashed           = "CRASHED"            // The test binary died, e.g. on a signal or out of memory
+	FailureKindDataRace          = "DATA_RACE"          // The race detector caught unsynchronized access (go test -race)
+	FailureKindSnapshotMismatch  = "SNAPSHOT_MISMATCH"  // The value didn't match its stored snapshot (Rust insta)
+	FailureKindUndefinedBehavior = "UNDEFINED_BEHAVIOR" // miri caught undefined behavior in unsafe code (cargo miri test)
+	FailureKindBuildError        = "BUILD_ERROR"        // The tests didn't compile or load
+	FailureKindHarnessError      = "HARNESS_ERROR"      // The test framework failed outside the tests, e.g. in setup
+	FailureKindUnknown           = "UNKNOWN"            // The runner didn't say
 )
 
 // Event is the base interface for all IPC events
@@ -44,7 +86,8 @@ func (e RunCompleteEvent) Type() EventType { return EventTypeRunComplete }
 type CollectionStartEvent struct {
 	EventType EventType `json:"eventType"`
 	Payload   struct {
-		Phase string `json:"phase"`
+		Phase   string `json:"phase"`
+		Workers int    `json:"workers,omitempty"` // Parallel workers, e.g. pytest-xdist's -n; 0 without
 	} `json:"payload"`
 }
 
@@ -72,6 +115,66 @@ type CollectionFinishEvent struct {
 
 func (e CollectionFinishEvent) Type() EventType { return EventTypeCollectionFinish }
 
+// BuildErrorEvent represents a compiler error that stopped tests from building (cargo specific)
+type BuildErrorEvent struct {
+	EventType EventType         `json:"eventType"`
+	Payload   BuildErrorPayload `json:"payload"`
+}
+
+func (e BuildErrorEvent) Type() EventType { return EventTypeBuildError }
+
+// BuildErrorPayload contains a single compiler diagnostic
+type BuildErrorPayload struct {
+	Target   string `json:"target,omitempty"` // Crate target that failed to compile
+	Code     string `json:"code,omitempty"`   // Compiler error code, e.g. "E0554"
+	Message  string `json:"message"`
+	Rendered string `json:"rendered,omitempty"` // Full compiler output for the diagnostic
+	File     string `json:"file,omitempty"`
+	Line     int    `json:"line,omitempty"`
+	Column   int    `json:"column,omitempty"`
+}
+
+// BinaryCrashEvent reports a test binary that died before finishing its run (cargo specific)
+type BinaryCrashEvent struct {
+	EventType EventType          `json:"eventType"`
+	Payload   BinaryCrashPayload `json:"payload"`
+}
+
+func (e BinaryCrashEvent) Type() EventType { return EventTypeBinaryCrash }
+
+// BinaryCrashPayload describes how a test binary crashed
+type BinaryCrashPayload struct {
+	Target     string `json:"target"`               // Crate group of the crashed binary
+	TestName   string `json:"testName,omitempty"`   // Test that was running, e.g. "tests::test_abort"
+	Signal     int    `json:"signal,omitempty"`     // Signal number, e.g. 6
+	SignalName string `json:"signalName,omitempty"` // Signal name, e.g. "SIGABRT"
+	ExitCode   int    `json:"exitCode,omitempty"`   // Exit code when the binary exited without a signal
+	Message    string `json:"message"`
+}
+
+// RunWarningsEvent lists the warnings the runner recorded during the run, e.g. the
+// deprecations of pytest's warnings summary (pytest specific)
+type RunWarningsEvent struct {
+	EventType EventType          `json:"eventType"`
+	Payload   RunWarningsPayload `json:"payload"`
+}
+
+func (e RunWarningsEvent) Type() EventType { return EventTypeRunWarnings }
+
+// RunWarningsPayload holds the warnings of a run, in the order they were recorded
+type RunWarningsPayload struct {
+	Warnings []RunWarning `json:"warnings"`
+}
+
+// RunWarning is a single recorded warning
+type RunWarning struct {
+	Category string `json:"category"`         // e.g. "DeprecationWarning"
+	Message  string `json:"message"`          // The warning's message
+	NodeID   string `json:"nodeId,omitempty"` // Test that triggered it, empty during collection or session setup
+	File     string `json:"file,omitempty"`   // Source file that issued the warning
+	Line     int    `json:"line,omitempty"`   // Line in File
+}
+
 // TestCase represents a test case in the test run state
 type TestCase struct {
 	Name     string     `json:"name"`
@@ -105,3 +208,16 @@ type TestRunState struct {
 	TestFiles      []TestFile `json:"testFiles"`
 	ErrorDetails   string     `json:"errorDetails,omitempty"` // Error details when status is ERROR
 }
+
+// FailedTestsFileName is the file in each run directory listing the run's failed tests,
+// which --rerun-failed reads back
+const FailedTestsFileName = "failed-tests.json"
+
+// FailedTest identifies a failed test by its group hierarchy, as in testCase events
+type FailedTest struct {
+	Name        string   `json:"name"`
+	ParentNames []string `json:"parentNames"`
+	TestID      string   `json:"testId,omitempty"`      // Stable test ID; missing in files of older 3pio versions
+	FailureKind string   `json:"failureKind,omitempty"` // FailureKind* constant of a failed test
+	CaseName    string   `json:"caseName,omitempty"`    // Full name of a parameter set, whose base test is the last parent
+}
@@ -9,6 +9,7 @@ const (
 	EventTypeGroupTestCase   EventType = "testCase"
 	EventTypeGroupStdout     EventType = "groupStdout"
 	EventTypeGroupStderr     EventType = "groupStderr"
+	EventTypeTestCaseStart   EventType = "testCaseStart"
 )
 
 // GroupDiscoveredEvent indicates a test group has been discovered (during collection phase)
@@ -20,9 +21,12 @@ type GroupDiscoveredEvent struct {
 func (e GroupDiscoveredEvent) Type() EventType { return EventTypeGroupDiscovered }
 
 type GroupDiscoveredPayload struct {
-	GroupName   string                 `json:"groupName"`             // Name of this group
-	ParentNames []string               `json:"parentNames,omitempty"` // Full hierarchy from root (excluding this group)
-	Metadata    map[string]interface{} `json:"metadata,omitempty"`    // Additional metadata (file path, line numbers, etc.)
+	GroupName     string                 `json:"groupName"`               // Name of this group
+	ParentNames   []string               `json:"parentNames,omitempty"`   // Full hierarchy from root (excluding this group)
+	Metadata      map[string]interface{} `json:"metadata,omitempty"`      // Additional metadata (file path, line numbers, etc.)
+	TargetKind    string                 `json:"targetKind,omitempty"`    // Rust test target kind: "lib", "bin", "test", "example" or "bench"
+	TargetName    string                 `json:"targetName,omitempty"`    // Rust test target name (e.g., "integration_test")
+	CustomHarness bool                   `json:"customHarness,omitempty"` // Rust target with harness = false, known only by its output and exit code
 }
 
 // GroupStartEvent indicates a test group has started executing
@@ -51,8 +55,9 @@ func (e GroupResultEvent) Type() EventType { return EventTypeGroupResult }
 type GroupResultPayload struct {
 	GroupName   string                 `json:"groupName"`
 	ParentNames []string               `json:"parentNames,omitempty"`
-	Status      string                 `json:"status"`             // "PASS", "FAIL", "SKIP"
-	Duration    float64                `json:"duration,omitempty"` // Duration in milliseconds
+	Status      string                 `json:"status"`               // "PASS", "FAIL", "SKIP", "WARN"
+	SkipReason  string                 `json:"skipReason,omitempty"` // Why a SKIP group didn't run, e.g. SkipReasonNoTestFiles
+	Duration    float64                `json:"duration,omitempty"`   // Duration in milliseconds
 	Totals      GroupTotals            `json:"totals,omitempty"`
 	Metadata    map[string]interface{} `json:"metadata,omitempty"`
 	Timestamp   int64                  `json:"timestamp,omitempty"`
@@ -78,26 +83,110 @@ type GroupTestCaseEvent struct {
 func (e GroupTestCaseEvent) Type() EventType { return EventTypeGroupTestCase }
 
 type TestCasePayload struct {
-	TestName    string                 `json:"testName"`
-	ParentNames []string               `json:"parentNames,omitempty"` // Full hierarchy including file and describe blocks
-	Status      string                 `json:"status"`                // "PASS", "FAIL", "SKIP", "PENDING", "XFAIL", "XPASS"
-	Duration    float64                `json:"duration,omitempty"`    // Duration in milliseconds
-	Error       *TestError             `json:"error,omitempty"`
-	Stdout      string                 `json:"stdout,omitempty"`
-	Stderr      string                 `json:"stderr,omitempty"`
-	XFailReason string                 `json:"xfailReason,omitempty"` // Reason for expected failure (xfail marker)
-	Metadata    map[string]interface{} `json:"metadata,omitempty"`
-	Timestamp   int64                  `json:"timestamp,omitempty"`
+	TestName           string                 `json:"testName"`
+	ParentNames        []string               `json:"parentNames,omitempty"` // Full hierarchy including file and describe blocks
+	Status             string                 `json:"status"`                // "PASS", "FAIL", "SKIP", "PENDING", "XFAIL", "XPASS", "BENCH", "FLAKY", "ERROR"
+	Duration           float64                `json:"duration,omitempty"`    // Duration in milliseconds
+	Error              *TestError             `json:"error,omitempty"`
+	Stdout             string                 `json:"stdout,omitempty"`
+	Stderr             string                 `json:"stderr,omitempty"`
+	XFailReason        string                 `json:"xfailReason,omitempty"`        // Reason for expected failure (xfail marker)
+	SkipReason         string                 `json:"skipReason,omitempty"`         // Why a SKIP test didn't run: SkipReasonIgnored, SkipReasonNotRun or SkipReasonTodo
+	SkipMessage        string                 `json:"skipMessage,omitempty"`        // Reason the test gave for skipping (e.g., @pytest.mark.skip(reason=...))
+	File               string                 `json:"file,omitempty"`               // Source file that defines the test
+	Line               int                    `json:"line,omitempty"`               // Line of the test definition in File
+	Column             int                    `json:"column,omitempty"`             // Column of the test definition, 0 if unknown
+	ItemPath           string                 `json:"itemPath,omitempty"`           // Documented item for doctests (e.g., "calculator::add")
+	Attempts           []TestAttempt          `json:"attempts,omitempty"`           // Every execution when the runner retried the test
+	Attachments        []TestAttachment       `json:"attachments,omitempty"`        // Files the runner saved for the test (Playwright traces, screenshots)
+	Slow               bool                   `json:"slow,omitempty"`               // Runner flagged the test as slow
+	Leaked             bool                   `json:"leaked,omitempty"`             // Test leaked handles or subprocesses
+	PanickedAsExpected bool                   `json:"panickedAsExpected,omitempty"` // Passed because it panicked (#[should_panic])
+	BenchMedianNs      float64                `json:"benchMedianNs,omitempty"`      // Benchmark median time per iteration in nanoseconds
+	BenchDeviationNs   float64                `json:"benchDeviationNs,omitempty"`   // Benchmark deviation (+/-) in nanoseconds
+	BenchMeanNs        float64                `json:"benchMeanNs,omitempty"`        // Benchmark mean time in nanoseconds (criterion)
+	BenchStdDevNs      float64                `json:"benchStdDevNs,omitempty"`      // Benchmark standard deviation in nanoseconds (criterion)
+	BenchChangePercent float64                `json:"benchChangePercent,omitempty"` // Change of the mean against the baseline, in percent
+	BenchRegressed     bool                   `json:"benchRegressed,omitempty"`     // Benchmark regressed against the baseline
+	BenchIterations    int64                  `json:"benchIterations,omitempty"`    // Iterations the benchmark ran (go test -bench)
+	BenchNsPerOp       float64                `json:"benchNsPerOp,omitempty"`       // Mean time per iteration in nanoseconds (go test -bench)
+	BenchMem           bool                   `json:"benchMem,omitempty"`           // Benchmark reported its allocations (-benchmem)
+	BenchBytesPerOp    int64                  `json:"benchBytesPerOp,omitempty"`    // Bytes allocated per iteration, if BenchMem
+	BenchAllocsPerOp   int64                  `json:"benchAllocsPerOp,omitempty"`   // Allocations per iteration, if BenchMem
+	WorkerID           string                 `json:"workerId,omitempty"`           // Worker that ran the test under a parallel runner (pytest-xdist: "gw1")
+	BaseName           string                 `json:"baseName,omitempty"`           // Parametrized test this is a parameter set of, grouping its sets
+	ParamID            string                 `json:"paramId,omitempty"`            // Parameter set of BaseName (e.g., "10-0-None", "case_1")
+	TeardownError      *TestError             `json:"teardownError,omitempty"`      // Teardown that also failed after the test FAILed (pytest)
+	Metadata           map[string]interface{} `json:"metadata,omitempty"`
+	Timestamp          int64                  `json:"timestamp,omitempty"`
+}
+
+// TestAttempt is a single execution of a test that the runner retried
+type TestAttempt struct {
+	Status   string  `json:"status"`
+	Duration float64 `json:"duration,omitempty"` // Duration in milliseconds
+	Stdout   string  `json:"stdout,omitempty"`
+	Stderr   string  `json:"stderr,omitempty"`
+}
+
+// TestAttachment is a file the runner saved for a test, such as a trace or screenshot
+type TestAttachment struct {
+	Name        string `json:"name"`                  // e.g., "trace", "screenshot"
+	Path        string `json:"path"`                  // Where the runner saved it
+	ContentType string `json:"contentType,omitempty"` // e.g., "image/png"
 }
 
 // TestError contains error information for failed tests
 type TestError struct {
-	Message   string `json:"message"`
-	Stack     string `json:"stack,omitempty"`
-	Expected  string `json:"expected,omitempty"`
-	Actual    string `json:"actual,omitempty"`
-	Location  string `json:"location,omitempty"`  // File:line
-	ErrorType string `json:"errorType,omitempty"` // e.g., "AssertionError"
+	Message           string `json:"message"`
+	Stack             string `json:"stack,omitempty"`
+	Expected          string `json:"expected,omitempty"`
+	Actual            string `json:"actual,omitempty"`
+	Location          string `json:"location,omitempty"`          // File:line
+	File              string `json:"file,omitempty"`              // Source file of Location, relative to the project (Rust: workspace) root
+	Line              int    `json:"line,omitempty"`              // Line of Location
+	Column            int    `json:"column,omitempty"`            // Column of Location, 0 if unknown
+	External          bool   `json:"external,omitempty"`          // File is outside the project, e.g. in the cargo registry or the Rust sysroot
+	ErrorType         string `json:"errorType,omitempty"`         // e.g., "AssertionError", "assert_eq" (Rust)
+	CustomMessage     string `json:"customMessage,omitempty"`     // Message given to the failed assertion, if any
+	ExpectedPanic     string `json:"expectedPanic,omitempty"`     // #[should_panic(expected = ...)] substring (Rust)
+	ActualPanic       string `json:"actualPanic,omitempty"`       // Panic message that did not contain it (Rust)
+	Backtrace         string `json:"backtrace,omitempty"`         // RUST_BACKTRACE frames of a panic, runtime frames trimmed (Rust)
+	FailureKind       string `json:"failureKind,omitempty"`       // FailureKind* constant, from parsers that can tell
+	DataRace          string `json:"dataRace,omitempty"`          // Race detector reports with the stacks of both accesses (Go -race)
+	UndefinedBehavior string `json:"undefinedBehavior,omitempty"` // miri's report of the undefined behavior that stopped the test (Rust cargo miri test)
+	Phase             string `json:"phase,omitempty"`             // ErrorPhaseSetup or ErrorPhaseTeardown for an ERROR test, "" if the test itself failed
+
+	Snapshot *SnapshotMismatch `json:"snapshot,omitempty"` // Snapshot the test's value didn't match (Rust insta)
+	Property *PropertyFailure  `json:"property,omitempty"` // Minimized case of a failed property test (Rust proptest, quickcheck)
+	Trybuild []TrybuildCase    `json:"trybuild,omitempty"` // UI test cases whose compiler output didn't match (Rust trybuild)
+}
+
+// TrybuildCase is a trybuild UI test case that failed, with paths relative to the workspace root
+type TrybuildCase struct {
+	Path       string `json:"path"`                 // Test case source, e.g. "tests/ui/missing-semicolon.rs"
+	Status     string `json:"status"`               // "mismatch", "wip" or "error", as trybuild reports it
+	Message    string `json:"message,omitempty"`    // trybuild's explanation of an error, e.g. that the case compiled
+	Expected   string `json:"expected,omitempty"`   // Compiler output stored in StderrFile
+	Actual     string `json:"actual,omitempty"`     // Compiler output of this run
+	StderrFile string `json:"stderrFile,omitempty"` // .stderr file with the expected output
+	WipFile    string `json:"wipFile,omitempty"`    // File trybuild wrote the actual output to, to be moved to StderrFile
+}
+
+// PropertyFailure is the smallest input a property test shrank its failing case to
+type PropertyFailure struct {
+	Framework      string `json:"framework"`                // "proptest" or "quickcheck"
+	Input          string `json:"input"`                    // Minimized arguments, e.g. "a = 0, b = -1" or "(0, -1)"
+	Seed           string `json:"seed,omitempty"`           // Hash of proptest's "cc" line that replays the case
+	RegressionFile string `json:"regressionFile,omitempty"` // proptest-regressions file the case was saved in
+}
+
+// SnapshotMismatch is a snapshot assertion that failed, as insta reports it
+type SnapshotMismatch struct {
+	Name    string `json:"name"`              // Snapshot name, "<inline>" for an inline snapshot
+	File    string `json:"file,omitempty"`    // Stored .snap file, relative to the workspace root
+	NewFile string `json:"newFile,omitempty"` // .snap.new or .pending-snap file with the new value, if insta wrote one
+	Diff    string `json:"diff,omitempty"`    // Stored and new value as diff lines, "-" for stored and "+" for new
 }
 
 // GroupStdoutChunkEvent represents stdout output from a test group
@@ -120,6 +209,28 @@ type OutputChunkPayload struct {
 	GroupName   string   `json:"groupName,omitempty"`   // The group this output belongs to
 	ParentNames []string `json:"parentNames,omitempty"` // Full hierarchy
 	Chunk       string   `json:"chunk"`                 // The output chunk
+	WorkerID    string   `json:"workerId,omitempty"`    // Without GroupName: the output of the test running on this worker
+	Timestamp   int64    `json:"timestamp,omitempty"`
+
+	// A console entry (Jest): the method it was logged with (log, info, warn, error,
+	// debug), and the test in GroupName that logged it, empty outside any test
+	Level    string `json:"level,omitempty"`
+	TestName string `json:"testName,omitempty"`
+}
+
+// TestCaseStartEvent is sent when a test starts running, so output chunks without a
+// group can be attributed to the test running on their worker
+type TestCaseStartEvent struct {
+	EventType string               `json:"eventType"`
+	Payload   TestCaseStartPayload `json:"payload"`
+}
+
+func (e TestCaseStartEvent) Type() EventType { return EventTypeTestCaseStart }
+
+type TestCaseStartPayload struct {
+	TestName    string   `json:"testName"`
+	ParentNames []string `json:"parentNames,omitempty"` // Full hierarchy including file and describe blocks
+	WorkerID    string   `json:"workerId,omitempty"`    // Worker running the test, "" without parallel workers
 	Timestamp   int64    `json:"timestamp,omitempty"`
 }
 
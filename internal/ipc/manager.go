@@ -1,13 +1,14 @@
 package ipc
 
 import (
-	"bufio"
+	"bytes"
 	"encoding/json"
 	"fmt"
 	"io"
 	"os"
 	"path/filepath"
 	"sync"
+	"time"
 
 	"github.com/fsnotify/fsnotify"
 )
@@ -16,18 +17,29 @@ import (
 type Manager struct {
 	IPCPath       string
 	watcher       *fsnotify.Watcher
+	polling       bool // The file couldn't be watched and is polled instead
 	Events        chan Event
 	stopChan      chan struct{}
-	stopped       chan struct{} // Signals when watchLoop has stopped
+	stopped       chan struct{} // Signals when watchLoop or pollLoop has stopped
 	mu            sync.RWMutex
 	closeOnce     sync.Once
 	logger        Logger
 	file          *os.File
-	reader        *bufio.Reader
-	readerMu      sync.Mutex // Protects concurrent access to reader
-	partialBuffer []byte
+	offset        int64      // Bytes of file read so far
+	readerMu      sync.Mutex // Protects concurrent access to file, offset and partialBuffer
+	partialBuffer []byte     // Start of a line whose rest hasn't been written yet
+	malformed     int        // Lines ignored because they couldn't be decoded
 }
 
+// readChunkSize is how much of the IPC file is read at a time
+const readChunkSize = 64 * 1024
+
+// pollInterval is how often the IPC file is read when it can't be watched
+const pollInterval = 100 * time.Millisecond
+
+// newWatcher creates the file watcher, and is replaced in tests
+var newWatcher = fsnotify.NewWatcher
+
 // Logger interface for debug logging
 type Logger interface {
 	Debug(format string, args ...interface{})
@@ -59,61 +71,140 @@ func NewManager(ipcPath string, logger Logger) (*Manager, error) {
 		stopped:  make(chan struct{}),
 		logger:   logger,
 		file:     file,
-		reader:   bufio.NewReader(file),
 	}, nil
 }
 
-// WatchEvents starts watching the IPC file for new events
+// WatchEvents starts watching the IPC file for new events. The file is watched with
+// the OS's file notifications (inotify, kqueue or ReadDirectoryChangesW), so events are
+// read as soon as they are written. Where that isn't available, e.g. when the inotify
+// watch limit is reached, the file is polled every pollInterval instead.
 func (m *Manager) WatchEvents() error {
-	if m.watcher != nil {
+	if m.watcher != nil || m.polling {
 		return fmt.Errorf("watch already started")
 	}
-	watcher, err := fsnotify.NewWatcher()
-	if err != nil {
-		return fmt.Errorf("failed to create file watcher: %w", err)
-	}
-	m.watcher = watcher
 
-	// Add the IPC file to the watcher
-	if err := watcher.Add(m.IPCPath); err != nil {
-		return fmt.Errorf("failed to watch IPC file: %w", err)
+	// Start the single loop that handles both existing and new events
+	if watcher, err := m.watchFile(); err != nil {
+		m.logger.Debug("Polling the IPC file every %v, it can't be watched: %v", pollInterval, err)
+		m.polling = true
+		go m.pollLoop()
+	} else {
+		m.watcher = watcher
+		go m.watchLoop()
 	}
 
-	// Start the single watch loop that handles both existing and new events
-	go m.watchLoop()
-
 	// Trigger initial read of any existing content
 	go m.readEvents()
 
 	return nil
 }
 
-// readEvents reads events from the current position in the file
+// watchFile returns a watcher of the IPC file
+func (m *Manager) watchFile() (*fsnotify.Watcher, error) {
+	watcher, err := newWatcher()
+	if err != nil {
+		return nil, fmt.Errorf("failed to create file watcher: %w", err)
+	}
+	if err := watcher.Add(m.IPCPath); err != nil {
+		_ = watcher.Close()
+		return nil, fmt.Errorf("failed to watch IPC file: %w", err)
+	}
+	return watcher, nil
+}
+
+// readEvents reads the events written since the last read, from the offset reached so
+// far. A file that shrank or was replaced, as by an adapter that recreates it, is read
+// again from the start.
 func (m *Manager) readEvents() {
 	m.readerMu.Lock()
 	defer m.readerMu.Unlock()
 
+	if m.file == nil {
+		return
+	}
+	if info, err := m.file.Stat(); err == nil && info.Size() < m.offset {
+		m.logger.Debug("IPC file shrank from %d to %d bytes, reading it from the start", m.offset, info.Size())
+		m.offset = 0
+		m.partialBuffer = nil
+	}
+	m.readAvailable()
+
+	if m.reopenIfReplaced() {
+		m.readAvailable()
+	}
+}
+
+// readAvailable reads the file from the offset to its end. Caller must hold m.readerMu.
+func (m *Manager) readAvailable() {
+	buf := make([]byte, readChunkSize)
 	for {
-		line, err := m.reader.ReadBytes('\n')
+		n, err := m.file.ReadAt(buf, m.offset)
+		if n > 0 {
+			m.offset += int64(n)
+			m.processChunk(buf[:n], m.offset-int64(n))
+		}
 		if err != nil {
-			if err == io.EOF {
-				if len(line) > 0 {
-					m.partialBuffer = append(m.partialBuffer, line...)
-				}
-				break
+			if err != io.EOF {
+				m.logger.Error("Error reading events: %v", err)
 			}
-			m.logger.Error("Error reading events: %v", err)
-			break
+			return
 		}
+	}
+}
 
-		if len(line) > 0 {
-			if len(m.partialBuffer) > 0 {
-				line = append(append([]byte(nil), m.partialBuffer...), line...)
-				m.partialBuffer = nil
-			}
-			m.parseAndSendEvent(line)
+// processChunk sends the events on each complete line of a chunk read at offset. A
+// partial line at the end is kept, and completed by the next chunk, since its writer
+// may not have flushed the rest yet. Caller must hold m.readerMu.
+func (m *Manager) processChunk(chunk []byte, offset int64) {
+	for {
+		i := bytes.IndexByte(chunk, '\n')
+		if i < 0 {
+			m.partialBuffer = append(m.partialBuffer, chunk...)
+			return
+		}
+		line := chunk[:i+1]
+		lineOffset := offset - int64(len(m.partialBuffer))
+		if len(m.partialBuffer) > 0 {
+			line = append(m.partialBuffer, line...)
+			m.partialBuffer = nil
+		}
+		m.parseAndSendEvent(line, lineOffset)
+		chunk = chunk[i+1:]
+		offset += int64(i + 1)
+	}
+}
+
+// reopenIfReplaced switches to the file at IPCPath when it is no longer the open one,
+// after the open one was read to its end. Caller must hold m.readerMu.
+func (m *Manager) reopenIfReplaced() bool {
+	pathInfo, err := os.Stat(m.IPCPath)
+	if err != nil {
+		// Removed, and not created again yet
+		return false
+	}
+	if fileInfo, err := m.file.Stat(); err == nil && os.SameFile(pathInfo, fileInfo) {
+		return false
+	}
+	file, err := os.Open(m.IPCPath)
+	if err != nil {
+		m.logger.Error("Failed to reopen replaced IPC file: %v", err)
+		return false
+	}
+	m.logger.Debug("IPC file was replaced, reading the new one from the start")
+	_ = m.file.Close()
+	m.file = file
+	m.offset = 0
+	m.partialBuffer = nil
+
+	// The watch followed the old file
+	m.mu.RLock()
+	if m.watcher != nil {
+		if err := m.watcher.Add(m.IPCPath); err != nil {
+			m.logger.Debug("Failed to watch the new IPC file: %v", err)
 		}
 	}
+	m.mu.RUnlock()
+	return true
 }
 
 // watchLoop watches for file changes and triggers reads
@@ -130,6 +221,9 @@ func (m *Manager) watchLoop() {
 			if event.Op&fsnotify.Write == fsnotify.Write {
 				m.logger.Debug("IPC file modified: %s", event.Name)
 				m.readEvents()
+			} else if event.Op&(fsnotify.Create|fsnotify.Remove|fsnotify.Rename) != 0 {
+				m.logger.Debug("IPC file replaced: %s (%v)", event.Name, event.Op)
+				m.readEvents()
 			}
 
 		case err, ok := <-m.watcher.Errors:
@@ -145,21 +239,66 @@ func (m *Manager) watchLoop() {
 	}
 }
 
-// parseAndSendEvent parses a JSON line and sends it as an event
-func (m *Manager) parseAndSendEvent(line []byte) {
+// pollLoop reads the events written every pollInterval, for a file that can't be watched
+func (m *Manager) pollLoop() {
+	defer close(m.stopped)
+
+	ticker := time.NewTicker(pollInterval)
+	defer ticker.Stop()
+	for {
+		select {
+		case <-ticker.C:
+			m.readEvents()
+		case <-m.stopChan:
+			return
+		}
+	}
+}
+
+// eventStart is how every event written to the IPC file starts
+var eventStart = []byte(`{"eventType"`)
+
+// parseAndSendEvent parses a JSON line read at offset and sends it as an event. Lines
+// that can't be decoded are skipped and counted, and blank lines are skipped.
+func (m *Manager) parseAndSendEvent(line []byte, offset int64) {
+	if len(bytes.TrimSpace(line)) == 0 {
+		return
+	}
+
 	// First, decode to determine event type
 	var rawEvent map[string]interface{}
 	if err := json.Unmarshal(line, &rawEvent); err != nil {
-		m.logger.Debug("Failed to parse event: %v", err)
+		// A writer that died mid-write leaves the start of an event, which the next
+		// event written is appended to. That event is still read.
+		if i := bytes.Index(line[1:], eventStart); i >= 0 {
+			m.malformedEvent(line[:i+1], offset, fmt.Errorf("incomplete event"))
+			m.parseAndSendEvent(line[i+1:], offset+int64(i+1))
+			return
+		}
+		m.malformedEvent(line, offset, err)
 		return
 	}
 
 	eventType, ok := rawEvent["eventType"].(string)
 	if !ok {
-		m.logger.Error("Event missing eventType field")
+		m.malformedEvent(line, offset, fmt.Errorf("missing eventType field"))
 		return
 	}
 
+	// Events of older schemas are upgraded. A handshake is read in any schema, so the
+	// run can fail on it if the adapter is incompatible.
+	schema, err := eventSchema(rawEvent)
+	if err != nil {
+		m.malformedEvent(line, offset, err)
+		return
+	}
+	if schema != SchemaVersion && EventType(eventType) != EventTypeAdapterHandshake {
+		if line, err = upgradeEvent(rawEvent, schema); err != nil {
+			m.logger.Debug("Ignoring %s event at byte %d: %v", eventType, offset, err)
+			return
+		}
+	}
+
 	// Parse based on event type
 	var event Event
 	switch EventType(eventType) {
@@ -167,7 +306,7 @@ func (m *Manager) parseAndSendEvent(line []byte) {
 		// Only new group-based testCase events are supported
 		var e GroupTestCaseEvent
 		if err := json.Unmarshal(line, &e); err != nil {
-			m.logger.Debug("Failed to parse group test case event: %v", err)
+			m.malformedEvent(line, offset, fmt.Errorf("invalid group test case event: %w", err))
 			return
 		}
 		event = e
@@ -175,7 +314,7 @@ func (m *Manager) parseAndSendEvent(line []byte) {
 	case EventTypeRunComplete:
 		var e RunCompleteEvent
 		if err := json.Unmarshal(line, &e); err != nil {
-			m.logger.Debug("Failed to parse run complete event: %v", err)
+			m.malformedEvent(line, offset, fmt.Errorf("invalid run complete event: %w", err))
 			return
 		}
 		event = e
@@ -183,7 +322,7 @@ func (m *Manager) parseAndSendEvent(line []byte) {
 	case EventTypeCollectionStart:
 		var e CollectionStartEvent
 		if err := json.Unmarshal(line, &e); err != nil {
-			m.logger.Debug("Failed to parse collection start event: %v", err)
+			m.malformedEvent(line, offset, fmt.Errorf("invalid collection start event: %w", err))
 			return
 		}
 		event = e
@@ -191,7 +330,7 @@ func (m *Manager) parseAndSendEvent(line []byte) {
 	case EventTypeCollectionError:
 		var e CollectionErrorEvent
 		if err := json.Unmarshal(line, &e); err != nil {
-			m.logger.Debug("Failed to parse collection error event: %v", err)
+			m.malformedEvent(line, offset, fmt.Errorf("invalid collection error event: %w", err))
 			return
 		}
 		event = e
@@ -199,7 +338,23 @@ func (m *Manager) parseAndSendEvent(line []byte) {
 	case EventTypeCollectionFinish:
 		var e CollectionFinishEvent
 		if err := json.Unmarshal(line, &e); err != nil {
-			m.logger.Debug("Failed to parse collection finish event: %v", err)
+			m.malformedEvent(line, offset, fmt.Errorf("invalid collection finish event: %w", err))
+			return
+		}
+		event = e
+
+	case EventTypeBuildError:
+		var e BuildErrorEvent
+		if err := json.Unmarshal(line, &e); err != nil {
+			m.malformedEvent(line, offset, fmt.Errorf("invalid build error event: %w", err))
+			return
+		}
+		event = e
+
+	case EventTypeBinaryCrash:
+		var e BinaryCrashEvent
+		if err := json.Unmarshal(line, &e); err != nil {
+			m.malformedEvent(line, offset, fmt.Errorf("invalid binary crash event: %w", err))
 			return
 		}
 		event = e
@@ -207,7 +362,7 @@ func (m *Manager) parseAndSendEvent(line []byte) {
 	case EventTypeGroupDiscovered:
 		var e GroupDiscoveredEvent
 		if err := json.Unmarshal(line, &e); err != nil {
-			m.logger.Debug("Failed to parse group discovered event: %v", err)
+			m.malformedEvent(line, offset, fmt.Errorf("invalid group discovered event: %w", err))
 			return
 		}
 		event = e
@@ -215,7 +370,7 @@ func (m *Manager) parseAndSendEvent(line []byte) {
 	case EventTypeGroupStart:
 		var e GroupStartEvent
 		if err := json.Unmarshal(line, &e); err != nil {
-			m.logger.Debug("Failed to parse group start event: %v", err)
+			m.malformedEvent(line, offset, fmt.Errorf("invalid group start event: %w", err))
 			return
 		}
 		event = e
@@ -223,7 +378,7 @@ func (m *Manager) parseAndSendEvent(line []byte) {
 	case EventTypeGroupResult:
 		var e GroupResultEvent
 		if err := json.Unmarshal(line, &e); err != nil {
-			m.logger.Debug("Failed to parse group result event: %v", err)
+			m.malformedEvent(line, offset, fmt.Errorf("invalid group result event: %w", err))
 			return
 		}
 		event = e
@@ -231,7 +386,7 @@ func (m *Manager) parseAndSendEvent(line []byte) {
 	case EventTypeGroupError:
 		var e GroupErrorEvent
 		if err := json.Unmarshal(line, &e); err != nil {
-			m.logger.Debug("Failed to parse group error event: %v", err)
+			m.malformedEvent(line, offset, fmt.Errorf("invalid group error event: %w", err))
 			return
 		}
 		event = e
@@ -239,7 +394,7 @@ func (m *Manager) parseAndSendEvent(line []byte) {
 	case EventTypeGroupStdout:
 		var e GroupStdoutChunkEvent
 		if err := json.Unmarshal(line, &e); err != nil {
-			m.logger.Debug("Failed to parse group stdout event: %v", err)
+			m.malformedEvent(line, offset, fmt.Errorf("invalid group stdout event: %w", err))
 			return
 		}
 		event = e
@@ -247,7 +402,31 @@ func (m *Manager) parseAndSendEvent(line []byte) {
 	case EventTypeGroupStderr:
 		var e GroupStderrChunkEvent
 		if err := json.Unmarshal(line, &e); err != nil {
-			m.logger.Debug("Failed to parse group stderr event: %v", err)
+			m.malformedEvent(line, offset, fmt.Errorf("invalid group stderr event: %w", err))
+			return
+		}
+		event = e
+
+	case EventTypeRunWarnings:
+		var e RunWarningsEvent
+		if err := json.Unmarshal(line, &e); err != nil {
+			m.malformedEvent(line, offset, fmt.Errorf("invalid run warnings event: %w", err))
+			return
+		}
+		event = e
+
+	case EventTypeTestCaseStart:
+		var e TestCaseStartEvent
+		if err := json.Unmarshal(line, &e); err != nil {
+			m.malformedEvent(line, offset, fmt.Errorf("invalid test case start event: %w", err))
+			return
+		}
+		event = e
+
+	case EventTypeAdapterHandshake:
+		var e AdapterHandshakeEvent
+		if err := json.Unmarshal(line, &e); err != nil {
+			m.malformedEvent(line, offset, fmt.Errorf("invalid adapter handshake event: %w", err))
 			return
 		}
 		event = e
@@ -262,6 +441,35 @@ func (m *Manager) parseAndSendEvent(line []byte) {
 	m.logger.Debug("Processing IPC event: %s", eventType)
 }
 
+// malformedPreviewLength is how much of a malformed line is shown in the debug log
+const malformedPreviewLength = 200
+
+// malformedEvent logs and counts a line that couldn't be decoded. Caller must hold
+// m.readerMu.
+func (m *Manager) malformedEvent(line []byte, offset int64, err error) {
+	m.malformed++
+	preview := bytes.TrimRight(line, "\r\n")
+	if len(preview) > malformedPreviewLength {
+		preview = preview[:malformedPreviewLength]
+	}
+	m.logger.Debug("Ignoring malformed IPC event at byte %d of %s: %v: %q", offset, m.IPCPath, err, preview)
+}
+
+// MalformedEvents returns the number of lines of the IPC file ignored because they
+// couldn't be decoded, including a partial line left at its end by Cleanup
+func (m *Manager) MalformedEvents() int {
+	m.readerMu.Lock()
+	defer m.readerMu.Unlock()
+	return m.malformed
+}
+
+// Drain reads the events written since the last read. Call it once the writer has
+// finished and before Cleanup, so events whose write notification is still on its
+// way aren't lost.
+func (m *Manager) Drain() {
+	m.readEvents()
+}
+
 // Cleanup stops watching and closes resources
 func (m *Manager) Cleanup() error {
 	// Signal stop to goroutines (not under lock to avoid deadlock)
@@ -272,8 +480,25 @@ func (m *Manager) Cleanup() error {
 		close(m.stopChan)
 	}
 
-	// Wait for watchLoop to finish before cleaning up resources
-	<-m.stopped
+	// Wait for watchLoop or pollLoop to finish before cleaning up resources. A manager
+	// that was only drained never started either. Events written since their last read
+	// are left to Drain, which the caller runs first.
+	if m.watcher != nil || m.polling {
+		<-m.stopped
+	}
+
+	// A read in progress holds readerMu and may take mu, so readerMu is taken first.
+	// The writers have finished, so a partial line left now will never be completed.
+	m.readerMu.Lock()
+	if len(bytes.TrimSpace(m.partialBuffer)) > 0 {
+		m.malformedEvent(m.partialBuffer, m.offset-int64(len(m.partialBuffer)), fmt.Errorf("truncated at the end of the file"))
+	}
+	m.partialBuffer = nil
+	if m.file != nil {
+		_ = m.file.Close()
+		m.file = nil
+	}
+	m.readerMu.Unlock()
 
 	m.mu.Lock()
 	defer m.mu.Unlock()
@@ -283,11 +508,6 @@ func (m *Manager) Cleanup() error {
 		m.watcher = nil
 	}
 
-	if m.file != nil {
-		_ = m.file.Close()
-		m.file = nil
-	}
-
 	// Close channels only once using sync.Once
 	m.closeOnce.Do(func() {
 		if m.Events != nil {
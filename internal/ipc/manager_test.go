@@ -0,0 +1,325 @@
+package ipc
+
+import (
+	"errors"
+	"fmt"
+	"math/rand"
+	"os"
+	"path/filepath"
+	"runtime"
+	"sort"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/fsnotify/fsnotify"
+)
+
+// testCaseLine returns the IPC line of a passing test named name
+func testCaseLine(name string) string {
+	return fmt.Sprintf(`{"eventType":"testCase","payload":{"testName":%q,"parentNames":["suite"],"status":"PASS"}}`+"\n", name)
+}
+
+// receivedTestNames returns the names of the test cases waiting in the events channel
+func receivedTestNames(m *Manager) []string {
+	var names []string
+	for {
+		select {
+		case event := <-m.Events:
+			if tc, ok := event.(GroupTestCaseEvent); ok {
+				names = append(names, tc.Payload.TestName)
+			}
+		default:
+			return names
+		}
+	}
+}
+
+func TestManager_PartialWrites(t *testing.T) {
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	manager, err := NewManager(ipcPath, &mockLogger{})
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	if err := manager.WatchEvents(); err != nil {
+		t.Fatalf("Failed to watch events: %v", err)
+	}
+
+	const total = 2000
+	received := make(chan []string)
+	go func() {
+		var names []string
+		for event := range manager.Events {
+			if tc, ok := event.(GroupTestCaseEvent); ok {
+				names = append(names, tc.Payload.TestName)
+			}
+		}
+		received <- names
+	}()
+
+	// Another goroutine writes the events split at random points, so lines are read
+	// while only part of them has been written
+	written := make(chan error)
+	go func() {
+		file, err := os.OpenFile(ipcPath, os.O_WRONLY|os.O_APPEND, 0644)
+		if err != nil {
+			written <- err
+			return
+		}
+		defer func() { _ = file.Close() }()
+		var data []byte
+		for i := 0; i < total; i++ {
+			data = append(data, testCaseLine(fmt.Sprintf("test_%d", i))...)
+		}
+		random := rand.New(rand.NewSource(1))
+		for len(data) > 0 {
+			n := min(1+random.Intn(300), len(data))
+			if _, err := file.Write(data[:n]); err != nil {
+				written <- err
+				return
+			}
+			data = data[n:]
+			if random.Intn(20) == 0 {
+				time.Sleep(time.Millisecond)
+			}
+		}
+		written <- nil
+	}()
+	if err := <-written; err != nil {
+		t.Fatalf("Failed to write events: %v", err)
+	}
+
+	manager.Drain()
+	_ = manager.Cleanup()
+	names := <-received
+
+	// Every event arrives once, in order
+	if len(names) != total {
+		t.Fatalf("Expected %d events, got %d", total, len(names))
+	}
+	for i, name := range names {
+		if name != fmt.Sprintf("test_%d", i) {
+			t.Fatalf("Event %d is %s, want test_%d", i, name, i)
+		}
+	}
+}
+
+// appendLine appends a line to the IPC file, as an adapter does
+func appendLine(t *testing.T, ipcPath, line string) {
+	t.Helper()
+	file, err := os.OpenFile(ipcPath, os.O_WRONLY|os.O_APPEND|os.O_CREATE, 0644)
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer func() { _ = file.Close() }()
+	if _, err := file.WriteString(line); err != nil {
+		t.Fatal(err)
+	}
+}
+
+// nextTestName waits up to timeout for the next test case event
+func nextTestName(m *Manager, timeout time.Duration) (string, bool) {
+	deadline := time.After(timeout)
+	for {
+		select {
+		case event := <-m.Events:
+			if tc, ok := event.(GroupTestCaseEvent); ok {
+				return tc.Payload.TestName, true
+			}
+		case <-deadline:
+			return "", false
+		}
+	}
+}
+
+func TestManager_EventLatency(t *testing.T) {
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	manager, err := NewManager(ipcPath, &mockLogger{})
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	defer func() { _ = manager.Cleanup() }()
+	if err := manager.WatchEvents(); err != nil {
+		t.Fatalf("Failed to watch events: %v", err)
+	}
+	if manager.polling {
+		t.Skip("The IPC file can't be watched here")
+	}
+
+	// Events are read when the file changes, well before a poll would read them
+	var latencies []time.Duration
+	for i := 0; i < 10; i++ {
+		start := time.Now()
+		appendLine(t, ipcPath, testCaseLine(fmt.Sprintf("test_%d", i)))
+		if _, ok := nextTestName(manager, 5*time.Second); !ok {
+			t.Fatalf("Event %d never arrived", i)
+		}
+		latencies = append(latencies, time.Since(start))
+	}
+	sort.Slice(latencies, func(i, j int) bool { return latencies[i] < latencies[j] })
+	if median := latencies[len(latencies)/2]; median >= pollInterval/2 {
+		t.Errorf("Median event latency is %v, want well under the %v poll interval", median, pollInterval)
+	}
+}
+
+func TestManager_PollsWhenFileCantBeWatched(t *testing.T) {
+	newWatcher = func() (*fsnotify.Watcher, error) {
+		return nil, errors.New("too many open files")
+	}
+	defer func() { newWatcher = fsnotify.NewWatcher }()
+
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	logger := &mockLogger{}
+	manager, err := NewManager(ipcPath, logger)
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	if err := manager.WatchEvents(); err != nil {
+		t.Fatalf("Expected polling instead of an error, got %v", err)
+	}
+	if !manager.polling {
+		t.Fatal("Expected the manager to poll the IPC file")
+	}
+	logger.mu.Lock()
+	logged := fmt.Sprint(logger.debugMessages)
+	logger.mu.Unlock()
+	if !strings.Contains(logged, "too many open files") {
+		t.Errorf("Expected the reason for polling in the debug log, got %s", logged)
+	}
+	if err := manager.WatchEvents(); err == nil {
+		t.Error("Expected an error watching a second time")
+	}
+
+	appendLine(t, ipcPath, testCaseLine("test_polled"))
+	if name, ok := nextTestName(manager, 5*time.Second); !ok || name != "test_polled" {
+		t.Fatalf("Expected test_polled from a poll, got %q", name)
+	}
+
+	// The final drain reads what was written after the last poll
+	appendLine(t, ipcPath, testCaseLine("test_last"))
+	manager.Drain()
+	if err := manager.Cleanup(); err != nil {
+		t.Fatalf("Cleanup failed: %v", err)
+	}
+	var names []string
+	for event := range manager.Events {
+		if tc, ok := event.(GroupTestCaseEvent); ok {
+			names = append(names, tc.Payload.TestName)
+		}
+	}
+	if fmt.Sprint(names) != "[test_last]" {
+		t.Errorf("Expected [test_last] after the drain, got %v", names)
+	}
+}
+
+func TestManager_MalformedLines(t *testing.T) {
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	logger := &mockLogger{}
+	manager, err := NewManager(ipcPath, logger)
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+
+	lines := []string{
+		testCaseLine("test_a"),
+		// Truncated JSON
+		`{"eventType":"testCase","payload":{"testNa` + "\n",
+		// A writer died mid-write, and the next event was appended to what it left
+		`{"eventType":"testCase","pay` + testCaseLine("test_b"),
+		// Garbage bytes
+		"\x00\xff\xfegarbage\n",
+		// An empty line, which isn't counted
+		"\n",
+		testCaseLine("test_c"),
+		// Not finished yet, so not malformed until the writers are done
+		`{"eventType":"testCase"`,
+	}
+	appendLine(t, ipcPath, strings.Join(lines, ""))
+
+	manager.Drain()
+	if names := receivedTestNames(manager); fmt.Sprint(names) != "[test_a test_b test_c]" {
+		t.Errorf("Expected [test_a test_b test_c], got %v", names)
+	}
+	if got := manager.MalformedEvents(); got != 3 {
+		t.Errorf("Expected 3 malformed events before the end of the run, got %d", got)
+	}
+
+	// The partial line is malformed once the run is over
+	if err := manager.Cleanup(); err != nil {
+		t.Fatalf("Cleanup failed: %v", err)
+	}
+	if got := manager.MalformedEvents(); got != 4 {
+		t.Errorf("Expected 4 malformed events after Cleanup, got %d", got)
+	}
+
+	// Each is logged with its byte offset
+	logger.mu.Lock()
+	logged := strings.Join(logger.debugMessages, "\n")
+	logger.mu.Unlock()
+	offset := 0
+	for i, line := range lines {
+		if i == 1 || i == 2 || i == 3 || i == 6 {
+			if want := fmt.Sprintf("malformed IPC event at byte %d of", offset); !strings.Contains(logged, want) {
+				t.Errorf("Expected %q in the debug log, got:\n%s", want, logged)
+			}
+		}
+		offset += len(line)
+	}
+}
+
+func TestManager_TruncatedAndReplacedFile(t *testing.T) {
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	manager, err := NewManager(ipcPath, &mockLogger{})
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	defer func() { _ = manager.Cleanup() }()
+
+	write := func(content string) {
+		file, err := os.OpenFile(ipcPath, os.O_WRONLY|os.O_APPEND|os.O_CREATE, 0644)
+		if err != nil {
+			t.Fatal(err)
+		}
+		defer func() { _ = file.Close() }()
+		if _, err := file.WriteString(content); err != nil {
+			t.Fatal(err)
+		}
+	}
+
+	// A partial line is held back until the rest of it is written
+	line := testCaseLine("test_a")
+	write(line[:20])
+	manager.Drain()
+	if names := receivedTestNames(manager); len(names) != 0 {
+		t.Fatalf("Expected no events from a partial line, got %v", names)
+	}
+	write(line[20:] + testCaseLine("test_b"))
+	manager.Drain()
+	if names := receivedTestNames(manager); fmt.Sprint(names) != "[test_a test_b]" {
+		t.Fatalf("Expected [test_a test_b], got %v", names)
+	}
+
+	// A truncated file is read again from the start
+	if err := os.Truncate(ipcPath, 0); err != nil {
+		t.Fatal(err)
+	}
+	write(testCaseLine("test_c"))
+	manager.Drain()
+	if names := receivedTestNames(manager); fmt.Sprint(names) != "[test_c]" {
+		t.Fatalf("Expected [test_c] after the truncation, got %v", names)
+	}
+
+	// So is a file that was removed and created again, which Windows doesn't allow
+	// while the manager has it open
+	if runtime.GOOS == "windows" {
+		return
+	}
+	if err := os.Remove(ipcPath); err != nil {
+		t.Fatal(err)
+	}
+	write(testCaseLine("test_d") + testCaseLine("test_e"))
+	manager.Drain()
+	if names := receivedTestNames(manager); fmt.Sprint(names) != "[test_d test_e]" {
+		t.Fatalf("Expected [test_d test_e] from the new file, got %v", names)
+	}
+}
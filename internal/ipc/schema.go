@@ -0,0 +1,100 @@
+package ipc
+
+import (
+	"encoding/json"
+	"fmt"
+)
+
+// SchemaVersion is the version of the event format this build reads and writes. Every
+// event carries it in its "schema" field. It only goes up when an event changes in a
+// way older readers get wrong: fields can be added without it, since encoding/json
+// ignores the fields it doesn't know and leaves the missing ones at their zero value.
+const SchemaVersion = 2
+
+// MinSchemaVersion is the oldest schema still read. Events written before events were
+// versioned have no schema field and are schema 1.
+const MinSchemaVersion = 1
+
+// EventTypeAdapterHandshake is the first event an adapter sends, naming its schema
+const EventTypeAdapterHandshake EventType = "adapterHandshake"
+
+// AdapterHandshakeEvent is sent by an adapter when it starts, so a stale adapter from
+// another 3pio version fails the run early instead of losing fields
+type AdapterHandshakeEvent struct {
+	EventType string                  `json:"eventType"`
+	Schema    int                     `json:"schema"`
+	Payload   AdapterHandshakePayload `json:"payload"`
+}
+
+func (e AdapterHandshakeEvent) Type() EventType { return EventTypeAdapterHandshake }
+
+// AdapterHandshakePayload names the adapter and the schema it writes
+type AdapterHandshakePayload struct {
+	Adapter string `json:"adapter"` // e.g. "jest", "pytest"
+	Schema  int    `json:"schema"`
+}
+
+// CheckSchema returns an error telling the user what to do when events of the given
+// schema, written by adapter, can't be read
+func CheckSchema(adapter string, schema int) error {
+	if schema >= MinSchemaVersion && schema <= SchemaVersion {
+		return nil
+	}
+	return fmt.Errorf("the %s adapter writes IPC schema %d, but this 3pio reads schemas %d to %d. "+
+		"It is a stale copy from another 3pio version: remove any cached 3pio adapters "+
+		"(.3pio/runs/*/adapters, and reporter or plugin paths pointing at them) and run again",
+		adapter, schema, MinSchemaVersion, SchemaVersion)
+}
+
+// eventSchema returns the schema of a decoded event
+func eventSchema(raw map[string]interface{}) (int, error) {
+	value, ok := raw["schema"]
+	if !ok {
+		return MinSchemaVersion, nil
+	}
+	schema, ok := value.(float64)
+	if !ok || schema != float64(int(schema)) {
+		return 0, fmt.Errorf("invalid schema %v", value)
+	}
+	return int(schema), nil
+}
+
+// upgradeEvent rewrites a decoded event of an older schema in the current one, and
+// returns it encoded again
+func upgradeEvent(raw map[string]interface{}, schema int) ([]byte, error) {
+	if schema < MinSchemaVersion || schema > SchemaVersion {
+		return nil, fmt.Errorf("unsupported schema %d", schema)
+	}
+	if schema < 2 {
+		upgradeSchema1(raw)
+	}
+	raw["schema"] = SchemaVersion
+	return json.Marshal(raw)
+}
+
+// upgradeSchema1 upgrades a schema 1 event. Its test cases named their file in
+// filePath and their describe block in suiteName rather than listing parentNames.
+func upgradeSchema1(raw map[string]interface{}) {
+	if raw["eventType"] != string(EventTypeTestCase) {
+		return
+	}
+	payload, ok := raw["payload"].(map[string]interface{})
+	if !ok {
+		return
+	}
+	if _, ok := payload["parentNames"]; ok {
+		return
+	}
+	var parents []interface{}
+	if filePath, ok := payload["filePath"].(string); ok && filePath != "" {
+		parents = append(parents, filePath)
+	}
+	if suiteName, ok := payload["suiteName"].(string); ok && suiteName != "" {
+		parents = append(parents, suiteName)
+	}
+	if len(parents) > 0 {
+		payload["parentNames"] = parents
+	}
+	delete(payload, "filePath")
+	delete(payload, "suiteName")
+}
@@ -0,0 +1,106 @@
+package ipc
+
+import (
+	"path/filepath"
+	"reflect"
+	"strings"
+	"testing"
+)
+
+// decodeLines returns the events the manager reads from lines
+func decodeLines(t *testing.T, lines ...string) []Event {
+	t.Helper()
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	manager, err := NewManager(ipcPath, &mockLogger{})
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	appendLine(t, ipcPath, strings.Join(lines, "\n")+"\n")
+	manager.Drain()
+	_ = manager.Cleanup()
+
+	var events []Event
+	for event := range manager.Events {
+		events = append(events, event)
+	}
+	return events
+}
+
+func TestDecode_CurrentSchema(t *testing.T) {
+	events := decodeLines(t,
+		`{"eventType":"adapterHandshake","schema":2,"payload":{"adapter":"jest","schema":2}}`,
+		`{"eventType":"testCase","schema":2,"payload":{"testName":"adds","parentNames":["math.test.js","math"],"status":"PASS","duration":3}}`,
+	)
+	if len(events) != 2 {
+		t.Fatalf("Expected 2 events, got %d: %v", len(events), events)
+	}
+	handshake, ok := events[0].(AdapterHandshakeEvent)
+	if !ok || handshake.Payload.Adapter != "jest" || handshake.Payload.Schema != SchemaVersion {
+		t.Errorf("Expected a jest handshake of schema %d, got %#v", SchemaVersion, events[0])
+	}
+	tc, ok := events[1].(GroupTestCaseEvent)
+	if !ok {
+		t.Fatalf("Expected a test case, got %#v", events[1])
+	}
+	if tc.Payload.TestName != "adds" || !reflect.DeepEqual(tc.Payload.ParentNames, []string{"math.test.js", "math"}) || tc.Payload.Duration != 3 {
+		t.Errorf("Unexpected test case payload: %#v", tc.Payload)
+	}
+}
+
+func TestDecode_LegacySchema(t *testing.T) {
+	// Schema 1 events have no schema field, and their test cases name their file and
+	// describe block instead of listing parentNames
+	events := decodeLines(t,
+		`{"eventType":"testCase","payload":{"filePath":"math.test.js","suiteName":"math","testName":"adds","status":"FAIL","error":{"message":"expected 3"}}}`,
+		`{"eventType":"testCase","payload":{"filePath":"util.test.js","testName":"trims","status":"PASS"}}`,
+		`{"eventType":"testGroupResult","payload":{"groupName":"math","parentNames":["math.test.js"],"status":"FAIL"}}`,
+	)
+	if len(events) != 3 {
+		t.Fatalf("Expected 3 events, got %d: %v", len(events), events)
+	}
+	tc := events[0].(GroupTestCaseEvent)
+	if !reflect.DeepEqual(tc.Payload.ParentNames, []string{"math.test.js", "math"}) {
+		t.Errorf("ParentNames = %q, want [math.test.js math]", tc.Payload.ParentNames)
+	}
+	if tc.Payload.Status != "FAIL" || tc.Payload.Error == nil || tc.Payload.Error.Message != "expected 3" {
+		t.Errorf("Fields were lost in the upgrade: %#v", tc.Payload)
+	}
+	if tc := events[1].(GroupTestCaseEvent); !reflect.DeepEqual(tc.Payload.ParentNames, []string{"util.test.js"}) {
+		t.Errorf("ParentNames = %q, want [util.test.js]", tc.Payload.ParentNames)
+	}
+	if result := events[2].(GroupResultEvent); result.Payload.GroupName != "math" || result.Payload.Status != "FAIL" {
+		t.Errorf("Unexpected group result: %#v", result.Payload)
+	}
+}
+
+func TestDecode_UnsupportedSchema(t *testing.T) {
+	// Events of a newer schema are skipped, but its handshake arrives to stop the run
+	events := decodeLines(t,
+		`{"eventType":"adapterHandshake","schema":9,"payload":{"adapter":"pytest","schema":9}}`,
+		`{"eventType":"testCase","schema":9,"payload":{"testName":"test_a","status":"PASS"}}`,
+	)
+	if len(events) != 1 {
+		t.Fatalf("Expected only the handshake, got %v", events)
+	}
+	if handshake, ok := events[0].(AdapterHandshakeEvent); !ok || handshake.Payload.Schema != 9 {
+		t.Errorf("Expected the schema 9 handshake, got %#v", events[0])
+	}
+}
+
+func TestCheckSchema(t *testing.T) {
+	for schema := MinSchemaVersion; schema <= SchemaVersion; schema++ {
+		if err := CheckSchema("jest", schema); err != nil {
+			t.Errorf("CheckSchema(%d) = %v, want nil", schema, err)
+		}
+	}
+	for _, schema := range []int{0, SchemaVersion + 1} {
+		err := CheckSchema("jest", schema)
+		if err == nil {
+			t.Errorf("CheckSchema(%d) = nil, want an error", schema)
+			continue
+		}
+		if !strings.Contains(err.Error(), "jest adapter") || !strings.Contains(err.Error(), ".3pio/runs/*/adapters") {
+			t.Errorf("Expected the error to name the adapter and its cache, got %v", err)
+		}
+	}
+}
@@ -0,0 +1,125 @@
+package ipc
+
+import (
+	"bytes"
+	"fmt"
+	"io"
+)
+
+// Transport is how adapters send their events to 3pio
+type Transport string
+
+const (
+	TransportFile  Transport = "file"  // Append to the IPC file (default)
+	TransportStdio Transport = "stdio" // Write to fd 3, or to stderr marked with EventSentinel
+)
+
+// ParseTransport parses the value of --ipc-transport
+func ParseTransport(value string) (Transport, error) {
+	switch Transport(value) {
+	case TransportFile, TransportStdio:
+		return Transport(value), nil
+	}
+	return "", fmt.Errorf("invalid IPC transport %q: expected file or stdio", value)
+}
+
+// TransportEnv tells adapters where to write their events: "file:<path>", "fd:<n>" or
+// "stderr". Adapters given a file descriptor they can't write fall back to stderr.
+const TransportEnv = "THREEPIO_IPC"
+
+// EventSentinel starts an event written to stderr. The event runs to the end of its
+// line, and may start in the middle of a line of ordinary output.
+const EventSentinel = "3PIO_EVT:"
+
+// Demuxer splits the events marked with EventSentinel out of a stderr stream. Events
+// go to events a whole line at a time, and everything else goes on to out.
+type Demuxer struct {
+	out     io.Writer
+	events  io.Writer
+	pending []byte // Output that may be the start of a sentinel
+	event   []byte // Event whose line hasn't ended yet
+	inEvent bool
+}
+
+// NewDemuxer returns a demuxer writing output to out and events to events
+func NewDemuxer(out, events io.Writer) *Demuxer {
+	return &Demuxer{out: out, events: events}
+}
+
+// Write passes p on to the output or the events
+func (d *Demuxer) Write(p []byte) (int, error) {
+	data := p
+	if len(d.pending) > 0 {
+		data = append(d.pending, p...)
+		d.pending = nil
+	}
+	sentinel := []byte(EventSentinel)
+	for len(data) > 0 {
+		if d.inEvent {
+			i := bytes.IndexByte(data, '\n')
+			if i < 0 {
+				d.event = append(d.event, data...)
+				break
+			}
+			d.event = append(d.event, data[:i+1]...)
+			data = data[i+1:]
+			if err := d.flushEvent(); err != nil {
+				return len(p), err
+			}
+			continue
+		}
+
+		i := bytes.Index(data, sentinel)
+		if i < 0 {
+			// Hold back an end that may be the start of a sentinel split across writes
+			keep := partialSentinel(data)
+			if _, err := d.out.Write(data[:len(data)-keep]); err != nil {
+				return len(p), err
+			}
+			d.pending = append([]byte(nil), data[len(data)-keep:]...)
+			break
+		}
+		if i > 0 {
+			if _, err := d.out.Write(data[:i]); err != nil {
+				return len(p), err
+			}
+		}
+		d.inEvent = true
+		data = data[i+len(sentinel):]
+	}
+	return len(p), nil
+}
+
+// Flush writes what is held back once the stream has ended. An event whose line never
+// ended is ended, so it is read if whole and counted as malformed if not.
+func (d *Demuxer) Flush() error {
+	if len(d.pending) > 0 {
+		if _, err := d.out.Write(d.pending); err != nil {
+			return err
+		}
+		d.pending = nil
+	}
+	if d.inEvent {
+		d.event = append(d.event, '\n')
+		return d.flushEvent()
+	}
+	return nil
+}
+
+// flushEvent writes the event read so far
+func (d *Demuxer) flushEvent() error {
+	_, err := d.events.Write(d.event)
+	d.event = d.event[:0]
+	d.inEvent = false
+	return err
+}
+
+// partialSentinel returns the length of the longest end of data that begins EventSentinel
+func partialSentinel(data []byte) int {
+	for n := min(len(EventSentinel)-1, len(data)); n > 0; n-- {
+		if bytes.HasSuffix(data, []byte(EventSentinel[:n])) {
+			return n
+		}
+	}
+	return 0
+}
@@ -0,0 +1,68 @@
+package ipc
+
+import (
+	"bytes"
+	"testing"
+)
+
+func TestDemuxer_InterleavedSentinels(t *testing.T) {
+	eventA := `{"eventType":"testCase","payload":{"testName":"a"}}`
+	eventB := `{"eventType":"testCase","payload":{"testName":"b"}}`
+	input := "ordinary line\n" +
+		// An event in the middle of a line whose rest follows it
+		"Running tests 3PIO_EVT:" + eventA + "\n... done\n" +
+		"3PIO_EVT:" + eventB + "\n" +
+		"mentions 3PIO but isn't an event\n" +
+		// Output ending in what could have been the start of a sentinel
+		"trailing 3PIO_EV"
+	wantOutput := "ordinary line\nRunning tests ... done\nmentions 3PIO but isn't an event\ntrailing 3PIO_EV"
+	wantEvents := eventA + "\n" + eventB + "\n"
+
+	// Whole, and split at every byte so sentinels and events span writes
+	for _, size := range []int{len(input), 1, 3, 7} {
+		var output, events bytes.Buffer
+		d := NewDemuxer(&output, &events)
+		for data := []byte(input); len(data) > 0; {
+			n := min(size, len(data))
+			if written, err := d.Write(data[:n]); err != nil || written != n {
+				t.Fatalf("Write = %d, %v, want %d", written, err, n)
+			}
+			data = data[n:]
+		}
+		if err := d.Flush(); err != nil {
+			t.Fatalf("Flush failed: %v", err)
+		}
+		if output.String() != wantOutput {
+			t.Errorf("Writes of %d bytes: output = %q, want %q", size, output.String(), wantOutput)
+		}
+		if events.String() != wantEvents {
+			t.Errorf("Writes of %d bytes: events = %q, want %q", size, events.String(), wantEvents)
+		}
+	}
+}
+
+func TestDemuxer_UnterminatedEvent(t *testing.T) {
+	var output, events bytes.Buffer
+	d := NewDemuxer(&output, &events)
+	_, _ = d.Write([]byte("output\n3PIO_EVT:{\"eventType\":\"testCa"))
+	if events.Len() != 0 {
+		t.Errorf("Expected the event to be held until its line ends, got %q", events.String())
+	}
+	if err := d.Flush(); err != nil {
+		t.Fatalf("Flush failed: %v", err)
+	}
+	if output.String() != "output\n" || events.String() != "{\"eventType\":\"testCa\n" {
+		t.Errorf("Output = %q, events = %q", output.String(), events.String())
+	}
+}
+
+func TestParseTransport(t *testing.T) {
+	for _, value := range []string{"file", "stdio"} {
+		if transport, err := ParseTransport(value); err != nil || string(transport) != value {
+			t.Errorf("ParseTransport(%q) = %q, %v", value, transport, err)
+		}
+	}
+	if _, err := ParseTransport("socket"); err == nil {
+		t.Error("Expected an error for an unknown transport")
+	}
+}
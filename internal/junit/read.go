@@ -0,0 +1,334 @@
+// Package junit reads JUnit XML files, as test runners and build tools write them, as
+// the events of a 3pio run of their tests
+package junit
+
+import (
+	"encoding/xml"
+	"errors"
+	"fmt"
+	"io"
+	"math"
+	"os"
+	"strconv"
+	"strings"
+
+	"github.com/zk/3pio/internal/ipc"
+)
+
+// JUnit XML as the tools that write it vary: a <testsuites> around the suites or a
+// single <testsuite>, and suites nested in suites
+type xmlSuite struct {
+	Name      string     `xml:"name,attr"`
+	Time      string     `xml:"time,attr"`
+	Suites    []xmlSuite `xml:"testsuite"`
+	TestCases []xmlCase  `xml:"testcase"`
+	SystemOut string     `xml:"system-out"`
+	SystemErr string     `xml:"system-err"`
+}
+
+type xmlCase struct {
+	Name      string      `xml:"name,attr"`
+	Classname string      `xml:"classname,attr"`
+	Time      string      `xml:"time,attr"`
+	File      string      `xml:"file,attr"`
+	Line      int         `xml:"line,attr"`
+	Failure   *xmlFailure `xml:"failure"`
+	Error     *xmlFailure `xml:"error"`
+	Skipped   *xmlSkipped `xml:"skipped"`
+	SystemOut string      `xml:"system-out"`
+	SystemErr string      `xml:"system-err"`
+}
+
+type xmlFailure struct {
+	Message string `xml:"message,attr"`
+	Type    string `xml:"type,attr"`
+	Text    string `xml:",chardata"`
+}
+
+type xmlSkipped struct {
+	Message string `xml:"message,attr"`
+}
+
+// suiteGroup counts the tests of a group while its events are built
+type suiteGroup struct {
+	path     []string
+	duration float64 // Milliseconds, from the suite's time; 0 adds up its tests
+	testTime float64 // Milliseconds of its tests and those of its subgroups
+	totals   ipc.GroupTotals
+}
+
+// Reader reads JUnit XML files as the events of one run of their tests: each
+// <testsuite> a root group, or a subgroup of the suite around it, and each test in
+// the group its classname names. A classname that extends the path of its suite
+// with dots, as 3pio writes them, is a subgroup per dotted part. Groups of the same
+// path in several files are one group.
+type Reader struct {
+	events []ipc.Event
+	groups map[string]*suiteGroup
+	order  []*suiteGroup // In the order they were started
+	prefix int           // Groups the suites of the file being added are under
+}
+
+// NewReader creates a reader with no files read yet
+func NewReader() *Reader {
+	return &Reader{groups: make(map[string]*suiteGroup)}
+}
+
+// Read reads a JUnit XML file as the events of a run of its tests
+func Read(path string) ([]ipc.Event, error) {
+	r := NewReader()
+	if err := r.Add(path, nil); err != nil {
+		return nil, err
+	}
+	return r.Events(), nil
+}
+
+// Add reads the suites of a JUnit XML file under the groups of parents, such as the
+// module of a multi-module build, or as root groups without them. Malformed XML is an
+// error with its line, and adds nothing.
+func (r *Reader) Add(path string, parents []string) error {
+	file, err := os.Open(path)
+	if err != nil {
+		return err
+	}
+	defer func() { _ = file.Close() }()
+
+	suites, err := decodeSuites(xml.NewDecoder(file))
+	if err != nil {
+		var syntaxErr *xml.SyntaxError
+		if errors.As(err, &syntaxErr) {
+			return fmt.Errorf("%s:%d: malformed XML: %s", path, syntaxErr.Line, syntaxErr.Msg)
+		}
+		return fmt.Errorf("%s: %w", path, err)
+	}
+	for _, suite := range suites {
+		if err := checkTimes(suite); err != nil {
+			return fmt.Errorf("%s: %w", path, err)
+		}
+	}
+
+	r.prefix = len(parents)
+	for _, suite := range suites {
+		r.addSuite(parents, suite)
+	}
+	return nil
+}
+
+// decodeSuites decodes the suites of the document's root element
+func decodeSuites(decoder *xml.Decoder) ([]xmlSuite, error) {
+	for {
+		token, err := decoder.Token()
+		if err == io.EOF {
+			return nil, fmt.Errorf("no <testsuites> or <testsuite> element")
+		}
+		if err != nil {
+			return nil, err
+		}
+		start, ok := token.(xml.StartElement)
+		if !ok {
+			continue
+		}
+
+		var suite xmlSuite
+		switch start.Name.Local {
+		case "testsuites":
+			if err := decoder.DecodeElement(&suite, &start); err != nil {
+				return nil, err
+			}
+			return suite.Suites, nil
+		case "testsuite":
+			if err := decoder.DecodeElement(&suite, &start); err != nil {
+				return nil, err
+			}
+			return []xmlSuite{suite}, nil
+		}
+		return nil, fmt.Errorf("root element is <%s>, not <testsuites> or <testsuite>", start.Name.Local)
+	}
+}
+
+// checkTimes checks the times of a suite, its tests and its subsuites are numbers of
+// seconds, so a file is added whole or not at all
+func checkTimes(suite xmlSuite) error {
+	if suite.Time != "" {
+		if _, err := parseMs(suite.Time); err != nil {
+			return fmt.Errorf("suite %q has time %q, not a number of seconds", suite.Name, suite.Time)
+		}
+	}
+	for _, tc := range suite.TestCases {
+		if tc.Time != "" {
+			if _, err := parseMs(tc.Time); err != nil {
+				return fmt.Errorf("test %q has time %q, not a number of seconds", tc.Name, tc.Time)
+			}
+		}
+	}
+	for _, child := range suite.Suites {
+		if err := checkTimes(child); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+func (r *Reader) addSuite(parent []string, suite xmlSuite) {
+	name := suite.Name
+	if name == "" && len(suite.TestCases) > 0 {
+		name = suite.TestCases[0].Classname
+	}
+	if name == "" {
+		name = "tests"
+	}
+	path := append(append([]string(nil), parent...), name)
+	group := r.group(path)
+	if suite.Time != "" {
+		group.duration, _ = parseMs(suite.Time)
+	}
+
+	for _, tc := range suite.TestCases {
+		r.addTestCase(path, tc)
+	}
+	for _, child := range suite.Suites {
+		r.addSuite(path, child)
+	}
+
+	// Output of the suite outside its tests
+	if suite.SystemOut != "" {
+		r.events = append(r.events, ipc.GroupStdoutChunkEvent{
+			EventType: string(ipc.EventTypeGroupStdout),
+			Payload:   ipc.OutputChunkPayload{GroupName: name, ParentNames: parent, Chunk: suite.SystemOut},
+		})
+	}
+	if suite.SystemErr != "" {
+		r.events = append(r.events, ipc.GroupStderrChunkEvent{
+			EventType: string(ipc.EventTypeGroupStderr),
+			Payload:   ipc.OutputChunkPayload{GroupName: name, ParentNames: parent, Chunk: suite.SystemErr},
+		})
+	}
+}
+
+func (r *Reader) addTestCase(suitePath []string, tc xmlCase) {
+	// The classname names groups of the file's suites, not the parents they were added under
+	path := suitePath
+	dotted := strings.Join(suitePath[r.prefix:], ".")
+	switch {
+	case tc.Classname == "" || tc.Classname == dotted || tc.Classname == suitePath[len(suitePath)-1]:
+	case strings.HasPrefix(tc.Classname, dotted+"."):
+		path = append(append([]string(nil), suitePath...), strings.Split(strings.TrimPrefix(tc.Classname, dotted+"."), ".")...)
+	default:
+		path = append(append([]string(nil), suitePath...), tc.Classname)
+	}
+
+	payload := ipc.TestCasePayload{
+		TestName:    tc.Name,
+		ParentNames: path,
+		Status:      "PASS",
+		Stdout:      tc.SystemOut,
+		Stderr:      tc.SystemErr,
+		File:        tc.File,
+		Line:        tc.Line,
+	}
+	if tc.Time != "" {
+		payload.Duration, _ = parseMs(tc.Time)
+	}
+	switch {
+	case tc.Failure != nil:
+		payload.Status = "FAIL"
+		payload.Error = testError(tc.Failure)
+	case tc.Error != nil:
+		payload.Status = "ERROR"
+		payload.Error = testError(tc.Error)
+	case tc.Skipped != nil:
+		payload.Status = "SKIP"
+		payload.SkipMessage = tc.Skipped.Message
+	}
+
+	for i := range path {
+		group := r.group(path[:i+1])
+		group.testTime += payload.Duration
+		switch payload.Status {
+		case "FAIL", "ERROR":
+			group.totals.Failed++
+		case "SKIP":
+			group.totals.Skipped++
+		default:
+			group.totals.Passed++
+		}
+		group.totals.Total++
+	}
+	r.events = append(r.events, ipc.GroupTestCaseEvent{EventType: string(ipc.EventTypeGroupTestCase), Payload: payload})
+}
+
+// testError turns a <failure> or <error> into a test error: its message, or the
+// first line of its text, with the text, usually the stack trace, as the stack
+func testError(failure *xmlFailure) *ipc.TestError {
+	text := strings.TrimSpace(failure.Text)
+	message := failure.Message
+	if message == "" {
+		message, _, _ = strings.Cut(text, "\n")
+	}
+	testErr := &ipc.TestError{Message: message, ErrorType: failure.Type}
+	if text != message {
+		testErr.Stack = text
+	}
+	return testErr
+}
+
+// parseMs parses JUnit's decimal seconds as milliseconds, rounded to the microsecond
+// so 0.029 isn't cut to 28ms where durations are truncated
+func parseMs(seconds string) (float64, error) {
+	s, err := strconv.ParseFloat(seconds, 64)
+	if err != nil {
+		return 0, err
+	}
+	return math.Round(s*1e6) / 1e3, nil
+}
+
+// group returns the group at path, sending its discovery and start the first time
+func (r *Reader) group(path []string) *suiteGroup {
+	key := strings.Join(path, "\x00")
+	if group, ok := r.groups[key]; ok {
+		return group
+	}
+	for i := 1; i < len(path); i++ {
+		r.group(path[:i])
+	}
+	group := &suiteGroup{path: append([]string(nil), path...)}
+	r.groups[key] = group
+	r.order = append(r.order, group)
+	name, parents := path[len(path)-1], path[:len(path)-1]
+	r.events = append(r.events,
+		ipc.GroupDiscoveredEvent{EventType: string(ipc.EventTypeGroupDiscovered), Payload: ipc.GroupDiscoveredPayload{GroupName: name, ParentNames: parents}},
+		ipc.GroupStartEvent{EventType: string(ipc.EventTypeGroupStart), Payload: ipc.GroupStartPayload{GroupName: name, ParentNames: parents}},
+	)
+	return group
+}
+
+// Events returns the events of the files read, ending their groups, innermost first,
+// and the run
+func (r *Reader) Events() []ipc.Event {
+	events := append([]ipc.Event(nil), r.events...)
+	for i := len(r.order) - 1; i >= 0; i-- {
+		group := r.order[i]
+		status := "PASS"
+		switch {
+		case group.totals.Failed > 0:
+			status = "FAIL"
+		case group.totals.Passed == 0 && group.totals.Skipped > 0:
+			status = "SKIP"
+		}
+		duration := group.duration
+		if duration == 0 {
+			duration = group.testTime
+		}
+		events = append(events, ipc.GroupResultEvent{
+			EventType: string(ipc.EventTypeGroupResult),
+			Payload: ipc.GroupResultPayload{
+				GroupName:   group.path[len(group.path)-1],
+				ParentNames: group.path[:len(group.path)-1],
+				Status:      status,
+				Duration:    duration,
+				Totals:      group.totals,
+			},
+		})
+	}
+	return append(events, ipc.RunCompleteEvent{EventType: ipc.EventTypeRunComplete})
+}
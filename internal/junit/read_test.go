@@ -0,0 +1,172 @@
+package junit
+
+import (
+	"os"
+	"path/filepath"
+	"slices"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/internal/ipc"
+)
+
+func TestRead(t *testing.T) {
+	// As pytest and Jest write them: the module or file in the classname, a skipped
+	// test, an error and a failure with its trace as the text
+	path := filepath.Join(t.TempDir(), "junit.xml")
+	doc := `<?xml version="1.0" encoding="UTF-8"?>
+<testsuites>
+  <testsuite name="pytest" time="1.250">
+    <testcase classname="tests.test_math" name="test_add" time="0.010" file="tests/test_math.py" line="3"/>
+    <testcase classname="tests.test_math" name="test_div" time="0.020">
+      <failure message="ZeroDivisionError: division by zero">Traceback (most recent call last):
+  File "tests/test_math.py", line 8, in test_div</failure>
+    </testcase>
+    <testcase classname="tests.test_db" name="test_connect">
+      <error message="fixture 'db' not found"/>
+    </testcase>
+    <testcase classname="tests.test_db" name="test_slow">
+      <skipped message="needs a database"/>
+      <system-out>skipped</system-out>
+    </testcase>
+  </testsuite>
+  <testsuite name="cart.test.js">
+    <testsuite name="Cart">
+      <testcase classname="Cart" name="adds an item" time="0.004"/>
+    </testsuite>
+  </testsuite>
+</testsuites>
+`
+	if err := os.WriteFile(path, []byte(doc), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	events, err := Read(path)
+	if err != nil {
+		t.Fatalf("Read failed: %v", err)
+	}
+	tests := make(map[string]ipc.TestCasePayload)
+	results := make(map[string]ipc.GroupResultPayload)
+	for _, event := range events {
+		switch e := event.(type) {
+		case ipc.GroupTestCaseEvent:
+			tests[e.Payload.TestName] = e.Payload
+		case ipc.GroupResultEvent:
+			results[strings.Join(append(append([]string(nil), e.Payload.ParentNames...), e.Payload.GroupName), "/")] = e.Payload
+		}
+	}
+	if _, ok := events[len(events)-1].(ipc.RunCompleteEvent); !ok {
+		t.Errorf("Expected the run to complete last, got %T", events[len(events)-1])
+	}
+
+	for name, want := range map[string][]string{
+		"test_add":     {"pytest", "tests.test_math"},
+		"test_connect": {"pytest", "tests.test_db"},
+		"adds an item": {"cart.test.js", "Cart"},
+	} {
+		if got := tests[name].ParentNames; !slices.Equal(got, want) {
+			t.Errorf("Expected %s in %v, got %v", name, want, got)
+		}
+	}
+	if add := tests["test_add"]; add.Status != "PASS" || add.Duration != 10 || add.File != "tests/test_math.py" || add.Line != 3 {
+		t.Errorf("Expected test_add to pass in 10ms at tests/test_math.py:3, got %+v", add)
+	}
+	div := tests["test_div"]
+	if div.Status != "FAIL" || div.Error == nil || div.Error.Message != "ZeroDivisionError: division by zero" ||
+		!strings.Contains(div.Error.Stack, `line 8, in test_div`) {
+		t.Errorf("Expected test_div to fail with its message and trace, got %+v", div.Error)
+	}
+	if connect := tests["test_connect"]; connect.Status != "ERROR" || connect.Error.Message != "fixture 'db' not found" {
+		t.Errorf("Expected test_connect to be an ERROR, got %+v", connect)
+	}
+	if slow := tests["test_slow"]; slow.Status != "SKIP" || slow.SkipMessage != "needs a database" || slow.Stdout != "skipped" {
+		t.Errorf("Expected test_slow to be skipped with its message and output, got %+v", slow)
+	}
+
+	if root := results["pytest"]; root.Status != "FAIL" || root.Duration != 1250 || root.Totals.Total != 4 || root.Totals.Failed != 2 {
+		t.Errorf("Expected the pytest suite to fail in 1250ms with 2 of 4 tests failed, got %+v", root)
+	}
+	if db := results["pytest/tests.test_db"]; db.Status != "FAIL" || db.Totals.Skipped != 1 {
+		t.Errorf("Expected tests.test_db to fail with a skipped test, got %+v", db)
+	}
+	if cart := results["cart.test.js"]; cart.Status != "PASS" || cart.Duration != 4 {
+		t.Errorf("Expected cart.test.js to take the time of its tests without a time of its own, got %+v", cart)
+	}
+}
+
+func TestRead_Malformed(t *testing.T) {
+	dir := t.TempDir()
+	for name, doc := range map[string]string{
+		"unclosed.xml": "<testsuites>\n  <testsuite name=\"a\">\n    <testcase name=\"x\"></testsuite>\n</testsuites>\n",
+		"html.xml":     "<html>\n</html>\n",
+	} {
+		path := filepath.Join(dir, name)
+		if err := os.WriteFile(path, []byte(doc), 0644); err != nil {
+			t.Fatal(err)
+		}
+		_, err := Read(path)
+		if err == nil || !strings.HasPrefix(err.Error(), path) {
+			t.Errorf("%s: expected an error naming the file, got %v", name, err)
+		}
+		if name == "unclosed.xml" && (err == nil || !strings.Contains(err.Error(), path+":3: malformed XML")) {
+			t.Errorf("Expected the line of the malformed element, got %v", err)
+		}
+	}
+}
+
+func TestReader_Parents(t *testing.T) {
+	// Surefire writes a file per test class, each read under its module's group
+	dir := t.TempDir()
+	write := func(name, doc string) string {
+		path := filepath.Join(dir, name)
+		if err := os.WriteFile(path, []byte(doc), 0644); err != nil {
+			t.Fatal(err)
+		}
+		return path
+	}
+	r := NewReader()
+	for _, file := range []struct {
+		path    string
+		parents []string
+	}{
+		{write("TEST-com.acme.CartTest.xml", `<testsuite name="com.acme.CartTest" time="0.5"><testcase classname="com.acme.CartTest" name="adds"/></testsuite>`), []string{"shop", "core"}},
+		{write("TEST-com.acme.TaxTest.xml", `<testsuite name="com.acme.TaxTest"><testcase classname="com.acme.TaxTest" name="rounds" time="0.25"><failure message="expected 2"/></testcase></testsuite>`), []string{"shop", "core"}},
+		{write("TEST-com.acme.ApiTest.xml", `<testsuite name="com.acme.ApiTest"><testcase classname="com.acme.ApiTest" name="serves" time="1"/></testsuite>`), []string{"shop", "api"}},
+	} {
+		if err := r.Add(file.path, file.parents); err != nil {
+			t.Fatalf("Add failed: %v", err)
+		}
+	}
+	if err := r.Add(write("bad.xml", `<testsuite name="x"><testcase name="y" time="soon"/></testsuite>`), nil); err == nil {
+		t.Errorf("Expected an error for a time that isn't a number")
+	}
+
+	discovered := 0
+	results := make(map[string]ipc.GroupResultPayload)
+	tests := make(map[string][]string)
+	for _, event := range r.Events() {
+		switch e := event.(type) {
+		case ipc.GroupDiscoveredEvent:
+			discovered++
+		case ipc.GroupResultEvent:
+			results[strings.Join(append(append([]string(nil), e.Payload.ParentNames...), e.Payload.GroupName), "/")] = e.Payload
+		case ipc.GroupTestCaseEvent:
+			tests[e.Payload.TestName] = e.Payload.ParentNames
+		}
+	}
+	if discovered != 6 {
+		t.Errorf("Expected 6 groups, the modules' once each, got %d", discovered)
+	}
+	if got := tests["rounds"]; !slices.Equal(got, []string{"shop", "core", "com.acme.TaxTest"}) {
+		t.Errorf("Expected rounds under its module, got %v", got)
+	}
+	if core := results["shop/core"]; core.Status != "FAIL" || core.Totals.Total != 2 || core.Duration != 250 {
+		t.Errorf("Expected shop/core to fail with 2 tests in 250ms, got %+v", core)
+	}
+	if shop := results["shop"]; shop.Totals.Total != 3 || shop.Duration != 1250 {
+		t.Errorf("Expected shop to have the 3 tests' time, got %+v", shop)
+	}
+	if _, ok := results["x"]; ok {
+		t.Errorf("Expected nothing of the file that failed to be read")
+	}
+}
@@ -60,13 +60,20 @@ type FileLogger struct {
 
 // NewFileLogger creates a new file-based logger
 func NewFileLogger() (*FileLogger, error) {
+	return NewFileLoggerIn("")
+}
+
+// NewFileLoggerIn creates a file-based logger writing to dir's .3pio/debug.log, or the
+// working directory's when dir is empty
+func NewFileLoggerIn(dir string) (*FileLogger, error) {
 	// Ensure .3pio directory exists
-	if err := os.MkdirAll(".3pio", 0755); err != nil {
+	stateDir := filepath.Join(dir, ".3pio")
+	if err := os.MkdirAll(stateDir, 0755); err != nil {
 		return nil, fmt.Errorf("failed to create .3pio directory: %w", err)
 	}
 
 	// Open debug log file in append mode
-	logPath := filepath.Join(".3pio", "debug.log")
+	logPath := filepath.Join(stateDir, "debug.log")
 	file, err := os.OpenFile(logPath, os.O_CREATE|os.O_APPEND|os.O_WRONLY, 0644)
 	if err != nil {
 		return nil, fmt.Errorf("failed to open debug log: %w", err)
@@ -80,7 +87,7 @@ func NewFileLogger() (*FileLogger, error) {
 		"---\n\n",
 		time.Now().Format(time.RFC3339),
 		os.Getpid(),
-		mustGetwd())
+		mustGetwd(dir))
 
 	if _, err := file.WriteString(header); err != nil {
 		_ = file.Close()
@@ -159,8 +166,11 @@ func (l *FileLogger) Close() error {
 	return nil
 }
 
-// mustGetwd returns the current working directory or "unknown"
-func mustGetwd() string {
+// mustGetwd returns dir, or the current working directory when it's empty, or "unknown"
+func mustGetwd(dir string) string {
+	if dir != "" {
+		return dir
+	}
 	wd, err := os.Getwd()
 	if err != nil {
 		return "unknown"
@@ -8,6 +8,7 @@ import (
 	"testing"
 	"time"
 
+	"github.com/zk/3pio/internal/ipc"
 	"github.com/zk/3pio/internal/logger"
 	"github.com/zk/3pio/internal/report"
 )
@@ -157,3 +158,223 @@ func TestDisplayGroupWithoutFailures(t *testing.T) {
 
 // TestFormatElapsedTime tests the elapsed time formatting
 // Removed elapsed time prefix from output; no longer testing formatElapsedTime
+
+// TestDisplayGroupWithAssertionDiffs tests that failed assertions show their values under the FAIL line
+func TestDisplayGroupWithAssertionDiffs(t *testing.T) {
+	// Capture stdout
+	old := os.Stdout
+	r, w, _ := os.Pipe()
+	os.Stdout = w
+
+	testLogger, _ := logger.NewFileLogger()
+	o := &Orchestrator{
+		runID:        "20250917T120000-test-run",
+		startTime:    time.Now(),
+		logger:       testLogger,
+		noTestGroups: make(map[string]bool),
+	}
+
+	tests := &report.TestGroup{
+		Name:        "tests",
+		ParentNames: []string{"rust-edge-cases"},
+		TestCases: []report.TestCase{
+			{
+				Name:   "test_assertion_failure",
+				Status: report.TestStatusFail,
+				Error:  &report.TestError{Message: "assertion `left == right` failed", Expected: "5", Actual: "4", Type: "assert_eq"},
+			},
+			{Name: "test_unexpected_panic", Status: report.TestStatusFail, Error: &report.TestError{Message: "Unexpected panic occurred!"}},
+			{Name: "test_normal_pass", Status: report.TestStatusPass},
+		},
+		Subgroups: make(map[string]*report.TestGroup),
+	}
+	group := &report.TestGroup{
+		Name:        "rust-edge-cases",
+		ParentNames: []string{},
+		Subgroups:   map[string]*report.TestGroup{"tests": tests},
+	}
+
+	o.displayGroupHierarchy(group, 0, 10.0)
+
+	// Restore stdout and read captured output
+	_ = w.Close()
+	os.Stdout = old
+
+	var buf bytes.Buffer
+	_, _ = io.Copy(&buf, r)
+	output := buf.String()
+
+	expected := "  ✕ tests > test_assertion_failure\n    - expected: 5\n    + actual:   4\n"
+	if !strings.Contains(output, expected) {
+		t.Errorf("Expected assertion diff under the FAIL line, got: %s", output)
+	}
+	if strings.Contains(output, "test_unexpected_panic") {
+		t.Errorf("Failures without assertion values should not be listed, got: %s", output)
+	}
+}
+
+// TestDisplayInterleavedGroups replays events from two crates whose test binaries ran
+// in parallel and checks each failure is printed under its own crate, in one block
+func TestDisplayInterleavedGroups(t *testing.T) {
+	testLogger := logger.NewTestLogger()
+	runDir := t.TempDir()
+	reportManager, err := report.NewManager(runDir, nil, testLogger, "cargo test", "cargo test")
+	if err != nil {
+		t.Fatalf("Failed to create report manager: %v", err)
+	}
+	if err := reportManager.Initialize("cargo test"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+	o := &Orchestrator{
+		runID:            "20250917T120000-test-run",
+		runDir:           runDir,
+		startTime:        time.Now(),
+		logger:           testLogger,
+		reportManager:    reportManager,
+		groupStartTimes:  make(map[string]time.Time),
+		groupFailedTests: make(map[string][]string),
+		completedGroups:  make(map[string]bool),
+		heldGroups:       make(map[string]float64),
+		noTestGroups:     make(map[string]bool),
+		testCaseStatuses: make(map[string]string),
+	}
+
+	start := func(name string, parents ...string) ipc.Event {
+		return ipc.GroupStartEvent{
+			EventType: string(ipc.EventTypeGroupStart),
+			Payload:   ipc.GroupStartPayload{GroupName: name, ParentNames: parents},
+		}
+	}
+	fail := func(name, expected, actual string, parents ...string) ipc.Event {
+		return ipc.GroupTestCaseEvent{
+			EventType: string(ipc.EventTypeTestCase),
+			Payload: ipc.TestCasePayload{
+				TestName:    name,
+				ParentNames: parents,
+				Status:      "FAIL",
+				Error:       &ipc.TestError{Message: "assertion failed", Expected: expected, Actual: actual},
+			},
+		}
+	}
+	result := func(name string, failed int, parents ...string) ipc.Event {
+		return ipc.GroupResultEvent{
+			EventType: string(ipc.EventTypeGroupResult),
+			Payload: ipc.GroupResultPayload{
+				GroupName:   name,
+				ParentNames: parents,
+				Status:      "FAIL",
+				Totals:      ipc.GroupTotals{Failed: failed, Total: failed},
+			},
+		}
+	}
+	events := []ipc.Event{
+		start("crate_a"),
+		start("lib", "crate_a"),
+		start("crate_b"),
+		start("unit", "crate_b"),
+		start("integration", "crate_a"),
+		fail("test_a_lib", "1", "2", "crate_a", "lib"),
+		fail("test_b_unit", "3", "4", "crate_b", "unit"),
+		result("lib", 1, "crate_a"),
+		// crate_a's result arrives while its integration tests still run
+		result("crate_a", 1),
+		fail("test_a_integration", "5", "6", "crate_a", "integration"),
+		result("unit", 1, "crate_b"),
+		result("crate_b", 1),
+		result("integration", 1, "crate_a"),
+	}
+
+	old := os.Stdout
+	r, w, _ := os.Pipe()
+	os.Stdout = w
+	for _, event := range events {
+		if err := reportManager.HandleEvent(event); err != nil {
+			t.Fatalf("HandleEvent failed: %v", err)
+		}
+		o.handleConsoleOutput(event)
+	}
+	o.displayHeldGroups()
+	_ = w.Close()
+	os.Stdout = old
+
+	var buf bytes.Buffer
+	_, _ = io.Copy(&buf, r)
+	_ = reportManager.Finalize(1)
+
+	// crate_b finished first; crate_a follows with the failures of both its binaries
+	want := []string{
+		"FAIL(1) $trun_dir/reports/crate_b/index.md",
+		"  ✕ unit > test_b_unit",
+		"    - expected: 3",
+		"    + actual:   4",
+		"FAIL(2) $trun_dir/reports/crate_a/index.md",
+		"  ✕ integration > test_a_integration",
+		"    - expected: 5",
+		"    + actual:   6",
+		"  ✕ lib > test_a_lib",
+		"    - expected: 1",
+		"    + actual:   2",
+	}
+	if got := strings.TrimRight(buf.String(), "\n"); got != strings.Join(want, "\n") {
+		t.Errorf("Console transcript:\n%s\nwant:\n%s", got, strings.Join(want, "\n"))
+	}
+	if len(o.heldGroups) != 0 {
+		t.Errorf("Expected no held groups, got %v", o.heldGroups)
+	}
+}
+
+// TestDisplayFailureSnippet checks the first failure is printed under the FAIL line,
+// capped by line after the runner's color codes are stripped
+func TestDisplayFailureSnippet(t *testing.T) {
+	testLogger, _ := logger.NewFileLogger()
+	o := &Orchestrator{
+		runID:        "20250917T120000-test-run",
+		startTime:    time.Now(),
+		logger:       testLogger,
+		noTestGroups: make(map[string]bool),
+		snippetLines: 3,
+	}
+	message := "\x1b[31mthread 'tests::test_b' panicked at src/lib.rs:4:5:\x1b[0m\n\x1b[1mline 2\x1b[0m\nline 3\nline 4\nline 5"
+	group := &report.TestGroup{
+		Name: "my_crate",
+		TestCases: []report.TestCase{
+			{Name: "test_a", Status: report.TestStatusPass},
+			{Name: "test_b", Status: report.TestStatusFail, Error: &report.TestError{Message: message}},
+			{Name: "test_c", Status: report.TestStatusFail, Error: &report.TestError{Message: "second failure"}},
+		},
+	}
+
+	capture := func() string {
+		old := os.Stdout
+		r, w, _ := os.Pipe()
+		os.Stdout = w
+		o.displayGroupHierarchy(group, 0, 0)
+		_ = w.Close()
+		os.Stdout = old
+		var buf bytes.Buffer
+		_, _ = io.Copy(&buf, r)
+		return buf.String()
+	}
+
+	want := "FAIL(2) PASS(1) $trun_dir/reports/my_crate/index.md\n" +
+		"  thread 'tests::test_b' panicked at src/lib.rs:4:5:\n" +
+		"  line 2\n" +
+		"  line 3\n" +
+		"  … see $trun_dir/reports/my_crate/index.md for full output\n"
+	if got := capture(); got != want {
+		t.Errorf("Output:\n%q\nwant:\n%q", got, want)
+	}
+
+	// In a terminal the snippet is dimmed
+	o.color = true
+	if got := capture(); !strings.Contains(got, "  \x1b[2mline 2\x1b[0m\n") {
+		t.Errorf("Expected dimmed snippet lines, got:\n%q", got)
+	}
+
+	// --snippet-lines 0 prints only the FAIL line
+	o.color = false
+	o.snippetLines = 0
+	if got := capture(); got != "FAIL(2) PASS(1) $trun_dir/reports/my_crate/index.md\n" {
+		t.Errorf("Expected no snippet with --snippet-lines 0, got:\n%q", got)
+	}
+}
@@ -0,0 +1,100 @@
+package orchestrator
+
+import (
+	"fmt"
+	"strings"
+	"time"
+)
+
+// DefaultHeartbeatInterval is how long the console may stay silent while the tests run
+// before a heartbeat line is printed, unless --heartbeat says otherwise
+const DefaultHeartbeatInterval = 60 * time.Second
+
+// printConsole prints output of the run to the console and remembers when, so the
+// heartbeat only fills silences
+func (o *Orchestrator) printConsole(s string) {
+	if s == "" {
+		return
+	}
+	o.consoleMu.Lock()
+	defer o.consoleMu.Unlock()
+	fmt.Fprint(o.stdout(), s)
+	o.lastConsoleOutput.Store(time.Now().UnixNano())
+}
+
+// startHeartbeat prints a line saying what is still running whenever the console has
+// been silent for the heartbeat interval (--heartbeat), since CI systems kill jobs that
+// print nothing for too long. It returns a function that stops it.
+func (o *Orchestrator) startHeartbeat(nativeDef interface{}) (stop func()) {
+	if o.heartbeatInterval <= 0 {
+		return func() {}
+	}
+	o.lastConsoleOutput.Store(time.Now().UnixNano())
+
+	stopCh := make(chan struct{})
+	done := make(chan struct{})
+	go func() {
+		defer close(done)
+		ticker := time.NewTicker(min(o.heartbeatInterval/10, time.Second))
+		defer ticker.Stop()
+		for {
+			select {
+			case <-stopCh:
+				return
+			case <-ticker.C:
+			}
+			if time.Since(time.Unix(0, o.lastConsoleOutput.Load())) < o.heartbeatInterval {
+				continue
+			}
+			var tests []string
+			if reporter, ok := nativeDef.(inFlightReporter); ok {
+				tests = reporter.RunningTests()
+			}
+			line := formatHeartbeat(time.Since(o.startTime), o.reportManager.RunningGroups(), tests)
+			o.printConsole(line + "\n")
+		}
+	}()
+	return func() {
+		close(stopCh)
+		<-done
+	}
+}
+
+// formatHeartbeat returns the heartbeat line for a run going for elapsed, e.g.
+// "… still running (4m20s): core > tests (2 tests in flight)". Only the innermost
+// running groups are listed, with the running tests inside each.
+func formatHeartbeat(elapsed time.Duration, runningGroups, runningTests []string) string {
+	line := fmt.Sprintf("… still running (%s)", elapsed.Round(time.Second))
+
+	var parts []string
+	for _, group := range runningGroups {
+		innermost := true
+		for _, other := range runningGroups {
+			if strings.HasPrefix(other, group+" > ") {
+				innermost = false
+				break
+			}
+		}
+		if !innermost {
+			continue
+		}
+		tests := 0
+		for _, test := range runningTests {
+			if strings.HasPrefix(test, group+" > ") {
+				tests++
+			}
+		}
+		switch tests {
+		case 0:
+			parts = append(parts, group)
+		case 1:
+			parts = append(parts, group+" (1 test in flight)")
+		default:
+			parts = append(parts, fmt.Sprintf("%s (%d tests in flight)", group, tests))
+		}
+	}
+	if len(parts) == 0 {
+		return line
+	}
+	return line + ": " + strings.Join(parts, ", ")
+}
@@ -0,0 +1,107 @@
+package orchestrator
+
+import (
+	"bufio"
+	"fmt"
+	"io"
+	"os"
+	"os/exec"
+	"runtime"
+	"sync"
+	"time"
+
+	"github.com/zk/3pio/internal/ipc"
+)
+
+// stdioDrainTimeout is how long the events still in the fd 3 pipe are read after the
+// test command exits, in case a process it left behind keeps the pipe open
+const stdioDrainTimeout = time.Second
+
+// stdioTransport carries the events adapters write to fd 3, or to stderr marked with
+// ipc.EventSentinel, into the IPC file, where the IPC manager reads them as usual
+type stdioTransport struct {
+	events   *os.File     // The IPC file, opened for appending
+	mu       sync.Mutex   // Keeps event lines from the pipe and stderr whole
+	demuxer  *ipc.Demuxer // Takes the events out of stderr
+	pipe     *os.File     // Read end of fd 3, nil where fds can't be passed (Windows)
+	child    *os.File     // Write end of fd 3, closed once the command started
+	pipeDone chan struct{}
+}
+
+// startStdioTransport sets cmd up to send its events over stdio. The command's stderr
+// goes to output with the events taken out of it.
+func (o *Orchestrator) startStdioTransport(cmd *exec.Cmd, output io.Writer) (*stdioTransport, error) {
+	events, err := os.OpenFile(o.ipcPath, os.O_WRONLY|os.O_APPEND|os.O_CREATE, 0644)
+	if err != nil {
+		return nil, fmt.Errorf("failed to open IPC file: %w", err)
+	}
+	t := &stdioTransport{events: events}
+	t.demuxer = ipc.NewDemuxer(output, t)
+	cmd.Stderr = t.demuxer
+
+	// Windows can't pass a file descriptor past stderr
+	transport := "stderr"
+	if runtime.GOOS != "windows" {
+		if t.pipe, t.child, err = os.Pipe(); err != nil {
+			_ = events.Close()
+			return nil, fmt.Errorf("failed to create IPC pipe: %w", err)
+		}
+		cmd.ExtraFiles = append(cmd.ExtraFiles, t.child)
+		transport = fmt.Sprintf("fd:%d", 2+len(cmd.ExtraFiles))
+		t.pipeDone = make(chan struct{})
+		go t.readPipe()
+	}
+	// The last value of a variable wins, replacing the file transport of commandEnv
+	cmd.Env = append(cmd.Env, fmt.Sprintf("%s=%s", ipc.TransportEnv, transport))
+	o.logger.Debug("Adapters send their events over %s", transport)
+	return t, nil
+}
+
+// started closes this process's copy of the pipe's write end, so the pipe ends when
+// the command and its children exit
+func (t *stdioTransport) started() {
+	if t.child != nil {
+		_ = t.child.Close()
+		t.child = nil
+	}
+}
+
+// Write appends event lines to the IPC file
+func (t *stdioTransport) Write(p []byte) (int, error) {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	return t.events.Write(p)
+}
+
+// readPipe copies the events written to fd 3 into the IPC file a line at a time
+func (t *stdioTransport) readPipe() {
+	defer close(t.pipeDone)
+	reader := bufio.NewReader(t.pipe)
+	for {
+		line, err := reader.ReadBytes('\n')
+		if len(line) > 0 {
+			_, _ = t.Write(line)
+		}
+		if err != nil {
+			return
+		}
+	}
+}
+
+// close finishes carrying the events once the command has exited
+func (t *stdioTransport) close() error {
+	t.started()
+	if t.pipe != nil {
+		select {
+		case <-t.pipeDone:
+		case <-time.After(stdioDrainTimeout):
+		}
+		_ = t.pipe.Close()
+		<-t.pipeDone
+	}
+	err := t.demuxer.Flush()
+	if closeErr := t.events.Close(); err == nil {
+		err = closeErr
+	}
+	return err
+}
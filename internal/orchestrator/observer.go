@@ -0,0 +1,107 @@
+package orchestrator
+
+import (
+	"io"
+	"os"
+	"path/filepath"
+
+	"github.com/zk/3pio/internal/ipc"
+	"github.com/zk/3pio/internal/report"
+)
+
+// Observer is told about a run as it happens, for programs that embed the orchestrator.
+// Its methods are called from the event loop, one at a time.
+type Observer interface {
+	RunStarted(runID, runDir string) // Before the test command starts
+	Event(event ipc.Event)           // Every event of the runner or its adapter, once the reports have it
+}
+
+// Result summarizes a finished run
+type Result struct {
+	RunID      string
+	RunDir     string
+	ExitCode   int
+	ExitReason report.ExitReason // Empty when the run didn't get as far as starting the tests
+	Passed     int
+	Failed     int
+	Skipped    int
+	Flaky      int // Failed, then passed on a retry; not counted as failed
+	Errored    int // Setup or teardown failed (pytest fixtures); not counted as failed
+	Total      int
+}
+
+// Result returns the outcome of the run, once Run has returned
+func (o *Orchestrator) Result() Result {
+	result := Result{
+		RunID:    o.runID,
+		RunDir:   o.runDir,
+		ExitCode: o.exitCode,
+		Passed:   o.passedTests,
+		Failed:   o.failedTests,
+		Skipped:  o.skippedTests,
+		Flaky:    o.flakyTests,
+		Errored:  o.erroredTests,
+		Total:    o.totalTests,
+	}
+	if o.reportManager != nil {
+		result.ExitReason = o.reportManager.ExitReason()
+	}
+	return result
+}
+
+// stdout returns where the console output goes: Config.Console, or os.Stdout
+func (o *Orchestrator) stdout() io.Writer {
+	if o.consoleOut != nil {
+		return o.consoleOut
+	}
+	return os.Stdout
+}
+
+// environ returns the environment the test command starts from: 3pio's own, with
+// Config.Env on top
+func (o *Orchestrator) environ() []string {
+	return append(os.Environ(), o.env...)
+}
+
+// stateDir returns the directory of the runs and the quarantine and redact files
+func (o *Orchestrator) stateDir() string {
+	if o.outputDir != "" {
+		return o.outputDir
+	}
+	return filepath.Join(o.dir, ".3pio")
+}
+
+// workDir returns the directory the test command runs in: Config.Dir, or 3pio's
+// working directory; "" when neither is known
+func (o *Orchestrator) workDir() string {
+	if o.dir != "" {
+		return o.dir
+	}
+	wd, err := os.Getwd()
+	if err != nil {
+		return ""
+	}
+	return wd
+}
+
+// absPath makes a path absolute, resolving a relative one against Config.Dir
+func (o *Orchestrator) absPath(path string) (string, error) {
+	if o.dir == "" || filepath.IsAbs(path) {
+		return filepath.Abs(path)
+	}
+	return filepath.Join(o.dir, path), nil
+}
+
+// inDir resolves a relative path against dir, the run's Config.Dir; without one, or
+// for an absolute path, it returns the path as given
+func inDir(dir, path string) string {
+	if dir == "" || path == "" || filepath.IsAbs(path) {
+		return path
+	}
+	return filepath.Join(dir, path)
+}
+
+// runsDir returns the directory that holds a directory per run
+func (o *Orchestrator) runsDir() string {
+	return filepath.Join(o.stateDir(), "runs")
+}
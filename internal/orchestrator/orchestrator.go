@@ -8,19 +8,129 @@ import (
 	"os/exec"
 	"os/signal"
 	"path/filepath"
+	"sort"
 	"strings"
 	"sync"
+	"sync/atomic"
 	"syscall"
 	"time"
 
 	"github.com/zk/3pio/internal/adapters"
+	"github.com/zk/3pio/internal/console"
+	"github.com/zk/3pio/internal/coverage"
 	"github.com/zk/3pio/internal/ipc"
 	"github.com/zk/3pio/internal/logger"
 	"github.com/zk/3pio/internal/report"
+	"github.com/zk/3pio/internal/runinfo"
 	"github.com/zk/3pio/internal/runner"
 	"github.com/zk/3pio/internal/runner/definitions"
+	"github.com/zk/3pio/internal/runs"
 )
 
+// maxConsoleBuildErrors limits how many compiler errors are printed to the console
+const maxConsoleBuildErrors = 3
+
+// maxConsoleAssertionDiffs limits how many assertion diffs are printed under a failed group
+const maxConsoleAssertionDiffs = 3
+
+// DefaultSnippetLines is how many lines of a group's first failure are printed under
+// its FAIL line unless --snippet-lines says otherwise
+const DefaultSnippetLines = 15
+
+// timeoutExitCode is the exit code after --timeout or --inactivity-timeout stops the run,
+// as with coreutils timeout
+const timeoutExitCode = 124
+
+// noTestsExitCode is the exit code when the test filter matched no test, as with pytest,
+// unless --allow-no-tests is given
+const noTestsExitCode = 5
+
+// interruptGracePeriod is how long the test command gets to exit after a forwarded
+// Ctrl-C before its process tree is killed
+const interruptGracePeriod = 5 * time.Second
+
+// inFlightReporter is implemented by native definitions that track which tests are running
+type inFlightReporter interface {
+	RunningTests() []string // Tests that started but haven't finished, e.g. "my-crate > tests > test_hang"
+	MarkTimedOut()          // Report the running tests as TIMED_OUT when the output ends
+}
+
+// interruptReporter is implemented by native definitions that would otherwise report
+// a killed test binary as crashed
+type interruptReporter interface {
+	MarkInterrupted() // Leave the groups of the running test binary unfinished when the output ends
+}
+
+// runDirUser is implemented by native definitions that have the runner write files of
+// its own to the run directory, as dotnet test writes TRX files
+type runDirUser interface {
+	SetRunDir(runDir string) // Called before the command is built
+}
+
+// resultsSourceReporter is implemented by native definitions that read the results of
+// the tests from files once the command exits, rather than as the tests run
+type resultsSourceReporter interface {
+	ResultsSource() string // e.g. "junit_xml", for summary.json
+}
+
+// lineLimiter is implemented by native definitions that parse their output line by line
+type lineLimiter interface {
+	SetMaxLineLength(n int) // Cut longer lines before parsing them; 0 uses the default
+}
+
+// noTestsReporter is implemented by native definitions that can tell when the
+// command's test filter matched no test
+type noTestsReporter interface {
+	NoTestsMatched() bool
+}
+
+// filterReporter is implemented by native definitions that can tell which filters the
+// command narrows its tests down with, once the command is built
+type filterReporter interface {
+	TestFilters() []string
+}
+
+// snapshotReporter is implemented by native definitions that can find the snapshot
+// files a run wrote for review, as insta writes .snap.new files
+type snapshotReporter interface {
+	PendingSnapshots() []string // Called once the output ends
+}
+
+// coverageReporter is implemented by native definitions whose command can measure the
+// code coverage of the tests, as cargo llvm-cov does
+type coverageReporter interface {
+	Coverage() (*coverage.Summary, error) // Called once the output ends; nil when the run measured none
+}
+
+// streamErrorReporter is implemented by native definitions whose output can be
+// malformed in ways the tests it reports don't show, such as TAP without a plan
+type streamErrorReporter interface {
+	StreamError() string
+}
+
+// environmentErrorReporter is implemented by native definitions whose tests need more
+// than the runner to run, such as the WebDriver of wasm-pack test's headless browser
+type environmentErrorReporter interface {
+	EnvironmentError() string // How to set up what's missing, or "" when the tests ran
+}
+
+// exitCodeReporter is implemented by native definitions whose result is the command's
+// exit code, as there is nothing in the output to read it from
+type exitCodeReporter interface {
+	SetExitCode(code int) // Called before the output ends
+}
+
+// reporterReplacer is implemented by runners that run 3pio's reporter in place of the
+// ones the command chose, as Mocha runs only one
+type reporterReplacer interface {
+	ReplacedReporters(command []string) []string
+}
+
+// rerunFilter is implemented by runners that can narrow a command down to chosen tests
+type rerunFilter interface {
+	RerunCommand(command []string, tests []ipc.FailedTest) ([]string, error)
+}
+
 // Orchestrator manages the test execution lifecycle
 type Orchestrator struct {
 	runnerManager *runner.Manager
@@ -32,6 +142,30 @@ type Orchestrator struct {
 	runDir         string
 	ipcPath        string
 	command        []string
+	runnerName     string // Runner chosen with --runner; empty to detect it from the command
+	noBacktrace    bool   // --no-backtrace: leave RUST_BACKTRACE unset for cargo test
+	rerunFailed    bool   // --rerun-failed: run only the tests that failed in the latest run
+	rerunOf        string // Run ID the re-run failed tests came from
+	rerunCount     int    // Number of failed tests being re-run
+	junitXMLPath   string // --junit-xml: also write the results as JUnit XML here
+	summaryJSON    string // --summary-json: copy summary.json here, "-" for stdout
+	ghaAnnotations bool   // --gha-annotations: print ::error workflow commands for failures
+	noSnippets     bool   // --no-snippets: don't embed source lines around failures in reports
+	allowNoTests   bool   // --allow-no-tests: exit 0 when the test filter matched no test
+	quarantinePath string // --quarantine: known-flaky tests, instead of .3pio/quarantine.toml
+	retries        int    // --retries: re-run failed tests up to this many times
+	failOnFlaky    bool   // --fail-on-flaky: fail the run when a test only passed on a retry
+	failOnXPass    bool   // --fail-on-xpass: fail the run when a test marked xfail passed
+	failOnWarnings bool   // --fail-on-warnings: fail the run when the runner recorded warnings
+	strictArgs     bool   // --strict-args: refuse watch flags instead of removing them
+	nativeRetries  bool   // The runner retries failed tests itself (cargo nextest --retries)
+	showProgress   bool   // --progress: keep a live progress line below the output
+	snippetLines   int    // --snippet-lines: lines of a group's first failure printed under it; 0 for none
+	maxLineLength  int    // --max-line-length: longest line of runner output parsed whole; 0 for the default
+	preflightList  bool   // --preflight-list: list the tests before running them, so they show as PENDING
+	redact         bool   // --redact: replace the values of secret environment variables in the reports
+	redactRaw      bool   // --redact-raw: also replace them in output.log and preflight-list.log
+	color          bool   // Console output uses colors, from --color, NO_COLOR and whether stdout is a terminal
 	exitCode       int
 	detectedRunner string // Track which test runner was detected
 
@@ -46,21 +180,77 @@ type Orchestrator struct {
 	passedTests      int                  // Track actual test cases
 	failedTests      int                  // Track actual test cases
 	skippedTests     int                  // Track actual test cases
+	ignoredTests     int                  // Track skipped tests that are ignored by default (Rust #[ignore])
 	xfailedTests     int                  // Track expected failures (xfail)
 	xpassedTests     int                  // Track unexpected passes (xpass)
 	totalTests       int                  // Track actual test cases
+	benchmarks       int                  // Track benchmark results (not counted as tests)
+	regressedBenches int                  // Track benchmarks that regressed against their baseline
+	quarantinedTests int                  // Track failures of quarantined tests (not counted as failed)
+	flakyTests       int                  // Track tests that passed on a retry (not counted as failed)
+	erroredTests     int                  // Track tests whose setup or teardown failed (not counted as failed)
 	displayedGroups  map[string]bool      // Track which groups we've already displayed
 	lastCollected    int                  // Track last collection count to avoid duplicates
 	groupStartTimes  map[string]time.Time // Track start time for each group
 	groupFailedTests map[string][]string  // Track failed test names by group
 	completedGroups  map[string]bool      // Track which groups have shown their final PASS/FAIL status
+	heldGroups       map[string]float64   // Top-level groups finished while subgroups still run, with their duration
 	noTestGroups     map[string]bool      // Track packages with no test files (Go specific)
+	testCaseStatuses map[string]string    // Last status per test, so re-sent (retried) tests are counted once
+
+	// Live progress line while the tests run, nil without --progress
+	progress *console.ProgressRenderer
+
+	// How much the console shows, from -q/--quiet and -v/--verbose
+	verbosity console.Verbosity
+
+	// When the console uses colors, from --color
+	colorMode console.ColorMode
+
+	// How adapters send their events, from --ipc-transport; the IPC file by default
+	ipcTransport ipc.Transport
+
+	consoleOut  io.Writer // Where the console output goes; nil for stdout
+	observer    Observer  // Told about the run as it happens, nil for none
+	eventStream io.Writer // Where the events are written as JSON lines (--output json), nil for nowhere
+	env         []string  // Variables for the test command on top of 3pio's environment
+	dir         string    // Absolute directory the test command runs in, which holds .3pio; empty for 3pio's working directory
+	outputDir   string    // Directory of the runs and the quarantine and redact files; empty for .3pio
+
+	// Serializes console output from the event loop and the heartbeat
+	consoleMu sync.Mutex
 
 	// Error capture
 	stderrCapture strings.Builder
 
 	// Cargo test support
 	cargoProcessExited chan<- struct{}
+
+	processTree *processTree // The test command and its children, so signals and kills reach the whole tree
+
+	runnerLogger *logger.FileLogger // Logger of the runner definitions, for fresh ones when retrying
+
+	// Timeouts (--timeout, --inactivity-timeout)
+	timeout           time.Duration // Stop the run after this long in total; 0 waits forever
+	inactivityTimeout time.Duration // Stop the run when no output or event arrives for this long; 0 waits forever
+	lastActivity      atomic.Int64  // UnixNano of the last output or IPC event
+	heartbeatInterval time.Duration // --heartbeat: print what is running after this long without console output; 0 never
+	lastConsoleOutput atomic.Int64  // UnixNano of the last output printed to the console
+
+	slowThreshold time.Duration // --slow-threshold: group reports sort tests by duration past it; 0 for the default
+
+	quarantine *report.Quarantine // Known-flaky tests whose failures don't fail the run, nil for none
+
+	redactor *report.Redactor // Replaces secret values in what the run writes (--redact), nil for none
+
+	// Receives the toolchain and environment metadata collected while the tests run
+	runInfo <-chan *runinfo.Metadata
+
+	// Part of a sharded test suite the command runs (jest --shard=2/4), nil for all of it
+	shard *runinfo.Shard
+
+	// Receives the error when an adapter's handshake names a schema this build can't read
+	schemaMismatch chan error
 }
 
 // TailReader implements io.Reader that tails a file until signaled to stop
@@ -102,8 +292,41 @@ type Logger interface {
 
 // Config holds orchestrator configuration
 type Config struct {
-	Command []string
-	Logger  Logger
+	Command           []string
+	Runner            string        // Runner name to use instead of detecting it (e.g. "criterion")
+	NoBacktrace       bool          // Don't set RUST_BACKTRACE=1 for cargo test
+	Timeout           time.Duration // Kill the run after this long in total; 0 disables it
+	InactivityTimeout time.Duration // Kill the run after this long without output or events; 0 disables it
+	RerunFailed       bool          // Run only the tests that failed in the latest run
+	JUnitXMLPath      string        // Also write the results as JUnit XML to this path
+	SummaryJSON       string        // Also copy summary.json to this path, "-" to print it after the summary
+	SlowThreshold     time.Duration // Group reports sort tests by duration once one is slower; 0 uses the default
+	GHAAnnotations    bool          // Print GitHub Actions ::error commands for failures with a location
+	NoSnippets        bool          // Don't embed the source lines around failures in group reports
+	AllowNoTests      bool          // Exit 0 instead of 5 when the test filter matched no test
+	QuarantinePath    string        // Quarantine file to use instead of .3pio/quarantine.toml
+	Retries           int           // Re-run failed tests up to this many times; 0 disables retries
+	FailOnFlaky       bool          // Fail the run when a test failed and then passed on a retry
+	FailOnXPass       bool          // Fail the run when a test marked as an expected failure passed
+	FailOnWarnings    bool          // Fail the run when the runner recorded warnings (pytest)
+	StrictArgs        bool          // Refuse watch flags, which never let the run exit, instead of removing them
+	SnippetLines      int           // Print this many lines of a group's first failure under it; 0 disables it
+	HeartbeatInterval time.Duration // Without a progress line, say what is running after this long without output; 0 disables it
+	MaxLineLength     int           // Parse at most this many bytes of a line of native runner output; 0 uses the default
+	Progress          bool          // Keep a live progress line below the output, for interactive terminals
+	PreflightList     bool          // List the tests before running them, for runners that can
+	Redact            bool          // Replace the values of secret environment variables in the reports
+	RedactRaw         bool          // Also replace them in output.log; implies Redact
+	Env               []string      // "NAME=value" variables for the test command, on top of 3pio's environment
+	Dir               string        // Directory the test command runs in, which holds .3pio and relative paths are resolved against; empty for the working directory
+	OutputDir         string        // Directory for the runs and the quarantine and redact files; empty for .3pio
+	Logger            Logger
+	Verbosity         console.Verbosity // Console detail; the run directory is the same at every level
+	Color             console.ColorMode // When the console uses colors; reports never do
+	IPCTransport      ipc.Transport     // How adapters send their events; empty uses the IPC file
+	Console           io.Writer         // Where the console output goes; nil for stdout
+	Observer          Observer          // Told about the run and its events as they happen; may be nil
+	EventStream       io.Writer         // Also write the events as JSON lines here (--output json); may be nil
 }
 
 // New creates a new orchestrator
@@ -115,27 +338,79 @@ func New(config Config) (*Orchestrator, error) {
 	// Cast logger to FileLogger for runner manager
 	// In tests, we use TestLogger which doesn't need file operations
 	var runnerMgr *runner.Manager
+	var runnerLogger *logger.FileLogger
 	if fileLogger, ok := config.Logger.(*logger.FileLogger); ok {
+		runnerLogger = fileLogger
 		runnerMgr = runner.NewManager(fileLogger)
 	} else if testLogger, ok := config.Logger.(*logger.TestLogger); ok {
 		// For tests, create a temporary FileLogger for the runner manager
 		// The test logger will still capture all logs via the orchestrator's logger field
-		tempLogger, _ := logger.NewFileLogger()
-		runnerMgr = runner.NewManager(tempLogger)
+		runnerLogger, _ = logger.NewFileLogger()
+		runnerMgr = runner.NewManager(runnerLogger)
 		_ = testLogger // avoid unused variable warning
 	} else {
 		return nil, fmt.Errorf("logger must be a *logger.FileLogger or *logger.TestLogger")
 	}
 
+	dir := config.Dir
+	if dir != "" {
+		abs, err := filepath.Abs(dir)
+		if err != nil {
+			return nil, fmt.Errorf("invalid directory %s: %w", dir, err)
+		}
+		dir = abs
+		runnerMgr.SetWorkDir(dir)
+	}
+	summaryJSON := config.SummaryJSON
+	if summaryJSON != "-" {
+		summaryJSON = inDir(dir, summaryJSON)
+	}
+
 	return &Orchestrator{
-		runnerManager:    runnerMgr,
-		logger:           config.Logger,
-		command:          config.Command,
-		displayedGroups:  make(map[string]bool),
-		groupStartTimes:  make(map[string]time.Time),
-		groupFailedTests: make(map[string][]string),
-		completedGroups:  make(map[string]bool),
-		noTestGroups:     make(map[string]bool),
+		runnerManager:     runnerMgr,
+		runnerLogger:      runnerLogger,
+		logger:            config.Logger,
+		command:           config.Command,
+		runnerName:        config.Runner,
+		noBacktrace:       config.NoBacktrace,
+		rerunFailed:       config.RerunFailed,
+		junitXMLPath:      inDir(dir, config.JUnitXMLPath),
+		summaryJSON:       summaryJSON,
+		ghaAnnotations:    config.GHAAnnotations,
+		noSnippets:        config.NoSnippets,
+		allowNoTests:      config.AllowNoTests,
+		quarantinePath:    inDir(dir, config.QuarantinePath),
+		retries:           config.Retries,
+		failOnFlaky:       config.FailOnFlaky,
+		failOnXPass:       config.FailOnXPass,
+		failOnWarnings:    config.FailOnWarnings,
+		strictArgs:        config.StrictArgs,
+		showProgress:      config.Progress,
+		snippetLines:      config.SnippetLines,
+		maxLineLength:     config.MaxLineLength,
+		preflightList:     config.PreflightList,
+		redact:            config.Redact || config.RedactRaw,
+		redactRaw:         config.RedactRaw,
+		verbosity:         config.Verbosity,
+		colorMode:         config.Color,
+		ipcTransport:      config.IPCTransport,
+		consoleOut:        config.Console,
+		observer:          config.Observer,
+		eventStream:       config.EventStream,
+		env:               config.Env,
+		dir:               dir,
+		outputDir:         inDir(dir, config.OutputDir),
+		slowThreshold:     config.SlowThreshold,
+		timeout:           config.Timeout,
+		inactivityTimeout: config.InactivityTimeout,
+		heartbeatInterval: config.HeartbeatInterval,
+		displayedGroups:   make(map[string]bool),
+		groupStartTimes:   make(map[string]time.Time),
+		groupFailedTests:  make(map[string][]string),
+		completedGroups:   make(map[string]bool),
+		heldGroups:        make(map[string]float64),
+		noTestGroups:      make(map[string]bool),
+		testCaseStatuses:  make(map[string]string),
 	}, nil
 }
 
@@ -154,9 +429,42 @@ func (o *Orchestrator) Run() error {
 		_ = o.Close()
 	}()
 
+	if err := o.preventWatchMode(); err != nil {
+		o.exitCode = 1
+		return err
+	}
+
+	// --rerun-failed narrows the command to the latest run's failures, or skips the run
+	if o.rerunFailed {
+		rerun, err := o.prepareRerun()
+		if err != nil {
+			o.exitCode = 1
+			return err
+		}
+		if !rerun {
+			return nil
+		}
+	}
+
+	// A broken quarantine file would otherwise fail the run on a flaky test
+	if err := o.loadQuarantine(); err != nil {
+		o.exitCode = 1
+		return err
+	}
+	// Likewise, a broken redact file would leak the secrets it names
+	if err := o.loadRedactor(); err != nil {
+		o.exitCode = 1
+		return err
+	}
+
+	// THREEPIO_AUTO_CLEAN prunes old runs before this one starts
+	if runs.AutoCleanEnabled() {
+		o.autoClean()
+	}
+
 	// Generate run ID
 	o.runID = generateRunID()
-	o.runDir = filepath.Join(".3pio", "runs", o.runID)
+	o.runDir = filepath.Join(o.runsDir(), o.runID)
 
 	// Setup IPC in the run directory (do this early so it's available even if runner detection fails)
 	o.ipcPath = filepath.Join(o.runDir, "ipc.jsonl")
@@ -167,27 +475,45 @@ func (o *Orchestrator) Run() error {
 	trunDir := o.runDir
 	fullReport := "$trun_dir/test-run.md"
 
-	// Get current working directory
-	cwd, err := os.Getwd()
-	if err != nil {
+	// Get the directory the tests run in
+	cwd := o.workDir()
+	if cwd == "" {
 		cwd = "unknown"
 	}
 
-	fmt.Println("---")
-	fmt.Printf("current_time: %s\n", currentTime)
-	fmt.Printf("cwd: %s\n", cwd)
-	fmt.Printf("test_command: `%s`\n", testCommand)
-	fmt.Printf("trun_dir: %s\n", trunDir)
-	fmt.Printf("full_report: %s\n", fullReport)
-	fmt.Println("---")
-	fmt.Println()
-	fmt.Println("Test execution starting, no output until test results.")
-	fmt.Println()
+	// --quiet leaves out everything but the failures and the summary
+	if o.verbosity != console.Quiet {
+		fmt.Fprintln(o.stdout(), "---")
+		fmt.Fprintf(o.stdout(), "current_time: %s\n", currentTime)
+		fmt.Fprintf(o.stdout(), "cwd: %s\n", cwd)
+		fmt.Fprintf(o.stdout(), "test_command: `%s`\n", testCommand)
+		fmt.Fprintf(o.stdout(), "trun_dir: %s\n", trunDir)
+		fmt.Fprintf(o.stdout(), "full_report: %s\n", fullReport)
+		fmt.Fprintln(o.stdout(), "---")
+		fmt.Fprintln(o.stdout())
+		fmt.Fprintln(o.stdout(), "Test execution starting, no output until test results.")
+		fmt.Fprintln(o.stdout())
+	}
+
+	if o.observer != nil {
+		o.observer.RunStarted(o.runID, o.runDir)
+	}
 
 	// Detect test runner
-	runnerDef, err := o.runnerManager.Detect(o.command)
+	runnerDef, err := o.selectRunner()
 	if err != nil {
-		return fmt.Errorf("failed to detect test runner: %w", err)
+		return err
+	}
+
+	// --retries has nextest retry failed tests itself, and re-runs them for other runners
+	if o.retries > 0 {
+		if retrier, ok := runnerDef.(nativeRetrier); ok {
+			o.command = retrier.RetriesCommand(o.command, o.retries)
+			o.nativeRetries = true
+		} else if _, ok := runnerDef.(rerunFilter); !ok {
+			o.exitCode = 1
+			return fmt.Errorf("--retries is not supported for this test runner")
+		}
 	}
 
 	// Create IPC manager
@@ -198,6 +524,7 @@ func (o *Orchestrator) Run() error {
 	// Cleanup will be called explicitly later, not deferred
 
 	// Start watching for events
+	o.schemaMismatch = make(chan error, 1)
 	if err := o.ipcManager.WatchEvents(); err != nil {
 		return fmt.Errorf("failed to start IPC watcher: %w", err)
 	}
@@ -219,6 +546,8 @@ func (o *Orchestrator) Run() error {
 		detectedRunner = "cypress"
 	case "mocha.js":
 		detectedRunner = "mocha"
+	case "playwright.js":
+		detectedRunner = "playwright"
 	case "":
 		// Native runner - determine which one based on the underlying definition
 		if nativeRunner, ok := runnerDef.(runner.NativeRunner); ok {
@@ -234,6 +563,30 @@ func (o *Orchestrator) Run() error {
 			case *definitions.NextestDefinition:
 				detectedRunner = "cargo nextest"
 				o.logger.Debug("Detected as cargo nextest")
+			case *definitions.CriterionDefinition:
+				detectedRunner = "cargo bench (criterion)"
+				o.logger.Debug("Detected as cargo bench (criterion)")
+			case *definitions.WasmPackDefinition:
+				detectedRunner = "wasm-pack test"
+				o.logger.Debug("Detected as wasm-pack test")
+			case *definitions.NodeTestDefinition:
+				detectedRunner = "node --test"
+				o.logger.Debug("Detected as node --test")
+			case *definitions.BunTestDefinition:
+				detectedRunner = "bun test"
+				o.logger.Debug("Detected as bun test")
+			case *definitions.DotnetTestDefinition:
+				detectedRunner = "dotnet test"
+				o.logger.Debug("Detected as dotnet test")
+			case *definitions.JUnitReportsDefinition:
+				detectedRunner = nativeDef.(*definitions.JUnitReportsDefinition).Name()
+				o.logger.Debug("Detected as %s", detectedRunner)
+			case *definitions.TAPDefinition:
+				detectedRunner = "tap"
+				o.logger.Debug("Detected as tap")
+			case *definitions.ExecDefinition:
+				detectedRunner = "exec"
+				o.logger.Debug("Running as exec")
 			default:
 				detectedRunner = fmt.Sprintf("unknown native (%T)", nativeDef)
 				o.logger.Debug("Unknown native type: %T", nativeDef)
@@ -252,6 +605,9 @@ func (o *Orchestrator) Run() error {
 	// Build modified command for logging
 	var modifiedCommand string
 	if adapterFile == "" {
+		if user, ok := nativeDefinition(runnerDef).(runDirUser); ok {
+			user.SetRunDir(o.runDir)
+		}
 		// Native runner
 		testCommandSlice := runnerDef.BuildCommand(o.command, "")
 		modifiedCommand = strings.Join(testCommandSlice, " ")
@@ -265,10 +621,46 @@ func (o *Orchestrator) Run() error {
 	if err != nil {
 		return fmt.Errorf("failed to create report manager: %w", err)
 	}
+	if o.rerunOf != "" {
+		o.reportManager.SetRerunOf(o.rerunOf, o.rerunCount)
+	} else if reporter, ok := nativeDefinition(runnerDef).(filterReporter); ok {
+		// A re-run's filters are the failed tests its header already counts
+		o.reportManager.SetTestFilters(reporter.TestFilters())
+	}
+	if reporter, ok := nativeDefinition(runnerDef).(resultsSourceReporter); ok {
+		o.reportManager.SetResultsSource(reporter.ResultsSource())
+	}
+	if o.junitXMLPath != "" {
+		o.reportManager.SetJUnitXMLPath(o.junitXMLPath)
+	}
+	if o.slowThreshold > 0 {
+		o.reportManager.SetSlowThreshold(o.slowThreshold)
+	}
+	if o.dir != "" {
+		o.reportManager.SetWorkDir(o.dir)
+	}
+	if !o.noSnippets {
+		if cwd := o.workDir(); cwd != "" {
+			o.reportManager.SetSourceRoot(cwd)
+		}
+	}
+	if o.quarantine != nil {
+		o.reportManager.SetQuarantine(o.quarantine)
+	}
+	if o.redactor != nil {
+		o.reportManager.SetRedactor(o.redactor)
+	}
+	if o.eventStream != nil {
+		o.reportManager.SetEventStream(o.eventStream)
+	}
+	o.setPreviousRun()
+	o.startRunInfo(runnerDef)
 	// Ensure report manager is finalized even on early return
 	defer func() {
 		if o.reportManager != nil {
+			o.finishRunInfo()
 			_ = o.reportManager.Finalize(o.exitCode, "")
+			o.redactRawLogs()
 		}
 	}()
 
@@ -288,14 +680,9 @@ func (o *Orchestrator) Run() error {
 	if adapterFileName == "" {
 		// Native runner - no adapter needed (e.g., Go test, cargo test, nextest)
 		isNativeRunner = true
-		// Try to get the native definition using type assertion
-		switch wrapper := runnerDef.(type) {
-		case *definitions.GoTestWrapper:
-			nativeDef = wrapper.GoTestDefinition
-		case *definitions.CargoTestWrapper:
-			nativeDef = wrapper.CargoTestDefinition
-		case *definitions.NextestWrapper:
-			nativeDef = wrapper.NextestDefinition
+		nativeDef = nativeDefinition(runnerDef)
+		if limiter, ok := nativeDef.(lineLimiter); ok {
+			limiter.SetMaxLineLength(o.maxLineLength)
 		}
 		testCommandSlice = runnerDef.BuildCommand(o.command, "")
 		o.logger.Debug("Using native runner for: %v", testCommandSlice)
@@ -307,6 +694,11 @@ func (o *Orchestrator) Run() error {
 		}
 		testCommandSlice = runnerDef.BuildCommand(o.command, adapterPath)
 		o.logger.Debug("Adapter path: %s", adapterPath)
+		if replacer, ok := runnerDef.(reporterReplacer); ok {
+			if reporters := replacer.ReplacedReporters(o.command); len(reporters) > 0 {
+				fmt.Fprintf(o.stdout(), "Note: 3pio's reporter runs in place of --reporter %s, as %s runs only one\n", strings.Join(reporters, ", "), o.detectedRunner)
+			}
+		}
 
 		// Update modified command now that we have the actual command
 		modifiedCommand = strings.Join(testCommandSlice, " ")
@@ -320,35 +712,50 @@ func (o *Orchestrator) Run() error {
 	sigChan := make(chan os.Signal, 1)
 	signal.Notify(sigChan, os.Interrupt, syscall.SIGTERM)
 
+	// Hard ceiling on the whole run, the preflight list included
+	var deadline <-chan time.Time
+	if o.timeout > 0 {
+		timer := time.NewTimer(o.timeout)
+		defer timer.Stop()
+		deadline = timer.C
+	}
+
+	// --preflight-list reports every test as PENDING before the tests start
+	if lister, ok := nativeDef.(testLister); ok && o.preflightList {
+		switch o.runPreflightList(lister, deadline, sigChan) {
+		case preflightInterrupted:
+			o.exitCode = 130
+			o.reportManager.SetInterrupted()
+			_ = o.ipcManager.Cleanup()
+			return nil
+		case preflightTimedOut:
+			o.exitCode = timeoutExitCode
+			o.reportManager.SetRunTimeout(o.timeout)
+			_ = o.ipcManager.Cleanup()
+			return nil
+		}
+	}
+
 	// Create command
 	cmd := exec.Command(testCommandSlice[0], testCommandSlice[1:]...)
 
-	// Set working directory to current directory (where 3pio was invoked)
-	if wd, err := os.Getwd(); err == nil {
+	// Set working directory to Config.Dir, or the directory 3pio was invoked in
+	if wd := o.workDir(); wd != "" {
 		cmd.Dir = wd
 		o.logger.Debug("Set working directory to: %s", wd)
 	} else {
-		o.logger.Error("Failed to get current working directory: %v", err)
+		o.logger.Error("Failed to get current working directory")
 	}
 
 	// Set environment
-	cmd.Env = append(os.Environ(), fmt.Sprintf("THREEPIO_IPC_PATH=%s", o.ipcPath))
-
-	// Add RUSTC_BOOTSTRAP=1 for cargo test to enable JSON output
-	if len(o.command) >= 2 && o.command[0] == "cargo" && o.command[1] == "test" {
-		cmd.Env = append(cmd.Env, "RUSTC_BOOTSTRAP=1")
-		o.logger.Debug("Added RUSTC_BOOTSTRAP=1 for cargo test JSON output")
-	}
-
-	// Add NEXTEST_EXPERIMENTAL_LIBTEST_JSON=1 for cargo nextest to enable JSON output
-	if len(o.command) >= 2 && o.command[0] == "cargo" && o.command[1] == "nextest" {
-		cmd.Env = append(cmd.Env, "NEXTEST_EXPERIMENTAL_LIBTEST_JSON=1")
-		o.logger.Debug("Added NEXTEST_EXPERIMENTAL_LIBTEST_JSON=1 for cargo nextest JSON output")
-	}
+	cmd.Env = o.commandEnv(o.ipcPath)
 
 	// Connect stdin to allow interactive prompts
 	cmd.Stdin = os.Stdin
 
+	// Stopping the run must reach the test binaries cargo spawns, not just cargo
+	prepareProcessTree(cmd)
+
 	// Create output.log for capturing all command output
 	outputPath := filepath.Join(o.runDir, "output.log")
 	outputFile, err := os.Create(outputPath)
@@ -396,6 +803,14 @@ func (o *Orchestrator) Run() error {
 		cmd.Stderr = outputFile
 	}
 
+	// --ipc-transport stdio: adapters send their events over fd 3 or stderr
+	var transport *stdioTransport
+	if o.ipcTransport == ipc.TransportStdio && !isNativeRunner {
+		if transport, err = o.startStdioTransport(cmd, outputFile); err != nil {
+			return err
+		}
+	}
+
 	// Debug: Log the exact command being executed
 	o.logger.Debug("Starting command: %s %v", cmd.Path, cmd.Args)
 	o.logger.Debug("Working directory: %s", cmd.Dir)
@@ -404,11 +819,43 @@ func (o *Orchestrator) Run() error {
 	// Start the command
 	if err := cmd.Start(); err != nil {
 		o.exitCode = 1 // Set error exit code
+		if transport != nil {
+			_ = transport.close()
+		}
 		return fmt.Errorf("failed to start test command: %w", err)
 	}
+	if transport != nil {
+		transport.started()
+	}
+	if tree, err := newProcessTree(cmd); err != nil {
+		o.logger.Debug("Failed to track process tree, only the test command will be stopped: %v", err)
+	} else {
+		o.processTree = tree
+		defer func() { _ = tree.close() }()
+	}
 
 	// Record start time for duration calculation
 	o.startTime = time.Now()
+	o.recordActivity()
+
+	// Colors on the console follow --color, and NO_COLOR in a terminal
+	consoleFile, _ := o.stdout().(*os.File)
+	o.color = o.colorMode.Enabled(consoleFile)
+
+	// Completed groups print above the progress line until the tests finish
+	if o.showProgress && o.verbosity != console.Quiet {
+		o.progress = console.NewProgressRenderer(o.stdout(), console.Width(consoleFile), o.color)
+		o.progress.Start()
+		defer o.progress.Stop()
+	}
+
+	// Stop a hung run once nothing has happened for the inactivity timeout
+	var inactive <-chan struct{}
+	stopWatchdog := make(chan struct{})
+	defer close(stopWatchdog)
+	if o.inactivityTimeout > 0 {
+		inactive = o.watchInactivity(outputPath, stopWatchdog)
+	}
 
 	// Open output.log for reading (tail -f style) only for native runners
 	var tailReader *os.File
@@ -420,6 +867,12 @@ func (o *Orchestrator) Run() error {
 		o.logger.Debug("Opened output.log for tailing: %s", outputPath)
 	}
 
+	// Without a progress line, CI logs get a heartbeat when nothing is printed for long
+	stopHeartbeat := func() {}
+	if o.progress == nil {
+		stopHeartbeat = o.startHeartbeat(nativeDef)
+	}
+
 	// Process events and output concurrently
 	var wg sync.WaitGroup
 	eventsDone := make(chan struct{})
@@ -477,15 +930,35 @@ func (o *Orchestrator) Run() error {
 
 	// Wait for command completion or signal
 	done := make(chan error, 1)
+	tree := o.processTree
 	go func() {
-		done <- cmd.Wait()
+		err := cmd.Wait()
+		if tree != nil {
+			tree.waited()
+		}
+		done <- err
 	}()
 
 	var commandErr error
+	var inFlight []string
+	var schemaErr error
+	timedOut := false
+	runTimedOut := false
+	interrupted := false
 	select {
 	case err := <-done:
-		commandErr = err
-		if err != nil {
+		if tree != nil && tree.interruptedAtTerminal(err) {
+			// The test command had the terminal, so Ctrl-C reached it rather than 3pio
+			o.logger.Info("Test command was interrupted from the terminal")
+			o.progress.Stop()
+			if reporter, ok := nativeDef.(interruptReporter); ok {
+				reporter.MarkInterrupted()
+			}
+			interrupted = true
+			o.exitCode = 130
+			o.reportManager.SetInterrupted()
+		} else if err != nil {
+			commandErr = err
 			if exitErr, ok := err.(*exec.ExitError); ok {
 				o.exitCode = exitErr.ExitCode()
 				o.logger.Debug("Command completed with exit code: %d", exitErr.ExitCode())
@@ -496,6 +969,9 @@ func (o *Orchestrator) Run() error {
 		} else {
 			o.logger.Debug("Command completed successfully")
 		}
+		if reporter, ok := nativeDef.(exitCodeReporter); ok {
+			reporter.SetExitCode(o.exitCode)
+		}
 		// Command finished, signal cargo reader if it exists
 		if o.cargoProcessExited != nil {
 			close(o.cargoProcessExited)
@@ -505,25 +981,83 @@ func (o *Orchestrator) Run() error {
 		o.logger.Debug("Command completed, waiting for readers to finish...")
 	case sig := <-sigChan:
 		o.logger.Info("Received signal: %v", sig)
-		_ = cmd.Process.Kill()
+		o.progress.Stop()
+		if reporter, ok := nativeDef.(interruptReporter); ok {
+			reporter.MarkInterrupted()
+		}
+		o.interruptProcess(cmd, done, sigChan)
+		interrupted = true
 		o.exitCode = 130 // Standard exit code for SIGINT
-		// Signal cargo reader if it exists (same as normal completion)
+		o.reportManager.SetInterrupted()
+		// Signal cargo reader so the remaining output is drained
 		if o.cargoProcessExited != nil {
 			close(o.cargoProcessExited)
 			o.logger.Debug("Signaled cargo reader that process was interrupted")
 		}
+	case schemaErr = <-o.schemaMismatch:
+		// The adapter's events would be misread, so stop before reporting wrong results
+		o.logger.Error("Stopping the run: %v", schemaErr)
+		o.stopProcessTree(cmd)
+		<-done
+		commandErr = schemaErr
+		o.exitCode = 1
+		if o.cargoProcessExited != nil {
+			close(o.cargoProcessExited)
+		}
+	case <-inactive:
+		inFlight = o.testsInFlight(nativeDef)
+		o.logger.Info("No output for %s, stopping test command", o.inactivityTimeout)
+		if reporter, ok := nativeDef.(inFlightReporter); ok {
+			reporter.MarkTimedOut()
+		}
+		o.stopProcessTree(cmd)
+		<-done
+		timedOut = true
+		o.exitCode = timeoutExitCode
+		o.reportManager.SetInactivityTimeout(o.inactivityTimeout, inFlight)
+		// Signal cargo reader so it reports the running tests as timed out
+		if o.cargoProcessExited != nil {
+			close(o.cargoProcessExited)
+			o.logger.Debug("Signaled cargo reader that process timed out")
+		}
+	case <-deadline:
+		o.logger.Info("Run timed out after %s, stopping test command", o.timeout)
+		if reporter, ok := nativeDef.(interruptReporter); ok {
+			reporter.MarkInterrupted()
+		}
+		o.stopProcessTree(cmd)
+		<-done
+		runTimedOut = true
+		o.exitCode = timeoutExitCode
+		o.reportManager.SetRunTimeout(o.timeout)
+		// Signal cargo reader so the remaining output is drained
+		if o.cargoProcessExited != nil {
+			close(o.cargoProcessExited)
+			o.logger.Debug("Signaled cargo reader that the run timed out")
+		}
 	}
 
 	// Wait for output capture to complete
 	wg.Wait()
 	o.logger.Debug("Output capture completed")
+	if transport != nil {
+		if err := transport.close(); err != nil {
+			o.logger.Error("Failed to forward the events sent over stdio: %v", err)
+		}
+	}
 
-	// Stop watching for events (this closes the Events channel and allows processEvents to exit)
+	// Read the events whose write notification is still on its way, then stop watching
+	// for events (this closes the Events channel and allows processEvents to exit)
+	o.ipcManager.Drain()
 	_ = o.ipcManager.Cleanup()
+	o.reportManager.AddMalformedIPCEvents(o.ipcManager.MalformedEvents())
 
 	// Wait for event processing to complete (channel is closed, range will exit)
 	<-eventsDone
 	o.logger.Debug("Event processing completed")
+	o.progress.Stop()
+	stopHeartbeat()
+	o.displayHeldGroups()
 
 	o.logger.Debug("Output capture completed")
 
@@ -540,16 +1074,58 @@ func (o *Orchestrator) Run() error {
 	// All goroutines should be finished at this point
 	// (they were waited for via outputDone)
 
+	// --retries re-runs the failed tests, and those that pass are flaky
+	if commandErr != nil && schemaErr == nil && o.retries > 0 && !o.nativeRetries && !interrupted && !timedOut && !runTimedOut && len(o.reportManager.GetBuildErrors()) == 0 {
+		result, retryInFlight := o.retryFailedTests(deadline, sigChan)
+		switch result {
+		case retryInterrupted:
+			interrupted = true
+			o.exitCode = 130
+			o.reportManager.SetInterrupted()
+		case retryTimedOut:
+			runTimedOut = true
+			o.exitCode = timeoutExitCode
+			o.reportManager.SetRunTimeout(o.timeout)
+		case retryInactive:
+			timedOut = true
+			inFlight = retryInFlight
+			o.exitCode = timeoutExitCode
+			o.reportManager.SetInactivityTimeout(o.inactivityTimeout, inFlight)
+		}
+	}
+
+	// Failures of quarantined tests, and of tests that passed on a retry, don't fail the run
+	toleratedHint := ""
+	if commandErr != nil && schemaErr == nil && !interrupted && !timedOut && !runTimedOut && o.reportManager.OnlyToleratedFailures(o.failOnFlaky) {
+		if o.stopsAtFirstFailure() {
+			toleratedHint = "Only quarantined or flaky tests failed, but cargo stopped at the first failing test binary; pass --no-fail-fast so they don't fail the run"
+		} else {
+			o.exitCode = 0
+			commandErr = nil
+		}
+	}
+
 	// Finalize report
 	var errorDetails string
 	var shouldShowError bool
 	if commandErr != nil {
+		// Exit code the runner uses for "tests ran and some failed"
+		testFailureExitCode := 1
+		if _, ok := nativeDef.(*definitions.NextestDefinition); ok {
+			testFailureExitCode = definitions.NextestExitTestRunFailed
+		}
+		// Whatever a plain command exits with, it's the result of its test
+		if _, ok := nativeDef.(*definitions.ExecDefinition); ok {
+			testFailureExitCode = o.exitCode
+		}
+
 		// Check if this is a configuration/startup error vs test failures
 		// Configuration errors happen when we have very few or no test groups
 		// or when the exit code suggests a setup problem
 		isConfigError := o.totalGroups == 0 ||
-			(o.exitCode != 0 && o.exitCode != 1 && o.totalGroups < 2) ||
-			(o.passedGroups == 0 && o.failedGroups == 0 && o.exitCode != 0)
+			(o.exitCode != 0 && o.exitCode != testFailureExitCode && o.totalGroups < 2) ||
+			(o.passedGroups == 0 && o.failedGroups == 0 && o.exitCode != 0) ||
+			runnerDef.InterpretExitCode(o.exitCode) == "error"
 
 		if isConfigError {
 			errorDetails = commandErr.Error()
@@ -583,51 +1159,210 @@ func (o *Orchestrator) Run() error {
 			}
 		}
 	}
+
+	// Compiler errors get their own report section, so keep the error summary short
+	buildErrors := o.reportManager.GetBuildErrors()
+	if commandErr != nil && len(buildErrors) > 0 {
+		errorDetails = fmt.Sprintf("Build failed with %d compile error(s)", len(buildErrors))
+		shouldShowError = true
+	}
+
+	// Files that failed to collect are errored groups with their tracebacks, and fail
+	// the run even if every collected test passed
+	collectionErrors := o.reportManager.CollectionErrors()
+	if len(collectionErrors) > 0 {
+		errorDetails = fmt.Sprintf("%d test file(s) failed to collect", len(collectionErrors))
+		shouldShowError = true
+		if o.exitCode == 0 {
+			o.exitCode = 1
+		}
+	}
+
+	// A stale adapter makes every other result suspect
+	if schemaErr != nil {
+		errorDetails = schemaErr.Error()
+		shouldShowError = true
+	}
+
+	// A crashed test binary has its own report section and the tests that finished
+	// still count, but the run must fail even if the crash went unnoticed by cargo
+	crashes := o.reportManager.GetCrashes()
+	if len(crashes) > 0 && len(buildErrors) == 0 {
+		errorDetails = ""
+		shouldShowError = false
+		if o.exitCode == 0 {
+			o.exitCode = 1
+		}
+	}
+
+	// nextest exits 0 when its retries passed, so --fail-on-flaky fails the run here
+	if o.failOnFlaky && o.exitCode == 0 && !interrupted && len(o.reportManager.FlakyTests()) > 0 {
+		o.exitCode = 1
+	}
+
+	// pytest exits 0 when an xfail test passes, unless the marker is strict
+	if o.failOnXPass && o.exitCode == 0 && !interrupted && len(o.reportManager.XPassedTests()) > 0 {
+		o.exitCode = 1
+	}
+
+	// Warnings never fail a pytest run on their own, short of -W error
+	if o.failOnWarnings && o.exitCode == 0 && !interrupted && len(o.reportManager.Warnings()) > 0 {
+		o.exitCode = 1
+	}
+
+	// cargo exits 0 when its filter matched nothing, which is rarely what was meant
+	stopped := interrupted || timedOut || runTimedOut
+	if reporter, ok := nativeDef.(noTestsReporter); ok && reporter.NoTestsMatched() && !stopped && len(buildErrors) == 0 {
+		o.reportManager.SetNoTestsFound()
+		if o.exitCode == 0 && !o.allowNoTests {
+			o.exitCode = noTestsExitCode
+		}
+	}
+
+	if reporter, ok := nativeDef.(snapshotReporter); ok {
+		o.reportManager.SetPendingSnapshots(reporter.PendingSnapshots())
+	}
+
+	// A missing coverage report is worth a warning, but the tests' results stand
+	var coverageWarning string
+	if reporter, ok := nativeDef.(coverageReporter); ok && !stopped && len(buildErrors) == 0 {
+		cov, err := reporter.Coverage()
+		if err != nil {
+			coverageWarning = err.Error()
+			o.logger.Info("No coverage to report: %v", err)
+		}
+		if cov != nil || coverageWarning != "" {
+			o.reportManager.SetCoverage(cov, coverageWarning)
+		}
+	}
+
+	// The tests that did report may all have passed, but the output can't be trusted
+	var streamErr string
+	if reporter, ok := nativeDef.(streamErrorReporter); ok && !stopped {
+		streamErr = reporter.StreamError()
+	}
+	if streamErr != "" {
+		errorDetails = streamErr
+		if o.exitCode == 0 {
+			o.exitCode = 1
+		}
+	}
+
+	// Nothing failed in the tests, so say what to set up instead
+	var envErr string
+	if reporter, ok := nativeDef.(environmentErrorReporter); ok {
+		envErr = reporter.EnvironmentError()
+	}
+	if envErr != "" {
+		o.reportManager.SetEnvironmentError()
+		errorDetails = envErr
+		if o.exitCode == 0 {
+			o.exitCode = 1
+		}
+	}
+
+	o.finishRunInfo()
 	if err := o.reportManager.Finalize(o.exitCode, errorDetails); err != nil {
 		o.logger.Error("Failed to finalize report: %v", err)
 	}
+	o.recordRun()
 
 	// If we didn't get GroupResult events, compute stats and display results from the report manager
 	if o.totalGroups == 0 {
 		o.computeStatsFromReportManager()
-		o.displayFinalResults()
+		if o.verbosity != console.Quiet {
+			o.displayFinalResults()
+		}
 	}
 
 	// Print completion message with TypeScript-style summary
-	fmt.Println()
+	if o.verbosity != console.Quiet {
+		fmt.Fprintln(o.stdout())
+	}
 
 	// Print error details if command failed and we have error details
 	if (commandErr != nil && errorDetails != "" && shouldShowError) ||
-		(commandErr != nil && o.totalGroups == 0 && errorDetails != "") {
-		fmt.Printf("Error: %s\n", errorDetails)
-		fmt.Println()
+		(commandErr != nil && o.totalGroups == 0 && errorDetails != "") || streamErr != "" || envErr != "" {
+		fmt.Fprintf(o.stdout(), "Error: %s\n", errorDetails)
+		fmt.Fprintln(o.stdout())
+	}
+
+	// Show why nothing ran when the tests failed to compile
+	if len(buildErrors) > 0 {
+		o.displayBuildErrors(buildErrors)
+	}
+
+	// Crashes leave some tests without results, so point them out before the summary
+	if len(crashes) > 0 {
+		o.displayCrashes(crashes)
 	}
 
-	// Add random failure exclamation if tests failed
-	if o.failedGroups > 0 {
+	// Show what was hanging when the run was stopped
+	if timedOut {
+		o.displayInactivityTimeout(inFlight)
+	}
+	// Entries that match nothing are likely stale, unless the run stopped early
+	if stopped := interrupted || timedOut || runTimedOut; !stopped && len(buildErrors) == 0 {
+		for _, entry := range o.quarantine.Unmatched() {
+			fmt.Fprintf(o.stdout(), "Warning: quarantine entry %q matched no test\n", entry)
+		}
+	}
+	if coverageWarning != "" {
+		fmt.Fprintf(o.stdout(), "Warning: no coverage to report: %s\n\n", coverageWarning)
+	}
+	if toleratedHint != "" {
+		fmt.Fprint(o.stdout(), toleratedHint + "\n\n")
+	}
+	if runTimedOut {
+		fmt.Fprintf(o.stdout(), "Run timed out after %.0fs, unfinished groups are marked INTERRUPTED\n\n", o.timeout.Seconds())
+	}
+	if interrupted {
+		fmt.Fprint(o.stdout(), "Run interrupted, unfinished groups are marked INTERRUPTED\n\n")
+	}
+
+	// Say what went wrong when it wasn't the tests, so the next step is clear
+	exitReason := o.reportManager.ExitReason()
+	switch {
+	case o.verbosity == console.Quiet:
+		// The failure digest replaces the per-group blocks
+		o.displayFailureDigest()
+	case exitReason == report.ExitReasonBuildFailed:
+		if len(collectionErrors) > 0 && len(buildErrors) == 0 {
+			fmt.Fprintln(o.stdout(), "Collection failed! Fix the errors in the test files above and run again")
+		} else {
+			fmt.Fprintln(o.stdout(), "Build failed! Fix the compile errors above and run again")
+		}
+	case exitReason == report.ExitReasonNoTestsFound:
+		fmt.Fprintln(o.stdout(), "No tests matched the filter")
+	case exitReason == report.ExitReasonEnvironmentError:
+		fmt.Fprintln(o.stdout(), "The tests couldn't run! Set up what the error above names and run again")
+	case exitReason == report.ExitReasonRunnerCrashed:
+		fmt.Fprintln(o.stdout(), "The test runner crashed before reporting every result")
+	case o.failedGroups > 0:
+		// Add random failure exclamation if tests failed
 		exclamations := []string{
 			"This is madness!",
 			"We're doomed!",
 			"Are you sure this thing is safe?",
 		}
 		randomExclamation := exclamations[time.Now().UnixNano()%int64(len(exclamations))]
-		fmt.Printf("Test failures! %s\n", randomExclamation)
+		fmt.Fprintf(o.stdout(), "Test failures! %s\n", randomExclamation)
 		// Test details are shown inline with each failing group
-	} else if o.passedGroups > 0 && o.skippedGroups == 0 {
+	case o.passedGroups > 0 && o.skippedGroups == 0:
 		// All tests that ran passed (no skips)
-		fmt.Println("Splendid! All tests passed successfully")
-	} else if o.passedGroups > 0 && o.skippedGroups > 0 {
+		fmt.Fprintln(o.stdout(), "Splendid! All tests passed successfully")
+	case o.passedGroups > 0 && o.skippedGroups > 0:
 		// Some tests passed, some were skipped
-		fmt.Println("Tests completed with some skipped")
-	} else if o.skippedGroups > 0 && o.passedGroups == 0 {
+		fmt.Fprintln(o.stdout(), "Tests completed with some skipped")
+	case o.skippedGroups > 0 && o.passedGroups == 0:
 		// Only skipped tests
-		fmt.Println("All tests were skipped")
+		fmt.Fprintln(o.stdout(), "All tests were skipped")
 	}
 
 	// Format results summary
 	// Show test case counts when we have actual test counts with skipped tests
 	// Otherwise show group counts (for compatibility with runners that don't report individual tests)
-	if o.totalTests > 0 && (o.skippedTests > 0 || o.xfailedTests > 0 || o.xpassedTests > 0 || strings.HasPrefix(o.detectedRunner, "cargo")) {
+	if o.totalTests > 0 && (o.skippedTests > 0 || o.ignoredTests > 0 || o.xfailedTests > 0 || o.xpassedTests > 0 || o.quarantinedTests > 0 || o.flakyTests > 0 || o.erroredTests > 0 || strings.HasPrefix(o.detectedRunner, "cargo")) {
 		// Show test case counts
 		// Build the results string dynamically to only include non-zero counts
 		var parts []string
@@ -635,17 +1370,29 @@ func (o *Orchestrator) Run() error {
 		if o.failedTests > 0 {
 			parts = append(parts, fmt.Sprintf("%d failed", o.failedTests))
 		}
+		if o.erroredTests > 0 {
+			parts = append(parts, fmt.Sprintf("%d errored", o.erroredTests))
+		}
 		if o.skippedTests > 0 {
 			parts = append(parts, fmt.Sprintf("%d skipped", o.skippedTests))
 		}
+		if o.ignoredTests > 0 {
+			parts = append(parts, fmt.Sprintf("%d ignored", o.ignoredTests))
+		}
 		if o.xfailedTests > 0 {
 			parts = append(parts, fmt.Sprintf("%d xfailed", o.xfailedTests))
 		}
 		if o.xpassedTests > 0 {
 			parts = append(parts, fmt.Sprintf("%d xpassed", o.xpassedTests))
 		}
+		if o.quarantinedTests > 0 {
+			parts = append(parts, fmt.Sprintf("%d quarantined", o.quarantinedTests))
+		}
+		if o.flakyTests > 0 {
+			parts = append(parts, fmt.Sprintf("%d flaky", o.flakyTests))
+		}
 		parts = append(parts, fmt.Sprintf("%d total", o.totalTests))
-		fmt.Printf("Results:     %s\n", strings.Join(parts, ", "))
+		fmt.Fprintf(o.stdout(), "Results:     %s\n", strings.Join(parts, ", "))
 	} else {
 		// Show group counts for other runners or when no test-level detail available
 		var parts []string
@@ -663,12 +1410,43 @@ func (o *Orchestrator) Run() error {
 			parts = append(parts, fmt.Sprintf("%d xpassed", o.xpassedGroups))
 		}
 		parts = append(parts, fmt.Sprintf("%d total", o.totalGroups))
-		fmt.Printf("Results:     %s\n", strings.Join(parts, ", "))
+		fmt.Fprintf(o.stdout(), "Results:     %s\n", strings.Join(parts, ", "))
+	}
+
+	if o.shard != nil {
+		fmt.Fprintf(o.stdout(), "Shard:       %s, the other shards' tests didn't run\n", o.shard)
+	}
+
+	// Benchmarks are not tests, so they get their own summary line
+	if o.benchmarks > 0 && o.regressedBenches > 0 {
+		fmt.Fprintf(o.stdout(), "Benchmarks:  %d (%d regressed)\n", o.benchmarks, o.regressedBenches)
+	} else if o.benchmarks > 0 {
+		fmt.Fprintf(o.stdout(), "Benchmarks:  %d\n", o.benchmarks)
+	}
+
+	// insta's snapshots wait for review after the run
+	if snapshots := o.reportManager.PendingSnapshots(); len(snapshots) > 0 {
+		fmt.Fprintf(o.stdout(), "Snapshots:   %d pending, run `cargo insta review` to accept or reject them\n", len(snapshots))
+	}
+
+	if cov := o.reportManager.Coverage(); cov != nil {
+		fmt.Fprintf(o.stdout(), "Coverage:    %.1f%% of lines, %.1f%% of functions (%s)\n", cov.Lines.Percent, cov.Functions.Percent, cov.Report)
 	}
 
 	// Calculate and display elapsed time
 	elapsed := time.Since(o.startTime).Seconds()
-	fmt.Printf("Total time:  %.3fs\n", elapsed)
+	if o.verbosity != console.Quiet {
+		fmt.Fprintf(o.stdout(), "Total time:  %.3fs\n", elapsed)
+	}
+	if exitReason != "" {
+		fmt.Fprintf(o.stdout(), "Exit reason: %s\n", exitReason)
+	}
+
+	o.reportToGitHubActions()
+
+	if o.summaryJSON != "" {
+		o.exportSummary()
+	}
 
 	// Return command error if there was one
 	if commandErr != nil {
@@ -678,23 +1456,523 @@ func (o *Orchestrator) Run() error {
 	return nil
 }
 
+// nativeDefinition returns the definition behind a native runner's wrapper, or nil for
+// a runner that uses an adapter
+func nativeDefinition(runnerDef runner.Definition) interface{} {
+	switch wrapper := runnerDef.(type) {
+	case *definitions.GoTestWrapper:
+		return wrapper.GoTestDefinition
+	case *definitions.CargoTestWrapper:
+		return wrapper.CargoTestDefinition
+	case *definitions.NextestWrapper:
+		return wrapper.NextestDefinition
+	case *definitions.CriterionWrapper:
+		return wrapper.CriterionDefinition
+	case *definitions.WasmPackWrapper:
+		return wrapper.WasmPackDefinition
+	case *definitions.NodeTestWrapper:
+		return wrapper.NodeTestDefinition
+	case *definitions.BunTestWrapper:
+		return wrapper.BunTestDefinition
+	case *definitions.DotnetTestWrapper:
+		return wrapper.DotnetTestDefinition
+	case *definitions.JUnitReportsWrapper:
+		return wrapper.JUnitReportsDefinition
+	case *definitions.TAPWrapper:
+		return wrapper.TAPDefinition
+	case *definitions.ExecWrapper:
+		return wrapper.ExecDefinition
+	}
+	return nil
+}
+
+// crossPassthroughEnv lists, separated by spaces, the environment variables cross passes
+// into its container, as build.env.passthrough in Cross.toml does
+const crossPassthroughEnv = "CROSS_BUILD_ENV_PASSTHROUGH"
+
+// commandEnv returns the environment of the test command, which writes its events to
+// ipcPath
+func (o *Orchestrator) commandEnv(ipcPath string) []string {
+	env := append(o.environ(), fmt.Sprintf("THREEPIO_IPC_PATH=%s", ipcPath))
+	env = append(env, fmt.Sprintf("%s=file:%s", ipc.TransportEnv, ipcPath))
+
+	// Add RUSTC_BOOTSTRAP=1 for cargo test and cargo bench to enable JSON output
+	if subcommand := definitions.CargoSubcommand(o.command); subcommand == "test" || subcommand == "bench" {
+		env = append(env, "RUSTC_BOOTSTRAP=1")
+		o.logger.Debug("Added RUSTC_BOOTSTRAP=1 for cargo test JSON output")
+	}
+
+	// Add RUST_BACKTRACE=1 so failed cargo tests report a backtrace, unless the user set it
+	if shouldSetRustBacktrace(o.command, o.noBacktrace) {
+		env = append(env, "RUST_BACKTRACE=1")
+		o.logger.Debug("Added RUST_BACKTRACE=1 for cargo test backtraces")
+	}
+
+	// cross runs the test binaries in its container, which only gets the variables it's
+	// told to pass through
+	if definitions.UsesCross(o.command) {
+		passthrough := strings.TrimSpace(os.Getenv(crossPassthroughEnv) + " RUSTC_BOOTSTRAP RUST_BACKTRACE")
+		env = append(env, crossPassthroughEnv+"="+passthrough)
+		o.logger.Debug("Passing RUSTC_BOOTSTRAP and RUST_BACKTRACE through to the cross container")
+	}
+
+	// Add NEXTEST_EXPERIMENTAL_LIBTEST_JSON=1 for cargo nextest to enable JSON output,
+	// also when cargo llvm-cov runs it
+	if len(o.command) >= 2 && o.command[0] == "cargo" && definitions.CargoSubcommand(o.command) == "nextest" {
+		env = append(env, "NEXTEST_EXPERIMENTAL_LIBTEST_JSON=1")
+		o.logger.Debug("Added NEXTEST_EXPERIMENTAL_LIBTEST_JSON=1 for cargo nextest JSON output")
+	}
+	return env
+}
+
+// selectRunner returns the runner chosen with --runner, or detects it from the command
+func (o *Orchestrator) selectRunner() (runner.Definition, error) {
+	if o.runnerName == "" {
+		runnerDef, err := o.runnerManager.Detect(o.command)
+		if err != nil {
+			return nil, fmt.Errorf("failed to detect test runner: %w", err)
+		}
+		return runnerDef, nil
+	}
+
+	runnerDef, ok := o.runnerManager.GetDefinition(o.runnerName)
+	if !ok {
+		return nil, fmt.Errorf("unknown test runner: %s", o.runnerName)
+	}
+	o.logger.Debug("Using runner from --runner: %s", o.runnerName)
+	return runnerDef, nil
+}
+
+// prepareRerun narrows the command down to the tests that failed in the latest run.
+// It returns false, after saying why, when there is nothing to re-run.
+func (o *Orchestrator) prepareRerun() (bool, error) {
+	runsDir := o.runsDir()
+	previousRun, err := runs.Latest(runsDir)
+	if err != nil {
+		return false, err
+	}
+	if previousRun == "" {
+		fmt.Fprintln(o.stdout(), "No previous run in .3pio/runs, nothing to re-run")
+		return false, nil
+	}
+
+	failed, err := report.ReadFailedTests(filepath.Join(runsDir, previousRun))
+	if err != nil && !os.IsNotExist(err) {
+		return false, err
+	}
+	if len(failed) == 0 {
+		fmt.Fprintf(o.stdout(), "No failed tests in the previous run (%s), nothing to re-run\n", previousRun)
+		return false, nil
+	}
+	if previous, err := report.ReadSummary(filepath.Join(runsDir, previousRun)); err == nil && previous.Shard != nil {
+		fmt.Fprintf(o.stdout(), "Warning: %s was shard %s, so only the failed tests of that shard are re-run\n", previousRun, previous.Shard)
+	}
+
+	runnerDef, err := o.selectRunner()
+	if err != nil {
+		return false, err
+	}
+	filter, ok := runnerDef.(rerunFilter)
+	if !ok {
+		return false, fmt.Errorf("--rerun-failed is not supported for this test runner")
+	}
+	command, err := filter.RerunCommand(o.command, failed)
+	if err != nil {
+		return false, fmt.Errorf("cannot re-run the failed tests from %s: %w", previousRun, err)
+	}
+
+	o.logger.Debug("Re-running %d failed tests from %s: %v", len(failed), previousRun, command)
+	o.command = command
+	o.rerunOf = previousRun
+	o.rerunCount = len(failed)
+	return true, nil
+}
+
+// setPreviousRun hands the summary of the latest finished run to the report manager,
+// so failures are marked as new or as still failing. Without one they aren't marked.
+func (o *Orchestrator) setPreviousRun() {
+	runsDir := filepath.Dir(o.runDir)
+	previousRun, err := runs.Latest(runsDir)
+	if err != nil || previousRun == "" || previousRun == o.runID {
+		return
+	}
+	summary, err := report.ReadSummary(filepath.Join(runsDir, previousRun))
+	if err != nil {
+		o.logger.Debug("Not comparing failures with %s: %v", previousRun, err)
+		return
+	}
+	o.reportManager.SetPreviousRun(summary)
+}
+
+// loadQuarantine reads the --quarantine file, or .3pio/quarantine.toml when it exists
+func (o *Orchestrator) loadQuarantine() error {
+	path := o.quarantinePath
+	if path == "" {
+		path = filepath.Join(o.stateDir(), report.QuarantineFileName)
+		if _, err := os.Stat(path); err != nil {
+			return nil
+		}
+	}
+	quarantine, err := report.LoadQuarantine(path)
+	if err != nil {
+		return fmt.Errorf("failed to load quarantine file: %w", err)
+	}
+	if o.dir != "" {
+		quarantine.SetWorkDir(o.dir)
+	}
+	o.quarantine = quarantine
+	return nil
+}
+
+// loadRedactor builds the --redact redactor from the default patterns and those of
+// .3pio/redact.toml when it exists
+func (o *Orchestrator) loadRedactor() error {
+	if !o.redact {
+		return nil
+	}
+	patterns := append([]string(nil), report.DefaultRedactPatterns...)
+	path := filepath.Join(o.stateDir(), report.RedactFileName)
+	if _, err := os.Stat(path); err == nil {
+		extra, err := report.LoadRedactConfig(path)
+		if err != nil {
+			return fmt.Errorf("failed to load redact file: %w", err)
+		}
+		patterns = append(patterns, extra...)
+	}
+	o.redactor = report.NewRedactor(o.environ(), patterns)
+	o.logger.Debug("Redacting the values of %v", o.redactor.Names())
+	return nil
+}
+
+// redactRawLogs replaces the secret values in output.log and preflight-list.log
+// (--redact-raw), once the run is over and nothing writes to them anymore
+func (o *Orchestrator) redactRawLogs() {
+	if !o.redactRaw {
+		return
+	}
+	for _, name := range []string{"output.log", preflightLogFile} {
+		path := filepath.Join(o.runDir, name)
+		if _, err := os.Stat(path); err != nil {
+			continue
+		}
+		if err := o.redactor.RedactFile(path); err != nil {
+			o.logger.Error("Failed to redact %s: %v", name, err)
+		}
+	}
+}
+
+// stopsAtFirstFailure reports whether the runner skipped the rest of the run after
+// the first failure, which cargo test and cargo nextest do without --no-fail-fast.
+// A quarantined failure then still kept other tests from running.
+func (o *Orchestrator) stopsAtFirstFailure() bool {
+	if !strings.HasPrefix(o.detectedRunner, "cargo") {
+		return false
+	}
+	for _, arg := range o.command {
+		if arg == "--" {
+			break
+		}
+		if arg == "--no-fail-fast" {
+			return false
+		}
+	}
+	return true
+}
+
+// autoClean removes the runs in .3pio/runs that runs.DefaultPolicy doesn't keep
+func (o *Orchestrator) autoClean() {
+	removed, err := runs.Clean(o.runsDir(), runs.DefaultPolicy, false, time.Now())
+	if err != nil {
+		o.logger.Error("Failed to clean old runs: %v", err)
+		return
+	}
+	o.logger.Debug("Removed %d old runs", len(removed))
+}
+
+// recordRun adds the finalized run to the runs index and points .3pio/runs/latest at it
+func (o *Orchestrator) recordRun() {
+	entry := runs.Entry{
+		RunID:     o.runID,
+		Command:   strings.Join(o.command, " "),
+		StartTime: o.startTime.UTC(),
+		EndTime:   time.Now().UTC(),
+		ExitCode:  o.exitCode,
+	}
+	if summary, err := report.ReadSummary(o.runDir); err == nil {
+		entry = runs.EntryFromSummary(summary)
+	}
+	if err := runs.Record(filepath.Dir(o.runDir), entry); err != nil {
+		o.logger.Error("Failed to record run in %s: %v", runs.IndexFileName, err)
+	}
+}
+
+// reportToGitHubActions prints ::error commands for the failures (--gha-annotations)
+// and appends a summary of the run to $GITHUB_STEP_SUMMARY when it is set
+func (o *Orchestrator) reportToGitHubActions() {
+	stepSummary := os.Getenv(report.GitHubStepSummaryEnv)
+	if !o.ghaAnnotations && stepSummary == "" {
+		return
+	}
+
+	omitted := 0
+	if o.ghaAnnotations {
+		var commands []string
+		commands, omitted = report.GitHubAnnotations(o.reportManager.GetRootGroups())
+		for _, command := range commands {
+			fmt.Fprintln(o.stdout(), command)
+		}
+	}
+
+	if stepSummary != "" {
+		summary, err := report.ReadSummary(o.runDir)
+		if err != nil {
+			o.logger.Error("Failed to read %s for the step summary: %v", report.SummaryFileName, err)
+			return
+		}
+		if err := report.AppendGitHubStepSummary(stepSummary, summary, omitted); err != nil {
+			o.logger.Error("Failed to write the step summary: %v", err)
+		}
+	}
+}
+
+// shouldSetRustBacktrace reports whether RUST_BACKTRACE=1 should be added for a command.
+// It is only added for cargo test, and never overrides a value the user already set.
+func shouldSetRustBacktrace(command []string, noBacktrace bool) bool {
+	if noBacktrace || definitions.CargoSubcommand(command) != "test" {
+		return false
+	}
+	_, set := os.LookupEnv("RUST_BACKTRACE")
+	return !set
+}
+
+// exportSummary copies the run's summary.json to the --summary-json path, or prints it
+// to stdout for "-"
+func (o *Orchestrator) exportSummary() {
+	data, err := os.ReadFile(filepath.Join(o.runDir, report.SummaryFileName))
+	if err != nil {
+		o.logger.Error("Failed to read %s: %v", report.SummaryFileName, err)
+		return
+	}
+	if o.summaryJSON == "-" {
+		fmt.Fprintln(o.stdout())
+		_, _ = o.stdout().Write(data)
+		return
+	}
+	if dir := filepath.Dir(o.summaryJSON); dir != "." {
+		if err := os.MkdirAll(dir, 0755); err != nil {
+			o.logger.Error("Failed to create summary directory: %v", err)
+			return
+		}
+	}
+	if err := os.WriteFile(o.summaryJSON, data, 0644); err != nil {
+		o.logger.Error("Failed to write summary to %s: %v", o.summaryJSON, err)
+	}
+}
+
+// displayBuildErrors prints the first few rendered compiler errors
+func (o *Orchestrator) displayBuildErrors(buildErrors []ipc.BuildErrorPayload) {
+	for i, buildErr := range buildErrors {
+		if i >= maxConsoleBuildErrors {
+			fmt.Fprintf(o.stdout(), "... and %d more build errors, see $trun_dir/test-run.md\n\n", len(buildErrors)-maxConsoleBuildErrors)
+			break
+		}
+		rendered := buildErr.Rendered
+		if rendered == "" {
+			rendered = "error: " + buildErr.Message
+		}
+		if !o.color {
+			rendered = console.StripANSI(rendered)
+		}
+		fmt.Fprintln(o.stdout(), strings.TrimRight(rendered, "\n"))
+		fmt.Fprintln(o.stdout())
+	}
+}
+
+// displayCrashes prints each test binary that crashed and the test it was running
+func (o *Orchestrator) displayCrashes(crashes []ipc.BinaryCrashPayload) {
+	for _, crash := range crashes {
+		line := fmt.Sprintf("%s: %s", crash.Target, crash.Message)
+		if crash.TestName != "" {
+			line += fmt.Sprintf(" while running %s", crash.TestName)
+		}
+		fmt.Fprintln(o.stdout(), line)
+	}
+	fmt.Fprintln(o.stdout())
+}
+
+// displayFailureDigest lists the failed tests for --quiet, which shows no group blocks
+func (o *Orchestrator) displayFailureDigest() {
+	failed := o.reportManager.FailedTests()
+	if len(failed) == 0 {
+		return
+	}
+	fmt.Fprintf(o.stdout(), "Failed tests (%d), see %s:\n", len(failed), filepath.Join(o.runDir, "test-run.md"))
+	for _, test := range failed {
+		tag := ""
+		if test.FailureKind != "" && test.FailureKind != ipc.FailureKindUnknown {
+			tag = " [" + test.FailureKind + "]"
+		}
+		path := o.consoleTestPath(test.ParentNames, test.Name)
+		if test.CaseName != "" && len(test.ParentNames) > 0 {
+			// The parameter set in full, as the runner's filter takes it
+			path = o.consoleTestPath(test.ParentNames[:len(test.ParentNames)-1], test.CaseName)
+		}
+		fmt.Fprintf(o.stdout(), "  ✕ %s%s\n", path, tag)
+	}
+}
+
+// displayInactivityTimeout prints the tests that were running when the inactivity timeout fired
+func (o *Orchestrator) displayInactivityTimeout(inFlight []string) {
+	fmt.Fprintf(o.stdout(), "Inactivity timeout: no output for %.0fs, stopped the test command\n", o.inactivityTimeout.Seconds())
+	if len(inFlight) > 0 {
+		fmt.Fprintln(o.stdout(), "In flight when the timeout fired:")
+		for _, name := range inFlight {
+			fmt.Fprintf(o.stdout(), "  - %s\n", name)
+		}
+	}
+	fmt.Fprintln(o.stdout())
+}
+
+// interruptProcess forwards the interrupt to the test command and waits for it to exit,
+// so tests that finish during the grace period are still reported. A second Ctrl-C, or
+// the grace period running out, kills the process tree instead.
+func (o *Orchestrator) interruptProcess(cmd *exec.Cmd, done <-chan error, sigChan <-chan os.Signal) {
+	var err error
+	if o.processTree != nil {
+		err = o.processTree.interrupt()
+	} else {
+		err = cmd.Process.Signal(os.Interrupt)
+	}
+	if err != nil {
+		o.logger.Debug("Failed to forward interrupt: %v", err)
+	}
+	fmt.Fprintf(o.stdout(), "\nInterrupted, waiting up to %.0fs for the tests to stop (Ctrl-C again to kill them)\n", interruptGracePeriod.Seconds())
+
+	timer := time.NewTimer(interruptGracePeriod)
+	defer timer.Stop()
+	select {
+	case <-done:
+		return
+	case <-sigChan:
+		o.logger.Info("Received second signal, killing test command")
+	case <-timer.C:
+		o.logger.Info("Test command still running after %s, killing it", interruptGracePeriod)
+	}
+	o.stopProcessTree(cmd)
+	<-done
+}
+
+// stopProcessTree kills the test command and the processes it started
+func (o *Orchestrator) stopProcessTree(cmd *exec.Cmd) {
+	if o.processTree != nil {
+		err := o.processTree.kill()
+		if err == nil {
+			return
+		}
+		o.logger.Debug("Failed to kill process tree: %v", err)
+	}
+	_ = cmd.Process.Kill()
+}
+
+// recordActivity resets the inactivity timer
+func (o *Orchestrator) recordActivity() {
+	o.lastActivity.Store(time.Now().UnixNano())
+}
+
+// watchInactivity returns a channel that is closed once neither output nor IPC
+// events have arrived for the inactivity timeout while a group or a retry is running.
+// Any growth of output.log counts, so slow suites that keep printing aren't stopped.
+func (o *Orchestrator) watchInactivity(outputPath string, stop <-chan struct{}) <-chan struct{} {
+	inactive := make(chan struct{})
+	interval := o.inactivityTimeout / 10
+	if interval > time.Second {
+		interval = time.Second
+	}
+
+	go func() {
+		ticker := time.NewTicker(interval)
+		defer ticker.Stop()
+		var outputSize int64
+		for {
+			select {
+			case <-stop:
+				return
+			case <-ticker.C:
+			}
+
+			if info, err := os.Stat(outputPath); err == nil && info.Size() != outputSize {
+				outputSize = info.Size()
+				o.recordActivity()
+			}
+			idle := time.Since(time.Unix(0, o.lastActivity.Load()))
+			if idle >= o.inactivityTimeout && (len(o.reportManager.RunningGroups()) > 0 || o.reportManager.Retrying()) {
+				close(inactive)
+				return
+			}
+		}
+	}()
+	return inactive
+}
+
+// testsInFlight names the tests running when the inactivity timeout fired, or the
+// running groups for runners that only report tests once they finish
+func (o *Orchestrator) testsInFlight(nativeDef interface{}) []string {
+	if reporter, ok := nativeDef.(inFlightReporter); ok {
+		if tests := reporter.RunningTests(); len(tests) > 0 {
+			return tests
+		}
+	}
+	return o.reportManager.RunningGroups()
+}
+
 // processEvents processes IPC events and displays console output
 func (o *Orchestrator) processEvents() {
 	for event := range o.ipcManager.Events {
+		o.recordActivity()
+
+		if e, ok := event.(ipc.AdapterHandshakeEvent); ok {
+			o.logger.Debug("%s adapter writes IPC schema %d", e.Payload.Adapter, e.Payload.Schema)
+			if err := ipc.CheckSchema(e.Payload.Adapter, e.Payload.Schema); err != nil {
+				select {
+				case o.schemaMismatch <- err:
+				default:
+				}
+			}
+			continue
+		}
+
 		// Pass event to report manager FIRST to update state
 		if err := o.reportManager.HandleEvent(event); err != nil {
 			o.logger.Error("Failed to handle event: %v", err)
 		}
 
-		// Then handle console output for different event types
-		o.handleConsoleOutput(event)
+		if o.observer != nil {
+			o.observer.Event(event)
+		}
+
+		// Then handle console output for different event types, above the progress line
+		o.progress.Suspend(func() { o.handleConsoleOutput(event) })
+		if o.progress != nil {
+			o.progress.Update(o.progressState())
+		}
+	}
+}
+
+// progressState returns the counts shown on the progress line
+func (o *Orchestrator) progressState() console.Progress {
+	return console.Progress{
+		CompletedGroups: o.totalGroups,
+		TotalGroups:     max(len(o.reportManager.GetRootGroups()), o.totalGroups),
+		Passed:          o.passedTests,
+		Failed:          o.failedTests,
+		Skipped:         o.skippedTests + o.ignoredTests,
 	}
 }
 
 // normalizePath normalizes a file path for console output deduplication
 func (o *Orchestrator) normalizePath(filePath string) string {
 	// Try to get absolute path
-	absPath, err := filepath.Abs(filePath)
+	absPath, err := o.absPath(filePath)
 	if err != nil {
 		// If absolute path fails, use the original path
 		return filePath
@@ -710,7 +1988,7 @@ func (o *Orchestrator) normalizePathForReportManager(name string) string {
 	}
 
 	// Convert to absolute path
-	absPath, err := filepath.Abs(name)
+	absPath, err := o.absPath(name)
 	if err != nil {
 		// If we can't get absolute path, return original
 		return name
@@ -736,8 +2014,8 @@ func (o *Orchestrator) makeRelativePath(name string) string {
 		return name
 	}
 
-	// Try to make relative to current working directory
-	if cwd, err := os.Getwd(); err == nil {
+	// Try to make relative to the directory the tests run in
+	if cwd := o.workDir(); cwd != "" {
 		if relPath, err := filepath.Rel(cwd, name); err == nil {
 			// Ensure relative paths start with ./
 			if !strings.HasPrefix(relPath, ".") && !strings.HasPrefix(relPath, "/") {
@@ -751,6 +2029,16 @@ func (o *Orchestrator) makeRelativePath(name string) string {
 	return name
 }
 
+// consoleTestPath returns a test's groups and name as shown on the console, with file
+// paths relative to the working directory, e.g. "my-crate → tests → test_add"
+func (o *Orchestrator) consoleTestPath(parentNames []string, testName string) string {
+	path := make([]string, 0, len(parentNames)+1)
+	for _, name := range parentNames {
+		path = append(path, o.makeRelativePath(name))
+	}
+	return report.BuildHierarchicalPathFromSlice(append(path, testName))
+}
+
 // handleConsoleOutput displays real-time console output for test events
 func (o *Orchestrator) handleConsoleOutput(event ipc.Event) {
 	switch e := event.(type) {
@@ -774,11 +2062,9 @@ func (o *Orchestrator) handleConsoleOutput(event ipc.Event) {
 		// o.displayGroupRunning(e.Payload.GroupName, e.Payload.ParentNames)
 
 	case ipc.GroupResultEvent:
-		// Check if this is a NOTESTS status (Go packages with no test files)
-		if e.Payload.Status == "NOTESTS" {
+		// Go packages with no test files are skipped for that reason
+		if e.Payload.SkipReason == ipc.SkipReasonNoTestFiles {
 			o.noTestGroups[e.Payload.GroupName] = true
-			// Convert to SKIP for internal handling
-			e.Payload.Status = "SKIP"
 		}
 
 		// For cargo test, mark groups with 0 tests as NO_TEST
@@ -789,15 +2075,23 @@ func (o *Orchestrator) handleConsoleOutput(event ipc.Event) {
 			}
 		}
 
-		// Display hierarchical output when a group completes
+		// A group that failed only because of quarantined tests passed
+		if e.Payload.Status == "FAIL" && o.quarantine != nil && o.reportGroupPassed(e.Payload.GroupName, e.Payload.ParentNames) {
+			e.Payload.Status = "PASS"
+		}
+
+		// Display hierarchical output when a group completes, unless --quiet
 		status := convertStringToTestStatus(e.Payload.Status)
-		o.displayGroupResult(e.Payload.GroupName, e.Payload.ParentNames, status, e.Payload.Duration)
+		if o.verbosity != console.Quiet {
+			o.displayGroupResult(e.Payload.GroupName, e.Payload.ParentNames, status, e.Payload.Duration)
+		}
 
 		// Update group counters for top-level groups
 		if len(e.Payload.ParentNames) == 0 {
 			o.totalGroups++
 			switch e.Payload.Status {
-			case "PASS":
+			case "PASS", "WARN":
+				// WARN groups ran successfully, e.g. with a regressed benchmark
 				o.passedGroups++
 			case "FAIL":
 				o.failedGroups++
@@ -807,23 +2101,40 @@ func (o *Orchestrator) handleConsoleOutput(event ipc.Event) {
 		}
 
 	case ipc.GroupTestCaseEvent:
-		// Track test case counts
-		o.totalTests++
-		switch e.Payload.Status {
-		case "PASS":
-			o.passedTests++
-		case "FAIL":
-			o.failedTests++
-		case "SKIP":
-			o.skippedTests++
-		case "XFAIL":
-			o.xfailedTests++
-		case "XPASS":
-			o.xpassedTests++
+		// Track test case counts. Runners re-send a test when it is retried
+		// or annotated later, so replace its previous status instead of adding
+		testKey := strings.Join(append(append([]string{}, e.Payload.ParentNames...), e.Payload.TestName), "\x00")
+		previousStatus, seen := o.testCaseStatuses[testKey]
+		if seen {
+			o.countTestStatus(previousStatus, -1)
+		} else if e.Payload.Status != "BENCH" {
+			// Benchmarks are counted on their own, not as tests
+			o.totalTests++
+		}
+		status := e.Payload.Status
+		if status == "SKIP" && e.Payload.SkipReason == ipc.SkipReasonIgnored {
+			// Ignored tests are counted apart from other skips
+			status = "IGNORED"
+		}
+		if (status == "FAIL" || status == "ERROR") && o.quarantine != nil &&
+			o.quarantine.Match(o.reportManager.TestID(e.Payload.ParentNames, e.Payload.TestName), append(append([]string(nil), e.Payload.ParentNames...), e.Payload.TestName)) {
+			// Quarantined failures are counted apart from other failures
+			status = "QUARANTINED"
+		}
+		if status == "BENCH" && e.Payload.BenchRegressed {
+			// Regressed benchmarks are counted as benchmarks and as regressions
+			status = "REGRESSED"
+		}
+		o.testCaseStatuses[testKey] = status
+		o.countTestStatus(status, 1)
+
+		// --verbose shows every test as it completes, like the runner itself
+		if o.verbosity == console.Verbose && status != "PENDING" && status != "RUNNING" {
+			o.displayTestCase(status, e.Payload)
 		}
 
 		// Track failed tests for hierarchical display
-		if e.Payload.Status == "FAIL" {
+		if status == "FAIL" && previousStatus != "FAIL" {
 			// Use the first parent name as file path (should be the file)
 			if len(e.Payload.ParentNames) > 0 {
 				normalizedPath := o.normalizePath(e.Payload.ParentNames[0])
@@ -840,6 +2151,45 @@ func (o *Orchestrator) handleConsoleOutput(event ipc.Event) {
 	}
 }
 
+// displayTestCase prints a completed test case for --verbose, e.g.
+// "PASS my-crate → tests → test_add (0.01s)"
+func (o *Orchestrator) displayTestCase(status string, tc ipc.TestCasePayload) {
+	line := status + " " + o.consoleTestPath(tc.ParentNames, tc.TestName)
+	if tc.Duration > 0 {
+		line += fmt.Sprintf(" (%.2fs)", tc.Duration/1000)
+	}
+	o.printConsole(line + "\n")
+}
+
+// countTestStatus adjusts the test case counter for a status by delta
+func (o *Orchestrator) countTestStatus(status string, delta int) {
+	switch status {
+	case "PASS":
+		o.passedTests += delta
+	case "FAIL":
+		o.failedTests += delta
+	case "SKIP":
+		o.skippedTests += delta
+	case "IGNORED":
+		o.ignoredTests += delta
+	case "BENCH":
+		o.benchmarks += delta
+	case "REGRESSED":
+		o.benchmarks += delta
+		o.regressedBenches += delta
+	case "XFAIL":
+		o.xfailedTests += delta
+	case "XPASS":
+		o.xpassedTests += delta
+	case "QUARANTINED":
+		o.quarantinedTests += delta
+	case "FLAKY":
+		o.flakyTests += delta
+	case "ERROR":
+		o.erroredTests += delta
+	}
+}
+
 // extractAdapter extracts the adapter file to a temporary directory
 func (o *Orchestrator) extractAdapter(adapterName string) (string, error) {
 	// Read log level from environment variable for adapter injection
@@ -850,7 +2200,7 @@ func (o *Orchestrator) extractAdapter(adapterName string) (string, error) {
 
 	// Always use embedded adapters in production
 	// Pass IPC path, run directory, and log level for injection
-	embeddedPath, err := adapters.GetAdapterPath(adapterName, o.ipcPath, o.runDir, logLevel)
+	embeddedPath, err := adapters.GetAdapterPath(adapterName, o.ipcPath, o.runDir, logLevel, o.dir)
 	if err != nil {
 		return "", fmt.Errorf("failed to extract embedded adapter %s: %w", adapterName, err)
 	}
@@ -878,6 +2228,16 @@ func (o *Orchestrator) displayGroupRunning(groupName string, parentNames []strin
 	// No longer printing RUNNING status to console
 }
 
+// reportGroupPassed reports whether the report manager has the group as passed
+func (o *Orchestrator) reportGroupPassed(groupName string, parentNames []string) bool {
+	normalizedParentNames := make([]string, len(parentNames))
+	for i, name := range parentNames {
+		normalizedParentNames[i] = o.normalizePathForReportManager(name)
+	}
+	group, exists := o.reportManager.GetGroup(report.GenerateGroupID(o.normalizePathForReportManager(groupName), normalizedParentNames))
+	return exists && group.Status == report.TestStatusPass
+}
+
 // displayGroupResult displays the result of a completed group
 func (o *Orchestrator) displayGroupResult(groupName string, parentNames []string, status ipc.TestStatus, duration float64) {
 	o.logger.Debug("displayGroupResult called: group=%s, parentNames=%v, status=%s, duration=%f",
@@ -908,6 +2268,15 @@ func (o *Orchestrator) displayGroupResult(groupName string, parentNames []string
 			return
 		}
 
+		// Parallel groups report out of order, e.g. a crate's result can arrive while one
+		// of its test binaries still runs. Hold the block until its subgroups finish so
+		// their failures are printed under this group, in one piece.
+		if hasRunningSubgroups(group) {
+			o.logger.Debug("Holding group until its subgroups finish: %s", groupName)
+			o.heldGroups[groupName] = duration
+			return
+		}
+
 		// Only mark as completed if this is truly the final status
 		// (all tests are done or it's a failure)
 		if group.IsComplete() || status == ipc.TestStatusFail {
@@ -916,7 +2285,47 @@ func (o *Orchestrator) displayGroupResult(groupName string, parentNames []string
 
 		o.logger.Debug("Calling displayGroupHierarchy for: %s", groupName)
 		o.displayGroupHierarchy(group, 0, duration)
+		return
+	}
+
+	// A subgroup finished, which may release its held top-level group
+	if heldDuration, held := o.heldGroups[parentNames[0]]; held {
+		root, exists := o.reportManager.GetGroup(report.GenerateGroupID(normalizedParentNames[0], nil))
+		if exists && !hasRunningSubgroups(root) {
+			delete(o.heldGroups, parentNames[0])
+			o.completedGroups[parentNames[0]] = true
+			o.displayGroupHierarchy(root, 0, heldDuration)
+		}
+	}
+}
+
+// displayHeldGroups prints the groups still held when the events end, e.g. because
+// the run was interrupted while their subgroups ran
+func (o *Orchestrator) displayHeldGroups() {
+	names := make([]string, 0, len(o.heldGroups))
+	for name := range o.heldGroups {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	for _, name := range names {
+		group, exists := o.reportManager.GetGroup(report.GenerateGroupID(o.normalizePathForReportManager(name), nil))
+		if exists {
+			o.completedGroups[name] = true
+			o.displayGroupHierarchy(group, 0, o.heldGroups[name])
+		}
+		delete(o.heldGroups, name)
+	}
+}
+
+// hasRunningSubgroups reports whether any subgroup of group, at any depth, has started
+// and not finished
+func hasRunningSubgroups(group *report.TestGroup) bool {
+	for _, subgroup := range group.Subgroups {
+		if subgroup.Status == report.TestStatusRunning || hasRunningSubgroups(subgroup) {
+			return true
+		}
 	}
+	return false
 }
 
 // displayGroupHierarchy displays a group and its children with hierarchical indentation
@@ -935,7 +2344,7 @@ func (o *Orchestrator) displayGroupHierarchy(group *report.TestGroup, indent int
 	if !group.HasTestCases() {
 		// Check if this is a package with no test files or failed group
 		isNoTests := o.noTestGroups[group.Name]
-		isFailed := group.Status == report.TestStatusFail
+		isFailed := group.Status == report.TestStatusFail || group.Status == report.TestStatusError
 
 		o.logger.Debug("Group %s has no test cases, isNoTests=%v, isFailed=%v", group.Name, isNoTests, isFailed)
 
@@ -980,7 +2389,7 @@ func (o *Orchestrator) displayGroupHierarchy(group *report.TestGroup, indent int
 			reportPath := fmt.Sprintf("$trun_dir/%s", filepath.ToSlash(relPath))
 
 			// Print all on one line
-			fmt.Printf("%s %s\n", strings.Join(statusParts, " "), reportPath)
+			o.printConsole(fmt.Sprintf("%s %s\n", strings.Join(statusParts, " "), reportPath))
 		}
 		return
 	}
@@ -994,14 +2403,21 @@ func (o *Orchestrator) displayGroupHierarchy(group *report.TestGroup, indent int
 
 	// Only display groups that have failures or no tests
 	// Use recursive stats to include subgroups
-	hasNoTestsAtAll := group.Stats.TotalTestsRecursive == 0 && group.Stats.SkippedTestsRecursive == 0
+	hasNoTestsAtAll := group.Stats.TotalTestsRecursive == 0 && group.Stats.SkippedTestsRecursive == 0 && group.Stats.BenchmarksRecursive == 0
 
 	o.logger.Debug("Group %s: FailedTestsRecursive=%d, TotalTestsRecursive=%d, PassedTestsRecursive=%d, SkippedTestsRecursive=%d",
 		group.Name, group.Stats.FailedTestsRecursive, group.Stats.TotalTestsRecursive, group.Stats.PassedTestsRecursive, group.Stats.SkippedTestsRecursive)
 
-	if group.Stats.FailedTestsRecursive > 0 || hasNoTestsAtAll {
+	// WARN groups are shown too, so regressed benchmarks aren't missed
+	isWarn := group.Status == report.TestStatusWarn
+
+	failed := group.Stats.FailedTestsRecursive > 0 || group.Stats.ErroredTestsRecursive > 0
+	if failed || hasNoTestsAtAll || isWarn {
 		// Build status string with fail/pass/skip counts
 		var statusParts []string
+		if isWarn {
+			statusParts = append(statusParts, "WARN")
+		}
 
 		// Check if this is a NO_TESTS case first
 		if hasNoTestsAtAll && o.noTestGroups[group.Name] {
@@ -1013,6 +2429,11 @@ func (o *Orchestrator) displayGroupHierarchy(group *report.TestGroup, indent int
 				statusParts = append(statusParts, fmt.Sprintf("FAIL(%d)", group.Stats.FailedTestsRecursive))
 			}
 
+			// Tests whose setup or teardown failed are counted apart, like pytest does
+			if group.Stats.ErroredTestsRecursive > 0 {
+				statusParts = append(statusParts, fmt.Sprintf("ERROR(%d)", group.Stats.ErroredTestsRecursive))
+			}
+
 			// Add PASS count only if > 0
 			if group.Stats.PassedTestsRecursive > 0 {
 				statusParts = append(statusParts, fmt.Sprintf("PASS(%d)", group.Stats.PassedTestsRecursive))
@@ -1053,9 +2474,129 @@ func (o *Orchestrator) displayGroupHierarchy(group *report.TestGroup, indent int
 		relPath := report.GetRelativeReportPath(group, o.runDir)
 		reportPath := fmt.Sprintf("$trun_dir/%s", filepath.ToSlash(relPath))
 
-		// Print all on one line
-		fmt.Printf("%s %s\n", strings.Join(statusParts, " "), reportPath)
+		// Buffer the group's lines and write them at once, so nothing lands between them
+		var block strings.Builder
+		fmt.Fprintf(&block, "%s %s\n", strings.Join(statusParts, " "), reportPath)
+
+		if failed {
+			o.writeFailureSnippet(&block, group, reportPath)
+			o.writeAssertionDiffs(&block, group)
+		}
+		o.printConsole(block.String())
+	}
+}
+
+// writeAssertionDiffs writes the expected and actual values of a group's first
+// few failed assertions under its FAIL line
+func (o *Orchestrator) writeAssertionDiffs(w io.Writer, group *report.TestGroup) {
+	failures := collectAssertionFailures(group, nil)
+	for i, failure := range failures {
+		if i >= maxConsoleAssertionDiffs {
+			_, _ = fmt.Fprintf(w, "  ... and %d more assertion failures\n", len(failures)-maxConsoleAssertionDiffs)
+			break
+		}
+		_, _ = fmt.Fprintf(w, "  ✕ %s%s\n", failure.name, o.failureTag(failure.id))
+		for _, line := range strings.Split(strings.TrimRight(failure.diff, "\n"), "\n") {
+			_, _ = fmt.Fprintf(w, "    %s\n", line)
+		}
+	}
+}
+
+// writeFailureSnippet writes the first lines of the group's first failure, dimmed,
+// under its FAIL line, capped at --snippet-lines
+func (o *Orchestrator) writeFailureSnippet(w io.Writer, group *report.TestGroup, reportPath string) {
+	if o.snippetLines <= 0 {
+		return
+	}
+	tc := firstFailure(group)
+	if tc == nil {
+		return
+	}
+	details := tc.Error.Message
+	if tc.Error.Stack != "" {
+		details += "\n" + tc.Error.Stack
+	}
+	// Color codes from the runner would count against the cap and garble the dimming
+	details = strings.TrimRight(console.StripANSI(details), "\r\n")
+	if strings.TrimSpace(details) == "" {
+		return
+	}
+	lines := strings.Split(details, "\n")
+	if len(lines) > o.snippetLines {
+		lines = lines[:o.snippetLines]
+	}
+	for _, line := range lines {
+		_, _ = fmt.Fprintf(w, "  %s\n", console.Dim(strings.TrimRight(line, "\r"), o.color))
+	}
+	_, _ = fmt.Fprintf(w, "  %s\n", console.Dim(fmt.Sprintf("… see %s for full output", reportPath), o.color))
+}
+
+// firstFailure returns the group's first failed test with error details, looking at
+// its own tests before its subgroups, in name order
+func firstFailure(group *report.TestGroup) *report.TestCase {
+	for i := range group.TestCases {
+		if tc := &group.TestCases[i]; (tc.Status == report.TestStatusFail || tc.Status == report.TestStatusError) && tc.Error != nil {
+			return tc
+		}
+	}
+	names := make([]string, 0, len(group.Subgroups))
+	for name := range group.Subgroups {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	for _, name := range names {
+		if tc := firstFailure(group.Subgroups[name]); tc != nil {
+			return tc
+		}
+	}
+	return nil
+}
+
+// failureTag marks a failed test as NEW or KNOWN against the previous run, or returns
+// "" when there is no previous run to compare with
+func (o *Orchestrator) failureTag(testID string) string {
+	known, compared := o.reportManager.KnownFailure(testID)
+	switch {
+	case !compared:
+		return ""
+	case known:
+		return " [KNOWN]"
+	default:
+		return " [NEW]"
+	}
+}
+
+// assertionFailure is a failed test with assertion values to show on the console
+type assertionFailure struct {
+	id   string
+	name string // Test name, prefixed with its subgroups (e.g., "tests > test_assertion_failure")
+	diff string
+}
+
+// collectAssertionFailures recursively collects the failed tests of a group that
+// have assertion values, in a stable order
+func collectAssertionFailures(group *report.TestGroup, path []string) []assertionFailure {
+	var failures []assertionFailure
+	for _, tc := range group.TestCases {
+		if tc.Status != report.TestStatusFail || tc.Error == nil {
+			continue
+		}
+		if diff := tc.Error.AssertionDiff(); diff != "" {
+			name := strings.Join(append(append([]string{}, path...), tc.Name), " > ")
+			failures = append(failures, assertionFailure{id: tc.TestID, name: name, diff: diff})
+		}
+	}
+
+	names := make([]string, 0, len(group.Subgroups))
+	for name := range group.Subgroups {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	for _, name := range names {
+		subgroup := group.Subgroups[name]
+		failures = append(failures, collectAssertionFailures(subgroup, append(append([]string{}, path...), subgroup.Name))...)
 	}
+	return failures
 }
 
 // formatElapsedTime returns a human-friendly elapsed time since startTime
@@ -1134,6 +2675,10 @@ func convertStringToTestStatus(status string) ipc.TestStatus {
 		return ipc.TestStatusXFail
 	case "XPASS":
 		return ipc.TestStatusXPass
+	case "BENCH":
+		return ipc.TestStatusBench
+	case "WARN":
+		return ipc.TestStatusWarn
 	case "NOTESTS", "NO_TESTS":
 		// Special status for packages with no test files
 		return ipc.TestStatusNoTests
@@ -1,15 +1,19 @@
 package orchestrator
 
 import (
+	"fmt"
 	"io"
 	"os"
 	"path/filepath"
+	"regexp"
 	"strings"
 	"sync"
 	"testing"
 	"time"
 
+	"github.com/zk/3pio/internal/ipc"
 	"github.com/zk/3pio/internal/logger"
+	"github.com/zk/3pio/internal/report"
 )
 
 func TestOrchestrator_New(t *testing.T) {
@@ -50,6 +54,36 @@ func TestOrchestrator_NewWithoutLogger(t *testing.T) {
 	}
 }
 
+// TestOrchestrator_NewWithDir checks a Config.Dir holds .3pio and the relative paths
+// of the options, without 3pio changing its working directory
+func TestOrchestrator_NewWithDir(t *testing.T) {
+	dir := t.TempDir()
+	orch, err := New(Config{
+		Command:      []string{"cargo", "test"},
+		Dir:          dir,
+		JUnitXMLPath: filepath.Join("results", "junit.xml"),
+		SummaryJSON:  "-",
+		Logger:       logger.NewTestLogger(),
+	})
+	if err != nil {
+		t.Fatalf("Failed to create orchestrator: %v", err)
+	}
+	defer func() { _ = orch.Close() }()
+
+	if got := orch.workDir(); got != dir {
+		t.Errorf("Expected the tests to run in %s, got %s", dir, got)
+	}
+	if got := orch.runsDir(); got != filepath.Join(dir, ".3pio", "runs") {
+		t.Errorf("Expected the runs in %s, got %s", filepath.Join(dir, ".3pio", "runs"), got)
+	}
+	if orch.junitXMLPath != filepath.Join(dir, "results", "junit.xml") {
+		t.Errorf("Expected --junit-xml resolved against the directory, got %s", orch.junitXMLPath)
+	}
+	if orch.summaryJSON != "-" {
+		t.Errorf("Expected --summary-json - to stay stdout, got %s", orch.summaryJSON)
+	}
+}
+
 func TestOrchestrator_RunnerDetection(t *testing.T) {
 	// Change to a temp directory for the test
 	originalDir, err := os.Getwd()
@@ -161,6 +195,427 @@ func TestOrchestrator_TestCountsWithSkippedTests(t *testing.T) {
 	}
 }
 
+func TestOrchestrator_IgnoredTestsCountedApartFromSkipped(t *testing.T) {
+	config := Config{
+		Command: []string{"cargo", "test"},
+		Logger:  logger.NewTestLogger(),
+	}
+
+	orch, err := New(config)
+	if err != nil {
+		t.Fatalf("Failed to create orchestrator: %v", err)
+	}
+	defer func() {
+		_ = orch.Close()
+	}()
+
+	sendTestCase := func(name, status, skipReason string) {
+		orch.handleConsoleOutput(ipc.GroupTestCaseEvent{
+			EventType: string(ipc.EventTypeTestCase),
+			Payload: ipc.TestCasePayload{
+				TestName:    name,
+				ParentNames: []string{"rust_basic", "tests"},
+				Status:      status,
+				SkipReason:  skipReason,
+			},
+		})
+	}
+
+	sendTestCase("test_add", "PASS", "")
+	sendTestCase("test_ignored", "SKIP", ipc.SkipReasonIgnored)
+	sendTestCase("test_not_run", "SKIP", ipc.SkipReasonNotRun)
+	sendTestCase("test_skipped", "SKIP", "")
+
+	if orch.ignoredTests != 1 {
+		t.Errorf("Expected 1 ignored test, got %d", orch.ignoredTests)
+	}
+	if orch.skippedTests != 2 {
+		t.Errorf("Expected 2 skipped tests, got %d", orch.skippedTests)
+	}
+	if orch.totalTests != 4 {
+		t.Errorf("Expected 4 total tests, got %d", orch.totalTests)
+	}
+
+	// Running the ignored test with --include-ignored replaces its SKIP result
+	sendTestCase("test_ignored", "PASS", "")
+	if orch.ignoredTests != 0 || orch.passedTests != 2 || orch.totalTests != 4 {
+		t.Errorf("Expected ignored test to be recounted as passed, got ignored=%d passed=%d total=%d",
+			orch.ignoredTests, orch.passedTests, orch.totalTests)
+	}
+}
+
+func TestOrchestrator_BenchmarksCountedApartFromTests(t *testing.T) {
+	config := Config{
+		Command: []string{"cargo", "bench"},
+		Logger:  logger.NewTestLogger(),
+	}
+
+	orch, err := New(config)
+	if err != nil {
+		t.Fatalf("Failed to create orchestrator: %v", err)
+	}
+	defer func() {
+		_ = orch.Close()
+	}()
+
+	for _, tc := range []struct{ name, status string }{
+		{"test_fibonacci", "PASS"},
+		{"bench_fibonacci_10", "BENCH"},
+		{"bench_factorial_10", "BENCH"},
+	} {
+		orch.handleConsoleOutput(ipc.GroupTestCaseEvent{
+			EventType: string(ipc.EventTypeTestCase),
+			Payload: ipc.TestCasePayload{
+				TestName:    tc.name,
+				ParentNames: []string{"rust-benchmarks", "tests"},
+				Status:      tc.status,
+			},
+		})
+	}
+
+	if orch.benchmarks != 2 {
+		t.Errorf("Expected 2 benchmarks, got %d", orch.benchmarks)
+	}
+	if orch.totalTests != 1 || orch.passedTests != 1 {
+		t.Errorf("Expected benchmarks to be left out of test counts, got total=%d passed=%d",
+			orch.totalTests, orch.passedTests)
+	}
+}
+
+func TestOrchestrator_RegressedBenchmarks(t *testing.T) {
+	config := Config{
+		Command: []string{"cargo", "bench"},
+		Runner:  "criterion",
+		Logger:  logger.NewTestLogger(),
+	}
+
+	orch, err := New(config)
+	if err != nil {
+		t.Fatalf("Failed to create orchestrator: %v", err)
+	}
+	defer func() {
+		_ = orch.Close()
+	}()
+
+	for _, tc := range []struct {
+		name      string
+		regressed bool
+	}{
+		{"fib 10", true},
+		{"fact 10", false},
+	} {
+		orch.handleConsoleOutput(ipc.GroupTestCaseEvent{
+			EventType: string(ipc.EventTypeTestCase),
+			Payload: ipc.TestCasePayload{
+				TestName:       tc.name,
+				ParentNames:    []string{"simple-bench", tc.name},
+				Status:         "BENCH",
+				BenchRegressed: tc.regressed,
+			},
+		})
+	}
+
+	if orch.benchmarks != 2 || orch.regressedBenches != 1 {
+		t.Errorf("Expected 2 benchmarks with 1 regressed, got %d with %d regressed",
+			orch.benchmarks, orch.regressedBenches)
+	}
+	if orch.totalTests != 0 {
+		t.Errorf("Expected benchmarks to be left out of test counts, got total=%d", orch.totalTests)
+	}
+}
+
+func TestOrchestrator_RunWithUnknownRunnerFlag(t *testing.T) {
+	originalDir, err := os.Getwd()
+	if err != nil {
+		t.Fatalf("Failed to get current directory: %v", err)
+	}
+
+	tempDir := t.TempDir()
+	if err := os.Chdir(tempDir); err != nil {
+		t.Fatalf("Failed to change to temp directory: %v", err)
+	}
+	defer func() { _ = os.Chdir(originalDir) }()
+
+	config := Config{
+		Command: []string{"cargo", "bench"},
+		Runner:  "no-such-runner",
+		Logger:  logger.NewTestLogger(),
+	}
+
+	orch, err := New(config)
+	if err != nil {
+		t.Fatalf("Failed to create orchestrator: %v", err)
+	}
+	defer func() {
+		_ = orch.Close()
+	}()
+
+	err = orch.Run()
+	if err == nil || !strings.Contains(err.Error(), "unknown test runner: no-such-runner") {
+		t.Errorf("Expected 'unknown test runner' error, got: %v", err)
+	}
+}
+
+func TestOrchestrator_PrepareRerun(t *testing.T) {
+	originalDir, err := os.Getwd()
+	if err != nil {
+		t.Fatalf("Failed to get current directory: %v", err)
+	}
+
+	tempDir := t.TempDir()
+	if err := os.Chdir(tempDir); err != nil {
+		t.Fatalf("Failed to change to temp directory: %v", err)
+	}
+	defer func() { _ = os.Chdir(originalDir) }()
+
+	orch, err := New(Config{
+		Command:     []string{"cargo", "test"},
+		Runner:      "cargo",
+		RerunFailed: true,
+		Logger:      logger.NewTestLogger(),
+	})
+	if err != nil {
+		t.Fatalf("Failed to create orchestrator: %v", err)
+	}
+	defer func() {
+		_ = orch.Close()
+	}()
+
+	// No previous run, so nothing runs
+	if rerun, err := orch.prepareRerun(); err != nil || rerun {
+		t.Fatalf("Expected nothing to re-run without a previous run, got %v, %v", rerun, err)
+	}
+
+	// The latest run decides; an older run's failures are ignored
+	writeRun := func(runID, failed string) {
+		runDir := filepath.Join(".3pio", "runs", runID)
+		if err := os.MkdirAll(runDir, 0755); err != nil {
+			t.Fatalf("Failed to create run directory: %v", err)
+		}
+		if err := os.WriteFile(filepath.Join(runDir, ipc.FailedTestsFileName), []byte(failed), 0644); err != nil {
+			t.Fatalf("Failed to write failed tests: %v", err)
+		}
+	}
+	writeRun("20261014T090000-kirk", `[{"name":"test_old","parentNames":["rust-performance","tests"]}]`)
+	writeRun("20261014T100000-spock", `[]`)
+	if rerun, err := orch.prepareRerun(); err != nil || rerun {
+		t.Fatalf("Expected nothing to re-run when the latest run passed, got %v, %v", rerun, err)
+	}
+
+	writeRun("20261014T110000-uhura", `[{"name":"test_add_004","parentNames":["rust-performance","tests"]}]`)
+	rerun, err := orch.prepareRerun()
+	if err != nil || !rerun {
+		t.Fatalf("Expected a re-run, got %v, %v", rerun, err)
+	}
+	if got := strings.Join(orch.command, " "); got != "cargo test -- --exact tests::test_add_004" {
+		t.Errorf("Expected the command narrowed to the failed test, got %q", got)
+	}
+	if orch.rerunOf != "20261014T110000-uhura" || orch.rerunCount != 1 {
+		t.Errorf("Expected re-run of 1 test from 20261014T110000-uhura, got %d from %q", orch.rerunCount, orch.rerunOf)
+	}
+
+	// A shard's failures are only part of the suite's
+	writeRun("20261014T120000-sulu", `[{"name":"test_add_004","parentNames":["rust-performance","tests"]}]`)
+	summary := `{"run_id":"20261014T120000-sulu","shard":{"index":2,"total":4}}`
+	if err := os.WriteFile(filepath.Join(".3pio", "runs", "20261014T120000-sulu", report.SummaryFileName), []byte(summary), 0644); err != nil {
+		t.Fatalf("Failed to write summary: %v", err)
+	}
+	var console strings.Builder
+	orch.consoleOut = &console
+	orch.command = []string{"cargo", "test"}
+	if rerun, err := orch.prepareRerun(); err != nil || !rerun {
+		t.Fatalf("Expected a re-run, got %v, %v", rerun, err)
+	}
+	if !strings.Contains(console.String(), "20261014T120000-sulu was shard 2/4") {
+		t.Errorf("Expected a warning that the run was a shard, got %q", console.String())
+	}
+}
+
+func TestShouldSetRustBacktrace(t *testing.T) {
+	// t.Setenv restores the variable afterwards, so it can be unset for the test
+	t.Setenv("RUST_BACKTRACE", "")
+	_ = os.Unsetenv("RUST_BACKTRACE")
+
+	tests := []struct {
+		name        string
+		command     []string
+		noBacktrace bool
+		expected    bool
+	}{
+		{"cargo test", []string{"cargo", "test"}, false, true},
+		{"cargo test with a toolchain", []string{"cargo", "+nightly", "test", "--lib"}, false, true},
+		{"cargo test by path", []string{"/home/dev/.cargo/bin/cargo", "test"}, false, true},
+		{"cargo test on Windows", []string{`C:\Users\dev\.cargo\bin\cargo.exe`, "test"}, false, true},
+		{"cargo test with --no-backtrace", []string{"cargo", "test"}, true, false},
+		{"cargo bench", []string{"cargo", "bench"}, false, false},
+		{"cross test", []string{"cross", "test", "--target", "aarch64-unknown-linux-gnu"}, false, true},
+		{"cargo nextest", []string{"cargo", "nextest", "run"}, false, false},
+		{"pytest", []string{"pytest"}, false, false},
+	}
+	for _, tt := range tests {
+		if result := shouldSetRustBacktrace(tt.command, tt.noBacktrace); result != tt.expected {
+			t.Errorf("%s: shouldSetRustBacktrace() = %v, expected %v", tt.name, result, tt.expected)
+		}
+	}
+
+	// A value set by the user is left alone
+	t.Setenv("RUST_BACKTRACE", "full")
+	if shouldSetRustBacktrace([]string{"cargo", "test"}, false) {
+		t.Errorf("Expected RUST_BACKTRACE set by the user to be kept")
+	}
+}
+
+func TestWatchInactivity(t *testing.T) {
+	orch, err := New(Config{
+		Command:           []string{"cargo", "test"},
+		InactivityTimeout: 200 * time.Millisecond,
+		Logger:            logger.NewTestLogger(),
+	})
+	if err != nil {
+		t.Fatalf("Failed to create orchestrator: %v", err)
+	}
+	defer func() {
+		_ = orch.Close()
+	}()
+
+	orch.reportManager, err = report.NewManager(t.TempDir(), nil, orch.logger, "cargo test", "cargo test")
+	if err != nil {
+		t.Fatalf("Failed to create report manager: %v", err)
+	}
+	if err := orch.reportManager.Initialize("cargo test"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+	outputPath := filepath.Join(t.TempDir(), "output.log")
+	if err := os.WriteFile(outputPath, nil, 0644); err != nil {
+		t.Fatalf("Failed to create output file: %v", err)
+	}
+
+	// Nothing is running yet, e.g. while cargo compiles silently
+	stop := make(chan struct{})
+	orch.recordActivity()
+	inactive := orch.watchInactivity(outputPath, stop)
+	select {
+	case <-inactive:
+		t.Fatal("Inactivity timeout fired with no running group")
+	case <-time.After(500 * time.Millisecond):
+	}
+	close(stop)
+
+	if err := orch.reportManager.HandleEvent(ipc.GroupStartEvent{
+		EventType: string(ipc.EventTypeGroupStart),
+		Payload:   ipc.GroupStartPayload{GroupName: "rust-basic"},
+	}); err != nil {
+		t.Fatalf("HandleEvent failed: %v", err)
+	}
+
+	// Output keeps a slow group alive
+	stop = make(chan struct{})
+	defer close(stop)
+	orch.recordActivity()
+	inactive = orch.watchInactivity(outputPath, stop)
+	for i := 0; i < 10; i++ {
+		time.Sleep(50 * time.Millisecond)
+		if err := os.WriteFile(outputPath, []byte(strings.Repeat(".", i+1)), 0644); err != nil {
+			t.Fatalf("Failed to write output: %v", err)
+		}
+		select {
+		case <-inactive:
+			t.Fatalf("Inactivity timeout fired while output was arriving")
+		default:
+		}
+	}
+
+	// Once the output stops, the timeout fires
+	select {
+	case <-inactive:
+	case <-time.After(2 * time.Second):
+		t.Fatal("Expected inactivity timeout after output stopped")
+	}
+	if running := orch.testsInFlight(nil); len(running) != 1 || running[0] != "rust-basic" {
+		t.Errorf("Expected rust-basic in flight, got %v", running)
+	}
+}
+
+func TestFormatHeartbeat(t *testing.T) {
+	tests := []struct {
+		groups []string
+		tests  []string
+		want   string
+	}{
+		{nil, nil, "… still running (4m20s)"},
+		{[]string{"core"}, nil, "… still running (4m20s): core"},
+		{
+			[]string{"core", "core > tests", "app", "app > unit"},
+			[]string{"core > tests > test_a", "core > tests > test_b", "app > unit > test_c"},
+			"… still running (4m20s): core > tests (2 tests in flight), app > unit (1 test in flight)",
+		},
+	}
+	for _, tt := range tests {
+		if got := formatHeartbeat(4*time.Minute+20*time.Second+400*time.Millisecond, tt.groups, tt.tests); got != tt.want {
+			t.Errorf("formatHeartbeat(%v, %v) = %q, want %q", tt.groups, tt.tests, got, tt.want)
+		}
+	}
+}
+
+func TestHeartbeat(t *testing.T) {
+	orch, err := New(Config{
+		Command:           []string{"cargo", "test"},
+		HeartbeatInterval: 200 * time.Millisecond,
+		Logger:            logger.NewTestLogger(),
+	})
+	if err != nil {
+		t.Fatalf("Failed to create orchestrator: %v", err)
+	}
+	defer func() {
+		_ = orch.Close()
+	}()
+	orch.reportManager, err = report.NewManager(t.TempDir(), nil, orch.logger, "cargo test", "cargo test")
+	if err != nil {
+		t.Fatalf("Failed to create report manager: %v", err)
+	}
+	if err := orch.reportManager.Initialize("cargo test"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+	if err := orch.reportManager.HandleEvent(ipc.GroupStartEvent{
+		EventType: string(ipc.EventTypeGroupStart),
+		Payload:   ipc.GroupStartPayload{GroupName: "core"},
+	}); err != nil {
+		t.Fatalf("HandleEvent failed: %v", err)
+	}
+
+	old := os.Stdout
+	r, w, _ := os.Pipe()
+	os.Stdout = w
+	orch.startTime = time.Now()
+	stop := orch.startHeartbeat(nil)
+
+	// Output printed within the interval keeps the heartbeat quiet
+	for i := 0; i < 8; i++ {
+		orch.printConsole(fmt.Sprintf("line %d\n", i))
+		time.Sleep(50 * time.Millisecond)
+	}
+	// Then a silence longer than the interval gets one
+	time.Sleep(350 * time.Millisecond)
+	stop()
+	_ = w.Close()
+	os.Stdout = old
+	output, _ := io.ReadAll(r)
+
+	lines := strings.Split(strings.TrimRight(string(output), "\n"), "\n")
+	if len(lines) < 9 {
+		t.Fatalf("Expected 8 lines and a heartbeat, got:\n%s", output)
+	}
+	for i, line := range lines[:8] {
+		if line != fmt.Sprintf("line %d", i) {
+			t.Errorf("Expected no heartbeat while output was printed, got:\n%s", output)
+			break
+		}
+	}
+	if !regexp.MustCompile(`^… still running \(\d+s\): core$`).MatchString(lines[8]) {
+		t.Errorf("Expected a heartbeat naming the running group, got %q", lines[8])
+	}
+}
+
 func TestOrchestrator_RunWithInvalidRunner(t *testing.T) {
 	// Change to a temp directory for the test
 	originalDir, err := os.Getwd()
@@ -477,3 +932,68 @@ func TestOutputFileRaceCondition(t *testing.T) {
 	// but we don't want to fail the test if the race doesn't occur
 	t.Log("Race condition did not occur in this test run (timing dependent)")
 }
+
+func TestStopWatchMode(t *testing.T) {
+	tests := []struct {
+		adapter  string
+		command  []string
+		expected []string
+		changes  []string
+	}{
+		{"jest.js", []string{"npx", "jest", "--watch"}, []string{"npx", "jest"}, []string{"--watch"}},
+		{"jest.js", []string{"npm", "test", "--", "--watchAll=true", "src"}, []string{"npm", "test", "--", "src"}, []string{"--watchAll=true"}},
+		{"jest.js", []string{"npx", "react-scripts", "test", "--watchAll=false"}, []string{"npx", "react-scripts", "test", "--watchAll=false"}, nil},
+		// -w is jest's --maxWorkers
+		{"jest.js", []string{"npx", "jest", "-w", "4"}, []string{"npx", "jest", "-w", "4"}, nil},
+		{"vitest.js", []string{"npx", "vitest", "run", "-w"}, []string{"npx", "vitest", "run"}, []string{"-w"}},
+		{"vitest.js", []string{"pnpm", "vitest", "watch", "src"}, []string{"pnpm", "vitest", "run", "src"}, []string{"vitest watch"}},
+		{"vitest.js", []string{"npx", "vitest", "--coverage.enabled=false"}, []string{"npx", "vitest", "--coverage.enabled=false"}, []string{"vitest"}},
+		{"vitest.js", []string{"npx", "vitest", "bench"}, []string{"npx", "vitest", "bench"}, nil},
+		{"vitest.js", []string{"npm", "test"}, []string{"npm", "test"}, nil},
+		{"mocha.js", []string{"npx", "mocha", "-w", "test/"}, []string{"npx", "mocha", "test/"}, []string{"-w"}},
+		{"pytest_adapter.py", []string{"pytest", "--looponfail"}, []string{"pytest"}, []string{"--looponfail"}},
+		{"jest.js", []string{"yarn", "test", "--watch"}, []string{"yarn", "test"}, []string{"--watch"}},
+		// After "--" jest takes test patterns, and the test binary its flags after -args
+		{"jest.js", []string{"npx", "jest", "--", "--watch"}, []string{"npx", "jest", "--", "--watch"}, nil},
+		{"jest.js", []string{"npm", "test", "--", "src", "--", "--watch"}, []string{"npm", "test", "--", "src", "--", "--watch"}, nil},
+		{"", []string{"go", "test", "./...", "-args", "--watch"}, []string{"go", "test", "./...", "-args", "--watch"}, nil},
+		// Runners that don't watch keep their flags
+		{"", []string{"node", "--watch", "run-tests.js"}, []string{"node", "--watch", "run-tests.js"}, nil},
+		{"", []string{"cargo", "test", "--", "--watch"}, []string{"cargo", "test", "--", "--watch"}, nil},
+	}
+	for _, tt := range tests {
+		command, changes := stopWatchMode(tt.adapter, tt.command)
+		if strings.Join(command, " ") != strings.Join(tt.expected, " ") {
+			t.Errorf("stopWatchMode(%q, %v) = %v, want %v", tt.adapter, tt.command, command, tt.expected)
+		}
+		var args []string
+		for _, change := range changes {
+			args = append(args, change.arg)
+		}
+		if strings.Join(args, ",") != strings.Join(tt.changes, ",") {
+			t.Errorf("stopWatchMode(%q, %v) changed %v, want %v", tt.adapter, tt.command, args, tt.changes)
+		}
+	}
+}
+
+func TestUnwrapWatcher(t *testing.T) {
+	tests := []struct {
+		command  []string
+		expected []string
+		wrapper  bool
+	}{
+		{[]string{"nodemon", "--watch", "src", "--exec", "npx jest --ci"}, []string{"npx", "jest", "--ci"}, true},
+		{[]string{"npx", "nodemon", "-x", "mocha"}, []string{"mocha"}, true},
+		{[]string{"nodemon", "test.js"}, nil, true},
+		{[]string{"npx", "watchexec", "--", "cargo", "test"}, nil, true},
+		{[]string{"npx", "jest", "--watch"}, nil, false},
+		{[]string{"node", "--watch", "--test"}, nil, false},
+		{[]string{"npx"}, nil, false},
+	}
+	for _, tt := range tests {
+		command, _, ok := unwrapWatcher(tt.command)
+		if ok != tt.wrapper || strings.Join(command, " ") != strings.Join(tt.expected, " ") {
+			t.Errorf("unwrapWatcher(%v) = %v, %v, want %v, %v", tt.command, command, ok, tt.expected, tt.wrapper)
+		}
+	}
+}
@@ -0,0 +1,125 @@
+package orchestrator
+
+import (
+	"io"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/zk/3pio/internal/ipc"
+)
+
+// preflightLogFile keeps the output of the preflight list in the run directory
+const preflightLogFile = "preflight-list.log"
+
+// testLister is implemented by native definitions that can list the tests of the
+// command before running it, so the report holds every test as PENDING from the start
+type testLister interface {
+	ListCommand(command []string, ipcPath string) []string // nil when the tests can't be listed first
+	ProcessTestList(output io.Reader, ipcPath string) error
+}
+
+// preflightResult is how the preflight list ended
+type preflightResult int
+
+const (
+	preflightDone        preflightResult = iota // Listed, or failed to; the run goes ahead either way
+	preflightInterrupted                        // Ctrl-C stopped it
+	preflightTimedOut                           // --timeout ran out
+)
+
+// runPreflightList runs the definition's list command before the test command
+// (--preflight-list). It builds the same test binaries, so the run reuses them. A
+// list that fails only leaves the report without the pending tests; the run then
+// reports the tests as they come, and any build error. deadline is the --timeout
+// timer, nil without one.
+func (o *Orchestrator) runPreflightList(lister testLister, deadline <-chan time.Time, sigChan <-chan os.Signal) preflightResult {
+	listCommand := lister.ListCommand(o.command, o.ipcPath)
+	if len(listCommand) == 0 {
+		o.logger.Debug("The tests of this command can't be listed before the run")
+		return preflightDone
+	}
+	o.logger.Debug("Listing the tests first: %v", listCommand)
+
+	logPath := filepath.Join(o.runDir, preflightLogFile)
+	logFile, err := os.Create(logPath)
+	if err != nil {
+		o.logger.Debug("Failed to create %s, not listing the tests first: %v", preflightLogFile, err)
+		return preflightDone
+	}
+	defer func() { _ = logFile.Close() }()
+
+	cmd := exec.Command(listCommand[0], listCommand[1:]...)
+	if wd := o.workDir(); wd != "" {
+		cmd.Dir = wd
+	}
+	cmd.Env = preflightEnv(o.commandEnv(o.ipcPath))
+	cmd.Stdout = logFile
+	cmd.Stderr = logFile
+	prepareProcessTree(cmd)
+	if err := cmd.Start(); err != nil {
+		o.logger.Debug("Failed to start the preflight list: %v", err)
+		return preflightDone
+	}
+	tree, err := newProcessTree(cmd)
+	if err != nil {
+		o.logger.Debug("Failed to track the preflight list's process tree: %v", err)
+	}
+	stop := func() {
+		if tree == nil || tree.kill() != nil {
+			_ = cmd.Process.Kill()
+		}
+	}
+	if tree != nil {
+		defer func() { _ = tree.close() }()
+	}
+
+	done := make(chan error, 1)
+	go func() {
+		done <- cmd.Wait()
+	}()
+	select {
+	case err := <-done:
+		if err != nil {
+			o.logger.Debug("The preflight list failed, running the tests without it: %v", err)
+			return preflightDone
+		}
+	case sig := <-sigChan:
+		o.logger.Info("Received signal while listing the tests: %v", sig)
+		stop()
+		<-done
+		return preflightInterrupted
+	case <-deadline:
+		o.logger.Info("Run timed out after %s while listing the tests", o.timeout)
+		stop()
+		<-done
+		return preflightTimedOut
+	}
+
+	output, err := os.Open(logPath)
+	if err != nil {
+		o.logger.Debug("Failed to read %s: %v", preflightLogFile, err)
+		return preflightDone
+	}
+	defer func() { _ = output.Close() }()
+	if err := lister.ProcessTestList(output, o.ipcPath); err != nil {
+		o.logger.Error("Failed to process the test list: %v", err)
+	}
+	return preflightDone
+}
+
+// preflightEnv is the test command's environment without the IPC path, so a custom
+// harness reporting to 3pio doesn't report the tests it lists as run. Everything
+// else stays the same: cargo rebuilds when the environment of a build changes.
+func preflightEnv(env []string) []string {
+	result := make([]string, 0, len(env))
+	for _, kv := range env {
+		if strings.HasPrefix(kv, "THREEPIO_IPC_PATH=") || strings.HasPrefix(kv, ipc.TransportEnv+"=") {
+			continue
+		}
+		result = append(result, kv)
+	}
+	return result
+}
@@ -0,0 +1,85 @@
+//go:build !windows
+
+package orchestrator
+
+import (
+	"errors"
+	"os"
+	"os/exec"
+	"os/signal"
+	"syscall"
+
+	"golang.org/x/sys/unix"
+
+	"github.com/zk/3pio/internal/console"
+)
+
+// processTree is the test command together with every process it starts, e.g.
+// the test binaries cargo spawns and the helpers those spawn in turn
+type processTree struct {
+	pid      int
+	terminal int // Descriptor of the terminal the group was given, or -1
+}
+
+// prepareProcessTree makes the command the leader of a new process group, so
+// the test binaries it spawns can be signalled along with it. When 3pio has the
+// terminal, the new group is made the terminal's foreground group: a test reading
+// stdin from outside it would be stopped by SIGTTIN.
+func prepareProcessTree(cmd *exec.Cmd) {
+	cmd.SysProcAttr = &syscall.SysProcAttr{Setpgid: true}
+	if stdin, ok := cmd.Stdin.(*os.File); ok && console.IsTerminal(stdin) {
+		fd := int(stdin.Fd())
+		if pgrp, err := unix.IoctlGetInt(fd, unix.TIOCGPGRP); err == nil && pgrp == syscall.Getpgrp() {
+			cmd.SysProcAttr.Foreground = true
+			cmd.SysProcAttr.Ctty = fd
+		}
+	}
+}
+
+// newProcessTree tracks the process group of a started command
+func newProcessTree(cmd *exec.Cmd) (*processTree, error) {
+	terminal := -1
+	if cmd.SysProcAttr != nil && cmd.SysProcAttr.Foreground {
+		terminal = cmd.SysProcAttr.Ctty
+	}
+	return &processTree{pid: cmd.Process.Pid, terminal: terminal}, nil
+}
+
+// kill sends SIGKILL to every process in the group
+func (t *processTree) kill() error {
+	return syscall.Kill(-t.pid, syscall.SIGKILL)
+}
+
+// interrupt sends SIGINT to every process in the group. When the group isn't
+// the terminal's foreground group, Ctrl-C only reaches it this way.
+func (t *processTree) interrupt() error {
+	return syscall.Kill(-t.pid, syscall.SIGINT)
+}
+
+// waited gives the terminal back to 3pio's process group once the command has
+// exited. tcsetpgrp from a background group raises SIGTTOU, which is ignored
+// for the call.
+func (t *processTree) waited() {
+	if t.terminal < 0 {
+		return
+	}
+	signal.Ignore(syscall.SIGTTOU)
+	defer signal.Reset(syscall.SIGTTOU)
+	_ = unix.IoctlSetPointerInt(t.terminal, unix.TIOCSPGRP, syscall.Getpgrp())
+}
+
+// interruptedAtTerminal reports whether the command exited from a Ctrl-C. With
+// the terminal's foreground group, the command gets Ctrl-C rather than 3pio.
+func (t *processTree) interruptedAtTerminal(err error) bool {
+	var exitErr *exec.ExitError
+	if t.terminal < 0 || !errors.As(err, &exitErr) {
+		return false
+	}
+	status, ok := exitErr.Sys().(syscall.WaitStatus)
+	return ok && (status.Signaled() && status.Signal() == syscall.SIGINT || status.ExitStatus() == 130)
+}
+
+// close releases the tree; a process group needs no cleanup
+func (t *processTree) close() error {
+	return nil
+}
@@ -0,0 +1,127 @@
+//go:build windows
+
+package orchestrator
+
+import (
+	"fmt"
+	"os/exec"
+	"syscall"
+	"unsafe"
+
+	"golang.org/x/sys/windows"
+)
+
+// threadSuspendResume is the THREAD_SUSPEND_RESUME access right
+const threadSuspendResume = 0x0002
+
+// processTree is the test command together with every process it starts, e.g.
+// the test binaries cargo spawns and the helpers those spawn in turn. Windows
+// has no process groups to kill, so the command is put in a Job Object.
+type processTree struct {
+	pid uint32
+	job windows.Handle
+}
+
+// prepareProcessTree starts the command in a new console process group, so
+// Ctrl-Break can be sent to it without reaching 3pio. It starts suspended, so
+// it can't start processes before newProcessTree puts it in the job.
+func prepareProcessTree(cmd *exec.Cmd) {
+	cmd.SysProcAttr = &syscall.SysProcAttr{CreationFlags: syscall.CREATE_NEW_PROCESS_GROUP | windows.CREATE_SUSPENDED}
+}
+
+// newProcessTree assigns a started command to a new Job Object, then resumes
+// it, so every process it starts belongs to the job too. The command is resumed
+// even when the job can't be set up; if it can't be, it is terminated.
+func newProcessTree(cmd *exec.Cmd) (*processTree, error) {
+	pid := uint32(cmd.Process.Pid)
+	job, err := assignToJob(pid)
+	if resumeErr := resumeProcess(pid); resumeErr != nil {
+		_ = cmd.Process.Kill()
+		if job != 0 {
+			_ = windows.CloseHandle(job)
+		}
+		return nil, fmt.Errorf("failed to resume test command: %w", resumeErr)
+	}
+	if err != nil {
+		return nil, err
+	}
+	return &processTree{pid: pid, job: job}, nil
+}
+
+// assignToJob creates a Job Object and assigns the process to it
+func assignToJob(pid uint32) (windows.Handle, error) {
+	job, err := windows.CreateJobObject(nil, nil)
+	if err != nil {
+		return 0, fmt.Errorf("failed to create job object: %w", err)
+	}
+
+	process, err := windows.OpenProcess(windows.PROCESS_SET_QUOTA|windows.PROCESS_TERMINATE, false, pid)
+	if err != nil {
+		_ = windows.CloseHandle(job)
+		return 0, fmt.Errorf("failed to open test command process: %w", err)
+	}
+	defer func() { _ = windows.CloseHandle(process) }()
+
+	if err := windows.AssignProcessToJobObject(job, process); err != nil {
+		_ = windows.CloseHandle(job)
+		return 0, fmt.Errorf("failed to assign test command to job object: %w", err)
+	}
+	return job, nil
+}
+
+// resumeProcess resumes the threads of a process started suspended. os.Process
+// doesn't keep the handle of its main thread, so they are looked up by process.
+func resumeProcess(pid uint32) error {
+	snapshot, err := windows.CreateToolhelp32Snapshot(windows.TH32CS_SNAPTHREAD, 0)
+	if err != nil {
+		return err
+	}
+	defer func() { _ = windows.CloseHandle(snapshot) }()
+
+	entry := windows.ThreadEntry32{Size: uint32(unsafe.Sizeof(windows.ThreadEntry32{}))}
+	resumed := false
+	for err = windows.Thread32First(snapshot, &entry); err == nil; err = windows.Thread32Next(snapshot, &entry) {
+		if entry.OwnerProcessID != pid {
+			continue
+		}
+		thread, err := windows.OpenThread(threadSuspendResume, false, entry.ThreadID)
+		if err != nil {
+			return err
+		}
+		_, err = windows.ResumeThread(thread)
+		_ = windows.CloseHandle(thread)
+		if err != nil {
+			return err
+		}
+		resumed = true
+	}
+	if !resumed {
+		return fmt.Errorf("no thread of process %d found", pid)
+	}
+	return nil
+}
+
+// kill terminates every process in the job
+func (t *processTree) kill() error {
+	return windows.TerminateJobObject(t.job, 1)
+}
+
+// interrupt sends Ctrl-Break to the command's console process group
+func (t *processTree) interrupt() error {
+	return windows.GenerateConsoleCtrlEvent(windows.CTRL_BREAK_EVENT, t.pid)
+}
+
+// waited does nothing on Windows: the command never takes over the console
+func (t *processTree) waited() {}
+
+// interruptedAtTerminal is always false on Windows: the command's console process
+// group doesn't get the Ctrl-C meant for 3pio
+func (t *processTree) interruptedAtTerminal(err error) bool {
+	return false
+}
+
+// close releases the job handle. The job has no kill-on-close limit, so
+// processes still running after a normal exit are left alone, as on Unix.
+func (t *processTree) close() error {
+	return windows.CloseHandle(t.job)
+}
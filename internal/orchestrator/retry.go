@@ -0,0 +1,296 @@
+package orchestrator
+
+import (
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/zk/3pio/internal/ipc"
+	"github.com/zk/3pio/internal/report"
+	"github.com/zk/3pio/internal/runner"
+	"github.com/zk/3pio/internal/runner/definitions"
+)
+
+// nativeRetrier is implemented by runners that retry failed tests themselves, so
+// --retries is passed through instead of re-running the failed tests
+type nativeRetrier interface {
+	RetriesCommand(command []string, retries int) []string
+}
+
+// retryResult is how the retries of the failed tests ended
+type retryResult int
+
+const (
+	retryDone        retryResult = iota // Every retry ran, or the tests stopped failing
+	retryInterrupted                    // Ctrl-C stopped a retry
+	retryTimedOut                       // --timeout ran out during a retry
+	retryInactive                       // --inactivity-timeout stopped a retry
+)
+
+// retryFailedTests re-runs the failed tests up to --retries times (--retries), each
+// attempt narrowed down to the tests still failing. Tests that pass on a retry become
+// FLAKY and their groups pass. The run's deadline and inactivity watchdog stop a
+// retry like the first run; after the inactivity timeout it also returns the tests
+// that were in flight.
+func (o *Orchestrator) retryFailedTests(deadline <-chan time.Time, sigChan <-chan os.Signal) (retryResult, []string) {
+	failedRoots := make(map[string]bool)
+	for _, group := range o.reportManager.GetRootGroups() {
+		if group.Status == report.TestStatusFail {
+			failedRoots[group.ID] = true
+		}
+	}
+	flakyBefore := len(o.reportManager.FlakyTests())
+
+	result := retryDone
+	var inFlight []string
+	for attempt := 2; attempt <= o.retries+1 && result == retryDone; attempt++ {
+		failed := o.reportManager.FailedTests()
+		if len(failed) == 0 {
+			break
+		}
+		runnerDef, err := o.retryRunner()
+		if err != nil {
+			o.logger.Error("Not retrying the failed tests: %v", err)
+			break
+		}
+		filter, ok := runnerDef.(rerunFilter)
+		if !ok {
+			break
+		}
+		command, err := filter.RerunCommand(o.command, failed)
+		if err != nil {
+			fmt.Fprintf(o.stdout(), "Not retrying the failed tests: %v\n\n", err)
+			break
+		}
+
+		fmt.Fprintf(o.stdout(), "Retrying %d failed tests (attempt %d of %d)\n", len(failed), attempt, o.retries+1)
+		o.logger.Debug("Retrying the failed tests: %v", command)
+		o.reportManager.BeginRetry()
+		result, inFlight, err = o.runRetry(runnerDef, command, attempt, deadline, sigChan)
+		o.reportManager.EndRetry()
+		if err != nil {
+			o.logger.Error("Failed to retry the failed tests: %v", err)
+			break
+		}
+		if result == retryInactive && len(inFlight) == 0 {
+			// The groups finished in the first run, so name the tests being retried
+			for _, test := range failed {
+				inFlight = append(inFlight, o.consoleTestPath(test.ParentNames, test.Name))
+			}
+		}
+	}
+
+	// The console counted these tests and groups as failed when they first ran
+	flaky := o.reportManager.FlakyTests()
+	o.failedTests -= len(flaky) - flakyBefore
+	o.flakyTests += len(flaky) - flakyBefore
+	for _, group := range o.reportManager.GetRootGroups() {
+		if failedRoots[group.ID] && group.Status == report.TestStatusPass {
+			o.failedGroups--
+			o.passedGroups++
+		}
+	}
+
+	if len(flaky) > 0 {
+		fmt.Fprintln(o.stdout())
+		for _, test := range flaky {
+			fmt.Fprintf(o.stdout(), "FLAKY %s (passed on a retry)\n", o.consoleTestPath(test.ParentNames, test.Name))
+		}
+	}
+	fmt.Fprintln(o.stdout())
+	return result, inFlight
+}
+
+// retryRunner returns a fresh definition of the run's runner, since a native
+// definition keeps the state of the output it already processed
+func (o *Orchestrator) retryRunner() (runner.Definition, error) {
+	// Not closed: the manager shares the run's logger
+	manager := runner.NewManager(o.runnerLogger)
+	if o.dir != "" {
+		manager.SetWorkDir(o.dir)
+	}
+	if o.runnerName == "" {
+		return manager.Detect(o.command)
+	}
+	runnerDef, ok := manager.GetDefinition(o.runnerName)
+	if !ok {
+		return nil, fmt.Errorf("unknown test runner: %s", o.runnerName)
+	}
+	return runnerDef, nil
+}
+
+// commandExitCode returns the exit code of a command from the error it finished with
+func commandExitCode(err error) int {
+	var exitErr *exec.ExitError
+	switch {
+	case err == nil:
+		return 0
+	case errors.As(err, &exitErr):
+		return exitErr.ExitCode()
+	}
+	return 1
+}
+
+// runRetry runs one retry of the failed tests, appending its output to output.log and
+// handing its events to the report manager. It returns how the retry ended, with the
+// tests in flight when the inactivity timeout stopped it.
+func (o *Orchestrator) runRetry(runnerDef runner.Definition, command []string, attempt int, deadline <-chan time.Time, sigChan <-chan os.Signal) (retryResult, []string, error) {
+	nativeDef := nativeDefinition(runnerDef)
+	if limiter, ok := nativeDef.(lineLimiter); ok {
+		limiter.SetMaxLineLength(o.maxLineLength)
+	}
+	if user, ok := nativeDef.(runDirUser); ok {
+		user.SetRunDir(o.runDir)
+	}
+	processor, ok := nativeDef.(interface {
+		ProcessOutput(io.Reader, string) error
+	})
+	if !ok {
+		return retryDone, nil, fmt.Errorf("the runner doesn't parse its own output")
+	}
+
+	// Each attempt writes its events to its own IPC file
+	ipcPath := filepath.Join(o.runDir, fmt.Sprintf("ipc-attempt-%d.jsonl", attempt))
+	ipcManager, err := ipc.NewManager(ipcPath, o.logger)
+	if err != nil {
+		return retryDone, nil, fmt.Errorf("failed to create IPC manager: %w", err)
+	}
+	if err := ipcManager.WatchEvents(); err != nil {
+		return retryDone, nil, fmt.Errorf("failed to start IPC watcher: %w", err)
+	}
+	eventsDone := make(chan struct{})
+	go func() {
+		defer close(eventsDone)
+		for event := range ipcManager.Events {
+			o.recordActivity()
+			if err := o.reportManager.HandleEvent(event); err != nil {
+				o.logger.Error("Failed to handle event: %v", err)
+			}
+			if o.observer != nil {
+				o.observer.Event(event)
+			}
+		}
+	}()
+	defer func() {
+		ipcManager.Drain()
+		_ = ipcManager.Cleanup()
+		o.reportManager.AddMalformedIPCEvents(ipcManager.MalformedEvents())
+		<-eventsDone
+	}()
+
+	outputFile, err := os.OpenFile(filepath.Join(o.runDir, "output.log"), os.O_WRONLY|os.O_APPEND|os.O_CREATE, 0644)
+	if err != nil {
+		return retryDone, nil, fmt.Errorf("failed to open output file: %w", err)
+	}
+	defer func() { _ = outputFile.Close() }()
+	_, _ = fmt.Fprintf(outputFile, "\n--- Retry %d of the failed tests: %s ---\n\n", attempt-1, strings.Join(command, " "))
+
+	testCommand := runnerDef.BuildCommand(command, "")
+	cmd := exec.Command(testCommand[0], testCommand[1:]...)
+	if wd := o.workDir(); wd != "" {
+		cmd.Dir = wd
+	}
+	cmd.Env = o.commandEnv(ipcPath)
+	cmd.Stdin = os.Stdin
+	prepareProcessTree(cmd)
+
+	// The output goes to output.log as in the first run, and through the definition
+	outputReader, outputWriter := io.Pipe()
+	cmd.Stdout = io.MultiWriter(outputFile, outputWriter)
+	if _, ok := nativeDef.(*definitions.GoTestDefinition); ok {
+		// go test's JSON is on stdout only
+		cmd.Stderr = outputFile
+	} else {
+		cmd.Stderr = cmd.Stdout
+	}
+	outputDone := make(chan struct{})
+	go func() {
+		defer close(outputDone)
+		if err := processor.ProcessOutput(outputReader, ipcPath); err != nil {
+			o.logger.Error("Failed to process native output: %v", err)
+		}
+		// Keep the command from blocking on output nobody reads
+		_, _ = io.Copy(io.Discard, outputReader)
+	}()
+	defer func() {
+		_ = outputWriter.Close()
+		<-outputDone
+	}()
+
+	if err := cmd.Start(); err != nil {
+		return retryDone, nil, fmt.Errorf("failed to start test command: %w", err)
+	}
+	runTree := o.processTree
+	o.processTree = nil
+	if tree, err := newProcessTree(cmd); err == nil {
+		o.processTree = tree
+	}
+	defer func() {
+		if o.processTree != nil {
+			_ = o.processTree.close()
+		}
+		o.processTree = runTree
+	}()
+
+	// The retry gets the whole inactivity timeout, however long the first run idled at its end
+	var inactive <-chan struct{}
+	if o.inactivityTimeout > 0 {
+		stopWatchdog := make(chan struct{})
+		defer close(stopWatchdog)
+		o.recordActivity()
+		inactive = o.watchInactivity(filepath.Join(o.runDir, "output.log"), stopWatchdog)
+	}
+	done := make(chan error, 1)
+	tree := o.processTree
+	go func() {
+		err := cmd.Wait()
+		if tree != nil {
+			tree.waited()
+		}
+		done <- err
+	}()
+	select {
+	case err := <-done:
+		o.logger.Debug("Retry %d finished: %v", attempt-1, err)
+		if tree != nil && tree.interruptedAtTerminal(err) {
+			// The test command had the terminal, so Ctrl-C reached it rather than 3pio
+			if reporter, ok := nativeDef.(interruptReporter); ok {
+				reporter.MarkInterrupted()
+			}
+			return retryInterrupted, nil, nil
+		}
+		if reporter, ok := nativeDef.(exitCodeReporter); ok {
+			reporter.SetExitCode(commandExitCode(err))
+		}
+		return retryDone, nil, nil
+	case sig := <-sigChan:
+		o.logger.Info("Received signal: %v", sig)
+		if reporter, ok := nativeDef.(interruptReporter); ok {
+			reporter.MarkInterrupted()
+		}
+		o.interruptProcess(cmd, done, sigChan)
+		return retryInterrupted, nil, nil
+	case <-inactive:
+		inFlight := o.testsInFlight(nativeDef)
+		o.logger.Info("No output for %s, stopping retry %d", o.inactivityTimeout, attempt-1)
+		if reporter, ok := nativeDef.(inFlightReporter); ok {
+			reporter.MarkTimedOut()
+		}
+		o.stopProcessTree(cmd)
+		<-done
+		return retryInactive, inFlight, nil
+	case <-deadline:
+		o.logger.Info("Run timed out after %s, stopping retry %d", o.timeout, attempt-1)
+		if reporter, ok := nativeDef.(interruptReporter); ok {
+			reporter.MarkInterrupted()
+		}
+		o.stopProcessTree(cmd)
+		<-done
+		return retryTimedOut, nil, nil
+	}
+}
@@ -0,0 +1,42 @@
+package orchestrator
+
+import (
+	"context"
+
+	"github.com/zk/3pio/internal/runinfo"
+	"github.com/zk/3pio/internal/runner"
+)
+
+// startRunInfo collects the toolchain and environment metadata of the run in the
+// background, so the version commands run alongside the build instead of before it
+func (o *Orchestrator) startRunInfo(runnerDef runner.Definition) {
+	var info runinfo.Info
+	if contributor, ok := runnerDef.(runinfo.RunnerInfo); ok {
+		info = contributor.RunnerInfo(o.command)
+	}
+	// The shard and cargo build are known from the command, so the report shows them
+	// from the start
+	if info.Shard != nil {
+		o.shard = info.Shard
+		o.reportManager.SetShard(info.Shard)
+	}
+	if info.Cargo != nil {
+		o.reportManager.SetCargo(info.Cargo)
+	}
+	done := make(chan *runinfo.Metadata, 1)
+	go func() {
+		done <- runinfo.Collect(context.Background(), info)
+	}()
+	o.runInfo = done
+}
+
+// finishRunInfo waits for the metadata and hands it to the report. Only the first
+// call does anything.
+func (o *Orchestrator) finishRunInfo() {
+	if o.runInfo == nil {
+		return
+	}
+	md := <-o.runInfo
+	o.runInfo = nil
+	o.reportManager.SetMetadata(md)
+}
@@ -0,0 +1,190 @@
+package orchestrator
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"slices"
+	"strings"
+
+	"github.com/zk/3pio/internal/runner/definitions"
+)
+
+// watchFlags are the flags that make a runner watch for changes and re-run its tests,
+// so it never exits, by the adapter of the runner
+var watchFlags = map[string][]string{
+	"jest.js":           {"--watch", "--watchAll"},
+	"vitest.js":         {"--watch", "-w"},
+	"mocha.js":          {"--watch", "-w"},
+	"pytest_adapter.py": {"--looponfail", "-f"}, // pytest-xdist
+}
+
+// watchWrappers re-run the command they're given whenever a file changes, so they
+// never exit. Only nodemon's command can be told apart from its own arguments: the one
+// given with --exec (-x).
+var watchWrappers = []string{"nodemon", "watchexec", "chokidar", "onchange"}
+
+// vitestSubcommands are the subcommands of vitest that run once and exit
+var vitestSubcommands = []string{"run", "bench", "typecheck", "list", "related", "init"}
+
+// watchModeChange is a change to the test command that keeps it from watching for changes
+type watchModeChange struct {
+	arg  string // The watch flag or command as given, e.g. "--watchAll"
+	with string // What runs in its place; empty when it's dropped
+}
+
+func (c watchModeChange) String() string {
+	if c.with == "" {
+		return fmt.Sprintf("removed %s, which would watch for changes and never exit", c.arg)
+	}
+	return fmt.Sprintf("running %s instead of %s, which would watch for changes and never exit", c.with, c.arg)
+}
+
+// stopWatchMode returns the command without the flags that make the runner of
+// adapterFile watch for changes, and what it changed. Runners without watch flags are
+// left alone, and so are the arguments after the runner's own (see runnerArgsEnd). A
+// vitest command without a subcommand watches as well; BuildCommand adds its run.
+func stopWatchMode(adapterFile string, command []string) ([]string, []watchModeChange) {
+	flags, ok := watchFlags[adapterFile]
+	if !ok {
+		return command, nil
+	}
+	end := runnerArgsEnd(command)
+	var changes []watchModeChange
+	result := make([]string, 0, len(command))
+	for i, arg := range command[:end] {
+		if isWatchFlag(arg, flags) {
+			changes = append(changes, watchModeChange{arg: arg})
+			continue
+		}
+		if adapterFile == "vitest.js" && i > 0 && isVitestCommand(command[i-1]) && (arg == "watch" || arg == "dev") {
+			changes = append(changes, watchModeChange{arg: command[i-1] + " " + arg, with: command[i-1] + " run"})
+			result = append(result, "run")
+			continue
+		}
+		result = append(result, arg)
+	}
+
+	if adapterFile == "vitest.js" {
+		for i, arg := range result {
+			if !isVitestCommand(arg) {
+				continue
+			}
+			if i+1 == len(result) || !slices.Contains(vitestSubcommands, result[i+1]) {
+				changes = append(changes, watchModeChange{arg: arg, with: arg + " run"})
+			}
+			break
+		}
+	}
+	return append(result, command[end:]...), changes
+}
+
+// unwrapWatcher returns the command a watch wrapper such as nodemon would re-run,
+// and what it changed. ok is false when command doesn't start with a wrapper, run
+// directly or with npx; command is nil when the wrapper runs no --exec command.
+func unwrapWatcher(command []string) (unwrapped []string, change watchModeChange, ok bool) {
+	start := 0
+	if len(command) > 1 && slices.Contains([]string{"npx", "pnpx", "bunx"}, commandName(command[0])) {
+		start = 1
+	}
+	if start == len(command) || !slices.Contains(watchWrappers, commandName(command[start])) {
+		return nil, watchModeChange{}, false
+	}
+	change = watchModeChange{arg: command[start]}
+	if commandName(command[start]) != "nodemon" {
+		return nil, change, true
+	}
+	for i := start + 1; i < len(command)-1; i++ {
+		if command[i] == "--exec" || command[i] == "-x" {
+			change.with = command[i+1]
+			return strings.Fields(command[i+1]), change, true
+		}
+	}
+	return nil, change, true
+}
+
+// runnerArgsEnd returns where the runner's own arguments end in command: at "--",
+// after which jest and vitest take test patterns and cargo the test binary's flags, or
+// at go test's -args. The first "--" of an npm, yarn, pnpm or bun command passes the
+// flags after it on to the package script's runner, so it doesn't end them.
+func runnerArgsEnd(command []string) int {
+	passThrough := len(command) > 0 && isPackageManagerCommand(command[0])
+	for i, arg := range command {
+		if arg == "--" && passThrough {
+			passThrough = false
+			continue
+		}
+		if arg == "--" || arg == "-args" {
+			return i
+		}
+	}
+	return len(command)
+}
+
+// isPackageManagerCommand reports whether arg runs npm, yarn, pnpm or bun
+func isPackageManagerCommand(arg string) bool {
+	return slices.Contains([]string{"npm", "yarn", "pnpm", "bun"}, commandName(arg))
+}
+
+// commandName returns the name of the program arg runs, without its directory or
+// Windows' .cmd
+func commandName(arg string) string {
+	return strings.TrimSuffix(filepath.Base(strings.ReplaceAll(arg, "\\", "/")), ".cmd")
+}
+
+// isWatchFlag reports whether arg is one of flags, alone or set to true
+// ("--watchAll=true"); "--watchAll=false" turns watching off
+func isWatchFlag(arg string, flags []string) bool {
+	name, value, hasValue := strings.Cut(arg, "=")
+	return slices.Contains(flags, name) && (!hasValue || value == "true")
+}
+
+// isVitestCommand reports whether arg runs vitest, as VitestDefinition.BuildCommand
+// recognizes it
+func isVitestCommand(arg string) bool {
+	return arg == "vitest" ||
+		strings.HasSuffix(arg, "/vitest") ||
+		strings.Contains(arg, "vitest@") ||
+		strings.Contains(arg, "vitest/dist/cli")
+}
+
+// preventWatchMode keeps the test command from watching for changes, which would hang
+// the run until it's killed: it drops the watch flags, and runs nodemon's command
+// without nodemon, with a warning, or refuses them with --strict-args. The warnings go
+// to stderr, which --output json and a Reporter leave to the user.
+func (o *Orchestrator) preventWatchMode() error {
+	runnerDef, err := o.selectRunner()
+	if err == nil {
+		if _, ok := nativeDefinition(runnerDef).(*definitions.ExecDefinition); ok {
+			// A plain command's --watch is its own, likely not a watch mode at all
+			return nil
+		}
+	}
+	if unwrapped, change, ok := unwrapWatcher(o.command); ok {
+		if o.strictArgs {
+			return fmt.Errorf("watch mode is not supported with --strict-args: %s would watch for changes and never exit", change.arg)
+		}
+		if unwrapped == nil {
+			return fmt.Errorf("watch mode is not supported: %s would watch for changes and never exit. Please run the test command without it", change.arg)
+		}
+		fmt.Fprintf(os.Stderr, "Warning: %s\n", change)
+		o.command = unwrapped
+		runnerDef, err = o.selectRunner()
+	}
+	if err != nil {
+		// Reported once the run has started
+		return nil
+	}
+	command, changes := stopWatchMode(runnerDef.GetAdapterFileName(), o.command)
+	if len(changes) == 0 {
+		return nil
+	}
+	if o.strictArgs {
+		return fmt.Errorf("watch mode is not supported with --strict-args: %s would watch for changes and never exit", changes[0].arg)
+	}
+	for _, change := range changes {
+		fmt.Fprintf(os.Stderr, "Warning: %s\n", change)
+	}
+	o.command = command
+	return nil
+}
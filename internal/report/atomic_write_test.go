@@ -0,0 +1,67 @@
+package report
+
+import (
+	"os"
+	"path/filepath"
+	"runtime"
+	"strings"
+	"sync"
+	"testing"
+)
+
+func TestWriteFileAtomic_ReadersNeverSeeTornFiles(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "test-run.md")
+
+	// Versions of different sizes, so a torn file would be a prefix of one of them
+	versions := make(map[string]bool)
+	var contents []string
+	for i := 1; i <= 20; i++ {
+		content := strings.Repeat(string(rune('a'+i)), i*4096) + "\n"
+		contents = append(contents, content)
+		versions[content] = true
+	}
+	if err := writeFileAtomic(path, []byte(contents[0])); err != nil {
+		t.Fatalf("writeFileAtomic failed: %v", err)
+	}
+
+	done := make(chan struct{})
+	var wg sync.WaitGroup
+	wg.Add(1)
+	go func() {
+		defer wg.Done()
+		for {
+			select {
+			case <-done:
+				return
+			default:
+			}
+			data, err := os.ReadFile(path)
+			if err != nil {
+				continue // Windows may refuse the read during the rename
+			}
+			if !versions[string(data)] {
+				t.Errorf("Read a torn file of %d bytes", len(data))
+				return
+			}
+		}
+	}()
+
+	for round := 0; round < 50; round++ {
+		for _, content := range contents {
+			if err := writeFileAtomic(path, []byte(content)); err != nil {
+				t.Errorf("writeFileAtomic failed: %v", err)
+			}
+		}
+	}
+	close(done)
+	wg.Wait()
+
+	// Only the report is left behind
+	entries, _ := os.ReadDir(filepath.Dir(path))
+	if len(entries) != 1 {
+		t.Errorf("Expected only test-run.md in the directory, got %d entries", len(entries))
+	}
+	if info, err := os.Stat(path); err != nil || runtime.GOOS != "windows" && info.Mode().Perm() != 0644 {
+		t.Errorf("Expected the report to keep mode 0644, got %v (%v)", info, err)
+	}
+}
@@ -0,0 +1,10 @@
+//go:build !windows
+
+package report
+
+import "os"
+
+// replaceFile renames src over dst, which is atomic on POSIX filesystems
+func replaceFile(src, dst string) error {
+	return os.Rename(src, dst)
+}
@@ -0,0 +1,42 @@
+//go:build windows
+
+package report
+
+import (
+	"errors"
+	"os"
+	"time"
+
+	"golang.org/x/sys/windows"
+)
+
+const (
+	// replaceAttempts is how often a rename over a report is tried while readers
+	// hold the report open
+	replaceAttempts = 50
+
+	// replaceRetryDelay is the wait between those attempts
+	replaceRetryDelay = 10 * time.Millisecond
+)
+
+// replaceFile renames src over dst. os.Rename uses MoveFileEx with
+// MOVEFILE_REPLACE_EXISTING, which replaces dst atomically but fails while another
+// process has dst open without FILE_SHARE_DELETE, as most readers do. Those reads are
+// short, so the rename is retried for a while.
+func replaceFile(src, dst string) error {
+	var err error
+	for attempt := 0; attempt < replaceAttempts; attempt++ {
+		if err = os.Rename(src, dst); err == nil || !isSharingError(err) {
+			return err
+		}
+		time.Sleep(replaceRetryDelay)
+	}
+	return err
+}
+
+// isSharingError reports whether a rename failed because dst is open elsewhere
+func isSharingError(err error) bool {
+	return errors.Is(err, windows.ERROR_SHARING_VIOLATION) ||
+		errors.Is(err, windows.ERROR_ACCESS_DENIED) ||
+		errors.Is(err, windows.ERROR_LOCK_VIOLATION)
+}
@@ -0,0 +1,60 @@
+package report
+
+import (
+	"regexp"
+	"strings"
+
+	"github.com/zk/3pio/internal/ipc"
+)
+
+// goCompileErrorRegex matches a Go compiler error, e.g. "./api.go:12:5: undefined: x"
+var goCompileErrorRegex = regexp.MustCompile(`\.go:\d+:\d+: `)
+
+// testFailureKind returns the failure kind of a test's error: the one its parser
+// reported, or else what the error type and values tell
+func testFailureKind(err *ipc.TestError) string {
+	if err.FailureKind != "" {
+		return err.FailureKind
+	}
+	switch {
+	case err.Phase != "":
+		// A fixture failed around the test, not the test
+		return ipc.FailureKindHarnessError
+	case err.ErrorType == ipc.ErrorTypeCrashed:
+		return ipc.FailureKindCrashed
+	case err.ErrorType == ipc.ErrorTypeTimedOut:
+		return ipc.FailureKindTimedOut
+	case err.Expected != "" || err.Actual != "" || strings.Contains(strings.ToLower(err.ErrorType), "assert"):
+		// e.g. AssertionError (pytest, mocha), assert_eq (Rust)
+		return ipc.FailureKindAssertionFailed
+	}
+	return ipc.FailureKindUnknown
+}
+
+// groupFailureKind returns the failure kind of a group error: a data race outside the
+// tests, the tests didn't compile or load, the runner gave up waiting for them, or the
+// framework failed around them
+func groupFailureKind(errorType, message string) string {
+	switch {
+	case errorType == "DATA_RACE":
+		return ipc.FailureKindDataRace
+	case errorType == ipc.ErrorTypeTimedOut:
+		// e.g. the headless browser of wasm-pack test didn't finish the tests
+		return ipc.FailureKindTimedOut
+	case errorType == "COMPILATION_FAILURE", errorType == "COLLECTION_ERROR", errorType == "SyntaxError", goCompileErrorRegex.MatchString(message):
+		return ipc.FailureKindBuildError
+	}
+	return ipc.FailureKindHarnessError
+}
+
+// FailureKind returns why the test failed, as an ipc.FailureKind* constant, or "" if
+// it didn't fail
+func (tc *TestCase) FailureKind() string {
+	if tc.Status != TestStatusFail && tc.Status != TestStatusQuarantinedFail && tc.Status != TestStatusError {
+		return ""
+	}
+	if tc.Error == nil || tc.Error.FailureKind == "" {
+		return ipc.FailureKindUnknown
+	}
+	return tc.Error.FailureKind
+}
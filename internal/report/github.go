@@ -0,0 +1,208 @@
+package report
+
+import (
+	"fmt"
+	"os"
+	"strconv"
+	"strings"
+)
+
+// GitHubStepSummaryEnv names the file GitHub Actions renders as the job summary
+const GitHubStepSummaryEnv = "GITHUB_STEP_SUMMARY"
+
+// maxGitHubAnnotations caps the ::error commands of a run, GitHub shows no more than
+// 10 error annotations per step
+const maxGitHubAnnotations = 10
+
+// GitHubAnnotations returns an ::error workflow command for each failed test whose
+// failure location, or else definition, is known, at most maxGitHubAnnotations of
+// them, and how many failures with a location were left out
+func GitHubAnnotations(rootGroups []*TestGroup) (commands []string, omitted int) {
+	var walk func(group *TestGroup, path []string)
+	walk = func(group *TestGroup, path []string) {
+		for _, tc := range group.TestCases {
+			if tc.Status != TestStatusFail && tc.Status != TestStatusError {
+				continue
+			}
+			command, ok := formatGitHubAnnotation(append(append([]string(nil), path...), tc.Name), &tc)
+			if !ok {
+				continue
+			}
+			if len(commands) == maxGitHubAnnotations {
+				omitted++
+				continue
+			}
+			commands = append(commands, command)
+		}
+
+		subgroups := make([]*TestGroup, 0, len(group.Subgroups))
+		for _, subgroup := range group.Subgroups {
+			subgroups = append(subgroups, subgroup)
+		}
+		for _, subgroup := range sortedGroups(subgroups) {
+			walk(subgroup, append(append([]string(nil), path...), subgroup.Name))
+		}
+	}
+	// Root groups are files or crates, which the annotation's file= already points into
+	for _, group := range sortedGroups(rootGroups) {
+		walk(group, nil)
+	}
+	return commands, omitted
+}
+
+// formatGitHubAnnotation formats the ::error command of a failed test, at the line
+// that failed or, when the runner didn't say, the test's definition. It returns false
+// when there is no location to annotate.
+func formatGitHubAnnotation(path []string, tc *TestCase) (string, bool) {
+	testErr := tc.Error
+	if testErr == nil {
+		testErr = &TestError{}
+	}
+	// Files outside the repository can't be annotated
+	if testErr.External {
+		return "", false
+	}
+	file, line, col := testErr.File, testErr.Line, testErr.Column
+	if file == "" || line <= 0 {
+		var ok bool
+		if file, line, col, ok = parseLocation(testErr.Location); !ok {
+			if tc.File == "" || tc.Line <= 0 {
+				return "", false
+			}
+			file, line, col = tc.File, tc.Line, tc.Column
+		}
+	}
+
+	properties := []string{"file=" + escapeGitHubProperty(file), "line=" + strconv.Itoa(line)}
+	if col > 0 {
+		properties = append(properties, "col="+strconv.Itoa(col))
+	}
+	properties = append(properties, "title="+escapeGitHubProperty(strings.Join(path, " > ")))
+
+	message := strings.TrimSpace(testErr.Message)
+	if diff := testErr.AssertionDiff(); diff != "" {
+		message = strings.TrimSpace(message + "\n" + diff)
+	}
+	if message == "" {
+		message = "Test failed"
+	}
+	return fmt.Sprintf("::error %s::%s", strings.Join(properties, ","), escapeGitHubData(message)), true
+}
+
+// parseLocation splits a "file:line" or "file:line:col" location, e.g. "src/lib.rs:10:5".
+// It splits from the right so Windows drive letters stay part of the file.
+func parseLocation(location string) (file string, line, col int, ok bool) {
+	rest, last, found := cutLast(strings.TrimSpace(location), ":")
+	if !found {
+		return "", 0, 0, false
+	}
+	lastNum, err := strconv.Atoi(last)
+	if err != nil || lastNum <= 0 {
+		return "", 0, 0, false
+	}
+	if name, lineText, found := cutLast(rest, ":"); found {
+		if lineNum, err := strconv.Atoi(lineText); err == nil && lineNum > 0 && name != "" {
+			return name, lineNum, lastNum, true
+		}
+	}
+	if rest == "" {
+		return "", 0, 0, false
+	}
+	return rest, lastNum, 0, true
+}
+
+// cutLast slices s around the last instance of sep
+func cutLast(s, sep string) (before, after string, found bool) {
+	if i := strings.LastIndex(s, sep); i >= 0 {
+		return s[:i], s[i+len(sep):], true
+	}
+	return s, "", false
+}
+
+// escapeGitHubData escapes the message of a workflow command
+func escapeGitHubData(s string) string {
+	return strings.NewReplacer("%", "%25", "\r", "%0D", "\n", "%0A").Replace(s)
+}
+
+// escapeGitHubProperty escapes a property value of a workflow command
+func escapeGitHubProperty(s string) string {
+	return strings.NewReplacer("%", "%25", "\r", "%0D", "\n", "%0A", ":", "%3A", ",", "%2C").Replace(s)
+}
+
+// FormatGitHubStepSummary renders a compact markdown summary of the run for the job
+// summary: the counts, the duration and the failed tests. omitted is the number of
+// failures that got no annotation because of GitHub's limit.
+func FormatGitHubStepSummary(summary *RunSummary, omitted int) string {
+	var sb strings.Builder
+	fmt.Fprintf(&sb, "### 3pio: `%s`\n\n", summary.Command)
+
+	result := "Passed"
+	switch {
+	case summary.Status != "" && summary.Status != "COMPLETED":
+		result = strings.ToLower(summary.Status)
+		result = strings.ToUpper(result[:1]) + result[1:]
+	case summary.Totals.Failed > 0 || summary.Totals.Errors > 0 || summary.ExitCode != 0:
+		result = "Failed"
+	}
+	counts := []string{fmt.Sprintf("%d passed", summary.Totals.Passed)}
+	if summary.Totals.Failed > 0 {
+		counts = append(counts, fmt.Sprintf("%d failed", summary.Totals.Failed))
+	}
+	if summary.Totals.Errors > 0 {
+		counts = append(counts, fmt.Sprintf("%d errored", summary.Totals.Errors))
+	}
+	if summary.Totals.Skipped > 0 {
+		counts = append(counts, fmt.Sprintf("%d skipped", summary.Totals.Skipped))
+	}
+	counts = append(counts, fmt.Sprintf("%d total", summary.Totals.Total))
+	fmt.Fprintf(&sb, "**%s**: %s", result, strings.Join(counts, ", "))
+	if !summary.StartTime.IsZero() && summary.EndTime.After(summary.StartTime) {
+		fmt.Fprintf(&sb, " in %.1fs", summary.EndTime.Sub(summary.StartTime).Seconds())
+	}
+	sb.WriteString("\n\n")
+
+	var failures []string
+	for _, group := range summary.Groups {
+		for _, tc := range group.TestCases {
+			if tc.Status != string(TestStatusFail) && tc.Status != string(TestStatusError) {
+				continue
+			}
+			name := BuildHierarchicalPathFromSlice(append(append([]string(nil), group.Path...), tc.Name))
+			failures = append(failures, fmt.Sprintf("| `%s` | %s |", escapeTableCell(name),
+				escapeTableCell(truncateExcerpt(firstLine(tc.FailureMessage)))))
+		}
+	}
+	if len(failures) > 0 {
+		sb.WriteString("| Failed test | Failure |\n|-------------|---------|\n")
+		for i, failure := range failures {
+			if i == maxFailureDigest {
+				fmt.Fprintf(&sb, "\n…and %d more\n", len(failures)-maxFailureDigest)
+				break
+			}
+			sb.WriteString(failure + "\n")
+		}
+		sb.WriteString("\n")
+	}
+	if omitted > 0 {
+		fmt.Fprintf(&sb, "%d more failures weren't annotated, GitHub shows at most %d per step.\n\n", omitted, maxGitHubAnnotations)
+	}
+	return sb.String()
+}
+
+// escapeTableCell keeps a value from breaking out of its markdown table cell
+func escapeTableCell(s string) string {
+	return strings.ReplaceAll(s, "|", "\\|")
+}
+
+// AppendGitHubStepSummary appends the run's summary to the job summary file
+func AppendGitHubStepSummary(path string, summary *RunSummary, omitted int) error {
+	file, err := os.OpenFile(path, os.O_APPEND|os.O_CREATE|os.O_WRONLY, 0644)
+	if err != nil {
+		return err
+	}
+	if _, err := file.WriteString(FormatGitHubStepSummary(summary, omitted)); err != nil {
+		_ = file.Close()
+		return err
+	}
+	return file.Close()
+}
@@ -0,0 +1,140 @@
+package report
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+	"time"
+)
+
+func TestGitHubAnnotations(t *testing.T) {
+	tests := &TestGroup{
+		Name: "tests",
+		TestCases: []TestCase{
+			{Name: "test_add", Status: TestStatusPass},
+			{Name: "test_sub", Status: TestStatusFail, Error: &TestError{
+				Message:  "assertion `left == right` failed",
+				Expected: "2",
+				Actual:   "1",
+				Location: "src/lib.rs:10:5",
+			}},
+			{Name: "test_no_location", Status: TestStatusFail, Error: &TestError{Message: "boom"}},
+			{Name: "test_no_error", Status: TestStatusFail},
+			{Name: "test_line_only", Status: TestStatusFail, Error: &TestError{
+				Message:  "50% done, then: failed",
+				Location: `C:\work\src\lib.rs:42`,
+			}},
+			{Name: "test_defined", Status: TestStatusFail, File: "tests/math.test.js", Line: 12, Column: 3, Error: &TestError{Message: "boom"}},
+		},
+	}
+	root := &TestGroup{Name: "my-crate", Subgroups: map[string]*TestGroup{"tests": tests}}
+
+	commands, omitted := GitHubAnnotations([]*TestGroup{root})
+	want := []string{
+		"::error file=src/lib.rs,line=10,col=5,title=tests > test_sub::assertion `left == right` failed%0A- expected: 2%0A+ actual:   1",
+		"::error file=C%3A\\work\\src\\lib.rs,line=42,title=tests > test_line_only::50%25 done, then: failed",
+		"::error file=tests/math.test.js,line=12,col=3,title=tests > test_defined::boom",
+	}
+	if omitted != 0 || len(commands) != len(want) {
+		t.Fatalf("Expected %d annotations and none omitted, got %d omitted:\n%s", len(want), omitted, strings.Join(commands, "\n"))
+	}
+	for i := range want {
+		if commands[i] != want[i] {
+			t.Errorf("Expected annotation\n%s\ngot\n%s", want[i], commands[i])
+		}
+	}
+}
+
+func TestGitHubAnnotations_Capped(t *testing.T) {
+	group := &TestGroup{Name: "math.test.js"}
+	for i := 0; i < 15; i++ {
+		group.TestCases = append(group.TestCases, TestCase{
+			Name:   fmt.Sprintf("test %02d", i),
+			Status: TestStatusFail,
+			Error:  &TestError{Message: "failed", Location: fmt.Sprintf("math.test.js:%d", i+1)},
+		})
+	}
+
+	commands, omitted := GitHubAnnotations([]*TestGroup{group})
+	if len(commands) != maxGitHubAnnotations || omitted != 5 {
+		t.Errorf("Expected %d annotations and 5 omitted, got %d and %d", maxGitHubAnnotations, len(commands), omitted)
+	}
+}
+
+func TestParseLocation(t *testing.T) {
+	testCases := []struct {
+		location  string
+		file      string
+		line, col int
+		ok        bool
+	}{
+		{"src/lib.rs:10:5", "src/lib.rs", 10, 5, true},
+		{"math.test.js:42", "math.test.js", 42, 0, true},
+		{`C:\src\lib.rs:10:5`, `C:\src\lib.rs`, 10, 5, true},
+		{"src/lib.rs", "", 0, 0, false},
+		{"", "", 0, 0, false},
+		{":10", "", 0, 0, false},
+		{"src/lib.rs:abc", "", 0, 0, false},
+	}
+	for _, tc := range testCases {
+		file, line, col, ok := parseLocation(tc.location)
+		if file != tc.file || line != tc.line || col != tc.col || ok != tc.ok {
+			t.Errorf("parseLocation(%q) = %q, %d, %d, %v; expected %q, %d, %d, %v",
+				tc.location, file, line, col, ok, tc.file, tc.line, tc.col, tc.ok)
+		}
+	}
+}
+
+func TestFormatGitHubStepSummary(t *testing.T) {
+	start := time.Date(2026, 10, 14, 10, 0, 0, 0, time.UTC)
+	summary := &RunSummary{
+		Command:   "cargo test",
+		Status:    "COMPLETED",
+		ExitCode:  101,
+		StartTime: start,
+		EndTime:   start.Add(3200 * time.Millisecond),
+		Totals:    SummaryCounts{Total: 3, Passed: 1, Failed: 2},
+		Groups: []SummaryGroup{{
+			Path: []string{"my-crate", "tests"},
+			TestCases: []SummaryTestCase{
+				{Name: "test_add", Status: "PASS"},
+				{Name: "test_sub", Status: "FAIL", FailureMessage: "assertion `left == right` failed\n  left: 1"},
+				{Name: "test_pipe", Status: "FAIL", FailureMessage: "a | b"},
+			},
+		}},
+	}
+
+	got := FormatGitHubStepSummary(summary, 3)
+	for _, want := range []string{
+		"### 3pio: `cargo test`\n\n**Failed**: 1 passed, 2 failed, 3 total in 3.2s\n\n",
+		"| `my-crate → tests → test_sub` | assertion `left == right` failed |\n",
+		"| `my-crate → tests → test_pipe` | a \\| b |\n",
+		"3 more failures weren't annotated, GitHub shows at most 10 per step.",
+	} {
+		if !strings.Contains(got, want) {
+			t.Errorf("Expected step summary to contain %q, got:\n%s", want, got)
+		}
+	}
+
+	summary.Totals = SummaryCounts{Total: 1, Passed: 1}
+	summary.Groups[0].TestCases = summary.Groups[0].TestCases[:1]
+	summary.ExitCode = 0
+	if got := FormatGitHubStepSummary(summary, 0); strings.Contains(got, "| Failed test |") || !strings.Contains(got, "**Passed**: 1 passed, 1 total") {
+		t.Errorf("Expected a passing summary without failures, got:\n%s", got)
+	}
+
+	// Appending keeps what earlier steps wrote
+	path := filepath.Join(t.TempDir(), "step_summary.md")
+	if err := os.WriteFile(path, []byte("earlier step\n"), 0644); err != nil {
+		t.Fatalf("Failed to write step summary: %v", err)
+	}
+	if err := AppendGitHubStepSummary(path, summary, 0); err != nil {
+		t.Fatalf("AppendGitHubStepSummary failed: %v", err)
+	}
+	content, _ := os.ReadFile(path)
+	if !strings.HasPrefix(string(content), "earlier step\n### 3pio:") {
+		t.Errorf("Expected the summary appended, got:\n%s", content)
+	}
+}
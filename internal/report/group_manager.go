@@ -5,10 +5,14 @@ import (
 	"fmt"
 	"os"
 	"path/filepath"
+	"slices"
+	"sort"
+	"strconv"
 	"strings"
 	"sync"
 	"time"
 
+	"github.com/zk/3pio/internal/console"
 	"github.com/zk/3pio/internal/ipc"
 )
 
@@ -32,8 +36,52 @@ type GroupManager struct {
 	pendingUpdates map[string]time.Time // Group ID -> last update time
 	updateTimer    *time.Timer
 	updateMutex    sync.Mutex
+
+	// Report files on disk, so unchanged groups aren't rewritten
+	writeMutex   sync.Mutex           // Serializes report file writes
+	reportedAt   map[string]time.Time // Group ID -> group's Updated time when its report was written
+	bytesWritten int64                // Bytes written to group reports so far
+
+	// Group reports list their tests slowest first once one takes longer than this
+	slowThreshold time.Duration
+
+	// Directory failure locations are resolved against for source snippets; empty
+	// disables them
+	sourceRoot string
+
+	// Known-flaky tests whose failures are recorded as QUARANTINED_FAIL, nil for none
+	quarantine *Quarantine
+
+	// Runner kind and working directory that test IDs are computed with
+	runnerKind string
+	testIDDir  string
+
+	// Directory relative group names are resolved against; empty for the working directory
+	workDir string
+
+	// Replaces secret values in reports and logs (--redact), nil to keep them
+	redactor *Redactor
+
+	// Test case ID -> console entries the test logged before its result arrived
+	pendingConsole map[string]string
+
+	// Directory and log file names handed out so far: scope (a group's subgroups or
+	// its logs) -> lowercased name -> who holds it. Lowercased because macOS and
+	// Windows file systems ignore case.
+	claimedNames map[string]map[string]*nameClaim
+}
+
+// nameClaim is the holder of a directory or log file name within a scope
+type nameClaim struct {
+	original  string // Name of the group or test holding it
+	sanitized string // The original's sanitized form
+	hashed    bool   // The name is the sanitized form with a hash of the original
+	shared    bool   // Other originals sanitize alike, so each of them holds a hashed name
 }
 
+// DefaultSlowThreshold is the --slow-threshold used when none is given
+const DefaultSlowThreshold = time.Second
+
 // NewGroupManager creates a new GroupManager instance
 func NewGroupManager(runDir string, ipcPath string, logger Logger) *GroupManager {
 	return &GroupManager{
@@ -43,9 +91,70 @@ func NewGroupManager(runDir string, ipcPath string, logger Logger) *GroupManager
 		ipcPath:        ipcPath,
 		logger:         logger,
 		pendingUpdates: make(map[string]time.Time),
+		reportedAt:     make(map[string]time.Time),
+		slowThreshold:  DefaultSlowThreshold,
+		testIDDir:      testIDDir(""),
+		claimedNames:   make(map[string]map[string]*nameClaim),
+		pendingConsole: make(map[string]string),
 	}
 }
 
+// SetRunnerKind sets the runner kind hashed into test IDs (see RunnerKind)
+func (gm *GroupManager) SetRunnerKind(kind string) {
+	gm.mu.Lock()
+	defer gm.mu.Unlock()
+	gm.runnerKind = kind
+}
+
+// SetWorkDir sets the directory the tests run in, when it isn't 3pio's working
+// directory: relative group names are resolved against it, and test IDs and displayed
+// paths are relative to it. Called before any event arrives.
+func (gm *GroupManager) SetWorkDir(dir string) {
+	gm.mu.Lock()
+	defer gm.mu.Unlock()
+	gm.workDir = dir
+	gm.testIDDir = testIDDir(dir)
+}
+
+// testID returns the test ID of a test, given its normalized parent names
+func (gm *GroupManager) testID(parentNames []string, testName string) string {
+	return TestID(gm.runnerKind, testIDPath(parentNames, gm.testIDDir), testName)
+}
+
+// TestID returns the test ID of a test as reported in a testCase event
+func (gm *GroupManager) TestID(parentNames []string, testName string) string {
+	gm.mu.RLock()
+	defer gm.mu.RUnlock()
+	normalized := make([]string, len(parentNames))
+	for i, name := range parentNames {
+		normalized[i] = gm.normalizeToAbsolutePath(name)
+	}
+	return gm.testID(normalized, testName)
+}
+
+// SetQuarantine sets the known-flaky tests whose failures don't fail the run
+func (gm *GroupManager) SetQuarantine(quarantine *Quarantine) {
+	gm.mu.Lock()
+	defer gm.mu.Unlock()
+	gm.quarantine = quarantine
+}
+
+// SetRedactor sets the redactor applied to group reports and the captured output
+// written to log files
+func (gm *GroupManager) SetRedactor(redactor *Redactor) {
+	gm.mu.Lock()
+	defer gm.mu.Unlock()
+	gm.redactor = redactor
+}
+
+// SetSlowThreshold sets the test duration above which a group report sorts its tests
+// by duration
+func (gm *GroupManager) SetSlowThreshold(threshold time.Duration) {
+	gm.mu.Lock()
+	defer gm.mu.Unlock()
+	gm.slowThreshold = threshold
+}
+
 // logDebug logs a debug message if logger is available
 func (gm *GroupManager) logDebug(format string, args ...interface{}) {
 	if gm.logger != nil {
@@ -75,7 +184,7 @@ func (gm *GroupManager) normalizeToAbsolutePath(name string) string {
 	}
 
 	// Convert to absolute path
-	absPath, err := filepath.Abs(name)
+	absPath, err := absPathIn(gm.workDir, name)
 	if err != nil {
 		// If we can't get absolute path, return original
 		return name
@@ -95,8 +204,24 @@ func (gm *GroupManager) normalizeToAbsolutePath(name string) string {
 	return absPath
 }
 
+// absPathIn makes a path absolute, resolving a relative one against dir, or the
+// working directory when dir is empty
+func absPathIn(dir, path string) (string, error) {
+	if dir == "" || filepath.IsAbs(path) {
+		return filepath.Abs(path)
+	}
+	return filepath.Join(dir, path), nil
+}
+
+// workDirOr returns dir, or the working directory when dir is empty
+func workDirOr(dir string) (string, error) {
+	if dir != "" {
+		return dir, nil
+	}
+	return os.Getwd()
+}
+
 // makeRelativePath converts absolute paths to relative for display purposes only
-// nolint:unused // relative path helper retained for future path normalization
 func (gm *GroupManager) makeRelativePath(name string) string {
 	// Only convert if it looks like an absolute file path
 	if !strings.HasPrefix(name, "/") && !strings.HasPrefix(name, "./") {
@@ -104,8 +229,8 @@ func (gm *GroupManager) makeRelativePath(name string) string {
 		return name
 	}
 
-	// Try to make relative to current working directory
-	if cwd, err := os.Getwd(); err == nil {
+	// Try to make relative to the directory the tests run in
+	if cwd, err := workDirOr(gm.workDir); err == nil {
 		if relPath, err := filepath.Rel(cwd, name); err == nil {
 			// Ensure relative paths start with ./
 			if !strings.HasPrefix(relPath, ".") && !strings.HasPrefix(relPath, "/") {
@@ -143,15 +268,18 @@ func (gm *GroupManager) ProcessGroupDiscovered(event ipc.GroupDiscoveredEvent) e
 
 	// Create the new group
 	group := &TestGroup{
-		ID:          groupID,
-		Name:        groupName,
-		ParentNames: parentNames,
-		Depth:       len(parentNames),
-		Status:      TestStatusPending,
-		Created:     time.Now(),
-		Updated:     time.Now(),
-		Subgroups:   make(map[string]*TestGroup),
-		TestCases:   make([]TestCase, 0),
+		ID:            groupID,
+		Name:          groupName,
+		ParentNames:   parentNames,
+		Depth:         len(parentNames),
+		TargetKind:    payload.TargetKind,
+		TargetName:    payload.TargetName,
+		CustomHarness: payload.CustomHarness,
+		Status:        TestStatusPending,
+		Created:       time.Now(),
+		Updated:       time.Now(),
+		Subgroups:     make(map[string]*TestGroup),
+		TestCases:     make([]TestCase, 0),
 	}
 
 	// Store in groups map
@@ -167,6 +295,7 @@ func (gm *GroupManager) ProcessGroupDiscovered(event ipc.GroupDiscoveredEvent) e
 			gm.logDebug("Failed to ensure parent hierarchy: %v", err)
 		}
 	}
+	gm.assignDirNames(group)
 
 	gm.logInfo("Discovered group: %s (ID: %s)",
 		BuildHierarchicalPath(group), groupID)
@@ -259,11 +388,22 @@ func (gm *GroupManager) ProcessGroupResult(event ipc.GroupResultEvent) error {
 	case "PASS":
 		group.Status = TestStatusPass
 	case "FAIL":
+		// A group error, e.g. a Go package that didn't build, says more than its result
+		if group.Status == TestStatusError {
+			break
+		}
 		group.Status = TestStatusFail
+		// The runner counts quarantined and flaky failures as failures
+		if onlyToleratedFailures(group) {
+			group.Status = TestStatusPass
+		}
 	case "SKIP":
 		group.Status = TestStatusSkip
+		group.SkipReason = payload.SkipReason
 	case "NO_TESTS":
 		group.Status = TestStatusNoTests
+	case "WARN":
+		group.Status = TestStatusWarn
 	default:
 		group.Status = TestStatusPending
 	}
@@ -281,7 +421,8 @@ func (gm *GroupManager) ProcessGroupResult(event ipc.GroupResultEvent) error {
 	if payload.Totals.Total > 0 || payload.Totals.Passed > 0 ||
 		payload.Totals.Failed > 0 || payload.Totals.Skipped > 0 {
 		group.Stats.PassedTests = payload.Totals.Passed
-		group.Stats.FailedTests = payload.Totals.Failed
+		// The runner's count includes the quarantined failures counted apart
+		group.Stats.FailedTests = max(payload.Totals.Failed-group.Stats.QuarantinedFailedTests, 0)
 		group.Stats.SkippedTests = payload.Totals.Skipped
 		group.Stats.TotalTests = payload.Totals.Total
 		if group.Stats.TotalTests == 0 {
@@ -342,8 +483,9 @@ func (gm *GroupManager) ProcessGroupError(event ipc.GroupErrorEvent) error {
 	// Store error information
 	if payload.Error != nil {
 		group.ErrorInfo = &TestError{
-			Message: payload.Error.Message,
-			Type:    payload.ErrorType,
+			Message:     payload.Error.Message,
+			Type:        payload.ErrorType,
+			FailureKind: groupFailureKind(payload.ErrorType, payload.Error.Message),
 		}
 	}
 
@@ -379,7 +521,10 @@ func (gm *GroupManager) ProcessTestCase(event ipc.GroupTestCaseEvent) error {
 		parentNames[i] = gm.normalizeToAbsolutePath(name)
 	}
 
-	// The test's parent is the full parent hierarchy
+	// The test's parent is the full parent hierarchy, and the base test for a parameter
+	// set. The test ID stays that of the reported hierarchy.
+	idParentNames := parentNames
+	parentNames = paramGroupPath(parentNames, payload)
 	parentID := GenerateGroupIDFromPath(parentNames)
 
 	// Find or create the parent group
@@ -406,10 +551,15 @@ func (gm *GroupManager) ProcessTestCase(event ipc.GroupTestCaseEvent) error {
 	// Create the test case
 	testCase := TestCase{
 		ID:        GenerateTestCaseID(payload.TestName, parentNames),
+		TestID:    gm.testID(idParentNames, payload.TestName),
 		GroupID:   parentID,
 		Name:      payload.TestName,
 		StartTime: time.Now(),
 	}
+	if payload.BaseName != "" {
+		parentGroup.Parametrized = true
+		testCase.CaseName = paramCaseName(payload)
+	}
 
 	// Set status
 	switch payload.Status {
@@ -423,14 +573,30 @@ func (gm *GroupManager) ProcessTestCase(event ipc.GroupTestCaseEvent) error {
 		testCase.Status = TestStatusXFail
 	case "XPASS":
 		testCase.Status = TestStatusXPass
+	case "BENCH":
+		testCase.Status = TestStatusBench
+	case "FLAKY":
+		testCase.Status = TestStatusFlaky
+	case "ERROR":
+		testCase.Status = TestStatusError
 	default:
 		testCase.Status = TestStatusPending
 	}
 
+	// Quarantined tests keep their result, but a failure doesn't fail the run
+	if gm.quarantine.Match(testCase.TestID, append(append([]string(nil), parentNames...), payload.TestName)) {
+		testCase.Quarantined = true
+		if testCase.Status == TestStatusFail || testCase.Status == TestStatusError {
+			testCase.Status = TestStatusQuarantinedFail
+		}
+	}
+
 	// Set xfail reason if present
 	if payload.XFailReason != "" {
 		testCase.XFailReason = payload.XFailReason
 	}
+	testCase.SkipReason = payload.SkipReason
+	testCase.SkipMessage = payload.SkipMessage
 
 	// Set duration
 	if payload.Duration > 0 {
@@ -438,45 +604,91 @@ func (gm *GroupManager) ProcessTestCase(event ipc.GroupTestCaseEvent) error {
 	}
 	testCase.EndTime = time.Now()
 
-	// Set error if present
+	// Set errors if present
 	if payload.Error != nil {
-		testCase.Error = &TestError{
-			Message:  payload.Error.Message,
-			Stack:    payload.Error.Stack,
-			Expected: payload.Error.Expected,
-			Actual:   payload.Error.Actual,
-			Location: payload.Error.Location,
-			Type:     payload.Error.ErrorType,
-		}
-	}
-
-	// Set output if present
-	testCase.Stdout = payload.Stdout
-	testCase.Stderr = payload.Stderr
-
-	// Check if test case already exists (deduplication)
-	testExists := false
-	for i, existingTest := range parentGroup.TestCases {
-		if existingTest.ID == testCase.ID {
-			// Update existing test case instead of adding duplicate
-			parentGroup.TestCases[i] = testCase
-			testExists = true
-			break
+		testCase.Error = newTestError(payload.Error)
+		if testCase.Status == TestStatusFail || testCase.Status == TestStatusQuarantinedFail || testCase.Status == TestStatusError {
+			testCase.Error.Snippet = gm.sourceSnippet(testCase.Error)
 		}
 	}
+	if payload.TeardownError != nil {
+		testCase.TeardownError = newTestError(payload.TeardownError)
+	}
 
-	// Add to parent group only if it doesn't exist
-	if !testExists {
-		parentGroup.TestCases = append(parentGroup.TestCases, testCase)
+	// Set output if present. The full output goes to the test's log file and only
+	// its tail is kept in memory.
+	testStdout := gm.redactor.Redact(console.PlainText(payload.Stdout))
+	testStderr := gm.redactor.Redact(console.PlainText(payload.Stderr))
+	if testStdout != "" || testStderr != "" {
+		gm.assignLogName(parentGroup, payload.TestName)
+		logPath := GetTestLogFilePath(parentGroup, payload.TestName, gm.runDir)
+		stdoutRef, stderrRef, err := writeTestOutput(logPath, testStdout, testStderr)
+		if err != nil {
+			gm.logError("Failed to write output of %s: %v", payload.TestName, err)
+		} else {
+			testCase.StdoutRef = stdoutRef
+			testCase.StderrRef = stderrRef
+		}
+	}
+	var stdoutCut, stderrCut bool
+	testCase.Stdout, stdoutCut = outputTail(testStdout)
+	testCase.Stderr, stderrCut = outputTail(testStderr)
+	testCase.OutputTruncated = stdoutCut || stderrCut
+
+	// Set source location if present
+	testCase.File = payload.File
+	testCase.Line = payload.Line
+	testCase.Column = payload.Column
+	testCase.ItemPath = payload.ItemPath
+
+	// Set runner annotations if present
+	testCase.Slow = payload.Slow
+	testCase.Leaked = payload.Leaked
+	testCase.PanickedAsExpected = payload.PanickedAsExpected
+
+	// Set benchmark results if present
+	testCase.BenchMedianNs = payload.BenchMedianNs
+	testCase.BenchDeviationNs = payload.BenchDeviationNs
+	testCase.BenchMeanNs = payload.BenchMeanNs
+	testCase.BenchStdDevNs = payload.BenchStdDevNs
+	testCase.BenchChangePercent = payload.BenchChangePercent
+	testCase.BenchRegressed = payload.BenchRegressed
+	testCase.BenchIterations = payload.BenchIterations
+	testCase.BenchNsPerOp = payload.BenchNsPerOp
+	testCase.BenchMem = payload.BenchMem
+	testCase.BenchBytesPerOp = payload.BenchBytesPerOp
+	testCase.BenchAllocsPerOp = payload.BenchAllocsPerOp
+	for _, attempt := range payload.Attempts {
+		stdout, _ := outputTail(console.PlainText(attempt.Stdout))
+		stderr, _ := outputTail(console.PlainText(attempt.Stderr))
+		testCase.Attempts = append(testCase.Attempts, TestAttempt{
+			Status:   TestStatus(attempt.Status),
+			Duration: time.Duration(attempt.Duration) * time.Millisecond,
+			Stdout:   stdout,
+			Stderr:   stderr,
+		})
 	}
-	parentGroup.Updated = time.Now()
 
-	// Update parent group statistics
-	parentGroup.UpdateStats()
+	for _, attachment := range payload.Attachments {
+		testCase.Attachments = append(testCase.Attachments, Attachment{Name: attachment.Name, Path: attachment.Path})
+	}
+
+	// Console entries logged while the test ran, before its result
+	if entries, ok := gm.pendingConsole[testCase.ID]; ok {
+		testCase.Console = entries
+		delete(gm.pendingConsole, testCase.ID)
+	}
+
+	// Add to the parent group, replacing a duplicate, and update its statistics
+	parentGroup.AddTestCase(testCase)
+	parentGroup.Updated = time.Now()
 
 	// Schedule report update
 	gm.scheduleReportUpdate(parentID)
 
+	// Ancestors show rolled-up counts, so refresh them as results arrive
+	gm.rollUpStats(parentGroup)
+
 	gm.logInfo("Test case: %s → %s [%s]",
 		BuildHierarchicalPathFromSlice(payload.ParentNames),
 		payload.TestName, testCase.Status)
@@ -484,6 +696,141 @@ func (gm *GroupManager) ProcessTestCase(event ipc.GroupTestCaseEvent) error {
 	return nil
 }
 
+// newTestError converts the error of a test case event. Runner output is kept as
+// plain text: colors and cursor movement only belong on a terminal, and output.log
+// keeps the raw bytes.
+func newTestError(err *ipc.TestError) *TestError {
+	return &TestError{
+		Message:  console.PlainText(err.Message),
+		Stack:    console.PlainText(err.Stack),
+		Expected: console.PlainText(err.Expected),
+		Actual:   console.PlainText(err.Actual),
+		Location: err.Location,
+		Type:     err.ErrorType,
+
+		FailureKind: testFailureKind(err),
+
+		File:     err.File,
+		Line:     err.Line,
+		Column:   err.Column,
+		External: err.External,
+
+		CustomMessage: err.CustomMessage,
+
+		ExpectedPanic: err.ExpectedPanic,
+		ActualPanic:   err.ActualPanic,
+
+		Backtrace:         console.PlainText(err.Backtrace),
+		DataRace:          err.DataRace,
+		UndefinedBehavior: err.UndefinedBehavior,
+
+		Phase: err.Phase,
+
+		Snapshot: err.Snapshot,
+		Property: err.Property,
+		Trybuild: err.Trybuild,
+	}
+}
+
+// paramGroupPath returns the path of the group a test case goes in, given its
+// normalized parents: a parameter set goes in the group of its base test, which
+// runners that nest it in a module of that name (rstest) already report as its parent
+func paramGroupPath(parentNames []string, payload ipc.TestCasePayload) []string {
+	if payload.BaseName == "" || (len(parentNames) > 0 && parentNames[len(parentNames)-1] == payload.BaseName) {
+		return parentNames
+	}
+	return append(append([]string(nil), parentNames...), payload.BaseName)
+}
+
+// paramCaseName returns the full name of a parameter set, copy-pasteable into the
+// runner's filter. rstest names a case by itself, under the module of its test.
+func paramCaseName(payload ipc.TestCasePayload) string {
+	if payload.TestName == payload.ParamID {
+		return payload.BaseName + "::" + payload.TestName
+	}
+	return payload.TestName
+}
+
+// ProcessRetriedTestCase handles a test case event from a retry of the failed tests
+// (--retries). Only tests that failed so far are updated: the retry is added to their
+// attempts, and a test that passes becomes FLAKY. Other tests the retry ran are ignored.
+func (gm *GroupManager) ProcessRetriedTestCase(event ipc.GroupTestCaseEvent) error {
+	payload := event.Payload
+	parentNames := make([]string, len(payload.ParentNames))
+	for i, name := range payload.ParentNames {
+		parentNames[i] = gm.normalizeToAbsolutePath(name)
+	}
+
+	gm.mu.RLock()
+	var previous *TestCase
+	if group, exists := gm.groups[GenerateGroupIDFromPath(paramGroupPath(parentNames, payload))]; exists {
+		testID := gm.testID(parentNames, payload.TestName)
+		for i := range group.TestCases {
+			if group.TestCases[i].TestID == testID {
+				previous = &group.TestCases[i]
+				break
+			}
+		}
+	}
+	var attempts []ipc.TestAttempt
+	if previous != nil && (previous.Status == TestStatusFail || previous.Status == TestStatusError) {
+		if len(previous.Attempts) == 0 {
+			attempts = append(attempts, ipc.TestAttempt{
+				Status:   string(previous.Status),
+				Duration: float64(previous.Duration.Milliseconds()),
+				Stdout:   previous.Stdout,
+				Stderr:   previous.Stderr,
+			})
+		}
+		for _, attempt := range previous.Attempts {
+			attempts = append(attempts, ipc.TestAttempt{
+				Status:   string(attempt.Status),
+				Duration: float64(attempt.Duration.Milliseconds()),
+				Stdout:   attempt.Stdout,
+				Stderr:   attempt.Stderr,
+			})
+		}
+	}
+	gm.mu.RUnlock()
+	if attempts == nil || (payload.Status != "PASS" && payload.Status != "FAIL" && payload.Status != "ERROR") {
+		return nil
+	}
+
+	payload.Attempts = append(attempts, ipc.TestAttempt{
+		Status:   payload.Status,
+		Duration: payload.Duration,
+		Stdout:   payload.Stdout,
+		Stderr:   payload.Stderr,
+	})
+	if payload.Status == "PASS" {
+		payload.Status = string(TestStatusFlaky)
+	}
+	event.Payload = payload
+	return gm.ProcessTestCase(event)
+}
+
+// ResolveRetriedGroups passes the failed groups whose failed tests all passed when
+// retried, innermost groups first, once a retry of the failed tests is done
+func (gm *GroupManager) ResolveRetriedGroups() {
+	gm.mu.Lock()
+	defer gm.mu.Unlock()
+
+	groups := make([]*TestGroup, 0, len(gm.groups))
+	for _, group := range gm.groups {
+		if group.Status == TestStatusFail {
+			groups = append(groups, group)
+		}
+	}
+	sort.Slice(groups, func(i, j int) bool { return groups[i].Depth > groups[j].Depth })
+	for _, group := range groups {
+		if onlyToleratedFailures(group) {
+			group.Status = TestStatusPass
+			group.Updated = time.Now()
+			gm.scheduleReportUpdate(group.ID)
+		}
+	}
+}
+
 // ProcessStdoutChunk handles stdout output for a group
 func (gm *GroupManager) ProcessStdoutChunk(groupName string, parentNames []string, chunk string) error {
 	gm.mu.Lock()
@@ -503,7 +850,7 @@ func (gm *GroupManager) ProcessStdoutChunk(groupName string, parentNames []strin
 		return nil
 	}
 
-	group.Stdout += chunk
+	gm.appendGroupOutput(group, &group.Stdout, chunk)
 	group.Updated = time.Now()
 
 	// Schedule debounced report update
@@ -531,7 +878,7 @@ func (gm *GroupManager) ProcessStderrChunk(groupName string, parentNames []strin
 		return nil
 	}
 
-	group.Stderr += chunk
+	gm.appendGroupOutput(group, &group.Stderr, chunk)
 	group.Updated = time.Now()
 
 	// Schedule debounced report update
@@ -540,6 +887,74 @@ func (gm *GroupManager) ProcessStderrChunk(groupName string, parentNames []strin
 	return nil
 }
 
+// ProcessConsoleChunk handles a console entry attributed to a test (Jest). It goes in
+// the console output of the test that logged it, or in the setup output of its
+// group when no test did; the group's output.log keeps every entry.
+func (gm *GroupManager) ProcessConsoleChunk(payload ipc.OutputChunkPayload) error {
+	gm.mu.Lock()
+	defer gm.mu.Unlock()
+
+	// Normalize paths to absolute for consistent storage
+	groupPath := make([]string, 0, len(payload.ParentNames)+1)
+	for _, name := range payload.ParentNames {
+		groupPath = append(groupPath, gm.normalizeToAbsolutePath(name))
+	}
+	groupPath = append(groupPath, gm.normalizeToAbsolutePath(payload.GroupName))
+
+	groupID := GenerateGroupIDFromPath(groupPath)
+	group, exists := gm.groups[groupID]
+	if !exists {
+		// Ignore output for unknown groups
+		return nil
+	}
+
+	entry := consoleEntry(payload.Level, gm.redactor.Redact(console.PlainText(payload.Chunk)))
+	if err := appendToFile(GetGroupOutputFilePath(group, gm.runDir), entry); err != nil {
+		gm.logError("Failed to write output of group %s: %v", group.Name, err)
+	}
+
+	if payload.TestName == "" {
+		group.SetupOutput, _ = appendCapturedOutput(group.SetupOutput, entry)
+	} else {
+		id := GenerateTestCaseID(payload.TestName, groupPath)
+		if tc := group.testCase(id); tc != nil {
+			tc.Console, _ = appendCapturedOutput(tc.Console, entry)
+		} else {
+			gm.pendingConsole[id], _ = appendCapturedOutput(gm.pendingConsole[id], entry)
+		}
+	}
+	group.Updated = time.Now()
+
+	// Schedule debounced report update
+	gm.scheduleReportUpdate(groupID)
+
+	return nil
+}
+
+// consoleEntry prefixes each line of a console entry with the level it was logged
+// at, e.g. "[warn] disk almost full"
+func consoleEntry(level, message string) string {
+	lines := strings.Split(strings.TrimSuffix(message, "\n"), "\n")
+	for i, line := range lines {
+		lines[i] = "[" + level + "] " + line
+	}
+	return strings.Join(lines, "\n") + "\n"
+}
+
+// appendGroupOutput appends a chunk of a group's output to its output.log and keeps
+// only the tail in memory, so a chatty suite doesn't grow the heap with its output
+func (gm *GroupManager) appendGroupOutput(group *TestGroup, tail *string, chunk string) {
+	chunk = gm.redactor.Redact(chunk)
+	if err := appendToFile(GetGroupOutputFilePath(group, gm.runDir), chunk); err != nil {
+		gm.logError("Failed to write output of group %s: %v", group.Name, err)
+	}
+	var dropped bool
+	*tail, dropped = appendCapturedOutput(*tail, chunk)
+	if dropped {
+		group.OutputTruncated = true
+	}
+}
+
 // ensureParentHierarchy ensures all parent groups exist for a given group
 func (gm *GroupManager) ensureParentHierarchy(group *TestGroup) error {
 	if len(group.ParentNames) == 0 {
@@ -572,6 +987,7 @@ func (gm *GroupManager) ensureParentHierarchy(group *TestGroup) error {
 			}
 
 			gm.groups[parentID] = parent
+			gm.assignDirNames(parent)
 
 			// Add to root groups if this is a root
 			if len(grandparentNames) == 0 {
@@ -643,12 +1059,169 @@ func (gm *GroupManager) ensureGroupHierarchy(path []string) error {
 					group.ParentID = parentID
 				}
 			}
+			gm.assignDirNames(group)
 		}
 	}
 
 	return nil
 }
 
+// assignDirNames gives a group the directory names of its report, using its parent's
+// and a name no sibling has claimed. Caller must hold gm.mu.
+func (gm *GroupManager) assignDirNames(group *TestGroup) {
+	if len(group.dirNames) > 0 {
+		return
+	}
+
+	var parentDirs []string
+	if len(group.ParentNames) > 0 {
+		if parent, exists := gm.groups[GetParentGroupID(group.ParentNames)]; exists {
+			gm.assignDirNames(parent)
+			parentDirs = parent.dirNames
+		} else {
+			parentDirs = sanitizeHierarchy(group.ParentNames, gm.runDir)
+		}
+	}
+
+	name := sanitizeComponent(group.Name, projectRootOf(gm.runDir))
+	name = gm.claimName("groups:"+GetParentGroupID(group.ParentNames), name, group.Name, func(sibling, name string) {
+		for _, other := range gm.groups {
+			if other != group && other.Name == sibling && slices.Equal(other.ParentNames, group.ParentNames) {
+				gm.moveGroupDir(other, name)
+			}
+		}
+	})
+	group.dirNames = append(append(make([]string, 0, len(parentDirs)+1), parentDirs...), name)
+}
+
+// moveGroupDir renames the directory of a group that already has one, moving its
+// files and those of its subgroups. Caller must hold gm.mu.
+func (gm *GroupManager) moveGroupDir(group *TestGroup, name string) {
+	if len(group.dirNames) == 0 {
+		return
+	}
+	oldPath := GenerateGroupPath(group, gm.runDir)
+	level := len(group.dirNames) - 1
+	moved := []*TestGroup{group}
+	for i := 0; i < len(moved); i++ {
+		if len(moved[i].dirNames) > level {
+			moved[i].dirNames = slices.Clone(moved[i].dirNames)
+			moved[i].dirNames[level] = name
+		}
+		for _, subgroup := range moved[i].Subgroups {
+			moved = append(moved, subgroup)
+		}
+	}
+	newPath := GenerateGroupPath(group, gm.runDir)
+	gm.movePath(oldPath, newPath, moved)
+}
+
+// moveTestLog renames the log file of a test that already has one. Caller must hold gm.mu.
+func (gm *GroupManager) moveTestLog(group *TestGroup, testName, name string) {
+	oldPath := GetTestLogFilePath(group, testName, gm.runDir)
+	group.logNames[testName] = name
+	gm.movePath(oldPath, GetTestLogFilePath(group, testName, gm.runDir), []*TestGroup{group})
+}
+
+// movePath moves a report directory or log file, if it was written yet, and points
+// the output references of the tests of groups at its new path. Caller must hold gm.mu.
+func (gm *GroupManager) movePath(oldPath, newPath string, groups []*TestGroup) {
+	if _, err := os.Stat(oldPath); err == nil {
+		if err := os.MkdirAll(filepath.Dir(newPath), 0755); err != nil {
+			gm.logError("Failed to create directory for %s: %v", newPath, err)
+		} else if err := os.Rename(oldPath, newPath); err != nil {
+			gm.logError("Failed to move %s to %s: %v", oldPath, newPath, err)
+		}
+	}
+
+	for _, group := range groups {
+		for i := range group.TestCases {
+			for _, ref := range []*OutputRef{group.TestCases[i].StdoutRef, group.TestCases[i].StderrRef} {
+				if ref == nil {
+					continue
+				}
+				if ref.Path == oldPath {
+					ref.Path = newPath
+				} else if rest, ok := strings.CutPrefix(ref.Path, oldPath+string(filepath.Separator)); ok {
+					ref.Path = filepath.Join(newPath, rest)
+				}
+			}
+		}
+	}
+}
+
+// assignLogName gives a test of a group a log file name no other test of the group
+// has claimed. Caller must hold gm.mu.
+func (gm *GroupManager) assignLogName(group *TestGroup, testName string) {
+	if group.logNames[testName] != "" {
+		return
+	}
+	if group.logNames == nil {
+		group.logNames = make(map[string]string)
+	}
+	group.logNames[testName] = gm.claimName("logs:"+group.ID, SanitizeGroupName(testName), testName, func(test, name string) {
+		gm.moveTestLog(group, test, name)
+	})
+}
+
+// claimName returns the name an original takes in a scope: its sanitized form, or,
+// when other originals of the scope sanitize alike (ignoring case), the sanitized form
+// with a hash of the original. Every one of the colliding originals gets the hash, so
+// none of their names depends on the order they arrived in; the one that took the
+// plain name before the collision is handed its hashed name through move.
+// Caller must hold gm.mu.
+func (gm *GroupManager) claimName(scope, sanitized, original string, move func(original, name string)) string {
+	claimed := gm.claimedNames[scope]
+	if claimed == nil {
+		claimed = make(map[string]*nameClaim)
+		gm.claimedNames[scope] = claimed
+	}
+
+	key := strings.ToLower(sanitized)
+	claim, taken := claimed[key]
+	switch {
+	case !taken:
+		claimed[key] = &nameClaim{original: original, sanitized: sanitized}
+		return sanitized
+	case claim.hashed || claim.shared:
+		// Held by a hashed name, or by names that all got theirs
+	case claim.original == original:
+		return sanitized
+	default:
+		claim.shared = true
+		move(claim.original, claimHashedName(claimed, claim.sanitized, claim.original))
+	}
+	return claimHashedName(claimed, sanitized, original)
+}
+
+// claimHashedName claims the sanitized form of original with a hash of it appended.
+// attempt rehashes for the (unlikely) case where the hashed name is taken too.
+func claimHashedName(claimed map[string]*nameClaim, sanitized, original string) string {
+	for attempt := 0; ; attempt++ {
+		name := withNameHash(sanitized, original, attempt)
+		key := strings.ToLower(name)
+		if claim, taken := claimed[key]; !taken || claim.hashed && claim.original == original {
+			claimed[key] = &nameClaim{original: original, sanitized: sanitized, hashed: true}
+			return name
+		}
+	}
+}
+
+// rollUpStats refreshes the recursive test counts of every ancestor of group.
+// Caller must hold gm.mu.
+func (gm *GroupManager) rollUpStats(group *TestGroup) {
+	parentID := group.ParentID
+	for parentID != "" {
+		parent, exists := gm.groups[parentID]
+		if !exists {
+			return
+		}
+		parent.refreshRecursiveStats()
+		gm.scheduleReportUpdate(parent.ID)
+		parentID = parent.ParentID
+	}
+}
+
 // propagateCompletion propagates completion status up the hierarchy
 func (gm *GroupManager) propagateCompletion(group *TestGroup) {
 	if group.ParentID == "" {
@@ -735,6 +1308,9 @@ func (gm *GroupManager) flushPendingUpdates() {
 
 // generateGroupReport generates a report file for a group
 func (gm *GroupManager) generateGroupReport(group *TestGroup) error {
+	gm.writeMutex.Lock()
+	defer gm.writeMutex.Unlock()
+
 	reportPath := GetReportFilePath(group, gm.runDir)
 
 	// Ensure directory exists
@@ -744,76 +1320,169 @@ func (gm *GroupManager) generateGroupReport(group *TestGroup) error {
 	}
 
 	// Generate report content
-	content := gm.formatGroupReport(group)
+	content := gm.redactor.Redact(gm.formatGroupReport(group))
 
 	// Write report file
-	if err := os.WriteFile(reportPath, []byte(content), 0644); err != nil {
+	if err := writeFileAtomic(reportPath, []byte(content)); err != nil {
 		return fmt.Errorf("failed to write report file: %w", err)
 	}
 
+	gm.reportedAt[group.ID] = group.Updated
+	gm.bytesWritten += int64(len(content))
 	return nil
 }
 
+// reportIsCurrent reports whether the group's report was written after its last change
+func (gm *GroupManager) reportIsCurrent(group *TestGroup) bool {
+	gm.writeMutex.Lock()
+	defer gm.writeMutex.Unlock()
+
+	reportedAt, written := gm.reportedAt[group.ID]
+	return written && !group.Updated.After(reportedAt)
+}
+
+// BytesWritten returns how many bytes of group reports have been written so far
+func (gm *GroupManager) BytesWritten() int64 {
+	gm.writeMutex.Lock()
+	defer gm.writeMutex.Unlock()
+	return gm.bytesWritten
+}
+
+// formatBenchNanos formats a benchmark time without trailing zeros, e.g. "212" or "212.5"
+func formatBenchNanos(nanos float64) string {
+	return strconv.FormatFloat(nanos, 'f', -1, 64)
+}
+
+// formatOptionalBenchNanos formats a criterion statistic to two decimals, or "-" if it is unknown
+func formatOptionalBenchNanos(nanos float64) string {
+	if nanos == 0 {
+		return "-"
+	}
+	return strconv.FormatFloat(nanos, 'f', 2, 64)
+}
+
+// formatBenchChange formats a benchmark's change against its baseline, e.g. "+12.30% (regressed)"
+func formatBenchChange(tc TestCase) string {
+	if tc.BenchChangePercent == 0 && !tc.BenchRegressed {
+		return "-"
+	}
+	change := fmt.Sprintf("%+.2f%%", tc.BenchChangePercent)
+	if tc.BenchRegressed {
+		change += " (regressed)"
+	}
+	return change
+}
+
+// hasGoBenchResults reports whether any benchmark came from go test -bench, and whether
+// any of them reported allocations
+func hasGoBenchResults(benchmarks []TestCase) (results, mem bool) {
+	for _, tc := range benchmarks {
+		results = results || tc.BenchIterations > 0
+		mem = mem || tc.BenchMem
+	}
+	return results, mem
+}
+
+// formatBenchAllocs formats a -benchmem column, or "-" if the benchmark didn't report it
+func formatBenchAllocs(tc TestCase, value int64) string {
+	if !tc.BenchMem {
+		return "-"
+	}
+	return strconv.FormatInt(value, 10)
+}
+
+// hasCriterionEstimates reports whether any benchmark came from criterion
+func hasCriterionEstimates(benchmarks []TestCase) bool {
+	for _, tc := range benchmarks {
+		if tc.BenchMeanNs > 0 {
+			return true
+		}
+	}
+	return false
+}
+
 // formatGroupReport formats a group's data as a markdown report
 func (gm *GroupManager) formatGroupReport(group *TestGroup) string {
-	var content string
+	var sb strings.Builder
 
 	// Use the ParentNames field which already contains the hierarchy
 	parentPath := group.ParentNames
 
 	// Metadata (YAML frontmatter) - MUST come first
-	content += "---\n"
-	content += fmt.Sprintf("group_name: %s\n", group.Name)
+	sb.WriteString("---\n")
+	fmt.Fprintf(&sb, "group_name: %s\n", group.Name)
 
 	// Parent path as slash-separated list for frontmatter
 	if len(parentPath) > 0 {
-		content += fmt.Sprintf("parent_path: %s\n", strings.Join(parentPath, "/"))
+		fmt.Fprintf(&sb, "parent_path: %s\n", strings.Join(parentPath, "/"))
 	} else {
-		content += "parent_path:\n"
+		sb.WriteString("parent_path:\n")
 	}
 
-	content += fmt.Sprintf("status: %s\n", group.Status)
+	fmt.Fprintf(&sb, "status: %s\n", group.Status)
+	if group.SkipReason != "" {
+		fmt.Fprintf(&sb, "skip_reason: %s\n", group.SkipReason)
+	}
+	if group.TargetKind != "" {
+		fmt.Fprintf(&sb, "target_kind: %s\n", group.TargetKind)
+		fmt.Fprintf(&sb, "target_name: %s\n", group.TargetName)
+	}
 
 	// Format duration - use seconds for all groups consistently
 	if group.Duration > 0 {
 		seconds := group.Duration.Seconds()
-		content += fmt.Sprintf("duration: %.2fs\n", seconds)
+		fmt.Fprintf(&sb, "duration: %.2fs\n", seconds)
 	}
 
-	content += fmt.Sprintf("created: %s\n", group.Created.Format(time.RFC3339))
-	content += fmt.Sprintf("updated: %s\n", group.Updated.Format(time.RFC3339))
-	content += "---\n\n"
+	fmt.Fprintf(&sb, "created: %s\n", group.Created.Format(time.RFC3339))
+	fmt.Fprintf(&sb, "updated: %s\n", group.Updated.Format(time.RFC3339))
+	sb.WriteString("---\n\n")
 
 	// Header - use consistent "Test Report:" format for all groups
 	if len(parentPath) == 0 {
 		// Root group (file)
-		content += fmt.Sprintf("# Test Report: %s\n\n", group.Name)
+		fmt.Fprintf(&sb, "# Test Report: %s\n\n", group.Name)
 	} else {
 		// All non-root groups show full hierarchical path
 		fullPath := strings.Join(append(parentPath, group.Name), " > ")
-		content += fmt.Sprintf("# Test Report: %s\n\n", fullPath)
+		fmt.Fprintf(&sb, "# Test Report: %s\n\n", fullPath)
 	}
 
 	// Summary section - show direct tests OR subgroups, not both aggregated counts
-	content += "## Summary\n\n"
+	sb.WriteString("## Summary\n\n")
+	if group.CustomHarness {
+		sb.WriteString("- Custom harness — individual tests not available\n")
+	}
 
 	// Only show direct test statistics if there are direct test cases
 	if len(group.TestCases) > 0 {
-		content += fmt.Sprintf("- Group tests: %d\n", group.Stats.TotalTests)
+		fmt.Fprintf(&sb, "- Group tests: %d\n", group.Stats.TotalTests)
 		if group.Stats.PassedTests > 0 {
-			content += fmt.Sprintf("- Group tests passed: %d\n", group.Stats.PassedTests)
+			fmt.Fprintf(&sb, "- Group tests passed: %d\n", group.Stats.PassedTests)
 		}
 		if group.Stats.FailedTests > 0 {
-			content += fmt.Sprintf("- Group tests failed: %d\n", group.Stats.FailedTests)
+			fmt.Fprintf(&sb, "- Group tests failed: %d\n", group.Stats.FailedTests)
+		}
+		if group.Stats.ErroredTests > 0 {
+			fmt.Fprintf(&sb, "- Group tests errored: %d\n", group.Stats.ErroredTests)
 		}
 		if group.Stats.SkippedTests > 0 {
-			content += fmt.Sprintf("- Group tests skipped: %d\n", group.Stats.SkippedTests)
+			fmt.Fprintf(&sb, "- Group tests skipped: %d\n", group.Stats.SkippedTests)
 		}
 		if group.Stats.XFailedTests > 0 {
-			content += fmt.Sprintf("- Group tests xfailed: %d\n", group.Stats.XFailedTests)
+			fmt.Fprintf(&sb, "- Group tests xfailed: %d\n", group.Stats.XFailedTests)
 		}
 		if group.Stats.XPassedTests > 0 {
-			content += fmt.Sprintf("- Group tests xpassed: %d\n", group.Stats.XPassedTests)
+			fmt.Fprintf(&sb, "- Group tests xpassed: %d\n", group.Stats.XPassedTests)
+		}
+		if group.Stats.QuarantinedFailedTests > 0 {
+			fmt.Fprintf(&sb, "- Group tests failed while quarantined: %d\n", group.Stats.QuarantinedFailedTests)
+		}
+		if group.Stats.FlakyTests > 0 {
+			fmt.Fprintf(&sb, "- Group tests flaky: %d\n", group.Stats.FlakyTests)
+		}
+		if group.Stats.Benchmarks > 0 {
+			fmt.Fprintf(&sb, "- Group benchmarks: %d\n", group.Stats.Benchmarks)
 		}
 
 		// Also show subgroup counts if we have both direct tests and subgroups
@@ -832,15 +1501,15 @@ func (gm *GroupManager) formatGroupReport(group *TestGroup) string {
 				}
 			}
 
-			content += fmt.Sprintf("- Subgroups: %d\n", len(group.Subgroups))
+			fmt.Fprintf(&sb, "- Subgroups: %d\n", len(group.Subgroups))
 			if passedSubgroups > 0 {
-				content += fmt.Sprintf("- Subgroups passed: %d\n", passedSubgroups)
+				fmt.Fprintf(&sb, "- Subgroups passed: %d\n", passedSubgroups)
 			}
 			if failedSubgroups > 0 {
-				content += fmt.Sprintf("- Subgroups failed: %d\n", failedSubgroups)
+				fmt.Fprintf(&sb, "- Subgroups failed: %d\n", failedSubgroups)
 			}
 			if skippedSubgroups > 0 {
-				content += fmt.Sprintf("- Subgroups skipped: %d\n", skippedSubgroups)
+				fmt.Fprintf(&sb, "- Subgroups skipped: %d\n", skippedSubgroups)
 			}
 		}
 	} else if len(group.Subgroups) > 0 {
@@ -859,66 +1528,316 @@ func (gm *GroupManager) formatGroupReport(group *TestGroup) string {
 			}
 		}
 
-		content += fmt.Sprintf("- Subgroups: %d\n", len(group.Subgroups))
+		fmt.Fprintf(&sb, "- Subgroups: %d\n", len(group.Subgroups))
 		if passedSubgroups > 0 {
-			content += fmt.Sprintf("- Subgroups passed: %d\n", passedSubgroups)
+			fmt.Fprintf(&sb, "- Subgroups passed: %d\n", passedSubgroups)
 		}
 		if failedSubgroups > 0 {
-			content += fmt.Sprintf("- Subgroups failed: %d\n", failedSubgroups)
+			fmt.Fprintf(&sb, "- Subgroups failed: %d\n", failedSubgroups)
 		}
 		if skippedSubgroups > 0 {
-			content += fmt.Sprintf("- Subgroups skipped: %d\n", skippedSubgroups)
+			fmt.Fprintf(&sb, "- Subgroups skipped: %d\n", skippedSubgroups)
+		}
+	}
+	sb.WriteString("\n")
+
+	// A race outside any test, e.g. during a Go package's teardown
+	if group.ErrorInfo != nil && group.ErrorInfo.FailureKind == ipc.FailureKindDataRace {
+		sb.WriteString("## Data race\n\n")
+		sb.WriteString("```\n")
+		sb.WriteString(group.ErrorInfo.Message)
+		sb.WriteString("\n```\n\n")
+	}
+
+	// Benchmarks get their own table, so split them from the test cases
+	var testCases, benchmarks []TestCase
+	for _, tc := range group.TestCases {
+		if tc.Status == TestStatusBench {
+			benchmarks = append(benchmarks, tc)
+		} else {
+			testCases = append(testCases, tc)
 		}
 	}
-	content += "\n"
 
 	// Test case results section - only show if there are test cases
-	if len(group.TestCases) > 0 {
-		content += "## Test case results\n\n"
-		for _, tc := range group.TestCases {
+	if len(testCases) > 0 {
+		sb.WriteString("## Test case results\n\n")
+
+		// Once a test is slow, the slowest tests are what the reader is after
+		slowTests := 0
+		for _, tc := range testCases {
+			if tc.Duration > gm.slowThreshold {
+				slowTests++
+			}
+		}
+		if slowTests > 0 {
+			sort.SliceStable(testCases, func(i, j int) bool {
+				return testCases[i].Duration > testCases[j].Duration
+			})
+			noun := "tests"
+			if slowTests == 1 {
+				noun = "test"
+			}
+			fmt.Fprintf(&sb, "Sorted by duration, %d %s took longer than %s.\n\n", slowTests, noun, gm.slowThreshold)
+		}
+
+		for _, tc := range testCases {
+			// Quarantined failures and setup or teardown errors are reported like any
+			// other failure
+			failed := tc.Status == TestStatusFail || tc.Status == TestStatusQuarantinedFail || tc.Status == TestStatusError
+
 			var icon string
-			switch tc.Status {
-			case TestStatusFail:
+			switch {
+			case failed:
 				icon = "✕"
-			case TestStatusSkip:
+			case tc.Status == TestStatusSkip:
 				icon = "○"
-			case TestStatusXFail:
+			case tc.Status == TestStatusXFail:
 				icon = "⊗" // Expected failure
-			case TestStatusXPass:
+			case tc.Status == TestStatusXPass:
 				icon = "⊕" // Unexpected pass
 			default:
 				icon = "✓"
 			}
 
-			content += fmt.Sprintf("- %s %s", icon, tc.Name)
+			fmt.Fprintf(&sb, "- %s %s", icon, tc.Name)
 			if tc.Duration > 0 {
-				content += fmt.Sprintf(" (%.2fs)", tc.Duration.Seconds())
+				fmt.Fprintf(&sb, " (%.2fs)", tc.Duration.Seconds())
+			}
+			if tc.Slow {
+				sb.WriteString(" [SLOW]")
+			}
+			if tc.Leaked {
+				sb.WriteString(" [LEAK]")
+			}
+			if tc.PanickedAsExpected {
+				sb.WriteString(" [PANICKED AS EXPECTED]")
+			}
+			if kind := tc.FailureKind(); kind != "" && kind != ipc.FailureKindUnknown {
+				fmt.Fprintf(&sb, " [%s]", kind)
+			}
+			if tc.Status == TestStatusSkip && tc.SkipReason != "" {
+				fmt.Fprintf(&sb, " [%s]", strings.ToUpper(tc.SkipReason))
+			}
+			if tc.Quarantined {
+				sb.WriteString(" [QUARANTINED]")
+			}
+			if tc.Status == TestStatusFlaky {
+				sb.WriteString(" [FLAKY]")
+			}
+			if tc.Status == TestStatusError {
+				sb.WriteString(" [ERRORED]")
+			}
+			sb.WriteString("\n")
+
+			// A quarantined test that passes may no longer be flaky
+			if tc.Quarantined && tc.Status == TestStatusPass {
+				sb.WriteString("  > Passed while quarantined; it may be removable from the quarantine list\n")
+			}
+
+			// Retried tests list the status of every attempt
+			if len(tc.Attempts) > 1 {
+				statuses := make([]string, len(tc.Attempts))
+				for i, attempt := range tc.Attempts {
+					statuses[i] = string(attempt.Status)
+				}
+				fmt.Fprintf(&sb, "  > Attempts: %d (%s)\n", len(tc.Attempts), strings.Join(statuses, ", "))
+			}
+
+			// Reasons from the xfail and skip markers, if available
+			switch {
+			case tc.Status == TestStatusXFail && tc.XFailReason != "":
+				fmt.Fprintf(&sb, "  > *Expected failure: %s*\n", tc.XFailReason)
+			case tc.Status == TestStatusXPass && tc.XFailReason != "":
+				fmt.Fprintf(&sb, "  > *Passed, but expected to fail: %s*\n", tc.XFailReason)
+			case tc.Status == TestStatusSkip && tc.SkipMessage != "":
+				fmt.Fprintf(&sb, "  > *Skipped: %s*\n", tc.SkipMessage)
+			}
+
+			// Error location so the failing source can be opened directly, else where
+			// the test is defined
+			switch {
+			case tc.Error != nil && failed && tc.Error.Location != "":
+				if tc.Error.External {
+					fmt.Fprintf(&sb, "  > Location: %s (external)\n", tc.Error.Location)
+				} else {
+					fmt.Fprintf(&sb, "  > Location: %s\n", tc.Error.Location)
+				}
+			case failed && tc.DefinitionLocation() != "":
+				fmt.Fprintf(&sb, "  > Location: %s\n", tc.DefinitionLocation())
+			}
+
+			// Traces and screenshots to open alongside the failure
+			for _, attachment := range tc.Attachments {
+				fmt.Fprintf(&sb, "  > Attachment (%s): %s\n", attachment.Name, attachment.Path)
 			}
-			content += "\n"
 
-			// XFail reason if available
-			if tc.Status == TestStatusXFail && tc.XFailReason != "" {
-				content += fmt.Sprintf("  > *Expected failure: %s*\n", tc.XFailReason)
+			// A property test's minimized case first, as its panic also shows the shrinking
+			if tc.Error != nil && failed && tc.Error.Property != nil {
+				writePropertyFailure(&sb, tc.Error.Property, gm.projectFileLink(group, tc.Error.Property.RegressionFile))
 			}
 
-			// Error details indented under the test
-			if tc.Error != nil && tc.Status == TestStatusFail {
-				content += "```\n"
-				content += tc.Error.Message
-				if tc.Error.Stack != "" {
-					content += "\n" + tc.Error.Stack
+			// Error details indented under the test, under the phase that failed if it
+			// wasn't the test itself
+			if tc.Error != nil && failed {
+				if heading := errorPhaseHeading(tc.Error.Phase); heading != "" {
+					fmt.Fprintf(&sb, "  > %s:\n", heading)
 				}
-				content += "\n```\n"
+				writeErrorBlock(&sb, tc.Error)
+			}
+
+			// Assertion values as an aligned diff
+			if tc.Error != nil && failed {
+				if diff := tc.Error.AssertionDiff(); diff != "" {
+					sb.WriteString("```diff\n" + diff + "```\n")
+				}
+			}
+
+			// A snapshot that didn't match, with the stored and new value as a diff
+			if tc.Error != nil && failed && tc.Error.Snapshot != nil {
+				writeSnapshotMismatch(&sb, tc.Error.Snapshot)
+			}
+
+			// trybuild cases whose compiler output didn't match, expected against actual
+			if tc.Error != nil && failed {
+				for _, tbCase := range tc.Error.Trybuild {
+					writeTrybuildCase(&sb, tbCase, gm.projectFileLink(group, tbCase.StderrFile))
+				}
+			}
+
+			// should_panic expectation mismatch as a two-line diff
+			if tc.Error != nil && failed && (tc.Error.ExpectedPanic != "" || tc.Error.ActualPanic != "") {
+				sb.WriteString("```diff\n")
+				fmt.Fprintf(&sb, "- expected panic: %q\n", tc.Error.ExpectedPanic)
+				fmt.Fprintf(&sb, "+ actual panic:   %q\n", tc.Error.ActualPanic)
+				sb.WriteString("```\n")
+			}
+
+			// Source lines around the failing line
+			if tc.Error != nil && failed && tc.Error.Snippet != "" {
+				sb.WriteString(formatSnippet(tc.Error))
+			}
+
+			// Rust backtraces are long, so they are collapsed by default
+			if tc.Error != nil && failed && tc.Error.Backtrace != "" {
+				sb.WriteString("<details>\n<summary>Backtrace</summary>\n\n")
+				sb.WriteString("```\n")
+				sb.WriteString(tc.Error.Backtrace)
+				sb.WriteString("\n```\n\n")
+				sb.WriteString("</details>\n")
+			}
+
+			// The race detector's stacks show which goroutines collided
+			if tc.Error != nil && failed && tc.Error.DataRace != "" {
+				sb.WriteString("  > Data race:\n")
+				sb.WriteString("```\n")
+				sb.WriteString(tc.Error.DataRace)
+				sb.WriteString("\n```\n")
+			}
+
+			// miri's diagnostic in full: the invalid operation, where, and the backtrace to the test
+			if tc.Error != nil && failed && tc.Error.UndefinedBehavior != "" {
+				sb.WriteString("  > Undefined behavior:\n")
+				sb.WriteString("```\n")
+				sb.WriteString(tc.Error.UndefinedBehavior)
+				sb.WriteString("\n```\n")
+			}
+
+			// A failed test can fail its teardown too
+			if tc.TeardownError != nil && failed {
+				fmt.Fprintf(&sb, "  > %s:\n", errorPhaseHeading(ipc.ErrorPhaseTeardown))
+				writeErrorBlock(&sb, tc.TeardownError)
 			}
 		}
-		content += "\n"
+		sb.WriteString("\n")
+	}
+
+	// Benchmark results section
+	if len(benchmarks) > 0 {
+		sb.WriteString("## Benchmarks\n\n")
+		goResults, goMem := hasGoBenchResults(benchmarks)
+		if goResults && goMem {
+			sb.WriteString("| Benchmark | Iterations | ns/op | B/op | allocs/op |\n")
+			sb.WriteString("|-----------|------------|-------|------|-----------|\n")
+			for _, tc := range benchmarks {
+				fmt.Fprintf(&sb, "| %s | %d | %s | %s | %s |\n", tc.Name, tc.BenchIterations, formatBenchNanos(tc.BenchNsPerOp),
+					formatBenchAllocs(tc, tc.BenchBytesPerOp), formatBenchAllocs(tc, tc.BenchAllocsPerOp))
+			}
+		} else if goResults {
+			// Without -benchmem Go reports no allocations, so their columns are left out
+			sb.WriteString("| Benchmark | Iterations | ns/op |\n")
+			sb.WriteString("|-----------|------------|-------|\n")
+			for _, tc := range benchmarks {
+				fmt.Fprintf(&sb, "| %s | %d | %s |\n", tc.Name, tc.BenchIterations, formatBenchNanos(tc.BenchNsPerOp))
+			}
+		} else if hasCriterionEstimates(benchmarks) {
+			// Criterion reports full statistics and the change against its baseline
+			sb.WriteString("| Benchmark | Mean (ns) | Median (ns) | Std. dev. (ns) | Change |\n")
+			sb.WriteString("|-----------|-----------|-------------|----------------|--------|\n")
+			for _, tc := range benchmarks {
+				fmt.Fprintf(&sb, "| %s | %s | %s | %s | %s |\n", tc.Name, formatOptionalBenchNanos(tc.BenchMeanNs),
+					formatOptionalBenchNanos(tc.BenchMedianNs), formatOptionalBenchNanos(tc.BenchStdDevNs), formatBenchChange(tc))
+			}
+		} else {
+			sb.WriteString("| Benchmark | Median (ns/iter) | Deviation (+/- ns) |\n")
+			sb.WriteString("|-----------|------------------|--------------------|\n")
+			for _, tc := range benchmarks {
+				fmt.Fprintf(&sb, "| %s | %s | %s |\n", tc.Name, formatBenchNanos(tc.BenchMedianNs), formatBenchNanos(tc.BenchDeviationNs))
+			}
+		}
+		sb.WriteString("\n")
+	}
+
+	// Output printed by failed tests, kept apart from the failure message above.
+	// Retried tests show the output of every attempt.
+	var capturedTests []TestCase
+	for _, tc := range group.TestCases {
+		failed := tc.Status == TestStatusFail || tc.Status == TestStatusQuarantinedFail || tc.Status == TestStatusError
+		if (failed && (tc.Stdout != "" || tc.Stderr != "")) || attemptsHaveOutput(tc.Attempts) {
+			capturedTests = append(capturedTests, tc)
+		}
+	}
+	if len(capturedTests) > 0 {
+		sb.WriteString("## Captured output\n\n")
+		for _, tc := range capturedTests {
+			fmt.Fprintf(&sb, "### %s\n\n", tc.Name)
+			if attemptsHaveOutput(tc.Attempts) {
+				for i, attempt := range tc.Attempts {
+					fmt.Fprintf(&sb, "#### Attempt %d (%s)\n\n", i+1, attempt.Status)
+					writeOutputBlock(&sb, attempt.Stdout, attempt.Stderr)
+				}
+				continue
+			}
+			writeOutputBlock(&sb, tc.Stdout, tc.Stderr)
+			if tc.OutputTruncated {
+				fmt.Fprintf(&sb, "Output truncated to the last %d KB, full output in %s\n\n",
+					maxOutputTail/1024, gm.relativeOutputPath(group, GetTestLogFilePath(group, tc.Name, gm.runDir)))
+			}
+		}
+	}
+
+	// Console entries of each test that logged any, and those logged outside a test
+	var consoleTests []TestCase
+	for _, tc := range group.TestCases {
+		if tc.Console != "" {
+			consoleTests = append(consoleTests, tc)
+		}
+	}
+	if len(consoleTests) > 0 {
+		sb.WriteString("## Console output\n\n")
+		for _, tc := range consoleTests {
+			fmt.Fprintf(&sb, "### %s\n\n```\n%s```\n\n", tc.Name, tc.Console)
+		}
+	}
+	if group.SetupOutput != "" {
+		fmt.Fprintf(&sb, "## Setup output\n\n```\n%s```\n\n", group.SetupOutput)
 	}
 
 	// Subgroups
 	if len(group.Subgroups) > 0 {
-		content += "## Subgroups\n\n"
-		content += "| Status | Name | Tests | Duration | Report |\n"
-		content += "|--------|------|-------|----------|--------|\n"
+		sb.WriteString("## Subgroups\n\n")
+		sb.WriteString("| Status | Name | Tests | Duration | Report |\n")
+		sb.WriteString("|--------|------|-------|----------|--------|\n")
 
 		for _, subgroup := range group.Subgroups {
 			relPath := GetRelativeReportPath(subgroup, gm.runDir)
@@ -930,6 +1849,9 @@ func (gm *GroupManager) formatGroupReport(group *TestGroup) string {
 
 			// Name column
 			nameStr := subgroup.Name
+			if subgroup.Parametrized {
+				nameStr += " (parametrized)"
+			}
 
 			// Tests column - show breakdown of test results (using recursive counts)
 			var testsStr string
@@ -941,10 +1863,15 @@ func (gm *GroupManager) formatGroupReport(group *TestGroup) string {
 				if subgroup.Stats.FailedTestsRecursive > 0 {
 					parts = append(parts, fmt.Sprintf("%d failed", subgroup.Stats.FailedTestsRecursive))
 				}
+				if subgroup.Stats.ErroredTestsRecursive > 0 {
+					parts = append(parts, fmt.Sprintf("%d errored", subgroup.Stats.ErroredTestsRecursive))
+				}
 				if subgroup.Stats.SkippedTestsRecursive > 0 {
 					parts = append(parts, fmt.Sprintf("%d skipped", subgroup.Stats.SkippedTestsRecursive))
 				}
 				testsStr = strings.Join(parts, ", ")
+			} else if subgroup.Stats.BenchmarksRecursive > 0 {
+				testsStr = fmt.Sprintf("%d benchmarks", subgroup.Stats.BenchmarksRecursive)
 			} else {
 				testsStr = "0 tests"
 			}
@@ -958,36 +1885,221 @@ func (gm *GroupManager) formatGroupReport(group *TestGroup) string {
 			// Report link column
 			reportStr := fmt.Sprintf("./%s", relPath)
 
-			content += fmt.Sprintf("| %s | %s | %s | %s | %s |\n",
+			fmt.Fprintf(&sb, "| %s | %s | %s | %s | %s |\n",
 				statusStr, nameStr, testsStr, durationStr, reportStr)
 		}
-		content += "\n"
+		sb.WriteString("\n")
 	}
 
 	// stdout/stderr section
 	if group.Stdout != "" || group.Stderr != "" {
-		content += "## stdout/stderr\n"
+		sb.WriteString("## stdout/stderr\n")
 
-		// Combined output in single code block as per migration plan
+		// Combined output in single code block as per migration plan. The group's
+		// output.log keeps the raw output, and the report its plain text.
 		if group.Stdout != "" || group.Stderr != "" {
-			content += "```\n"
-			if group.Stdout != "" {
-				content += group.Stdout
-				if !strings.HasSuffix(group.Stdout, "\n") {
-					content += "\n"
+			sb.WriteString("```\n")
+			if stdout := console.PlainText(group.Stdout); stdout != "" {
+				sb.WriteString(stdout)
+				if !strings.HasSuffix(stdout, "\n") {
+					sb.WriteString("\n")
 				}
 			}
-			if group.Stderr != "" {
-				content += group.Stderr
-				if !strings.HasSuffix(group.Stderr, "\n") {
-					content += "\n"
+			if stderr := console.PlainText(group.Stderr); stderr != "" {
+				sb.WriteString(stderr)
+				if !strings.HasSuffix(stderr, "\n") {
+					sb.WriteString("\n")
 				}
 			}
-			content += "```\n"
+			sb.WriteString("```\n")
+		}
+		if group.OutputTruncated {
+			fmt.Fprintf(&sb, "\nOutput truncated to the last %d KB, full output in %s\n",
+				maxOutputTail/1024, gm.relativeOutputPath(group, GetGroupOutputFilePath(group, gm.runDir)))
 		}
 	}
 
-	return content
+	return sb.String()
+}
+
+// attemptsHaveOutput reports whether any attempt of a retried test printed output
+func attemptsHaveOutput(attempts []TestAttempt) bool {
+	if len(attempts) < 2 {
+		return false
+	}
+	for _, attempt := range attempts {
+		if attempt.Stdout != "" || attempt.Stderr != "" {
+			return true
+		}
+	}
+	return false
+}
+
+// errorPhaseHeading returns the heading of an error raised in a test's setup or
+// teardown, or "" for an error of the test itself
+func errorPhaseHeading(phase string) string {
+	switch phase {
+	case ipc.ErrorPhaseSetup:
+		return "Setup error"
+	case ipc.ErrorPhaseTeardown:
+		return "Teardown error"
+	}
+	return ""
+}
+
+// writeErrorBlock writes an error's message and stack as one code block
+func writeErrorBlock(sb *strings.Builder, err *TestError) {
+	sb.WriteString("```\n")
+	sb.WriteString(err.Message)
+	if err.Stack != "" {
+		sb.WriteString("\n" + err.Stack)
+	}
+	sb.WriteString("\n```\n")
+}
+
+// writePropertyFailure writes the minimized failing input of a property test, with the
+// seed and file proptest saved the case in to replay it, linked to at regressionLink
+func writePropertyFailure(sb *strings.Builder, failure *ipc.PropertyFailure, regressionLink string) {
+	fmt.Fprintf(sb, "  > Minimal failing input (%s):\n", failure.Framework)
+	sb.WriteString("```\n" + failure.Input + "\n```\n")
+	switch {
+	case failure.RegressionFile != "" && failure.Seed != "":
+		fmt.Fprintf(sb, "  > Saved as `cc %s` in [%s](%s)\n", failure.Seed, failure.RegressionFile, regressionLink)
+	case failure.RegressionFile != "":
+		fmt.Fprintf(sb, "  > Saved in [%s](%s)\n", failure.RegressionFile, regressionLink)
+	case failure.Seed != "":
+		fmt.Fprintf(sb, "  > Seed: `cc %s`\n", failure.Seed)
+	}
+}
+
+// projectFileLink returns a link from the report of group to a file relative to the
+// project, or the path as it is when there is no project to resolve it against
+func (gm *GroupManager) projectFileLink(group *TestGroup, path string) string {
+	if gm.sourceRoot == "" || path == "" || filepath.IsAbs(path) {
+		return path
+	}
+	rel, err := filepath.Rel(filepath.Dir(GetReportFilePath(group, gm.runDir)), filepath.Join(gm.sourceRoot, filepath.FromSlash(path)))
+	if err != nil {
+		return path
+	}
+	return filepath.ToSlash(rel)
+}
+
+// writeSnapshotMismatch writes the snapshot a test's value didn't match: its files, the
+// command that accepts or rejects the new value, and the values as a diff
+func writeSnapshotMismatch(sb *strings.Builder, snapshot *ipc.SnapshotMismatch) {
+	line := fmt.Sprintf("  > Snapshot `%s`", snapshot.Name)
+	if snapshot.File != "" {
+		line += fmt.Sprintf(": `%s`", snapshot.File)
+	}
+	if snapshot.NewFile != "" {
+		line += fmt.Sprintf(", new value in `%s`; run `cargo insta review` to accept or reject it", snapshot.NewFile)
+	}
+	sb.WriteString(line + "\n")
+	if snapshot.Diff != "" {
+		sb.WriteString("```diff\n" + snapshot.Diff + "```\n")
+	}
+}
+
+// writeTrybuildCase writes a trybuild UI test case that failed, with how to accept its
+// output, linking its .stderr file at stderrLink, and its compiler output against the
+// expected one as a diff
+func writeTrybuildCase(sb *strings.Builder, tbCase ipc.TrybuildCase, stderrLink string) {
+	line := fmt.Sprintf("  > trybuild `%s`: %s", tbCase.Path, tbCase.Status)
+	if tbCase.Message != "" {
+		line += ", " + tbCase.Message
+	}
+	switch {
+	case tbCase.WipFile != "" && tbCase.StderrFile != "":
+		line += fmt.Sprintf("; output written to `%s`, move it to [%s](%s) to accept it", tbCase.WipFile, tbCase.StderrFile, stderrLink)
+	case tbCase.StderrFile != "":
+		line += fmt.Sprintf(" with [%s](%s); rerun with `TRYBUILD=overwrite` to accept the actual output", tbCase.StderrFile, stderrLink)
+	}
+	sb.WriteString(line + "\n")
+	switch {
+	case tbCase.Expected != "":
+		sb.WriteString("```diff\n" + lineDiff(tbCase.Expected, tbCase.Actual) + "```\n")
+	case tbCase.Actual != "":
+		sb.WriteString("```\n" + tbCase.Actual + "\n```\n")
+	}
+}
+
+// maxLineDiffCells caps the lines of one output times the lines of the other that
+// lineDiff compares
+const maxLineDiffCells = 1 << 20
+
+// lineDiff diffs want against got line by line, "-" marking lines only in want and "+"
+// lines only in got. Outputs too long to diff are shown whole, one after the other.
+func lineDiff(want, got string) string {
+	a, b := strings.Split(want, "\n"), strings.Split(got, "\n")
+	var sb strings.Builder
+	if len(a)*len(b) > maxLineDiffCells {
+		for _, line := range a {
+			sb.WriteString("-" + line + "\n")
+		}
+		for _, line := range b {
+			sb.WriteString("+" + line + "\n")
+		}
+		return sb.String()
+	}
+
+	// lcs[i][j] is the length of the longest common subsequence of a[i:] and b[j:]
+	lcs := make([][]int, len(a)+1)
+	for i := range lcs {
+		lcs[i] = make([]int, len(b)+1)
+	}
+	for i := len(a) - 1; i >= 0; i-- {
+		for j := len(b) - 1; j >= 0; j-- {
+			if a[i] == b[j] {
+				lcs[i][j] = lcs[i+1][j+1] + 1
+			} else {
+				lcs[i][j] = max(lcs[i+1][j], lcs[i][j+1])
+			}
+		}
+	}
+	for i, j := 0, 0; i < len(a) || j < len(b); {
+		switch {
+		case i < len(a) && j < len(b) && a[i] == b[j]:
+			sb.WriteString(" " + a[i] + "\n")
+			i++
+			j++
+		case i < len(a) && (j == len(b) || lcs[i+1][j] >= lcs[i][j+1]):
+			sb.WriteString("-" + a[i] + "\n")
+			i++
+		default:
+			sb.WriteString("+" + b[j] + "\n")
+			j++
+		}
+	}
+	return sb.String()
+}
+
+// writeOutputBlock writes a test's stdout and stderr as one code block
+func writeOutputBlock(sb *strings.Builder, stdout, stderr string) {
+	if stdout == "" && stderr == "" {
+		sb.WriteString("No output.\n\n")
+		return
+	}
+	sb.WriteString("```\n")
+	for _, output := range []string{stdout, stderr} {
+		if output == "" {
+			continue
+		}
+		sb.WriteString(output)
+		if !strings.HasSuffix(output, "\n") {
+			sb.WriteString("\n")
+		}
+	}
+	sb.WriteString("```\n\n")
+}
+
+// relativeOutputPath returns the path of an output file relative to the group's report
+func (gm *GroupManager) relativeOutputPath(group *TestGroup, path string) string {
+	relPath, err := filepath.Rel(GenerateGroupPath(group, gm.runDir), path)
+	if err != nil {
+		return path
+	}
+	return "./" + NormalizeFilePath(relPath)
 }
 
 // GetRootGroups returns all root-level groups
@@ -1000,6 +2112,83 @@ func (gm *GroupManager) GetRootGroups() []*TestGroup {
 	return result
 }
 
+// RunningGroups returns the paths of groups that have started but not finished,
+// e.g. "my-crate > tests", sorted
+func (gm *GroupManager) RunningGroups() []string {
+	gm.mu.RLock()
+	defer gm.mu.RUnlock()
+
+	var running []string
+	for _, group := range gm.groups {
+		if group.Status == TestStatusRunning {
+			running = append(running, strings.Join(group.GetFullPath(), " > "))
+		}
+	}
+	sort.Strings(running)
+	return running
+}
+
+// FailedTests returns every test case that failed or whose setup or teardown failed,
+// sorted by its group path
+func (gm *GroupManager) FailedTests() []ipc.FailedTest {
+	return gm.testsWithStatus(TestStatusFail, TestStatusError)
+}
+
+// FlakyTests returns every test case that failed and then passed when retried, sorted
+// by its group path
+func (gm *GroupManager) FlakyTests() []ipc.FailedTest {
+	return gm.testsWithStatus(TestStatusFlaky)
+}
+
+// XPassedTests returns every test case marked as an expected failure that passed,
+// sorted by its group path
+func (gm *GroupManager) XPassedTests() []ipc.FailedTest {
+	return gm.testsWithStatus(TestStatusXPass)
+}
+
+// testsWithStatus returns the test cases with one of the statuses, sorted by their
+// group path
+func (gm *GroupManager) testsWithStatus(statuses ...TestStatus) []ipc.FailedTest {
+	gm.mu.RLock()
+	defer gm.mu.RUnlock()
+
+	var tests []ipc.FailedTest
+	for _, group := range gm.groups {
+		for _, tc := range group.TestCases {
+			if slices.Contains(statuses, tc.Status) {
+				parents := append(append([]string(nil), group.ParentNames...), group.Name)
+				tests = append(tests, ipc.FailedTest{Name: tc.Name, ParentNames: parents, TestID: tc.TestID, FailureKind: tc.FailureKind(), CaseName: tc.CaseName})
+			}
+		}
+	}
+	sort.Slice(tests, func(i, j int) bool {
+		a := strings.Join(tests[i].ParentNames, "\x00") + "\x00" + tests[i].Name
+		b := strings.Join(tests[j].ParentNames, "\x00") + "\x00" + tests[j].Name
+		return a < b
+	})
+	return tests
+}
+
+// InterruptIncompleteGroups marks every group that hasn't finished as INTERRUPTED,
+// after --timeout or Ctrl-C stopped the run
+func (gm *GroupManager) InterruptIncompleteGroups() {
+	gm.mu.Lock()
+	defer gm.mu.Unlock()
+
+	now := time.Now()
+	for _, group := range gm.groups {
+		if group.Status != TestStatusRunning && group.Status != TestStatusPending {
+			continue
+		}
+		group.Status = TestStatusInterrupted
+		group.EndTime = now
+		if !group.StartTime.IsZero() {
+			group.Duration = now.Sub(group.StartTime)
+		}
+		group.Updated = now
+	}
+}
+
 // GetGroup returns a group by ID
 func (gm *GroupManager) GetGroup(groupID string) (*TestGroup, bool) {
 	gm.mu.RLock()
@@ -1009,6 +2198,42 @@ func (gm *GroupManager) GetGroup(groupID string) (*TestGroup, bool) {
 	return group, exists
 }
 
+// findGroup returns a group by the path of names an event gives for it
+func (gm *GroupManager) findGroup(path []string) (*TestGroup, bool) {
+	gm.mu.RLock()
+	defer gm.mu.RUnlock()
+
+	group, _, exists := gm.groupAt(path)
+	return group, exists
+}
+
+// findTestCase returns a copy of the test called name in the group at path, as
+// findGroup finds the group
+func (gm *GroupManager) findTestCase(path []string, name string) (*TestGroup, TestCase, bool) {
+	gm.mu.RLock()
+	defer gm.mu.RUnlock()
+
+	group, normalized, exists := gm.groupAt(path)
+	if !exists {
+		return nil, TestCase{}, false
+	}
+	tc := group.testCase(GenerateTestCaseID(name, normalized))
+	if tc == nil {
+		return nil, TestCase{}, false
+	}
+	return group, *tc, true
+}
+
+// groupAt returns the group at path and the normalized path. Callers hold gm.mu.
+func (gm *GroupManager) groupAt(path []string) (*TestGroup, []string, bool) {
+	normalized := make([]string, len(path))
+	for i, name := range path {
+		normalized[i] = gm.normalizeToAbsolutePath(name)
+	}
+	group, exists := gm.groups[GenerateGroupIDFromPath(normalized)]
+	return group, normalized, exists
+}
+
 // GetAllGroups returns all groups
 func (gm *GroupManager) GetAllGroups() map[string]*TestGroup {
 	gm.mu.RLock()
@@ -1036,9 +2261,9 @@ func (gm *GroupManager) GenerateFinalReport() error {
 
 	// Generate root summary
 	summaryPath := filepath.Join(gm.runDir, "test-run.md")
-	summaryContent := gm.generateSummaryReport()
+	summaryContent := gm.redactor.Redact(gm.generateSummaryReport())
 
-	if err := os.WriteFile(summaryPath, []byte(summaryContent), 0644); err != nil {
+	if err := writeFileAtomic(summaryPath, []byte(summaryContent)); err != nil {
 		return fmt.Errorf("failed to write summary report: %w", err)
 	}
 
@@ -1086,6 +2311,10 @@ func (gm *GroupManager) generateSummaryReport() string {
 			icon = "⊗" // Expected failure
 		case TestStatusXPass:
 			icon = "⊕" // Unexpected pass
+		case TestStatusWarn:
+			icon = "⚠" // Completed with a warning
+		case TestStatusInterrupted:
+			icon = "⏹" // Stopped by --timeout or Ctrl-C
 		case TestStatusRunning:
 			icon = "⚡"
 		case TestStatusPending:
@@ -1114,6 +2343,15 @@ func (gm *GroupManager) generateSummaryReport() string {
 			if group.Stats.XPassedTestsRecursive > 0 {
 				statParts = append(statParts, fmt.Sprintf("%d xpassed", group.Stats.XPassedTestsRecursive))
 			}
+			if group.Stats.QuarantinedFailedTestsRecursive > 0 {
+				statParts = append(statParts, fmt.Sprintf("%d quarantined", group.Stats.QuarantinedFailedTestsRecursive))
+			}
+			if group.Stats.FlakyTestsRecursive > 0 {
+				statParts = append(statParts, fmt.Sprintf("%d flaky", group.Stats.FlakyTestsRecursive))
+			}
+			if group.Stats.ErroredTestsRecursive > 0 {
+				statParts = append(statParts, fmt.Sprintf("%d errored", group.Stats.ErroredTestsRecursive))
+			}
 			if len(statParts) > 0 {
 				content += fmt.Sprintf(" (%d tests: %s)", group.Stats.TotalTestsRecursive, strings.Join(statParts, ", "))
 			} else {
@@ -1172,11 +2410,17 @@ func (gm *GroupManager) getRootGroupIDs() []string {
 
 // ProcessGroupStdout processes stdout event from IPC
 func (gm *GroupManager) ProcessGroupStdout(event ipc.GroupStdoutChunkEvent) error {
+	if event.Payload.Level != "" {
+		return gm.ProcessConsoleChunk(event.Payload)
+	}
 	return gm.ProcessStdoutChunk(event.Payload.GroupName, event.Payload.ParentNames, event.Payload.Chunk)
 }
 
 // ProcessGroupStderr processes stderr event from IPC
 func (gm *GroupManager) ProcessGroupStderr(event ipc.GroupStderrChunkEvent) error {
+	if event.Payload.Level != "" {
+		return gm.ProcessConsoleChunk(event.Payload)
+	}
 	return gm.ProcessStderrChunk(event.Payload.GroupName, event.Payload.ParentNames, event.Payload.Chunk)
 }
 
@@ -1203,14 +2447,23 @@ func (gm *GroupManager) Flush() {
 	// Flush all pending updates immediately
 	gm.flushPendingUpdates()
 
-	// Also generate reports for all groups to ensure nothing is missed
+	// Also write the groups whose report is missing or older than their last change
 	gm.mu.RLock()
 	defer gm.mu.RUnlock()
 
 	for _, group := range gm.groups {
+		if gm.reportIsCurrent(group) {
+			continue
+		}
 		if err := gm.generateGroupReport(group); err != nil {
 			gm.logError("Failed to generate report for group %s: %v",
 				group.ID, err)
 		}
 	}
+
+	if len(gm.groups) > 0 {
+		if err := gm.writePathIndex(); err != nil {
+			gm.logError("Failed to write %s: %v", PathIndexFileName, err)
+		}
+	}
 }
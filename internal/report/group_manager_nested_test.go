@@ -7,6 +7,7 @@ import (
 	"testing"
 	"time"
 
+	"github.com/zk/3pio/internal/ipc"
 	"github.com/zk/3pio/internal/logger"
 )
 
@@ -150,3 +151,87 @@ func TestGenerateGroupReport_NestedTestAggregation(t *testing.T) {
 		t.Errorf("Body module recursive passed count = %d, want 2", body.Stats.PassedTestsRecursive)
 	}
 }
+
+func TestProcessTestCase_RustModuleTree(t *testing.T) {
+	tmpDir := t.TempDir()
+	log, _ := logger.NewFileLogger()
+	t.Cleanup(func() { _ = log.Close() })
+	gm := NewGroupManager(tmpDir, "", log)
+
+	// Events as cargo sends them for the rust-edge-cases nested modules:
+	// rust-edge-cases
+	//   └── tests
+	//       └── nested_module_tests
+	//           ├── deeply_nested (test fn, same name as the module below)
+	//           └── deeply_nested (module)
+	//               ├── very_nested_test (PASS)
+	//               └── very_nested_panic (FAIL)
+	hierarchy := []string{"rust-edge-cases", "tests", "nested_module_tests", "deeply_nested"}
+	for i := range hierarchy {
+		if err := gm.ProcessGroupDiscovered(ipc.GroupDiscoveredEvent{
+			EventType: string(ipc.EventTypeGroupDiscovered),
+			Payload: ipc.GroupDiscoveredPayload{
+				GroupName:   hierarchy[i],
+				ParentNames: hierarchy[:i],
+			},
+		}); err != nil {
+			t.Fatalf("ProcessGroupDiscovered failed: %v", err)
+		}
+	}
+
+	testCases := []ipc.TestCasePayload{
+		{TestName: "very_nested_test", ParentNames: hierarchy, Status: "PASS"},
+		{TestName: "very_nested_panic", ParentNames: hierarchy, Status: "FAIL"},
+		{TestName: "deeply_nested", ParentNames: hierarchy[:3], Status: "PASS"},
+	}
+	for _, payload := range testCases {
+		if err := gm.ProcessTestCase(ipc.GroupTestCaseEvent{
+			EventType: string(ipc.EventTypeTestCase),
+			Payload:   payload,
+		}); err != nil {
+			t.Fatalf("ProcessTestCase failed: %v", err)
+		}
+	}
+
+	nestedModule, ok := gm.GetGroup(GenerateGroupIDFromPath(hierarchy[:3]))
+	if !ok {
+		t.Fatal("nested_module_tests group not found")
+	}
+	deeplyNested, ok := gm.GetGroup(GenerateGroupIDFromPath(hierarchy))
+	if !ok {
+		t.Fatal("deeply_nested group not found")
+	}
+
+	// The test fn and the module named deeply_nested must both survive
+	if len(nestedModule.TestCases) != 1 || nestedModule.TestCases[0].Name != "deeply_nested" {
+		t.Errorf("Expected test deeply_nested directly under nested_module_tests, got %v", nestedModule.TestCases)
+	}
+	if len(nestedModule.Subgroups) != 1 {
+		t.Errorf("Expected module deeply_nested as the only subgroup, got %d subgroups", len(nestedModule.Subgroups))
+	}
+	if len(deeplyNested.TestCases) != 2 {
+		t.Errorf("Expected 2 tests in deeply_nested, got %d", len(deeplyNested.TestCases))
+	}
+
+	// Counts roll up through every ancestor before any group result arrives
+	if deeplyNested.Stats.PassedTestsRecursive != 1 || deeplyNested.Stats.FailedTestsRecursive != 1 {
+		t.Errorf("deeply_nested recursive = %d passed / %d failed, want 1 / 1",
+			deeplyNested.Stats.PassedTestsRecursive, deeplyNested.Stats.FailedTestsRecursive)
+	}
+	root, _ := gm.GetGroup(GenerateGroupIDFromPath(hierarchy[:1]))
+	if root.Stats.TotalTestsRecursive != 3 || root.Stats.FailedTestsRecursive != 1 {
+		t.Errorf("root recursive = %d total / %d failed, want 3 / 1",
+			root.Stats.TotalTestsRecursive, root.Stats.FailedTestsRecursive)
+	}
+
+	content := gm.formatGroupReport(nestedModule)
+	if !strings.Contains(content, "| deeply_nested | 1 passed, 1 failed |") {
+		t.Errorf("Expected deeply_nested subgroup row with 1 passed, 1 failed, got:\n%s", content)
+	}
+
+	// Report paths nest per module, sanitizing each segment on its own
+	want := filepath.Join(tmpDir, "reports", "rust_edge_cases", "tests", "nested_module_tests", "deeply_nested", "index.md")
+	if got := GetReportFilePath(deeplyNested, tmpDir); got != want {
+		t.Errorf("GetReportFilePath() = %s, want %s", got, want)
+	}
+}
@@ -264,6 +264,85 @@ func TestGroupManager_ProcessTestCase(t *testing.T) {
 	}
 }
 
+func TestGroupManager_ParametrizedTests(t *testing.T) {
+	gm := NewGroupManager(t.TempDir(), "", &mockLogger{})
+	gm.SetRunnerKind(RunnerKind("pytest"))
+	for _, payload := range []ipc.TestCasePayload{
+		{TestName: "test_divide[10-2-5]", ParentNames: []string{"test_math.py"}, Status: "PASS", BaseName: "test_divide", ParamID: "10-2-5"},
+		{TestName: "test_divide[10-0-None]", ParentNames: []string{"test_math.py"}, Status: "FAIL", BaseName: "test_divide", ParamID: "10-0-None"},
+		{TestName: "test_add", ParentNames: []string{"test_math.py"}, Status: "PASS"},
+		// rstest nests the cases in a module named after the test
+		{TestName: "case_2", ParentNames: []string{"my-crate", "tests", "fibonacci"}, Status: "FAIL", BaseName: "fibonacci", ParamID: "case_2"},
+	} {
+		if err := gm.ProcessTestCase(ipc.GroupTestCaseEvent{EventType: string(ipc.EventTypeTestCase), Payload: payload}); err != nil {
+			t.Fatalf("ProcessTestCase failed: %v", err)
+		}
+	}
+
+	divide, exists := gm.GetGroup(GenerateGroupIDFromPath([]string{"test_math.py", "test_divide"}))
+	if !exists || !divide.Parametrized || len(divide.TestCases) != 2 {
+		t.Fatalf("Expected a parametrized test_divide group with both parameter sets, got %+v", divide)
+	}
+	// The test ID is that of the test as pytest reports it
+	if got, want := divide.TestCases[0].TestID, TestID("pytest", []string{"test_math.py"}, "test_divide[10-2-5]"); got != want {
+		t.Errorf("Expected test ID %s, got %s", want, got)
+	}
+	file, _ := gm.GetGroup(GenerateGroupIDFromPath([]string{"test_math.py"}))
+	if len(file.TestCases) != 1 || file.Parametrized {
+		t.Errorf("Expected only test_add directly in the file, got %+v", file.TestCases)
+	}
+	if _, exists := gm.GetGroup(GenerateGroupIDFromPath([]string{"my-crate", "tests", "fibonacci", "fibonacci"})); exists {
+		t.Error("Expected the rstest cases in their module, not a group of their own")
+	}
+
+	failed := gm.FailedTests()
+	if len(failed) != 2 || failed[0].CaseName != "fibonacci::case_2" || failed[1].CaseName != "test_divide[10-0-None]" {
+		t.Errorf("Expected the failed parameter sets in full, got %+v", failed)
+	}
+	if report := gm.formatGroupReport(file); !strings.Contains(report, "| test_divide (parametrized) |") {
+		t.Errorf("Expected test_divide listed as a parametrized subgroup, got:\n%s", report)
+	}
+}
+
+func TestGroupManager_SetupAndTeardownErrors(t *testing.T) {
+	gm := NewGroupManager(t.TempDir(), "", &mockLogger{})
+	setupErr := &ipc.TestError{Message: "ValueError: database is down", Phase: ipc.ErrorPhaseSetup}
+	teardownErr := &ipc.TestError{Message: "OSError: cannot remove tmp", Phase: ipc.ErrorPhaseTeardown}
+	for _, payload := range []ipc.TestCasePayload{
+		{TestName: "test_query", ParentNames: []string{"test_db.py"}, Status: "ERROR", Error: setupErr},
+		{TestName: "test_write", ParentNames: []string{"test_db.py"}, Status: "ERROR", Error: teardownErr},
+		{TestName: "test_read", ParentNames: []string{"test_db.py"}, Status: "FAIL", Error: &ipc.TestError{Message: "assert 1 == 2"}, TeardownError: teardownErr},
+		{TestName: "test_ping", ParentNames: []string{"test_db.py"}, Status: "PASS"},
+	} {
+		if err := gm.ProcessTestCase(ipc.GroupTestCaseEvent{EventType: string(ipc.EventTypeTestCase), Payload: payload}); err != nil {
+			t.Fatalf("ProcessTestCase failed: %v", err)
+		}
+	}
+
+	group, _ := gm.GetGroup(GenerateGroupIDFromPath([]string{"test_db.py"}))
+	if group.Stats.ErroredTests != 2 || group.Stats.FailedTests != 1 || group.Stats.PassedTests != 1 {
+		t.Errorf("Expected 2 errored, 1 failed and 1 passed test, got %+v", group.Stats)
+	}
+	if !group.HasFailures() {
+		t.Error("Expected errored tests to count as failures of the group")
+	}
+	if failed := gm.FailedTests(); len(failed) != 3 || failed[0].FailureKind != ipc.FailureKindHarnessError {
+		t.Errorf("Expected the errored and failed tests, errors as harness errors, got %+v", failed)
+	}
+
+	report := gm.formatGroupReport(group)
+	for _, want := range []string{
+		"- Group tests errored: 2",
+		"✕ test_query [HARNESS_ERROR] [ERRORED]\n  > Setup error:\n```\nValueError: database is down",
+		"✕ test_write [HARNESS_ERROR] [ERRORED]\n  > Teardown error:\n```\nOSError: cannot remove tmp",
+		"```\nassert 1 == 2\n```\n  > Teardown error:\n```\nOSError: cannot remove tmp",
+	} {
+		if !strings.Contains(report, want) {
+			t.Errorf("Expected report to contain %q, got:\n%s", want, report)
+		}
+	}
+}
+
 func TestGroupManager_HierarchyBuilding(t *testing.T) {
 	tmpDir := t.TempDir()
 	log, _ := logger.NewFileLogger()
@@ -359,6 +438,81 @@ func TestGroupManager_OutputCapture(t *testing.T) {
 	}
 }
 
+func TestGroupManager_ConsoleAttribution(t *testing.T) {
+	tmpDir := t.TempDir()
+	log, _ := logger.NewFileLogger()
+	t.Cleanup(func() { _ = log.Close() })
+	gm := NewGroupManager(tmpDir, "", log)
+
+	file := "math.test.js"
+	_ = gm.ProcessGroupDiscovered(ipc.GroupDiscoveredEvent{
+		EventType: string(ipc.EventTypeGroupDiscovered),
+		Payload:   ipc.GroupDiscoveredPayload{GroupName: file},
+	})
+	_ = gm.ProcessGroupDiscovered(ipc.GroupDiscoveredEvent{
+		EventType: string(ipc.EventTypeGroupDiscovered),
+		Payload:   ipc.GroupDiscoveredPayload{GroupName: "Math", ParentNames: []string{file}},
+	})
+	testCase := func(name string) {
+		t.Helper()
+		err := gm.ProcessTestCase(ipc.GroupTestCaseEvent{
+			EventType: string(ipc.EventTypeTestCase),
+			Payload:   ipc.TestCasePayload{TestName: name, ParentNames: []string{file, "Math"}, Status: "PASS"},
+		})
+		if err != nil {
+			t.Fatalf("ProcessTestCase failed: %v", err)
+		}
+	}
+	stdout := func(payload ipc.OutputChunkPayload) {
+		t.Helper()
+		if err := gm.ProcessGroupStdout(ipc.GroupStdoutChunkEvent{Payload: payload}); err != nil {
+			t.Fatalf("ProcessGroupStdout failed: %v", err)
+		}
+	}
+
+	// Logged while the test ran, before its result (verbose runs)
+	stdout(ipc.OutputChunkPayload{GroupName: "Math", ParentNames: []string{file}, TestName: "adds", Level: "log", Chunk: "adding\n"})
+	testCase("adds")
+	// Logged after the results arrived (buffered console)
+	testCase("multiplies")
+	stdout(ipc.OutputChunkPayload{GroupName: "Math", ParentNames: []string{file}, TestName: "multiplies", Level: "info", Chunk: "multiplying\n"})
+	if err := gm.ProcessGroupStderr(ipc.GroupStderrChunkEvent{
+		Payload: ipc.OutputChunkPayload{GroupName: "Math", ParentNames: []string{file}, TestName: "multiplies", Level: "warn", Chunk: "by zero\nagain\n"},
+	}); err != nil {
+		t.Fatalf("ProcessGroupStderr failed: %v", err)
+	}
+	// Logged outside any test
+	stdout(ipc.OutputChunkPayload{GroupName: file, Level: "log", Chunk: "connecting\n"})
+
+	math, _ := gm.GetGroup(GenerateGroupID("Math", []string{file}))
+	consoles := map[string]string{}
+	for _, tc := range math.TestCases {
+		consoles[tc.Name] = tc.Console
+	}
+	if consoles["adds"] != "[log] adding\n" {
+		t.Errorf("Expected adds to keep the entry logged before its result, got %q", consoles["adds"])
+	}
+	if consoles["multiplies"] != "[info] multiplying\n[warn] by zero\n[warn] again\n" {
+		t.Errorf("Expected multiplies' entries with their levels, got %q", consoles["multiplies"])
+	}
+	if math.Stdout != "" || math.Stderr != "" {
+		t.Errorf("Expected the entries to stay out of the group output, got %q and %q", math.Stdout, math.Stderr)
+	}
+
+	content := gm.formatGroupReport(math)
+	if !strings.Contains(content, "## Console output\n\n### adds\n\n```\n[log] adding\n```\n\n### multiplies\n\n") {
+		t.Errorf("Expected a console output section per test, got:\n%s", content)
+	}
+
+	fileGroup, _ := gm.GetGroup(GenerateGroupID(file, nil))
+	if fileGroup.SetupOutput != "[log] connecting\n" {
+		t.Errorf("Expected the entry outside any test as setup output, got %q", fileGroup.SetupOutput)
+	}
+	if content := gm.formatGroupReport(fileGroup); !strings.Contains(content, "## Setup output\n\n```\n[log] connecting\n```\n") {
+		t.Errorf("Expected a setup output section, got:\n%s", content)
+	}
+}
+
 func TestGroupManager_ReportGeneration(t *testing.T) {
 	tmpDir := t.TempDir()
 	log, _ := logger.NewFileLogger()
@@ -662,3 +816,880 @@ func TestFormatGroupReport_OnlyShowCountsGreaterThanZero(t *testing.T) {
 		t.Error("Should not show failed tests line when count is 0")
 	}
 }
+
+func TestFormatGroupReport_ShowsFailureLocation(t *testing.T) {
+	tmpDir := t.TempDir()
+	log, _ := logger.NewFileLogger()
+	t.Cleanup(func() { _ = log.Close() })
+	gm := NewGroupManager(tmpDir, "", log)
+
+	group := &TestGroup{
+		ID:          "doctests-group",
+		Name:        "doctests",
+		ParentNames: []string{"rust-comprehensive"},
+		Status:      TestStatusFail,
+		Created:     time.Now(),
+		Updated:     time.Now(),
+		TestCases: []TestCase{
+			{
+				Name:     "src/lib.rs - calculator::subtract (line 24)",
+				Status:   TestStatusFail,
+				File:     "src/lib.rs",
+				Line:     24,
+				ItemPath: "calculator::subtract",
+				Error:    &TestError{Message: "assertion failed", Location: "src/lib.rs:24"},
+			},
+			{
+				Name:   "generated case",
+				Status: TestStatusFail,
+				File:   "tests/math.test.js",
+				Line:   12,
+				Column: 3,
+				Error:  &TestError{Message: "expect(received).toBe(expected)"},
+			},
+			{Name: "passes", Status: TestStatusPass, File: "tests/math.test.js", Line: 20},
+		},
+		Stats:     TestGroupStats{TotalTests: 3, PassedTests: 1, FailedTests: 2},
+		Subgroups: make(map[string]*TestGroup),
+	}
+
+	content := gm.formatGroupReport(group)
+
+	if !strings.Contains(content, "  > Location: src/lib.rs:24") {
+		t.Errorf("Expected failure location in report, got:\n%s", content)
+	}
+	// Without a failure location, the definition of the failed test
+	if !strings.Contains(content, "  > Location: tests/math.test.js:12:3\n") {
+		t.Errorf("Expected the test's definition as its location, got:\n%s", content)
+	}
+	if strings.Contains(content, "tests/math.test.js:20") {
+		t.Errorf("Expected no location for a passing test, got:\n%s", content)
+	}
+}
+
+func TestFormatGroupReport_CustomHarness(t *testing.T) {
+	tmpDir := t.TempDir()
+	log, _ := logger.NewFileLogger()
+	t.Cleanup(func() { _ = log.Close() })
+	gm := NewGroupManager(tmpDir, "", log)
+
+	discovered := ipc.GroupDiscoveredEvent{EventType: "testGroupDiscovered"}
+	discovered.Payload.GroupName = "plain-harness"
+	discovered.Payload.TargetKind = "test"
+	discovered.Payload.TargetName = "plain_harness"
+	discovered.Payload.CustomHarness = true
+	if err := gm.ProcessGroupDiscovered(discovered); err != nil {
+		t.Fatalf("ProcessGroupDiscovered failed: %v", err)
+	}
+	if err := gm.ProcessStdoutChunk("plain-harness", nil, "checking 3 inputs\n2 of 3 inputs failed\n"); err != nil {
+		t.Fatalf("ProcessStdoutChunk failed: %v", err)
+	}
+	result := ipc.GroupResultEvent{EventType: "testGroupResult"}
+	result.Payload.GroupName = "plain-harness"
+	result.Payload.Status = "FAIL"
+	if err := gm.ProcessGroupResult(result); err != nil {
+		t.Fatalf("ProcessGroupResult failed: %v", err)
+	}
+
+	content := gm.formatGroupReport(gm.groups[GenerateGroupID("plain-harness", nil)])
+	for _, expected := range []string{"status: FAIL", "- Custom harness — individual tests not available", "2 of 3 inputs failed"} {
+		if !strings.Contains(content, expected) {
+			t.Errorf("Expected %q in report, got:\n%s", expected, content)
+		}
+	}
+	if strings.Contains(content, "## Test case results") {
+		t.Errorf("Expected no test cases for a custom harness, got:\n%s", content)
+	}
+}
+
+func TestFormatGroupReport_CapturedOutput(t *testing.T) {
+	tmpDir := t.TempDir()
+	log, _ := logger.NewFileLogger()
+	t.Cleanup(func() { _ = log.Close() })
+	gm := NewGroupManager(tmpDir, "", log)
+
+	group := &TestGroup{
+		ID:          "tests-group",
+		Name:        "tests",
+		ParentNames: []string{"rust-edge-cases"},
+		Status:      TestStatusFail,
+		Created:     time.Now(),
+		Updated:     time.Now(),
+		TestCases: []TestCase{
+			{
+				Name:   "test_assertion_failure",
+				Status: TestStatusFail,
+				Error:  &TestError{Message: "thread 'tests::test_assertion_failure' panicked at src/lib.rs:40:9:\nMath is broken!"},
+				Stdout: "computing 2 + 2\n\nexpecting 5",
+			},
+			{
+				Name:   "test_unexpected_panic",
+				Status: TestStatusFail,
+				Error:  &TestError{Message: "Unexpected panic occurred!"},
+			},
+			{
+				Name:   "test_normal_pass",
+				Status: TestStatusPass,
+				Stdout: "not shown for passing tests",
+			},
+		},
+		Stats:     TestGroupStats{TotalTests: 3, PassedTests: 1, FailedTests: 2},
+		Subgroups: make(map[string]*TestGroup),
+	}
+
+	content := gm.formatGroupReport(group)
+
+	expected := "## Captured output\n\n### test_assertion_failure\n\n```\ncomputing 2 + 2\n\nexpecting 5\n```\n"
+	if !strings.Contains(content, expected) {
+		t.Errorf("Expected captured output section, got:\n%s", content)
+	}
+	if strings.Contains(content, "### test_unexpected_panic") {
+		t.Errorf("Tests without output should not get a captured output entry, got:\n%s", content)
+	}
+	if strings.Contains(content, "not shown for passing tests") {
+		t.Errorf("Passing test output should not be shown, got:\n%s", content)
+	}
+	// The panic stays in the error block, not in the captured output
+	if strings.Index(content, "Math is broken!") > strings.Index(content, "## Captured output") {
+		t.Errorf("Expected panic message before the captured output section, got:\n%s", content)
+	}
+}
+
+func TestGroupManager_StripsANSIFromReports(t *testing.T) {
+	tmpDir := t.TempDir()
+	log, _ := logger.NewFileLogger()
+	t.Cleanup(func() { _ = log.Close() })
+	gm := NewGroupManager(tmpDir, "", log)
+
+	_ = gm.ProcessGroupDiscovered(ipc.GroupDiscoveredEvent{
+		EventType: string(ipc.EventTypeGroupDiscovered),
+		Payload:   ipc.GroupDiscoveredPayload{GroupName: "math.test.js"},
+	})
+	// jest colors its output and redraws its progress in place
+	rawOutput := "\x1b[2K\rRUNS math.test.js\r\x1b[1m\x1b[31mFAIL\x1b[39m\x1b[22m math.test.js\n"
+	if err := gm.ProcessStdoutChunk("math.test.js", nil, rawOutput); err != nil {
+		t.Fatalf("ProcessStdoutChunk failed: %v", err)
+	}
+	err := gm.ProcessTestCase(ipc.GroupTestCaseEvent{
+		EventType: string(ipc.EventTypeTestCase),
+		Payload: ipc.TestCasePayload{
+			TestName:    "adds",
+			ParentNames: []string{"math.test.js"},
+			Status:      "FAIL",
+			Error: &ipc.TestError{
+				Message: "\x1b[2mexpect(\x1b[22m\x1b[31mreceived\x1b[39m\x1b[2m).\x1b[22mtoBe\x1b[2m(\x1b[22m\x1b[32mexpected\x1b[39m\x1b[2m)\x1b[22m",
+				Stack:   "at \x1b]8;;file:///src/math.test.js\x1b\\src/math.test.js:3:15\x1b]8;;\x1b\\",
+			},
+			Stdout: "\x1b[36mconsole.log\x1b[39m\n    computing 1 + 2\n",
+		},
+	})
+	if err != nil {
+		t.Fatalf("ProcessTestCase failed: %v", err)
+	}
+
+	group, _ := gm.GetGroup(GenerateGroupID("math.test.js", nil))
+	tc := group.TestCases[0]
+	if tc.Error.Message != "expect(received).toBe(expected)" || tc.Error.Stack != "at src/math.test.js:3:15" {
+		t.Errorf("Expected a plain-text error, got %q and %q", tc.Error.Message, tc.Error.Stack)
+	}
+	testLog, err := os.ReadFile(GetTestLogFilePath(group, "adds", tmpDir))
+	if err != nil {
+		t.Fatalf("Failed to read the test log: %v", err)
+	}
+	if string(testLog) != "console.log\n    computing 1 + 2\n" {
+		t.Errorf("Test log = %q, want plain text", testLog)
+	}
+
+	content := gm.formatGroupReport(group)
+	if strings.Contains(content, "\x1b") || strings.Contains(content, "\r") {
+		t.Errorf("Expected no escape sequences or carriage returns in the report, got:\n%q", content)
+	}
+	if !strings.Contains(content, "```\nFAIL math.test.js\n```\n") {
+		t.Errorf("Expected the group output as it displays, got:\n%s", content)
+	}
+
+	// The group's output.log keeps the raw bytes
+	outputLog, err := os.ReadFile(GetGroupOutputFilePath(group, tmpDir))
+	if err != nil {
+		t.Fatalf("Failed to read output.log: %v", err)
+	}
+	if string(outputLog) != rawOutput {
+		t.Errorf("output.log = %q, want the raw output %q", outputLog, rawOutput)
+	}
+}
+
+func TestFormatGroupReport_ShouldPanic(t *testing.T) {
+	tmpDir := t.TempDir()
+	log, _ := logger.NewFileLogger()
+	t.Cleanup(func() { _ = log.Close() })
+	gm := NewGroupManager(tmpDir, "", log)
+
+	group := &TestGroup{
+		ID:          "tests-group",
+		Name:        "tests",
+		ParentNames: []string{"rust-edge-cases"},
+		Status:      TestStatusFail,
+		Created:     time.Now(),
+		Updated:     time.Now(),
+		TestCases: []TestCase{
+			{
+				Name:               "test_expected_panic",
+				Status:             TestStatusPass,
+				PanickedAsExpected: true,
+			},
+			{
+				Name:   "test_expected_panic_mismatch",
+				Status: TestStatusFail,
+				Error: &TestError{
+					Message:       "thread 'tests::test_expected_panic_mismatch' panicked at src/lib.rs:7:5:\nThis function always panics!",
+					ExpectedPanic: "This message does not match",
+					ActualPanic:   "This function always panics!",
+				},
+			},
+			{
+				Name:   "test_normal_pass",
+				Status: TestStatusPass,
+			},
+		},
+		Stats:     TestGroupStats{TotalTests: 3, PassedTests: 2, FailedTests: 1},
+		Subgroups: make(map[string]*TestGroup),
+	}
+
+	content := gm.formatGroupReport(group)
+
+	if !strings.Contains(content, "test_expected_panic [PANICKED AS EXPECTED]") {
+		t.Errorf("Expected should_panic pass to be marked, got:\n%s", content)
+	}
+	if strings.Contains(content, "test_normal_pass [PANICKED AS EXPECTED]") {
+		t.Error("Plain passes must not be marked as expected panics")
+	}
+
+	expected := "```diff\n- expected panic: \"This message does not match\"\n+ actual panic:   \"This function always panics!\"\n```\n"
+	if !strings.Contains(content, expected) {
+		t.Errorf("Expected should_panic mismatch diff, got:\n%s", content)
+	}
+}
+
+func TestFormatGroupReport_SnapshotMismatch(t *testing.T) {
+	tmpDir := t.TempDir()
+	log, _ := logger.NewFileLogger()
+	t.Cleanup(func() { _ = log.Close() })
+	gm := NewGroupManager(tmpDir, "", log)
+
+	group := &TestGroup{
+		ID:          "tests-group",
+		Name:        "tests",
+		ParentNames: []string{"rust-insta"},
+		Status:      TestStatusFail,
+		Created:     time.Now(),
+		Updated:     time.Now(),
+		TestCases: []TestCase{
+			{
+				Name:   "greeting",
+				Status: TestStatusFail,
+				Error: &TestError{
+					Message:     "snapshot assertion for 'greeting' failed in line 16",
+					FailureKind: ipc.FailureKindSnapshotMismatch,
+					Snapshot: &ipc.SnapshotMismatch{
+						Name:    "greeting",
+						File:    "src/snapshots/rust_insta__tests__greeting.snap",
+						NewFile: "src/snapshots/rust_insta__tests__greeting.snap.new",
+						Diff:    "-Hello, world\n+Hello, world!\n",
+					},
+				},
+			},
+		},
+		Stats:     TestGroupStats{TotalTests: 1, FailedTests: 1},
+		Subgroups: make(map[string]*TestGroup),
+	}
+
+	content := gm.formatGroupReport(group)
+	for _, want := range []string{
+		"  > Snapshot `greeting`: `src/snapshots/rust_insta__tests__greeting.snap`, new value in `src/snapshots/rust_insta__tests__greeting.snap.new`; run `cargo insta review` to accept or reject it\n",
+		"```diff\n-Hello, world\n+Hello, world!\n```\n",
+	} {
+		if !strings.Contains(content, want) {
+			t.Errorf("Expected the report to contain %q, got:\n%s", want, content)
+		}
+	}
+}
+
+func TestFormatGroupReport_PropertyFailure(t *testing.T) {
+	tmpDir := t.TempDir()
+	log, _ := logger.NewFileLogger()
+	t.Cleanup(func() { _ = log.Close() })
+	gm := NewGroupManager(tmpDir, "", log)
+
+	group := &TestGroup{
+		ID:          "tests-group",
+		Name:        "tests",
+		ParentNames: []string{"rust-proptest"},
+		Status:      TestStatusFail,
+		Created:     time.Now(),
+		Updated:     time.Now(),
+		TestCases: []TestCase{
+			{
+				Name:   "round_trips",
+				Status: TestStatusFail,
+				Error: &TestError{
+					Message: "Test failed: assertion failed: `(left == right)` at src/lib.rs:11.\nminimal failing input: n = -1\n\tsuccesses: 0",
+					Property: &ipc.PropertyFailure{
+						Framework: "proptest",
+						Input:     "n = -1",
+						Seed:      "6c5d30c1f7b4c0e2",
+					},
+				},
+			},
+		},
+		Stats:     TestGroupStats{TotalTests: 1, FailedTests: 1},
+		Subgroups: make(map[string]*TestGroup),
+	}
+
+	content := gm.formatGroupReport(group)
+	expected := "  > Minimal failing input (proptest):\n```\nn = -1\n```\n  > Seed: `cc 6c5d30c1f7b4c0e2`\n```\nTest failed:"
+	if !strings.Contains(content, expected) {
+		t.Errorf("Expected the minimal failing input above the panic, got:\n%s", content)
+	}
+}
+
+func TestFormatGroupReport_Trybuild(t *testing.T) {
+	tmpDir := t.TempDir()
+	log, _ := logger.NewFileLogger()
+	t.Cleanup(func() { _ = log.Close() })
+	gm := NewGroupManager(tmpDir, "", log)
+	gm.SetSourceRoot(tmpDir)
+
+	group := &TestGroup{
+		ID:          "compiletest-group",
+		Name:        "compiletest",
+		ParentNames: []string{"rust-trybuild"},
+		Status:      TestStatusFail,
+		Created:     time.Now(),
+		Updated:     time.Now(),
+		TestCases: []TestCase{
+			{
+				Name:   "ui",
+				Status: TestStatusFail,
+				Error: &TestError{
+					Message: "thread 'ui' panicked at tests/compiletest.rs:4:1:\n2 of 2 tests failed",
+					Trybuild: []ipc.TrybuildCase{
+						{
+							Path:       "tests/ui/missing-semicolon.rs",
+							Status:     "mismatch",
+							Expected:   "error: expected `;`, found `}`\n --> tests/ui/missing-semicolon.rs:2:14",
+							Actual:     "error: expected `;`, found keyword `let`\n --> tests/ui/missing-semicolon.rs:2:14",
+							StderrFile: "tests/ui/missing-semicolon.stderr",
+						},
+						{
+							Path:       "tests/ui/new-case.rs",
+							Status:     "wip",
+							Actual:     "error[E0308]: mismatched types",
+							StderrFile: "tests/ui/new-case.stderr",
+							WipFile:    "wip/new-case.stderr",
+						},
+					},
+				},
+			},
+		},
+		Stats:     TestGroupStats{TotalTests: 1, FailedTests: 1},
+		Subgroups: make(map[string]*TestGroup),
+	}
+
+	content := gm.formatGroupReport(group)
+	for _, expected := range []string{
+		"  > trybuild `tests/ui/missing-semicolon.rs`: mismatch with [tests/ui/missing-semicolon.stderr](../../../tests/ui/missing-semicolon.stderr); rerun with `TRYBUILD=overwrite` to accept the actual output\n" +
+			"```diff\n-error: expected `;`, found `}`\n+error: expected `;`, found keyword `let`\n  --> tests/ui/missing-semicolon.rs:2:14\n```\n",
+		"  > trybuild `tests/ui/new-case.rs`: wip; output written to `wip/new-case.stderr`, move it to [tests/ui/new-case.stderr](../../../tests/ui/new-case.stderr) to accept it\n" +
+			"```\nerror[E0308]: mismatched types\n```\n",
+	} {
+		if !strings.Contains(content, expected) {
+			t.Errorf("Expected report to contain:\n%s\ngot:\n%s", expected, content)
+		}
+	}
+}
+
+func TestLineDiff(t *testing.T) {
+	testCases := []struct {
+		desc, want, got, expected string
+	}{
+		{"same", "a\nb", "a\nb", " a\n b\n"},
+		{"changed line", "a\nb\nc", "a\nx\nc", " a\n-b\n+x\n c\n"},
+		{"added lines", "a", "a\nb\nc", " a\n+b\n+c\n"},
+		{"removed line", "a\nb\nc", "a\nc", " a\n-b\n c\n"},
+	}
+	for _, tc := range testCases {
+		t.Run(tc.desc, func(t *testing.T) {
+			if diff := lineDiff(tc.want, tc.got); diff != tc.expected {
+				t.Errorf("Expected %q, got %q", tc.expected, diff)
+			}
+		})
+	}
+}
+
+func TestFormatGroupReport_Backtrace(t *testing.T) {
+	tmpDir := t.TempDir()
+	log, _ := logger.NewFileLogger()
+	t.Cleanup(func() { _ = log.Close() })
+	gm := NewGroupManager(tmpDir, "", log)
+
+	group := &TestGroup{
+		ID:          "tests-group",
+		Name:        "tests",
+		ParentNames: []string{"rust-edge-cases"},
+		Status:      TestStatusFail,
+		Created:     time.Now(),
+		Updated:     time.Now(),
+		TestCases: []TestCase{
+			{
+				Name:   "test_unwrap_none",
+				Status: TestStatusFail,
+				Error: &TestError{
+					Message:   "thread 'tests::test_unwrap_none' panicked at src/lib.rs:119:11:\ncalled `Option::unwrap()` on a `None` value",
+					Backtrace: "   5: rust_edge_cases::tests::test_unwrap_none\n             at ./src/lib.rs:119:11",
+				},
+			},
+		},
+		Stats:     TestGroupStats{TotalTests: 1, FailedTests: 1},
+		Subgroups: make(map[string]*TestGroup),
+	}
+
+	content := gm.formatGroupReport(group)
+
+	expected := "<details>\n<summary>Backtrace</summary>\n\n```\n   5: rust_edge_cases::tests::test_unwrap_none\n             at ./src/lib.rs:119:11\n```\n\n</details>\n"
+	if !strings.Contains(content, expected) {
+		t.Errorf("Expected collapsible backtrace section, got:\n%s", content)
+	}
+	if strings.Index(content, "called `Option::unwrap()`") > strings.Index(content, "<details>") {
+		t.Errorf("Expected the panic message before the backtrace, got:\n%s", content)
+	}
+}
+
+func TestFormatGroupReport_AssertionDiff(t *testing.T) {
+	tmpDir := t.TempDir()
+	log, _ := logger.NewFileLogger()
+	t.Cleanup(func() { _ = log.Close() })
+	gm := NewGroupManager(tmpDir, "", log)
+
+	group := &TestGroup{
+		ID:          "tests-group",
+		Name:        "tests",
+		ParentNames: []string{"rust-edge-cases"},
+		Status:      TestStatusFail,
+		Created:     time.Now(),
+		Updated:     time.Now(),
+		TestCases: []TestCase{
+			{
+				Name:   "test_point",
+				Status: TestStatusFail,
+				Error: &TestError{
+					Message:  "thread 'tests::test_point' panicked at src/lib.rs:20:9:\nassertion `left == right` failed",
+					Expected: "Point {\n    x: 2,\n}",
+					Actual:   "Point {\n    x: 1,\n}",
+					Type:     "assert_eq",
+				},
+			},
+			{
+				Name:   "test_ne",
+				Status: TestStatusFail,
+				Error: &TestError{
+					Message:  "thread 'tests::test_ne' panicked at src/lib.rs:12:5:\nassertion `left != right` failed",
+					Expected: "3",
+					Actual:   "3",
+					Type:     "assert_ne",
+				},
+			},
+		},
+		Stats:     TestGroupStats{TotalTests: 2, FailedTests: 2},
+		Subgroups: make(map[string]*TestGroup),
+	}
+
+	content := gm.formatGroupReport(group)
+
+	expected := "```diff\n- expected: Point {\n-               x: 2,\n-           }\n+ actual:   Point {\n+               x: 1,\n+           }\n```\n"
+	if !strings.Contains(content, expected) {
+		t.Errorf("Expected aligned multi-line assertion diff, got:\n%s", content)
+	}
+	if !strings.Contains(content, "```diff\n  left:  3\n  right: 3\n```\n") {
+		t.Errorf("Expected assert_ne values as context lines, got:\n%s", content)
+	}
+	// The raw message is kept alongside the diff
+	if !strings.Contains(content, "assertion `left == right` failed") {
+		t.Errorf("Expected the raw panic message, got:\n%s", content)
+	}
+}
+
+func TestFormatGroupReport_SkipReason(t *testing.T) {
+	tmpDir := t.TempDir()
+	log, _ := logger.NewFileLogger()
+	t.Cleanup(func() { _ = log.Close() })
+	gm := NewGroupManager(tmpDir, "", log)
+
+	group := &TestGroup{
+		ID:          "tests-group",
+		Name:        "tests",
+		ParentNames: []string{"rust-basic"},
+		Status:      TestStatusPass,
+		Created:     time.Now(),
+		Updated:     time.Now(),
+		TestCases: []TestCase{
+			{Name: "test_add", Status: TestStatusPass},
+			{Name: "test_ignored", Status: TestStatusSkip, SkipReason: "ignored"},
+			{Name: "test_skipped", Status: TestStatusSkip},
+		},
+		Stats:     TestGroupStats{TotalTests: 3, PassedTests: 1, SkippedTests: 2},
+		Subgroups: make(map[string]*TestGroup),
+	}
+
+	content := gm.formatGroupReport(group)
+
+	if !strings.Contains(content, "- ○ test_ignored [IGNORED]\n") {
+		t.Errorf("Expected ignored test to show its skip reason, got:\n%s", content)
+	}
+	if !strings.Contains(content, "- ○ test_skipped\n") {
+		t.Errorf("Expected skipped test without a reason to be unmarked, got:\n%s", content)
+	}
+}
+
+func TestFormatGroupReport_Attachments(t *testing.T) {
+	tmpDir := t.TempDir()
+	log, _ := logger.NewFileLogger()
+	t.Cleanup(func() { _ = log.Close() })
+	gm := NewGroupManager(tmpDir, "", log)
+
+	group := &TestGroup{
+		ID:          "checkout-group",
+		Name:        "checkout",
+		ParentNames: []string{"chromium", "/app/tests/cart.spec.ts"},
+		Status:      TestStatusFail,
+		Created:     time.Now(),
+		Updated:     time.Now(),
+		TestCases: []TestCase{
+			{
+				Name:   "pays by card",
+				Status: TestStatusFail,
+				Error:  &TestError{Message: "Test timeout of 30000ms exceeded."},
+				Attachments: []Attachment{
+					{Name: "screenshot", Path: "/app/test-results/cart-pays-by-card-chromium/test-failed-1.png"},
+					{Name: "trace", Path: "/app/test-results/cart-pays-by-card-chromium/trace.zip"},
+				},
+			},
+		},
+		Stats:     TestGroupStats{TotalTests: 1, FailedTests: 1},
+		Subgroups: make(map[string]*TestGroup),
+	}
+
+	content := gm.formatGroupReport(group)
+
+	want := "  > Attachment (screenshot): /app/test-results/cart-pays-by-card-chromium/test-failed-1.png\n" +
+		"  > Attachment (trace): /app/test-results/cart-pays-by-card-chromium/trace.zip\n"
+	if !strings.Contains(content, want) {
+		t.Errorf("Expected the attachment paths under the test, got:\n%s", content)
+	}
+}
+
+func TestFormatGroupReport_Benchmarks(t *testing.T) {
+	tmpDir := t.TempDir()
+	log, _ := logger.NewFileLogger()
+	t.Cleanup(func() { _ = log.Close() })
+	gm := NewGroupManager(tmpDir, "", log)
+
+	group := &TestGroup{
+		ID:          "tests-group",
+		Name:        "tests",
+		ParentNames: []string{"rust-benchmarks"},
+		Status:      TestStatusPending,
+		Created:     time.Now(),
+		Updated:     time.Now(),
+		TestCases: []TestCase{
+			{Name: "test_fibonacci", Status: TestStatusPass},
+			{Name: "bench_fibonacci_10", Status: TestStatusBench, BenchMedianNs: 212, BenchDeviationNs: 7},
+			{Name: "bench_factorial_10", Status: TestStatusBench, BenchMedianNs: 1234.5, BenchDeviationNs: 56.25},
+		},
+		Subgroups: make(map[string]*TestGroup),
+	}
+	group.UpdateStats()
+
+	if group.Stats.TotalTests != 1 || group.Stats.Benchmarks != 2 {
+		t.Errorf("Expected 1 test and 2 benchmarks, got %d tests and %d benchmarks",
+			group.Stats.TotalTests, group.Stats.Benchmarks)
+	}
+	if group.Status != TestStatusPass {
+		t.Errorf("Expected group with passing tests and benchmarks to pass, got %s", group.Status)
+	}
+
+	content := gm.formatGroupReport(group)
+
+	expected := "## Benchmarks\n\n" +
+		"| Benchmark | Median (ns/iter) | Deviation (+/- ns) |\n" +
+		"|-----------|------------------|--------------------|\n" +
+		"| bench_fibonacci_10 | 212 | 7 |\n" +
+		"| bench_factorial_10 | 1234.5 | 56.25 |\n"
+	if !strings.Contains(content, expected) {
+		t.Errorf("Expected benchmarks table, got:\n%s", content)
+	}
+	if !strings.Contains(content, "- Group benchmarks: 2\n") {
+		t.Errorf("Expected benchmark count in summary, got:\n%s", content)
+	}
+	if strings.Contains(content, "✓ bench_fibonacci_10") {
+		t.Error("Benchmarks should not be listed as test case results")
+	}
+}
+
+func TestFormatGroupReport_CriterionBenchmarks(t *testing.T) {
+	tmpDir := t.TempDir()
+	log, _ := logger.NewFileLogger()
+	t.Cleanup(func() { _ = log.Close() })
+	gm := NewGroupManager(tmpDir, "", log)
+
+	group := &TestGroup{
+		ID:          "fib-group",
+		Name:        "fib",
+		ParentNames: []string{"simple-bench"},
+		Status:      TestStatusWarn,
+		Created:     time.Now(),
+		Updated:     time.Now(),
+		TestCases: []TestCase{
+			{Name: "10", Status: TestStatusBench, BenchMeanNs: 163.534, BenchMedianNs: 163.1, BenchStdDevNs: 2.25,
+				BenchChangePercent: 11.386, BenchRegressed: true},
+			{Name: "20", Status: TestStatusBench, BenchMeanNs: 20012.5},
+		},
+		Subgroups: make(map[string]*TestGroup),
+	}
+	group.UpdateStats()
+
+	if !group.IsComplete() {
+		t.Error("Expected WARN group to be complete")
+	}
+
+	content := gm.formatGroupReport(group)
+
+	expected := "## Benchmarks\n\n" +
+		"| Benchmark | Mean (ns) | Median (ns) | Std. dev. (ns) | Change |\n" +
+		"|-----------|-----------|-------------|----------------|--------|\n" +
+		"| 10 | 163.53 | 163.10 | 2.25 | +11.39% (regressed) |\n" +
+		"| 20 | 20012.50 | - | - | - |\n"
+	if !strings.Contains(content, expected) {
+		t.Errorf("Expected criterion benchmarks table, got:\n%s", content)
+	}
+	if !strings.Contains(content, "status: WARN\n") {
+		t.Errorf("Expected WARN status in frontmatter, got:\n%s", content)
+	}
+}
+
+func TestFormatGroupReport_GoBenchmarks(t *testing.T) {
+	tmpDir := t.TempDir()
+	log, _ := logger.NewFileLogger()
+	t.Cleanup(func() { _ = log.Close() })
+	gm := NewGroupManager(tmpDir, "", log)
+
+	group := &TestGroup{
+		ID:      "bench-group",
+		Name:    "example.com/bench",
+		Status:  TestStatusPass,
+		Created: time.Now(),
+		Updated: time.Now(),
+		TestCases: []TestCase{
+			{Name: "TestJoin", Status: TestStatusPass},
+			{Name: "BenchmarkJoin-8", Status: TestStatusBench, BenchIterations: 1000000, BenchNsPerOp: 1053,
+				BenchMem: true, BenchBytesPerOp: 24},
+			{Name: "BenchmarkSplit-8", Status: TestStatusBench, BenchIterations: 300000, BenchNsPerOp: 4012.5},
+		},
+		Subgroups: make(map[string]*TestGroup),
+	}
+	group.UpdateStats()
+	if group.Stats.TotalTests != 1 || group.Stats.Benchmarks != 2 {
+		t.Errorf("Expected 1 test and 2 benchmarks, got %d and %d", group.Stats.TotalTests, group.Stats.Benchmarks)
+	}
+
+	// Zero allocations are reported, unknown ones are not
+	expected := "## Benchmarks\n\n" +
+		"| Benchmark | Iterations | ns/op | B/op | allocs/op |\n" +
+		"|-----------|------------|-------|------|-----------|\n" +
+		"| BenchmarkJoin-8 | 1000000 | 1053 | 24 | 0 |\n" +
+		"| BenchmarkSplit-8 | 300000 | 4012.5 | - | - |\n"
+	if content := gm.formatGroupReport(group); !strings.Contains(content, expected) {
+		t.Errorf("Expected go benchmarks table, got:\n%s", content)
+	}
+
+	// Without -benchmem the allocation columns are left out
+	group.TestCases[1].BenchMem = false
+	expected = "| Benchmark | Iterations | ns/op |\n" +
+		"|-----------|------------|-------|\n" +
+		"| BenchmarkJoin-8 | 1000000 | 1053 |\n"
+	if content := gm.formatGroupReport(group); !strings.Contains(content, expected) {
+		t.Errorf("Expected go benchmarks table without allocations, got:\n%s", content)
+	}
+}
+
+func TestFormatGroupReport_SourceSnippet(t *testing.T) {
+	tmpDir := t.TempDir()
+	log, _ := logger.NewFileLogger()
+	t.Cleanup(func() { _ = log.Close() })
+	gm := NewGroupManager(tmpDir, "", log)
+	gm.SetSourceRoot(filepath.Join("..", "..", "tests", "fixtures", "rust-basic"))
+
+	_ = gm.ProcessGroupDiscovered(ipc.GroupDiscoveredEvent{
+		EventType: string(ipc.EventTypeGroupDiscovered),
+		Payload:   ipc.GroupDiscoveredPayload{GroupName: "tests", ParentNames: []string{"rust_basic"}},
+	})
+	failures := []struct {
+		name string
+		err  *ipc.TestError
+	}{
+		{"test_that_fails", &ipc.TestError{Message: "assertion failed", Location: "src/lib.rs:57:9", File: "src/lib.rs", Line: 57, Column: 9}},
+		{"test_changed_file", &ipc.TestError{Message: "assertion failed", File: "src/lib.rs", Line: 5000, Column: 9}},
+		{"test_in_std", &ipc.TestError{Message: "unwrap on None", File: "/rustc/abc/library/core/src/option.rs", Line: 2, External: true}},
+	}
+	for _, failure := range failures {
+		_ = gm.ProcessTestCase(ipc.GroupTestCaseEvent{
+			EventType: string(ipc.EventTypeTestCase),
+			Payload: ipc.TestCasePayload{
+				TestName:    failure.name,
+				ParentNames: []string{"rust_basic", "tests"},
+				Status:      "FAIL",
+				Error:       failure.err,
+			},
+		})
+	}
+
+	group, ok := gm.GetGroup(GenerateGroupID("tests", []string{"rust_basic"}))
+	if !ok {
+		t.Fatal("Expected the tests group")
+	}
+	content := gm.formatGroupReport(group)
+
+	expected := "- ✕ test_that_fails\n" +
+		"  > Location: src/lib.rs:57:9\n" +
+		"```\nassertion failed\n```\n" +
+		"```rust\n" +
+		"  54 |     #[test]\n" +
+		"  55 |     #[should_panic(expected = \"assertion failed\")]\n" +
+		"  56 |     fn test_that_fails() {\n" +
+		"> 57 |         assert_eq!(add(2, 2), 5, \"assertion failed\");\n" +
+		"  58 |     }\n" +
+		"  59 |\n" +
+		"  60 |     #[test]\n" +
+		"```\n"
+	if !strings.Contains(content, expected) {
+		t.Errorf("Expected the failure with its source snippet:\n%s\ngot:\n%s", expected, content)
+	}
+	if strings.Count(content, "```rust") != 1 {
+		t.Errorf("Expected no snippet for a line past the end of the file or an external file, got:\n%s", content)
+	}
+}
+
+func TestSourceSnippet(t *testing.T) {
+	root := t.TempDir()
+	if err := os.MkdirAll(filepath.Join(root, "utils", "src"), 0755); err != nil {
+		t.Fatalf("Failed to create source dir: %v", err)
+	}
+	source := "fn one() {}\nfn two() {}\nfn three() {}\n"
+	if err := os.WriteFile(filepath.Join(root, "utils", "src", "lib.rs"), []byte(source), 0644); err != nil {
+		t.Fatalf("Failed to write source: %v", err)
+	}
+	if err := os.WriteFile(filepath.Join(root, "big.rs"), make([]byte, maxSnippetFileSize+1), 0644); err != nil {
+		t.Fatalf("Failed to write source: %v", err)
+	}
+
+	log, _ := logger.NewFileLogger()
+	t.Cleanup(func() { _ = log.Close() })
+	gm := NewGroupManager(t.TempDir(), "", log)
+
+	testCases := []struct {
+		desc     string
+		root     string
+		err      TestError
+		expected string
+	}{
+		{"first line", root, TestError{File: "utils/src/lib.rs", Line: 1}, "> 1 | fn one() {}\n  2 | fn two() {}\n  3 | fn three() {}\n"},
+		{"location only", root, TestError{Location: "utils/src/lib.rs:3:1"}, "  1 | fn one() {}\n  2 | fn two() {}\n> 3 | fn three() {}\n"},
+		{"found in a parent directory", filepath.Join(root, "utils"), TestError{File: "utils/src/lib.rs", Line: 2}, "  1 | fn one() {}\n> 2 | fn two() {}\n  3 | fn three() {}\n"},
+		{"line out of range", root, TestError{File: "utils/src/lib.rs", Line: 4}, ""},
+		{"missing file", root, TestError{File: "src/gone.rs", Line: 1}, ""},
+		{"file too large", root, TestError{File: "big.rs", Line: 1}, ""},
+		{"external", root, TestError{File: filepath.Join(root, "utils", "src", "lib.rs"), Line: 1, External: true}, ""},
+		{"snippets disabled", "", TestError{File: "utils/src/lib.rs", Line: 1}, ""},
+	}
+	for _, tc := range testCases {
+		t.Run(tc.desc, func(t *testing.T) {
+			gm.SetSourceRoot(tc.root)
+			if snippet := gm.sourceSnippet(&tc.err); snippet != tc.expected {
+				t.Errorf("Expected snippet %q, got %q", tc.expected, snippet)
+			}
+		})
+	}
+}
+
+func TestGroupManager_ProcessRetriedTestCase(t *testing.T) {
+	log, _ := logger.NewFileLogger()
+	t.Cleanup(func() { _ = log.Close() })
+	gm := NewGroupManager(t.TempDir(), "", log)
+
+	parents := []string{"my-crate", "tests"}
+	send := func(process func(ipc.GroupTestCaseEvent) error, name, status, stdout string) {
+		t.Helper()
+		event := ipc.GroupTestCaseEvent{
+			EventType: string(ipc.EventTypeTestCase),
+			Payload:   ipc.TestCasePayload{TestName: name, ParentNames: parents, Status: status, Stdout: stdout},
+		}
+		if err := process(event); err != nil {
+			t.Fatalf("Processing %s failed: %v", name, err)
+		}
+	}
+	send(gm.ProcessTestCase, "test_flaky", "FAIL", "attempt 1\n")
+	send(gm.ProcessTestCase, "test_broken", "FAIL", "")
+	send(gm.ProcessTestCase, "test_ok", "PASS", "")
+	for _, result := range []ipc.GroupResultPayload{
+		{GroupName: "tests", ParentNames: []string{"my-crate"}, Status: "FAIL", Totals: ipc.GroupTotals{Passed: 1, Failed: 2, Total: 3}},
+		{GroupName: "my-crate", Status: "FAIL"},
+	} {
+		if err := gm.ProcessGroupResult(ipc.GroupResultEvent{EventType: string(ipc.EventTypeGroupResult), Payload: result}); err != nil {
+			t.Fatalf("ProcessGroupResult failed: %v", err)
+		}
+	}
+
+	// The first retry passes one test. Tests that hadn't failed are left alone.
+	send(gm.ProcessRetriedTestCase, "test_flaky", "PASS", "attempt 2\n")
+	send(gm.ProcessRetriedTestCase, "test_broken", "FAIL", "")
+	send(gm.ProcessRetriedTestCase, "test_ok", "FAIL", "")
+	gm.ResolveRetriedGroups()
+
+	group, _ := gm.GetGroup(GenerateGroupIDFromPath(parents))
+	if group.Status != TestStatusFail {
+		t.Errorf("Group status = %v, want FAIL while a test still fails", group.Status)
+	}
+	if got := gm.FailedTests(); len(got) != 1 || got[0].Name != "test_broken" {
+		t.Errorf("FailedTests = %v, want test_broken", got)
+	}
+
+	// The second retry passes the other
+	send(gm.ProcessRetriedTestCase, "test_broken", "PASS", "")
+	gm.ResolveRetriedGroups()
+
+	for _, id := range []string{GenerateGroupIDFromPath(parents), GenerateGroupIDFromPath([]string{"my-crate"})} {
+		if g, _ := gm.GetGroup(id); g.Status != TestStatusPass {
+			t.Errorf("Group %s status = %v, want PASS", g.Name, g.Status)
+		}
+	}
+	if group.Stats.FailedTests != 0 || group.Stats.FlakyTests != 2 || group.Stats.PassedTests != 1 {
+		t.Errorf("Failed = %d, flaky = %d, passed = %d, want 0, 2 and 1", group.Stats.FailedTests, group.Stats.FlakyTests, group.Stats.PassedTests)
+	}
+	if got := gm.FlakyTests(); len(got) != 2 {
+		t.Errorf("FlakyTests = %v, want test_broken and test_flaky", got)
+	}
+
+	content := gm.formatGroupReport(group)
+	for _, want := range []string{
+		"- Group tests flaky: 2\n",
+		"- ✓ test_flaky [FLAKY]\n  > Attempts: 2 (FAIL, PASS)\n",
+		"- ✓ test_broken [FLAKY]\n  > Attempts: 3 (FAIL, FAIL, PASS)\n",
+		"### test_flaky\n\n#### Attempt 1 (FAIL)\n\n```\nattempt 1\n```\n\n#### Attempt 2 (PASS)\n\n```\nattempt 2\n```\n\n",
+	} {
+		if !strings.Contains(content, want) {
+			t.Errorf("Report missing %q:\n%s", want, content)
+		}
+	}
+	if strings.Contains(content, "### test_broken") {
+		t.Errorf("Expected no captured output for attempts without output:\n%s", content)
+	}
+}
@@ -8,12 +8,27 @@ import (
 	"regexp"
 	"runtime"
 	"strings"
+	"unicode/utf8"
 )
 
 const (
-	// MaxWindowsPathLength is the maximum path length on Windows
+	// MaxWindowsPathLength is the maximum path length on Windows (MAX_PATH)
 	MaxWindowsPathLength = 260
 
+	// MaxDarwinPathLength is the maximum path length on macOS (PATH_MAX)
+	MaxDarwinPathLength = 1024
+
+	// MaxUnixPathLength is the maximum path length on Linux and other Unix systems (PATH_MAX)
+	MaxUnixPathLength = 4096
+
+	// shortComponentLength is the length directory and log names are cut to when a
+	// path would exceed the platform's limit
+	shortComponentLength = 32
+
+	// reservedFileLength is the room kept after a group directory for the files
+	// written into it: index.md, output.log and logs/<shortened test name>.log
+	reservedFileLength = len("/logs/") + shortComponentLength + len(".log")
+
 	// MaxComponentLength is the maximum length for a single path component
 	MaxComponentLength = 252
 
@@ -39,9 +54,28 @@ var (
 		"COM9": true, "LPT1": true, "LPT2": true, "LPT3": true,
 		"LPT4": true, "LPT5": true, "LPT6": true, "LPT7": true,
 		"LPT8": true, "LPT9": true,
+		"COM0": true, "LPT0": true, "CONIN$": true, "CONOUT$": true,
+		"COM¹": true, "COM²": true, "COM³": true,
+		"LPT¹": true, "LPT²": true, "LPT³": true,
 	}
+
+	// maxPathLength is the longest absolute path report files may have on this platform.
+	// The limits count the terminating NUL.
+	maxPathLength = platformMaxPathLength(runtime.GOOS) - 1
 )
 
+// platformMaxPathLength returns the path length limit of an OS
+func platformMaxPathLength(goos string) int {
+	switch goos {
+	case "windows":
+		return MaxWindowsPathLength
+	case "darwin", "ios":
+		return MaxDarwinPathLength
+	default:
+		return MaxUnixPathLength
+	}
+}
+
 // SanitizeGroupName sanitizes a group name for use as a filesystem path component
 func SanitizeGroupName(name string) string {
 	if name == "" {
@@ -49,6 +83,7 @@ func SanitizeGroupName(name string) string {
 	}
 
 	// Step 1: Remove leading/trailing dots and spaces FIRST
+	name = strings.ToValidUTF8(name, "_")
 	name = trimPattern.ReplaceAllString(name, "")
 
 	// Step 2: Replace path separators with underscores
@@ -83,7 +118,7 @@ func SanitizeGroupName(name string) string {
 		// Keep first part and add hash suffix
 		hash := sha256.Sum256([]byte(name))
 		hashStr := hex.EncodeToString(hash[:4]) // 8 chars
-		name = name[:MaxComponentLength-9] + "_" + hashStr
+		name = truncateComponent(name, MaxComponentLength-9) + "_" + hashStr
 	}
 
 	return name
@@ -98,67 +133,24 @@ func GenerateGroupPath(group *TestGroup, runDir string) string {
 	// Build the full hierarchy path
 	hierarchy := append(group.ParentNames, group.Name)
 
-	// Limit depth to prevent excessive nesting
-	if len(hierarchy) > MaxDepth {
-		// Collapse intermediate levels
-		hierarchy = collapseHierarchy(hierarchy)
-	}
-
-	// Sanitize each component
-	components := make([]string, 0, len(hierarchy)+2)
-	components = append(components, runDir, "reports")
-
-	// Derive the test execution directory from runDir
-	// runDir is something like "/tmp/3pio-open-source/jest/.3pio/runs/[id]"
-	// We need to get "/tmp/3pio-open-source/jest"
-	var testExecDir string
-	if absRunDir, err := filepath.Abs(runDir); err == nil {
-		// Go up from runDir to find the project root (parent of .3pio)
-		testExecDir = filepath.Dir(filepath.Dir(absRunDir)) // Go up twice: [id] -> runs -> .3pio
-		testExecDir = filepath.Dir(testExecDir)             // Go up once more: .3pio -> project root
-
-		// Resolve symlinks for consistent comparison
-		if resolved, err := filepath.EvalSymlinks(testExecDir); err == nil {
-			testExecDir = resolved
+	// Groups tracked by a GroupManager carry directory names that are unique among
+	// their siblings; others are sanitized here
+	components := group.dirNames
+	if len(components) != len(hierarchy) {
+		// Limit depth to prevent excessive nesting
+		if len(hierarchy) > MaxDepth {
+			// Collapse intermediate levels
+			hierarchy = collapseHierarchy(hierarchy)
 		}
+		components = sanitizeHierarchy(hierarchy, runDir)
+	} else if len(components) > MaxDepth {
+		components = collapseHierarchy(components)
 	}
 
-	for _, part := range hierarchy {
-		// For absolute paths, make them relative to the test execution directory
-		if strings.HasPrefix(part, "/") && testExecDir != "" {
-			// Resolve symlinks in the file path for consistent comparison
-			resolvedPart := part
-			if resolved, err := filepath.EvalSymlinks(part); err == nil {
-				resolvedPart = resolved
-			}
-
-			// Try to make the path relative to the test execution directory
-			if relPath, err := filepath.Rel(testExecDir, resolvedPart); err == nil {
-				// Only use relative path if it doesn't go outside the project (no ../..)
-				if !strings.HasPrefix(relPath, "..") {
-					part = relPath
-				}
-			}
-		}
-
-		// Always sanitize the entire group name as a single unit
-		// This ensures Go package names like "github.com/zk/3pio" become "github_com_zk_3pio"
-		// and file paths like "./src/test.js" become "_src_test_js"
-		sanitized := SanitizeGroupName(part)
-		if sanitized != "" {
-			components = append(components, sanitized)
-		}
-	}
-
-	// Build the path
-	path := filepath.Join(components...)
-
-	// Check Windows path length limit
-	if runtime.GOOS == "windows" && len(path) > MaxWindowsPathLength {
-		path = shortenPath(path, runDir, hierarchy)
-	}
-
-	return path
+	// Build the path, shortening it to the platform's path length limit
+	reportsDir := filepath.Join(runDir, "reports")
+	components = fitPathLength(absPathLength(reportsDir), components)
+	return filepath.Join(append([]string{reportsDir}, components...)...)
 }
 
 // GenerateGroupPathFromHierarchy generates a filesystem path from a hierarchy slice
@@ -172,61 +164,88 @@ func GenerateGroupPathFromHierarchy(hierarchy []string, runDir string) string {
 		hierarchy = collapseHierarchy(hierarchy)
 	}
 
-	// Sanitize each component
-	components := make([]string, 0, len(hierarchy)+2)
-	components = append(components, runDir, "reports")
+	// Build the path, shortening it to the platform's path length limit
+	reportsDir := filepath.Join(runDir, "reports")
+	components := fitPathLength(absPathLength(reportsDir), sanitizeHierarchy(hierarchy, runDir))
+	return filepath.Join(append([]string{reportsDir}, components...)...)
+}
+
+// sanitizeHierarchy turns each name of a group hierarchy into a directory name
+func sanitizeHierarchy(hierarchy []string, runDir string) []string {
+	projectRoot := projectRootOf(runDir)
+	components := make([]string, 0, len(hierarchy))
+	for _, part := range hierarchy {
+		components = append(components, sanitizeComponent(part, projectRoot))
+	}
+	return components
+}
 
-	// Derive the test execution directory from runDir
+// projectRootOf derives the test execution directory from runDir
+func projectRootOf(runDir string) string {
 	// runDir is something like "/tmp/3pio-open-source/jest/.3pio/runs/[id]"
 	// We need to get "/tmp/3pio-open-source/jest"
-	var testExecDir string
-	if absRunDir, err := filepath.Abs(runDir); err == nil {
-		// Go up from runDir to find the project root (parent of .3pio)
-		testExecDir = filepath.Dir(filepath.Dir(absRunDir)) // Go up twice: [id] -> runs -> .3pio
-		testExecDir = filepath.Dir(testExecDir)             // Go up once more: .3pio -> project root
-
-		// Resolve symlinks for consistent comparison
-		if resolved, err := filepath.EvalSymlinks(testExecDir); err == nil {
-			testExecDir = resolved
-		}
+	absRunDir, err := filepath.Abs(runDir)
+	if err != nil {
+		return ""
 	}
+	// Go up from runDir to find the project root (parent of .3pio)
+	testExecDir := filepath.Dir(filepath.Dir(absRunDir)) // Go up twice: [id] -> runs -> .3pio
+	testExecDir = filepath.Dir(testExecDir)              // Go up once more: .3pio -> project root
 
-	for _, part := range hierarchy {
-		// For absolute paths, make them relative to the test execution directory
-		if strings.HasPrefix(part, "/") && testExecDir != "" {
-			// Resolve symlinks in the file path for consistent comparison
-			resolvedPart := part
-			if resolved, err := filepath.EvalSymlinks(part); err == nil {
-				resolvedPart = resolved
-			}
+	// Resolve symlinks for consistent comparison
+	if resolved, err := filepath.EvalSymlinks(testExecDir); err == nil {
+		testExecDir = resolved
+	}
+	return testExecDir
+}
 
-			// Try to make the path relative to the test execution directory
-			if relPath, err := filepath.Rel(testExecDir, resolvedPart); err == nil {
-				// Only use relative path if it doesn't go outside the project (no ../..)
-				if !strings.HasPrefix(relPath, "..") {
-					part = relPath
-				}
-			}
+// sanitizeComponent turns one group name into a directory name, making absolute
+// paths inside the project relative to it first
+func sanitizeComponent(part string, projectRoot string) string {
+	// For absolute paths, make them relative to the test execution directory
+	if strings.HasPrefix(part, "/") && projectRoot != "" {
+		// Resolve symlinks in the file path for consistent comparison
+		resolvedPart := part
+		if resolved, err := filepath.EvalSymlinks(part); err == nil {
+			resolvedPart = resolved
 		}
 
-		// Always sanitize the entire group name as a single unit
-		// This ensures Go package names like "github.com/zk/3pio" become "github_com_zk_3pio"
-		// and file paths like "./src/test.js" become "_src_test_js"
-		sanitized := SanitizeGroupName(part)
-		if sanitized != "" {
-			components = append(components, sanitized)
+		// Try to make the path relative to the test execution directory
+		if relPath, err := filepath.Rel(projectRoot, resolvedPart); err == nil {
+			// Only use relative path if it doesn't go outside the project (no ../..)
+			if !strings.HasPrefix(relPath, "..") {
+				part = relPath
+			}
 		}
 	}
 
-	// Build the path
-	path := filepath.Join(components...)
+	// Always sanitize the entire group name as a single unit
+	// This ensures Go package names like "github.com/zk/3pio" become "github_com_zk_3pio"
+	// and file paths like "./src/test.js" become "_src_test_js"
+	return SanitizeGroupName(part)
+}
 
-	// Check Windows path length limit
-	if runtime.GOOS == "windows" && len(path) > MaxWindowsPathLength {
-		path = shortenPath(path, runDir, hierarchy)
+// withNameHash appends a short hash of the original name to a sanitized name, so two
+// names that sanitize alike get different directories. attempt > 0 rehashes for the
+// (unlikely) case where the suffixed name is taken too.
+func withNameHash(sanitized, original string, attempt int) string {
+	if attempt > 0 {
+		original = fmt.Sprintf("%s#%d", original, attempt)
 	}
+	hash := sha256.Sum256([]byte(original))
+	return truncateComponent(sanitized, MaxComponentLength-9) + "_" + hex.EncodeToString(hash[:4])
+}
 
-	return path
+// truncateComponent cuts a name to at most maxLen bytes without splitting a character
+func truncateComponent(name string, maxLen int) string {
+	if len(name) <= maxLen {
+		return name
+	}
+	name = name[:maxLen]
+	for len(name) > 0 && !utf8.ValidString(name) {
+		name = name[:len(name)-1]
+	}
+	return name
 }
 
 // collapseHierarchy reduces hierarchy depth by combining intermediate levels
@@ -261,42 +280,67 @@ func collapseHierarchy(hierarchy []string) []string {
 	return result
 }
 
-// shortenPath shortens a path that exceeds Windows path length limit
-func shortenPath(longPath string, runDir string, hierarchy []string) string {
-	// Strategy: Use hash-based short names for intermediate directories
-
-	if len(hierarchy) == 0 {
-		return runDir
+// fitPathLength shortens the directory names of a group's report until its absolute
+// path, plus the files written into it, fits maxPathLength. baseLen is the length of
+// the absolute reports directory. Intermediate directories are shortened first, then
+// merged into one hashed directory, and only then the group's own name, so the last
+// component stays recognizable. The result depends only on the inputs, so a group
+// keeps its directory for the whole run; index.json records where it ended up.
+func fitPathLength(baseLen int, components []string) []string {
+	limit := maxPathLength - reservedFileLength
+	if joinedLength(baseLen, components) <= limit || len(components) == 0 {
+		return components
 	}
 
-	// Always keep the last component readable
-	lastComponent := SanitizeGroupName(hierarchy[len(hierarchy)-1])
+	fitted := append([]string(nil), components...)
+	last := len(fitted) - 1
 
-	// Calculate available space
-	baseLen := len(runDir) + len(lastComponent) + 10 // Extra space for separators
-	availableSpace := MaxWindowsPathLength - baseLen
+	// Cut intermediate directory names, from the top down
+	for i := 0; i < last && joinedLength(baseLen, fitted) > limit; i++ {
+		fitted[i] = shortenComponent(fitted[i])
+	}
 
-	if availableSpace <= 0 {
-		// Even the last component is too long, hash everything
-		hash := sha256.Sum256([]byte(strings.Join(hierarchy, ":")))
-		hashStr := hex.EncodeToString(hash[:8])
-		return filepath.Join(runDir, hashStr)
+	// Merge all intermediate directories into one
+	if joinedLength(baseLen, fitted) > limit && last > 1 {
+		hash := sha256.Sum256([]byte(strings.Join(components[:last], "/")))
+		fitted = []string{"_h_" + hex.EncodeToString(hash[:8]), fitted[last]}
+		last = 1
 	}
 
-	// Build shortened path
-	components := []string{runDir}
+	// Cut the group's own name. If the reports directory itself is too deep there is
+	// nothing more to shorten, and writing may still fail.
+	if joinedLength(baseLen, fitted) > limit {
+		fitted[last] = shortenComponent(fitted[last])
+	}
 
-	if len(hierarchy) > 1 {
-		// Hash all intermediate components
-		intermediate := hierarchy[:len(hierarchy)-1]
-		hash := sha256.Sum256([]byte(strings.Join(intermediate, ":")))
-		hashStr := hex.EncodeToString(hash[:8])
-		components = append(components, hashStr)
+	return fitted
+}
+
+// joinedLength returns the length of a path made of a base and components
+func joinedLength(baseLen int, components []string) int {
+	length := baseLen
+	for _, component := range components {
+		length += len(component) + 1 // Separator
 	}
+	return length
+}
 
-	components = append(components, lastComponent)
+// shortenComponent cuts a directory or log name to shortComponentLength, keeping a
+// hash of the whole name so shortened names stay unique
+func shortenComponent(name string) string {
+	if len(name) <= shortComponentLength {
+		return name
+	}
+	hash := sha256.Sum256([]byte(name))
+	return truncateComponent(name, shortComponentLength-9) + "_" + hex.EncodeToString(hash[:4])
+}
 
-	return filepath.Join(components...)
+// absPathLength returns the length a path has once made absolute
+func absPathLength(path string) int {
+	if absPath, err := filepath.Abs(path); err == nil {
+		return len(absPath)
+	}
+	return len(path)
 }
 
 // GetReportFilePath returns the path to the report file for a group
@@ -309,6 +353,13 @@ func GetReportFilePath(group *TestGroup, runDir string) string {
 func GetTestLogFilePath(group *TestGroup, testName string, runDir string) string {
 	groupPath := GenerateGroupPath(group, runDir)
 	sanitizedTestName := SanitizeGroupName(testName)
+	if group != nil && group.logNames[testName] != "" {
+		sanitizedTestName = group.logNames[testName]
+	}
+	// Long test names are cut when the log would exceed the path length limit
+	if absPathLength(groupPath)+len("/logs/")+len(sanitizedTestName)+len(".log") > maxPathLength {
+		sanitizedTestName = shortenComponent(sanitizedTestName)
+	}
 	return filepath.Join(groupPath, "logs", sanitizedTestName+".log")
 }
 
@@ -1,10 +1,16 @@
 package report
 
 import (
+	"math/rand"
 	"path/filepath"
 	"runtime"
 	"strings"
 	"testing"
+	"unicode/utf16"
+	"unicode/utf8"
+
+	"github.com/zk/3pio/internal/ipc"
+	"github.com/zk/3pio/internal/logger"
 )
 
 func TestSanitizeGroupName(t *testing.T) {
@@ -430,3 +436,127 @@ func TestGetRelativeReportPath(t *testing.T) {
 		t.Errorf("Should end with index.md: %s", relPath)
 	}
 }
+
+// adversarialNames returns names that are hard to turn into file names: unicode,
+// emoji, very long names, trailing dots and spaces, reserved Windows device names,
+// control characters, invalid UTF-8, and names that differ only in case or in
+// characters the sanitizer replaces
+func adversarialNames(rng *rand.Rand, n int) []string {
+	names := []string{
+		"module_a::test", "module_A::test", "Module_a::test",
+		"param/a", "param_a", "param.a", "param-a", "param a", "param:a", "param\\a",
+		"CON", "con", "Nul.txt", "COM1", "lpt9", "COM¹", "CONIN$", "AUX ", " prn.",
+		"trailing.", "trailing ", "trailing. . .", "...", "   ", "",
+		"ünïcödé", "名前", "emoji_🦀_test", "🦀", "🦀🦀",
+		strings.Repeat("a", 300), strings.Repeat("a", 300) + "b", strings.Repeat("é", 150), strings.Repeat("🦀", 80),
+		"bad\xffutf8", "ctrl\x01\x1fchars", "tab\tand\nnewline",
+	}
+	alphabet := []rune("aA_-./\\: .é🦀<>|?*\"\x00\x7f名")
+	for i := 0; i < n; i++ {
+		length := 1 + rng.Intn(12)
+		if i%10 == 0 {
+			length = 250 + rng.Intn(60)
+		}
+		runes := make([]rune, length)
+		for j := range runes {
+			runes[j] = alphabet[rng.Intn(len(alphabet))]
+		}
+		names = append(names, string(runes))
+	}
+	return names
+}
+
+// checkWindowsComponent reports why name can't be a file name on Windows, or ""
+func checkWindowsComponent(name string) string {
+	switch {
+	case name == "":
+		return "empty"
+	case !utf8.ValidString(name):
+		return "invalid UTF-8"
+	case len(utf16.Encode([]rune(name))) > 255 || len(name) > 255:
+		return "too long"
+	case strings.ContainsAny(name, `<>:"/\|?*`):
+		return "reserved character"
+	case strings.HasSuffix(name, ".") || strings.HasSuffix(name, " "):
+		return "trailing dot or space"
+	}
+	for _, r := range name {
+		if r < 0x20 {
+			return "control character"
+		}
+	}
+	base := strings.ToUpper(name)
+	if i := strings.Index(base, "."); i >= 0 {
+		base = base[:i]
+	}
+	if windowsReservedNames[strings.TrimRight(base, " ")] {
+		return "reserved device name"
+	}
+	return ""
+}
+
+func TestSanitizeGroupName_WindowsValid(t *testing.T) {
+	rng := rand.New(rand.NewSource(1027))
+	for _, name := range adversarialNames(rng, 500) {
+		sanitized := SanitizeGroupName(name)
+		if problem := checkWindowsComponent(sanitized); problem != "" {
+			t.Errorf("SanitizeGroupName(%q) = %q: %s", name, sanitized, problem)
+		}
+		if again := SanitizeGroupName(name); again != sanitized {
+			t.Errorf("SanitizeGroupName(%q) is not deterministic: %q then %q", name, sanitized, again)
+		}
+	}
+}
+
+func TestGenerateGroupPath_UniqueAmongSiblings(t *testing.T) {
+	rng := rand.New(rand.NewSource(1027))
+	names := adversarialNames(rng, 300)
+
+	dirsOf := func() map[string]string {
+		tmpDir := t.TempDir()
+		log, _ := logger.NewFileLogger()
+		t.Cleanup(func() { _ = log.Close() })
+		gm := NewGroupManager(tmpDir, "", log)
+
+		dirs := make(map[string]string)
+		for _, name := range names {
+			for _, parents := range [][]string{{"my-crate"}, {"my-crate", "tests"}} {
+				_ = gm.ProcessGroupDiscovered(ipc.GroupDiscoveredEvent{
+					EventType: string(ipc.EventTypeGroupDiscovered),
+					Payload:   ipc.GroupDiscoveredPayload{GroupName: name, ParentNames: parents},
+				})
+			}
+		}
+
+		seen := make(map[string]string) // lowercased path -> name of the group using it
+		for _, group := range gm.groups {
+			path := GenerateGroupPath(group, tmpDir)
+			rel, _ := filepath.Rel(tmpDir, path)
+			for _, component := range strings.Split(rel, string(filepath.Separator)) {
+				if problem := checkWindowsComponent(component); problem != "" {
+					t.Errorf("Group %q got directory component %q: %s", group.Name, component, problem)
+				}
+			}
+			key := strings.ToLower(rel)
+			if other, taken := seen[key]; taken {
+				t.Errorf("Groups %q and %q share the directory %s", other, BuildHierarchicalPath(group), rel)
+			}
+			seen[key] = BuildHierarchicalPath(group)
+			dirs[BuildHierarchicalPath(group)] = rel
+		}
+		return dirs
+	}
+
+	// Same names in the same order give the same directories
+	first, second := dirsOf(), dirsOf()
+	for name, dir := range first {
+		if second[name] != dir {
+			t.Errorf("Group %q got %s in one run and %s in another", name, dir, second[name])
+		}
+	}
+
+	// Names that don't collide keep their plain sanitized directory
+	if dir := first["my-crate → tests → ünïcödé"]; dir != filepath.Join("reports", "my_crate", "tests", "ünïcödé") {
+		t.Errorf("Expected a plain directory for a unique name, got %s", dir)
+	}
+}
@@ -1,7 +1,11 @@
 package report
 
 import (
+	"fmt"
+	"strings"
 	"time"
+
+	"github.com/zk/3pio/internal/ipc"
 )
 
 // TestStatus represents the status of a test or group
@@ -14,9 +18,16 @@ const (
 	TestStatusFail    TestStatus = "FAIL"
 	TestStatusSkip    TestStatus = "SKIP"
 	TestStatusNoTests TestStatus = "NO_TESTS"
-	TestStatusError   TestStatus = "ERROR"
+	TestStatusError   TestStatus = "ERROR" // Group errored, or a test's setup or teardown failed (pytest fixtures)
 	TestStatusXFail   TestStatus = "XFAIL" // Test failed as expected
 	TestStatusXPass   TestStatus = "XPASS" // Test passed unexpectedly
+	TestStatusBench   TestStatus = "BENCH" // Benchmark result, counted apart from tests
+	TestStatusWarn    TestStatus = "WARN"  // Completed with a warning, e.g. a benchmark regressed
+	TestStatusFlaky   TestStatus = "FLAKY" // Failed, then passed when retried (--retries or the runner's own retries)
+
+	TestStatusInterrupted TestStatus = "INTERRUPTED" // Still running when the run was stopped (--timeout, Ctrl-C)
+
+	TestStatusQuarantinedFail TestStatus = "QUARANTINED_FAIL" // Failed, but is on the quarantine list so the run doesn't fail
 )
 
 // TestGroup represents a hierarchical group of tests (file, describe block, class, etc.)
@@ -28,13 +39,25 @@ type TestGroup struct {
 	ParentNames []string // Full hierarchy from root (excludes this group's name)
 	Depth       int      // Depth in hierarchy (0 for root)
 
+	// Rust test target the group's tests were built into, empty for other runners
+	TargetKind string // "lib", "bin", "test", "example" or "bench"
+	TargetName string // Target name (e.g., "integration_test")
+
+	// Rust target with harness = false, reported by exit code without test cases
+	CustomHarness bool
+
+	// A parametrized test, with a test case per parameter set (pytest's test_divide[10-2-5],
+	// rstest's case_1)
+	Parametrized bool
+
 	// Status and timing
-	Status    TestStatus
-	Duration  time.Duration
-	StartTime time.Time
-	EndTime   time.Time
-	Created   time.Time
-	Updated   time.Time
+	Status     TestStatus
+	SkipReason string // Why a skipped group didn't run (e.g., "no_test_files")
+	Duration   time.Duration
+	StartTime  time.Time
+	EndTime    time.Time
+	Created    time.Time
+	Updated    time.Time
 
 	// Test data
 	TestCases []TestCase            // Direct test cases in this group
@@ -46,9 +69,24 @@ type TestGroup struct {
 	// Error information for group-level failures
 	ErrorInfo *TestError
 
-	// Output
-	Stdout string // Accumulated stdout for this group
-	Stderr string // Accumulated stderr for this group
+	// Output. Only the last maxOutputTail bytes are kept, the full output is in the
+	// group's output.log.
+	Stdout          string // Tail of the stdout for this group
+	Stderr          string // Tail of the stderr for this group
+	OutputTruncated bool   // Stdout or Stderr dropped earlier output
+
+	// Tail of the console entries logged outside any test, e.g. in beforeAll, one
+	// "[level] message" line per line (Jest)
+	SetupOutput string
+
+	// Test case ID -> index in TestCases, so adding a test case doesn't rescan the others
+	testCaseIndex map[string]int
+
+	// Directory name of each level of the hierarchy and log file name of each test,
+	// unique among their siblings. Set by the GroupManager; groups built elsewhere
+	// sanitize their names on the fly.
+	dirNames []string
+	logNames map[string]string // Test name -> log file name without .log
 }
 
 // TestGroupStats holds aggregated statistics for a test group
@@ -59,8 +97,13 @@ type TestGroupStats struct {
 	SkippedTests int
 	XFailedTests int  // Tests that failed as expected
 	XPassedTests int  // Tests that passed unexpectedly
+	Benchmarks   int  // Benchmark results (not included in TotalTests)
 	SetupFailed  bool // Indicates this group failed during setup/initialization
 
+	QuarantinedFailedTests int // Quarantined tests that failed (not included in FailedTests)
+	FlakyTests             int // Tests that failed, then passed when retried (not included in FailedTests)
+	ErroredTests           int // Tests whose setup or teardown failed (not included in FailedTests)
+
 	// Recursive counts (includes subgroups)
 	TotalTestsRecursive   int
 	PassedTestsRecursive  int
@@ -68,28 +111,104 @@ type TestGroupStats struct {
 	SkippedTestsRecursive int
 	XFailedTestsRecursive int
 	XPassedTestsRecursive int
+	BenchmarksRecursive   int
+
+	QuarantinedFailedTestsRecursive int
+	FlakyTestsRecursive             int
+	ErroredTestsRecursive           int
 }
 
 // TestCase represents an individual test
 type TestCase struct {
 	// Identification
 	ID      string // Unique ID for this test case
+	TestID  string // Stable ID for tools that track tests across runs (see TestID)
 	GroupID string // ID of parent group
 	Name    string // Test name (e.g., "should add two numbers")
 
+	// Full name of a parameter set as the runner's filter takes it (e.g.,
+	// "test_divide[10-0-None]", "fibonacci::case_1"), empty if not parametrized
+	CaseName string
+
 	// Status and timing
 	Status      TestStatus
 	Duration    time.Duration
 	StartTime   time.Time
 	EndTime     time.Time
 	XFailReason string // Reason for expected failure (xfail marker)
+	SkipReason  string // Why a skipped test didn't run ("ignored", "not_run" or "todo")
+	SkipMessage string // Reason the test gave for skipping (e.g., pytest skip markers)
+
+	// Source location
+	File     string // Source file that defines the test (e.g., "src/lib.rs")
+	Line     int    // Line of the test definition in File (0 if unknown)
+	Column   int    // Column of the test definition (0 if unknown)
+	ItemPath string // Documented item for doctests (e.g., "calculator::add")
+
+	// Runner annotations
+	Attempts           []TestAttempt // Every execution when the runner retried the test (empty if run once)
+	Attachments        []Attachment  // Files the runner saved for the test, e.g. Playwright traces
+	Slow               bool          // Runner flagged the test as slow
+	Leaked             bool          // Test leaked handles or subprocesses
+	PanickedAsExpected bool          // Passed because it panicked as intended (#[should_panic])
+	Quarantined        bool          // On the quarantine list of known-flaky tests
+
+	// Benchmark results (Status == TestStatusBench)
+	BenchMedianNs      float64 // Median time per iteration in nanoseconds
+	BenchDeviationNs   float64 // Deviation (+/-) of the median in nanoseconds
+	BenchMeanNs        float64 // Mean time in nanoseconds (criterion)
+	BenchStdDevNs      float64 // Standard deviation in nanoseconds (criterion)
+	BenchChangePercent float64 // Change of the mean against the baseline, in percent
+	BenchRegressed     bool    // Benchmark regressed against the baseline
+	BenchIterations    int64   // Iterations the benchmark ran (go test -bench)
+	BenchNsPerOp       float64 // Mean time per iteration in nanoseconds (go test -bench)
+	BenchMem           bool    // Benchmark reported its allocations (-benchmem)
+	BenchBytesPerOp    int64   // Bytes allocated per iteration, if BenchMem
+	BenchAllocsPerOp   int64   // Allocations per iteration, if BenchMem
+
+	// Error information. An ERROR test's Error has the Phase that failed; a failed
+	// test whose teardown failed too has it in TeardownError.
+	Error         *TestError
+	TeardownError *TestError
+
+	// Output. Stdout and Stderr hold at most the last maxOutputTail bytes, the refs
+	// locate the full output in the test's log file.
+	Stdout          string     // Tail of the stdout captured during this test
+	Stderr          string     // Tail of the stderr captured during this test
+	StdoutRef       *OutputRef // Full stdout on disk (nil if none)
+	StderrRef       *OutputRef // Full stderr on disk (nil if none)
+	OutputTruncated bool       // Stdout or Stderr dropped earlier output
+
+	// Tail of the console entries the test logged, one "[level] message" line per
+	// line (Jest)
+	Console string
+}
+
+// DefinitionLocation returns where the test is defined as "file:line" or
+// "file:line:col", or "" when the runner didn't say
+func (tc *TestCase) DefinitionLocation() string {
+	switch {
+	case tc.File == "" || tc.Line <= 0:
+		return ""
+	case tc.Column > 0:
+		return fmt.Sprintf("%s:%d:%d", tc.File, tc.Line, tc.Column)
+	default:
+		return fmt.Sprintf("%s:%d", tc.File, tc.Line)
+	}
+}
 
-	// Error information
-	Error *TestError
+// TestAttempt represents a single execution of a retried test
+type TestAttempt struct {
+	Status   TestStatus
+	Duration time.Duration
+	Stdout   string // Tail of the stdout captured during this attempt
+	Stderr   string // Tail of the stderr captured during this attempt
+}
 
-	// Output
-	Stdout string // stdout captured during this test
-	Stderr string // stderr captured during this test
+// Attachment is a file the runner saved for a test
+type Attachment struct {
+	Name string // e.g., "trace", "screenshot"
+	Path string // Where the runner saved it
 }
 
 // TestError represents error information for a failed test or group
@@ -99,7 +218,61 @@ type TestError struct {
 	Expected string // Expected value (for assertions)
 	Actual   string // Actual value (for assertions)
 	Location string // File:line where error occurred
-	Type     string // Type of error (e.g., "AssertionError", "SETUP_FAILURE")
+	Type     string // Type of error (e.g., "AssertionError", "SETUP_FAILURE", "assert_eq")
+
+	FailureKind string // Why it failed: an ipc.FailureKind* constant
+
+	// Location split into its parts, when the runner reports them
+	File     string // Relative to the project (Rust: workspace) root
+	Line     int
+	Column   int  // 0 if unknown
+	External bool // File is outside the project, e.g. in the cargo registry or the Rust sysroot
+
+	CustomMessage string // Message given to the failed assertion, if any
+
+	// Rust #[should_panic(expected = ...)] mismatch
+	ExpectedPanic string // Substring the panic message was expected to contain
+	ActualPanic   string // Panic message the test actually produced
+
+	Backtrace         string // Rust panic backtrace (RUST_BACKTRACE=1), shown collapsed
+	DataRace          string // Race detector reports with the stacks of both accesses (go test -race)
+	UndefinedBehavior string // miri's report of the undefined behavior that stopped the test (cargo miri test)
+
+	Phase string // ipc.ErrorPhaseSetup or ipc.ErrorPhaseTeardown for an ERROR test, "" otherwise
+
+	Snapshot *ipc.SnapshotMismatch // Snapshot the value didn't match (Rust insta), nil otherwise
+	Property *ipc.PropertyFailure  // Minimized case of a failed property test (Rust proptest, quickcheck), nil otherwise
+	Trybuild []ipc.TrybuildCase    // UI test cases whose compiler output didn't match (Rust trybuild)
+
+	Snippet string // Numbered source lines around Line, read when the failure is recorded
+}
+
+// AssertionDiff renders the expected and actual values of a failed assertion as
+// diff lines, aligned so multi-line values stay readable. assert_ne! failures show
+// both sides as context lines since they were expected to differ. It returns ""
+// when the error has no values.
+func (e *TestError) AssertionDiff() string {
+	if e.Expected == "" && e.Actual == "" {
+		return ""
+	}
+	if e.Type == "assert_ne" {
+		return formatDiffValue(" ", "left:  ", e.Actual) + formatDiffValue(" ", "right: ", e.Expected)
+	}
+	return formatDiffValue("-", "expected: ", e.Expected) + formatDiffValue("+", "actual:   ", e.Actual)
+}
+
+// formatDiffValue writes a labeled value as diff lines, indenting continuation lines under the first
+func formatDiffValue(marker, label, value string) string {
+	var b strings.Builder
+	indent := strings.Repeat(" ", len(label))
+	for i, line := range strings.Split(value, "\n") {
+		if i == 0 {
+			b.WriteString(marker + " " + label + line + "\n")
+		} else {
+			b.WriteString(marker + " " + indent + line + "\n")
+		}
+	}
+	return b.String()
 }
 
 // IsComplete returns true if the group has finished executing
@@ -109,7 +282,9 @@ func (g *TestGroup) IsComplete() bool {
 		g.Status == TestStatusSkip ||
 		g.Status == TestStatusError ||
 		g.Status == TestStatusXFail ||
-		g.Status == TestStatusXPass
+		g.Status == TestStatusXPass ||
+		g.Status == TestStatusWarn ||
+		g.Status == TestStatusInterrupted
 }
 
 // HasFailures returns true if the group or any of its children have failures
@@ -119,7 +294,7 @@ func (g *TestGroup) HasFailures() bool {
 	}
 
 	for _, tc := range g.TestCases {
-		if tc.Status == TestStatusFail {
+		if tc.Status == TestStatusFail || tc.Status == TestStatusError {
 			return true
 		}
 	}
@@ -133,48 +308,156 @@ func (g *TestGroup) HasFailures() bool {
 	return false
 }
 
+// onlyToleratedFailures reports whether a failed group failed only because of tests
+// whose failures don't fail the run: quarantined tests, and tests that passed when
+// retried. At least one such test failed, and nothing else below the group did.
+func onlyToleratedFailures(group *TestGroup) bool {
+	return !hasUntoleratedFailure(group) && countQuarantinedTestCases(group)+countFlakyTestCases(group) > 0
+}
+
+// hasUntoleratedFailure reports whether a test below group failed without being
+// quarantined or passing on a retry, or a group below it errored, failed to set up or
+// failed on its own
+func hasUntoleratedFailure(group *TestGroup) bool {
+	if group.ErrorInfo != nil || group.Stats.SetupFailed || group.Status == TestStatusError {
+		return true
+	}
+	for _, tc := range group.TestCases {
+		if tc.Status == TestStatusFail || tc.Status == TestStatusError {
+			return true
+		}
+	}
+	for _, sg := range group.Subgroups {
+		if sg.Status == TestStatusFail || hasUntoleratedFailure(sg) {
+			return true
+		}
+	}
+	return false
+}
+
 // UpdateStats recalculates statistics for this group and all subgroups
 func (g *TestGroup) UpdateStats() {
 	// Reset stats
 	g.Stats = TestGroupStats{}
+	g.countDirectTestCases()
 
-	// Count direct test cases
-	for _, tc := range g.TestCases {
-		g.Stats.TotalTests++
-		g.Stats.TotalTestsRecursive++
-
-		switch tc.Status {
-		case TestStatusPass:
-			g.Stats.PassedTests++
-			g.Stats.PassedTestsRecursive++
-		case TestStatusFail:
-			g.Stats.FailedTests++
-			g.Stats.FailedTestsRecursive++
-		case TestStatusSkip:
-			g.Stats.SkippedTests++
-			g.Stats.SkippedTestsRecursive++
-		case TestStatusXFail:
-			g.Stats.XFailedTests++
-			g.Stats.XFailedTestsRecursive++
-		case TestStatusXPass:
-			g.Stats.XPassedTests++
-			g.Stats.XPassedTestsRecursive++
+	for _, sg := range g.Subgroups {
+		sg.UpdateStats()
+	}
+	g.refreshRecursiveStats()
+
+	// Update group status based on children
+	g.updateStatusFromChildren()
+}
+
+// testCase returns the direct test case with the given ID, nil if there's none. Groups
+// filled by AddTestCase look it up in their index.
+func (g *TestGroup) testCase(id string) *TestCase {
+	if i, ok := g.testCaseIndex[id]; ok && i < len(g.TestCases) && g.TestCases[i].ID == id {
+		return &g.TestCases[i]
+	}
+	for i := range g.TestCases {
+		if g.TestCases[i].ID == id {
+			return &g.TestCases[i]
 		}
 	}
+	return nil
+}
+
+// AddTestCase adds a test case, or replaces the one with the same ID, and updates
+// the group's counts and status. Unlike UpdateStats it doesn't recount the other
+// test cases, so a group with thousands of tests stays cheap to update.
+func (g *TestGroup) AddTestCase(tc TestCase) {
+	if g.testCaseIndex == nil {
+		g.testCaseIndex = make(map[string]int, len(g.TestCases))
+		for i, existing := range g.TestCases {
+			g.testCaseIndex[existing.ID] = i
+		}
+		g.countDirectTestCases()
+	}
+
+	if i, exists := g.testCaseIndex[tc.ID]; exists {
+		g.countTestCase(g.TestCases[i], -1)
+		g.TestCases[i] = tc
+	} else {
+		g.testCaseIndex[tc.ID] = len(g.TestCases)
+		g.TestCases = append(g.TestCases, tc)
+	}
+	g.countTestCase(tc, 1)
+
+	g.refreshRecursiveStats()
+	g.updateStatusFromChildren()
+}
+
+// countDirectTestCases recounts the direct test case statistics from scratch
+func (g *TestGroup) countDirectTestCases() {
+	g.Stats.TotalTests = 0
+	g.Stats.PassedTests = 0
+	g.Stats.FailedTests = 0
+	g.Stats.SkippedTests = 0
+	g.Stats.XFailedTests = 0
+	g.Stats.XPassedTests = 0
+	g.Stats.Benchmarks = 0
+	g.Stats.QuarantinedFailedTests = 0
+	g.Stats.FlakyTests = 0
+	g.Stats.ErroredTests = 0
+	for _, tc := range g.TestCases {
+		g.countTestCase(tc, 1)
+	}
+}
+
+// countTestCase adds (delta 1) or removes (delta -1) a test case from the direct counts
+func (g *TestGroup) countTestCase(tc TestCase, delta int) {
+	switch tc.Status {
+	case TestStatusBench:
+		g.Stats.Benchmarks += delta
+		return
+	case TestStatusPass:
+		g.Stats.PassedTests += delta
+	case TestStatusFail:
+		g.Stats.FailedTests += delta
+	case TestStatusSkip:
+		g.Stats.SkippedTests += delta
+	case TestStatusXFail:
+		g.Stats.XFailedTests += delta
+	case TestStatusXPass:
+		g.Stats.XPassedTests += delta
+	case TestStatusQuarantinedFail:
+		g.Stats.QuarantinedFailedTests += delta
+	case TestStatusFlaky:
+		g.Stats.FlakyTests += delta
+	case TestStatusError:
+		g.Stats.ErroredTests += delta
+	}
+	g.Stats.TotalTests += delta
+}
+
+// refreshRecursiveStats recomputes the recursive counts from the direct test cases
+// and the subgroups' current recursive counts, without recursing into subgroups
+func (g *TestGroup) refreshRecursiveStats() {
+	g.Stats.TotalTestsRecursive = g.Stats.TotalTests
+	g.Stats.PassedTestsRecursive = g.Stats.PassedTests
+	g.Stats.FailedTestsRecursive = g.Stats.FailedTests
+	g.Stats.SkippedTestsRecursive = g.Stats.SkippedTests
+	g.Stats.XFailedTestsRecursive = g.Stats.XFailedTests
+	g.Stats.XPassedTestsRecursive = g.Stats.XPassedTests
+	g.Stats.BenchmarksRecursive = g.Stats.Benchmarks
+	g.Stats.QuarantinedFailedTestsRecursive = g.Stats.QuarantinedFailedTests
+	g.Stats.FlakyTestsRecursive = g.Stats.FlakyTests
+	g.Stats.ErroredTestsRecursive = g.Stats.ErroredTests
 
-	// Add subgroup stats
 	for _, sg := range g.Subgroups {
-		sg.UpdateStats()
 		g.Stats.TotalTestsRecursive += sg.Stats.TotalTestsRecursive
 		g.Stats.PassedTestsRecursive += sg.Stats.PassedTestsRecursive
 		g.Stats.FailedTestsRecursive += sg.Stats.FailedTestsRecursive
 		g.Stats.SkippedTestsRecursive += sg.Stats.SkippedTestsRecursive
 		g.Stats.XFailedTestsRecursive += sg.Stats.XFailedTestsRecursive
 		g.Stats.XPassedTestsRecursive += sg.Stats.XPassedTestsRecursive
+		g.Stats.BenchmarksRecursive += sg.Stats.BenchmarksRecursive
+		g.Stats.QuarantinedFailedTestsRecursive += sg.Stats.QuarantinedFailedTestsRecursive
+		g.Stats.FlakyTestsRecursive += sg.Stats.FlakyTestsRecursive
+		g.Stats.ErroredTestsRecursive += sg.Stats.ErroredTestsRecursive
 	}
-
-	// Update group status based on children
-	g.updateStatusFromChildren()
 }
 
 // updateStatusFromChildren updates the group's status based on its children
@@ -192,7 +475,7 @@ func (g *TestGroup) updateStatusFromChildren() {
 				allComplete = false
 				break
 			}
-			if tc.Status == TestStatusFail {
+			if tc.Status == TestStatusFail || tc.Status == TestStatusError {
 				hasFailures = true
 			}
 			if tc.Status == TestStatusSkip {
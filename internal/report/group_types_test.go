@@ -159,6 +159,46 @@ func TestTestGroup_UpdateStats(t *testing.T) {
 	}
 }
 
+func TestTestGroup_AddTestCase(t *testing.T) {
+	group := &TestGroup{
+		Status:    TestStatusRunning,
+		StartTime: time.Now(),
+		TestCases: []TestCase{{ID: "existing", Status: TestStatusPass}},
+		Subgroups: map[string]*TestGroup{
+			"sub": {Status: TestStatusPass, Stats: TestGroupStats{TotalTestsRecursive: 2, PassedTestsRecursive: 2}},
+		},
+	}
+
+	group.AddTestCase(TestCase{ID: "flaky", Status: TestStatusFail})
+	group.AddTestCase(TestCase{ID: "bench", Status: TestStatusBench})
+	// A second result for the same test replaces the first
+	group.AddTestCase(TestCase{ID: "flaky", Status: TestStatusPass})
+
+	if len(group.TestCases) != 3 {
+		t.Fatalf("Expected the duplicate to be replaced, got %d test cases", len(group.TestCases))
+	}
+	if tc := group.testCase("flaky"); tc == nil || tc.Status != TestStatusPass {
+		t.Errorf("Expected the index to find the replacement, got %+v", tc)
+	}
+	if tc := group.testCase("existing"); tc == nil || tc.Status != TestStatusPass {
+		t.Errorf("Expected the index to cover test cases added before it, got %+v", tc)
+	}
+	if group.Stats.TotalTests != 2 || group.Stats.PassedTests != 2 || group.Stats.FailedTests != 0 || group.Stats.Benchmarks != 1 {
+		t.Errorf("Unexpected direct stats: %+v", group.Stats)
+	}
+	if group.Stats.TotalTestsRecursive != 4 || group.Stats.PassedTestsRecursive != 4 {
+		t.Errorf("Expected recursive counts to include the subgroup, got %+v", group.Stats)
+	}
+
+	// Same counts as recounting from scratch
+	counted := group.Stats
+	group.UpdateStats()
+	if group.Stats.TotalTests != counted.TotalTests || group.Stats.PassedTests != counted.PassedTests ||
+		group.Stats.Benchmarks != counted.Benchmarks {
+		t.Errorf("AddTestCase counts %+v differ from UpdateStats %+v", counted, group.Stats)
+	}
+}
+
 func TestTestGroup_UpdateStatusFromChildren(t *testing.T) {
 	tests := []struct {
 		name           string
@@ -0,0 +1,255 @@
+package report
+
+import (
+	"encoding/xml"
+	"fmt"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/zk/3pio/internal/console"
+)
+
+// JUnit XML document, limited to what the Jenkins JUnit schema allows so GitLab
+// and Jenkins both accept it
+type junitTestSuites struct {
+	XMLName  xml.Name         `xml:"testsuites"`
+	Tests    int              `xml:"tests,attr"`
+	Failures int              `xml:"failures,attr"`
+	Errors   int              `xml:"errors,attr"`
+	Skipped  int              `xml:"skipped,attr"`
+	Time     string           `xml:"time,attr"`
+	Suites   []junitTestSuite `xml:"testsuite"`
+}
+
+type junitTestSuite struct {
+	Name      string          `xml:"name,attr"`
+	Tests     int             `xml:"tests,attr"`
+	Failures  int             `xml:"failures,attr"`
+	Errors    int             `xml:"errors,attr"`
+	Skipped   int             `xml:"skipped,attr"`
+	Time      string          `xml:"time,attr"`
+	Timestamp string          `xml:"timestamp,attr,omitempty"`
+	TestCases []junitTestCase `xml:"testcase"`
+}
+
+type junitTestCase struct {
+	Name      string        `xml:"name,attr"`
+	Classname string        `xml:"classname,attr"`
+	Time      string        `xml:"time,attr"`
+	Skipped   *junitSkipped `xml:"skipped,omitempty"`
+	Error     *junitFailure `xml:"error,omitempty"`
+	Failure   *junitFailure `xml:"failure,omitempty"`
+	SystemOut *junitOutput  `xml:"system-out,omitempty"`
+	SystemErr *junitOutput  `xml:"system-err,omitempty"`
+}
+
+type junitFailure struct {
+	Message string `xml:"message,attr"`
+	Type    string `xml:"type,attr"`
+	Text    string `xml:",cdata"`
+}
+
+type junitSkipped struct {
+	Message string `xml:"message,attr,omitempty"`
+}
+
+type junitOutput struct {
+	Text string `xml:",cdata"`
+}
+
+// WriteJUnitXML writes the results as a JUnit XML file: one <testsuite> per root
+// group, with the tests of nested groups flattened into dotted classnames
+func WriteJUnitXML(path string, rootGroups []*TestGroup) error {
+	return writeJUnitXML(path, rootGroups, nil)
+}
+
+// writeJUnitXML writes the JUnit XML file with the secret values replaced
+func writeJUnitXML(path string, rootGroups []*TestGroup, redactor *Redactor) error {
+	doc := junitTestSuites{}
+	var total time.Duration
+	for _, group := range sortedGroups(rootGroups) {
+		suite, duration := buildJUnitSuite(group)
+		doc.Tests += suite.Tests
+		doc.Failures += suite.Failures
+		doc.Errors += suite.Errors
+		doc.Skipped += suite.Skipped
+		total += duration
+		doc.Suites = append(doc.Suites, suite)
+	}
+	doc.Time = junitSeconds(total)
+
+	data, err := xml.MarshalIndent(doc, "", "  ")
+	if err != nil {
+		return fmt.Errorf("failed to encode JUnit XML: %w", err)
+	}
+	if dir := filepath.Dir(path); dir != "." {
+		if err := os.MkdirAll(dir, 0755); err != nil {
+			return fmt.Errorf("failed to create JUnit XML directory: %w", err)
+		}
+	}
+	content := append([]byte(xml.Header), redactor.RedactBytes(data)...)
+	return writeFileAtomic(path, append(content, '\n'))
+}
+
+// buildJUnitSuite collects a root group's tests, and those of its subgroups, into one suite
+func buildJUnitSuite(root *TestGroup) (junitTestSuite, time.Duration) {
+	suite := junitTestSuite{Name: junitText(root.Name)}
+	if !root.StartTime.IsZero() {
+		suite.Timestamp = root.StartTime.UTC().Format("2006-01-02T15:04:05")
+	}
+
+	var testTime time.Duration
+	var walk func(group *TestGroup)
+	walk = func(group *TestGroup) {
+		classname := junitText(strings.Join(group.GetFullPath(), "."))
+
+		// A group that failed outside any test (e.g. during setup) still needs to show up
+		if group.ErrorInfo != nil && len(group.TestCases) == 0 {
+			suite.TestCases = append(suite.TestCases, junitTestCase{
+				Name:      junitText(group.Name),
+				Classname: classname,
+				Time:      junitSeconds(0),
+				Error:     junitFailureFor(group.ErrorInfo, "ERROR"),
+			})
+			suite.Tests++
+			suite.Errors++
+		}
+
+		for _, tc := range group.TestCases {
+			if tc.Status == TestStatusBench {
+				continue // Benchmarks aren't tests
+			}
+			testCase := junitTestCase{
+				Name:      junitText(tc.Name),
+				Classname: classname,
+				Time:      junitSeconds(tc.Duration),
+			}
+			switch tc.Status {
+			case TestStatusPass, TestStatusXPass, TestStatusFlaky:
+			case TestStatusFail:
+				testCase.Failure = junitFailureFor(tc.Error, "FAIL")
+				suite.Failures++
+			case TestStatusError:
+				// Setup and teardown errors are <error>s, as in pytest's own JUnit XML
+				testCase.Error = junitFailureFor(tc.Error, "ERROR")
+				suite.Errors++
+			case TestStatusSkip:
+				message := tc.SkipReason
+				if tc.SkipMessage != "" {
+					message = tc.SkipMessage
+				}
+				testCase.Skipped = &junitSkipped{Message: junitText(message)}
+				suite.Skipped++
+			case TestStatusXFail:
+				testCase.Skipped = &junitSkipped{Message: junitText(strings.TrimSpace("expected failure " + tc.XFailReason))}
+				suite.Skipped++
+			case TestStatusQuarantinedFail:
+				// CI systems fail the build on a <failure>, which quarantining is meant to avoid
+				message := "quarantined"
+				if tc.Error != nil && tc.Error.Message != "" {
+					message += ": " + firstLine(tc.Error.Message)
+				}
+				testCase.Skipped = &junitSkipped{Message: junitText(message)}
+				suite.Skipped++
+			default:
+				testCase.Skipped = &junitSkipped{Message: "did not finish"}
+				suite.Skipped++
+			}
+			if tc.Stdout != "" {
+				testCase.SystemOut = &junitOutput{Text: junitText(tc.Stdout)}
+			}
+			if tc.Stderr != "" {
+				testCase.SystemErr = &junitOutput{Text: junitText(tc.Stderr)}
+			}
+			suite.TestCases = append(suite.TestCases, testCase)
+			suite.Tests++
+			testTime += tc.Duration
+		}
+
+		subgroups := make([]*TestGroup, 0, len(group.Subgroups))
+		for _, subgroup := range group.Subgroups {
+			subgroups = append(subgroups, subgroup)
+		}
+		for _, subgroup := range sortedGroups(subgroups) {
+			walk(subgroup)
+		}
+	}
+	walk(root)
+
+	// Prefer the group's wall-clock time; parallel tests add up to more than it
+	duration := root.Duration
+	if duration == 0 {
+		duration = testTime
+	}
+	suite.Time = junitSeconds(duration)
+	return suite, duration
+}
+
+// junitFailureFor turns a test error into a <failure> or <error> element, with the
+// full message, location and stack in its text
+func junitFailureFor(testErr *TestError, fallbackType string) *junitFailure {
+	if testErr == nil {
+		return &junitFailure{Type: fallbackType}
+	}
+
+	var text strings.Builder
+	text.WriteString(testErr.Message)
+	if diff := testErr.AssertionDiff(); diff != "" {
+		text.WriteString("\n\n")
+		text.WriteString(diff)
+	}
+	if testErr.Location != "" {
+		fmt.Fprintf(&text, "\n\nat %s", testErr.Location)
+	}
+	if testErr.Stack != "" {
+		text.WriteString("\n\n")
+		text.WriteString(testErr.Stack)
+	}
+	if testErr.Backtrace != "" {
+		text.WriteString("\n\n")
+		text.WriteString(testErr.Backtrace)
+	}
+
+	errType := testErr.Type
+	if errType == "" {
+		errType = fallbackType
+	}
+	message, _, _ := strings.Cut(testErr.Message, "\n")
+	return &junitFailure{
+		Message: junitText(message),
+		Type:    junitText(errType),
+		Text:    junitText(strings.TrimSpace(text.String())),
+	}
+}
+
+// junitText strips terminal color codes, which XML can't carry, and characters XML 1.0
+// doesn't allow
+func junitText(s string) string {
+	s = console.StripANSI(s)
+	return strings.Map(func(r rune) rune {
+		switch {
+		case r == '\t' || r == '\n' || r == '\r':
+			return r
+		case r < 0x20, r >= 0xD800 && r <= 0xDFFF, r == 0xFFFE, r == 0xFFFF:
+			return -1
+		}
+		return r
+	}, s)
+}
+
+// junitSeconds formats a duration as JUnit's decimal seconds
+func junitSeconds(d time.Duration) string {
+	return fmt.Sprintf("%.3f", d.Seconds())
+}
+
+// sortedGroups returns the groups ordered by name, so the XML is stable between runs
+func sortedGroups(groups []*TestGroup) []*TestGroup {
+	sorted := append([]*TestGroup(nil), groups...)
+	sort.SliceStable(sorted, func(i, j int) bool {
+		return sorted[i].Name < sorted[j].Name
+	})
+	return sorted
+}
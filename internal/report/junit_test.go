@@ -0,0 +1,237 @@
+package report
+
+import (
+	"bytes"
+	"encoding/xml"
+	"io"
+	"os"
+	"path/filepath"
+	"strconv"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/internal/ipc"
+)
+
+// junitElement is a generic XML element, for checking the output against the schema
+type junitElement struct {
+	XMLName  xml.Name
+	Attrs    []xml.Attr     `xml:",any,attr"`
+	Children []junitElement `xml:",any"`
+	Text     string         `xml:",chardata"`
+}
+
+func (e junitElement) attr(name string) (string, bool) {
+	for _, a := range e.Attrs {
+		if a.Name.Local == name {
+			return a.Value, true
+		}
+	}
+	return "", false
+}
+
+// validateJUnitSchema checks the document against the rules of the Jenkins JUnit
+// schema (junit-10.xsd): allowed elements, required attributes, and counters and
+// times of the right types that agree with the test cases
+func validateJUnitSchema(t *testing.T, data []byte) junitElement {
+	t.Helper()
+
+	// Must be well-formed XML
+	decoder := xml.NewDecoder(bytes.NewReader(data))
+	decoder.Strict = true
+	for {
+		if _, err := decoder.Token(); err == io.EOF {
+			break
+		} else if err != nil {
+			t.Fatalf("JUnit XML is not well-formed: %v\n%s", err, data)
+		}
+	}
+
+	var root junitElement
+	if err := xml.Unmarshal(data, &root); err != nil {
+		t.Fatalf("Failed to parse JUnit XML: %v", err)
+	}
+	if root.XMLName.Local != "testsuites" {
+		t.Fatalf("Expected <testsuites> root, got <%s>", root.XMLName.Local)
+	}
+
+	requireInt := func(e junitElement, name string) int {
+		value, ok := e.attr(name)
+		if !ok {
+			t.Errorf("<%s> is missing required attribute %s", e.XMLName.Local, name)
+			return 0
+		}
+		n, err := strconv.Atoi(value)
+		if err != nil || n < 0 {
+			t.Errorf("<%s %s=%q> is not a non-negative integer", e.XMLName.Local, name, value)
+		}
+		return n
+	}
+	requireDecimal := func(e junitElement, name string) {
+		value, ok := e.attr(name)
+		if !ok {
+			return
+		}
+		if _, err := strconv.ParseFloat(value, 64); err != nil || strings.ContainsAny(value, "eE") {
+			t.Errorf("<%s %s=%q> is not a decimal", e.XMLName.Local, name, value)
+		}
+	}
+
+	for _, suite := range root.Children {
+		if suite.XMLName.Local != "testsuite" {
+			t.Errorf("Unexpected <%s> in <testsuites>", suite.XMLName.Local)
+			continue
+		}
+		if name, ok := suite.attr("name"); !ok || name == "" {
+			t.Errorf("<testsuite> is missing its name")
+		}
+		requireDecimal(suite, "time")
+
+		var cases, failures, errors, skipped int
+		for _, tc := range suite.Children {
+			switch tc.XMLName.Local {
+			case "properties", "system-out", "system-err":
+				continue
+			case "testcase":
+			default:
+				t.Errorf("Unexpected <%s> in <testsuite>", tc.XMLName.Local)
+				continue
+			}
+			cases++
+			if _, ok := tc.attr("name"); !ok {
+				t.Errorf("<testcase> is missing its name")
+			}
+			if _, ok := tc.attr("classname"); !ok {
+				t.Errorf("<testcase> is missing its classname")
+			}
+			requireDecimal(tc, "time")
+			for _, child := range tc.Children {
+				switch child.XMLName.Local {
+				case "failure":
+					failures++
+				case "error":
+					errors++
+				case "skipped":
+					skipped++
+				case "system-out", "system-err":
+				default:
+					t.Errorf("Unexpected <%s> in <testcase>", child.XMLName.Local)
+				}
+			}
+		}
+
+		for name, count := range map[string]int{"tests": cases, "failures": failures, "errors": errors, "skipped": skipped} {
+			if got := requireInt(suite, name); got != count {
+				t.Errorf("<testsuite %s=%d> but it holds %d", name, got, count)
+			}
+		}
+	}
+	return root
+}
+
+func TestManager_JUnitXML(t *testing.T) {
+	tempDir := t.TempDir()
+	logger := &mockLogger{}
+
+	manager, err := NewManager(tempDir, nil, logger, "cargo test", "cargo test")
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	xmlPath := filepath.Join(tempDir, "ci", "junit.xml")
+	manager.SetJUnitXMLPath(xmlPath)
+	if err := manager.Initialize("cargo test"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+
+	testCases := []ipc.TestCasePayload{
+		{TestName: "test_add", ParentNames: []string{"rust-basic", "tests"}, Status: "PASS", Duration: 1.5},
+		{
+			TestName:    `test_quotes_"double"_and_'single'`,
+			ParentNames: []string{"rust-basic", "tests", "nested"},
+			Status:      "FAIL",
+			Duration:    2,
+			Error: &ipc.TestError{
+				Message:   "assertion `left == right` failed\n  left: \"<a & b>\"\n right: \"]]>\"",
+				ErrorType: "assert_eq",
+				Location:  "src/lib.rs:42:9",
+				Backtrace: "\x1b[31m0: rust_basic::tests::nested\x1b[0m",
+			},
+			Stdout: "printed ünïcödé ✓\x01",
+		},
+		{TestName: "test_ünïcödé_名前", ParentNames: []string{"rust-basic", "tests"}, Status: "SKIP", SkipReason: "ignored"},
+		{TestName: "bench_fib", ParentNames: []string{"rust-basic", "benches"}, Status: "BENCH"},
+		{TestName: "test_other", ParentNames: []string{"another-crate"}, Status: "PASS"},
+	}
+	for _, payload := range testCases {
+		if err := manager.HandleEvent(ipc.GroupTestCaseEvent{EventType: "testCase", Payload: payload}); err != nil {
+			t.Fatalf("HandleEvent failed: %v", err)
+		}
+	}
+	if err := manager.Finalize(101, ""); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+
+	data, err := os.ReadFile(xmlPath)
+	if err != nil {
+		t.Fatalf("Failed to read JUnit XML: %v", err)
+	}
+	root := validateJUnitSchema(t, data)
+
+	if len(root.Children) != 2 {
+		t.Fatalf("Expected one testsuite per root group, got %d:\n%s", len(root.Children), data)
+	}
+	for name, want := range map[string]string{"tests": "4", "failures": "1", "skipped": "1", "errors": "0"} {
+		if got, _ := root.attr(name); got != want {
+			t.Errorf("Expected <testsuites %s=%q>, got %q", name, want, got)
+		}
+	}
+
+	suite := root.Children[1]
+	if name, _ := suite.attr("name"); name != "rust-basic" {
+		t.Fatalf("Expected suites sorted by name, got %q second", name)
+	}
+	cases := make(map[string]junitElement)
+	for _, tc := range suite.Children {
+		name, _ := tc.attr("name")
+		cases[name] = tc
+	}
+	if _, ok := cases["bench_fib"]; ok {
+		t.Errorf("Benchmarks should not be exported as test cases")
+	}
+
+	// Names round-trip through escaping, and nested modules become dotted classnames
+	failed, ok := cases[`test_quotes_"double"_and_'single'`]
+	if !ok {
+		t.Fatalf("Missing the failed test with quotes in its name, got %v", cases)
+	}
+	if classname, _ := failed.attr("classname"); classname != "rust-basic.tests.nested" {
+		t.Errorf("Expected classname rust-basic.tests.nested, got %q", classname)
+	}
+	if _, ok := cases["test_ünïcödé_名前"]; !ok {
+		t.Errorf("Missing the test with a unicode name, got %v", cases)
+	}
+
+	var failure junitElement
+	for _, child := range failed.Children {
+		if child.XMLName.Local == "failure" {
+			failure = child
+		}
+	}
+	if message, _ := failure.attr("message"); message != "assertion `left == right` failed" {
+		t.Errorf("Expected the first line of the error as the message, got %q", message)
+	}
+	if errType, _ := failure.attr("type"); errType != "assert_eq" {
+		t.Errorf("Expected failure type assert_eq, got %q", errType)
+	}
+	for _, want := range []string{`left: "<a & b>"`, `right: "]]>"`, "at src/lib.rs:42:9", "0: rust_basic::tests::nested"} {
+		if !strings.Contains(failure.Text, want) {
+			t.Errorf("Expected failure text to contain %q, got %q", want, failure.Text)
+		}
+	}
+	if strings.Contains(failure.Text, "\x1b") {
+		t.Errorf("Expected color codes stripped from the failure text, got %q", failure.Text)
+	}
+	if !bytes.Contains(data, []byte("<![CDATA[assertion")) {
+		t.Errorf("Expected the failure text in CDATA, got:\n%s", data)
+	}
+}
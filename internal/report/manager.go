@@ -1,14 +1,21 @@
 package report
 
 import (
+	"encoding/json"
 	"fmt"
+	"io"
 	"os"
 	"path/filepath"
+	"slices"
+	"sort"
 	"strings"
 	"sync"
 	"time"
 
+	"github.com/zk/3pio/internal/console"
+	"github.com/zk/3pio/internal/coverage"
 	"github.com/zk/3pio/internal/ipc"
+	"github.com/zk/3pio/internal/runinfo"
 	"github.com/zk/3pio/internal/runner"
 )
 
@@ -24,6 +31,94 @@ type Manager struct {
 	// Group manager for hierarchical test organization
 	groupManager *GroupManager
 
+	// Compiler errors that stopped tests from building
+	buildErrors []ipc.BuildErrorPayload
+
+	// Test files that failed to collect (pytest)
+	collectionErrors []string
+
+	// Warnings the runner recorded (pytest), one per message and location
+	warnings []recordedWarning
+
+	// Parallel workers of the run (pytest-xdist), 0 without
+	workers int
+	// Test running on each worker, "" without parallel workers, to attribute output
+	// chunks that name no group
+	runningTests map[string]ipc.TestCaseStartPayload
+	// Output of the running tests by test case ID, until their result arrives
+	testOutput map[string]*testOutput
+
+	// Test binaries that died before finishing their tests
+	crashes []ipc.BinaryCrashPayload
+
+	// Set when the run was stopped by --timeout or --inactivity-timeout
+	runTimeout        time.Duration
+	inactivityTimeout time.Duration
+	timedOutTests     []string // Tests (or groups) in flight when the timeout fired
+
+	// Set when the run was stopped by Ctrl-C (SIGINT) or SIGTERM
+	interrupted bool
+
+	// Set when the runner's test filter matched no test
+	noTestsFound bool
+
+	// Set when the tests couldn't run as something they need is missing, e.g. a WebDriver
+	environmentError bool
+
+	// Lines of the IPC file that couldn't be decoded, whose results may be missing
+	malformedIPCEvents int
+
+	// Set while --retries re-runs the failed tests, whose events only update them
+	retrying bool
+
+	// Directory the tests run in; empty for the working directory
+	workDir string
+
+	// Exit code 3pio exits with, known once the run is finalized
+	exitCode int
+
+	// Set when --rerun-failed narrowed the command to an earlier run's failures
+	rerunOf    string // Run ID the failed tests came from
+	rerunCount int    // Number of failed tests re-run
+
+	// Filters the command narrows its tests down with, e.g. "--skip slow"
+	testFilters []string
+
+	// Snapshot files insta wrote during the run for cargo insta review, relative to the
+	// workspace root
+	pendingSnapshots []string
+
+	// Code coverage the run measured (cargo llvm-cov), or why a run that measured it
+	// has none to report
+	coverage        *coverage.Summary
+	coverageWarning string
+
+	// Part of a sharded test suite the run covers, nil for all of it
+	shard *runinfo.Shard
+
+	// Profile, features and target a cargo command builds the tests with
+	cargo *runinfo.Cargo
+
+	// Files the runner's results were read from once the command exited, such as
+	// "junit_xml"; empty when they arrived as the tests ran
+	resultsSource string
+
+	// Set when an earlier run's summary is known, so failures can be marked new or known
+	previousRunID    string
+	previousFailures map[string]bool // Group IDs and test IDs of what failed in it
+
+	// Where --junit-xml writes a JUnit XML copy of the results, empty for none
+	junitXMLPath string
+
+	// Toolchain and environment the run ran with, nil until collected
+	metadata *runinfo.Metadata
+
+	// Replaces secret values in everything written but output.log (--redact), nil to keep them
+	redactor *Redactor
+
+	// Where the events are written as JSON lines (--output json), nil for nowhere
+	eventStream io.Writer
+
 	// Track if we created our own FileLogger that needs closing
 
 	// File handles for incremental writing
@@ -36,14 +131,14 @@ type Manager struct {
 	stdoutBuffers map[string][]string
 	stderrBuffers map[string][]string
 
-	// Debouncing for main report writes
+	// Throttling for main report writes
 	writeTimer   *time.Timer
 	writeMutex   sync.Mutex
 	pendingWrite bool
+	bytesWritten int64 // Bytes written to test-run.md so far
 
-	mu           sync.RWMutex
-	debounceTime time.Duration
-	maxWaitTime  time.Duration
+	mu            sync.RWMutex
+	writeInterval time.Duration // test-run.md is rewritten at most this often while events arrive
 
 	// Track test run start time for wall-clock duration
 	startTime time.Time
@@ -71,6 +166,7 @@ func NewManager(runDir string, parser runner.OutputParser, lg Logger, detectedRu
 
 	// Initialize GroupManager for hierarchical test organization
 	groupManager := NewGroupManager(runDir, "", lg)
+	groupManager.SetRunnerKind(RunnerKind(detectedRunner))
 
 	return &Manager{
 		runDir:          runDir,
@@ -85,9 +181,10 @@ func NewManager(runDir string, parser runner.OutputParser, lg Logger, detectedRu
 		outputFile:      outputFile,
 		stdoutBuffers:   make(map[string][]string),
 		stderrBuffers:   make(map[string][]string),
+		runningTests:    make(map[string]ipc.TestCaseStartPayload),
+		testOutput:      make(map[string]*testOutput),
 		pendingWrite:    false,
-		debounceTime:    200 * time.Millisecond,
-		maxWaitTime:     500 * time.Millisecond,
+		writeInterval:   500 * time.Millisecond,
 		startTime:       time.Now(),
 	}, nil
 }
@@ -119,6 +216,7 @@ func (m *Manager) Initialize(args string) error {
 	if err := m.writeOutputLogHeader(args); err != nil {
 		return fmt.Errorf("failed to write output log header: %w", err)
 	}
+	m.streamRunStarted(args)
 
 	// Write initial state
 	return m.writeState()
@@ -129,11 +227,30 @@ func (m *Manager) HandleEvent(event ipc.Event) error {
 	m.mu.Lock()
 	defer m.mu.Unlock()
 
+	if m.retrying {
+		if e, ok := event.(ipc.GroupTestCaseEvent); ok && m.groupManager != nil {
+			if err := m.groupManager.ProcessRetriedTestCase(e); err != nil {
+				return err
+			}
+			m.streamTestCase(e.Payload.ParentNames, e.Payload.TestName)
+			return m.scheduleWrite()
+		}
+		return nil
+	}
+
 	switch e := event.(type) {
 
 	case ipc.CollectionErrorEvent:
 		return m.handleCollectionError(e)
 
+	case ipc.BuildErrorEvent:
+		m.buildErrors = append(m.buildErrors, e.Payload)
+		return m.scheduleWrite()
+
+	case ipc.BinaryCrashEvent:
+		m.crashes = append(m.crashes, e.Payload)
+		return m.scheduleWrite()
+
 	// Group events - forward to GroupManager and trigger report updates
 	case ipc.GroupDiscoveredEvent:
 		if m.groupManager != nil {
@@ -151,6 +268,7 @@ func (m *Manager) HandleEvent(event ipc.Event) error {
 			if err != nil {
 				return err
 			}
+			m.streamGroup(StreamGroupStarted, append(append([]string(nil), e.Payload.ParentNames...), e.Payload.GroupName))
 			// Update test-run.md to show group as RUNNING
 			return m.scheduleWrite()
 		}
@@ -161,6 +279,7 @@ func (m *Manager) HandleEvent(event ipc.Event) error {
 			if err != nil {
 				return err
 			}
+			m.streamGroup(StreamGroupFinished, append(append([]string(nil), e.Payload.ParentNames...), e.Payload.GroupName))
 			// Update test-run.md with final group status
 			return m.scheduleWrite()
 		}
@@ -176,21 +295,41 @@ func (m *Manager) HandleEvent(event ipc.Event) error {
 		}
 
 	case ipc.GroupTestCaseEvent:
+		m.attachTestOutput(&e.Payload)
 		if m.groupManager != nil {
 			err := m.groupManager.ProcessTestCase(e)
 			if err != nil {
 				return err
 			}
+			m.streamTestCase(e.Payload.ParentNames, e.Payload.TestName)
 			// Update test-run.md with incremental test counts
 			return m.scheduleWrite()
 		}
 
+	case ipc.CollectionStartEvent:
+		m.workers = e.Payload.Workers
+
+	case ipc.RunWarningsEvent:
+		m.addWarnings(e.Payload.Warnings)
+		return m.scheduleWrite()
+
+	case ipc.TestCaseStartEvent:
+		m.runningTests[e.Payload.WorkerID] = e.Payload
+
 	case ipc.GroupStdoutChunkEvent:
+		if e.Payload.GroupName == "" {
+			m.appendTestOutput(e.Payload, false)
+			return nil
+		}
 		if m.groupManager != nil {
 			return m.groupManager.ProcessGroupStdout(e)
 		}
 
 	case ipc.GroupStderrChunkEvent:
+		if e.Payload.GroupName == "" {
+			m.appendTestOutput(e.Payload, true)
+			return nil
+		}
 		if m.groupManager != nil {
 			return m.groupManager.ProcessGroupStderr(e)
 		}
@@ -200,6 +339,9 @@ func (m *Manager) HandleEvent(event ipc.Event) error {
 			return m.groupManager.ProcessRunComplete(e)
 		}
 
+	case ipc.AdapterHandshakeEvent:
+		// Checked by the orchestrator, which stops the run for an incompatible adapter
+
 	default:
 		m.logger.Debug("Unknown event type: %T", event)
 	}
@@ -207,27 +349,99 @@ func (m *Manager) HandleEvent(event ipc.Event) error {
 	return nil
 }
 
-// handleCollectionError handles collection error events (pytest specific)
-func (m *Manager) handleCollectionError(event ipc.CollectionErrorEvent) error {
-	filePath := event.Payload.FilePath
+// testOutput is the output of a running test, from chunks that name no group
+type testOutput struct {
+	stdout strings.Builder
+	stderr strings.Builder
+}
 
-	// Find the test file and set execution error
-	normalizedPath := m.normalizePath(filePath)
-	for i := range m.state.TestFiles {
-		if m.normalizePath(m.state.TestFiles[i].File) == normalizedPath {
-			// Set execution error and update status to indicate error
-			m.state.TestFiles[i].ExecutionError = fmt.Sprintf("Collection failed: %s", event.Payload.Error)
-			m.state.TestFiles[i].Updated = time.Now()
+// appendTestOutput adds an output chunk that names no group to the test running on
+// its worker. Workers run their tests in parallel, so the test that started last on
+// any worker is not the one that printed.
+func (m *Manager) appendTestOutput(chunk ipc.OutputChunkPayload, stderr bool) {
+	test, ok := m.runningTests[chunk.WorkerID]
+	if !ok {
+		m.logger.Debug("Dropping output of worker %q, which runs no test", chunk.WorkerID)
+		return
+	}
+	id := GenerateTestCaseID(test.TestName, append([]string(nil), test.ParentNames...))
+	output := m.testOutput[id]
+	if output == nil {
+		output = &testOutput{}
+		m.testOutput[id] = output
+	}
+	if stderr {
+		output.stderr.WriteString(chunk.Chunk)
+	} else {
+		output.stdout.WriteString(chunk.Chunk)
+	}
+}
 
-			// Mark file as having execution error (will map to ERRORED in status)
-			// Keep existing status logic but the presence of ExecutionError will trigger ERRORED in report
-			break
+// attachTestOutput gives a test's result the output collected while it ran, unless the
+// adapter sent the output with the result
+func (m *Manager) attachTestOutput(payload *ipc.TestCasePayload) {
+	id := GenerateTestCaseID(payload.TestName, append([]string(nil), payload.ParentNames...))
+	if output := m.testOutput[id]; output != nil {
+		if payload.Stdout == "" {
+			payload.Stdout = output.stdout.String()
+		}
+		if payload.Stderr == "" {
+			payload.Stderr = output.stderr.String()
+		}
+		delete(m.testOutput, id)
+	}
+	if test, ok := m.runningTests[payload.WorkerID]; ok && test.TestName == payload.TestName && slices.Equal(test.ParentNames, payload.ParentNames) {
+		delete(m.runningTests, payload.WorkerID)
+	}
+}
+
+// recordedWarning is a warning with how often it was recorded at its location
+type recordedWarning struct {
+	ipc.RunWarning
+	count int
+}
+
+// addWarnings records warnings, counting a repeated message and location once
+func (m *Manager) addWarnings(warnings []ipc.RunWarning) {
+	for _, warning := range warnings {
+		i := slices.IndexFunc(m.warnings, func(w recordedWarning) bool {
+			return w.Message == warning.Message && w.File == warning.File && w.Line == warning.Line
+		})
+		if i >= 0 {
+			m.warnings[i].count++
+			continue
 		}
+		m.warnings = append(m.warnings, recordedWarning{RunWarning: warning, count: 1})
+	}
+}
+
+// Warnings returns the warnings the runner recorded, one per message and location
+func (m *Manager) Warnings() []ipc.RunWarning {
+	m.mu.RLock()
+	defer m.mu.RUnlock()
+	warnings := make([]ipc.RunWarning, len(m.warnings))
+	for i, warning := range m.warnings {
+		warnings[i] = warning.RunWarning
 	}
+	return warnings
+}
 
+// handleCollectionError records a test file that failed to collect (pytest specific).
+// The adapter also reports the file as an errored group with the traceback.
+func (m *Manager) handleCollectionError(event ipc.CollectionErrorEvent) error {
+	if !slices.Contains(m.collectionErrors, event.Payload.FilePath) {
+		m.collectionErrors = append(m.collectionErrors, event.Payload.FilePath)
+	}
 	return m.scheduleWrite()
 }
 
+// CollectionErrors returns the test files that failed to collect, in the order reported
+func (m *Manager) CollectionErrors() []string {
+	m.mu.RLock()
+	defer m.mu.RUnlock()
+	return append([]string(nil), m.collectionErrors...)
+}
+
 // SetExecutionError sets an execution error for a test file
 // This is used for errors that occur during test execution (not test failures)
 func (m *Manager) SetExecutionError(filePath string, errorMsg string) error {
@@ -249,29 +463,25 @@ func (m *Manager) SetExecutionError(filePath string, errorMsg string) error {
 
 // Legacy file registration methods removed - using group-based model
 
-// scheduleWrite schedules a debounced state write
+// scheduleWrite schedules a state write. While events keep arriving test-run.md is
+// rewritten once per writeInterval, not once per event, since every rewrite renders
+// the whole report.
 func (m *Manager) scheduleWrite() error {
 	m.writeMutex.Lock()
 	defer m.writeMutex.Unlock()
 
 	m.pendingWrite = true
-
-	// Cancel existing timer
-	if m.writeTimer != nil {
-		m.writeTimer.Stop()
+	if m.writeTimer == nil {
+		m.writeTimer = time.AfterFunc(m.writeInterval, m.flushWrite)
 	}
 
-	// Schedule write after debounceTime of inactivity
-	m.writeTimer = time.AfterFunc(m.debounceTime, func() {
-		m.flushWrite()
-	})
-
 	return nil
 }
 
 // flushWrite executes pending write to disk
 func (m *Manager) flushWrite() {
 	m.writeMutex.Lock()
+	m.writeTimer = nil
 	if !m.pendingWrite {
 		m.writeMutex.Unlock()
 		return
@@ -279,6 +489,8 @@ func (m *Manager) flushWrite() {
 	m.pendingWrite = false
 	m.writeMutex.Unlock()
 
+	m.mu.Lock()
+	defer m.mu.Unlock()
 	if err := m.writeState(); err != nil {
 		m.logger.Error("Failed to write state: %v", err)
 	}
@@ -289,11 +501,15 @@ func (m *Manager) writeState() error {
 	m.state.UpdatedAt = time.Now()
 
 	// Generate markdown report
-	report := m.generateMarkdownReport()
+	report := m.redactor.Redact(m.generateMarkdownReport())
 
 	// Write to file
 	reportPath := filepath.Join(m.runDir, "test-run.md")
-	return os.WriteFile(reportPath, []byte(report), 0644)
+	if err := writeFileAtomic(reportPath, []byte(report)); err != nil {
+		return err
+	}
+	m.bytesWritten += int64(len(report))
+	return nil
 }
 
 // writeOutputLogHeader writes the header for output.log
@@ -318,18 +534,7 @@ func (m *Manager) generateMarkdownReport() string {
 	runID := filepath.Base(m.runDir)
 
 	// Map internal status to spec status
-	var statusText string
-	// Use group-based tracking for completion status
-	switch m.state.Status {
-	case "RUNNING":
-		statusText = "RUNNING"
-	case "COMPLETE":
-		statusText = "COMPLETED"
-	case "ERROR":
-		statusText = "ERRORED"
-	default:
-		statusText = "PENDING"
-	}
+	statusText := m.statusText()
 
 	// YAML frontmatter
 	sb.WriteString("---\n")
@@ -337,15 +542,80 @@ func (m *Manager) generateMarkdownReport() string {
 	fmt.Fprintf(sb, "run_path: %s\n", m.runDir)
 	fmt.Fprintf(sb, "detected_runner: %s\n", m.detectedRunner)
 	fmt.Fprintf(sb, "modified_command: `%s`\n", m.modifiedCommand)
+	if m.workers > 0 {
+		fmt.Fprintf(sb, "workers: %d\n", m.workers)
+	}
 	fmt.Fprintf(sb, "created: %s\n", m.state.Timestamp.UTC().Format("2006-01-02T15:04:05.000Z"))
 	fmt.Fprintf(sb, "updated: %s\n", m.state.UpdatedAt.UTC().Format("2006-01-02T15:04:05.000Z"))
 	fmt.Fprintf(sb, "status: %s\n", statusText)
+	if exitReason := m.exitReason(); exitReason != "" {
+		fmt.Fprintf(sb, "exit_reason: %s\n", exitReason)
+	}
+	if m.rerunOf != "" {
+		fmt.Fprintf(sb, "rerun_of: %s\n", m.rerunOf)
+	}
+	if m.shard != nil {
+		fmt.Fprintf(sb, "shard: %s\n", m.shard)
+	}
+	if m.cargo != nil {
+		fmt.Fprintf(sb, "cargo: %s\n", m.cargo)
+	}
 	sb.WriteString("---\n\n")
 
 	// Header
 	sb.WriteString("# 3pio Test Run\n\n")
 	fmt.Fprintf(sb, "- Test command: `%s`\n", m.state.Arguments)
-	sb.WriteString("- Run stdout/stderr: `./output.log`\n\n")
+	sb.WriteString("- Run stdout/stderr: `./output.log`\n")
+	if m.rerunOf != "" {
+		fmt.Fprintf(sb, "- Re-run of %d failed tests from %s\n", m.rerunCount, m.rerunOf)
+	}
+	if len(m.testFilters) > 0 {
+		fmt.Fprintf(sb, "- Filters: %s\n", strings.Join(m.testFilters, ", "))
+	}
+	if m.shard != nil {
+		fmt.Fprintf(sb, "- Shard %s: only this part of the test suite ran\n", m.shard)
+	}
+	if m.cargo != nil {
+		fmt.Fprintf(sb, "- Cargo build: %s\n", m.cargo)
+	}
+	if m.resultsSource != "" {
+		fmt.Fprintf(sb, "- Test results read from the %s once the command exited, not as the tests ran\n", resultsSourceName(m.resultsSource))
+	}
+	if m.interrupted {
+		sb.WriteString("- **Run interrupted**; groups that hadn't finished are marked INTERRUPTED\n")
+	} else if m.runTimeout > 0 {
+		fmt.Fprintf(sb, "- **Run timed out after %.0fs**; groups that hadn't finished are marked INTERRUPTED\n", m.runTimeout.Seconds())
+	}
+	if names := m.redactor.Names(); len(names) > 0 {
+		fmt.Fprintf(sb, "- Values of `%s` redacted\n", strings.Join(names, "`, `"))
+	}
+	if m.malformedIPCEvents == 1 {
+		sb.WriteString("- **Warning: 1 malformed IPC event ignored**; results may be missing, see .3pio/debug.log\n")
+	} else if m.malformedIPCEvents > 1 {
+		fmt.Fprintf(sb, "- **Warning: %d malformed IPC events ignored**; results may be missing, see .3pio/debug.log\n", m.malformedIPCEvents)
+	}
+	if len(m.warnings) > 0 {
+		fmt.Fprintf(sb, "- Warnings: %d, see Warnings below\n", len(m.warnings))
+	}
+	sb.WriteString("\n")
+
+	// Failures come first so they can be found without reading the whole report
+	if m.groupManager != nil {
+		m.generateFailuresSection(sb)
+		m.generateQuarantineSection(sb)
+		m.generateFlakySection(sb)
+		m.generateXPassSection(sb)
+	}
+	m.generateWarningsSection(sb)
+	m.generatePendingSnapshotsSection(sb)
+
+	// A stopped or crashed run leaves the results incomplete, so say so up front
+	if m.inactivityTimeout > 0 {
+		m.generateInactivityTimeoutSection(sb)
+	}
+	if len(m.crashes) > 0 {
+		m.generateCrashSection(sb)
+	}
 
 	// Error details if status is ERRORED
 	if statusText == "ERRORED" && m.state.ErrorDetails != "" {
@@ -355,6 +625,11 @@ func (m *Manager) generateMarkdownReport() string {
 		sb.WriteString("\n```\n\n")
 	}
 
+	// Compiler errors explain why no (or only some) tests ran
+	if len(m.buildErrors) > 0 {
+		m.generateBuildErrorsSection(sb)
+	}
+
 	// Always use group-based reporting
 	if m.groupManager != nil {
 		// Generate hierarchical summary and results using group data
@@ -365,208 +640,953 @@ func (m *Manager) generateMarkdownReport() string {
 		sb.WriteString("No test results available.\n")
 	}
 
+	if m.coverage != nil || m.coverageWarning != "" {
+		m.generateCoverageSection(sb)
+	}
+
+	// What the tests ran with, after the results it explains
+	if m.metadata != nil {
+		m.generateEnvironmentSection(sb)
+	}
+
 	return sb.String()
 }
 
-// generateGroupBasedReport generates summary and results using hierarchical group data
-func (m *Manager) generateGroupBasedReport(sb *strings.Builder, statusText string) {
-	// Summary section with test case statistics
-	if statusText != "ERRORED" {
-		sb.WriteString("## Summary\n\n")
+// statusText maps the internal run status to the one shown in the report
+func (m *Manager) statusText() string {
+	switch m.state.Status {
+	case "RUNNING":
+		return "RUNNING"
+	case "COMPLETE":
+		return "COMPLETED"
+	case "ERROR":
+		return "ERRORED"
+	case "INTERRUPTED":
+		return "INTERRUPTED"
+	default:
+		return "PENDING"
+	}
+}
 
-		// Calculate test case statistics from group data
-		rootGroups := m.groupManager.GetRootGroups()
-		totalTestCases := 0
-		completedTestCases := 0
-		passedTestCases := 0
-		failedTestCases := 0
-		skippedTestCases := 0
-		runningTestCases := 0
+// ExitReason says why a run ended, so the next step can be told apart from the exit
+// code alone: fix the build, fix the filter or fix the tests
+type ExitReason string
+
+const (
+	ExitReasonOK                ExitReason = "ok"
+	ExitReasonTestsFailed       ExitReason = "tests_failed"
+	ExitReasonBuildFailed       ExitReason = "build_failed"
+	ExitReasonNoTestsFound      ExitReason = "no_tests_found"     // The test filter matched no test
+	ExitReasonRunnerCrashed     ExitReason = "runner_crashed"     // A test binary died, or the runner failed without results
+	ExitReasonTimedOut          ExitReason = "timeout"            // --timeout
+	ExitReasonTestTimeout       ExitReason = "test_timeout"       // The runner's own timeout killed a test, e.g. go test -timeout
+	ExitReasonInactivityTimeout ExitReason = "inactivity_timeout" // --inactivity-timeout
+	ExitReasonInterrupted       ExitReason = "interrupted"
+	ExitReasonEnvironmentError  ExitReason = "environment_error" // The tests need something that isn't installed or set up
+	ExitReasonIncomplete        ExitReason = "incomplete"        // 3pio merge was missing shards of a sharded run
+)
 
-		// Calculate wall-clock duration from start time
-		totalDuration := time.Since(m.startTime).Seconds()
+// ExitReason returns why the run ended, or "" while it is still running
+func (m *Manager) ExitReason() ExitReason {
+	m.mu.RLock()
+	defer m.mu.RUnlock()
+	return m.exitReason()
+}
 
-		for _, group := range rootGroups {
-			// Count all test cases in the group and its subgroups
-			totalTestCases += countTotalTestCases(group)
-			completedTestCases += countCompletedTestCases(group)
-			passedTestCases += countPassedTestCases(group)
-			failedTestCases += countFailedTestCases(group)
-			skippedTestCases += countSkippedTestCases(group)
-			runningTestCases += countRunningTestCases(group)
-		}
+// exitReason returns why the run ended. A run stopped early has its reason as soon as
+// it's known, the others only once the run is finalized.
+func (m *Manager) exitReason() ExitReason {
+	switch {
+	case (len(m.buildErrors) > 0 || len(m.collectionErrors) > 0) && (m.groupManager == nil || len(m.groupManager.FailedTests()) == 0):
+		// Go builds each package and pytest collects each file, so other tests may have failed too
+		return ExitReasonBuildFailed
+	case m.interrupted:
+		return ExitReasonInterrupted
+	case m.runTimeout > 0:
+		return ExitReasonTimedOut
+	case m.inactivityTimeout > 0:
+		return ExitReasonInactivityTimeout
+	case len(m.crashes) > 0:
+		return ExitReasonRunnerCrashed
+	case m.state == nil || (m.state.Status != "COMPLETE" && m.state.Status != "ERROR"):
+		return ""
+	case m.environmentError:
+		return ExitReasonEnvironmentError
+	case m.noTestsFound:
+		return ExitReasonNoTestsFound
+	case m.state.Status == "ERROR":
+		return ExitReasonRunnerCrashed
+	case m.groupManager != nil && hasFailureKind(m.groupManager.FailedTests(), ipc.FailureKindTimedOut):
+		return ExitReasonTestTimeout
+	case m.exitCode != 0 || (m.groupManager != nil && len(m.groupManager.FailedTests()) > 0):
+		return ExitReasonTestsFailed
+	default:
+		return ExitReasonOK
+	}
+}
 
-		fmt.Fprintf(sb, "- Total test cases: %d\n", totalTestCases)
-		fmt.Fprintf(sb, "- Test cases completed: %d\n", completedTestCases)
-		if runningTestCases > 0 {
-			fmt.Fprintf(sb, "- Test cases running: %d\n", runningTestCases)
+// hasFailureKind reports whether any of the tests failed with the failure kind
+func hasFailureKind(tests []ipc.FailedTest, kind string) bool {
+	for _, test := range tests {
+		if test.FailureKind == kind {
+			return true
 		}
-		fmt.Fprintf(sb, "- Test cases passed: %d\n", passedTestCases)
-		fmt.Fprintf(sb, "- Test cases failed: %d\n", failedTestCases)
-		fmt.Fprintf(sb, "- Test cases skipped: %d\n", skippedTestCases)
-		fmt.Fprintf(sb, "- Total duration: %.2fs\n\n", totalDuration)
 	}
+	return false
+}
 
-	// Test group results section with table format
-	if len(m.groupManager.GetRootGroups()) > 0 {
-		sb.WriteString("## Test group results\n\n")
-		sb.WriteString("| Status | Name | Tests | Duration | Report |\n")
-		sb.WriteString("|--------|------|-------|----------|--------|\n")
+// SetNoTestsFound records that the runner ran no tests because its filter matched none
+func (m *Manager) SetNoTestsFound() {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	m.noTestsFound = true
+}
 
-		for _, group := range m.groupManager.GetRootGroups() {
-			statusStr := strings.ToUpper(string(group.Status))
-			if statusStr == "" {
-				statusStr = "PENDING"
-			}
-			filename := filepath.Base(group.Name)
-
-			// Tests column - show breakdown of test results including running tests
-			var testsStr string
-			// Calculate recursive counts on-the-fly for accurate display
-			runningCount := countRunningTestCases(group)
-			totalCount := countTotalTestCases(group)
-			passedCount := countPassedTestCases(group)
-			failedCount := countFailedTestCases(group)
-			skippedCount := countSkippedTestCases(group)
-
-			if statusStr == "RUNNING" || runningCount > 0 {
-				// Show running progress
-				parts := []string{}
-				if passedCount > 0 {
-					parts = append(parts, fmt.Sprintf("%d passed", passedCount))
-				}
-				if failedCount > 0 {
-					parts = append(parts, fmt.Sprintf("%d failed", failedCount))
-				}
-				if runningCount > 0 {
-					parts = append(parts, fmt.Sprintf("%d running", runningCount))
-				}
-				if skippedCount > 0 {
-					parts = append(parts, fmt.Sprintf("%d skipped", skippedCount))
-				}
-				if totalCount > 0 && len(parts) == 0 {
-					// No tests have started yet
-					testsStr = fmt.Sprintf("%d pending", totalCount)
-				} else if len(parts) > 0 {
-					testsStr = strings.Join(parts, ", ")
-				} else {
-					testsStr = "-"
-				}
-			} else if totalCount > 0 {
-				// Completed group - show final results based on recursive counts
-				parts := []string{}
-				if passedCount > 0 {
-					parts = append(parts, fmt.Sprintf("%d passed", passedCount))
-				}
-				if failedCount > 0 {
-					parts = append(parts, fmt.Sprintf("%d failed", failedCount))
-				}
-				if skippedCount > 0 {
-					parts = append(parts, fmt.Sprintf("%d skipped", skippedCount))
-				}
-				testsStr = strings.Join(parts, ", ")
-			} else if group.Stats.SetupFailed {
-				// Setup failure - no tests ran
-				testsStr = "setup failed"
-			} else {
-				testsStr = "0 tests"
-			}
+// SetEnvironmentError records that the tests couldn't run as the environment they need
+// isn't set up, e.g. the WebDriver of a headless browser failed to start
+func (m *Manager) SetEnvironmentError() {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	m.environmentError = true
+}
 
-			// Duration column - show elapsed time for running groups, final duration for completed
-			var durationStr string
-			if statusStr == "RUNNING" && !group.StartTime.IsZero() {
-				// Show elapsed time for running groups
-				elapsed := time.Since(group.StartTime).Seconds()
-				durationStr = fmt.Sprintf("%.2fs", elapsed)
-			} else if group.Duration > 0 {
-				// Show final duration for completed groups
-				durationStr = fmt.Sprintf("%.2fs", group.Duration.Seconds())
-			} else if statusStr == "PENDING" {
-				// Not started yet
-				durationStr = "-"
-			} else {
-				// Fallback
-				durationStr = "0.00s"
-			}
+// generateInactivityTimeoutSection lists the tests that were running when the run was stopped
+func (m *Manager) generateInactivityTimeoutSection(sb *strings.Builder) {
+	sb.WriteString("## Inactivity timeout\n\n")
+	fmt.Fprintf(sb, "No output or test events arrived for %.0fs while tests were running, so the test command was stopped.\n\n", m.inactivityTimeout.Seconds())
+	if len(m.timedOutTests) > 0 {
+		sb.WriteString("In flight when the timeout fired:\n\n")
+		for _, name := range m.timedOutTests {
+			fmt.Fprintf(sb, "- `%s`\n", name)
+		}
+		sb.WriteString("\n")
+	}
+}
+
+// generateCrashSection lists the test binaries that crashed and the test each was running
+func (m *Manager) generateCrashSection(sb *strings.Builder) {
+	sb.WriteString("## Test binary crashed\n\n")
+	for _, crash := range m.crashes {
+		fmt.Fprintf(sb, "- `%s`: %s", crash.Target, crash.Message)
+		if crash.TestName != "" {
+			fmt.Fprintf(sb, " while running `%s`", crash.TestName)
+		}
+		sb.WriteString("\n")
+	}
+	sb.WriteString("\nTests that had started but not finished are reported as not run. See `./output.log` for the full output.\n\n")
+}
+
+// generateBuildErrorsSection lists compiler errors with their location and rendered output
+func (m *Manager) generateBuildErrorsSection(sb *strings.Builder) {
+	sb.WriteString("## Build errors\n\n")
+	for _, buildErr := range m.buildErrors {
+		title := buildErr.Message
+		if buildErr.Code != "" {
+			title = fmt.Sprintf("error[%s]: %s", buildErr.Code, buildErr.Message)
+		}
+		fmt.Fprintf(sb, "### %s\n\n", title)
 
-			// Generate report file path
-			reportFile := GetReportFilePath(group, m.runDir)
-			// Make it relative to the run directory
-			if relPath, err := filepath.Rel(m.runDir, reportFile); err == nil {
-				reportFile = "./" + relPath
+		if buildErr.File != "" {
+			location := buildErr.File
+			if buildErr.Line > 0 {
+				location = fmt.Sprintf("%s:%d", buildErr.File, buildErr.Line)
 			}
+			fmt.Fprintf(sb, "- Location: `%s`\n", location)
+		}
+		if buildErr.Target != "" {
+			fmt.Fprintf(sb, "- Target: `%s`\n", buildErr.Target)
+		}
 
-			fmt.Fprintf(sb, "| %s | %s | %s | %s | %s |\n", statusStr, filename, testsStr, durationStr, reportFile)
+		// cargo renders errors in color when it writes to a terminal
+		rendered := buildErr.Rendered
+		if rendered == "" {
+			rendered = buildErr.Message
 		}
+		sb.WriteString("\n```\n")
+		sb.WriteString(strings.TrimRight(console.PlainText(rendered), "\n"))
+		sb.WriteString("\n```\n\n")
 	}
 }
 
-// Helper functions to count test cases recursively
-func countTotalTestCases(group *TestGroup) int {
-	count := len(group.TestCases)
-	for _, subgroup := range group.Subgroups {
-		count += countTotalTestCases(subgroup)
+// maxFailureDigest caps the failures listed at the top of test-run.md
+const maxFailureDigest = 50
+
+// generateFailuresSection lists every failed test and errored group with a one-line
+// excerpt of the failure and a link to its group report. Nothing is written when
+// there are no failures.
+func (m *Manager) generateFailuresSection(sb *strings.Builder) {
+	type failure struct {
+		path       []string
+		excerpt    string
+		report     string
+		annotation string // New or still failing since the previous run, empty without one
 	}
-	return count
-}
+	var failures []failure
 
-func countCompletedTestCases(group *TestGroup) int {
-	count := 0
-	for _, test := range group.TestCases {
-		if test.Status != TestStatusPending && test.Status != TestStatusRunning {
-			count++
+	var walk func(group *TestGroup)
+	walk = func(group *TestGroup) {
+		path := make([]string, 0, len(group.ParentNames)+2)
+		for _, name := range group.GetFullPath() {
+			path = append(path, m.groupManager.makeRelativePath(name))
+		}
+		if group.Status == TestStatusError && group.ErrorInfo != nil {
+			failures = append(failures, failure{
+				path:       path,
+				excerpt:    failureExcerpt(group.ErrorInfo),
+				report:     relativeReportPath(group, m.runDir),
+				annotation: m.failureAnnotation(group.ID),
+			})
+		}
+		for _, tc := range group.TestCases {
+			if tc.Status == TestStatusFail || tc.Status == TestStatusError {
+				failures = append(failures, failure{
+					path:       append(append([]string(nil), path...), tc.Name),
+					excerpt:    failureExcerpt(tc.Error),
+					report:     relativeReportPath(group, m.runDir),
+					annotation: m.failureAnnotation(tc.TestID),
+				})
+			}
+		}
+
+		subgroups := make([]*TestGroup, 0, len(group.Subgroups))
+		for _, subgroup := range group.Subgroups {
+			subgroups = append(subgroups, subgroup)
+		}
+		for _, subgroup := range sortedGroups(subgroups) {
+			walk(subgroup)
 		}
 	}
-	for _, subgroup := range group.Subgroups {
-		count += countCompletedTestCases(subgroup)
+	for _, group := range sortedGroups(m.groupManager.GetRootGroups()) {
+		walk(group)
 	}
-	return count
-}
 
-func countPassedTestCases(group *TestGroup) int {
-	count := 0
-	for _, test := range group.TestCases {
-		if test.Status == TestStatusPass {
-			count++
-		}
+	if len(failures) == 0 {
+		return
 	}
-	for _, subgroup := range group.Subgroups {
-		count += countPassedTestCases(subgroup)
+
+	sb.WriteString("## Failures\n\n")
+	for i, f := range failures {
+		if i == maxFailureDigest {
+			fmt.Fprintf(sb, "- …and %d more\n", len(failures)-maxFailureDigest)
+			break
+		}
+		fmt.Fprintf(sb, "- `%s`", BuildHierarchicalPathFromSlice(f.path))
+		if f.annotation != "" {
+			fmt.Fprintf(sb, " (%s)", f.annotation)
+		}
+		if f.excerpt != "" {
+			fmt.Fprintf(sb, ": %s", f.excerpt)
+		}
+		fmt.Fprintf(sb, " ([report](%s))\n", NormalizeFilePath(f.report))
 	}
-	return count
+	sb.WriteString("\n")
 }
 
-func countFailedTestCases(group *TestGroup) int {
-	count := 0
-	for _, test := range group.TestCases {
-		if test.Status == TestStatusFail {
-			count++
+// generateQuarantineSection lists the quarantined tests that failed, which didn't fail
+// the run, and the ones that passed, which may no longer need to be quarantined.
+// Nothing is written when no test matched the quarantine list.
+func (m *Manager) generateQuarantineSection(sb *strings.Builder) {
+	var failed, passed []string
+
+	var walk func(group *TestGroup)
+	walk = func(group *TestGroup) {
+		path := make([]string, 0, len(group.ParentNames)+2)
+		for _, name := range group.GetFullPath() {
+			path = append(path, m.groupManager.makeRelativePath(name))
+		}
+		for _, tc := range group.TestCases {
+			if !tc.Quarantined {
+				continue
+			}
+			name := BuildHierarchicalPathFromSlice(append(append([]string(nil), path...), tc.Name))
+			switch tc.Status {
+			case TestStatusQuarantinedFail:
+				line := fmt.Sprintf("- `%s`", name)
+				if excerpt := failureExcerpt(tc.Error); excerpt != "" {
+					line += ": " + excerpt
+				}
+				failed = append(failed, line+fmt.Sprintf(" ([report](%s))", NormalizeFilePath(relativeReportPath(group, m.runDir))))
+			case TestStatusPass:
+				passed = append(passed, fmt.Sprintf("- `%s`", name))
+			}
+		}
+
+		subgroups := make([]*TestGroup, 0, len(group.Subgroups))
+		for _, subgroup := range group.Subgroups {
+			subgroups = append(subgroups, subgroup)
+		}
+		for _, subgroup := range sortedGroups(subgroups) {
+			walk(subgroup)
 		}
 	}
-	for _, subgroup := range group.Subgroups {
-		count += countFailedTestCases(subgroup)
+	for _, group := range sortedGroups(m.groupManager.GetRootGroups()) {
+		walk(group)
 	}
-	return count
-}
 
-func countSkippedTestCases(group *TestGroup) int {
-	count := 0
-	for _, test := range group.TestCases {
-		if test.Status == TestStatusSkip {
-			count++
+	if len(failed) > 0 {
+		sb.WriteString("## Quarantined failures\n\n")
+		sb.WriteString("These tests are on the quarantine list, so their failures don't fail the run.\n\n")
+		for i, line := range failed {
+			if i == maxFailureDigest {
+				fmt.Fprintf(sb, "- …and %d more\n", len(failed)-maxFailureDigest)
+				break
+			}
+			sb.WriteString(line + "\n")
 		}
+		sb.WriteString("\n")
 	}
-	for _, subgroup := range group.Subgroups {
-		count += countSkippedTestCases(subgroup)
+	if len(passed) > 0 {
+		sb.WriteString("## Quarantined tests that passed\n\n")
+		sb.WriteString("These tests may be removable from the quarantine list.\n\n")
+		for _, line := range passed {
+			sb.WriteString(line + "\n")
+		}
+		sb.WriteString("\n")
 	}
-	return count
 }
 
-func countRunningTestCases(group *TestGroup) int {
-	count := 0
-	for _, test := range group.TestCases {
-		if test.Status == TestStatusRunning || test.Status == TestStatusPending {
-			count++
+// generateFlakySection lists the tests that failed and then passed when retried
+// (--retries), with the attempts each needed
+func (m *Manager) generateFlakySection(sb *strings.Builder) {
+	var lines []string
+
+	var walk func(group *TestGroup)
+	walk = func(group *TestGroup) {
+		path := make([]string, 0, len(group.ParentNames)+2)
+		for _, name := range group.GetFullPath() {
+			path = append(path, m.groupManager.makeRelativePath(name))
+		}
+		for _, tc := range group.TestCases {
+			if tc.Status != TestStatusFlaky {
+				continue
+			}
+			name := BuildHierarchicalPathFromSlice(append(append([]string(nil), path...), tc.Name))
+			lines = append(lines, fmt.Sprintf("- `%s`: passed on attempt %d ([report](%s))",
+				name, len(tc.Attempts), NormalizeFilePath(relativeReportPath(group, m.runDir))))
+		}
+
+		subgroups := make([]*TestGroup, 0, len(group.Subgroups))
+		for _, subgroup := range group.Subgroups {
+			subgroups = append(subgroups, subgroup)
+		}
+		for _, subgroup := range sortedGroups(subgroups) {
+			walk(subgroup)
+		}
+	}
+	for _, group := range sortedGroups(m.groupManager.GetRootGroups()) {
+		walk(group)
+	}
+
+	if len(lines) == 0 {
+		return
+	}
+	sb.WriteString("## Flaky tests\n\n")
+	sb.WriteString("These tests failed, then passed when retried. Each attempt's output is in the group report.\n\n")
+	for i, line := range lines {
+		if i == maxFailureDigest {
+			fmt.Fprintf(sb, "- …and %d more\n", len(lines)-maxFailureDigest)
+			break
+		}
+		sb.WriteString(line + "\n")
+	}
+	sb.WriteString("\n")
+}
+
+// generateWarningsSection lists the warnings the runner recorded, as pytest's warnings
+// summary does
+func (m *Manager) generateWarningsSection(sb *strings.Builder) {
+	if len(m.warnings) == 0 {
+		return
+	}
+	sb.WriteString("## Warnings\n\n")
+	for i, warning := range m.warnings {
+		if i == maxFailureDigest {
+			fmt.Fprintf(sb, "- …and %d more\n", len(m.warnings)-maxFailureDigest)
+			break
+		}
+		line := fmt.Sprintf("- **%s**", warning.Category)
+		if warning.File != "" {
+			location := warning.File
+			if m.groupManager != nil {
+				location = m.groupManager.makeRelativePath(location)
+			}
+			if warning.Line > 0 {
+				location += fmt.Sprintf(":%d", warning.Line)
+			}
+			line += fmt.Sprintf(" at `%s`", location)
+		}
+		line += ": " + strings.Join(strings.Fields(warning.Message), " ")
+		var notes []string
+		if warning.NodeID != "" {
+			notes = append(notes, fmt.Sprintf("`%s`", warning.NodeID))
+		}
+		if warning.count > 1 {
+			notes = append(notes, fmt.Sprintf("%d times", warning.count))
+		}
+		if len(notes) > 0 {
+			line += " (" + strings.Join(notes, ", ") + ")"
+		}
+		sb.WriteString(line + "\n")
+	}
+	sb.WriteString("\n")
+}
+
+// generatePendingSnapshotsSection lists the snapshots that didn't match, which insta
+// wrote the new values of for review
+func (m *Manager) generatePendingSnapshotsSection(sb *strings.Builder) {
+	if len(m.pendingSnapshots) == 0 {
+		return
+	}
+	sb.WriteString("## Pending snapshots\n\n")
+	sb.WriteString("These snapshots didn't match, and insta wrote their new values next to them. Run `cargo insta review` to accept or reject them.\n\n")
+	for i, path := range m.pendingSnapshots {
+		if i == maxFailureDigest {
+			fmt.Fprintf(sb, "- …and %d more\n", len(m.pendingSnapshots)-maxFailureDigest)
+			break
+		}
+		fmt.Fprintf(sb, "- `%s`\n", path)
+	}
+	sb.WriteString("\n")
+}
+
+// maxLeastCoveredFiles is the number of files in the coverage section's table
+const maxLeastCoveredFiles = 10
+
+// generateCoverageSection writes the run's total line and function coverage with a
+// table of the least covered files of the workspace
+func (m *Manager) generateCoverageSection(sb *strings.Builder) {
+	sb.WriteString("## Coverage\n\n")
+	if m.coverage == nil {
+		fmt.Fprintf(sb, "**Warning: no coverage to report**: %s\n\n", m.coverageWarning)
+		return
+	}
+	lines, functions := m.coverage.Lines, m.coverage.Functions
+	fmt.Fprintf(sb, "- Lines: %.2f%% (%d of %d)\n", lines.Percent, lines.Covered, lines.Total)
+	fmt.Fprintf(sb, "- Functions: %.2f%% (%d of %d)\n", functions.Percent, functions.Covered, functions.Total)
+	fmt.Fprintf(sb, "- Report: `%s`\n\n", m.coverage.Report)
+
+	files := m.coverage.LeastCovered(maxLeastCoveredFiles)
+	if len(files) == 0 {
+		return
+	}
+	sb.WriteString("| Lines | Functions | File |\n")
+	sb.WriteString("|-------|-----------|------|\n")
+	for _, file := range files {
+		fmt.Fprintf(sb, "| %.2f%% (%d of %d) | %.2f%% (%d of %d) | %s |\n",
+			file.Lines.Percent, file.Lines.Covered, file.Lines.Total,
+			file.Functions.Percent, file.Functions.Covered, file.Functions.Total, file.Path)
+	}
+	sb.WriteString("\n")
+}
+
+// generateXPassSection lists the tests marked as expected failures (pytest xfail) that
+// passed, with the reasons of their markers
+func (m *Manager) generateXPassSection(sb *strings.Builder) {
+	var lines []string
+
+	var walk func(group *TestGroup)
+	walk = func(group *TestGroup) {
+		path := make([]string, 0, len(group.ParentNames)+2)
+		for _, name := range group.GetFullPath() {
+			path = append(path, m.groupManager.makeRelativePath(name))
+		}
+		for _, tc := range group.TestCases {
+			if tc.Status != TestStatusXPass {
+				continue
+			}
+			name := BuildHierarchicalPathFromSlice(append(append([]string(nil), path...), tc.Name))
+			line := fmt.Sprintf("- `%s`", name)
+			if tc.XFailReason != "" {
+				line += ": " + tc.XFailReason
+			}
+			lines = append(lines, fmt.Sprintf("%s ([report](%s))", line, NormalizeFilePath(relativeReportPath(group, m.runDir))))
+		}
+
+		subgroups := make([]*TestGroup, 0, len(group.Subgroups))
+		for _, subgroup := range group.Subgroups {
+			subgroups = append(subgroups, subgroup)
+		}
+		for _, subgroup := range sortedGroups(subgroups) {
+			walk(subgroup)
+		}
+	}
+	for _, group := range sortedGroups(m.groupManager.GetRootGroups()) {
+		walk(group)
+	}
+
+	if len(lines) == 0 {
+		return
+	}
+	sb.WriteString("## Unexpected passes\n\n")
+	sb.WriteString("These tests are marked as expected to fail, but passed. What the marker is waiting for may be fixed.\n\n")
+	for i, line := range lines {
+		if i == maxFailureDigest {
+			fmt.Fprintf(sb, "- …and %d more\n", len(lines)-maxFailureDigest)
+			break
+		}
+		sb.WriteString(line + "\n")
+	}
+	sb.WriteString("\n")
+}
+
+// maxSlowestTests is the number of tests in the slowest tests table
+const maxSlowestTests = 10
+
+// generateSlowestTestsSection writes a table of the slowest tests across all groups.
+// Runners that don't report per-test durations get no table rather than one of zeros.
+func (m *Manager) generateSlowestTestsSection(sb *strings.Builder) {
+	type timedTest struct {
+		name     string
+		group    *TestGroup
+		duration time.Duration
+	}
+	var tests []timedTest
+	var walk func(group *TestGroup)
+	walk = func(group *TestGroup) {
+		for _, tc := range group.TestCases {
+			if tc.Duration > 0 && tc.Status != TestStatusBench {
+				tests = append(tests, timedTest{name: tc.Name, group: group, duration: tc.Duration})
+			}
+		}
+		for _, subgroup := range group.Subgroups {
+			walk(subgroup)
+		}
+	}
+	for _, group := range m.groupManager.GetRootGroups() {
+		walk(group)
+	}
+	if len(tests) == 0 {
+		return
+	}
+
+	sort.SliceStable(tests, func(i, j int) bool {
+		if tests[i].duration != tests[j].duration {
+			return tests[i].duration > tests[j].duration
+		}
+		return tests[i].group.ID+"\x00"+tests[i].name < tests[j].group.ID+"\x00"+tests[j].name
+	})
+	if len(tests) > maxSlowestTests {
+		tests = tests[:maxSlowestTests]
+	}
+
+	sb.WriteString("## Slowest tests\n\n")
+	sb.WriteString("| Duration | Test | Group | Report |\n")
+	sb.WriteString("|----------|------|-------|--------|\n")
+	for _, test := range tests {
+		path := make([]string, 0, len(test.group.ParentNames)+1)
+		for _, name := range test.group.GetFullPath() {
+			path = append(path, m.groupManager.makeRelativePath(name))
+		}
+		fmt.Fprintf(sb, "| %.2fs | %s | %s | %s |\n", test.duration.Seconds(), test.name,
+			BuildHierarchicalPathFromSlice(path), NormalizeFilePath(relativeReportPath(test.group, m.runDir)))
+	}
+	sb.WriteString("\n")
+}
+
+// maxExcerptLength caps a failure excerpt in the failures section, in characters
+const maxExcerptLength = 120
+
+// failureExcerpt returns a one-line summary of a failure: how to accept a new trybuild
+// case or which case failed, expected and actual values for structured assertions,
+// otherwise the first line of the message
+func failureExcerpt(testErr *TestError) string {
+	if testErr == nil {
+		return ""
+	}
+	for _, tbCase := range testErr.Trybuild {
+		if tbCase.WipFile != "" && tbCase.StderrFile != "" {
+			// The one command that accepts the new case's output
+			return fmt.Sprintf("trybuild wrote `%s`, accept it with `mv %s %s`", tbCase.WipFile, tbCase.WipFile, tbCase.StderrFile)
+		}
+	}
+	if len(testErr.Trybuild) > 0 {
+		tbCase := testErr.Trybuild[0]
+		return fmt.Sprintf("trybuild `%s`: %s", tbCase.Path, tbCase.Status)
+	}
+	if testErr.Expected != "" || testErr.Actual != "" {
+		return fmt.Sprintf("expected `%s`, got `%s`",
+			truncateExcerpt(firstLine(testErr.Expected)), truncateExcerpt(firstLine(testErr.Actual)))
+	}
+	return truncateExcerpt(firstLine(testErr.Message))
+}
+
+// firstLine returns the first non-empty line of s, trimmed
+func firstLine(s string) string {
+	for _, line := range strings.Split(s, "\n") {
+		if line = strings.TrimSpace(line); line != "" {
+			return line
+		}
+	}
+	return ""
+}
+
+// truncateExcerpt shortens s to maxExcerptLength characters, marking the cut with …
+func truncateExcerpt(s string) string {
+	runes := []rune(s)
+	if len(runes) <= maxExcerptLength {
+		return s
+	}
+	return string(runes[:maxExcerptLength-1]) + "…"
+}
+
+// generateGroupBasedReport generates summary and results using hierarchical group data
+func (m *Manager) generateGroupBasedReport(sb *strings.Builder, statusText string) {
+	// Summary section with test case statistics
+	if statusText != "ERRORED" {
+		sb.WriteString("## Summary\n\n")
+
+		// Calculate test case statistics from group data
+		rootGroups := m.groupManager.GetRootGroups()
+		totalTestCases := 0
+		completedTestCases := 0
+		passedTestCases := 0
+		failedTestCases := 0
+		skippedTestCases := 0
+		quarantinedTestCases := 0
+		flakyTestCases := 0
+		erroredTestCases := 0
+		runningTestCases := 0
+		benchmarks := 0
+		regressedBenchmarks := 0
+
+		// Calculate wall-clock duration from start time
+		totalDuration := time.Since(m.startTime).Seconds()
+
+		for _, group := range rootGroups {
+			// Count all test cases in the group and its subgroups
+			totalTestCases += countTotalTestCases(group)
+			completedTestCases += countCompletedTestCases(group)
+			passedTestCases += countPassedTestCases(group)
+			failedTestCases += countFailedTestCases(group)
+			skippedTestCases += countSkippedTestCases(group)
+			quarantinedTestCases += countQuarantinedTestCases(group)
+			flakyTestCases += countFlakyTestCases(group)
+			erroredTestCases += countErroredTestCases(group)
+			runningTestCases += countRunningTestCases(group)
+			benchmarks += countBenchmarks(group)
+			regressedBenchmarks += countRegressedBenchmarks(group)
+		}
+
+		fmt.Fprintf(sb, "- Total test cases: %d\n", totalTestCases)
+		fmt.Fprintf(sb, "- Test cases completed: %d\n", completedTestCases)
+		if runningTestCases > 0 {
+			fmt.Fprintf(sb, "- Test cases running: %d\n", runningTestCases)
+		}
+		fmt.Fprintf(sb, "- Test cases passed: %d\n", passedTestCases)
+		fmt.Fprintf(sb, "- Test cases failed: %d\n", failedTestCases)
+		if erroredTestCases > 0 {
+			// Setup and teardown errors are counted apart from failures, like pytest does
+			fmt.Fprintf(sb, "- Test cases errored: %d\n", erroredTestCases)
+		}
+		fmt.Fprintf(sb, "- Test cases skipped: %d\n", skippedTestCases)
+		if quarantinedTestCases > 0 {
+			fmt.Fprintf(sb, "- Test cases failed while quarantined: %d\n", quarantinedTestCases)
+		}
+		if flakyTestCases > 0 {
+			fmt.Fprintf(sb, "- Test cases flaky: %d\n", flakyTestCases)
+		}
+		if len(m.collectionErrors) > 0 {
+			fmt.Fprintf(sb, "- Test files that failed to collect: %d\n", len(m.collectionErrors))
+		}
+		if benchmarks > 0 {
+			// Benchmarks are not test cases, so they are counted on their own
+			fmt.Fprintf(sb, "- Benchmarks: %d\n", benchmarks)
+		}
+		if regressedBenchmarks > 0 {
+			fmt.Fprintf(sb, "- Benchmarks regressed: %d\n", regressedBenchmarks)
+		}
+		fmt.Fprintf(sb, "- Total duration: %.2fs\n\n", totalDuration)
+	}
+
+	m.generateSlowestTestsSection(sb)
+
+	// Test group results section with table format
+	rootGroups := m.groupManager.GetRootGroups()
+	if len(rootGroups) > 0 {
+		sb.WriteString("## Test group results\n\n")
+		if !hasTargetKinds(rootGroups) {
+			writeGroupResultsTable(sb, rootGroups, m.runDir)
+			return
+		}
+
+		// Rust test targets each get their own section, so e.g. a unit test and an
+		// integration test with the same name are told apart
+		byKind := make(map[string][]*TestGroup)
+		for _, group := range rootGroups {
+			byKind[group.TargetKind] = append(byKind[group.TargetKind], group)
+		}
+		for _, section := range targetKindSections {
+			if groups := byKind[section.kind]; len(groups) > 0 {
+				fmt.Fprintf(sb, "### %s\n\n", section.title)
+				writeGroupResultsTable(sb, groups, m.runDir)
+				sb.WriteString("\n")
+			}
+		}
+		if groups := byKind[""]; len(groups) > 0 {
+			sb.WriteString("### Other\n\n")
+			writeGroupResultsTable(sb, groups, m.runDir)
+			sb.WriteString("\n")
+		}
+	}
+}
+
+// targetKindSections orders and titles the test-run.md sections for Rust test target kinds
+var targetKindSections = []struct {
+	kind  string
+	title string
+}{
+	{"lib", "Unit tests (lib)"},
+	{"bin", "Binary tests (bin)"},
+	{"test", "Integration tests (test)"},
+	{"example", "Example tests (example)"},
+	{"bench", "Benchmark targets (bench)"},
+}
+
+// hasTargetKinds reports whether any group is tagged with a Rust test target kind
+func hasTargetKinds(groups []*TestGroup) bool {
+	for _, group := range groups {
+		if group.TargetKind != "" {
+			return true
+		}
+	}
+	return false
+}
+
+// writeGroupResultsTable writes the results table for a list of root groups
+func writeGroupResultsTable(sb *strings.Builder, groups []*TestGroup, runDir string) {
+	sb.WriteString("| Status | Name | Tests | Duration | Report |\n")
+	sb.WriteString("|--------|------|-------|----------|--------|\n")
+	for _, group := range groups {
+		writeGroupResultRow(sb, group, runDir)
+	}
+}
+
+// writeGroupResultRow writes a root group's row in the results table
+func writeGroupResultRow(sb *strings.Builder, group *TestGroup, runDir string) {
+	statusStr := strings.ToUpper(string(group.Status))
+	if statusStr == "" {
+		statusStr = "PENDING"
+	}
+	filename := filepath.Base(group.Name)
+
+	// Tests column - show breakdown of test results including running tests
+	var testsStr string
+	// Calculate recursive counts on-the-fly for accurate display
+	runningCount := countRunningTestCases(group)
+	totalCount := countTotalTestCases(group)
+	passedCount := countPassedTestCases(group)
+	failedCount := countFailedTestCases(group)
+	erroredCount := countErroredTestCases(group)
+	skippedCount := countSkippedTestCases(group)
+
+	if statusStr == "RUNNING" || runningCount > 0 {
+		// Show running progress
+		parts := []string{}
+		if passedCount > 0 {
+			parts = append(parts, fmt.Sprintf("%d passed", passedCount))
+		}
+		if failedCount > 0 {
+			parts = append(parts, fmt.Sprintf("%d failed", failedCount))
+		}
+		if erroredCount > 0 {
+			parts = append(parts, fmt.Sprintf("%d errored", erroredCount))
+		}
+		if runningCount > 0 {
+			parts = append(parts, fmt.Sprintf("%d running", runningCount))
+		}
+		if skippedCount > 0 {
+			parts = append(parts, fmt.Sprintf("%d skipped", skippedCount))
+		}
+		if totalCount > 0 && len(parts) == 0 {
+			// No tests have started yet
+			testsStr = fmt.Sprintf("%d pending", totalCount)
+		} else if len(parts) > 0 {
+			testsStr = strings.Join(parts, ", ")
+		} else {
+			testsStr = "-"
+		}
+	} else if totalCount > 0 {
+		// Completed group - show final results based on recursive counts
+		parts := []string{}
+		if passedCount > 0 {
+			parts = append(parts, fmt.Sprintf("%d passed", passedCount))
+		}
+		if failedCount > 0 {
+			parts = append(parts, fmt.Sprintf("%d failed", failedCount))
+		}
+		if erroredCount > 0 {
+			parts = append(parts, fmt.Sprintf("%d errored", erroredCount))
+		}
+		if skippedCount > 0 {
+			parts = append(parts, fmt.Sprintf("%d skipped", skippedCount))
+		}
+		testsStr = strings.Join(parts, ", ")
+	} else if group.Stats.SetupFailed {
+		// Setup failure - no tests ran
+		testsStr = "setup failed"
+	} else {
+		testsStr = "0 tests"
+	}
+
+	// Duration column - show elapsed time for running groups, final duration for completed
+	var durationStr string
+	if statusStr == "RUNNING" && !group.StartTime.IsZero() {
+		// Show elapsed time for running groups
+		elapsed := time.Since(group.StartTime).Seconds()
+		durationStr = fmt.Sprintf("%.2fs", elapsed)
+	} else if group.Duration > 0 {
+		// Show final duration for completed groups
+		durationStr = fmt.Sprintf("%.2fs", group.Duration.Seconds())
+	} else if statusStr == "PENDING" {
+		// Not started yet
+		durationStr = "-"
+	} else {
+		// Fallback
+		durationStr = "0.00s"
+	}
+
+	// Report file path, relative to the run directory
+	reportFile := relativeReportPath(group, runDir)
+
+	fmt.Fprintf(sb, "| %s | %s | %s | %s | %s |\n", statusStr, filename, testsStr, durationStr, reportFile)
+}
+
+// Helper functions to count test cases recursively
+func countTotalTestCases(group *TestGroup) int {
+	count := 0
+	for _, test := range group.TestCases {
+		if test.Status != TestStatusBench {
+			count++
+		}
+	}
+	for _, subgroup := range group.Subgroups {
+		count += countTotalTestCases(subgroup)
+	}
+	return count
+}
+
+func countCompletedTestCases(group *TestGroup) int {
+	count := 0
+	for _, test := range group.TestCases {
+		if test.Status != TestStatusPending && test.Status != TestStatusRunning && test.Status != TestStatusBench {
+			count++
+		}
+	}
+	for _, subgroup := range group.Subgroups {
+		count += countCompletedTestCases(subgroup)
+	}
+	return count
+}
+
+func countPassedTestCases(group *TestGroup) int {
+	count := 0
+	for _, test := range group.TestCases {
+		if test.Status == TestStatusPass {
+			count++
+		}
+	}
+	for _, subgroup := range group.Subgroups {
+		count += countPassedTestCases(subgroup)
+	}
+	return count
+}
+
+func countFailedTestCases(group *TestGroup) int {
+	count := 0
+	for _, test := range group.TestCases {
+		if test.Status == TestStatusFail {
+			count++
+		}
+	}
+	for _, subgroup := range group.Subgroups {
+		count += countFailedTestCases(subgroup)
+	}
+	return count
+}
+
+func countQuarantinedTestCases(group *TestGroup) int {
+	count := 0
+	for _, test := range group.TestCases {
+		if test.Status == TestStatusQuarantinedFail {
+			count++
+		}
+	}
+	for _, subgroup := range group.Subgroups {
+		count += countQuarantinedTestCases(subgroup)
+	}
+	return count
+}
+
+func countFlakyTestCases(group *TestGroup) int {
+	count := 0
+	for _, test := range group.TestCases {
+		if test.Status == TestStatusFlaky {
+			count++
+		}
+	}
+	for _, subgroup := range group.Subgroups {
+		count += countFlakyTestCases(subgroup)
+	}
+	return count
+}
+
+func countErroredTestCases(group *TestGroup) int {
+	count := 0
+	for _, test := range group.TestCases {
+		if test.Status == TestStatusError {
+			count++
+		}
+	}
+	for _, subgroup := range group.Subgroups {
+		count += countErroredTestCases(subgroup)
+	}
+	return count
+}
+
+func countBenchmarks(group *TestGroup) int {
+	count := 0
+	for _, test := range group.TestCases {
+		if test.Status == TestStatusBench {
+			count++
+		}
+	}
+	for _, subgroup := range group.Subgroups {
+		count += countBenchmarks(subgroup)
+	}
+	return count
+}
+
+func countRegressedBenchmarks(group *TestGroup) int {
+	count := 0
+	for _, test := range group.TestCases {
+		if test.Status == TestStatusBench && test.BenchRegressed {
+			count++
+		}
+	}
+	for _, subgroup := range group.Subgroups {
+		count += countRegressedBenchmarks(subgroup)
+	}
+	return count
+}
+
+func countSkippedTestCases(group *TestGroup) int {
+	count := 0
+	for _, test := range group.TestCases {
+		if test.Status == TestStatusSkip {
+			count++
+		}
+	}
+	for _, subgroup := range group.Subgroups {
+		count += countSkippedTestCases(subgroup)
+	}
+	return count
+}
+
+func countRunningTestCases(group *TestGroup) int {
+	count := 0
+	for _, test := range group.TestCases {
+		if test.Status == TestStatusRunning || test.Status == TestStatusPending {
+			count++
 		}
 	}
 	for _, subgroup := range group.Subgroups {
@@ -620,7 +1640,7 @@ func countRunningTestCases(group *TestGroup) int {
 		fmt.Fprintf(sb, "%s%s %s%s\n", testIndent, testIcon, testCase.Name, durationStr)
 
 		// Show error for failed tests
-		if testCase.Status == TestStatusFail && testCase.Error != nil && testCase.Error.Message != "" {
+		if (testCase.Status == TestStatusFail || testCase.Status == TestStatusError) && testCase.Error != nil && testCase.Error.Message != "" {
 			errorIndent := strings.Repeat("  ", indent+2)
 			fmt.Fprintf(sb, "%s```\n", errorIndent)
 			fmt.Fprintf(sb, "%s%s\n", errorIndent, testCase.Error.Message)
@@ -649,7 +1669,27 @@ func (m *Manager) Finalize(exitCode int, errorDetails ...string) error {
 
 	// Flush all pending group reports
 	if m.groupManager != nil {
+		if m.runTimeout > 0 || m.interrupted {
+			m.groupManager.InterruptIncompleteGroups()
+		}
 		m.groupManager.Flush()
+
+		// Record the failures so the next run can re-run just those
+		if err := m.writeFailedTests(); err != nil {
+			m.logger.Error("Failed to write %s: %v", ipc.FailedTestsFileName, err)
+		}
+
+		if m.junitXMLPath != "" {
+			if err := writeJUnitXML(m.junitXMLPath, m.groupManager.GetRootGroups(), m.redactor); err != nil {
+				m.logger.Error("Failed to write JUnit XML: %v", err)
+			}
+		}
+	}
+
+	if m.metadata != nil {
+		if err := m.writeMetadata(); err != nil {
+			m.logger.Error("Failed to write %s: %v", runinfo.FileName, err)
+		}
 	}
 
 	// Close output.log
@@ -661,22 +1701,38 @@ func (m *Manager) Finalize(exitCode int, errorDetails ...string) error {
 	// Close our owned FileLogger if we created one
 
 	// Update final status if we have state and it's not already finalized
-	if m.state != nil && m.state.Status != "COMPLETE" && m.state.Status != "ERROR" {
-		// Cancel any pending timer to prevent race condition
+	if m.state != nil && m.state.Status != "COMPLETE" && m.state.Status != "ERROR" && m.state.Status != "INTERRUPTED" {
+		// Cancel any pending write, the final one below replaces it
+		m.writeMutex.Lock()
 		if m.writeTimer != nil {
 			m.writeTimer.Stop()
 			m.writeTimer = nil
 		}
+		m.pendingWrite = false
+		m.writeMutex.Unlock()
+
+		m.exitCode = exitCode
 
 		// Only set ERROR status for actual command errors, not test failures
-		if len(errorDetails) > 0 && errorDetails[0] != "" {
+		if m.interrupted {
+			m.state.Status = "INTERRUPTED"
+		} else if len(errorDetails) > 0 && errorDetails[0] != "" {
 			m.state.Status = "ERROR"
 			m.state.ErrorDetails = errorDetails[0]
 		} else {
 			m.state.Status = "COMPLETE"
 		}
 
-		// Write final state immediately (bypass debouncing)
+		if m.groupManager != nil {
+			summary := m.buildSummary(exitCode)
+			if err := m.writeSummary(summary); err != nil {
+				m.logger.Error("Failed to write %s: %v", SummaryFileName, err)
+			}
+			m.streamRunFinished(summary)
+		}
+
+		// Write final state immediately (bypass debouncing). test-run.md is written
+		// last, so once its status leaves RUNNING every other file is complete.
 		return m.writeState()
 	}
 
@@ -686,7 +1742,7 @@ func (m *Manager) Finalize(exitCode int, errorDetails ...string) error {
 // normalizePath normalizes a file path for comparison
 func (m *Manager) normalizePath(filePath string) string {
 	// Try to get absolute path
-	absPath, err := filepath.Abs(filePath)
+	absPath, err := absPathIn(m.workDir, filePath)
 	if err != nil {
 		// If we can't get absolute path, use the original
 		return filePath
@@ -694,6 +1750,341 @@ func (m *Manager) normalizePath(filePath string) string {
 	return absPath
 }
 
+// BytesWritten returns how many bytes of test-run.md and group reports have been written
+func (m *Manager) BytesWritten() int64 {
+	m.mu.RLock()
+	defer m.mu.RUnlock()
+
+	written := m.bytesWritten
+	if m.groupManager != nil {
+		written += m.groupManager.BytesWritten()
+	}
+	return written
+}
+
+// GetBuildErrors returns the compiler errors reported during the run
+func (m *Manager) GetBuildErrors() []ipc.BuildErrorPayload {
+	m.mu.RLock()
+	defer m.mu.RUnlock()
+	return append([]ipc.BuildErrorPayload(nil), m.buildErrors...)
+}
+
+// SetJUnitXMLPath makes Finalize also write the results as JUnit XML to path
+func (m *Manager) SetJUnitXMLPath(path string) {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	m.junitXMLPath = path
+}
+
+// SetSlowThreshold sets the test duration above which group reports list their tests
+// slowest first (--slow-threshold)
+func (m *Manager) SetSlowThreshold(threshold time.Duration) {
+	m.groupManager.SetSlowThreshold(threshold)
+}
+
+// SetSourceRoot sets the directory relative failure locations are resolved against
+// for the source snippets in group reports
+func (m *Manager) SetSourceRoot(dir string) {
+	m.groupManager.SetSourceRoot(dir)
+}
+
+// SetWorkDir sets the directory the tests run in, when it isn't 3pio's working
+// directory. Called before any event arrives.
+func (m *Manager) SetWorkDir(dir string) {
+	m.mu.Lock()
+	m.workDir = dir
+	m.mu.Unlock()
+	m.groupManager.SetWorkDir(dir)
+}
+
+// SetQuarantine sets the known-flaky tests whose failures don't fail the run
+func (m *Manager) SetQuarantine(quarantine *Quarantine) {
+	m.groupManager.SetQuarantine(quarantine)
+}
+
+// SetRedactor has the secret values replaced in test-run.md, the group reports and
+// logs, summary.json, metadata.json, the JUnit XML and the event stream
+func (m *Manager) SetRedactor(redactor *Redactor) {
+	m.mu.Lock()
+	m.redactor = redactor
+	m.mu.Unlock()
+	m.groupManager.SetRedactor(redactor)
+}
+
+// SetRerunOf records that the run re-runs count failed tests from an earlier run
+func (m *Manager) SetRerunOf(runID string, count int) {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	m.rerunOf = runID
+	m.rerunCount = count
+}
+
+// SetTestFilters records the filters the command narrows its tests down with
+func (m *Manager) SetTestFilters(filters []string) {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	m.testFilters = filters
+}
+
+// SetPendingSnapshots records the snapshot files insta wrote during the run for review
+func (m *Manager) SetPendingSnapshots(paths []string) {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	m.pendingSnapshots = paths
+}
+
+// PendingSnapshots returns the snapshot files insta wrote during the run for review
+func (m *Manager) PendingSnapshots() []string {
+	m.mu.RLock()
+	defer m.mu.RUnlock()
+	return m.pendingSnapshots
+}
+
+// SetCoverage records the code coverage the run measured, or why a run that measured
+// it has none to report
+func (m *Manager) SetCoverage(summary *coverage.Summary, warning string) {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	m.coverage, m.coverageWarning = summary, warning
+}
+
+// Coverage returns the code coverage the run measured, nil when it measured none
+func (m *Manager) Coverage() *coverage.Summary {
+	m.mu.RLock()
+	defer m.mu.RUnlock()
+	return m.coverage
+}
+
+// SetShard records the part of a sharded test suite the run covers
+func (m *Manager) SetShard(shard *runinfo.Shard) {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	m.shard = shard
+}
+
+// SetCargo records the profile, features and target a cargo command builds the tests with
+func (m *Manager) SetCargo(cargo *runinfo.Cargo) {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	m.cargo = cargo
+}
+
+// SetResultsSource records that the results were read from the files of source, such
+// as "junit_xml", once the command exited
+func (m *Manager) SetResultsSource(source string) {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	m.resultsSource = source
+}
+
+// resultsSourceName names the files of a results source in test-run.md
+func resultsSourceName(source string) string {
+	switch source {
+	case "junit_xml":
+		return "JUnit XML reports"
+	case "trx":
+		return "TRX files"
+	}
+	return source + " files"
+}
+
+// SetPreviousRun records the summary of the run before this one. Failures are then
+// marked in test-run.md as new, or as still failing since that run.
+func (m *Manager) SetPreviousRun(previous *RunSummary) {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	m.previousRunID = previous.RunID
+	m.previousFailures = make(map[string]bool)
+	for _, group := range previous.Groups {
+		if group.Status == string(TestStatusError) {
+			m.previousFailures[group.ID] = true
+		}
+		for _, tc := range group.TestCases {
+			if tc.Status == string(TestStatusFail) || tc.Status == string(TestStatusError) {
+				m.previousFailures[SummaryTestID(previous, group, tc)] = true
+			}
+		}
+	}
+}
+
+// KnownFailure reports whether the failed test (by test ID) or group (by group ID) also
+// failed in the previous run. compared is false when there is no previous run to tell.
+func (m *Manager) KnownFailure(id string) (known, compared bool) {
+	m.mu.RLock()
+	defer m.mu.RUnlock()
+	return m.previousFailures[id], m.previousFailures != nil
+}
+
+// failureAnnotation describes a failure against the previous run. Caller must hold m.mu.
+func (m *Manager) failureAnnotation(id string) string {
+	switch {
+	case m.previousFailures == nil:
+		return ""
+	case m.previousFailures[id]:
+		return "still failing since " + m.previousRunID
+	default:
+		return "new failure"
+	}
+}
+
+// writeFailedTests writes the run's failed tests to failed-tests.json
+func (m *Manager) writeFailedTests() error {
+	failed := m.groupManager.FailedTests()
+	if failed == nil {
+		failed = []ipc.FailedTest{}
+	}
+	data, err := json.MarshalIndent(failed, "", "  ")
+	if err != nil {
+		return err
+	}
+	return writeFileAtomic(filepath.Join(m.runDir, ipc.FailedTestsFileName), append(data, '\n'))
+}
+
+// ReadFailedTests reads the failed tests recorded in a run directory
+func ReadFailedTests(runDir string) ([]ipc.FailedTest, error) {
+	data, err := os.ReadFile(filepath.Join(runDir, ipc.FailedTestsFileName))
+	if err != nil {
+		return nil, err
+	}
+	var failed []ipc.FailedTest
+	if err := json.Unmarshal(data, &failed); err != nil {
+		return nil, fmt.Errorf("failed to parse %s: %w", ipc.FailedTestsFileName, err)
+	}
+	return failed, nil
+}
+
+// SetInterrupted records that the user stopped the run. Groups still unfinished when
+// the report is finalized are marked INTERRUPTED, and so is the run.
+func (m *Manager) SetInterrupted() {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	m.interrupted = true
+}
+
+// AddMalformedIPCEvents counts lines of the IPC file that were ignored because they
+// couldn't be decoded, so the report can warn that results may be missing
+func (m *Manager) AddMalformedIPCEvents(count int) {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	m.malformedIPCEvents += count
+}
+
+// SetRunTimeout records that --timeout stopped the run after the given time. Groups
+// still unfinished when the report is finalized are marked INTERRUPTED.
+func (m *Manager) SetRunTimeout(timeout time.Duration) {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	m.runTimeout = timeout
+}
+
+// SetInactivityTimeout records that the run was stopped after the given time without
+// output, with the tests (or groups) that were still running
+func (m *Manager) SetInactivityTimeout(timeout time.Duration, inFlight []string) {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	m.inactivityTimeout = timeout
+	m.timedOutTests = append([]string(nil), inFlight...)
+}
+
+// RunningGroups returns the paths of groups that have started but not finished
+func (m *Manager) RunningGroups() []string {
+	if m.groupManager == nil {
+		return nil
+	}
+	return m.groupManager.RunningGroups()
+}
+
+// GetCrashes returns the test binaries that crashed during the run
+func (m *Manager) GetCrashes() []ipc.BinaryCrashPayload {
+	m.mu.RLock()
+	defer m.mu.RUnlock()
+	return append([]ipc.BinaryCrashPayload(nil), m.crashes...)
+}
+
+// BeginRetry starts a retry of the failed tests (--retries). Until EndRetry, test case
+// events only record another attempt of a failed test and other events are ignored.
+func (m *Manager) BeginRetry() {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	m.retrying = true
+}
+
+// Retrying reports whether a retry of the failed tests is running
+func (m *Manager) Retrying() bool {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	return m.retrying
+}
+
+// EndRetry ends a retry of the failed tests, passing the groups whose failed tests all
+// passed when retried
+func (m *Manager) EndRetry() {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	m.retrying = false
+	if m.groupManager != nil {
+		m.groupManager.ResolveRetriedGroups()
+	}
+	_ = m.scheduleWrite()
+}
+
+// FailedTests returns the tests that failed so far
+func (m *Manager) FailedTests() []ipc.FailedTest {
+	if m.groupManager == nil {
+		return nil
+	}
+	return m.groupManager.FailedTests()
+}
+
+// TestID returns the stable test ID of a test as reported in a testCase event
+func (m *Manager) TestID(parentNames []string, testName string) string {
+	if m.groupManager == nil {
+		return TestID(RunnerKind(m.detectedRunner), parentNames, testName)
+	}
+	return m.groupManager.TestID(parentNames, testName)
+}
+
+// FlakyTests returns the tests that failed and then passed when retried
+func (m *Manager) FlakyTests() []ipc.FailedTest {
+	if m.groupManager == nil {
+		return nil
+	}
+	return m.groupManager.FlakyTests()
+}
+
+// XPassedTests returns the tests marked as expected failures that passed, sorted by
+// their group path
+func (m *Manager) XPassedTests() []ipc.FailedTest {
+	if m.groupManager == nil {
+		return nil
+	}
+	return m.groupManager.XPassedTests()
+}
+
+// OnlyToleratedFailures reports whether the only failures of the run were those of
+// quarantined tests and of tests that passed when retried, with no build error, crash
+// or errored group. With failOnFlaky (--fail-on-flaky) tests that passed when retried
+// count as failures.
+func (m *Manager) OnlyToleratedFailures(failOnFlaky bool) bool {
+	m.mu.RLock()
+	defer m.mu.RUnlock()
+	if m.groupManager == nil || len(m.buildErrors) > 0 || len(m.crashes) > 0 {
+		return false
+	}
+	tolerated := 0
+	for _, group := range m.groupManager.GetRootGroups() {
+		if group.Status == TestStatusFail || hasUntoleratedFailure(group) {
+			return false
+		}
+		flaky := countFlakyTestCases(group)
+		if failOnFlaky && flaky > 0 {
+			return false
+		}
+		tolerated += countQuarantinedTestCases(group) + flaky
+	}
+	return tolerated > 0
+}
+
 // GetRootGroups returns root groups from the group manager for console display
 func (m *Manager) GetRootGroups() []*TestGroup {
 	if m.groupManager == nil {
@@ -0,0 +1,105 @@
+package report
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/zk/3pio/internal/ipc"
+)
+
+// TestManager_LargeSuiteWriteBudget feeds 50k test results through the manager, the
+// size of a large crate, and checks that report writing stays roughly linear: each
+// report is rendered a bounded number of times instead of once per test.
+func TestManager_LargeSuiteWriteBudget(t *testing.T) {
+	if testing.Short() {
+		t.Skip("Skipping large suite test in short mode")
+	}
+
+	const (
+		modules        = 40
+		testsPerModule = 1000
+		bigModuleTests = 10000 // One module much bigger than the rest
+		timeBudget     = 30 * time.Second
+	)
+
+	tempDir := t.TempDir()
+	manager, err := NewManager(tempDir, nil, &mockLogger{}, "cargo test", "cargo test")
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	if err := manager.Initialize("cargo test"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+
+	send := func(module string, i int) {
+		status := "PASS"
+		var testErr *ipc.TestError
+		if i%500 == 0 {
+			status = "FAIL"
+			testErr = &ipc.TestError{Message: fmt.Sprintf("assertion failed in test_%05d", i)}
+		}
+		err := manager.HandleEvent(ipc.GroupTestCaseEvent{
+			EventType: "testCase",
+			Payload: ipc.TestCasePayload{
+				TestName:    fmt.Sprintf("test_%05d", i),
+				ParentNames: []string{"big-crate", "tests", module},
+				Status:      status,
+				Duration:    1,
+				Error:       testErr,
+			},
+		})
+		if err != nil {
+			t.Fatalf("HandleEvent failed: %v", err)
+		}
+	}
+
+	start := time.Now()
+	for m := 0; m < modules; m++ {
+		for i := 0; i < testsPerModule; i++ {
+			send(fmt.Sprintf("module_%02d", m), i)
+		}
+	}
+	for i := 0; i < bigModuleTests; i++ {
+		send("big_module", i)
+	}
+	if err := manager.Finalize(101, ""); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+	elapsed := time.Since(start)
+
+	// Size of the reports on disk, which is what a single write of each comes to
+	var finalSize int64
+	err = filepath.Walk(tempDir, func(path string, info os.FileInfo, err error) error {
+		if err != nil {
+			return err
+		}
+		if strings.HasSuffix(path, ".md") {
+			finalSize += info.Size()
+		}
+		return nil
+	})
+	if err != nil {
+		t.Fatalf("Failed to walk reports: %v", err)
+	}
+
+	written := manager.BytesWritten()
+	t.Logf("%d tests: %v, %d bytes written for %d bytes of reports", modules*testsPerModule+bigModuleTests,
+		elapsed, written, finalSize)
+
+	if elapsed > timeBudget {
+		t.Errorf("Expected the run to be reported within %v, took %v", timeBudget, elapsed)
+	}
+	if written > 4*finalSize {
+		t.Errorf("Expected at most 4x the final report size (%d bytes) to be written, wrote %d", finalSize, written)
+	}
+
+	// The results are all there
+	group, ok := manager.GetGroup(GenerateGroupIDFromPath([]string{"big-crate", "tests", "big_module"}))
+	if !ok || len(group.TestCases) != bigModuleTests || group.Stats.FailedTests != bigModuleTests/500 {
+		t.Fatalf("Expected %d test cases with %d failures in big_module", bigModuleTests, bigModuleTests/500)
+	}
+}
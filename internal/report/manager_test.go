@@ -6,7 +6,9 @@ import (
 	"path/filepath"
 	"strings"
 	"testing"
+	"time"
 
+	"github.com/zk/3pio/internal/coverage"
 	"github.com/zk/3pio/internal/ipc"
 	"github.com/zk/3pio/internal/runner"
 )
@@ -211,6 +213,662 @@ func TestManager_FinalizeWithError(t *testing.T) {
 	}
 }
 
+func TestManager_BuildErrorsSection(t *testing.T) {
+	tempDir := t.TempDir()
+	logger := &mockLogger{}
+
+	manager, err := NewManager(tempDir, nil, logger, "cargo test", "cargo test --message-format json")
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+
+	if err := manager.Initialize("cargo test"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+
+	buildErrors := []ipc.BuildErrorPayload{
+		{
+			Target:   "rust_benchmarks",
+			Message:  "expected one of `;` or `}`, found `let`",
+			Rendered: "error: expected one of `;` or `}`, found `let`\n  --> src/lib.rs:12:5",
+			File:     "src/lib.rs",
+			Line:     12,
+			Column:   5,
+		},
+		{
+			Target:   "rust_benchmarks",
+			Code:     "E0554",
+			Message:  "`#![feature]` may not be used on the stable release channel",
+			Rendered: "error[E0554]: `#![feature]` may not be used on the stable release channel\n --> src/lib.rs:1:1",
+			File:     "src/lib.rs",
+			Line:     1,
+			Column:   1,
+		},
+	}
+	for _, payload := range buildErrors {
+		event := ipc.BuildErrorEvent{EventType: ipc.EventTypeBuildError, Payload: payload}
+		if err := manager.HandleEvent(event); err != nil {
+			t.Fatalf("HandleEvent failed: %v", err)
+		}
+	}
+
+	if got := manager.GetBuildErrors(); len(got) != 2 {
+		t.Fatalf("Expected 2 build errors, got %d", len(got))
+	}
+
+	if err := manager.Finalize(101, "Build failed with 2 compile error(s)"); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+
+	content, err := os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+	if err != nil {
+		t.Fatalf("Failed to read finalized report: %v", err)
+	}
+	report := string(content)
+
+	expected := []string{
+		"status: ERRORED\nexit_reason: build_failed\n",
+		"## Build errors",
+		"### expected one of `;` or `}`, found `let`",
+		"- Location: `src/lib.rs:12`",
+		"### error[E0554]: `#![feature]` may not be used on the stable release channel",
+		"- Location: `src/lib.rs:1`",
+		"- Target: `rust_benchmarks`",
+		"error[E0554]: `#![feature]` may not be used on the stable release channel\n --> src/lib.rs:1:1\n```",
+	}
+	for _, want := range expected {
+		if !strings.Contains(report, want) {
+			t.Errorf("Expected report to contain %q, got:\n%s", want, report)
+		}
+	}
+}
+
+func TestManager_BinaryCrashSection(t *testing.T) {
+	tempDir := t.TempDir()
+	logger := &mockLogger{}
+
+	manager, err := NewManager(tempDir, nil, logger, "cargo test", "cargo test")
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	if err := manager.Initialize("cargo test"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+
+	event := ipc.BinaryCrashEvent{
+		EventType: ipc.EventTypeBinaryCrash,
+		Payload: ipc.BinaryCrashPayload{
+			Target:     "rust-crash",
+			TestName:   "tests::test_abort",
+			Signal:     6,
+			SignalName: "SIGABRT",
+			Message:    "test binary crashed with signal 6 (SIGABRT)",
+		},
+	}
+	if err := manager.HandleEvent(event); err != nil {
+		t.Fatalf("HandleEvent failed: %v", err)
+	}
+	if got := manager.GetCrashes(); len(got) != 1 {
+		t.Fatalf("Expected 1 crash, got %d", len(got))
+	}
+
+	if err := manager.Finalize(101); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+
+	content, err := os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+	if err != nil {
+		t.Fatalf("Failed to read finalized report: %v", err)
+	}
+	report := string(content)
+
+	expected := []string{
+		"status: COMPLETED\nexit_reason: runner_crashed\n",
+		"## Test binary crashed",
+		"- `rust-crash`: test binary crashed with signal 6 (SIGABRT) while running `tests::test_abort`",
+	}
+	for _, want := range expected {
+		if !strings.Contains(report, want) {
+			t.Errorf("Expected report to contain %q, got:\n%s", want, report)
+		}
+	}
+}
+
+func TestManager_InactivityTimeoutSection(t *testing.T) {
+	tempDir := t.TempDir()
+	logger := &mockLogger{}
+
+	manager, err := NewManager(tempDir, nil, logger, "cargo test", "cargo test")
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	if err := manager.Initialize("cargo test"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+
+	manager.SetInactivityTimeout(30*time.Second, []string{"rust-basic > tests > test_deadlock"})
+	if err := manager.Finalize(124); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+
+	content, err := os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+	if err != nil {
+		t.Fatalf("Failed to read finalized report: %v", err)
+	}
+	report := string(content)
+
+	expected := []string{
+		"exit_reason: inactivity_timeout\n",
+		"## Inactivity timeout",
+		"No output or test events arrived for 30s while tests were running",
+		"- `rust-basic > tests > test_deadlock`",
+	}
+	for _, want := range expected {
+		if !strings.Contains(report, want) {
+			t.Errorf("Expected report to contain %q, got:\n%s", want, report)
+		}
+	}
+}
+
+func TestManager_PendingSnapshotsSection(t *testing.T) {
+	tempDir := t.TempDir()
+	logger := &mockLogger{}
+
+	manager, err := NewManager(tempDir, nil, logger, "cargo test", "cargo test")
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	if err := manager.Initialize("cargo test"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+
+	manager.SetPendingSnapshots([]string{"crates/parser/src/.lib.rs.pending-snap", "src/snapshots/rust_insta__tests__greeting.snap.new"})
+	if err := manager.Finalize(101); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+
+	content, err := os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+	if err != nil {
+		t.Fatalf("Failed to read finalized report: %v", err)
+	}
+	report := string(content)
+
+	expected := []string{
+		"## Pending snapshots\n",
+		"Run `cargo insta review` to accept or reject them.",
+		"- `crates/parser/src/.lib.rs.pending-snap`\n- `src/snapshots/rust_insta__tests__greeting.snap.new`\n",
+	}
+	for _, want := range expected {
+		if !strings.Contains(report, want) {
+			t.Errorf("Expected report to contain %q, got:\n%s", want, report)
+		}
+	}
+}
+
+func TestManager_Coverage(t *testing.T) {
+	newManager := func(t *testing.T) (*Manager, string) {
+		tempDir := t.TempDir()
+		manager, err := NewManager(tempDir, nil, &mockLogger{}, "cargo llvm-cov --lcov --output-path cov.lcov test", "cargo test")
+		if err != nil {
+			t.Fatalf("Failed to create manager: %v", err)
+		}
+		if err := manager.Initialize("cargo llvm-cov --lcov --output-path cov.lcov test"); err != nil {
+			t.Fatalf("Initialize failed: %v", err)
+		}
+		return manager, tempDir
+	}
+
+	manager, tempDir := newManager(t)
+	manager.SetCoverage(&coverage.Summary{
+		Report:    "cov.lcov",
+		Lines:     coverage.Counts{Covered: 21, Total: 30, Percent: 70},
+		Functions: coverage.Counts{Covered: 5, Total: 6, Percent: 83.33},
+		Files: []coverage.File{
+			{Path: "src/lib.rs", Lines: coverage.Counts{Covered: 19, Total: 20, Percent: 95}, Functions: coverage.Counts{Covered: 4, Total: 4, Percent: 100}},
+			{Path: "src/io.rs", Lines: coverage.Counts{Covered: 2, Total: 10, Percent: 20}, Functions: coverage.Counts{Covered: 1, Total: 2, Percent: 50}},
+		},
+	}, "")
+	if err := manager.Finalize(0); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+	content, err := os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+	if err != nil {
+		t.Fatalf("Failed to read finalized report: %v", err)
+	}
+	want := "## Coverage\n\n" +
+		"- Lines: 70.00% (21 of 30)\n- Functions: 83.33% (5 of 6)\n- Report: `cov.lcov`\n\n" +
+		"| Lines | Functions | File |\n|-------|-----------|------|\n" +
+		"| 20.00% (2 of 10) | 50.00% (1 of 2) | src/io.rs |\n" +
+		"| 95.00% (19 of 20) | 100.00% (4 of 4) | src/lib.rs |\n"
+	if !strings.Contains(string(content), want) {
+		t.Errorf("Expected report to contain %q, got:\n%s", want, content)
+	}
+	summary, err := ReadSummary(tempDir)
+	if err != nil {
+		t.Fatalf("Failed to read summary: %v", err)
+	}
+	if summary.Coverage == nil || summary.Coverage.Lines.Percent != 70 || summary.Coverage.Functions.Percent != 83.33 || summary.Coverage.Files != nil {
+		t.Errorf("Expected summary.json to carry the totals without the files, got %+v", summary.Coverage)
+	}
+
+	// A missing report is a warning, and the run still passes
+	manager, tempDir = newManager(t)
+	manager.SetCoverage(nil, "coverage report cov.lcov wasn't written by this run")
+	if err := manager.Finalize(0); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+	content, err = os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+	if err != nil {
+		t.Fatalf("Failed to read finalized report: %v", err)
+	}
+	if want := "## Coverage\n\n**Warning: no coverage to report**: coverage report cov.lcov wasn't written by this run\n"; !strings.Contains(string(content), want) {
+		t.Errorf("Expected report to contain %q, got:\n%s", want, content)
+	}
+	if summary, err := ReadSummary(tempDir); err != nil || summary.Coverage != nil || summary.ExitReason != ExitReasonOK {
+		t.Errorf("Expected a passing run without coverage, got %+v (%v)", summary, err)
+	}
+}
+
+func TestManager_RunTimeout(t *testing.T) {
+	tempDir := t.TempDir()
+	logger := &mockLogger{}
+
+	manager, err := NewManager(tempDir, nil, logger, "cargo test", "cargo test")
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	if err := manager.Initialize("cargo test"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+
+	// rust-basic finished, rust-timeout was still running when the run was stopped
+	for _, group := range []string{"rust-basic", "rust-timeout"} {
+		if err := manager.HandleEvent(ipc.GroupStartEvent{
+			EventType: string(ipc.EventTypeGroupStart),
+			Payload:   ipc.GroupStartPayload{GroupName: group},
+		}); err != nil {
+			t.Fatalf("HandleEvent failed: %v", err)
+		}
+	}
+	if err := manager.HandleEvent(ipc.GroupResultEvent{
+		EventType: string(ipc.EventTypeGroupResult),
+		Payload: ipc.GroupResultPayload{
+			GroupName: "rust-basic",
+			Status:    "PASS",
+			Totals:    ipc.GroupTotals{Total: 1, Passed: 1},
+		},
+	}); err != nil {
+		t.Fatalf("HandleEvent failed: %v", err)
+	}
+
+	manager.SetRunTimeout(600 * time.Second)
+	if err := manager.Finalize(124); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+
+	content, err := os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+	if err != nil {
+		t.Fatalf("Failed to read finalized report: %v", err)
+	}
+	report := string(content)
+
+	expected := []string{
+		"exit_reason: timeout\n",
+		"- **Run timed out after 600s**; groups that hadn't finished are marked INTERRUPTED",
+		"| INTERRUPTED |",
+	}
+	for _, want := range expected {
+		if !strings.Contains(report, want) {
+			t.Errorf("Expected report to contain %q, got:\n%s", want, report)
+		}
+	}
+
+	group, ok := manager.GetGroup(GenerateGroupID("rust-basic", nil))
+	if !ok || group.Status != TestStatusPass {
+		t.Errorf("Expected the finished group to keep its PASS status, got %v", group)
+	}
+}
+
+func TestManager_Interrupted(t *testing.T) {
+	tempDir := t.TempDir()
+	logger := &mockLogger{}
+
+	manager, err := NewManager(tempDir, nil, logger, "cargo test", "cargo test")
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	if err := manager.Initialize("cargo test"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+
+	if err := manager.HandleEvent(ipc.GroupStartEvent{
+		EventType: string(ipc.EventTypeGroupStart),
+		Payload:   ipc.GroupStartPayload{GroupName: "rust-performance"},
+	}); err != nil {
+		t.Fatalf("HandleEvent failed: %v", err)
+	}
+
+	manager.SetInterrupted()
+	if err := manager.Finalize(130, ""); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+	// The deferred safety Finalize must not change the status again
+	if err := manager.Finalize(130, ""); err != nil {
+		t.Fatalf("Second Finalize failed: %v", err)
+	}
+
+	content, err := os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+	if err != nil {
+		t.Fatalf("Failed to read finalized report: %v", err)
+	}
+	report := string(content)
+
+	expected := []string{
+		"status: INTERRUPTED\n",
+		"exit_reason: interrupted\n",
+		"- **Run interrupted**; groups that hadn't finished are marked INTERRUPTED",
+		"| INTERRUPTED |",
+	}
+	for _, want := range expected {
+		if !strings.Contains(report, want) {
+			t.Errorf("Expected report to contain %q, got:\n%s", want, report)
+		}
+	}
+}
+
+func TestManager_MalformedIPCEventsWarning(t *testing.T) {
+	for _, count := range []int{0, 1, 3} {
+		tempDir := t.TempDir()
+		manager, err := NewManager(tempDir, nil, &mockLogger{}, "cargo test", "cargo test")
+		if err != nil {
+			t.Fatalf("Failed to create manager: %v", err)
+		}
+		if err := manager.Initialize("cargo test"); err != nil {
+			t.Fatalf("Initialize failed: %v", err)
+		}
+		// Counted once for the run and once for a retry
+		manager.AddMalformedIPCEvents(count - count/2)
+		manager.AddMalformedIPCEvents(count / 2)
+		if err := manager.Finalize(0, ""); err != nil {
+			t.Fatalf("Finalize failed: %v", err)
+		}
+
+		content, err := os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+		if err != nil {
+			t.Fatalf("Failed to read finalized report: %v", err)
+		}
+		report := string(content)
+		switch count {
+		case 0:
+			if strings.Contains(report, "malformed IPC") {
+				t.Errorf("Expected no malformed event warning, got:\n%s", report)
+			}
+		case 1:
+			if !strings.Contains(report, "- **Warning: 1 malformed IPC event ignored**; results may be missing") {
+				t.Errorf("Expected a warning about 1 malformed event, got:\n%s", report)
+			}
+		default:
+			if !strings.Contains(report, "- **Warning: 3 malformed IPC events ignored**; results may be missing") {
+				t.Errorf("Expected a warning about 3 malformed events, got:\n%s", report)
+			}
+		}
+	}
+}
+
+func TestManager_FailedTestsForRerun(t *testing.T) {
+	tempDir := t.TempDir()
+	logger := &mockLogger{}
+
+	manager, err := NewManager(tempDir, nil, logger, "cargo test", "cargo test -- --exact tests::test_add_004")
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	manager.SetRerunOf("20261014T101500-rey", 2)
+	if err := manager.Initialize("cargo test"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+
+	for _, tc := range []struct{ name, status string }{
+		{"test_add_004", "FAIL"},
+		{"test_add_005", "PASS"},
+	} {
+		if err := manager.HandleEvent(ipc.GroupTestCaseEvent{
+			EventType: "testCase",
+			Payload: ipc.TestCasePayload{
+				TestName:    tc.name,
+				ParentNames: []string{"rust-performance", "tests"},
+				Status:      tc.status,
+			},
+		}); err != nil {
+			t.Fatalf("HandleEvent failed: %v", err)
+		}
+	}
+	if err := manager.Finalize(101, ""); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+
+	failed, err := ReadFailedTests(tempDir)
+	if err != nil {
+		t.Fatalf("ReadFailedTests failed: %v", err)
+	}
+	if len(failed) != 1 || failed[0].Name != "test_add_004" || strings.Join(failed[0].ParentNames, " > ") != "rust-performance > tests" {
+		t.Errorf("Expected only rust-performance > tests > test_add_004, got %+v", failed)
+	}
+
+	content, err := os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+	if err != nil {
+		t.Fatalf("Failed to read finalized report: %v", err)
+	}
+	report := string(content)
+	for _, want := range []string{"rerun_of: 20261014T101500-rey\n", "- Re-run of 2 failed tests from 20261014T101500-rey"} {
+		if !strings.Contains(report, want) {
+			t.Errorf("Expected report to contain %q, got:\n%s", want, report)
+		}
+	}
+}
+
+func TestManager_FailuresMarkedAgainstPreviousRun(t *testing.T) {
+	runAndRead := func(runDir string, previous *RunSummary, statuses map[string]string) (*Manager, string) {
+		t.Helper()
+		manager, err := NewManager(runDir, nil, &mockLogger{}, "cargo test", "cargo test")
+		if err != nil {
+			t.Fatalf("Failed to create manager: %v", err)
+		}
+		if previous != nil {
+			manager.SetPreviousRun(previous)
+		}
+		if err := manager.Initialize("cargo test"); err != nil {
+			t.Fatalf("Initialize failed: %v", err)
+		}
+		for _, name := range []string{"test_known", "test_new"} {
+			if err := manager.HandleEvent(ipc.GroupTestCaseEvent{
+				EventType: "testCase",
+				Payload: ipc.TestCasePayload{
+					TestName:    name,
+					ParentNames: []string{"my-crate", "tests"},
+					Status:      statuses[name],
+				},
+			}); err != nil {
+				t.Fatalf("HandleEvent failed: %v", err)
+			}
+		}
+		if err := manager.Finalize(101, ""); err != nil {
+			t.Fatalf("Finalize failed: %v", err)
+		}
+		content, err := os.ReadFile(filepath.Join(runDir, "test-run.md"))
+		if err != nil {
+			t.Fatalf("Failed to read finalized report: %v", err)
+		}
+		return manager, string(content)
+	}
+
+	firstDir := filepath.Join(t.TempDir(), "20261014T100000-rey")
+	_, first := runAndRead(firstDir, nil, map[string]string{"test_known": "FAIL", "test_new": "PASS"})
+	if strings.Contains(first, "new failure") || strings.Contains(first, "still failing") {
+		t.Errorf("Expected no annotations without a previous run, got:\n%s", first)
+	}
+
+	previous, err := ReadSummary(firstDir)
+	if err != nil {
+		t.Fatalf("ReadSummary failed: %v", err)
+	}
+	manager, second := runAndRead(t.TempDir(), previous, map[string]string{"test_known": "FAIL", "test_new": "FAIL"})
+	for _, want := range []string{
+		"`my-crate → tests → test_known` (still failing since 20261014T100000-rey)",
+		"`my-crate → tests → test_new` (new failure)",
+	} {
+		if !strings.Contains(second, want) {
+			t.Errorf("Expected report to contain %q, got:\n%s", want, second)
+		}
+	}
+
+	for _, tc := range previous.Groups[len(previous.Groups)-1].TestCases {
+		known, compared := manager.KnownFailure(tc.TestID)
+		if !compared || known != (tc.Name == "test_known") {
+			t.Errorf("Expected %s known=%v, got known=%v compared=%v", tc.Name, tc.Name == "test_known", known, compared)
+		}
+	}
+}
+
+func TestManager_ExitReasonWithoutBuildErrors(t *testing.T) {
+	tempDir := t.TempDir()
+	logger := &mockLogger{}
+
+	manager, err := NewManager(tempDir, nil, logger, "cargo test", "cargo test")
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	if err := manager.Initialize("cargo test"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+	if err := manager.Finalize(101, "Command failed with error"); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+
+	content, err := os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+	if err != nil {
+		t.Fatalf("Failed to read finalized report: %v", err)
+	}
+	if !strings.Contains(string(content), "exit_reason: runner_crashed\n") || strings.Contains(string(content), "## Build errors") {
+		t.Errorf("Expected the failed command without build failure metadata, got:\n%s", content)
+	}
+}
+
+func TestManager_ExitReason(t *testing.T) {
+	testCases := []struct {
+		desc             string
+		status           string
+		exitCode         int
+		noTestsFound     bool
+		buildError       bool
+		environmentError bool
+		failureKind      string
+		expected         ExitReason
+	}{
+		{"all tests passed", "PASS", 0, false, false, false, "", ExitReasonOK},
+		{"a test failed", "FAIL", 101, false, false, false, "", ExitReasonTestsFailed},
+		{"the filter matched nothing", "", 5, true, false, false, "", ExitReasonNoTestsFound},
+		{"a package didn't build", "PASS", 1, false, true, false, "", ExitReasonBuildFailed},
+		{"a package didn't build and a test failed", "FAIL", 1, false, true, false, "", ExitReasonTestsFailed},
+		{"a test ran past go test -timeout", "FAIL", 1, false, false, false, ipc.FailureKindTimedOut, ExitReasonTestTimeout},
+		{"the WebDriver failed to start", "", 1, false, false, true, "", ExitReasonEnvironmentError},
+	}
+	for _, tc := range testCases {
+		t.Run(tc.desc, func(t *testing.T) {
+			tempDir := t.TempDir()
+			manager, err := NewManager(tempDir, nil, &mockLogger{}, "cargo test", "cargo test")
+			if err != nil {
+				t.Fatalf("Failed to create manager: %v", err)
+			}
+			if err := manager.Initialize("cargo test"); err != nil {
+				t.Fatalf("Initialize failed: %v", err)
+			}
+			if tc.status != "" {
+				payload := ipc.TestCasePayload{TestName: "test_add", ParentNames: []string{"my-crate", "tests"}, Status: tc.status}
+				if tc.failureKind != "" {
+					payload.Error = &ipc.TestError{Message: "panic: test timed out after 1s", FailureKind: tc.failureKind}
+				}
+				if err := manager.HandleEvent(ipc.GroupTestCaseEvent{EventType: "testCase", Payload: payload}); err != nil {
+					t.Fatalf("HandleEvent failed: %v", err)
+				}
+			}
+			if tc.noTestsFound {
+				manager.SetNoTestsFound()
+			}
+			if tc.environmentError {
+				manager.SetEnvironmentError()
+			}
+			if tc.buildError {
+				payload := ipc.BuildErrorPayload{Target: "example.com/broken", Message: "undefined: x"}
+				if err := manager.HandleEvent(ipc.BuildErrorEvent{EventType: ipc.EventTypeBuildError, Payload: payload}); err != nil {
+					t.Fatalf("HandleEvent failed: %v", err)
+				}
+			} else if reason := manager.ExitReason(); reason != "" {
+				t.Errorf("Expected no exit reason while running, got %q", reason)
+			}
+
+			if err := manager.Finalize(tc.exitCode, ""); err != nil {
+				t.Fatalf("Finalize failed: %v", err)
+			}
+			if reason := manager.ExitReason(); reason != tc.expected {
+				t.Errorf("Expected exit reason %q, got %q", tc.expected, reason)
+			}
+			content, err := os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+			if err != nil {
+				t.Fatalf("Failed to read finalized report: %v", err)
+			}
+			if !strings.Contains(string(content), "exit_reason: "+string(tc.expected)+"\n") {
+				t.Errorf("Expected exit_reason %s in the frontmatter, got:\n%s", tc.expected, content)
+			}
+		})
+	}
+}
+
+func TestManager_XPassedTests(t *testing.T) {
+	tempDir := t.TempDir()
+	manager, err := NewManager(tempDir, nil, &mockLogger{}, "pytest", "pytest")
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	if err := manager.Initialize("pytest"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+	for _, payload := range []ipc.TestCasePayload{
+		{TestName: "test_parse_dates", ParentNames: []string{"test_dates.py"}, Status: "XPASS", XFailReason: "issue 12"},
+		{TestName: "test_leap_year", ParentNames: []string{"test_dates.py"}, Status: "XFAIL", XFailReason: "issue 13"},
+		{TestName: "test_windows", ParentNames: []string{"test_dates.py"}, Status: "SKIP", SkipMessage: "needs Windows"},
+	} {
+		if err := manager.HandleEvent(ipc.GroupTestCaseEvent{EventType: "testCase", Payload: payload}); err != nil {
+			t.Fatalf("HandleEvent failed: %v", err)
+		}
+	}
+	if xpassed := manager.XPassedTests(); len(xpassed) != 1 || xpassed[0].Name != "test_parse_dates" {
+		t.Errorf("Expected test_parse_dates to have passed unexpectedly, got %+v", xpassed)
+	}
+	if err := manager.Finalize(0, ""); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+
+	content, err := os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+	if err != nil {
+		t.Fatalf("Failed to read finalized report: %v", err)
+	}
+	if !strings.Contains(string(content), "## Unexpected passes\n") || !strings.Contains(string(content), "test_parse_dates`: issue 12 ([report](") {
+		t.Errorf("Expected test_parse_dates with its reason under Unexpected passes, got:\n%s", content)
+	}
+	if strings.Contains(string(content), "test_leap_year`: issue 13") {
+		t.Errorf("Expected the expected failure not to be listed, got:\n%s", content)
+	}
+
+	group := manager.groupManager.GetRootGroups()[0]
+	report := manager.groupManager.formatGroupReport(group)
+	for _, want := range []string{"  > *Passed, but expected to fail: issue 12*\n", "  > *Expected failure: issue 13*\n", "  > *Skipped: needs Windows*\n"} {
+		if !strings.Contains(report, want) {
+			t.Errorf("Expected %q in the group report, got:\n%s", want, report)
+		}
+	}
+}
+
 func TestManager_ReportFormat(t *testing.T) {
 	tempDir := t.TempDir()
 	logger := &mockLogger{}
@@ -552,3 +1210,472 @@ func TestManager_RecursiveTestCountsInTable(t *testing.T) {
 		t.Errorf("Expected summary to show 1 failed test case")
 	}
 }
+
+func TestManager_TargetKindSections(t *testing.T) {
+	tempDir := t.TempDir()
+	logger := &mockLogger{}
+	parser := runner.NewJestOutputParser()
+
+	manager, err := NewManager(tempDir, parser, logger, "cargo", "cargo test")
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	if err := manager.Initialize("cargo test"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+
+	// Each target of rust-comprehensive has a test with the same name
+	targets := []struct {
+		group string
+		kind  string
+		name  string
+	}{
+		{"integration-test", "test", "integration_test"},
+		{"my-binary", "bin", "my_binary"},
+		{"rust-comprehensive", "lib", "rust_comprehensive"},
+	}
+	for _, target := range targets {
+		_ = manager.groupManager.ProcessGroupDiscovered(ipc.GroupDiscoveredEvent{
+			EventType: "testGroupDiscovered",
+			Payload: ipc.GroupDiscoveredPayload{
+				GroupName:  target.group,
+				TargetKind: target.kind,
+				TargetName: target.name,
+			},
+		})
+		_ = manager.groupManager.ProcessTestCase(ipc.GroupTestCaseEvent{
+			EventType: "testCase",
+			Payload: ipc.TestCasePayload{
+				TestName:    "test_add",
+				ParentNames: []string{target.group},
+				Status:      "PASS",
+			},
+		})
+		_ = manager.groupManager.ProcessGroupResult(ipc.GroupResultEvent{
+			EventType: "testGroupResult",
+			Payload: ipc.GroupResultPayload{
+				GroupName: target.group,
+				Status:    "PASS",
+				Totals:    ipc.GroupTotals{Total: 1, Passed: 1},
+			},
+		})
+	}
+
+	_ = manager.Finalize(0)
+
+	content, err := os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+	if err != nil {
+		t.Fatalf("Failed to read report: %v", err)
+	}
+	reportContent := string(content)
+
+	// Sections follow target kind order (lib, bin, test), not the order groups ran in
+	sections := []string{
+		"### Unit tests (lib)\n\n| Status | Name | Tests | Duration | Report |\n|--------|------|-------|----------|--------|\n| PASS | rust-comprehensive |",
+		"### Binary tests (bin)\n\n| Status | Name | Tests | Duration | Report |\n|--------|------|-------|----------|--------|\n| PASS | my-binary |",
+		"### Integration tests (test)\n\n| Status | Name | Tests | Duration | Report |\n|--------|------|-------|----------|--------|\n| PASS | integration-test |",
+	}
+	last := -1
+	for _, section := range sections {
+		index := strings.Index(reportContent, section)
+		if index < 0 {
+			t.Fatalf("Expected section %q in report:\n%s", section, reportContent)
+		}
+		if index < last {
+			t.Errorf("Expected section %q after the previous one in report:\n%s", section, reportContent)
+		}
+		last = index
+	}
+
+	// Group reports record their target
+	group, ok := manager.groupManager.GetGroup(GenerateGroupID("my-binary", nil))
+	if !ok {
+		t.Fatal("Expected my-binary group")
+	}
+	if groupReport := manager.groupManager.formatGroupReport(group); !strings.Contains(groupReport, "target_kind: bin\ntarget_name: my_binary\n") {
+		t.Errorf("Expected target in group report frontmatter, got:\n%s", groupReport)
+	}
+}
+
+func TestManager_FailuresSection(t *testing.T) {
+	newManager := func(t *testing.T) (*Manager, string) {
+		tempDir := t.TempDir()
+		manager, err := NewManager(tempDir, nil, &mockLogger{}, "cargo test", "cargo test")
+		if err != nil {
+			t.Fatalf("Failed to create manager: %v", err)
+		}
+		if err := manager.Initialize("cargo test"); err != nil {
+			t.Fatalf("Initialize failed: %v", err)
+		}
+		return manager, tempDir
+	}
+	send := func(t *testing.T, manager *Manager, payload ipc.TestCasePayload) {
+		if err := manager.HandleEvent(ipc.GroupTestCaseEvent{EventType: "testCase", Payload: payload}); err != nil {
+			t.Fatalf("HandleEvent failed: %v", err)
+		}
+	}
+
+	t.Run("lists failures after the header", func(t *testing.T) {
+		manager, tempDir := newManager(t)
+		send(t, manager, ipc.TestCasePayload{TestName: "test_add", ParentNames: []string{"my-crate", "tests"}, Status: "PASS"})
+		send(t, manager, ipc.TestCasePayload{
+			TestName:    "test_math",
+			ParentNames: []string{"my-crate", "tests"},
+			Status:      "FAIL",
+			Error:       &ipc.TestError{Message: "assertion `left == right` failed", Expected: "5", Actual: "4\nmore"},
+		})
+		send(t, manager, ipc.TestCasePayload{
+			TestName:    "test_panic",
+			ParentNames: []string{"my-crate", "tests", "nested"},
+			Status:      "FAIL",
+			Error:       &ipc.TestError{Message: "\nUnexpected panic occurred!\nnote: run with `RUST_BACKTRACE=1`"},
+		})
+		if err := manager.Finalize(101, ""); err != nil {
+			t.Fatalf("Finalize failed: %v", err)
+		}
+
+		content, err := os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+		if err != nil {
+			t.Fatalf("Failed to read finalized report: %v", err)
+		}
+		report := string(content)
+		section := strings.Index(report, "## Failures\n\n")
+		if section == -1 || section > strings.Index(report, "## Summary") {
+			t.Fatalf("Expected a failures section before the summary, got:\n%s", report)
+		}
+		want := "## Failures\n\n" +
+			"- `my-crate → tests → test_math`: expected `5`, got `4` ([report](./reports/my_crate/tests/index.md))\n" +
+			"- `my-crate → tests → nested → test_panic`: Unexpected panic occurred! ([report](./reports/my_crate/tests/nested/index.md))\n\n"
+		if !strings.HasPrefix(report[section:], want) {
+			t.Errorf("Expected failures section:\n%s\ngot:\n%s", want, report[section:])
+		}
+		for _, link := range []string{"reports/my_crate/tests/index.md", "reports/my_crate/tests/nested/index.md"} {
+			if _, err := os.Stat(filepath.Join(tempDir, link)); err != nil {
+				t.Errorf("Expected linked report %s to exist: %v", link, err)
+			}
+		}
+	})
+
+	t.Run("caps long lists", func(t *testing.T) {
+		manager, tempDir := newManager(t)
+		for i := 0; i < maxFailureDigest+7; i++ {
+			send(t, manager, ipc.TestCasePayload{
+				TestName:    fmt.Sprintf("test_%03d", i),
+				ParentNames: []string{"my-crate"},
+				Status:      "FAIL",
+				Error:       &ipc.TestError{Message: strings.Repeat("x", 200)},
+			})
+		}
+		if err := manager.Finalize(101, ""); err != nil {
+			t.Fatalf("Finalize failed: %v", err)
+		}
+
+		content, _ := os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+		report := string(content)
+		if got := strings.Count(report, "([report]("); got != maxFailureDigest {
+			t.Errorf("Expected %d listed failures, got %d", maxFailureDigest, got)
+		}
+		if !strings.Contains(report, "- …and 7 more\n") {
+			t.Errorf("Expected a line counting the failures left out, got:\n%s", report)
+		}
+		if !strings.Contains(report, ": "+strings.Repeat("x", maxExcerptLength-1)+"… ([report]") {
+			t.Errorf("Expected long messages cut to %d characters", maxExcerptLength)
+		}
+	})
+
+	t.Run("omitted without failures", func(t *testing.T) {
+		manager, tempDir := newManager(t)
+		send(t, manager, ipc.TestCasePayload{TestName: "test_add", ParentNames: []string{"my-crate"}, Status: "PASS"})
+		if err := manager.Finalize(0, ""); err != nil {
+			t.Fatalf("Finalize failed: %v", err)
+		}
+
+		content, _ := os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+		if strings.Contains(string(content), "## Failures") {
+			t.Errorf("Expected no failures section, got:\n%s", content)
+		}
+	})
+
+	t.Run("gives the command that accepts a new trybuild case", func(t *testing.T) {
+		manager, tempDir := newManager(t)
+		send(t, manager, ipc.TestCasePayload{
+			TestName:    "ui",
+			ParentNames: []string{"my-crate", "compiletest"},
+			Status:      "FAIL",
+			Error: &ipc.TestError{
+				Message: "2 of 2 tests failed",
+				Trybuild: []ipc.TrybuildCase{
+					{Path: "tests/ui/missing-semicolon.rs", Status: "mismatch", StderrFile: "tests/ui/missing-semicolon.stderr"},
+					{Path: "tests/ui/new-case.rs", Status: "wip", StderrFile: "tests/ui/new-case.stderr", WipFile: "wip/new-case.stderr"},
+				},
+			},
+		})
+		if err := manager.Finalize(101, ""); err != nil {
+			t.Fatalf("Finalize failed: %v", err)
+		}
+
+		content, _ := os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+		want := "- `my-crate → compiletest → ui`: trybuild wrote `wip/new-case.stderr`, accept it with `mv wip/new-case.stderr tests/ui/new-case.stderr` ("
+		if !strings.Contains(string(content), want) {
+			t.Errorf("Expected the failure to give the command, got:\n%s", content)
+		}
+	})
+}
+
+func TestManager_SlowestTestsSection(t *testing.T) {
+	tempDir := t.TempDir()
+	manager, err := NewManager(tempDir, nil, &mockLogger{}, "cargo test", "cargo test")
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	manager.SetSlowThreshold(50 * time.Millisecond)
+	if err := manager.Initialize("cargo test"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+
+	// 12 timed tests over two groups, plus one without a duration
+	for i := 1; i <= 12; i++ {
+		parents := []string{"my-crate", "tests"}
+		if i%2 == 0 {
+			parents = []string{"my-crate", "tests", "nested"}
+		}
+		if err := manager.HandleEvent(ipc.GroupTestCaseEvent{EventType: "testCase", Payload: ipc.TestCasePayload{
+			TestName:    fmt.Sprintf("test_%02d", i),
+			ParentNames: parents,
+			Status:      "PASS",
+			Duration:    float64(i * 10),
+		}}); err != nil {
+			t.Fatalf("HandleEvent failed: %v", err)
+		}
+	}
+	if err := manager.HandleEvent(ipc.GroupTestCaseEvent{EventType: "testCase", Payload: ipc.TestCasePayload{
+		TestName:    "test_untimed",
+		ParentNames: []string{"my-crate", "tests"},
+		Status:      "PASS",
+	}}); err != nil {
+		t.Fatalf("HandleEvent failed: %v", err)
+	}
+	if err := manager.Finalize(0, ""); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+
+	content, err := os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+	if err != nil {
+		t.Fatalf("Failed to read finalized report: %v", err)
+	}
+	report := string(content)
+	section := strings.Index(report, "## Slowest tests\n\n")
+	if section == -1 {
+		t.Fatalf("Expected a slowest tests section, got:\n%s", report)
+	}
+	table := report[section:]
+	table = table[:strings.Index(table, "\n\n## ")]
+	rows := strings.Split(table, "\n")[4:]
+	if len(rows) != maxSlowestTests {
+		t.Fatalf("Expected %d rows, got %d:\n%s", maxSlowestTests, len(rows), table)
+	}
+	if want := "| 0.12s | test_12 | my-crate → tests → nested | ./reports/my_crate/tests/nested/index.md |"; rows[0] != want {
+		t.Errorf("Expected the slowest test first:\n%s\ngot:\n%s", want, rows[0])
+	}
+	if !strings.HasPrefix(rows[9], "| 0.03s | test_03 |") || strings.Contains(table, "test_untimed") {
+		t.Errorf("Expected the ten slowest timed tests, got:\n%s", table)
+	}
+
+	// Group reports with a test over the threshold list their tests slowest first
+	groupReport, err := os.ReadFile(filepath.Join(tempDir, "reports", "my_crate", "tests", "index.md"))
+	if err != nil {
+		t.Fatalf("Failed to read group report: %v", err)
+	}
+	if !strings.Contains(string(groupReport), "Sorted by duration, 3 tests took longer than 50ms.") {
+		t.Errorf("Expected the group report to say it is sorted by duration, got:\n%s", groupReport)
+	}
+	first := strings.Index(string(groupReport), "test_11 (0.11s)")
+	last := strings.Index(string(groupReport), "test_01 (0.01s)")
+	if first == -1 || last == -1 || first > last {
+		t.Errorf("Expected tests sorted by duration, got:\n%s", groupReport)
+	}
+}
+
+func TestManager_SlowestTestsOmittedWithoutDurations(t *testing.T) {
+	tempDir := t.TempDir()
+	manager, err := NewManager(tempDir, nil, &mockLogger{}, "cargo test", "cargo test")
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	if err := manager.Initialize("cargo test"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+	for _, name := range []string{"test_b", "test_a"} {
+		if err := manager.HandleEvent(ipc.GroupTestCaseEvent{EventType: "testCase", Payload: ipc.TestCasePayload{
+			TestName:    name,
+			ParentNames: []string{"my-crate"},
+			Status:      "PASS",
+		}}); err != nil {
+			t.Fatalf("HandleEvent failed: %v", err)
+		}
+	}
+	if err := manager.Finalize(0, ""); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+
+	content, _ := os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+	if strings.Contains(string(content), "## Slowest tests") {
+		t.Errorf("Expected no slowest tests section without durations, got:\n%s", content)
+	}
+	groupReport, _ := os.ReadFile(filepath.Join(tempDir, "reports", "my_crate", "index.md"))
+	if strings.Contains(string(groupReport), "Sorted by duration") {
+		t.Errorf("Expected group report in arrival order without durations, got:\n%s", groupReport)
+	}
+}
+
+func TestManager_CollectionErrors(t *testing.T) {
+	tempDir := t.TempDir()
+	manager, err := NewManager(tempDir, nil, &mockLogger{}, "pytest", "pytest")
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	if err := manager.Initialize("pytest"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+	collectionError := ipc.CollectionErrorEvent{EventType: ipc.EventTypeCollectionError}
+	collectionError.Payload.FilePath = "test_broken.py"
+	collectionError.Payload.Error = "E   ModuleNotFoundError: No module named 'missing'"
+	events := []ipc.Event{
+		ipc.GroupTestCaseEvent{EventType: "testCase", Payload: ipc.TestCasePayload{TestName: "test_add", ParentNames: []string{"test_good.py"}, Status: "PASS"}},
+		// pytest may report a file's collector more than once
+		collectionError,
+		collectionError,
+		ipc.GroupErrorEvent{EventType: ipc.EventTypeGroupError, Payload: ipc.GroupErrorPayload{
+			GroupName: "test_broken.py",
+			ErrorType: "COLLECTION_ERROR",
+			Error:     &ipc.GroupError{Message: collectionError.Payload.Error, Phase: "collection"},
+		}},
+	}
+	for _, event := range events {
+		if err := manager.HandleEvent(event); err != nil {
+			t.Fatalf("HandleEvent failed: %v", err)
+		}
+	}
+	if files := manager.CollectionErrors(); len(files) != 1 || files[0] != "test_broken.py" {
+		t.Errorf("Expected test_broken.py to have failed to collect once, got %q", files)
+	}
+
+	if err := manager.Finalize(2, ""); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+	if reason := manager.ExitReason(); reason != ExitReasonBuildFailed {
+		t.Errorf("Expected exit reason %q though every collected test passed, got %q", ExitReasonBuildFailed, reason)
+	}
+	summary, err := ReadSummary(tempDir)
+	if err != nil {
+		t.Fatalf("ReadSummary failed: %v", err)
+	}
+	if summary.Totals.CollectionErrors != 1 || summary.Totals.Passed != 1 {
+		t.Errorf("Expected 1 collection error and 1 passed test, got %+v", summary.Totals)
+	}
+	var broken *SummaryGroup
+	for i := range summary.Groups {
+		if strings.HasSuffix(summary.Groups[i].Name, "test_broken.py") {
+			broken = &summary.Groups[i]
+		}
+	}
+	if broken == nil || broken.Status != "ERROR" || broken.FailureKind != ipc.FailureKindBuildError || !strings.Contains(broken.Error, "No module named 'missing'") {
+		t.Errorf("Expected test_broken.py as an errored group with the traceback, got %+v", broken)
+	}
+}
+
+func TestManager_OutputPerWorker(t *testing.T) {
+	tempDir := t.TempDir()
+	manager, err := NewManager(tempDir, nil, &mockLogger{}, "pytest", "pytest")
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	if err := manager.Initialize("pytest -n 2"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+	collectionStart := ipc.CollectionStartEvent{EventType: ipc.EventTypeCollectionStart}
+	collectionStart.Payload.Workers = 2
+	start := func(worker, test string) ipc.Event {
+		return ipc.TestCaseStartEvent{EventType: string(ipc.EventTypeTestCaseStart), Payload: ipc.TestCaseStartPayload{TestName: test, ParentNames: []string{"test_io.py"}, WorkerID: worker}}
+	}
+	stdout := func(worker, chunk string) ipc.Event {
+		return ipc.GroupStdoutChunkEvent{EventType: string(ipc.EventTypeGroupStdout), Payload: ipc.OutputChunkPayload{Chunk: chunk, WorkerID: worker}}
+	}
+	result := func(worker, test string) ipc.Event {
+		return ipc.GroupTestCaseEvent{EventType: "testCase", Payload: ipc.TestCasePayload{TestName: test, ParentNames: []string{"test_io.py"}, Status: "FAIL", WorkerID: worker}}
+	}
+	// test_read started last, but gw0 is still printing for test_write
+	events := []ipc.Event{
+		collectionStart,
+		start("gw0", "test_write"),
+		stdout("gw0", "writing "),
+		start("gw1", "test_read"),
+		stdout("gw0", "done\n"),
+		stdout("gw1", "reading\n"),
+		result("gw1", "test_read"),
+		result("gw0", "test_write"),
+	}
+	for _, event := range events {
+		if err := manager.HandleEvent(event); err != nil {
+			t.Fatalf("HandleEvent failed: %v", err)
+		}
+	}
+
+	output := make(map[string]string)
+	for _, tc := range manager.groupManager.GetRootGroups()[0].TestCases {
+		output[tc.Name] = tc.Stdout
+	}
+	if output["test_write"] != "writing done\n" || output["test_read"] != "reading\n" {
+		t.Errorf("Expected each test to get its own worker's output, got %q", output)
+	}
+
+	if err := manager.Finalize(1, ""); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+	content, err := os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+	if err != nil {
+		t.Fatalf("Failed to read finalized report: %v", err)
+	}
+	if !strings.Contains(string(content), "workers: 2\n") {
+		t.Errorf("Expected the worker count in the frontmatter, got:\n%s", content)
+	}
+}
+
+func TestManager_Warnings(t *testing.T) {
+	tempDir := t.TempDir()
+	manager, err := NewManager(tempDir, nil, &mockLogger{}, "pytest", "pytest")
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	if err := manager.Initialize("pytest"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+	deprecation := ipc.RunWarning{Category: "DeprecationWarning", Message: "parse() is deprecated", NodeID: "test_dates.py::test_parse", File: "src/dates.py", Line: 12}
+	repeated := deprecation
+	repeated.NodeID = "test_dates.py::test_format"
+	resource := ipc.RunWarning{Category: "ResourceWarning", Message: "unclosed file", File: "src/io.py", Line: 3}
+	event := ipc.RunWarningsEvent{EventType: ipc.EventTypeRunWarnings, Payload: ipc.RunWarningsPayload{Warnings: []ipc.RunWarning{deprecation, repeated, resource}}}
+	if err := manager.HandleEvent(event); err != nil {
+		t.Fatalf("HandleEvent failed: %v", err)
+	}
+	if warnings := manager.Warnings(); len(warnings) != 2 {
+		t.Errorf("Expected the repeated warning to be counted once, got %+v", warnings)
+	}
+
+	if err := manager.Finalize(0, ""); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+	content, err := os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+	if err != nil {
+		t.Fatalf("Failed to read finalized report: %v", err)
+	}
+	for _, want := range []string{
+		"- Warnings: 2, see Warnings below\n",
+		"## Warnings\n\n- **DeprecationWarning** at `src/dates.py:12`: parse() is deprecated (`test_dates.py::test_parse`, 2 times)\n",
+		"- **ResourceWarning** at `src/io.py:3`: unclosed file\n",
+	} {
+		if !strings.Contains(string(content), want) {
+			t.Errorf("Expected %q in the report, got:\n%s", want, content)
+		}
+	}
+}
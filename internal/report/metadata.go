@@ -0,0 +1,48 @@
+package report
+
+import (
+	"encoding/json"
+	"fmt"
+	"path/filepath"
+	"strings"
+
+	"github.com/zk/3pio/internal/runinfo"
+)
+
+// SetMetadata records the toolchain and environment the run ran with. Finalize writes
+// it to metadata.json, and test-run.md shows it in its Environment section.
+func (m *Manager) SetMetadata(md *runinfo.Metadata) {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	m.metadata = md
+}
+
+// writeMetadata writes metadata.json
+func (m *Manager) writeMetadata() error {
+	data, err := json.MarshalIndent(m.metadata, "", "  ")
+	if err != nil {
+		return err
+	}
+	return writeFileAtomic(filepath.Join(m.runDir, runinfo.FileName), append(m.redactor.RedactBytes(data), '\n'))
+}
+
+// generateEnvironmentSection lists the tool versions, platform and environment
+// variables of the run, for telling apart runs that only fail on one machine
+func (m *Manager) generateEnvironmentSection(sb *strings.Builder) {
+	md := m.metadata
+	sb.WriteString("## Environment\n\n")
+	fmt.Fprintf(sb, "- Platform: %s/%s, %d CPUs\n", md.OS, md.Arch, md.CPUs)
+	if md.Toolchain != "" {
+		fmt.Fprintf(sb, "- Toolchain: %s\n", md.Toolchain)
+	}
+	if md.Target != "" {
+		fmt.Fprintf(sb, "- Target: %s\n", md.Target)
+	}
+	for _, tool := range md.Tools {
+		fmt.Fprintf(sb, "- %s: `%s`\n", tool.Name, tool.Version)
+	}
+	for _, name := range md.EnvNames() {
+		fmt.Fprintf(sb, "- `%s=%s`\n", name, md.Env[name])
+	}
+	fmt.Fprintf(sb, "\nFull version output: `./%s`\n\n", runinfo.FileName)
+}
@@ -0,0 +1,97 @@
+package report
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/internal/runinfo"
+)
+
+func TestManager_Metadata(t *testing.T) {
+	tempDir := t.TempDir()
+
+	manager, err := NewManager(tempDir, nil, &mockLogger{}, "cargo test", "cargo test")
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	if err := manager.Initialize("cargo test"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+	manager.SetMetadata(&runinfo.Metadata{
+		OS:        "linux",
+		Arch:      "amd64",
+		CPUs:      8,
+		Toolchain: "stable-x86_64-unknown-linux-gnu",
+		Target:    "x86_64-unknown-linux-gnu",
+		Tools: []runinfo.Tool{
+			{Name: "rustc", Version: "rustc 1.82.0 (f6e511eec 2024-10-15)", Output: "rustc 1.82.0 (f6e511eec 2024-10-15)\nhost: x86_64-unknown-linux-gnu"},
+			{Name: "cargo", Version: "cargo 1.82.0 (8f40fc59f 2024-08-21)"},
+		},
+		Env: map[string]string{"RUST_TEST_THREADS": "1", "RUSTFLAGS": "-D warnings"},
+	})
+	if err := manager.Finalize(0, ""); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+
+	data, err := os.ReadFile(filepath.Join(tempDir, runinfo.FileName))
+	if err != nil {
+		t.Fatalf("Failed to read %s: %v", runinfo.FileName, err)
+	}
+	var md runinfo.Metadata
+	if err := json.Unmarshal(data, &md); err != nil {
+		t.Fatalf("Failed to parse %s: %v", runinfo.FileName, err)
+	}
+	if md.CPUs != 8 || len(md.Tools) != 2 || md.Tools[0].Output == "" || md.Env["RUSTFLAGS"] != "-D warnings" {
+		t.Errorf("Unexpected %s: %+v", runinfo.FileName, md)
+	}
+
+	report, err := os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+	if err != nil {
+		t.Fatalf("Failed to read test-run.md: %v", err)
+	}
+	_, environment, found := strings.Cut(string(report), "## Environment\n")
+	if !found {
+		t.Fatalf("Expected an Environment section, got:\n%s", report)
+	}
+	for _, want := range []string{
+		"- Platform: linux/amd64, 8 CPUs\n",
+		"- Toolchain: stable-x86_64-unknown-linux-gnu\n",
+		"- Target: x86_64-unknown-linux-gnu\n",
+		"- rustc: `rustc 1.82.0 (f6e511eec 2024-10-15)`\n",
+		"- cargo: `cargo 1.82.0 (8f40fc59f 2024-08-21)`\n",
+		"- `RUSTFLAGS=-D warnings`\n- `RUST_TEST_THREADS=1`\n",
+	} {
+		if !strings.Contains(environment, want) {
+			t.Errorf("Expected %q in the Environment section, got:\n%s", want, environment)
+		}
+	}
+}
+
+func TestManager_NoMetadata(t *testing.T) {
+	tempDir := t.TempDir()
+
+	manager, err := NewManager(tempDir, nil, &mockLogger{}, "go test", "go test")
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	if err := manager.Initialize("go test"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+	if err := manager.Finalize(0, ""); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+
+	if _, err := os.Stat(filepath.Join(tempDir, runinfo.FileName)); !os.IsNotExist(err) {
+		t.Errorf("Expected no %s without metadata, got %v", runinfo.FileName, err)
+	}
+	report, err := os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+	if err != nil {
+		t.Fatalf("Failed to read test-run.md: %v", err)
+	}
+	if strings.Contains(string(report), "## Environment") {
+		t.Errorf("Expected no Environment section, got:\n%s", report)
+	}
+}
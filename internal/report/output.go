@@ -0,0 +1,149 @@
+package report
+
+import (
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"strings"
+	"unicode/utf8"
+
+	"github.com/zk/3pio/internal/console"
+)
+
+// maxOutputTail is how much of a group's or test's output is kept in memory for the
+// reports. The full output is written to files in the group's report directory.
+const maxOutputTail = 64 * 1024
+
+// maxPendingLine is how much of an unfinished line of captured output is buffered
+// before the progress redraws in it are collapsed
+const maxPendingLine = 4 * 1024
+
+// OutputRef locates captured output in a file on disk
+type OutputRef struct {
+	Path   string // File holding the output
+	Offset int64  // Byte offset of the output in the file
+	Length int64  // Length of the output in bytes
+}
+
+// ReadOutput reads the output a ref points to back from disk
+func ReadOutput(ref *OutputRef) (string, error) {
+	if ref == nil {
+		return "", nil
+	}
+	file, err := os.Open(ref.Path)
+	if err != nil {
+		return "", err
+	}
+	defer func() { _ = file.Close() }()
+
+	data := make([]byte, ref.Length)
+	if _, err := file.ReadAt(data, ref.Offset); err != nil && err != io.EOF {
+		return "", err
+	}
+	return string(data), nil
+}
+
+// appendOutputTail appends chunk to tail and keeps only the last maxOutputTail bytes,
+// starting at a line boundary when there is one. It reports whether anything was dropped.
+func appendOutputTail(tail, chunk string) (string, bool) {
+	if len(tail)+len(chunk) <= maxOutputTail {
+		return tail + chunk, false
+	}
+	var kept string
+	if len(chunk) >= maxOutputTail {
+		kept = chunk[len(chunk)-maxOutputTail:]
+	} else {
+		kept = tail[len(tail)-(maxOutputTail-len(chunk)):] + chunk
+	}
+	return trimToBoundary(kept), true
+}
+
+// appendCapturedOutput appends a chunk of a group's output to its tail. Finished lines
+// are kept as they read on a terminal (see console.PlainText), so a progress bar redrawn
+// with "\r" leaves only its last state instead of every frame on one line. The
+// unfinished last line stays raw until its newline arrives, since "\r\n" or an escape
+// sequence may be split across chunks, and once it outgrows maxPendingLine the redraws
+// so far are collapsed. It reports whether output was dropped, as appendOutputTail.
+func appendCapturedOutput(tail, chunk string) (string, bool) {
+	head, pending := "", tail
+	if i := strings.LastIndexByte(tail, '\n'); i >= 0 {
+		head, pending = tail[:i+1], tail[i+1:]
+	}
+	pending += chunk
+
+	dropped := false
+	if i := strings.LastIndexByte(pending, '\n'); i >= 0 {
+		lines := pending[:i+1]
+		pending = pending[i+1:]
+		if len(lines) > maxOutputTail {
+			head = ""
+			lines, dropped = outputTail(lines)
+		}
+		head += console.PlainText(lines)
+	}
+	if len(pending) > maxPendingLine {
+		if i := strings.LastIndexByte(pending, '\r'); i > 0 {
+			pending = console.PlainText(pending[:i]) + pending[i:]
+		}
+	}
+
+	tail, cut := appendOutputTail(head, pending)
+	return tail, dropped || cut
+}
+
+// outputTail returns the last maxOutputTail bytes of output, and whether it was cut
+func outputTail(output string) (string, bool) {
+	if len(output) <= maxOutputTail {
+		return output, false
+	}
+	return trimToBoundary(output[len(output)-maxOutputTail:]), true
+}
+
+// trimToBoundary drops the partial line (or at least the partial UTF-8 character)
+// at the start of a tail that was cut
+func trimToBoundary(s string) string {
+	if i := strings.IndexByte(s, '\n'); i >= 0 && i < len(s)-1 {
+		return s[i+1:]
+	}
+	for len(s) > 0 && !utf8.RuneStartByte(s[0]) {
+		s = s[1:]
+	}
+	return s
+}
+
+// appendToFile appends data to a file, creating it and its directory if needed
+func appendToFile(path, data string) error {
+	if err := os.MkdirAll(filepath.Dir(path), 0755); err != nil {
+		return fmt.Errorf("failed to create output directory: %w", err)
+	}
+	file, err := os.OpenFile(path, os.O_CREATE|os.O_APPEND|os.O_WRONLY, 0644)
+	if err != nil {
+		return fmt.Errorf("failed to open output file: %w", err)
+	}
+	if _, err := file.WriteString(data); err != nil {
+		_ = file.Close()
+		return fmt.Errorf("failed to write output file: %w", err)
+	}
+	return file.Close()
+}
+
+// writeTestOutput writes a test's captured stdout and stderr to its log file, replacing
+// an earlier attempt's, and returns where each of them is in the file
+func writeTestOutput(path, stdout, stderr string) (*OutputRef, *OutputRef, error) {
+	if err := os.MkdirAll(filepath.Dir(path), 0755); err != nil {
+		return nil, nil, fmt.Errorf("failed to create log directory: %w", err)
+	}
+	if err := os.WriteFile(path, []byte(stdout+stderr), 0644); err != nil {
+		return nil, nil, fmt.Errorf("failed to write test log: %w", err)
+	}
+
+	var stdoutRef, stderrRef *OutputRef
+	if stdout != "" {
+		stdoutRef = &OutputRef{Path: path, Offset: 0, Length: int64(len(stdout))}
+	}
+	if stderr != "" {
+		stderrRef = &OutputRef{Path: path, Offset: int64(len(stdout)), Length: int64(len(stderr))}
+	}
+	return stdoutRef, stderrRef, nil
+}
@@ -0,0 +1,198 @@
+package report
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"runtime"
+	"strings"
+	"testing"
+	"unicode/utf8"
+
+	"github.com/zk/3pio/internal/ipc"
+	"github.com/zk/3pio/internal/logger"
+)
+
+func TestAppendOutputTail(t *testing.T) {
+	// Small output is kept as is
+	tail, dropped := appendOutputTail("line 1\n", "line 2\n")
+	if tail != "line 1\nline 2\n" || dropped {
+		t.Errorf("Expected small output kept whole, got %q (dropped %v)", tail, dropped)
+	}
+
+	// Large output keeps the last maxOutputTail bytes, starting at a line
+	line := strings.Repeat("x", 99) + "\n"
+	tail = ""
+	for i := 0; i < 2*maxOutputTail/len(line); i++ {
+		tail, dropped = appendOutputTail(tail, line)
+	}
+	if !dropped || len(tail) > maxOutputTail || len(tail) < maxOutputTail-len(line) {
+		t.Errorf("Expected a tail of about %d bytes, got %d (dropped %v)", maxOutputTail, len(tail), dropped)
+	}
+	if !strings.HasPrefix(tail, line) || !strings.HasSuffix(tail, line) {
+		t.Errorf("Expected the tail to start and end on a line boundary")
+	}
+
+	// A single chunk bigger than the tail, without newlines, is cut at a character
+	chunk := strings.Repeat("✓", maxOutputTail)
+	tail, dropped = appendOutputTail("before\n", chunk)
+	if !dropped || len(tail) > maxOutputTail || !utf8.ValidString(tail) || !strings.HasSuffix(chunk, tail) {
+		t.Errorf("Expected a valid UTF-8 tail of the chunk, got %d bytes (dropped %v)", len(tail), dropped)
+	}
+}
+
+func TestAppendCapturedOutput_ProgressBar(t *testing.T) {
+	tail, _ := appendCapturedOutput("", "Compiling wasm\n")
+
+	// A progress bar redrawn 10k times with "\r", arriving in chunks that split frames
+	var raw, chunk strings.Builder
+	for n := 1; n <= 10000; n++ {
+		fmt.Fprintf(&chunk, "\r\x1b[32m[%-50s]\x1b[0m %5d/10000", strings.Repeat("#", n*50/10000), n)
+		if n%37 == 0 {
+			raw.WriteString(chunk.String())
+			var dropped bool
+			tail, dropped = appendCapturedOutput(tail, chunk.String())
+			chunk.Reset()
+			if dropped || len(tail) > maxPendingLine+4*1024 {
+				t.Fatalf("Expected the pending line to stay bounded, got %d bytes (dropped %v) after %d updates", len(tail), dropped, n)
+			}
+		}
+	}
+	raw.WriteString(chunk.String())
+	tail, _ = appendCapturedOutput(tail, chunk.String()+"\r\n")
+	// "\r\n" split across chunks still ends the line
+	tail, _ = appendCapturedOutput(tail, "Done\r")
+	tail, _ = appendCapturedOutput(tail, "\n")
+
+	want := "Compiling wasm\n[" + strings.Repeat("#", 50) + "] 10000/10000\nDone\n"
+	if tail != want {
+		t.Errorf("Expected the final state of each line, got %q (from %d raw bytes)", tail, raw.Len())
+	}
+
+	// A line without redraws is kept whole until it outgrows the tail
+	line := strings.Repeat("x", 2*maxPendingLine)
+	if tail, _ := appendCapturedOutput("", line); tail != line {
+		t.Errorf("Expected a long line without carriage returns kept as is, got %d bytes", len(tail))
+	}
+}
+
+func TestGroupManager_OutputStreamedToDisk(t *testing.T) {
+	tmpDir := t.TempDir()
+	log, _ := logger.NewFileLogger()
+	t.Cleanup(func() { _ = log.Close() })
+	gm := NewGroupManager(tmpDir, "", log)
+
+	_ = gm.ProcessGroupDiscovered(ipc.GroupDiscoveredEvent{
+		EventType: string(ipc.EventTypeGroupDiscovered),
+		Payload:   ipc.GroupDiscoveredPayload{GroupName: "chatty_crate"},
+	})
+
+	// Group output: everything goes to output.log, only the tail stays in memory
+	line := strings.Repeat("o", 63) + "\n"
+	var full strings.Builder
+	for i := 0; i < 4*maxOutputTail/len(line); i++ {
+		full.WriteString(line)
+		if err := gm.ProcessStdoutChunk("chatty_crate", nil, line); err != nil {
+			t.Fatalf("ProcessStdoutChunk failed: %v", err)
+		}
+	}
+
+	group, _ := gm.GetGroup(GenerateGroupID("chatty_crate", nil))
+	if !group.OutputTruncated || len(group.Stdout) > maxOutputTail {
+		t.Errorf("Expected only a tail of the output in memory, got %d bytes (truncated %v)",
+			len(group.Stdout), group.OutputTruncated)
+	}
+	logged, err := os.ReadFile(GetGroupOutputFilePath(group, tmpDir))
+	if err != nil {
+		t.Fatalf("Failed to read output.log: %v", err)
+	}
+	if string(logged) != full.String() {
+		t.Errorf("Expected output.log to hold all %d bytes of output, got %d", full.Len(), len(logged))
+	}
+
+	// Test output: written to the test's log, with refs locating stdout and stderr
+	stdout := strings.Repeat("s", 2*maxOutputTail)
+	stderr := "thread 'test_loud' panicked\n"
+	_ = gm.ProcessTestCase(ipc.GroupTestCaseEvent{
+		EventType: string(ipc.EventTypeTestCase),
+		Payload: ipc.TestCasePayload{
+			TestName:    "test_loud",
+			ParentNames: []string{"chatty_crate"},
+			Status:      "FAIL",
+			Stdout:      stdout,
+			Stderr:      stderr,
+		},
+	})
+	gm.Flush()
+
+	group, _ = gm.GetGroup(GenerateGroupID("chatty_crate", nil))
+	tc := group.TestCases[0]
+	if !tc.OutputTruncated || len(tc.Stdout) > maxOutputTail || tc.Stderr != stderr {
+		t.Errorf("Expected a truncated stdout and the whole stderr, got %d and %q", len(tc.Stdout), tc.Stderr)
+	}
+	if tc.StdoutRef == nil || tc.StderrRef == nil {
+		t.Fatalf("Expected refs to the test's output, got %+v and %+v", tc.StdoutRef, tc.StderrRef)
+	}
+	if tc.StderrRef.Offset != int64(len(stdout)) || tc.StderrRef.Length != int64(len(stderr)) {
+		t.Errorf("Unexpected stderr ref %+v", tc.StderrRef)
+	}
+	for ref, want := range map[*OutputRef]string{tc.StdoutRef: stdout, tc.StderrRef: stderr} {
+		if got, err := ReadOutput(ref); err != nil || got != want {
+			t.Errorf("Expected %d bytes at %+v, got %d (%v)", len(want), ref, len(got), err)
+		}
+	}
+
+	// The report points at the files holding the full output
+	report, err := os.ReadFile(GetReportFilePath(group, tmpDir))
+	if err != nil {
+		t.Fatalf("Failed to read report: %v", err)
+	}
+	for _, want := range []string{"full output in ./output.log", "full output in ./logs/test_loud.log"} {
+		if !strings.Contains(string(report), want) {
+			t.Errorf("Expected the report to mention %q", want)
+		}
+	}
+	if filepath.Dir(tc.StdoutRef.Path) != filepath.Join(GenerateGroupPath(group, tmpDir), "logs") {
+		t.Errorf("Expected the test log in the group's logs directory, got %s", tc.StdoutRef.Path)
+	}
+}
+
+// TestGroupManager_OutputMemoryFlat streams 500MB of output through a group and checks
+// that the heap doesn't grow with it
+func TestGroupManager_OutputMemoryFlat(t *testing.T) {
+	if testing.Short() {
+		t.Skip("Skipping 500MB output test in short mode")
+	}
+
+	tmpDir := t.TempDir()
+	log, _ := logger.NewFileLogger()
+	t.Cleanup(func() { _ = log.Close() })
+	gm := NewGroupManager(tmpDir, "", log)
+	_ = gm.ProcessGroupDiscovered(ipc.GroupDiscoveredEvent{
+		EventType: string(ipc.EventTypeGroupDiscovered),
+		Payload:   ipc.GroupDiscoveredPayload{GroupName: "chatty_crate"},
+	})
+
+	var before, after runtime.MemStats
+	runtime.GC()
+	runtime.ReadMemStats(&before)
+
+	chunk := strings.Repeat(strings.Repeat("o", 1023)+"\n", 1024) // 1MB
+	for i := 0; i < 500; i++ {
+		if err := gm.ProcessStdoutChunk("chatty_crate", nil, chunk); err != nil {
+			t.Fatalf("ProcessStdoutChunk failed: %v", err)
+		}
+	}
+
+	runtime.GC()
+	runtime.ReadMemStats(&after)
+	if growth := int64(after.HeapInuse) - int64(before.HeapInuse); growth > 32<<20 {
+		t.Errorf("Expected the heap to stay flat, it grew by %d MB", growth>>20)
+	}
+
+	group, _ := gm.GetGroup(GenerateGroupID("chatty_crate", nil))
+	info, err := os.Stat(GetGroupOutputFilePath(group, tmpDir))
+	if err != nil || info.Size() != 500*int64(len(chunk)) {
+		t.Errorf("Expected all 500MB in output.log, got %v (%v)", info, err)
+	}
+}
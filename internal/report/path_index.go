@@ -0,0 +1,87 @@
+package report
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+	"sort"
+)
+
+// PathIndexFileName is the file in the run directory that maps group and test names
+// to the report files written for them
+const PathIndexFileName = "index.json"
+
+// PathIndex is the schema of index.json. Names are sanitized into file names
+// lossily (and get a hash suffix when two of them end up alike), so consumers look up
+// the original names here instead of sanitizing them again.
+type PathIndex struct {
+	SchemaVersion int              `json:"schema_version"`
+	Groups        []PathIndexGroup `json:"groups"`
+}
+
+// PathIndexGroup maps one group to its report directory
+type PathIndexGroup struct {
+	ID       string            `json:"id"`
+	Path     []string          `json:"path"`                // Names from the root group down to this one
+	Dir      string            `json:"dir"`                 // Report directory, relative to the run directory
+	Report   string            `json:"report"`              // index.md of the group, relative to the run directory
+	TestLogs map[string]string `json:"test_logs,omitempty"` // Test name -> its log file, relative to the run directory
+	TestIDs  map[string]string `json:"test_ids,omitempty"`  // Test name -> its stable test ID (see TestID)
+}
+
+// writePathIndex writes index.json for every group. Caller must hold gm.mu.
+func (gm *GroupManager) writePathIndex() error {
+	index := PathIndex{SchemaVersion: 1, Groups: make([]PathIndexGroup, 0, len(gm.groups))}
+	for _, group := range gm.groups {
+		entry := PathIndexGroup{
+			ID:     group.ID,
+			Path:   append(append([]string(nil), group.ParentNames...), group.Name),
+			Dir:    gm.runRelativePath(GenerateGroupPath(group, gm.runDir)),
+			Report: gm.runRelativePath(GetReportFilePath(group, gm.runDir)),
+		}
+		for testName := range group.logNames {
+			if entry.TestLogs == nil {
+				entry.TestLogs = make(map[string]string)
+			}
+			entry.TestLogs[testName] = gm.runRelativePath(GetTestLogFilePath(group, testName, gm.runDir))
+		}
+		for _, tc := range group.TestCases {
+			if entry.TestIDs == nil {
+				entry.TestIDs = make(map[string]string)
+			}
+			entry.TestIDs[tc.Name] = tc.TestID
+		}
+		index.Groups = append(index.Groups, entry)
+	}
+	sort.Slice(index.Groups, func(i, j int) bool {
+		return index.Groups[i].Dir < index.Groups[j].Dir
+	})
+
+	data, err := json.MarshalIndent(index, "", "  ")
+	if err != nil {
+		return err
+	}
+	return writeFileAtomic(filepath.Join(gm.runDir, PathIndexFileName), append(data, '\n'))
+}
+
+// runRelativePath returns a path relative to the run directory, with forward slashes
+func (gm *GroupManager) runRelativePath(path string) string {
+	if relPath, err := filepath.Rel(gm.runDir, path); err == nil {
+		path = relPath
+	}
+	return filepath.ToSlash(path)
+}
+
+// ReadPathIndex reads the index.json of a run directory
+func ReadPathIndex(runDir string) (*PathIndex, error) {
+	data, err := os.ReadFile(filepath.Join(runDir, PathIndexFileName))
+	if err != nil {
+		return nil, err
+	}
+	var index PathIndex
+	if err := json.Unmarshal(data, &index); err != nil {
+		return nil, fmt.Errorf("failed to parse %s: %w", PathIndexFileName, err)
+	}
+	return &index, nil
+}
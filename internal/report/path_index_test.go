@@ -0,0 +1,195 @@
+package report
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"reflect"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/internal/ipc"
+	"github.com/zk/3pio/internal/logger"
+)
+
+func TestGroupManager_CollidingNamesGetHashSuffix(t *testing.T) {
+	// Module names differing only in case, and test names that sanitize alike, in
+	// both orders
+	modules, tests := []string{"module_a", "module_A"}, []string{"case a-b", "case a_b"}
+	first := collidingNameDirs(t, modules, tests)
+	second := collidingNameDirs(t, []string{modules[1], modules[0]}, []string{tests[1], tests[0]})
+
+	lower, upper := first["my-crate/module_a"], first["my-crate/module_A"]
+	if !strings.HasPrefix(lower.Dir, "reports/my_crate/module_a_") || len(lower.Dir) != len("reports/my_crate/module_a")+9 {
+		t.Errorf("Expected the first module to get a hash suffix too, got %q", lower.Dir)
+	}
+	if !strings.HasPrefix(upper.Dir, "reports/my_crate/module_A_") || len(upper.Dir) != len(lower.Dir) {
+		t.Errorf("Expected the second module to get a hash suffix, got %q", upper.Dir)
+	}
+	if dir := first["my-crate"].Dir; dir != "reports/my_crate" {
+		t.Errorf("Expected a plain directory for a name that doesn't collide, got %q", dir)
+	}
+
+	// The names don't depend on the order the groups and tests arrived in
+	for path, group := range first {
+		if other := second[path]; other.Dir != group.Dir || !reflect.DeepEqual(other.TestLogs, group.TestLogs) {
+			t.Errorf("Expected %s to get the same files in either order, got %s %v and %s %v", path, group.Dir, group.TestLogs, other.Dir, other.TestLogs)
+		}
+	}
+}
+
+// collidingNameDirs reports the tests of each module in order, each test writing its
+// module and name to its log, and returns the path index by group path after checking
+// each report and log it lists holds the right group's and test's files
+func collidingNameDirs(t *testing.T, modules, tests []string) map[string]PathIndexGroup {
+	t.Helper()
+	tmpDir := t.TempDir()
+	log, _ := logger.NewFileLogger()
+	t.Cleanup(func() { _ = log.Close() })
+	gm := NewGroupManager(tmpDir, "", log)
+
+	for _, module := range modules {
+		for _, test := range tests {
+			_ = gm.ProcessTestCase(ipc.GroupTestCaseEvent{
+				EventType: string(ipc.EventTypeTestCase),
+				Payload: ipc.TestCasePayload{
+					TestName:    test,
+					ParentNames: []string{"my-crate", module},
+					Status:      "FAIL",
+					Stdout:      module + " " + test + "\n",
+				},
+			})
+		}
+	}
+	gm.Flush()
+
+	index, err := ReadPathIndex(tmpDir)
+	if err != nil {
+		t.Fatalf("Failed to read %s: %v", PathIndexFileName, err)
+	}
+	dirs := make(map[string]PathIndexGroup)
+	for _, group := range index.Groups {
+		dirs[strings.Join(group.Path, "/")] = group
+	}
+
+	for _, module := range modules {
+		group := dirs["my-crate/"+module]
+		if _, err := os.Stat(filepath.Join(tmpDir, filepath.FromSlash(group.Report))); err != nil {
+			t.Errorf("Expected the report listed in the index to exist: %v", err)
+		}
+		if len(group.TestLogs) != 2 || group.TestLogs["case a-b"] == group.TestLogs["case a_b"] {
+			t.Fatalf("Expected a log file per test, got %v", group.TestLogs)
+		}
+		if len(group.TestIDs) != 2 || !IsTestID(group.TestIDs["case a-b"]) || group.TestIDs["case a-b"] == group.TestIDs["case a_b"] {
+			t.Errorf("Expected a distinct test ID per test, got %v", group.TestIDs)
+		}
+		// Each log holds its own test's output, so nothing was overwritten or left
+		// behind when a name was taken back
+		for test, logPath := range group.TestLogs {
+			data, err := os.ReadFile(filepath.Join(tmpDir, filepath.FromSlash(logPath)))
+			if err != nil || string(data) != module+" "+test+"\n" {
+				t.Errorf("Expected %s to hold the output of %q, got %q (%v)", logPath, test, data, err)
+			}
+		}
+		// The tests' output references follow their logs
+		moduleGroup, ok := gm.GetGroup(GenerateGroupID(module, []string{"my-crate"}))
+		if !ok {
+			t.Fatalf("Group %s not found", module)
+		}
+		for _, tc := range moduleGroup.TestCases {
+			if want := filepath.Join(tmpDir, filepath.FromSlash(group.TestLogs[tc.Name])); tc.StdoutRef == nil || tc.StdoutRef.Path != want {
+				t.Errorf("Expected the output of %q at %s, got %+v", tc.Name, want, tc.StdoutRef)
+			}
+		}
+	}
+	entries, err := os.ReadDir(filepath.Join(tmpDir, "reports", "my_crate"))
+	if err != nil {
+		t.Fatal(err)
+	}
+	for _, entry := range entries {
+		if entry.Name() == "module_a" || entry.Name() == "module_A" {
+			t.Errorf("Expected no directory left at the plain name, found %s", entry.Name())
+		}
+	}
+	return dirs
+}
+
+func TestGroupManager_DeepGroupsFitPathLimit(t *testing.T) {
+	// Once with this platform's limit, and once with Windows' on every platform
+	limits := map[string]int{"platform": maxPathLength, "windows": MaxWindowsPathLength - 1}
+	for name, limit := range limits {
+		t.Run(name, func(t *testing.T) {
+			defer func(saved int) { maxPathLength = saved }(maxPathLength)
+			maxPathLength = limit
+
+			tmpDir := t.TempDir()
+			log, _ := logger.NewFileLogger()
+			t.Cleanup(func() { _ = log.Close() })
+			gm := NewGroupManager(tmpDir, "", log)
+
+			// 10 levels of groups with 100 character names, and a test with a long name
+			var names []string
+			for i := 0; i < 10; i++ {
+				names = append(names, fmt.Sprintf("level_%d_%s", i, strings.Repeat("x", 92)))
+			}
+			testName := "test_" + strings.Repeat("y", 95)
+			_ = gm.ProcessGroupDiscovered(ipc.GroupDiscoveredEvent{
+				EventType: string(ipc.EventTypeGroupDiscovered),
+				Payload:   ipc.GroupDiscoveredPayload{GroupName: names[9], ParentNames: names[:9]},
+			})
+			if err := gm.ProcessStdoutChunk(names[9], names[:9], "group output\n"); err != nil {
+				t.Fatalf("ProcessStdoutChunk failed: %v", err)
+			}
+			_ = gm.ProcessTestCase(ipc.GroupTestCaseEvent{
+				EventType: string(ipc.EventTypeTestCase),
+				Payload: ipc.TestCasePayload{
+					TestName:    testName,
+					ParentNames: names,
+					Status:      "FAIL",
+					Stdout:      "test output\n",
+				},
+			})
+			gm.Flush()
+
+			index, err := ReadPathIndex(tmpDir)
+			if err != nil {
+				t.Fatalf("Failed to read %s: %v", PathIndexFileName, err)
+			}
+			if len(index.Groups) != 10 {
+				t.Fatalf("Expected all 10 groups in the index, got %d", len(index.Groups))
+			}
+
+			// The index maps the original names to files that exist
+			var deepest PathIndexGroup
+			for _, group := range index.Groups {
+				if len(group.Path) == 10 {
+					deepest = group
+				}
+				if _, err := os.Stat(filepath.Join(tmpDir, filepath.FromSlash(group.Report))); err != nil {
+					t.Errorf("Expected the report of %s to exist: %v", group.Path[len(group.Path)-1], err)
+				}
+			}
+			if strings.Join(deepest.Path, "/") != strings.Join(names, "/") {
+				t.Fatalf("Expected the deepest group to keep its original names in the index, got %v", deepest.Path)
+			}
+			files := []string{
+				filepath.Join(deepest.Dir, "output.log"),
+				deepest.TestLogs[testName],
+			}
+			for _, file := range files {
+				if _, err := os.Stat(filepath.Join(tmpDir, filepath.FromSlash(file))); err != nil {
+					t.Errorf("Expected %s to be written: %v", file, err)
+				}
+			}
+
+			// Nothing written exceeds the limit
+			absDir, _ := filepath.Abs(tmpDir)
+			_ = filepath.Walk(absDir, func(path string, info os.FileInfo, err error) error {
+				if err == nil && len(path) > limit {
+					t.Errorf("Path of %d characters exceeds the limit of %d: %s", len(path), limit, path)
+				}
+				return nil
+			})
+		})
+	}
+}
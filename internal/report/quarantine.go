@@ -0,0 +1,281 @@
+package report
+
+import (
+	"fmt"
+	"os"
+	"path"
+	"path/filepath"
+	"strconv"
+	"strings"
+	"sync"
+)
+
+// QuarantineFileName is the quarantine list in the .3pio directory, used when
+// --quarantine doesn't name another file
+const QuarantineFileName = "quarantine.toml"
+
+// QuarantineSeparator separates the groups and the test name of a quarantine entry,
+// e.g. "my-crate > tests > test_flaky"
+const QuarantineSeparator = " > "
+
+// Quarantine is a list of known-flaky tests whose failures don't fail the run. Each
+// entry is a test's group path and name joined by " > ", or its test ID. Every part of
+// a path may be a glob ("test_net_*"), and "**" stands for any number of groups.
+type Quarantine struct {
+	mu       sync.Mutex
+	entries  []string
+	patterns [][]string // Nil for test ID entries
+	matched  []bool
+	workDir  string // Directory file path groups are relative to; empty for the working directory
+}
+
+// LoadQuarantine reads a quarantine file
+func LoadQuarantine(path string) (*Quarantine, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return nil, err
+	}
+	q, err := ParseQuarantine(string(data))
+	if err != nil {
+		return nil, fmt.Errorf("%s: %w", path, err)
+	}
+	return q, nil
+}
+
+// ParseQuarantine parses a quarantine file. It takes the subset of TOML the file
+// needs: comments and a single "tests" array of strings, which may span lines.
+//
+//	tests = [
+//	  "my-crate > tests > test_flaky",
+//	  "my-crate > net > **",
+//	]
+func ParseQuarantine(content string) (*Quarantine, error) {
+	lines := strings.Split(content, "\n")
+	for i, line := range lines {
+		lines[i] = stripTOMLComment(line)
+	}
+	s := &tomlScanner{input: strings.Join(lines, "\n")}
+
+	if !s.consume("tests") || !s.consume("=") || !s.consume("[") {
+		return nil, s.errorf("expected tests = [...]")
+	}
+	q := &Quarantine{}
+	for !s.consume("]") {
+		entry, err := s.string()
+		if err != nil {
+			return nil, err
+		}
+		if err := q.add(entry); err != nil {
+			return nil, s.errorf("%v", err)
+		}
+		if !s.consume(",") && !s.peek("]") {
+			return nil, s.errorf("expected , or ] after %q", entry)
+		}
+	}
+	if s.skipSpace(); s.pos < len(s.input) {
+		return nil, s.errorf("unexpected %q after the tests array", s.rest())
+	}
+	return q, nil
+}
+
+// add adds an entry after checking its globs
+func (q *Quarantine) add(entry string) error {
+	if IsTestID(strings.TrimSpace(entry)) {
+		q.entries = append(q.entries, strings.TrimSpace(entry))
+		q.patterns = append(q.patterns, nil)
+		q.matched = append(q.matched, false)
+		return nil
+	}
+	patterns := strings.Split(entry, QuarantineSeparator)
+	for i := range patterns {
+		patterns[i] = strings.TrimSpace(patterns[i])
+		if _, err := path.Match(patterns[i], ""); err != nil {
+			return fmt.Errorf("invalid pattern %q: %w", entry, err)
+		}
+	}
+	q.entries = append(q.entries, entry)
+	q.patterns = append(q.patterns, patterns)
+	q.matched = append(q.matched, false)
+	return nil
+}
+
+// tomlScanner reads the tokens of a quarantine or redact file
+type tomlScanner struct {
+	input string
+	pos   int
+}
+
+// skipSpace skips whitespace, including newlines
+func (s *tomlScanner) skipSpace() {
+	for s.pos < len(s.input) && strings.ContainsRune(" \t\r\n", rune(s.input[s.pos])) {
+		s.pos++
+	}
+}
+
+// peek reports whether the next token is token
+func (s *tomlScanner) peek(token string) bool {
+	s.skipSpace()
+	return strings.HasPrefix(s.input[s.pos:], token)
+}
+
+// consume skips the next token if it is token
+func (s *tomlScanner) consume(token string) bool {
+	if !s.peek(token) {
+		return false
+	}
+	s.pos += len(token)
+	return true
+}
+
+// string reads a basic ("...") or literal ('...') string on a single line
+func (s *tomlScanner) string() (string, error) {
+	s.skipSpace()
+	if s.pos >= len(s.input) || (s.input[s.pos] != '"' && s.input[s.pos] != '\'') {
+		return "", s.errorf("expected a quoted string, got %q", s.rest())
+	}
+	quote := s.input[s.pos]
+	for end := s.pos + 1; end < len(s.input) && s.input[end] != '\n'; end++ {
+		if quote == '"' && s.input[end] == '\\' {
+			end++
+			continue
+		}
+		if s.input[end] != quote {
+			continue
+		}
+		raw := s.input[s.pos : end+1]
+		value := raw[1 : len(raw)-1]
+		if quote == '"' {
+			var err error
+			if value, err = strconv.Unquote(raw); err != nil {
+				return "", s.errorf("invalid string %s", raw)
+			}
+		}
+		s.pos = end + 1
+		return value, nil
+	}
+	return "", s.errorf("unterminated string")
+}
+
+// rest returns the rest of the current line, for error messages
+func (s *tomlScanner) rest() string {
+	rest, _, _ := strings.Cut(s.input[s.pos:], "\n")
+	return rest
+}
+
+// errorf returns an error at the current line
+func (s *tomlScanner) errorf(format string, args ...interface{}) error {
+	line := strings.Count(s.input[:s.pos], "\n") + 1
+	return fmt.Errorf("line %d: %s", line, fmt.Sprintf(format, args...))
+}
+
+// stripTOMLComment removes a "#" comment that isn't inside a string
+func stripTOMLComment(line string) string {
+	var quote rune
+	for i, r := range line {
+		switch {
+		case quote != 0 && r == quote && (quote == '\'' || i == 0 || line[i-1] != '\\'):
+			quote = 0
+		case quote == 0 && (r == '"' || r == '\''):
+			quote = r
+		case quote == 0 && r == '#':
+			return line[:i]
+		}
+	}
+	return line
+}
+
+// Match reports whether the test with the given test ID, at path (its group names,
+// then its name), is quarantined, and remembers which entries matched. A group that
+// is a file path also matches in its form relative to the working directory.
+func (q *Quarantine) Match(testID string, testPath []string) bool {
+	if q == nil {
+		return false
+	}
+	q.mu.Lock()
+	defer q.mu.Unlock()
+
+	relPath := make([]string, len(testPath))
+	for i, name := range testPath {
+		relPath[i] = relativeToDir(q.workDir, name)
+	}
+	found := false
+	for i, patterns := range q.patterns {
+		if patterns == nil {
+			if testID != "" && q.entries[i] == testID {
+				q.matched[i] = true
+				found = true
+			}
+			continue
+		}
+		if matchQuarantinePath(patterns, testPath) || matchQuarantinePath(patterns, relPath) {
+			q.matched[i] = true
+			found = true
+		}
+	}
+	return found
+}
+
+// Unmatched returns the entries that haven't matched a test, which are likely stale
+func (q *Quarantine) Unmatched() []string {
+	if q == nil {
+		return nil
+	}
+	q.mu.Lock()
+	defer q.mu.Unlock()
+
+	var unmatched []string
+	for i, entry := range q.entries {
+		if !q.matched[i] {
+			unmatched = append(unmatched, entry)
+		}
+	}
+	return unmatched
+}
+
+// matchQuarantinePath matches path against glob patterns one part at a time, "**"
+// matching any number of parts
+func matchQuarantinePath(patterns, parts []string) bool {
+	if len(patterns) == 0 {
+		return len(parts) == 0
+	}
+	if patterns[0] == "**" {
+		for i := 0; i <= len(parts); i++ {
+			if matchQuarantinePath(patterns[1:], parts[i:]) {
+				return true
+			}
+		}
+		return false
+	}
+	if len(parts) == 0 {
+		return false
+	}
+	if ok, _ := path.Match(patterns[0], parts[0]); !ok && patterns[0] != parts[0] {
+		return false
+	}
+	return matchQuarantinePath(patterns[1:], parts[1:])
+}
+
+// SetWorkDir sets the directory the tests run in, which file path groups of the
+// entries are relative to
+func (q *Quarantine) SetWorkDir(dir string) {
+	q.mu.Lock()
+	defer q.mu.Unlock()
+	q.workDir = dir
+}
+
+// relativeToDir returns an absolute file path relative to dir, or the working
+// directory when it's empty, and any other name unchanged
+func relativeToDir(dir, name string) string {
+	if !filepath.IsAbs(name) {
+		return name
+	}
+	cwd := testIDDir(dir)
+	if cwd == "" {
+		return name
+	}
+	rel, err := filepath.Rel(cwd, name)
+	if err != nil || strings.HasPrefix(rel, "..") {
+		return name
+	}
+	return filepath.ToSlash(rel)
+}
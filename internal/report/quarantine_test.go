@@ -0,0 +1,219 @@
+package report
+
+import (
+	"os"
+	"path/filepath"
+	"reflect"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/internal/ipc"
+	"github.com/zk/3pio/internal/logger"
+)
+
+func TestParseQuarantine(t *testing.T) {
+	q, err := ParseQuarantine(`# Known-flaky tests, see #123
+tests = [
+  "my-crate > tests > test_flaky",  # times out on CI
+  'my-crate > net > **',
+  "test_#hash",
+]
+`)
+	if err != nil {
+		t.Fatalf("ParseQuarantine failed: %v", err)
+	}
+	want := []string{"my-crate > tests > test_flaky", "my-crate > net > **", "test_#hash"}
+	if !reflect.DeepEqual(q.entries, want) {
+		t.Errorf("Entries = %q, want %q", q.entries, want)
+	}
+
+	q, err = ParseQuarantine(`tests = ["a > b", "c"]`)
+	if err != nil {
+		t.Fatalf("ParseQuarantine of a single-line array failed: %v", err)
+	}
+	if !reflect.DeepEqual(q.entries, []string{"a > b", "c"}) {
+		t.Errorf("Entries = %q, want [a > b c]", q.entries)
+	}
+
+	q, err = ParseQuarantine("tests = []\n")
+	if err != nil || len(q.entries) != 0 {
+		t.Errorf("Expected an empty quarantine, got %v, %v", q, err)
+	}
+}
+
+func TestParseQuarantine_Errors(t *testing.T) {
+	tests := []struct {
+		content string
+		want    string
+	}{
+		{"", "line 1: expected tests = [...]"},
+		{"flaky = [\"a\"]", "line 1: expected tests = [...]"},
+		{"tests = [\n  \"a\"\n  \"b\"\n]", `line 3: expected , or ] after "a"`},
+		{"tests = [\n  \"a\",\n  b,\n]", `line 3: expected a quoted string, got "b,"`},
+		{"tests = [\n  \"unterminated\n]", "line 2: unterminated string"},
+		{"tests = [\"a > [b\"]", `line 1: invalid pattern "a > [b"`},
+		{"tests = [\"a\"]\nmore = 1", `line 2: unexpected "more = 1" after the tests array`},
+	}
+	for _, tt := range tests {
+		_, err := ParseQuarantine(tt.content)
+		if err == nil || !strings.HasPrefix(err.Error(), tt.want) {
+			t.Errorf("ParseQuarantine(%q) error = %v, want %q", tt.content, err, tt.want)
+		}
+	}
+}
+
+func TestLoadQuarantine(t *testing.T) {
+	path := filepath.Join(t.TempDir(), QuarantineFileName)
+	if err := os.WriteFile(path, []byte("tests = [\n  oops\n]\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	_, err := LoadQuarantine(path)
+	if err == nil || !strings.Contains(err.Error(), path+": line 2:") {
+		t.Errorf("Expected an error naming the file and line, got %v", err)
+	}
+
+	if _, err := LoadQuarantine(filepath.Join(t.TempDir(), "missing.toml")); !os.IsNotExist(err) {
+		t.Errorf("Expected a not-exist error for a missing file, got %v", err)
+	}
+}
+
+func TestQuarantine_MatchHierarchicalPaths(t *testing.T) {
+	cwd, err := os.Getwd()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if resolved, err := filepath.EvalSymlinks(cwd); err == nil {
+		cwd = resolved
+	}
+
+	tests := []struct {
+		entry string
+		path  []string
+		want  bool
+	}{
+		{"my-crate > tests > test_flaky", []string{"my-crate", "tests", "test_flaky"}, true},
+		{"my-crate > tests > test_flaky", []string{"my-crate", "tests", "test_flaky_too"}, false},
+		{"my-crate > tests > test_flaky", []string{"my-crate", "test_flaky"}, false},
+		{"my-crate > tests > test_net_*", []string{"my-crate", "tests", "test_net_timeout"}, true},
+		{"my-crate > tests > test_net_*", []string{"my-crate", "other", "test_net_timeout"}, false},
+		{"my-crate > * > test_flaky", []string{"my-crate", "tests", "test_flaky"}, true},
+		{"my-crate > * > test_flaky", []string{"my-crate", "a", "b", "test_flaky"}, false},
+		{"** > test_flaky", []string{"my-crate", "a", "b", "test_flaky"}, true},
+		{"** > test_flaky", []string{"test_flaky"}, true},
+		{"my-crate > **", []string{"my-crate", "net", "test_dns"}, true},
+		{"my-crate > **", []string{"other-crate", "net", "test_dns"}, false},
+		{"my-crate > ** > test_?", []string{"my-crate", "net", "tls", "test_a"}, true},
+		{"my-crate > ** > test_?", []string{"my-crate", "net", "tls", "test_ab"}, false},
+		{"suite > adds [1, 2]", []string{"suite", "adds [1, 2]"}, true},
+		// Files are matched by their path relative to the working directory
+		{"src/net.test.js > retries", []string{filepath.Join(cwd, "src", "net.test.js"), "retries"}, true},
+		{"src/*.test.js > retries", []string{filepath.Join(cwd, "src", "net.test.js"), "retries"}, true},
+		{"net.test.js > retries", []string{filepath.Join(cwd, "src", "net.test.js"), "retries"}, false},
+	}
+	for _, tt := range tests {
+		q := &Quarantine{}
+		if err := q.add(tt.entry); err != nil {
+			t.Fatalf("add(%q) failed: %v", tt.entry, err)
+		}
+		if got := q.Match("", tt.path); got != tt.want {
+			t.Errorf("%q matching %q = %v, want %v", tt.entry, tt.path, got, tt.want)
+		}
+	}
+}
+
+func TestQuarantine_Unmatched(t *testing.T) {
+	q, err := ParseQuarantine(`tests = ["crate > test_a", "crate > test_b*", "crate > gone"]`)
+	if err != nil {
+		t.Fatal(err)
+	}
+	q.Match("", []string{"crate", "test_a"})
+	q.Match("", []string{"crate", "test_b2"})
+	q.Match("", []string{"crate", "test_c"})
+	if got := q.Unmatched(); !reflect.DeepEqual(got, []string{"crate > gone"}) {
+		t.Errorf("Unmatched = %q, want [crate > gone]", got)
+	}
+
+	var none *Quarantine
+	if none.Match("", []string{"crate", "test_a"}) || none.Unmatched() != nil {
+		t.Error("A nil quarantine should match nothing")
+	}
+}
+
+func TestGroupManager_QuarantinedFailure(t *testing.T) {
+	tmpDir := t.TempDir()
+	log, _ := logger.NewFileLogger()
+	t.Cleanup(func() { _ = log.Close() })
+	gm := NewGroupManager(tmpDir, "", log)
+	q, err := ParseQuarantine(`tests = ["my-crate > tests > test_flaky", "my-crate > tests > test_fixed"]`)
+	if err != nil {
+		t.Fatal(err)
+	}
+	gm.SetQuarantine(q)
+
+	parents := []string{"my-crate", "tests"}
+	for _, tc := range []ipc.TestCasePayload{
+		{TestName: "test_flaky", Status: "FAIL", Error: &ipc.TestError{Message: "connection reset"}},
+		{TestName: "test_fixed", Status: "PASS"},
+		{TestName: "test_ok", Status: "PASS"},
+	} {
+		tc.ParentNames = parents
+		if err := gm.ProcessTestCase(ipc.GroupTestCaseEvent{EventType: string(ipc.EventTypeTestCase), Payload: tc}); err != nil {
+			t.Fatalf("ProcessTestCase failed: %v", err)
+		}
+	}
+	// The runner reports the group as failed because of the quarantined test
+	err = gm.ProcessGroupResult(ipc.GroupResultEvent{
+		EventType: string(ipc.EventTypeGroupResult),
+		Payload: ipc.GroupResultPayload{
+			GroupName:   "tests",
+			ParentNames: []string{"my-crate"},
+			Status:      "FAIL",
+			Totals:      ipc.GroupTotals{Passed: 2, Failed: 1, Total: 3},
+		},
+	})
+	if err != nil {
+		t.Fatalf("ProcessGroupResult failed: %v", err)
+	}
+
+	group, _ := gm.GetGroup(GenerateGroupIDFromPath(parents))
+	if group.Status != TestStatusPass {
+		t.Errorf("Group status = %v, want PASS", group.Status)
+	}
+	if group.Stats.FailedTests != 0 || group.Stats.QuarantinedFailedTests != 1 {
+		t.Errorf("Failed = %d, quarantined = %d, want 0 and 1", group.Stats.FailedTests, group.Stats.QuarantinedFailedTests)
+	}
+	if group.TestCases[0].Status != TestStatusQuarantinedFail || !group.TestCases[0].Quarantined {
+		t.Errorf("test_flaky = %v (quarantined %v), want QUARANTINED_FAIL", group.TestCases[0].Status, group.TestCases[0].Quarantined)
+	}
+
+	content := gm.formatGroupReport(group)
+	for _, want := range []string{
+		"- Group tests failed while quarantined: 1\n",
+		"- ✕ test_flaky [QUARANTINED]\n```\nconnection reset\n```\n",
+		"- ✓ test_fixed [QUARANTINED]\n  > Passed while quarantined; it may be removable from the quarantine list\n",
+		"- ✓ test_ok\n",
+	} {
+		if !strings.Contains(content, want) {
+			t.Errorf("Report missing %q:\n%s", want, content)
+		}
+	}
+
+	// Another failure in the group fails it again
+	err = gm.ProcessTestCase(ipc.GroupTestCaseEvent{
+		EventType: string(ipc.EventTypeTestCase),
+		Payload:   ipc.TestCasePayload{TestName: "test_ok", ParentNames: parents, Status: "FAIL"},
+	})
+	if err != nil {
+		t.Fatal(err)
+	}
+	err = gm.ProcessGroupResult(ipc.GroupResultEvent{
+		EventType: string(ipc.EventTypeGroupResult),
+		Payload:   ipc.GroupResultPayload{GroupName: "tests", ParentNames: []string{"my-crate"}, Status: "FAIL"},
+	})
+	if err != nil {
+		t.Fatal(err)
+	}
+	if group, _ := gm.GetGroup(GenerateGroupIDFromPath(parents)); group.Status != TestStatusFail {
+		t.Errorf("Group status = %v, want FAIL", group.Status)
+	}
+}
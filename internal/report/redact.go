@@ -0,0 +1,266 @@
+package report
+
+import (
+	"bufio"
+	"bytes"
+	"encoding/json"
+	"encoding/xml"
+	"fmt"
+	"io"
+	"net/url"
+	"os"
+	"path"
+	"path/filepath"
+	"regexp"
+	"sort"
+	"strings"
+)
+
+// RedactFileName is the file in the .3pio directory whose patterns are redacted on
+// top of DefaultRedactPatterns
+const RedactFileName = "redact.toml"
+
+// DefaultRedactPatterns are the names of the environment variables whose values
+// --redact removes from the reports. Each is a glob matched against the whole name,
+// ignoring case.
+var DefaultRedactPatterns = []string{"*TOKEN*", "*SECRET*", "*PASSWORD*", "AWS_*"}
+
+// minRedactLength is the shortest value that is redacted. Shorter ones, like "1" or
+// "true", are too likely to also be ordinary text.
+const minRedactLength = 6
+
+// percentEscapeRegex matches the %XX escapes of a URL-encoded value
+var percentEscapeRegex = regexp.MustCompile(`%[0-9A-F]{2}`)
+
+// Redactor replaces the values of secret environment variables with
+// «redacted:NAME», whether they appear as is, URL-encoded, or escaped inside a JSON
+// or XML string. A nil Redactor leaves text unchanged.
+type Redactor struct {
+	replacer *strings.Replacer
+	names    []string
+}
+
+// NewRedactor returns a Redactor for the variables of env ("NAME=value", as from
+// os.Environ) whose names match one of patterns
+func NewRedactor(env []string, patterns []string) *Redactor {
+	secrets := make(map[string]string) // Variable name -> value
+	for _, kv := range env {
+		name, value, ok := strings.Cut(kv, "=")
+		if !ok || len(value) < minRedactLength || !matchRedactPattern(patterns, name) {
+			continue
+		}
+		secrets[name] = value
+	}
+
+	r := &Redactor{}
+	for name := range secrets {
+		r.names = append(r.names, name)
+	}
+	sort.Strings(r.names)
+
+	// Each form goes to the first variable with it, so the output doesn't depend on
+	// map order when two variables hold the same value
+	replacements := make(map[string]string)
+	var forms []string
+	for _, name := range r.names {
+		for _, form := range redactForms(secrets[name]) {
+			if _, taken := replacements[form]; !taken {
+				replacements[form] = "«redacted:" + name + "»"
+				forms = append(forms, form)
+			}
+		}
+	}
+	if len(forms) == 0 {
+		return r
+	}
+
+	// strings.Replacer tries the pairs in order, so a value that contains another is
+	// replaced whole
+	sort.SliceStable(forms, func(i, j int) bool { return len(forms[i]) > len(forms[j]) })
+	pairs := make([]string, 0, 2*len(forms))
+	for _, form := range forms {
+		pairs = append(pairs, form, replacements[form])
+	}
+	r.replacer = strings.NewReplacer(pairs...)
+	return r
+}
+
+// matchRedactPattern reports whether an environment variable name matches one of
+// the patterns
+func matchRedactPattern(patterns []string, name string) bool {
+	for _, pattern := range patterns {
+		if ok, _ := path.Match(strings.ToUpper(pattern), strings.ToUpper(name)); ok {
+			return true
+		}
+	}
+	return false
+}
+
+// redactForms returns the ways value may be written into output: as is, escaped in
+// a JSON string (with and without HTML escaping) or in XML, and URL-encoded as a
+// query value or a path, with upper or lowercase hex digits
+func redactForms(value string) []string {
+	forms := []string{value}
+	if data, err := json.Marshal(value); err == nil {
+		forms = append(forms, string(data[1:len(data)-1]))
+	}
+	var buf bytes.Buffer
+	encoder := json.NewEncoder(&buf)
+	encoder.SetEscapeHTML(false)
+	if err := encoder.Encode(value); err == nil {
+		data := bytes.TrimSuffix(buf.Bytes(), []byte("\n"))
+		forms = append(forms, string(data[1:len(data)-1]))
+	}
+	buf.Reset()
+	if err := xml.EscapeText(&buf, []byte(value)); err == nil {
+		forms = append(forms, buf.String())
+	}
+	for _, escaped := range []string{url.QueryEscape(value), url.PathEscape(value)} {
+		forms = append(forms, escaped, percentEscapeRegex.ReplaceAllStringFunc(escaped, strings.ToLower))
+	}
+
+	seen := make(map[string]bool)
+	unique := forms[:0]
+	for _, form := range forms {
+		if !seen[form] {
+			seen[form] = true
+			unique = append(unique, form)
+		}
+	}
+	return unique
+}
+
+// Redact returns s with the secret values replaced
+func (r *Redactor) Redact(s string) string {
+	if r == nil || r.replacer == nil {
+		return s
+	}
+	return r.replacer.Replace(s)
+}
+
+// RedactBytes returns data with the secret values replaced
+func (r *Redactor) RedactBytes(data []byte) []byte {
+	if r == nil || r.replacer == nil {
+		return data
+	}
+	return []byte(r.replacer.Replace(string(data)))
+}
+
+// Names returns the names of the variables whose values are redacted, sorted
+func (r *Redactor) Names() []string {
+	if r == nil {
+		return nil
+	}
+	return r.names
+}
+
+// RedactFile replaces the secret values in a log file (--redact-raw). It goes line by
+// line, so logs larger than memory work, and rewrites the file in place rather than
+// renaming over it, so handles still open on it don't get in the way.
+func (r *Redactor) RedactFile(filePath string) error {
+	if r == nil || r.replacer == nil {
+		return nil
+	}
+	file, err := os.OpenFile(filePath, os.O_RDWR, 0)
+	if err != nil {
+		return err
+	}
+	defer func() { _ = file.Close() }()
+
+	tmp, err := os.CreateTemp(filepath.Dir(filePath), "."+filepath.Base(filePath)+".*.tmp")
+	if err != nil {
+		return fmt.Errorf("failed to create temporary file for %s: %w", filepath.Base(filePath), err)
+	}
+	defer func() {
+		_ = tmp.Close()
+		_ = os.Remove(tmp.Name())
+	}()
+
+	reader := bufio.NewReader(file)
+	writer := bufio.NewWriter(tmp)
+	changed := false
+	for {
+		line, err := reader.ReadString('\n')
+		if redacted := r.replacer.Replace(line); redacted != line {
+			line = redacted
+			changed = true
+		}
+		if _, werr := writer.WriteString(line); werr != nil {
+			return werr
+		}
+		if err == io.EOF {
+			break
+		}
+		if err != nil {
+			return err
+		}
+	}
+	if !changed {
+		return nil
+	}
+	if err := writer.Flush(); err != nil {
+		return err
+	}
+
+	if _, err := tmp.Seek(0, io.SeekStart); err != nil {
+		return err
+	}
+	if _, err := file.Seek(0, io.SeekStart); err != nil {
+		return err
+	}
+	if err := file.Truncate(0); err != nil {
+		return err
+	}
+	_, err = io.Copy(file, tmp)
+	return err
+}
+
+// LoadRedactConfig reads the patterns of a redact.toml file
+func LoadRedactConfig(configPath string) ([]string, error) {
+	data, err := os.ReadFile(configPath)
+	if err != nil {
+		return nil, err
+	}
+	patterns, err := ParseRedactConfig(string(data))
+	if err != nil {
+		return nil, fmt.Errorf("%s: %w", configPath, err)
+	}
+	return patterns, nil
+}
+
+// ParseRedactConfig parses a redact.toml file: comments and a single "env" array of
+// the environment variable name patterns to redact, on top of the default ones.
+//
+//	env = [
+//	  "*API_KEY*",
+//	  "DATABASE_URL",
+//	]
+func ParseRedactConfig(content string) ([]string, error) {
+	lines := strings.Split(content, "\n")
+	for i, line := range lines {
+		lines[i] = stripTOMLComment(line)
+	}
+	s := &tomlScanner{input: strings.Join(lines, "\n")}
+
+	if !s.consume("env") || !s.consume("=") || !s.consume("[") {
+		return nil, s.errorf("expected env = [...]")
+	}
+	var patterns []string
+	for !s.consume("]") {
+		pattern, err := s.string()
+		if err != nil {
+			return nil, err
+		}
+		if _, err := path.Match(pattern, ""); err != nil {
+			return nil, s.errorf("invalid pattern %q: %v", pattern, err)
+		}
+		patterns = append(patterns, pattern)
+		if !s.consume(",") && !s.peek("]") {
+			return nil, s.errorf("expected , or ] after %q", pattern)
+		}
+	}
+	if s.skipSpace(); s.pos < len(s.input) {
+		return nil, s.errorf("unexpected %q after the env array", s.rest())
+	}
+	return patterns, nil
+}
@@ -0,0 +1,154 @@
+package report
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/internal/ipc"
+)
+
+func TestRedactor_Redact(t *testing.T) {
+	env := []string{
+		"GITHUB_TOKEN=ghp_abc/def+ghi\"jkl",
+		"AWS_SECRET_ACCESS_KEY=wJalrXUtnFEMI",
+		"db_password=hunter2hunter2",
+		"DEPLOY_TOKEN=ghp_abc", // Shorter than GITHUB_TOKEN's value, which contains it
+		"PATH=/usr/bin:/bin",
+		"API_TOKEN=1", // Too short to redact
+	}
+	r := NewRedactor(env, DefaultRedactPatterns)
+
+	if names := strings.Join(r.Names(), ","); names != "AWS_SECRET_ACCESS_KEY,DEPLOY_TOKEN,GITHUB_TOKEN,db_password" {
+		t.Errorf("Expected the matching variables with long enough values, got %s", names)
+	}
+	testCases := []struct {
+		name     string
+		input    string
+		expected string
+	}{
+		{"as is", `token ghp_abc/def+ghi"jkl rejected`, "token «redacted:GITHUB_TOKEN» rejected"},
+		{"query escaped", "GET /items?token=ghp_abc%2Fdef%2Bghi%22jkl", "GET /items?token=«redacted:GITHUB_TOKEN»"},
+		{"lowercase escapes", "token=ghp_abc%2fdef%2bghi%22jkl", "token=«redacted:GITHUB_TOKEN»"},
+		{"path escaped", "/tokens/ghp_abc%2Fdef+ghi%22jkl", "/tokens/«redacted:GITHUB_TOKEN»"},
+		{"JSON string", `{"authorization":"ghp_abc/def+ghi\"jkl"}`, `{"authorization":"«redacted:GITHUB_TOKEN»"}`},
+		{"contained value", "ghp_abc and ghp_abc/def+ghi\"jkl", "«redacted:DEPLOY_TOKEN» and «redacted:GITHUB_TOKEN»"},
+		{"case of the name ignored", "login hunter2hunter2", "login «redacted:db_password»"},
+		{"AWS prefix", "key=wJalrXUtnFEMI", "key=«redacted:AWS_SECRET_ACCESS_KEY»"},
+		{"other variables kept", "PATH=/usr/bin:/bin, 1 test", "PATH=/usr/bin:/bin, 1 test"},
+	}
+	for _, tc := range testCases {
+		t.Run(tc.name, func(t *testing.T) {
+			if got := r.Redact(tc.input); got != tc.expected {
+				t.Errorf("Expected %q, got %q", tc.expected, got)
+			}
+		})
+	}
+
+	var none *Redactor
+	if got := none.Redact("ghp_abc"); got != "ghp_abc" {
+		t.Errorf("Expected a nil redactor to keep the text, got %q", got)
+	}
+}
+
+func TestRedactor_RedactFile(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "output.log")
+	content := "=== RUN TestLogin\nusing s3cr3t-value\n--- FAIL: TestLogin\nno newline at the end s3cr3t-value"
+	if err := os.WriteFile(path, []byte(content), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	r := NewRedactor([]string{"MY_SECRET=s3cr3t-value"}, DefaultRedactPatterns)
+	if err := r.RedactFile(path); err != nil {
+		t.Fatalf("RedactFile failed: %v", err)
+	}
+	data, err := os.ReadFile(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	expected := "=== RUN TestLogin\nusing «redacted:MY_SECRET»\n--- FAIL: TestLogin\nno newline at the end «redacted:MY_SECRET»"
+	if string(data) != expected {
+		t.Errorf("Expected %q, got %q", expected, data)
+	}
+}
+
+func TestParseRedactConfig(t *testing.T) {
+	patterns, err := ParseRedactConfig(`# Also redact these
+env = [
+  "*API_KEY*",
+  'DATABASE_URL', # a literal string
+]
+`)
+	if err != nil {
+		t.Fatalf("ParseRedactConfig failed: %v", err)
+	}
+	if strings.Join(patterns, ",") != "*API_KEY*,DATABASE_URL" {
+		t.Errorf("Expected the two patterns, got %v", patterns)
+	}
+
+	for _, content := range []string{
+		`tests = ["*TOKEN*"]`,
+		`env = ["[unclosed"]`,
+		`env = ["*KEY*" "*PASS*"]`,
+	} {
+		if _, err := ParseRedactConfig(content); err == nil {
+			t.Errorf("Expected an error for %q", content)
+		}
+	}
+}
+
+func TestManager_Redact(t *testing.T) {
+	tempDir := t.TempDir()
+
+	manager, err := NewManager(tempDir, nil, &mockLogger{}, "cargo test", "cargo test")
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	manager.SetRedactor(NewRedactor([]string{"FAKE_SERVICE_TOKEN=tok_0123456789"}, DefaultRedactPatterns))
+	if err := manager.Initialize("cargo test"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+	payload := ipc.TestCasePayload{
+		TestName:    "test_login",
+		ParentNames: []string{"my-crate", "tests"},
+		Status:      "FAIL",
+		Stdout:      "using tok_0123456789\n",
+		Error:       &ipc.TestError{Message: "unauthorized: tok_0123456789"},
+	}
+	if err := manager.HandleEvent(ipc.GroupTestCaseEvent{EventType: "testCase", Payload: payload}); err != nil {
+		t.Fatalf("HandleEvent failed: %v", err)
+	}
+	if err := manager.Finalize(101, ""); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+
+	var written int
+	err = filepath.WalkDir(tempDir, func(path string, d os.DirEntry, err error) error {
+		if err != nil || d.IsDir() {
+			return err
+		}
+		content, err := os.ReadFile(path)
+		if err != nil {
+			return err
+		}
+		if strings.Contains(string(content), "tok_0123456789") {
+			t.Errorf("Expected the token to be redacted from %s, got:\n%s", path, content)
+		}
+		if strings.Contains(string(content), "«redacted:FAKE_SERVICE_TOKEN»") {
+			written++
+		}
+		return nil
+	})
+	if err != nil {
+		t.Fatal(err)
+	}
+	// test-run.md, summary.json, the group report and the test's log
+	if written < 4 {
+		t.Errorf("Expected the placeholder in at least 4 files, got %d", written)
+	}
+	testRun, _ := os.ReadFile(filepath.Join(tempDir, "test-run.md"))
+	if !strings.Contains(string(testRun), "- Values of `FAKE_SERVICE_TOKEN` redacted\n") {
+		t.Errorf("Expected the redacted variables in the header, got:\n%s", testRun)
+	}
+}
@@ -0,0 +1,112 @@
+package report
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+)
+
+// snippetContext is the number of lines shown above and below a failing line
+const snippetContext = 3
+
+// maxSnippetFileSize skips snippets of files too large to be hand-written source
+const maxSnippetFileSize = 1 << 20
+
+// snippetLanguages maps source file extensions to the info string of the code fence
+var snippetLanguages = map[string]string{
+	".rs":  "rust",
+	".go":  "go",
+	".py":  "python",
+	".js":  "javascript",
+	".jsx": "jsx",
+	".mjs": "javascript",
+	".cjs": "javascript",
+	".ts":  "typescript",
+	".tsx": "tsx",
+}
+
+// SetSourceRoot sets the directory relative failure locations are resolved against
+// to embed a source snippet under each failure. Empty (the default) disables snippets.
+func (gm *GroupManager) SetSourceRoot(dir string) {
+	gm.mu.Lock()
+	defer gm.mu.Unlock()
+	gm.sourceRoot = dir
+}
+
+// sourceSnippet returns the lines around a failure's location, each numbered and the
+// failing one marked with ">", or "" when the failure has no location in the project,
+// the file can't be read or is too large, or the line no longer exists because the
+// file changed during the run. Caller must hold gm.mu.
+func (gm *GroupManager) sourceSnippet(testErr *TestError) string {
+	if gm.sourceRoot == "" || testErr.External {
+		return ""
+	}
+	file, line := testErr.File, testErr.Line
+	if file == "" || line <= 0 {
+		var ok bool
+		if file, line, _, ok = parseLocation(testErr.Location); !ok {
+			return ""
+		}
+	}
+
+	path := findSourceFile(gm.sourceRoot, filepath.FromSlash(file))
+	if path == "" {
+		return ""
+	}
+	info, err := os.Stat(path)
+	if err != nil || !info.Mode().IsRegular() || info.Size() > maxSnippetFileSize {
+		return ""
+	}
+	content, err := os.ReadFile(path)
+	if err != nil {
+		return ""
+	}
+
+	lines := strings.Split(strings.TrimSuffix(strings.ReplaceAll(string(content), "\r\n", "\n"), "\n"), "\n")
+	if line > len(lines) {
+		return ""
+	}
+	first := max(line-snippetContext, 1)
+	last := min(line+snippetContext, len(lines))
+	width := len(fmt.Sprint(last))
+
+	var sb strings.Builder
+	for n := first; n <= last; n++ {
+		marker := " "
+		if n == line {
+			marker = ">"
+		}
+		sb.WriteString(strings.TrimRight(fmt.Sprintf("%s %*d | %s", marker, width, n, lines[n-1]), " \t") + "\n")
+	}
+	return sb.String()
+}
+
+// findSourceFile looks a relative path up in root and then in root's parents, since
+// cargo names the files of a workspace member relative to the workspace root even
+// when it runs inside the member. It returns "" when there is no such file.
+func findSourceFile(root, file string) string {
+	if filepath.IsAbs(file) {
+		return file
+	}
+	for dir := root; ; {
+		path := filepath.Join(dir, file)
+		if _, err := os.Stat(path); err == nil {
+			return path
+		}
+		parent := filepath.Dir(dir)
+		if parent == dir {
+			return ""
+		}
+		dir = parent
+	}
+}
+
+// formatSnippet renders a source snippet as a fenced code block
+func formatSnippet(testErr *TestError) string {
+	file := testErr.File
+	if file == "" {
+		file, _, _, _ = parseLocation(testErr.Location)
+	}
+	return "```" + snippetLanguages[strings.ToLower(filepath.Ext(file))] + "\n" + testErr.Snippet + "```\n"
+}
@@ -0,0 +1,106 @@
+package report
+
+import (
+	"encoding/json"
+	"io"
+	"path/filepath"
+	"time"
+)
+
+// StreamEventType is what a line of the event stream (--output json) reports
+type StreamEventType string
+
+const (
+	StreamRunStarted    StreamEventType = "run_started"    // Before the test command starts
+	StreamGroupStarted  StreamEventType = "group_started"  // A group started running
+	StreamTestFinished  StreamEventType = "test_finished"  // A test finished, again when --retries re-ran it
+	StreamGroupFinished StreamEventType = "group_finished" // A group finished, with its test cases
+	StreamRunFinished   StreamEventType = "run_finished"   // Once the reports are written, with the run's summary
+)
+
+// StreamEvent is a line of the event stream: one JSON object per line, as the run
+// goes. Groups, test cases and the summary have the schema of summary.json, so a
+// test's id is the same in both.
+type StreamEvent struct {
+	Type          StreamEventType  `json:"type"`
+	Time          time.Time        `json:"time"`                      // RFC 3339, UTC
+	SchemaVersion int              `json:"schema_version,omitempty"`  // run_started: SummarySchemaVersion
+	RunID         string           `json:"run_id,omitempty"`          // run_started
+	RunDir        string           `json:"run_dir,omitempty"`         // run_started
+	Command       string           `json:"command,omitempty"`         // run_started
+	Runner        string           `json:"detected_runner,omitempty"` // run_started
+	GroupID       string           `json:"group_id,omitempty"`        // test_finished: the test's group
+	Group         *SummaryGroup    `json:"group,omitempty"`           // group_started, group_finished
+	Test          *SummaryTestCase `json:"test,omitempty"`            // test_finished
+	Summary       *RunSummary      `json:"summary,omitempty"`         // run_finished: the content of summary.json
+}
+
+// SetEventStream has the run's events written to w as JSON lines (--output json)
+func (m *Manager) SetEventStream(w io.Writer) {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	m.eventStream = w
+}
+
+// streamEvent writes an event to the event stream, if there is one
+func (m *Manager) streamEvent(event StreamEvent) {
+	if m.eventStream == nil {
+		return
+	}
+	event.Time = time.Now().UTC()
+	data, err := json.Marshal(event)
+	if err != nil {
+		m.logger.Error("Failed to encode %s event: %v", event.Type, err)
+		return
+	}
+	if _, err := m.eventStream.Write(append(m.redactor.RedactBytes(data), '\n')); err != nil {
+		m.logger.Error("Failed to write %s event: %v", event.Type, err)
+	}
+}
+
+// streamRunStarted writes the run_started event
+func (m *Manager) streamRunStarted(args string) {
+	m.streamEvent(StreamEvent{
+		Type:          StreamRunStarted,
+		SchemaVersion: SummarySchemaVersion,
+		RunID:         filepath.Base(m.runDir),
+		RunDir:        m.runDir,
+		Command:       args,
+		Runner:        m.detectedRunner,
+	})
+}
+
+// streamGroup writes a group_started or group_finished event for the group at path
+func (m *Manager) streamGroup(eventType StreamEventType, path []string) {
+	if m.eventStream == nil || m.groupManager == nil {
+		return
+	}
+	group, ok := m.groupManager.findGroup(path)
+	if !ok {
+		return
+	}
+	entry := summaryGroup(group, m.runDir)
+	if eventType == StreamGroupStarted {
+		entry.TestCases = []SummaryTestCase{}
+	}
+	m.streamEvent(StreamEvent{Type: eventType, Group: &entry})
+}
+
+// streamTestCase writes a test_finished event for a test of the group at path. Tests
+// listed before they run (PENDING) haven't finished and are left out.
+func (m *Manager) streamTestCase(path []string, name string) {
+	if m.eventStream == nil || m.groupManager == nil {
+		return
+	}
+	group, tc, ok := m.groupManager.findTestCase(path, name)
+	if !ok || tc.Status == TestStatusPending || tc.Status == TestStatusRunning {
+		return
+	}
+	testCase := summaryTestCase(&tc, relativeReportPath(group, m.runDir))
+	m.streamEvent(StreamEvent{Type: StreamTestFinished, GroupID: group.ID, Test: &testCase})
+}
+
+// streamRunFinished writes the run_finished event with the run's summary
+func (m *Manager) streamRunFinished(summary RunSummary) {
+	m.streamEvent(StreamEvent{Type: StreamRunFinished, Summary: &summary})
+}
@@ -0,0 +1,93 @@
+package report
+
+import (
+	"bufio"
+	"bytes"
+	"encoding/json"
+	"testing"
+
+	"github.com/zk/3pio/internal/ipc"
+)
+
+func TestManager_EventStream(t *testing.T) {
+	tempDir := t.TempDir()
+	manager, err := NewManager(tempDir, nil, &mockLogger{}, "cargo test", "cargo test")
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	var stream bytes.Buffer
+	manager.SetEventStream(&stream)
+	if err := manager.Initialize("cargo test"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+
+	events := []ipc.Event{
+		ipc.GroupStartEvent{EventType: "testGroupStart", Payload: ipc.GroupStartPayload{GroupName: "tests", ParentNames: []string{"my-crate"}}},
+		ipc.GroupTestCaseEvent{EventType: "testCase", Payload: ipc.TestCasePayload{TestName: "test_add", ParentNames: []string{"my-crate", "tests"}, Status: "PENDING"}},
+		ipc.GroupTestCaseEvent{EventType: "testCase", Payload: ipc.TestCasePayload{TestName: "test_add", ParentNames: []string{"my-crate", "tests"}, Status: "PASS", Duration: 12}},
+		ipc.GroupTestCaseEvent{EventType: "testCase", Payload: ipc.TestCasePayload{
+			TestName:    "test_divide",
+			ParentNames: []string{"my-crate", "tests"},
+			Status:      "FAIL",
+			Error:       &ipc.TestError{Message: "attempt to divide by zero"},
+		}},
+		ipc.GroupResultEvent{EventType: "testGroupResult", Payload: ipc.GroupResultPayload{GroupName: "tests", ParentNames: []string{"my-crate"}, Status: "FAIL"}},
+	}
+	for _, event := range events {
+		if err := manager.HandleEvent(event); err != nil {
+			t.Fatalf("HandleEvent failed: %v", err)
+		}
+	}
+	if err := manager.Finalize(101, ""); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+
+	var lines []StreamEvent
+	scanner := bufio.NewScanner(&stream)
+	for scanner.Scan() {
+		var event StreamEvent
+		if err := json.Unmarshal(scanner.Bytes(), &event); err != nil {
+			t.Fatalf("Failed to parse %q: %v", scanner.Text(), err)
+		}
+		lines = append(lines, event)
+	}
+
+	// The PENDING listing hasn't finished the test and is left out
+	wantTypes := []StreamEventType{StreamRunStarted, StreamGroupStarted, StreamTestFinished, StreamTestFinished, StreamGroupFinished, StreamRunFinished}
+	if len(lines) != len(wantTypes) {
+		t.Fatalf("Expected %d events, got %+v", len(wantTypes), lines)
+	}
+	for i, want := range wantTypes {
+		if lines[i].Type != want {
+			t.Errorf("Expected event %d to be %s, got %s", i, want, lines[i].Type)
+		}
+		if lines[i].Time.IsZero() {
+			t.Errorf("Expected event %d to have a time", i)
+		}
+	}
+
+	failed := lines[3]
+	if failed.Test == nil || failed.Test.Name != "test_divide" || failed.Test.Status != "FAIL" || failed.Test.FailureMessage != "attempt to divide by zero" {
+		t.Errorf("Expected test_divide to finish with its failure, got %+v", failed.Test)
+	}
+
+	// IDs and counts match summary.json
+	summary, err := ReadSummary(tempDir)
+	if err != nil {
+		t.Fatalf("Failed to read summary: %v", err)
+	}
+	if started := lines[0]; started.RunID != summary.RunID || started.Command != "cargo test" || started.SchemaVersion != SummarySchemaVersion {
+		t.Errorf("Unexpected run_started event: %+v", started)
+	}
+	finished := lines[len(lines)-1].Summary
+	if finished == nil || finished.Totals != summary.Totals || len(finished.Groups) != len(summary.Groups) {
+		t.Fatalf("Expected run_finished to carry summary.json, got %+v", finished)
+	}
+	group := lines[4].Group
+	if group == nil || group.ID != summary.Groups[1].ID || len(group.TestCases) != 2 {
+		t.Errorf("Expected group_finished for %s with both tests, got %+v", summary.Groups[1].ID, group)
+	}
+	if failed.GroupID != summary.Groups[1].ID || failed.Test.ID != summary.Groups[1].TestCases[1].ID {
+		t.Errorf("Expected the test's IDs of summary.json, got %s in %s", failed.Test.ID, failed.GroupID)
+	}
+}
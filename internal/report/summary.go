@@ -0,0 +1,263 @@
+package report
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+	"time"
+
+	"github.com/zk/3pio/internal/coverage"
+	"github.com/zk/3pio/internal/runinfo"
+)
+
+// SummaryFileName is the machine-readable summary written next to test-run.md
+const SummaryFileName = "summary.json"
+
+// SummarySchemaVersion is bumped whenever a field of summary.json is renamed,
+// removed or changes meaning. Adding a field doesn't bump it.
+const SummarySchemaVersion = 2
+
+// RunSummary is the schema of summary.json
+type RunSummary struct {
+	SchemaVersion int               `json:"schema_version"`
+	RunID         string            `json:"run_id"`
+	Command       string            `json:"command"`            // Test command as given to 3pio
+	Runner        string            `json:"detected_runner"`    // e.g., "cargo test", "vitest"
+	Cwd           string            `json:"cwd"`                // Directory the command ran in
+	StartTime     time.Time         `json:"start_time"`         // RFC 3339, UTC
+	EndTime       time.Time         `json:"end_time"`           // RFC 3339, UTC
+	ExitCode      int               `json:"exit_code"`          // Exit code 3pio exits with
+	Status        string            `json:"status"`             // COMPLETED, ERRORED or INTERRUPTED, as in test-run.md
+	ExitReason    ExitReason        `json:"exit_reason"`        // Why the run ended, e.g. "ok", "tests_failed" or "build_failed"
+	RerunOf       string            `json:"rerun_of,omitempty"` // Run ID the failed tests came from (--rerun-failed)
+	Shard         *runinfo.Shard    `json:"shard,omitempty"`    // Part of a sharded test suite the run covers, e.g. jest --shard=2/4
+	Cargo         *runinfo.Cargo    `json:"cargo,omitempty"`    // Profile, features and target of a cargo run, which 3pio diff compares
+	Coverage      *coverage.Summary `json:"coverage,omitempty"` // Line and function coverage of a cargo llvm-cov run, which 3pio diff compares
+	Totals        SummaryCounts     `json:"totals"`
+	Groups        []SummaryGroup    `json:"groups"`
+	Merge         *SummaryMerge     `json:"merge,omitempty"`          // Set when 3pio merge combined the runs of shards
+	ResultsSource string            `json:"results_source,omitempty"` // Files the results were read from once the command exited, e.g. "junit_xml"
+}
+
+// SummaryCounts holds the aggregate test counts of the run
+type SummaryCounts struct {
+	Total            int `json:"total"` // Tests, not counting benchmarks
+	Passed           int `json:"passed"`
+	Failed           int `json:"failed"`
+	Skipped          int `json:"skipped"`
+	Quarantined      int `json:"quarantined"` // Quarantined tests that failed, not counted in failed
+	Flaky            int `json:"flaky"`       // Tests that passed when retried, not counted in failed
+	Errors           int `json:"errors"`      // Tests whose setup or teardown failed, not counted in failed
+	Benchmarks       int `json:"benchmarks"`
+	CollectionErrors int `json:"collection_errors"` // Test files that failed to collect, each an ERROR group
+}
+
+// SummaryGroup is one test group (file, crate, module, describe block, ...) with its
+// own test cases. Groups are listed depth-first, so subgroups follow their parent.
+type SummaryGroup struct {
+	ID          string            `json:"id"`
+	Name        string            `json:"name"`
+	Path        []string          `json:"path"` // Names from the root group down to this one
+	Status      string            `json:"status"`
+	DurationMs  float64           `json:"duration_ms"`
+	Report      string            `json:"report"`                 // Detail report, relative to the run directory
+	Error       string            `json:"error,omitempty"`        // Group-level failure, e.g. a setup error
+	FailureKind string            `json:"failure_kind,omitempty"` // BUILD_ERROR or HARNESS_ERROR, with Error
+	SkipReason  string            `json:"skip_reason,omitempty"`  // Why a SKIP group didn't run, e.g. "no_test_files"
+	TestCases   []SummaryTestCase `json:"test_cases"`
+}
+
+// SummaryTestCase is a single test of a group
+type SummaryTestCase struct {
+	ID             string               `json:"id"`
+	TestID         string               `json:"test_id,omitempty"` // Stable ID across runs (see TestID); missing before 3pio recorded it
+	Name           string               `json:"name"`
+	Status         string               `json:"status"`
+	DurationMs     float64              `json:"duration_ms"`
+	FailureMessage string               `json:"failure_message,omitempty"`
+	FailureKind    string               `json:"failure_kind,omitempty"`  // Why a failed test failed, e.g. ASSERTION_FAILED or PANICKED
+	MinimalInput   *SummaryMinimalInput `json:"minimal_input,omitempty"` // Input a failed property test shrank its case to
+	Report         string               `json:"report"`                  // Detail report of the test's group, relative to the run directory
+}
+
+// SummaryMinimalInput is the minimized failing case of a property test
+type SummaryMinimalInput struct {
+	Framework      string `json:"framework"` // "proptest" or "quickcheck"
+	Input          string `json:"input"`     // e.g. "a = 0, b = -1"
+	Seed           string `json:"seed,omitempty"`
+	RegressionFile string `json:"regression_file,omitempty"` // proptest-regressions file the case was saved in
+}
+
+// SummaryMerge describes the shards a merged summary was made from
+type SummaryMerge struct {
+	Shards     []SummaryShard    `json:"shards"`
+	WallMs     float64           `json:"wall_ms"`    // Of the longest shard, how long the sharded run took
+	ShardMs    float64           `json:"shard_ms"`   // Of all shards together, the machine time the run took
+	Duplicates int               `json:"duplicates"` // Tests that ran on more than one shard with the same result, counted once
+	Conflicts  []SummaryConflict `json:"conflicts"`  // Tests with different results on different shards
+
+	// Indexes of the shards of a sharded suite that weren't merged, so the merged run
+	// doesn't cover the whole suite
+	MissingShards []int `json:"missing_shards,omitempty"`
+}
+
+// SummaryShard is one of the runs a merged summary was made from
+type SummaryShard struct {
+	RunID      string         `json:"run_id"`
+	Command    string         `json:"command"`
+	StartTime  time.Time      `json:"start_time"`
+	EndTime    time.Time      `json:"end_time"`
+	ExitCode   int            `json:"exit_code"`
+	ExitReason ExitReason     `json:"exit_reason"`
+	Totals     SummaryCounts  `json:"totals"`
+	Shard      *runinfo.Shard `json:"shard,omitempty"` // Part of the suite the run covers, nil if unknown
+}
+
+// SummaryConflict is a test that ran on more than one shard with different results.
+// The merged summary has its worst result, e.g. FAIL over PASS.
+type SummaryConflict struct {
+	ID      string            `json:"id"`
+	Path    []string          `json:"path"` // Names of the test's groups
+	Name    string            `json:"name"`
+	Results map[string]string `json:"results"` // Status by shard run ID
+}
+
+// buildSummary collects the run's metadata and results into a RunSummary
+func (m *Manager) buildSummary(exitCode int) RunSummary {
+	summary := RunSummary{
+		SchemaVersion: SummarySchemaVersion,
+		RunID:         filepath.Base(m.runDir),
+		Command:       m.state.Arguments,
+		Runner:        m.detectedRunner,
+		StartTime:     m.startTime.UTC(),
+		EndTime:       time.Now().UTC(),
+		ExitCode:      exitCode,
+		Status:        m.statusText(),
+		ExitReason:    m.exitReason(),
+		RerunOf:       m.rerunOf,
+		Shard:         m.shard,
+		Cargo:         m.cargo,
+		Coverage:      m.coverage,
+		Groups:        []SummaryGroup{},
+		ResultsSource: m.resultsSource,
+	}
+	summary.Totals.CollectionErrors = len(m.collectionErrors)
+	if cwd, err := workDirOr(m.workDir); err == nil {
+		summary.Cwd = cwd
+	}
+
+	var walk func(group *TestGroup)
+	walk = func(group *TestGroup) {
+		summary.Groups = append(summary.Groups, summaryGroup(group, m.runDir))
+
+		subgroups := make([]*TestGroup, 0, len(group.Subgroups))
+		for _, subgroup := range group.Subgroups {
+			subgroups = append(subgroups, subgroup)
+		}
+		for _, subgroup := range sortedGroups(subgroups) {
+			walk(subgroup)
+		}
+	}
+
+	for _, group := range sortedGroups(m.groupManager.GetRootGroups()) {
+		summary.Totals.Total += countTotalTestCases(group)
+		summary.Totals.Passed += countPassedTestCases(group)
+		summary.Totals.Failed += countFailedTestCases(group)
+		summary.Totals.Skipped += countSkippedTestCases(group)
+		summary.Totals.Quarantined += countQuarantinedTestCases(group)
+		summary.Totals.Flaky += countFlakyTestCases(group)
+		summary.Totals.Errors += countErroredTestCases(group)
+		summary.Totals.Benchmarks += countBenchmarks(group)
+		walk(group)
+	}
+	return summary
+}
+
+// summaryGroup returns the summary.json entry of a group, with its test cases
+func summaryGroup(group *TestGroup, runDir string) SummaryGroup {
+	reportPath := relativeReportPath(group, runDir)
+	entry := SummaryGroup{
+		ID:         group.ID,
+		Name:       group.Name,
+		Path:       append([]string(nil), group.GetFullPath()...),
+		Status:     string(group.Status),
+		SkipReason: group.SkipReason,
+		DurationMs: durationMs(group.Duration),
+		Report:     reportPath,
+		TestCases:  []SummaryTestCase{},
+	}
+	if entry.Status == "" {
+		entry.Status = string(TestStatusPending)
+	}
+	if group.ErrorInfo != nil {
+		entry.Error = group.ErrorInfo.Message
+		entry.FailureKind = group.ErrorInfo.FailureKind
+	}
+	for i := range group.TestCases {
+		entry.TestCases = append(entry.TestCases, summaryTestCase(&group.TestCases[i], reportPath))
+	}
+	return entry
+}
+
+// summaryTestCase returns the summary.json entry of a test case
+func summaryTestCase(tc *TestCase, reportPath string) SummaryTestCase {
+	testCase := SummaryTestCase{
+		ID:         tc.ID,
+		TestID:     tc.TestID,
+		Name:       tc.Name,
+		Status:     string(tc.Status),
+		DurationMs: durationMs(tc.Duration),
+		Report:     reportPath,
+	}
+	if (tc.Status == TestStatusFail || tc.Status == TestStatusQuarantinedFail || tc.Status == TestStatusError) && tc.Error != nil {
+		testCase.FailureMessage = tc.Error.Message
+	}
+	testCase.FailureKind = tc.FailureKind()
+	if testCase.FailureMessage != "" && tc.Error.Property != nil {
+		property := tc.Error.Property
+		testCase.MinimalInput = &SummaryMinimalInput{
+			Framework:      property.Framework,
+			Input:          property.Input,
+			Seed:           property.Seed,
+			RegressionFile: property.RegressionFile,
+		}
+	}
+	return testCase
+}
+
+// writeSummary writes summary.json into the run directory
+func (m *Manager) writeSummary(summary RunSummary) error {
+	data, err := json.MarshalIndent(summary, "", "  ")
+	if err != nil {
+		return err
+	}
+	return writeFileAtomic(filepath.Join(m.runDir, SummaryFileName), append(m.redactor.RedactBytes(data), '\n'))
+}
+
+// ReadSummary reads the summary.json of a run directory
+func ReadSummary(runDir string) (*RunSummary, error) {
+	data, err := os.ReadFile(filepath.Join(runDir, SummaryFileName))
+	if err != nil {
+		return nil, err
+	}
+	var summary RunSummary
+	if err := json.Unmarshal(data, &summary); err != nil {
+		return nil, fmt.Errorf("failed to parse %s: %w", SummaryFileName, err)
+	}
+	return &summary, nil
+}
+
+// relativeReportPath returns the path of a group's report relative to the run directory
+func relativeReportPath(group *TestGroup, runDir string) string {
+	reportFile := GetReportFilePath(group, runDir)
+	if relPath, err := filepath.Rel(runDir, reportFile); err == nil {
+		reportFile = "./" + relPath
+	}
+	return reportFile
+}
+
+// durationMs converts a duration to fractional milliseconds
+func durationMs(d time.Duration) float64 {
+	return float64(d) / float64(time.Millisecond)
+}
@@ -0,0 +1,144 @@
+package report
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/zk/3pio/internal/ipc"
+)
+
+func TestManager_Summary(t *testing.T) {
+	tempDir := t.TempDir()
+	logger := &mockLogger{}
+
+	manager, err := NewManager(tempDir, nil, logger, "cargo test", "cargo test")
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	manager.SetRerunOf("20260101T000000-previous-run", 1)
+	if err := manager.Initialize("cargo test"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+
+	testCases := []ipc.TestCasePayload{
+		{TestName: "test_add", ParentNames: []string{"my-crate", "tests"}, Status: "PASS", Duration: 12},
+		{
+			TestName:    "test_divide",
+			ParentNames: []string{"my-crate", "tests", "nested"},
+			Status:      "FAIL",
+			Duration:    3,
+			Error:       &ipc.TestError{Message: "attempt to divide by zero"},
+		},
+		{TestName: "test_slow", ParentNames: []string{"my-crate", "tests"}, Status: "SKIP", SkipReason: "ignored"},
+	}
+	for _, payload := range testCases {
+		if err := manager.HandleEvent(ipc.GroupTestCaseEvent{EventType: "testCase", Payload: payload}); err != nil {
+			t.Fatalf("HandleEvent failed: %v", err)
+		}
+	}
+	if err := manager.Finalize(101, ""); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+
+	summary, err := ReadSummary(tempDir)
+	if err != nil {
+		t.Fatalf("Failed to read summary: %v", err)
+	}
+
+	if summary.SchemaVersion != SummarySchemaVersion {
+		t.Errorf("Expected schema_version %d, got %d", SummarySchemaVersion, summary.SchemaVersion)
+	}
+	if summary.RunID != filepath.Base(tempDir) || summary.Command != "cargo test" || summary.Runner != "cargo test" {
+		t.Errorf("Unexpected run metadata: %+v", summary)
+	}
+	if summary.ExitCode != 101 || summary.Status != "COMPLETED" || summary.ExitReason != ExitReasonTestsFailed {
+		t.Errorf("Expected exit_code 101, status COMPLETED and exit_reason tests_failed, got %d, %q, %q",
+			summary.ExitCode, summary.Status, summary.ExitReason)
+	}
+	if summary.RerunOf != "20260101T000000-previous-run" {
+		t.Errorf("Expected rerun_of to be recorded, got %q", summary.RerunOf)
+	}
+	if summary.StartTime.IsZero() || summary.EndTime.Before(summary.StartTime) {
+		t.Errorf("Expected start_time <= end_time, got %v and %v", summary.StartTime, summary.EndTime)
+	}
+	if want := (SummaryCounts{Total: 3, Passed: 1, Failed: 1, Skipped: 1}); summary.Totals != want {
+		t.Errorf("Expected totals %+v, got %+v", want, summary.Totals)
+	}
+
+	// Groups are listed depth-first with their own test cases
+	var paths []string
+	for _, group := range summary.Groups {
+		paths = append(paths, filepath.Join(group.Path...))
+	}
+	wantPaths := []string{"my-crate", filepath.Join("my-crate", "tests"), filepath.Join("my-crate", "tests", "nested")}
+	if len(paths) != len(wantPaths) {
+		t.Fatalf("Expected groups %v, got %v", wantPaths, paths)
+	}
+	for i := range wantPaths {
+		if paths[i] != wantPaths[i] {
+			t.Errorf("Expected group %d to be %s, got %s", i, wantPaths[i], paths[i])
+		}
+	}
+
+	nested := summary.Groups[2]
+	if len(nested.TestCases) != 1 {
+		t.Fatalf("Expected one test case in the nested group, got %+v", nested.TestCases)
+	}
+	failed := nested.TestCases[0]
+	if failed.ID == "" || failed.Name != "test_divide" || failed.Status != "FAIL" {
+		t.Errorf("Unexpected failed test case: %+v", failed)
+	}
+	if failed.FailureMessage != "attempt to divide by zero" {
+		t.Errorf("Expected the failure message, got %q", failed.FailureMessage)
+	}
+	if failed.DurationMs != 3 {
+		t.Errorf("Expected duration_ms 3, got %v", failed.DurationMs)
+	}
+	if failed.Report != nested.Report {
+		t.Errorf("Expected the test case to point at its group's report %s, got %s", nested.Report, failed.Report)
+	}
+	if _, err := os.Stat(filepath.Join(tempDir, nested.Report)); err != nil {
+		t.Errorf("Expected the detail report to exist: %v", err)
+	}
+}
+
+func TestManager_SummaryMinimalInput(t *testing.T) {
+	tempDir := t.TempDir()
+	manager, err := NewManager(tempDir, nil, &mockLogger{}, "cargo test", "cargo test")
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	if err := manager.Initialize("cargo test"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+
+	property := &ipc.PropertyFailure{Framework: "proptest", Input: "n = -1", Seed: "6c5d30c1f7b4c0e2", RegressionFile: "proptest-regressions/lib.txt"}
+	payload := ipc.TestCasePayload{
+		TestName:    "round_trips",
+		ParentNames: []string{"rust-proptest", "tests"},
+		Status:      "FAIL",
+		Error:       &ipc.TestError{Message: "Test failed: assertion failed", Property: property},
+	}
+	if err := manager.HandleEvent(ipc.GroupTestCaseEvent{EventType: "testCase", Payload: payload}); err != nil {
+		t.Fatalf("HandleEvent failed: %v", err)
+	}
+	if err := manager.Finalize(101, ""); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+
+	summary, err := ReadSummary(tempDir)
+	if err != nil {
+		t.Fatalf("Failed to read summary: %v", err)
+	}
+	want := SummaryMinimalInput{Framework: "proptest", Input: "n = -1", Seed: "6c5d30c1f7b4c0e2", RegressionFile: "proptest-regressions/lib.txt"}
+	for _, group := range summary.Groups {
+		for _, tc := range group.TestCases {
+			if tc.MinimalInput == nil || *tc.MinimalInput != want {
+				t.Errorf("Expected minimal_input %+v, got %+v", want, tc.MinimalInput)
+			}
+			return
+		}
+	}
+	t.Fatal("Expected the failed test in the summary")
+}
@@ -0,0 +1,109 @@
+package report
+
+import (
+	"crypto/sha256"
+	"encoding/hex"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+)
+
+// TestIDVersion is the version of the test ID scheme. It is hashed into every ID and
+// shows in its prefix ("t1-"), so a new scheme yields IDs that can't be mistaken for
+// the old ones.
+const TestIDVersion = 1
+
+// testIDPrefix starts every test ID of the current scheme
+var testIDPrefix = fmt.Sprintf("t%d-", TestIDVersion)
+
+// TestID returns the stable ID of a test, for tools that track tests across runs. It is
+// the first 16 bytes of a SHA-256 over:
+//
+//	version byte | runner kind | 0x00 | group names, each followed by 0x00 | test name
+//
+// in hex, prefixed with "t1-". The runner kind comes from RunnerKind, and the group
+// names run from the target (crate, package or file) down to the test's own group.
+// File paths among them are relative to the working directory with forward slashes,
+// so the ID doesn't depend on where the project is checked out.
+func TestID(runnerKind string, groupPath []string, testName string) string {
+	data := append([]byte{TestIDVersion}, runnerKind...)
+	data = append(data, 0)
+	for _, name := range groupPath {
+		data = append(data, name...)
+		data = append(data, 0)
+	}
+	data = append(data, testName...)
+	hash := sha256.Sum256(data)
+	return testIDPrefix + hex.EncodeToString(hash[:16])
+}
+
+// IsTestID reports whether s has the form of a test ID of the current scheme
+func IsTestID(s string) bool {
+	digits, ok := strings.CutPrefix(s, testIDPrefix)
+	if !ok || len(digits) != 32 {
+		return false
+	}
+	_, err := hex.DecodeString(digits)
+	return err == nil
+}
+
+// RunnerKind returns the runner kind hashed into test IDs for a detected runner, so
+// the ID of a Rust test is the same under cargo test and cargo nextest
+func RunnerKind(detectedRunner string) string {
+	switch detectedRunner {
+	case "cargo test", "cargo nextest":
+		return "cargo"
+	case "cargo bench (criterion)":
+		return "criterion"
+	case "go test":
+		return "go"
+	}
+	return detectedRunner
+}
+
+// testIDPath returns the group path hashed into a test ID: absolute file paths become
+// relative to dir with forward slashes, other names are kept
+func testIDPath(groupPath []string, dir string) []string {
+	path := make([]string, len(groupPath))
+	for i, name := range groupPath {
+		path[i] = name
+		if !filepath.IsAbs(name) || dir == "" {
+			continue
+		}
+		if rel, err := filepath.Rel(dir, name); err == nil && !strings.HasPrefix(rel, "..") {
+			path[i] = filepath.ToSlash(rel)
+		}
+	}
+	return path
+}
+
+// testIDDir returns the directory test ID paths are relative to: dir, or the working
+// directory when it's empty. Group names have their symlinks resolved, so the
+// directory is resolved too.
+func testIDDir(dir string) string {
+	cwd := dir
+	if cwd == "" {
+		var err error
+		if cwd, err = os.Getwd(); err != nil {
+			return ""
+		}
+	}
+	if resolved, err := filepath.EvalSymlinks(cwd); err == nil {
+		cwd = resolved
+	}
+	return cwd
+}
+
+// SummaryTestID returns the test ID of a test in a summary, computing it for summaries
+// of older 3pio versions that don't record one
+func SummaryTestID(summary *RunSummary, group SummaryGroup, tc SummaryTestCase) string {
+	if tc.TestID != "" {
+		return tc.TestID
+	}
+	dir := summary.Cwd
+	if resolved, err := filepath.EvalSymlinks(dir); err == nil {
+		dir = resolved
+	}
+	return TestID(RunnerKind(summary.Runner), testIDPath(group.Path, dir), tc.Name)
+}
@@ -0,0 +1,88 @@
+package report
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestTestID(t *testing.T) {
+	// The scheme is documented for other tools, so the value is pinned
+	id := TestID("cargo", []string{"my-crate", "tests"}, "test_add")
+	if id != "t1-8599fdcb6bd2a1cfb90d2b7be8c5517f" {
+		t.Errorf("TestID changed to %s; bump TestIDVersion to change the scheme", id)
+	}
+	if !IsTestID(id) {
+		t.Errorf("Expected %s to be recognized as a test ID", id)
+	}
+
+	if TestID(RunnerKind("cargo test"), []string{"my-crate"}, "test_add") != TestID(RunnerKind("cargo nextest"), []string{"my-crate"}, "test_add") {
+		t.Error("Expected cargo test and cargo nextest to give the same ID")
+	}
+	// Names are separated, so moving text between the group and the test changes the ID
+	for _, other := range []string{
+		TestID("cargo", []string{"my-crate", "tests::test"}, "add"),
+		TestID("cargo", []string{"my-crate"}, "tests"+"\x00"+"test_add"),
+		TestID("go", []string{"my-crate", "tests"}, "test_add"),
+	} {
+		if other == id {
+			t.Errorf("Expected %s to differ from %s", other, id)
+		}
+	}
+
+	for _, s := range []string{"", "t1-", "t2-8599fdcb6bd2a1cfb90d2b7be8c5517f", "t1-8599fdcb6bd2a1cfb90d2b7be8c5517", "t1-zz99fdcb6bd2a1cfb90d2b7be8c5517f"} {
+		if IsTestID(s) {
+			t.Errorf("Expected %q not to be a test ID", s)
+		}
+	}
+}
+
+func TestSummaryTestID(t *testing.T) {
+	checkout, err := filepath.EvalSymlinks(t.TempDir())
+	if err != nil {
+		t.Fatal(err)
+	}
+	// Older summaries have absolute file groups and no test IDs
+	summary := &RunSummary{Runner: "jest", Cwd: checkout}
+	group := SummaryGroup{Path: []string{filepath.Join(checkout, "src", "math.test.js"), "Calculator"}}
+	want := TestID("jest", []string{"src/math.test.js", "Calculator"}, "adds")
+	if got := SummaryTestID(summary, group, SummaryTestCase{Name: "adds"}); got != want {
+		t.Errorf("Expected the computed ID %s, got %s", want, got)
+	}
+	if got := SummaryTestID(summary, group, SummaryTestCase{Name: "adds", TestID: "t1-recorded"}); got != "t1-recorded" {
+		t.Errorf("Expected the recorded ID to be kept, got %s", got)
+	}
+}
+
+func TestGroupManager_TestIDIndependentOfCheckout(t *testing.T) {
+	cwd, err := os.Getwd()
+	if err != nil {
+		t.Fatal(err)
+	}
+	gm := NewGroupManager(t.TempDir(), "", &mockLogger{})
+	gm.SetRunnerKind(RunnerKind("vitest"))
+	// A file that exists, so its symlinks resolve like the working directory's
+	absolute := gm.TestID([]string{filepath.Join(cwd, "test_id_test.go"), "Calculator"}, "adds")
+	relative := gm.TestID([]string{"./test_id_test.go", "Calculator"}, "adds")
+	want := TestID("vitest", []string{"test_id_test.go", "Calculator"}, "adds")
+	if absolute != want || relative != want {
+		t.Errorf("Expected %s for the absolute and relative file, got %s and %s", want, absolute, relative)
+	}
+}
+
+func TestQuarantine_MatchTestID(t *testing.T) {
+	id := TestID("cargo", []string{"my-crate", "tests"}, "test_flaky")
+	q, err := ParseQuarantine(`tests = ["` + id + `", "my-crate > net > **"]`)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if !q.Match(id, []string{"my-crate", "tests", "test_flaky"}) {
+		t.Error("Expected the test ID entry to match")
+	}
+	if q.Match(TestID("cargo", []string{"my-crate", "tests"}, "test_other"), []string{"my-crate", "tests", "test_other"}) {
+		t.Error("Expected another test not to match")
+	}
+	if got := q.Unmatched(); len(got) != 1 || got[0] != "my-crate > net > **" {
+		t.Errorf("Expected only the path entry to be unmatched, got %q", got)
+	}
+}
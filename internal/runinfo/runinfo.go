@@ -0,0 +1,207 @@
+// Package runinfo records what a test run ran with: the versions of the tools the
+// runner uses, the platform, and the environment variables that change how tests
+// are built and run. It is written to metadata.json and the report's Environment
+// section, for telling apart runs that only fail on one machine.
+package runinfo
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"os/exec"
+	"runtime"
+	"slices"
+	"sort"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+)
+
+// FileName is the metadata file written next to test-run.md
+const FileName = "metadata.json"
+
+// probeTimeout bounds each version command, so a hung tool can't hold up the report
+const probeTimeout = 10 * time.Second
+
+// Probe is a command that prints the version of a tool the tests run with
+type Probe struct {
+	Tool  string                            // Name in the report, e.g. "rustc"; empty to only Parse the output
+	Args  []string                          // Command to run, e.g. rustc --version --verbose
+	Parse func(output string, md *Metadata) // Records more than the version from the output, optional
+}
+
+// Info is what a runner contributes to the metadata of its runs
+type Info struct {
+	Probes  []Probe
+	EnvVars []string // Environment variables recorded when set, e.g. RUSTFLAGS
+	Target  string   // Target the tests are built for when the command names it
+	Shard   *Shard   // Part of the test suite the command runs, nil for all of it
+	Cargo   *Cargo   // How a cargo command builds the tests, nil for other runners
+}
+
+// RunnerInfo is implemented by runner definitions that know which tools their
+// tests run with
+type RunnerInfo interface {
+	RunnerInfo(command []string) Info
+}
+
+// Metadata is the schema of metadata.json
+type Metadata struct {
+	OS        string            `json:"os"`   // runtime.GOOS of 3pio, e.g. "linux"
+	Arch      string            `json:"arch"` // runtime.GOARCH of 3pio, e.g. "amd64"
+	CPUs      int               `json:"cpus"`
+	Toolchain string            `json:"toolchain,omitempty"` // Active toolchain, e.g. "stable-x86_64-unknown-linux-gnu"
+	Target    string            `json:"target,omitempty"`    // Target triple the tests are built for
+	Tools     []Tool            `json:"tools"`
+	Env       map[string]string `json:"env"`             // The runner's environment variables that were set
+	Shard     *Shard            `json:"shard,omitempty"` // Part of the test suite the run covers
+	Cargo     *Cargo            `json:"cargo,omitempty"` // Profile, features and target of a cargo run
+}
+
+// Tool is the version of a tool the tests ran with
+type Tool struct {
+	Name    string `json:"name"`
+	Version string `json:"version"`          // First line of the version command's output
+	Output  string `json:"output,omitempty"` // Whole output, when it has more than the version
+}
+
+// Shard is the part of a split test suite a run covers, e.g. shard 2 of jest
+// --shard=2/4
+type Shard struct {
+	Index int `json:"index"` // From 1
+	Total int `json:"total"`
+}
+
+func (s Shard) String() string { return fmt.Sprintf("%d/%d", s.Index, s.Total) }
+
+// Cargo is how a cargo command builds the tests, which changes their results as much as
+// the code does: cargo test --release can pass where cargo test fails
+type Cargo struct {
+	Profile           string   `json:"profile"`                       // "dev", "release", "bench" or the --profile given
+	Features          []string `json:"features,omitempty"`            // --features, sorted
+	AllFeatures       bool     `json:"all_features,omitempty"`        // --all-features
+	NoDefaultFeatures bool     `json:"no_default_features,omitempty"` // --no-default-features
+	Target            string   `json:"target,omitempty"`              // --target triple
+}
+
+// String describes the build, e.g. "profile release, features serde, target wasm32-wasip1"
+func (c Cargo) String() string {
+	parts := []string{"profile " + c.Profile}
+	if c.AllFeatures {
+		parts = append(parts, "all features")
+	}
+	if c.NoDefaultFeatures {
+		parts = append(parts, "no default features")
+	}
+	if len(c.Features) > 0 {
+		parts = append(parts, "features "+strings.Join(c.Features, ","))
+	}
+	if c.Target != "" {
+		parts = append(parts, "target "+c.Target)
+	}
+	return strings.Join(parts, ", ")
+}
+
+// Equal reports whether two builds are the same
+func (c Cargo) Equal(other Cargo) bool {
+	return c.Profile == other.Profile && slices.Equal(c.Features, other.Features) &&
+		c.AllFeatures == other.AllFeatures && c.NoDefaultFeatures == other.NoDefaultFeatures &&
+		c.Target == other.Target
+}
+
+// ShardFlag returns the shard the command's flag selects, given as "--shard=2/4" or
+// "--shard 2/4". The value may start with how the tests are split, as nextest's
+// "--partition count:2/4" does. Nil without the flag or with an invalid value.
+func ShardFlag(command []string, flag string) *Shard {
+	for i, arg := range command {
+		value, ok := strings.CutPrefix(arg, flag+"=")
+		if !ok && arg == flag && i+1 < len(command) {
+			value, ok = command[i+1], true
+		}
+		if !ok {
+			continue
+		}
+		if _, split, found := strings.Cut(value, ":"); found {
+			value = split
+		}
+		indexText, totalText, _ := strings.Cut(value, "/")
+		index, indexErr := strconv.Atoi(indexText)
+		total, totalErr := strconv.Atoi(totalText)
+		if indexErr != nil || totalErr != nil || index < 1 || index > total {
+			return nil
+		}
+		return &Shard{Index: index, Total: total}
+	}
+	return nil
+}
+
+// runProbe runs a version command and returns its output. Replaced in tests.
+var runProbe = func(ctx context.Context, args []string) (string, error) {
+	output, err := exec.CommandContext(ctx, args[0], args[1:]...).Output()
+	return string(output), err
+}
+
+// Collect runs the runner's probes concurrently and returns the metadata. Probes that
+// fail, e.g. for a tool that isn't installed, are left out.
+func Collect(ctx context.Context, info Info) *Metadata {
+	md := &Metadata{
+		OS:     runtime.GOOS,
+		Arch:   runtime.GOARCH,
+		CPUs:   runtime.NumCPU(),
+		Target: info.Target,
+		Shard:  info.Shard,
+		Cargo:  info.Cargo,
+		Env:    make(map[string]string),
+	}
+	for _, name := range info.EnvVars {
+		if value, ok := os.LookupEnv(name); ok {
+			md.Env[name] = value
+		}
+	}
+
+	outputs := make([]string, len(info.Probes))
+	var wg sync.WaitGroup
+	for i, probe := range info.Probes {
+		wg.Add(1)
+		go func(i int, args []string) {
+			defer wg.Done()
+			probeCtx, cancel := context.WithTimeout(ctx, probeTimeout)
+			defer cancel()
+			if output, err := runProbe(probeCtx, args); err == nil {
+				outputs[i] = strings.TrimSpace(output)
+			}
+		}(i, probe.Args)
+	}
+	wg.Wait()
+
+	// Probes are applied in order, so the metadata doesn't depend on which finished first
+	for i, probe := range info.Probes {
+		output := outputs[i]
+		if output == "" {
+			continue
+		}
+		if probe.Tool != "" {
+			tool := Tool{Name: probe.Tool, Version: output}
+			if first, _, multiline := strings.Cut(output, "\n"); multiline {
+				tool.Version = strings.TrimSpace(first)
+				tool.Output = output
+			}
+			md.Tools = append(md.Tools, tool)
+		}
+		if probe.Parse != nil {
+			probe.Parse(output, md)
+		}
+	}
+	return md
+}
+
+// EnvNames returns the names of the recorded environment variables, sorted
+func (md *Metadata) EnvNames() []string {
+	names := make([]string, 0, len(md.Env))
+	for name := range md.Env {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	return names
+}
@@ -0,0 +1,108 @@
+package runinfo
+
+import (
+	"context"
+	"errors"
+	"os"
+	"runtime"
+	"strings"
+	"testing"
+)
+
+// stubProbes has runProbe answer from outputs, keyed by the command line; commands
+// without an output fail as if the tool weren't installed
+func stubProbes(t *testing.T, outputs map[string]string) {
+	original := runProbe
+	runProbe = func(_ context.Context, args []string) (string, error) {
+		if output, ok := outputs[strings.Join(args, " ")]; ok {
+			return output, nil
+		}
+		return "", errors.New("executable file not found in $PATH")
+	}
+	t.Cleanup(func() { runProbe = original })
+}
+
+func TestShardFlag(t *testing.T) {
+	tests := []struct {
+		name    string
+		command []string
+		flag    string
+		want    *Shard
+	}{
+		{"with =", []string{"npx", "jest", "--shard=2/4"}, "--shard", &Shard{Index: 2, Total: 4}},
+		{"separate value", []string{"npx", "vitest", "run", "--shard", "1/3"}, "--shard", &Shard{Index: 1, Total: 3}},
+		{"nextest partition", []string{"cargo", "nextest", "run", "--partition", "count:3/3"}, "--partition", &Shard{Index: 3, Total: 3}},
+		{"hash partition", []string{"cargo", "nextest", "run", "--partition=hash:1/2"}, "--partition", &Shard{Index: 1, Total: 2}},
+		{"no flag", []string{"npx", "jest", "src"}, "--shard", nil},
+		{"out of range", []string{"npx", "jest", "--shard=5/4"}, "--shard", nil},
+		{"not a shard", []string{"npx", "jest", "--shard=half"}, "--shard", nil},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := ShardFlag(tt.command, tt.flag)
+			if (got == nil) != (tt.want == nil) || (got != nil && *got != *tt.want) {
+				t.Errorf("ShardFlag(%v, %q) = %v, want %v", tt.command, tt.flag, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestCollect(t *testing.T) {
+	stubProbes(t, map[string]string{
+		"rustc --version --verbose":    "rustc 1.82.0 (f6e511eec 2024-10-15)\nhost: x86_64-unknown-linux-gnu\n",
+		"cargo --version":              "cargo 1.82.0 (8f40fc59f 2024-08-21)\n",
+		"rustup show active-toolchain": "stable-x86_64-unknown-linux-gnu (default)\n",
+	})
+	t.Setenv("RUSTFLAGS", "-D warnings")
+	t.Setenv("RUST_TEST_THREADS", "")
+	// t.Setenv restores the variable afterwards, so it can be unset for the test
+	t.Setenv("CARGO_BUILD_JOBS", "")
+	_ = os.Unsetenv("CARGO_BUILD_JOBS")
+
+	var parsed []string
+	record := func(name string) func(string, *Metadata) {
+		return func(_ string, _ *Metadata) {
+			parsed = append(parsed, name)
+		}
+	}
+	md := Collect(context.Background(), Info{
+		Probes: []Probe{
+			{Tool: "rustc", Args: []string{"rustc", "--version", "--verbose"}, Parse: record("rustc")},
+			{Tool: "cargo", Args: []string{"cargo", "--version"}},
+			{Tool: "cargo-nextest", Args: []string{"cargo", "nextest", "--version"}, Parse: record("cargo-nextest")},
+			{Args: []string{"rustup", "show", "active-toolchain"}, Parse: record("rustup")},
+		},
+		EnvVars: []string{"RUSTFLAGS", "RUST_TEST_THREADS", "CARGO_BUILD_JOBS"},
+		Target:  "wasm32-unknown-unknown",
+	})
+
+	if md.OS != runtime.GOOS || md.Arch != runtime.GOARCH || md.CPUs != runtime.NumCPU() {
+		t.Errorf("Expected the platform of this process, got %s/%s with %d CPUs", md.OS, md.Arch, md.CPUs)
+	}
+	if md.Target != "wasm32-unknown-unknown" {
+		t.Errorf("Expected the target of the command, got %q", md.Target)
+	}
+
+	// A failed probe is left out and isn't parsed; the others are parsed in order
+	if strings.Join(parsed, ",") != "rustc,rustup" {
+		t.Errorf("Expected rustc and rustup parsed in order, got %v", parsed)
+	}
+	if len(md.Tools) != 2 {
+		t.Fatalf("Expected rustc and cargo, got %+v", md.Tools)
+	}
+	rustc, cargo := md.Tools[0], md.Tools[1]
+	if rustc.Name != "rustc" || rustc.Version != "rustc 1.82.0 (f6e511eec 2024-10-15)" || !strings.Contains(rustc.Output, "host: x86_64-unknown-linux-gnu") {
+		t.Errorf("Expected rustc's version and its whole output, got %+v", rustc)
+	}
+	if cargo.Name != "cargo" || cargo.Version != "cargo 1.82.0 (8f40fc59f 2024-08-21)" || cargo.Output != "" {
+		t.Errorf("Expected cargo's version alone, got %+v", cargo)
+	}
+
+	// Variables set to an empty string are recorded, unset ones aren't
+	if len(md.Env) != 2 || md.Env["RUSTFLAGS"] != "-D warnings" || md.Env["RUST_TEST_THREADS"] != "" {
+		t.Errorf("Expected RUSTFLAGS and RUST_TEST_THREADS, got %v", md.Env)
+	}
+	if names := md.EnvNames(); strings.Join(names, ",") != "RUSTFLAGS,RUST_TEST_THREADS" {
+		t.Errorf("Expected the names sorted, got %v", names)
+	}
+}
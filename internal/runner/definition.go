@@ -39,6 +39,17 @@ type NativeRunner interface {
 type BaseDefinition struct {
 	name        string
 	adapterFile string
+	workDir     string // Directory package.json is read from; empty for the working directory
+}
+
+// SetWorkDir sets the directory the test command runs in, which holds package.json
+func (b *BaseDefinition) SetWorkDir(dir string) {
+	b.workDir = dir
+}
+
+// packageJSON returns the path of the project's package.json
+func (b *BaseDefinition) packageJSON() string {
+	return filepath.Join(b.workDir, "package.json")
 }
 
 // GetAdapterFileName returns the adapter file name
@@ -121,9 +132,10 @@ func (j *JestDefinition) GetTestFiles(args []string) ([]string, error) {
 	return []string{}, nil // Dynamic discovery
 }
 
-// BuildCommand builds Jest command with adapter
+// BuildCommand builds Jest command with adapter. --testLocationInResults gives the
+// adapter the line of each test.
 func (j *JestDefinition) BuildCommand(args []string, adapterPath string) []string {
-	result := make([]string, 0, len(args)+5)
+	result := make([]string, 0, len(args)+6)
 
 	foundJest := false
 	jestIndex := -1
@@ -206,15 +218,15 @@ func (j *JestDefinition) BuildCommand(args []string, adapterPath string) []strin
 		if hasSeparator {
 			// Append reporter flags at the end (after all other Jest flags)
 			result = append(result, args...)
-			result = append(result, "--reporters", adapterPath)
+			result = append(result, "--reporters", adapterPath, "--testLocationInResults")
 		} else if isYarnScript {
 			// For yarn scripts, don't use -- separator
 			result = append(result, args...)
-			result = append(result, "--reporters", adapterPath)
+			result = append(result, "--reporters", adapterPath, "--testLocationInResults")
 		} else {
 			// Add all args, then -- separator, then reporter flags (for npm, pnpm, bun)
 			result = append(result, args...)
-			result = append(result, "--", "--reporters", adapterPath)
+			result = append(result, "--", "--reporters", adapterPath, "--testLocationInResults")
 		}
 
 		return result
@@ -239,14 +251,14 @@ func (j *JestDefinition) BuildCommand(args []string, adapterPath string) []strin
 
 		// Add reporter after jest command
 		if !reporterAdded && strings.Contains(arg, "jest") {
-			result = append(result, "--reporters", adapterPath)
+			result = append(result, "--reporters", adapterPath, "--testLocationInResults")
 			reporterAdded = true
 		}
 	}
 
 	// If jest wasn't found in args (fallback case), add reporter at the end
 	if !foundJest && !reporterAdded {
-		result = append(result, "--reporters", adapterPath)
+		result = append(result, "--reporters", adapterPath, "--testLocationInResults")
 	}
 
 	return result
@@ -254,7 +266,7 @@ func (j *JestDefinition) BuildCommand(args []string, adapterPath string) []strin
 
 // isJestInPackageJSON checks if Jest is configured in package.json
 func (j *JestDefinition) isJestInPackageJSON() bool {
-	data, err := os.ReadFile("package.json")
+	data, err := os.ReadFile(j.packageJSON())
 	if err != nil {
 		return false
 	}
@@ -391,10 +403,13 @@ func (v *VitestDefinition) BuildCommand(args []string, adapterPath string) []str
 
 		// Check if -- separator already exists
 		hasSeparator := false
+		hasRun := false
 		for _, arg := range args {
 			if arg == "--" {
 				hasSeparator = true
-				break
+			}
+			if arg == "--run" {
+				hasRun = true
 			}
 		}
 
@@ -406,6 +421,11 @@ func (v *VitestDefinition) BuildCommand(args []string, adapterPath string) []str
 
 		result = append(result, "--reporter", adapterPath, "--reporter", "default")
 
+		// The script may run bare vitest, which watches for changes and never exits
+		if !hasRun {
+			result = append(result, "--run")
+		}
+
 		return result
 	}
 
@@ -466,7 +486,7 @@ func (v *VitestDefinition) BuildCommand(args []string, adapterPath string) []str
 
 // isVitestInPackageJSON checks if Vitest is configured in package.json
 func (v *VitestDefinition) isVitestInPackageJSON() bool {
-	data, err := os.ReadFile("package.json")
+	data, err := os.ReadFile(v.packageJSON())
 	if err != nil {
 		return false
 	}
@@ -662,7 +682,7 @@ func (c *CypressDefinition) BuildCommand(args []string, adapterPath string) []st
 
 // isCypressInPackageJSON checks if Cypress is configured in package.json
 func (c *CypressDefinition) isCypressInPackageJSON() bool {
-	data, err := os.ReadFile("package.json")
+	data, err := os.ReadFile(c.packageJSON())
 	if err != nil {
 		return false
 	}
@@ -723,6 +743,9 @@ func (m *MochaDefinition) GetTestFiles(args []string) ([]string, error) {
 func (m *MochaDefinition) BuildCommand(args []string, adapterPath string) []string {
 	result := make([]string, 0, len(args)+4)
 
+	// Mocha runs a single reporter, so 3pio's replaces the command's
+	args, _ = withoutMochaReporters(args)
+
 	isPackageManagerCommand := false
 	if len(args) > 0 {
 		cmd := args[0]
@@ -762,9 +785,38 @@ func (m *MochaDefinition) BuildCommand(args []string, adapterPath string) []stri
 	return result
 }
 
+// ReplacedReporters returns the reporters the command chose, which 3pio's reporter
+// runs in place of
+func (m *MochaDefinition) ReplacedReporters(args []string) []string {
+	_, reporters := withoutMochaReporters(args)
+	return reporters
+}
+
+// withoutMochaReporters drops --reporter (-R) and its --reporter-option (-O) flags
+// from a command, returning the reporters they named
+func withoutMochaReporters(args []string) ([]string, []string) {
+	var kept, reporters []string
+	for i := 0; i < len(args); i++ {
+		name, value, hasValue := strings.Cut(args[i], "=")
+		switch name {
+		case "--reporter", "-R", "--reporter-option", "--reporter-options", "-O":
+			if !hasValue && i+1 < len(args) {
+				i++
+				value = args[i]
+			}
+			if name == "--reporter" || name == "-R" {
+				reporters = append(reporters, value)
+			}
+		default:
+			kept = append(kept, args[i])
+		}
+	}
+	return kept, reporters
+}
+
 // isMochaInPackageJSON checks if Mocha is configured in package.json
 func (m *MochaDefinition) isMochaInPackageJSON() bool {
-	data, err := os.ReadFile("package.json")
+	data, err := os.ReadFile(m.packageJSON())
 	if err != nil {
 		return false
 	}
@@ -791,3 +843,107 @@ func (m *MochaDefinition) isMochaInPackageJSON() bool {
 	}
 	return false
 }
+
+// PlaywrightDefinition implements Definition for Playwright Test
+type PlaywrightDefinition struct {
+	BaseDefinition
+}
+
+// NewPlaywrightDefinition creates a new Playwright definition
+func NewPlaywrightDefinition() *PlaywrightDefinition {
+	return &PlaywrightDefinition{
+		BaseDefinition: BaseDefinition{
+			name:        "playwright",
+			adapterFile: "playwright.js",
+		},
+	}
+}
+
+// Matches checks if the command is for Playwright Test
+func (p *PlaywrightDefinition) Matches(command []string) bool {
+	return p.invokesPlaywright(command) || p.isPlaywrightInPackageJSON()
+}
+
+// invokesPlaywright checks for a "playwright test" in the command, as in
+// `npx playwright test`; other playwright subcommands (install, show-report) run no tests
+func (p *PlaywrightDefinition) invokesPlaywright(command []string) bool {
+	for i := 0; i+1 < len(command); i++ {
+		if containsTestRunner(command[i:i+1], "playwright") && command[i+1] == "test" {
+			return true
+		}
+	}
+	return false
+}
+
+// GetTestFiles gets test files for Playwright (dynamic by default)
+func (p *PlaywrightDefinition) GetTestFiles(args []string) ([]string, error) {
+	// Playwright finds specs from its config's testDir; rely on dynamic discovery
+	return []string{}, nil
+}
+
+// BuildCommand builds Playwright command with reporter injection
+func (p *PlaywrightDefinition) BuildCommand(args []string, adapterPath string) []string {
+	result := make([]string, 0, len(args)+3)
+
+	isPackageManagerCommand := false
+	if len(args) > 0 {
+		cmd := args[0]
+		isPackageManagerCommand = (cmd == "npm" || strings.HasPrefix(cmd, "npm ")) ||
+			(cmd == "yarn" || strings.HasPrefix(cmd, "yarn ")) ||
+			(cmd == "pnpm" || strings.HasPrefix(cmd, "pnpm ")) ||
+			(cmd == "bun" || strings.HasPrefix(cmd, "bun "))
+	}
+
+	// Same strategy as Cypress: npm/yarn/bun need '--' for script flags; pnpm
+	// passes them directly, as do npx and pnpm exec
+	if isPackageManagerCommand && !p.invokesPlaywright(args) {
+		cmd := args[0]
+		needsSeparator := (cmd == "npm" || strings.HasPrefix(cmd, "npm ")) ||
+			(cmd == "yarn" || strings.HasPrefix(cmd, "yarn ")) ||
+			(cmd == "bun" || strings.HasPrefix(cmd, "bun "))
+
+		hasSeparator := false
+		for _, a := range args {
+			if a == "--" {
+				hasSeparator = true
+				break
+			}
+		}
+
+		result = append(result, args...)
+		if hasSeparator || !needsSeparator {
+			result = append(result, "--reporter", adapterPath)
+		} else {
+			result = append(result, "--", "--reporter", adapterPath)
+		}
+		return result
+	}
+
+	// Direct invocations (playwright test ... or npx/bunx playwright test ...).
+	// The CLI reporter replaces the ones in playwright.config.
+	result = append(result, args...)
+	result = append(result, "--reporter", adapterPath)
+	return result
+}
+
+// isPlaywrightInPackageJSON checks if the package.json test script runs Playwright.
+// Its dependencies aren't checked: @playwright/test usually sits beside a unit test
+// runner that `npm test` runs.
+func (p *PlaywrightDefinition) isPlaywrightInPackageJSON() bool {
+	data, err := os.ReadFile(p.packageJSON())
+	if err != nil {
+		return false
+	}
+
+	var pkg map[string]interface{}
+	if err := json.Unmarshal(data, &pkg); err != nil {
+		return false
+	}
+
+	if scripts, ok := pkg["scripts"].(map[string]interface{}); ok {
+		if test, ok := scripts["test"].(string); ok {
+			return strings.Contains(test, "playwright test")
+		}
+	}
+	return false
+}
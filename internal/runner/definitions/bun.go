@@ -0,0 +1,566 @@
+package definitions
+
+import (
+	"fmt"
+	"io"
+	"path/filepath"
+	"regexp"
+	"strconv"
+	"strings"
+
+	"github.com/zk/3pio/internal/console"
+	"github.com/zk/3pio/internal/ipc"
+	"github.com/zk/3pio/internal/logger"
+)
+
+// Patterns for bun test's text output, e.g.
+//
+//	test/math.test.ts:
+//	(pass) math > adds [0.21ms]
+//	1 | test("divides", () => {
+//	2 |   expect(1 / 0).toBe(0);
+//	                    ^
+//	error: expect(received).toBe(expected)
+//
+//	Expected: 0
+//	Received: Infinity
+//
+//	      at <anonymous> (/app/test/math.test.ts:2:17)
+//	(fail) math > divides [0.61ms]
+//	(skip) math > rounds
+//
+//	 1 pass
+//	 1 skip
+//	 1 fail
+//	Ran 3 tests across 1 file. [12.00ms]
+//
+// A failed test's error and its output come before its result line. In a terminal bun
+// marks results with symbols instead of words.
+var (
+	bunVersionRegex   = regexp.MustCompile(`^bun test v\d`)
+	bunFileRegex      = regexp.MustCompile(`^(\S.*\.[cm]?[jt]sx?):$`)
+	bunResultRegex    = regexp.MustCompile(`^(?:\((pass|fail|skip|todo)\)|(✓|✔|✗|✘|×|⏭|»|↓|✎))\s+(.*?)(?:\s+\[(\d+(?:\.\d+)?)(ms|s)\])?$`)
+	bunCountRegex     = regexp.MustCompile(`^\s+\d+ (?:pass|fail|skip|todo|errors?|snapshots?|expect\(\) calls?)\b`)
+	bunRanRegex       = regexp.MustCompile(`^Ran \d+ tests? across \d+ files?\.`)
+	bunUnhandledRegex = regexp.MustCompile(`^# Unhandled error between tests$`)
+	bunRuleRegex      = regexp.MustCompile(`^-{5,}$`)
+	bunErrorRegex     = regexp.MustCompile(`^(?:error|\w*Error)(?::\s|:$)`)
+	bunSnippetRegex   = regexp.MustCompile(`^\s*(?:\d+ \||\^\s*$)`)
+	bunStackRegex     = regexp.MustCompile(`^\s+at\s+(.*)$`)
+	bunLocationRegex  = regexp.MustCompile(`\(?((?:[A-Za-z]:)?[^():\s]+):(\d+):(\d+)\)?$`)
+	bunTimeoutRegex   = regexp.MustCompile(`timed out after \d+ms`)
+)
+
+// bunSymbols maps the result symbols bun prints in a terminal to its words
+var bunSymbols = map[string]string{
+	"✓": "pass", "✔": "pass",
+	"✗": "fail", "✘": "fail", "×": "fail",
+	"⏭": "skip", "»": "skip", "↓": "skip",
+	"✎": "todo",
+}
+
+// bunParser reads one of bun test's output formats and reports through the
+// definition's startFile, reportTest, reportFileError and finishFile. The text parser
+// is the only one so far; a parser for a machine-readable reporter would take its
+// place in ProcessOutput.
+type bunParser interface {
+	parseLine(line string)
+	finish()
+}
+
+// BunTestDefinition runs bun test and parses its text output
+type BunTestDefinition struct {
+	logger    *logger.FileLogger
+	ipcWriter *IPCWriter
+
+	lineLimit // Longest line of output parsed whole (--max-line-length)
+
+	file *bunFile // File whose tests are being reported
+}
+
+// bunFile is a test file and the describe blocks in it that had tests
+type bunFile struct {
+	Name    string
+	Totals  tapTotals
+	Groups  []*bunGroup          // Describe blocks in the order their first test finished
+	byPath  map[string]*bunGroup // Describe blocks by their path joined with " > "
+	Output  strings.Builder      // Output of the file outside its tests
+	Errored bool                 // Sent a testGroupError, which ends the group
+}
+
+// bunGroup is a describe block
+type bunGroup struct {
+	Path   []string // File, then the describe blocks down to this one
+	Totals tapTotals
+}
+
+// bunTestResult is a test as a parser read it
+type bunTestResult struct {
+	Names    []string // Describe blocks, then the test
+	Status   string   // "pass", "fail", "skip" or "todo"
+	Duration float64  // Milliseconds
+	Output   string
+	Error    *bunError // Set for a failed test
+}
+
+// bunError is why a test failed
+type bunError struct {
+	Message          string
+	Expected, Actual string
+	Stack            string
+	File             string
+	Line             int
+	TimedOut         bool
+}
+
+// NewBunTestDefinition creates a new bun test runner definition
+func NewBunTestDefinition(logger *logger.FileLogger) *BunTestDefinition {
+	return &BunTestDefinition{logger: logger}
+}
+
+// Name returns the name of this test runner
+func (b *BunTestDefinition) Name() string {
+	return "bun"
+}
+
+// Detect checks if the command is bun test
+func (b *BunTestDefinition) Detect(args []string) bool {
+	if len(args) < 2 || args[1] != "test" {
+		return false
+	}
+	base := args[0]
+	if idx := strings.LastIndexAny(base, `/\`); idx != -1 {
+		base = base[idx+1:]
+	}
+	return strings.TrimSuffix(strings.ToLower(base), ".exe") == "bun"
+}
+
+// ModifyCommand returns the command unchanged, as bun test's text output is parsed
+func (b *BunTestDefinition) ModifyCommand(cmd []string, ipcPath, runID string) []string {
+	return append([]string{}, cmd...)
+}
+
+// GetTestFiles returns empty array for dynamic discovery
+func (b *BunTestDefinition) GetTestFiles(args []string) ([]string, error) {
+	return []string{}, nil
+}
+
+// RequiresAdapter returns false as bun test's output is parsed directly
+func (b *BunTestDefinition) RequiresAdapter() bool {
+	return false
+}
+
+// ProcessOutput reads bun test's output and converts it to IPC events
+func (b *BunTestDefinition) ProcessOutput(combinedOutput io.Reader, ipcPath string) error {
+	var err error
+	b.ipcWriter, err = NewIPCWriter(ipcPath)
+	if err != nil {
+		return fmt.Errorf("failed to create IPC writer: %w", err)
+	}
+	defer func() {
+		if err := b.ipcWriter.Close(); err != nil {
+			b.logger.Debug("Failed to close IPC writer: %v", err)
+		}
+	}()
+
+	var parser bunParser = &bunTextParser{def: b}
+	lines := b.newLineReader(combinedOutput)
+	for {
+		line, _, ok := lines.Next()
+		if !ok {
+			break
+		}
+		parser.parseLine(line)
+	}
+
+	if err := lines.Err(); err != nil {
+		return fmt.Errorf("error reading bun test output: %w", err)
+	}
+
+	parser.finish()
+	b.finishFile()
+
+	runCompleteEvent := map[string]interface{}{
+		"eventType": "runComplete",
+		"payload":   map[string]interface{}{},
+	}
+	if err := b.ipcWriter.WriteEvent(runCompleteEvent); err != nil {
+		b.logger.Debug("Failed to send runComplete event: %v", err)
+	}
+
+	return nil
+}
+
+// bunTextParser reads the output bun test prints by default
+type bunTextParser struct {
+	def *BunTestDefinition
+
+	pending     []string // Lines since the last result, which belong to the next one
+	unhandled   []string // Lines of an error thrown outside any test
+	inUnhandled bool     // Reading an unhandled error, until the rule under it
+	rules       int      // Rules around the unhandled error read so far
+	summary     bool     // Past the counts at the end of the run
+}
+
+func (p *bunTextParser) parseLine(line string) {
+	line = strings.TrimRight(console.StripANSI(line), "\r")
+
+	if p.inUnhandled {
+		if bunRuleRegex.MatchString(line) {
+			p.rules++
+			if p.rules == 2 {
+				p.inUnhandled = false
+				p.def.reportFileError(bunFailure(p.unhandled))
+				p.unhandled = nil
+			}
+			return
+		}
+		p.unhandled = append(p.unhandled, line)
+		return
+	}
+
+	switch {
+	case p.summary:
+		return
+	case bunVersionRegex.MatchString(line):
+		return
+	case bunUnhandledRegex.MatchString(line):
+		p.inUnhandled = true
+		p.rules = 0
+		return
+	case bunCountRegex.MatchString(line), bunRanRegex.MatchString(line):
+		// The counts end the last file
+		p.flushOutput()
+		p.def.finishFile()
+		p.summary = true
+		return
+	}
+
+	if matches := bunFileRegex.FindStringSubmatch(line); matches != nil {
+		p.flushOutput()
+		name := matches[1]
+		if abs, err := filepath.Abs(name); err == nil {
+			name = abs
+		}
+		p.def.startFile(name)
+		return
+	}
+
+	if matches := bunResultRegex.FindStringSubmatch(line); matches != nil {
+		status := matches[1]
+		if status == "" {
+			status = bunSymbols[matches[2]]
+		}
+		result := &bunTestResult{
+			Names:  strings.Split(matches[3], " > "),
+			Status: status,
+		}
+		if matches[4] != "" {
+			result.Duration, _ = strconv.ParseFloat(matches[4], 64)
+			if matches[5] == "s" {
+				result.Duration *= 1000
+			}
+		}
+		if status == "fail" {
+			result.Output, result.Error = splitBunFailure(p.pending)
+		} else {
+			result.Output = joinBunLines(p.pending)
+		}
+		p.pending = nil
+		p.def.reportTest(result)
+		return
+	}
+
+	p.pending = append(p.pending, line)
+}
+
+func (p *bunTextParser) finish() {
+	if p.inUnhandled {
+		p.def.reportFileError(bunFailure(p.unhandled))
+		p.unhandled = nil
+		p.inUnhandled = false
+	}
+	p.flushOutput()
+}
+
+// flushOutput gives the lines after a file's last test to the file
+func (p *bunTextParser) flushOutput() {
+	if output := joinBunLines(p.pending); output != "" {
+		p.def.appendFileOutput(output)
+	}
+	p.pending = nil
+}
+
+// joinBunLines joins output lines, dropping the blank lines bun prints between files
+func joinBunLines(lines []string) string {
+	output := strings.Trim(strings.Join(lines, "\n"), "\n")
+	if strings.TrimSpace(output) == "" {
+		return ""
+	}
+	return output + "\n"
+}
+
+// splitBunFailure splits the lines before a failed test's result into the test's own
+// output and its error, which starts at the source snippet bun prints above it
+func splitBunFailure(lines []string) (string, *bunError) {
+	errorStart := -1
+	for i, line := range lines {
+		if bunErrorRegex.MatchString(strings.TrimSpace(line)) {
+			errorStart = i
+			break
+		}
+	}
+	if errorStart == -1 {
+		return joinBunLines(lines), &bunError{Message: strings.TrimSpace(strings.Join(lines, "\n"))}
+	}
+	outputEnd := errorStart
+	for outputEnd > 0 && bunSnippetRegex.MatchString(lines[outputEnd-1]) {
+		outputEnd--
+	}
+	return joinBunLines(lines[:outputEnd]), bunFailure(lines[errorStart:])
+}
+
+// bunFailure parses an error bun printed: its message, any expected and received
+// values, and the stack under it
+func bunFailure(lines []string) *bunError {
+	failure := &bunError{}
+	var message, stack []string
+	for _, line := range lines {
+		if matches := bunStackRegex.FindStringSubmatch(line); matches != nil {
+			stack = append(stack, matches[1])
+			if failure.File == "" {
+				if location := bunLocationRegex.FindStringSubmatch(matches[1]); location != nil {
+					failure.File = location[1]
+					failure.Line, _ = strconv.Atoi(location[2])
+				}
+			}
+			continue
+		}
+		if len(stack) > 0 || bunSnippetRegex.MatchString(line) {
+			continue
+		}
+		if value, ok := strings.CutPrefix(line, "Expected: "); ok {
+			failure.Expected = value
+		} else if value, ok := strings.CutPrefix(line, "Received: "); ok {
+			failure.Actual = value
+		}
+		message = append(message, line)
+	}
+	failure.Message = strings.TrimSpace(strings.TrimPrefix(strings.TrimSpace(strings.Join(message, "\n")), "error: "))
+	failure.Stack = strings.Join(stack, "\n")
+	failure.TimedOut = bunTimeoutRegex.MatchString(failure.Message)
+	return failure
+}
+
+// startFile starts reporting the tests of a file, ending the file before it
+func (b *BunTestDefinition) startFile(name string) {
+	b.finishFile()
+	b.file = &bunFile{Name: name, byPath: make(map[string]*bunGroup)}
+	b.sendGroupDiscovered(name, []string{})
+	b.sendGroupStart(name, []string{})
+}
+
+// currentFile returns the file being reported, starting one named after the runner
+// for results bun printed before any file
+func (b *BunTestDefinition) currentFile() *bunFile {
+	if b.file == nil {
+		b.startFile("bun test")
+	}
+	return b.file
+}
+
+// reportTest reports a test under its file and describe blocks
+func (b *BunTestDefinition) reportTest(result *bunTestResult) {
+	file := b.currentFile()
+	parents := []string{file.Name}
+	names := result.Names
+	for _, describe := range names[:len(names)-1] {
+		parents = append(parents, describe)
+		key := strings.Join(parents[1:], " > ")
+		if _, ok := file.byPath[key]; !ok {
+			group := &bunGroup{Path: append([]string{}, parents...)}
+			file.byPath[key] = group
+			file.Groups = append(file.Groups, group)
+			b.sendGroupDiscovered(describe, group.Path[:len(group.Path)-1])
+			b.sendGroupStart(describe, group.Path[:len(group.Path)-1])
+		}
+	}
+
+	totals := b.sendTestCase(result, names[len(names)-1], parents)
+	file.Totals.add(totals)
+	for i := 2; i <= len(parents); i++ {
+		file.byPath[strings.Join(parents[1:i], " > ")].Totals.add(totals)
+	}
+}
+
+// reportFileError reports an error thrown outside any test, which fails its file
+func (b *BunTestDefinition) reportFileError(failure *bunError) {
+	file := b.currentFile()
+	file.Errored = true
+	b.sendIPCEvent(map[string]interface{}{
+		"eventType": "testGroupError",
+		"payload": map[string]interface{}{
+			"groupName":   file.Name,
+			"parentNames": []string{},
+			"errorType":   "UNHANDLED_ERROR",
+			"duration":    0,
+			"error": map[string]interface{}{
+				"message": failure.Message,
+				"stack":   failure.Stack,
+			},
+		},
+	})
+}
+
+// appendFileOutput keeps output printed outside the tests with the file
+func (b *BunTestDefinition) appendFileOutput(output string) {
+	if b.file != nil {
+		b.file.Output.WriteString(output)
+	}
+}
+
+// finishFile sends the results of the file being reported and its describe blocks
+func (b *BunTestDefinition) finishFile() {
+	file := b.file
+	if file == nil {
+		return
+	}
+	b.file = nil
+
+	if file.Output.Len() > 0 {
+		b.sendIPCEvent(map[string]interface{}{
+			"eventType": "groupStdout",
+			"payload": map[string]interface{}{
+				"groupName":   file.Name,
+				"parentNames": []string{},
+				"chunk":       file.Output.String(),
+			},
+		})
+	}
+	// Innermost describe blocks first, as they were found after their parents
+	for i := len(file.Groups) - 1; i >= 0; i-- {
+		group := file.Groups[i]
+		b.sendGroupResult(group.Path[len(group.Path)-1], group.Path[:len(group.Path)-1], group.Totals)
+	}
+	if !file.Errored {
+		b.sendGroupResult(file.Name, []string{}, file.Totals)
+	}
+}
+
+func (b *BunTestDefinition) sendGroupDiscovered(groupName string, parentNames []string) {
+	b.sendIPCEvent(map[string]interface{}{
+		"eventType": "testGroupDiscovered",
+		"payload": map[string]interface{}{
+			"groupName":   groupName,
+			"parentNames": parentNames,
+		},
+	})
+}
+
+func (b *BunTestDefinition) sendGroupStart(groupName string, parentNames []string) {
+	b.sendIPCEvent(map[string]interface{}{
+		"eventType": "testGroupStart",
+		"payload": map[string]interface{}{
+			"groupName":   groupName,
+			"parentNames": parentNames,
+		},
+	})
+}
+
+// sendTestCase sends the result of a test and returns its totals
+func (b *BunTestDefinition) sendTestCase(result *bunTestResult, name string, parents []string) tapTotals {
+	payload := map[string]interface{}{
+		"testName":    name,
+		"parentNames": parents,
+	}
+	if result.Duration > 0 {
+		payload["duration"] = result.Duration
+	}
+	if result.Output != "" {
+		payload["stdout"] = result.Output
+	}
+
+	var totals tapTotals
+	switch result.Status {
+	case "pass":
+		payload["status"] = "PASS"
+		totals.passed++
+	case "fail":
+		payload["status"] = "FAIL"
+		payload["error"] = bunTestError(result.Error)
+		totals.failed++
+	case "todo":
+		payload["status"] = "SKIP"
+		payload["skipReason"] = ipc.SkipReasonTodo
+		totals.skipped++
+	default:
+		payload["status"] = "SKIP"
+		totals.skipped++
+	}
+
+	b.sendIPCEvent(map[string]interface{}{
+		"eventType": "testCase",
+		"payload":   payload,
+	})
+	return totals
+}
+
+// bunTestError builds the error of a failed test
+func bunTestError(failure *bunError) map[string]interface{} {
+	testError := map[string]interface{}{
+		"message": failure.Message,
+	}
+	if failure.Stack != "" {
+		testError["stack"] = failure.Stack
+	}
+	if failure.Expected != "" {
+		testError["expected"] = failure.Expected
+	}
+	if failure.Actual != "" {
+		testError["actual"] = failure.Actual
+	}
+	if failure.File != "" {
+		testError["location"] = fmt.Sprintf("%s:%d", failure.File, failure.Line)
+		testError["file"] = failure.File
+		testError["line"] = failure.Line
+	}
+	if failure.TimedOut {
+		testError["errorType"] = ipc.ErrorTypeTimedOut
+		testError["failureKind"] = ipc.FailureKindTimedOut
+	}
+	return testError
+}
+
+func (b *BunTestDefinition) sendGroupResult(groupName string, parentNames []string, totals tapTotals) {
+	status := "PASS"
+	switch {
+	case totals.failed > 0:
+		status = "FAIL"
+	case totals.passed == 0 && totals.skipped > 0:
+		status = "SKIP"
+	}
+	b.sendIPCEvent(map[string]interface{}{
+		"eventType": "testGroupResult",
+		"payload": map[string]interface{}{
+			"groupName":   groupName,
+			"parentNames": parentNames,
+			"status":      status,
+			"totals": map[string]interface{}{
+				"passed":  totals.passed,
+				"failed":  totals.failed,
+				"skipped": totals.skipped,
+			},
+		},
+	})
+}
+
+func (b *BunTestDefinition) sendIPCEvent(event map[string]interface{}) {
+	if b.ipcWriter == nil {
+		b.logger.Debug("IPC writer not initialized, skipping event: %v", event)
+		return
+	}
+
+	if err := b.ipcWriter.WriteEvent(event); err != nil {
+		b.logger.Debug("Failed to write IPC event: %v", err)
+	}
+}
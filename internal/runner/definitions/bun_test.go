@@ -0,0 +1,228 @@
+package definitions
+
+import (
+	"io"
+	"path/filepath"
+	"strings"
+	"testing"
+	"testing/iotest"
+)
+
+// bunOutput is a run of bun test with its output piped, so results are marked with
+// words, across a file with a describe block, one without and one that throws
+const bunOutput = `bun test v1.1.29 (6d43b366)
+
+test/cart.test.ts:
+adding widget to cart
+(pass) cart > adds an item [0.21ms]
+ 7 |     expect([1].length).toBe(1);
+ 8 |   });
+ 9 |
+10 |   test("applies a discount", () => {
+11 |     expect(90).toBe(80);
+                    ^
+error: expect(received).toBe(expected)
+
+Expected: 80
+Received: 90
+
+      at <anonymous> (/app/test/cart.test.ts:11:16)
+(fail) cart > applies a discount [0.61ms]
+(skip) cart > ships abroad
+
+test/math.test.ts:
+(pass) adds numbers [0.05ms]
+(todo) divides by zero
+
+test/broken.test.ts:
+
+# Unhandled error between tests
+-------------------------------
+1 | throw new Error("boom");
+          ^
+error: boom
+      at /app/test/broken.test.ts:1:7
+-------------------------------
+
+
+ 2 pass
+ 1 skip
+ 1 todo
+ 1 fail
+ 1 error
+ 3 expect() calls
+Ran 5 tests across 3 files. [40.00ms]
+`
+
+// runBun parses bun test output read through reader and returns its events
+func runBun(t *testing.T, reader func(io.Reader) io.Reader, output string) *TestIPCCapture {
+	t.Helper()
+	def := NewBunTestDefinition(createTestLogger(t))
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(reader(strings.NewReader(output)), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	return NewTestIPCCapture(ipcPath)
+}
+
+func wholeReader(r io.Reader) io.Reader { return r }
+
+func TestBunTestDefinition_Detect(t *testing.T) {
+	def := NewBunTestDefinition(createTestLogger(t))
+
+	tests := []struct {
+		name     string
+		args     []string
+		expected bool
+	}{
+		{"bun test", []string{"bun", "test"}, true},
+		{"bun test with paths", []string{"bun", "test", "test/cart", "--timeout", "500"}, true},
+		{"full path to bun", []string{"/home/dev/.bun/bin/bun", "test"}, true},
+		{"bun.exe", []string{`C:\Users\dev\.bun\bin\bun.exe`, "test"}, true},
+		{"bun running a script", []string{"bun", "run", "test"}, false},
+		{"bun running jest", []string{"bun", "jest"}, false},
+		{"bunx", []string{"bunx", "test"}, false},
+		{"package manager", []string{"test"}, false},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if result := def.Detect(tt.args); result != tt.expected {
+				t.Errorf("Detect(%v) = %v, expected %v", tt.args, result, tt.expected)
+			}
+		})
+	}
+}
+
+func TestBunTestDefinition_ProcessOutput(t *testing.T) {
+	capture := runBun(t, wholeReader, bunOutput)
+	cartFile, _ := filepath.Abs(filepath.Join("test", "cart.test.ts"))
+	mathFile, _ := filepath.Abs(filepath.Join("test", "math.test.ts"))
+	brokenFile, _ := filepath.Abs(filepath.Join("test", "broken.test.ts"))
+
+	tests := make(map[string]map[string]interface{})
+	for _, e := range capture.GetEventsByType("testCase") {
+		payload := e["payload"].(map[string]interface{})
+		tests[payload["testName"].(string)] = payload
+	}
+	if len(tests) != 5 {
+		t.Fatalf("Expected 5 tests, got %d: %v", len(tests), tests)
+	}
+
+	added := tests["adds an item"]
+	if added["status"] != "PASS" || added["duration"] != 0.21 || added["stdout"] != "adding widget to cart\n" {
+		t.Errorf("Expected adds an item to pass in 0.21ms with its output, got %v", added)
+	}
+	if parents := added["parentNames"].([]interface{}); len(parents) != 2 || parents[0] != cartFile || parents[1] != "cart" {
+		t.Errorf("Expected adds an item under %s > cart, got %v", cartFile, parents)
+	}
+
+	discount := tests["applies a discount"]
+	testError, _ := discount["error"].(map[string]interface{})
+	if discount["status"] != "FAIL" || testError == nil {
+		t.Fatalf("Expected applies a discount to fail with an error, got %v", discount)
+	}
+	if testError["message"] != "expect(received).toBe(expected)\n\nExpected: 80\nReceived: 90" {
+		t.Errorf("Expected the assertion message without the source snippet, got %q", testError["message"])
+	}
+	if testError["expected"] != "80" || testError["actual"] != "90" {
+		t.Errorf("Expected 80 and 90, got %v", testError)
+	}
+	if testError["file"] != "/app/test/cart.test.ts" || testError["line"] != float64(11) || testError["stack"] != "<anonymous> (/app/test/cart.test.ts:11:16)" {
+		t.Errorf("Expected the location and stack, got %v", testError)
+	}
+	if _, ok := discount["stdout"]; ok {
+		t.Errorf("Expected the error block not to be the test's output, got %q", discount["stdout"])
+	}
+
+	if skipped := tests["ships abroad"]; skipped["status"] != "SKIP" {
+		t.Errorf("Expected ships abroad to be skipped, got %v", skipped)
+	}
+	if todo := tests["divides by zero"]; todo["status"] != "SKIP" || todo["skipReason"] != "todo" {
+		t.Errorf("Expected divides by zero to be skipped as todo, got %v", todo)
+	}
+	if parents := tests["adds numbers"]["parentNames"].([]interface{}); len(parents) != 1 || parents[0] != mathFile {
+		t.Errorf("Expected adds numbers directly under %s, got %v", mathFile, parents)
+	}
+
+	results := make(map[string]map[string]interface{})
+	for _, e := range capture.GetEventsByType("testGroupResult") {
+		payload := e["payload"].(map[string]interface{})
+		results[payload["groupName"].(string)] = payload
+	}
+	cart := results["cart"]
+	totals, _ := cart["totals"].(map[string]interface{})
+	if cart["status"] != "FAIL" || totals["passed"] != float64(1) || totals["failed"] != float64(1) || totals["skipped"] != float64(1) {
+		t.Errorf("Expected cart to fail with 1 passed, 1 failed and 1 skipped, got %v", cart)
+	}
+	if results[cartFile]["status"] != "FAIL" || results[mathFile]["status"] != "PASS" {
+		t.Errorf("Expected cart.test.ts to fail and math.test.ts to pass, got %v", results)
+	}
+
+	errors := capture.GetEventsByType("testGroupError")
+	if len(errors) != 1 {
+		t.Fatalf("Expected broken.test.ts to error, got %v", errors)
+	}
+	groupError := errors[0]["payload"].(map[string]interface{})
+	if groupError["groupName"] != brokenFile || groupError["error"].(map[string]interface{})["message"] != "boom" {
+		t.Errorf("Expected %s to error with boom, got %v", brokenFile, groupError)
+	}
+	if _, ok := results[brokenFile]; ok {
+		t.Errorf("Expected no result after the group error, got %v", results[brokenFile])
+	}
+
+	if len(capture.GetEventsByType("runComplete")) != 1 {
+		t.Error("Expected a runComplete event")
+	}
+}
+
+func TestBunTestDefinition_ProcessOutput_Chunking(t *testing.T) {
+	// bun starts fast enough that a whole run often arrives in one read; the groups
+	// must not depend on how the output was split
+	whole := runBun(t, wholeReader, bunOutput).GetEvents()
+	byteAtATime := runBun(t, iotest.OneByteReader, bunOutput).GetEvents()
+	if len(whole) == 0 || len(whole) != len(byteAtATime) {
+		t.Fatalf("Expected the same events however the output was read, got %d and %d", len(whole), len(byteAtATime))
+	}
+	for i := range whole {
+		if whole[i]["eventType"] != byteAtATime[i]["eventType"] {
+			t.Errorf("Event %d: %v read whole, %v a byte at a time", i, whole[i], byteAtATime[i])
+		}
+	}
+}
+
+func TestBunTestDefinition_ProcessOutput_Terminal(t *testing.T) {
+	// Forced colors, as in FORCE_COLOR=1 bun test, with symbols for the results
+	output := "test/slow.test.ts:\n" +
+		"\x1b[0m\x1b[32m✓\x1b[0m slow > warms the cache \x1b[0m\x1b[2m[1.50s]\x1b[0m\n" +
+		"error: Test \"times out\" timed out after 5000ms\n" +
+		"\x1b[0m\x1b[31m✗\x1b[0m slow > times out \x1b[0m\x1b[2m[5001.00ms]\x1b[0m\n" +
+		"\x1b[0m\x1b[33m»\x1b[0m slow > later\n"
+	capture := runBun(t, wholeReader, output)
+
+	tests := make(map[string]map[string]interface{})
+	for _, e := range capture.GetEventsByType("testCase") {
+		payload := e["payload"].(map[string]interface{})
+		tests[payload["testName"].(string)] = payload
+	}
+	if warm := tests["warms the cache"]; warm["status"] != "PASS" || warm["duration"] != float64(1500) {
+		t.Errorf("Expected warms the cache to pass in 1500ms, got %v", warm)
+	}
+	testError, _ := tests["times out"]["error"].(map[string]interface{})
+	if tests["times out"]["status"] != "FAIL" || testError == nil || testError["errorType"] != "TIMED_OUT" {
+		t.Errorf("Expected times out to fail as TIMED_OUT, got %v", tests["times out"])
+	}
+	if later := tests["later"]; later["status"] != "SKIP" {
+		t.Errorf("Expected later to be skipped, got %v", later)
+	}
+
+	// The file is ended by the end of the output when the counts are missing
+	slowFile, _ := filepath.Abs(filepath.Join("test", "slow.test.ts"))
+	var finished bool
+	for _, e := range capture.GetEventsByType("testGroupResult") {
+		finished = finished || e["payload"].(map[string]interface{})["groupName"] == slowFile
+	}
+	if !finished {
+		t.Errorf("Expected a result for %s", slowFile)
+	}
+}
@@ -0,0 +1,59 @@
+package definitions
+
+import (
+	"io"
+)
+
+// BunTestWrapper wraps BunTestDefinition to implement the Definition interface from runner package
+type BunTestWrapper struct {
+	*BunTestDefinition
+}
+
+// NewBunTestWrapper creates a new wrapper for bun test
+func NewBunTestWrapper(impl *BunTestDefinition) *BunTestWrapper {
+	return &BunTestWrapper{BunTestDefinition: impl}
+}
+
+// Matches checks if this runner can handle the given command
+func (b *BunTestWrapper) Matches(command []string) bool {
+	return b.Detect(command)
+}
+
+// GetTestFiles returns list of test files (empty for dynamic discovery)
+func (b *BunTestWrapper) GetTestFiles(args []string) ([]string, error) {
+	return b.BunTestDefinition.GetTestFiles(args)
+}
+
+// BuildCommand returns the bun test command unchanged
+func (b *BunTestWrapper) BuildCommand(args []string, adapterPath string) []string {
+	// bun test uses native processing, no adapter needed
+	return b.ModifyCommand(args, "", "")
+}
+
+// GetAdapterFileName returns empty as bun test doesn't use an adapter
+func (b *BunTestWrapper) GetAdapterFileName() string {
+	return ""
+}
+
+// InterpretExitCode maps exit codes to success/failure
+func (b *BunTestWrapper) InterpretExitCode(code int) string {
+	if code == 0 {
+		return "success"
+	}
+	return "failure"
+}
+
+// IsNative returns true as the text output is processed directly
+func (b *BunTestWrapper) IsNative() bool {
+	return true
+}
+
+// GetNativeDefinition returns the underlying bun test definition
+func (b *BunTestWrapper) GetNativeDefinition() interface{} {
+	return b.BunTestDefinition
+}
+
+// ProcessOutput processes the bun test output
+func (b *BunTestWrapper) ProcessOutput(stdout io.Reader, ipcPath string) error {
+	return b.BunTestDefinition.ProcessOutput(stdout, ipcPath)
+}
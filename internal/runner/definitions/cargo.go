@@ -5,28 +5,119 @@ import (
 	"encoding/json"
 	"fmt"
 	"io"
+	"os"
+	"path/filepath"
 	"regexp"
+	"slices"
 	"sort"
+	"strconv"
 	"strings"
 	"sync"
 	"time"
 
+	"github.com/zk/3pio/internal/console"
+	"github.com/zk/3pio/internal/ipc"
 	"github.com/zk/3pio/internal/logger"
 )
 
-// Keep these for backwards compatibility if needed
-var runningUnittestsRegex = regexp.MustCompile(`Running unittests .* \(target/.*/deps/(.*?)-[a-f0-9]+\)`)
-var runningIntegrationTestsRegex = regexp.MustCompile(`Running tests/.* \(target/.*/deps/(.*?)-[a-f0-9]+\)`)
+// Keep these for backwards compatibility if needed. The executable is in any target
+// directory: target/<triple>/ for --target, --target-dir's, or /target in cross's container.
+var runningUnittestsRegex = regexp.MustCompile(`Running unittests .* \((?:.*[/\\])?deps[/\\](.*?)-[a-f0-9]+(?:\.exe)?\)`)
+var runningIntegrationTestsRegex = regexp.MustCompile(`Running (?:tests|benches)/.* \((?:.*[/\\])?deps[/\\](.*?)-[a-f0-9]+(?:\.exe)?\)`)
+
+// runningTargetRegex matches cargo's banner for any test binary, e.g.
+// "Running unittests src/lib.rs (target/debug/deps/my_crate-0123456789abcdef)" or
+// "Running tests/api.rs (target/debug/deps/api-0123456789abcdef)". It captures the
+// target's source path, the executable's file name and the target name.
+var runningTargetRegex = regexp.MustCompile(`Running (?:unittests )?(\S+) \((?:.*[/\\])?((.+?)-[a-f0-9]+)(?:\.exe)?\)`)
+
+// binaryExitRegex matches cargo's note for a test binary that failed, e.g.
+// "process didn't exit successfully: `target/debug/deps/foo-1a2b` (signal: 6, SIGABRT: process abort signal)"
+var binaryExitRegex = regexp.MustCompile("process didn't exit successfully: `.*` \\((?:signal: (\\d+), (SIG\\w+)|exit (?:status|code): (\\w+))")
 
 // docTestsRegex matches "Doc-tests crate_name" with optional leading whitespace
 var docTestsRegex = regexp.MustCompile(`^\s*Doc-tests\s+(.+)$`)
 
+// rstestCaseRegex matches a test rstest generates for a #[case], e.g. "case_1", or
+// "case_2_division_by_zero" for #[case::division_by_zero]
+var rstestCaseRegex = regexp.MustCompile(`^case_\d+(_\w+)?$`)
+
+// docTestNameRegex parses rustdoc test names like "src/lib.rs - calculator::add (line 7)".
+// Newer toolchains may append attributes after the line, e.g. " - compile fail".
+var docTestNameRegex = regexp.MustCompile(`^(.+?) - (.*?)\s*\(line (\d+)\)(?: - .+)?$`)
+
+// libtestSectionHeaderRegex matches the header of a libtest failure block, e.g. "---- tests::foo stdout ----"
+var libtestSectionHeaderRegex = regexp.MustCompile(`^---- (.+) (stdout|stderr) ----\s*$`)
+
+// libtestPanicRegex matches the first line of a test panic in captured output.
+// Covers both "panicked at src/lib.rs:10:5:" and the older "panicked at 'msg', src/lib.rs:10:5".
+var libtestPanicRegex = regexp.MustCompile(`^thread '.*' panicked at `)
+
+// Headers of an assert_eq!/assert_ne! panic. Since Rust 1.73 it reads
+// "assertion `left == right` failed: msg" followed by "  left: 4" and " right: 5";
+// older toolchains print "assertion failed: `(left == right)`" with backticked values.
+var (
+	assertionHeaderRegex    = regexp.MustCompile("^assertion `left (==|!=) right` failed(?:: (.*))?$")
+	oldAssertionHeaderRegex = regexp.MustCompile("^assertion failed: `\\(left (==|!=) right\\)`$")
+)
+
+// libtestBacktraceFrameRegex matches a frame line of a RUST_BACKTRACE backtrace, e.g.
+// "   3: rust_edge_cases::tests::test_unwrap_none". Source locations follow on "at" lines.
+var libtestBacktraceFrameRegex = regexp.MustCompile(`^\s*\d+:\s+(.+)$`)
+
+// backtraceRuntimeCrates are the crates whose frames run a test, trimmed from the bottom
+// of a backtrace. No user crate can have their names.
+var backtraceRuntimeCrates = []string{"std::", "core::", "alloc::", "panic_unwind::"}
+
+// backtraceRuntimeSymbols are the other frames that run a test, matched whole as a
+// user crate may have a test module or a clone function: libtest's, and those of the
+// C runtime and the OS that start the test thread
+var backtraceRuntimeSymbols = map[string]bool{
+	"rust_begin_unwind": true, "__rust_begin_short_backtrace": true, "__rust_end_short_backtrace": true, "__rust_try": true,
+	"test::__rust_begin_short_backtrace": true, "test::run_test": true, "test::run_test_in_process": true,
+	"test::run_test_in_spawned_subprocess": true, "test::run_tests": true, "test::console::run_tests_console": true,
+	"test::test_main": true, "test::test_main_static": true, "test::types::RunnableTest::run": true,
+	"main": true, "_start": true, "__libc_start_main": true, "__libc_start_call_main": true, "__libc_start_main_impl": true,
+	"start_thread": true, "clone": true, "clone3": true, "__clone": true, "__clone3": true, "__GI___clone3": true,
+	"_pthread_start": true, "thread_start": true, "BaseThreadInitThunk": true, "RtlUserThreadStart": true, "<unknown>": true,
+}
+
+// backtraceSymbolHashRegex matches the hash RUST_BACKTRACE=full adds to a symbol, e.g.
+// "::h0123456789abcdef"
+var backtraceSymbolHashRegex = regexp.MustCompile(`::h[0-9a-f]{16}$`)
+
+// shouldPanicMismatchRegex parses libtest's note for a #[should_panic(expected = ...)] test
+// whose panic message did not contain the expected substring
+var shouldPanicMismatchRegex = regexp.MustCompile("panic did not (?:contain|include) expected string\\s+panic message: `(.*)`,\\s+expected substring: `(.*)`")
+
+// truncatedTestEventRegex matches the name, result and time at the start of a libtest
+// JSON test event, e.g. {"type":"test","name":"tests::test_dump","event":"failed",...
+var truncatedTestEventRegex = regexp.MustCompile(`^\{"type":"test","name":"((?:[^"\\]|\\.)*)","event":"(\w+)"(?:,"exec_time":([0-9.eE+-]+))?`)
+
+// libtestBenchRegex matches a #[bench] result line in libtest's pretty output, e.g.
+// "test tests::bench_fib ... bench:         212 ns/iter (+/- 7)". Newer toolchains
+// print fractional values and older ones group thousands with commas.
+var libtestBenchRegex = regexp.MustCompile(`^test (\S+) \.\.\. bench:\s+([\d,.]+) ns/iter \(\+/- ([\d,.]+)\)`)
+
+// Source patterns used to find #[should_panic] tests
+var (
+	shouldPanicAttrRegex = regexp.MustCompile(`#\[should_panic\b`)
+	rustFnRegex          = regexp.MustCompile(`^\s*(?:pub(?:\([^)]*\))?\s+)?(?:async\s+)?fn\s+(\w+)`)
+	rustInlineModRegex   = regexp.MustCompile(`^\s*(?:pub(?:\([^)]*\))?\s+)?mod\s+(\w+)\s*\{`)
+)
+
+// docTestsGroupName is the group that holds a crate's doctests, nested under the crate group
+const docTestsGroupName = "doctests"
+
 // CargoTestDefinition implements support for Rust's cargo test runner
 type CargoTestDefinition struct {
 	logger    *logger.FileLogger
 	mu        sync.RWMutex
 	ipcWriter *IPCWriter
 
+	lineLimit     // Longest line of output parsed whole (--max-line-length)
+	workDirectory // Directory cargo runs in
+
 	// Workspace and crate tracking
 	workspaceName    string                     // Name of workspace if detected
 	currentCrate     string                     // Currently executing crate
@@ -37,6 +128,110 @@ type CargoTestDefinition struct {
 	discoveredGroups map[string]bool            // Track discovered groups to avoid duplicates
 	groupStarts      map[string]bool            // Track started groups
 	testStates       map[string]*CargoTestState // Track test state
+	lastStartedTest  string                     // Libtest name of the test that started most recently
+	timedOut         bool                       // Set when 3pio stopped the run after an inactivity timeout
+	interrupted      bool                       // Set when 3pio stopped the run at its --timeout or on Ctrl-C
+
+	// Set from the command and the suite events, to tell a filter that matched no test
+	// apart from a run that had nothing to run
+	hasTestFilter bool     // The command passes a test name filter to libtest
+	testFilters   []string // Name filters and --skip filters, for the report
+	suitesStarted int      // Test binaries (and doctest suites) that started
+	testsInSuites int      // Tests those binaries said they would run
+
+	// Captured output from libtest "---- name stdout ----" failure blocks
+	currentSection *CapturedSection            // Block currently being read, nil outside a block
+	capturedOutput map[string]*CapturedOutput  // Keyed by crate + "::" + full test name
+	failedTests    map[string]*CargoFailedTest // Reported failures, so late blocks can be attached
+
+	// Libtest names (e.g., "tests::test_expected_panic") of #[should_panic] tests found in the sources
+	shouldPanicTests map[string]bool
+
+	// Test targets (lib, bin, test, example, bench) behind each crate group
+	targets         map[string]*CargoTarget // Keyed by crate key
+	artifactTargets map[string]*CargoTarget // From compiler-artifact records, keyed by executable name
+
+	// Root of the cargo workspace (or of the package outside one) that panic locations
+	// are made relative to
+	workspaceRoot string
+
+	// Set when the command runs cross, whose paths are those of the container it builds
+	// and runs the tests in
+	cross bool
+
+	// When the command was first built; snapshot files and coverage reports older than
+	// that aren't the run's
+	started time.Time
+
+	// Output trybuild wrote past libtest's capture, keyed by crate key, from its first
+	// test case line until the crate's failed test takes it
+	trybuildOutput map[string][]string
+
+	// miri's report of undefined behavior in the running test binary, from its header
+	// until miri aborts the binary
+	miriDiagnostic []string
+	miriReading    bool
+
+	// Targets with harness = false that don't report through threepio-reporter, as
+	// normalized target name -> kind. Their output is kept whole, not parsed.
+	customHarnesses map[string]string
+	harnessRun      *customHarnessRun // Custom harness binary running now, nil otherwise
+
+	// The command's cargo flags (before "--"), and the display names of the groups
+	// cargo metadata says will run whose binaries haven't started yet
+	cargoArgs     []string
+	plannedGroups map[string]bool
+
+	// cargo metadata, loaded once for the preflight list and the run
+	metadata       *CargoMetadata // nil when cargo metadata failed
+	metadataLoaded bool
+
+	// Tests from the preflight list (ListCommand) whose binary hasn't run yet, keyed
+	// by the crate key of the binary ("doc:" + crate for doctests)
+	listedTests map[string][]ListedTest
+}
+
+// CargoTarget is the cargo target a test binary was built from
+type CargoTarget struct {
+	Kind        string // "lib", "bin", "test", "example" or "bench"
+	Name        string // Target name (e.g., "integration_test")
+	ManifestDir string // Directory of the package's Cargo.toml, empty if unknown
+}
+
+// CapturedSection is a libtest "---- name stdout ----" block being collected
+type CapturedSection struct {
+	Crate    string   // Crate that was running when the block started
+	TestName string   // Full libtest name (e.g., "tests::test_assertion_failure")
+	Stream   string   // "stdout" or "stderr"
+	Lines    []string // Block contents, including blank lines
+}
+
+// CapturedOutput is the output libtest captured for a single failed test
+type CapturedOutput struct {
+	Stdout string
+	Stderr string
+}
+
+// CargoFailedTest remembers how a failed test was reported
+type CargoFailedTest struct {
+	Name     string
+	Parents  []string
+	Duration float64
+	Message  string // libtest's failure note (e.g., a should_panic mismatch)
+}
+
+// AssertionFailure is a parsed assert_eq! or assert_ne! panic
+type AssertionFailure struct {
+	Kind          string // "assert_eq" or "assert_ne"
+	Left          string // Full left value, which may span several lines
+	Right         string // Full right value, which may span several lines
+	CustomMessage string // Message passed to the macro, if any
+}
+
+// ShouldPanicMismatch is the expected and actual panic message of a failed #[should_panic] test
+type ShouldPanicMismatch struct {
+	Expected string
+	Actual   string
 }
 
 // CrateMetadata stores metadata from Cargo.toml
@@ -62,6 +257,21 @@ type CargoTestInfo struct {
 	Duration float64
 }
 
+// DocTestLocation is the source location parsed from a rustdoc test name
+type DocTestLocation struct {
+	File     string // Source file containing the doc comment (e.g., "src/lib.rs")
+	ItemPath string // Documented item (e.g., "calculator::subtract"), empty for crate docs
+	Line     int    // Line where the doctest code block starts
+}
+
+// PanicLocation is where a test panicked, from the "panicked at" line of its panic message
+type PanicLocation struct {
+	File     string // Relative to the workspace root once resolved, with forward slashes
+	Line     int
+	Column   int
+	External bool // In the cargo registry, the Rust sysroot or otherwise outside the workspace
+}
+
 // CargoTestState tracks the state of a running test
 type CargoTestState struct {
 	Name      string
@@ -72,7 +282,7 @@ type CargoTestState struct {
 
 // CargoTestEvent represents a single event from cargo test --format json output
 type CargoTestEvent struct {
-	Type      string  `json:"type"`  // "suite" or "test"
+	Type      string  `json:"type"`  // "suite", "test" or "bench"
 	Event     string  `json:"event"` // "started", "ok", "failed", "ignored"
 	Name      string  `json:"name,omitempty"`
 	TestCount int     `json:"test_count,omitempty"`
@@ -82,6 +292,42 @@ type CargoTestEvent struct {
 	ExecTime  float64 `json:"exec_time,omitempty"`
 	Stdout    string  `json:"stdout,omitempty"`
 	Stderr    string  `json:"stderr,omitempty"`
+	Message   string  `json:"message,omitempty"`   // Failure note, e.g. a should_panic mismatch
+	Median    float64 `json:"median,omitempty"`    // Bench: median ns/iter
+	Deviation float64 `json:"deviation,omitempty"` // Bench: deviation (+/-) ns/iter
+
+	UndefinedBehavior string `json:"-"` // Crashed: miri's report of undefined behavior in the test
+}
+
+// CargoMessage represents a record from cargo --message-format json output
+type CargoMessage struct {
+	Reason string `json:"reason"` // "compiler-message", "compiler-artifact", "build-finished", ...
+	Target struct {
+		Name string   `json:"name"`
+		Kind []string `json:"kind"` // e.g., ["lib"], ["bin"], ["test"], ["example"]
+	} `json:"target"`
+	Message      *CargoDiagnostic `json:"message,omitempty"`
+	Executable   string           `json:"executable,omitempty"`    // Test binary of a compiler-artifact
+	ManifestPath string           `json:"manifest_path,omitempty"` // Cargo.toml of the artifact's package
+}
+
+// CargoDiagnostic is a rustc diagnostic embedded in a compiler-message record
+type CargoDiagnostic struct {
+	Message  string `json:"message"`
+	Level    string `json:"level"` // "error", "warning", "error: internal compiler error", ...
+	Rendered string `json:"rendered"`
+	Code     *struct {
+		Code string `json:"code"`
+	} `json:"code"`
+	Spans []CargoDiagnosticSpan `json:"spans"`
+}
+
+// CargoDiagnosticSpan is a source location attached to a rustc diagnostic
+type CargoDiagnosticSpan struct {
+	FileName    string `json:"file_name"`
+	LineStart   int    `json:"line_start"`
+	ColumnStart int    `json:"column_start"`
+	IsPrimary   bool   `json:"is_primary"`
 }
 
 // NewCargoTestDefinition creates a new cargo test runner definition
@@ -95,6 +341,12 @@ func NewCargoTestDefinition(logger *logger.FileLogger) *CargoTestDefinition {
 		discoveredGroups: make(map[string]bool),
 		groupStarts:      make(map[string]bool),
 		testStates:       make(map[string]*CargoTestState),
+		capturedOutput:   make(map[string]*CapturedOutput),
+		failedTests:      make(map[string]*CargoFailedTest),
+		targets:          make(map[string]*CargoTarget),
+		artifactTargets:  make(map[string]*CargoTarget),
+		plannedGroups:    make(map[string]bool),
+		listedTests:      make(map[string][]ListedTest),
 	}
 }
 
@@ -103,43 +355,262 @@ func (c *CargoTestDefinition) Name() string {
 	return "cargo"
 }
 
-// Detect checks if the command is for cargo test
+// Detect checks if the command is for cargo test or cargo bench
 func (c *CargoTestDefinition) Detect(args []string) bool {
-	if len(args) < 2 {
+	subcommand := CargoSubcommand(args)
+	if !isLibtestSubcommand(subcommand) {
 		return false
 	}
 
-	// Check for "cargo test" command
-	if args[0] == "cargo" && args[1] == "test" {
-		return true
+	// Criterion benchmarks have their own harness, handled by the criterion runner
+	return subcommand != "bench" || !usesCriterion(c.inWorkDir("Cargo.toml"), args)
+}
+
+// CargoSubcommand returns the subcommand of a cargo or cross invocation ("test",
+// "bench", ...), skipping a "+<toolchain>" override. "cargo miri test" runs the test
+// subcommand under miri, so it is "test", and "cargo llvm-cov [options] test" or
+// "cargo llvm-cov nextest" run theirs with coverage; a plain "cargo llvm-cov" runs the
+// tests too. It returns "" if args don't run cargo.
+func CargoSubcommand(args []string) string {
+	i := cargoSubcommandIndex(args)
+	switch {
+	case i < 0:
+		return ""
+	case args[i] == "llvm-cov":
+		return "test"
 	}
+	return args[i]
+}
 
-	// Check for "cargo +<toolchain> test" command
-	if args[0] == "cargo" && len(args) > 2 && strings.HasPrefix(args[1], "+") && args[2] == "test" {
-		return true
+// UsesLLVMCov reports whether a cargo command measures the code coverage of its tests
+// with cargo llvm-cov
+func UsesLLVMCov(args []string) bool {
+	i := cargoSubcommandIndex(args)
+	return i > 0 && (args[i] == "llvm-cov" || slices.Contains(args[1:i], "llvm-cov"))
+}
+
+// cargoSubcommandIndex returns where the subcommand of a cargo or cross invocation is
+// in args, after a "+<toolchain>" override, miri, and cargo llvm-cov with its options,
+// or -1 if args don't run cargo. It is that of "llvm-cov" when llvm-cov runs the tests
+// without a subcommand.
+func cargoSubcommandIndex(args []string) int {
+	if len(args) < 2 || !(executableBase(args[0]) == "cargo" || UsesCross(args)) {
+		return -1
 	}
 
-	// Check for full path to cargo binary
-	if strings.HasSuffix(args[0], "/cargo") && len(args) > 1 && args[1] == "test" {
-		return true
+	// Check for "cargo +<toolchain> <subcommand>"
+	i := 1
+	if strings.HasPrefix(args[1], "+") {
+		i++
+	}
+	if i < len(args) && args[i] == "miri" {
+		i++
+	}
+	if i >= len(args) {
+		return -1
 	}
+	if args[i] == "llvm-cov" {
+		for j := i + 1; j < len(args) && args[j] != "--"; j++ {
+			if !strings.HasPrefix(args[j], "-") {
+				return j
+			}
+			if cargoValueFlags[args[j]] {
+				j++
+			}
+		}
+	}
+	return i
+}
 
-	// Check for full path with toolchain
-	if strings.HasSuffix(args[0], "/cargo") && len(args) > 2 && strings.HasPrefix(args[1], "+") && args[2] == "test" {
-		return true
+// UsesMiri reports whether a cargo command runs under miri ("cargo miri test"), which
+// interprets the test binaries to catch undefined behavior in unsafe code
+func UsesMiri(args []string) bool {
+	i := cargoSubcommandIndex(args)
+	return i > 0 && args[i-1] == "miri"
+}
+
+// UsesCross reports whether a command runs cross, which builds and runs the tests in a
+// container for another target, taking cargo's arguments
+func UsesCross(args []string) bool {
+	if len(args) == 0 {
+		return false
 	}
+	return executableBase(args[0]) == "cross"
+}
 
-	return false
+// executableBase returns the name of the executable a command runs, without its
+// directory or a Windows .exe suffix, e.g. "cargo" for "/home/dev/.cargo/bin/cargo"
+// or "cargo.exe"
+func executableBase(arg string) string {
+	return strings.TrimSuffix(filepath.Base(strings.ReplaceAll(arg, "\\", "/")), ".exe")
+}
+
+// isLibtestSubcommand checks for the cargo subcommands that run libtest harnesses.
+// cargo bench runs the same harness in bench mode, so it shares this runner.
+func isLibtestSubcommand(arg string) bool {
+	return arg == "test" || arg == "bench"
 }
 
 // ModifyCommand adds JSON output flags to cargo test command
 func (c *CargoTestDefinition) ModifyCommand(cmd []string, ipcPath, runID string) []string {
-	result := make([]string, 0, len(cmd)+6)
+	result := make([]string, 0, len(cmd)+8)
+
+	// Split at the -- separator so cargo flags stay before the test binary flags
+	cargoArgs, testArgs := splitTestArgs(cmd)
+	result = append(result, cargoArgs...)
+
+	c.mu.Lock()
+	c.cargoArgs = cargoArgs
+	c.hasTestFilter = hasLibtestFilter(cargoArgs, testArgs)
+	c.testFilters = libtestFilters(cargoArgs, testArgs)
+	c.cross = UsesCross(cargoArgs)
+	if c.started.IsZero() {
+		// Retries build their commands again, and the first attempt's snapshots still count
+		c.started = time.Now()
+	}
+	c.mu.Unlock()
+
+	// Ask cargo for JSON compiler diagnostics so build errors can be reported
+	if !hasMessageFormatArg(cargoArgs) {
+		result = append(result, "--message-format", "json")
+	}
+
+	result = append(result, "--")
+	result = append(result, testArgs...)
+
+	// Add JSON output flags (RUSTC_BOOTSTRAP=1 is set in orchestrator to enable on stable)
+	result = append(result, "-Z", "unstable-options", "--format", "json", "--report-time")
+
+	return result
+}
+
+// splitTestArgs splits a cargo command at its "--" separator, into the cargo flags
+// and the arguments of the test binaries
+func splitTestArgs(cmd []string) (cargoArgs, testArgs []string) {
+	for i, arg := range cmd {
+		if arg == "--" {
+			return cmd[:i], cmd[i+1:]
+		}
+	}
+	return cmd, nil
+}
+
+// cargoValueFlags are the cargo test flags that take a separate value, so the value
+// isn't mistaken for a TESTNAME filter
+var cargoValueFlags = map[string]bool{
+	"-p": true, "--package": true, "--exclude": true, "--test": true, "--bin": true,
+	"--example": true, "--bench": true, "-F": true, "--features": true, "--target": true,
+	"--target-dir": true, "--manifest-path": true, "-j": true, "--jobs": true,
+	"--profile": true, "--color": true, "--message-format": true, "-Z": true, "--config": true,
+
+	// cargo llvm-cov's, which takes cargo test's flags too
+	"--output-path": true, "--output-dir": true, "--ignore-filename-regex": true, "--dep-coverage": true,
+	"--fail-under-lines": true, "--fail-under-functions": true, "--fail-under-regions": true,
+	"--fail-uncovered-lines": true, "--fail-uncovered-functions": true, "--fail-uncovered-regions": true,
+}
+
+// libtestValueFlags are the libtest flags that take a separate value
+var libtestValueFlags = map[string]bool{
+	"--skip": true, "--test-threads": true, "--format": true, "--logfile": true,
+	"--color": true, "-Z": true, "--shuffle-seed": true,
+}
+
+// hasLibtestFilter reports whether a cargo test command narrows the tests down by name,
+// with cargo's TESTNAME argument or a filter after "--"
+func hasLibtestFilter(cargoArgs, testArgs []string) bool {
+	return len(libtestNameFilters(cargoArgs, testArgs)) > 0
+}
 
-	// Copy original command
-	result = append(result, cmd...)
+// libtestNameFilters returns the test name filters of a cargo test command: cargo's
+// TESTNAME argument and the filters after "--"
+func libtestNameFilters(cargoArgs, testArgs []string) []string {
+	// Skip "cargo", a "+toolchain" override, miri and the subcommand
+	start := cargoSubcommandIndex(cargoArgs) + 1
+	if start == 0 {
+		start = len(cargoArgs)
+	}
+	return append(positionalArgs(cargoArgs, start, cargoValueFlags), positionalArgs(testArgs, 0, libtestValueFlags)...)
+}
+
+// positionalArgs returns the arguments of args from start on that are neither flags
+// nor the values of one
+func positionalArgs(args []string, start int, valueFlags map[string]bool) []string {
+	var positional []string
+	for i := start; i < len(args); i++ {
+		arg := args[i]
+		if !strings.HasPrefix(arg, "-") {
+			positional = append(positional, arg)
+		} else if valueFlags[arg] {
+			i++
+		}
+	}
+	return positional
+}
+
+// libtestFilters describes how a cargo test command narrows its tests down, for the
+// report: its name filters, marked "(exact)" under --exact, then its --skip filters,
+// e.g. ["math::add (exact)", "--skip slow"]. It is nil for a command that runs them all.
+func libtestFilters(cargoArgs, testArgs []string) []string {
+	filters := libtestNameFilters(cargoArgs, testArgs)
+	var skips []string
+	exact := false
+	for i := 0; i < len(testArgs); i++ {
+		switch arg := testArgs[i]; {
+		case arg == "--exact":
+			exact = true
+		case arg == "--skip" && i+1 < len(testArgs):
+			i++
+			skips = append(skips, "--skip "+testArgs[i])
+		case strings.HasPrefix(arg, "--skip="):
+			skips = append(skips, "--skip "+strings.TrimPrefix(arg, "--skip="))
+		case libtestValueFlags[arg]:
+			i++
+		}
+	}
+	if exact {
+		for i := range filters {
+			filters[i] += " (exact)"
+		}
+	}
+	return append(filters, skips...)
+}
+
+// TestFilters returns the name and --skip filters the command narrows its tests down
+// with, nil when it runs them all
+func (c *CargoTestDefinition) TestFilters() []string {
+	c.mu.RLock()
+	defer c.mu.RUnlock()
+	return c.testFilters
+}
+
+// NoTestsMatched reports whether the command's test filters matched no test: every test
+// binary started and ran zero tests, as when a name filter is misspelled or a --skip
+// excludes everything
+func (c *CargoTestDefinition) NoTestsMatched() bool {
+	c.mu.RLock()
+	defer c.mu.RUnlock()
+	return len(c.testFilters) > 0 && c.suitesStarted > 0 && c.testsInSuites == 0
+}
+
+// hasMessageFormatArg checks if the user already chose a cargo --message-format
+func hasMessageFormatArg(args []string) bool {
+	for _, arg := range args {
+		if arg == "--message-format" || strings.HasPrefix(arg, "--message-format=") {
+			return true
+		}
+	}
+	return false
+}
+
+// RerunCommand narrows a cargo test command down to the given failed tests, passing
+// each test's path as an exact libtest filter
+func (c *CargoTestDefinition) RerunCommand(cmd []string, tests []ipc.FailedTest) ([]string, error) {
+	paths := rustTestPaths(tests)
+	if len(paths) == 0 {
+		return nil, fmt.Errorf("the failed tests are all doctests, which cargo test can't select by name")
+	}
 
-	// Check if -- separator already exists
+	result := append([]string(nil), cmd...)
 	hasSeparator := false
 	for _, arg := range cmd {
 		if arg == "--" {
@@ -147,16 +618,39 @@ func (c *CargoTestDefinition) ModifyCommand(cmd []string, ipcPath, runID string)
 			break
 		}
 	}
-
-	// Add separator if not present
 	if !hasSeparator {
 		result = append(result, "--")
 	}
+	result = append(result, "--exact")
+	return append(result, paths...), nil
+}
 
-	// Add JSON output flags (RUSTC_BOOTSTRAP=1 is set in orchestrator to enable on stable)
-	result = append(result, "-Z", "unstable-options", "--format", "json", "--report-time")
-
-	return result
+// rustTestPaths turns failed Rust tests back into libtest names ("tests::test_add"),
+// dropping the crate group. Doctests have no name a filter can match, so they're skipped.
+func rustTestPaths(tests []ipc.FailedTest) []string {
+	var paths []string
+	seen := make(map[string]bool)
+	for _, test := range tests {
+		if len(test.ParentNames) == 0 {
+			continue
+		}
+		isDoctest := false
+		for _, parent := range test.ParentNames {
+			if parent == docTestsGroupName {
+				isDoctest = true
+				break
+			}
+		}
+		if isDoctest {
+			continue
+		}
+		path := strings.Join(append(append([]string(nil), test.ParentNames[1:]...), test.Name), "::")
+		if !seen[path] {
+			seen[path] = true
+			paths = append(paths, path)
+		}
+	}
+	return paths
 }
 
 // GetTestFiles returns empty array for dynamic discovery
@@ -185,73 +679,78 @@ func (c *CargoTestDefinition) ProcessOutput(combinedOutput io.Reader, ipcPath st
 		}
 	}()
 
-	// Load cargo metadata for crate descriptions
-	c.loadCargoMetadata()
+	// Find the targets whose output isn't libtest's, and announce the groups of the
+	// targets the command will run
+	c.mu.Lock()
+	metadata := c.loadCargoMetadata(filepath.Dir(ipcPath))
+	if metadata != nil {
+		c.findCustomHarnesses(metadata)
+		c.createPlannedGroups(metadata)
+	}
+
+	// Find #[should_panic] tests so passes can be marked as expected panics
+	c.shouldPanicTests = c.scanShouldPanicTests(metadata)
+	c.logger.Debug("Found %d #[should_panic] tests", len(c.shouldPanicTests))
+	c.mu.Unlock()
+
+	if cwd := c.workingDir(); cwd != "" {
+		c.workspaceRoot = findCargoWorkspaceRoot(cwd)
+	}
 
 	lineCount := 0
 	jsonEventCount := 0
-	totalBytes := 0
-
-	// Chunk reading with partial line handling for real-time processing
-	// Use LARGER chunks for faster reading to keep up with output
-	buffer := make([]byte, 256*1024) // 256KB chunks for aggressive reading
-	var partial []byte
+	truncatedLines := 0
 
+	// Lines are split off as the output arrives, for real-time processing. Tests may
+	// print any bytes: lines are parsed with invalid UTF-8 replaced as the JSON decoder
+	// does, so a failure block's test name matches the name in the JSON events.
+	lines := c.newLineReader(combinedOutput)
 	for {
-		n, err := combinedOutput.Read(buffer)
-		if n > 0 {
-			totalBytes += n
-			// Append partial line from previous iteration
-			data := append(partial, buffer[:n]...)
-
-			// Split into lines
-			lines := bytes.Split(data, []byte{'\n'})
-
-			// Process all complete lines (all except the last one)
-			for i := 0; i < len(lines)-1; i++ {
-				line := string(lines[i])
-				if len(line) > 0 { // Process non-empty lines
-					lineCount++
-					c.processLineData(line, &jsonEventCount)
-				}
-			}
-
-			// Save the last piece as partial for next iteration
-			// (it might be incomplete if we're in the middle of a line)
-			partial = lines[len(lines)-1]
+		raw, cut, ok := lines.Next()
+		if !ok {
+			break
 		}
-
-		if err != nil {
-			if err == io.EOF {
-				// Process final partial line if it exists
-				if len(partial) > 0 {
-					lineCount++
-					line := string(partial)
-					c.processLineData(line, &jsonEventCount)
-				}
-				c.logger.Debug("Reached EOF after reading %d bytes", totalBytes)
-				break
-			} else if strings.Contains(err.Error(), "file already closed") {
-				// Process final partial line even on pipe closure
-				if len(partial) > 0 {
-					lineCount++
-					line := string(partial)
-					c.processLineData(line, &jsonEventCount)
-				}
-				c.logger.Debug("Pipe closed after reading %d bytes: %v", totalBytes, err)
-				break
-			} else {
-				// Log other errors but continue reading
-				c.logger.Debug("Read error at byte %d: %v, continuing", totalBytes, err)
-				// Try to continue reading even on errors
-				continue
-			}
+		if cut {
+			truncatedLines++
+		}
+		line := console.ValidUTF8(raw)
+		if len(line) > 0 { // Process non-empty lines
+			lineCount++
+			c.processLineData(line, &jsonEventCount)
+		} else if !c.processCapturedSectionLine(line) {
+			// Blank lines only matter inside captured failure blocks and uncaptured reports
+			c.collectUncapturedLine(line)
 		}
 	}
+	if err := lines.Err(); err != nil {
+		c.logger.Debug("Output ended with a read error: %v", err)
+	}
+	if truncatedLines > 0 {
+		c.logger.Debug("Truncated %d lines longer than the maximum line length", truncatedLines)
+	}
 
 	// Log processing summary
 	c.logger.Debug("ProcessOutput completed: %d total lines, %d JSON events processed", lineCount, jsonEventCount)
 
+	// Attach a failure block that ran to the end of the output. A test binary
+	// still running at this point never printed its summary.
+	c.mu.Lock()
+	c.flushCapturedSection()
+	if c.harnessRun != nil {
+		// cargo reports a failed binary before the output ends
+		c.finishHarnessRun("PASS")
+	}
+	if c.interrupted {
+		c.leaveRunningGroupsUnfinished()
+	} else if c.timedOut {
+		c.reportTimedOutTests()
+	} else {
+		c.handleBinaryCrash(0, "", 0)
+	}
+	c.skipUnrunListedTests()
+	c.skipUnrunPlannedGroups()
+	c.mu.Unlock()
+
 	// Send final events for any remaining groups
 	c.finalizePendingGroups()
 
@@ -267,32 +766,47 @@ func (c *CargoTestDefinition) ProcessOutput(combinedOutput io.Reader, ipcPath st
 	return nil
 }
 
-// loadCargoMetadata loads crate metadata from cargo metadata command
-func (c *CargoTestDefinition) loadCargoMetadata() {
-	// TODO: In a full implementation, we would run `cargo metadata --format-version 1`
-	// and parse the JSON output to get crate descriptions. For now, we'll use
-	// a simplified approach that could be extended later.
-	c.logger.Debug("Loading cargo metadata (placeholder for now)")
-}
-
 // processLineData processes a single line of cargo test output
 func (c *CargoTestDefinition) processLineData(line string, jsonEventCount *int) {
-	// Check if this is a "Running unittests" line from stderr
-	if matches := runningUnittestsRegex.FindStringSubmatch(line); matches != nil {
-		crateName := matches[1]
+	// A custom harness prints no libtest output, so none of its lines are parsed
+	if c.processHarnessLine(line) {
+		return
+	}
+
+	// Lines inside a libtest failure block belong to that test's captured output
+	if c.processCapturedSectionLine(line) {
+		return
+	}
+
+	// Check if this is a "Running ..." banner from stderr, which starts a test binary
+	if matches := runningTargetRegex.FindStringSubmatch(line); matches != nil {
 		c.mu.Lock()
-		c.currentCrate = crateName
-		c.logger.Debug("Set current crate to: %s (unit tests)", crateName)
+		target := c.resolveTarget(matches[1], matches[2], matches[3])
+		c.currentCrate = c.targetGroupKey(target)
+		c.logger.Debug("Set current crate to: %s (%s target %s)", c.currentCrate, target.Kind, target.Name)
+		c.markPlannedGroupRun(c.currentCrate)
+		delete(c.listedTests, c.currentCrate)
+		delete(c.trybuildOutput, c.currentCrate)
+		c.miriDiagnostic, c.miriReading = nil, false
+		if c.isCustomHarness(target) {
+			c.startHarnessRun(c.currentCrate)
+			c.currentCrate = ""
+		}
 		c.mu.Unlock()
 		return
 	}
 
-	// Check if this is a "Running tests/..." line for integration tests
-	if matches := runningIntegrationTestsRegex.FindStringSubmatch(line); matches != nil {
-		testName := matches[1]
+	// A test binary that exits before its suite summary crashed mid-run
+	if matches := binaryExitRegex.FindStringSubmatch(line); matches != nil {
+		signal, _ := strconv.Atoi(matches[1])
+		exitCode, _ := strconv.ParseInt(matches[3], 0, 64)
 		c.mu.Lock()
-		c.currentCrate = testName
-		c.logger.Debug("Set current crate to: %s (integration tests)", testName)
+		if c.interrupted {
+			// The binary died from the interrupt 3pio forwarded
+			c.leaveRunningGroupsUnfinished()
+		} else {
+			c.handleBinaryCrash(signal, matches[2], int(exitCode))
+		}
 		c.mu.Unlock()
 		return
 	}
@@ -306,6 +820,7 @@ func (c *CargoTestDefinition) processLineData(line string, jsonEventCount *int)
 			c.mu.Lock()
 			c.currentCrate = docCrateName
 			c.logger.Debug("Set current crate to: %s (doc tests)", docCrateName)
+			delete(c.listedTests, docCrateName)
 			c.mu.Unlock()
 			return
 		} else {
@@ -313,18 +828,942 @@ func (c *CargoTestDefinition) processLineData(line string, jsonEventCount *int)
 		}
 	}
 
-	// Try to parse as JSON event
-	var event CargoTestEvent
-	if err := json.Unmarshal([]byte(line), &event); err != nil {
-		// Not JSON, might be compilation output or other messages
-		return
+	// Records from cargo --message-format json (compiler diagnostics, artifacts)
+	// carry a "reason" field instead of a test event type
+	if strings.Contains(line, `"reason":`) {
+		var msg CargoMessage
+		if err := json.Unmarshal([]byte(line), &msg); err == nil && msg.Reason != "" {
+			c.processCargoMessage(&msg)
+			return
+		}
+	}
+
+	// Bench results printed by libtest's pretty formatter
+	if matches := libtestBenchRegex.FindStringSubmatch(line); matches != nil {
+		event := CargoTestEvent{
+			Type:      "bench",
+			Name:      matches[1],
+			Median:    parseBenchNanos(matches[2]),
+			Deviation: parseBenchNanos(matches[3]),
+		}
+		if err := c.processEvent(&event); err != nil {
+			c.logger.Debug("Error processing bench result: %v", err)
+		}
+		return
+	}
+
+	// Try to parse as JSON event
+	var event CargoTestEvent
+	if err := json.Unmarshal([]byte(line), &event); err != nil {
+		truncated, ok := parseTruncatedTestEvent(line)
+		if !ok {
+			// Not JSON, might be compilation output, trybuild's, miri's or other messages
+			c.collectUncapturedLine(line)
+			return
+		}
+		event = truncated
+	}
+
+	*jsonEventCount++
+	// Process the JSON event with current crate context
+	if err := c.processEvent(&event); err != nil {
+		c.logger.Debug("Error processing cargo test event: %v", err)
+	}
+}
+
+// parseTruncatedTestEvent reads a libtest test event cut at the maximum line length,
+// as when a test prints a huge blob. libtest writes the test's name and result before
+// its output, so those survive and the output is left to output.log.
+func parseTruncatedTestEvent(line string) (CargoTestEvent, bool) {
+	if !strings.HasSuffix(line, LineTruncatedMarker) {
+		return CargoTestEvent{}, false
+	}
+	matches := truncatedTestEventRegex.FindStringSubmatch(line)
+	if matches == nil {
+		return CargoTestEvent{}, false
+	}
+	event := CargoTestEvent{Type: "test", Event: matches[2], Stdout: "(output cut, see output.log)"}
+	if err := json.Unmarshal([]byte(`"`+matches[1]+`"`), &event.Name); err != nil {
+		return CargoTestEvent{}, false
+	}
+	event.ExecTime, _ = strconv.ParseFloat(matches[3], 64)
+	return event, true
+}
+
+// collectUncapturedLine keeps a line the test binary printed past libtest's capture
+// when trybuild or miri wrote it about the running test
+func (c *CargoTestDefinition) collectUncapturedLine(line string) {
+	c.collectTrybuildLine(line)
+	c.collectMiriLine(line)
+}
+
+// collectTrybuildLine keeps a line of the current crate's output once trybuild checked
+// a test case in it, as trybuild writes to stderr past libtest's capture
+func (c *CargoTestDefinition) collectTrybuildLine(line string) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	if _, ok := c.trybuildOutput[c.currentCrate]; !ok {
+		if !isTrybuildTestLine(line) {
+			return
+		}
+		if c.trybuildOutput == nil {
+			c.trybuildOutput = make(map[string][]string)
+		}
+	}
+	c.trybuildOutput[c.currentCrate] = append(c.trybuildOutput[c.currentCrate], line)
+}
+
+// collectMiriLine keeps the lines of miri's report of undefined behavior, which it
+// prints before it aborts the test binary
+func (c *CargoTestDefinition) collectMiriLine(line string) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	line = strings.TrimRight(console.StripANSI(line), "\r")
+	switch {
+	case strings.HasPrefix(line, miriUndefinedBehaviorHeader):
+		c.miriDiagnostic, c.miriReading = []string{line}, true
+	case c.miriReading && strings.HasPrefix(line, miriAbortNote):
+		c.miriReading = false
+	case c.miriReading:
+		c.miriDiagnostic = append(c.miriDiagnostic, line)
+	}
+}
+
+// processCapturedSectionLine collects libtest's "---- name stdout ----" failure
+// blocks line by line. It returns true when the line belongs to a block.
+func (c *CargoTestDefinition) processCapturedSectionLine(line string) bool {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	line = strings.TrimRight(line, "\r")
+	if matches := libtestSectionHeaderRegex.FindStringSubmatch(line); matches != nil {
+		// Back-to-back failures: a new header ends the previous block
+		c.flushCapturedSection()
+		c.currentSection = &CapturedSection{
+			Crate:    c.currentCrate,
+			TestName: matches[1],
+			Stream:   matches[2],
+		}
+		return true
+	}
+
+	if c.currentSection == nil {
+		return false
+	}
+
+	if isCapturedSectionEnd(line) {
+		c.flushCapturedSection()
+		return false
+	}
+
+	c.currentSection.Lines = append(c.currentSection.Lines, line)
+	return true
+}
+
+// isCapturedSectionEnd reports whether a line ends the current failure block
+func isCapturedSectionEnd(line string) bool {
+	switch {
+	case line == "failures:" || line == "successes:":
+		return true
+	case strings.HasPrefix(line, "test result:"):
+		return true
+	case strings.HasPrefix(line, "{"):
+		// Back to JSON events
+		return true
+	case runningTargetRegex.MatchString(line) || docTestsRegex.MatchString(line) || binaryExitRegex.MatchString(line):
+		return true
+	}
+	return false
+}
+
+// flushCapturedSection stores the block being collected and, if its test was
+// already reported as failed, re-sends the test with the output attached.
+// Caller must hold c.mu.
+func (c *CargoTestDefinition) flushCapturedSection() {
+	section := c.currentSection
+	if section == nil {
+		return
+	}
+	c.currentSection = nil
+
+	// Blank lines inside the block are kept, surrounding ones are not
+	content := strings.Trim(strings.Join(section.Lines, "\n"), "\n")
+	if content == "" {
+		return
+	}
+
+	key := section.Crate + "::" + section.TestName
+	captured, ok := c.capturedOutput[key]
+	if !ok {
+		captured = &CapturedOutput{}
+		c.capturedOutput[key] = captured
+	}
+	if section.Stream == "stderr" {
+		captured.Stderr = content
+	} else {
+		captured.Stdout = content
+	}
+
+	if failed, ok := c.failedTests[key]; ok {
+		c.sendFailedTestCase(section.Crate, failed.Name, failed.Parents, failed.Duration, captured.Stdout, captured.Stderr, failed.Message)
+	}
+}
+
+// splitCapturedOutput separates a failed test's captured stdout into what the test
+// printed and the panic message libtest appended after it
+func splitCapturedOutput(captured string) (printed, panicMessage string) {
+	lines := strings.Split(captured, "\n")
+	for i, line := range lines {
+		if libtestPanicRegex.MatchString(line) {
+			printed = strings.Trim(strings.Join(lines[:i], "\n"), "\n")
+			panicMessage = strings.TrimSpace(strings.Join(lines[i:], "\n"))
+			return printed, panicMessage
+		}
+	}
+	return strings.Trim(captured, "\n"), ""
+}
+
+// splitBacktrace separates a panic message from the "stack backtrace:" block that
+// RUST_BACKTRACE=1 adds after it. The backtrace is trimmed of the runtime frames
+// below the test and of libtest's closing note.
+func splitBacktrace(panicMessage string) (message, backtrace string) {
+	lines := strings.Split(panicMessage, "\n")
+	start := -1
+	for i, line := range lines {
+		if strings.TrimSpace(line) == "stack backtrace:" {
+			start = i
+			break
+		}
+	}
+	if start < 0 {
+		return panicMessage, ""
+	}
+
+	message = strings.TrimSpace(strings.Join(lines[:start], "\n"))
+	frames := lines[start+1:]
+	for len(frames) > 0 {
+		last := strings.TrimSpace(frames[len(frames)-1])
+		if last != "" && !strings.HasPrefix(last, "note:") {
+			break
+		}
+		frames = frames[:len(frames)-1]
+	}
+
+	// Drop frames from the bottom until the first one that isn't runtime code
+	end := len(frames)
+	for i := len(frames) - 1; i >= 0; i-- {
+		matches := libtestBacktraceFrameRegex.FindStringSubmatch(frames[i])
+		if matches == nil {
+			continue
+		}
+		if !isBacktraceRuntimeFrame(matches[1]) {
+			break
+		}
+		end = i
+	}
+	if end == 0 {
+		// The panic never reached test code, so there is nothing to trim to
+		end = len(frames)
+	}
+	backtrace = strings.Trim(strings.Join(frames[:end], "\n"), "\n")
+	return message, backtrace
+}
+
+// isBacktraceRuntimeFrame reports whether a backtrace symbol belongs to std, core or libtest,
+// including trait impls like "<F as core::ops::function::FnOnce<()>>::call_once" and
+// closures like "test::run_test::{{closure}}"
+func isBacktraceRuntimeFrame(symbol string) bool {
+	if backtraceRuntimeSymbols[symbol] {
+		return true
+	}
+	symbol = backtraceSymbolHashRegex.ReplaceAllString(symbol, "")
+	for strings.HasSuffix(symbol, "::{{closure}}") {
+		symbol = strings.TrimSuffix(symbol, "::{{closure}}")
+	}
+	symbol = strings.TrimPrefix(symbol, "<")
+	if _, impl, ok := strings.Cut(symbol, " as "); ok {
+		symbol = impl
+	}
+	if backtraceRuntimeSymbols[symbol] {
+		return true
+	}
+	for _, crate := range backtraceRuntimeCrates {
+		if strings.HasPrefix(symbol, crate) {
+			return true
+		}
+	}
+	return false
+}
+
+// parseAssertionFailure recognizes the assert_eq!/assert_ne! panic format in a panic
+// message, or returns nil for other panics
+func parseAssertionFailure(panicMessage string) *AssertionFailure {
+	lines := panicPayloadLines(panicMessage)
+	if len(lines) == 0 {
+		return nil
+	}
+
+	if matches := assertionHeaderRegex.FindStringSubmatch(lines[0]); matches != nil {
+		failure := &AssertionFailure{Kind: assertionKind(matches[1])}
+		var message []string
+		if matches[2] != "" {
+			message = append(message, matches[2])
+		}
+		left := 1
+		for left < len(lines) && !strings.HasPrefix(lines[left], "  left: ") {
+			// A custom message may span lines
+			message = append(message, lines[left])
+			left++
+		}
+		right := left + 1
+		for right < len(lines) && !strings.HasPrefix(lines[right], " right: ") {
+			right++
+		}
+		if right >= len(lines) {
+			return nil
+		}
+		failure.CustomMessage = strings.TrimSpace(strings.Join(message, "\n"))
+		failure.Left = strings.TrimPrefix(strings.Join(lines[left:right], "\n"), "  left: ")
+		failure.Right = strings.TrimPrefix(strings.Join(lines[right:], "\n"), " right: ")
+		return failure
+	}
+
+	if matches := oldAssertionHeaderRegex.FindStringSubmatch(lines[0]); matches != nil {
+		if len(lines) < 3 || !strings.HasPrefix(lines[1], "  left: `") {
+			return nil
+		}
+		right := 2
+		for right < len(lines) && !strings.HasPrefix(lines[right], " right: `") {
+			right++
+		}
+		if right >= len(lines) {
+			return nil
+		}
+		left := strings.TrimPrefix(strings.Join(lines[1:right], "\n"), "  left: `")
+		rightText := strings.TrimPrefix(strings.Join(lines[right:], "\n"), " right: `")
+
+		// The right value closes with a backtick, optionally followed by ": msg"
+		for i := 0; i < len(rightText); i++ {
+			if rightText[i] != '`' {
+				continue
+			}
+			rest := rightText[i+1:]
+			if rest != "" && !strings.HasPrefix(rest, ": ") {
+				continue
+			}
+			return &AssertionFailure{
+				Kind:          assertionKind(matches[1]),
+				Left:          strings.TrimSuffix(left, "`,"),
+				Right:         rightText[:i],
+				CustomMessage: strings.TrimPrefix(rest, ": "),
+			}
+		}
+	}
+
+	return nil
+}
+
+// assertionKind names the macro behind an assertion operator
+func assertionKind(operator string) string {
+	if operator == "!=" {
+		return "assert_ne"
+	}
+	return "assert_eq"
+}
+
+// panicPayloadLines returns the lines of what a test panicked with, without the
+// "thread '...' panicked at" header and the trailing "note: ..." hints
+func panicPayloadLines(panicMessage string) []string {
+	lines := strings.Split(strings.TrimSpace(panicMessage), "\n")
+	if !libtestPanicRegex.MatchString(lines[0]) {
+		return nil
+	}
+
+	var payload []string
+	if _, quoted, ok := strings.Cut(lines[0], "panicked at '"); ok {
+		// Older toolchains quote the payload: "panicked at 'msg', src/lib.rs:10:5"
+		text := strings.Join(append([]string{quoted}, lines[1:]...), "\n")
+		end := strings.LastIndex(text, "', ")
+		if end < 0 {
+			return nil
+		}
+		payload = strings.Split(text[:end], "\n")
+	} else {
+		payload = lines[1:]
+	}
+
+	for len(payload) > 0 {
+		last := payload[len(payload)-1]
+		if strings.TrimSpace(last) != "" && !strings.HasPrefix(last, "note: ") {
+			break
+		}
+		payload = payload[:len(payload)-1]
+	}
+	return payload
+}
+
+// rustSourceLocationRegex matches a "file:line:column" source location
+var rustSourceLocationRegex = regexp.MustCompile(`^(.+):(\d+):(\d+)$`)
+
+// parsePanicLocation returns where a test panicked, as printed by libtest: at the end
+// of "thread '...' panicked at src/lib.rs:10:5:", or after the quoted payload of older
+// toolchains ("panicked at 'msg', src/lib.rs:10:5"). It returns nil if there is none.
+func parsePanicLocation(panicMessage string) *PanicLocation {
+	lines := strings.Split(strings.TrimSpace(panicMessage), "\n")
+	if !libtestPanicRegex.MatchString(lines[0]) {
+		return nil
+	}
+
+	var location string
+	if _, rest, _ := strings.Cut(lines[0], " panicked at "); !strings.HasPrefix(rest, "'") {
+		location = strings.TrimSuffix(strings.TrimSpace(rest), ":")
+	} else {
+		for _, line := range lines {
+			if i := strings.LastIndex(line, "', "); i >= 0 {
+				location = strings.TrimSpace(line[i+len("', "):])
+			}
+		}
+	}
+
+	matches := rustSourceLocationRegex.FindStringSubmatch(location)
+	if matches == nil {
+		return nil
+	}
+	line, _ := strconv.Atoi(matches[2])
+	column, _ := strconv.Atoi(matches[3])
+	return &PanicLocation{File: matches[1], Line: line, Column: column}
+}
+
+// resolvePanicLocation makes a panic location relative to the workspace root. rustc
+// names the files of workspace members relative to the workspace root but those of a
+// package built on its own relative to the package, so a path that doesn't exist under
+// the root is looked up in the crate's manifest directory. Files of dependencies and of
+// the standard library are marked external. Caller must hold c.mu.
+func (c *CargoTestDefinition) resolvePanicLocation(location *PanicLocation, crateKey string) {
+	manifestDir := ""
+	if target, ok := c.targets[crateKey]; ok {
+		manifestDir = target.ManifestDir
+	}
+	location.File, location.External = resolveRustSourcePath(c.hostPath(location.File), c.workspaceRoot, manifestDir)
+}
+
+// Where cross mounts the workspace and its target directory in its container
+const (
+	crossProjectDir = "/project"
+	crossTargetDir  = "/target"
+)
+
+// hostPath translates a path in cross's container to the file on the host. Paths of
+// other runs, paths outside the mounted directories and paths whose file the host
+// doesn't have are returned as they are, and are then reported as external, unlinked.
+// Caller must hold c.mu.
+func (c *CargoTestDefinition) hostPath(path string) string {
+	if !c.cross {
+		return path
+	}
+	workspaceRoot, targetDir := c.workspaceRoot, ""
+	if c.metadata != nil {
+		if c.metadata.WorkspaceRoot != "" {
+			workspaceRoot = c.metadata.WorkspaceRoot
+		}
+		targetDir = c.metadata.TargetDirectory
+	}
+	if targetDir == "" && workspaceRoot != "" {
+		targetDir = filepath.Join(workspaceRoot, "target")
+	}
+
+	for _, mount := range []struct{ container, host string }{
+		{crossProjectDir, workspaceRoot},
+		{crossTargetDir, targetDir},
+	} {
+		rest, ok := strings.CutPrefix(path, mount.container+"/")
+		if !ok || mount.host == "" {
+			continue
+		}
+		hostPath := filepath.Join(mount.host, filepath.FromSlash(rest))
+		if _, err := os.Stat(hostPath); err != nil {
+			return path
+		}
+		return hostPath
+	}
+	return path
+}
+
+// resolveRustSourcePath returns a source path relative to workspaceRoot, with forward
+// slashes, and whether it is outside the workspace
+func resolveRustSourcePath(file, workspaceRoot, manifestDir string) (string, bool) {
+	if isExternalRustSource(file) {
+		return filepath.ToSlash(file), true
+	}
+	if workspaceRoot == "" {
+		return filepath.ToSlash(file), false
+	}
+
+	path := filepath.FromSlash(file)
+	if !filepath.IsAbs(path) {
+		if _, err := os.Stat(filepath.Join(workspaceRoot, path)); err != nil && manifestDir != "" {
+			path = filepath.Join(manifestDir, path)
+		} else {
+			path = filepath.Join(workspaceRoot, path)
+		}
+	}
+	rel, err := filepath.Rel(workspaceRoot, path)
+	if err != nil || rel == ".." || strings.HasPrefix(rel, ".."+string(filepath.Separator)) {
+		// e.g. a path dependency next to the workspace
+		return filepath.ToSlash(path), true
+	}
+	return filepath.ToSlash(rel), false
+}
+
+// isExternalRustSource reports whether a source path belongs to the Rust sysroot
+// ("/rustc/<commit>/library/core/src/option.rs"), a rustup toolchain or a crate
+// downloaded by cargo
+func isExternalRustSource(file string) bool {
+	file = strings.ReplaceAll(file, "\\", "/")
+	if strings.HasPrefix(file, "/rustc/") {
+		return true
+	}
+	for _, dir := range []string{"/.cargo/registry/", "/.cargo/git/", "/.rustup/toolchains/"} {
+		if strings.Contains(file, dir) {
+			return true
+		}
+	}
+	if cargoHome := os.Getenv("CARGO_HOME"); cargoHome != "" {
+		cargoHome = strings.TrimSuffix(strings.ReplaceAll(cargoHome, "\\", "/"), "/") + "/"
+		return strings.HasPrefix(file, cargoHome+"registry/") || strings.HasPrefix(file, cargoHome+"git/")
+	}
+	return false
+}
+
+// findCargoWorkspaceRoot returns the closest directory at or above dir whose Cargo.toml
+// declares a [workspace], as cargo does, or dir itself when there is none
+func findCargoWorkspaceRoot(dir string) string {
+	for current := dir; ; {
+		if content, err := os.ReadFile(filepath.Join(current, "Cargo.toml")); err == nil {
+			for _, line := range strings.Split(string(content), "\n") {
+				if strings.TrimSpace(line) == "[workspace]" {
+					return current
+				}
+			}
+		}
+		parent := filepath.Dir(current)
+		if parent == current {
+			return dir
+		}
+		current = parent
+	}
+}
+
+// parseBenchNanos parses a ns/iter value from a libtest bench line, e.g. "1,234" or "212.50"
+func parseBenchNanos(value string) float64 {
+	nanos, err := strconv.ParseFloat(strings.ReplaceAll(value, ",", ""), 64)
+	if err != nil {
+		return 0
+	}
+	return nanos
+}
+
+// shouldPanicNotMetNote is what libtest prints for a #[should_panic] test that returned
+const shouldPanicNotMetNote = "test did not panic as expected"
+
+// rustAssertionMacroRegex matches a line that calls one of the assertion macros
+var rustAssertionMacroRegex = regexp.MustCompile(`\b(?:debug_)?assert(?:_eq|_ne)?!\s*[(\[{]`)
+
+// rustFailureKind classifies a failed test like classifyRustPanic. assert! with a
+// custom message panics with just that message, so for other panics the source line
+// the panic points at is checked for an assertion macro. Caller must hold c.mu.
+func (c *CargoTestDefinition) rustFailureKind(panicMessage, errorMessage string, location *PanicLocation) string {
+	kind := classifyRustPanic(panicMessage, errorMessage)
+	if kind == ipc.FailureKindPanicked && location != nil && !location.External && c.isAssertionLine(location) {
+		return ipc.FailureKindAssertionFailed
+	}
+	return kind
+}
+
+// classifyRustPanic tells a failed assertion from another panic by the panic message
+// and libtest's note. #[should_panic] tests that didn't panic as expected count as
+// failed assertions.
+func classifyRustPanic(panicMessage, errorMessage string) string {
+	payload := panicPayloadLines(panicMessage)
+	switch {
+	case parseShouldPanicMismatch(errorMessage) != nil, strings.Contains(errorMessage, shouldPanicNotMetNote):
+		return ipc.FailureKindAssertionFailed
+	case len(payload) > 0 && strings.HasPrefix(payload[0], "assertion "):
+		// "assertion failed: x > 0", "assertion `left == right` failed"
+		return ipc.FailureKindAssertionFailed
+	case panicMessage != "":
+		return ipc.FailureKindPanicked
+	}
+	return ipc.FailureKindUnknown
+}
+
+// PendingSnapshots returns the insta snapshots the run wrote for cargo insta review,
+// relative to the workspace root
+func (c *CargoTestDefinition) PendingSnapshots() []string {
+	c.mu.RLock()
+	root, started := c.workspaceRoot, c.started
+	c.mu.RUnlock()
+	if root == "" || started.IsZero() {
+		return nil
+	}
+	return findPendingSnapshots(root, started)
+}
+
+// isAssertionLine reports whether the source line of a panic location, relative to
+// the workspace root, calls an assertion macro. Caller must hold c.mu.
+func (c *CargoTestDefinition) isAssertionLine(location *PanicLocation) bool {
+	content, err := os.ReadFile(filepath.Join(c.workspaceRoot, filepath.FromSlash(location.File)))
+	if err != nil {
+		return false
+	}
+	lines := strings.Split(string(content), "\n")
+	if location.Line < 1 || location.Line > len(lines) {
+		return false
+	}
+	return rustAssertionMacroRegex.MatchString(lines[location.Line-1])
+}
+
+// parseShouldPanicMismatch extracts the expected and actual panic message from
+// libtest's should_panic mismatch note, or returns nil if there is none
+func parseShouldPanicMismatch(text string) *ShouldPanicMismatch {
+	matches := shouldPanicMismatchRegex.FindStringSubmatch(text)
+	if matches == nil {
+		return nil
+	}
+	return &ShouldPanicMismatch{
+		Expected: unquoteRustString(matches[2]),
+		Actual:   unquoteRustString(matches[1]),
+	}
+}
+
+// unquoteRustString undoes the {:?} formatting libtest applies to panic messages
+func unquoteRustString(s string) string {
+	if unquoted, err := strconv.Unquote(s); err == nil {
+		return unquoted
+	}
+	return strings.TrimSuffix(strings.TrimPrefix(s, `"`), `"`)
+}
+
+// scanShouldPanicTests walks the Rust sources of the packages the command builds and
+// returns the functions marked #[should_panic], keyed by shouldPanicKey. libtest
+// doesn't report why a test passed, so this is how expected panics are told apart
+// from plain passes. Without cargo metadata only the package cargo finds from the
+// manifest directory is walked, and the names of its targets aren't known.
+// Caller must hold c.mu.
+func (c *CargoTestDefinition) scanShouldPanicTests(metadata *CargoMetadata) map[string]bool {
+	dir := c.manifestDir()
+	packages := []CargoMetadataPackage{{ManifestPath: filepath.Join(dir, "Cargo.toml")}}
+	if metadata != nil {
+		packages = selectedPackages(metadata, parseCargoSelection(c.cargoArgs), dir)
+	}
+
+	tests := make(map[string]bool)
+	for _, pkg := range packages {
+		scanPackageShouldPanicTests(pkg, tests)
+	}
+	return tests
+}
+
+// scanPackageShouldPanicTests adds the #[should_panic] tests of a package to tests.
+// Only its src/ and tests/ directories are walked, not target/ or vendored code.
+// Inline modules are tracked by brace depth, which is good enough for test code.
+func scanPackageShouldPanicTests(pkg CargoMetadataPackage, tests map[string]bool) {
+	root := filepath.Dir(pkg.ManifestPath)
+	for _, dir := range []string{"src", "tests"} {
+		_ = filepath.Walk(filepath.Join(root, dir), func(path string, info os.FileInfo, err error) error {
+			if err != nil {
+				return nil
+			}
+			if info.IsDir() {
+				if strings.HasPrefix(info.Name(), ".") {
+					return filepath.SkipDir
+				}
+				return nil
+			}
+			if !strings.HasSuffix(path, ".rs") {
+				return nil
+			}
+
+			content, err := os.ReadFile(path)
+			if err != nil || !bytes.Contains(content, []byte("should_panic")) {
+				return nil
+			}
+
+			rel, err := filepath.Rel(root, path)
+			if err != nil {
+				return nil
+			}
+			target, modulePath, ok := rustModulePath(strings.Split(filepath.ToSlash(rel), "/"))
+			if !ok {
+				return nil
+			}
+			target = packageTarget(pkg, target)
+
+			for _, name := range findShouldPanicFns(string(content)) {
+				tests[shouldPanicKey(target, strings.Join(append(append([]string{}, modulePath...), name...), "::"))] = true
+			}
+			return nil
+		})
+	}
+}
+
+// shouldPanicKey keys a #[should_panic] test by its target's kind and name, so tests
+// of the same name in two crates are told apart. cargo spells target names with
+// dashes where the test binaries have underscores.
+func shouldPanicKey(target CargoTarget, testName string) string {
+	return target.Kind + " " + strings.ReplaceAll(target.Name, "-", "_") + "::" + testName
+}
+
+// isShouldPanicTest reports whether a test of a crate is marked #[should_panic]. The
+// tests found without cargo metadata have no target name, so they match by kind.
+// Caller must hold c.mu.
+func (c *CargoTestDefinition) isShouldPanicTest(crateKey, testName string) bool {
+	target, ok := c.targets[crateKey]
+	if !ok {
+		return false
+	}
+	return c.shouldPanicTests[shouldPanicKey(*target, testName)] ||
+		c.shouldPanicTests[shouldPanicKey(CargoTarget{Kind: target.Kind}, testName)]
+}
+
+// packageTarget names the target rustModulePath found for a file of src/, whose crate
+// is the package's lib, or its src/main.rs program when it has none. The target is
+// returned unnamed when the package's targets aren't known.
+func packageTarget(pkg CargoMetadataPackage, target CargoTarget) CargoTarget {
+	if target.Name != "" {
+		return target
+	}
+	var bin *CargoMetadataTarget
+	for i, t := range pkg.Targets {
+		if len(t.Kind) == 0 {
+			continue
+		}
+		switch kind := cargoTargetKind(t.Kind[0]); {
+		case kind == "lib" && t.Kind[0] != "custom-build" && target.Kind == "lib":
+			return CargoTarget{Kind: kind, Name: t.Name}
+		case kind == "bin" && filepath.ToSlash(t.SrcPath) == filepath.ToSlash(filepath.Join(filepath.Dir(pkg.ManifestPath), "src", "main.rs")):
+			bin = &pkg.Targets[i]
+		}
+	}
+	if bin != nil {
+		return CargoTarget{Kind: "bin", Name: bin.Name}
+	}
+	return target
+}
+
+// rustModulePath maps a source file, given relative to its package, to the target it
+// is compiled into and its module path within that target's test binary:
+//
+//	src/lib.rs -> lib [], src/net/tcp.rs -> lib [net tcp], src/tests/util.rs -> lib [tests util],
+//	src/bin/tool.rs -> bin tool [], tests/api.rs -> test api [], tests/api/util.rs -> test api [util]
+//
+// Files of src/ other than src/main.rs and src/bin/ are taken to be the lib's, and
+// its name is left to packageTarget.
+func rustModulePath(parts []string) (CargoTarget, []string, bool) {
+	if len(parts) < 2 || parts[0] != "src" && parts[0] != "tests" {
+		return CargoTarget{}, nil, false
+	}
+
+	target := CargoTarget{Kind: "lib"}
+	rest := parts[1:]
+	switch {
+	case parts[0] == "tests":
+		// tests/<name>.rs and tests/<name>/main.rs are crate roots of their own
+		target = CargoTarget{Kind: "test", Name: strings.TrimSuffix(rest[0], ".rs")}
+		rest = rest[1:]
+	case rest[0] == "bin" && len(rest) > 1:
+		// So are the programs in src/bin/
+		target = CargoTarget{Kind: "bin", Name: strings.TrimSuffix(rest[1], ".rs")}
+		rest = rest[2:]
+	case len(rest) == 1 && rest[0] == "main.rs":
+		target.Kind = "bin"
+	}
+
+	var modules []string
+	for i, part := range rest {
+		if i == len(rest)-1 {
+			part = strings.TrimSuffix(part, ".rs")
+			if part == "lib" || part == "main" || part == "mod" {
+				break
+			}
+		}
+		modules = append(modules, part)
+	}
+	return target, modules, true
+}
+
+// findShouldPanicFns returns the inline-module-qualified names of the functions
+// in a Rust source file that carry a #[should_panic] attribute
+func findShouldPanicFns(source string) [][]string {
+	type openModule struct {
+		name  string
+		depth int
+	}
+
+	var found [][]string
+	var modules []openModule
+	pendingShouldPanic := false
+	depth := 0
+
+	for _, line := range strings.Split(source, "\n") {
+		if strings.HasPrefix(strings.TrimSpace(line), "//") {
+			continue
+		}
+
+		if shouldPanicAttrRegex.MatchString(line) {
+			pendingShouldPanic = true
+		}
+		if matches := rustInlineModRegex.FindStringSubmatch(line); matches != nil {
+			modules = append(modules, openModule{name: matches[1], depth: depth})
+		}
+		if matches := rustFnRegex.FindStringSubmatch(line); matches != nil {
+			if pendingShouldPanic {
+				name := make([]string, 0, len(modules)+1)
+				for _, mod := range modules {
+					name = append(name, mod.name)
+				}
+				found = append(found, append(name, matches[1]))
+			}
+			pendingShouldPanic = false
+		}
+
+		depth += strings.Count(line, "{") - strings.Count(line, "}")
+		for len(modules) > 0 && depth <= modules[len(modules)-1].depth {
+			modules = modules[:len(modules)-1]
+		}
+	}
+	return found
+}
+
+// processCargoMessage reports compiler errors from cargo's JSON messages as build errors
+func (c *CargoTestDefinition) processCargoMessage(msg *CargoMessage) {
+	if msg.Reason == "compiler-artifact" {
+		c.recordArtifactTarget(msg)
+		return
+	}
+	if msg.Reason != "compiler-message" || msg.Message == nil {
+		return
+	}
+
+	diag := msg.Message
+	if !isBuildErrorDiagnostic(diag) {
+		return
+	}
+
+	payload := map[string]interface{}{
+		"message":  diag.Message,
+		"rendered": strings.TrimRight(diag.Rendered, "\n"),
+	}
+	if msg.Target.Name != "" {
+		payload["target"] = msg.Target.Name
+	}
+	if diag.Code != nil && diag.Code.Code != "" {
+		payload["code"] = diag.Code.Code
+	}
+	if span := primaryDiagnosticSpan(diag.Spans); span != nil {
+		c.mu.Lock()
+		payload["file"] = c.hostPath(span.FileName)
+		c.mu.Unlock()
+		payload["line"] = span.LineStart
+		payload["column"] = span.ColumnStart
+	}
+
+	c.logger.Debug("Build error in %s: %s", msg.Target.Name, diag.Message)
+	c.sendIPCEvent(map[string]interface{}{
+		"eventType": "buildError",
+		"payload":   payload,
+	})
+}
+
+// recordArtifactTarget remembers which target a test executable was built from, so
+// its "Running" banner can be attributed exactly
+func (c *CargoTestDefinition) recordArtifactTarget(msg *CargoMessage) {
+	if msg.Executable == "" || len(msg.Target.Kind) == 0 {
+		return
+	}
+	executable := strings.ReplaceAll(msg.Executable, "\\", "/")
+	name := strings.TrimSuffix(executable[strings.LastIndex(executable, "/")+1:], ".exe")
+	target := &CargoTarget{Kind: cargoTargetKind(msg.Target.Kind[0]), Name: msg.Target.Name}
+
+	c.mu.Lock()
+	if msg.ManifestPath != "" {
+		target.ManifestDir = filepath.Dir(c.hostPath(msg.ManifestPath))
+	}
+	c.artifactTargets[name] = target
+	c.mu.Unlock()
+}
+
+// resolveTarget identifies the target of a "Running" banner, preferring the
+// compiler-artifact record for its executable over guessing from the source path.
+// Caller must hold c.mu.
+func (c *CargoTestDefinition) resolveTarget(sourcePath, executable, name string) *CargoTarget {
+	if target, ok := c.artifactTargets[executable]; ok {
+		return target
 	}
 
-	*jsonEventCount++
-	// Process the JSON event with current crate context
-	if err := c.processEvent(&event); err != nil {
-		c.logger.Debug("Error processing cargo test event: %v", err)
+	sourcePath = strings.ReplaceAll(sourcePath, "\\", "/")
+	kind := "lib"
+	switch {
+	case strings.HasPrefix(sourcePath, "tests/"):
+		kind = "test"
+	case strings.HasPrefix(sourcePath, "examples/"):
+		kind = "example"
+	case strings.HasPrefix(sourcePath, "benches/"):
+		kind = "bench"
+	case strings.HasPrefix(sourcePath, "src/bin/") || sourcePath == "src/main.rs":
+		kind = "bin"
+	}
+	return &CargoTarget{Kind: kind, Name: name}
+}
+
+// targetGroupKey returns the crate key for a target's group. Groups are named after
+// the target, but a target sharing its name with one of another kind (src/main.rs
+// next to src/lib.rs) gets the kind appended, so the two aren't merged.
+// Caller must hold c.mu.
+func (c *CargoTestDefinition) targetGroupKey(target *CargoTarget) string {
+	key := target.Name
+	if existing, ok := c.targets[key]; ok && existing.Kind != target.Kind {
+		key = fmt.Sprintf("%s (%s)", target.Name, target.Kind)
+	}
+	c.targets[key] = target
+	return key
+}
+
+// cargoTargetKind maps a cargo target kind to the kinds 3pio reports. Library
+// crate types (rlib, cdylib, proc-macro, ...) are all reported as "lib".
+func cargoTargetKind(kind string) string {
+	switch kind {
+	case "bin", "test", "example", "bench":
+		return kind
 	}
+	return "lib"
+}
+
+// isBuildErrorDiagnostic filters compiler diagnostics down to the errors worth reporting.
+// Warnings and rustc's trailing "aborting due to N previous errors" summary are skipped.
+func isBuildErrorDiagnostic(diag *CargoDiagnostic) bool {
+	if !strings.HasPrefix(diag.Level, "error") {
+		return false
+	}
+	if len(diag.Spans) == 0 && strings.HasPrefix(diag.Message, "aborting due to") {
+		return false
+	}
+	return true
+}
+
+// primaryDiagnosticSpan returns the span rustc marked as primary, or the first span
+func primaryDiagnosticSpan(spans []CargoDiagnosticSpan) *CargoDiagnosticSpan {
+	for i := range spans {
+		if spans[i].IsPrimary {
+			return &spans[i]
+		}
+	}
+	if len(spans) > 0 {
+		return &spans[0]
+	}
+	return nil
 }
 
 // processEvent processes a single cargo test JSON event
@@ -337,6 +1776,10 @@ func (c *CargoTestDefinition) processEvent(event *CargoTestEvent) error {
 		return c.processSuiteEvent(event)
 	case "test":
 		return c.processTestEvent(event)
+	case "bench":
+		// Bench results have no "event" field; handle them as a finished test
+		event.Event = "bench"
+		return c.processTestEvent(event)
 	default:
 		c.logger.Debug("Unknown cargo test event type: %s", event.Type)
 	}
@@ -351,6 +1794,9 @@ func (c *CargoTestDefinition) processSuiteEvent(event *CargoTestEvent) error {
 
 	switch event.Event {
 	case "started":
+		c.suitesStarted++
+		c.testsInSuites += event.TestCount
+
 		// A suite started event tells us how many tests to expect
 		// The currentCrate should already be set by the previous stderr "Running" line
 		if c.currentCrate != "" {
@@ -358,8 +1804,9 @@ func (c *CargoTestDefinition) processSuiteEvent(event *CargoTestEvent) error {
 			c.crateTestCounts[c.currentCrate] = event.TestCount
 			c.crateTestsSeen[c.currentCrate] = 0
 
-			// Create the crate group if it doesn't exist
-			if c.crateGroups[c.currentCrate] == nil {
+			// Create the crate group if it doesn't exist. Doc-test suites are
+			// nested under their crate group once the first doctest arrives.
+			if c.crateGroups[c.currentCrate] == nil && !strings.HasPrefix(c.currentCrate, "doc:") {
 				c.crateGroups[c.currentCrate] = &CrateGroupInfo{
 					Name:      c.currentCrate,
 					StartTime: time.Now(),
@@ -386,23 +1833,16 @@ func (c *CargoTestDefinition) processSuiteEvent(event *CargoTestEvent) error {
 			// For suites with 0 tests, we need to create and complete the group now
 			// since no test events will be generated
 			totalTests := event.Passed + event.Failed + event.Ignored
-			if totalTests == 0 {
-				// Check if this is a doc-test
-				var displayCrateName string
-				if strings.HasPrefix(crateName, "doc:") {
-					// Doc-test: display as "Doc-tests <crate>"
-					actualCrateName := strings.TrimPrefix(crateName, "doc:")
-					// Convert underscores to hyphens for consistency
-					actualCrateName = strings.ReplaceAll(actualCrateName, "_", "-")
-					displayCrateName = "Doc-tests " + actualCrateName
-				} else {
-					// Regular test: convert underscores to hyphens for display
-					displayCrateName = strings.ReplaceAll(crateName, "_", "-")
-				}
+			if totalTests == 0 && strings.HasPrefix(crateName, "doc:") {
+				// No doctests - don't add an empty doctests group to the crate
+				c.logger.Debug("No doctests in %s, skipping doctests group", crateName)
+			} else if totalTests == 0 {
+				// Convert underscores to hyphens for display
+				displayCrateName := strings.ReplaceAll(crateName, "_", "-")
 
 				// Send group discovered
 				if !c.discoveredGroups[crateName] {
-					c.sendGroupDiscovered(displayCrateName, nil)
+					c.sendCrateGroupDiscovered(crateName, displayCrateName, nil)
 					c.discoveredGroups[crateName] = true
 				}
 
@@ -476,6 +1916,11 @@ func (c *CargoTestDefinition) processTestEvent(event *CargoTestEvent) error {
 		return nil
 	}
 
+	// Doctests keep rustdoc's "file - item (line N)" name instead of module groups
+	if strings.HasPrefix(crateName, "doc:") {
+		return c.processDocTestEvent(event, strings.TrimPrefix(crateName, "doc:"))
+	}
+
 	// Parse test name to extract module hierarchy (without crate prefix)
 	// The JSON test names don't include the crate name, just module::test
 	parts := strings.Split(event.Name, "::")
@@ -504,30 +1949,17 @@ func (c *CargoTestDefinition) processTestEvent(event *CargoTestEvent) error {
 	}
 
 	// Convert underscores to hyphens for display
-	var displayCrateName string
-	var enhancedCrateName string
+	displayCrateName := strings.ReplaceAll(crateName, "_", "-")
 
-	// Check if this is a doc-test crate
-	if strings.HasPrefix(crateName, "doc:") {
-		// Doc-test: display as "Doc-tests <crate>"
-		actualCrateName := strings.TrimPrefix(crateName, "doc:")
-		// Convert underscores to hyphens for consistency
-		actualCrateName = strings.ReplaceAll(actualCrateName, "_", "-")
-		displayCrateName = "Doc-tests " + actualCrateName
-		enhancedCrateName = displayCrateName
-	} else {
-		// Regular crate: convert underscores to hyphens
-		displayCrateName = strings.ReplaceAll(crateName, "_", "-")
-		// Build enhanced crate name with description
-		enhancedCrateName = displayCrateName
-		if crateDesc != "" {
-			enhancedCrateName = fmt.Sprintf("%s (%s)", displayCrateName, crateDesc)
-		}
+	// Build enhanced crate name with description
+	enhancedCrateName := displayCrateName
+	if crateDesc != "" {
+		enhancedCrateName = fmt.Sprintf("%s (%s)", displayCrateName, crateDesc)
 	}
 
 	// Ensure crate group exists
 	if !c.discoveredGroups[crateName] {
-		c.sendGroupDiscovered(enhancedCrateName, parentNames)
+		c.sendCrateGroupDiscovered(crateName, enhancedCrateName, parentNames)
 		c.discoveredGroups[crateName] = true
 	}
 
@@ -545,7 +1977,6 @@ func (c *CargoTestDefinition) processTestEvent(event *CargoTestEvent) error {
 
 	// Build full parent hierarchy for the test
 	// Start with base hierarchy: [workspace?] + crate (use display name)
-	// For doc-tests, use the full "Doc-tests <crate>" format
 	testParents := append(parentNames, displayCrateName)
 
 	// Handle nested modules as groups
@@ -601,8 +2032,9 @@ func (c *CargoTestDefinition) processTestEvent(event *CargoTestEvent) error {
 			Crate:     crateName,
 			StartTime: time.Now(),
 		}
+		c.lastStartedTest = event.Name
 
-	case "ok", "failed", "ignored":
+	case "ok", "failed", "ignored", "bench", "crashed", "not_run", "timed_out":
 		// Track that we've seen a test for this crate (only count completed tests)
 		c.crateTestsSeen[crateName]++
 
@@ -615,17 +2047,83 @@ func (c *CargoTestDefinition) processTestEvent(event *CargoTestEvent) error {
 		// Map status
 		var status string
 		switch event.Event {
-		case "failed":
+		case "failed", "crashed", "timed_out":
 			status = "FAIL"
-		case "ignored":
+		case "ignored", "not_run":
 			status = "SKIP"
+		case "bench":
+			status = "BENCH"
 		default:
 			status = "PASS"
 		}
 
 		// Send test case event (convert duration from seconds to milliseconds)
 		durationMs := event.ExecTime * 1000
-		c.sendTestCase(testName, testParents, status, durationMs, event.Stdout, event.Stderr)
+		if event.Event == "crashed" || event.Event == "timed_out" {
+			errorType, failureKind := ipc.ErrorTypeCrashed, ipc.FailureKindCrashed
+			if event.Event == "timed_out" {
+				errorType, failureKind = ipc.ErrorTypeTimedOut, ipc.FailureKindTimedOut
+			}
+			payload := c.newTestCasePayload(testName, testParents, status, durationMs, "", "")
+			errPayload := map[string]interface{}{
+				"message":     event.Message,
+				"errorType":   errorType,
+				"failureKind": failureKind,
+			}
+			if event.UndefinedBehavior != "" {
+				errPayload["undefinedBehavior"] = event.UndefinedBehavior
+				errPayload["failureKind"] = ipc.FailureKindUndefinedBehavior
+			}
+			payload["error"] = errPayload
+			c.sendIPCEvent(map[string]interface{}{
+				"eventType": "testCase",
+				"payload":   payload,
+			})
+		} else if event.Event == "not_run" {
+			payload := c.newTestCasePayload(testName, testParents, status, durationMs, "", "")
+			payload["skipReason"] = ipc.SkipReasonNotRun
+			c.sendIPCEvent(map[string]interface{}{
+				"eventType": "testCase",
+				"payload":   payload,
+			})
+		} else if status == "FAIL" {
+			// Prefer the JSON capture, fall back to a failure block seen earlier
+			stdout, stderr := event.Stdout, event.Stderr
+			testKey := crateName + "::" + event.Name
+			if captured, ok := c.capturedOutput[testKey]; ok {
+				if stdout == "" {
+					stdout = captured.Stdout
+				}
+				if stderr == "" {
+					stderr = captured.Stderr
+				}
+			}
+			c.sendFailedTestCase(crateName, testName, testParents, durationMs, stdout, stderr, event.Message)
+			c.failedTests[testKey] = &CargoFailedTest{
+				Name:     testName,
+				Parents:  append([]string(nil), testParents...),
+				Duration: durationMs,
+				Message:  event.Message,
+			}
+		} else if status == "BENCH" {
+			payload := c.newTestCasePayload(testName, testParents, status, durationMs, event.Stdout, event.Stderr)
+			payload["benchMedianNs"] = event.Median
+			payload["benchDeviationNs"] = event.Deviation
+			c.sendIPCEvent(map[string]interface{}{
+				"eventType": "testCase",
+				"payload":   payload,
+			})
+		} else if status == "PASS" && c.isShouldPanicTest(crateName, event.Name) {
+			// #[should_panic] tests pass by panicking, so tell them apart from plain passes
+			payload := c.newTestCasePayload(testName, testParents, status, durationMs, event.Stdout, event.Stderr)
+			payload["panickedAsExpected"] = true
+			c.sendIPCEvent(map[string]interface{}{
+				"eventType": "testCase",
+				"payload":   payload,
+			})
+		} else {
+			c.sendTestCase(testName, testParents, status, durationMs, event.Stdout, event.Stderr)
+		}
 
 		// Create test info
 		testInfo := CargoTestInfo{
@@ -665,6 +2163,297 @@ func (c *CargoTestDefinition) processTestEvent(event *CargoTestEvent) error {
 	return nil
 }
 
+// handleBinaryCrash reports the current test binary as crashed if it never printed
+// its suite summary. The test that started last, or the one miri found undefined
+// behavior in, is failed as CRASHED and the other tests still running are skipped
+// as not run. Caller must hold c.mu.
+func (c *CargoTestDefinition) handleBinaryCrash(signal int, signalName string, exitCode int) {
+	crateName := c.currentCrate
+	if crateName == "" || strings.HasPrefix(crateName, "doc:") {
+		// Finished suites clear currentCrate; rustdoc reports crashed doctests as failures
+		return
+	}
+	if _, ok := c.crateTestCounts[crateName]; !ok {
+		// A target with its own harness (harness = false) prints no libtest suite, so
+		// its exit can't be told from failed tests. It reports them through
+		// threepio-reporter, if at all.
+		c.logger.Debug("Target %s ran no libtest suite, not reporting its exit as a crash", crateName)
+		c.currentCrate = ""
+		return
+	}
+
+	message := binaryCrashMessage(signal, signalName, exitCode)
+	diagnostic, thread := c.takeMiriDiagnostic()
+	if diagnostic != "" {
+		// miri stopped the binary on undefined behavior in the test on that thread
+		header, _, _ := strings.Cut(diagnostic, "\n")
+		message = strings.TrimPrefix(header, "error: ")
+	}
+	c.logger.Debug("Test binary for %s crashed: %s", crateName, message)
+
+	crashing := c.lastStartedTest
+	if state, ok := c.testStates[thread]; ok && state.Crate == crateName {
+		crashing = thread
+	}
+	var crashed string
+	var notRun []string
+	for name, state := range c.testStates {
+		if state.Crate != crateName {
+			continue
+		}
+		if name == crashing {
+			crashed = name
+		} else {
+			notRun = append(notRun, name)
+		}
+	}
+	sort.Strings(notRun)
+
+	if crashed != "" {
+		event := &CargoTestEvent{
+			Type:              "test",
+			Event:             "crashed",
+			Name:              crashed,
+			ExecTime:          time.Since(c.testStates[crashed].StartTime).Seconds(),
+			Message:           message,
+			UndefinedBehavior: diagnostic,
+		}
+		if err := c.processTestEvent(event); err != nil {
+			c.logger.Debug("Error reporting crashed test %s: %v", crashed, err)
+		}
+	}
+	for _, name := range notRun {
+		if err := c.processTestEvent(&CargoTestEvent{Type: "test", Event: "not_run", Name: name}); err != nil {
+			c.logger.Debug("Error reporting unfinished test %s: %v", name, err)
+		}
+	}
+
+	payload := map[string]interface{}{
+		"target":  strings.ReplaceAll(crateName, "_", "-"),
+		"message": message,
+	}
+	if crashed != "" {
+		payload["testName"] = crashed
+	}
+	if signal > 0 {
+		payload["signal"] = signal
+		payload["signalName"] = signalName
+	} else if exitCode != 0 {
+		payload["exitCode"] = exitCode
+	}
+	c.sendIPCEvent(map[string]interface{}{
+		"eventType": "binaryCrash",
+		"payload":   payload,
+	})
+
+	c.currentCrate = ""
+	c.lastStartedTest = ""
+}
+
+// MarkTimedOut makes the tests still running when the output ends report as
+// TIMED_OUT instead of crashed, after 3pio killed a hung run
+func (c *CargoTestDefinition) MarkTimedOut() {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	c.timedOut = true
+}
+
+// RunningTests returns the tests that started but haven't finished, as
+// "crate > module > test" paths
+func (c *CargoTestDefinition) RunningTests() []string {
+	c.mu.RLock()
+	defer c.mu.RUnlock()
+
+	var running []string
+	for name, state := range c.testStates {
+		crate := strings.ReplaceAll(state.Crate, "_", "-")
+		running = append(running, crate+" > "+strings.ReplaceAll(name, "::", " > "))
+	}
+	sort.Strings(running)
+	return running
+}
+
+// MarkInterrupted makes the groups of the test binary still running when the output
+// ends stay unfinished, so the report marks them INTERRUPTED instead of crashed
+func (c *CargoTestDefinition) MarkInterrupted() {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	c.interrupted = true
+}
+
+// leaveRunningGroupsUnfinished stops finalizePendingGroups from sending results for
+// the crate and module groups of the running test binary. Caller must hold c.mu.
+func (c *CargoTestDefinition) leaveRunningGroupsUnfinished() {
+	crateName := c.currentCrate
+	if crateName == "" {
+		return
+	}
+	for key := range c.crateGroups {
+		if key == crateName || strings.HasPrefix(key, crateName+"::") {
+			delete(c.crateGroups, key)
+		}
+	}
+	c.currentCrate = ""
+}
+
+// reportTimedOutTests fails every test still running as TIMED_OUT. Caller must hold c.mu.
+func (c *CargoTestDefinition) reportTimedOutTests() {
+	var names []string
+	for name, state := range c.testStates {
+		if state.Crate == c.currentCrate {
+			names = append(names, name)
+		}
+	}
+	sort.Strings(names)
+
+	for _, name := range names {
+		event := &CargoTestEvent{
+			Type:     "test",
+			Event:    "timed_out",
+			Name:     name,
+			ExecTime: time.Since(c.testStates[name].StartTime).Seconds(),
+			Message:  "test was still running when the inactivity timeout stopped the run",
+		}
+		if err := c.processTestEvent(event); err != nil {
+			c.logger.Debug("Error reporting timed out test %s: %v", name, err)
+		}
+	}
+	c.currentCrate = ""
+}
+
+// binaryCrashMessage describes how a test binary stopped before finishing its tests
+func binaryCrashMessage(signal int, signalName string, exitCode int) string {
+	switch {
+	case signal > 0:
+		return fmt.Sprintf("test binary crashed with signal %d (%s)", signal, signalName)
+	case exitCode != 0:
+		return fmt.Sprintf("test binary exited with code %d before finishing its tests", exitCode)
+	default:
+		return "test binary stopped before finishing its tests"
+	}
+}
+
+// processDocTestEvent handles a test event from a "Doc-tests <crate>" suite.
+// Doctests are reported in a "doctests" group under the crate group, and the
+// rustdoc name is parsed into file, item path and line for the test case.
+func (c *CargoTestDefinition) processDocTestEvent(event *CargoTestEvent, crateName string) error {
+	var parentNames []string
+	if c.workspaceName != "" {
+		parentNames = append(parentNames, c.workspaceName)
+	}
+	displayCrateName := strings.ReplaceAll(crateName, "_", "-")
+	docGroupKey := fmt.Sprintf("%s::%s", crateName, docTestsGroupName)
+
+	// Ensure the crate group exists - a crate may have doctests but no unit tests
+	if !c.discoveredGroups[crateName] {
+		c.sendCrateGroupDiscovered(crateName, displayCrateName, parentNames)
+		c.discoveredGroups[crateName] = true
+	}
+	if !c.groupStarts[crateName] {
+		c.sendGroupStart(displayCrateName, parentNames)
+		c.groupStarts[crateName] = true
+	}
+	crateGroup, ok := c.crateGroups[crateName]
+	if !ok {
+		crateGroup = &CrateGroupInfo{
+			Name:      crateName,
+			StartTime: time.Now(),
+			Tests:     []CargoTestInfo{},
+			Status:    "RUNNING",
+		}
+		c.crateGroups[crateName] = crateGroup
+	} else if crateGroup.Finalized {
+		// An empty unit test suite already reported NO_TESTS for the crate;
+		// reopen it so the final result includes the doctests
+		crateGroup.Finalized = false
+	}
+
+	// Ensure the doctests group exists under the crate
+	docParents := append(append([]string{}, parentNames...), displayCrateName)
+	if !c.discoveredGroups[docGroupKey] {
+		c.sendGroupDiscovered(docTestsGroupName, docParents)
+		c.discoveredGroups[docGroupKey] = true
+	}
+	if !c.groupStarts[docGroupKey] {
+		c.sendGroupStart(docTestsGroupName, docParents)
+		c.groupStarts[docGroupKey] = true
+		c.crateGroups[docGroupKey] = &CrateGroupInfo{
+			Name:      docTestsGroupName,
+			StartTime: time.Now(),
+			Tests:     []CargoTestInfo{},
+			Status:    "RUNNING",
+		}
+	}
+
+	testParents := append(docParents, docTestsGroupName)
+
+	switch event.Event {
+	case "started":
+		c.testStates[event.Name] = &CargoTestState{
+			Name:      event.Name,
+			Crate:     "doc:" + crateName,
+			StartTime: time.Now(),
+		}
+
+	case "ok", "failed", "ignored":
+		c.crateTestsSeen["doc:"+crateName]++
+
+		var status string
+		switch event.Event {
+		case "failed":
+			status = "FAIL"
+		case "ignored":
+			status = "SKIP"
+		default:
+			status = "PASS"
+		}
+
+		location := parseDocTestName(event.Name)
+		if location == nil {
+			c.logger.Debug("Could not parse doctest name: %s", event.Name)
+		}
+
+		durationMs := event.ExecTime * 1000
+		c.sendDocTestCase(event.Name, testParents, status, durationMs, event.Stdout, event.Stderr, location)
+
+		testInfo := CargoTestInfo{
+			Name:     event.Name,
+			Status:   status,
+			Duration: durationMs,
+		}
+		for _, group := range []*CrateGroupInfo{crateGroup, c.crateGroups[docGroupKey]} {
+			group.Tests = append(group.Tests, testInfo)
+			if status == "FAIL" && group.Status != "FAIL" {
+				group.Status = "FAIL"
+			}
+		}
+
+		delete(c.testStates, event.Name)
+	}
+
+	return nil
+}
+
+// parseDocTestName parses rustdoc's "file - item::path (line N)" test name.
+// Returns nil if the name does not follow the doctest naming convention.
+func parseDocTestName(name string) *DocTestLocation {
+	matches := docTestNameRegex.FindStringSubmatch(name)
+	if matches == nil {
+		return nil
+	}
+
+	line, err := strconv.Atoi(matches[3])
+	if err != nil {
+		return nil
+	}
+
+	return &DocTestLocation{
+		File:     matches[1],
+		ItemPath: matches[2],
+		Line:     line,
+	}
+}
+
 // finalizePendingGroups sends result events for any groups that haven't been finalized
 func (c *CargoTestDefinition) finalizePendingGroups() {
 	c.logger.Debug("finalizePendingGroups called with %d groups", len(c.crateGroups))
@@ -690,6 +2479,7 @@ func (c *CargoTestDefinition) finalizePendingGroups() {
 			passed := 0
 			failed := 0
 			skipped := 0
+			benches := 0
 			totalDuration := 0.0
 
 			for _, test := range group.Tests {
@@ -700,19 +2490,22 @@ func (c *CargoTestDefinition) finalizePendingGroups() {
 					failed++
 				case "SKIP":
 					skipped++
+				case "BENCH":
+					benches++
 				}
 				totalDuration += test.Duration
 			}
 
-			// Determine final status
+			// Determine final status. Benchmarks don't count as tests,
+			// but a group that only ran benchmarks still passed.
 			finalStatus := "PASS"
 			total := passed + failed + skipped
-			if total == 0 {
+			if total == 0 && benches == 0 {
 				// No tests at all - mark as NO_TESTS
 				finalStatus = "NO_TESTS"
 			} else if failed > 0 {
 				finalStatus = "FAIL"
-			} else if passed == 0 && skipped > 0 {
+			} else if passed == 0 && benches == 0 && skipped > 0 {
 				finalStatus = "SKIP"
 			}
 
@@ -805,6 +2598,25 @@ func (c *CargoTestDefinition) sendGroupDiscovered(groupName string, parentNames
 	c.sendIPCEvent(event)
 }
 
+// sendCrateGroupDiscovered announces a crate group, tagged with the test target it runs
+func (c *CargoTestDefinition) sendCrateGroupDiscovered(crateKey, groupName string, parentNames []string) {
+	payload := map[string]interface{}{
+		"groupName":   groupName,
+		"parentNames": parentNames,
+	}
+	if target, ok := c.targets[crateKey]; ok {
+		payload["targetKind"] = target.Kind
+		payload["targetName"] = target.Name
+		if c.isCustomHarness(target) {
+			payload["customHarness"] = true
+		}
+	}
+	c.sendIPCEvent(map[string]interface{}{
+		"eventType": "testGroupDiscovered",
+		"payload":   payload,
+	})
+}
+
 func (c *CargoTestDefinition) sendGroupStart(groupName string, parentNames []string) {
 	event := map[string]interface{}{
 		"eventType": "testGroupStart",
@@ -817,6 +2629,129 @@ func (c *CargoTestDefinition) sendGroupStart(groupName string, parentNames []str
 }
 
 func (c *CargoTestDefinition) sendTestCase(testName string, parentNames []string, status string, duration float64, stdout, stderr string) {
+	event := map[string]interface{}{
+		"eventType": "testCase",
+		"payload":   c.newTestCasePayload(testName, parentNames, status, duration, stdout, stderr),
+	}
+	c.sendIPCEvent(event)
+}
+
+// sendFailedTestCase sends a failed test with its captured stdout split into the
+// panic message, reported as the error, and the output the test printed before it.
+// message is libtest's failure note, which explains should_panic failures.
+// Caller must hold c.mu.
+func (c *CargoTestDefinition) sendFailedTestCase(crateKey, testName string, parentNames []string, duration float64, stdout, stderr, message string) {
+	printed, panicMessage := splitCapturedOutput(stdout)
+	panicMessage, backtrace := splitBacktrace(panicMessage)
+	payload := c.newTestCasePayload(testName, parentNames, "FAIL", duration, printed, stderr)
+	trybuildOutput := strings.Join(c.trybuildOutput[crateKey], "\n")
+	delete(c.trybuildOutput, crateKey)
+
+	errorMessage := panicMessage
+	if message != "" && !strings.Contains(panicMessage, message) {
+		if errorMessage != "" {
+			errorMessage += "\n"
+		}
+		errorMessage += "note: " + message
+	}
+	if errorMessage == "" && strings.Contains(printed, shouldPanicNotMetNote) {
+		// libtest prints the note with the test's output, not as a message
+		errorMessage = "note: " + shouldPanicNotMetNote
+	}
+	if errorMessage != "" {
+		errPayload := map[string]interface{}{
+			"message": errorMessage,
+		}
+		if mismatch := parseShouldPanicMismatch(errorMessage); mismatch != nil {
+			errPayload["expectedPanic"] = mismatch.Expected
+			errPayload["actualPanic"] = mismatch.Actual
+		}
+		if assertion := parseAssertionFailure(panicMessage); assertion != nil {
+			// assert_eq!(actual, expected) is the common argument order
+			errPayload["errorType"] = assertion.Kind
+			errPayload["actual"] = assertion.Left
+			errPayload["expected"] = assertion.Right
+			if assertion.CustomMessage != "" {
+				errPayload["customMessage"] = assertion.CustomMessage
+			}
+		}
+		if backtrace != "" {
+			errPayload["backtrace"] = backtrace
+		}
+		location := parsePanicLocation(panicMessage)
+		if location != nil {
+			c.resolvePanicLocation(location, crateKey)
+			errPayload["location"] = fmt.Sprintf("%s:%d:%d", location.File, location.Line, location.Column)
+			errPayload["file"] = location.File
+			errPayload["line"] = location.Line
+			errPayload["column"] = location.Column
+			if location.External {
+				errPayload["external"] = true
+			}
+		}
+		errPayload["failureKind"] = c.rustFailureKind(panicMessage, errorMessage, location)
+		if snapshot := parseInstaSnapshot(printed, panicMessage, c.workspaceRoot); snapshot != nil {
+			errPayload["snapshot"] = snapshot
+			errPayload["failureKind"] = ipc.FailureKindSnapshotMismatch
+		}
+		if property := parsePropertyFailure(printed, panicMessage, c.workspaceRoot); property != nil {
+			errPayload["property"] = property
+			errPayload["failureKind"] = propertyFailureKind(property, panicMessage)
+		}
+		manifestDir := c.workspaceRoot
+		if target, ok := c.targets[crateKey]; ok && target.ManifestDir != "" {
+			manifestDir = target.ManifestDir
+		}
+		if cases := parseTrybuildCases(printed+"\n"+trybuildOutput, manifestDir, c.workspaceRoot); len(cases) > 0 {
+			errPayload["trybuild"] = cases
+		}
+		payload["error"] = errPayload
+	}
+
+	event := map[string]interface{}{
+		"eventType": "testCase",
+		"payload":   payload,
+	}
+	c.sendIPCEvent(event)
+}
+
+// sendDocTestCase sends a doctest result with the doc comment location attached
+func (c *CargoTestDefinition) sendDocTestCase(testName string, parentNames []string, status string, duration float64, stdout, stderr string, location *DocTestLocation) {
+	payload := c.newTestCasePayload(testName, parentNames, status, duration, stdout, stderr)
+
+	// rustdoc reports doctest failures on stdout, so fall back to it for the error message
+	if status == "FAIL" && payload["error"] == nil {
+		message := strings.TrimSpace(stdout)
+		if message == "" {
+			message = "Doctest failed"
+		}
+		// The output is the error message, so don't report it twice
+		delete(payload, "stdout")
+		payload["error"] = map[string]interface{}{
+			"message": message,
+		}
+	}
+
+	if location != nil {
+		payload["file"] = location.File
+		payload["line"] = location.Line
+		if location.ItemPath != "" {
+			payload["itemPath"] = location.ItemPath
+		}
+		if errPayload, ok := payload["error"].(map[string]interface{}); ok {
+			errPayload["location"] = fmt.Sprintf("%s:%d", location.File, location.Line)
+		}
+	}
+
+	event := map[string]interface{}{
+		"eventType": "testCase",
+		"payload":   payload,
+	}
+	c.sendIPCEvent(event)
+}
+
+// newTestCasePayload builds the common testCase payload for a finished test
+func (c *CargoTestDefinition) newTestCasePayload(testName string, parentNames []string, status string, duration float64, stdout, stderr string) map[string]interface{} {
 	payload := map[string]interface{}{
 		"testName":    testName,
 		"parentNames": parentNames,
@@ -839,11 +2774,24 @@ func (c *CargoTestDefinition) sendTestCase(testName string, parentNames []string
 		}
 	}
 
-	event := map[string]interface{}{
-		"eventType": "testCase",
-		"payload":   payload,
+	// libtest only reports skips for #[ignore]; filtered-out tests produce no events
+	if status == "SKIP" {
+		payload["skipReason"] = ipc.SkipReasonIgnored
 	}
-	c.sendIPCEvent(event)
+	addRstestCase(payload, testName, parentNames)
+
+	return payload
+}
+
+// addRstestCase marks a test rstest generated for a #[case] as a parameter set of its
+// test, the module rstest generated the cases in
+func addRstestCase(payload map[string]interface{}, testName string, parentNames []string) {
+	// The first parent is the crate, so the test needs a module of its own
+	if len(parentNames) < 2 || !rstestCaseRegex.MatchString(testName) {
+		return
+	}
+	payload["baseName"] = parentNames[len(parentNames)-1]
+	payload["paramId"] = testName
 }
 
 func (c *CargoTestDefinition) sendGroupResult(groupName string, parentNames []string, status string, duration float64, passed, failed, skipped int) {
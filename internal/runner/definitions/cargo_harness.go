@@ -0,0 +1,205 @@
+package definitions
+
+import (
+	"os"
+	"path/filepath"
+	"regexp"
+	"strings"
+	"time"
+)
+
+// threepioReporterCrate is the crate custom harnesses report their tests with. Targets
+// of a package depending on it report themselves and are parsed as usual.
+const threepioReporterCrate = "threepio-reporter"
+
+// manifestTargetHeaderRegex matches the Cargo.toml sections that declare a target
+var manifestTargetHeaderRegex = regexp.MustCompile(`^\[\[?\s*(lib|bin|test|example|bench)\s*\]\]?\s*(?:#.*)?$`)
+
+// manifestKeyRegex matches a `key = value` line, the value without its comment
+var manifestKeyRegex = regexp.MustCompile(`^([\w-]+)\s*=\s*("[^"]*"|'[^']*'|[^#\s]+)`)
+
+// findCustomHarnesses finds the targets of the workspace that run their own harness
+// (harness = false) and don't report through threepio-reporter. cargo metadata has
+// no harness field, so it gives the manifests to read it from. Caller must hold c.mu.
+func (c *CargoTestDefinition) findCustomHarnesses(metadata *CargoMetadata) {
+	harnesses := make(map[string]string)
+	for _, pkg := range metadata.Packages {
+		if dependsOn(pkg, threepioReporterCrate) {
+			c.logger.Debug("Package %s reports through %s", pkg.Name, threepioReporterCrate)
+			continue
+		}
+		manifest, err := os.ReadFile(pkg.ManifestPath)
+		if err != nil {
+			c.logger.Debug("Failed to read %s: %v", pkg.ManifestPath, err)
+			continue
+		}
+		for name, kind := range customHarnessTargets(string(manifest), pkg.Name) {
+			c.logger.Debug("Target %s (%s) of %s has its own harness", name, kind, pkg.Name)
+			harnesses[name] = kind
+		}
+	}
+	c.customHarnesses = harnesses
+}
+
+// dependsOn reports whether a package has a dependency (of any kind) on crate
+func dependsOn(pkg CargoMetadataPackage, crate string) bool {
+	for _, dep := range pkg.Dependencies {
+		if dep.Name == crate {
+			return true
+		}
+	}
+	return false
+}
+
+// customHarnessTargets returns the targets of a Cargo.toml that set harness = false,
+// as target name -> kind. Names are normalized with harnessTargetName.
+func customHarnessTargets(manifest, packageName string) map[string]string {
+	targets := make(map[string]string)
+	var kind, name, path string
+	harness := true
+	endTarget := func() {
+		if kind != "" && !harness {
+			switch {
+			case name != "":
+			case path != "":
+				name = strings.TrimSuffix(filepath.Base(path), filepath.Ext(path))
+			case kind == "lib" || kind == "bin":
+				name = packageName
+			}
+			if name != "" {
+				targets[harnessTargetName(name)] = kind
+			}
+		}
+		kind, name, path, harness = "", "", "", true
+	}
+
+	for _, line := range strings.Split(manifest, "\n") {
+		line = strings.TrimSpace(line)
+		if strings.HasPrefix(line, "[") {
+			endTarget()
+			if matches := manifestTargetHeaderRegex.FindStringSubmatch(line); matches != nil {
+				kind = matches[1]
+			}
+			continue
+		}
+		if kind == "" {
+			continue
+		}
+		matches := manifestKeyRegex.FindStringSubmatch(line)
+		if matches == nil {
+			continue
+		}
+		value := strings.Trim(matches[2], `"'`)
+		switch matches[1] {
+		case "name":
+			name = value
+		case "path":
+			path = value
+		case "harness":
+			harness = value != "false"
+		}
+	}
+	endTarget()
+	return targets
+}
+
+// harnessTargetName normalizes a target name the way test executables are named,
+// with hyphens turned into underscores
+func harnessTargetName(name string) string {
+	return strings.ReplaceAll(name, "-", "_")
+}
+
+// isCustomHarness reports whether target runs its own harness that doesn't report
+// to 3pio. Caller must hold c.mu.
+func (c *CargoTestDefinition) isCustomHarness(target *CargoTarget) bool {
+	kind, ok := c.customHarnesses[harnessTargetName(target.Name)]
+	return ok && kind == target.Kind
+}
+
+// harnessOutputChunkSize is how much custom harness output is held before it is sent
+const harnessOutputChunkSize = 64 * 1024
+
+// customHarnessRun is a custom harness binary whose output is being captured. It is
+// reported as a single group, passing or failing with the binary's exit code.
+type customHarnessRun struct {
+	groupName   string
+	parentNames []string
+	start       time.Time
+	output      strings.Builder // Output not sent yet
+}
+
+// startHarnessRun reports the group of a custom harness target whose binary cargo
+// just started. Caller must hold c.mu.
+func (c *CargoTestDefinition) startHarnessRun(crateKey string) {
+	run := &customHarnessRun{
+		groupName:   strings.ReplaceAll(crateKey, "_", "-"),
+		parentNames: c.rootParentNames(),
+		start:       time.Now(),
+	}
+	c.logger.Debug("Capturing the output of custom harness %s", run.groupName)
+
+	if !c.discoveredGroups[crateKey] {
+		c.sendCrateGroupDiscovered(crateKey, run.groupName, run.parentNames)
+		c.discoveredGroups[crateKey] = true
+	}
+	c.groupStarts[crateKey] = true
+	c.sendGroupStart(run.groupName, run.parentNames)
+	c.harnessRun = run
+}
+
+// processHarnessLine captures a line of the custom harness running now. It returns
+// false when no harness is running or cargo moved on to the next binary, leaving the
+// line to the libtest parser.
+func (c *CargoTestDefinition) processHarnessLine(line string) bool {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	run := c.harnessRun
+	if run == nil {
+		return false
+	}
+	trimmed := strings.TrimSpace(line)
+	if (strings.HasPrefix(trimmed, "Running ") && runningTargetRegex.MatchString(line)) || docTestsRegex.MatchString(line) {
+		// cargo only starts the next binary after this one succeeded
+		c.finishHarnessRun("PASS")
+		return false
+	}
+
+	run.output.WriteString(line)
+	run.output.WriteString("\n")
+	if binaryExitRegex.MatchString(line) {
+		c.finishHarnessRun("FAIL")
+	} else if run.output.Len() >= harnessOutputChunkSize {
+		c.flushHarnessOutput(run)
+	}
+	return true
+}
+
+// finishHarnessRun reports the result of the custom harness running now. A run 3pio
+// stopped is left unfinished, as are libtest groups. Caller must hold c.mu.
+func (c *CargoTestDefinition) finishHarnessRun(status string) {
+	run := c.harnessRun
+	c.harnessRun = nil
+	c.flushHarnessOutput(run)
+	if c.interrupted || c.timedOut {
+		return
+	}
+	duration := float64(time.Since(run.start).Milliseconds())
+	c.sendGroupResult(run.groupName, run.parentNames, status, duration, 0, 0, 0)
+}
+
+// flushHarnessOutput sends the output captured from a custom harness as is
+func (c *CargoTestDefinition) flushHarnessOutput(run *customHarnessRun) {
+	if run.output.Len() == 0 {
+		return
+	}
+	c.sendIPCEvent(map[string]interface{}{
+		"eventType": "groupStdout",
+		"payload": map[string]interface{}{
+			"groupName":   run.groupName,
+			"parentNames": run.parentNames,
+			"chunk":       run.output.String(),
+		},
+	})
+	run.output.Reset()
+}
@@ -0,0 +1,245 @@
+package definitions
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"regexp"
+	"sort"
+	"strings"
+
+	"github.com/zk/3pio/internal/console"
+	"github.com/zk/3pio/internal/ipc"
+)
+
+// listEntryRegex matches a test printed by libtest's --list --format terse, e.g.
+// "tests::test_add: test" or "src/lib.rs - add (line 5): test"
+var listEntryRegex = regexp.MustCompile(`^(.+): (test|bench)$`)
+
+// ListedTest is a test from the preflight list, reported as PENDING until it runs
+type ListedTest struct {
+	Name    string
+	Parents []string
+}
+
+// ListCommand returns the command that lists the tests of a cargo test command
+// without running them (-- --list), for the preflight list. It passes the same cargo
+// flags, so the test binaries it builds are the ones the run reuses. It returns nil
+// when the tests can't be listed first: a custom harness may not know --list and
+// run its tests instead, and without cargo metadata there is no telling.
+func (c *CargoTestDefinition) ListCommand(cmd []string, ipcPath string) []string {
+	if CargoSubcommand(cmd) != "test" || UsesLLVMCov(cmd) {
+		// cargo llvm-cov would write a coverage report of the listing too
+		return nil
+	}
+	cargoArgs, testArgs := splitTestArgs(cmd)
+	for _, arg := range testArgs {
+		if arg == "--list" {
+			return nil
+		}
+	}
+
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	metadata := c.loadCargoMetadata(filepath.Dir(ipcPath))
+	if metadata == nil {
+		return nil
+	}
+	harnesses := c.ownHarnessTargets(metadata)
+	for _, target := range plannedTargets(metadata, cargoArgs, c.manifestDir()) {
+		if kind, ok := harnesses[harnessTargetName(target.Name)]; ok && kind == target.Kind {
+			c.logger.Debug("Not listing the tests first, %s target %s has its own harness", target.Kind, target.Name)
+			return nil
+		}
+	}
+
+	result := make([]string, 0, len(cmd)+8)
+	result = append(result, cargoArgs...)
+	if !hasMessageFormatArg(cargoArgs) {
+		result = append(result, "--message-format", "json")
+	}
+	result = append(result, "--")
+	result = append(result, testArgs...)
+	return append(result, "--list", "--format", "terse")
+}
+
+// ownHarnessTargets returns the harness = false targets of the workspace by name,
+// with their kind. Unlike the custom harnesses the run captures whole, these include
+// the targets reporting through threepio-reporter.
+func (c *CargoTestDefinition) ownHarnessTargets(metadata *CargoMetadata) map[string]string {
+	harnesses := make(map[string]string)
+	for _, pkg := range metadata.Packages {
+		manifest, err := os.ReadFile(pkg.ManifestPath)
+		if err != nil {
+			c.logger.Debug("Failed to read %s: %v", pkg.ManifestPath, err)
+			continue
+		}
+		for name, kind := range customHarnessTargets(string(manifest), pkg.Name) {
+			harnesses[name] = kind
+		}
+	}
+	return harnesses
+}
+
+// ProcessTestList reads the output of the ListCommand and reports every listed test
+// as PENDING, in the group the run reports it in
+func (c *CargoTestDefinition) ProcessTestList(output io.Reader, ipcPath string) error {
+	var err error
+	c.ipcWriter, err = NewIPCWriter(ipcPath)
+	if err != nil {
+		return fmt.Errorf("failed to create IPC writer: %w", err)
+	}
+	defer func() {
+		if err := c.ipcWriter.Close(); err != nil {
+			c.logger.Debug("Failed to close IPC writer: %v", err)
+		}
+	}()
+
+	c.mu.Lock()
+	if c.metadata != nil {
+		c.createPlannedGroups(c.metadata)
+	}
+	c.mu.Unlock()
+
+	crateKey := ""
+	listed := 0
+	lines := c.newLineReader(output)
+	for {
+		raw, _, ok := lines.Next()
+		if !ok {
+			break
+		}
+		line := console.ValidUTF8(raw)
+
+		if matches := runningTargetRegex.FindStringSubmatch(line); matches != nil {
+			c.mu.Lock()
+			crateKey = c.targetGroupKey(c.resolveTarget(matches[1], matches[2], matches[3]))
+			c.mu.Unlock()
+			continue
+		}
+		if matches := docTestsRegex.FindStringSubmatch(line); matches != nil {
+			crateKey = "doc:" + matches[1]
+			continue
+		}
+		// Build errors are left to the run, which reports them
+		if strings.Contains(line, `"reason":"compiler-artifact"`) {
+			var msg CargoMessage
+			if err := json.Unmarshal([]byte(line), &msg); err == nil {
+				c.recordArtifactTarget(&msg)
+			}
+			continue
+		}
+		if matches := listEntryRegex.FindStringSubmatch(line); matches != nil && crateKey != "" {
+			c.mu.Lock()
+			c.sendListedTest(crateKey, matches[1])
+			c.mu.Unlock()
+			listed++
+		}
+	}
+	if err := lines.Err(); err != nil {
+		c.logger.Debug("Test list ended with a read error: %v", err)
+	}
+	c.logger.Debug("Listed %d tests before the run", listed)
+	return nil
+}
+
+// sendListedTest reports a listed test as PENDING, discovering the groups the run
+// will report it in: its crate group and module groups, or the crate's doctests
+// group. Caller must hold c.mu.
+func (c *CargoTestDefinition) sendListedTest(crateKey, name string) {
+	parentNames := c.rootParentNames()
+	crateName := strings.TrimPrefix(crateKey, "doc:")
+	displayCrateName := strings.ReplaceAll(crateName, "_", "-")
+	if !c.discoveredGroups[crateName] {
+		c.sendCrateGroupDiscovered(crateName, displayCrateName, parentNames)
+		c.discoveredGroups[crateName] = true
+	}
+	testParents := append(append([]string{}, parentNames...), displayCrateName)
+
+	testName := name
+	if strings.HasPrefix(crateKey, "doc:") {
+		// Doctests keep rustdoc's "file - item (line N)" name
+		docGroupKey := fmt.Sprintf("%s::%s", crateName, docTestsGroupName)
+		if !c.discoveredGroups[docGroupKey] {
+			c.sendGroupDiscovered(docTestsGroupName, testParents)
+			c.discoveredGroups[docGroupKey] = true
+		}
+		testParents = append(testParents, docTestsGroupName)
+	} else {
+		parts := strings.Split(name, "::")
+		for i, moduleName := range parts[:len(parts)-1] {
+			moduleKey := fmt.Sprintf("%s::%s", crateName, strings.Join(parts[:i+1], "::"))
+			if !c.discoveredGroups[moduleKey] {
+				c.sendGroupDiscovered(moduleName, append([]string{}, testParents...))
+				c.discoveredGroups[moduleKey] = true
+			}
+			testParents = append(testParents, moduleName)
+		}
+		testName = parts[len(parts)-1]
+	}
+
+	c.sendTestCase(testName, testParents, "PENDING", 0, "", "")
+	c.listedTests[crateKey] = append(c.listedTests[crateKey], ListedTest{Name: testName, Parents: testParents})
+}
+
+// skipUnrunListedTests reports the listed tests whose binary never ran as skipped,
+// as when cargo stopped at the first failing binary, and finishes their groups as
+// SKIP. A run 3pio stopped leaves them pending. Caller must hold c.mu.
+func (c *CargoTestDefinition) skipUnrunListedTests() {
+	if c.interrupted || c.timedOut {
+		return
+	}
+	keys := make([]string, 0, len(c.listedTests))
+	for key := range c.listedTests {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+	root := len(c.rootParentNames())
+	for _, key := range keys {
+		c.logger.Debug("Listed tests of %s never ran", key)
+
+		// The groups below the crate group belong to the binary that never ran; the
+		// crate group has run already when only its doctests didn't
+		skipped := make(map[string]int)
+		groups := make(map[string][]string)
+		for _, test := range c.listedTests[key] {
+			payload := c.newTestCasePayload(test.Name, test.Parents, "SKIP", 0, "", "")
+			payload["skipReason"] = ipc.SkipReasonNotRun
+			c.sendIPCEvent(map[string]interface{}{
+				"eventType": "testCase",
+				"payload":   payload,
+			})
+			for depth := root; depth < len(test.Parents); depth++ {
+				groupPath := strings.Join(test.Parents[:depth+1], "\x00")
+				skipped[groupPath]++
+				groups[groupPath] = test.Parents[:depth+1]
+			}
+		}
+
+		// Nested groups finish before their parents
+		paths := make([]string, 0, len(groups))
+		for groupPath := range groups {
+			paths = append(paths, groupPath)
+		}
+		sort.Slice(paths, func(i, j int) bool {
+			if len(groups[paths[i]]) != len(groups[paths[j]]) {
+				return len(groups[paths[i]]) > len(groups[paths[j]])
+			}
+			return paths[i] < paths[j]
+		})
+		for _, groupPath := range paths {
+			group := groups[groupPath]
+			groupName := group[len(group)-1]
+			if len(group) == root+1 {
+				if !c.plannedGroups[groupName] {
+					continue
+				}
+				delete(c.plannedGroups, groupName)
+			}
+			c.sendGroupResult(groupName, group[:len(group)-1], "SKIP", 0, 0, 0, skipped[groupPath])
+		}
+	}
+	c.listedTests = make(map[string][]ListedTest)
+}
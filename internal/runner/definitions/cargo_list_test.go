@@ -0,0 +1,235 @@
+package definitions
+
+import (
+	"encoding/json"
+	"errors"
+	"os"
+	"path/filepath"
+	"reflect"
+	"strings"
+	"testing"
+)
+
+// stubCargoMetadata has cargo metadata return a rust-basic package with a lib and an
+// integration test, whose manifest is written to a temporary directory
+func stubCargoMetadata(t *testing.T, manifest string) {
+	dir := t.TempDir()
+	manifestPath := filepath.Join(dir, "Cargo.toml")
+	if err := os.WriteFile(manifestPath, []byte(manifest), 0644); err != nil {
+		t.Fatal(err)
+	}
+	metadata, _ := json.Marshal(map[string]interface{}{
+		"packages": []map[string]interface{}{{
+			"id":            "rust-basic 0.1.0",
+			"name":          "rust-basic",
+			"manifest_path": manifestPath,
+			"targets": []map[string]interface{}{
+				{"name": "rust_basic", "kind": []string{"lib"}, "test": true, "doctest": true},
+				{"name": "integration", "kind": []string{"test"}, "test": true},
+			},
+		}},
+		"workspace_members": []string{"rust-basic 0.1.0"},
+	})
+	original := runCargoMetadata
+	runCargoMetadata = func(string, string) ([]byte, error) {
+		return metadata, nil
+	}
+	t.Cleanup(func() { runCargoMetadata = original })
+}
+
+func TestCargoTestDefinition_ListCommand(t *testing.T) {
+	testCases := []struct {
+		name     string
+		manifest string
+		cmd      []string
+		expected []string
+	}{
+		{
+			name:     "plain",
+			manifest: "[package]\nname = \"rust-basic\"\n",
+			cmd:      []string{"cargo", "test", "-p", "rust-basic"},
+			expected: []string{"cargo", "test", "-p", "rust-basic", "--message-format", "json", "--", "--list", "--format", "terse"},
+		},
+		{
+			name:     "test filters",
+			manifest: "[package]\nname = \"rust-basic\"\n",
+			cmd:      []string{"cargo", "test", "--message-format=json-diagnostic-short", "--", "tests::", "--skip", "slow"},
+			expected: []string{"cargo", "test", "--message-format=json-diagnostic-short", "--", "tests::", "--skip", "slow", "--list", "--format", "terse"},
+		},
+		{
+			name:     "exact name filter",
+			manifest: "[package]\nname = \"rust-basic\"\n",
+			cmd:      []string{"cargo", "test", "fibonacci", "--", "--exact"},
+			expected: []string{"cargo", "test", "fibonacci", "--message-format", "json", "--", "--exact", "--list", "--format", "terse"},
+		},
+		{
+			name:     "already lists",
+			manifest: "[package]\nname = \"rust-basic\"\n",
+			cmd:      []string{"cargo", "test", "--", "--list"},
+		},
+		{
+			name:     "own harness",
+			manifest: "[package]\nname = \"rust-basic\"\n\n[[test]]\nname = \"integration\"\nharness = false\n",
+			cmd:      []string{"cargo", "test", "-p", "rust-basic"},
+		},
+		{
+			name:     "own harness not selected",
+			manifest: "[package]\nname = \"rust-basic\"\n\n[[test]]\nname = \"integration\"\nharness = false\n",
+			cmd:      []string{"cargo", "test", "-p", "rust-basic", "--lib"},
+			expected: []string{"cargo", "test", "-p", "rust-basic", "--lib", "--message-format", "json", "--", "--list", "--format", "terse"},
+		},
+		{
+			name:     "bench",
+			manifest: "[package]\nname = \"rust-basic\"\n",
+			cmd:      []string{"cargo", "bench"},
+		},
+	}
+	for _, tc := range testCases {
+		t.Run(tc.name, func(t *testing.T) {
+			stubCargoMetadata(t, tc.manifest)
+			ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+			def := NewCargoTestDefinition(createTestLogger(t))
+			def.ModifyCommand(tc.cmd, ipcPath, "run")
+			if got := def.ListCommand(tc.cmd, ipcPath); !reflect.DeepEqual(got, tc.expected) {
+				t.Errorf("Expected %v, got %v", tc.expected, got)
+			}
+		})
+	}
+
+	// Without cargo metadata there is no telling whether a target has its own harness
+	original := runCargoMetadata
+	runCargoMetadata = func(string, string) ([]byte, error) {
+		return nil, errors.New("failed to parse manifest")
+	}
+	t.Cleanup(func() { runCargoMetadata = original })
+	cmd := []string{"cargo", "test"}
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	def := NewCargoTestDefinition(createTestLogger(t))
+	def.ModifyCommand(cmd, ipcPath, "run")
+	if got := def.ListCommand(cmd, ipcPath); got != nil {
+		t.Errorf("Expected no list command without cargo metadata, got %v", got)
+	}
+}
+
+func TestCargoTestDefinition_ProcessTestList(t *testing.T) {
+	stubCargoMetadata(t, "[package]\nname = \"rust-basic\"\n")
+
+	list := `   Compiling rust-basic v0.1.0 (/work)
+    Finished ` + "`test`" + ` profile [unoptimized + debuginfo] target(s) in 0.52s
+     Running unittests src/lib.rs (target/debug/deps/rust_basic-0123456789abcdef)
+tests::test_add: test
+tests::test_sub: test
+     Running tests/integration.rs (target/debug/deps/integration-0123456789abcdef)
+it_works: test
+   Doc-tests rust_basic
+src/lib.rs - add (line 5): test
+`
+	// cargo stops after the lib fails, so the integration tests and doctests never
+	// run; test_extra wasn't listed
+	output := `     Running unittests src/lib.rs (target/debug/deps/rust_basic-0123456789abcdef)
+{"type":"suite","event":"started","test_count":3}
+{"type":"test","event":"started","name":"tests::test_add"}
+{"type":"test","name":"tests::test_add","event":"failed","exec_time":0.001}
+{"type":"test","event":"started","name":"tests::test_sub"}
+{"type":"test","name":"tests::test_sub","event":"ok","exec_time":0.001}
+{"type":"test","event":"started","name":"tests::test_extra"}
+{"type":"test","name":"tests::test_extra","event":"ok","exec_time":0.001}
+{"type":"suite","event":"failed","passed":2,"failed":1,"ignored":0,"measured":0,"filtered_out":0,"exec_time":0.003}
+`
+	cmd := []string{"cargo", "test", "-p", "rust-basic"}
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	def := NewCargoTestDefinition(createTestLogger(t))
+	def.ModifyCommand(cmd, ipcPath, "run")
+	if def.ListCommand(cmd, ipcPath) == nil {
+		t.Fatal("Expected a list command")
+	}
+	if err := def.ProcessTestList(strings.NewReader(list), ipcPath); err != nil {
+		t.Fatalf("ProcessTestList failed: %v", err)
+	}
+
+	// The listed tests are pending in the groups the run reports them in
+	pending := make(map[string][]string)
+	for _, e := range NewTestIPCCapture(ipcPath).GetEventsByType("testCase") {
+		payload := e["payload"].(map[string]interface{})
+		if payload["status"] != "PENDING" {
+			t.Errorf("Expected only pending tests before the run, got %v", payload)
+		}
+		var parents []string
+		for _, parent := range payload["parentNames"].([]interface{}) {
+			parents = append(parents, parent.(string))
+		}
+		pending[payload["testName"].(string)] = parents
+	}
+	expected := map[string][]string{
+		"test_add":                  {"rust-basic", "tests"},
+		"test_sub":                  {"rust-basic", "tests"},
+		"it_works":                  {"integration"},
+		"src/lib.rs - add (line 5)": {"rust-basic", "doctests"},
+	}
+	if !reflect.DeepEqual(pending, expected) {
+		t.Errorf("Expected pending tests %v, got %v", expected, pending)
+	}
+
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	capture := NewTestIPCCapture(ipcPath)
+	statuses := make(map[string]interface{})
+	skipReasons := make(map[string]interface{})
+	for _, e := range capture.GetEventsByType("testCase") {
+		payload := e["payload"].(map[string]interface{})
+		statuses[payload["testName"].(string)] = payload["status"]
+		skipReasons[payload["testName"].(string)] = payload["skipReason"]
+	}
+	expectedStatuses := map[string]interface{}{
+		"test_add":                  "FAIL",
+		"test_sub":                  "PASS",
+		"test_extra":                "PASS",
+		"it_works":                  "SKIP",
+		"src/lib.rs - add (line 5)": "SKIP",
+	}
+	if !reflect.DeepEqual(statuses, expectedStatuses) {
+		t.Errorf("Expected statuses %v, got %v", expectedStatuses, statuses)
+	}
+	for _, name := range []string{"it_works", "src/lib.rs - add (line 5)"} {
+		if skipReasons[name] != "not_run" {
+			t.Errorf("Expected %s skipped as not_run, got %v", name, skipReasons[name])
+		}
+	}
+
+	results := make(map[string]interface{})
+	for _, e := range capture.GetEventsByType("testGroupResult") {
+		payload := e["payload"].(map[string]interface{})
+		results[payload["groupName"].(string)] = payload["status"]
+	}
+	if results["rust-basic"] != "FAIL" || results["integration"] != "SKIP" || results["doctests"] != "SKIP" {
+		t.Errorf("Expected the lib to fail and the groups that never ran to be skipped, got %v", results)
+	}
+}
+
+func TestCargoTestDefinition_ListedTestsInterrupted(t *testing.T) {
+	stubCargoMetadata(t, "[package]\nname = \"rust-basic\"\n")
+
+	list := `     Running tests/integration.rs (target/debug/deps/integration-0123456789abcdef)
+it_works: test
+`
+	cmd := []string{"cargo", "test", "-p", "rust-basic", "--test", "integration"}
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	def := NewCargoTestDefinition(createTestLogger(t))
+	def.ModifyCommand(cmd, ipcPath, "run")
+	def.ListCommand(cmd, ipcPath)
+	if err := def.ProcessTestList(strings.NewReader(list), ipcPath); err != nil {
+		t.Fatalf("ProcessTestList failed: %v", err)
+	}
+	def.MarkInterrupted()
+	if err := def.ProcessOutput(strings.NewReader(""), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+
+	// Tests 3pio stopped before they ran aren't skipped
+	for _, e := range NewTestIPCCapture(ipcPath).GetEventsByType("testCase") {
+		if status := e["payload"].(map[string]interface{})["status"]; status != "PENDING" {
+			t.Errorf("Expected it_works to stay pending, got %v", status)
+		}
+	}
+}
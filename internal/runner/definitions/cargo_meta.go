@@ -0,0 +1,417 @@
+package definitions
+
+import (
+	"context"
+	"encoding/json"
+	"os"
+	"os/exec"
+	"path"
+	"path/filepath"
+	"sort"
+	"strings"
+	"time"
+)
+
+// cargoMetadataTimeout bounds `cargo metadata`, which runs while the test command builds
+const cargoMetadataTimeout = 10 * time.Second
+
+// cargoMetadataCacheFile is where the run directory keeps the output of cargo metadata,
+// so the retries of a run don't ask cargo again
+const cargoMetadataCacheFile = "cargo-metadata.json"
+
+// CargoMetadata is the part of `cargo metadata --format-version 1 --no-deps` 3pio reads
+type CargoMetadata struct {
+	Packages                []CargoMetadataPackage `json:"packages"`
+	WorkspaceMembers        []string               `json:"workspace_members"`
+	WorkspaceDefaultMembers []string               `json:"workspace_default_members"` // Since cargo 1.71
+	WorkspaceRoot           string                 `json:"workspace_root"`
+	TargetDirectory         string                 `json:"target_directory"` // Where cargo builds, which cross mounts at /target
+}
+
+// CargoMetadataPackage is a workspace member in cargo metadata
+type CargoMetadataPackage struct {
+	ID           string                `json:"id"`
+	Name         string                `json:"name"`
+	ManifestPath string                `json:"manifest_path"`
+	Targets      []CargoMetadataTarget `json:"targets"`
+	Dependencies []struct {
+		Name string `json:"name"`
+	} `json:"dependencies"`
+}
+
+// CargoMetadataTarget is a target of a package in cargo metadata
+type CargoMetadataTarget struct {
+	Name    string   `json:"name"`
+	Kind    []string `json:"kind"` // e.g. ["lib"], ["rlib", "cdylib"], ["test"], ["custom-build"]
+	SrcPath string   `json:"src_path"`
+	Test    bool     `json:"test"` // Tested by a plain `cargo test`
+	Doctest bool     `json:"doctest"`
+}
+
+// runCargoMetadata runs cargo metadata in dir, or the working directory when it's
+// empty, for the manifest the command names if it does. Dependencies aren't resolved
+// (--no-deps): only workspace members have test targets, and resolving could hit the
+// network. Replaced in tests.
+var runCargoMetadata = func(dir, manifestPath string) ([]byte, error) {
+	ctx, cancel := context.WithTimeout(context.Background(), cargoMetadataTimeout)
+	defer cancel()
+	args := []string{"metadata", "--format-version", "1", "--no-deps"}
+	if manifestPath != "" {
+		args = append(args, "--manifest-path", manifestPath)
+	}
+	cmd := exec.CommandContext(ctx, "cargo", args...)
+	cmd.Dir = dir
+	return cmd.Output()
+}
+
+// loadCargoMetadata returns the workspace's cargo metadata, from the run directory's
+// cache when a previous attempt of the run saved it. It returns nil when cargo
+// metadata fails, e.g. on a broken manifest, and groups are then only known from the
+// banners of the binaries cargo runs. Caller must hold c.mu.
+func (c *CargoTestDefinition) loadCargoMetadata(runDir string) *CargoMetadata {
+	if !c.metadataLoaded {
+		c.metadata = c.readCargoMetadata(runDir)
+		c.metadataLoaded = true
+	}
+	return c.metadata
+}
+
+// readCargoMetadata reads the metadata from the cache or runs cargo metadata
+func (c *CargoTestDefinition) readCargoMetadata(runDir string) *CargoMetadata {
+	cachePath := filepath.Join(runDir, cargoMetadataCacheFile)
+	output, err := os.ReadFile(cachePath)
+	if err != nil {
+		output, err = runCargoMetadata(c.dir, cargoFlagValue(c.cargoArgs, "--manifest-path"))
+		if err != nil {
+			c.logger.Warn("cargo metadata failed, groups will be discovered as test binaries start: %v", err)
+			return nil
+		}
+		if err := os.WriteFile(cachePath, output, 0644); err != nil {
+			c.logger.Debug("Failed to cache cargo metadata: %v", err)
+		}
+	}
+
+	var metadata CargoMetadata
+	if err := json.Unmarshal(output, &metadata); err != nil {
+		c.logger.Warn("Failed to parse cargo metadata, groups will be discovered as test binaries start: %v", err)
+		return nil
+	}
+	return &metadata
+}
+
+// plannedTargets returns the test targets a cargo test or cargo bench command will run,
+// in the order cargo runs them, from the packages the command selects (-p, --workspace,
+// --exclude) and its target flags (--lib, --test NAME, --all-targets, ...). dir is
+// where cargo looks for the manifest when the command names none. Doctests aren't
+// included: their groups are nested under the crate group once they run.
+func plannedTargets(metadata *CargoMetadata, cargoArgs []string, dir string) []*CargoTarget {
+	subcommand := CargoSubcommand(cargoArgs)
+	if subcommand != "test" && subcommand != "bench" {
+		return nil
+	}
+	selection := parseCargoSelection(cargoArgs)
+	if selection.docOnly {
+		return nil
+	}
+
+	var targets []*CargoTarget
+	for _, pkg := range selectedPackages(metadata, selection, dir) {
+		var pkgTargets []*CargoTarget
+		for _, target := range pkg.Targets {
+			if len(target.Kind) == 0 || target.Kind[0] == "custom-build" {
+				continue
+			}
+			kind := cargoTargetKind(target.Kind[0])
+			if selection.selects(kind, target, subcommand) {
+				pkgTargets = append(pkgTargets, &CargoTarget{Kind: kind, Name: target.Name, ManifestDir: filepath.Dir(pkg.ManifestPath)})
+			}
+		}
+		// cargo runs a package's lib first, then its bins, tests, benches and examples
+		sort.SliceStable(pkgTargets, func(i, j int) bool {
+			return cargoTargetOrder[pkgTargets[i].Kind] < cargoTargetOrder[pkgTargets[j].Kind]
+		})
+		targets = append(targets, pkgTargets...)
+	}
+	return targets
+}
+
+// cargoTargetOrder ranks target kinds in the order cargo runs them
+var cargoTargetOrder = map[string]int{"lib": 0, "bin": 1, "test": 2, "bench": 3, "example": 4}
+
+// cargoKindFlags are the flags that select every target of a kind
+var cargoKindFlags = map[string]string{"--bins": "bin", "--examples": "example", "--benches": "bench"}
+
+// cargoSelection is what a cargo test command selects with its package and target flags
+type cargoSelection struct {
+	workspace bool     // --workspace (or its deprecated alias --all)
+	packages  []string // -p/--package specs
+	excludes  []string // --exclude specs
+
+	// Target flags. byKind holds the kinds selected whole (--bins, --examples, ...) and
+	// byName the target names (or globs) selected per kind (--bin NAME, ...).
+	byKind      map[string]bool
+	byName      map[string][]string
+	lib         bool
+	hasTargets  bool // Any target flag was passed, so only the selected targets run
+	docOnly     bool // --doc runs nothing but doctests
+	testTargets bool // --tests selects every target with test = true
+}
+
+// parseCargoSelection reads the package and target flags of a cargo command, up to "--"
+func parseCargoSelection(cargoArgs []string) cargoSelection {
+	selection := cargoSelection{byKind: make(map[string]bool), byName: make(map[string][]string)}
+	for i := 0; i < len(cargoArgs); i++ {
+		arg := cargoArgs[i]
+		if arg == "--" {
+			break
+		}
+		flag, value, hasValue := strings.Cut(arg, "=")
+		if strings.HasPrefix(arg, "-p") && len(arg) > 2 && arg[2] != '=' {
+			flag, value, hasValue = "-p", arg[2:], true
+		}
+		nextValue := func() string {
+			if hasValue {
+				return value
+			}
+			if i+1 < len(cargoArgs) {
+				i++
+				return cargoArgs[i]
+			}
+			return ""
+		}
+
+		switch flag {
+		case "--workspace", "--all":
+			selection.workspace = true
+		case "-p", "--package":
+			selection.packages = append(selection.packages, nextValue())
+		case "--exclude":
+			selection.excludes = append(selection.excludes, nextValue())
+		case "--lib":
+			selection.lib, selection.hasTargets = true, true
+		case "--bins", "--examples", "--benches":
+			selection.byKind[cargoKindFlags[flag]] = true
+			selection.hasTargets = true
+		case "--tests":
+			selection.testTargets, selection.hasTargets = true, true
+		case "--all-targets":
+			selection.lib, selection.hasTargets = true, true
+			for _, kind := range []string{"bin", "test", "bench", "example"} {
+				selection.byKind[kind] = true
+			}
+		case "--bin", "--example", "--test", "--bench":
+			kind := flag[2:]
+			selection.byName[kind] = append(selection.byName[kind], nextValue())
+			selection.hasTargets = true
+		case "--doc":
+			selection.docOnly = true
+		default:
+			if cargoValueFlags[arg] {
+				i++
+			}
+		}
+	}
+	return selection
+}
+
+// selects reports whether the command runs a target of the given kind
+func (s cargoSelection) selects(kind string, target CargoMetadataTarget, subcommand string) bool {
+	if !s.hasTargets {
+		if subcommand == "bench" {
+			// cargo bench runs the targets with bench = true, which cargo metadata
+			// doesn't report: by default the lib, bins and benches
+			return kind == "lib" || kind == "bin" || kind == "bench"
+		}
+		return target.Test
+	}
+	if kind == "lib" && s.lib {
+		return true
+	}
+	if s.byKind[kind] || (s.testTargets && target.Test) {
+		return true
+	}
+	for _, pattern := range s.byName[kind] {
+		if matchCargoName(pattern, target.Name) {
+			return true
+		}
+	}
+	return false
+}
+
+// selectedPackages returns the workspace members a command tests. Without package
+// flags that is the package of the nearest manifest, or the default members of a
+// virtual workspace.
+func selectedPackages(metadata *CargoMetadata, selection cargoSelection, dir string) []CargoMetadataPackage {
+	members := make(map[string]bool, len(metadata.WorkspaceMembers))
+	for _, id := range metadata.WorkspaceMembers {
+		members[id] = true
+	}
+	isMember := func(pkg CargoMetadataPackage) bool {
+		return len(members) == 0 || members[pkg.ID]
+	}
+
+	var selected []CargoMetadataPackage
+	switch {
+	case selection.workspace:
+		for _, pkg := range metadata.Packages {
+			if isMember(pkg) && !matchesAnySpec(selection.excludes, pkg.Name) {
+				selected = append(selected, pkg)
+			}
+		}
+	case len(selection.packages) > 0:
+		for _, pkg := range metadata.Packages {
+			if matchesAnySpec(selection.packages, pkg.Name) {
+				selected = append(selected, pkg)
+			}
+		}
+	default:
+		if pkg, ok := nearestPackage(metadata, dir); ok {
+			return []CargoMetadataPackage{pkg}
+		}
+		defaults := make(map[string]bool)
+		for _, id := range metadata.WorkspaceDefaultMembers {
+			defaults[id] = true
+		}
+		for _, pkg := range metadata.Packages {
+			if defaults[pkg.ID] || (len(defaults) == 0 && members[pkg.ID]) {
+				selected = append(selected, pkg)
+			}
+		}
+	}
+	return selected
+}
+
+// nearestPackage finds the package whose Cargo.toml cargo would pick up from dir,
+// the first one found walking up. A virtual manifest belongs to no package.
+func nearestPackage(metadata *CargoMetadata, dir string) (CargoMetadataPackage, bool) {
+	for {
+		manifest := filepath.Join(dir, "Cargo.toml")
+		if _, err := os.Stat(manifest); err == nil {
+			for _, pkg := range metadata.Packages {
+				if filepath.Clean(pkg.ManifestPath) == manifest {
+					return pkg, true
+				}
+			}
+			return CargoMetadataPackage{}, false
+		}
+		parent := filepath.Dir(dir)
+		if parent == dir {
+			return CargoMetadataPackage{}, false
+		}
+		dir = parent
+	}
+}
+
+// matchesAnySpec reports whether a package name matches one of the package specs
+// of -p or --exclude
+func matchesAnySpec(specs []string, name string) bool {
+	for _, spec := range specs {
+		if matchCargoName(packageSpecName(spec), name) {
+			return true
+		}
+	}
+	return false
+}
+
+// packageSpecName returns the name part of a package ID spec: "serde", "serde@1.0.0",
+// "serde:1.0.0" or "path+file:///work/serde#serde@1.0.0" all name serde
+func packageSpecName(spec string) string {
+	if i := strings.LastIndex(spec, "#"); i >= 0 {
+		spec = spec[i+1:]
+	} else if strings.Contains(spec, "://") {
+		// A URL without a fragment names the package after its last path segment
+		spec = spec[strings.LastIndex(spec, "/")+1:]
+	}
+	if i := strings.IndexAny(spec, "@:"); i >= 0 {
+		spec = spec[:i]
+	}
+	return spec
+}
+
+// matchCargoName matches a package or target name against a name cargo accepts,
+// which may be a glob (-p 'serde*', --test 'it_*')
+func matchCargoName(pattern, name string) bool {
+	if pattern == name {
+		return true
+	}
+	matched, err := path.Match(pattern, name)
+	return err == nil && matched
+}
+
+// cargoFlagValue returns the value of a cargo flag ("--flag value" or "--flag=value"),
+// "" if the command doesn't pass it
+func cargoFlagValue(cargoArgs []string, flag string) string {
+	for i, arg := range cargoArgs {
+		if arg == "--" {
+			break
+		}
+		if arg == flag && i+1 < len(cargoArgs) {
+			return cargoArgs[i+1]
+		}
+		if value, ok := strings.CutPrefix(arg, flag+"="); ok {
+			return value
+		}
+	}
+	return ""
+}
+
+// createPlannedGroups announces the group of every target the command will run, so
+// the report lists them as pending before their binaries start. Caller must hold c.mu.
+func (c *CargoTestDefinition) createPlannedGroups(metadata *CargoMetadata) {
+	for _, target := range plannedTargets(metadata, c.cargoArgs, c.manifestDir()) {
+		crateKey := c.targetGroupKey(target)
+		groupName := strings.ReplaceAll(crateKey, "_", "-")
+		if c.plannedGroups[groupName] {
+			// Targets of the same name in two packages share a group
+			continue
+		}
+		c.sendCrateGroupDiscovered(crateKey, groupName, c.rootParentNames())
+		c.discoveredGroups[crateKey] = true
+		c.plannedGroups[groupName] = true
+	}
+	c.logger.Debug("Planned %d groups from cargo metadata", len(c.plannedGroups))
+}
+
+// manifestDir returns the directory cargo looks for the manifest from: the directory of
+// the command's --manifest-path, or the working directory
+func (c *CargoTestDefinition) manifestDir() string {
+	if manifestPath := cargoFlagValue(c.cargoArgs, "--manifest-path"); manifestPath != "" {
+		if dir, err := filepath.Abs(c.inWorkDir(filepath.Dir(manifestPath))); err == nil {
+			return dir
+		}
+	}
+	return c.workingDir()
+}
+
+// markPlannedGroupRun records that the binary of a planned group started
+func (c *CargoTestDefinition) markPlannedGroupRun(crateKey string) {
+	delete(c.plannedGroups, strings.ReplaceAll(crateKey, "_", "-"))
+}
+
+// skipUnrunPlannedGroups finishes the planned groups whose binaries never ran, as when
+// cargo stopped at the first failing binary or the build failed. A command with a test
+// filter may be a retry, whose groups already have results from the first attempt, so
+// they are left alone. Caller must hold c.mu.
+func (c *CargoTestDefinition) skipUnrunPlannedGroups() {
+	if c.hasTestFilter || c.interrupted || c.timedOut {
+		return
+	}
+	names := make([]string, 0, len(c.plannedGroups))
+	for name := range c.plannedGroups {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	for _, name := range names {
+		c.logger.Debug("Planned group %s never ran", name)
+		c.sendGroupResult(name, c.rootParentNames(), "SKIP", 0, 0, 0, 0)
+	}
+	c.plannedGroups = make(map[string]bool)
+}
+
+// rootParentNames returns the parents of a crate group
+func (c *CargoTestDefinition) rootParentNames() []string {
+	if c.workspaceName != "" {
+		return []string{c.workspaceName}
+	}
+	return nil
+}
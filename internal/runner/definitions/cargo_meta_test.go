@@ -0,0 +1,223 @@
+package definitions
+
+import (
+	"encoding/json"
+	"errors"
+	"os"
+	"path/filepath"
+	"reflect"
+	"strings"
+	"testing"
+)
+
+// testWorkspace writes a virtual workspace with a core and an app member and returns
+// its root and cargo metadata
+func testWorkspace(t *testing.T) (string, *CargoMetadata) {
+	root := t.TempDir()
+	for _, manifest := range []string{"Cargo.toml", "core/Cargo.toml", "app/Cargo.toml"} {
+		path := filepath.Join(root, manifest)
+		if err := os.MkdirAll(filepath.Dir(path), 0755); err != nil {
+			t.Fatal(err)
+		}
+		if err := os.WriteFile(path, []byte("[package]\n"), 0644); err != nil {
+			t.Fatal(err)
+		}
+	}
+	metadata := &CargoMetadata{
+		Packages: []CargoMetadataPackage{
+			{
+				ID:           "path+file://" + root + "/core#0.1.0",
+				Name:         "core",
+				ManifestPath: filepath.Join(root, "core", "Cargo.toml"),
+				Targets: []CargoMetadataTarget{
+					{Name: "build-script-build", Kind: []string{"custom-build"}},
+					{Name: "integration", Kind: []string{"test"}, Test: true},
+					{Name: "core", Kind: []string{"rlib", "cdylib"}, Test: true, Doctest: true},
+					{Name: "core-cli", Kind: []string{"bin"}, Test: true},
+					{Name: "demo", Kind: []string{"example"}},
+					{Name: "speed", Kind: []string{"bench"}},
+				},
+			},
+			{
+				ID:           "path+file://" + root + "/app#0.1.0",
+				Name:         "app",
+				ManifestPath: filepath.Join(root, "app", "Cargo.toml"),
+				Targets: []CargoMetadataTarget{
+					{Name: "app", Kind: []string{"bin"}, Test: true},
+					{Name: "app_it", Kind: []string{"test"}, Test: true},
+				},
+			},
+		},
+	}
+	for _, pkg := range metadata.Packages {
+		metadata.WorkspaceMembers = append(metadata.WorkspaceMembers, pkg.ID)
+	}
+	return root, metadata
+}
+
+func TestPlannedTargets(t *testing.T) {
+	root, metadata := testWorkspace(t)
+
+	tests := []struct {
+		name    string
+		command string
+		dir     string
+		want    []string
+	}{
+		{"virtual workspace runs every member", "cargo test", "", []string{"lib:core", "bin:core-cli", "test:integration", "bin:app", "test:app_it"}},
+		{"member directory runs its package", "cargo test", "core/src", []string{"lib:core", "bin:core-cli", "test:integration"}},
+		{"package flag", "cargo test -p app", "", []string{"bin:app", "test:app_it"}},
+		{"package spec with version", "cargo test --package=core@0.1.0 --examples", "", []string{"example:demo"}},
+		{"attached package flag", "cargo test -pcore --lib", "", []string{"lib:core"}},
+		{"workspace with exclude", "cargo test --workspace --exclude core", "core", []string{"bin:app", "test:app_it"}},
+		{"package glob", "cargo test -p a* --tests", "", []string{"bin:app", "test:app_it"}},
+		{"named test target", "cargo test -p core --test integration", "", []string{"test:integration"}},
+		{"all targets", "cargo test -p core --all-targets", "", []string{"lib:core", "bin:core-cli", "test:integration", "bench:speed", "example:demo"}},
+		{"bench runs lib, bins and benches", "cargo bench -p core", "", []string{"lib:core", "bin:core-cli", "bench:speed"}},
+		{"filter after the separator", "cargo test -p app -- --test-threads 1", "", []string{"bin:app", "test:app_it"}},
+		{"doctests only", "cargo test --doc", "", nil},
+		{"not a test command", "cargo build", "", nil},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			var got []string
+			for _, target := range plannedTargets(metadata, strings.Fields(tt.command), filepath.Join(root, tt.dir)) {
+				got = append(got, target.Kind+":"+target.Name)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("plannedTargets(%q) = %v, want %v", tt.command, got, tt.want)
+			}
+		})
+	}
+
+	// Default members narrow down what a virtual workspace runs
+	metadata.WorkspaceDefaultMembers = []string{metadata.Packages[1].ID}
+	if got := plannedTargets(metadata, []string{"cargo", "test"}, root); len(got) != 2 || got[0].Name != "app" {
+		t.Errorf("Expected the default member's targets, got %v", got)
+	}
+}
+
+func TestPackageSpecName(t *testing.T) {
+	for spec, want := range map[string]string{
+		"serde":                                   "serde",
+		"serde@1.0.0":                             "serde",
+		"serde:1.0.0":                             "serde",
+		"path+file:///work/serde#serde@1.0.0":     "serde",
+		"path+file:///work/serde_json#serde_json": "serde_json",
+		"path+file:///work/serde":                 "serde",
+	} {
+		if got := packageSpecName(spec); got != want {
+			t.Errorf("packageSpecName(%q) = %q, want %q", spec, got, want)
+		}
+	}
+}
+
+func TestCargoTestDefinition_PlannedGroups(t *testing.T) {
+	metadata, _ := json.Marshal(map[string]interface{}{
+		"packages": []map[string]interface{}{{
+			"id":            "rust-basic 0.1.0",
+			"name":          "rust-basic",
+			"manifest_path": "/work/Cargo.toml",
+			"targets": []map[string]interface{}{
+				{"name": "rust_basic", "kind": []string{"lib"}, "test": true},
+				{"name": "integration", "kind": []string{"test"}, "test": true},
+			},
+		}},
+		"workspace_members": []string{"rust-basic 0.1.0"},
+	})
+	original := runCargoMetadata
+	calls := 0
+	runCargoMetadata = func(string, string) ([]byte, error) {
+		calls++
+		return metadata, nil
+	}
+	t.Cleanup(func() { runCargoMetadata = original })
+
+	// cargo stops after the lib fails, so the integration tests never run
+	output := `     Running unittests src/lib.rs (target/debug/deps/rust_basic-0123456789abcdef)
+{"type":"suite","event":"started","test_count":1}
+{"type":"test","event":"started","name":"tests::test_add"}
+{"type":"test","name":"tests::test_add","event":"failed","exec_time":0.001}
+{"type":"suite","event":"failed","passed":0,"failed":1,"ignored":0,"measured":0,"filtered_out":0,"exec_time":0.001}
+`
+	runDir := t.TempDir()
+	ipcPath := filepath.Join(runDir, "ipc.jsonl")
+	def := NewCargoTestDefinition(createTestLogger(t))
+	def.ModifyCommand([]string{"cargo", "test", "-p", "rust-basic"}, ipcPath, "run")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	capture := NewTestIPCCapture(ipcPath)
+
+	// Both groups are announced before the lib's binary starts
+	var discovered []string
+	for _, e := range capture.GetEvents() {
+		if e["eventType"] == "testGroupStart" {
+			break
+		}
+		if e["eventType"] == "testGroupDiscovered" {
+			discovered = append(discovered, e["payload"].(map[string]interface{})["groupName"].(string))
+		}
+	}
+	if !reflect.DeepEqual(discovered, []string{"rust-basic", "integration"}) {
+		t.Errorf("Expected both groups discovered up front, got %v", discovered)
+	}
+
+	results := make(map[string]interface{})
+	for _, e := range capture.GetEventsByType("testGroupResult") {
+		payload := e["payload"].(map[string]interface{})
+		results[payload["groupName"].(string)] = payload["status"]
+	}
+	if results["rust-basic"] != "FAIL" || results["integration"] != "SKIP" {
+		t.Errorf("Expected the lib to fail and the integration tests to be skipped, got %v", results)
+	}
+
+	// A retry in the same run directory reads the cached metadata
+	if _, err := os.Stat(filepath.Join(runDir, cargoMetadataCacheFile)); err != nil {
+		t.Fatalf("Expected cargo metadata cached in the run directory: %v", err)
+	}
+	retry := NewCargoTestDefinition(createTestLogger(t))
+	retryIPC := filepath.Join(runDir, "ipc-attempt-2.jsonl")
+	retry.ModifyCommand([]string{"cargo", "test", "-p", "rust-basic", "--", "--exact", "tests::test_add"}, retryIPC, "run")
+	if err := retry.ProcessOutput(strings.NewReader(output), retryIPC); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	if calls != 1 {
+		t.Errorf("Expected cargo metadata to run once, ran %d times", calls)
+	}
+	for _, e := range NewTestIPCCapture(retryIPC).GetEventsByType("testGroupResult") {
+		if payload := e["payload"].(map[string]interface{}); payload["groupName"] == "integration" {
+			t.Errorf("Expected a filtered retry not to skip the groups it didn't run, got %v", payload)
+		}
+	}
+}
+
+func TestCargoTestDefinition_CargoMetadataFails(t *testing.T) {
+	original := runCargoMetadata
+	runCargoMetadata = func(string, string) ([]byte, error) {
+		return nil, errors.New("failed to parse manifest at `/work/Cargo.toml`")
+	}
+	t.Cleanup(func() { runCargoMetadata = original })
+
+	output := `     Running unittests src/lib.rs (target/debug/deps/rust_basic-0123456789abcdef)
+{"type":"suite","event":"started","test_count":1}
+{"type":"test","event":"started","name":"tests::test_add"}
+{"type":"test","name":"tests::test_add","event":"ok","exec_time":0.001}
+{"type":"suite","event":"ok","passed":1,"failed":0,"ignored":0,"measured":0,"filtered_out":0,"exec_time":0.001}
+`
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	def := NewCargoTestDefinition(createTestLogger(t))
+	def.ModifyCommand([]string{"cargo", "test"}, ipcPath, "run")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+
+	// Groups are still found from the banners
+	results := NewTestIPCCapture(ipcPath).GetEventsByType("testGroupResult")
+	if len(results) != 1 || results[0]["payload"].(map[string]interface{})["status"] != "PASS" {
+		t.Errorf("Expected the lib group to pass, got %v", results)
+	}
+	if _, err := os.Stat(filepath.Join(filepath.Dir(ipcPath), cargoMetadataCacheFile)); !os.IsNotExist(err) {
+		t.Errorf("Expected no cached metadata after cargo metadata failed, got %v", err)
+	}
+}
@@ -1,10 +1,15 @@
 package definitions
 
 import (
+	"encoding/json"
 	"os"
+	"path/filepath"
+	"reflect"
 	"strings"
 	"testing"
+	"unicode/utf8"
 
+	"github.com/zk/3pio/internal/ipc"
 	"github.com/zk/3pio/internal/logger"
 )
 
@@ -47,11 +52,36 @@ func TestCargoTestDefinition_Detect(t *testing.T) {
 			args:     []string{"cargo", "+stable", "test", "--release"},
 			expected: true,
 		},
+		{
+			name:     "cargo bench",
+			args:     []string{"cargo", "bench"},
+			expected: true,
+		},
+		{
+			name:     "cargo bench with toolchain",
+			args:     []string{"cargo", "+nightly", "bench"},
+			expected: true,
+		},
 		{
 			name:     "full path cargo test",
 			args:     []string{"/usr/bin/cargo", "test"},
 			expected: true,
 		},
+		{
+			name:     "cross test",
+			args:     []string{"cross", "test", "--target", "aarch64-unknown-linux-gnu"},
+			expected: true,
+		},
+		{
+			name:     "cross test with toolchain",
+			args:     []string{"cross", "+nightly", "test"},
+			expected: true,
+		},
+		{
+			name:     "cross build",
+			args:     []string{"cross", "build"},
+			expected: false,
+		},
 		{
 			name:     "full path with toolchain",
 			args:     []string{"/usr/bin/cargo", "+nightly", "test"},
@@ -124,6 +154,26 @@ func TestCargoTestDefinition_ModifyCommand(t *testing.T) {
 			cmd:      []string{"cargo", "+nightly", "test"},
 			contains: []string{"cargo", "+nightly", "test", "--", "-Z", "unstable-options"},
 		},
+		{
+			name:     "cargo test requests JSON compiler messages",
+			cmd:      []string{"cargo", "test", "--lib", "--", "--nocapture"},
+			contains: []string{"cargo test --lib --message-format json -- --nocapture -Z unstable-options"},
+		},
+		{
+			name:     "cargo test passes through --include-ignored",
+			cmd:      []string{"cargo", "test", "--", "--include-ignored"},
+			contains: []string{"cargo test --message-format json -- --include-ignored -Z unstable-options --format json"},
+		},
+		{
+			name:     "cargo test passes through --ignored",
+			cmd:      []string{"cargo", "test", "--lib", "--", "--ignored"},
+			contains: []string{"cargo test --lib --message-format json -- --ignored -Z unstable-options --format json"},
+		},
+		{
+			name:     "cargo test keeps user message format",
+			cmd:      []string{"cargo", "test", "--message-format=short"},
+			contains: []string{"cargo test --message-format=short -- -Z unstable-options"},
+		},
 	}
 
 	for _, tt := range tests {
@@ -139,6 +189,43 @@ func TestCargoTestDefinition_ModifyCommand(t *testing.T) {
 	}
 }
 
+func TestCargoTestDefinition_RerunCommand(t *testing.T) {
+	def := NewCargoTestDefinition(createTestLogger(t))
+
+	failed := []ipc.FailedTest{
+		{Name: "test_add_004", ParentNames: []string{"rust-performance", "tests"}},
+		{Name: "test_parse", ParentNames: []string{"rust-performance", "parser", "tests"}},
+		{Name: "test_cli", ParentNames: []string{"integration_test"}},
+		{Name: "src/lib.rs - add (line 5)", ParentNames: []string{"rust-performance", docTestsGroupName}},
+		{Name: "test_add_004", ParentNames: []string{"rust-performance", "tests"}},
+	}
+
+	tests := []struct {
+		name     string
+		cmd      []string
+		expected string
+	}{
+		{"adds exact filters", []string{"cargo", "test"}, "cargo test -- --exact tests::test_add_004 parser::tests::test_parse test_cli"},
+		{"keeps test binary args", []string{"cargo", "test", "--lib", "--", "--nocapture"}, "cargo test --lib -- --nocapture --exact tests::test_add_004 parser::tests::test_parse test_cli"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			result, err := def.RerunCommand(tt.cmd, failed)
+			if err != nil {
+				t.Fatalf("RerunCommand failed: %v", err)
+			}
+			if strings.Join(result, " ") != tt.expected {
+				t.Errorf("Expected %q, got %q", tt.expected, strings.Join(result, " "))
+			}
+		})
+	}
+
+	// Doctests can't be selected by name
+	if _, err := def.RerunCommand([]string{"cargo", "test"}, failed[3:4]); err == nil {
+		t.Errorf("Expected an error when only doctests failed")
+	}
+}
+
 func TestCargoTestDefinition_ProcessJSONEvents(t *testing.T) {
 	logger, _ := logger.NewFileLogger()
 	defer func() { _ = logger.Close() }()
@@ -213,3 +300,1568 @@ func TestCargoTestDefinition_SetEnvironment(t *testing.T) {
 		t.Errorf("SetEnvironment should return RUSTC_BOOTSTRAP=1, got %s", env[0])
 	}
 }
+
+func TestParseDocTestName(t *testing.T) {
+	tests := []struct {
+		name     string
+		input    string
+		expected *DocTestLocation
+	}{
+		{
+			name:     "item doctest",
+			input:    "src/lib.rs - calculator::subtract (line 24)",
+			expected: &DocTestLocation{File: "src/lib.rs", ItemPath: "calculator::subtract", Line: 24},
+		},
+		{
+			name:     "crate level doctest",
+			input:    "src/lib.rs - (line 1)",
+			expected: &DocTestLocation{File: "src/lib.rs", ItemPath: "", Line: 1},
+		},
+		{
+			name:     "doctest with attribute suffix",
+			input:    "src/parser.rs - parser::Parser::new (line 42) - compile fail",
+			expected: &DocTestLocation{File: "src/parser.rs", ItemPath: "parser::Parser::new", Line: 42},
+		},
+		{
+			name:     "regular unit test",
+			input:    "tests::test_add",
+			expected: nil,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			result := parseDocTestName(tt.input)
+			if tt.expected == nil {
+				if result != nil {
+					t.Errorf("parseDocTestName(%q) = %+v, expected nil", tt.input, result)
+				}
+				return
+			}
+			if result == nil || *result != *tt.expected {
+				t.Errorf("parseDocTestName(%q) = %+v, expected %+v", tt.input, result, tt.expected)
+			}
+		})
+	}
+}
+
+func TestCargoTestDefinition_DocTestsGroup(t *testing.T) {
+	def := NewCargoTestDefinition(createTestLogger(t))
+
+	output := `     Running unittests src/lib.rs (target/debug/deps/rust_comprehensive-0123456789abcdef)
+{"type":"suite","event":"started","test_count":1}
+{"type":"test","event":"started","name":"tests::test_add"}
+{"type":"test","name":"tests::test_add","event":"ok","exec_time":0.001}
+{"type":"suite","event":"ok","passed":1,"failed":0,"ignored":0,"exec_time":0.001}
+   Doc-tests rust_comprehensive
+{"type":"suite","event":"started","test_count":2}
+{"type":"test","event":"started","name":"src/lib.rs - calculator::add (line 7)"}
+{"type":"test","name":"src/lib.rs - calculator::add (line 7)","event":"ok","exec_time":0.2}
+{"type":"test","event":"started","name":"src/lib.rs - calculator::subtract (line 24)"}
+{"type":"test","name":"src/lib.rs - calculator::subtract (line 24)","event":"failed","stdout":"assertion failed: subtract(0, 5) == 5"}
+{"type":"suite","event":"failed","passed":1,"failed":1,"ignored":0,"exec_time":0.4}
+`
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	capture := NewTestIPCCapture(ipcPath)
+
+	// No group may be named after the rustdoc test name or the old "Doc-tests" root
+	for _, e := range capture.GetEventsByType("testGroupDiscovered") {
+		payload := e["payload"].(map[string]interface{})
+		groupName := payload["groupName"].(string)
+		if strings.Contains(groupName, "Doc-tests") || strings.Contains(groupName, "(line") {
+			t.Errorf("Unexpected group %q", groupName)
+		}
+	}
+
+	var subtract map[string]interface{}
+	for _, e := range capture.GetEventsByType("testCase") {
+		payload := e["payload"].(map[string]interface{})
+		if payload["testName"] == "src/lib.rs - calculator::subtract (line 24)" {
+			subtract = payload
+		}
+	}
+	if subtract == nil {
+		t.Fatal("Expected testCase event for the subtract doctest")
+	}
+
+	parents := convertToStringSlice(subtract["parentNames"])
+	if strings.Join(parents, "/") != "rust-comprehensive/doctests" {
+		t.Errorf("Expected parents [rust-comprehensive doctests], got %v", parents)
+	}
+	if subtract["file"] != "src/lib.rs" {
+		t.Errorf("Expected file src/lib.rs, got %v", subtract["file"])
+	}
+	if subtract["itemPath"] != "calculator::subtract" {
+		t.Errorf("Expected itemPath calculator::subtract, got %v", subtract["itemPath"])
+	}
+	if subtract["line"] != float64(24) {
+		t.Errorf("Expected line 24, got %v", subtract["line"])
+	}
+
+	errPayload, ok := subtract["error"].(map[string]interface{})
+	if !ok {
+		t.Fatal("Expected error on failed doctest")
+	}
+	if errPayload["location"] != "src/lib.rs:24" {
+		t.Errorf("Expected error location src/lib.rs:24, got %v", errPayload["location"])
+	}
+	if !strings.Contains(errPayload["message"].(string), "assertion failed") {
+		t.Errorf("Expected error message from doctest stdout, got %v", errPayload["message"])
+	}
+
+	// The doctests group result should be reported under the crate
+	foundDocGroup := false
+	for _, e := range capture.GetEventsByType("testGroupResult") {
+		payload := e["payload"].(map[string]interface{})
+		if payload["groupName"] == "doctests" {
+			foundDocGroup = true
+			if payload["status"] != "FAIL" {
+				t.Errorf("Expected doctests group to FAIL, got %v", payload["status"])
+			}
+		}
+	}
+	if !foundDocGroup {
+		t.Error("Expected a testGroupResult for the doctests group")
+	}
+}
+
+func TestCargoTestDefinition_BuildErrors(t *testing.T) {
+	def := NewCargoTestDefinition(createTestLogger(t))
+
+	// Syntax error, missing-feature error (E0554 on stable), plus records that must be ignored
+	output := `   Compiling rust-benchmarks v0.1.0 (/tmp/rust-benchmarks)
+{"reason":"compiler-message","package_id":"rust-benchmarks 0.1.0","target":{"kind":["lib"],"name":"rust_benchmarks"},"message":{"message":"expected one of ` + "`;`" + ` or ` + "`}`" + `, found ` + "`let`" + `","code":null,"level":"error","spans":[{"file_name":"src/lib.rs","line_start":12,"column_start":5,"is_primary":true}],"rendered":"error: expected one of ` + "`;`" + ` or ` + "`}`" + `, found ` + "`let`" + `\n  --> src/lib.rs:12:5\n"}}
+{"reason":"compiler-message","package_id":"rust-benchmarks 0.1.0","target":{"kind":["lib"],"name":"rust_benchmarks"},"message":{"message":"` + "`#![feature]`" + ` may not be used on the stable release channel","code":{"code":"E0554","explanation":null},"level":"error","spans":[{"file_name":"src/lib.rs","line_start":1,"column_start":1,"is_primary":true}],"rendered":"error[E0554]: ` + "`#![feature]`" + ` may not be used on the stable release channel\n --> src/lib.rs:1:1\n"}}
+{"reason":"compiler-message","package_id":"rust-benchmarks 0.1.0","target":{"kind":["lib"],"name":"rust_benchmarks"},"message":{"message":"unused variable: ` + "`x`" + `","code":{"code":"unused_variables","explanation":null},"level":"warning","spans":[{"file_name":"src/lib.rs","line_start":20,"column_start":9,"is_primary":true}],"rendered":"warning: unused variable\n"}}
+{"reason":"compiler-message","package_id":"rust-benchmarks 0.1.0","target":{"kind":["lib"],"name":"rust_benchmarks"},"message":{"message":"aborting due to 2 previous errors","code":null,"level":"error","spans":[],"rendered":"error: aborting due to 2 previous errors\n"}}
+{"reason":"build-finished","success":false}
+error: could not compile ` + "`rust-benchmarks`" + ` (lib test) due to 2 previous errors
+`
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	capture := NewTestIPCCapture(ipcPath)
+
+	buildErrors := capture.GetEventsByType("buildError")
+	if len(buildErrors) != 2 {
+		t.Fatalf("Expected 2 buildError events, got %d: %v", len(buildErrors), buildErrors)
+	}
+
+	syntax := buildErrors[0]["payload"].(map[string]interface{})
+	if syntax["file"] != "src/lib.rs" || syntax["line"] != float64(12) || syntax["column"] != float64(5) {
+		t.Errorf("Expected syntax error at src/lib.rs:12:5, got %v", syntax)
+	}
+	if _, hasCode := syntax["code"]; hasCode {
+		t.Errorf("Syntax error should have no error code, got %v", syntax["code"])
+	}
+	if !strings.HasPrefix(syntax["rendered"].(string), "error: expected one of") ||
+		strings.HasSuffix(syntax["rendered"].(string), "\n") {
+		t.Errorf("Unexpected rendered message %q", syntax["rendered"])
+	}
+
+	feature := buildErrors[1]["payload"].(map[string]interface{})
+	if feature["code"] != "E0554" {
+		t.Errorf("Expected error code E0554, got %v", feature["code"])
+	}
+	if feature["target"] != "rust_benchmarks" {
+		t.Errorf("Expected target rust_benchmarks, got %v", feature["target"])
+	}
+	if feature["line"] != float64(1) {
+		t.Errorf("Expected E0554 on line 1, got %v", feature["line"])
+	}
+
+	// Cargo records must not be mistaken for test events
+	if groups := capture.GetEventsByType("testGroupDiscovered"); len(groups) != 0 {
+		t.Errorf("Expected no groups for a failed build, got %v", groups)
+	}
+}
+
+func TestSplitCapturedOutput(t *testing.T) {
+	tests := []struct {
+		name        string
+		captured    string
+		wantPrinted string
+		wantPanic   string
+	}{
+		{
+			name:        "println before panic",
+			captured:    "computing 2 + 2\n\nexpecting 5\nthread 'tests::test_assertion_failure' panicked at src/lib.rs:40:9:\nassertion `left == right` failed: Math is broken!\n  left: 4\n right: 5\nnote: run with `RUST_BACKTRACE=1` environment variable to display a backtrace\n",
+			wantPrinted: "computing 2 + 2\n\nexpecting 5",
+			wantPanic:   "thread 'tests::test_assertion_failure' panicked at src/lib.rs:40:9:\nassertion `left == right` failed: Math is broken!\n  left: 4\n right: 5\nnote: run with `RUST_BACKTRACE=1` environment variable to display a backtrace",
+		},
+		{
+			name:        "panic only, old message format",
+			captured:    "thread 'tests::test_unexpected_panic' panicked at 'Unexpected panic occurred!', src/lib.rs:51:9\n",
+			wantPrinted: "",
+			wantPanic:   "thread 'tests::test_unexpected_panic' panicked at 'Unexpected panic occurred!', src/lib.rs:51:9",
+		},
+		{
+			name:        "no panic",
+			captured:    "\nsome output\n\n",
+			wantPrinted: "some output",
+			wantPanic:   "",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			printed, panicMessage := splitCapturedOutput(tt.captured)
+			if printed != tt.wantPrinted {
+				t.Errorf("printed = %q, want %q", printed, tt.wantPrinted)
+			}
+			if panicMessage != tt.wantPanic {
+				t.Errorf("panic = %q, want %q", panicMessage, tt.wantPanic)
+			}
+		})
+	}
+}
+
+func TestParseAssertionFailure(t *testing.T) {
+	tests := []struct {
+		name     string
+		message  string
+		expected *AssertionFailure
+	}{
+		{
+			name:     "assert_eq with message",
+			message:  "thread 'tests::test_assertion_failure' panicked at src/lib.rs:40:9:\nassertion `left == right` failed: Math is broken!\n  left: 4\n right: 5\nnote: run with `RUST_BACKTRACE=1` environment variable to display a backtrace",
+			expected: &AssertionFailure{Kind: "assert_eq", Left: "4", Right: "5", CustomMessage: "Math is broken!"},
+		},
+		{
+			name:     "assert_ne without message",
+			message:  "thread 'tests::test_ne' panicked at src/lib.rs:12:5:\nassertion `left != right` failed\n  left: \"a\"\n right: \"a\"",
+			expected: &AssertionFailure{Kind: "assert_ne", Left: `"a"`, Right: `"a"`},
+		},
+		{
+			name:     "multi-line values",
+			message:  "thread 'tests::test_point' panicked at src/lib.rs:20:9:\nassertion `left == right` failed\n  left: Point {\n    x: 1,\n}\n right: Point {\n    x: 2,\n}",
+			expected: &AssertionFailure{Kind: "assert_eq", Left: "Point {\n    x: 1,\n}", Right: "Point {\n    x: 2,\n}"},
+		},
+		{
+			name:     "multi-line custom message",
+			message:  "thread 'tests::test_sum' panicked at src/lib.rs:30:9:\nassertion `left == right` failed: sum mismatch\nfor input [1, 2]\n  left: 3\n right: 4",
+			expected: &AssertionFailure{Kind: "assert_eq", Left: "3", Right: "4", CustomMessage: "sum mismatch\nfor input [1, 2]"},
+		},
+		{
+			name:     "older toolchain format",
+			message:  "thread 'tests::test_assertion_failure' panicked at 'assertion failed: `(left == right)`\n  left: `4`,\n right: `5`: Math is broken!', src/lib.rs:40:9",
+			expected: &AssertionFailure{Kind: "assert_eq", Left: "4", Right: "5", CustomMessage: "Math is broken!"},
+		},
+		{
+			name:     "older toolchain multi-line values",
+			message:  "thread 'tests::test_point' panicked at 'assertion failed: `(left == right)`\n  left: `Point {\n    x: 1,\n}`,\n right: `Point {\n    x: 2,\n}`', src/lib.rs:20:9",
+			expected: &AssertionFailure{Kind: "assert_eq", Left: "Point {\n    x: 1,\n}", Right: "Point {\n    x: 2,\n}"},
+		},
+		{
+			name:     "plain panic",
+			message:  "thread 'tests::test_unexpected_panic' panicked at src/lib.rs:51:9:\nUnexpected panic occurred!",
+			expected: nil,
+		},
+		{
+			name:     "assert! without values",
+			message:  "thread 'tests::test_flag' panicked at src/lib.rs:60:9:\nassertion failed: flag",
+			expected: nil,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			result := parseAssertionFailure(tt.message)
+			if tt.expected == nil {
+				if result != nil {
+					t.Errorf("Expected no assertion, got %+v", result)
+				}
+				return
+			}
+			if result == nil || *result != *tt.expected {
+				t.Errorf("parseAssertionFailure() = %+v, expected %+v", result, tt.expected)
+			}
+		})
+	}
+}
+
+// unwrapNoneBacktrace is the RUST_BACKTRACE=1 failure block of rust-edge-cases' test_unwrap_none
+const unwrapNoneBacktrace = `thread 'tests::test_unwrap_none' panicked at src/lib.rs:119:11:
+called ` + "`Option::unwrap()`" + ` on a ` + "`None`" + ` value
+stack backtrace:
+   0: rust_begin_unwind
+             at /rustc/3f5fd8dd41153bc5fdca9427e9e05be2c767ba23/library/std/src/panicking.rs:652:5
+   1: core::panicking::panic_fmt
+             at /rustc/3f5fd8dd41153bc5fdca9427e9e05be2c767ba23/library/core/src/panicking.rs:72:14
+   2: core::panicking::panic
+             at /rustc/3f5fd8dd41153bc5fdca9427e9e05be2c767ba23/library/core/src/panicking.rs:146:5
+   3: core::option::unwrap_failed
+             at /rustc/3f5fd8dd41153bc5fdca9427e9e05be2c767ba23/library/core/src/option.rs:1985:5
+   4: core::option::Option<T>::unwrap
+             at /rustc/3f5fd8dd41153bc5fdca9427e9e05be2c767ba23/library/core/src/option.rs:932:21
+   5: rust_edge_cases::tests::test_unwrap_none
+             at ./src/lib.rs:119:11
+   6: rust_edge_cases::tests::test_unwrap_none::{{closure}}
+             at ./src/lib.rs:117:26
+   7: core::ops::function::FnOnce::call_once
+             at /rustc/3f5fd8dd41153bc5fdca9427e9e05be2c767ba23/library/core/src/ops/function.rs:250:5
+   8: <F as core::ops::function::FnOnce<()>>::call_once
+             at /rustc/3f5fd8dd41153bc5fdca9427e9e05be2c767ba23/library/core/src/ops/function.rs:250:5
+note: Some details are omitted, run with ` + "`RUST_BACKTRACE=full`" + ` for a verbose backtrace.
+`
+
+func TestSplitBacktrace(t *testing.T) {
+	message, backtrace := splitBacktrace(strings.TrimSpace(unwrapNoneBacktrace))
+	if message != "thread 'tests::test_unwrap_none' panicked at src/lib.rs:119:11:\ncalled `Option::unwrap()` on a `None` value" {
+		t.Errorf("Expected the panic message without the backtrace, got %q", message)
+	}
+	if !strings.Contains(backtrace, "rust_edge_cases::tests::test_unwrap_none") || !strings.Contains(backtrace, "./src/lib.rs:119:11") {
+		t.Errorf("Expected the backtrace to reach the test in src/lib.rs, got %q", backtrace)
+	}
+	if !strings.HasSuffix(backtrace, "at ./src/lib.rs:117:26") {
+		t.Errorf("Expected libtest frames and the closing note to be trimmed, got %q", backtrace)
+	}
+
+	// Panics without RUST_BACKTRACE are returned unchanged
+	plain := "thread 'tests::test_unexpected_panic' panicked at src/lib.rs:51:9:\nUnexpected panic occurred!"
+	if message, backtrace := splitBacktrace(plain); message != plain || backtrace != "" {
+		t.Errorf("Expected no backtrace, got message %q, backtrace %q", message, backtrace)
+	}
+}
+
+func TestIsBacktraceRuntimeFrame(t *testing.T) {
+	tests := []struct {
+		symbol  string
+		runtime bool
+	}{
+		{"core::ops::function::FnOnce::call_once", true},
+		{"<F as core::ops::function::FnOnce<()>>::call_once", true},
+		{"<alloc::boxed::Box<F,A> as core::ops::function::FnOnce<Args>>::call_once::h0123456789abcdef", true},
+		{"std::sys::pal::unix::thread::Thread::new::thread_start", true},
+		{"test::__rust_begin_short_backtrace", true},
+		{"test::run_test::{{closure}}::{{closure}}", true},
+		{"__rust_begin_short_backtrace", true},
+		{"clone3", true},
+		{"__libc_start_main", true},
+		// User code that only looks like the runtime
+		{"test::helpers::check", false},
+		{"my_crate::test::roundtrip", false},
+		{"clone_tree", false},
+		{"__setup_fixture", false},
+		{"my_crate::tests::test_unwrap_none::{{closure}}", false},
+	}
+	for _, tt := range tests {
+		if got := isBacktraceRuntimeFrame(tt.symbol); got != tt.runtime {
+			t.Errorf("isBacktraceRuntimeFrame(%q) = %v, expected %v", tt.symbol, got, tt.runtime)
+		}
+	}
+}
+
+func TestCargoTestDefinition_Backtrace(t *testing.T) {
+	def := NewCargoTestDefinition(createTestLogger(t))
+
+	output := `     Running unittests src/lib.rs (target/debug/deps/rust_edge_cases-0123456789abcdef)
+{"type":"suite","event":"started","test_count":1}
+{"type":"test","event":"started","name":"tests::test_unwrap_none"}
+{"type":"test","name":"tests::test_unwrap_none","event":"failed","exec_time":0.001}
+
+failures:
+
+---- tests::test_unwrap_none stdout ----
+` + unwrapNoneBacktrace + `
+
+failures:
+    tests::test_unwrap_none
+
+{"type":"suite","event":"failed","passed":0,"failed":1,"ignored":0,"exec_time":0.01}
+`
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+
+	testCases := NewTestIPCCapture(ipcPath).GetEventsByType("testCase")
+	if len(testCases) == 0 {
+		t.Fatalf("Expected testCase events")
+	}
+	payload := testCases[len(testCases)-1]["payload"].(map[string]interface{})
+	errPayload, ok := payload["error"].(map[string]interface{})
+	if !ok {
+		t.Fatalf("Expected an error payload, got %v", payload)
+	}
+	backtrace, _ := errPayload["backtrace"].(string)
+	if !strings.Contains(backtrace, "src/lib.rs") {
+		t.Errorf("Expected the backtrace to contain the fixture's src/lib.rs, got %q", backtrace)
+	}
+	if message := errPayload["message"].(string); strings.Contains(message, "stack backtrace:") {
+		t.Errorf("Expected the backtrace to be kept out of the error message, got %q", message)
+	}
+}
+
+func TestCargoTestDefinition_TestTargets(t *testing.T) {
+	def := NewCargoTestDefinition(createTestLogger(t))
+
+	// Every target of the crate runs a test with the same name
+	suite := `{"type":"suite","event":"started","test_count":1}
+{"type":"test","event":"started","name":"tests::test_add"}
+{"type":"test","name":"tests::test_add","event":"ok","exec_time":0.001}
+{"type":"suite","event":"ok","passed":1,"failed":0,"ignored":0,"exec_time":0.01}
+`
+	output := `{"reason":"compiler-artifact","target":{"name":"cli","kind":["bin"]},"executable":"/work/target/debug/deps/cli-0123456789abcdef"}
+     Running unittests src/lib.rs (target/debug/deps/rust_comprehensive-0123456789abcdef)
+` + suite + `     Running unittests src/main.rs (target/debug/deps/rust_comprehensive-fedcba9876543210)
+` + suite + `     Running unittests src/bin/my_binary.rs (target/debug/deps/my_binary-0123456789abcdef)
+` + suite + `     Running unittests src/cli.rs (target/debug/deps/cli-0123456789abcdef)
+` + suite + `     Running tests/integration_test.rs (target/debug/deps/integration_test-0123456789abcdef)
+` + suite + `     Running unittests examples/basic_example.rs (target/debug/examples/basic_example-0123456789abcdef)
+` + suite
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	capture := NewTestIPCCapture(ipcPath)
+
+	targets := make(map[string]string)
+	for _, e := range capture.GetEventsByType("testGroupDiscovered") {
+		payload := e["payload"].(map[string]interface{})
+		if payload["parentNames"] != nil {
+			continue
+		}
+		kind, _ := payload["targetKind"].(string)
+		name, _ := payload["targetName"].(string)
+		targets[payload["groupName"].(string)] = kind + ":" + name
+	}
+
+	expected := map[string]string{
+		"rust-comprehensive":       "lib:rust_comprehensive",
+		"rust-comprehensive (bin)": "bin:rust_comprehensive", // src/main.rs shares the lib's name
+		"my-binary":                "bin:my_binary",
+		"cli":                      "bin:cli", // Known from the compiler-artifact record
+		"integration-test":         "test:integration_test",
+		"basic-example":            "example:basic_example",
+	}
+	if len(targets) != len(expected) {
+		t.Errorf("Expected %d target groups, got %v", len(expected), targets)
+	}
+	for group, target := range expected {
+		if targets[group] != target {
+			t.Errorf("Expected group %s for target %s, got %q", group, target, targets[group])
+		}
+	}
+
+	// The same test name is reported once per target
+	testGroups := make(map[string]bool)
+	for _, e := range capture.GetEventsByType("testCase") {
+		parents := convertToStringSlice(e["payload"].(map[string]interface{})["parentNames"])
+		testGroups[parents[0]] = true
+	}
+	if len(testGroups) != len(expected) {
+		t.Errorf("Expected test_add in %d separate groups, got %v", len(expected), testGroups)
+	}
+}
+
+func TestCargoTestDefinition_InvalidUTF8(t *testing.T) {
+	def := NewCargoTestDefinition(createTestLogger(t))
+
+	// A test name in Latin-1 and a test that printed raw binary data
+	output := "     Running unittests src/lib.rs (target/debug/deps/rust_edge_cases-0123456789abcdef)\n" +
+		`{"type":"suite","event":"started","test_count":2}` + "\n" +
+		"{\"type\":\"test\",\"event\":\"started\",\"name\":\"tests::caf\xe9\"}\n" +
+		"{\"type\":\"test\",\"name\":\"tests::caf\xe9\",\"event\":\"failed\",\"exec_time\":0.001}\n" +
+		"\xff\xfe\x00\x01 raw bytes from a test\n" +
+		`{"type":"test","event":"started","name":"tests::test_ok"}` + "\n" +
+		`{"type":"test","name":"tests::test_ok","event":"ok","exec_time":0.001}` + "\n" +
+		"\nfailures:\n\n" +
+		"---- tests::caf\xe9 stdout ----\n" +
+		"read \xff\xfe header\n" +
+		"thread 'tests::caf\xe9' panicked at src/lib.rs:3:5:\n" +
+		"bad magic \xff\xfe\n\n" +
+		"failures:\n    tests::caf\xe9\n\n" +
+		`{"type":"suite","event":"failed","passed":1,"failed":1,"ignored":0,"exec_time":0.01}` + "\n"
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	data, err := os.ReadFile(ipcPath)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if !utf8.Valid(data) {
+		t.Errorf("Expected the IPC events to be valid UTF-8, got:\n%q", data)
+	}
+
+	// Each invalid byte reads as U+FFFD, and the failure block still finds its test
+	capture := NewTestIPCCapture(ipcPath)
+	var failed map[string]interface{}
+	for _, e := range capture.GetEventsByType("testCase") {
+		payload := e["payload"].(map[string]interface{})
+		if payload["testName"] == "caf\uFFFD" {
+			failed = payload
+		}
+	}
+	if failed == nil {
+		t.Fatalf("Expected a testCase event for caf\uFFFD, got %v", capture.GetEventsByType("testCase"))
+	}
+	if failed["status"] != "FAIL" || failed["stdout"] != "read \uFFFD\uFFFD header" {
+		t.Errorf("Expected the failure with its output, got status %v and stdout %q", failed["status"], failed["stdout"])
+	}
+	if errPayload, ok := failed["error"].(map[string]interface{}); !ok || !strings.HasSuffix(errPayload["message"].(string), "bad magic \uFFFD\uFFFD") {
+		t.Errorf("Expected the panic message, got %v", failed["error"])
+	}
+}
+
+func TestCargoTestDefinition_CapturedFailureBlocks(t *testing.T) {
+	def := NewCargoTestDefinition(createTestLogger(t))
+
+	// Failures reported as JSON first, followed by libtest's failure blocks back to back
+	output := `     Running unittests src/lib.rs (target/debug/deps/rust_edge_cases-0123456789abcdef)
+{"type":"suite","event":"started","test_count":4}
+{"type":"test","event":"started","name":"tests::test_normal_pass"}
+{"type":"test","name":"tests::test_normal_pass","event":"ok","exec_time":0.001}
+{"type":"test","event":"started","name":"tests::test_assertion_failure"}
+{"type":"test","name":"tests::test_assertion_failure","event":"failed","exec_time":0.001}
+{"type":"test","event":"started","name":"tests::test_unexpected_panic"}
+{"type":"test","name":"tests::test_unexpected_panic","event":"failed","exec_time":0.001}
+{"type":"test","event":"started","name":"tests::nested_module_tests::deeply_nested::very_nested_panic"}
+{"type":"test","name":"tests::nested_module_tests::deeply_nested::very_nested_panic","event":"failed","exec_time":0.001}
+
+failures:
+
+---- tests::test_assertion_failure stdout ----
+computing 2 + 2
+
+expecting 5
+thread 'tests::test_assertion_failure' panicked at src/lib.rs:40:9:
+assertion ` + "`left == right`" + ` failed: Math is broken!
+  left: 4
+ right: 5
+note: run with ` + "`RUST_BACKTRACE=1`" + ` environment variable to display a backtrace
+
+---- tests::test_unexpected_panic stdout ----
+thread 'tests::test_unexpected_panic' panicked at src/lib.rs:51:9:
+Unexpected panic occurred!
+---- tests::nested_module_tests::deeply_nested::very_nested_panic stdout ----
+
+thread 'tests::nested_module_tests::deeply_nested::very_nested_panic' panicked at src/lib.rs:143:13:
+Deep panic!
+
+
+failures:
+    tests::test_assertion_failure
+    tests::test_unexpected_panic
+    tests::nested_module_tests::deeply_nested::very_nested_panic
+
+{"type":"suite","event":"failed","passed":1,"failed":3,"ignored":0,"exec_time":0.01}
+`
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	capture := NewTestIPCCapture(ipcPath)
+
+	// The last testCase event for each test carries the attached block
+	latest := make(map[string]map[string]interface{})
+	for _, e := range capture.GetEventsByType("testCase") {
+		payload := e["payload"].(map[string]interface{})
+		latest[payload["testName"].(string)] = payload
+	}
+
+	errorMessage := func(payload map[string]interface{}) string {
+		errPayload, ok := payload["error"].(map[string]interface{})
+		if !ok {
+			return ""
+		}
+		return errPayload["message"].(string)
+	}
+
+	assertion := latest["test_assertion_failure"]
+	if assertion == nil {
+		t.Fatal("Expected testCase event for test_assertion_failure")
+	}
+	if assertion["stdout"] != "computing 2 + 2\n\nexpecting 5" {
+		t.Errorf("Expected println output with its blank line, got %q", assertion["stdout"])
+	}
+	if msg := errorMessage(assertion); !strings.HasPrefix(msg, "thread 'tests::test_assertion_failure' panicked") ||
+		!strings.Contains(msg, "Math is broken!") || strings.Contains(msg, "computing") {
+		t.Errorf("Expected panic message without println output, got %q", msg)
+	}
+	if errPayload := assertion["error"].(map[string]interface{}); errPayload["expected"] != "5" || errPayload["actual"] != "4" ||
+		errPayload["customMessage"] != "Math is broken!" || errPayload["errorType"] != "assert_eq" {
+		t.Errorf("Expected structured assert_eq fields, got %v", errPayload)
+	}
+
+	unexpected := latest["test_unexpected_panic"]
+	if unexpected == nil {
+		t.Fatal("Expected testCase event for test_unexpected_panic")
+	}
+	if _, hasStdout := unexpected["stdout"]; hasStdout {
+		t.Errorf("Expected no println output, got %q", unexpected["stdout"])
+	}
+	if msg := errorMessage(unexpected); msg != "thread 'tests::test_unexpected_panic' panicked at src/lib.rs:51:9:\nUnexpected panic occurred!" {
+		t.Errorf("Unexpected panic message %q", msg)
+	}
+
+	nested := latest["very_nested_panic"]
+	if nested == nil {
+		t.Fatal("Expected testCase event for very_nested_panic")
+	}
+	if msg := errorMessage(nested); !strings.HasSuffix(msg, "Deep panic!") {
+		t.Errorf("Expected deep panic message, got %q", msg)
+	}
+	parents := convertToStringSlice(nested["parentNames"])
+	if strings.Join(parents, "/") != "rust-edge-cases/tests/nested_module_tests/deeply_nested" {
+		t.Errorf("Expected nested parents, got %v", parents)
+	}
+
+	// Passing tests are never re-sent
+	passCount := 0
+	for _, e := range capture.GetEventsByType("testCase") {
+		if e["payload"].(map[string]interface{})["testName"] == "test_normal_pass" {
+			passCount++
+		}
+	}
+	if passCount != 1 {
+		t.Errorf("Expected one event for test_normal_pass, got %d", passCount)
+	}
+}
+
+func TestParseShouldPanicMismatch(t *testing.T) {
+	note := "panic did not contain expected string\n" +
+		"      panic message: `\"This function always panics!\"`,\n" +
+		" expected substring: `\"This message does not match\"`"
+
+	mismatch := parseShouldPanicMismatch(note)
+	if mismatch == nil {
+		t.Fatal("Expected mismatch to be parsed")
+	}
+	if mismatch.Expected != "This message does not match" {
+		t.Errorf("Expected = %q", mismatch.Expected)
+	}
+	if mismatch.Actual != "This function always panics!" {
+		t.Errorf("Actual = %q", mismatch.Actual)
+	}
+
+	if parseShouldPanicMismatch("test did not panic as expected") != nil {
+		t.Error("A missing panic is not an expectation mismatch")
+	}
+}
+
+func TestFindShouldPanicFns(t *testing.T) {
+	source := `pub fn will_panic() {
+    panic!("This function always panics!");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "This function always panics!")]
+    fn test_expected_panic() {
+        will_panic();
+    }
+
+    #[test]
+    fn test_normal_pass() {
+        assert_eq!(2 + 2, 4);
+    }
+
+    mod nested {
+        #[test]
+        #[should_panic]
+        fn test_nested_panic() {
+            panic!("{}", 1);
+        }
+    }
+
+    // #[should_panic]
+    #[test]
+    fn test_after_nested() {}
+}
+`
+	found := findShouldPanicFns(source)
+	var names []string
+	for _, name := range found {
+		names = append(names, strings.Join(name, "::"))
+	}
+	want := "tests::test_expected_panic,tests::nested::test_nested_panic"
+	if strings.Join(names, ",") != want {
+		t.Errorf("findShouldPanicFns() = %v, want %s", names, want)
+	}
+}
+
+func TestRustModulePath(t *testing.T) {
+	tests := []struct {
+		path   string
+		target CargoTarget
+		want   string
+		ok     bool
+	}{
+		{"src/lib.rs", CargoTarget{Kind: "lib"}, "", true},
+		{"src/main.rs", CargoTarget{Kind: "bin"}, "", true},
+		{"src/net/tcp.rs", CargoTarget{Kind: "lib"}, "net::tcp", true},
+		{"src/net/mod.rs", CargoTarget{Kind: "lib"}, "net", true},
+		{"src/tests/util.rs", CargoTarget{Kind: "lib"}, "tests::util", true},
+		{"src/tests/mod.rs", CargoTarget{Kind: "lib"}, "tests", true},
+		{"src/bin/tool.rs", CargoTarget{Kind: "bin", Name: "tool"}, "", true},
+		{"src/bin/tool/cli.rs", CargoTarget{Kind: "bin", Name: "tool"}, "cli", true},
+		{"tests/api.rs", CargoTarget{Kind: "test", Name: "api"}, "", true},
+		{"tests/api/main.rs", CargoTarget{Kind: "test", Name: "api"}, "", true},
+		{"tests/api/util.rs", CargoTarget{Kind: "test", Name: "api"}, "util", true},
+		{"build.rs", CargoTarget{}, "", false},
+		{"vendor/dep/src/lib.rs", CargoTarget{}, "", false},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.path, func(t *testing.T) {
+			target, got, ok := rustModulePath(strings.Split(tt.path, "/"))
+			if ok != tt.ok || target != tt.target || strings.Join(got, "::") != tt.want {
+				t.Errorf("rustModulePath(%s) = %+v %v, %v; want %+v %q, %v", tt.path, target, got, ok, tt.target, tt.want, tt.ok)
+			}
+		})
+	}
+}
+
+func TestScanShouldPanicTests(t *testing.T) {
+	// A workspace of three packages, the command testing two of them
+	root := t.TempDir()
+	shouldPanic := "#[cfg(test)]\nmod tests {\n    #[test]\n    #[should_panic]\n    fn boom() {\n        panic!();\n    }\n}\n"
+	files := map[string]string{
+		"alpha/src/lib.rs":               shouldPanic,
+		"alpha/src/tests/extra.rs":       "#[test]\n#[should_panic]\nfn overflow() {}\n",
+		"alpha/tests/api.rs":             shouldPanic,
+		"beta/src/lib.rs":                "#[cfg(test)]\nmod tests {\n    #[test]\n    fn boom() {}\n}\n// no should_panic here\n",
+		"beta/src/main.rs":               shouldPanic,
+		"beta/target/debug/build/out.rs": shouldPanic,
+		"beta/vendor/dep/src/lib.rs":     shouldPanic,
+		"gamma/src/lib.rs":               shouldPanic,
+	}
+	for name, content := range files {
+		path := filepath.Join(root, filepath.FromSlash(name))
+		if err := os.MkdirAll(filepath.Dir(path), 0755); err != nil {
+			t.Fatal(err)
+		}
+		if err := os.WriteFile(path, []byte(content), 0644); err != nil {
+			t.Fatal(err)
+		}
+	}
+
+	pkg := func(name string, targets ...CargoMetadataTarget) CargoMetadataPackage {
+		return CargoMetadataPackage{ID: name, Name: name, ManifestPath: filepath.Join(root, name, "Cargo.toml"), Targets: targets}
+	}
+	metadata := &CargoMetadata{
+		Packages: []CargoMetadataPackage{
+			pkg("alpha", CargoMetadataTarget{Name: "alpha", Kind: []string{"lib"}}, CargoMetadataTarget{Name: "api", Kind: []string{"test"}}),
+			pkg("beta", CargoMetadataTarget{Name: "beta", Kind: []string{"lib"}},
+				CargoMetadataTarget{Name: "beta", Kind: []string{"bin"}, SrcPath: filepath.Join(root, "beta", "src", "main.rs")}),
+			pkg("gamma", CargoMetadataTarget{Name: "gamma", Kind: []string{"lib"}}),
+		},
+		WorkspaceMembers: []string{"alpha", "beta", "gamma"},
+	}
+
+	def := NewCargoTestDefinition(createTestLogger(t))
+	def.cargoArgs = []string{"cargo", "test", "-p", "alpha", "-p", "beta"}
+	tests := def.scanShouldPanicTests(metadata)
+
+	// beta's lib has a tests::boom too, but it isn't #[should_panic], and neither
+	// target/, vendored code nor the unselected gamma is read
+	want := map[string]bool{
+		"lib alpha::tests::boom":            true,
+		"lib alpha::tests::extra::overflow": true,
+		"test api::tests::boom":             true,
+		"bin beta::tests::boom":             true,
+	}
+	if !reflect.DeepEqual(tests, want) {
+		t.Errorf("scanShouldPanicTests() = %v, want %v", tests, want)
+	}
+
+	def.targets["alpha"] = &CargoTarget{Kind: "lib", Name: "alpha"}
+	def.targets["beta"] = &CargoTarget{Kind: "lib", Name: "beta"}
+	def.shouldPanicTests = tests
+	if !def.isShouldPanicTest("alpha", "tests::boom") || def.isShouldPanicTest("beta", "tests::boom") {
+		t.Error("Expected only alpha's tests::boom to be #[should_panic]")
+	}
+}
+
+func TestCargoTestDefinition_ShouldPanic(t *testing.T) {
+	def := NewCargoTestDefinition(createTestLogger(t))
+
+	// Run from a crate whose sources mark test_expected_panic as #[should_panic]
+	crateDir := t.TempDir()
+	if err := os.MkdirAll(filepath.Join(crateDir, "src"), 0755); err != nil {
+		t.Fatal(err)
+	}
+	source := "#[cfg(test)]\nmod tests {\n    #[test]\n    #[should_panic(expected = \"boom\")]\n    fn test_expected_panic() {\n        panic!(\"boom\");\n    }\n}\n"
+	if err := os.WriteFile(filepath.Join(crateDir, "src", "lib.rs"), []byte(source), 0644); err != nil {
+		t.Fatal(err)
+	}
+	originalDir, err := os.Getwd()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if err := os.Chdir(crateDir); err != nil {
+		t.Fatal(err)
+	}
+	defer func() { _ = os.Chdir(originalDir) }()
+
+	output := `     Running unittests src/lib.rs (target/debug/deps/rust_edge_cases-0123456789abcdef)
+{"type":"suite","event":"started","test_count":3}
+{"type":"test","event":"started","name":"tests::test_expected_panic"}
+{"type":"test","name":"tests::test_expected_panic","event":"ok","exec_time":0.001}
+{"type":"test","event":"started","name":"tests::test_normal_pass"}
+{"type":"test","name":"tests::test_normal_pass","event":"ok","exec_time":0.001}
+{"type":"test","event":"started","name":"tests::test_expected_panic_mismatch"}
+{"type":"test","name":"tests::test_expected_panic_mismatch","event":"failed","exec_time":0.001,"stdout":"thread 'tests::test_expected_panic_mismatch' panicked at src/lib.rs:7:5:\nThis function always panics!\n","message":"panic did not contain expected string\n      panic message: ` + "`" + `\"This function always panics!\"` + "`" + `,\n expected substring: ` + "`" + `\"This message does not match\"` + "`" + `"}
+{"type":"suite","event":"failed","passed":2,"failed":1,"ignored":0,"exec_time":0.01}
+`
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+
+	payloads := make(map[string]map[string]interface{})
+	for _, e := range NewTestIPCCapture(ipcPath).GetEventsByType("testCase") {
+		payload := e["payload"].(map[string]interface{})
+		payloads[payload["testName"].(string)] = payload
+	}
+
+	if payloads["test_expected_panic"]["panickedAsExpected"] != true {
+		t.Errorf("Expected test_expected_panic to be marked panickedAsExpected, got %v", payloads["test_expected_panic"])
+	}
+	if _, marked := payloads["test_normal_pass"]["panickedAsExpected"]; marked {
+		t.Error("A plain pass must not be marked panickedAsExpected")
+	}
+
+	errPayload, ok := payloads["test_expected_panic_mismatch"]["error"].(map[string]interface{})
+	if !ok {
+		t.Fatal("Expected error on should_panic mismatch")
+	}
+	if errPayload["expectedPanic"] != "This message does not match" {
+		t.Errorf("expectedPanic = %v", errPayload["expectedPanic"])
+	}
+	if errPayload["actualPanic"] != "This function always panics!" {
+		t.Errorf("actualPanic = %v", errPayload["actualPanic"])
+	}
+	message := errPayload["message"].(string)
+	if !strings.HasPrefix(message, "thread 'tests::test_expected_panic_mismatch' panicked") ||
+		!strings.Contains(message, "note: panic did not contain expected string") {
+		t.Errorf("Expected panic and libtest note in the error message, got %q", message)
+	}
+}
+
+func TestCargoTestDefinition_FailureKind(t *testing.T) {
+	def := NewCargoTestDefinition(createTestLogger(t))
+
+	// Run from a crate whose sources show what the custom-message assert! and the
+	// unwrap panic at
+	crateDir := t.TempDir()
+	if err := os.MkdirAll(filepath.Join(crateDir, "src"), 0755); err != nil {
+		t.Fatal(err)
+	}
+	source := "#[cfg(test)]\nmod tests {\n    #[test]\n    fn test_custom_assert() {\n        assert!(1 + 1 == 3, \"math is broken\");\n    }\n    #[test]\n    fn test_unwrap_none() {\n        let value: Option<i32> = None;\n        value.unwrap();\n    }\n}\n"
+	if err := os.WriteFile(filepath.Join(crateDir, "src", "lib.rs"), []byte(source), 0644); err != nil {
+		t.Fatal(err)
+	}
+	originalDir, err := os.Getwd()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if err := os.Chdir(crateDir); err != nil {
+		t.Fatal(err)
+	}
+	defer func() { _ = os.Chdir(originalDir) }()
+
+	output := `     Running unittests src/lib.rs (target/debug/deps/rust_edge_cases-0123456789abcdef)
+{"type":"suite","event":"started","test_count":6}
+{"type":"test","event":"started","name":"tests::test_assert_eq"}
+{"type":"test","name":"tests::test_assert_eq","event":"failed","exec_time":0.001,"stdout":"thread 'tests::test_assert_eq' panicked at src/lib.rs:5:9:\nassertion ` + "`" + `left == right` + "`" + ` failed\n  left: 2\n right: 3\n"}
+{"type":"test","event":"started","name":"tests::test_custom_assert"}
+{"type":"test","name":"tests::test_custom_assert","event":"failed","exec_time":0.001,"stdout":"thread 'tests::test_custom_assert' panicked at src/lib.rs:5:9:\nmath is broken\n"}
+{"type":"test","event":"started","name":"tests::test_not_panicking"}
+{"type":"test","name":"tests::test_not_panicking","event":"failed","exec_time":0.001,"stdout":"note: test did not panic as expected\n"}
+{"type":"test","event":"started","name":"tests::test_unwrap_none"}
+{"type":"test","name":"tests::test_unwrap_none","event":"failed","exec_time":0.001,"stdout":"thread 'tests::test_unwrap_none' panicked at src/lib.rs:10:15:\ncalled ` + "`" + `Option::unwrap()` + "`" + ` on a ` + "`" + `None` + "`" + ` value\n"}
+{"type":"test","event":"started","name":"tests::test_index_out_of_bounds"}
+{"type":"test","name":"tests::test_index_out_of_bounds","event":"failed","exec_time":0.001,"stdout":"thread 'tests::test_index_out_of_bounds' panicked at src/lib.rs:10:15:\nindex out of bounds: the len is 3 but the index is 10\n"}
+{"type":"test","event":"started","name":"tests::test_plain_panic"}
+{"type":"test","name":"tests::test_plain_panic","event":"failed","exec_time":0.001,"stdout":"thread 'tests::test_plain_panic' panicked at src/lib.rs:2:1:\nsomething went wrong\n"}
+{"type":"suite","event":"failed","passed":0,"failed":6,"ignored":0,"exec_time":0.01}
+`
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+
+	kinds := make(map[string]interface{})
+	for _, e := range NewTestIPCCapture(ipcPath).GetEventsByType("testCase") {
+		payload := e["payload"].(map[string]interface{})
+		if errPayload, ok := payload["error"].(map[string]interface{}); ok {
+			kinds[payload["testName"].(string)] = errPayload["failureKind"]
+		}
+	}
+
+	expected := map[string]string{
+		"test_assert_eq":           ipc.FailureKindAssertionFailed,
+		"test_custom_assert":       ipc.FailureKindAssertionFailed,
+		"test_not_panicking":       ipc.FailureKindAssertionFailed,
+		"test_unwrap_none":         ipc.FailureKindPanicked,
+		"test_index_out_of_bounds": ipc.FailureKindPanicked,
+		"test_plain_panic":         ipc.FailureKindPanicked,
+	}
+	for name, kind := range expected {
+		if kinds[name] != kind {
+			t.Errorf("Expected %s to fail with %s, got %v", name, kind, kinds[name])
+		}
+	}
+}
+
+func TestCargoTestDefinition_IgnoredTests(t *testing.T) {
+	def := NewCargoTestDefinition(createTestLogger(t))
+
+	output := `     Running unittests src/lib.rs (target/debug/deps/rust_basic-0123456789abcdef)
+{"type":"suite","event":"started","test_count":2}
+{"type":"test","event":"started","name":"tests::test_add"}
+{"type":"test","name":"tests::test_add","event":"ok","exec_time":0.001}
+{"type":"test","event":"started","name":"tests::test_ignored"}
+{"type":"test","name":"tests::test_ignored","event":"ignored"}
+{"type":"suite","event":"ok","passed":1,"failed":0,"ignored":1,"exec_time":0.01}
+`
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+
+	payloads := make(map[string]map[string]interface{})
+	for _, e := range NewTestIPCCapture(ipcPath).GetEventsByType("testCase") {
+		payload := e["payload"].(map[string]interface{})
+		payloads[payload["testName"].(string)] = payload
+	}
+
+	ignored := payloads["test_ignored"]
+	if ignored == nil {
+		t.Fatal("Expected testCase event for test_ignored")
+	}
+	if ignored["status"] != "SKIP" || ignored["skipReason"] != "ignored" {
+		t.Errorf("Expected SKIP with reason ignored, got status=%v skipReason=%v", ignored["status"], ignored["skipReason"])
+	}
+	if _, hasReason := payloads["test_add"]["skipReason"]; hasReason {
+		t.Error("Passing tests must not carry a skip reason")
+	}
+}
+
+func TestCargoTestDefinition_RstestCases(t *testing.T) {
+	def := NewCargoTestDefinition(createTestLogger(t))
+
+	output := `     Running unittests src/lib.rs (target/debug/deps/rust_basic-0123456789abcdef)
+{"type":"suite","event":"started","test_count":3}
+{"type":"test","event":"started","name":"tests::fibonacci::case_1"}
+{"type":"test","name":"tests::fibonacci::case_1","event":"ok","exec_time":0.001}
+{"type":"test","event":"started","name":"tests::fibonacci::case_2_large"}
+{"type":"test","name":"tests::fibonacci::case_2_large","event":"ok","exec_time":0.001}
+{"type":"test","event":"started","name":"tests::showcase_1"}
+{"type":"test","name":"tests::showcase_1","event":"ok","exec_time":0.001}
+{"type":"suite","event":"ok","passed":3,"failed":0,"ignored":0,"exec_time":0.01}
+`
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+
+	payloads := make(map[string]map[string]interface{})
+	for _, e := range NewTestIPCCapture(ipcPath).GetEventsByType("testCase") {
+		payload := e["payload"].(map[string]interface{})
+		payloads[payload["testName"].(string)] = payload
+	}
+	for _, name := range []string{"case_1", "case_2_large"} {
+		if payloads[name]["baseName"] != "fibonacci" || payloads[name]["paramId"] != name {
+			t.Errorf("Expected %s to be a parameter set of fibonacci, got %v", name, payloads[name])
+		}
+	}
+	if _, ok := payloads["showcase_1"]["baseName"]; ok {
+		t.Error("Expected a test not generated by rstest to have no base name")
+	}
+}
+
+func TestCargoTestDefinition_Benchmarks(t *testing.T) {
+	def := NewCargoTestDefinition(createTestLogger(t))
+
+	// Bench results arrive as JSON "bench" records or, from the pretty formatter, as text lines
+	output := `     Running unittests src/lib.rs (target/release/deps/rust_benchmarks-0123456789abcdef)
+{"type":"suite","event":"started","test_count":4}
+{"type":"test","event":"started","name":"tests::test_fibonacci"}
+{"type":"test","name":"tests::test_fibonacci","event":"ok","exec_time":0.001}
+{"type":"test","event":"started","name":"tests::bench_fibonacci_10"}
+{ "type": "bench", "name": "tests::bench_fibonacci_10", "median": 212, "deviation": 7 }
+test tests::bench_factorial_10 ... bench:       1,234.50 ns/iter (+/- 56.25)
+{"type":"suite","event":"ok","passed":1,"failed":0,"ignored":0,"exec_time":1.2}
+`
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	capture := NewTestIPCCapture(ipcPath)
+
+	payloads := make(map[string]map[string]interface{})
+	for _, e := range capture.GetEventsByType("testCase") {
+		payload := e["payload"].(map[string]interface{})
+		payloads[payload["testName"].(string)] = payload
+	}
+
+	tests := []struct {
+		name      string
+		median    float64
+		deviation float64
+	}{
+		{"bench_fibonacci_10", 212, 7},
+		{"bench_factorial_10", 1234.5, 56.25},
+	}
+	for _, tt := range tests {
+		payload := payloads[tt.name]
+		if payload == nil {
+			t.Errorf("Expected testCase event for %s", tt.name)
+			continue
+		}
+		if payload["status"] != "BENCH" {
+			t.Errorf("%s: expected status BENCH, got %v", tt.name, payload["status"])
+		}
+		if payload["benchMedianNs"] != tt.median || payload["benchDeviationNs"] != tt.deviation {
+			t.Errorf("%s: expected %v +/- %v ns/iter, got %v +/- %v",
+				tt.name, tt.median, tt.deviation, payload["benchMedianNs"], payload["benchDeviationNs"])
+		}
+	}
+	if payloads["test_fibonacci"]["status"] != "PASS" {
+		t.Errorf("Expected test_fibonacci to pass, got %v", payloads["test_fibonacci"]["status"])
+	}
+
+	// A module that ran benchmarks alongside tests still passes
+	for _, e := range capture.GetEventsByType("testGroupResult") {
+		payload := e["payload"].(map[string]interface{})
+		if payload["groupName"] == "tests" && payload["status"] != "PASS" {
+			t.Errorf("Expected tests module to pass, got %v", payload["status"])
+		}
+	}
+}
+
+func TestParseBenchNanos(t *testing.T) {
+	tests := map[string]float64{
+		"212":      212,
+		"1,234":    1234,
+		"1,234.50": 1234.5,
+		"0.25":     0.25,
+		"n/a":      0,
+	}
+	for input, want := range tests {
+		if got := parseBenchNanos(input); got != want {
+			t.Errorf("parseBenchNanos(%q) = %v, want %v", input, got, want)
+		}
+	}
+}
+
+func TestCargoTestDefinition_BinaryCrash(t *testing.T) {
+	tests := []struct {
+		name            string
+		exitLine        string
+		expectedMessage string
+		expectedSignal  interface{}
+	}{
+		{
+			name:            "killed by signal",
+			exitLine:        "  process didn't exit successfully: `/work/target/debug/deps/rust_crash-0123456789abcdef --format json` (signal: 6, SIGABRT: process abort signal)\n",
+			expectedMessage: "test binary crashed with signal 6 (SIGABRT)",
+			expectedSignal:  float64(6),
+		},
+		{
+			name:            "output ends without a summary",
+			expectedMessage: "test binary stopped before finishing its tests",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			def := NewCargoTestDefinition(createTestLogger(t))
+
+			output := `     Running unittests src/lib.rs (target/debug/deps/rust_crash-0123456789abcdef)
+{"type":"suite","event":"started","test_count":3}
+{"type":"test","event":"started","name":"tests::test_double"}
+{"type":"test","event":"started","name":"tests::test_slow"}
+{"type":"test","name":"tests::test_double","event":"ok","exec_time":0.001}
+{"type":"test","event":"started","name":"tests::test_abort"}
+error: test failed, to rerun pass ` + "`--lib`" + `
+
+Caused by:
+` + tt.exitLine
+			ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+			if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+				t.Fatalf("ProcessOutput failed: %v", err)
+			}
+			capture := NewTestIPCCapture(ipcPath)
+
+			results := make(map[string]map[string]interface{})
+			for _, e := range capture.GetEventsByType("testCase") {
+				payload := e["payload"].(map[string]interface{})
+				results[payload["testName"].(string)] = payload
+			}
+
+			// The test that started last was running when the binary died
+			abort := results["test_abort"]
+			if abort == nil || abort["status"] != "FAIL" {
+				t.Fatalf("Expected test_abort to fail, got %v", abort)
+			}
+			errPayload := abort["error"].(map[string]interface{})
+			if errPayload["errorType"] != "CRASHED" || errPayload["message"] != tt.expectedMessage {
+				t.Errorf("Expected CRASHED error %q, got %v", tt.expectedMessage, errPayload)
+			}
+
+			// Other unfinished tests are not run; finished tests keep their result
+			if slow := results["test_slow"]; slow == nil || slow["status"] != "SKIP" || slow["skipReason"] != "not_run" {
+				t.Errorf("Expected test_slow to be skipped as not_run, got %v", slow)
+			}
+			if double := results["test_double"]; double == nil || double["status"] != "PASS" {
+				t.Errorf("Expected test_double to pass, got %v", double)
+			}
+
+			crashes := capture.GetEventsByType("binaryCrash")
+			if len(crashes) != 1 {
+				t.Fatalf("Expected 1 binaryCrash event, got %d", len(crashes))
+			}
+			crash := crashes[0]["payload"].(map[string]interface{})
+			if crash["target"] != "rust-crash" || crash["testName"] != "tests::test_abort" || crash["signal"] != tt.expectedSignal {
+				t.Errorf("Unexpected binaryCrash payload: %v", crash)
+			}
+
+			for _, e := range capture.GetEventsByType("testGroupResult") {
+				payload := e["payload"].(map[string]interface{})
+				if payload["groupName"] == "rust-crash" && payload["status"] != "FAIL" {
+					t.Errorf("Expected crate group to fail, got %v", payload["status"])
+				}
+			}
+		})
+	}
+}
+
+func TestCargoTestDefinition_CustomHarnessExit(t *testing.T) {
+	// A harness = false target prints its own output and exits 101 when a test fails
+	output := `     Running tests/custom_harness.rs (target/debug/deps/custom_harness-0123456789abcdef)
+
+running 2 tests
+test math::adds ... ok
+test math::fails_on_purpose ... FAILED
+
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out
+
+error: test failed, to rerun pass ` + "`--test custom_harness`" + `
+
+Caused by:
+  process didn't exit successfully: ` + "`/work/target/debug/deps/custom_harness-0123456789abcdef -Z unstable-options --format json`" + ` (exit status: 101)
+`
+	def := NewCargoTestDefinition(createTestLogger(t))
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	capture := NewTestIPCCapture(ipcPath)
+
+	if crashes := capture.GetEventsByType("binaryCrash"); len(crashes) != 0 {
+		t.Errorf("Expected no binaryCrash event for a custom harness, got %v", crashes)
+	}
+	if groups := capture.GetEventsByType("testGroupDiscovered"); len(groups) != 0 {
+		t.Errorf("Expected the custom harness to report its own groups, got %v", groups)
+	}
+}
+
+func TestCustomHarnessTargets(t *testing.T) {
+	manifest := `[package]
+name = "rust-plain-harness"
+
+[lib]
+harness = false # doctests only
+
+[[test]]
+name = "plain-harness"
+harness = false
+
+[[test]]
+name = "libtest"
+
+[[bench]]
+path = "benches/fuzz_driver.rs"
+harness = false
+
+[dependencies]
+harness = "1.0"
+`
+	targets := customHarnessTargets(manifest, "rust-plain-harness")
+	expected := map[string]string{"rust_plain_harness": "lib", "plain_harness": "test", "fuzz_driver": "bench"}
+	if !reflect.DeepEqual(targets, expected) {
+		t.Errorf("customHarnessTargets = %v, want %v", targets, expected)
+	}
+}
+
+func TestCargoTestDefinition_CustomHarnessCapture(t *testing.T) {
+	dir := t.TempDir()
+	manifestPath := filepath.Join(dir, "Cargo.toml")
+	manifest := "[package]\nname = \"rust-plain-harness\"\n\n[[test]]\nname = \"plain_harness\"\nharness = false\n"
+	if err := os.WriteFile(manifestPath, []byte(manifest), 0644); err != nil {
+		t.Fatal(err)
+	}
+	metadata, _ := json.Marshal(map[string]interface{}{
+		"packages": []map[string]interface{}{{"name": "rust-plain-harness", "manifest_path": manifestPath}},
+	})
+	original := runCargoMetadata
+	runCargoMetadata = func(string, string) ([]byte, error) { return metadata, nil }
+	t.Cleanup(func() { runCargoMetadata = original })
+
+	// The harness prints lines that look like libtest's, which must not become tests
+	output := `     Running unittests src/lib.rs (target/debug/deps/rust_plain_harness-0123456789abcdef)
+{"type":"suite","event":"started","test_count":1}
+{"type":"test","event":"started","name":"tests::test_add"}
+{"type":"test","name":"tests::test_add","event":"ok","exec_time":0.001}
+{"type":"suite","event":"ok","passed":1,"failed":0,"ignored":0,"measured":0,"filtered_out":0,"exec_time":0.001}
+     Running tests/plain_harness.rs (target/debug/deps/plain_harness-0123456789abcdef)
+test phantom ... ok
+{"type":"test","name":"phantom_json","event":"failed"}
+checked 3 inputs, 1 failed
+error: test failed, to rerun pass ` + "`--test plain_harness`" + `
+
+Caused by:
+  process didn't exit successfully: ` + "`/work/target/debug/deps/plain_harness-0123456789abcdef -Z unstable-options --format json`" + ` (exit status: 1)
+`
+	def := NewCargoTestDefinition(createTestLogger(t))
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	capture := NewTestIPCCapture(ipcPath)
+
+	for _, e := range capture.GetEventsByType("testCase") {
+		if name := e["payload"].(map[string]interface{})["testName"]; name != "test_add" {
+			t.Errorf("Expected only test_add, got the phantom test %v", name)
+		}
+	}
+
+	var harnessGroup map[string]interface{}
+	for _, e := range capture.GetEventsByType("testGroupDiscovered") {
+		if payload := e["payload"].(map[string]interface{}); payload["groupName"] == "plain-harness" {
+			harnessGroup = payload
+		}
+	}
+	if harnessGroup == nil || harnessGroup["customHarness"] != true || harnessGroup["targetKind"] != "test" {
+		t.Fatalf("Expected a custom harness group for the test target, got %v", harnessGroup)
+	}
+
+	stdout := capture.GetEventsByType("groupStdout")
+	if len(stdout) != 1 {
+		t.Fatalf("Expected the harness output in one chunk, got %v", stdout)
+	}
+	chunk := stdout[0]["payload"].(map[string]interface{})["chunk"].(string)
+	for _, line := range []string{"test phantom ... ok", "checked 3 inputs, 1 failed", "(exit status: 1)"} {
+		if !strings.Contains(chunk, line) {
+			t.Errorf("Expected %q in the captured output, got %q", line, chunk)
+		}
+	}
+
+	results := make(map[string]interface{})
+	for _, e := range capture.GetEventsByType("testGroupResult") {
+		payload := e["payload"].(map[string]interface{})
+		results[payload["groupName"].(string)] = payload["status"]
+	}
+	if results["plain-harness"] != "FAIL" || results["rust-plain-harness"] != "PASS" {
+		t.Errorf("Expected the harness to fail by its exit code next to the passing lib, got %v", results)
+	}
+	if crashes := capture.GetEventsByType("binaryCrash"); len(crashes) != 0 {
+		t.Errorf("Expected no binaryCrash event, got %v", crashes)
+	}
+}
+
+func TestCargoTestDefinition_InactivityTimeout(t *testing.T) {
+	output := `     Running unittests src/lib.rs (target/debug/deps/rust_basic-0123456789abcdef)
+{"type":"suite","event":"started","test_count":3}
+{"type":"test","event":"started","name":"test_add"}
+{"type":"test","event":"started","name":"tests::test_deadlock"}
+{"type":"test","name":"test_add","event":"ok","exec_time":0.001}
+{"type":"test","event":"started","name":"tests::test_long_running"}
+`
+
+	// The orchestrator asks which tests are in flight before killing the run
+	def := NewCargoTestDefinition(createTestLogger(t))
+	jsonEventCount := 0
+	for _, line := range strings.Split(strings.TrimSpace(output), "\n") {
+		def.processLineData(line, &jsonEventCount)
+	}
+	running := def.RunningTests()
+	expected := []string{"rust-basic > tests > test_deadlock", "rust-basic > tests > test_long_running"}
+	if strings.Join(running, ", ") != strings.Join(expected, ", ") {
+		t.Errorf("Expected running tests %v, got %v", expected, running)
+	}
+
+	// Tests still running when the output ends are timed out, not crashed
+	def = NewCargoTestDefinition(createTestLogger(t))
+	def.MarkTimedOut()
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	capture := NewTestIPCCapture(ipcPath)
+
+	timedOut := 0
+	for _, e := range capture.GetEventsByType("testCase") {
+		payload := e["payload"].(map[string]interface{})
+		if payload["testName"] == "test_add" {
+			continue
+		}
+		errPayload, _ := payload["error"].(map[string]interface{})
+		if payload["status"] != "FAIL" || errPayload["errorType"] != "TIMED_OUT" {
+			t.Errorf("Expected %s to fail as TIMED_OUT, got %v", payload["testName"], payload)
+		}
+		timedOut++
+	}
+	if timedOut != 2 {
+		t.Errorf("Expected 2 timed out tests, got %d", timedOut)
+	}
+	if crashes := capture.GetEventsByType("binaryCrash"); len(crashes) != 0 {
+		t.Errorf("Expected no binaryCrash event after a timeout, got %v", crashes)
+	}
+}
+
+func TestCargoTestDefinition_Interrupted(t *testing.T) {
+	def := NewCargoTestDefinition(createTestLogger(t))
+	def.MarkInterrupted()
+
+	// rust_basic finished before the timeout; rust_timeout was still running
+	output := `     Running unittests src/lib.rs (target/debug/deps/rust_basic-0123456789abcdef)
+{"type":"suite","event":"started","test_count":1}
+{"type":"test","event":"started","name":"tests::test_add"}
+{"type":"test","name":"tests::test_add","event":"ok","exec_time":0.001}
+{"type":"suite","event":"ok","passed":1,"failed":0,"ignored":0,"exec_time":0.01}
+     Running unittests src/lib.rs (target/debug/deps/rust_timeout-0123456789abcdef)
+{"type":"suite","event":"started","test_count":2}
+{"type":"test","event":"started","name":"tests::test_sleeps_past_timeout"}
+{"type":"test","event":"started","name":"tests::test_triple"}
+{"type":"test","name":"tests::test_triple","event":"ok","exec_time":0.001}
+`
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	capture := NewTestIPCCapture(ipcPath)
+
+	// The killed binary's groups get no result, so the report marks them INTERRUPTED
+	results := make(map[string]interface{})
+	for _, e := range capture.GetEventsByType("testGroupResult") {
+		payload := e["payload"].(map[string]interface{})
+		parents := convertToStringSlice(payload["parentNames"])
+		results[strings.Join(append(parents, payload["groupName"].(string)), " > ")] = payload["status"]
+	}
+	expected := map[string]interface{}{
+		"rust-basic":         "PASS",
+		"rust-basic > tests": "PASS",
+	}
+	if len(results) != len(expected) {
+		t.Errorf("Expected group results %v, got %v", expected, results)
+	}
+	for group, status := range expected {
+		if results[group] != status {
+			t.Errorf("Expected %s to be %v, got %v", group, status, results[group])
+		}
+	}
+
+	// Finished tests are still reported, and nothing is reported as crashed
+	if testCases := capture.GetEventsByType("testCase"); len(testCases) != 2 {
+		t.Errorf("Expected 2 testCase events, got %d", len(testCases))
+	}
+	if crashes := capture.GetEventsByType("binaryCrash"); len(crashes) != 0 {
+		t.Errorf("Expected no binaryCrash event after an interrupted run, got %v", crashes)
+	}
+}
+
+func TestParsePanicLocation(t *testing.T) {
+	testCases := []struct {
+		desc     string
+		message  string
+		expected *PanicLocation
+	}{
+		{"current toolchains", "thread 'tests::test_sub' panicked at src/lib.rs:42:5:\nassertion failed", &PanicLocation{File: "src/lib.rs", Line: 42, Column: 5}},
+		{"older toolchains", "thread 'tests::test_sub' panicked at 'Math is broken!\nreally', utils/src/lib.rs:10:9\nnote: run with `RUST_BACKTRACE=1`", &PanicLocation{File: "utils/src/lib.rs", Line: 10, Column: 9}},
+		{"windows path", `thread 'main' panicked at C:\work\src\lib.rs:3:1:` + "\nboom", &PanicLocation{File: `C:\work\src\lib.rs`, Line: 3, Column: 1}},
+		{"no panic", "error: test failed", nil},
+		{"no location", "thread 'tests::test_sub' panicked at somewhere:\nboom", nil},
+	}
+	for _, tc := range testCases {
+		t.Run(tc.desc, func(t *testing.T) {
+			location := parsePanicLocation(tc.message)
+			if (location == nil) != (tc.expected == nil) || (location != nil && *location != *tc.expected) {
+				t.Errorf("Expected %+v, got %+v", tc.expected, location)
+			}
+		})
+	}
+}
+
+func TestResolveRustSourcePath(t *testing.T) {
+	root := t.TempDir()
+	utilsDir := filepath.Join(root, "utils")
+	if err := os.MkdirAll(filepath.Join(utilsDir, "src"), 0755); err != nil {
+		t.Fatalf("Failed to create workspace: %v", err)
+	}
+	for path, content := range map[string]string{
+		filepath.Join(root, "Cargo.toml"):            "[workspace]\nmembers = [\"utils\"]\n",
+		filepath.Join(utilsDir, "Cargo.toml"):        "[package]\nname = \"utils\"\n",
+		filepath.Join(utilsDir, "src", "lib.rs"):     "",
+		filepath.Join(utilsDir, "src", "helpers.rs"): "",
+	} {
+		if err := os.WriteFile(path, []byte(content), 0644); err != nil {
+			t.Fatalf("Failed to write %s: %v", path, err)
+		}
+	}
+
+	if found := findCargoWorkspaceRoot(utilsDir); found != root {
+		t.Errorf("Expected workspace root %s, got %s", root, found)
+	}
+	if found := findCargoWorkspaceRoot(t.TempDir()); found == root {
+		t.Errorf("Expected a directory outside any workspace to be its own root")
+	}
+
+	testCases := []struct {
+		desc        string
+		file        string
+		manifestDir string
+		expected    string
+		external    bool
+	}{
+		{"already relative to the workspace", "utils/src/lib.rs", utilsDir, "utils/src/lib.rs", false},
+		{"relative to the crate", "src/helpers.rs", utilsDir, "utils/src/helpers.rs", false},
+		{"absolute inside the workspace", filepath.Join(utilsDir, "src", "lib.rs"), "", "utils/src/lib.rs", false},
+		{"unknown crate", "src/lib.rs", "", "src/lib.rs", false},
+		{"rust sysroot", "/rustc/90b35a6239c3d8bdabc530a6a0816f7ff89a0aaf/library/core/src/option.rs", utilsDir,
+			"/rustc/90b35a6239c3d8bdabc530a6a0816f7ff89a0aaf/library/core/src/option.rs", true},
+		{"cargo registry", "/home/dev/.cargo/registry/src/index.crates.io-6f17d22bba15001f/serde-1.0.0/src/de.rs", utilsDir,
+			"/home/dev/.cargo/registry/src/index.crates.io-6f17d22bba15001f/serde-1.0.0/src/de.rs", true},
+		{"outside the workspace", filepath.Join(filepath.Dir(root), "vendored", "src", "lib.rs"), "",
+			filepath.ToSlash(filepath.Join(filepath.Dir(root), "vendored", "src", "lib.rs")), true},
+	}
+	for _, tc := range testCases {
+		t.Run(tc.desc, func(t *testing.T) {
+			path, external := resolveRustSourcePath(tc.file, root, tc.manifestDir)
+			if path != tc.expected || external != tc.external {
+				t.Errorf("Expected %s (external %v), got %s (external %v)", tc.expected, tc.external, path, external)
+			}
+		})
+	}
+}
+
+func TestCargoTestDefinition_CrossPaths(t *testing.T) {
+	root := t.TempDir()
+	targetDir := filepath.Join(t.TempDir(), "target")
+	for _, path := range []string{filepath.Join(root, "src", "lib.rs"), filepath.Join(targetDir, "debug", "build", "gen.rs")} {
+		if err := os.MkdirAll(filepath.Dir(path), 0755); err != nil {
+			t.Fatal(err)
+		}
+		if err := os.WriteFile(path, nil, 0644); err != nil {
+			t.Fatal(err)
+		}
+	}
+
+	def := NewCargoTestDefinition(createTestLogger(t))
+	def.workspaceRoot = root
+	def.metadata = &CargoMetadata{WorkspaceRoot: root, TargetDirectory: targetDir}
+	if path := def.hostPath("/project/src/lib.rs"); path != filepath.Join(root, "src", "lib.rs") {
+		t.Errorf("Expected cargo's own paths to stay as they are without cross, got %s", path)
+	}
+
+	def.ModifyCommand([]string{"cross", "test", "--target", "aarch64-unknown-linux-gnu"}, "", "")
+	testCases := []struct {
+		desc     string
+		file     string
+		expected string
+		external bool
+	}{
+		{"in the mounted workspace", "/project/src/lib.rs", "src/lib.rs", false},
+		{"in the mounted target directory", "/target/debug/build/gen.rs", filepath.ToSlash(filepath.Join(targetDir, "debug", "build", "gen.rs")), true},
+		{"missing from the host", "/project/src/gone.rs", "/project/src/gone.rs", true},
+		{"rust sysroot of the container", "/rustc/90b35a6239c3d8bdabc530a6a0816f7ff89a0aaf/library/core/src/option.rs",
+			"/rustc/90b35a6239c3d8bdabc530a6a0816f7ff89a0aaf/library/core/src/option.rs", true},
+	}
+	for _, tc := range testCases {
+		t.Run(tc.desc, func(t *testing.T) {
+			path, external := resolveRustSourcePath(def.hostPath(tc.file), root, "")
+			if path != tc.expected || external != tc.external {
+				t.Errorf("Expected %s (external %v), got %s (external %v)", tc.expected, tc.external, path, external)
+			}
+		})
+	}
+}
+
+func TestHasLibtestFilter(t *testing.T) {
+	testCases := []struct {
+		cmd      []string
+		expected bool
+	}{
+		{[]string{"cargo", "test"}, false},
+		{[]string{"cargo", "test", "test_add"}, true},
+		{[]string{"cargo", "+nightly", "test", "math::"}, true},
+		{[]string{"cargo", "test", "-p", "utils", "--features", "serde"}, false},
+		{[]string{"cargo", "test", "--test", "integration", "--", "--nocapture"}, false},
+		{[]string{"cargo", "test", "--", "--exact", "tests::test_add"}, true},
+		{[]string{"cargo", "test", "--", "--skip", "slow", "--test-threads", "1"}, false},
+	}
+	for _, tc := range testCases {
+		cargoArgs, testArgs := tc.cmd, []string(nil)
+		for i, arg := range tc.cmd {
+			if arg == "--" {
+				cargoArgs, testArgs = tc.cmd[:i], tc.cmd[i+1:]
+				break
+			}
+		}
+		if got := hasLibtestFilter(cargoArgs, testArgs); got != tc.expected {
+			t.Errorf("hasLibtestFilter(%v) = %v, expected %v", tc.cmd, got, tc.expected)
+		}
+	}
+}
+
+func TestLibtestFilters(t *testing.T) {
+	testCases := []struct {
+		cmd      []string
+		expected []string
+	}{
+		{[]string{"cargo", "test"}, nil},
+		{[]string{"cargo", "test", "-p", "utils", "--", "--nocapture", "--test-threads", "1"}, nil},
+		{[]string{"cargo", "test", "fibonacci"}, []string{"fibonacci"}},
+		{[]string{"cargo", "+nightly", "test", "math::", "--", "--exact", "tests::test_add"}, []string{"math:: (exact)", "tests::test_add (exact)"}},
+		{[]string{"cargo", "test", "--", "--skip", "slow", "--skip=net::"}, []string{"--skip slow", "--skip net::"}},
+		{[]string{"cargo", "test", "add", "--", "--skip", "add_overflow"}, []string{"add", "--skip add_overflow"}},
+	}
+	for _, tc := range testCases {
+		cargoArgs, testArgs := splitTestArgs(tc.cmd)
+		if got := libtestFilters(cargoArgs, testArgs); !reflect.DeepEqual(got, tc.expected) {
+			t.Errorf("libtestFilters(%v) = %q, expected %q", tc.cmd, got, tc.expected)
+		}
+	}
+}
+
+func TestCargoTestDefinition_NoTestsMatched(t *testing.T) {
+	testCases := []struct {
+		desc       string
+		cmd        []string
+		testCounts []int
+		expected   bool
+	}{
+		{"filter matched nothing", []string{"cargo", "test", "no_such_test"}, []int{0, 0, 0}, true},
+		{"filter matched in one binary", []string{"cargo", "test", "test_add"}, []int{0, 1, 0}, false},
+		{"skip excluded everything", []string{"cargo", "test", "--", "--skip", "test_"}, []int{0, 0}, true},
+		{"skip left some tests", []string{"cargo", "test", "--", "--skip", "slow"}, []int{0, 3}, false},
+		{"no filter and no tests", []string{"cargo", "test"}, []int{0}, false},
+		{"build failed before any binary ran", []string{"cargo", "test", "test_add"}, nil, false},
+	}
+	for _, tc := range testCases {
+		t.Run(tc.desc, func(t *testing.T) {
+			def := NewCargoTestDefinition(createTestLogger(t))
+			def.ModifyCommand(tc.cmd, "/tmp/test.jsonl", "test-run-id")
+			for _, count := range tc.testCounts {
+				if err := def.processEvent(&CargoTestEvent{Type: "suite", Event: "started", TestCount: count}); err != nil {
+					t.Fatalf("processEvent failed: %v", err)
+				}
+			}
+			if got := def.NoTestsMatched(); got != tc.expected {
+				t.Errorf("Expected NoTestsMatched %v, got %v", tc.expected, got)
+			}
+		})
+	}
+}
@@ -0,0 +1,588 @@
+package definitions
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"regexp"
+	"sort"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+	"unicode/utf8"
+
+	"github.com/zk/3pio/internal/logger"
+)
+
+// Patterns for criterion's console output, e.g.
+//
+//	Benchmarking fib 10: Analyzing
+//	fib 10                  time:   [163.27 ns 163.53 ns 163.84 ns]
+//	                        change: [+10.112% +11.385% +12.604%] (p = 0.00 < 0.05)
+//	                        Performance has regressed.
+//
+// IDs too long to fit before "time:" are printed on their own line first. Benchmarks
+// with a throughput print the change as "change:" followed by "time: [...%]" lines.
+var (
+	criterionBenchmarkingRegex = regexp.MustCompile(`^Benchmarking (.+?)(?:: (?:Warming up|Collecting|Analyzing|Complete)\b.*)?$`)
+	criterionTimeRegex         = regexp.MustCompile(`^(.*?)\s*time:\s+\[([\d.]+) (\S+) ([\d.]+) (\S+) ([\d.]+) (\S+)\]`)
+	criterionChangeRegex       = regexp.MustCompile(`^(?:change:\s+(?:time:\s+)?|time:\s+)\[(\S+)% (\S+)% (\S+)%\]`)
+)
+
+// criterionManifestRegex finds a criterion dependency in a Cargo.toml
+var criterionManifestRegex = regexp.MustCompile(`(?m)^\s*(?:criterion\s*=|\[(?:dev-)?dependencies\.criterion\])`)
+
+// Verdicts criterion prints after comparing a benchmark with its saved baseline
+const (
+	criterionRegressedVerdict = "Performance has regressed."
+	criterionImprovedVerdict  = "Performance has improved."
+	criterionNoChangeVerdict  = "No change in performance detected."
+	criterionNoiseVerdict     = "Change within noise threshold."
+)
+
+// criterionNoiseThreshold is criterion's default --noise-threshold, as a fraction.
+// It decides regressions from change/estimates.json when no verdict was printed.
+const criterionNoiseThreshold = 0.01
+
+// criterionDefaultTarget names the target group when cargo's "Running" line wasn't seen
+const criterionDefaultTarget = "benches"
+
+// criterionTimeUnits converts criterion's time units to nanoseconds
+var criterionTimeUnits = map[string]float64{
+	"ps": 0.001,
+	"ns": 1,
+	"µs": 1e3,
+	"us": 1e3,
+	"ms": 1e6,
+	"s":  1e9,
+}
+
+// CriterionDefinition implements support for criterion.rs benchmarks run by cargo bench
+type CriterionDefinition struct {
+	logger    *logger.FileLogger
+	mu        sync.Mutex
+	ipcWriter *IPCWriter
+
+	lineLimit     // Longest line of output parsed whole (--max-line-length)
+	workDirectory // Directory cargo bench runs in
+
+	targetDir     string                         // Cargo target directory holding target/criterion
+	currentTarget string                         // Bench target whose output is being read
+	lastLine      string                         // Previous non-empty line, which holds long benchmark IDs
+	current       *CriterionBenchmark            // Benchmark whose result lines are being read
+	groups        map[string]*CriterionGroupInfo // Groups by "/"-joined path, to avoid duplicates
+	groupOrder    []string                       // Group keys in discovery order
+}
+
+// CriterionGroupInfo tracks a group of benchmarks (bench target, criterion group or benchmark function)
+type CriterionGroupInfo struct {
+	Name      string
+	Parents   []string
+	StartTime time.Time
+	Regressed bool // A benchmark in this group or its subgroups regressed
+	Finalized bool
+}
+
+// CriterionBenchmark is the result of a single criterion benchmark
+type CriterionBenchmark struct {
+	ID      string   // Full benchmark ID, e.g. "fib 10" or "group/function/input"
+	Name    string   // Test case name (last part of the ID)
+	Parents []string // Groups the benchmark is reported under
+
+	// Times in nanoseconds
+	MeanNs      float64
+	MedianNs    float64
+	StdDevNs    float64
+	DeviationNs float64 // libtest's +/- for #[bench] results, which have no mean or std-dev
+
+	ChangePercent float64 // Change of the mean against the baseline, in percent
+	HasChange     bool    // A baseline existed to compare against
+	Regressed     bool
+	HasVerdict    bool // Criterion printed its own regression verdict
+}
+
+// criterionEstimates is the subset of criterion's estimates.json that 3pio reads
+type criterionEstimates struct {
+	Mean   criterionEstimate  `json:"mean"`
+	Median criterionEstimate  `json:"median"`
+	StdDev *criterionEstimate `json:"std_dev"` // Not written for change/estimates.json
+}
+
+// criterionEstimate is a single statistic in estimates.json
+type criterionEstimate struct {
+	PointEstimate      float64 `json:"point_estimate"`
+	ConfidenceInterval struct {
+		LowerBound float64 `json:"lower_bound"`
+		UpperBound float64 `json:"upper_bound"`
+	} `json:"confidence_interval"`
+}
+
+// NewCriterionDefinition creates a new criterion benchmark runner definition
+func NewCriterionDefinition(logger *logger.FileLogger) *CriterionDefinition {
+	return &CriterionDefinition{
+		logger: logger,
+		groups: make(map[string]*CriterionGroupInfo),
+	}
+}
+
+// Name returns the name of this test runner
+func (c *CriterionDefinition) Name() string {
+	return "criterion"
+}
+
+// Detect checks if the command is cargo bench in a project that uses criterion
+func (c *CriterionDefinition) Detect(args []string) bool {
+	return CargoSubcommand(args) == "bench" && usesCriterion(c.inWorkDir("Cargo.toml"), args)
+}
+
+// usesCriterion checks if a cargo bench command runs criterion benchmarks, going by
+// the manifest at manifestPath. Only the libtest harness runs with --lib, so that
+// stays with the cargo runner.
+func usesCriterion(manifestPath string, args []string) bool {
+	for _, arg := range args {
+		if arg == "--" {
+			break
+		}
+		if arg == "--lib" {
+			return false
+		}
+	}
+
+	content, err := os.ReadFile(manifestPath)
+	if err != nil {
+		return false
+	}
+	return criterionManifestRegex.Match(content)
+}
+
+// ModifyCommand returns the command unchanged: criterion's harness rejects libtest's JSON flags
+func (c *CriterionDefinition) ModifyCommand(cmd []string, ipcPath, runID string) []string {
+	return append([]string{}, cmd...)
+}
+
+// GetTestFiles returns empty array for dynamic discovery
+func (c *CriterionDefinition) GetTestFiles(args []string) ([]string, error) {
+	// Benchmarks are discovered as they run
+	return []string{}, nil
+}
+
+// RequiresAdapter returns false as criterion doesn't need an external adapter
+func (c *CriterionDefinition) RequiresAdapter() bool {
+	return false
+}
+
+// ProcessOutput reads combined cargo bench output and converts criterion results to IPC events
+func (c *CriterionDefinition) ProcessOutput(combinedOutput io.Reader, ipcPath string) error {
+	// Initialize IPC writer
+	var err error
+	c.ipcWriter, err = NewIPCWriter(ipcPath)
+	if err != nil {
+		return fmt.Errorf("failed to create IPC writer: %w", err)
+	}
+	defer func() {
+		if err := c.ipcWriter.Close(); err != nil {
+			c.logger.Debug("Failed to close IPC writer: %v", err)
+		}
+	}()
+
+	if c.targetDir == "" {
+		c.targetDir = c.inWorkDir(criterionTargetDir())
+	}
+	c.logger.Debug("Reading criterion estimates from %s", c.targetDir)
+
+	lines := c.newLineReader(combinedOutput)
+	for {
+		line, _, ok := lines.Next()
+		if !ok {
+			break
+		}
+		c.processLine(line)
+	}
+
+	if err := lines.Err(); err != nil {
+		return fmt.Errorf("error reading cargo bench output: %w", err)
+	}
+
+	c.mu.Lock()
+	c.finishBenchmark()
+	c.mu.Unlock()
+
+	// Send final events for any pending groups
+	c.finalizePendingGroups()
+
+	// Send runComplete event to signal processing is done
+	runCompleteEvent := map[string]interface{}{
+		"eventType": "runComplete",
+		"payload":   map[string]interface{}{},
+	}
+	if err := c.ipcWriter.WriteEvent(runCompleteEvent); err != nil {
+		c.logger.Debug("Failed to send runComplete event: %v", err)
+	}
+
+	return nil
+}
+
+// criterionTargetDir returns the cargo target directory criterion writes to
+func criterionTargetDir() string {
+	if dir := os.Getenv("CARGO_TARGET_DIR"); dir != "" {
+		return dir
+	}
+	return "target"
+}
+
+// processLine processes a single line of cargo bench output
+func (c *CriterionDefinition) processLine(line string) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	// Progress lines on a terminal are rewritten with carriage returns
+	if idx := strings.LastIndex(line, "\r"); idx >= 0 {
+		line = line[idx+1:]
+	}
+	trimmed := strings.TrimSpace(line)
+	if trimmed == "" {
+		return
+	}
+	defer func() { c.lastLine = trimmed }()
+
+	// "Running benches/..." starts a new bench target
+	if matches := runningIntegrationTestsRegex.FindStringSubmatch(line); matches != nil {
+		c.finishBenchmark()
+		c.currentTarget = matches[1]
+		c.logger.Debug("Set current bench target to: %s", c.currentTarget)
+		return
+	}
+	if matches := runningUnittestsRegex.FindStringSubmatch(line); matches != nil {
+		c.finishBenchmark()
+		c.currentTarget = matches[1]
+		c.logger.Debug("Set current bench target to: %s (unit benches)", c.currentTarget)
+		return
+	}
+
+	// #[bench] functions in libtest targets are run by the same cargo bench
+	if matches := libtestBenchRegex.FindStringSubmatch(line); matches != nil {
+		c.finishBenchmark()
+		c.reportBenchmark(&CriterionBenchmark{
+			ID:          matches[1],
+			Name:        matches[1],
+			Parents:     []string{c.targetGroupName()},
+			MedianNs:    parseBenchNanos(matches[2]),
+			DeviationNs: parseBenchNanos(matches[3]),
+		})
+		return
+	}
+
+	if criterionBenchmarkingRegex.MatchString(trimmed) {
+		c.finishBenchmark()
+		return
+	}
+
+	if matches := criterionTimeRegex.FindStringSubmatch(trimmed); matches != nil {
+		c.finishBenchmark()
+		id := strings.TrimSpace(matches[1])
+		if id == "" {
+			// Long IDs are printed on the line before "time:"
+			id = c.lastLine
+		}
+		c.startBenchmark(id, parseCriterionTime(matches[4], matches[5]))
+		return
+	}
+
+	if c.current == nil {
+		return
+	}
+
+	if matches := criterionChangeRegex.FindStringSubmatch(trimmed); matches != nil {
+		if change, err := strconv.ParseFloat(strings.TrimPrefix(matches[2], "+"), 64); err == nil {
+			c.current.ChangePercent = change
+			c.current.HasChange = true
+		}
+		return
+	}
+
+	switch trimmed {
+	case criterionRegressedVerdict:
+		c.current.Regressed = true
+		c.current.HasVerdict = true
+	case criterionImprovedVerdict, criterionNoChangeVerdict, criterionNoiseVerdict:
+		c.current.HasVerdict = true
+	}
+}
+
+// startBenchmark begins collecting the result lines of a benchmark
+func (c *CriterionDefinition) startBenchmark(id string, estimateNs float64) {
+	parts := strings.Split(id, "/")
+
+	// One group per benchmark function: "group/function/input" is reported
+	// as case "input" in group "function", nested under group "group"
+	groupPath := parts
+	if len(parts) >= 3 {
+		groupPath = parts[:len(parts)-1]
+	}
+
+	c.current = &CriterionBenchmark{
+		ID:      id,
+		Name:    parts[len(parts)-1],
+		Parents: append([]string{c.targetGroupName()}, groupPath...),
+		MeanNs:  estimateNs,
+	}
+}
+
+// finishBenchmark reports the benchmark being collected, if any.
+// Must be called with c.mu held.
+func (c *CriterionDefinition) finishBenchmark() {
+	if c.current == nil {
+		return
+	}
+	bench := c.current
+	c.current = nil
+
+	// Prefer criterion's saved estimates over the rounded console values
+	benchDir := filepath.Join(c.targetDir, "criterion", criterionDirectoryName(bench.ID))
+	if estimates, err := readCriterionEstimates(filepath.Join(benchDir, "new", "estimates.json")); err == nil {
+		bench.MeanNs = estimates.Mean.PointEstimate
+		bench.MedianNs = estimates.Median.PointEstimate
+		if estimates.StdDev != nil {
+			bench.StdDevNs = estimates.StdDev.PointEstimate
+		}
+	} else {
+		c.logger.Debug("No criterion estimates for %s: %v", bench.ID, err)
+	}
+
+	// Compare against the baseline criterion keeps from the previous run
+	if change, err := readCriterionEstimates(filepath.Join(benchDir, "change", "estimates.json")); err == nil {
+		if !bench.HasChange {
+			bench.ChangePercent = change.Mean.PointEstimate * 100
+			bench.HasChange = true
+		}
+		if !bench.HasVerdict {
+			bench.Regressed = change.Mean.ConfidenceInterval.LowerBound > criterionNoiseThreshold
+		}
+	}
+
+	c.reportBenchmark(bench)
+}
+
+// reportBenchmark sends a benchmark result, creating its groups as needed.
+// Must be called with c.mu held.
+func (c *CriterionDefinition) reportBenchmark(bench *CriterionBenchmark) {
+	for i := range bench.Parents {
+		group := c.ensureGroup(bench.Parents[:i+1])
+		if bench.Regressed {
+			group.Regressed = true
+		}
+	}
+
+	c.sendBenchmarkResult(bench)
+}
+
+// ensureGroup discovers and starts a group the first time it is seen
+func (c *CriterionDefinition) ensureGroup(path []string) *CriterionGroupInfo {
+	key := strings.Join(path, "/")
+	if group, ok := c.groups[key]; ok {
+		return group
+	}
+
+	group := &CriterionGroupInfo{
+		Name:      path[len(path)-1],
+		Parents:   append([]string{}, path[:len(path)-1]...),
+		StartTime: time.Now(),
+	}
+	c.groups[key] = group
+	c.groupOrder = append(c.groupOrder, key)
+
+	c.sendGroupDiscovered(group.Name, group.Parents)
+	c.sendGroupStart(group.Name, group.Parents)
+	return group
+}
+
+// targetGroupName returns the display name of the current bench target group
+func (c *CriterionDefinition) targetGroupName() string {
+	if c.currentTarget == "" {
+		return criterionDefaultTarget
+	}
+	// Convert underscores to hyphens like the cargo test runner
+	return strings.ReplaceAll(c.currentTarget, "_", "-")
+}
+
+// finalizePendingGroups sends result events for groups that haven't been finalized.
+// Groups with a regressed benchmark get the WARN status.
+func (c *CriterionDefinition) finalizePendingGroups() {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	// Finalize child groups before their parents
+	keys := append([]string{}, c.groupOrder...)
+	sort.SliceStable(keys, func(i, j int) bool {
+		return strings.Count(keys[i], "/") > strings.Count(keys[j], "/")
+	})
+
+	for _, key := range keys {
+		group := c.groups[key]
+		if group.Finalized {
+			continue
+		}
+
+		status := "PASS"
+		if group.Regressed {
+			status = "WARN"
+		}
+		duration := float64(time.Since(group.StartTime).Milliseconds())
+		c.sendGroupResult(group.Name, group.Parents, status, duration)
+		group.Finalized = true
+	}
+}
+
+// parseCriterionTime converts a criterion time like "163.53" "ns" to nanoseconds
+func parseCriterionTime(value, unit string) float64 {
+	parsed, err := strconv.ParseFloat(value, 64)
+	if err != nil {
+		return 0
+	}
+	scale, ok := criterionTimeUnits[unit]
+	if !ok {
+		return 0
+	}
+	return parsed * scale
+}
+
+// criterionDirectoryName returns the directory criterion stores a benchmark's data in,
+// relative to target/criterion. Each part of the ID is made filename-safe the way criterion does.
+func criterionDirectoryName(id string) string {
+	parts := strings.Split(id, "/")
+	for i, part := range parts {
+		parts[i] = criterionFilenameSafe(part)
+	}
+	return filepath.Join(parts...)
+}
+
+// criterionFilenameSafe mirrors criterion's make_filename_safe
+func criterionFilenameSafe(name string) string {
+	safe := strings.Map(func(r rune) rune {
+		if strings.ContainsRune(`?"/\*<>:|^`, r) {
+			return '_'
+		}
+		return r
+	}, name)
+
+	// Criterion truncates long names at a character boundary
+	const maxDirectoryNameLen = 64
+	if len(safe) > maxDirectoryNameLen {
+		end := maxDirectoryNameLen
+		for end > 0 && !utf8.RuneStart(safe[end]) {
+			end--
+		}
+		safe = safe[:end]
+	}
+
+	return strings.TrimSpace(safe)
+}
+
+// readCriterionEstimates reads one of criterion's estimates.json files
+func readCriterionEstimates(path string) (*criterionEstimates, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return nil, err
+	}
+	var estimates criterionEstimates
+	if err := json.Unmarshal(data, &estimates); err != nil {
+		return nil, fmt.Errorf("failed to parse %s: %w", path, err)
+	}
+	return &estimates, nil
+}
+
+// IPC event sending methods
+
+func (c *CriterionDefinition) sendGroupDiscovered(groupName string, parentNames []string) {
+	event := map[string]interface{}{
+		"eventType": "testGroupDiscovered",
+		"payload": map[string]interface{}{
+			"groupName":   groupName,
+			"parentNames": parentNames,
+		},
+	}
+	c.sendIPCEvent(event)
+}
+
+func (c *CriterionDefinition) sendGroupStart(groupName string, parentNames []string) {
+	event := map[string]interface{}{
+		"eventType": "testGroupStart",
+		"payload": map[string]interface{}{
+			"groupName":   groupName,
+			"parentNames": parentNames,
+		},
+	}
+	c.sendIPCEvent(event)
+}
+
+func (c *CriterionDefinition) sendBenchmarkResult(bench *CriterionBenchmark) {
+	payload := map[string]interface{}{
+		"testName":    bench.Name,
+		"parentNames": bench.Parents,
+		"status":      "BENCH",
+	}
+
+	// Only include the statistics that are known
+	if bench.MeanNs > 0 {
+		payload["benchMeanNs"] = bench.MeanNs
+	}
+	if bench.MedianNs > 0 {
+		payload["benchMedianNs"] = bench.MedianNs
+	}
+	if bench.StdDevNs > 0 {
+		payload["benchStdDevNs"] = bench.StdDevNs
+	}
+	if bench.DeviationNs > 0 {
+		payload["benchDeviationNs"] = bench.DeviationNs
+	}
+	if bench.HasChange {
+		payload["benchChangePercent"] = bench.ChangePercent
+	}
+	if bench.Regressed {
+		payload["benchRegressed"] = true
+	}
+
+	event := map[string]interface{}{
+		"eventType": "testCase",
+		"payload":   payload,
+	}
+	c.sendIPCEvent(event)
+}
+
+func (c *CriterionDefinition) sendGroupResult(groupName string, parentNames []string, status string, duration float64) {
+	event := map[string]interface{}{
+		"eventType": "testGroupResult",
+		"payload": map[string]interface{}{
+			"groupName":   groupName,
+			"parentNames": parentNames,
+			"status":      status,
+			"duration":    duration,
+			"totals": map[string]interface{}{
+				"passed":  0,
+				"failed":  0,
+				"skipped": 0,
+			},
+		},
+	}
+	c.sendIPCEvent(event)
+}
+
+func (c *CriterionDefinition) sendIPCEvent(event map[string]interface{}) {
+	if c.ipcWriter == nil {
+		c.logger.Debug("IPC writer not initialized, skipping event: %v", event)
+		return
+	}
+
+	if err := c.ipcWriter.WriteEvent(event); err != nil {
+		c.logger.Debug("Failed to write IPC event: %v", err)
+	}
+}
+
+// SetEnvironment returns the environment cargo bench needs.
+// RUSTC_BOOTSTRAP lets libtest #[bench] functions in the same crate build on stable.
+func (c *CriterionDefinition) SetEnvironment() []string {
+	return []string{"RUSTC_BOOTSTRAP=1"}
+}
@@ -0,0 +1,247 @@
+package definitions
+
+import (
+	"math"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+// writeCriterionEstimates writes an estimates.json the way criterion stores it under target/criterion
+func writeCriterionEstimates(t *testing.T, targetDir, benchDir, kind, content string) {
+	t.Helper()
+	dir := filepath.Join(targetDir, "criterion", benchDir, kind)
+	if err := os.MkdirAll(dir, 0755); err != nil {
+		t.Fatalf("Failed to create %s: %v", dir, err)
+	}
+	if err := os.WriteFile(filepath.Join(dir, "estimates.json"), []byte(content), 0644); err != nil {
+		t.Fatalf("Failed to write estimates: %v", err)
+	}
+}
+
+func TestCriterionDefinition_Detect(t *testing.T) {
+	def := NewCriterionDefinition(createTestLogger(t))
+
+	oldDir, err := os.Getwd()
+	if err != nil {
+		t.Fatalf("Failed to get working directory: %v", err)
+	}
+	defer func() { _ = os.Chdir(oldDir) }()
+
+	criterionProject := t.TempDir()
+	manifest := "[package]\nname = \"benches\"\n\n[dev-dependencies]\ncriterion = \"0.5\"\n"
+	if err := os.WriteFile(filepath.Join(criterionProject, "Cargo.toml"), []byte(manifest), 0644); err != nil {
+		t.Fatalf("Failed to write Cargo.toml: %v", err)
+	}
+	plainProject := t.TempDir()
+	if err := os.WriteFile(filepath.Join(plainProject, "Cargo.toml"), []byte("[package]\nname = \"plain\"\n"), 0644); err != nil {
+		t.Fatalf("Failed to write Cargo.toml: %v", err)
+	}
+
+	tests := []struct {
+		name     string
+		dir      string
+		args     []string
+		expected bool
+	}{
+		{"cargo bench with criterion", criterionProject, []string{"cargo", "bench"}, true},
+		{"cargo bench with toolchain", criterionProject, []string{"cargo", "+nightly", "bench"}, true},
+		{"cargo bench single target", criterionProject, []string{"cargo", "bench", "--bench", "simple_bench"}, true},
+		{"cargo bench --lib runs libtest", criterionProject, []string{"cargo", "bench", "--lib"}, false},
+		{"cargo test with criterion", criterionProject, []string{"cargo", "test"}, false},
+		{"cargo bench without criterion", plainProject, []string{"cargo", "bench"}, false},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if err := os.Chdir(tt.dir); err != nil {
+				t.Fatalf("Failed to chdir: %v", err)
+			}
+			if result := def.Detect(tt.args); result != tt.expected {
+				t.Errorf("Detect(%v) = %v, expected %v", tt.args, result, tt.expected)
+			}
+
+			// cargo test runner must not claim criterion benchmarks
+			cargoDef := NewCargoTestDefinition(createTestLogger(t))
+			if tt.expected && cargoDef.Detect(tt.args) {
+				t.Errorf("CargoTestDefinition.Detect(%v) should leave criterion benches to the criterion runner", tt.args)
+			}
+		})
+	}
+}
+
+func TestCriterionDefinition_ModifyCommand(t *testing.T) {
+	def := NewCriterionDefinition(createTestLogger(t))
+
+	cmd := []string{"cargo", "bench", "--bench", "simple_bench", "--", "--sample-size", "10"}
+	result := def.ModifyCommand(cmd, "", "")
+	if strings.Join(result, " ") != strings.Join(cmd, " ") {
+		t.Errorf("Expected command unchanged, got %v", result)
+	}
+}
+
+func TestCriterionDefinition_ProcessOutput(t *testing.T) {
+	def := NewCriterionDefinition(createTestLogger(t))
+	def.targetDir = t.TempDir()
+
+	writeCriterionEstimates(t, def.targetDir, "fib 10", "new",
+		`{"mean":{"point_estimate":163.53},"median":{"point_estimate":163.1},"std_dev":{"point_estimate":2.25}}`)
+	writeCriterionEstimates(t, def.targetDir, "fib 10", "change",
+		`{"mean":{"point_estimate":0.1138,"confidence_interval":{"lower_bound":0.101,"upper_bound":0.126}}}`)
+	writeCriterionEstimates(t, def.targetDir, filepath.Join("parsing", "parse_long_document_name", "1000"), "new",
+		`{"mean":{"point_estimate":45210.5},"median":{"point_estimate":45001.0},"std_dev":{"point_estimate":310.7}}`)
+
+	output := `   Compiling rust-benchmarks v0.1.0
+     Running benches/simple_bench.rs (target/release/deps/simple_bench-1a2b3c4d5e6f7a8b)
+Benchmarking fib 10
+Benchmarking fib 10: Warming up for 3.0000 s
+Benchmarking fib 10: Collecting 100 samples in estimated 5.0008 s (30M iterations)
+Benchmarking fib 10: Analyzing
+fib 10                  time:   [163.27 ns 163.53 ns 163.84 ns]
+                        change: [+10.112% +11.385% +12.604%] (p = 0.00 < 0.05)
+                        Performance has regressed.
+Found 3 outliers among 100 measurements (3.00%)
+  3 (3.00%) high mild
+Benchmarking fact 10: Analyzing
+fact 10                 time:   [10.012 ns 10.031 ns 10.052 ns]
+                        change: [-0.5012% +0.1031% +0.7120%] (p = 0.74 > 0.05)
+                        No change in performance detected.
+Benchmarking parsing/parse_long_document_name/1000: Analyzing
+parsing/parse_long_document_name/1000
+                        time:   [45.102 µs 45.210 µs 45.330 µs]
+`
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	capture := NewTestIPCCapture(ipcPath)
+
+	benchmarks := make(map[string]map[string]interface{})
+	for _, e := range capture.GetEventsByType("testCase") {
+		payload := e["payload"].(map[string]interface{})
+		benchmarks[payload["testName"].(string)] = payload
+	}
+	if len(benchmarks) != 3 {
+		t.Fatalf("Expected 3 benchmarks, got %d: %v", len(benchmarks), benchmarks)
+	}
+
+	// Statistics come from estimates.json, the change from the console
+	fib := benchmarks["fib 10"]
+	if fib["status"] != "BENCH" {
+		t.Errorf("Expected fib 10 status BENCH, got %v", fib["status"])
+	}
+	if fib["benchMeanNs"] != 163.53 || fib["benchMedianNs"] != 163.1 || fib["benchStdDevNs"] != 2.25 {
+		t.Errorf("Expected fib 10 estimates from estimates.json, got %v", fib)
+	}
+	if fib["benchChangePercent"] != 11.385 || fib["benchRegressed"] != true {
+		t.Errorf("Expected fib 10 to regress by 11.385%%, got %v", fib)
+	}
+	if parents := fib["parentNames"].([]interface{}); len(parents) != 2 || parents[0] != "simple-bench" || parents[1] != "fib 10" {
+		t.Errorf("Expected fib 10 under simple-bench > fib 10, got %v", parents)
+	}
+
+	// Without estimates.json the console estimate is used as the mean
+	fact := benchmarks["fact 10"]
+	if fact["benchMeanNs"] != 10.031 || fact["benchRegressed"] != nil {
+		t.Errorf("Expected fact 10 mean 10.031 without regression, got %v", fact)
+	}
+
+	// A long ID printed on its own line is grouped by benchmark function
+	parse := benchmarks["1000"]
+	if parse == nil {
+		t.Fatalf("Expected benchmark 1000 from parsing/parse_long_document_name/1000")
+	}
+	if parse["benchMeanNs"] != 45210.5 {
+		t.Errorf("Expected mean from estimates.json, got %v", parse["benchMeanNs"])
+	}
+	parents := parse["parentNames"].([]interface{})
+	if len(parents) != 3 || parents[1] != "parsing" || parents[2] != "parse_long_document_name" {
+		t.Errorf("Expected simple-bench > parsing > parse_long_document_name, got %v", parents)
+	}
+
+	// Groups holding a regressed benchmark get the WARN status
+	statuses := make(map[string]interface{})
+	for _, e := range capture.GetEventsByType("testGroupResult") {
+		payload := e["payload"].(map[string]interface{})
+		statuses[payload["groupName"].(string)] = payload["status"]
+	}
+	expected := map[string]string{
+		"simple-bench":             "WARN",
+		"fib 10":                   "WARN",
+		"fact 10":                  "PASS",
+		"parsing":                  "PASS",
+		"parse_long_document_name": "PASS",
+	}
+	for group, status := range expected {
+		if statuses[group] != status {
+			t.Errorf("Expected group %s status %s, got %v", group, status, statuses[group])
+		}
+	}
+}
+
+func TestCriterionDefinition_RegressionFromChangeEstimates(t *testing.T) {
+	def := NewCriterionDefinition(createTestLogger(t))
+	def.targetDir = t.TempDir()
+
+	// --quiet output has no verdict; the saved change decides instead
+	writeCriterionEstimates(t, def.targetDir, "fact 10", "change",
+		`{"mean":{"point_estimate":0.052,"confidence_interval":{"lower_bound":0.031,"upper_bound":0.074}}}`)
+
+	output := "fact 10                 time:   [10.012 ns 10.031 ns 10.052 ns]\n"
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+
+	testCases := NewTestIPCCapture(ipcPath).GetEventsByType("testCase")
+	if len(testCases) != 1 {
+		t.Fatalf("Expected 1 testCase event, got %d", len(testCases))
+	}
+	payload := testCases[0]["payload"].(map[string]interface{})
+	if payload["benchRegressed"] != true {
+		t.Errorf("Expected regression from change/estimates.json, got %v", payload)
+	}
+	if parents := payload["parentNames"].([]interface{}); parents[0] != criterionDefaultTarget {
+		t.Errorf("Expected default target group %q, got %v", criterionDefaultTarget, parents)
+	}
+}
+
+func TestParseCriterionTime(t *testing.T) {
+	tests := []struct {
+		value    string
+		unit     string
+		expected float64
+	}{
+		{"163.53", "ns", 163.53},
+		{"45.210", "µs", 45210},
+		{"1.5", "ms", 1500000},
+		{"2", "s", 2e9},
+		{"500", "ps", 0.5},
+		{"1.0", "weeks", 0},
+	}
+
+	for _, tt := range tests {
+		if result := parseCriterionTime(tt.value, tt.unit); math.Abs(result-tt.expected) > 1e-6 {
+			t.Errorf("parseCriterionTime(%q, %q) = %v, expected %v", tt.value, tt.unit, result, tt.expected)
+		}
+	}
+}
+
+func TestCriterionDirectoryName(t *testing.T) {
+	tests := []struct {
+		id       string
+		expected string
+	}{
+		{"fib 10", "fib 10"},
+		{"group/function/100", filepath.Join("group", "function", "100")},
+		{"sort: <i32>", "sort_ _i32_"},
+		{strings.Repeat("a", 70), strings.Repeat("a", 64)},
+	}
+
+	for _, tt := range tests {
+		if result := criterionDirectoryName(tt.id); result != tt.expected {
+			t.Errorf("criterionDirectoryName(%q) = %q, expected %q", tt.id, result, tt.expected)
+		}
+	}
+}
@@ -0,0 +1,59 @@
+package definitions
+
+import (
+	"io"
+)
+
+// CriterionWrapper wraps CriterionDefinition to implement the Definition interface from runner package
+type CriterionWrapper struct {
+	*CriterionDefinition
+}
+
+// NewCriterionWrapper creates a new wrapper for criterion benchmarks
+func NewCriterionWrapper(impl *CriterionDefinition) *CriterionWrapper {
+	return &CriterionWrapper{CriterionDefinition: impl}
+}
+
+// Matches checks if this runner can handle the given command
+func (c *CriterionWrapper) Matches(command []string) bool {
+	return c.Detect(command)
+}
+
+// GetTestFiles returns list of test files (empty for dynamic discovery)
+func (c *CriterionWrapper) GetTestFiles(args []string) ([]string, error) {
+	return c.CriterionDefinition.GetTestFiles(args)
+}
+
+// BuildCommand returns the cargo bench command to run
+func (c *CriterionWrapper) BuildCommand(args []string, adapterPath string) []string {
+	// criterion uses native processing, no adapter needed
+	return c.ModifyCommand(args, "", "")
+}
+
+// GetAdapterFileName returns empty as criterion doesn't use an adapter
+func (c *CriterionWrapper) GetAdapterFileName() string {
+	return ""
+}
+
+// InterpretExitCode maps exit codes to success/failure
+func (c *CriterionWrapper) InterpretExitCode(code int) string {
+	if code == 0 {
+		return "success"
+	}
+	return "failure"
+}
+
+// IsNative returns true as criterion output is processed directly
+func (c *CriterionWrapper) IsNative() bool {
+	return true
+}
+
+// GetNativeDefinition returns the underlying criterion definition
+func (c *CriterionWrapper) GetNativeDefinition() interface{} {
+	return c.CriterionDefinition
+}
+
+// ProcessOutput processes the cargo bench output
+func (c *CriterionWrapper) ProcessOutput(stdout io.Reader, ipcPath string) error {
+	return c.CriterionDefinition.ProcessOutput(stdout, ipcPath)
+}
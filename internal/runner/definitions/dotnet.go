@@ -0,0 +1,662 @@
+package definitions
+
+import (
+	"encoding/xml"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"regexp"
+	"slices"
+	"sort"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/zk/3pio/internal/ipc"
+	"github.com/zk/3pio/internal/logger"
+)
+
+// DotnetResultsDir is the directory of the run directory dotnet test writes its TRX
+// files to, unless the command chose its own --results-directory
+const DotnetResultsDir = "dotnet-results"
+
+// DotnetTestDefinition runs dotnet test with its TRX logger. The TRX files the run
+// wrote, one per test project, are read once the output ends: a group per test class,
+// holding its tests with their outcomes, durations, error messages, stack traces and
+// output. When there is no TRX file, such as when the run was stopped first, the
+// results the console logger printed are reported instead. MSBuild errors before the
+// tests start are build errors.
+type DotnetTestDefinition struct {
+	logger    *logger.FileLogger
+	ipcWriter *IPCWriter
+
+	lineLimit     // Longest line of output parsed whole (--max-line-length)
+	workDirectory // Directory dotnet test runs in
+
+	runDir     string
+	resultsDir string               // Where dotnet test writes the TRX files
+	existing   map[string]time.Time // TRX files in resultsDir before the run, by path
+
+	buildErrorsSent map[string]bool // MSBuild repeats its errors in its summary
+	console         []dotnetResult  // Results the console logger printed
+	consoleFailure  *dotnetResult   // Failed result whose message and stack trace follow
+	consoleSection  string          // "message" or "stack" while they do
+}
+
+// dotnetResult is the result of one test, from a TRX file or the console logger
+type dotnetResult struct {
+	className  string
+	name       string
+	outcome    string // TRX outcome, e.g. "Passed", "Failed" or "NotExecuted"
+	durationMs float64
+	message    string // Error message, or why the test was skipped
+	stackTrace string
+	stdout     string
+	stderr     string
+}
+
+// NewDotnetTestDefinition creates a new dotnet test runner definition
+func NewDotnetTestDefinition(logger *logger.FileLogger) *DotnetTestDefinition {
+	return &DotnetTestDefinition{
+		logger:          logger,
+		buildErrorsSent: make(map[string]bool),
+	}
+}
+
+// Name returns the name of this test runner
+func (d *DotnetTestDefinition) Name() string {
+	return "dotnet"
+}
+
+// Detect checks if the command runs dotnet test
+func (d *DotnetTestDefinition) Detect(args []string) bool {
+	if len(args) < 2 {
+		return false
+	}
+	base := strings.TrimSuffix(filepath.Base(strings.ReplaceAll(args[0], "\\", "/")), ".exe")
+	return base == "dotnet" && args[1] == "test"
+}
+
+// SetRunDir sets the run directory the TRX files are written to
+func (d *DotnetTestDefinition) SetRunDir(runDir string) {
+	d.runDir = runDir
+}
+
+// ModifyCommand adds the TRX logger, writing to the run directory unless the command
+// chose a --results-directory, and the console logger at normal verbosity so the
+// console has every result too. Each test project writes a TRX file of its own there,
+// where a fixed LogFileName would have a solution's projects overwrite each other's.
+// The TRX files already in the directory are remembered, to read only this run's.
+func (d *DotnetTestDefinition) ModifyCommand(cmd []string, ipcPath, runID string) []string {
+	result := append([]string{}, cmd...)
+
+	d.resultsDir = dotnetOptionValue(cmd, "--results-directory")
+	if d.resultsDir == "" && d.runDir != "" {
+		d.resultsDir = filepath.Join(d.runDir, DotnetResultsDir)
+		result = append(result, "--results-directory", d.resultsDir)
+	}
+	if d.resultsDir != "" {
+		if abs, err := filepath.Abs(d.inWorkDir(d.resultsDir)); err == nil {
+			d.resultsDir = abs
+		}
+	}
+	if !hasDotnetLogger(cmd, "trx") {
+		result = append(result, "--logger", "trx")
+	}
+	if !hasDotnetLogger(cmd, "console") {
+		result = append(result, "--logger", "console;verbosity=normal")
+	}
+
+	d.existing = make(map[string]time.Time)
+	for _, path := range d.trxFiles() {
+		if info, err := os.Stat(path); err == nil {
+			d.existing[path] = info.ModTime()
+		}
+	}
+	return result
+}
+
+// dotnetOptionValue returns the value of an option given as "--name value",
+// "--name:value" or "--name=value", or "" without it
+func dotnetOptionValue(args []string, names ...string) string {
+	for i, arg := range args {
+		for _, name := range names {
+			if arg == name && i+1 < len(args) {
+				return args[i+1]
+			}
+			for _, sep := range []string{":", "="} {
+				if strings.HasPrefix(arg, name+sep) {
+					return strings.TrimPrefix(arg, name+sep)
+				}
+			}
+		}
+	}
+	return ""
+}
+
+// hasDotnetLogger reports whether the command already chose a logger, e.g. "trx" in
+// --logger "trx;LogFileName=results.trx"
+func hasDotnetLogger(args []string, logger string) bool {
+	for i, arg := range args {
+		value := ""
+		switch {
+		case (arg == "--logger" || arg == "-l") && i+1 < len(args):
+			value = args[i+1]
+		case strings.HasPrefix(arg, "--logger:") || strings.HasPrefix(arg, "--logger="):
+			value = arg[len("--logger:"):]
+		}
+		name, _, _ := strings.Cut(value, ";")
+		if strings.EqualFold(strings.Trim(name, `"`), logger) {
+			return true
+		}
+	}
+	return false
+}
+
+// ResultsSource says the results are read from the TRX files once the command exits
+func (d *DotnetTestDefinition) ResultsSource() string {
+	return "trx"
+}
+
+// GetTestFiles returns empty array for dynamic discovery
+func (d *DotnetTestDefinition) GetTestFiles(args []string) ([]string, error) {
+	return []string{}, nil
+}
+
+// RequiresAdapter returns false as the TRX files are read directly
+func (d *DotnetTestDefinition) RequiresAdapter() bool {
+	return false
+}
+
+// RerunCommand narrows the command down to the failed tests with a --filter on their
+// fully qualified names, within the command's own filter if it has one
+func (d *DotnetTestDefinition) RerunCommand(cmd []string, tests []ipc.FailedTest) ([]string, error) {
+	var names []string
+	for _, test := range tests {
+		if len(test.ParentNames) == 0 {
+			continue
+		}
+		method, _, _ := strings.Cut(test.Name, "(")
+		condition := "FullyQualifiedName=" + escapeDotnetFilter(test.ParentNames[0]+"."+strings.TrimSpace(method))
+		if !slices.Contains(names, condition) {
+			names = append(names, condition)
+		}
+	}
+	if len(names) == 0 {
+		return nil, fmt.Errorf("no failed test has a test class to filter on")
+	}
+	filter := strings.Join(names, "|")
+
+	result := make([]string, 0, len(cmd)+2)
+	for i := 0; i < len(cmd); i++ {
+		arg := cmd[i]
+		switch {
+		case arg == "--filter" && i+1 < len(cmd):
+			filter = "(" + cmd[i+1] + ")&(" + filter + ")"
+			i++
+		case strings.HasPrefix(arg, "--filter:") || strings.HasPrefix(arg, "--filter="):
+			filter = "(" + arg[len("--filter:"):] + ")&(" + filter + ")"
+		default:
+			result = append(result, arg)
+		}
+	}
+	return append(result, "--filter", filter), nil
+}
+
+// escapeDotnetFilter escapes the characters a dotnet test --filter value gives meaning to
+func escapeDotnetFilter(value string) string {
+	var sb strings.Builder
+	for _, r := range value {
+		if strings.ContainsRune(`\()&|=!~`, r) {
+			sb.WriteRune('\\')
+		}
+		sb.WriteRune(r)
+	}
+	return sb.String()
+}
+
+// ProcessOutput reads MSBuild errors and the console logger's results as the command
+// prints them, and reports the tests of the TRX files once the output ends
+func (d *DotnetTestDefinition) ProcessOutput(combinedOutput io.Reader, ipcPath string) error {
+	var err error
+	d.ipcWriter, err = NewIPCWriter(ipcPath)
+	if err != nil {
+		return fmt.Errorf("failed to create IPC writer: %w", err)
+	}
+	defer func() {
+		if err := d.ipcWriter.Close(); err != nil {
+			d.logger.Debug("Failed to close IPC writer: %v", err)
+		}
+	}()
+
+	lines := d.newLineReader(combinedOutput)
+	for {
+		line, _, ok := lines.Next()
+		if !ok {
+			break
+		}
+		d.processLine(strings.TrimRight(line, "\r"))
+	}
+	d.endConsoleFailure()
+	if err := lines.Err(); err != nil {
+		return fmt.Errorf("error reading dotnet test output: %w", err)
+	}
+
+	results := d.readTRXResults()
+	if len(results) == 0 {
+		results = d.console
+	}
+	d.sendResults(results)
+
+	runCompleteEvent := map[string]interface{}{
+		"eventType": "runComplete",
+		"payload":   map[string]interface{}{},
+	}
+	if err := d.ipcWriter.WriteEvent(runCompleteEvent); err != nil {
+		d.logger.Debug("Failed to send runComplete event: %v", err)
+	}
+	return nil
+}
+
+var (
+	// C:\src\Calc.cs(12,5): error CS1002: ; expected [C:\src\Calc.csproj]
+	msbuildLocatedError = regexp.MustCompile(`^\s*(.+?)\((\d+),(\d+)(?:,\d+,\d+)?\): error ([A-Za-z]+\d+): (.*?)(?: \[([^\]]+)\])?$`)
+	// MSBUILD : error MSB1009: Project file does not exist. or /src/Calc.csproj : error NU1101: ...
+	msbuildError = regexp.MustCompile(`^\s*(.+?) : error ([A-Za-z]+\d+): (.*?)(?: \[([^\]]+)\])?$`)
+	// Passed Calc.Tests.CalculatorTests.Adds [3 ms], as the console logger prints results
+	dotnetConsoleResult = regexp.MustCompile(`^\s+(Passed|Failed|Skipped) (\S.*?) \[([^\]]+)\]$`)
+)
+
+// processLine reads a line of dotnet test's output
+func (d *DotnetTestDefinition) processLine(line string) {
+	if m := dotnetConsoleResult.FindStringSubmatch(line); m != nil {
+		d.endConsoleFailure()
+		outcome := m[1]
+		if outcome == "Skipped" {
+			outcome = "NotExecuted"
+		}
+		className, name := splitDotnetTestName(m[2])
+		result := dotnetResult{className: className, name: name, outcome: outcome, durationMs: parseDotnetConsoleDuration(m[3])}
+		d.console = append(d.console, result)
+		if outcome == "Failed" {
+			d.consoleFailure = &d.console[len(d.console)-1]
+		}
+		return
+	}
+	if d.consoleFailure != nil {
+		switch strings.TrimSpace(line) {
+		case "Error Message:":
+			d.consoleSection = "message"
+			return
+		case "Stack Trace:":
+			d.consoleSection = "stack"
+			return
+		case "":
+			d.endConsoleFailure()
+			return
+		}
+		switch d.consoleSection {
+		case "message":
+			d.consoleFailure.message += strings.TrimSpace(line) + "\n"
+			return
+		case "stack":
+			d.consoleFailure.stackTrace += strings.TrimSpace(line) + "\n"
+			return
+		}
+		d.endConsoleFailure()
+	}
+	d.processBuildError(line)
+}
+
+// endConsoleFailure ends the message and stack trace of the failed console result
+func (d *DotnetTestDefinition) endConsoleFailure() {
+	if d.consoleFailure == nil {
+		return
+	}
+	d.consoleFailure.message = strings.TrimSpace(d.consoleFailure.message)
+	d.consoleFailure.stackTrace = strings.TrimSpace(d.consoleFailure.stackTrace)
+	d.consoleFailure = nil
+	d.consoleSection = ""
+}
+
+// processBuildError reports an MSBuild error line as a build error, once
+func (d *DotnetTestDefinition) processBuildError(line string) {
+	var payload map[string]interface{}
+	var project string
+	if m := msbuildLocatedError.FindStringSubmatch(line); m != nil {
+		lineNumber, _ := strconv.Atoi(m[2])
+		column, _ := strconv.Atoi(m[3])
+		payload = map[string]interface{}{
+			"code":    m[4],
+			"message": m[5],
+			"file":    m[1],
+			"line":    lineNumber,
+			"column":  column,
+		}
+		project = m[6]
+	} else if m := msbuildError.FindStringSubmatch(line); m != nil {
+		payload = map[string]interface{}{
+			"code":    m[2],
+			"message": m[3],
+		}
+		project = m[4]
+	} else {
+		return
+	}
+
+	rendered := strings.TrimSpace(line)
+	if d.buildErrorsSent[rendered] {
+		return
+	}
+	d.buildErrorsSent[rendered] = true
+	payload["rendered"] = rendered
+	if project != "" {
+		base := filepath.Base(strings.ReplaceAll(project, "\\", "/"))
+		payload["target"] = strings.TrimSuffix(base, filepath.Ext(base))
+	}
+
+	d.logger.Debug("Build error: %s", rendered)
+	d.sendIPCEvent(map[string]interface{}{
+		"eventType": "buildError",
+		"payload":   payload,
+	})
+}
+
+// splitDotnetTestName splits a fully qualified test name, such as
+// "Calc.Tests.CalculatorTests.Adds(a: 1, b: 2)", into its class and the test's name
+func splitDotnetTestName(fullName string) (className, name string) {
+	method := fullName
+	if i := strings.Index(method, "("); i >= 0 {
+		method = method[:i]
+	}
+	i := strings.LastIndex(method, ".")
+	if i < 0 {
+		return "", fullName
+	}
+	return fullName[:i], fullName[i+1:]
+}
+
+// parseDotnetConsoleDuration parses a duration as the console logger prints them,
+// e.g. "< 1 ms", "15 ms" or "1 m 2 s", as milliseconds
+func parseDotnetConsoleDuration(value string) float64 {
+	fields := strings.Fields(strings.TrimPrefix(strings.TrimSpace(value), "<"))
+	var ms float64
+	for i := 0; i+1 < len(fields); i += 2 {
+		n, err := strconv.ParseFloat(fields[i], 64)
+		if err != nil {
+			return 0
+		}
+		switch fields[i+1] {
+		case "ms":
+			ms += n
+		case "s":
+			ms += n * 1000
+		case "m":
+			ms += n * 60 * 1000
+		case "h":
+			ms += n * 60 * 60 * 1000
+		}
+	}
+	return ms
+}
+
+// trxFiles returns the TRX files in the results directory
+func (d *DotnetTestDefinition) trxFiles() []string {
+	if d.resultsDir == "" {
+		return nil
+	}
+	files, _ := filepath.Glob(filepath.Join(d.resultsDir, "*.trx"))
+	sort.Strings(files)
+	return files
+}
+
+// readTRXResults reads the results of the TRX files this run wrote
+func (d *DotnetTestDefinition) readTRXResults() []dotnetResult {
+	var results []dotnetResult
+	for _, path := range d.trxFiles() {
+		info, err := os.Stat(path)
+		if err != nil {
+			continue
+		}
+		if modTime, ok := d.existing[path]; ok && modTime.Equal(info.ModTime()) {
+			continue
+		}
+		fileResults, err := readTRX(path)
+		if err != nil {
+			d.logger.Error("Failed to read %s: %v", path, err)
+			continue
+		}
+		results = append(results, fileResults...)
+	}
+	return results
+}
+
+// The parts of a TRX file the results are read from. Each test's result refers to
+// its definition, which names its class.
+type trxTestRun struct {
+	Results         []trxUnitTestResult `xml:"Results>UnitTestResult"`
+	TestDefinitions []trxUnitTest       `xml:"TestDefinitions>UnitTest"`
+}
+
+type trxUnitTestResult struct {
+	TestID   string `xml:"testId,attr"`
+	TestName string `xml:"testName,attr"`
+	Outcome  string `xml:"outcome,attr"`
+	Duration string `xml:"duration,attr"`
+	StdOut   string `xml:"Output>StdOut"`
+	StdErr   string `xml:"Output>StdErr"`
+	Message  string `xml:"Output>ErrorInfo>Message"`
+	Stack    string `xml:"Output>ErrorInfo>StackTrace"`
+}
+
+type trxUnitTest struct {
+	ID         string `xml:"id,attr"`
+	Name       string `xml:"name,attr"`
+	TestMethod struct {
+		ClassName string `xml:"className,attr"`
+		Name      string `xml:"name,attr"`
+	} `xml:"TestMethod"`
+}
+
+// readTRX reads the test results of a TRX file
+func readTRX(path string) ([]dotnetResult, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return nil, err
+	}
+	var run trxTestRun
+	if err := xml.Unmarshal(data, &run); err != nil {
+		return nil, err
+	}
+
+	classes := make(map[string]string, len(run.TestDefinitions))
+	for _, def := range run.TestDefinitions {
+		classes[def.ID] = def.TestMethod.ClassName
+	}
+	results := make([]dotnetResult, 0, len(run.Results))
+	for _, r := range run.Results {
+		className := classes[r.TestID]
+		name := r.TestName
+		// xUnit names tests by class and method, MSTest and NUnit by method alone
+		if className != "" {
+			name = strings.TrimPrefix(name, className+".")
+		} else {
+			className, name = splitDotnetTestName(name)
+		}
+		// MSTest's assembly-qualified class names end with the assembly
+		className, _, _ = strings.Cut(className, ",")
+		results = append(results, dotnetResult{
+			className:  className,
+			name:       name,
+			outcome:    r.Outcome,
+			durationMs: parseTRXDuration(r.Duration),
+			message:    strings.TrimSpace(r.Message),
+			stackTrace: strings.TrimSpace(r.Stack),
+			stdout:     r.StdOut,
+			stderr:     r.StdErr,
+		})
+	}
+	return results, nil
+}
+
+// parseTRXDuration parses a TRX duration, e.g. "00:00:01.2345670", as milliseconds
+func parseTRXDuration(value string) float64 {
+	parts := strings.Split(value, ":")
+	if len(parts) != 3 {
+		return 0
+	}
+	hours, err1 := strconv.ParseFloat(parts[0], 64)
+	minutes, err2 := strconv.ParseFloat(parts[1], 64)
+	seconds, err3 := strconv.ParseFloat(parts[2], 64)
+	if err1 != nil || err2 != nil || err3 != nil {
+		return 0
+	}
+	return ((hours*60+minutes)*60 + seconds) * 1000
+}
+
+// dotnetStatus maps a TRX outcome to a test status, and says why a test that didn't
+// run was skipped. NotExecuted is a skipped test, Inconclusive one that decided it
+// couldn't say, and the rest of the outcomes that didn't run never got to.
+func dotnetStatus(outcome string) (status, skipReason string) {
+	switch outcome {
+	case "Passed", "PassedButRunAborted", "Warning", "Completed":
+		return "PASS", ""
+	case "NotExecuted", "Inconclusive":
+		return "SKIP", ""
+	case "Aborted", "NotRunnable", "Pending", "InProgress":
+		return "SKIP", ipc.SkipReasonNotRun
+	}
+	return "FAIL", ""
+}
+
+// dotnetStackLocation finds the source location of the first frame of a stack trace
+// that has one, e.g. "at Calc.Tests.CalculatorTests.Adds() in /src/CalculatorTests.cs:line 12"
+var dotnetStackLocation = regexp.MustCompile(` in (.+):line (\d+)`)
+
+// sendResults reports the results as a group per test class, in the order the
+// classes first appear
+func (d *DotnetTestDefinition) sendResults(results []dotnetResult) {
+	var classes []string
+	byClass := make(map[string][]dotnetResult)
+	for _, r := range results {
+		className := r.className
+		if className == "" {
+			className = "tests"
+		}
+		if _, ok := byClass[className]; !ok {
+			classes = append(classes, className)
+		}
+		byClass[className] = append(byClass[className], r)
+	}
+
+	for _, className := range classes {
+		parents := []string{className}
+		d.sendIPCEvent(map[string]interface{}{
+			"eventType": "testGroupDiscovered",
+			"payload":   map[string]interface{}{"groupName": className, "parentNames": []string{}},
+		})
+		d.sendIPCEvent(map[string]interface{}{
+			"eventType": "testGroupStart",
+			"payload":   map[string]interface{}{"groupName": className, "parentNames": []string{}},
+		})
+
+		var duration float64
+		passed, failed, skipped := 0, 0, 0
+		for _, r := range byClass[className] {
+			status, skipReason := dotnetStatus(r.outcome)
+			payload := map[string]interface{}{
+				"testName":    r.name,
+				"parentNames": parents,
+				"status":      status,
+				"duration":    r.durationMs,
+			}
+			if r.stdout != "" {
+				payload["stdout"] = r.stdout
+			}
+			if r.stderr != "" {
+				payload["stderr"] = r.stderr
+			}
+			switch status {
+			case "FAIL":
+				failed++
+				payload["error"] = d.testError(r)
+			case "SKIP":
+				skipped++
+				if skipReason != "" {
+					payload["skipReason"] = skipReason
+				}
+				if r.message != "" {
+					payload["skipMessage"] = r.message
+				}
+			default:
+				passed++
+			}
+			duration += r.durationMs
+			d.sendIPCEvent(map[string]interface{}{"eventType": "testCase", "payload": payload})
+		}
+
+		status := "PASS"
+		switch {
+		case failed > 0:
+			status = "FAIL"
+		case passed == 0 && skipped > 0:
+			status = "SKIP"
+		}
+		d.sendIPCEvent(map[string]interface{}{
+			"eventType": "testGroupResult",
+			"payload": map[string]interface{}{
+				"groupName":   className,
+				"parentNames": []string{},
+				"status":      status,
+				"duration":    duration,
+				"totals": map[string]interface{}{
+					"passed":  passed,
+					"failed":  failed,
+					"skipped": skipped,
+				},
+			},
+		})
+	}
+}
+
+// testError builds the error of a failed test, located at the first frame of its
+// stack trace with a source file
+func (d *DotnetTestDefinition) testError(r dotnetResult) map[string]interface{} {
+	message := r.message
+	if message == "" {
+		message = fmt.Sprintf("%s %s", r.name, strings.ToLower(r.outcome))
+	}
+	testError := map[string]interface{}{"message": message}
+	if r.stackTrace != "" {
+		testError["stack"] = r.stackTrace
+	}
+	if r.outcome == "Timeout" {
+		testError["errorType"] = ipc.ErrorTypeTimedOut
+		testError["failureKind"] = ipc.FailureKindTimedOut
+	}
+	if m := dotnetStackLocation.FindStringSubmatch(r.stackTrace); m != nil {
+		file := m[1]
+		if wd := d.workingDir(); wd != "" {
+			if rel, err := filepath.Rel(wd, file); err == nil && !strings.HasPrefix(rel, "..") {
+				file = filepath.ToSlash(rel)
+			}
+		}
+		line, _ := strconv.Atoi(m[2])
+		testError["file"] = file
+		testError["line"] = line
+		testError["location"] = fmt.Sprintf("%s:%d", file, line)
+	}
+	return testError
+}
+
+func (d *DotnetTestDefinition) sendIPCEvent(event map[string]interface{}) {
+	if d.ipcWriter == nil {
+		d.logger.Debug("IPC writer not initialized, skipping event: %v", event)
+		return
+	}
+
+	if err := d.ipcWriter.WriteEvent(event); err != nil {
+		d.logger.Debug("Failed to write IPC event: %v", err)
+	}
+}
@@ -0,0 +1,226 @@
+package definitions
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/internal/ipc"
+)
+
+// A TRX file as dotnet test writes it for an xUnit project: a passed, a failed and a
+// skipped test of one class, and a data-driven test of another
+const dotnetTRX = `<?xml version="1.0" encoding="utf-8"?>
+<TestRun id="0b4c7f3a" name="dev@build 2026-10-14 10:00:00" xmlns="http://microsoft.com/schemas/VisualStudio/TeamTest/2010">
+  <Results>
+    <UnitTestResult executionId="e1" testId="t1" testName="Calc.Tests.CalculatorTests.Adds" duration="00:00:00.0030000" outcome="Passed">
+      <Output>
+        <StdOut>adding 2 and 2</StdOut>
+      </Output>
+    </UnitTestResult>
+    <UnitTestResult executionId="e2" testId="t2" testName="Calc.Tests.CalculatorTests.Divides" duration="00:00:00.0125000" outcome="Failed">
+      <Output>
+        <ErrorInfo>
+          <Message>Assert.Equal() Failure
+Expected: 2
+Actual:   3</Message>
+          <StackTrace>   at Calc.Tests.CalculatorTests.Divides() in /src/Calc.Tests/CalculatorTests.cs:line 21</StackTrace>
+        </ErrorInfo>
+      </Output>
+    </UnitTestResult>
+    <UnitTestResult executionId="e3" testId="t3" testName="Calc.Tests.CalculatorTests.Overflows" duration="00:00:00" outcome="NotExecuted">
+      <Output>
+        <ErrorInfo>
+          <Message>needs a 128-bit machine</Message>
+        </ErrorInfo>
+      </Output>
+    </UnitTestResult>
+    <UnitTestResult executionId="e4" testId="t4" testName="Calc.Tests.ParserTests.Parses(input: &quot;1+1&quot;)" duration="00:00:01.5000000" outcome="Passed" />
+  </Results>
+  <TestDefinitions>
+    <UnitTest name="Calc.Tests.CalculatorTests.Adds" id="t1"><TestMethod className="Calc.Tests.CalculatorTests" name="Adds" /></UnitTest>
+    <UnitTest name="Calc.Tests.CalculatorTests.Divides" id="t2"><TestMethod className="Calc.Tests.CalculatorTests" name="Divides" /></UnitTest>
+    <UnitTest name="Calc.Tests.CalculatorTests.Overflows" id="t3"><TestMethod className="Calc.Tests.CalculatorTests" name="Overflows" /></UnitTest>
+    <UnitTest name="Calc.Tests.ParserTests.Parses(input: &quot;1+1&quot;)" id="t4"><TestMethod className="Calc.Tests.ParserTests" name="Parses" /></UnitTest>
+  </TestDefinitions>
+</TestRun>
+`
+
+// runDotnet runs dotnet test output through the definition, after writeTRX wrote the
+// run's TRX files to its results directory, and returns its events
+func runDotnet(t *testing.T, output string, writeTRX func(resultsDir string)) *TestIPCCapture {
+	t.Helper()
+	runDir := t.TempDir()
+	def := NewDotnetTestDefinition(createTestLogger(t))
+	def.SetRunDir(runDir)
+	def.ModifyCommand([]string{"dotnet", "test"}, "", "")
+	if writeTRX != nil {
+		resultsDir := filepath.Join(runDir, DotnetResultsDir)
+		if err := os.MkdirAll(resultsDir, 0755); err != nil {
+			t.Fatal(err)
+		}
+		writeTRX(resultsDir)
+	}
+	ipcPath := filepath.Join(runDir, "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	return NewTestIPCCapture(ipcPath)
+}
+
+func TestDotnetTestDefinition_Detect(t *testing.T) {
+	def := NewDotnetTestDefinition(createTestLogger(t))
+	for args, want := range map[string]bool{
+		"dotnet test":                       true,
+		"dotnet test Calc.sln --no-build":   true,
+		"/usr/share/dotnet/dotnet.exe test": true,
+		"dotnet build":                      false,
+		"dotnet watch test":                 false,
+		"npx jest":                          false,
+	} {
+		if got := def.Detect(strings.Fields(args)); got != want {
+			t.Errorf("Detect(%q) = %v, expected %v", args, got, want)
+		}
+	}
+}
+
+func TestDotnetTestDefinition_ModifyCommand(t *testing.T) {
+	def := NewDotnetTestDefinition(createTestLogger(t))
+	def.SetRunDir("/runs/1")
+
+	tests := []struct {
+		name     string
+		cmd      []string
+		expected string
+	}{
+		{"adds the loggers", []string{"dotnet", "test"}, "dotnet test --results-directory " + filepath.Join("/runs/1", DotnetResultsDir) + " --logger trx --logger console;verbosity=normal"},
+		{"keeps the command's loggers", []string{"dotnet", "test", "--logger", "trx;LogFileName=ci.trx", "--logger:console;verbosity=detailed", "--results-directory", "out"}, "dotnet test --logger trx;LogFileName=ci.trx --logger:console;verbosity=detailed --results-directory out"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := strings.Join(def.ModifyCommand(tt.cmd, "", ""), " "); got != tt.expected {
+				t.Errorf("Expected %q, got %q", tt.expected, got)
+			}
+		})
+	}
+}
+
+func TestDotnetTestDefinition_TRX(t *testing.T) {
+	capture := runDotnet(t, "  Passed Calc.Tests.CalculatorTests.Adds [3 ms]\n", func(resultsDir string) {
+		if err := os.WriteFile(filepath.Join(resultsDir, "dev_build_2026-10-14_10_00_00.trx"), []byte(dotnetTRX), 0644); err != nil {
+			t.Fatal(err)
+		}
+	})
+
+	tests := tapPayloads(capture, "testCase", "testName")
+	if len(tests) != 4 {
+		t.Fatalf("Expected the 4 tests of the TRX file, not the console's, got %d", len(tests))
+	}
+	if adds := tests["Adds"]; adds["status"] != "PASS" || adds["duration"] != 3.0 || adds["stdout"] != "adding 2 and 2" {
+		t.Errorf("Expected Adds to pass in 3ms with its output, got %v", adds)
+	}
+	divides := tests["Divides"]
+	testErr, _ := divides["error"].(map[string]interface{})
+	if divides["status"] != "FAIL" || !strings.HasPrefix(testErr["message"].(string), "Assert.Equal() Failure") ||
+		testErr["location"] != "/src/Calc.Tests/CalculatorTests.cs:21" {
+		t.Errorf("Expected Divides to fail at its stack trace's location, got %v", divides)
+	}
+	if overflows := tests["Overflows"]; overflows["status"] != "SKIP" || overflows["skipMessage"] != "needs a 128-bit machine" {
+		t.Errorf("Expected Overflows to be skipped with its reason, got %v", overflows)
+	}
+	if parses := tests[`Parses(input: "1+1")`]; parses["status"] != "PASS" || parses["parentNames"].([]interface{})[0] != "Calc.Tests.ParserTests" {
+		t.Errorf("Expected the data-driven test in its class's group, got %v", parses)
+	}
+
+	results := tapPayloads(capture, "testGroupResult", "groupName")
+	calculator := results["Calc.Tests.CalculatorTests"]
+	if calculator["status"] != "FAIL" || calculator["totals"].(map[string]interface{})["skipped"] != 1.0 {
+		t.Errorf("Expected the CalculatorTests group to fail with a skipped test, got %v", calculator)
+	}
+}
+
+func TestDotnetTestDefinition_ConsoleFallback(t *testing.T) {
+	output := `  Passed Calc.Tests.CalculatorTests.Adds [3 ms]
+  Failed Calc.Tests.CalculatorTests.Divides [1 s 250 ms]
+  Error Message:
+   Assert.Equal() Failure
+  Stack Trace:
+     at Calc.Tests.CalculatorTests.Divides() in /src/Calc.Tests/CalculatorTests.cs:line 21
+
+  Skipped Calc.Tests.CalculatorTests.Overflows [< 1 ms]
+
+Failed!  - Failed:     1, Passed:     1, Skipped:     1, Total:     3, Duration: 1 s
+`
+	capture := runDotnet(t, output, nil)
+
+	tests := tapPayloads(capture, "testCase", "testName")
+	if len(tests) != 3 {
+		t.Fatalf("Expected the 3 tests the console printed, got %d", len(tests))
+	}
+	divides := tests["Divides"]
+	testErr, _ := divides["error"].(map[string]interface{})
+	if divides["status"] != "FAIL" || divides["duration"] != 1250.0 || testErr["message"] != "Assert.Equal() Failure" ||
+		!strings.Contains(testErr["stack"].(string), "CalculatorTests.cs:line 21") {
+		t.Errorf("Expected Divides to fail with its message and stack trace, got %v", divides)
+	}
+	if tests["Overflows"]["status"] != "SKIP" || tests["Adds"]["status"] != "PASS" {
+		t.Errorf("Expected Adds to pass and Overflows to be skipped, got %v", tests)
+	}
+}
+
+func TestDotnetTestDefinition_BuildErrors(t *testing.T) {
+	output := `  Determining projects to restore...
+/src/Calc/Calculator.cs(12,5): error CS1002: ; expected [/src/Calc/Calc.csproj]
+/src/Calc/Calculator.cs(3,1): warning CS8019: Unnecessary using directive [/src/Calc/Calc.csproj]
+
+Build FAILED.
+
+/src/Calc/Calculator.cs(12,5): error CS1002: ; expected [/src/Calc/Calc.csproj]
+MSBUILD : error MSB1009: Project file does not exist.
+`
+	capture := runDotnet(t, output, nil)
+
+	errors := capture.GetEventsByType("buildError")
+	if len(errors) != 2 {
+		t.Fatalf("Expected the 2 errors once each, got %d: %v", len(errors), errors)
+	}
+	compile := errors[0]["payload"].(map[string]interface{})
+	if compile["code"] != "CS1002" || compile["message"] != "; expected" || compile["file"] != "/src/Calc/Calculator.cs" ||
+		compile["line"] != 12.0 || compile["column"] != 5.0 || compile["target"] != "Calc" {
+		t.Errorf("Expected the compile error with its location and project, got %v", compile)
+	}
+	if msbuild := errors[1]["payload"].(map[string]interface{}); msbuild["code"] != "MSB1009" || msbuild["file"] != nil {
+		t.Errorf("Expected the MSBuild error without a location, got %v", msbuild)
+	}
+}
+
+func TestDotnetTestDefinition_RerunCommand(t *testing.T) {
+	def := NewDotnetTestDefinition(createTestLogger(t))
+
+	failed := []ipc.FailedTest{
+		{Name: "Divides", ParentNames: []string{"Calc.Tests.CalculatorTests"}},
+		{Name: `Parses(input: "1+1")`, ParentNames: []string{"Calc.Tests.ParserTests"}},
+		{Name: `Parses(input: "2*2")`, ParentNames: []string{"Calc.Tests.ParserTests"}},
+	}
+
+	tests := []struct {
+		name     string
+		cmd      []string
+		expected string
+	}{
+		{"adds a filter", []string{"dotnet", "test"}, "dotnet test --filter FullyQualifiedName=Calc.Tests.CalculatorTests.Divides|FullyQualifiedName=Calc.Tests.ParserTests.Parses"},
+		{"narrows the command's filter", []string{"dotnet", "test", "--filter", "Category=Unit", "--no-build"}, "dotnet test --no-build --filter (Category=Unit)&(FullyQualifiedName=Calc.Tests.CalculatorTests.Divides|FullyQualifiedName=Calc.Tests.ParserTests.Parses)"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			result, err := def.RerunCommand(tt.cmd, failed)
+			if err != nil {
+				t.Fatalf("RerunCommand failed: %v", err)
+			}
+			if strings.Join(result, " ") != tt.expected {
+				t.Errorf("Expected %q, got %q", tt.expected, strings.Join(result, " "))
+			}
+		})
+	}
+}
@@ -0,0 +1,59 @@
+package definitions
+
+import (
+	"io"
+)
+
+// DotnetTestWrapper wraps DotnetTestDefinition to implement the Definition interface from runner package
+type DotnetTestWrapper struct {
+	*DotnetTestDefinition
+}
+
+// NewDotnetTestWrapper creates a new wrapper for the dotnet test runner
+func NewDotnetTestWrapper(impl *DotnetTestDefinition) *DotnetTestWrapper {
+	return &DotnetTestWrapper{DotnetTestDefinition: impl}
+}
+
+// Matches checks if this runner can handle the given command
+func (d *DotnetTestWrapper) Matches(command []string) bool {
+	return d.Detect(command)
+}
+
+// GetTestFiles returns list of test files (empty for dynamic discovery)
+func (d *DotnetTestWrapper) GetTestFiles(args []string) ([]string, error) {
+	return d.DotnetTestDefinition.GetTestFiles(args)
+}
+
+// BuildCommand builds the command with the TRX logger
+func (d *DotnetTestWrapper) BuildCommand(args []string, adapterPath string) []string {
+	// The TRX files are read directly, no adapter needed
+	return d.ModifyCommand(args, "", "")
+}
+
+// GetAdapterFileName returns empty as dotnet test doesn't use an adapter
+func (d *DotnetTestWrapper) GetAdapterFileName() string {
+	return ""
+}
+
+// InterpretExitCode maps exit codes to success/failure
+func (d *DotnetTestWrapper) InterpretExitCode(code int) string {
+	if code == 0 {
+		return "success"
+	}
+	return "failure"
+}
+
+// IsNative returns true as the TRX files are processed directly
+func (d *DotnetTestWrapper) IsNative() bool {
+	return true
+}
+
+// GetNativeDefinition returns the underlying dotnet test definition
+func (d *DotnetTestWrapper) GetNativeDefinition() interface{} {
+	return d.DotnetTestDefinition
+}
+
+// ProcessOutput processes the dotnet test output
+func (d *DotnetTestWrapper) ProcessOutput(stdout io.Reader, ipcPath string) error {
+	return d.DotnetTestDefinition.ProcessOutput(stdout, ipcPath)
+}
@@ -0,0 +1,250 @@
+package definitions
+
+import (
+	"fmt"
+	"io"
+	"path/filepath"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/zk/3pio/internal/ipc"
+	"github.com/zk/3pio/internal/logger"
+)
+
+// ExecTestName is the test an exec run reports its command's result as
+const ExecTestName = "exits 0"
+
+// execChunkSize is how much output is collected before it is sent to the group
+const execChunkSize = 16 * 1024
+
+// ExecDefinition runs any command, such as a smoke test script, whose output has no
+// structure 3pio can read. The run is one group named after the command, with the
+// command's output, holding one test that passes when the command exits 0. It is only
+// chosen with 3pio exec or --runner exec.
+type ExecDefinition struct {
+	logger    *logger.FileLogger
+	ipcWriter *IPCWriter
+
+	lineLimit // Longest line of output sent whole (--max-line-length)
+
+	groupName string
+
+	mu          sync.Mutex
+	exitCode    int
+	exited      bool // exitCode is set
+	running     bool
+	timedOut    bool
+	interrupted bool
+}
+
+// NewExecDefinition creates a new exec runner definition
+func NewExecDefinition(logger *logger.FileLogger) *ExecDefinition {
+	return &ExecDefinition{logger: logger}
+}
+
+// Name returns the name of this test runner
+func (e *ExecDefinition) Name() string {
+	return "exec"
+}
+
+// Detect never claims a command, as any command could be run this way
+func (e *ExecDefinition) Detect(args []string) bool {
+	return false
+}
+
+// ModifyCommand returns the command unchanged, recording the group it is reported as
+func (e *ExecDefinition) ModifyCommand(cmd []string, ipcPath, runID string) []string {
+	e.groupName = execGroupName(cmd)
+	return append([]string{}, cmd...)
+}
+
+// execGroupName names the group of a command after the script it runs, as the TAP
+// runner does, else after the whole command, e.g. "make smoke"
+func execGroupName(cmd []string) string {
+	if name := tapRootGroup(cmd); filepath.IsAbs(name) {
+		return name
+	}
+	if len(cmd) == 0 {
+		return "exec"
+	}
+	return strings.Join(cmd, " ")
+}
+
+// GetTestFiles returns empty array, as the command has no test files 3pio knows of
+func (e *ExecDefinition) GetTestFiles(args []string) ([]string, error) {
+	return []string{}, nil
+}
+
+// RequiresAdapter returns false as the command runs as given
+func (e *ExecDefinition) RequiresAdapter() bool {
+	return false
+}
+
+// RerunCommand re-runs the whole command, which is the only test there is
+func (e *ExecDefinition) RerunCommand(command []string, tests []ipc.FailedTest) ([]string, error) {
+	return append([]string{}, command...), nil
+}
+
+// SetExitCode records the command's exit code, which decides the result of the test
+// once the output ends
+func (e *ExecDefinition) SetExitCode(code int) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+	e.exitCode = code
+	e.exited = true
+}
+
+// RunningTests returns the command's test while the command runs
+func (e *ExecDefinition) RunningTests() []string {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+	if !e.running {
+		return nil
+	}
+	return []string{e.groupName + " > " + ExecTestName}
+}
+
+// MarkTimedOut makes the test report as TIMED_OUT when the output ends, after 3pio
+// stopped a command that had stopped printing
+func (e *ExecDefinition) MarkTimedOut() {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+	e.timedOut = true
+}
+
+// MarkInterrupted leaves the group unfinished when the output ends, so the report
+// marks it INTERRUPTED
+func (e *ExecDefinition) MarkInterrupted() {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+	e.interrupted = true
+}
+
+// ProcessOutput sends the command's output to its group as it arrives, and the
+// command's result once the output ends
+func (e *ExecDefinition) ProcessOutput(combinedOutput io.Reader, ipcPath string) error {
+	var err error
+	e.ipcWriter, err = NewIPCWriter(ipcPath)
+	if err != nil {
+		return fmt.Errorf("failed to create IPC writer: %w", err)
+	}
+	defer func() {
+		if err := e.ipcWriter.Close(); err != nil {
+			e.logger.Debug("Failed to close IPC writer: %v", err)
+		}
+	}()
+
+	if e.groupName == "" {
+		e.groupName = "exec"
+	}
+	start := time.Now()
+	e.sendIPCEvent(map[string]interface{}{
+		"eventType": "testGroupDiscovered",
+		"payload":   map[string]interface{}{"groupName": e.groupName},
+	})
+	e.sendIPCEvent(map[string]interface{}{
+		"eventType": "testGroupStart",
+		"payload":   map[string]interface{}{"groupName": e.groupName},
+	})
+	e.mu.Lock()
+	e.running = true
+	e.mu.Unlock()
+
+	var chunk strings.Builder
+	flush := func() {
+		if chunk.Len() == 0 {
+			return
+		}
+		e.sendIPCEvent(map[string]interface{}{
+			"eventType": "groupStdout",
+			"payload":   map[string]interface{}{"groupName": e.groupName, "chunk": chunk.String()},
+		})
+		chunk.Reset()
+	}
+	lines := e.newLineReader(combinedOutput)
+	for {
+		line, _, ok := lines.Next()
+		if !ok {
+			break
+		}
+		chunk.WriteString(line)
+		chunk.WriteString("\n")
+		if chunk.Len() >= execChunkSize {
+			flush()
+		}
+	}
+	flush()
+	if err := lines.Err(); err != nil {
+		return fmt.Errorf("error reading command output: %w", err)
+	}
+
+	e.finish(float64(time.Since(start)) / float64(time.Millisecond))
+
+	runCompleteEvent := map[string]interface{}{
+		"eventType": "runComplete",
+		"payload":   map[string]interface{}{},
+	}
+	if err := e.ipcWriter.WriteEvent(runCompleteEvent); err != nil {
+		e.logger.Debug("Failed to send runComplete event: %v", err)
+	}
+	return nil
+}
+
+// finish reports the command's test and group from how the command ended
+func (e *ExecDefinition) finish(duration float64) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+	e.running = false
+	if e.interrupted {
+		return
+	}
+
+	payload := map[string]interface{}{
+		"testName":    ExecTestName,
+		"parentNames": []string{e.groupName},
+		"status":      "PASS",
+		"duration":    duration,
+	}
+	switch {
+	case e.timedOut:
+		payload["status"] = "FAIL"
+		payload["error"] = map[string]interface{}{
+			"message":     "was still running when the inactivity timeout stopped it",
+			"errorType":   ipc.ErrorTypeTimedOut,
+			"failureKind": ipc.FailureKindTimedOut,
+		}
+	case e.exited && e.exitCode < 0:
+		payload["status"] = "FAIL"
+		payload["error"] = map[string]interface{}{"message": "was killed by a signal"}
+	case e.exited && e.exitCode != 0:
+		payload["status"] = "FAIL"
+		payload["error"] = map[string]interface{}{"message": fmt.Sprintf("exited with code %d", e.exitCode)}
+	}
+	e.sendIPCEvent(map[string]interface{}{"eventType": "testCase", "payload": payload})
+
+	passed, failed := 1, 0
+	if payload["status"] == "FAIL" {
+		passed, failed = 0, 1
+	}
+	e.sendIPCEvent(map[string]interface{}{
+		"eventType": "testGroupResult",
+		"payload": map[string]interface{}{
+			"groupName": e.groupName,
+			"status":    payload["status"],
+			"duration":  duration,
+			"totals":    map[string]interface{}{"passed": passed, "failed": failed, "skipped": 0},
+		},
+	})
+}
+
+func (e *ExecDefinition) sendIPCEvent(event map[string]interface{}) {
+	if e.ipcWriter == nil {
+		e.logger.Debug("IPC writer not initialized, skipping event: %v", event)
+		return
+	}
+
+	if err := e.ipcWriter.WriteEvent(event); err != nil {
+		e.logger.Debug("Failed to write IPC event: %v", err)
+	}
+}
@@ -0,0 +1,62 @@
+package definitions
+
+import (
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+// runExec runs the output of make smoke through the exec runner after the command
+// exited with exitCode, and returns its events
+func runExec(t *testing.T, output string, exitCode int, interrupted bool) *TestIPCCapture {
+	t.Helper()
+	def := NewExecDefinition(createTestLogger(t))
+	def.ModifyCommand([]string{"make", "smoke"}, "", "")
+	def.SetExitCode(exitCode)
+	if interrupted {
+		def.MarkInterrupted()
+	}
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	return NewTestIPCCapture(ipcPath)
+}
+
+func TestExecDefinition_ProcessOutput(t *testing.T) {
+	capture := runExec(t, "checking the config\nconnection refused\n", 3, false)
+
+	chunks := capture.GetEventsByType("groupStdout")
+	if len(chunks) != 1 {
+		t.Fatalf("Expected the output in one chunk, got %d", len(chunks))
+	}
+	chunk := chunks[0]["payload"].(map[string]interface{})
+	if chunk["groupName"] != "make smoke" || chunk["chunk"] != "checking the config\nconnection refused\n" {
+		t.Errorf("Expected the output in the make smoke group, got %v", chunk)
+	}
+
+	tests := capture.GetEventsByType("testCase")
+	if len(tests) != 1 {
+		t.Fatalf("Expected one test, got %d", len(tests))
+	}
+	test := tests[0]["payload"].(map[string]interface{})
+	errPayload, _ := test["error"].(map[string]interface{})
+	if test["testName"] != ExecTestName || test["status"] != "FAIL" || errPayload["message"] != "exited with code 3" {
+		t.Errorf("Expected the test to fail with the exit code, got %v", test)
+	}
+	results := capture.GetEventsByType("testGroupResult")
+	if len(results) != 1 || results[0]["payload"].(map[string]interface{})["status"] != "FAIL" {
+		t.Errorf("Expected the group to fail, got %v", results)
+	}
+
+	capture = runExec(t, "ok\n", 0, false)
+	if tests := capture.GetEventsByType("testCase"); len(tests) != 1 || tests[0]["payload"].(map[string]interface{})["status"] != "PASS" {
+		t.Errorf("Expected the test to pass when the command exits 0, got %v", tests)
+	}
+
+	// An interrupted command has no result, so its group is reported INTERRUPTED
+	capture = runExec(t, "checking the config\n", -1, true)
+	if tests := capture.GetEventsByType("testCase"); len(tests) != 0 {
+		t.Errorf("Expected no result for an interrupted command, got %v", tests)
+	}
+}
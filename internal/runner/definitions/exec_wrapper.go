@@ -0,0 +1,59 @@
+package definitions
+
+import (
+	"io"
+)
+
+// ExecWrapper wraps ExecDefinition to implement the Definition interface from runner package
+type ExecWrapper struct {
+	*ExecDefinition
+}
+
+// NewExecWrapper creates a new wrapper for the exec runner
+func NewExecWrapper(impl *ExecDefinition) *ExecWrapper {
+	return &ExecWrapper{ExecDefinition: impl}
+}
+
+// Matches never claims a command, as the exec runner is only chosen with 3pio exec or --runner exec
+func (e *ExecWrapper) Matches(command []string) bool {
+	return e.Detect(command)
+}
+
+// GetTestFiles returns list of test files (empty for dynamic discovery)
+func (e *ExecWrapper) GetTestFiles(args []string) ([]string, error) {
+	return e.ExecDefinition.GetTestFiles(args)
+}
+
+// BuildCommand returns the command unchanged
+func (e *ExecWrapper) BuildCommand(args []string, adapterPath string) []string {
+	// The command runs as given, no adapter needed
+	return e.ModifyCommand(args, "", "")
+}
+
+// GetAdapterFileName returns empty as the exec runner doesn't use an adapter
+func (e *ExecWrapper) GetAdapterFileName() string {
+	return ""
+}
+
+// InterpretExitCode maps exit codes to success/failure
+func (e *ExecWrapper) InterpretExitCode(code int) string {
+	if code == 0 {
+		return "success"
+	}
+	return "failure"
+}
+
+// IsNative returns true as the output is processed directly
+func (e *ExecWrapper) IsNative() bool {
+	return true
+}
+
+// GetNativeDefinition returns the underlying exec definition
+func (e *ExecWrapper) GetNativeDefinition() interface{} {
+	return e.ExecDefinition
+}
+
+// ProcessOutput processes the command output
+func (e *ExecWrapper) ProcessOutput(stdout io.Reader, ipcPath string) error {
+	return e.ExecDefinition.ProcessOutput(stdout, ipcPath)
+}
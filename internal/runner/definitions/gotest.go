@@ -1,15 +1,18 @@
 package definitions
 
 import (
-	"bufio"
 	"encoding/json"
 	"fmt"
 	"io"
 	"os"
+	"regexp"
+	"sort"
+	"strconv"
 	"strings"
 	"sync"
 	"time"
 
+	"github.com/zk/3pio/internal/ipc"
 	"github.com/zk/3pio/internal/logger"
 )
 
@@ -21,6 +24,8 @@ type GoTestDefinition struct {
 	mu         sync.RWMutex
 	ipcWriter  *IPCWriter
 
+	lineLimit // Longest line of output parsed whole (--max-line-length)
+
 	// Package-level tracking
 	packageTestFiles  map[string][]string          // Map of package to its test files
 	packageStarted    map[string]bool              // Track if we've sent package group start
@@ -31,6 +36,11 @@ type GoTestDefinition struct {
 	packageGroups     map[string]*PackageGroupInfo // Track package-level group info
 	packageResultSent map[string]bool              // Track if result has been sent for package
 	packageErrors     map[string][]string          // Buffer package-level error output
+	buildOutput       map[string][]string          // Compiler output per package, from build-output events
+	buildFailed       map[string]bool              // Packages reported as "[build failed]"
+	buildErrorsSent   map[string]bool              // Packages whose compiler errors were sent
+	packageOutput     map[string][]string          // Package-level output lines, for data races outside a test
+	timeoutOutput     map[string][]string          // Output from "panic: test timed out" on, per package
 
 	// Group tracking for universal abstractions
 	discoveredGroups map[string]bool           // Track discovered groups to avoid duplicates
@@ -47,6 +57,11 @@ type TestState struct {
 	StartTime time.Time
 	Output    []string
 	IsPaused  bool
+
+	// Set for tests that hadn't finished when their test binary hit -timeout
+	TimeoutPanic  string // The "panic: test timed out after 1s" line, if the test was in flight
+	TimeoutStacks string // Stacks of the goroutines running the test, if in flight
+	NotRun        bool   // The test wasn't in flight, e.g. paused until its parent's end
 }
 
 // TestInfo tracks individual test information
@@ -64,6 +79,10 @@ type GoTestEvent struct {
 	Test    string    `json:"Test,omitempty"`
 	Output  string    `json:"Output,omitempty"`
 	Elapsed float64   `json:"Elapsed,omitempty"`
+
+	// Go 1.24 and later report builds in the stream too
+	ImportPath  string `json:"ImportPath,omitempty"`  // Package of a build-output or build-fail event
+	FailedBuild string `json:"FailedBuild,omitempty"` // Package that didn't build, on the result of a package that needed it
 }
 
 // PackageGroupInfo tracks information for a package group
@@ -106,6 +125,11 @@ func NewGoTestDefinition(logger *logger.FileLogger) *GoTestDefinition {
 		packageGroups:     make(map[string]*PackageGroupInfo),
 		packageResultSent: make(map[string]bool),
 		packageErrors:     make(map[string][]string),
+		buildOutput:       make(map[string][]string),
+		buildFailed:       make(map[string]bool),
+		buildErrorsSent:   make(map[string]bool),
+		packageOutput:     make(map[string][]string),
+		timeoutOutput:     make(map[string][]string),
 		discoveredGroups:  make(map[string]bool),
 		groupStarts:       make(map[string]bool),
 		subgroupStats:     make(map[string]*SubgroupStats),
@@ -161,6 +185,44 @@ func (g *GoTestDefinition) ModifyCommand(cmd []string, ipcPath, runID string) []
 	return result
 }
 
+// RerunCommand narrows a go test command down to the given failed tests with a -run
+// pattern. A failed subtest selects its top-level test, so its other subtests run too.
+func (g *GoTestDefinition) RerunCommand(cmd []string, tests []ipc.FailedTest) ([]string, error) {
+	var names []string
+	seen := make(map[string]bool)
+	for _, test := range tests {
+		// Parents are the package followed by the enclosing tests
+		name := test.Name
+		if len(test.ParentNames) > 1 {
+			name = test.ParentNames[1]
+		}
+		if !seen[name] {
+			seen[name] = true
+			names = append(names, regexp.QuoteMeta(name))
+		}
+	}
+	if len(names) == 0 {
+		return nil, fmt.Errorf("no failed tests to select")
+	}
+	pattern := "^(" + strings.Join(names, "|") + ")$"
+
+	// Flags after -args go to the test binary, so -run must come before it. A later
+	// -run wins, overriding any the user passed.
+	result := make([]string, 0, len(cmd)+2)
+	inserted := false
+	for _, arg := range cmd {
+		if arg == "-args" && !inserted {
+			result = append(result, "-run", pattern)
+			inserted = true
+		}
+		result = append(result, arg)
+	}
+	if !inserted {
+		result = append(result, "-run", pattern)
+	}
+	return result, nil
+}
+
 // GetTestFiles extracts test files from command arguments or uses go list
 func (g *GoTestDefinition) GetTestFiles(args []string) ([]string, error) {
 	// Check if specific test files are provided
@@ -212,15 +274,19 @@ func (g *GoTestDefinition) ProcessOutput(stdout io.Reader, ipcPath string) error
 		}
 	}()
 
-	scanner := bufio.NewScanner(stdout)
-	// Configure larger buffer for long JSON lines (especially with embedded output)
-	// Default is 64KB which can be exceeded by test output
-	const maxScanTokenSize = 10 * 1024 * 1024 // 10MB max line size
-	buf := make([]byte, 0, 1024*1024)         // 1MB initial buffer
-	scanner.Buffer(buf, maxScanTokenSize)
-
-	for scanner.Scan() {
-		line := scanner.Bytes()
+	// Lines longer than the maximum, such as an output event holding a huge blob,
+	// are cut and fail to parse; output.log still has them
+	lines := g.newLineReader(stdout)
+	for {
+		text, cut, ok := lines.Next()
+		if !ok {
+			break
+		}
+		line := []byte(text)
+		if cut {
+			g.logger.Debug("Skipping go test event longer than %d bytes", len(line)-len(LineTruncatedMarker))
+			continue
+		}
 
 		// Try to parse as JSON
 		var event GoTestEvent
@@ -237,8 +303,8 @@ func (g *GoTestDefinition) ProcessOutput(stdout io.Reader, ipcPath string) error
 		}
 	}
 
-	// Check for scanner error but don't fail immediately - we need to finalize groups
-	scanErr := scanner.Err()
+	// Check for read error but don't fail immediately - we need to finalize groups
+	scanErr := lines.Err()
 	if scanErr != nil {
 		g.logger.Debug("Scanner encountered error (will finalize groups anyway): %v", scanErr)
 	}
@@ -295,6 +361,7 @@ func (g *GoTestDefinition) processEvent(event *GoTestEvent) error {
 		if event.Test != "" {
 			g.handleTestResult(event)
 		} else {
+			g.reportUnfinishedTests(event)
 			g.handlePackageResult(event)
 		}
 
@@ -302,9 +369,17 @@ func (g *GoTestDefinition) processEvent(event *GoTestEvent) error {
 		// Test output
 		g.handleOutput(event)
 
+	case "build-output":
+		// Compiler output of a package being built
+		g.handleBuildOutput(event)
+
+	case "build-fail":
+		// The package results that follow report the failure
+		g.logger.Debug("Build failed: %s", event.ImportPath)
+
 	case "bench":
-		// Benchmark result (not supported yet)
-		g.logger.Debug("Benchmark event (not supported): %+v", event)
+		// Benchmark log output (--- BENCH:); the results arrive as output lines
+		g.logger.Debug("Benchmark event: %+v", event)
 	}
 
 	return nil
@@ -405,6 +480,14 @@ func (g *GoTestDefinition) handleTestResult(event *GoTestEvent) {
 	defer g.mu.Unlock()
 
 	key := fmt.Sprintf("%s/%s", event.Package, event.Test)
+
+	// Benchmarks are reported from their result lines, so their passing doesn't count
+	// as a test. A benchmark that fails (b.Fatal) still shows as a failed test.
+	if strings.HasPrefix(event.Test, "Benchmark") && event.Action != "fail" {
+		delete(g.testStates, key)
+		return
+	}
+
 	state, ok := g.testStates[key]
 	if !ok {
 		// Create state if it doesn't exist
@@ -425,7 +508,7 @@ func (g *GoTestDefinition) handleTestResult(event *GoTestEvent) {
 	status := strings.ToUpper(event.Action)
 
 	// Parse the test hierarchy (handle subtests with "/" separator)
-	suiteChain, finalTestName := g.parseTestHierarchy(event.Test)
+	suiteChain, finalTestName := g.parseTestHierarchy(event.Package, event.Test)
 
 	// Ensure all parent groups are discovered and started
 	g.ensureGroupsDiscovered(event.Package, suiteChain)
@@ -440,8 +523,12 @@ func (g *GoTestDefinition) handleTestResult(event *GoTestEvent) {
 	parentNames := g.buildHierarchyFromPackage(event.Package, suiteChain)
 
 	// Send test case event with group hierarchy
-	outputStr := strings.Join(state.Output, "\n")
-	g.sendTestCaseWithGroups(finalTestName, parentNames, status, event.Elapsed, outputStr)
+	if state.TimeoutPanic != "" || state.NotRun {
+		g.sendUnfinishedTest(finalTestName, parentNames, event.Elapsed, state)
+	} else {
+		outputStr := strings.Join(state.Output, "\n")
+		g.sendTestCaseWithGroups(finalTestName, parentNames, status, event.Elapsed, outputStr, goDataRaces(state.Output))
+	}
 
 	// Track subgroup statistics for parent groups
 	if len(suiteChain) > 0 {
@@ -631,28 +718,37 @@ func (g *GoTestDefinition) handlePackageResult(event *GoTestEvent) {
 			}
 		}
 
-		// Check if this is a package with no test files
+		// A package with no test files is skipped, with that as the reason
+		skipReason := ""
 		if pkgGroup, ok := g.packageGroups[event.Package]; ok {
 			if pkgGroup.NoTestFiles && status == "SKIP" {
-				// Mark this specially so orchestrator can display ???
-				status = "NOTESTS"
+				skipReason = ipc.SkipReasonNoTestFiles
 			}
 		}
 
-		// Detect setup failures and send testGroupError event
+		// Detect build and setup failures and send testGroupError event
 		if event.Action == "fail" && totals["total"].(int) == 0 {
-			// This is a setup failure - construct error message
-			errorMessage := g.constructErrorMessage(event.Package)
+			if event.FailedBuild != "" || g.buildFailed[event.Package] {
+				g.sendBuildFailure(event)
+			} else {
+				// This is a setup failure - construct error message
+				errorMessage := g.constructErrorMessage(event.Package)
 
-			// Send testGroupError event
-			g.sendGroupError(event.Package, []string{}, "SETUP_FAILURE", event.Elapsed, errorMessage)
+				// Send testGroupError event
+				g.sendGroupError(event.Package, []string{}, "SETUP_FAILURE", event.Elapsed, errorMessage)
+			}
 
 			// Mark setupFailed in testGroupResult totals
 			totals["setupFailed"] = true
 		}
 
+		// A race outside any test, e.g. during teardown, is the package's
+		if race := goDataRaces(g.packageOutput[event.Package]); race != "" {
+			g.sendGroupError(event.Package, []string{}, "DATA_RACE", event.Elapsed, race)
+		}
+
 		// Send GroupResult for the package
-		g.sendGroupResult(event.Package, []string{}, status, event.Elapsed, totals)
+		g.sendGroupResultWithReason(event.Package, []string{}, status, skipReason, event.Elapsed, totals)
 
 		// Clear the started flag since we've sent the result
 		delete(g.packageStarted, event.Package)
@@ -670,6 +766,17 @@ func (g *GoTestDefinition) handleOutput(event *GoTestEvent) {
 	g.mu.Lock()
 	defer g.mu.Unlock()
 
+	// After a -timeout panic the rest of the output is the goroutine dump
+	if _, timedOut := g.timeoutOutput[event.Package]; timedOut || strings.HasPrefix(event.Output, goTestTimeoutPanic) {
+		g.timeoutOutput[event.Package] = append(g.timeoutOutput[event.Package], event.Output)
+	}
+
+	// Benchmark results are attributed to the benchmark by older Go versions only
+	if result, ok := parseGoBenchLine(event.Output); ok {
+		g.sendBenchmark(event.Package, result)
+		return
+	}
+
 	// If output is for a specific test, buffer it
 	if event.Test != "" {
 		key := fmt.Sprintf("%s/%s", event.Package, event.Test)
@@ -678,6 +785,7 @@ func (g *GoTestDefinition) handleOutput(event *GoTestEvent) {
 		}
 	} else {
 		// Package-level output processing
+		g.packageOutput[event.Package] = append(g.packageOutput[event.Package], event.Output)
 
 		// Filter and capture relevant error lines
 		output := strings.TrimSpace(event.Output)
@@ -689,6 +797,11 @@ func (g *GoTestDefinition) handleOutput(event *GoTestEvent) {
 			g.packageErrors[event.Package] = append(g.packageErrors[event.Package], output)
 		}
 
+		// Older Go versions write the compiler output to stderr, only this line to the stream
+		if strings.Contains(event.Output, "[build failed]") {
+			g.buildFailed[event.Package] = true
+		}
+
 		// Check for "no test files" indicator
 		if strings.Contains(event.Output, "[no test files]") {
 			// Ensure package group exists
@@ -736,13 +849,30 @@ func (g *GoTestDefinition) getGroupId(hierarchy []string) string {
 	return strings.Join(hierarchy, ":")
 }
 
-func (g *GoTestDefinition) parseTestHierarchy(testName string) (suiteChain []string, finalTestName string) {
-	if strings.Contains(testName, "/") {
-		parts := strings.Split(testName, "/")
+// parseTestHierarchy splits a test name into its parent tests and its own name. Go
+// joins subtest names with "/" but keeps slashes within a name, so the name is only
+// split after prefixes that are running as tests of their own; parents outlive their
+// subtests. Without any, as when the run events are missing, it is split at every
+// slash. Caller must hold g.mu.
+func (g *GoTestDefinition) parseTestHierarchy(packageName, testName string) (suiteChain []string, finalTestName string) {
+	if !strings.Contains(testName, "/") {
+		return []string{}, testName
+	}
+	parts := strings.Split(testName, "/")
+	suiteChain = []string{}
+	start := 0
+	for i := 1; i < len(parts); i++ {
+		if _, running := g.testStates[packageName+"/"+strings.Join(parts[:i], "/")]; running {
+			suiteChain = append(suiteChain, strings.Join(parts[start:i], "/"))
+			start = i
+		}
+	}
+	if start == 0 {
 		return parts[:len(parts)-1], parts[len(parts)-1]
 	}
-	return []string{}, testName
+	return suiteChain, strings.Join(parts[start:], "/")
 }
+
 func (g *GoTestDefinition) buildHierarchyFromPackage(packageName string, suiteChain []string) []string {
 	hierarchy := []string{packageName}
 	hierarchy = append(hierarchy, suiteChain...)
@@ -863,15 +993,25 @@ func (g *GoTestDefinition) sendGroupStart(groupName string, parentNames []string
 }
 
 func (g *GoTestDefinition) sendGroupResult(groupName string, parentNames []string, status string, duration float64, totals map[string]interface{}) {
+	g.sendGroupResultWithReason(groupName, parentNames, status, "", duration, totals)
+}
+
+// sendGroupResultWithReason sends a testGroupResult event, with the reason a SKIP
+// group didn't run
+func (g *GoTestDefinition) sendGroupResultWithReason(groupName string, parentNames []string, status string, skipReason string, duration float64, totals map[string]interface{}) {
+	payload := map[string]interface{}{
+		"groupName":   groupName,
+		"parentNames": parentNames,
+		"status":      status,
+		"duration":    duration * 1000, // Convert seconds to milliseconds
+		"totals":      totals,
+	}
+	if skipReason != "" {
+		payload["skipReason"] = skipReason
+	}
 	event := map[string]interface{}{
 		"eventType": "testGroupResult",
-		"payload": map[string]interface{}{
-			"groupName":   groupName,
-			"parentNames": parentNames,
-			"status":      status,
-			"duration":    duration * 1000, // Convert seconds to milliseconds
-			"totals":      totals,
-		},
+		"payload":   payload,
 	}
 	if err := g.ipcWriter.WriteEvent(event); err != nil {
 		g.logger.Error("Failed to send testGroupResult: %v", err)
@@ -879,7 +1019,7 @@ func (g *GoTestDefinition) sendGroupResult(groupName string, parentNames []strin
 }
 
 // sendTestCaseWithGroups sends a test case event with group hierarchy
-func (g *GoTestDefinition) sendTestCaseWithGroups(testName string, parentNames []string, status string, duration float64, output string) {
+func (g *GoTestDefinition) sendTestCaseWithGroups(testName string, parentNames []string, status string, duration float64, output, dataRace string) {
 	event := map[string]interface{}{
 		"eventType": "testCase",
 		"payload": map[string]interface{}{
@@ -892,9 +1032,14 @@ func (g *GoTestDefinition) sendTestCaseWithGroups(testName string, parentNames [
 
 	// Add error details for failed tests
 	if status == "FAIL" && output != "" {
-		event["payload"].(map[string]interface{})["error"] = map[string]interface{}{
-			"message": output,
+		errPayload := map[string]interface{}{
+			"message":     output,
+			"failureKind": goFailureKind(output),
 		}
+		if dataRace != "" {
+			errPayload["dataRace"] = dataRace
+		}
+		event["payload"].(map[string]interface{})["error"] = errPayload
 	}
 
 	if err := g.ipcWriter.WriteEvent(event); err != nil {
@@ -902,6 +1047,282 @@ func (g *GoTestDefinition) sendTestCaseWithGroups(testName string, parentNames [
 	}
 }
 
+// goTestTimeoutPanic starts the output of a test binary that ran past -timeout. The
+// panic kills the binary, so its unfinished tests never report a result.
+const goTestTimeoutPanic = "panic: test timed out after"
+
+// reportUnfinishedTests reports the tests of a package whose test binary hit -timeout:
+// the ones in flight fail as TIMED_OUT, the others are NOT_RUN. Runs before the
+// package's own result.
+func (g *GoTestDefinition) reportUnfinishedTests(event *GoTestEvent) {
+	g.mu.Lock()
+	dump, timedOut := g.timeoutOutput[event.Package]
+	delete(g.timeoutOutput, event.Package)
+	if !timedOut {
+		g.mu.Unlock()
+		return
+	}
+
+	var names []string
+	for _, state := range g.testStates {
+		if state.Package == event.Package {
+			names = append(names, state.Name)
+		}
+	}
+	// Subtests sort after their parents, so reversed they are reported first
+	sort.Sort(sort.Reverse(sort.StringSlice(names)))
+
+	panicLine := strings.TrimSpace(dump[0])
+	running, listed := goTimeoutRunningTests(dump)
+	var failed []string
+	var results []*GoTestEvent
+	for _, name := range names {
+		state := g.testStates[event.Package+"/"+name]
+		action := "fail"
+		switch {
+		case running[name] || (!listed && !state.IsPaused):
+			// Go before 1.21 doesn't list the running tests, then all but the paused ones were
+			state.TimeoutPanic = panicLine
+			state.TimeoutStacks = goTimeoutStacks(dump, name)
+			failed = append(failed, name)
+		case hasSubtestIn(name, failed):
+			// Fails with its subtest, as Go fails the parent of a failed subtest
+			failed = append(failed, name)
+		default:
+			state.NotRun = true
+			action = "skip"
+		}
+
+		elapsed := 0.0
+		if !state.StartTime.IsZero() && !event.Time.IsZero() {
+			elapsed = event.Time.Sub(state.StartTime).Seconds()
+		}
+		results = append(results, &GoTestEvent{Time: event.Time, Action: action, Package: event.Package, Test: name, Elapsed: elapsed})
+	}
+	g.mu.Unlock()
+
+	for _, result := range results {
+		g.handleTestResult(result)
+	}
+}
+
+// hasSubtestIn reports whether names holds a subtest of the test
+func hasSubtestIn(test string, names []string) bool {
+	for _, name := range names {
+		if strings.HasPrefix(name, test+"/") {
+			return true
+		}
+	}
+	return false
+}
+
+// goTimeoutRunningTests reads the tests in flight from the "running tests:" section of
+// a -timeout panic, e.g. "\t\tTestHang (1s)". listed is false if there is no section.
+func goTimeoutRunningTests(dump []string) (running map[string]bool, listed bool) {
+	running = make(map[string]bool)
+	for _, line := range dump {
+		trimmed := strings.TrimSpace(line)
+		if !listed {
+			listed = trimmed == "running tests:"
+			continue
+		}
+		if trimmed == "" || (line[0] != ' ' && line[0] != '\t') {
+			break
+		}
+		name, _, _ := strings.Cut(trimmed, " (")
+		running[name] = true
+	}
+	return running, listed
+}
+
+// goTimeoutStacks returns the goroutines of a -timeout dump whose stack runs the test's
+// function, separated by a blank line
+func goTimeoutStacks(dump []string, testName string) string {
+	function, _, _ := strings.Cut(testName, "/")
+	var stacks, stack []string
+	flush := func() {
+		text := strings.Join(stack, "\n")
+		if strings.Contains(text, "."+function+"(") || strings.Contains(text, "."+function+".func") {
+			stacks = append(stacks, text)
+		}
+		stack = nil
+	}
+	for _, line := range dump {
+		line = strings.TrimRight(line, "\r\n")
+		switch {
+		case strings.HasPrefix(line, "goroutine "):
+			flush()
+			stack = []string{line}
+		case line == "" || line == "FAIL" || strings.HasPrefix(line, "FAIL\t") || strings.HasPrefix(line, "exit status "):
+			// A blank line separates goroutines, and the binary's exit ends the dump
+			flush()
+		case stack != nil:
+			stack = append(stack, line)
+		}
+	}
+	flush()
+	return strings.Join(stacks, "\n\n")
+}
+
+// sendUnfinishedTest sends a test that hadn't finished when its test binary hit
+// -timeout: TIMED_OUT with its stacks if it was in flight, else NOT_RUN
+func (g *GoTestDefinition) sendUnfinishedTest(testName string, parentNames []string, duration float64, state *TestState) {
+	payload := map[string]interface{}{
+		"testName":    testName,
+		"parentNames": parentNames,
+		"status":      "SKIP",
+		"skipReason":  ipc.SkipReasonNotRun,
+	}
+	if state.TimeoutPanic != "" {
+		payload = map[string]interface{}{
+			"testName":    testName,
+			"parentNames": parentNames,
+			"status":      "FAIL",
+			"duration":    int64(duration * 1000),
+			"error": map[string]interface{}{
+				"message":     state.TimeoutPanic,
+				"stack":       state.TimeoutStacks,
+				"errorType":   ipc.ErrorTypeTimedOut,
+				"failureKind": ipc.FailureKindTimedOut,
+			},
+		}
+	}
+
+	event := map[string]interface{}{
+		"eventType": "testCase",
+		"payload":   payload,
+	}
+	if err := g.ipcWriter.WriteEvent(event); err != nil {
+		g.logger.Debug("Failed to write test case event: %v", err)
+	}
+}
+
+// goBenchResult is a benchmark result line of go test -bench
+type goBenchResult struct {
+	Name        string // As printed, with the GOMAXPROCS suffix (e.g. "BenchmarkParse-8")
+	Iterations  int64
+	NsPerOp     float64
+	Mem         bool // Reported B/op and allocs/op (-benchmem)
+	BytesPerOp  int64
+	AllocsPerOp int64
+}
+
+// parseGoBenchLine parses a benchmark result line, e.g.
+// "BenchmarkParse-8   1000000   1053 ns/op   24 B/op   1 allocs/op". Metrics of
+// b.ReportMetric are value and unit pairs like the others and are skipped.
+func parseGoBenchLine(line string) (goBenchResult, bool) {
+	fields := strings.Fields(line)
+	if len(fields) < 4 || len(fields)%2 != 0 || !strings.HasPrefix(fields[0], "Benchmark") {
+		return goBenchResult{}, false
+	}
+	iterations, err := strconv.ParseInt(fields[1], 10, 64)
+	if err != nil {
+		return goBenchResult{}, false
+	}
+
+	result := goBenchResult{Name: fields[0], Iterations: iterations}
+	hasNs, hasBytes, hasAllocs := false, false, false
+	for i := 2; i < len(fields); i += 2 {
+		value, err := strconv.ParseFloat(fields[i], 64)
+		if err != nil {
+			return goBenchResult{}, false
+		}
+		switch fields[i+1] {
+		case "ns/op":
+			result.NsPerOp, hasNs = value, true
+		case "B/op":
+			result.BytesPerOp, hasBytes = int64(value), true
+		case "allocs/op":
+			result.AllocsPerOp, hasAllocs = int64(value), true
+		}
+	}
+	result.Mem = hasBytes && hasAllocs
+	return result, hasNs
+}
+
+// sendBenchmark sends a benchmark result as a BENCH test case of its package
+func (g *GoTestDefinition) sendBenchmark(packageName string, result goBenchResult) {
+	g.ensureGroupsDiscovered(packageName, []string{})
+	g.ensureGroupStarted([]string{packageName})
+
+	payload := map[string]interface{}{
+		"testName":        result.Name,
+		"parentNames":     []string{packageName},
+		"status":          "BENCH",
+		"benchIterations": result.Iterations,
+		"benchNsPerOp":    result.NsPerOp,
+	}
+	if result.Mem {
+		payload["benchMem"] = true
+		payload["benchBytesPerOp"] = result.BytesPerOp
+		payload["benchAllocsPerOp"] = result.AllocsPerOp
+	}
+
+	event := map[string]interface{}{
+		"eventType": "testCase",
+		"payload":   payload,
+	}
+	if err := g.ipcWriter.WriteEvent(event); err != nil {
+		g.logger.Debug("Failed to write benchmark event: %v", err)
+	}
+}
+
+// goFailureKind classifies a failed Go test by its output. Tests fail through t.Error
+// and t.Fatal, which report failed checks, unless they panic, hit -timeout or race.
+func goFailureKind(output string) string {
+	switch {
+	case strings.Contains(output, goDataRaceWarning):
+		return ipc.FailureKindDataRace
+	case strings.Contains(output, "panic: test timed out after"):
+		return ipc.FailureKindTimedOut
+	case strings.Contains(output, "panic: "):
+		return ipc.FailureKindPanicked
+	}
+	return ipc.FailureKindAssertionFailed
+}
+
+// goDataRaceWarning starts a race detector report (go test -race), which ends with a
+// line of "=" like the one before it
+const goDataRaceWarning = "WARNING: DATA RACE"
+
+// goDataRaceAccessRegex matches the first line of an access in a race report, e.g.
+// "Previous write at 0x00c000014118 by goroutine 7:"
+var goDataRaceAccessRegex = regexp.MustCompile(`^(?:Previous )?(?:[Rr]ead|[Ww]rite) at 0x[0-9a-f]+ by (?:goroutine \d+|main goroutine):$`)
+
+// goDataRaces extracts the race detector reports from output lines: the stacks of the
+// two conflicting accesses, without where their goroutines were created. Reports are
+// separated by a blank line.
+func goDataRaces(lines []string) string {
+	var races, race []string
+	inRace, inAccess := false, false
+	for _, line := range lines {
+		line = strings.TrimRight(line, "\r\n")
+		trimmed := strings.TrimSpace(line)
+		switch {
+		case trimmed == goDataRaceWarning:
+			inRace, inAccess, race = true, false, nil
+		case !inRace:
+			continue
+		case strings.HasPrefix(trimmed, "=================="):
+			if len(race) > 0 {
+				races = append(races, strings.Join(race, "\n"))
+			}
+			inRace = false
+		case goDataRaceAccessRegex.MatchString(trimmed):
+			if len(race) > 0 {
+				race = append(race, "")
+			}
+			race = append(race, trimmed)
+			inAccess = true
+		case trimmed == "":
+			inAccess = false
+		case inAccess:
+			race = append(race, line)
+		}
+	}
+	return strings.Join(races, "\n\n")
+}
+
 // finalizePendingGroups sends group results for any groups that haven't been finalized
 func (g *GoTestDefinition) finalizePendingGroups() {
 	g.mu.Lock()
@@ -1055,6 +1476,82 @@ func (g *GoTestDefinition) sendGroupError(groupName string, parentNames []string
 // cleanupPackageErrors removes buffered errors to prevent memory leaks
 func (g *GoTestDefinition) cleanupPackageErrors(packageName string) {
 	delete(g.packageErrors, packageName)
+	delete(g.buildFailed, packageName)
+	delete(g.packageOutput, packageName)
+}
+
+// goCompileErrorLineRegex matches a compiler error, e.g. "./api.go:12:5: undefined: x"
+var goCompileErrorLineRegex = regexp.MustCompile(`^(\S+\.go):(\d+):(\d+): (.+)$`)
+
+// handleBuildOutput buffers the compiler output of a package. Build events name the
+// package by import path, followed by the test binary in brackets for test builds.
+func (g *GoTestDefinition) handleBuildOutput(event *GoTestEvent) {
+	g.mu.Lock()
+	defer g.mu.Unlock()
+
+	pkg, _, _ := strings.Cut(event.ImportPath, " ")
+	if line := strings.TrimRight(event.Output, "\n"); line != "" {
+		g.buildOutput[pkg] = append(g.buildOutput[pkg], line)
+	}
+}
+
+// sendBuildFailure reports a package that didn't build: the compiler output as its
+// group error, and each compiler error once for the report's build errors section.
+// Older Go versions write the compiler output to stderr, so the errors are missing
+// and the package's own output is used. Caller must hold g.mu.
+func (g *GoTestDefinition) sendBuildFailure(event *GoTestEvent) {
+	failed, _, _ := strings.Cut(event.FailedBuild, " ")
+	if failed == "" {
+		failed = event.Package
+	}
+	output := g.buildOutput[failed]
+	message := strings.Join(output, "\n")
+	if message == "" {
+		message = g.constructErrorMessage(event.Package)
+	}
+	g.sendGroupError(event.Package, []string{}, "COMPILATION_FAILURE", event.Elapsed, message)
+
+	// Packages that import a broken one all fail with its errors
+	if g.buildErrorsSent[failed] {
+		return
+	}
+	g.buildErrorsSent[failed] = true
+	sent := false
+	for _, line := range output {
+		match := goCompileErrorLineRegex.FindStringSubmatch(line)
+		if match == nil {
+			continue
+		}
+		lineNumber, _ := strconv.Atoi(match[2])
+		column, _ := strconv.Atoi(match[3])
+		g.sendBuildError(map[string]interface{}{
+			"target":   failed,
+			"message":  match[4],
+			"rendered": line,
+			"file":     match[1],
+			"line":     lineNumber,
+			"column":   column,
+		})
+		sent = true
+	}
+	if !sent {
+		g.sendBuildError(map[string]interface{}{
+			"target":   failed,
+			"message":  "build failed",
+			"rendered": message,
+		})
+	}
+}
+
+// sendBuildError sends a buildError event
+func (g *GoTestDefinition) sendBuildError(payload map[string]interface{}) {
+	event := map[string]interface{}{
+		"eventType": "buildError",
+		"payload":   payload,
+	}
+	if err := g.ipcWriter.WriteEvent(event); err != nil {
+		g.logger.Error("Failed to send buildError: %v", err)
+	}
 }
 
 // sendTestFileResult, sendTestFileResultWithDuration, sendStdoutChunk removed - using group events instead
@@ -1077,6 +1574,11 @@ func (w *IPCWriter) WriteEvent(event interface{}) error {
 	w.mu.Lock()
 	defer w.mu.Unlock()
 
+	if fields, ok := event.(map[string]interface{}); ok {
+		if _, ok := fields["schema"]; !ok {
+			fields["schema"] = ipc.SchemaVersion
+		}
+	}
 	data, err := json.Marshal(event)
 	if err != nil {
 		return fmt.Errorf("failed to marshal event: %w", err)
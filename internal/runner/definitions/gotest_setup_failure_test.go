@@ -270,3 +270,100 @@ func TestGoTestDefinition_IsErrorOutput(t *testing.T) {
 		})
 	}
 }
+
+// TestGoTestDefinition_BuildFailure tests packages that didn't compile
+func TestGoTestDefinition_BuildFailure(t *testing.T) {
+	def := NewGoTestDefinition(createTestLogger(t))
+	ipcPath := filepath.Join(t.TempDir(), "events.jsonl")
+	var err error
+	def.ipcWriter, err = NewIPCWriter(ipcPath)
+	if err != nil {
+		t.Fatalf("Failed to create IPC writer: %v", err)
+	}
+	t.Cleanup(func() { _ = def.ipcWriter.Close() })
+	capture := NewTestIPCCapture(ipcPath)
+
+	// Go 1.24 reports the compiler output as build-output events; both packages import
+	// the broken one, the second from before the FailedBuild field
+	events := []GoTestEvent{
+		{Action: "build-output", ImportPath: "example.com/mod/broken [example.com/mod/broken.test]", Output: "# example.com/mod/broken [example.com/mod/broken.test]\n"},
+		{Action: "build-output", ImportPath: "example.com/mod/broken [example.com/mod/broken.test]", Output: "./broken.go:5:9: undefined: missing\n"},
+		{Action: "build-fail", ImportPath: "example.com/mod/broken [example.com/mod/broken.test]"},
+		{Action: "start", Package: "example.com/mod/broken"},
+		{Action: "output", Package: "example.com/mod/broken", Output: "FAIL\texample.com/mod/broken [build failed]\n"},
+		{Action: "fail", Package: "example.com/mod/broken", FailedBuild: "example.com/mod/broken [example.com/mod/broken.test]"},
+		{Action: "start", Package: "example.com/mod/user"},
+		{Action: "output", Package: "example.com/mod/user", Output: "FAIL\texample.com/mod/user [build failed]\n"},
+		{Action: "fail", Package: "example.com/mod/user"},
+	}
+	for _, event := range events {
+		if err := def.processEvent(&event); err != nil {
+			t.Fatalf("Failed to process event: %v", err)
+		}
+	}
+	_ = def.ipcWriter.Close()
+
+	groupErrors := make(map[string]map[string]interface{})
+	for _, e := range capture.GetEventsByType("testGroupError") {
+		payload := e["payload"].(map[string]interface{})
+		groupErrors[payload["groupName"].(string)] = payload
+	}
+	broken := groupErrors["example.com/mod/broken"]
+	if broken == nil || broken["errorType"] != "COMPILATION_FAILURE" {
+		t.Fatalf("Expected a COMPILATION_FAILURE for the broken package, got %v", broken)
+	}
+	if message := broken["error"].(map[string]interface{})["message"].(string); !strings.Contains(message, "./broken.go:5:9: undefined: missing") {
+		t.Errorf("Expected the compiler output in the group error, got %q", message)
+	}
+	if user := groupErrors["example.com/mod/user"]; user == nil || user["errorType"] != "COMPILATION_FAILURE" {
+		t.Errorf("Expected a COMPILATION_FAILURE for the package reported as [build failed], got %v", user)
+	}
+
+	// The compiler error is sent once, with its location
+	buildErrors := capture.GetEventsByType("buildError")
+	if len(buildErrors) != 2 {
+		t.Fatalf("Expected a buildError for each broken package, got %v", buildErrors)
+	}
+	first := buildErrors[0]["payload"].(map[string]interface{})
+	if first["target"] != "example.com/mod/broken" || first["message"] != "undefined: missing" ||
+		first["file"] != "./broken.go" || first["line"] != 5.0 || first["column"] != 9.0 {
+		t.Errorf("Unexpected buildError: %v", first)
+	}
+	if second := buildErrors[1]["payload"].(map[string]interface{}); second["target"] != "example.com/mod/user" {
+		t.Errorf("Expected the second buildError for the package without compiler output, got %v", second)
+	}
+}
+
+// TestGoTestDefinition_NoTestFiles tests packages without _test.go files
+func TestGoTestDefinition_NoTestFiles(t *testing.T) {
+	def := NewGoTestDefinition(createTestLogger(t))
+	ipcPath := filepath.Join(t.TempDir(), "events.jsonl")
+	var err error
+	def.ipcWriter, err = NewIPCWriter(ipcPath)
+	if err != nil {
+		t.Fatalf("Failed to create IPC writer: %v", err)
+	}
+	t.Cleanup(func() { _ = def.ipcWriter.Close() })
+	capture := NewTestIPCCapture(ipcPath)
+
+	events := []GoTestEvent{
+		{Action: "start", Package: "example.com/mod/notests"},
+		{Action: "output", Package: "example.com/mod/notests", Output: "?   \texample.com/mod/notests\t[no test files]\n"},
+		{Action: "skip", Package: "example.com/mod/notests"},
+	}
+	for _, event := range events {
+		if err := def.processEvent(&event); err != nil {
+			t.Fatalf("Failed to process event: %v", err)
+		}
+	}
+	_ = def.ipcWriter.Close()
+
+	results := capture.GetEventsByType("testGroupResult")
+	if len(results) != 1 {
+		t.Fatalf("Expected one testGroupResult, got %v", results)
+	}
+	payload := results[0]["payload"].(map[string]interface{})
+	if payload["status"] != "SKIP" || payload["skipReason"] != ipc.SkipReasonNoTestFiles {
+		t.Errorf("Expected the package to be skipped with no test files, got %v", payload)
+	}
+}
@@ -10,6 +10,7 @@ import (
 	"testing"
 	"time"
 
+	"github.com/zk/3pio/internal/ipc"
 	"github.com/zk/3pio/internal/logger"
 )
 
@@ -232,6 +233,36 @@ func TestGoTestDefinition_ModifyCommand(t *testing.T) {
 }
 
 // Test GetTestFiles method
+func TestGoTestDefinition_RerunCommand(t *testing.T) {
+	def := NewGoTestDefinition(createTestLogger(t))
+
+	failed := []ipc.FailedTest{
+		{Name: "TestAdd", ParentNames: []string{"github.com/example/calc"}},
+		{Name: "negative", ParentNames: []string{"github.com/example/calc", "TestDivide"}},
+		{Name: "zero", ParentNames: []string{"github.com/example/calc", "TestDivide"}},
+	}
+
+	tests := []struct {
+		name     string
+		cmd      []string
+		expected string
+	}{
+		{"appends -run", []string{"go", "test", "./..."}, "go test ./... -run ^(TestAdd|TestDivide)$"},
+		{"-run before -args", []string{"go", "test", "./...", "-args", "-v"}, "go test ./... -run ^(TestAdd|TestDivide)$ -args -v"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			result, err := def.RerunCommand(tt.cmd, failed)
+			if err != nil {
+				t.Fatalf("RerunCommand failed: %v", err)
+			}
+			if strings.Join(result, " ") != tt.expected {
+				t.Errorf("Expected %q, got %q", tt.expected, strings.Join(result, " "))
+			}
+		})
+	}
+}
+
 func TestGoTestDefinition_GetTestFiles(t *testing.T) {
 	tests := []struct {
 		name          string
@@ -597,6 +628,7 @@ func TestGoTestDefinition_ParseTestHierarchy(t *testing.T) {
 	tests := []struct {
 		name              string
 		testName          string
+		running           []string // Tests with a run event but no result yet
 		expectedSuite     []string
 		expectedFinalName string
 	}{
@@ -624,12 +656,37 @@ func TestGoTestDefinition_ParseTestHierarchy(t *testing.T) {
 			expectedSuite:     []string{"TestExample"},
 			expectedFinalName: "case_1",
 		},
+		{
+			name:              "Nested subtests that ran",
+			testName:          "TestExample/group/subtest",
+			running:           []string{"TestExample", "TestExample/group"},
+			expectedSuite:     []string{"TestExample", "group"},
+			expectedFinalName: "subtest",
+		},
+		{
+			name:              "Slash within a subtest name",
+			testName:          "TestExample/a/b",
+			running:           []string{"TestExample"},
+			expectedSuite:     []string{"TestExample"},
+			expectedFinalName: "a/b",
+		},
+		{
+			name:              "Slash within a group name",
+			testName:          "TestExample/a/b/subtest",
+			running:           []string{"TestExample", "TestExample/a/b"},
+			expectedSuite:     []string{"TestExample", "a/b"},
+			expectedFinalName: "subtest",
+		},
 	}
 
 	g := NewGoTestDefinition(createTestLogger(t))
 	for _, tt := range tests {
 		t.Run(tt.name, func(t *testing.T) {
-			suite, finalName := g.parseTestHierarchy(tt.testName)
+			g.testStates = make(map[string]*TestState)
+			for _, name := range tt.running {
+				g.testStates["example.com/pkg/"+name] = &TestState{Name: name, Package: "example.com/pkg"}
+			}
+			suite, finalName := g.parseTestHierarchy("example.com/pkg", tt.testName)
 
 			if len(suite) != len(tt.expectedSuite) {
 				t.Errorf("Suite length mismatch: got %v, want %v", suite, tt.expectedSuite)
@@ -831,6 +888,96 @@ func TestGoTestDefinition_SubgroupDuration(t *testing.T) {
 	}
 }
 
+// Test parallel subtests, which pause and continue, in a failing package
+func TestGoTestDefinition_ParallelSubtests(t *testing.T) {
+	g := NewGoTestDefinition(createTestLogger(t))
+	tmpDir := t.TempDir()
+	ipcPath := filepath.Join(tmpDir, "test.jsonl")
+	ipcWriter, err := NewIPCWriter(ipcPath)
+	if err != nil {
+		t.Fatalf("Failed to create IPC writer: %v", err)
+	}
+	g.ipcWriter = ipcWriter
+	t.Cleanup(func() { _ = ipcWriter.Close() })
+	capture := NewTestIPCCapture(ipcPath)
+
+	// t.Parallel() pauses each subtest until its parent returns, then continues it;
+	// their output interleaves
+	pkg := "example.com/parallel"
+	events := []*GoTestEvent{
+		{Action: "start", Package: pkg},
+		{Action: "run", Package: pkg, Test: "TestParallel"},
+		{Action: "run", Package: pkg, Test: "TestParallel/A"},
+		{Action: "pause", Package: pkg, Test: "TestParallel/A"},
+		{Action: "run", Package: pkg, Test: "TestParallel/B"},
+		{Action: "pause", Package: pkg, Test: "TestParallel/B"},
+		{Action: "cont", Package: pkg, Test: "TestParallel/A"},
+		{Action: "cont", Package: pkg, Test: "TestParallel/B"},
+		{Action: "output", Package: pkg, Test: "TestParallel/B", Output: "    parallel_test.go:12: boom\n"},
+		{Action: "output", Package: pkg, Test: "TestParallel/A", Output: "    parallel_test.go:8: fine\n"},
+		{Action: "pass", Package: pkg, Test: "TestParallel/A", Elapsed: 0.2},
+		{Action: "fail", Package: pkg, Test: "TestParallel/B", Elapsed: 0.3},
+		{Action: "fail", Package: pkg, Test: "TestParallel", Elapsed: 0.5},
+		{Action: "output", Package: pkg, Output: "FAIL\n"},
+		{Action: "fail", Package: pkg, Elapsed: 1.5},
+	}
+	for _, event := range events {
+		if err := g.processEvent(event); err != nil {
+			t.Fatalf("Failed to process event: %v", err)
+		}
+	}
+	_ = ipcWriter.Close()
+
+	// Each subtest is reported once, with its own output
+	cases := make(map[string][]map[string]interface{})
+	for _, event := range capture.GetEventsByType("testCase") {
+		payload := event["payload"].(map[string]interface{})
+		name := payload["testName"].(string)
+		cases[name] = append(cases[name], payload)
+	}
+	if len(cases["A"]) != 1 || len(cases["B"]) != 1 {
+		t.Fatalf("Expected one testCase event each for A and B, got %v", cases)
+	}
+	if cases["A"][0]["status"] != "PASS" || cases["B"][0]["status"] != "FAIL" {
+		t.Errorf("Expected A to pass and B to fail, got %v and %v", cases["A"][0]["status"], cases["B"][0]["status"])
+	}
+	errPayload, ok := cases["B"][0]["error"].(map[string]interface{})
+	if !ok {
+		t.Fatal("Expected an error on B")
+	}
+	message := errPayload["message"].(string)
+	if !strings.Contains(message, "parallel_test.go:12: boom") || strings.Contains(message, "fine") {
+		t.Errorf("Expected only B's output in its error, got %q", message)
+	}
+
+	// Continuing a paused subtest doesn't start its group again
+	starts := 0
+	for _, event := range capture.GetEventsByType("testGroupStart") {
+		payload := event["payload"].(map[string]interface{})
+		if payload["groupName"] == "TestParallel" {
+			starts++
+		}
+	}
+	if starts != 1 {
+		t.Errorf("Expected TestParallel to start once, got %d", starts)
+	}
+
+	// The package takes its status and duration from its own result
+	var packageResult map[string]interface{}
+	for _, event := range capture.GetEventsByType("testGroupResult") {
+		payload := event["payload"].(map[string]interface{})
+		if payload["groupName"] == pkg && len(convertToStringSlice(payload["parentNames"])) == 0 {
+			packageResult = payload
+		}
+	}
+	if packageResult == nil {
+		t.Fatal("Package-level group result not found")
+	}
+	if packageResult["status"] != "FAIL" || packageResult["duration"] != 1500.0 {
+		t.Errorf("Expected the package to fail after 1500ms, got %v after %v", packageResult["status"], packageResult["duration"])
+	}
+}
+
 // Test deeply nested subgroups with empty names
 func TestGoTestDefinition_DeeplyNestedSubgroups(t *testing.T) {
 	g := NewGoTestDefinition(createTestLogger(t))
@@ -1156,3 +1303,302 @@ func BenchmarkGoTestDefinition_ProcessEvent(b *testing.B) {
 		_ = g.processEvent(event)
 	}
 }
+
+// Test race detector reports in a test and outside of any test
+func TestGoTestDefinition_DataRace(t *testing.T) {
+	g := NewGoTestDefinition(createTestLogger(t))
+	ipcPath := filepath.Join(t.TempDir(), "test.jsonl")
+	ipcWriter, err := NewIPCWriter(ipcPath)
+	if err != nil {
+		t.Fatalf("Failed to create IPC writer: %v", err)
+	}
+	g.ipcWriter = ipcWriter
+	t.Cleanup(func() { _ = ipcWriter.Close() })
+	capture := NewTestIPCCapture(ipcPath)
+
+	race := []string{
+		"==================\n",
+		"WARNING: DATA RACE\n",
+		"Write at 0x00c000014118 by goroutine 8:\n",
+		"  example.com/race.TestCounter.func1()\n",
+		"      /src/race/race_test.go:12 +0x44\n",
+		"\n",
+		"Previous write at 0x00c000014118 by goroutine 7:\n",
+		"  example.com/race.TestCounter()\n",
+		"      /src/race/race_test.go:15 +0x88\n",
+		"\n",
+		"Goroutine 8 (running) created at:\n",
+		"  example.com/race.TestCounter()\n",
+		"      /src/race/race_test.go:11 +0x7c\n",
+		"==================\n",
+	}
+	pkg := "example.com/race"
+	events := []*GoTestEvent{
+		{Action: "start", Package: pkg},
+		{Action: "run", Package: pkg, Test: "TestCounter"},
+	}
+	for _, line := range race {
+		events = append(events, &GoTestEvent{Action: "output", Package: pkg, Test: "TestCounter", Output: line})
+	}
+	events = append(events,
+		&GoTestEvent{Action: "output", Package: pkg, Test: "TestCounter", Output: "    testing.go:1465: race detected during execution of test\n"},
+		&GoTestEvent{Action: "fail", Package: pkg, Test: "TestCounter", Elapsed: 0.01},
+	)
+	// The same race again after the tests finished
+	for _, line := range race {
+		events = append(events, &GoTestEvent{Action: "output", Package: pkg, Output: line})
+	}
+	events = append(events, &GoTestEvent{Action: "fail", Package: pkg, Elapsed: 0.5})
+	for _, event := range events {
+		if err := g.processEvent(event); err != nil {
+			t.Fatalf("Failed to process event: %v", err)
+		}
+	}
+	_ = ipcWriter.Close()
+
+	// Both accesses, without where the goroutine was created
+	expected := "Write at 0x00c000014118 by goroutine 8:\n" +
+		"  example.com/race.TestCounter.func1()\n" +
+		"      /src/race/race_test.go:12 +0x44\n" +
+		"\n" +
+		"Previous write at 0x00c000014118 by goroutine 7:\n" +
+		"  example.com/race.TestCounter()\n" +
+		"      /src/race/race_test.go:15 +0x88"
+
+	testCases := capture.GetEventsByType("testCase")
+	if len(testCases) != 1 {
+		t.Fatalf("Expected one testCase event, got %v", testCases)
+	}
+	errPayload, ok := testCases[0]["payload"].(map[string]interface{})["error"].(map[string]interface{})
+	if !ok {
+		t.Fatal("Expected an error on TestCounter")
+	}
+	if errPayload["failureKind"] != ipc.FailureKindDataRace {
+		t.Errorf("Expected failure kind %s, got %v", ipc.FailureKindDataRace, errPayload["failureKind"])
+	}
+	if errPayload["dataRace"] != expected {
+		t.Errorf("Expected the race stacks\n%s\ngot\n%v", expected, errPayload["dataRace"])
+	}
+
+	groupErrors := capture.GetEventsByType("testGroupError")
+	if len(groupErrors) != 1 {
+		t.Fatalf("Expected a testGroupError for the race outside the test, got %v", groupErrors)
+	}
+	payload := groupErrors[0]["payload"].(map[string]interface{})
+	if payload["groupName"] != pkg || payload["errorType"] != "DATA_RACE" ||
+		payload["error"].(map[string]interface{})["message"] != expected {
+		t.Errorf("Unexpected testGroupError: %v", payload)
+	}
+}
+
+func TestParseGoBenchLine(t *testing.T) {
+	tests := []struct {
+		line string
+		ok   bool
+		want goBenchResult
+	}{
+		{"BenchmarkFoo-8   \t 1000000\t      1053 ns/op\n", true,
+			goBenchResult{Name: "BenchmarkFoo-8", Iterations: 1000000, NsPerOp: 1053}},
+		{"BenchmarkFoo-8   \t 1000000\t      1053 ns/op\t      24 B/op\t       0 allocs/op\n", true,
+			goBenchResult{Name: "BenchmarkFoo-8", Iterations: 1000000, NsPerOp: 1053, Mem: true, BytesPerOp: 24}},
+		{"BenchmarkCopy/4KB   \t  500\t  2304.5 ns/op\t 1777.37 MB/s\t 4096 B/op\t 1 allocs/op\n", true,
+			goBenchResult{Name: "BenchmarkCopy/4KB", Iterations: 500, NsPerOp: 2304.5, Mem: true, BytesPerOp: 4096, AllocsPerOp: 1}},
+		// The name printed before the benchmark runs, and log lines
+		{"BenchmarkFoo\n", false, goBenchResult{}},
+		{"BenchmarkFoo-8   \t", false, goBenchResult{}},
+		{"    bench_test.go:12: 1000 ns/op\n", false, goBenchResult{}},
+		{"BenchmarkFoo-8   \t 1000000\t 12.5 MB/s\n", false, goBenchResult{}},
+	}
+	for _, tt := range tests {
+		got, ok := parseGoBenchLine(tt.line)
+		if ok != tt.ok || got != tt.want {
+			t.Errorf("parseGoBenchLine(%q) = %+v, %v; want %+v, %v", tt.line, got, ok, tt.want, tt.ok)
+		}
+	}
+}
+
+func TestGoTestDefinition_Benchmarks(t *testing.T) {
+	g := NewGoTestDefinition(createTestLogger(t))
+	ipcPath := filepath.Join(t.TempDir(), "test.jsonl")
+	ipcWriter, err := NewIPCWriter(ipcPath)
+	if err != nil {
+		t.Fatalf("Failed to create IPC writer: %v", err)
+	}
+	g.ipcWriter = ipcWriter
+	t.Cleanup(func() { _ = ipcWriter.Close() })
+	capture := NewTestIPCCapture(ipcPath)
+
+	pkg := "example.com/bench"
+	events := []*GoTestEvent{
+		{Action: "start", Package: pkg},
+		{Action: "run", Package: pkg, Test: "TestJoin"},
+		{Action: "pass", Package: pkg, Test: "TestJoin", Elapsed: 0.01},
+		// Newer Go versions attribute the result to the benchmark
+		{Action: "run", Package: pkg, Test: "BenchmarkJoin"},
+		{Action: "output", Package: pkg, Test: "BenchmarkJoin", Output: "BenchmarkJoin\n"},
+		{Action: "output", Package: pkg, Test: "BenchmarkJoin", Output: "BenchmarkJoin-8   \t 1000000\t      1053 ns/op\t      24 B/op\t       1 allocs/op\n"},
+		{Action: "pass", Package: pkg, Test: "BenchmarkJoin", Elapsed: 1.2},
+		// Older ones leave it to the package
+		{Action: "output", Package: pkg, Output: "BenchmarkSplit-8   \t  300000\t      4012 ns/op\n"},
+		{Action: "output", Package: pkg, Output: "PASS\n"},
+		{Action: "pass", Package: pkg, Elapsed: 2.5},
+	}
+	for _, event := range events {
+		if err := g.processEvent(event); err != nil {
+			t.Fatalf("Failed to process event: %v", err)
+		}
+	}
+	_ = ipcWriter.Close()
+
+	statuses := make(map[string]map[string]interface{})
+	for _, event := range capture.GetEventsByType("testCase") {
+		payload := event["payload"].(map[string]interface{})
+		statuses[payload["testName"].(string)] = payload
+	}
+	if len(statuses) != 3 {
+		t.Fatalf("Expected TestJoin and two benchmarks, got %v", statuses)
+	}
+	if statuses["TestJoin"]["status"] != "PASS" {
+		t.Errorf("Expected TestJoin to pass, got %v", statuses["TestJoin"])
+	}
+	join := statuses["BenchmarkJoin-8"]
+	if join["status"] != "BENCH" || join["benchIterations"] != float64(1000000) || join["benchNsPerOp"] != float64(1053) ||
+		join["benchMem"] != true || join["benchBytesPerOp"] != float64(24) || join["benchAllocsPerOp"] != float64(1) {
+		t.Errorf("Unexpected BenchmarkJoin-8 payload: %v", join)
+	}
+	split := statuses["BenchmarkSplit-8"]
+	if split["status"] != "BENCH" || split["benchIterations"] != float64(300000) || split["benchMem"] != nil {
+		t.Errorf("Unexpected BenchmarkSplit-8 payload: %v", split)
+	}
+	if parents := convertToStringSlice(split["parentNames"]); len(parents) != 1 || parents[0] != pkg {
+		t.Errorf("Expected the benchmark in package %s, got %v", pkg, parents)
+	}
+
+	// Only the test counts towards the package totals
+	results := capture.GetEventsByType("testGroupResult")
+	if len(results) != 1 {
+		t.Fatalf("Expected one testGroupResult, got %v", results)
+	}
+	totals := results[0]["payload"].(map[string]interface{})["totals"].(map[string]interface{})
+	if totals["total"] != float64(1) || totals["passed"] != float64(1) {
+		t.Errorf("Expected one passed test in the totals, got %v", totals)
+	}
+}
+
+func TestGoTestDefinition_TestTimeout(t *testing.T) {
+	g := NewGoTestDefinition(createTestLogger(t))
+	ipcPath := filepath.Join(t.TempDir(), "test.jsonl")
+	ipcWriter, err := NewIPCWriter(ipcPath)
+	if err != nil {
+		t.Fatalf("Failed to create IPC writer: %v", err)
+	}
+	g.ipcWriter = ipcWriter
+	t.Cleanup(func() { _ = ipcWriter.Close() })
+	capture := NewTestIPCCapture(ipcPath)
+
+	pkg := "example.com/hang"
+	start := time.Date(2024, 1, 1, 12, 0, 0, 0, time.UTC)
+	events := []*GoTestEvent{
+		{Action: "start", Package: pkg, Time: start},
+		{Action: "run", Package: pkg, Test: "TestQuick", Time: start},
+		{Action: "pass", Package: pkg, Test: "TestQuick", Elapsed: 0.01},
+		{Action: "run", Package: pkg, Test: "TestHang", Time: start},
+		{Action: "run", Package: pkg, Test: "TestHang/slow", Time: start},
+		// Parallel, so paused until TestHang returns
+		{Action: "run", Package: pkg, Test: "TestLater", Time: start},
+		{Action: "pause", Package: pkg, Test: "TestLater"},
+	}
+	for _, line := range []string{
+		"panic: test timed out after 1s\n",
+		"\trunning tests:\n",
+		"\t\tTestHang (1s)\n",
+		"\t\tTestHang/slow (1s)\n",
+		"\n",
+		"goroutine 7 [running]:\n",
+		"testing.(*M).startAlarm.func1()\n",
+		"\t/usr/local/go/src/testing/testing.go:2259 +0x1f2\n",
+		"\n",
+		"goroutine 8 [chan receive]:\n",
+		"testing.(*T).Run(0xc000007860, {0x5bf1d4, 0x4}, 0x5c6a30)\n",
+		"\t/usr/local/go/src/testing/testing.go:1649 +0x3c8\n",
+		"example.com/hang.TestHang(0xc000007860)\n",
+		"\t/src/hang/hang_test.go:12 +0x2b\n",
+		"\n",
+		"goroutine 9 [sleep]:\n",
+		"time.Sleep(0x3b9aca00)\n",
+		"example.com/hang.TestHang.func1(0xc000007a00)\n",
+		"\t/src/hang/hang_test.go:14 +0x25\n",
+		"FAIL\texample.com/hang\t1.012s\n",
+	} {
+		events = append(events, &GoTestEvent{Action: "output", Package: pkg, Test: "TestHang/slow", Output: line})
+	}
+	events = append(events, &GoTestEvent{Action: "fail", Package: pkg, Time: start.Add(1500 * time.Millisecond), Elapsed: 1.5})
+	for _, event := range events {
+		if err := g.processEvent(event); err != nil {
+			t.Fatalf("Failed to process event: %v", err)
+		}
+	}
+	_ = ipcWriter.Close()
+
+	tests := make(map[string]map[string]interface{})
+	for _, event := range capture.GetEventsByType("testCase") {
+		payload := event["payload"].(map[string]interface{})
+		tests[payload["testName"].(string)] = payload
+	}
+	if len(tests) != 4 {
+		t.Fatalf("Expected TestQuick, TestHang, slow and TestLater, got %v", tests)
+	}
+	if tests["TestQuick"]["status"] != "PASS" {
+		t.Errorf("Expected TestQuick to keep its result, got %v", tests["TestQuick"])
+	}
+	if tests["TestLater"]["status"] != "SKIP" || tests["TestLater"]["skipReason"] != ipc.SkipReasonNotRun {
+		t.Errorf("Expected the paused TestLater to be not run, got %v", tests["TestLater"])
+	}
+
+	slow := tests["slow"]
+	errPayload, ok := slow["error"].(map[string]interface{})
+	if slow["status"] != "FAIL" || !ok {
+		t.Fatalf("Expected slow to fail with an error, got %v", slow)
+	}
+	if errPayload["message"] != "panic: test timed out after 1s" || errPayload["errorType"] != ipc.ErrorTypeTimedOut ||
+		errPayload["failureKind"] != ipc.FailureKindTimedOut || slow["duration"] != float64(1500) {
+		t.Errorf("Unexpected error on slow: %v", slow)
+	}
+	// The goroutines running TestHang and its subtest, not the alarm's
+	stack := errPayload["stack"].(string)
+	if !strings.Contains(stack, "goroutine 8 [chan receive]:") || !strings.Contains(stack, "example.com/hang.TestHang.func1(") ||
+		strings.Contains(stack, "startAlarm") || strings.Contains(stack, "FAIL") {
+		t.Errorf("Unexpected stacks on slow:\n%s", stack)
+	}
+	if parents := convertToStringSlice(slow["parentNames"]); len(parents) != 2 || parents[1] != "TestHang" {
+		t.Errorf("Expected slow under TestHang, got %v", parents)
+	}
+
+	results := make(map[string]string)
+	for _, event := range capture.GetEventsByType("testGroupResult") {
+		payload := event["payload"].(map[string]interface{})
+		results[payload["groupName"].(string)] = payload["status"].(string)
+	}
+	if results["TestHang"] != "FAIL" || results[pkg] != "FAIL" {
+		t.Errorf("Expected TestHang and the package to fail, got %v", results)
+	}
+}
+
+func TestGoTimeoutRunningTests(t *testing.T) {
+	running, listed := goTimeoutRunningTests([]string{
+		"panic: test timed out after 1s\n",
+		"\trunning tests:\n",
+		"\t\tTestA (1s)\n",
+		"\t\tTestB/case_one (900ms)\n",
+		"\n",
+		"\t\tTestNotListed (1s)\n",
+	})
+	if !listed || len(running) != 2 || !running["TestA"] || !running["TestB/case_one"] {
+		t.Errorf("Expected TestA and TestB/case_one, got %v (listed: %v)", running, listed)
+	}
+
+	// Go before 1.21 doesn't list them
+	if _, listed := goTimeoutRunningTests([]string{"panic: test timed out after 1s\n", "\n", "goroutine 7 [running]:\n"}); listed {
+		t.Error("Expected no running tests section")
+	}
+}
@@ -0,0 +1,136 @@
+package definitions
+
+import (
+	"io/fs"
+	"os"
+	"path/filepath"
+	"regexp"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/zk/3pio/internal/console"
+	"github.com/zk/3pio/internal/ipc"
+)
+
+// insta prints a summary of a snapshot that didn't match with the test's output, then
+// panics with "snapshot assertion for 'greeting' failed in line 12":
+//
+//	Snapshot file: src/snapshots/basic__greeting.snap
+//	Snapshot: greeting
+//	Source: src/lib.rs:12
+//	────────────────────────────────
+//	-old snapshot
+//	+new results
+//	────────────┬───────────────────
+//	    0       │-Hello, world
+//	          0 │+Hello, world!
+//	────────────┴───────────────────
+//	To update snapshots run `cargo insta review`
+var (
+	instaPanicRegex        = regexp.MustCompile(`snapshot assertion for '(.*)' failed in line \d+`)
+	instaSnapshotFileRegex = regexp.MustCompile(`^Snapshot file: (.+)$`)
+	instaSnapshotNameRegex = regexp.MustCompile(`^Snapshot: (.+)$`)
+	instaSourceRegex       = regexp.MustCompile(`^Source: (.+?)(?::\d+)?$`)
+	instaDiffLineRegex     = regexp.MustCompile(`^[ \d]*│([-+ ]?)(.*)$`)
+)
+
+// parseInstaSnapshot reads the snapshot of a failed insta assertion from the test's
+// output and panic message, or returns nil when the test failed otherwise. The file
+// of the new value is only given if insta wrote it under root: it doesn't under CI
+// unless INSTA_UPDATE says to.
+func parseInstaSnapshot(output, panicMessage, root string) *ipc.SnapshotMismatch {
+	m := instaPanicRegex.FindStringSubmatch(panicMessage)
+	if m == nil {
+		return nil
+	}
+	snapshot := &ipc.SnapshotMismatch{Name: m[1]}
+	if snapshot.Name == "" {
+		snapshot.Name = "<inline>"
+	}
+
+	var source string
+	var diff strings.Builder
+	inDiff := false
+	for _, line := range strings.Split(console.StripANSI(output), "\n") {
+		line = strings.TrimRight(line, "\r")
+		switch {
+		case strings.Contains(line, "┬"):
+			inDiff = true
+		case strings.Contains(line, "┴"):
+			inDiff = false
+		case inDiff:
+			if d := instaDiffLineRegex.FindStringSubmatch(line); d != nil {
+				marker := d[1]
+				if marker == "" {
+					marker = " "
+				}
+				diff.WriteString(marker + d[2] + "\n")
+			}
+		default:
+			if f := instaSnapshotFileRegex.FindStringSubmatch(line); f != nil {
+				snapshot.File = filepath.ToSlash(f[1])
+			} else if n := instaSnapshotNameRegex.FindStringSubmatch(line); n != nil && m[1] == "" {
+				snapshot.Name = n[1]
+			} else if s := instaSourceRegex.FindStringSubmatch(line); s != nil {
+				source = filepath.ToSlash(s[1])
+			}
+		}
+	}
+	snapshot.Diff = diff.String()
+	if newFile := pendingSnapshotFile(snapshot.File, source); newFile != "" {
+		if _, err := os.Stat(filepath.Join(root, filepath.FromSlash(newFile))); err == nil {
+			snapshot.NewFile = newFile
+		}
+	}
+	return snapshot
+}
+
+// pendingSnapshotFile returns where insta writes the new value of a snapshot for
+// review: next to the stored file, or for an inline snapshot next to the source file
+func pendingSnapshotFile(file, source string) string {
+	switch {
+	case file != "":
+		return file + ".new"
+	case source != "":
+		dir, name := filepath.Split(source)
+		return filepath.ToSlash(filepath.Join(dir, "."+name+".pending-snap"))
+	}
+	return ""
+}
+
+// isPendingSnapshot reports whether a file holds a snapshot waiting for cargo insta review
+func isPendingSnapshot(name string) bool {
+	return strings.HasSuffix(name, ".snap.new") || strings.HasSuffix(name, ".pending-snap")
+}
+
+// findPendingSnapshots finds the snapshots written for review under root since the
+// run started, relative to root, in the order of their paths. Build output, hidden
+// directories and node_modules are not searched.
+func findPendingSnapshots(root string, since time.Time) []string {
+	var snapshots []string
+	cutoff := since.Truncate(time.Second)
+	_ = filepath.WalkDir(root, func(path string, entry fs.DirEntry, err error) error {
+		if err != nil {
+			return nil
+		}
+		name := entry.Name()
+		if entry.IsDir() {
+			if path != root && (strings.HasPrefix(name, ".") || name == "target" || name == "node_modules") {
+				return filepath.SkipDir
+			}
+			return nil
+		}
+		if !isPendingSnapshot(name) {
+			return nil
+		}
+		if info, err := os.Stat(path); err == nil && !info.ModTime().Before(cutoff) {
+			if rel, err := filepath.Rel(root, path); err == nil {
+				snapshots = append(snapshots, filepath.ToSlash(rel))
+			}
+		}
+		return nil
+	})
+	sort.Strings(snapshots)
+	return snapshots
+}
@@ -0,0 +1,147 @@
+package definitions
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"reflect"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/zk/3pio/internal/ipc"
+)
+
+// instaGreetingOutput is what insta prints for the greeting snapshot of the basic
+// crate, stored as "Hello, world" while the test now returns "Hello, world!"
+const instaGreetingOutput = "\x1b[1m━━━━━━━━━━━━━━━━ Snapshot Summary ━━━━━━━━━━━━━━━━\x1b[0m\n" +
+	"Snapshot file: src/snapshots/basic__tests__greeting.snap\n" +
+	"Snapshot: \x1b[33mgreeting\x1b[0m\n" +
+	"Source: src/lib.rs:12\n" +
+	"──────────────────────────────────────────────────\n" +
+	"Expression: greet(\"world\")\n" +
+	"──────────────────────────────────────────────────\n" +
+	"-old snapshot\n" +
+	"+new results\n" +
+	"────────────┬─────────────────────────────────────\n" +
+	"    0     0 │ greeting:\n" +
+	"    1       │-  Hello, world\n" +
+	"          1 │+  Hello, world!\n" +
+	"────────────┴─────────────────────────────────────\n" +
+	"To update snapshots run `cargo insta review`\n" +
+	"Stopped on the first failure. Run `cargo insta test` to run all snapshots.\n"
+
+// writePendingSnapshot writes an empty file under root, creating its directory
+func writePendingSnapshot(t *testing.T, root, path string) string {
+	t.Helper()
+	full := filepath.Join(root, filepath.FromSlash(path))
+	if err := os.MkdirAll(filepath.Dir(full), 0755); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(full, nil, 0644); err != nil {
+		t.Fatal(err)
+	}
+	return full
+}
+
+func TestParseInstaSnapshot(t *testing.T) {
+	root := t.TempDir()
+	writePendingSnapshot(t, root, "src/snapshots/basic__tests__greeting.snap.new")
+	panicMessage := "thread 'tests::greeting' panicked at /home/dev/.cargo/registry/src/index.crates.io-6f17d22bba15001f/insta-1.39.0/src/runtime.rs:679:13:\nsnapshot assertion for 'greeting' failed in line 12"
+
+	snapshot := parseInstaSnapshot(instaGreetingOutput, panicMessage, root)
+	expected := &ipc.SnapshotMismatch{
+		Name:    "greeting",
+		File:    "src/snapshots/basic__tests__greeting.snap",
+		NewFile: "src/snapshots/basic__tests__greeting.snap.new",
+		Diff:    " greeting:\n-  Hello, world\n+  Hello, world!\n",
+	}
+	if !reflect.DeepEqual(snapshot, expected) {
+		t.Errorf("Expected %+v, got %+v", expected, snapshot)
+	}
+
+	// Under CI insta doesn't write the new value unless told to
+	if snapshot := parseInstaSnapshot(instaGreetingOutput, panicMessage, t.TempDir()); snapshot == nil || snapshot.NewFile != "" {
+		t.Errorf("Expected no new file when insta didn't write one, got %+v", snapshot)
+	}
+
+	// An inline snapshot's new value goes next to the source file
+	inline := strings.Replace(strings.Replace(instaGreetingOutput, "Snapshot file: src/snapshots/basic__tests__greeting.snap\n", "", 1),
+		"\x1b[33mgreeting\x1b[0m", "<inline>", 1)
+	writePendingSnapshot(t, root, "src/.lib.rs.pending-snap")
+	snapshot = parseInstaSnapshot(inline, "snapshot assertion for '' failed in line 12", root)
+	if snapshot == nil || snapshot.Name != "<inline>" || snapshot.File != "" || snapshot.NewFile != "src/.lib.rs.pending-snap" {
+		t.Errorf("Expected the inline snapshot with its pending file, got %+v", snapshot)
+	}
+
+	if snapshot := parseInstaSnapshot(instaGreetingOutput, "assertion failed: x > 0", root); snapshot != nil {
+		t.Errorf("Expected no snapshot for another panic, got %+v", snapshot)
+	}
+}
+
+func TestFindPendingSnapshots(t *testing.T) {
+	root := t.TempDir()
+	started := time.Now()
+	writePendingSnapshot(t, root, "src/snapshots/basic__tests__greeting.snap.new")
+	writePendingSnapshot(t, root, "crates/parser/src/.lib.rs.pending-snap")
+	writePendingSnapshot(t, root, "src/snapshots/basic__tests__farewell.snap")
+	writePendingSnapshot(t, root, "target/package/basic-0.1.0/src/snapshots/basic__tests__greeting.snap.new")
+
+	// Left from an earlier run that was never reviewed
+	stale := writePendingSnapshot(t, root, "src/snapshots/basic__tests__stale.snap.new")
+	old := started.Add(-time.Hour)
+	if err := os.Chtimes(stale, old, old); err != nil {
+		t.Fatal(err)
+	}
+
+	expected := []string{"crates/parser/src/.lib.rs.pending-snap", "src/snapshots/basic__tests__greeting.snap.new"}
+	if got := findPendingSnapshots(root, started); !reflect.DeepEqual(got, expected) {
+		t.Errorf("Expected %v, got %v", expected, got)
+	}
+}
+
+func TestCargoTestDefinition_InstaSnapshot(t *testing.T) {
+	crateDir := t.TempDir()
+	originalDir, err := os.Getwd()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if err := os.Chdir(crateDir); err != nil {
+		t.Fatal(err)
+	}
+	defer func() { _ = os.Chdir(originalDir) }()
+
+	def := NewCargoTestDefinition(createTestLogger(t))
+	def.ModifyCommand([]string{"cargo", "test"}, "", "")
+	writePendingSnapshot(t, crateDir, "src/snapshots/basic__tests__greeting.snap.new")
+
+	stdout, err := json.Marshal(instaGreetingOutput + "thread 'tests::greeting' panicked at /home/dev/.cargo/registry/src/index.crates.io-6f17d22bba15001f/insta-1.39.0/src/runtime.rs:679:13:\nsnapshot assertion for 'greeting' failed in line 12\n")
+	if err != nil {
+		t.Fatal(err)
+	}
+	output := `     Running unittests src/lib.rs (target/debug/deps/basic-0123456789abcdef)
+{"type":"suite","event":"started","test_count":1}
+{"type":"test","event":"started","name":"tests::greeting"}
+{"type":"test","name":"tests::greeting","event":"failed","exec_time":0.001,"stdout":` + string(stdout) + `}
+{"type":"suite","event":"failed","passed":0,"failed":1,"ignored":0,"exec_time":0.01}
+`
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+
+	greeting := tapPayloads(NewTestIPCCapture(ipcPath), "testCase", "testName")["greeting"]
+	errPayload, _ := greeting["error"].(map[string]interface{})
+	if errPayload["failureKind"] != ipc.FailureKindSnapshotMismatch {
+		t.Errorf("Expected the test to fail with %s, got %v", ipc.FailureKindSnapshotMismatch, errPayload["failureKind"])
+	}
+	snapshot, _ := errPayload["snapshot"].(map[string]interface{})
+	if snapshot["name"] != "greeting" || snapshot["newFile"] != "src/snapshots/basic__tests__greeting.snap.new" ||
+		!strings.Contains(snapshot["diff"].(string), "+  Hello, world!") {
+		t.Errorf("Expected the snapshot with its new file and diff, got %v", snapshot)
+	}
+
+	if pending := def.PendingSnapshots(); !reflect.DeepEqual(pending, []string{"src/snapshots/basic__tests__greeting.snap.new"}) {
+		t.Errorf("Expected the snapshot written during the run to be pending, got %v", pending)
+	}
+}
@@ -0,0 +1,311 @@
+package definitions
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"regexp"
+	"sort"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/zk/3pio/internal/ipc"
+	"github.com/zk/3pio/internal/junit"
+	"github.com/zk/3pio/internal/logger"
+)
+
+// junitReportTool is a build tool whose tests are only known from the JUnit XML
+// reports it writes, as its console prints a summary at most
+type junitReportTool struct {
+	name        string   // Runner name, e.g. "maven"
+	executables []string // Commands that run the tool, e.g. "mvn" and its wrapper "mvnw"
+	testTask    func(arg string) bool
+	buildDir    string   // Directory of a module the reports are written under
+	reportDirs  []string // Directories of the build directory with the reports; "*" matches any
+}
+
+var (
+	mavenTool = junitReportTool{
+		name:        "maven",
+		executables: []string{"mvn", "mvnw"},
+		testTask: func(arg string) bool {
+			switch arg {
+			case "test", "integration-test", "verify", "package", "install", "deploy":
+				return true
+			}
+			return strings.HasSuffix(arg, ":test") || strings.HasSuffix(arg, ":integration-test")
+		},
+		buildDir:   "target",
+		reportDirs: []string{"surefire-reports", "failsafe-reports"},
+	}
+	gradleTool = junitReportTool{
+		name:        "gradle",
+		executables: []string{"gradle", "gradlew"},
+		testTask: func(arg string) bool {
+			task := arg[strings.LastIndex(arg, ":")+1:]
+			return task == "test" || task == "check" || task == "build" || strings.HasSuffix(task, "Test")
+		},
+		buildDir:   "build",
+		reportDirs: []string{filepath.Join("test-results", "*")},
+	}
+)
+
+// JUnitReportsDefinition runs a Java build, mvn test or gradle test, whose console
+// has no per-test results to parse. The output is read for compiler errors as the
+// build runs, and once the command exits the JUnit XML
+// reports the run wrote are read: Maven's target/surefire-reports (and
+// failsafe-reports), Gradle's build/test-results/<task>. The tests of each module of a
+// multi-module build are under a group named after the module's directory. A build
+// narrowed with -pl or --tests simply reports the reports it wrote.
+type JUnitReportsDefinition struct {
+	logger    *logger.FileLogger
+	ipcWriter *IPCWriter
+	tool      junitReportTool
+
+	lineLimit // Longest line of output parsed whole (--max-line-length)
+
+	started         time.Time       // Reports older than this are from an earlier build
+	buildErrorsSent map[string]bool // Maven repeats the compiler's errors in its summary
+}
+
+// NewMavenDefinition creates a new Maven runner definition
+func NewMavenDefinition(logger *logger.FileLogger) *JUnitReportsDefinition {
+	return newJUnitReportsDefinition(logger, mavenTool)
+}
+
+// NewGradleDefinition creates a new Gradle runner definition
+func NewGradleDefinition(logger *logger.FileLogger) *JUnitReportsDefinition {
+	return newJUnitReportsDefinition(logger, gradleTool)
+}
+
+func newJUnitReportsDefinition(logger *logger.FileLogger, tool junitReportTool) *JUnitReportsDefinition {
+	return &JUnitReportsDefinition{
+		logger:          logger,
+		tool:            tool,
+		buildErrorsSent: make(map[string]bool),
+	}
+}
+
+// Name returns the name of this test runner
+func (j *JUnitReportsDefinition) Name() string {
+	return j.tool.name
+}
+
+// Detect checks if the command runs the build tool with a task that runs the tests
+func (j *JUnitReportsDefinition) Detect(args []string) bool {
+	if len(args) < 2 {
+		return false
+	}
+	base := filepath.Base(strings.ReplaceAll(args[0], "\\", "/"))
+	base = strings.TrimSuffix(strings.TrimSuffix(base, ".cmd"), ".bat")
+	if !containsName(j.tool.executables, base) {
+		return false
+	}
+	for i := 1; i < len(args); i++ {
+		arg := args[i]
+		if arg == "--tests" || arg == "-pl" || arg == "--projects" {
+			i++ // Its value names tests or modules, not a task
+			continue
+		}
+		if !strings.HasPrefix(arg, "-") && j.tool.testTask(arg) {
+			return true
+		}
+	}
+	return false
+}
+
+func containsName(names []string, name string) bool {
+	for _, n := range names {
+		if n == name {
+			return true
+		}
+	}
+	return false
+}
+
+// ModifyCommand returns the command unchanged, as the build writes its reports anyway,
+// recording when the build starts
+func (j *JUnitReportsDefinition) ModifyCommand(cmd []string, ipcPath, runID string) []string {
+	j.started = time.Now()
+	return append([]string{}, cmd...)
+}
+
+// GetTestFiles returns empty array for dynamic discovery
+func (j *JUnitReportsDefinition) GetTestFiles(args []string) ([]string, error) {
+	return []string{}, nil
+}
+
+// RequiresAdapter returns false as the reports are read directly
+func (j *JUnitReportsDefinition) RequiresAdapter() bool {
+	return false
+}
+
+// ResultsSource says the results are read from the JUnit XML reports once the build exits
+func (j *JUnitReportsDefinition) ResultsSource() string {
+	return "junit_xml"
+}
+
+var (
+	// [ERROR] /src/main/java/com/acme/Cart.java:[12,5] cannot find symbol (Maven)
+	mavenCompileError = regexp.MustCompile(`^\[ERROR\] (.+\.(?:java|kt|groovy|scala)):\[(\d+),(\d+)\] (.+)$`)
+	// /src/main/java/com/acme/Cart.java:12: error: cannot find symbol (javac under Gradle)
+	javacError = regexp.MustCompile(`^(.+\.java):(\d+): error: (.+)$`)
+	// e: file:///src/main/kotlin/Cart.kt:12:5 Unresolved reference: total (kotlinc)
+	kotlinError = regexp.MustCompile(`^e: (?:file://)?(.+\.kts?):(\d+):(\d+) (.+)$`)
+)
+
+// ProcessOutput reads the build's output for compiler errors, and reports the tests of the reports the build wrote once the output ends
+func (j *JUnitReportsDefinition) ProcessOutput(combinedOutput io.Reader, ipcPath string) error {
+	var err error
+	j.ipcWriter, err = NewIPCWriter(ipcPath)
+	if err != nil {
+		return fmt.Errorf("failed to create IPC writer: %w", err)
+	}
+	defer func() {
+		if err := j.ipcWriter.Close(); err != nil {
+			j.logger.Debug("Failed to close IPC writer: %v", err)
+		}
+	}()
+
+	lines := j.newLineReader(combinedOutput)
+	for {
+		line, _, ok := lines.Next()
+		if !ok {
+			break
+		}
+		j.processLine(strings.TrimRight(line, "\r"))
+	}
+	if err := lines.Err(); err != nil {
+		return fmt.Errorf("error reading %s output: %w", j.tool.name, err)
+	}
+
+	// The reports end with the run's runComplete
+	reader := junit.NewReader()
+	for _, report := range j.findReports() {
+		if err := reader.Add(report.path, report.parents); err != nil {
+			j.logger.Error("Failed to read %s: %v", report.path, err)
+		}
+	}
+	for _, event := range reader.Events() {
+		j.sendEvent(event)
+	}
+	return nil
+}
+
+// processLine reads a line of the build's output
+func (j *JUnitReportsDefinition) processLine(line string) {
+	plain := strings.TrimSpace(line)
+	var payload map[string]interface{}
+	if m := mavenCompileError.FindStringSubmatch(plain); m != nil {
+		payload = compileErrorPayload(m[1], m[2], m[3], m[4])
+	} else if m := javacError.FindStringSubmatch(plain); m != nil {
+		payload = compileErrorPayload(m[1], m[2], "", m[3])
+	} else if m := kotlinError.FindStringSubmatch(plain); m != nil {
+		payload = compileErrorPayload(m[1], m[2], m[3], m[4])
+	} else {
+		return
+	}
+	if j.buildErrorsSent[plain] {
+		return
+	}
+	j.buildErrorsSent[plain] = true
+	payload["rendered"] = plain
+
+	j.logger.Debug("Build error: %s", plain)
+	j.sendIPCEvent(map[string]interface{}{
+		"eventType": "buildError",
+		"payload":   payload,
+	})
+}
+
+// compileErrorPayload builds the buildError payload of a compiler error
+func compileErrorPayload(file, line, column, message string) map[string]interface{} {
+	payload := map[string]interface{}{
+		"message": message,
+		"file":    file,
+	}
+	if n, err := strconv.Atoi(line); err == nil {
+		payload["line"] = n
+	}
+	if n, err := strconv.Atoi(column); err == nil {
+		payload["column"] = n
+	}
+	return payload
+}
+
+// junitReport is a report file with the groups of the module it belongs to
+type junitReport struct {
+	path    string
+	parents []string
+}
+
+// findReports finds the reports this build wrote in the modules under the current
+// directory, in the order of their paths. The tests of a module other than the root
+// one are under its directory's group, e.g. "services/api".
+func (j *JUnitReportsDefinition) findReports() []junitReport {
+	var reports []junitReport
+	cutoff := j.started.Truncate(time.Second)
+	err := filepath.WalkDir(".", func(path string, entry fs.DirEntry, err error) error {
+		if err != nil || !entry.IsDir() {
+			return nil
+		}
+		name := entry.Name()
+		if path != "." && (strings.HasPrefix(name, ".") || name == "node_modules") {
+			return filepath.SkipDir
+		}
+		if name != j.tool.buildDir {
+			return nil
+		}
+
+		var parents []string
+		if module := filepath.ToSlash(filepath.Dir(path)); module != "." {
+			parents = []string{module}
+		}
+		for _, dir := range j.tool.reportDirs {
+			files, _ := filepath.Glob(filepath.Join(path, dir, "TEST-*.xml"))
+			sort.Strings(files)
+			for _, file := range files {
+				if info, err := os.Stat(file); err == nil && !info.ModTime().Before(cutoff) {
+					reports = append(reports, junitReport{path: file, parents: parents})
+				}
+			}
+		}
+		// The build directory holds nothing else to report, and may be large
+		return filepath.SkipDir
+	})
+	if err != nil {
+		j.logger.Debug("Failed to look for %s reports: %v", j.tool.name, err)
+	}
+	return reports
+}
+
+// sendEvent sends an event read from a report, as the IPC events written by native
+// definitions are
+func (j *JUnitReportsDefinition) sendEvent(event ipc.Event) {
+	data, err := json.Marshal(event)
+	if err != nil {
+		j.logger.Debug("Failed to encode %s event: %v", event.Type(), err)
+		return
+	}
+	var fields map[string]interface{}
+	if err := json.Unmarshal(data, &fields); err != nil {
+		j.logger.Debug("Failed to encode %s event: %v", event.Type(), err)
+		return
+	}
+	j.sendIPCEvent(fields)
+}
+
+func (j *JUnitReportsDefinition) sendIPCEvent(event map[string]interface{}) {
+	if j.ipcWriter == nil {
+		j.logger.Debug("IPC writer not initialized, skipping event: %v", event)
+		return
+	}
+
+	if err := j.ipcWriter.WriteEvent(event); err != nil {
+		j.logger.Debug("Failed to write IPC event: %v", err)
+	}
+}
@@ -0,0 +1,184 @@
+package definitions
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+	"time"
+)
+
+// A Surefire report of a class with a passed and a failed test
+const surefireCartTest = `<?xml version="1.0" encoding="UTF-8"?>
+<testsuite name="com.acme.CartTest" time="0.05" tests="2" failures="1" errors="0" skipped="0">
+  <testcase name="addsItems" classname="com.acme.CartTest" time="0.01"/>
+  <testcase name="appliesDiscount" classname="com.acme.CartTest" time="0.04">
+    <failure message="expected: &lt;90&gt; but was: &lt;100&gt;" type="org.opentest4j.AssertionFailedError">org.opentest4j.AssertionFailedError: expected: &lt;90&gt; but was: &lt;100&gt;
+	at com.acme.CartTest.appliesDiscount(CartTest.java:21)</failure>
+  </testcase>
+</testsuite>
+`
+
+// writeReport writes a report to dir, creating it
+func writeReport(t *testing.T, dir, name, xml string) string {
+	t.Helper()
+	if err := os.MkdirAll(dir, 0755); err != nil {
+		t.Fatal(err)
+	}
+	path := filepath.Join(dir, name)
+	if err := os.WriteFile(path, []byte(xml), 0644); err != nil {
+		t.Fatal(err)
+	}
+	return path
+}
+
+// runJUnitReports runs a build's output through the definition in a project directory,
+// after writeReports wrote the reports of the build there, and returns its events
+func runJUnitReports(t *testing.T, def *JUnitReportsDefinition, output string, writeReports func(projectDir string)) *TestIPCCapture {
+	t.Helper()
+	projectDir := t.TempDir()
+	originalDir, err := os.Getwd()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if err := os.Chdir(projectDir); err != nil {
+		t.Fatal(err)
+	}
+	t.Cleanup(func() { _ = os.Chdir(originalDir) })
+
+	def.ModifyCommand([]string{def.Name(), "test"}, "", "")
+	if writeReports != nil {
+		writeReports(projectDir)
+	}
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	return NewTestIPCCapture(ipcPath)
+}
+
+func TestJUnitReportsDefinition_Detect(t *testing.T) {
+	maven := NewMavenDefinition(createTestLogger(t))
+	gradle := NewGradleDefinition(createTestLogger(t))
+	for args, want := range map[string]bool{
+		"mvn test":                         true,
+		"./mvnw -pl services/api verify":   true,
+		"mvnw.cmd -B surefire:test":        true,
+		"mvn compile":                      false,
+		"mvn -pl test compile":             false,
+		"./gradlew test":                   false,
+		"gradle test --tests test.Cart":    false,
+		"npx jest":                         false,
+		"/opt/maven/bin/mvn clean install": true,
+	} {
+		if got := maven.Detect(strings.Fields(args)); got != want {
+			t.Errorf("Maven Detect(%q) = %v, expected %v", args, got, want)
+		}
+	}
+	for args, want := range map[string]bool{
+		"./gradlew test":                    true,
+		"gradle :api:test --tests com.Cart": true,
+		"gradlew.bat check":                 true,
+		"./gradlew integrationTest":         true,
+		"./gradlew assemble":                false,
+		"./gradlew assemble --tests test":   false,
+		"mvn test":                          false,
+	} {
+		if got := gradle.Detect(strings.Fields(args)); got != want {
+			t.Errorf("Gradle Detect(%q) = %v, expected %v", args, got, want)
+		}
+	}
+}
+
+func TestJUnitReportsDefinition_Maven(t *testing.T) {
+	capture := runJUnitReports(t, NewMavenDefinition(createTestLogger(t)), "[INFO] Running com.acme.CartTest\n", func(projectDir string) {
+		writeReport(t, filepath.Join(projectDir, "target", "surefire-reports"), "TEST-com.acme.CartTest.xml", surefireCartTest)
+		writeReport(t, filepath.Join(projectDir, "services", "api", "target", "failsafe-reports"), "TEST-com.acme.ApiIT.xml",
+			`<testsuite name="com.acme.ApiIT"><testcase name="serves" classname="com.acme.ApiIT" time="1.5"/></testsuite>`)
+
+		// Left from an earlier build of a module this one skipped
+		stale := writeReport(t, filepath.Join(projectDir, "services", "web", "target", "surefire-reports"), "TEST-com.acme.WebTest.xml",
+			`<testsuite name="com.acme.WebTest"><testcase name="renders" classname="com.acme.WebTest"/></testsuite>`)
+		old := time.Now().Add(-time.Hour)
+		if err := os.Chtimes(stale, old, old); err != nil {
+			t.Fatal(err)
+		}
+	})
+
+	tests := tapPayloads(capture, "testCase", "testName")
+	if len(tests) != 3 {
+		t.Fatalf("Expected the 3 tests of this build's reports, got %d: %v", len(tests), tests)
+	}
+	discount := tests["appliesDiscount"]
+	testErr, _ := discount["error"].(map[string]interface{})
+	if discount["status"] != "FAIL" || testErr["message"] != "expected: <90> but was: <100>" ||
+		!strings.Contains(testErr["stack"].(string), "CartTest.java:21") {
+		t.Errorf("Expected appliesDiscount to fail with its message and stack trace, got %v", discount)
+	}
+	if parents := tests["addsItems"]["parentNames"].([]interface{}); len(parents) != 1 || parents[0] != "com.acme.CartTest" {
+		t.Errorf("Expected the root module's test in its class's group alone, got %v", parents)
+	}
+	serves := tests["serves"]
+	if parents := serves["parentNames"].([]interface{}); len(parents) != 2 || parents[0] != "services/api" || parents[1] != "com.acme.ApiIT" {
+		t.Errorf("Expected the module's test under the module's group, got %v", parents)
+	}
+
+	results := tapPayloads(capture, "testGroupResult", "groupName")
+	if module := results["services/api"]; module["status"] != "PASS" || module["duration"] != 1500.0 {
+		t.Errorf("Expected the module's group to pass in 1.5s, got %v", module)
+	}
+	if complete := capture.GetEventsByType("runComplete"); len(complete) != 1 {
+		t.Errorf("Expected one runComplete, got %d", len(complete))
+	}
+}
+
+func TestJUnitReportsDefinition_Gradle(t *testing.T) {
+	capture := runJUnitReports(t, NewGradleDefinition(createTestLogger(t)), "> Task :app:test\n", func(projectDir string) {
+		writeReport(t, filepath.Join(projectDir, "app", "build", "test-results", "test"), "TEST-com.acme.CartTest.xml", surefireCartTest)
+		writeReport(t, filepath.Join(projectDir, "app", "build", "test-results", "integrationTest"), "TEST-com.acme.ApiIT.xml",
+			`<testsuite name="com.acme.ApiIT"><testcase name="serves" classname="com.acme.ApiIT"/></testsuite>`)
+		writeReport(t, filepath.Join(projectDir, "app", "build", "reports", "tests"), "TEST-ignored.xml",
+			`<testsuite name="ignored"><testcase name="ignored"/></testsuite>`)
+	})
+
+	tests := tapPayloads(capture, "testCase", "testName")
+	if len(tests) != 3 || tests["ignored"] != nil {
+		t.Fatalf("Expected the 3 tests of both test tasks' results, got %v", tests)
+	}
+	if parents := tests["serves"]["parentNames"].([]interface{}); parents[0] != "app" {
+		t.Errorf("Expected the test under its module's group, got %v", parents)
+	}
+	if app := tapPayloads(capture, "testGroupResult", "groupName")["app"]; app["status"] != "FAIL" {
+		t.Errorf("Expected the module's group to fail, got %v", app)
+	}
+}
+
+func TestJUnitReportsDefinition_BuildErrors(t *testing.T) {
+	output := `[INFO] Compiling 3 source files to /src/target/classes
+[ERROR] /src/main/java/com/acme/Cart.java:[12,5] cannot find symbol
+[ERROR] Failed to execute goal org.apache.maven.plugins:maven-compiler-plugin:3.11.0:compile
+[ERROR] /src/main/java/com/acme/Cart.java:[12,5] cannot find symbol
+/src/main/java/com/acme/Price.java:7: error: ';' expected
+e: file:///src/main/kotlin/Tax.kt:3:9 Unresolved reference: rate
+`
+	capture := runJUnitReports(t, NewMavenDefinition(createTestLogger(t)), output, nil)
+
+	errors := capture.GetEventsByType("buildError")
+	if len(errors) != 3 {
+		t.Fatalf("Expected the 3 compile errors once each, got %d: %v", len(errors), errors)
+	}
+	maven := errors[0]["payload"].(map[string]interface{})
+	if maven["file"] != "/src/main/java/com/acme/Cart.java" || maven["line"] != 12.0 || maven["column"] != 5.0 ||
+		maven["message"] != "cannot find symbol" {
+		t.Errorf("Expected Maven's compile error with its location, got %v", maven)
+	}
+	if javac := errors[1]["payload"].(map[string]interface{}); javac["line"] != 7.0 || javac["column"] != nil {
+		t.Errorf("Expected javac's error with its line alone, got %v", javac)
+	}
+	if kotlin := errors[2]["payload"].(map[string]interface{}); kotlin["file"] != "/src/main/kotlin/Tax.kt" || kotlin["message"] != "Unresolved reference: rate" {
+		t.Errorf("Expected kotlinc's error with its file, got %v", kotlin)
+	}
+	if tests := capture.GetEventsByType("testCase"); len(tests) != 0 {
+		t.Errorf("Expected no tests without reports, got %v", tests)
+	}
+}
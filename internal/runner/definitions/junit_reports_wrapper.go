@@ -0,0 +1,59 @@
+package definitions
+
+import (
+	"io"
+)
+
+// JUnitReportsWrapper wraps JUnitReportsDefinition to implement the Definition interface from runner package
+type JUnitReportsWrapper struct {
+	*JUnitReportsDefinition
+}
+
+// NewJUnitReportsWrapper creates a new wrapper for the Maven or Gradle runner
+func NewJUnitReportsWrapper(impl *JUnitReportsDefinition) *JUnitReportsWrapper {
+	return &JUnitReportsWrapper{JUnitReportsDefinition: impl}
+}
+
+// Matches checks if this runner can handle the given command
+func (d *JUnitReportsWrapper) Matches(command []string) bool {
+	return d.Detect(command)
+}
+
+// GetTestFiles returns list of test files (empty for dynamic discovery)
+func (d *JUnitReportsWrapper) GetTestFiles(args []string) ([]string, error) {
+	return d.JUnitReportsDefinition.GetTestFiles(args)
+}
+
+// BuildCommand returns the command unchanged
+func (d *JUnitReportsWrapper) BuildCommand(args []string, adapterPath string) []string {
+	// The JUnit XML reports are read directly, no adapter needed
+	return d.ModifyCommand(args, "", "")
+}
+
+// GetAdapterFileName returns empty as Maven and Gradle don't use an adapter
+func (d *JUnitReportsWrapper) GetAdapterFileName() string {
+	return ""
+}
+
+// InterpretExitCode maps exit codes to success/failure
+func (d *JUnitReportsWrapper) InterpretExitCode(code int) string {
+	if code == 0 {
+		return "success"
+	}
+	return "failure"
+}
+
+// IsNative returns true as the JUnit XML reports are processed directly
+func (d *JUnitReportsWrapper) IsNative() bool {
+	return true
+}
+
+// GetNativeDefinition returns the underlying Maven or Gradle definition
+func (d *JUnitReportsWrapper) GetNativeDefinition() interface{} {
+	return d.JUnitReportsDefinition
+}
+
+// ProcessOutput processes the build's output
+func (d *JUnitReportsWrapper) ProcessOutput(stdout io.Reader, ipcPath string) error {
+	return d.JUnitReportsDefinition.ProcessOutput(stdout, ipcPath)
+}
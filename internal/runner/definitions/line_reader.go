@@ -0,0 +1,105 @@
+package definitions
+
+import (
+	"bytes"
+	"io"
+)
+
+// DefaultMaxLineLength is the longest line of runner output parsed whole, unless
+// --max-line-length sets another
+const DefaultMaxLineLength = 4 * 1024 * 1024
+
+// LineTruncatedMarker ends a line of runner output that was cut at the maximum line
+// length. The whole line is still in output.log.
+const LineTruncatedMarker = " … [line truncated by 3pio, full line in output.log]"
+
+// lineReaderChunkSize is how much runner output is read at a time
+const lineReaderChunkSize = 256 * 1024
+
+// lineLimit holds the maximum line length of a native definition's output parser
+type lineLimit struct {
+	maxLineLength int // 0 uses DefaultMaxLineLength
+}
+
+// SetMaxLineLength sets how much of each line of output is parsed (--max-line-length)
+func (l *lineLimit) SetMaxLineLength(n int) {
+	l.maxLineLength = n
+}
+
+// newLineReader returns a reader of r's lines cut at the definition's maximum length
+func (l *lineLimit) newLineReader(r io.Reader) *lineReader {
+	maxLength := l.maxLineLength
+	if maxLength <= 0 {
+		maxLength = DefaultMaxLineLength
+	}
+	return &lineReader{r: r, maxLength: maxLength, chunk: make([]byte, lineReaderChunkSize)}
+}
+
+// lineReader splits runner output into lines as it arrives, reading it in fixed-size
+// chunks. A line longer than maxLength is cut there and ends with LineTruncatedMarker;
+// the rest of it is skipped without being buffered, so a test that prints a huge blob
+// on one line can't stall the parser or grow the heap with it.
+type lineReader struct {
+	r         io.Reader
+	maxLength int
+	chunk     []byte // Read buffer
+	buf       []byte // Part of chunk not split into lines yet
+	line      []byte // Current line so far, at most maxLength bytes
+	cut       bool   // The current line reached maxLength
+	err       error  // Error that ended the output
+}
+
+// Next returns the next line without its newline, whether it was cut, and false once
+// the output has ended. The last line is returned even without a newline.
+func (lr *lineReader) Next() (string, bool, bool) {
+	for {
+		if i := bytes.IndexByte(lr.buf, '\n'); i >= 0 {
+			lr.add(lr.buf[:i])
+			lr.buf = lr.buf[i+1:]
+			return lr.take()
+		}
+		lr.add(lr.buf)
+		lr.buf = nil
+		if lr.err != nil {
+			if len(lr.line) == 0 && !lr.cut {
+				return "", false, false
+			}
+			return lr.take()
+		}
+		n, err := lr.r.Read(lr.chunk)
+		lr.buf = lr.chunk[:n]
+		lr.err = err
+	}
+}
+
+// Err returns the error that ended the output, or nil at the end of the output
+func (lr *lineReader) Err() error {
+	if lr.err == io.EOF {
+		return nil
+	}
+	return lr.err
+}
+
+// add appends part of the current line, up to maxLength
+func (lr *lineReader) add(p []byte) {
+	if lr.cut {
+		return
+	}
+	if room := lr.maxLength - len(lr.line); len(p) > room {
+		lr.line = append(lr.line, p[:room]...)
+		lr.cut = true
+		return
+	}
+	lr.line = append(lr.line, p...)
+}
+
+// take returns the current line and starts the next one
+func (lr *lineReader) take() (string, bool, bool) {
+	line, cut := string(lr.line), lr.cut
+	if cut {
+		line += LineTruncatedMarker
+	}
+	lr.line = lr.line[:0]
+	lr.cut = false
+	return line, cut, true
+}
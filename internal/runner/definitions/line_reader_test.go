@@ -0,0 +1,121 @@
+package definitions
+
+import (
+	"errors"
+	"io"
+	"path/filepath"
+	"runtime"
+	"strings"
+	"testing"
+	"testing/iotest"
+)
+
+func TestLineReader(t *testing.T) {
+	input := "first\n\nthis line is too long\nafter\r\nlast"
+	limit := lineLimit{maxLineLength: 8}
+	// One byte at a time, so every line is split across reads
+	lines := limit.newLineReader(iotest.OneByteReader(strings.NewReader(input)))
+
+	type line struct {
+		text string
+		cut  bool
+	}
+	want := []line{
+		{"first", false},
+		{"", false},
+		{"this lin" + LineTruncatedMarker, true},
+		{"after\r", false},
+		{"last", false},
+	}
+	var got []line
+	for {
+		text, cut, ok := lines.Next()
+		if !ok {
+			break
+		}
+		got = append(got, line{text, cut})
+	}
+	if len(got) != len(want) {
+		t.Fatalf("Got lines %q, want %q", got, want)
+	}
+	for i := range want {
+		if got[i] != want[i] {
+			t.Errorf("Line %d = %q (cut %v), want %q (cut %v)", i, got[i].text, got[i].cut, want[i].text, want[i].cut)
+		}
+	}
+	if err := lines.Err(); err != nil {
+		t.Errorf("Expected no error at the end of the output, got %v", err)
+	}
+
+	// A read error ends the output after the line read so far
+	failing := limit.newLineReader(io.MultiReader(strings.NewReader("partial"), iotest.ErrReader(errors.New("pipe broke"))))
+	if text, _, ok := failing.Next(); !ok || text != "partial" {
+		t.Errorf("Expected the partial line, got %q (%v)", text, ok)
+	}
+	if _, _, ok := failing.Next(); ok || failing.Err() == nil || failing.Err().Error() != "pipe broke" {
+		t.Errorf("Expected the end of the output with the read error, got %v", failing.Err())
+	}
+}
+
+// repeatReader reads n copies of a byte without holding them in memory
+type repeatReader struct {
+	b byte
+	n int
+}
+
+func (r *repeatReader) Read(p []byte) (int, error) {
+	if r.n == 0 {
+		return 0, io.EOF
+	}
+	n := min(len(p), r.n)
+	for i := range p[:n] {
+		p[i] = r.b
+	}
+	r.n -= n
+	return n, nil
+}
+
+// TestCargoTestDefinition_64MBLine pipes a 64MB line, as from a test that printed a
+// huge JSON blob, through the cargo parser: the memory used stays far below the line's
+// size and the tests after it are still reported
+func TestCargoTestDefinition_64MBLine(t *testing.T) {
+	def := NewCargoTestDefinition(createTestLogger(t))
+
+	output := io.MultiReader(
+		strings.NewReader("     Running unittests src/lib.rs (target/debug/deps/rust_basic-0123456789abcdef)\n"+
+			`{"type":"suite","event":"started","test_count":2}`+"\n"+
+			`{"type":"test","event":"started","name":"tests::test_dump"}`+"\n"+
+			`{"type":"test","name":"tests::test_dump","event":"failed","exec_time":1.5,"stdout":"{\"blob\":\"`),
+		&repeatReader{b: 'x', n: 64 * 1024 * 1024},
+		strings.NewReader(`\"}"}`+"\n"+
+			`{"type":"test","event":"started","name":"tests::test_add"}`+"\n"+
+			`{"type":"test","name":"tests::test_add","event":"ok","exec_time":0.001}`+"\n"+
+			`{"type":"suite","event":"failed","passed":1,"failed":1,"ignored":0,"exec_time":1.6}`+"\n"),
+	)
+
+	var before, after runtime.MemStats
+	runtime.GC()
+	runtime.ReadMemStats(&before)
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(output, ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	runtime.ReadMemStats(&after)
+	if allocated := after.TotalAlloc - before.TotalAlloc; allocated > 48<<20 {
+		t.Errorf("Expected the line not to be buffered whole, %d MB were allocated", allocated>>20)
+	}
+
+	// The cut event still reports its test, and the run finishes
+	capture := NewTestIPCCapture(ipcPath)
+	statuses := make(map[string]interface{})
+	for _, e := range capture.GetEventsByType("testCase") {
+		payload := e["payload"].(map[string]interface{})
+		statuses[payload["testName"].(string)] = payload["status"]
+	}
+	if statuses["test_dump"] != "FAIL" || statuses["test_add"] != "PASS" {
+		t.Errorf("Expected test_dump to fail and test_add to pass, got %v", statuses)
+	}
+	if len(capture.GetEventsByType("testGroupResult")) == 0 {
+		t.Error("Expected the group to finish")
+	}
+}
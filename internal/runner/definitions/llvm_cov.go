@@ -0,0 +1,100 @@
+package definitions
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/zk/3pio/internal/coverage"
+)
+
+// cargo llvm-cov builds the tests instrumented and runs them through cargo test or
+// cargo nextest, whose output is parsed as usual. Once they pass it writes the
+// coverage report the command asks for, to stdout unless --output-path names a file:
+//
+//	cargo llvm-cov --lcov --output-path cov.lcov test
+//	cargo llvm-cov nextest --json --summary-only --output-path cov.json
+
+// llvmCovReport returns the coverage report file a cargo llvm-cov command writes and
+// its format, or an error saying why there is none 3pio can read. Both are "" when
+// the command writes no report (--no-report).
+func llvmCovReport(args []string) (path, format string, err error) {
+	var other string
+	for i := 0; i < len(args) && args[i] != "--"; i++ {
+		flag, value, hasValue := strings.Cut(args[i], "=")
+		switch flag {
+		case "--no-report":
+			return "", "", nil
+		case "--lcov":
+			format = coverage.FormatLCOV
+		case "--json":
+			format = coverage.FormatJSON
+		case "--html", "--open", "--text", "--cobertura", "--codecov":
+			other = flag
+		case "--output-path":
+			if !hasValue && i+1 < len(args) {
+				i++
+				value = args[i]
+			}
+			path = value
+		}
+	}
+
+	switch {
+	case format == "" && other != "":
+		return "", "", fmt.Errorf("cargo llvm-cov wrote a %s report, which 3pio doesn't read; give --lcov or --json with --output-path <file> for coverage in the report", strings.TrimPrefix(other, "--"))
+	case format == "":
+		return "", "", fmt.Errorf("cargo llvm-cov printed its coverage summary to the console; give --lcov --output-path <file> or --json --output-path <file> for coverage in the report")
+	case path == "":
+		return "", "", fmt.Errorf("cargo llvm-cov printed its %s report to stdout; give --output-path <file> for coverage in the report", format)
+	}
+	return path, format, nil
+}
+
+// readLLVMCovCoverage reads the coverage report a cargo llvm-cov command run in dir
+// wrote since started, keeping the files under root. It returns nil for other commands
+// and for those that write no report.
+func readLLVMCovCoverage(args []string, dir, root string, started time.Time) (*coverage.Summary, error) {
+	if !UsesLLVMCov(args) {
+		return nil, nil
+	}
+	path, format, err := llvmCovReport(args)
+	if err != nil || path == "" {
+		return nil, err
+	}
+	if dir != "" && !filepath.IsAbs(path) {
+		path = filepath.Join(dir, path)
+	}
+
+	if info, err := os.Stat(path); err != nil || info.ModTime().Before(started.Truncate(time.Second)) {
+		return nil, fmt.Errorf("coverage report %s wasn't written by this run; cargo llvm-cov writes none when a test fails, unless given --ignore-run-fail", path)
+	}
+	if root == "" {
+		root = dir
+	}
+	return coverage.Read(path, format, root)
+}
+
+// Coverage reads the coverage report of a cargo llvm-cov run, or returns nil for
+// other runs. An error says why a run that measured coverage has none to report.
+func (c *CargoTestDefinition) Coverage() (*coverage.Summary, error) {
+	c.mu.RLock()
+	cargoArgs, root, started := c.cargoArgs, c.workspaceRoot, c.started
+	c.mu.RUnlock()
+	return readLLVMCovCoverage(cargoArgs, c.workingDir(), root, started)
+}
+
+// Coverage reads the coverage report of a cargo llvm-cov nextest run, or returns nil
+// for other runs. An error says why a run that measured coverage has none to report.
+func (n *NextestDefinition) Coverage() (*coverage.Summary, error) {
+	n.mu.RLock()
+	cmd, started := n.command, n.started
+	n.mu.RUnlock()
+	dir, root := n.workingDir(), ""
+	if dir != "" {
+		root = findCargoWorkspaceRoot(dir)
+	}
+	return readLLVMCovCoverage(cmd, dir, root, started)
+}
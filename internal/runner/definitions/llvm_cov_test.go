@@ -0,0 +1,122 @@
+package definitions
+
+import (
+	"os"
+	"path/filepath"
+	"reflect"
+	"slices"
+	"strings"
+	"testing"
+	"time"
+)
+
+func TestCargoSubcommand_LLVMCov(t *testing.T) {
+	tests := []struct {
+		args       []string
+		subcommand string
+		llvmCov    bool
+	}{
+		{[]string{"cargo", "llvm-cov", "--lcov", "--output-path", "cov.lcov", "test"}, "test", true},
+		{[]string{"cargo", "llvm-cov", "--json", "--output-path=cov.json", "nextest", "--workspace"}, "nextest", true},
+		{[]string{"cargo", "+nightly", "llvm-cov", "--lcov"}, "test", true},
+		{[]string{"cargo", "llvm-cov", "report", "--lcov"}, "report", true},
+		{[]string{"cargo", "test", "llvm-cov"}, "test", false},
+	}
+	for _, tt := range tests {
+		if got := CargoSubcommand(tt.args); got != tt.subcommand {
+			t.Errorf("CargoSubcommand(%v): expected %q, got %q", tt.args, tt.subcommand, got)
+		}
+		if got := UsesLLVMCov(tt.args); got != tt.llvmCov {
+			t.Errorf("UsesLLVMCov(%v): expected %v, got %v", tt.args, tt.llvmCov, got)
+		}
+	}
+
+	cargo, nextest := NewCargoTestDefinition(createTestLogger(t)), NewNextestDefinition(createTestLogger(t))
+	if cmd := []string{"cargo", "llvm-cov", "--lcov", "--output-path", "cov.lcov", "test"}; !cargo.Detect(cmd) || nextest.Detect(cmd) {
+		t.Error("Expected cargo llvm-cov test to be detected as cargo test")
+	}
+	if cmd := []string{"cargo", "llvm-cov", "nextest"}; cargo.Detect(cmd) || !nextest.Detect(cmd) {
+		t.Error("Expected cargo llvm-cov nextest to be detected as cargo nextest")
+	}
+	if cargo.Detect([]string{"cargo", "llvm-cov", "report"}) {
+		t.Error("Expected cargo llvm-cov report not to be detected, as it runs no tests")
+	}
+	if filters := libtestNameFilters([]string{"cargo", "llvm-cov", "--lcov", "--output-path", "cov.lcov", "test", "parser"}, nil); !reflect.DeepEqual(filters, []string{"parser"}) {
+		t.Errorf("Expected only the test name filter, got %v", filters)
+	}
+	if modified := nextest.ModifyCommand([]string{"cargo", "llvm-cov", "nextest"}, "", ""); slices.Contains(modified, "run") {
+		t.Errorf("Expected no run subcommand added under cargo llvm-cov, got %v", modified)
+	}
+}
+
+func TestLLVMCovReport(t *testing.T) {
+	tests := []struct {
+		args   []string
+		path   string
+		format string
+		err    string
+	}{
+		{[]string{"cargo", "llvm-cov", "--lcov", "--output-path", "cov.lcov", "test"}, "cov.lcov", "lcov", ""},
+		{[]string{"cargo", "llvm-cov", "nextest", "--json", "--summary-only", "--output-path=cov.json"}, "cov.json", "json", ""},
+		{[]string{"cargo", "llvm-cov", "--no-report", "test"}, "", "", ""},
+		{[]string{"cargo", "llvm-cov", "--lcov", "test"}, "", "", "printed its lcov report to stdout"},
+		{[]string{"cargo", "llvm-cov", "test"}, "", "", "printed its coverage summary to the console"},
+		{[]string{"cargo", "llvm-cov", "--html", "test"}, "", "", "wrote a html report, which 3pio doesn't read"},
+		{[]string{"cargo", "llvm-cov", "test", "--", "--lcov", "--output-path", "cov.lcov"}, "", "", "printed its coverage summary"},
+	}
+	for _, tt := range tests {
+		path, format, err := llvmCovReport(tt.args)
+		if path != tt.path || format != tt.format {
+			t.Errorf("llvmCovReport(%v): expected %q in %q format, got %q in %q", tt.args, tt.path, tt.format, path, format)
+		}
+		if (err == nil) != (tt.err == "") || (err != nil && !strings.Contains(err.Error(), tt.err)) {
+			t.Errorf("llvmCovReport(%v): expected error %q, got %v", tt.args, tt.err, err)
+		}
+	}
+}
+
+func TestCargoTestDefinition_Coverage(t *testing.T) {
+	originalDir, err := os.Getwd()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if err := os.Chdir(t.TempDir()); err != nil {
+		t.Fatal(err)
+	}
+	defer func() { _ = os.Chdir(originalDir) }()
+	crateDir, err := os.Getwd()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	def := NewCargoTestDefinition(createTestLogger(t))
+	def.ModifyCommand([]string{"cargo", "llvm-cov", "--lcov", "--output-path", "cov.lcov", "test"}, "", "")
+	if _, err := def.Coverage(); err == nil || !strings.Contains(err.Error(), "unless given --ignore-run-fail") {
+		t.Errorf("Expected an error for the missing report, got %v", err)
+	}
+
+	tracefile := "SF:" + filepath.Join(crateDir, "src", "lib.rs") + "\nFNF:2\nFNH:1\nLF:8\nLH:6\nend_of_record\n"
+	if err := os.WriteFile("cov.lcov", []byte(tracefile), 0644); err != nil {
+		t.Fatal(err)
+	}
+	summary, err := def.Coverage()
+	if err != nil {
+		t.Fatalf("Coverage failed: %v", err)
+	}
+	if summary.Report != "cov.lcov" || summary.Lines.Percent != 75 || len(summary.Files) != 1 || summary.Files[0].Path != "src/lib.rs" {
+		t.Errorf("Expected 75%% of the lines of src/lib.rs from cov.lcov, got %+v", summary)
+	}
+
+	// A report left over from an earlier run isn't this run's
+	old := time.Now().Add(-time.Hour)
+	if err := os.Chtimes("cov.lcov", old, old); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := def.Coverage(); err == nil || !strings.Contains(err.Error(), "wasn't written by this run") {
+		t.Errorf("Expected an error for the stale report, got %v", err)
+	}
+
+	if summary, err := NewCargoTestDefinition(createTestLogger(t)).Coverage(); summary != nil || err != nil {
+		t.Errorf("Expected no coverage for a run without cargo llvm-cov, got %+v (%v)", summary, err)
+	}
+}
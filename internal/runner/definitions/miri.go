@@ -0,0 +1,40 @@
+package definitions
+
+import (
+	"regexp"
+	"strings"
+)
+
+// miri reports undefined behavior in the test it interprets with a compiler-style
+// diagnostic, then aborts the test binary, so libtest never reports the test:
+//
+//	error: Undefined Behavior: memory access failed: attempting to access 4 bytes, but got alloc912+0x4 which is at or beyond the end of the allocation of size 4 bytes
+//	 --> src/lib.rs:4:14
+//	  |
+//	4 |     unsafe { *values.as_ptr().add(index) }
+//	  |              ^^^^^^^^^^^^^^^^^^^^^^^^^^^ Undefined Behavior occurred here
+//	  |
+//	  = help: this indicates a bug in the program: it performed an invalid operation, and caused Undefined Behavior
+//	  = note: BACKTRACE on thread `tests::reads_past_the_end`:
+//	  = note: inside `rust_miri::read_at` at src/lib.rs:4:14: 4:41
+//	...
+//	error: aborting due to 1 previous error
+const (
+	miriUndefinedBehaviorHeader = "error: Undefined Behavior"
+	miriAbortNote               = "error: aborting due to"
+)
+
+// miriThreadRegex matches the thread of miri's backtrace, which libtest names after the test
+var miriThreadRegex = regexp.MustCompile("BACKTRACE on thread `([^`]+)`")
+
+// takeMiriDiagnostic returns miri's report of undefined behavior in the test binary
+// that stopped, with the thread it happened on, and forgets it. The report is ""
+// when miri found none. Caller must hold c.mu.
+func (c *CargoTestDefinition) takeMiriDiagnostic() (diagnostic, thread string) {
+	diagnostic = strings.TrimRight(strings.Join(c.miriDiagnostic, "\n"), "\n")
+	c.miriDiagnostic, c.miriReading = nil, false
+	if m := miriThreadRegex.FindStringSubmatch(diagnostic); m != nil {
+		thread = m[1]
+	}
+	return diagnostic, thread
+}
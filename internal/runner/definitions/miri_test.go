@@ -0,0 +1,102 @@
+package definitions
+
+import (
+	"path/filepath"
+	"reflect"
+	"slices"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/internal/ipc"
+	"github.com/zk/3pio/internal/runinfo"
+)
+
+func TestCargoSubcommand_Miri(t *testing.T) {
+	tests := []struct {
+		args       []string
+		subcommand string
+		miri       bool
+	}{
+		{[]string{"cargo", "miri", "test"}, "test", true},
+		{[]string{"cargo", "+nightly", "miri", "test", "--lib"}, "test", true},
+		{[]string{"cargo", "miri"}, "", false},
+		{[]string{"cargo", "test", "miri"}, "test", false},
+	}
+	for _, tt := range tests {
+		if got := CargoSubcommand(tt.args); got != tt.subcommand {
+			t.Errorf("CargoSubcommand(%v): expected %q, got %q", tt.args, tt.subcommand, got)
+		}
+		if got := UsesMiri(tt.args); got != tt.miri {
+			t.Errorf("UsesMiri(%v): expected %v, got %v", tt.args, tt.miri, got)
+		}
+	}
+
+	if !NewCargoTestDefinition(createTestLogger(t)).Detect([]string{"cargo", "+nightly", "miri", "test"}) {
+		t.Error("Expected cargo miri test to be detected as cargo test")
+	}
+	if filters := libtestNameFilters([]string{"cargo", "+nightly", "miri", "test", "parser"}, nil); !reflect.DeepEqual(filters, []string{"parser"}) {
+		t.Errorf("Expected only the test name filter, got %v", filters)
+	}
+}
+
+func TestCargoTestDefinition_MiriRunnerInfo(t *testing.T) {
+	t.Setenv("RUSTC", "")
+	info := NewCargoTestDefinition(createTestLogger(t)).RunnerInfo([]string{"cargo", "+nightly", "miri", "test"})
+
+	miri := info.Probes[len(info.Probes)-1]
+	expected := runinfo.Probe{Tool: "miri", Args: []string{"cargo", "+nightly", "miri", "--version"}}
+	if !reflect.DeepEqual(miri, expected) {
+		t.Errorf("Expected %+v, got %+v", expected, miri)
+	}
+	if !slices.Contains(info.EnvVars, "MIRIFLAGS") || slices.Contains(rustEnvVars, "MIRIFLAGS") {
+		t.Errorf("Expected MIRIFLAGS to be recorded for the miri run only, got %v", info.EnvVars)
+	}
+}
+
+func TestCargoTestDefinition_MiriUndefinedBehavior(t *testing.T) {
+	// miri stops the binary in the second test while the first is still running
+	output := `     Running unittests src/lib.rs (target/miri/x86_64-unknown-linux-gnu/debug/deps/rust_miri-0123456789abcdef)
+{"type":"suite","event":"started","test_count":3}
+{"type":"test","event":"started","name":"tests::reads_past_the_end"}
+{"type":"test","event":"started","name":"tests::reads_in_bounds"}
+error: Undefined Behavior: memory access failed: attempting to access 4 bytes, but got alloc912+0x4 which is at or beyond the end of the allocation of size 4 bytes
+ --> src/lib.rs:4:14
+  |
+4 |     unsafe { *values.as_ptr().add(index) }
+  |              ^^^^^^^^^^^^^^^^^^^^^^^^^^^ Undefined Behavior occurred here
+  |
+  = help: this indicates a bug in the program: it performed an invalid operation, and caused Undefined Behavior
+  = note: BACKTRACE on thread ` + "`tests::reads_past_the_end`" + `:
+  = note: inside ` + "`rust_miri::read_at`" + ` at src/lib.rs:4:14: 4:41
+
+note: some details are omitted, run with ` + "`MIRIFLAGS=-Zmiri-backtrace=full`" + ` for a verbose backtrace
+
+error: aborting due to 1 previous error
+
+error: test failed, to rerun pass ` + "`--lib`" + `
+
+Caused by:
+  process didn't exit successfully: ` + "`/home/dev/.rustup/toolchains/nightly-x86_64-unknown-linux-gnu/bin/cargo-miri runner target/miri/x86_64-unknown-linux-gnu/debug/deps/rust_miri-0123456789abcdef`" + ` (exit status: 1)
+`
+	def := NewCargoTestDefinition(createTestLogger(t))
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+
+	results := tapPayloads(NewTestIPCCapture(ipcPath), "testCase", "testName")
+	errPayload, _ := results["reads_past_the_end"]["error"].(map[string]interface{})
+	if errPayload["failureKind"] != ipc.FailureKindUndefinedBehavior {
+		t.Fatalf("Expected the test on miri's thread to fail with %s, got %v", ipc.FailureKindUndefinedBehavior, results["reads_past_the_end"])
+	}
+	if !strings.HasPrefix(errPayload["message"].(string), "Undefined Behavior: memory access failed") {
+		t.Errorf("Expected miri's error as the message, got %q", errPayload["message"])
+	}
+	diagnostic, _ := errPayload["undefinedBehavior"].(string)
+	if !strings.Contains(diagnostic, " --> src/lib.rs:4:14\n") || !strings.Contains(diagnostic, "-Zmiri-backtrace=full") || strings.Contains(diagnostic, "aborting") {
+		t.Errorf("Expected miri's whole diagnostic without its abort note, got %q", diagnostic)
+	}
+	if status := results["reads_in_bounds"]["status"]; status != "SKIP" {
+		t.Errorf("Expected the test that was still running to be skipped, got %v", status)
+	}
+}
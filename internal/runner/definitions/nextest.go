@@ -1,29 +1,54 @@
 package definitions
 
 import (
-	"bufio"
 	"encoding/json"
 	"fmt"
 	"io"
+	"regexp"
+	"strconv"
 	"strings"
 	"sync"
 	"time"
 
+	"github.com/zk/3pio/internal/ipc"
 	"github.com/zk/3pio/internal/logger"
 )
 
+// Exit codes used by cargo-nextest
+// https://docs.rs/nextest-metadata/latest/nextest_metadata/enum.NextestExitCode.html
+const (
+	NextestExitTestRunFailed = 100 // One or more tests failed
+	NextestExitBuildFailed   = 101 // Building the test binaries failed
+)
+
+// nextestStatusLineRegex matches nextest's human-readable status lines on stderr,
+// e.g. "        SLOW [> 60.000s] my-crate tests::test_slow". These carry the SLOW
+// and LEAK statuses that have no libtest-json equivalent.
+var nextestStatusLineRegex = regexp.MustCompile(`^\s*(SLOW|LEAK|LEAK-FAIL)\s+\[[^\]]*\]\s+(\S+)\s+(\S+)\s*$`)
+
 // NextestDefinition implements support for cargo-nextest runner
 type NextestDefinition struct {
 	logger    *logger.FileLogger
 	mu        sync.RWMutex
 	ipcWriter *IPCWriter
 
+	lineLimit     // Longest line of output parsed whole (--max-line-length)
+	workDirectory // Directory cargo nextest runs in
+
 	// Workspace and package tracking
 	workspaceName    string                              // Name of workspace if detected
 	packageGroups    map[string]*NextestPackageGroupInfo // Map of package name to group info
 	discoveredGroups map[string]bool                     // Track discovered groups to avoid duplicates
 	groupStarts      map[string]bool                     // Track started groups
 	testStates       map[string]*NextestTestState        // Track test state
+	testResults      map[string]*NextestTestResult       // Reported tests by normalized name, for retries and status lines
+	listedTests      map[string]ListedTest               // Tests from the preflight list by normalized name
+	interrupted      bool                                // 3pio stopped the run, so unrun listed tests stay pending
+
+	// The command as given and when it was first built, for the coverage report of
+	// cargo llvm-cov nextest
+	command []string
+	started time.Time
 }
 
 // NextestPackageGroupInfo tracks information for a package group
@@ -41,6 +66,26 @@ type NextestTestInfo struct {
 	Duration float64
 }
 
+// NextestTestResult tracks every attempt of a test, so retries update
+// a single test case instead of reporting duplicates
+type NextestTestResult struct {
+	TestName   string
+	Parents    []string
+	Package    string
+	Attempts   []NextestAttempt
+	Slow       bool // Reported as SLOW by nextest (or libtest timeout warning)
+	Leaked     bool // Reported as LEAK or LEAK-FAIL by nextest
+	groupIndex int  // Index of this test in its package group's Tests
+}
+
+// NextestAttempt is a single execution of a test
+type NextestAttempt struct {
+	Status   string
+	Duration float64 // Duration in milliseconds
+	Stdout   string
+	Stderr   string
+}
+
 // NextestTestState tracks the state of a running test
 type NextestTestState struct {
 	Name      string
@@ -70,6 +115,8 @@ func NewNextestDefinition(logger *logger.FileLogger) *NextestDefinition {
 		discoveredGroups: make(map[string]bool),
 		groupStarts:      make(map[string]bool),
 		testStates:       make(map[string]*NextestTestState),
+		testResults:      make(map[string]*NextestTestResult),
+		listedTests:      make(map[string]ListedTest),
 	}
 }
 
@@ -104,7 +151,8 @@ func (n *NextestDefinition) Detect(args []string) bool {
 		return true
 	}
 
-	return false
+	// Check for "cargo llvm-cov [options] nextest", which runs cargo nextest run with coverage
+	return UsesLLVMCov(args) && CargoSubcommand(args) == "nextest"
 }
 
 // ModifyCommand adds JSON output flags to cargo nextest command
@@ -114,8 +162,8 @@ func (n *NextestDefinition) ModifyCommand(cmd []string, ipcPath, runID string) [
 	// Copy original command
 	result = append(result, cmd...)
 
-	// If "run" is not present, add it
-	hasRun := false
+	// If "run" is not present, add it; cargo llvm-cov nextest runs it itself
+	hasRun := UsesLLVMCov(cmd)
 	for _, arg := range cmd {
 		if arg == "run" {
 			hasRun = true
@@ -129,9 +177,85 @@ func (n *NextestDefinition) ModifyCommand(cmd []string, ipcPath, runID string) [
 	// Add JSON output format
 	result = append(result, "--message-format", "libtest-json")
 
+	n.mu.Lock()
+	n.command = cmd
+	if n.started.IsZero() {
+		n.started = time.Now()
+	}
+	n.mu.Unlock()
+
 	return result
 }
 
+// RerunCommand narrows a cargo nextest command down to the given failed tests with a
+// filterset expression matching each test's name exactly
+func (n *NextestDefinition) RerunCommand(cmd []string, tests []ipc.FailedTest) ([]string, error) {
+	paths := rustTestPaths(tests)
+	if len(paths) == 0 {
+		return nil, fmt.Errorf("the failed tests are all doctests, which nextest doesn't run")
+	}
+	exprs := make([]string, len(paths))
+	for i, path := range paths {
+		exprs[i] = "test(=" + path + ")"
+	}
+
+	// -E belongs to "run", and must come before the -- that starts the test binary arguments
+	args := withNextestRun(cmd)
+	separator := len(args)
+	for i, arg := range args {
+		if arg == "--" {
+			separator = i
+			break
+		}
+	}
+
+	result := make([]string, 0, len(args)+2)
+	result = append(result, args[:separator]...)
+	result = append(result, "-E", strings.Join(exprs, " | "))
+	return append(result, args[separator:]...), nil
+}
+
+// RetriesCommand has nextest retry failed tests itself (--retries), so every attempt
+// is reported as it runs. A --retries the user already passed is kept.
+func (n *NextestDefinition) RetriesCommand(cmd []string, retries int) []string {
+	args := withNextestRun(cmd)
+	separator := len(args)
+	for i, arg := range args {
+		if arg == "--" {
+			separator = i
+			break
+		}
+		if arg == "--retries" || strings.HasPrefix(arg, "--retries=") {
+			return cmd
+		}
+	}
+
+	result := make([]string, 0, len(args)+2)
+	result = append(result, args[:separator]...)
+	result = append(result, "--retries", strconv.Itoa(retries))
+	return append(result, args[separator:]...)
+}
+
+// withNextestRun adds the "run" subcommand after "nextest" when the command relies on
+// it being the default, so flags of "run" can be appended
+func withNextestRun(cmd []string) []string {
+	args := make([]string, 0, len(cmd)+1)
+	hasRun := UsesLLVMCov(cmd)
+	for _, arg := range cmd {
+		if arg == "run" {
+			hasRun = true
+		}
+	}
+	for _, arg := range cmd {
+		args = append(args, arg)
+		if arg == "nextest" && !hasRun {
+			args = append(args, "run")
+			hasRun = true
+		}
+	}
+	return args
+}
+
 // GetTestFiles returns empty array for dynamic discovery
 func (n *NextestDefinition) GetTestFiles(args []string) ([]string, error) {
 	// Nextest tests are discovered dynamically as they run
@@ -157,17 +281,27 @@ func (n *NextestDefinition) ProcessOutput(stdout io.Reader, ipcPath string) erro
 		}
 	}()
 
-	scanner := bufio.NewScanner(stdout)
-	// Configure larger buffer for long JSON lines (especially with embedded output)
-	// Default is 64KB which can be exceeded by test output
-	const maxScanTokenSize = 10 * 1024 * 1024 // 10MB max line size
-	buf := make([]byte, 0, 1024*1024)         // 1MB initial buffer
-	scanner.Buffer(buf, maxScanTokenSize)
-
 	testCount := 0
 
-	for scanner.Scan() {
-		line := scanner.Bytes()
+	// Lines longer than the maximum are cut, and the event on such a line is lost to
+	// the parser; output.log still has it
+	lines := n.newLineReader(stdout)
+	for {
+		text, cut, ok := lines.Next()
+		if !ok {
+			break
+		}
+		if cut {
+			n.logger.Debug("Skipping nextest line longer than %d bytes", len(text)-len(LineTruncatedMarker))
+			continue
+		}
+		line := []byte(text)
+
+		// SLOW and LEAK only appear in nextest's human-readable status lines
+		if matches := nextestStatusLineRegex.FindStringSubmatch(string(line)); matches != nil {
+			n.processStatusLine(matches[1], matches[2], matches[3])
+			continue
+		}
 
 		// Try to parse as JSON
 		var event NextestEvent
@@ -183,12 +317,15 @@ func (n *NextestDefinition) ProcessOutput(stdout io.Reader, ipcPath string) erro
 		}
 	}
 
-	if err := scanner.Err(); err != nil {
+	if err := lines.Err(); err != nil {
 		return fmt.Errorf("error reading nextest output: %w", err)
 	}
 
 	// Send final events for any pending groups
 	n.finalizePendingGroups()
+	n.mu.Lock()
+	n.skipUnrunListedTests()
+	n.mu.Unlock()
 
 	return nil
 }
@@ -233,7 +370,8 @@ func (n *NextestDefinition) processTestEvent(event *NextestEvent, testCount *int
 
 	// Parse test name to extract package and module hierarchy
 	// Nextest format often includes package name: package_name::module::test_name
-	parts := strings.Split(event.Name, "::")
+	// or separates the binary from the test with "$": package_name$module::test_name
+	parts := parseNextestTestName(event.Name)
 	if len(parts) == 0 {
 		return nil
 	}
@@ -305,7 +443,10 @@ func (n *NextestDefinition) processTestEvent(event *NextestEvent, testCount *int
 			Package:   packageName,
 			StartTime: time.Now(),
 		}
-		(*testCount)++
+		// Retries start the same test again; count it only once
+		if len(n.testResult(event.Name).Attempts) == 0 {
+			(*testCount)++
+		}
 
 	case "ok", "failed", "ignored":
 		// Ensure groups are created even if we didn't see a "started" event
@@ -357,27 +498,98 @@ func (n *NextestDefinition) processTestEvent(event *NextestEvent, testCount *int
 			status = "PASS"
 		}
 
+		// Record the attempt - a test seen before is a retry of the same test
+		durationMs := event.ExecTime * 1000
+		result := n.testResult(event.Name)
+		result.TestName = testName
+		result.Parents = testParents
+		result.Package = packageName
+		result.Attempts = append(result.Attempts, NextestAttempt{
+			Status:   status,
+			Duration: durationMs,
+			Stdout:   event.Stdout,
+			Stderr:   event.Stderr,
+		})
+
 		// Send test case event
-		n.sendTestCase(testName, testParents, status, event.ExecTime, event.Stdout, event.Stderr)
+		n.sendTestResult(result)
 
 		// Track test in package group
 		if group, ok := n.packageGroups[packageName]; ok {
-			group.Tests = append(group.Tests, NextestTestInfo{
+			testInfo := NextestTestInfo{
 				Name:     testName,
 				Status:   status,
-				Duration: event.ExecTime,
-			})
+				Duration: durationMs,
+			}
+			if len(result.Attempts) > 1 && result.groupIndex < len(group.Tests) {
+				// Retry: the latest attempt decides the test's status
+				group.Tests[result.groupIndex] = testInfo
+			} else {
+				result.groupIndex = len(group.Tests)
+				group.Tests = append(group.Tests, testInfo)
+			}
 
 			// Don't update group status here - let finalizePendingGroups determine final status
 		}
 
 		// Clean up test state
 		delete(n.testStates, event.Name)
+
+	case "slow", "timeout":
+		// libtest emits "timeout" when a test runs longer than 60s; it is a warning, not a result
+		result := n.testResult(event.Name)
+		result.Slow = true
+		if len(result.Attempts) > 0 {
+			n.sendTestResult(result)
+		}
 	}
 
 	return nil
 }
 
+// processStatusLine applies a SLOW or LEAK status from nextest's human-readable output
+func (n *NextestDefinition) processStatusLine(status, binaryID, testName string) {
+	n.mu.Lock()
+	defer n.mu.Unlock()
+
+	n.logger.Debug("Nextest status %s for %s %s", status, binaryID, testName)
+
+	result := n.testResult(binaryID + "::" + testName)
+	switch status {
+	case "SLOW":
+		result.Slow = true
+	case "LEAK", "LEAK-FAIL":
+		result.Leaked = true
+	}
+
+	// Re-send tests that were already reported so the flag reaches the report
+	if len(result.Attempts) > 0 {
+		n.sendTestResult(result)
+	}
+}
+
+// testResult returns the tracked result for a test, creating it if needed
+func (n *NextestDefinition) testResult(name string) *NextestTestResult {
+	key := nextestTestKey(name)
+	result, ok := n.testResults[key]
+	if !ok {
+		result = &NextestTestResult{}
+		n.testResults[key] = result
+	}
+	return result
+}
+
+// parseNextestTestName splits a nextest test name into package, modules and test name
+func parseNextestTestName(name string) []string {
+	return strings.Split(strings.Replace(name, "$", "::", 1), "::")
+}
+
+// nextestTestKey normalizes a test name so JSON names ("my_crate$tests::a") and
+// status line names ("my-crate tests::a") refer to the same test
+func nextestTestKey(name string) string {
+	return strings.ReplaceAll(strings.Join(parseNextestTestName(name), "::"), "-", "_")
+}
+
 // finalizePendingGroups sends result events for any groups that haven't been finalized
 func (n *NextestDefinition) finalizePendingGroups() {
 	for packageName, group := range n.packageGroups {
@@ -464,12 +676,27 @@ func (n *NextestDefinition) sendGroupStart(groupName string, parentNames []strin
 	n.sendIPCEvent(event)
 }
 
-func (n *NextestDefinition) sendTestCase(testName string, parentNames []string, status string, duration float64, stdout, stderr string) {
+func (n *NextestDefinition) sendTestResult(result *NextestTestResult) {
+	latest := result.Attempts[len(result.Attempts)-1]
+	status := latest.Status
+	stdout := latest.Stdout
+	stderr := latest.Stderr
+
+	// A test that passed on a retry after failing is flaky
+	if status == "PASS" {
+		for _, attempt := range result.Attempts[:len(result.Attempts)-1] {
+			if attempt.Status == "FAIL" {
+				status = "FLAKY"
+				break
+			}
+		}
+	}
+
 	payload := map[string]interface{}{
-		"testName":    testName,
-		"parentNames": parentNames,
+		"testName":    result.TestName,
+		"parentNames": result.Parents,
 		"status":      status,
-		"duration":    duration,
+		"duration":    latest.Duration,
 	}
 
 	// Only include stdout/stderr if non-empty
@@ -482,9 +709,58 @@ func (n *NextestDefinition) sendTestCase(testName string, parentNames []string,
 
 	// Include error message for failed tests
 	if status == "FAIL" && stderr != "" {
-		payload["error"] = map[string]interface{}{
-			"message": stderr,
+		output := stdout + "\n" + stderr
+		_, panicMessage := splitCapturedOutput(output)
+		testError := map[string]interface{}{
+			"message":     stderr,
+			"failureKind": classifyRustPanic(panicMessage, output),
+		}
+		// insta's paths are relative to the workspace root, which nextest usually runs from
+		if snapshot := parseInstaSnapshot(output, panicMessage, ""); snapshot != nil {
+			testError["snapshot"] = snapshot
+			testError["failureKind"] = ipc.FailureKindSnapshotMismatch
+		}
+		cwd := n.workingDir()
+		if property := parsePropertyFailure(output, panicMessage, cwd); property != nil {
+			testError["property"] = property
+			testError["failureKind"] = propertyFailureKind(property, panicMessage)
+		}
+		if cases := parseTrybuildCases(output, cwd, cwd); len(cases) > 0 {
+			testError["trybuild"] = cases
+		}
+		payload["error"] = testError
+	}
+
+	// nextest reports #[ignore] tests as skipped
+	if status == "SKIP" {
+		payload["skipReason"] = ipc.SkipReasonIgnored
+	}
+	addRstestCase(payload, result.TestName, result.Parents)
+
+	if result.Slow {
+		payload["slow"] = true
+	}
+	if result.Leaked {
+		payload["leaked"] = true
+	}
+
+	// Include every attempt when nextest retried the test
+	if len(result.Attempts) > 1 {
+		attempts := make([]map[string]interface{}, 0, len(result.Attempts))
+		for _, attempt := range result.Attempts {
+			entry := map[string]interface{}{
+				"status":   attempt.Status,
+				"duration": attempt.Duration,
+			}
+			if attempt.Stdout != "" {
+				entry["stdout"] = attempt.Stdout
+			}
+			if attempt.Stderr != "" {
+				entry["stderr"] = attempt.Stderr
+			}
+			attempts = append(attempts, entry)
 		}
+		payload["attempts"] = attempts
 	}
 
 	event := map[string]interface{}{
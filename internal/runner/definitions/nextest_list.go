@@ -0,0 +1,249 @@
+package definitions
+
+import (
+	"bufio"
+	"encoding/json"
+	"fmt"
+	"io"
+	"sort"
+	"strings"
+
+	"github.com/zk/3pio/internal/ipc"
+)
+
+// nextestRunOnlyFlags are the flags of cargo nextest run that cargo nextest list
+// rejects, with whether they take a separate value
+var nextestRunOnlyFlags = map[string]bool{
+	"--no-fail-fast": false, "--fail-fast": false, "--no-capture": false, "--nocapture": false,
+	"--hide-progress-bar": false, "--retries": true, "--test-threads": true, "-j": true,
+	"--failure-output": true, "--success-output": true, "--status-level": true,
+	"--final-status-level": true, "--max-fail": true, "--no-tests": true,
+}
+
+// NextestList is the part of `cargo nextest list --message-format json` 3pio reads
+type NextestList struct {
+	RustSuites map[string]NextestListSuite `json:"rust-suites"`
+}
+
+// NextestListSuite is a test binary in the list, keyed by its binary ID
+// ("my-crate", "my-crate::integration", "my-crate::bin/cli")
+type NextestListSuite struct {
+	TestCases map[string]struct {
+		FilterMatch struct {
+			Status string `json:"status"` // "matches" or "mismatch"
+		} `json:"filter-match"`
+	} `json:"testcases"`
+}
+
+// ListCommand returns the cargo nextest list command for a cargo nextest run command,
+// for the preflight list. It builds the test binaries the run then reuses.
+func (n *NextestDefinition) ListCommand(cmd []string, ipcPath string) []string {
+	if UsesLLVMCov(cmd) {
+		// cargo llvm-cov nextest has no list, and builds instrumented binaries of its own
+		return nil
+	}
+	result := make([]string, 0, len(cmd)+3)
+	subcommand := false
+	for i := 0; i < len(cmd); i++ {
+		arg := cmd[i]
+		if arg == "--" {
+			result = append(result, cmd[i:]...)
+			break
+		}
+		if !subcommand && i > 0 && cmd[i-1] == "nextest" {
+			// Only cargo nextest run runs tests; list already only lists them
+			if arg != "run" {
+				return nil
+			}
+			subcommand = true
+			result = append(result, "list")
+			continue
+		}
+		flag, _, hasValue := strings.Cut(arg, "=")
+		if takesValue, ok := nextestRunOnlyFlags[flag]; ok {
+			if takesValue && !hasValue {
+				i++
+			}
+			continue
+		}
+		if flag == "--message-format" {
+			if !hasValue {
+				i++
+			}
+			continue
+		}
+		result = append(result, arg)
+	}
+	if !subcommand {
+		if len(cmd) == 0 || cmd[len(cmd)-1] != "nextest" {
+			return nil
+		}
+		// "run" is the default subcommand
+		result = append(result, "list")
+	}
+
+	// --message-format goes with the list flags, before the test filters after "--"
+	separator := len(result)
+	for i, arg := range result {
+		if arg == "--" {
+			separator = i
+			break
+		}
+	}
+	list := append(append([]string(nil), result[:separator]...), "--message-format", "json")
+	return append(list, result[separator:]...)
+}
+
+// ProcessTestList reads the output of the ListCommand and reports every test the run
+// will run as PENDING, in the groups the run reports it in
+func (n *NextestDefinition) ProcessTestList(output io.Reader, ipcPath string) error {
+	var err error
+	n.ipcWriter, err = NewIPCWriter(ipcPath)
+	if err != nil {
+		return fmt.Errorf("failed to create IPC writer: %w", err)
+	}
+	defer func() {
+		if err := n.ipcWriter.Close(); err != nil {
+			n.logger.Debug("Failed to close IPC writer: %v", err)
+		}
+	}()
+
+	// The list is one JSON document on a line of its own, which for a large
+	// workspace is longer than the output lines parsed otherwise
+	var list NextestList
+	found := false
+	reader := bufio.NewReader(output)
+	for {
+		line, err := reader.ReadString('\n')
+		if trimmed := strings.TrimSpace(line); strings.HasPrefix(trimmed, "{") && !found {
+			found = json.Unmarshal([]byte(trimmed), &list) == nil && list.RustSuites != nil
+		}
+		if err != nil {
+			break
+		}
+	}
+	if !found {
+		n.logger.Debug("No test list in the cargo nextest list output")
+		return nil
+	}
+
+	n.mu.Lock()
+	defer n.mu.Unlock()
+	binaryIDs := make([]string, 0, len(list.RustSuites))
+	for binaryID := range list.RustSuites {
+		binaryIDs = append(binaryIDs, binaryID)
+	}
+	sort.Strings(binaryIDs)
+	listed := 0
+	for _, binaryID := range binaryIDs {
+		suite := list.RustSuites[binaryID]
+		names := make([]string, 0, len(suite.TestCases))
+		for name, testCase := range suite.TestCases {
+			if testCase.FilterMatch.Status == "matches" {
+				names = append(names, name)
+			}
+		}
+		sort.Strings(names)
+		for _, name := range names {
+			n.sendListedTest(binaryID + "$" + name)
+			listed++
+		}
+	}
+	n.logger.Debug("Listed %d tests before the run", listed)
+	return nil
+}
+
+// sendListedTest reports a listed test as PENDING under the groups the run will
+// report it in. Caller must hold n.mu.
+func (n *NextestDefinition) sendListedTest(fullName string) {
+	parts := parseNextestTestName(fullName)
+	if len(parts) < 2 {
+		return
+	}
+	packageName := parts[0]
+	var parentNames []string
+	if n.workspaceName != "" {
+		parentNames = append(parentNames, n.workspaceName)
+	}
+	if !n.discoveredGroups[packageName] {
+		n.sendGroupDiscovered(packageName, parentNames)
+		n.discoveredGroups[packageName] = true
+	}
+
+	testParents := append(append([]string{}, parentNames...), packageName)
+	for i := 1; i < len(parts)-1; i++ {
+		moduleKey := strings.Join(parts[:i+1], "::")
+		if !n.discoveredGroups[moduleKey] {
+			n.sendGroupDiscovered(parts[i], append(append([]string{}, parentNames...), parts[:i]...))
+			n.discoveredGroups[moduleKey] = true
+		}
+		testParents = append(testParents, parts[i])
+	}
+
+	testName := parts[len(parts)-1]
+	n.sendIPCEvent(map[string]interface{}{
+		"eventType": "testCase",
+		"payload": map[string]interface{}{
+			"testName":    testName,
+			"parentNames": testParents,
+			"status":      "PENDING",
+			"duration":    0,
+		},
+	})
+	n.listedTests[nextestTestKey(fullName)] = ListedTest{Name: testName, Parents: testParents}
+}
+
+// MarkInterrupted leaves the listed tests that haven't run pending when the output
+// ends, after 3pio stopped the run
+func (n *NextestDefinition) MarkInterrupted() {
+	n.mu.Lock()
+	defer n.mu.Unlock()
+	n.interrupted = true
+}
+
+// skipUnrunListedTests reports the listed tests nextest never ran as skipped, as when
+// it stopped at the first failure. Caller must hold n.mu.
+func (n *NextestDefinition) skipUnrunListedTests() {
+	if n.interrupted {
+		return
+	}
+	keys := make([]string, 0, len(n.listedTests))
+	for key := range n.listedTests {
+		if result, ok := n.testResults[key]; !ok || len(result.Attempts) == 0 {
+			keys = append(keys, key)
+		}
+	}
+	sort.Strings(keys)
+	var parentNames []string
+	if n.workspaceName != "" {
+		parentNames = append(parentNames, n.workspaceName)
+	}
+	skipped := make(map[string]int)
+	for _, key := range keys {
+		test := n.listedTests[key]
+		skipped[test.Parents[len(parentNames)]]++
+		n.sendIPCEvent(map[string]interface{}{
+			"eventType": "testCase",
+			"payload": map[string]interface{}{
+				"testName":    test.Name,
+				"parentNames": test.Parents,
+				"status":      "SKIP",
+				"duration":    0,
+				"skipReason":  ipc.SkipReasonNotRun,
+			},
+		})
+	}
+
+	// Packages none of whose tests ran were never started
+	packageNames := make([]string, 0, len(skipped))
+	for packageName := range skipped {
+		if !n.groupStarts[packageName] {
+			packageNames = append(packageNames, packageName)
+		}
+	}
+	sort.Strings(packageNames)
+	for _, packageName := range packageNames {
+		n.sendGroupResult(packageName, parentNames, "SKIP", 0, 0, 0, skipped[packageName])
+	}
+	n.listedTests = make(map[string]ListedTest)
+}
@@ -4,9 +4,11 @@ import (
 	"bytes"
 	"encoding/json"
 	"os"
+	"path/filepath"
 	"strings"
 	"testing"
 
+	"github.com/zk/3pio/internal/ipc"
 	"github.com/zk/3pio/internal/logger"
 )
 
@@ -172,6 +174,60 @@ func TestNextestDefinition_ModifyCommand(t *testing.T) {
 	}
 }
 
+func TestNextestDefinition_RerunCommand(t *testing.T) {
+	def := NewNextestDefinition(createTestLogger(t))
+
+	failed := []ipc.FailedTest{
+		{Name: "test_add_004", ParentNames: []string{"rust-performance", "tests"}},
+		{Name: "test_cli", ParentNames: []string{"integration_test"}},
+	}
+	filter := "test(=tests::test_add_004) | test(=test_cli)"
+
+	tests := []struct {
+		name     string
+		cmd      []string
+		expected []string
+	}{
+		{"appends the filterset", []string{"cargo", "nextest", "run"}, []string{"cargo", "nextest", "run", "-E", filter}},
+		{"adds run when missing", []string{"cargo", "nextest"}, []string{"cargo", "nextest", "run", "-E", filter}},
+		{"filters before test binary args", []string{"cargo", "nextest", "run", "--", "--nocapture"}, []string{"cargo", "nextest", "run", "-E", filter, "--", "--nocapture"}},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			result, err := def.RerunCommand(tt.cmd, failed)
+			if err != nil {
+				t.Fatalf("RerunCommand failed: %v", err)
+			}
+			if strings.Join(result, "\x00") != strings.Join(tt.expected, "\x00") {
+				t.Errorf("Expected %q, got %q", tt.expected, result)
+			}
+		})
+	}
+}
+
+func TestNextestDefinition_RetriesCommand(t *testing.T) {
+	def := NewNextestDefinition(createTestLogger(t))
+
+	tests := []struct {
+		name     string
+		cmd      []string
+		expected []string
+	}{
+		{"appends retries", []string{"cargo", "nextest", "run"}, []string{"cargo", "nextest", "run", "--retries", "2"}},
+		{"adds run when missing", []string{"cargo", "nextest"}, []string{"cargo", "nextest", "run", "--retries", "2"}},
+		{"retries before test binary args", []string{"cargo", "nextest", "run", "--", "--nocapture"}, []string{"cargo", "nextest", "run", "--retries", "2", "--", "--nocapture"}},
+		{"keeps the user's retries", []string{"cargo", "nextest", "run", "--retries=5"}, []string{"cargo", "nextest", "run", "--retries=5"}},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			result := def.RetriesCommand(tt.cmd, 2)
+			if strings.Join(result, "\x00") != strings.Join(tt.expected, "\x00") {
+				t.Errorf("Expected %q, got %q", tt.expected, result)
+			}
+		})
+	}
+}
+
 func TestNextestDefinition_ProcessJSONEvents(t *testing.T) {
 	logger, _ := logger.NewFileLogger()
 	defer func() { _ = logger.Close() }()
@@ -247,10 +303,6 @@ func TestNextestDefinition_SetEnvironment(t *testing.T) {
 }
 
 func TestNextestDefinition_ParseTestName(t *testing.T) {
-	logger, _ := logger.NewFileLogger()
-	defer func() { _ = logger.Close() }()
-	def := NewNextestDefinition(logger)
-
 	tests := []struct {
 		name         string
 		testName     string
@@ -290,10 +342,19 @@ func TestNextestDefinition_ParseTestName(t *testing.T) {
 
 	for _, tt := range tests {
 		t.Run(tt.name, func(t *testing.T) {
-			// This would test internal parsing logic if exposed
-			// For now, we just verify the definition handles various formats
-			// without crashing
-			_ = def
+			parts := parseNextestTestName(tt.testName)
+			if len(parts) < 2 {
+				t.Fatalf("parseNextestTestName(%q) = %v, expected package and test", tt.testName, parts)
+			}
+			if parts[0] != tt.expectedPkg {
+				t.Errorf("package = %q, expected %q", parts[0], tt.expectedPkg)
+			}
+			if mod := strings.Join(parts[1:len(parts)-1], "::"); mod != tt.expectedMod {
+				t.Errorf("module = %q, expected %q", mod, tt.expectedMod)
+			}
+			if test := parts[len(parts)-1]; test != tt.expectedTest {
+				t.Errorf("test = %q, expected %q", test, tt.expectedTest)
+			}
 		})
 	}
 }
@@ -401,3 +462,193 @@ func TestNextestDefinition_SuiteCompletionEvents(t *testing.T) {
 		})
 	}
 }
+
+func TestNextestDefinition_RetriesAreAttempts(t *testing.T) {
+	def := NewNextestDefinition(createTestLogger(t))
+
+	jsonEvents := `{"type":"suite","event":"started","test_count":1}
+{"type":"test","event":"started","name":"my_crate$tests::test_flaky"}
+{"type":"test","event":"failed","name":"my_crate$tests::test_flaky","exec_time":0.01,"stderr":"boom"}
+{"type":"test","event":"started","name":"my_crate$tests::test_flaky"}
+{"type":"test","event":"ok","name":"my_crate$tests::test_flaky","exec_time":0.02}
+{"type":"suite","event":"ok","passed":1,"failed":0,"ignored":0}
+`
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(jsonEvents), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	capture := NewTestIPCCapture(ipcPath)
+
+	testCases := capture.GetEventsByType("testCase")
+	if len(testCases) != 2 {
+		t.Fatalf("Expected 2 testCase events (one per attempt), got %d", len(testCases))
+	}
+
+	// The final event for the test carries both attempts, and passing after a failure is flaky
+	payload := testCases[1]["payload"].(map[string]interface{})
+	if payload["testName"] != "test_flaky" || payload["status"] != "FLAKY" {
+		t.Errorf("Expected test_flaky FLAKY, got %v %v", payload["testName"], payload["status"])
+	}
+	attempts, ok := payload["attempts"].([]interface{})
+	if !ok || len(attempts) != 2 {
+		t.Fatalf("Expected 2 attempts, got %v", payload["attempts"])
+	}
+	if first := attempts[0].(map[string]interface{}); first["status"] != "FAIL" || first["stderr"] != "boom" {
+		t.Errorf("Expected first attempt to FAIL with its stderr, got %v", first)
+	}
+
+	// The package group counts the test once, with its final status
+	results := capture.GetEventsByType("testGroupResult")
+	if len(results) != 1 {
+		t.Fatalf("Expected 1 testGroupResult, got %d", len(results))
+	}
+	groupPayload := results[0]["payload"].(map[string]interface{})
+	totals := groupPayload["totals"].(map[string]interface{})
+	if totals["passed"] != float64(1) || totals["failed"] != float64(0) {
+		t.Errorf("Expected totals passed=1 failed=0, got %v", totals)
+	}
+	if groupPayload["status"] != "PASS" {
+		t.Errorf("Expected group PASS after successful retry, got %v", groupPayload["status"])
+	}
+}
+
+func TestNextestDefinition_SlowAndLeakStatusLines(t *testing.T) {
+	def := NewNextestDefinition(createTestLogger(t))
+
+	output := `{"type":"suite","event":"started","test_count":2}
+{"type":"test","event":"started","name":"my-crate$tests::test_slow"}
+        SLOW [> 60.000s] my-crate tests::test_slow
+{"type":"test","event":"ok","name":"my-crate$tests::test_slow","exec_time":61.0}
+{"type":"test","event":"started","name":"my-crate$tests::test_leaky"}
+{"type":"test","event":"ok","name":"my-crate$tests::test_leaky","exec_time":0.1}
+        LEAK [   0.103s] my-crate tests::test_leaky
+{"type":"suite","event":"ok","passed":2,"failed":0,"ignored":0}
+`
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+
+	// Keep the last event per test, as the report does
+	latest := make(map[string]map[string]interface{})
+	for _, e := range NewTestIPCCapture(ipcPath).GetEventsByType("testCase") {
+		payload := e["payload"].(map[string]interface{})
+		latest[payload["testName"].(string)] = payload
+	}
+
+	if latest["test_slow"]["slow"] != true {
+		t.Errorf("Expected test_slow to be flagged slow, got %v", latest["test_slow"])
+	}
+	if latest["test_leaky"]["leaked"] != true {
+		t.Errorf("Expected test_leaky to be flagged leaked, got %v", latest["test_leaky"])
+	}
+	if latest["test_leaky"]["status"] != "PASS" {
+		t.Errorf("LEAK should not change the test status, got %v", latest["test_leaky"]["status"])
+	}
+}
+
+func TestNextestWrapper_InterpretExitCode(t *testing.T) {
+	wrapper := NewNextestWrapper(NewNextestDefinition(createTestLogger(t)))
+
+	tests := []struct {
+		code     int
+		expected string
+	}{
+		{0, "success"},
+		{NextestExitTestRunFailed, "failure"},
+		{NextestExitBuildFailed, "error"},
+		{1, "error"},
+	}
+
+	for _, tt := range tests {
+		if result := wrapper.InterpretExitCode(tt.code); result != tt.expected {
+			t.Errorf("InterpretExitCode(%d) = %q, expected %q", tt.code, result, tt.expected)
+		}
+	}
+}
+
+func TestNextestDefinition_ListCommand(t *testing.T) {
+	def := NewNextestDefinition(createTestLogger(t))
+
+	tests := []struct {
+		name     string
+		cmd      []string
+		expected []string
+	}{
+		{"run becomes list", []string{"cargo", "nextest", "run", "-p", "my-crate"}, []string{"cargo", "nextest", "list", "-p", "my-crate", "--message-format", "json"}},
+		{"adds list when run is missing", []string{"cargo", "nextest"}, []string{"cargo", "nextest", "list", "--message-format", "json"}},
+		{"drops run flags", []string{"cargo", "nextest", "run", "--no-fail-fast", "--retries", "2", "--test-threads=4", "-E", "test(add)"}, []string{"cargo", "nextest", "list", "-E", "test(add)", "--message-format", "json"}},
+		{"keeps filters after --", []string{"cargo", "+nightly", "nextest", "run", "--", "tests::"}, []string{"cargo", "+nightly", "nextest", "list", "--message-format", "json", "--", "tests::"}},
+		{"already lists", []string{"cargo", "nextest", "list"}, nil},
+		{"other subcommand", []string{"cargo", "nextest", "archive", "--archive-file", "a.tar.zst"}, nil},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			result := def.ListCommand(tt.cmd, "")
+			if strings.Join(result, "\x00") != strings.Join(tt.expected, "\x00") {
+				t.Errorf("Expected %q, got %q", tt.expected, result)
+			}
+		})
+	}
+}
+
+func TestNextestDefinition_ProcessTestList(t *testing.T) {
+	def := NewNextestDefinition(createTestLogger(t))
+
+	list := `   Compiling my-crate v0.1.0 (/work)
+    Finished ` + "`test`" + ` profile [unoptimized + debuginfo] target(s) in 0.52s
+{"rust-build-meta":{},"test-count":4,"rust-suites":{"my-crate":{"binary-id":"my-crate","testcases":{"tests::test_add":{"ignored":false,"filter-match":{"status":"matches"}},"tests::test_slow":{"ignored":false,"filter-match":{"status":"mismatch","reason":"expression"}}}},"my-crate::integration":{"binary-id":"my-crate::integration","testcases":{"it_works":{"ignored":false,"filter-match":{"status":"matches"}}}},"other":{"binary-id":"other","testcases":{"tests::test_other":{"ignored":false,"filter-match":{"status":"matches"}}}}}}
+`
+	// nextest stops after the first failure, so the others never run
+	jsonEvents := `{"type":"suite","event":"started","test_count":3}
+{"type":"test","event":"started","name":"my-crate$tests::test_add"}
+{"type":"test","event":"failed","name":"my-crate$tests::test_add","exec_time":0.01}
+{"type":"suite","event":"failed","passed":0,"failed":1,"ignored":0}
+`
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessTestList(strings.NewReader(list), ipcPath); err != nil {
+		t.Fatalf("ProcessTestList failed: %v", err)
+	}
+	pending := make(map[string]string)
+	for _, e := range NewTestIPCCapture(ipcPath).GetEventsByType("testCase") {
+		payload := e["payload"].(map[string]interface{})
+		pending[payload["testName"].(string)] = payload["status"].(string)
+	}
+	expected := map[string]string{"test_add": "PENDING", "it_works": "PENDING", "test_other": "PENDING"}
+	if len(pending) != len(expected) {
+		t.Errorf("Expected the matching tests pending, got %v", pending)
+	}
+	for name, status := range expected {
+		if pending[name] != status {
+			t.Errorf("Expected %s %s, got %q", name, status, pending[name])
+		}
+	}
+
+	if err := def.ProcessOutput(strings.NewReader(jsonEvents), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	capture := NewTestIPCCapture(ipcPath)
+	final := make(map[string]map[string]interface{})
+	for _, e := range capture.GetEventsByType("testCase") {
+		payload := e["payload"].(map[string]interface{})
+		final[payload["testName"].(string)] = payload
+	}
+	if final["test_add"]["status"] != "FAIL" {
+		t.Errorf("Expected test_add to fail, got %v", final["test_add"])
+	}
+	for _, name := range []string{"it_works", "test_other"} {
+		if final[name]["status"] != "SKIP" || final[name]["skipReason"] != ipc.SkipReasonNotRun {
+			t.Errorf("Expected %s skipped as not_run, got %v", name, final[name])
+		}
+	}
+
+	// The package that never started is skipped; the one that ran keeps its result
+	results := make(map[string]interface{})
+	for _, e := range capture.GetEventsByType("testGroupResult") {
+		payload := e["payload"].(map[string]interface{})
+		results[payload["groupName"].(string)] = payload["status"]
+	}
+	if results["my-crate"] != "FAIL" || results["other"] != "SKIP" {
+		t.Errorf("Expected my-crate FAIL and other SKIP, got %v", results)
+	}
+}
@@ -36,12 +36,18 @@ func (n *NextestWrapper) GetAdapterFileName() string {
 	return ""
 }
 
-// InterpretExitCode maps exit codes to success/failure
+// InterpretExitCode maps exit codes to success/failure.
+// Only NextestExitTestRunFailed means tests failed; other codes are
+// build or internal errors where the test results are incomplete.
 func (n *NextestWrapper) InterpretExitCode(code int) string {
-	if code == 0 {
+	switch code {
+	case 0:
 		return "success"
+	case NextestExitTestRunFailed:
+		return "failure"
+	default:
+		return "error"
 	}
-	return "failure"
 }
 
 // IsNative returns true as nextest processes output directly
@@ -0,0 +1,797 @@
+package definitions
+
+import (
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"regexp"
+	"slices"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/zk/3pio/internal/ipc"
+	"github.com/zk/3pio/internal/logger"
+)
+
+// Patterns for the TAP that node --test --test-reporter=tap prints, e.g.
+//
+//	# Subtest: Cart
+//	    # Subtest: adds an item
+//	    ok 1 - adds an item
+//	      ---
+//	      duration_ms: 0.512
+//	      ...
+//	    1..1
+//	ok 1 - Cart
+//
+// Subtests are indented four spaces per level, their diagnostics two more.
+var (
+	nodeTestSubtestRegex  = regexp.MustCompile(`^# Subtest: (.*)$`)
+	nodeTestPointRegex    = regexp.MustCompile(`^(ok|not ok)(?: \d+)?(?: - (.*))?$`)
+	nodeTestPlanRegex     = regexp.MustCompile(`^1\.\.\d+`)
+	nodeTestSummaryRegex  = regexp.MustCompile(`^# (?:tests|suites|pass|fail|cancelled|skipped|todo|duration_ms) [\d.]+$`)
+	nodeTestLocationRegex = regexp.MustCompile(`^(.*):(\d+):(\d+)$`)
+)
+
+// nodeTestDirectiveRegex splits a test name from its "# SKIP" or "# TODO" directive.
+// Node escapes "#" in names as "\#", so only an unescaped one starts a directive.
+var nodeTestDirectiveRegex = regexp.MustCompile(`^(.*?)\s+#\s*(?i:(SKIP|TODO))\b\s*(.*)$`)
+
+// nodeTestValueFlags are the node options that take their value as the next argument
+var nodeTestValueFlags = map[string]bool{
+	"-r": true, "--require": true, "--import": true, "--loader": true, "--experimental-loader": true,
+	"-C": true, "--conditions": true, "--env-file": true, "--input-type": true, "--title": true,
+	"--test-name-pattern": true, "--test-skip-pattern": true, "--test-concurrency": true,
+	"--test-timeout": true, "--test-shard": true, "--test-reporter": true, "--test-reporter-destination": true,
+}
+
+// nodeTestDefaultGroup names the file group of tests whose file Node didn't report
+const nodeTestDefaultGroup = "tests"
+
+// nodeTestFileRegex matches the files node --test runs by default: *.test.js,
+// *-test.js, *_test.js, test-*.js and test.js, and any script under a test directory
+var nodeTestFileRegex = regexp.MustCompile(`(?:^|/)(?:test/.*|[^/]*[._-]test|test-[^/]*|test)\.[cm]?[jt]s$`)
+
+// NodeTestDefinition implements support for Node's built-in test runner (node --test)
+type NodeTestDefinition struct {
+	logger    *logger.FileLogger
+	ipcWriter *IPCWriter
+
+	lineLimit // Longest line of output parsed whole (--max-line-length)
+
+	testPaths []string          // Files and patterns given on the command line
+	sources   map[string]string // Contents of the test files by path, read when Node didn't tell a test's file
+
+	stack    []*nodeTestNode // Subtests that started and haven't finished, outermost first
+	lastDone *nodeTestNode   // Subtest whose result line was the previous line, whose diagnostics may follow
+	finished *nodeTestNode   // Top-level test that finished, reported once its diagnostics are read
+	yaml     *tapDiagnostics // YAML diagnostics of a subtest being read
+
+	pendingOutput strings.Builder          // Output printed outside any subtest, for the next file reported
+	files         map[string]*nodeTestFile // File groups by path
+	fileOrder     []string                 // File group paths in discovery order
+	currentFile   string                   // File group the last test was reported in
+}
+
+// nodeTestNode is a test or suite in node's TAP output
+type nodeTestNode struct {
+	Name      string
+	Nesting   int
+	Children  []*nodeTestNode
+	Failed    bool              // "not ok"
+	Directive string            // "SKIP" or "TODO"
+	Reason    string            // Text after the directive
+	Details   map[string]string // YAML diagnostics, e.g. duration_ms, location and error
+	Output    strings.Builder   // Output printed while the subtest ran
+}
+
+// nodeTestFile tracks a test file group
+type nodeTestFile struct {
+	Path      string
+	StartTime time.Time
+	Totals    tapTotals
+	Errored   bool // Sent a testGroupError, which ends the group
+	Finalized bool
+}
+
+// NewNodeTestDefinition creates a new node --test runner definition
+func NewNodeTestDefinition(logger *logger.FileLogger) *NodeTestDefinition {
+	return &NodeTestDefinition{
+		logger: logger,
+		files:  make(map[string]*nodeTestFile),
+	}
+}
+
+// Name returns the name of this test runner
+func (n *NodeTestDefinition) Name() string {
+	return "node"
+}
+
+// Detect checks if the command runs node with --test. Only options before the first
+// positional argument are node's; after a script they are the script's.
+func (n *NodeTestDefinition) Detect(args []string) bool {
+	if len(args) < 2 || !isNodeExecutable(args[0]) {
+		return false
+	}
+	for i := 1; i < len(args); i++ {
+		arg := args[i]
+		switch {
+		case arg == "--test":
+			return true
+		case nodeTestValueFlags[arg]:
+			i++
+		case !strings.HasPrefix(arg, "-"):
+			return false
+		}
+	}
+	return false
+}
+
+// AcceptsReporter checks if a --test-reporter appended to a node --test command is
+// still node's, as with a package.json script 3pio only adds options to: not after a
+// test file, whose options they'd become, nor beside a reporter the command chose.
+func (n *NodeTestDefinition) AcceptsReporter(args []string) bool {
+	for i := 1; i < len(args); i++ {
+		arg := args[i]
+		name, _, _ := strings.Cut(arg, "=")
+		switch {
+		case name == "--test-reporter":
+			return false
+		case nodeTestValueFlags[arg]:
+			i++
+		case !strings.HasPrefix(arg, "-"):
+			return false
+		}
+	}
+	return true
+}
+
+// isNodeExecutable checks if a command runs node, e.g. "node" or "/usr/bin/node.exe"
+func isNodeExecutable(command string) bool {
+	base := command
+	if idx := strings.LastIndexAny(base, `/\`); idx != -1 {
+		base = base[idx+1:]
+	}
+	return strings.TrimSuffix(strings.ToLower(base), ".exe") == "node"
+}
+
+// ModifyCommand switches node to the TAP reporter, writing to stdout. Reporters the
+// command chose are dropped, as 3pio reads the results from the TAP.
+func (n *NodeTestDefinition) ModifyCommand(cmd []string, ipcPath, runID string) []string {
+	result := make([]string, 0, len(cmd)+2)
+	n.testPaths = nil
+
+	// A package.json script running node --test, e.g. npm test, which takes the
+	// reporter as the script's options: after -- for npm, yarn and bun
+	if len(cmd) > 0 && !isNodeExecutable(cmd[0]) {
+		result = append(result, cmd...)
+		manager := strings.ToLower(cmd[0][strings.LastIndexAny(cmd[0], `/\`)+1:])
+		if strings.TrimSuffix(manager, ".cmd") != "pnpm" && !slices.Contains(cmd, "--") {
+			result = append(result, "--")
+		}
+		return append(result, "--test-reporter=tap")
+	}
+
+	for i := 0; i < len(cmd); i++ {
+		arg := cmd[i]
+		name, _, _ := strings.Cut(arg, "=")
+		if i > 0 && (name == "--test-reporter" || name == "--test-reporter-destination") {
+			n.logger.Debug("Dropping %s: 3pio reads node's TAP reporter", arg)
+			if name == arg {
+				i++
+			}
+			continue
+		}
+		result = append(result, arg)
+		switch {
+		case i == 0:
+		case arg == "--test":
+			result = append(result, "--test-reporter=tap")
+		case nodeTestValueFlags[arg]:
+			if i+1 < len(cmd) {
+				i++
+				result = append(result, cmd[i])
+			}
+		case !strings.HasPrefix(arg, "-"):
+			n.testPaths = append(n.testPaths, arg)
+		}
+	}
+	return result
+}
+
+// GetTestFiles returns empty array for dynamic discovery
+func (n *NodeTestDefinition) GetTestFiles(args []string) ([]string, error) {
+	// Node finds the test files itself
+	return []string{}, nil
+}
+
+// RequiresAdapter returns false as node's TAP is parsed directly
+func (n *NodeTestDefinition) RequiresAdapter() bool {
+	return false
+}
+
+// ProcessOutput reads combined node --test output and converts its TAP to IPC events.
+// Output of the tests between the TAP lines is kept with the subtest it was printed in.
+func (n *NodeTestDefinition) ProcessOutput(combinedOutput io.Reader, ipcPath string) error {
+	var err error
+	n.ipcWriter, err = NewIPCWriter(ipcPath)
+	if err != nil {
+		return fmt.Errorf("failed to create IPC writer: %w", err)
+	}
+	defer func() {
+		if err := n.ipcWriter.Close(); err != nil {
+			n.logger.Debug("Failed to close IPC writer: %v", err)
+		}
+	}()
+
+	lines := n.newLineReader(combinedOutput)
+	for {
+		line, _, ok := lines.Next()
+		if !ok {
+			break
+		}
+		n.processLine(line)
+	}
+
+	if err := lines.Err(); err != nil {
+		return fmt.Errorf("error reading node --test output: %w", err)
+	}
+
+	// A run that was cut short leaves subtests open; report what finished
+	n.flushFinished()
+	n.finalizeFiles()
+
+	runCompleteEvent := map[string]interface{}{
+		"eventType": "runComplete",
+		"payload":   map[string]interface{}{},
+	}
+	if err := n.ipcWriter.WriteEvent(runCompleteEvent); err != nil {
+		n.logger.Debug("Failed to send runComplete event: %v", err)
+	}
+
+	return nil
+}
+
+// processLine processes a single line of node --test output
+func (n *NodeTestDefinition) processLine(line string) {
+	line = strings.TrimSuffix(line, "\r")
+	if n.yaml != nil {
+		consumed := n.yaml.readLine(line)
+		if n.yaml.done {
+			n.yaml = nil
+		}
+		if consumed {
+			return
+		}
+	}
+
+	trimmed := strings.TrimLeft(line, " ")
+	indent := len(line) - len(trimmed)
+	nesting := indent / 4
+
+	lastDone := n.lastDone
+	n.lastDone = nil
+	if trimmed == "---" && lastDone != nil && indent == lastDone.Nesting*4+2 {
+		n.yaml = newTAPDiagnostics(lastDone.Details, indent)
+		return
+	}
+	n.flushFinished()
+
+	switch {
+	case trimmed == "":
+		return
+	case strings.HasPrefix(trimmed, "TAP version"), nodeTestPlanRegex.MatchString(trimmed), strings.HasPrefix(trimmed, "Bail out!"):
+		return
+	}
+
+	if matches := nodeTestSubtestRegex.FindStringSubmatch(trimmed); matches != nil {
+		n.startSubtest(unescapeTAP(matches[1]), nesting)
+		return
+	}
+	if matches := nodeTestPointRegex.FindStringSubmatch(trimmed); matches != nil {
+		n.lastDone = n.finishSubtest(matches[1] == "not ok", matches[2], nesting)
+		return
+	}
+
+	if trimmed == "#" || strings.HasPrefix(trimmed, "# ") {
+		// Diagnostics, and in process isolation the output of the test files
+		if indent == 0 && nodeTestSummaryRegex.MatchString(trimmed) {
+			return
+		}
+		n.appendOutput(unescapeTAP(strings.TrimPrefix(strings.TrimPrefix(trimmed, "#"), " ")))
+		return
+	}
+
+	// Output the tests printed straight to the TAP stream
+	n.appendOutput(line)
+}
+
+// startSubtest opens a subtest at the given nesting
+func (n *NodeTestDefinition) startSubtest(name string, nesting int) {
+	if nesting > len(n.stack) {
+		nesting = len(n.stack)
+	}
+	n.stack = n.stack[:nesting]
+	node := &nodeTestNode{Name: name, Nesting: nesting, Details: make(map[string]string)}
+	if nesting > 0 {
+		parent := n.stack[nesting-1]
+		parent.Children = append(parent.Children, node)
+	}
+	n.stack = append(n.stack, node)
+}
+
+// finishSubtest records the result line of the subtest at the given nesting and
+// returns it. Reporters that print no "# Subtest:" line get the subtest created here.
+func (n *NodeTestDefinition) finishSubtest(failed bool, description string, nesting int) *nodeTestNode {
+	name, directive, reason := description, "", ""
+	if matches := nodeTestDirectiveRegex.FindStringSubmatch(description); matches != nil {
+		name, directive, reason = matches[1], strings.ToUpper(matches[2]), matches[3]
+	}
+	name = unescapeTAP(name)
+
+	if nesting >= len(n.stack) || n.stack[nesting].Name != name {
+		n.startSubtest(name, nesting)
+		nesting = len(n.stack) - 1
+	}
+	node := n.stack[nesting]
+	node.Failed = failed
+	node.Directive = directive
+	node.Reason = unescapeTAP(reason)
+	n.stack = n.stack[:nesting]
+
+	if nesting == 0 {
+		n.finished = node
+	}
+	return node
+}
+
+// appendOutput keeps a line of output with the innermost running subtest, or for the
+// next file when no subtest is running
+func (n *NodeTestDefinition) appendOutput(text string) {
+	if len(n.stack) > 0 {
+		output := &n.stack[len(n.stack)-1].Output
+		output.WriteString(text)
+		output.WriteString("\n")
+		return
+	}
+	n.pendingOutput.WriteString(text)
+	n.pendingOutput.WriteString("\n")
+}
+
+// flushFinished reports the top-level test that finished, if any
+func (n *NodeTestDefinition) flushFinished() {
+	if n.finished == nil {
+		return
+	}
+	node := n.finished
+	n.finished = nil
+	if n.yaml != nil {
+		n.yaml.finish()
+		n.yaml = nil
+	}
+	n.reportTopLevel(node)
+}
+
+// reportTopLevel reports a top-level test with its subtests under its file group.
+// Node before v20 wraps the tests of each file in a test named after the file.
+func (n *NodeTestDefinition) reportTopLevel(node *nodeTestNode) {
+	if isTestFile(node.Name) {
+		file := n.ensureFile(node.Name)
+		n.flushFileOutput(file, node.Output.String())
+		if len(node.Children) == 0 && node.Failed {
+			// The file failed to load or crashed before reporting its tests
+			n.sendFileError(file, node)
+			return
+		}
+		for _, child := range node.Children {
+			file.Totals.add(n.reportNode(child, []string{file.Path}))
+		}
+		return
+	}
+
+	file := n.ensureFile(n.fileOf(node))
+	n.flushFileOutput(file, "")
+	file.Totals.add(n.reportNode(node, []string{file.Path}))
+}
+
+// fileOf returns the file group of a top-level test: the file of the first location
+// among its diagnostics, which Node only prints for failures, else the only test file
+// that declares a test of that name
+func (n *NodeTestDefinition) fileOf(node *nodeTestNode) string {
+	if file, _, _ := parseNodeTestLocation(firstLocation(node)); file != "" {
+		return file
+	}
+	if file := n.fileDeclaring(node.Name); file != "" {
+		return file
+	}
+	return nodeTestDefaultGroup
+}
+
+// fileDeclaring returns the test file that has name as a string literal, or "" if
+// none or several do
+func (n *NodeTestDefinition) fileDeclaring(name string) string {
+	if n.sources == nil {
+		n.sources = readNodeTestSources(n.testPaths)
+	}
+	found := ""
+	for path, source := range n.sources {
+		if !strings.Contains(source, "'"+name+"'") && !strings.Contains(source, `"`+name+`"`) && !strings.Contains(source, "`"+name+"`") {
+			continue
+		}
+		if found != "" {
+			return ""
+		}
+		found = path
+	}
+	return found
+}
+
+// readNodeTestSources reads the test files node --test runs: the files given on the
+// command line, the scripts in the directories given, or else the files matching its
+// default patterns under the working directory
+func readNodeTestSources(testPaths []string) map[string]string {
+	sources := make(map[string]string)
+	read := func(path string) {
+		if abs, err := filepath.Abs(path); err == nil {
+			if content, err := os.ReadFile(abs); err == nil {
+				sources[abs] = string(content)
+			}
+		}
+	}
+	walk := func(root string, match func(string) bool) {
+		_ = filepath.WalkDir(root, func(path string, entry os.DirEntry, err error) error {
+			if err != nil {
+				return nil
+			}
+			if entry.IsDir() {
+				if path != root && (entry.Name() == "node_modules" || strings.HasPrefix(entry.Name(), ".")) {
+					return filepath.SkipDir
+				}
+				return nil
+			}
+			if match(filepath.ToSlash(path)) {
+				read(path)
+			}
+			return nil
+		})
+	}
+
+	if len(testPaths) == 0 {
+		walk(".", nodeTestFileRegex.MatchString)
+		return sources
+	}
+	for _, path := range testPaths {
+		info, err := os.Stat(path)
+		switch {
+		case err != nil:
+			// A glob, which node expands itself
+			matches, _ := filepath.Glob(path)
+			for _, match := range matches {
+				read(match)
+			}
+		case info.IsDir():
+			walk(path, func(p string) bool { return strings.HasSuffix(p, "js") || strings.HasSuffix(p, "ts") })
+		default:
+			read(path)
+		}
+	}
+	return sources
+}
+
+// firstLocation returns the first "location" diagnostic of a test or its subtests
+func firstLocation(node *nodeTestNode) string {
+	if location := node.Details["location"]; location != "" {
+		return location
+	}
+	for _, child := range node.Children {
+		if location := firstLocation(child); location != "" {
+			return location
+		}
+	}
+	return ""
+}
+
+// isTestFile checks if a test name is the path of an existing file
+func isTestFile(name string) bool {
+	if !strings.ContainsAny(name, `/\`) {
+		return false
+	}
+	info, err := os.Stat(name)
+	return err == nil && !info.IsDir()
+}
+
+// ensureFile discovers and starts a file group the first time it is seen, finalizing
+// the file group reported before it
+func (n *NodeTestDefinition) ensureFile(path string) *nodeTestFile {
+	if n.currentFile != "" && n.currentFile != path {
+		n.finalizeFile(n.files[n.currentFile])
+	}
+	n.currentFile = path
+
+	if file, ok := n.files[path]; ok {
+		return file
+	}
+	file := &nodeTestFile{Path: path, StartTime: time.Now()}
+	n.files[path] = file
+	n.fileOrder = append(n.fileOrder, path)
+	n.sendGroupDiscovered(path, []string{})
+	n.sendGroupStart(path, []string{})
+	return file
+}
+
+// flushFileOutput sends the output printed outside the tests to a file group
+func (n *NodeTestDefinition) flushFileOutput(file *nodeTestFile, output string) {
+	output = n.pendingOutput.String() + output
+	n.pendingOutput.Reset()
+	if output != "" {
+		n.sendGroupOutput(file.Path, []string{}, output)
+	}
+}
+
+// reportNode reports a test, or a suite and its subtests, and returns its totals
+func (n *NodeTestDefinition) reportNode(node *nodeTestNode, parents []string) tapTotals {
+	if len(node.Children) == 0 && node.Details["type"] != "suite" {
+		return n.sendTestCase(node, parents)
+	}
+
+	n.sendGroupDiscovered(node.Name, parents)
+	n.sendGroupStart(node.Name, parents)
+	if node.Output.Len() > 0 {
+		n.sendGroupOutput(node.Name, parents, node.Output.String())
+	}
+
+	var totals tapTotals
+	path := append(append([]string{}, parents...), node.Name)
+	for _, child := range node.Children {
+		totals.add(n.reportNode(child, path))
+	}
+
+	if failureType := node.Details["failureType"]; node.Failed && failureType != "subtestsFailed" && failureType != "cancelledByParent" {
+		// A hook or the suite itself failed, not just its subtests
+		n.sendGroupError(node.Name, parents, failureType, parseDurationMs(node.Details["duration_ms"]), nodeTestErrorMessage(node))
+		totals.errored = true
+		return totals
+	}
+
+	status := "PASS"
+	switch {
+	case node.Failed || totals.failed > 0 || totals.errored:
+		status = "FAIL"
+	case node.Directive != "" || (totals.passed == 0 && totals.skipped > 0):
+		status = "SKIP"
+	}
+	n.sendGroupResult(node.Name, parents, status, parseDurationMs(node.Details["duration_ms"]), totals)
+	return totals
+}
+
+// finalizeFile sends the result of a file group
+func (n *NodeTestDefinition) finalizeFile(file *nodeTestFile) {
+	if file == nil || file.Finalized {
+		return
+	}
+	file.Finalized = true
+	if file.Errored {
+		return
+	}
+	status := "PASS"
+	switch {
+	case file.Totals.failed > 0 || file.Totals.errored:
+		status = "FAIL"
+	case file.Totals.passed == 0 && file.Totals.skipped > 0:
+		status = "SKIP"
+	}
+	duration := float64(time.Since(file.StartTime).Milliseconds())
+	n.sendGroupResult(file.Path, []string{}, status, duration, file.Totals)
+}
+
+// finalizeFiles sends the results of the file groups not finalized yet, with any
+// output printed after the last test
+func (n *NodeTestDefinition) finalizeFiles() {
+	if n.pendingOutput.Len() > 0 && len(n.fileOrder) > 0 {
+		n.flushFileOutput(n.files[n.fileOrder[len(n.fileOrder)-1]], "")
+	}
+	for _, path := range n.fileOrder {
+		n.finalizeFile(n.files[path])
+	}
+}
+
+// parseNodeTestLocation splits a location like "/app/cart.test.js:12:5" into its file,
+// line and column
+func parseNodeTestLocation(location string) (string, int, int) {
+	matches := nodeTestLocationRegex.FindStringSubmatch(location)
+	if matches == nil {
+		return "", 0, 0
+	}
+	file := strings.TrimPrefix(matches[1], "file://")
+	line, _ := strconv.Atoi(matches[2])
+	column, _ := strconv.Atoi(matches[3])
+	return file, line, column
+}
+
+// nodeTestErrorMessage returns the error of a failed test, or its output when Node
+// only said the test failed
+func nodeTestErrorMessage(node *nodeTestNode) string {
+	message := strings.TrimSpace(node.Details["error"])
+	if (message == "" || message == "test failed") && node.Output.Len() > 0 {
+		return strings.TrimSpace(node.Output.String())
+	}
+	if message == "" {
+		return "test failed"
+	}
+	return message
+}
+
+// IPC event sending methods
+
+func (n *NodeTestDefinition) sendGroupDiscovered(groupName string, parentNames []string) {
+	n.sendIPCEvent(map[string]interface{}{
+		"eventType": "testGroupDiscovered",
+		"payload": map[string]interface{}{
+			"groupName":   groupName,
+			"parentNames": parentNames,
+		},
+	})
+}
+
+func (n *NodeTestDefinition) sendGroupStart(groupName string, parentNames []string) {
+	n.sendIPCEvent(map[string]interface{}{
+		"eventType": "testGroupStart",
+		"payload": map[string]interface{}{
+			"groupName":   groupName,
+			"parentNames": parentNames,
+		},
+	})
+}
+
+func (n *NodeTestDefinition) sendGroupOutput(groupName string, parentNames []string, output string) {
+	n.sendIPCEvent(map[string]interface{}{
+		"eventType": "groupStdout",
+		"payload": map[string]interface{}{
+			"groupName":   groupName,
+			"parentNames": parentNames,
+			"chunk":       output,
+		},
+	})
+}
+
+// sendTestCase sends the result of a test and returns its totals
+func (n *NodeTestDefinition) sendTestCase(node *nodeTestNode, parents []string) tapTotals {
+	payload := map[string]interface{}{
+		"testName":    node.Name,
+		"parentNames": parents,
+	}
+	if duration := parseDurationMs(node.Details["duration_ms"]); duration > 0 {
+		payload["duration"] = duration
+	}
+	file, line, _ := parseNodeTestLocation(node.Details["location"])
+	if file != "" {
+		payload["file"] = file
+		payload["line"] = line
+	}
+	if node.Output.Len() > 0 {
+		payload["stdout"] = node.Output.String()
+	}
+
+	var totals tapTotals
+	switch {
+	case node.Directive == "TODO":
+		payload["status"] = "SKIP"
+		payload["skipReason"] = ipc.SkipReasonTodo
+		if node.Reason != "" {
+			payload["skipMessage"] = node.Reason
+		}
+		totals.skipped++
+	case node.Directive == "SKIP":
+		payload["status"] = "SKIP"
+		if node.Reason != "" {
+			payload["skipMessage"] = node.Reason
+		}
+		totals.skipped++
+	case node.Failed && node.Details["failureType"] == "cancelledByParent":
+		// Never finished, as its parent failed or timed out first
+		payload["status"] = "SKIP"
+		payload["skipReason"] = ipc.SkipReasonNotRun
+		totals.skipped++
+	case node.Failed:
+		payload["status"] = "FAIL"
+		payload["error"] = nodeTestError(node)
+		totals.failed++
+	default:
+		payload["status"] = "PASS"
+		totals.passed++
+	}
+
+	n.sendIPCEvent(map[string]interface{}{
+		"eventType": "testCase",
+		"payload":   payload,
+	})
+	return totals
+}
+
+// nodeTestError builds the error of a failed test from its diagnostics
+func nodeTestError(node *nodeTestNode) map[string]interface{} {
+	details := node.Details
+	testError := map[string]interface{}{
+		"message": nodeTestErrorMessage(node),
+	}
+	if stack := details["stack"]; stack != "" {
+		testError["stack"] = stack
+	}
+	if expected, ok := details["expected"]; ok {
+		testError["expected"] = expected
+	}
+	if actual, ok := details["actual"]; ok {
+		testError["actual"] = actual
+	}
+	if errorType := details["name"]; errorType != "" {
+		testError["errorType"] = errorType
+	} else if code := details["code"]; code != "" {
+		testError["errorType"] = code
+	}
+	if file, line, column := parseNodeTestLocation(details["location"]); file != "" {
+		testError["location"] = fmt.Sprintf("%s:%d", file, line)
+		testError["file"] = file
+		testError["line"] = line
+		testError["column"] = column
+	}
+	if details["failureType"] == "testTimeoutFailure" {
+		testError["errorType"] = ipc.ErrorTypeTimedOut
+		testError["failureKind"] = ipc.FailureKindTimedOut
+	}
+	return testError
+}
+
+func (n *NodeTestDefinition) sendGroupResult(groupName string, parentNames []string, status string, duration float64, totals tapTotals) {
+	n.sendIPCEvent(map[string]interface{}{
+		"eventType": "testGroupResult",
+		"payload": map[string]interface{}{
+			"groupName":   groupName,
+			"parentNames": parentNames,
+			"status":      status,
+			"duration":    duration,
+			"totals": map[string]interface{}{
+				"passed":  totals.passed,
+				"failed":  totals.failed,
+				"skipped": totals.skipped,
+			},
+		},
+	})
+}
+
+func (n *NodeTestDefinition) sendGroupError(groupName string, parentNames []string, errorType string, duration float64, message string) {
+	n.sendIPCEvent(map[string]interface{}{
+		"eventType": "testGroupError",
+		"payload": map[string]interface{}{
+			"groupName":   groupName,
+			"parentNames": parentNames,
+			"errorType":   errorType,
+			"duration":    duration,
+			"error": map[string]interface{}{
+				"message": message,
+			},
+		},
+	})
+}
+
+// sendFileError reports a test file that failed before reporting any tests
+func (n *NodeTestDefinition) sendFileError(file *nodeTestFile, node *nodeTestNode) {
+	errorType := node.Details["failureType"]
+	if errorType == "" {
+		errorType = "testCodeFailure"
+	}
+	n.sendGroupError(file.Path, []string{}, errorType, parseDurationMs(node.Details["duration_ms"]), nodeTestErrorMessage(node))
+	file.Errored = true
+}
+
+func (n *NodeTestDefinition) sendIPCEvent(event map[string]interface{}) {
+	if n.ipcWriter == nil {
+		n.logger.Debug("IPC writer not initialized, skipping event: %v", event)
+		return
+	}
+
+	if err := n.ipcWriter.WriteEvent(event); err != nil {
+		n.logger.Debug("Failed to write IPC event: %v", err)
+	}
+}
@@ -0,0 +1,334 @@
+package definitions
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestNodeTestDefinition_Detect(t *testing.T) {
+	def := NewNodeTestDefinition(createTestLogger(t))
+
+	tests := []struct {
+		name     string
+		args     []string
+		expected bool
+	}{
+		{"node --test", []string{"node", "--test"}, true},
+		{"node --test with paths", []string{"node", "--test", "test/", "src/cart.test.js"}, true},
+		{"node options before --test", []string{"node", "--import", "tsx", "--test"}, true},
+		{"full path to node", []string{"/usr/local/bin/node", "--test"}, true},
+		{"node.exe", []string{`C:\Program Files\nodejs\node.exe`, "--test"}, true},
+		{"script taking --test", []string{"node", "build.js", "--test"}, false},
+		{"node without --test", []string{"node", "server.js"}, false},
+		{"nodemon", []string{"nodemon", "--test"}, false},
+		{"single node", []string{"node"}, false},
+		{"package manager", []string{"test"}, false},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if result := def.Detect(tt.args); result != tt.expected {
+				t.Errorf("Detect(%v) = %v, expected %v", tt.args, result, tt.expected)
+			}
+		})
+	}
+}
+
+func TestNodeTestDefinition_ModifyCommand(t *testing.T) {
+	def := NewNodeTestDefinition(createTestLogger(t))
+
+	tests := []struct {
+		name     string
+		args     []string
+		expected []string
+	}{
+		{"adds the TAP reporter", []string{"node", "--test"}, []string{"node", "--test", "--test-reporter=tap"}},
+		{"keeps options and paths", []string{"node", "--import", "tsx", "--test", "test/"}, []string{"node", "--import", "tsx", "--test", "--test-reporter=tap", "test/"}},
+		{"drops the command's reporters", []string{"node", "--test", "--test-reporter", "spec", "--test-reporter-destination=out.txt", "test/"}, []string{"node", "--test", "--test-reporter=tap", "test/"}},
+		{"npm script", []string{"npm", "test"}, []string{"npm", "test", "--", "--test-reporter=tap"}},
+		{"pnpm script", []string{"pnpm", "run", "test:unit"}, []string{"pnpm", "run", "test:unit", "--test-reporter=tap"}},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			result := def.ModifyCommand(tt.args, "", "")
+			if strings.Join(result, " ") != strings.Join(tt.expected, " ") {
+				t.Errorf("ModifyCommand(%v) = %v, expected %v", tt.args, result, tt.expected)
+			}
+		})
+	}
+}
+
+func TestNodeTestDefinition_AcceptsReporter(t *testing.T) {
+	def := NewNodeTestDefinition(createTestLogger(t))
+
+	tests := []struct {
+		name     string
+		args     []string
+		expected bool
+	}{
+		{"bare", []string{"node", "--test"}, true},
+		{"with options", []string{"node", "--import", "tsx", "--test", "--test-concurrency", "2"}, true},
+		{"with a test file", []string{"node", "--test", "test/cart.test.js"}, false},
+		{"with a reporter", []string{"node", "--test", "--test-reporter=spec"}, false},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if result := def.AcceptsReporter(tt.args); result != tt.expected {
+				t.Errorf("AcceptsReporter(%v) = %v, expected %v", tt.args, result, tt.expected)
+			}
+		})
+	}
+}
+
+func TestNodeTestDefinition_ProcessOutput(t *testing.T) {
+	def := NewNodeTestDefinition(createTestLogger(t))
+
+	// Node prints no location for passing tests, so their file is the one declaring them
+	if err := os.MkdirAll("test", 0755); err != nil {
+		t.Fatalf("Failed to create test directory: %v", err)
+	}
+	if err := os.WriteFile(filepath.Join("test", "checkout.test.js"), []byte("describe('checkout', () => {})\n"), 0644); err != nil {
+		t.Fatalf("Failed to write checkout.test.js: %v", err)
+	}
+	checkoutFile, err := filepath.Abs(filepath.Join("test", "checkout.test.js"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute path: %v", err)
+	}
+
+	// A test's console.log in the stream, and the output of a test file run in its
+	// own process as comments
+	output := `TAP version 13
+# Subtest: Cart
+    # Subtest: adds an item
+adding widget to cart
+    ok 1 - adds an item
+      ---
+      duration_ms: 0.512
+      type: 'test'
+      ...
+    # Subtest: applies a discount
+    not ok 2 - applies a discount
+      ---
+      duration_ms: 1.25
+      type: 'test'
+      location: '/app/cart.test.js:8:3'
+      failureType: 'testCodeFailure'
+      error: |-
+        Expected values to be strictly equal:
+
+        90 !== 80
+
+      code: 'ERR_ASSERTION'
+      name: 'AssertionError'
+      expected: 80
+      actual: 90
+      operator: 'strictEqual'
+      stack: |-
+        TestContext.<anonymous> (file:///app/cart.test.js:9:12)
+        Test.runInAsyncScope (node:async_hooks:206:9)
+      ...
+    # Subtest: ships abroad
+    ok 3 - ships abroad # SKIP no carrier yet
+      ---
+      duration_ms: 0.05
+      type: 'test'
+      ...
+    # Subtest: saves the cart \# later
+    ok 4 - saves the cart \# later # TODO
+      ---
+      duration_ms: 0.03
+      type: 'test'
+      ...
+    1..4
+not ok 1 - Cart
+  ---
+  duration_ms: 3.1
+  type: 'suite'
+  location: '/app/cart.test.js:3:1'
+  failureType: 'subtestsFailed'
+  error: '1 subtest failed'
+  code: 'ERR_TEST_FAILURE'
+  ...
+# charging 90
+# Subtest: checkout
+    # Subtest: charges the total
+    ok 1 - charges the total
+      ---
+      duration_ms: 0.2
+      ...
+    1..1
+ok 2 - checkout
+  ---
+  duration_ms: 0.9
+  type: 'suite'
+  ...
+1..2
+# tests 5
+# suites 2
+# pass 2
+# fail 1
+# cancelled 0
+# skipped 1
+# todo 1
+# duration_ms 48.2
+`
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	capture := NewTestIPCCapture(ipcPath)
+
+	tests := make(map[string]map[string]interface{})
+	for _, e := range capture.GetEventsByType("testCase") {
+		payload := e["payload"].(map[string]interface{})
+		tests[payload["testName"].(string)] = payload
+	}
+	if len(tests) != 5 {
+		t.Fatalf("Expected 5 tests, got %d: %v", len(tests), tests)
+	}
+
+	added := tests["adds an item"]
+	if added["status"] != "PASS" || added["duration"] != 0.512 {
+		t.Errorf("Expected adds an item to pass in 0.512ms, got %v", added)
+	}
+	if parents := added["parentNames"].([]interface{}); len(parents) != 2 || parents[0] != "/app/cart.test.js" || parents[1] != "Cart" {
+		t.Errorf("Expected adds an item under /app/cart.test.js > Cart, got %v", parents)
+	}
+	if added["stdout"] != "adding widget to cart\n" {
+		t.Errorf("Expected the test's output to be kept with it, got %q", added["stdout"])
+	}
+
+	discount := tests["applies a discount"]
+	testError, _ := discount["error"].(map[string]interface{})
+	if discount["status"] != "FAIL" || testError == nil {
+		t.Fatalf("Expected applies a discount to fail with an error, got %v", discount)
+	}
+	if testError["message"] != "Expected values to be strictly equal:\n\n90 !== 80" {
+		t.Errorf("Expected the assertion message, got %q", testError["message"])
+	}
+	if testError["expected"] != "80" || testError["actual"] != "90" || testError["errorType"] != "AssertionError" {
+		t.Errorf("Expected 80, 90 and AssertionError, got %v", testError)
+	}
+	if discount["file"] != "/app/cart.test.js" || discount["line"] != float64(8) {
+		t.Errorf("Expected applies a discount at cart.test.js:8, got %v", discount)
+	}
+	if testError["line"] != float64(8) || !strings.HasPrefix(testError["stack"].(string), "TestContext.<anonymous>") {
+		t.Errorf("Expected the location and stack, got %v", testError)
+	}
+
+	if skipped := tests["ships abroad"]; skipped["status"] != "SKIP" || skipped["skipMessage"] != "no carrier yet" {
+		t.Errorf("Expected ships abroad to be skipped with its reason, got %v", skipped)
+	}
+	if todo := tests["saves the cart # later"]; todo["status"] != "SKIP" || todo["skipReason"] != "todo" {
+		t.Errorf("Expected the todo test to be skipped as todo, got %v", todo)
+	}
+	if parents := tests["charges the total"]["parentNames"].([]interface{}); len(parents) != 2 || parents[0] != checkoutFile {
+		t.Errorf("Expected charges the total under %s, got %v", checkoutFile, parents)
+	}
+
+	results := make(map[string]map[string]interface{})
+	for _, e := range capture.GetEventsByType("testGroupResult") {
+		payload := e["payload"].(map[string]interface{})
+		results[payload["groupName"].(string)] = payload
+	}
+	cart := results["Cart"]
+	totals, _ := cart["totals"].(map[string]interface{})
+	if cart["status"] != "FAIL" || totals["passed"] != float64(1) || totals["failed"] != float64(1) || totals["skipped"] != float64(2) {
+		t.Errorf("Expected Cart to fail with 1 passed, 1 failed and 2 skipped, got %v", cart)
+	}
+	if results["/app/cart.test.js"]["status"] != "FAIL" || results[checkoutFile]["status"] != "PASS" {
+		t.Errorf("Expected cart.test.js to fail and checkout.test.js to pass, got %v", results)
+	}
+	if len(capture.GetEventsByType("testGroupError")) != 0 {
+		t.Error("Expected no group errors for failed subtests")
+	}
+
+	// Output outside the tests goes to the file reported next; the summary is dropped
+	var fileOutput strings.Builder
+	for _, e := range capture.GetEventsByType("groupStdout") {
+		payload := e["payload"].(map[string]interface{})
+		if payload["groupName"] != checkoutFile || payload["chunk"] != "charging 90\n" {
+			t.Errorf("Expected only checkout.test.js's output, got %v", payload)
+		}
+		fileOutput.WriteString(payload["chunk"].(string))
+	}
+	if fileOutput.String() != "charging 90\n" {
+		t.Errorf("Expected the comment output of checkout.test.js, got %q", fileOutput.String())
+	}
+
+	if len(capture.GetEventsByType("runComplete")) != 1 {
+		t.Error("Expected a runComplete event")
+	}
+}
+
+func TestNodeTestDefinition_ProcessOutput_FileSubtests(t *testing.T) {
+	def := NewNodeTestDefinition(createTestLogger(t))
+
+	// Node 18 reports each test file as a test named after it
+	dir := t.TempDir()
+	passing := filepath.Join(dir, "passing.test.js")
+	broken := filepath.Join(dir, "broken.test.js")
+	for _, file := range []string{passing, broken} {
+		if err := os.WriteFile(file, []byte("\n"), 0644); err != nil {
+			t.Fatalf("Failed to write %s: %v", file, err)
+		}
+	}
+
+	output := `TAP version 13
+# Subtest: ` + passing + `
+    # Subtest: works
+    ok 1 - works
+      ---
+      duration_ms: 1.1
+      ...
+    1..1
+ok 1 - ` + passing + `
+  ---
+  duration_ms: 40.2
+  ...
+# Subtest: ` + broken + `
+SyntaxError: Unexpected token '}'
+not ok 2 - ` + broken + `
+  ---
+  duration_ms: 30.5
+  failureType: 'testCodeFailure'
+  exitCode: 1
+  error: 'test failed'
+  code: 'ERR_TEST_FAILURE'
+  ...
+1..2
+`
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	capture := NewTestIPCCapture(ipcPath)
+
+	cases := capture.GetEventsByType("testCase")
+	if len(cases) != 1 {
+		t.Fatalf("Expected 1 test, got %v", cases)
+	}
+	payload := cases[0]["payload"].(map[string]interface{})
+	if parents := payload["parentNames"].([]interface{}); payload["testName"] != "works" || len(parents) != 1 || parents[0] != passing {
+		t.Errorf("Expected works directly under %s, got %v", passing, payload)
+	}
+
+	errors := capture.GetEventsByType("testGroupError")
+	if len(errors) != 1 {
+		t.Fatalf("Expected the broken file to error, got %v", errors)
+	}
+	groupError := errors[0]["payload"].(map[string]interface{})
+	message := groupError["error"].(map[string]interface{})["message"]
+	if groupError["groupName"] != broken || message != "SyntaxError: Unexpected token '}'" {
+		t.Errorf("Expected %s to error with its output, got %v", broken, groupError)
+	}
+	for _, e := range capture.GetEventsByType("testGroupResult") {
+		if e["payload"].(map[string]interface{})["groupName"] == broken {
+			t.Errorf("Expected no result after the group error, got %v", e)
+		}
+	}
+}
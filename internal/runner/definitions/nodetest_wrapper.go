@@ -0,0 +1,59 @@
+package definitions
+
+import (
+	"io"
+)
+
+// NodeTestWrapper wraps NodeTestDefinition to implement the Definition interface from runner package
+type NodeTestWrapper struct {
+	*NodeTestDefinition
+}
+
+// NewNodeTestWrapper creates a new wrapper for node --test
+func NewNodeTestWrapper(impl *NodeTestDefinition) *NodeTestWrapper {
+	return &NodeTestWrapper{NodeTestDefinition: impl}
+}
+
+// Matches checks if this runner can handle the given command
+func (n *NodeTestWrapper) Matches(command []string) bool {
+	return n.Detect(command)
+}
+
+// GetTestFiles returns list of test files (empty for dynamic discovery)
+func (n *NodeTestWrapper) GetTestFiles(args []string) ([]string, error) {
+	return n.NodeTestDefinition.GetTestFiles(args)
+}
+
+// BuildCommand returns the node --test command with the TAP reporter
+func (n *NodeTestWrapper) BuildCommand(args []string, adapterPath string) []string {
+	// node --test uses native processing, no adapter needed
+	return n.ModifyCommand(args, "", "")
+}
+
+// GetAdapterFileName returns empty as node --test doesn't use an adapter
+func (n *NodeTestWrapper) GetAdapterFileName() string {
+	return ""
+}
+
+// InterpretExitCode maps exit codes to success/failure
+func (n *NodeTestWrapper) InterpretExitCode(code int) string {
+	if code == 0 {
+		return "success"
+	}
+	return "failure"
+}
+
+// IsNative returns true as the TAP output is processed directly
+func (n *NodeTestWrapper) IsNative() bool {
+	return true
+}
+
+// GetNativeDefinition returns the underlying node --test definition
+func (n *NodeTestWrapper) GetNativeDefinition() interface{} {
+	return n.NodeTestDefinition
+}
+
+// ProcessOutput processes the node --test output
+func (n *NodeTestWrapper) ProcessOutput(stdout io.Reader, ipcPath string) error {
+	return n.NodeTestDefinition.ProcessOutput(stdout, ipcPath)
+}
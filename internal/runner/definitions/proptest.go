@@ -0,0 +1,74 @@
+package definitions
+
+import (
+	"path/filepath"
+	"regexp"
+	"strings"
+
+	"github.com/zk/3pio/internal/console"
+	"github.com/zk/3pio/internal/ipc"
+)
+
+// proptest panics with the failure of the smallest input it shrank the failing case to,
+// after printing where it saved the case:
+//
+//	proptest: Saving this and future failures in /src/app/proptest-regressions/lib.txt
+//	cc 6c5d30c1f7b4c0e2 # shrinks to n = -1
+//	thread 'tests::round_trips' panicked at src/lib.rs:9:5:
+//	Test failed: assertion failed: `(left == right)` at src/lib.rs:11.
+//	minimal failing input: n = -1
+//		successes: 0
+//
+// quickcheck panics with the arguments alone:
+//
+//	[quickcheck] TEST FAILED. Arguments: (-1)
+//	[quickcheck] TEST FAILED (runtime error). Arguments: (0, -1)
+var (
+	proptestInputRegex     = regexp.MustCompile(`(?m)^minimal failing input: (.*)$`)
+	proptestReasonRegex    = regexp.MustCompile(`(?m)^Test failed: (.*)$`)
+	proptestSavingRegex    = regexp.MustCompile(`(?m)^proptest: Saving this and future failures in (.+?)\s*$`)
+	proptestSeedRegex      = regexp.MustCompile(`(?m)^cc ([0-9a-f]+)(?: #.*)?$`)
+	quickcheckFailureRegex = regexp.MustCompile(`\[quickcheck\] TEST FAILED( \(runtime error\))?\. Arguments: \((.*)\)`)
+)
+
+// parsePropertyFailure reads the minimized failing case of a proptest or quickcheck
+// test from its panic message and output, or returns nil when the test failed
+// otherwise. The regression file is relative to root when it is inside it.
+func parsePropertyFailure(output, panicMessage, root string) *ipc.PropertyFailure {
+	panicMessage = console.StripANSI(panicMessage)
+	if m := quickcheckFailureRegex.FindStringSubmatch(panicMessage); m != nil {
+		return &ipc.PropertyFailure{Framework: "quickcheck", Input: m[2]}
+	}
+	m := proptestInputRegex.FindStringSubmatch(panicMessage)
+	if m == nil {
+		return nil
+	}
+	failure := &ipc.PropertyFailure{Framework: "proptest", Input: strings.TrimSpace(m[1])}
+
+	output = console.StripANSI(output)
+	if s := proptestSavingRegex.FindStringSubmatch(output); s != nil {
+		failure.RegressionFile = filepath.ToSlash(s[1])
+		if rel, err := filepath.Rel(root, s[1]); err == nil && root != "" && !strings.HasPrefix(rel, "..") {
+			failure.RegressionFile = filepath.ToSlash(rel)
+		}
+	}
+	if s := proptestSeedRegex.FindStringSubmatch(output); s != nil {
+		failure.Seed = s[1]
+	}
+	return failure
+}
+
+// propertyFailureKind classifies a failed property test: the property didn't hold
+// unless the code under it panicked outside an assertion
+func propertyFailureKind(failure *ipc.PropertyFailure, panicMessage string) string {
+	if failure.Framework == "quickcheck" {
+		if m := quickcheckFailureRegex.FindStringSubmatch(panicMessage); m != nil && m[1] != "" {
+			return ipc.FailureKindPanicked
+		}
+		return ipc.FailureKindAssertionFailed
+	}
+	if m := proptestReasonRegex.FindStringSubmatch(panicMessage); m != nil && !strings.HasPrefix(m[1], "assertion") {
+		return ipc.FailureKindPanicked
+	}
+	return ipc.FailureKindAssertionFailed
+}
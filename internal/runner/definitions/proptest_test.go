@@ -0,0 +1,111 @@
+package definitions
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"reflect"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/internal/ipc"
+)
+
+func TestParsePropertyFailure(t *testing.T) {
+	root := filepath.Join(string(filepath.Separator), "src", "app")
+	proptestOutput := "proptest: Saving this and future failures in " + filepath.Join(root, "proptest-regressions", "lib.txt") + "\n" +
+		"proptest: If this test was run on a CI system, you may wish to add the following line to your copy of the file. (You may need to create it.)\n" +
+		"cc 6c5d30c1f7b4c0e2a9d1 # shrinks to n = -1\n"
+
+	testCases := []struct {
+		desc         string
+		output       string
+		panicMessage string
+		expected     *ipc.PropertyFailure
+		kind         string
+	}{
+		{
+			"proptest assertion",
+			proptestOutput,
+			"Test failed: assertion failed: `(left == right)`\n  left: `4294967295`,\n right: `-1` at src/lib.rs:11.\nminimal failing input: n = -1\n\tsuccesses: 0\n\tlocal rejects: 0\n\tglobal rejects: 0\n",
+			&ipc.PropertyFailure{Framework: "proptest", Input: "n = -1", Seed: "6c5d30c1f7b4c0e2a9d1", RegressionFile: "proptest-regressions/lib.txt"},
+			ipc.FailureKindAssertionFailed,
+		},
+		{
+			"proptest panic without a regression file",
+			"",
+			"Test failed: called `Option::unwrap()` on a `None` value.\nminimal failing input: a = 0, b = -1\n\tsuccesses: 12\n",
+			&ipc.PropertyFailure{Framework: "proptest", Input: "a = 0, b = -1"},
+			ipc.FailureKindPanicked,
+		},
+		{
+			"quickcheck property",
+			"",
+			"[quickcheck] TEST FAILED. Arguments: (-1)",
+			&ipc.PropertyFailure{Framework: "quickcheck", Input: "-1"},
+			ipc.FailureKindAssertionFailed,
+		},
+		{
+			"quickcheck runtime error",
+			"",
+			"[quickcheck] TEST FAILED (runtime error). Arguments: (0, -1)\nError: \"attempt to divide by zero\"",
+			&ipc.PropertyFailure{Framework: "quickcheck", Input: "0, -1"},
+			ipc.FailureKindPanicked,
+		},
+		{"plain assertion", "", "assertion `left == right` failed\n  left: 1\n right: 2", nil, ""},
+	}
+	for _, tc := range testCases {
+		t.Run(tc.desc, func(t *testing.T) {
+			failure := parsePropertyFailure(tc.output, tc.panicMessage, root)
+			if !reflect.DeepEqual(failure, tc.expected) {
+				t.Fatalf("Expected %+v, got %+v", tc.expected, failure)
+			}
+			if failure != nil {
+				if kind := propertyFailureKind(failure, tc.panicMessage); kind != tc.kind {
+					t.Errorf("Expected %s, got %s", tc.kind, kind)
+				}
+			}
+		})
+	}
+}
+
+func TestCargoTestDefinition_PropertyFailure(t *testing.T) {
+	crateDir := t.TempDir()
+	originalDir, err := os.Getwd()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if err := os.Chdir(crateDir); err != nil {
+		t.Fatal(err)
+	}
+	defer func() { _ = os.Chdir(originalDir) }()
+
+	stdout, err := json.Marshal("proptest: Saving this and future failures in " + filepath.Join(crateDir, "proptest-regressions", "lib.txt") + "\n" +
+		"cc 6c5d30c1f7b4c0e2 # shrinks to n = -1\n" +
+		"thread 'tests::round_trips' panicked at src/lib.rs:9:5:\n" +
+		"Test failed: assertion failed: `(left == right)` at src/lib.rs:11.\nminimal failing input: n = -1\n\tsuccesses: 0\n")
+	if err != nil {
+		t.Fatal(err)
+	}
+	output := `     Running unittests src/lib.rs (target/debug/deps/rust_proptest-0123456789abcdef)
+{"type":"suite","event":"started","test_count":1}
+{"type":"test","event":"started","name":"tests::round_trips"}
+{"type":"test","name":"tests::round_trips","event":"failed","exec_time":0.02,"stdout":` + string(stdout) + `}
+{"type":"suite","event":"failed","passed":0,"failed":1,"ignored":0,"exec_time":0.03}
+`
+	def := NewCargoTestDefinition(createTestLogger(t))
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+
+	test := tapPayloads(NewTestIPCCapture(ipcPath), "testCase", "testName")["round_trips"]
+	errPayload, _ := test["error"].(map[string]interface{})
+	property, _ := errPayload["property"].(map[string]interface{})
+	if property["input"] != "n = -1" || property["seed"] != "6c5d30c1f7b4c0e2" || property["regressionFile"] != "proptest-regressions/lib.txt" {
+		t.Errorf("Expected the minimized input with its seed and regression file, got %v", property)
+	}
+	if errPayload["failureKind"] != ipc.FailureKindAssertionFailed {
+		t.Errorf("Expected %s, got %v", ipc.FailureKindAssertionFailed, errPayload["failureKind"])
+	}
+}
@@ -0,0 +1,246 @@
+package definitions
+
+import (
+	"os"
+	"slices"
+	"sort"
+	"strings"
+
+	"github.com/zk/3pio/internal/runinfo"
+)
+
+// rustEnvVars are the environment variables that change how Rust tests are built and run
+var rustEnvVars = []string{
+	"RUST_TEST_THREADS", "RUSTFLAGS", "RUSTDOCFLAGS", "CARGO_BUILD_JOBS", "CARGO_BUILD_TARGET",
+	"CARGO_INCREMENTAL", "CARGO_PROFILE_TEST_OPT_LEVEL",
+}
+
+// RunnerInfo returns the Rust toolchain probes for the cargo test command
+func (c *CargoTestDefinition) RunnerInfo(command []string) runinfo.Info {
+	return rustInfo(command)
+}
+
+// RunnerInfo returns the Rust toolchain probes, the version of cargo-nextest and the
+// --partition the command runs. nextest's --profile is its own, not cargo's.
+func (n *NextestDefinition) RunnerInfo(command []string) runinfo.Info {
+	info := rustInfo(command)
+	info.Cargo.Profile = cargoProfile(command, "--cargo-profile", "dev")
+	toolchain := cargoToolchain(command)
+	info.Probes = append(info.Probes, runinfo.Probe{
+		Tool: "cargo-nextest",
+		Args: withToolchain(toolchain, cargoPath(command), "nextest", "--version"),
+	})
+	info.EnvVars = append(info.EnvVars, "NEXTEST_PROFILE", "NEXTEST_TEST_THREADS", "NEXTEST_RETRIES")
+	info.Shard = runinfo.ShardFlag(command, "--partition")
+	return info
+}
+
+// RunnerInfo returns the Rust toolchain probes for the cargo bench command
+func (c *CriterionDefinition) RunnerInfo(command []string) runinfo.Info {
+	info := rustInfo(command)
+	info.Cargo.Profile = cargoProfile(command, "--profile", "bench")
+	return info
+}
+
+// RunnerInfo returns the Rust toolchain probes and the version of wasm-pack, which
+// builds the tests for wasm32-unknown-unknown with the cargo flags after "--"
+func (w *WasmPackDefinition) RunnerInfo(command []string) runinfo.Info {
+	wasmPack, cargoArgs := "wasm-pack", []string{}
+	if len(command) > 0 {
+		wasmPack = command[0]
+	}
+	if i := slices.Index(command, "--"); i != -1 {
+		cargoArgs = command[i+1:]
+	}
+	info := rustInfo([]string{"cargo", "test"})
+	info.Probes = append(info.Probes, runinfo.Probe{Tool: "wasm-pack", Args: []string{wasmPack, "--version"}})
+	info.Target = "wasm32-unknown-unknown"
+	info.Cargo = cargoBuild(cargoArgs, info.Target)
+	info.Cargo.Profile = cargoProfile(command, "--profile", "dev")
+	info.EnvVars = append(append([]string(nil), rustEnvVars...), "WASM_BINDGEN_TEST_TIMEOUT", "CHROMEDRIVER", "GECKODRIVER", "SAFARIDRIVER")
+	return info
+}
+
+// RunnerInfo returns the version of node, which is also the version of its test runner
+func (n *NodeTestDefinition) RunnerInfo(command []string) runinfo.Info {
+	nodeCmd := "node"
+	if len(command) > 0 {
+		nodeCmd = command[0]
+	}
+	return runinfo.Info{
+		Probes:  []runinfo.Probe{{Tool: "node", Args: []string{nodeCmd, "--version"}}},
+		EnvVars: []string{"NODE_OPTIONS", "NODE_ENV", "TZ"},
+	}
+}
+
+// RunnerInfo returns the version of bun, which is also the version of its test runner
+func (b *BunTestDefinition) RunnerInfo(command []string) runinfo.Info {
+	bunCmd := "bun"
+	if len(command) > 0 {
+		bunCmd = command[0]
+	}
+	return runinfo.Info{
+		Probes:  []runinfo.Probe{{Tool: "bun", Args: []string{bunCmd, "--version"}}},
+		EnvVars: []string{"NODE_ENV", "TZ"},
+	}
+}
+
+// RunnerInfo returns the Go version and the platform go test builds for
+func (g *GoTestDefinition) RunnerInfo(command []string) runinfo.Info {
+	goCmd := "go"
+	if len(command) > 0 {
+		goCmd = command[0]
+	}
+	return runinfo.Info{
+		Probes: []runinfo.Probe{
+			{Tool: "go", Args: []string{goCmd, "version"}},
+			{Args: []string{goCmd, "env", "GOOS", "GOARCH"}, Parse: parseGoEnvTarget},
+		},
+		EnvVars: []string{"GOFLAGS", "GOMAXPROCS", "CGO_ENABLED", "GOOS", "GOARCH", "GOEXPERIMENT"},
+	}
+}
+
+// rustInfo returns the probes for the toolchain a cargo command runs with: rustc,
+// cargo and the active rustup toolchain, under the command's "+toolchain" if it has
+// one, and the profile, features and target it builds the tests with
+func rustInfo(command []string) runinfo.Info {
+	toolchain := cargoToolchain(command)
+	rustc := []string{"rustc", "--version", "--verbose"}
+	if custom := os.Getenv("RUSTC"); custom != "" {
+		// cargo builds with $RUSTC, which isn't a rustup proxy
+		rustc[0] = custom
+	} else {
+		rustc = withToolchain(toolchain, rustc...)
+	}
+
+	target := cargoFlagValue(command, "--target")
+	if target == "" && UsesCross(command) {
+		target = os.Getenv("CROSS_BUILD_TARGET")
+	}
+	if target == "" {
+		target = os.Getenv("CARGO_BUILD_TARGET")
+	}
+	info := runinfo.Info{
+		Probes: []runinfo.Probe{
+			{Tool: "rustc", Args: rustc, Parse: parseRustcHost},
+			{Tool: "cargo", Args: withToolchain(toolchain, cargoPath(command), "--version")},
+			{Args: withToolchain(toolchain, "rustup", "show", "active-toolchain"), Parse: parseActiveToolchain},
+		},
+		EnvVars: rustEnvVars,
+		Target:  target,
+		Cargo:   cargoBuild(command, target),
+	}
+	if UsesCross(command) {
+		// The toolchain of the container, not the host's, builds the tests
+		info.Probes = append(info.Probes, runinfo.Probe{Tool: "cross", Args: []string{command[0], "--version"}})
+		info.EnvVars = append(append([]string(nil), rustEnvVars...), "CROSS_BUILD_TARGET", "CROSS_CONTAINER_ENGINE", "CROSS_CONTAINER_OPTS")
+	}
+	if UsesMiri(command) {
+		// Results depend on the miri of the toolchain and the checks MIRIFLAGS turns on
+		info.Probes = append(info.Probes, runinfo.Probe{Tool: "miri", Args: withToolchain(toolchain, cargoPath(command), "miri", "--version")})
+		info.EnvVars = append(append([]string(nil), info.EnvVars...), "MIRIFLAGS", "MIRI_SYSROOT")
+	}
+	return info
+}
+
+// cargoBuild returns the profile and features a cargo test command builds with
+func cargoBuild(command []string, target string) *runinfo.Cargo {
+	build := &runinfo.Cargo{Profile: cargoProfile(command, "--profile", "dev"), Target: target}
+	for i, arg := range command {
+		if arg == "--" {
+			break
+		}
+		switch arg {
+		case "--all-features":
+			build.AllFeatures = true
+		case "--no-default-features":
+			build.NoDefaultFeatures = true
+		}
+		value, ok := strings.CutPrefix(arg, "--features=")
+		if !ok && (arg == "--features" || arg == "-F") && i+1 < len(command) {
+			value, ok = command[i+1], true
+		}
+		if !ok {
+			continue
+		}
+		// -F serde,derive and -F "serde derive" alike
+		for _, feature := range strings.FieldsFunc(value, func(r rune) bool { return r == ',' || r == ' ' }) {
+			if !slices.Contains(build.Features, feature) {
+				build.Features = append(build.Features, feature)
+			}
+		}
+	}
+	sort.Strings(build.Features)
+	return build
+}
+
+// cargoProfile returns the profile the command's flag names, "release" for --release,
+// or the subcommand's default
+func cargoProfile(command []string, flag, defaultProfile string) string {
+	if profile := cargoFlagValue(command, flag); profile != "" {
+		return profile
+	}
+	for _, arg := range command {
+		if arg == "--" {
+			break
+		}
+		if arg == "--release" || arg == "-r" {
+			return "release"
+		}
+	}
+	return defaultProfile
+}
+
+// cargoPath returns how the command runs cargo: the host's cargo for cross, which runs
+// it in its container
+func cargoPath(command []string) string {
+	if len(command) > 0 && !UsesCross(command) {
+		return command[0]
+	}
+	return "cargo"
+}
+
+// cargoToolchain returns the "+toolchain" override of a cargo command, "" without one
+func cargoToolchain(command []string) string {
+	if len(command) > 1 && strings.HasPrefix(command[1], "+") {
+		return command[1]
+	}
+	return ""
+}
+
+// withToolchain passes a "+toolchain" override to a rustup proxy (cargo, rustc, rustup)
+func withToolchain(toolchain string, args ...string) []string {
+	if toolchain == "" {
+		return args
+	}
+	return append([]string{args[0], toolchain}, args[1:]...)
+}
+
+// parseRustcHost records the host triple of rustc --version --verbose as the target,
+// unless the command builds for another one
+func parseRustcHost(output string, md *runinfo.Metadata) {
+	if md.Target != "" {
+		return
+	}
+	for _, line := range strings.Split(output, "\n") {
+		if host, ok := strings.CutPrefix(strings.TrimSpace(line), "host: "); ok {
+			md.Target = host
+			return
+		}
+	}
+}
+
+// parseActiveToolchain records the toolchain of rustup show active-toolchain, whose
+// output is e.g. "stable-x86_64-unknown-linux-gnu (default)"
+func parseActiveToolchain(output string, md *runinfo.Metadata) {
+	if fields := strings.Fields(output); len(fields) > 0 {
+		md.Toolchain = fields[0]
+	}
+}
+
+// parseGoEnvTarget records the GOOS and GOARCH of go env as the target, e.g. "linux/amd64"
+func parseGoEnvTarget(output string, md *runinfo.Metadata) {
+	if fields := strings.Fields(output); len(fields) == 2 {
+		md.Target = fields[0] + "/" + fields[1]
+	}
+}
@@ -0,0 +1,137 @@
+package definitions
+
+import (
+	"os"
+	"reflect"
+	"testing"
+
+	"github.com/zk/3pio/internal/runinfo"
+)
+
+func TestNextestDefinition_RunnerInfo(t *testing.T) {
+	// t.Setenv restores the variables afterwards, so they can be unset for the test
+	for _, name := range []string{"RUSTC", "CARGO_BUILD_TARGET"} {
+		t.Setenv(name, "")
+		_ = os.Unsetenv(name)
+	}
+
+	def := NewNextestDefinition(createTestLogger(t))
+	info := def.RunnerInfo([]string{"cargo", "+nightly", "nextest", "run", "--target", "wasm32-wasip1", "--", "slow"})
+
+	var probes [][]string
+	for _, probe := range info.Probes {
+		probes = append(probes, probe.Args)
+	}
+	expected := [][]string{
+		{"rustc", "+nightly", "--version", "--verbose"},
+		{"cargo", "+nightly", "--version"},
+		{"rustup", "+nightly", "show", "active-toolchain"},
+		{"cargo", "+nightly", "nextest", "--version"},
+	}
+	if !reflect.DeepEqual(probes, expected) {
+		t.Errorf("Expected probes %v, got %v", expected, probes)
+	}
+	if info.Target != "wasm32-wasip1" {
+		t.Errorf("Expected the --target of the command, got %q", info.Target)
+	}
+	if info.Shard != nil {
+		t.Errorf("Expected no shard without --partition, got %v", info.Shard)
+	}
+
+	info = def.RunnerInfo([]string{"cargo", "nextest", "run", "--partition", "count:2/3"})
+	if info.Shard == nil || *info.Shard != (runinfo.Shard{Index: 2, Total: 3}) {
+		t.Errorf("Expected shard 2/3 from --partition, got %v", info.Shard)
+	}
+}
+
+func TestCargoTestDefinition_RunnerInfo(t *testing.T) {
+	t.Setenv("RUSTC", "/opt/rust/bin/rustc")
+	t.Setenv("CARGO_BUILD_TARGET", "aarch64-unknown-linux-gnu")
+
+	def := NewCargoTestDefinition(createTestLogger(t))
+	info := def.RunnerInfo([]string{"cargo", "+stable", "test"})
+
+	// $RUSTC isn't a rustup proxy, so it gets no +toolchain
+	if rustc := info.Probes[0].Args; !reflect.DeepEqual(rustc, []string{"/opt/rust/bin/rustc", "--version", "--verbose"}) {
+		t.Errorf("Expected $RUSTC to be probed, got %v", rustc)
+	}
+	if info.Target != "aarch64-unknown-linux-gnu" {
+		t.Errorf("Expected the target of CARGO_BUILD_TARGET, got %q", info.Target)
+	}
+
+	// The probes' output fills in the toolchain, and the host when no target is given
+	md := &runinfo.Metadata{}
+	parseRustcHost("rustc 1.82.0 (f6e511eec 2024-10-15)\nbinary: rustc\nhost: x86_64-unknown-linux-gnu\nrelease: 1.82.0", md)
+	parseActiveToolchain("stable-x86_64-unknown-linux-gnu (default)", md)
+	if md.Target != "x86_64-unknown-linux-gnu" || md.Toolchain != "stable-x86_64-unknown-linux-gnu" {
+		t.Errorf("Expected the host target and the active toolchain, got %q and %q", md.Target, md.Toolchain)
+	}
+	md = &runinfo.Metadata{Target: "wasm32-wasip1"}
+	parseRustcHost("host: x86_64-unknown-linux-gnu", md)
+	if md.Target != "wasm32-wasip1" {
+		t.Errorf("Expected the command's target to be kept, got %q", md.Target)
+	}
+}
+
+func TestRunnerInfo_Cargo(t *testing.T) {
+	t.Setenv("CARGO_BUILD_TARGET", "")
+	_ = os.Unsetenv("CARGO_BUILD_TARGET")
+	t.Setenv("CROSS_BUILD_TARGET", "armv7-unknown-linux-gnueabihf")
+
+	tests := []struct {
+		name     string
+		info     runinfo.Info
+		expected runinfo.Cargo
+	}{
+		{
+			name:     "cargo test",
+			info:     NewCargoTestDefinition(createTestLogger(t)).RunnerInfo([]string{"cargo", "test"}),
+			expected: runinfo.Cargo{Profile: "dev"},
+		},
+		{
+			name: "cargo test with features",
+			info: NewCargoTestDefinition(createTestLogger(t)).RunnerInfo([]string{
+				"cargo", "test", "--release", "--features", "serde,derive", "-F", "tokio serde", "--no-default-features",
+				"--target", "x86_64-unknown-linux-musl", "--", "--features=ignored",
+			}),
+			expected: runinfo.Cargo{
+				Profile:           "release",
+				Features:          []string{"derive", "serde", "tokio"},
+				NoDefaultFeatures: true,
+				Target:            "x86_64-unknown-linux-musl",
+			},
+		},
+		{
+			name:     "cargo test with a profile",
+			info:     NewCargoTestDefinition(createTestLogger(t)).RunnerInfo([]string{"cargo", "test", "--profile=ci", "--all-features"}),
+			expected: runinfo.Cargo{Profile: "ci", AllFeatures: true},
+		},
+		{
+			name:     "nextest's own profile",
+			info:     NewNextestDefinition(createTestLogger(t)).RunnerInfo([]string{"cargo", "nextest", "run", "--profile", "ci"}),
+			expected: runinfo.Cargo{Profile: "dev"},
+		},
+		{
+			name:     "nextest's cargo profile",
+			info:     NewNextestDefinition(createTestLogger(t)).RunnerInfo([]string{"cargo", "nextest", "run", "--cargo-profile", "fast"}),
+			expected: runinfo.Cargo{Profile: "fast"},
+		},
+		{
+			name:     "cargo bench",
+			info:     NewCriterionDefinition(createTestLogger(t)).RunnerInfo([]string{"cargo", "bench"}),
+			expected: runinfo.Cargo{Profile: "bench"},
+		},
+		{
+			name:     "cross test with the target of its environment",
+			info:     NewCargoTestDefinition(createTestLogger(t)).RunnerInfo([]string{"cross", "test"}),
+			expected: runinfo.Cargo{Profile: "dev", Target: "armv7-unknown-linux-gnueabihf"},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if tt.info.Cargo == nil || !reflect.DeepEqual(*tt.info.Cargo, tt.expected) {
+				t.Errorf("Expected %+v, got %+v", tt.expected, tt.info.Cargo)
+			}
+		})
+	}
+}
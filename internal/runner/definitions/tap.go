@@ -0,0 +1,794 @@
+package definitions
+
+import (
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"regexp"
+	"strconv"
+	"strings"
+
+	"github.com/zk/3pio/internal/ipc"
+	"github.com/zk/3pio/internal/logger"
+)
+
+// Patterns for TAP version 12 and 13, e.g.
+//
+//	TAP version 13
+//	1..3
+//	ok 1 - adds numbers
+//	not ok 2 - divides
+//	  ---
+//	  message: 'expected 2, got 0'
+//	  ...
+//	ok 3 # SKIP no network
+//
+// Subtests are a TAP stream indented four spaces, printed before the test point that
+// ends them and optionally after a "# Subtest: name" comment.
+var (
+	tapVersionRegex = regexp.MustCompile(`^TAP version \d+$`)
+	tapPlanRegex    = regexp.MustCompile(`^1\.\.(\d+)(?:\s*#\s*(.*))?$`)
+	tapPointRegex   = regexp.MustCompile(`^(ok|not ok)(?:\s+(\d+))?(?:\s*-)?(?:\s+(.*))?$`)
+	tapSubtestRegex = regexp.MustCompile(`^# Subtest(?::|$)`)
+	tapBailOutRegex = regexp.MustCompile(`^Bail out!\s*(.*)$`)
+	tapYAMLKeyRegex = regexp.MustCompile(`^(\w+):(?: (.*))?$`)
+)
+
+// Patterns for what follows the "#" that ends a test point's description: a SKIP or
+// TODO directive, which the spec matches in any case and with any suffix (e.g.
+// "# skipped: no db"), or a comment such as node-tap's "# time=12.5ms"
+var (
+	tapDirectiveRegex = regexp.MustCompile(`^(?i:(SKIP|TODO))\S*(?:\s+(.*))?$`)
+	tapTimeRegex      = regexp.MustCompile(`^time=(\d+(?:\.\d+)?)(ms|s)$`)
+)
+
+// TAPDefinition runs any command that prints TAP to stdout, such as a Perl test script,
+// bats --tap or a libtap suite. It is only chosen with --runner tap.
+type TAPDefinition struct {
+	logger    *logger.FileLogger
+	ipcWriter *IPCWriter
+
+	lineLimit // Longest line of output parsed whole (--max-line-length)
+
+	rootGroup   string // Group the tests are reported under, named after the test script
+	rootStarted bool
+	rootOutput  strings.Builder // Output before the first test point and between top-level tests
+	totals      tapTotals
+
+	levels         []*tapLevel     // TAP streams being read: the document, then the subtests in it
+	pending        *tapNode        // Top-level test point, reported once the output after it is read
+	lastDone       *tapNode        // Test point that was the previous line, whose diagnostics may follow
+	lastDoneIndent int             // Indentation of lastDone
+	yaml           *tapDiagnostics // YAML diagnostics of a test point being read
+
+	bailedOut    bool
+	streamErrors []string // Why the TAP can't be trusted, e.g. it had no plan
+}
+
+// tapLevel is a TAP stream: the document, or the subtests of a test point
+type tapLevel struct {
+	plan    int  // Planned number of tests, -1 until the plan line
+	skipAll bool // The plan was "1..0 # SKIP", as nothing could run
+	next    int  // Number the next test point gets when it has none
+	seen    map[int]bool
+	points  []*tapNode
+	output  strings.Builder // Output before the first test point
+}
+
+// tapNode is a test point, with the subtests printed before it
+type tapNode struct {
+	Name      string
+	Number    int
+	Failed    bool              // "not ok"
+	Directive string            // "SKIP" or "TODO"
+	Reason    string            // Text after the directive
+	Details   map[string]string // YAML diagnostics
+	Output    strings.Builder   // Output of the test, such as the diagnostics Test::More prints after a failure
+	Children  []*tapNode
+	Missing   bool // Counted by the plan but never printed
+}
+
+// tapTotals counts the test results under a group
+type tapTotals struct {
+	passed, failed, skipped int
+	errored                 bool // A suite under the group failed outside its tests
+}
+
+func (t *tapTotals) add(other tapTotals) {
+	t.passed += other.passed
+	t.failed += other.failed
+	t.skipped += other.skipped
+	t.errored = t.errored || other.errored
+}
+
+// NewTAPDefinition creates a new TAP runner definition
+func NewTAPDefinition(logger *logger.FileLogger) *TAPDefinition {
+	return &TAPDefinition{logger: logger}
+}
+
+// Name returns the name of this test runner
+func (t *TAPDefinition) Name() string {
+	return "tap"
+}
+
+// Detect never claims a command: what prints TAP can't be told from the command line
+func (t *TAPDefinition) Detect(args []string) bool {
+	return false
+}
+
+// ModifyCommand returns the command unchanged, recording the group its tests go under
+func (t *TAPDefinition) ModifyCommand(cmd []string, ipcPath, runID string) []string {
+	t.rootGroup = tapRootGroup(cmd)
+	return append([]string{}, cmd...)
+}
+
+// tapRootGroup names the group of a command's tests after its last argument that is a
+// file, e.g. "perl t/basic.t" or "./run_tests", else after the command
+func tapRootGroup(cmd []string) string {
+	for i := len(cmd) - 1; i >= 0; i-- {
+		if info, err := os.Stat(cmd[i]); err == nil && !info.IsDir() && strings.ContainsAny(cmd[i], `/\.`) {
+			if abs, err := filepath.Abs(cmd[i]); err == nil {
+				return abs
+			}
+		}
+	}
+	if len(cmd) == 0 {
+		return "tap"
+	}
+	return filepath.Base(cmd[0])
+}
+
+// GetTestFiles returns empty array for dynamic discovery
+func (t *TAPDefinition) GetTestFiles(args []string) ([]string, error) {
+	return []string{}, nil
+}
+
+// RequiresAdapter returns false as TAP is parsed directly
+func (t *TAPDefinition) RequiresAdapter() bool {
+	return false
+}
+
+// StreamError says why the TAP can't be trusted, which errors the run whatever the
+// command's exit code, or "" if it can
+func (t *TAPDefinition) StreamError() string {
+	return strings.Join(t.streamErrors, "; ")
+}
+
+// ProcessOutput reads the command's output and converts its TAP to IPC events. Lines
+// that aren't TAP are output of the test point before them.
+func (t *TAPDefinition) ProcessOutput(combinedOutput io.Reader, ipcPath string) error {
+	var err error
+	t.ipcWriter, err = NewIPCWriter(ipcPath)
+	if err != nil {
+		return fmt.Errorf("failed to create IPC writer: %w", err)
+	}
+	defer func() {
+		if err := t.ipcWriter.Close(); err != nil {
+			t.logger.Debug("Failed to close IPC writer: %v", err)
+		}
+	}()
+
+	if t.rootGroup == "" {
+		t.rootGroup = "tap"
+	}
+	t.levels = []*tapLevel{newTAPLevel()}
+
+	lines := t.newLineReader(combinedOutput)
+	for {
+		line, _, ok := lines.Next()
+		if !ok {
+			break
+		}
+		t.processLine(line)
+	}
+
+	if err := lines.Err(); err != nil {
+		return fmt.Errorf("error reading TAP output: %w", err)
+	}
+
+	t.finish()
+
+	runCompleteEvent := map[string]interface{}{
+		"eventType": "runComplete",
+		"payload":   map[string]interface{}{},
+	}
+	if err := t.ipcWriter.WriteEvent(runCompleteEvent); err != nil {
+		t.logger.Debug("Failed to send runComplete event: %v", err)
+	}
+
+	return nil
+}
+
+func newTAPLevel() *tapLevel {
+	return &tapLevel{plan: -1, next: 1, seen: make(map[int]bool)}
+}
+
+// processLine processes a single line of TAP
+func (t *TAPDefinition) processLine(line string) {
+	line = strings.TrimSuffix(line, "\r")
+	if t.yaml != nil {
+		consumed := t.yaml.readLine(line)
+		if t.yaml.done {
+			t.yaml = nil
+		}
+		if consumed {
+			return
+		}
+	}
+
+	trimmed := strings.TrimLeft(line, " ")
+	indent := len(line) - len(trimmed)
+	nesting := indent / 4
+
+	lastDone := t.lastDone
+	t.lastDone = nil
+	if trimmed == "---" && lastDone != nil && indent == t.lastDoneIndent+2 {
+		t.yaml = newTAPDiagnostics(lastDone.Details, indent)
+		return
+	}
+
+	switch {
+	case trimmed == "", tapVersionRegex.MatchString(trimmed), strings.HasPrefix(trimmed, "pragma "):
+		return
+	}
+
+	if matches := tapBailOutRegex.FindStringSubmatch(trimmed); matches != nil {
+		t.flushPending()
+		t.bailedOut = true
+		t.streamErrors = append(t.streamErrors, strings.TrimSpace("Bail out! "+matches[1]))
+		return
+	}
+	if tapSubtestRegex.MatchString(trimmed) {
+		// Producers indent it with the parent (node) or with the subtests (Test::More),
+		// so the subtests' own indentation is what nests them. At the top level it
+		// ends the test before it, and the output that follows is the next test's.
+		if nesting == 0 {
+			t.flushPending()
+		}
+		return
+	}
+	if matches := tapPlanRegex.FindStringSubmatch(trimmed); matches != nil {
+		t.setPlan(nesting, matches[1], matches[2])
+		return
+	}
+	if matches := tapPointRegex.FindStringSubmatch(trimmed); matches != nil {
+		t.readTestPoint(nesting, matches[1] == "not ok", matches[2], matches[3])
+		t.lastDoneIndent = indent
+		return
+	}
+
+	if strings.HasPrefix(trimmed, "#") {
+		// Diagnostics of the test point before them, at their own level
+		t.outputTarget(nesting).WriteString(strings.TrimPrefix(strings.TrimPrefix(trimmed, "#"), " ") + "\n")
+		return
+	}
+
+	// Output that isn't TAP at all has no level of its own
+	t.outputTarget(len(t.levels) - 1).WriteString(line + "\n")
+}
+
+// openLevels starts subtest streams until the one at depth is being read
+func (t *TAPDefinition) openLevels(depth int) {
+	if depth > 0 && len(t.levels) == 1 {
+		// The next top-level test starts with its subtests
+		t.flushPending()
+	}
+	for len(t.levels) <= depth {
+		t.levels = append(t.levels, newTAPLevel())
+	}
+}
+
+// closeLevels ends the subtest streams deeper than depth and returns the test points
+// and output of the one just below it, the subtests of the test point that ends them
+func (t *TAPDefinition) closeLevels(depth int) ([]*tapNode, string) {
+	var points []*tapNode
+	var output string
+	for len(t.levels) > depth+1 {
+		level := t.levels[len(t.levels)-1]
+		t.levels = t.levels[:len(t.levels)-1]
+		t.finishLevel(level)
+		// Subtests that no test point ended stay with the stream above them
+		points = append(level.points, points...)
+		output = level.output.String() + output
+	}
+	return points, output
+}
+
+// setPlan records the plan of the stream at nesting
+func (t *TAPDefinition) setPlan(nesting int, count, comment string) {
+	t.openLevels(nesting)
+	if nesting == 0 {
+		t.flushPending()
+	}
+	level := t.levels[nesting]
+	if level.plan >= 0 {
+		t.streamErrors = append(t.streamErrors, "More than one plan")
+		return
+	}
+	level.plan, _ = strconv.Atoi(count)
+	level.skipAll = level.plan == 0 && strings.HasPrefix(strings.ToUpper(comment), "SKIP")
+	if level.skipAll && nesting == 0 && comment != "" {
+		t.rootOutput.WriteString(comment + "\n")
+	}
+}
+
+// readTestPoint records a test point at nesting, ending the subtests printed before it
+func (t *TAPDefinition) readTestPoint(nesting int, failed bool, number, description string) {
+	t.openLevels(nesting)
+	children, childOutput := t.closeLevels(nesting)
+	level := t.levels[nesting]
+
+	node := &tapNode{Failed: failed, Details: make(map[string]string), Children: children}
+	node.Output.WriteString(childOutput)
+	description, comment := splitTAPDescription(description)
+	if matches := tapDirectiveRegex.FindStringSubmatch(comment); matches != nil {
+		node.Directive, node.Reason = strings.ToUpper(matches[1]), matches[2]
+	} else if matches := tapTimeRegex.FindStringSubmatch(comment); matches != nil {
+		duration := parseDurationMs(matches[1])
+		if matches[2] == "s" {
+			duration *= 1000
+		}
+		node.Details["duration_ms"] = strconv.FormatFloat(duration, 'f', -1, 64)
+	}
+	node.Name = unescapeTAP(description)
+
+	if number == "" {
+		node.Number = level.next
+	} else {
+		node.Number, _ = strconv.Atoi(number)
+		switch {
+		case level.seen[node.Number]:
+			t.streamErrors = append(t.streamErrors, fmt.Sprintf("Test %d was reported twice", node.Number))
+		case node.Number != level.next:
+			t.streamErrors = append(t.streamErrors, fmt.Sprintf("Tests out of sequence: found test %d where %d was expected", node.Number, level.next))
+		}
+	}
+	level.seen[node.Number] = true
+	level.next = node.Number + 1
+	level.points = append(level.points, node)
+	if node.Name == "" {
+		node.Name = fmt.Sprintf("test %d", node.Number)
+	}
+
+	if nesting == 0 {
+		t.flushPending()
+		t.pending = node
+	}
+	t.lastDone = node
+}
+
+// splitTAPDescription splits a test point's description at its first unescaped "#",
+// returning the description and the text after the "#"
+func splitTAPDescription(description string) (string, string) {
+	for i := 0; i < len(description); i++ {
+		switch description[i] {
+		case '\\':
+			i++
+		case '#':
+			return strings.TrimSpace(description[:i]), strings.TrimSpace(description[i+1:])
+		}
+	}
+	return strings.TrimSpace(description), ""
+}
+
+// finishLevel checks a stream against its plan once it has ended, adding the tests
+// the plan counted that never reported
+func (t *TAPDefinition) finishLevel(level *tapLevel) {
+	if level.plan < 0 || t.bailedOut {
+		return
+	}
+	for _, point := range level.points {
+		if point.Number > level.plan {
+			t.streamErrors = append(t.streamErrors, fmt.Sprintf("Planned %d tests but test %d ran", level.plan, point.Number))
+			break
+		}
+	}
+	for number := 1; number <= level.plan; number++ {
+		if level.seen[number] {
+			continue
+		}
+		missing := &tapNode{Name: fmt.Sprintf("test %d", number), Number: number, Failed: true, Missing: true, Details: map[string]string{
+			"message": fmt.Sprintf("Test %d never reported a result, though the plan counted %d tests", number, level.plan),
+		}}
+		level.points = append(level.points, missing)
+	}
+}
+
+// outputTarget returns where output at nesting goes: to the last test point of its
+// stream, else to the stream itself
+func (t *TAPDefinition) outputTarget(nesting int) *strings.Builder {
+	if nesting >= len(t.levels) {
+		nesting = len(t.levels) - 1
+	}
+	if nesting == 0 {
+		if t.pending != nil {
+			return &t.pending.Output
+		}
+		return &t.rootOutput
+	}
+	level := t.levels[nesting]
+	if len(level.points) > 0 {
+		return &level.points[len(level.points)-1].Output
+	}
+	return &level.output
+}
+
+// flushPending reports the top-level test point whose output was being read
+func (t *TAPDefinition) flushPending() {
+	if t.pending == nil {
+		return
+	}
+	node := t.pending
+	t.pending = nil
+	if t.yaml != nil {
+		t.yaml.finish()
+		t.yaml = nil
+	}
+	t.ensureRoot()
+	t.totals.add(t.reportNode(node, []string{t.rootGroup}))
+}
+
+// finish reports what is left once the output has ended and checks the document
+// against its plan
+func (t *TAPDefinition) finish() {
+	if t.yaml != nil {
+		t.yaml.finish()
+		t.yaml = nil
+	}
+	// Subtests the output ended in the middle of are reported as top-level tests
+	orphans, orphanOutput := t.closeLevels(0)
+	t.flushPending()
+	t.rootOutput.WriteString(orphanOutput)
+
+	root := t.levels[0]
+	reported := len(root.points)
+	root.points = append(root.points, orphans...)
+	if root.plan < 0 && !t.bailedOut {
+		t.streamErrors = append(t.streamErrors, "No plan: the TAP never said how many tests to expect")
+	}
+	t.finishLevel(root)
+
+	t.ensureRoot()
+	for _, node := range root.points[reported:] {
+		t.totals.add(t.reportNode(node, []string{t.rootGroup}))
+	}
+	if t.rootOutput.Len() > 0 {
+		t.sendGroupOutput(t.rootGroup, []string{}, t.rootOutput.String())
+	}
+
+	status := "PASS"
+	switch {
+	case t.totals.failed > 0 || t.totals.errored:
+		status = "FAIL"
+	case root.skipAll || (t.totals.passed == 0 && t.totals.skipped > 0):
+		status = "SKIP"
+	}
+	t.sendGroupResult(t.rootGroup, []string{}, status, 0, t.totals)
+}
+
+// ensureRoot discovers and starts the group of the tests
+func (t *TAPDefinition) ensureRoot() {
+	if t.rootStarted {
+		return
+	}
+	t.rootStarted = true
+	t.sendGroupDiscovered(t.rootGroup, []string{})
+	t.sendGroupStart(t.rootGroup, []string{})
+}
+
+// reportNode reports a test point, or a test point with subtests as a group, and
+// returns its totals
+func (t *TAPDefinition) reportNode(node *tapNode, parents []string) tapTotals {
+	if len(node.Children) == 0 {
+		return t.sendTestCase(node, parents)
+	}
+
+	t.sendGroupDiscovered(node.Name, parents)
+	t.sendGroupStart(node.Name, parents)
+	if node.Output.Len() > 0 {
+		t.sendGroupOutput(node.Name, parents, node.Output.String())
+	}
+
+	var totals tapTotals
+	path := append(append([]string{}, parents...), node.Name)
+	for _, child := range node.Children {
+		totals.add(t.reportNode(child, path))
+	}
+
+	status := "PASS"
+	switch {
+	case node.Directive == "TODO", node.Directive == "SKIP":
+		status = "SKIP"
+	case node.Failed && totals.failed == 0:
+		// The subtests passed, so the failure is the test's own, such as a wrong plan
+		t.sendGroupError(node.Name, parents, parseDurationMs(node.Details["duration_ms"]), tapErrorMessage(node))
+		totals.errored = true
+		return totals
+	case node.Failed || totals.failed > 0:
+		status = "FAIL"
+	case totals.passed == 0 && totals.skipped > 0:
+		status = "SKIP"
+	}
+	t.sendGroupResult(node.Name, parents, status, parseDurationMs(node.Details["duration_ms"]), totals)
+	return totals
+}
+
+// sendTestCase sends the result of a test point and returns its totals
+func (t *TAPDefinition) sendTestCase(node *tapNode, parents []string) tapTotals {
+	payload := map[string]interface{}{
+		"testName":    node.Name,
+		"parentNames": parents,
+	}
+	if duration := parseDurationMs(node.Details["duration_ms"]); duration > 0 {
+		payload["duration"] = duration
+	}
+	if node.Output.Len() > 0 {
+		payload["stdout"] = node.Output.String()
+	}
+
+	var totals tapTotals
+	switch {
+	case node.Directive == "TODO":
+		// A TODO test is expected to fail, and neither result counts
+		payload["status"] = "SKIP"
+		payload["skipReason"] = ipc.SkipReasonTodo
+		if node.Reason != "" {
+			payload["skipMessage"] = node.Reason
+		}
+		totals.skipped++
+	case node.Directive == "SKIP":
+		payload["status"] = "SKIP"
+		if node.Reason != "" {
+			payload["skipMessage"] = node.Reason
+		}
+		totals.skipped++
+	case node.Failed:
+		payload["status"] = "FAIL"
+		payload["error"] = tapError(node)
+		totals.failed++
+	default:
+		payload["status"] = "PASS"
+		totals.passed++
+	}
+
+	t.sendIPCEvent(map[string]interface{}{
+		"eventType": "testCase",
+		"payload":   payload,
+	})
+	return totals
+}
+
+// tapErrorMessage returns why a test point failed, from its diagnostics or else the
+// comments after it, falling back to the test point itself
+func tapErrorMessage(node *tapNode) string {
+	if message := strings.TrimSpace(node.Details["message"]); message != "" {
+		return message
+	}
+	if output := strings.TrimSpace(node.Output.String()); output != "" {
+		return output
+	}
+	return fmt.Sprintf("not ok %d - %s", node.Number, node.Name)
+}
+
+// tapError builds the error of a failed test point
+func tapError(node *tapNode) map[string]interface{} {
+	details := node.Details
+	testError := map[string]interface{}{
+		"message": tapErrorMessage(node),
+	}
+	for _, key := range []string{"expected", "wanted", "expect"} {
+		if value, ok := details[key]; ok {
+			testError["expected"] = value
+			break
+		}
+	}
+	for _, key := range []string{"actual", "found", "got"} {
+		if value, ok := details[key]; ok {
+			testError["actual"] = value
+			break
+		}
+	}
+	if stack := details["stack"]; stack != "" {
+		testError["stack"] = stack
+	}
+	if node.Missing {
+		testError["errorType"] = "MISSING"
+	}
+	return testError
+}
+
+func (t *TAPDefinition) sendGroupDiscovered(groupName string, parentNames []string) {
+	t.sendIPCEvent(map[string]interface{}{
+		"eventType": "testGroupDiscovered",
+		"payload": map[string]interface{}{
+			"groupName":   groupName,
+			"parentNames": parentNames,
+		},
+	})
+}
+
+func (t *TAPDefinition) sendGroupStart(groupName string, parentNames []string) {
+	t.sendIPCEvent(map[string]interface{}{
+		"eventType": "testGroupStart",
+		"payload": map[string]interface{}{
+			"groupName":   groupName,
+			"parentNames": parentNames,
+		},
+	})
+}
+
+func (t *TAPDefinition) sendGroupOutput(groupName string, parentNames []string, output string) {
+	t.sendIPCEvent(map[string]interface{}{
+		"eventType": "groupStdout",
+		"payload": map[string]interface{}{
+			"groupName":   groupName,
+			"parentNames": parentNames,
+			"chunk":       output,
+		},
+	})
+}
+
+func (t *TAPDefinition) sendGroupResult(groupName string, parentNames []string, status string, duration float64, totals tapTotals) {
+	t.sendIPCEvent(map[string]interface{}{
+		"eventType": "testGroupResult",
+		"payload": map[string]interface{}{
+			"groupName":   groupName,
+			"parentNames": parentNames,
+			"status":      status,
+			"duration":    duration,
+			"totals": map[string]interface{}{
+				"passed":  totals.passed,
+				"failed":  totals.failed,
+				"skipped": totals.skipped,
+			},
+		},
+	})
+}
+
+func (t *TAPDefinition) sendGroupError(groupName string, parentNames []string, duration float64, message string) {
+	t.sendIPCEvent(map[string]interface{}{
+		"eventType": "testGroupError",
+		"payload": map[string]interface{}{
+			"groupName":   groupName,
+			"parentNames": parentNames,
+			"errorType":   "TEST_FAILURE",
+			"duration":    duration,
+			"error": map[string]interface{}{
+				"message": message,
+			},
+		},
+	})
+}
+
+func (t *TAPDefinition) sendIPCEvent(event map[string]interface{}) {
+	if t.ipcWriter == nil {
+		t.logger.Debug("IPC writer not initialized, skipping event: %v", event)
+		return
+	}
+
+	if err := t.ipcWriter.WriteEvent(event); err != nil {
+		t.logger.Debug("Failed to write IPC event: %v", err)
+	}
+}
+
+// tapDiagnostics reads the YAML diagnostics of a TAP test point, between a "---" and
+// a "..." indented two spaces more than the test point. Values are kept as text, and
+// block scalars and nested maps as their lines.
+type tapDiagnostics struct {
+	details map[string]string
+	indent  int    // Indentation of the "---"
+	key     string // Key whose value continues on the following lines
+	done    bool
+}
+
+func newTAPDiagnostics(details map[string]string, indent int) *tapDiagnostics {
+	return &tapDiagnostics{details: details, indent: indent}
+}
+
+// readLine reads a line of the diagnostics, returning false once the line is no longer
+// part of them
+func (d *tapDiagnostics) readLine(line string) bool {
+	trimmed := strings.TrimLeft(line, " ")
+	indent := len(line) - len(trimmed)
+
+	switch {
+	case trimmed == "":
+		if d.key != "" {
+			d.details[d.key] += "\n"
+		}
+		return true
+	case indent < d.indent:
+		// Diagnostics that were never closed
+		d.finish()
+		return false
+	case indent == d.indent && trimmed == "...":
+		d.finish()
+		return true
+	case indent == d.indent:
+		matches := tapYAMLKeyRegex.FindStringSubmatch(trimmed)
+		if matches == nil {
+			d.key = ""
+			return true
+		}
+		d.key = matches[1]
+		switch value := matches[2]; value {
+		case "", "|", "|-", "|+", ">", ">-":
+			d.details[d.key] = ""
+		default:
+			d.details[d.key] = unquoteYAML(value)
+		}
+		return true
+	}
+
+	// A block value or nested map, indented under its key
+	if d.key != "" {
+		value := line[min(len(line), d.indent+2):]
+		if existing := d.details[d.key]; existing != "" && !strings.HasSuffix(existing, "\n") {
+			d.details[d.key] = existing + "\n"
+		}
+		d.details[d.key] += value + "\n"
+	}
+	return true
+}
+
+// finish ends the diagnostics, dropping the newlines that end block values
+func (d *tapDiagnostics) finish() {
+	for key, value := range d.details {
+		d.details[key] = strings.TrimRight(value, "\n")
+	}
+	d.done = true
+}
+
+// parseDurationMs parses a duration_ms diagnostic
+func parseDurationMs(value string) float64 {
+	duration, err := strconv.ParseFloat(value, 64)
+	if err != nil {
+		return 0
+	}
+	return duration
+}
+
+// unescapeTAP reverses the escaping of "#" and "\" in TAP names and comments, and the
+// "\n" and "\t" Node also prints
+func unescapeTAP(s string) string {
+	if !strings.Contains(s, `\`) {
+		return s
+	}
+	var sb strings.Builder
+	for i := 0; i < len(s); i++ {
+		if s[i] != '\\' || i+1 == len(s) {
+			sb.WriteByte(s[i])
+			continue
+		}
+		i++
+		switch s[i] {
+		case 'n':
+			sb.WriteByte('\n')
+		case 't':
+			sb.WriteByte('\t')
+		case 'r':
+			sb.WriteByte('\r')
+		default:
+			// "\#" and "\\"
+			sb.WriteByte(s[i])
+		}
+	}
+	return sb.String()
+}
+
+// unquoteYAML returns the value of a single-line YAML scalar
+func unquoteYAML(value string) string {
+	switch {
+	case len(value) >= 2 && value[0] == '\'' && value[len(value)-1] == '\'':
+		return strings.ReplaceAll(value[1:len(value)-1], "''", "'")
+	case len(value) >= 2 && value[0] == '"' && value[len(value)-1] == '"':
+		if unquoted, err := strconv.Unquote(value); err == nil {
+			return unquoted
+		}
+		return value[1 : len(value)-1]
+	case value == "~":
+		return ""
+	}
+	return value
+}
@@ -0,0 +1,317 @@
+package definitions
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+// runTAP parses TAP output and returns the definition and its events
+func runTAP(t *testing.T, output string) (*TAPDefinition, *TestIPCCapture) {
+	t.Helper()
+	def := NewTAPDefinition(createTestLogger(t))
+	def.ModifyCommand([]string{"prove"}, "", "")
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	return def, NewTestIPCCapture(ipcPath)
+}
+
+// tapPayloads returns the payloads of the events of a type by their name key
+func tapPayloads(capture *TestIPCCapture, eventType, key string) map[string]map[string]interface{} {
+	payloads := make(map[string]map[string]interface{})
+	for _, e := range capture.GetEventsByType(eventType) {
+		payload := e["payload"].(map[string]interface{})
+		payloads[payload[key].(string)] = payload
+	}
+	return payloads
+}
+
+func TestTAPDefinition_Detect(t *testing.T) {
+	def := NewTAPDefinition(createTestLogger(t))
+	for _, args := range [][]string{{"prove", "-v", "t/"}, {"bats", "--tap", "test/"}, {"./run_tests"}} {
+		if def.Detect(args) {
+			t.Errorf("Expected %v to need --runner tap, but it was detected", args)
+		}
+	}
+}
+
+func TestTAPDefinition_ModifyCommand(t *testing.T) {
+	def := NewTAPDefinition(createTestLogger(t))
+	if err := os.MkdirAll("t", 0755); err != nil {
+		t.Fatalf("Failed to create t directory: %v", err)
+	}
+	if err := os.WriteFile(filepath.Join("t", "basic.t"), []byte("\n"), 0644); err != nil {
+		t.Fatalf("Failed to write basic.t: %v", err)
+	}
+	script, err := filepath.Abs(filepath.Join("t", "basic.t"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute path: %v", err)
+	}
+
+	tests := []struct {
+		name     string
+		args     []string
+		expected string
+	}{
+		{"test script", []string{"perl", "t/basic.t"}, script},
+		{"no test script", []string{"make", "test"}, "make"},
+		{"full path to the command", []string{"/usr/local/bin/tap-suite", "--all"}, "tap-suite"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			result := def.ModifyCommand(tt.args, "", "")
+			if strings.Join(result, " ") != strings.Join(tt.args, " ") {
+				t.Errorf("Expected the command unchanged, got %v", result)
+			}
+			if def.rootGroup != tt.expected {
+				t.Errorf("Expected tests under %q, got %q", tt.expected, def.rootGroup)
+			}
+		})
+	}
+}
+
+func TestTAPDefinition_TestMore(t *testing.T) {
+	// prove -v t/cart.t, with Test::More's diagnostics on stderr
+	def, capture := runTAP(t, `ok 1 - use Cart;
+not ok 2 - total includes tax
+#   Failed test 'total includes tax'
+#   at t/cart.t line 12.
+#          got: '90'
+#     expected: '80'
+ok 3 # skip no network
+not ok 4 - refunds # TODO refunds not implemented
+#   Failed (TODO) test 'refunds'
+#   at t/cart.t line 20.
+    # Subtest: discounts
+    ok 1 - percentage
+    ok 2 - fixed
+    1..2
+ok 5 - discounts
+1..5
+# Looks like you failed 1 test of 5.
+`)
+	tests := tapPayloads(capture, "testCase", "testName")
+	if len(tests) != 6 {
+		t.Fatalf("Expected 6 tests, got %d: %v", len(tests), tests)
+	}
+
+	if used := tests["use Cart;"]; used["status"] != "PASS" {
+		t.Errorf("Expected use Cart; to pass, got %v", used)
+	}
+	tax := tests["total includes tax"]
+	testError, _ := tax["error"].(map[string]interface{})
+	if tax["status"] != "FAIL" || testError == nil {
+		t.Fatalf("Expected total includes tax to fail with an error, got %v", tax)
+	}
+	if message := testError["message"].(string); !strings.HasPrefix(message, "Failed test 'total includes tax'") || !strings.Contains(message, "got: '90'") {
+		t.Errorf("Expected the diagnostics after the test as its message, got %q", message)
+	}
+	if skipped := tests["test 3"]; skipped["status"] != "SKIP" || skipped["skipMessage"] != "no network" {
+		t.Errorf("Expected test 3 to be skipped with its reason, got %v", skipped)
+	}
+	if todo := tests["refunds"]; todo["status"] != "SKIP" || todo["skipReason"] != "todo" || todo["skipMessage"] != "refunds not implemented" {
+		t.Errorf("Expected refunds to be skipped as todo, got %v", todo)
+	}
+	if parents := tests["percentage"]["parentNames"].([]interface{}); len(parents) != 2 || parents[0] != "prove" || parents[1] != "discounts" {
+		t.Errorf("Expected percentage under prove > discounts, got %v", parents)
+	}
+
+	results := tapPayloads(capture, "testGroupResult", "groupName")
+	if discounts := results["discounts"]; discounts["status"] != "PASS" {
+		t.Errorf("Expected discounts to pass, got %v", discounts)
+	}
+	root := results["prove"]
+	totals, _ := root["totals"].(map[string]interface{})
+	if root["status"] != "FAIL" || totals["passed"] != float64(3) || totals["failed"] != float64(1) || totals["skipped"] != float64(2) {
+		t.Errorf("Expected prove to fail with 3 passed, 1 failed and 2 skipped, got %v", root)
+	}
+
+	output := tapPayloads(capture, "groupStdout", "groupName")
+	if output["prove"]["chunk"] != "Looks like you failed 1 test of 5.\n" {
+		t.Errorf("Expected the output after the plan on the root group, got %v", output)
+	}
+	if def.StreamError() != "" {
+		t.Errorf("Expected no stream error, got %q", def.StreamError())
+	}
+}
+
+func TestTAPDefinition_Bats(t *testing.T) {
+	// bats --tap test/calc.bats
+	def, capture := runTAP(t, `1..3
+ok 1 adds numbers
+not ok 2 handles empty input
+# (in test file test/calc.bats, line 9)
+#   `+"`"+`[ "$status" -eq 0 ]' failed
+ok 3 prints help # skip no help yet
+`)
+	tests := tapPayloads(capture, "testCase", "testName")
+	if tests["adds numbers"]["status"] != "PASS" {
+		t.Errorf("Expected adds numbers to pass, got %v", tests["adds numbers"])
+	}
+	testError, _ := tests["handles empty input"]["error"].(map[string]interface{})
+	if testError == nil || !strings.HasPrefix(testError["message"].(string), "(in test file test/calc.bats, line 9)") {
+		t.Errorf("Expected handles empty input to fail with bats' diagnostics, got %v", tests["handles empty input"])
+	}
+	if help := tests["prints help"]; help["status"] != "SKIP" || help["skipMessage"] != "no help yet" {
+		t.Errorf("Expected prints help to be skipped with its reason, got %v", help)
+	}
+	if def.StreamError() != "" {
+		t.Errorf("Expected no stream error, got %q", def.StreamError())
+	}
+}
+
+func TestTAPDefinition_YAMLDiagnostics(t *testing.T) {
+	// node-tap's TAP 13, with a subtest's plan before its tests
+	_, capture := runTAP(t, `TAP version 13
+# Subtest: parser
+    1..2
+    ok 1 - reads a plan
+    not ok 2 - reads yaml
+      ---
+      found: 1
+      wanted: 2
+      at:
+        line: 4
+        file: test/parser.js
+      stack: |
+        Test.<anonymous> (test/parser.js:4:5)
+      ...
+not ok 1 - parser # time=12.5ms
+1..1
+# failed 1 of 1 tests
+`)
+	tests := tapPayloads(capture, "testCase", "testName")
+	if len(tests) != 2 {
+		t.Fatalf("Expected 2 tests, got %v", tests)
+	}
+	testError, _ := tests["reads yaml"]["error"].(map[string]interface{})
+	if testError == nil {
+		t.Fatalf("Expected reads yaml to fail with an error, got %v", tests["reads yaml"])
+	}
+	if testError["expected"] != "2" || testError["actual"] != "1" {
+		t.Errorf("Expected wanted 2 and found 1, got %v", testError)
+	}
+	if testError["stack"] != "Test.<anonymous> (test/parser.js:4:5)" || testError["message"] != "not ok 2 - reads yaml" {
+		t.Errorf("Expected the stack and the test point as the message, got %v", testError)
+	}
+
+	parser := tapPayloads(capture, "testGroupResult", "groupName")["parser"]
+	if parser["status"] != "FAIL" || parser["duration"] != 12.5 {
+		t.Errorf("Expected parser to fail in 12.5ms, got %v", parser)
+	}
+}
+
+func TestTAPDefinition_MissingTests(t *testing.T) {
+	// libtap's output when the program exits early
+	def, capture := runTAP(t, `1..4
+ok 1 - a
+not ok 2 - b
+#   Failed test 'b'
+#   at t/test.c line 10.
+ok 3 - c
+# Looks like you planned 4 tests but ran 3.
+`)
+	tests := tapPayloads(capture, "testCase", "testName")
+	missing := tests["test 4"]
+	testError, _ := missing["error"].(map[string]interface{})
+	if missing["status"] != "FAIL" || testError == nil || testError["errorType"] != "MISSING" {
+		t.Fatalf("Expected the test the plan counted to fail as missing, got %v", missing)
+	}
+	if !strings.Contains(testError["message"].(string), "never reported a result") {
+		t.Errorf("Expected the missing test's message to say why it failed, got %q", testError["message"])
+	}
+	if def.StreamError() != "" {
+		t.Errorf("Expected a missing test to fail, not error the run, got %q", def.StreamError())
+	}
+}
+
+func TestTAPDefinition_NestedFailures(t *testing.T) {
+	// A subtest that ran fewer tests than it planned, and one that failed after its
+	// tests passed
+	_, capture := runTAP(t, `1..2
+    # Subtest: short plan
+    ok 1 - inner
+    1..2
+not ok 1 - short plan
+    ok 1 - runs
+    1..1
+not ok 2 - cleanup
+# cleanup left files behind
+`)
+	tests := tapPayloads(capture, "testCase", "testName")
+	if parents := tests["test 2"]["parentNames"].([]interface{}); tests["test 2"]["status"] != "FAIL" || len(parents) != 2 || parents[1] != "short plan" {
+		t.Errorf("Expected the missing subtest to fail under short plan, got %v", tests["test 2"])
+	}
+
+	results := tapPayloads(capture, "testGroupResult", "groupName")
+	if results["short plan"]["status"] != "FAIL" {
+		t.Errorf("Expected short plan to fail, got %v", results["short plan"])
+	}
+	if _, ok := results["cleanup"]; ok {
+		t.Errorf("Expected no result for cleanup after its error, got %v", results["cleanup"])
+	}
+	errors := tapPayloads(capture, "testGroupError", "groupName")
+	cleanup := errors["cleanup"]
+	if cleanup == nil || cleanup["error"].(map[string]interface{})["message"] != "cleanup left files behind" {
+		t.Errorf("Expected cleanup to error with its diagnostics, got %v", errors)
+	}
+	if results["prove"]["status"] != "FAIL" {
+		t.Errorf("Expected prove to fail, got %v", results["prove"])
+	}
+}
+
+func TestTAPDefinition_StreamErrors(t *testing.T) {
+	tests := []struct {
+		name     string
+		output   string
+		expected []string
+	}{
+		{
+			"no plan and out of sequence",
+			"ok 1 - first\nok 3 - third\nok 3 - third again\n",
+			[]string{"found test 3 where 2 was expected", "Test 3 was reported twice", "No plan"},
+		},
+		{
+			"bail out",
+			"1..3\nok 1 - connects\nBail out! database is down\n",
+			[]string{"Bail out! database is down"},
+		},
+		{
+			"test beyond the plan",
+			"1..1\nok 1 - first\nok 2 - second\n",
+			[]string{"Planned 1 tests but test 2 ran"},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			def, _ := runTAP(t, tt.output)
+			for _, expected := range tt.expected {
+				if !strings.Contains(def.StreamError(), expected) {
+					t.Errorf("Expected the stream error to contain %q, got %q", expected, def.StreamError())
+				}
+			}
+		})
+	}
+
+	// A bail out leaves the tests after it unreported rather than missing
+	_, capture := runTAP(t, "1..3\nok 1 - connects\nBail out! database is down\n")
+	if cases := capture.GetEventsByType("testCase"); len(cases) != 1 {
+		t.Errorf("Expected only the test before the bail out, got %v", cases)
+	}
+}
+
+func TestTAPDefinition_SkipAll(t *testing.T) {
+	def, capture := runTAP(t, "1..0 # SKIP no database\n")
+	root := tapPayloads(capture, "testGroupResult", "groupName")["prove"]
+	if root["status"] != "SKIP" {
+		t.Errorf("Expected a skip-all plan to skip the tests, got %v", root)
+	}
+	if def.StreamError() != "" {
+		t.Errorf("Expected no stream error, got %q", def.StreamError())
+	}
+}
@@ -0,0 +1,59 @@
+package definitions
+
+import (
+	"io"
+)
+
+// TAPWrapper wraps TAPDefinition to implement the Definition interface from runner package
+type TAPWrapper struct {
+	*TAPDefinition
+}
+
+// NewTAPWrapper creates a new wrapper for the TAP runner
+func NewTAPWrapper(impl *TAPDefinition) *TAPWrapper {
+	return &TAPWrapper{TAPDefinition: impl}
+}
+
+// Matches never claims a command, as the TAP runner is only chosen with --runner tap
+func (t *TAPWrapper) Matches(command []string) bool {
+	return t.Detect(command)
+}
+
+// GetTestFiles returns list of test files (empty for dynamic discovery)
+func (t *TAPWrapper) GetTestFiles(args []string) ([]string, error) {
+	return t.TAPDefinition.GetTestFiles(args)
+}
+
+// BuildCommand returns the command unchanged
+func (t *TAPWrapper) BuildCommand(args []string, adapterPath string) []string {
+	// The TAP is parsed as the command prints it, no adapter needed
+	return t.ModifyCommand(args, "", "")
+}
+
+// GetAdapterFileName returns empty as the TAP runner doesn't use an adapter
+func (t *TAPWrapper) GetAdapterFileName() string {
+	return ""
+}
+
+// InterpretExitCode maps exit codes to success/failure
+func (t *TAPWrapper) InterpretExitCode(code int) string {
+	if code == 0 {
+		return "success"
+	}
+	return "failure"
+}
+
+// IsNative returns true as the TAP output is processed directly
+func (t *TAPWrapper) IsNative() bool {
+	return true
+}
+
+// GetNativeDefinition returns the underlying TAP definition
+func (t *TAPWrapper) GetNativeDefinition() interface{} {
+	return t.TAPDefinition
+}
+
+// ProcessOutput processes the TAP output
+func (t *TAPWrapper) ProcessOutput(stdout io.Reader, ipcPath string) error {
+	return t.TAPDefinition.ProcessOutput(stdout, ipcPath)
+}
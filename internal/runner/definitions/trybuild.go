@@ -0,0 +1,130 @@
+package definitions
+
+import (
+	"path/filepath"
+	"regexp"
+	"strings"
+
+	"github.com/zk/3pio/internal/console"
+	"github.com/zk/3pio/internal/ipc"
+)
+
+// trybuild checks each UI test case in turn, then panics with "1 of 2 tests failed".
+// It writes to stderr itself, so libtest doesn't capture the output:
+//
+//	test tests/ui/missing-semicolon.rs ... mismatch
+//
+//	EXPECTED:
+//	┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈
+//	error: expected `;`, found `}`
+//	┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈
+//
+//	ACTUAL OUTPUT:
+//	┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈
+//	error: expected `;`, found keyword `let`
+//	┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈
+//	note: If the actual output is the correct output you can bless it by rerunning
+//	      your test with the environment variable TRYBUILD=overwrite
+//
+// A case without a .stderr file yet is "wip", its output written for review:
+//
+//	test tests/ui/new-case.rs ... wip
+//
+//	NOTE: writing the following output to `wip/new-case.stderr`.
+//	Move this file to `tests/ui/new-case.stderr` to accept it as correct.
+var (
+	trybuildTestRegex    = regexp.MustCompile(`^test (\S+\.rs)(?: \[should (?:pass|fail to compile)\])? \.\.\. (ok|mismatch|wip|error)$`)
+	trybuildWritingRegex = regexp.MustCompile("^NOTE: writing the following output to `(.+)`\\.$")
+	trybuildMoveRegex    = regexp.MustCompile("^Move this file to `(.+)` to accept it as correct\\.$")
+)
+
+// trybuildSnippetRule is how the lines around each compiler output start
+const trybuildSnippetRule = "┈┈┈┈┈┈┈┈┈┈"
+
+// isTrybuildTestLine reports whether a line starts the check of a trybuild test case
+func isTrybuildTestLine(line string) bool {
+	return trybuildTestRegex.MatchString(strings.TrimRight(console.StripANSI(line), "\r"))
+}
+
+// parseTrybuildCases reads the trybuild test cases that failed from the output of a
+// trybuild test, in the order trybuild checked them. trybuild's paths are relative to
+// the crate's directory dir; they are returned relative to root when inside it.
+func parseTrybuildCases(output, dir, root string) []ipc.TrybuildCase {
+	var cases []ipc.TrybuildCase
+	var current *ipc.TrybuildCase
+	var snippet []string
+	label, inSnippet, wantMessage := "", false, false
+
+	finish := func() {
+		if current != nil {
+			cases = append(cases, *current)
+		}
+		current = nil
+	}
+	for _, line := range strings.Split(console.StripANSI(output), "\n") {
+		line = strings.TrimRight(line, "\r")
+		if m := trybuildTestRegex.FindStringSubmatch(line); m != nil {
+			finish()
+			if m[2] != "ok" {
+				current = &ipc.TrybuildCase{Path: m[1], Status: m[2]}
+				if m[2] == "mismatch" {
+					current.StderrFile = strings.TrimSuffix(m[1], ".rs") + ".stderr"
+				}
+			}
+			label, inSnippet, wantMessage = "", false, m[2] == "error"
+			continue
+		}
+		if current == nil {
+			continue
+		}
+
+		switch {
+		case strings.HasPrefix(line, trybuildSnippetRule):
+			if inSnippet {
+				text := strings.Join(snippet, "\n")
+				if label == "expected" {
+					current.Expected = text
+				} else {
+					current.Actual = text
+				}
+			}
+			snippet, inSnippet, wantMessage = nil, !inSnippet, false
+		case inSnippet:
+			snippet = append(snippet, line)
+		case line == "EXPECTED:":
+			label = "expected"
+		case line == "ACTUAL OUTPUT:":
+			label = "actual"
+		default:
+			if m := trybuildWritingRegex.FindStringSubmatch(line); m != nil {
+				// Under TRYBUILD=overwrite the output goes straight to the .stderr file
+				current.StderrFile = m[1]
+			} else if m := trybuildMoveRegex.FindStringSubmatch(line); m != nil {
+				current.WipFile, current.StderrFile = current.StderrFile, m[1]
+			} else if wantMessage && strings.TrimSpace(line) != "" {
+				current.Message = strings.TrimSpace(line)
+				wantMessage = false
+			}
+		}
+	}
+	finish()
+
+	for i := range cases {
+		cases[i].Path = trybuildPath(cases[i].Path, dir, root)
+		cases[i].StderrFile = trybuildPath(cases[i].StderrFile, dir, root)
+		cases[i].WipFile = trybuildPath(cases[i].WipFile, dir, root)
+	}
+	return cases
+}
+
+// trybuildPath makes a path relative to the crate's directory relative to root
+func trybuildPath(path, dir, root string) string {
+	if path == "" || dir == "" || root == "" || filepath.IsAbs(path) {
+		return filepath.ToSlash(path)
+	}
+	rel, err := filepath.Rel(root, filepath.Join(dir, filepath.FromSlash(path)))
+	if err != nil || strings.HasPrefix(rel, "..") {
+		return filepath.ToSlash(path)
+	}
+	return filepath.ToSlash(rel)
+}
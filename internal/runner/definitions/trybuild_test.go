@@ -0,0 +1,121 @@
+package definitions
+
+import (
+	"os"
+	"path/filepath"
+	"reflect"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/internal/ipc"
+)
+
+// trybuildUIOutput is what trybuild writes for rust-trybuild's UI tests: a case whose
+// error changed since its .stderr file was written, and a case without one yet
+const trybuildUIOutput = "\n" +
+	"test tests/ui/ok.rs ... ok\n" +
+	"test tests/ui/missing-semicolon.rs ... mismatch\n" +
+	"\n" +
+	"EXPECTED:\n" +
+	"┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈\n" +
+	"error: expected `;`, found `}`\n" +
+	"\n" +
+	" --> tests/ui/missing-semicolon.rs:2:14\n" +
+	"┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈\n" +
+	"\n" +
+	"ACTUAL OUTPUT:\n" +
+	"┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈\n" +
+	"error: expected `;`, found keyword `let`\n" +
+	"\n" +
+	" --> tests/ui/missing-semicolon.rs:2:14\n" +
+	"┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈\n" +
+	"note: If the actual output is the correct output you can bless it by rerunning\n" +
+	"      your test with the environment variable TRYBUILD=overwrite\n" +
+	"\n" +
+	"test tests/ui/new-case.rs ... wip\n" +
+	"\n" +
+	"NOTE: writing the following output to `wip/new-case.stderr`.\n" +
+	"Move this file to `tests/ui/new-case.stderr` to accept it as correct.\n" +
+	"┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈\n" +
+	"error[E0308]: mismatched types\n" +
+	"┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈\n" +
+	"\n"
+
+func TestParseTrybuildCases(t *testing.T) {
+	root := filepath.Join(string(filepath.Separator), "src", "app")
+	expected := []ipc.TrybuildCase{
+		{
+			Path:       "tests/ui/missing-semicolon.rs",
+			Status:     "mismatch",
+			Expected:   "error: expected `;`, found `}`\n\n --> tests/ui/missing-semicolon.rs:2:14",
+			Actual:     "error: expected `;`, found keyword `let`\n\n --> tests/ui/missing-semicolon.rs:2:14",
+			StderrFile: "tests/ui/missing-semicolon.stderr",
+		},
+		{
+			Path:       "tests/ui/new-case.rs",
+			Status:     "wip",
+			Actual:     "error[E0308]: mismatched types",
+			StderrFile: "tests/ui/new-case.stderr",
+			WipFile:    "wip/new-case.stderr",
+		},
+	}
+	if cases := parseTrybuildCases(trybuildUIOutput, root, root); !reflect.DeepEqual(cases, expected) {
+		t.Errorf("Expected %+v, got %+v", expected, cases)
+	}
+
+	// A workspace member's paths are given from the workspace root
+	cases := parseTrybuildCases(trybuildUIOutput, filepath.Join(root, "crates", "macros"), root)
+	if len(cases) != 2 || cases[0].Path != "crates/macros/tests/ui/missing-semicolon.rs" || cases[1].WipFile != "crates/macros/wip/new-case.stderr" {
+		t.Errorf("Expected paths relative to the workspace root, got %+v", cases)
+	}
+
+	errorOutput := "test tests/ui/compiles.rs [should fail to compile] ... error\nExpected test case to fail to compile, but it succeeded.\n"
+	expected = []ipc.TrybuildCase{{Path: "tests/ui/compiles.rs", Status: "error", Message: "Expected test case to fail to compile, but it succeeded."}}
+	if cases := parseTrybuildCases(errorOutput, root, root); !reflect.DeepEqual(cases, expected) {
+		t.Errorf("Expected %+v, got %+v", expected, cases)
+	}
+
+	if cases := parseTrybuildCases("test tests/ui/ok.rs ... ok\n", root, root); len(cases) != 0 {
+		t.Errorf("Expected no failed cases, got %+v", cases)
+	}
+}
+
+func TestCargoTestDefinition_Trybuild(t *testing.T) {
+	crateDir := t.TempDir()
+	originalDir, err := os.Getwd()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if err := os.Chdir(crateDir); err != nil {
+		t.Fatal(err)
+	}
+	defer func() { _ = os.Chdir(originalDir) }()
+
+	// trybuild writes its output between the test's events, past libtest's capture
+	output := `     Running tests/compiletest.rs (target/debug/deps/compiletest-0123456789abcdef)
+{"type":"suite","event":"started","test_count":1}
+{"type":"test","event":"started","name":"ui"}
+` + trybuildUIOutput + `{"type":"test","name":"ui","event":"failed","exec_time":1.5,"stdout":"thread 'ui' panicked at /home/dev/.cargo/registry/src/index.crates.io-6f17d22bba15001f/trybuild-1.0.99/src/run.rs:101:13:\n2 of 3 tests failed\n"}
+{"type":"suite","event":"failed","passed":0,"failed":1,"ignored":0,"exec_time":1.5}
+`
+	def := NewCargoTestDefinition(createTestLogger(t))
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+
+	test := tapPayloads(NewTestIPCCapture(ipcPath), "testCase", "testName")["ui"]
+	errPayload, _ := test["error"].(map[string]interface{})
+	cases, _ := errPayload["trybuild"].([]interface{})
+	if len(cases) != 2 {
+		t.Fatalf("Expected the two failed trybuild cases, got %v", errPayload["trybuild"])
+	}
+	mismatch, _ := cases[0].(map[string]interface{})
+	if mismatch["status"] != "mismatch" || !strings.Contains(mismatch["expected"].(string), "\n\n --> tests/ui/missing-semicolon.rs:2:14") {
+		t.Errorf("Expected the mismatch with its blank lines kept, got %v", mismatch)
+	}
+	wip, _ := cases[1].(map[string]interface{})
+	if wip["wipFile"] != "wip/new-case.stderr" || wip["stderrFile"] != "tests/ui/new-case.stderr" {
+		t.Errorf("Expected the wip file to move to the .stderr file, got %v", wip)
+	}
+}
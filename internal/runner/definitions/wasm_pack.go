@@ -0,0 +1,705 @@
+package definitions
+
+import (
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"regexp"
+	"slices"
+	"strconv"
+	"strings"
+
+	"github.com/zk/3pio/internal/console"
+	"github.com/zk/3pio/internal/ipc"
+	"github.com/zk/3pio/internal/logger"
+)
+
+// wasm-pack test builds the tests for wasm32-unknown-unknown and runs each test binary
+// with wasm-bindgen-test-runner, in Node.js or a headless browser. The harness prints
+// libtest-like lines, but has no JSON format, and the browser's WebDriver prints
+// around them:
+//
+//	     Running unittests src/lib.rs (target/wasm32-unknown-unknown/debug/deps/wasm_app-0123456789abcdef.wasm)
+//	Running headless tests in Chrome on `http://127.0.0.1:39527/`
+//	Try find `webdriver.json` for configure browser's capabilities:
+//	Not found
+//	running 3 tests
+//	test wasm_app::tests::adds ... ok
+//	test wasm_app::tests::divides ... FAIL
+//	test wasm_app::tests::fetches ... ignored, needs network
+//
+//	failures:
+//
+//	---- wasm_app::tests::divides output ----
+//	    error output:
+//	        panicked at src/lib.rs:20:9:
+//	        assertion `left == right` failed
+//	          left: 0
+//	         right: 2
+//
+//	        Stack:
+//
+//	        Error
+//	            at http://127.0.0.1:39527/wasm-bindgen-test:1174:21
+//
+//	    JS exception that was thrown:
+//	        RuntimeError: unreachable
+//
+//	failures:
+//
+//	    wasm_app::tests::divides
+//
+//	test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 filtered out; finished in 0.04s
+var (
+	wasmRunningRegex    = regexp.MustCompile(`Running (?:unittests )?(\S+) \((?:.*[/\\])?(.+?)-[a-f0-9]+\.wasm\)`)
+	wasmPlanRegex       = regexp.MustCompile(`^running (\d+) tests?$`)
+	wasmTestRegex       = regexp.MustCompile(`^test (\S+) \.\.\. (ok|FAIL|ignored)(?:, (.*))?$`)
+	wasmFailureRegex    = regexp.MustCompile(`^---- (\S+) output ----$`)
+	wasmOutputKindRegex = regexp.MustCompile(`^(?:(log|error|warn|info|debug) output|JS exception that was thrown):$`)
+	wasmResultRegex     = regexp.MustCompile(`^test result: (?:ok|FAILED)\.`)
+	wasmConsoleDivRegex = regexp.MustCompile(`^console\.(\w+) div contained:$`)
+	wasmTimeoutSetRegex = regexp.MustCompile(`^Set timeout to (\d+) seconds\.\.\.$`)
+)
+
+// When the browser doesn't finish in time, wasm-bindgen-test-runner gives up and prints
+// what the harness wrote so far, indented
+const (
+	wasmTimeoutNote    = "Failed to detect test as having been run. It might have timed out."
+	wasmOutputDivNote  = "output div contained:"
+	wasmDivIndent      = "    "
+	wasmDefaultTimeout = 20 // Seconds, unless WASM_BINDGEN_TEST_TIMEOUT says otherwise
+)
+
+// A WebDriver that is missing or can't start its browser stops the run before any test.
+// wasm-pack says "Error: chromedriver binaries are unavailable for this target",
+// wasm-bindgen-test-runner "Error: failed to find a suitable WebDriver binary ...", and
+// a driver whose browser is missing or of another version answers
+// {"value":{"error":"session not created","message":"session not created: ..."}}.
+var (
+	wasmDriverErrorRegex  = regexp.MustCompile(`^Error: (.*(?i:webdriver|chromedriver|geckodriver|safaridriver|\bdriver\b).*)$`)
+	wasmSessionErrorRegex = regexp.MustCompile(`session not created: ([^"\\]+)`)
+)
+
+// wasmPackValueFlags are the options of wasm-pack and wasm-pack test given a value
+var wasmPackValueFlags = []string{"--log-level", "--mode", "-m", "--chromedriver", "--geckodriver", "--safaridriver"}
+
+// webDriver is a WebDriver wasm-pack test runs a headless browser with
+type webDriver struct {
+	name    string // e.g. "chromedriver"
+	flag    string // wasm-pack test flag that picks the browser
+	browser string
+	hint    string // How to set it up
+}
+
+var webDrivers = []webDriver{
+	{"chromedriver", "--chrome", "Chrome", "Install Chrome and a chromedriver of the same version, then put chromedriver on PATH or set CHROMEDRIVER to its path"},
+	{"geckodriver", "--firefox", "Firefox", "Install Firefox and geckodriver, then put geckodriver on PATH or set GECKODRIVER to its path"},
+	{"safaridriver", "--safari", "Safari", "Enable it once with `safaridriver --enable`, or set SAFARIDRIVER to its path"},
+}
+
+// WasmPackDefinition runs wasm-pack test, parsing the output of the wasm-bindgen test
+// harness. Each test binary is a group, with a group per module like cargo test.
+type WasmPackDefinition struct {
+	logger    *logger.FileLogger
+	ipcWriter *IPCWriter
+
+	lineLimit // Longest line of output parsed whole (--max-line-length)
+
+	browserFlags []string // --chrome, --firefox, --safari given to wasm-pack test
+	defaultGroup string   // Group of the tests when no Running banner names their binary
+
+	binary         *wasmBinary // Test binary whose output is being read
+	failure        *wasmTest   // Test whose "---- name output ----" block is being read
+	failureIndent  int         // Indentation of that block's header
+	outputKind     string      // Kind of the block's output being read, e.g. "error"
+	div            string      // "output" or the console method of the div being printed
+	timeoutSeconds int
+
+	environmentError string
+}
+
+// wasmBinary is a test binary and the results of its tests, reported when it finishes
+type wasmBinary struct {
+	name     string // Group name, the target with hyphens
+	target   string // Target name as in the test paths, e.g. "wasm_app"
+	planned  int    // From "running N tests", -1 before
+	tests    []*wasmTest
+	byName   map[string]*wasmTest
+	console  strings.Builder // What the tests logged to the browser's console outside the harness
+	timedOut bool
+}
+
+// wasmTest is the result of a test, with the output the harness captured for it
+type wasmTest struct {
+	path   string // As printed, e.g. "wasm_app::tests::adds"
+	status string // "ok", "FAIL" or "ignored"
+	reason string // Of an ignored test
+	output map[string]*strings.Builder
+}
+
+// NewWasmPackDefinition creates a new wasm-pack test runner definition
+func NewWasmPackDefinition(logger *logger.FileLogger) *WasmPackDefinition {
+	return &WasmPackDefinition{logger: logger}
+}
+
+// Name returns the name of this test runner
+func (w *WasmPackDefinition) Name() string {
+	return "wasm-pack"
+}
+
+// Detect checks if the command is wasm-pack test, after wasm-pack's global options
+func (w *WasmPackDefinition) Detect(args []string) bool {
+	if len(args) < 2 {
+		return false
+	}
+	base := args[0]
+	if idx := strings.LastIndexAny(base, `/\`); idx != -1 {
+		base = base[idx+1:]
+	}
+	if strings.TrimSuffix(strings.ToLower(base), ".exe") != "wasm-pack" {
+		return false
+	}
+	for i := 1; i < len(args); i++ {
+		switch {
+		case slices.Contains(wasmPackValueFlags, args[i]):
+			i++
+		case !strings.HasPrefix(args[i], "-"):
+			return args[i] == "test"
+		}
+	}
+	return false
+}
+
+// ModifyCommand returns the command unchanged, as the harness output is parsed, and
+// records the browser it runs the tests in
+func (w *WasmPackDefinition) ModifyCommand(cmd []string, ipcPath, runID string) []string {
+	w.browserFlags = nil
+	dir, subcommand := "", false
+	for i := 1; i < len(cmd) && cmd[i] != "--"; i++ {
+		arg := cmd[i]
+		switch {
+		case slices.Contains(wasmPackValueFlags, arg):
+			i++
+		case slices.ContainsFunc(webDrivers, func(d webDriver) bool { return d.flag == arg }):
+			w.browserFlags = append(w.browserFlags, arg)
+		case strings.HasPrefix(arg, "-"):
+			// --headless, --release and the other switches
+		case !subcommand:
+			subcommand = true
+		default:
+			// wasm-pack test [options] [path]
+			dir = arg
+		}
+	}
+	w.defaultGroup = wasmDefaultGroup(dir)
+	return append([]string{}, cmd...)
+}
+
+// wasmDefaultGroup names the group of tests without a Running banner after the crate's
+// directory
+func wasmDefaultGroup(dir string) string {
+	if abs, err := filepath.Abs(dir); err == nil {
+		dir = abs
+	}
+	if name := filepath.Base(dir); name != "" && name != "." && name != string(filepath.Separator) {
+		return name
+	}
+	return "wasm-pack"
+}
+
+// GetTestFiles returns empty array for dynamic discovery
+func (w *WasmPackDefinition) GetTestFiles(args []string) ([]string, error) {
+	return []string{}, nil
+}
+
+// RequiresAdapter returns false as the harness output is parsed directly
+func (w *WasmPackDefinition) RequiresAdapter() bool {
+	return false
+}
+
+// EnvironmentError says how to set up the WebDriver that couldn't start the headless
+// browser, or "" if the tests ran
+func (w *WasmPackDefinition) EnvironmentError() string {
+	return w.environmentError
+}
+
+// ProcessOutput reads wasm-pack test's output and converts the harness results to IPC
+// events. wasm-pack's progress and the browser's and WebDriver's messages are left in
+// output.log.
+func (w *WasmPackDefinition) ProcessOutput(combinedOutput io.Reader, ipcPath string) error {
+	var err error
+	w.ipcWriter, err = NewIPCWriter(ipcPath)
+	if err != nil {
+		return fmt.Errorf("failed to create IPC writer: %w", err)
+	}
+	defer func() {
+		if err := w.ipcWriter.Close(); err != nil {
+			w.logger.Debug("Failed to close IPC writer: %v", err)
+		}
+	}()
+
+	if w.defaultGroup == "" {
+		w.defaultGroup = wasmDefaultGroup("")
+	}
+	w.timeoutSeconds = wasmDefaultTimeout
+	if seconds, err := strconv.Atoi(os.Getenv("WASM_BINDGEN_TEST_TIMEOUT")); err == nil && seconds > 0 {
+		w.timeoutSeconds = seconds
+	}
+
+	lines := w.newLineReader(combinedOutput)
+	for {
+		line, _, ok := lines.Next()
+		if !ok {
+			break
+		}
+		w.processLine(line)
+	}
+	if err := lines.Err(); err != nil {
+		return fmt.Errorf("error reading wasm-pack output: %w", err)
+	}
+	w.finishBinary()
+
+	runCompleteEvent := map[string]interface{}{
+		"eventType": "runComplete",
+		"payload":   map[string]interface{}{},
+	}
+	if err := w.ipcWriter.WriteEvent(runCompleteEvent); err != nil {
+		w.logger.Debug("Failed to send runComplete event: %v", err)
+	}
+	return nil
+}
+
+// processLine processes a line of wasm-pack test's output
+func (w *WasmPackDefinition) processLine(line string) {
+	line = strings.TrimRight(console.StripANSI(line), "\r")
+	if w.div != "" {
+		if content, ok := strings.CutPrefix(line, wasmDivIndent); ok {
+			if w.div == "output" {
+				w.processHarnessLine(content)
+			} else {
+				w.currentBinary().console.WriteString(content + "\n")
+			}
+			return
+		}
+		w.div = ""
+	}
+
+	if matches := wasmRunningRegex.FindStringSubmatch(line); matches != nil {
+		w.finishBinary()
+		w.binary = newWasmBinary(matches[2])
+		return
+	}
+	if matches := wasmSessionErrorRegex.FindStringSubmatch(line); matches != nil {
+		w.setEnvironmentError(strings.TrimSpace(matches[1]))
+		return
+	}
+	if matches := wasmDriverErrorRegex.FindStringSubmatch(line); matches != nil {
+		w.setEnvironmentError(matches[1])
+		return
+	}
+	if matches := wasmTimeoutSetRegex.FindStringSubmatch(line); matches != nil {
+		w.timeoutSeconds, _ = strconv.Atoi(matches[1])
+		return
+	}
+	if matches := wasmConsoleDivRegex.FindStringSubmatch(line); matches != nil {
+		w.div = matches[1]
+		return
+	}
+	switch line {
+	case wasmTimeoutNote:
+		w.currentBinary().timedOut = true
+	case wasmOutputDivNote:
+		w.div = "output"
+	default:
+		w.processHarnessLine(line)
+	}
+}
+
+// processHarnessLine processes a line the wasm-bindgen test harness printed
+func (w *WasmPackDefinition) processHarnessLine(line string) {
+	trimmed := strings.TrimSpace(line)
+	if matches := wasmPlanRegex.FindStringSubmatch(trimmed); matches != nil && w.failure == nil {
+		w.currentBinary().planned, _ = strconv.Atoi(matches[1])
+		return
+	}
+	if matches := wasmTestRegex.FindStringSubmatch(trimmed); matches != nil {
+		w.failure = nil
+		binary := w.currentBinary()
+		test := binary.test(matches[1])
+		test.status, test.reason = matches[2], matches[3]
+		return
+	}
+	if matches := wasmFailureRegex.FindStringSubmatch(trimmed); matches != nil {
+		w.failure = w.currentBinary().test(matches[1])
+		w.failureIndent = len(line) - len(strings.TrimLeft(line, " "))
+		w.outputKind = ""
+		return
+	}
+	switch {
+	case trimmed == "failures:":
+		// The second one lists the failed tests, which their blocks already gave
+		w.failure = nil
+	case wasmResultRegex.MatchString(trimmed):
+		w.finishBinary()
+	case w.failure != nil:
+		if matches := wasmOutputKindRegex.FindStringSubmatch(trimmed); matches != nil {
+			w.outputKind = matches[1]
+			if w.outputKind == "" {
+				w.outputKind = "exception"
+			}
+			return
+		}
+		if w.outputKind == "" {
+			return
+		}
+		// The output is indented twice under the block's header
+		body := line
+		for i := 0; i < w.failureIndent+8 && strings.HasPrefix(body, " "); i++ {
+			body = body[1:]
+		}
+		w.failure.appendOutput(w.outputKind, body)
+	}
+}
+
+// setEnvironmentError records why the WebDriver couldn't start the browser, keeping
+// the first error as the ones after it follow from it
+func (w *WasmPackDefinition) setEnvironmentError(detail string) {
+	if w.environmentError != "" {
+		return
+	}
+	detail = strings.TrimSuffix(strings.TrimSpace(detail), ".")
+	w.environmentError = w.webDriverErrorMessage(detail)
+	w.logger.Debug("WebDriver failed to start: %s", detail)
+}
+
+// webDriverErrorMessage says what to set up when the WebDriver the error names, or
+// else that of the browser the command asked for, couldn't start
+func (w *WasmPackDefinition) webDriverErrorMessage(detail string) string {
+	for _, driver := range webDrivers {
+		if strings.Contains(strings.ToLower(detail), driver.name) {
+			return fmt.Sprintf("%s couldn't start headless %s: %s. %s", driver.name, driver.browser, detail, driver.hint)
+		}
+	}
+	for _, driver := range webDrivers {
+		if slices.Contains(w.browserFlags, driver.flag) {
+			return fmt.Sprintf("%s couldn't start headless %s: %s. %s", driver.name, driver.browser, detail, driver.hint)
+		}
+	}
+	return fmt.Sprintf("The WebDriver couldn't start a headless browser: %s. Install the browser and its WebDriver (chromedriver, geckodriver or safaridriver), then put it on PATH or set CHROMEDRIVER, GECKODRIVER or SAFARIDRIVER to its path", detail)
+}
+
+// currentBinary returns the test binary being read, starting one for output that comes
+// without a Running banner
+func (w *WasmPackDefinition) currentBinary() *wasmBinary {
+	if w.binary == nil {
+		w.binary = newWasmBinary(strings.ReplaceAll(w.defaultGroup, "-", "_"))
+	}
+	return w.binary
+}
+
+func newWasmBinary(target string) *wasmBinary {
+	return &wasmBinary{
+		name:    strings.ReplaceAll(target, "_", "-"),
+		target:  target,
+		planned: -1,
+		byName:  make(map[string]*wasmTest),
+	}
+}
+
+// test returns the test of the binary with the path, adding it in the order its
+// result was printed
+func (b *wasmBinary) test(path string) *wasmTest {
+	if test, ok := b.byName[path]; ok {
+		return test
+	}
+	test := &wasmTest{path: path, output: make(map[string]*strings.Builder)}
+	b.byName[path] = test
+	b.tests = append(b.tests, test)
+	return test
+}
+
+// appendOutput adds a line of the output of kind the harness captured for the test
+func (t *wasmTest) appendOutput(kind, line string) {
+	if t.output[kind] == nil {
+		t.output[kind] = &strings.Builder{}
+	}
+	t.output[kind].WriteString(line + "\n")
+}
+
+// capturedOutput returns the output of kind the harness captured for the test
+func (t *wasmTest) capturedOutput(kinds ...string) string {
+	var parts []string
+	for _, kind := range kinds {
+		if output := t.output[kind]; output != nil {
+			if text := strings.TrimRight(output.String(), "\n "); text != "" {
+				parts = append(parts, text)
+			}
+		}
+	}
+	return strings.Join(parts, "\n")
+}
+
+// finishBinary reports the tests of the binary being read, its groups and, when the
+// browser stopped before the harness finished, its timeout
+func (w *WasmPackDefinition) finishBinary() {
+	binary := w.binary
+	w.binary, w.failure, w.div = nil, nil, ""
+	if binary == nil || (binary.planned < 0 && len(binary.tests) == 0 && !binary.timedOut) {
+		return
+	}
+
+	groups := newWasmGroups(w, binary.name)
+	for _, test := range binary.tests {
+		parts := strings.Split(test.path, "::")
+		if len(parts) > 1 && parts[0] == binary.target {
+			// The harness prints the crate's module path, which the group already names
+			parts = parts[1:]
+		}
+		parents := groups.open(parts[:len(parts)-1])
+		groups.count(parents, w.sendTestCase(test, parts[len(parts)-1], parents))
+	}
+	if binary.console.Len() > 0 {
+		w.sendGroupOutput(binary.name, []string{}, binary.console.String())
+	}
+
+	var rootError string
+	if binary.timedOut {
+		rootError = fmt.Sprintf("The headless browser didn't finish the tests within %ds", w.timeoutSeconds)
+		if binary.planned >= 0 {
+			rootError += fmt.Sprintf(": %d of %d reported", len(binary.tests), binary.planned)
+		}
+		rootError += ". Raise WASM_BINDGEN_TEST_TIMEOUT to wait longer"
+	}
+	groups.close(rootError)
+}
+
+// sendTestCase sends the result of a test and returns its totals
+func (w *WasmPackDefinition) sendTestCase(test *wasmTest, name string, parents []string) tapTotals {
+	payload := map[string]interface{}{
+		"testName":    name,
+		"parentNames": parents,
+	}
+	if stdout := test.capturedOutput("log", "info", "debug", "warn"); stdout != "" {
+		payload["stdout"] = stdout
+	}
+
+	var totals tapTotals
+	switch test.status {
+	case "ignored":
+		payload["status"] = "SKIP"
+		payload["skipReason"] = ipc.SkipReasonIgnored
+		if test.reason != "" {
+			payload["skipMessage"] = test.reason
+		}
+		totals.skipped++
+	case "FAIL":
+		payload["status"] = "FAIL"
+		if stderr := test.capturedOutput("error"); stderr != "" {
+			payload["stderr"] = stderr
+		}
+		payload["error"] = wasmTestError(test)
+		totals.failed++
+	default:
+		payload["status"] = "PASS"
+		totals.passed++
+	}
+
+	w.sendIPCEvent(map[string]interface{}{
+		"eventType": "testCase",
+		"payload":   payload,
+	})
+	return totals
+}
+
+// wasmTestError builds the error of a failed test from its panic, which the panic hook
+// writes to console.error without libtest's "thread '...'" header, or else from the
+// JS exception the test threw
+func wasmTestError(test *wasmTest) map[string]interface{} {
+	errorOutput := test.capturedOutput("error")
+	exception := test.capturedOutput("exception")
+
+	i := strings.Index(errorOutput, "panicked at ")
+	if i < 0 {
+		message := exception
+		if message == "" {
+			message = "test failed"
+		}
+		testError := map[string]interface{}{"message": message}
+		if first, stack, ok := strings.Cut(exception, "\n"); ok {
+			testError["message"] = first
+			testError["stack"] = stack
+		}
+		return testError
+	}
+
+	panicText, stack, _ := strings.Cut(errorOutput[i:], "\n\nStack:\n")
+	panicText = strings.TrimSpace(panicText)
+	panicMessage := fmt.Sprintf("thread '%s' %s", test.path, panicText)
+	testError := map[string]interface{}{
+		"message":     panicText,
+		"failureKind": classifyRustPanic(panicMessage, ""),
+	}
+	if stack = strings.TrimSpace(stack); stack != "" {
+		testError["stack"] = stack
+	}
+	if assertion := parseAssertionFailure(panicMessage); assertion != nil {
+		// assert_eq!(actual, expected) is the common argument order
+		testError["errorType"] = assertion.Kind
+		testError["actual"] = assertion.Left
+		testError["expected"] = assertion.Right
+		if assertion.CustomMessage != "" {
+			testError["customMessage"] = assertion.CustomMessage
+		}
+	}
+	if location := parsePanicLocation(panicMessage); location != nil {
+		location.File = filepath.ToSlash(location.File)
+		testError["location"] = fmt.Sprintf("%s:%d:%d", location.File, location.Line, location.Column)
+		testError["file"] = location.File
+		testError["line"] = location.Line
+		testError["column"] = location.Column
+		if filepath.IsAbs(location.File) || strings.HasPrefix(location.File, "/rustc/") {
+			// The standard library or a dependency in the cargo registry
+			testError["external"] = true
+		}
+	}
+	return testError
+}
+
+// wasmGroups discovers the module groups of a test binary as its tests need them and
+// sends their results once the binary is done
+type wasmGroups struct {
+	w      *WasmPackDefinition
+	root   string
+	order  []string // Group paths joined with "::", parents first
+	totals map[string]*tapTotals
+}
+
+func newWasmGroups(w *WasmPackDefinition, root string) *wasmGroups {
+	w.sendGroupDiscovered(root, []string{})
+	w.sendGroupStart(root, []string{})
+	return &wasmGroups{w: w, root: root, order: []string{""}, totals: map[string]*tapTotals{"": {}}}
+}
+
+// open discovers the groups of the modules, under the binary's group, and returns the
+// parent names of a test in the innermost
+func (g *wasmGroups) open(modules []string) []string {
+	parents := []string{g.root}
+	for i, module := range modules {
+		key := strings.Join(modules[:i+1], "::")
+		if _, ok := g.totals[key]; !ok {
+			g.w.sendGroupDiscovered(module, parents)
+			g.w.sendGroupStart(module, parents)
+			g.order = append(g.order, key)
+			g.totals[key] = &tapTotals{}
+		}
+		parents = append(parents, module)
+	}
+	return parents
+}
+
+// count adds a test's totals to the groups it is in
+func (g *wasmGroups) count(parents []string, totals tapTotals) {
+	for i := range parents {
+		g.totals[strings.Join(parents[1:i+1], "::")].add(totals)
+	}
+}
+
+// close sends the results of the groups, innermost first. The binary's group errors
+// with rootError instead when it isn't "".
+func (g *wasmGroups) close(rootError string) {
+	for i := len(g.order) - 1; i >= 0; i-- {
+		key := g.order[i]
+		name, parents := g.root, []string{}
+		if key != "" {
+			modules := strings.Split(key, "::")
+			name = modules[len(modules)-1]
+			parents = append([]string{g.root}, modules[:len(modules)-1]...)
+		} else if rootError != "" {
+			g.w.sendGroupError(name, parents, ipc.ErrorTypeTimedOut, rootError)
+			continue
+		}
+
+		totals := *g.totals[key]
+		status := "PASS"
+		switch {
+		case totals.failed > 0:
+			status = "FAIL"
+		case totals.passed == 0 && totals.skipped > 0:
+			status = "SKIP"
+		}
+		g.w.sendGroupResult(name, parents, status, totals)
+	}
+}
+
+func (w *WasmPackDefinition) sendGroupDiscovered(groupName string, parentNames []string) {
+	w.sendIPCEvent(map[string]interface{}{
+		"eventType": "testGroupDiscovered",
+		"payload": map[string]interface{}{
+			"groupName":   groupName,
+			"parentNames": parentNames,
+		},
+	})
+}
+
+func (w *WasmPackDefinition) sendGroupStart(groupName string, parentNames []string) {
+	w.sendIPCEvent(map[string]interface{}{
+		"eventType": "testGroupStart",
+		"payload": map[string]interface{}{
+			"groupName":   groupName,
+			"parentNames": parentNames,
+		},
+	})
+}
+
+func (w *WasmPackDefinition) sendGroupOutput(groupName string, parentNames []string, output string) {
+	w.sendIPCEvent(map[string]interface{}{
+		"eventType": "groupStdout",
+		"payload": map[string]interface{}{
+			"groupName":   groupName,
+			"parentNames": parentNames,
+			"chunk":       output,
+		},
+	})
+}
+
+func (w *WasmPackDefinition) sendGroupResult(groupName string, parentNames []string, status string, totals tapTotals) {
+	w.sendIPCEvent(map[string]interface{}{
+		"eventType": "testGroupResult",
+		"payload": map[string]interface{}{
+			"groupName":   groupName,
+			"parentNames": parentNames,
+			"status":      status,
+			"totals": map[string]interface{}{
+				"passed":  totals.passed,
+				"failed":  totals.failed,
+				"skipped": totals.skipped,
+			},
+		},
+	})
+}
+
+func (w *WasmPackDefinition) sendGroupError(groupName string, parentNames []string, errorType, message string) {
+	w.sendIPCEvent(map[string]interface{}{
+		"eventType": "testGroupError",
+		"payload": map[string]interface{}{
+			"groupName":   groupName,
+			"parentNames": parentNames,
+			"errorType":   errorType,
+			"error": map[string]interface{}{
+				"message": message,
+			},
+		},
+	})
+}
+
+func (w *WasmPackDefinition) sendIPCEvent(event map[string]interface{}) {
+	if w.ipcWriter == nil {
+		w.logger.Debug("IPC writer not initialized, skipping event: %v", event)
+		return
+	}
+
+	if err := w.ipcWriter.WriteEvent(event); err != nil {
+		w.logger.Debug("Failed to write IPC event: %v", err)
+	}
+}
@@ -0,0 +1,227 @@
+package definitions
+
+import (
+	"path/filepath"
+	"reflect"
+	"slices"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/internal/ipc"
+	"github.com/zk/3pio/internal/runinfo"
+)
+
+func TestWasmPackDefinition_Detect(t *testing.T) {
+	def := NewWasmPackDefinition(createTestLogger(t))
+	testCases := []struct {
+		args     []string
+		expected bool
+	}{
+		{[]string{"wasm-pack", "test", "--headless", "--chrome"}, true},
+		{[]string{"wasm-pack", "--log-level", "warn", "test", "--node"}, true},
+		{[]string{"/home/dev/.cargo/bin/wasm-pack.exe", "test", "--firefox"}, true},
+		{[]string{"wasm-pack", "build", "--target", "web"}, false},
+		{[]string{"wasm-pack"}, false},
+		{[]string{"cargo", "test", "--target", "wasm32-unknown-unknown"}, false},
+	}
+	for _, tc := range testCases {
+		if detected := def.Detect(tc.args); detected != tc.expected {
+			t.Errorf("Detect(%v) = %v, expected %v", tc.args, detected, tc.expected)
+		}
+	}
+}
+
+func TestWasmPackDefinition_ProcessOutput(t *testing.T) {
+	output := `[INFO]: 🎯  Checking for the Wasm target...
+    Finished ` + "`test`" + ` profile [unoptimized + debuginfo] target(s) in 0.05s
+     Running unittests src/lib.rs (target/wasm32-unknown-unknown/debug/deps/wasm_app-0123456789abcdef.wasm)
+Running headless tests in Chrome on ` + "`http://127.0.0.1:39527/`" + `
+Try find ` + "`webdriver.json`" + ` for configure browser's capabilities:
+Not found
+running 3 tests
+test wasm_app::tests::adds ... ok
+test wasm_app::tests::divides ... FAIL
+test wasm_app::tests::fetches ... ignored, needs network
+
+failures:
+
+---- wasm_app::tests::divides output ----
+    log output:
+        dividing 4 by 2
+
+    error output:
+        panicked at src/lib.rs:20:9:
+        assertion ` + "`left == right`" + ` failed
+          left: 0
+         right: 2
+
+        Stack:
+
+        Error
+            at http://127.0.0.1:39527/wasm-bindgen-test:1174:21
+
+    JS exception that was thrown:
+        RuntimeError: unreachable
+
+failures:
+
+    wasm_app::tests::divides
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 filtered out; finished in 0.04s
+
+     Running tests/web.rs (target/wasm32-unknown-unknown/debug/deps/web-fedcba9876543210.wasm)
+running 1 test
+test web::renders ... ok
+
+test result: ok. 1 passed; 0 failed; 0 ignored; 0 filtered out; finished in 0.01s
+
+Error: Running Wasm tests with wasm-bindgen-test failed
+`
+	def := NewWasmPackDefinition(createTestLogger(t))
+	def.ModifyCommand([]string{"wasm-pack", "test", "--headless", "--chrome"}, "", "")
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+	if envErr := def.EnvironmentError(); envErr != "" {
+		t.Errorf("Expected no environment error, got %q", envErr)
+	}
+
+	capture := NewTestIPCCapture(ipcPath)
+	tests := tapPayloads(capture, "testCase", "testName")
+	if len(tests) != 4 {
+		t.Fatalf("Expected 4 tests, got %v", tests)
+	}
+	if adds := tests["adds"]; adds["status"] != "PASS" || !reflect.DeepEqual(adds["parentNames"], []interface{}{"wasm-app", "tests"}) {
+		t.Errorf("Expected adds to pass under wasm-app > tests, got %v", adds)
+	}
+	if fetches := tests["fetches"]; fetches["status"] != "SKIP" || fetches["skipReason"] != ipc.SkipReasonIgnored || fetches["skipMessage"] != "needs network" {
+		t.Errorf("Expected fetches to be ignored with its reason, got %v", fetches)
+	}
+	if renders := tests["renders"]; renders["status"] != "PASS" || !reflect.DeepEqual(renders["parentNames"], []interface{}{"web"}) {
+		t.Errorf("Expected renders to pass under web, got %v", renders)
+	}
+
+	divides := tests["divides"]
+	if divides["status"] != "FAIL" || divides["stdout"] != "dividing 4 by 2" {
+		t.Errorf("Expected divides to fail with its log output, got %v", divides)
+	}
+	errPayload, _ := divides["error"].(map[string]interface{})
+	if errPayload["errorType"] != "assert_eq" || errPayload["actual"] != "0" || errPayload["expected"] != "2" {
+		t.Errorf("Expected the assertion's values, got %v", errPayload)
+	}
+	if errPayload["location"] != "src/lib.rs:20:9" || errPayload["failureKind"] != ipc.FailureKindAssertionFailed {
+		t.Errorf("Expected an assertion failure at src/lib.rs:20:9, got %v", errPayload)
+	}
+	if stack, _ := errPayload["stack"].(string); !strings.HasPrefix(stack, "Error\n    at http://127.0.0.1:39527/") {
+		t.Errorf("Expected the JS stack of the panic, got %q", stack)
+	}
+
+	groups := tapPayloads(capture, "testGroupResult", "groupName")
+	root, _ := groups["wasm-app"]["totals"].(map[string]interface{})
+	if groups["wasm-app"]["status"] != "FAIL" || root["passed"] != float64(1) || root["failed"] != float64(1) || root["skipped"] != float64(1) {
+		t.Errorf("Expected wasm-app to fail with 1 passed, 1 failed and 1 skipped, got %v", groups["wasm-app"])
+	}
+	if groups["web"]["status"] != "PASS" {
+		t.Errorf("Expected web to pass, got %v", groups["web"])
+	}
+}
+
+func TestWasmPackDefinition_BrowserTimeout(t *testing.T) {
+	// The runner prints what the harness wrote to the page when it gives up
+	output := `     Running tests/web.rs (target/wasm32-unknown-unknown/debug/deps/web-fedcba9876543210.wasm)
+Set timeout to 5 seconds...
+Running headless tests in Firefox on ` + "`http://127.0.0.1:41233/`" + `
+Failed to detect test as having been run. It might have timed out.
+output div contained:
+    running 2 tests
+    test web::renders ... ok
+console.log div contained:
+    fetching /api/items
+Error: some tests failed
+`
+	def := NewWasmPackDefinition(createTestLogger(t))
+	ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+	if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+		t.Fatalf("ProcessOutput failed: %v", err)
+	}
+
+	capture := NewTestIPCCapture(ipcPath)
+	if renders := tapPayloads(capture, "testCase", "testName")["renders"]; renders["status"] != "PASS" {
+		t.Errorf("Expected the test that finished to pass, got %v", renders)
+	}
+	groupError := tapPayloads(capture, "testGroupError", "groupName")["web"]
+	errPayload, _ := groupError["error"].(map[string]interface{})
+	expected := "The headless browser didn't finish the tests within 5s: 1 of 2 reported. Raise WASM_BINDGEN_TEST_TIMEOUT to wait longer"
+	if groupError["errorType"] != ipc.ErrorTypeTimedOut || errPayload["message"] != expected {
+		t.Errorf("Expected a %s error %q, got %v", ipc.ErrorTypeTimedOut, expected, groupError)
+	}
+	if output := tapPayloads(capture, "groupStdout", "groupName")["web"]; output["chunk"] != "fetching /api/items\n" {
+		t.Errorf("Expected the console output of the page, got %v", output)
+	}
+	if def.EnvironmentError() != "" {
+		t.Errorf("Expected a timeout not to be an environment error, got %q", def.EnvironmentError())
+	}
+}
+
+func TestWasmPackDefinition_WebDriverError(t *testing.T) {
+	testCases := []struct {
+		desc     string
+		command  []string
+		line     string
+		expected string
+	}{
+		{
+			"wasm-pack can't install chromedriver",
+			[]string{"wasm-pack", "test", "--headless", "--chrome"},
+			"Error: chromedriver binaries are unavailable for this target",
+			"chromedriver couldn't start headless Chrome: chromedriver binaries are unavailable for this target. Install Chrome and a chromedriver of the same version",
+		},
+		{
+			"the runner finds no WebDriver",
+			[]string{"wasm-pack", "test", "--headless", "--firefox"},
+			"Error: failed to find a suitable WebDriver binary or remote running WebDriver to drive tests in a headless browser",
+			"geckodriver couldn't start headless Firefox: failed to find a suitable WebDriver binary",
+		},
+		{
+			"chromedriver and Chrome versions differ",
+			[]string{"wasm-pack", "test", "--headless", "--chrome"},
+			`Error: non-200 response code: 500` + "\n" + `{"value":{"error":"session not created","message":"session not created: This version of ChromeDriver only supports Chrome version 114\nCurrent browser version is 120.0.6099.71"}}`,
+			"chromedriver couldn't start headless Chrome: This version of ChromeDriver only supports Chrome version 114",
+		},
+	}
+	for _, tc := range testCases {
+		t.Run(tc.desc, func(t *testing.T) {
+			def := NewWasmPackDefinition(createTestLogger(t))
+			def.ModifyCommand(tc.command, "", "")
+			output := "     Running unittests src/lib.rs (target/wasm32-unknown-unknown/debug/deps/wasm_app-0123456789abcdef.wasm)\n" + tc.line + "\n"
+			ipcPath := filepath.Join(t.TempDir(), "ipc.jsonl")
+			if err := def.ProcessOutput(strings.NewReader(output), ipcPath); err != nil {
+				t.Fatalf("ProcessOutput failed: %v", err)
+			}
+			if envErr := def.EnvironmentError(); !strings.HasPrefix(envErr, tc.expected) {
+				t.Errorf("Expected an environment error starting %q, got %q", tc.expected, envErr)
+			}
+			if groups := NewTestIPCCapture(ipcPath).GetEventsByType("testGroupDiscovered"); len(groups) != 0 {
+				t.Errorf("Expected no groups when no test ran, got %v", groups)
+			}
+		})
+	}
+}
+
+func TestWasmPackDefinition_RunnerInfo(t *testing.T) {
+	t.Setenv("RUSTC", "")
+	info := NewWasmPackDefinition(createTestLogger(t)).RunnerInfo([]string{"wasm-pack", "test", "--release", "--node", "--", "--features", "serde"})
+
+	wasmPack := info.Probes[len(info.Probes)-1]
+	expected := runinfo.Probe{Tool: "wasm-pack", Args: []string{"wasm-pack", "--version"}}
+	if !reflect.DeepEqual(wasmPack, expected) {
+		t.Errorf("Expected %+v, got %+v", expected, wasmPack)
+	}
+	if info.Target != "wasm32-unknown-unknown" || info.Cargo.Profile != "release" || !reflect.DeepEqual(info.Cargo.Features, []string{"serde"}) {
+		t.Errorf("Expected a release build of feature serde for wasm32-unknown-unknown, got %+v %+v", info, info.Cargo)
+	}
+	if !slices.Contains(info.EnvVars, "WASM_BINDGEN_TEST_TIMEOUT") || slices.Contains(rustEnvVars, "WASM_BINDGEN_TEST_TIMEOUT") {
+		t.Errorf("Expected WASM_BINDGEN_TEST_TIMEOUT to be recorded for wasm-pack runs only, got %v", info.EnvVars)
+	}
+}
@@ -0,0 +1,59 @@
+package definitions
+
+import (
+	"io"
+)
+
+// WasmPackWrapper wraps WasmPackDefinition to implement the Definition interface from runner package
+type WasmPackWrapper struct {
+	*WasmPackDefinition
+}
+
+// NewWasmPackWrapper creates a new wrapper for the wasm-pack test runner
+func NewWasmPackWrapper(impl *WasmPackDefinition) *WasmPackWrapper {
+	return &WasmPackWrapper{WasmPackDefinition: impl}
+}
+
+// Matches checks if this runner can handle the given command
+func (w *WasmPackWrapper) Matches(command []string) bool {
+	return w.Detect(command)
+}
+
+// GetTestFiles returns list of test files (empty for dynamic discovery)
+func (w *WasmPackWrapper) GetTestFiles(args []string) ([]string, error) {
+	return w.WasmPackDefinition.GetTestFiles(args)
+}
+
+// BuildCommand returns the command unchanged
+func (w *WasmPackWrapper) BuildCommand(args []string, adapterPath string) []string {
+	// The harness output is parsed as wasm-pack prints it, no adapter needed
+	return w.ModifyCommand(args, "", "")
+}
+
+// GetAdapterFileName returns empty as wasm-pack test doesn't use an adapter
+func (w *WasmPackWrapper) GetAdapterFileName() string {
+	return ""
+}
+
+// InterpretExitCode maps exit codes to success/failure
+func (w *WasmPackWrapper) InterpretExitCode(code int) string {
+	if code == 0 {
+		return "success"
+	}
+	return "failure"
+}
+
+// IsNative returns true as the harness output is processed directly
+func (w *WasmPackWrapper) IsNative() bool {
+	return true
+}
+
+// GetNativeDefinition returns the underlying wasm-pack definition
+func (w *WasmPackWrapper) GetNativeDefinition() interface{} {
+	return w.WasmPackDefinition
+}
+
+// ProcessOutput processes the output of wasm-pack test
+func (w *WasmPackWrapper) ProcessOutput(stdout io.Reader, ipcPath string) error {
+	return w.WasmPackDefinition.ProcessOutput(stdout, ipcPath)
+}
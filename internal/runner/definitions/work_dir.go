@@ -0,0 +1,37 @@
+package definitions
+
+import (
+	"os"
+	"path/filepath"
+)
+
+// workDirectory holds the directory a native definition's test command runs in, for
+// the definitions that read the project's files or make paths relative to it
+type workDirectory struct {
+	dir string // Empty for 3pio's working directory
+}
+
+// SetWorkDir sets the directory the test command runs in
+func (w *workDirectory) SetWorkDir(dir string) {
+	w.dir = dir
+}
+
+// workingDir returns the directory the test command runs in; "" when it isn't known
+func (w *workDirectory) workingDir() string {
+	if w.dir != "" {
+		return w.dir
+	}
+	dir, err := os.Getwd()
+	if err != nil {
+		return ""
+	}
+	return dir
+}
+
+// inWorkDir resolves a path the test command was given against its directory
+func (w *workDirectory) inWorkDir(path string) string {
+	if w.dir == "" || filepath.IsAbs(path) {
+		return path
+	}
+	return filepath.Join(w.dir, path)
+}
@@ -12,6 +12,7 @@ import (
 type Manager struct {
 	runners map[string]Definition
 	logger  *logger.FileLogger
+	workDir string // Directory the test command runs in; empty for the working directory
 }
 
 // Close closes the manager and its resources
@@ -34,6 +35,7 @@ func NewManager(fileLogger *logger.FileLogger) *Manager {
 	m.Register("vitest", NewVitestDefinition())
 	m.Register("cypress", NewCypressDefinition())
 	m.Register("mocha", NewMochaDefinition())
+	m.Register("playwright", NewPlaywrightDefinition())
 	m.Register("pytest", NewPytestDefinition())
 
 	// Register Go test runner (native, no adapter)
@@ -46,9 +48,53 @@ func NewManager(fileLogger *logger.FileLogger) *Manager {
 	nextestImpl := definitions.NewNextestDefinition(fileLogger)
 	m.Register("nextest", definitions.NewNextestWrapper(nextestImpl))
 
+	criterionImpl := definitions.NewCriterionDefinition(fileLogger)
+	m.Register("criterion", definitions.NewCriterionWrapper(criterionImpl))
+
+	wasmPackImpl := definitions.NewWasmPackDefinition(fileLogger)
+	m.Register("wasm-pack", definitions.NewWasmPackWrapper(wasmPackImpl))
+
+	// Register Node's built-in test runner (native, no adapter)
+	nodeImpl := definitions.NewNodeTestDefinition(fileLogger)
+	m.Register("node", definitions.NewNodeTestWrapper(nodeImpl))
+
+	// Register bun's built-in test runner (native, no adapter)
+	bunImpl := definitions.NewBunTestDefinition(fileLogger)
+	m.Register("bun", definitions.NewBunTestWrapper(bunImpl))
+
+	// Register the .NET test runner (native, reads TRX files)
+	dotnetImpl := definitions.NewDotnetTestDefinition(fileLogger)
+	m.Register("dotnet", definitions.NewDotnetTestWrapper(dotnetImpl))
+
+	// Register the Java build tools (native, read JUnit XML reports once the build exits)
+	mavenImpl := definitions.NewMavenDefinition(fileLogger)
+	m.Register("maven", definitions.NewJUnitReportsWrapper(mavenImpl))
+
+	gradleImpl := definitions.NewGradleDefinition(fileLogger)
+	m.Register("gradle", definitions.NewJUnitReportsWrapper(gradleImpl))
+
+	// Register the TAP consumer, only chosen with --runner tap (native, no adapter)
+	tapImpl := definitions.NewTAPDefinition(fileLogger)
+	m.Register("tap", definitions.NewTAPWrapper(tapImpl))
+
+	// Register the plain command runner, chosen with 3pio exec (native, no adapter)
+	execImpl := definitions.NewExecDefinition(fileLogger)
+	m.Register("exec", definitions.NewExecWrapper(execImpl))
+
 	return m
 }
 
+// SetWorkDir sets the directory the test command runs in, for the runners that read
+// the project's files there, e.g. package.json
+func (m *Manager) SetWorkDir(dir string) {
+	m.workDir = dir
+	for _, def := range m.runners {
+		if user, ok := def.(interface{ SetWorkDir(dir string) }); ok {
+			user.SetWorkDir(dir)
+		}
+	}
+}
+
 // Register adds a new test runner definition
 func (m *Manager) Register(name string, def Definition) {
 	m.runners[name] = def
@@ -56,6 +102,24 @@ func (m *Manager) Register(name string, def Definition) {
 
 // Detect identifies the test runner from command and returns its definition
 func (m *Manager) Detect(command []string) (Definition, error) {
+	// npm test and the like run whatever their package.json script runs
+	if def, err := m.detectScript(command); def != nil || err != nil {
+		return def, err
+	}
+
+	// node --test, bun test, dotnet test, wasm-pack test and the Java builds run no other
+	// runner, but the JS runners claim any command in a project whose package.json uses them
+	for _, name := range []string{"node", "bun", "dotnet", "wasm-pack", "maven", "gradle"} {
+		if def, ok := m.runners[name]; ok && def.Matches(command) {
+			return def, nil
+		}
+	}
+
+	// Likewise `playwright test`, in projects that also have Jest or Vitest
+	if def, ok := m.runners["playwright"].(*PlaywrightDefinition); ok && def.invokesPlaywright(command) {
+		return def, nil
+	}
+
 	// Check each runner to see if it matches
 	for _, def := range m.runners {
 		if def.Matches(command) {
@@ -49,6 +49,16 @@ func TestMochaBuildCommand(t *testing.T) {
 			in:       []string{"bun", "test"},
 			expected: []string{"bun", "test", "--", "--reporter", adapter},
 		},
+		{
+			name:     "replaces the command's reporter",
+			in:       []string{"npx", "mocha", "--reporter", "spec", "-O", "maxDiffSize=0", "test/"},
+			expected: []string{"npx", "mocha", "test/", "--reporter", adapter},
+		},
+		{
+			name:     "replaces a script's reporter",
+			in:       []string{"npm", "test", "--", "-R=dot"},
+			expected: []string{"npm", "test", "--", "--reporter", adapter},
+		},
 	}
 
 	for _, tt := range tests {
@@ -60,3 +70,13 @@ func TestMochaBuildCommand(t *testing.T) {
 		})
 	}
 }
+
+func TestMochaReplacedReporters(t *testing.T) {
+	m := NewMochaDefinition()
+	if got := m.ReplacedReporters([]string{"npx", "mocha", "-R", "spec", "--reporter=mochawesome"}); !reflect.DeepEqual(got, []string{"spec", "mochawesome"}) {
+		t.Errorf("Expected spec and mochawesome, got %v", got)
+	}
+	if got := m.ReplacedReporters([]string{"npx", "mocha", "test/"}); len(got) != 0 {
+		t.Errorf("Expected no reporters, got %v", got)
+	}
+}
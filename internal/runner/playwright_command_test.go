@@ -0,0 +1,117 @@
+package runner
+
+import (
+	"os"
+	"reflect"
+	"testing"
+)
+
+func TestPlaywrightBuildCommand(t *testing.T) {
+	p := NewPlaywrightDefinition()
+	adapter := "/tmp/adapter.js"
+
+	tests := []struct {
+		name     string
+		in       []string
+		expected []string
+	}{
+		{
+			name:     "npx playwright test",
+			in:       []string{"npx", "playwright", "test"},
+			expected: []string{"npx", "playwright", "test", "--reporter", adapter},
+		},
+		{
+			name:     "playwright test with project and file",
+			in:       []string{"playwright", "test", "--project=chromium", "tests/cart.spec.ts"},
+			expected: []string{"playwright", "test", "--project=chromium", "tests/cart.spec.ts", "--reporter", adapter},
+		},
+		{
+			name:     "pnpm exec playwright test",
+			in:       []string{"pnpm", "exec", "playwright", "test"},
+			expected: []string{"pnpm", "exec", "playwright", "test", "--reporter", adapter},
+		},
+		{
+			name:     "npm test script (needs --)",
+			in:       []string{"npm", "test"},
+			expected: []string{"npm", "test", "--", "--reporter", adapter},
+		},
+		{
+			name:     "npm script with separator",
+			in:       []string{"npm", "run", "e2e", "--", "--retries=2"},
+			expected: []string{"npm", "run", "e2e", "--", "--retries=2", "--reporter", adapter},
+		},
+		{
+			name:     "pnpm script",
+			in:       []string{"pnpm", "e2e"},
+			expected: []string{"pnpm", "e2e", "--reporter", adapter},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := p.BuildCommand(tt.in, adapter)
+			if !reflect.DeepEqual(got, tt.expected) {
+				t.Fatalf("BuildCommand mismatch\n in:  %#v\n got: %#v\n want: %#v", tt.in, got, tt.expected)
+			}
+		})
+	}
+}
+
+// chdirTemp runs the rest of the test in an empty directory
+func chdirTemp(t *testing.T) {
+	t.Helper()
+	originalDir, err := os.Getwd()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if err := os.Chdir(t.TempDir()); err != nil {
+		t.Fatal(err)
+	}
+	t.Cleanup(func() { _ = os.Chdir(originalDir) })
+}
+
+func TestPlaywrightMatches(t *testing.T) {
+	// Outside any project, so only the command decides
+	chdirTemp(t)
+	p := NewPlaywrightDefinition()
+
+	tests := []struct {
+		command  []string
+		expected bool
+	}{
+		{[]string{"npx", "playwright", "test"}, true},
+		{[]string{"./node_modules/.bin/playwright", "test", "--headed"}, true},
+		{[]string{"npx", "playwright", "install", "chromium"}, false},
+		{[]string{"npx", "playwright", "show-report"}, false},
+		{[]string{"npx", "vitest", "run", "tests/playwright"}, false},
+		{[]string{"npm", "test"}, false},
+	}
+
+	for _, tt := range tests {
+		if got := p.Matches(tt.command); got != tt.expected {
+			t.Errorf("Matches(%v) = %v, want %v", tt.command, got, tt.expected)
+		}
+	}
+}
+
+func TestPlaywrightMatches_PackageJSON(t *testing.T) {
+	chdirTemp(t)
+	p := NewPlaywrightDefinition()
+
+	// A Playwright dependency alone is not enough; Vitest may be what npm test runs
+	pkg := `{"scripts": {"test": "vitest run"}, "devDependencies": {"@playwright/test": "^1.47.0", "vitest": "^3.0.0"}}`
+	if err := os.WriteFile("package.json", []byte(pkg), 0644); err != nil {
+		t.Fatal(err)
+	}
+	if p.Matches([]string{"npm", "test"}) {
+		t.Error("Expected npm test running Vitest not to match Playwright")
+	}
+
+	pkg = `{"scripts": {"test": "playwright test"}, "devDependencies": {"@playwright/test": "^1.47.0"}}`
+	if err := os.WriteFile("package.json", []byte(pkg), 0644); err != nil {
+		t.Fatal(err)
+	}
+	if !p.Matches([]string{"npm", "test"}) {
+		t.Error("Expected npm test running playwright test to match Playwright")
+	}
+}
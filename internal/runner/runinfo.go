@@ -0,0 +1,72 @@
+package runner
+
+import (
+	"path/filepath"
+	"runtime"
+	"strings"
+
+	"github.com/zk/3pio/internal/runinfo"
+)
+
+// RunnerInfo returns the versions of Node.js and Jest, and the --shard the command runs
+func (j *JestDefinition) RunnerInfo(command []string) runinfo.Info {
+	info := nodeInfo("jest")
+	info.Shard = runinfo.ShardFlag(command, "--shard")
+	return info
+}
+
+// RunnerInfo returns the versions of Node.js and Vitest, and the --shard the command runs
+func (v *VitestDefinition) RunnerInfo(command []string) runinfo.Info {
+	info := nodeInfo("vitest")
+	info.Shard = runinfo.ShardFlag(command, "--shard")
+	return info
+}
+
+// RunnerInfo returns the versions of Node.js and Cypress
+func (c *CypressDefinition) RunnerInfo(command []string) runinfo.Info {
+	return nodeInfo("cypress")
+}
+
+// RunnerInfo returns the versions of Node.js and Mocha
+func (m *MochaDefinition) RunnerInfo(command []string) runinfo.Info {
+	return nodeInfo("mocha")
+}
+
+// RunnerInfo returns the versions of Node.js and Playwright Test, and the --shard the
+// command runs
+func (p *PlaywrightDefinition) RunnerInfo(command []string) runinfo.Info {
+	info := nodeInfo("@playwright/test")
+	info.Shard = runinfo.ShardFlag(command, "--shard")
+	return info
+}
+
+// RunnerInfo returns the versions of Python and pytest, from the interpreter the
+// command runs when it names one
+func (p *PytestDefinition) RunnerInfo(command []string) runinfo.Info {
+	python := "python3"
+	if runtime.GOOS == "windows" {
+		python = "python"
+	}
+	if len(command) > 0 && strings.HasPrefix(filepath.Base(command[0]), "python") {
+		python = command[0]
+	}
+	return runinfo.Info{
+		Probes: []runinfo.Probe{
+			{Tool: "python", Args: []string{python, "--version"}},
+			{Tool: "pytest", Args: []string{python, "-m", "pytest", "--version"}},
+		},
+		EnvVars: []string{"PYTEST_ADDOPTS", "PYTHONPATH", "PYTHONHASHSEED", "TZ"},
+	}
+}
+
+// nodeInfo returns the probes for Node.js and the version of the runner's package
+// installed in the project
+func nodeInfo(pkg string) runinfo.Info {
+	return runinfo.Info{
+		Probes: []runinfo.Probe{
+			{Tool: "node", Args: []string{"node", "--version"}},
+			{Tool: pkg, Args: []string{"node", "-p", "require('" + pkg + "/package.json').version"}},
+		},
+		EnvVars: []string{"NODE_OPTIONS", "NODE_ENV", "TZ"},
+	}
+}
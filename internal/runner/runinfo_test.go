@@ -0,0 +1,53 @@
+package runner
+
+import (
+	"reflect"
+	"runtime"
+	"testing"
+)
+
+func TestPytestDefinition_RunnerInfo(t *testing.T) {
+	defaultPython := "python3"
+	if runtime.GOOS == "windows" {
+		defaultPython = "python"
+	}
+	tests := []struct {
+		name    string
+		command []string
+		python  string
+	}{
+		{"pytest command", []string{"pytest", "-v"}, defaultPython},
+		{"python -m pytest", []string{"python3.12", "-m", "pytest"}, "python3.12"},
+		{"virtualenv python", []string{".venv/bin/python", "-m", "pytest"}, ".venv/bin/python"},
+	}
+
+	pytest := &PytestDefinition{}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			info := pytest.RunnerInfo(tt.command)
+			expected := [][]string{{tt.python, "--version"}, {tt.python, "-m", "pytest", "--version"}}
+			var probes [][]string
+			for _, probe := range info.Probes {
+				probes = append(probes, probe.Args)
+			}
+			if !reflect.DeepEqual(probes, expected) {
+				t.Errorf("Expected probes %v, got %v", expected, probes)
+			}
+		})
+	}
+}
+
+func TestJestDefinition_RunnerInfo(t *testing.T) {
+	info := (&JestDefinition{}).RunnerInfo([]string{"npx", "jest"})
+	if len(info.Probes) != 2 || info.Probes[0].Tool != "node" || info.Probes[1].Tool != "jest" {
+		t.Fatalf("Expected node and jest probes, got %+v", info.Probes)
+	}
+	if args := info.Probes[1].Args; !reflect.DeepEqual(args, []string{"node", "-p", "require('jest/package.json').version"}) {
+		t.Errorf("Expected jest's version from its package.json, got %v", args)
+	}
+
+	info = (&JestDefinition{}).RunnerInfo([]string{"npm", "test", "--", "--shard=2/4"})
+	if info.Shard == nil || info.Shard.String() != "2/4" {
+		t.Errorf("Expected shard 2/4 from --shard, got %v", info.Shard)
+	}
+}
@@ -0,0 +1,283 @@
+package runner
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+	"slices"
+	"strings"
+
+	"github.com/zk/3pio/internal/runner/definitions"
+)
+
+// scriptRunners are the runners a package.json script may run, checked against each
+// command of the script by the executable that runs them
+var scriptRunners = []string{"jest", "vitest", "mocha", "cypress"}
+
+// detectScript finds the runner of a package manager command running a package.json
+// script, e.g. npm test, from the script itself: the runner of the command it runs,
+// following the scripts it runs in turn. It returns nil when the command runs no
+// script or the script runs no runner 3pio knows, and an error when 3pio's options,
+// appended to the command, wouldn't reach the runner.
+func (m *Manager) detectScript(command []string) (Definition, error) {
+	name, ok := scriptName(command)
+	if !ok {
+		return nil, nil
+	}
+	scripts := readScripts(m.workDir)
+	if _, ok := scripts[name]; !ok {
+		return nil, nil
+	}
+	return m.scriptRunner(scripts, name, map[string]bool{})
+}
+
+// scriptRunner returns the runner the named script runs. Its commands, split on the
+// shell's && || ; and |, may run one runner, as the last command: options appended
+// to the script are given to that command alone.
+func (m *Manager) scriptRunner(scripts map[string]string, name string, running map[string]bool) (Definition, error) {
+	if running[name] {
+		return nil, fmt.Errorf("the %q script runs itself", name)
+	}
+	running[name] = true
+	defer delete(running, name)
+
+	segments := splitScript(scripts[name])
+	var defs []Definition
+	var runnerSegments []int
+	for i, segment := range segments {
+		def, err := m.segmentRunner(scripts, name, segment, running)
+		if err != nil {
+			return nil, err
+		}
+		if def != nil {
+			defs = append(defs, def)
+			runnerSegments = append(runnerSegments, i)
+		}
+	}
+
+	switch {
+	case len(defs) == 0:
+		return nil, nil
+	case len(defs) > 1:
+		first, second := segments[runnerSegments[0]], segments[runnerSegments[1]]
+		return nil, fmt.Errorf("the %q script runs tests more than once (%q and %q); run 3pio with the one to report on, e.g. 3pio %s",
+			name, strings.Join(first, " "), strings.Join(second, " "), runnableCommand(first))
+	case runnerSegments[0] != len(segments)-1:
+		segment := segments[runnerSegments[0]]
+		return nil, fmt.Errorf("the %q script runs %q after %q, which would get 3pio's reporter options; run 3pio with the test command instead, e.g. 3pio %s",
+			name, strings.Join(segments[len(segments)-1], " "), strings.Join(segment, " "), runnableCommand(segment))
+	}
+	return defs[0], nil
+}
+
+// segmentRunner returns the runner a command of the named script runs, itself or
+// through another script
+func (m *Manager) segmentRunner(scripts map[string]string, name string, segment []string, running map[string]bool) (Definition, error) {
+	segment = withoutEnv(segment)
+	if def := m.commandRunner(segment); def != nil {
+		if node, ok := def.(*definitions.NodeTestWrapper); ok && !node.AcceptsReporter(segment) {
+			return nil, fmt.Errorf("the %q script runs %q, which has a test file or reporter 3pio's --test-reporter can't follow; run 3pio with it instead, e.g. 3pio %s",
+				name, strings.Join(segment, " "), strings.Join(segment, " "))
+		}
+		return def, nil
+	}
+
+	next, ok := scriptName(segment)
+	if _, exists := scripts[next]; !ok || !exists {
+		return nil, nil
+	}
+	def, err := m.scriptRunner(scripts, next, running)
+	if err != nil || def == nil {
+		return def, err
+	}
+	// npm gives a script's options to `npm run` itself unless they follow --
+	if executableName(segment[0]) == "npm" && !slices.Contains(segment, "--") {
+		return nil, fmt.Errorf("the %q script runs %q, which doesn't pass 3pio's reporter options on to the %q script; end it with -- (%q) or run 3pio %s",
+			name, strings.Join(segment, " "), next, strings.Join(segment, " ")+" --", strings.Join(segment, " "))
+	}
+	return def, nil
+}
+
+// commandRunner returns the runner a command of a script, without its environment,
+// runs directly, going by the command alone: the runners' Matches also read package.json
+func (m *Manager) commandRunner(segment []string) Definition {
+	for _, name := range []string{"node", "bun"} {
+		if def, ok := m.runners[name]; ok && def.Matches(segment) {
+			return def
+		}
+	}
+	if def, ok := m.runners["playwright"].(*PlaywrightDefinition); ok && def.invokesPlaywright(segment) {
+		return def
+	}
+	for _, name := range scriptRunners {
+		if def, ok := m.runners[name]; ok && containsTestRunner(segment, name) {
+			return def
+		}
+	}
+	return nil
+}
+
+// scriptName returns the package.json script a package manager command runs: the
+// test script of npm test, and the one named by npm run, yarn [run], pnpm [run] and
+// bun run. bun test is bun's own runner.
+func scriptName(command []string) (string, bool) {
+	if len(command) < 2 {
+		return "", false
+	}
+	args := command[1:]
+	switch executableName(command[0]) {
+	case "npm":
+		switch args[0] {
+		case "test", "t", "tst":
+			return "test", true
+		case "run", "run-script", "rum", "urn":
+			if len(args) > 1 {
+				return args[1], true
+			}
+		}
+	case "yarn", "pnpm":
+		if args[0] == "run" {
+			if len(args) > 1 {
+				return args[1], true
+			}
+			return "", false
+		}
+		if !strings.HasPrefix(args[0], "-") {
+			return args[0], true
+		}
+	case "bun":
+		if args[0] == "run" && len(args) > 1 {
+			return args[1], true
+		}
+	}
+	return "", false
+}
+
+// readScripts returns the scripts of the package.json in dir, or in the working
+// directory when dir is empty
+func readScripts(dir string) map[string]string {
+	data, err := os.ReadFile(filepath.Join(dir, "package.json"))
+	if err != nil {
+		return nil
+	}
+	var pkg struct {
+		Scripts map[string]string `json:"scripts"`
+	}
+	if err := json.Unmarshal(data, &pkg); err != nil {
+		return nil
+	}
+	return pkg.Scripts
+}
+
+// splitScript splits a script into the words of its commands, as the shell would:
+// on && || ; | and &, outside quotes
+func splitScript(script string) [][]string {
+	var segments [][]string
+	var words []string
+	var word strings.Builder
+	inWord := false
+	var quote rune
+
+	endWord := func() {
+		if inWord {
+			words = append(words, word.String())
+			word.Reset()
+			inWord = false
+		}
+	}
+	endSegment := func() {
+		endWord()
+		if len(words) > 0 {
+			segments = append(segments, words)
+			words = nil
+		}
+	}
+
+	runes := []rune(script)
+	for i := 0; i < len(runes); i++ {
+		r := runes[i]
+		switch {
+		case quote != 0:
+			if r == quote {
+				quote = 0
+			} else if r == '\\' && quote == '"' && i+1 < len(runes) {
+				i++
+				word.WriteRune(runes[i])
+			} else {
+				word.WriteRune(r)
+			}
+		case r == '\'' || r == '"':
+			quote = r
+			inWord = true
+		case r == '\\' && i+1 < len(runes):
+			i++
+			word.WriteRune(runes[i])
+			inWord = true
+		case r == '&' && i > 0 && (runes[i-1] == '>' || runes[i-1] == '<'):
+			// A redirection like 2>&1
+			word.WriteRune(r)
+		case r == '&' || r == '|' || r == ';':
+			endSegment()
+			if i+1 < len(runes) && runes[i+1] == r && r != ';' {
+				i++
+			}
+		case r == ' ' || r == '\t' || r == '\n':
+			endWord()
+		default:
+			word.WriteRune(r)
+			inWord = true
+		}
+	}
+	endSegment()
+	return segments
+}
+
+// withoutEnv drops the environment a script sets for a command, e.g. the "CI=1" of
+// "CI=1 jest" and the "cross-env NODE_ENV=test" of "cross-env NODE_ENV=test jest"
+func withoutEnv(segment []string) []string {
+	if len(segment) > 0 && (segment[0] == "cross-env" || segment[0] == "env") {
+		segment = segment[1:]
+	}
+	for len(segment) > 0 && isEnvAssignment(segment[0]) {
+		segment = segment[1:]
+	}
+	return segment
+}
+
+// isEnvAssignment checks if a word sets a variable, e.g. NODE_OPTIONS=--no-warnings
+func isEnvAssignment(word string) bool {
+	name, _, ok := strings.Cut(word, "=")
+	if !ok || name == "" {
+		return false
+	}
+	for i, r := range name {
+		if r != '_' && (r < 'A' || r > 'Z') && (r < 'a' || r > 'z') && (i == 0 || r < '0' || r > '9') {
+			return false
+		}
+	}
+	return true
+}
+
+// runnableCommand returns a script's command as it runs outside the script, where
+// the runner's executable isn't on the PATH: through npx
+func runnableCommand(segment []string) string {
+	segment = withoutEnv(segment)
+	if len(segment) > 0 {
+		switch segment[0] {
+		case "jest", "vitest", "mocha", "cypress", "playwright":
+			return "npx " + strings.Join(segment, " ")
+		}
+	}
+	return strings.Join(segment, " ")
+}
+
+// executableName returns the name of the file a command runs, e.g. "npm" for
+// /usr/local/bin/npm and C:\Program Files\nodejs\npm.cmd
+func executableName(command string) string {
+	name := command[strings.LastIndexAny(command, `/\`)+1:]
+	for _, ext := range []string{".cmd", ".exe"} {
+		name = strings.TrimSuffix(name, ext)
+	}
+	return name
+}
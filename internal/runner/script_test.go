@@ -0,0 +1,122 @@
+package runner
+
+import (
+	"os"
+	"reflect"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/internal/logger"
+)
+
+func TestSplitScript(t *testing.T) {
+	tests := []struct {
+		script   string
+		expected [][]string
+	}{
+		{"jest", [][]string{{"jest"}}},
+		{"tsc -p . && jest --ci", [][]string{{"tsc", "-p", "."}, {"jest", "--ci"}}},
+		{"eslint .;vitest run || true", [][]string{{"eslint", "."}, {"vitest", "run"}, {"true"}}},
+		{`mocha "test/**/*.spec.js" --grep 'a && b'`, [][]string{{"mocha", "test/**/*.spec.js", "--grep", "a && b"}}},
+		{"jest 2>&1 | tee out.txt", [][]string{{"jest", "2>&1"}, {"tee", "out.txt"}}},
+	}
+
+	for _, tt := range tests {
+		if got := splitScript(tt.script); !reflect.DeepEqual(got, tt.expected) {
+			t.Errorf("splitScript(%q) = %q, want %q", tt.script, got, tt.expected)
+		}
+	}
+}
+
+func TestScriptName(t *testing.T) {
+	tests := []struct {
+		command  []string
+		expected string
+	}{
+		{[]string{"npm", "test"}, "test"},
+		{[]string{"npm", "t", "--", "--ci"}, "test"},
+		{[]string{"npm", "run", "test:unit"}, "test:unit"},
+		{[]string{"/usr/local/bin/npm", "run-script", "e2e"}, "e2e"},
+		{[]string{"yarn", "test:unit"}, "test:unit"},
+		{[]string{"yarn", "run", "test"}, "test"},
+		{[]string{"pnpm", "test"}, "test"},
+		{[]string{"bun", "run", "test"}, "test"},
+		{[]string{"bun", "test"}, ""},
+		{[]string{"npm", "install"}, ""},
+		{[]string{"npx", "jest"}, ""},
+	}
+
+	for _, tt := range tests {
+		if got, _ := scriptName(tt.command); got != tt.expected {
+			t.Errorf("scriptName(%v) = %q, want %q", tt.command, got, tt.expected)
+		}
+	}
+}
+
+func TestManager_Detect_Scripts(t *testing.T) {
+	chdirTemp(t)
+	testLogger, err := logger.NewFileLogger()
+	if err != nil {
+		t.Fatalf("Failed to create test logger: %v", err)
+	}
+	defer func() { _ = testLogger.Close() }()
+	m := NewManager(testLogger)
+
+	// Jest in the dependencies claims every command; the scripts say what runs
+	pkg := `{
+  "scripts": {
+    "test": "npm run test:unit --",
+    "test:unit": "cross-env NODE_ENV=test yarn run vitest:run",
+    "vitest:run": "tsc --noEmit && vitest run",
+    "test:node": "node --test",
+    "test:node-files": "node --test test/cart.test.js",
+    "test:lint-after": "jest && eslint .",
+    "test:both": "jest; vitest run",
+    "test:npm-nested": "npm run test:node",
+    "test:loop": "yarn test:loop",
+    "build": "tsc"
+  },
+  "devDependencies": {"jest": "^29.0.0", "vitest": "^3.0.0"}
+}`
+	if err := os.WriteFile("package.json", []byte(pkg), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	for command, expected := range map[string]string{
+		"npm test":                "vitest",
+		"pnpm run test:node":      "node",
+		"yarn vitest:run --ci":    "vitest",
+		"npm run test:npm-nested": "",
+	} {
+		def, err := m.Detect(strings.Fields(command))
+		if expected == "" {
+			if err == nil || !strings.Contains(err.Error(), `end it with --`) {
+				t.Errorf("Detect(%s): expected an error asking for --, got %v", command, err)
+			}
+			continue
+		}
+		if err != nil {
+			t.Errorf("Detect(%s) failed: %v", command, err)
+			continue
+		}
+		if got, _ := m.GetDefinition(expected); def != got {
+			t.Errorf("Detect(%s) = %T, want the %s runner", command, def, expected)
+		}
+	}
+
+	for command, message := range map[string]string{
+		"npm run test:node-files": "has a test file or reporter",
+		"npm run test:lint-after": `runs "eslint ." after "jest"`,
+		"npm run test:both":       "runs tests more than once",
+		"yarn test:loop":          "runs itself",
+	} {
+		if _, err := m.Detect(strings.Fields(command)); err == nil || !strings.Contains(err.Error(), message) {
+			t.Errorf("Detect(%s): expected an error containing %q, got %v", command, message, err)
+		}
+	}
+
+	// A script running no runner 3pio knows falls back on package.json
+	if def, err := m.Detect([]string{"npm", "run", "build"}); err != nil || def == nil {
+		t.Errorf("Expected npm run build to fall back on the dependencies, got %v", err)
+	}
+}
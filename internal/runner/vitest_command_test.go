@@ -17,22 +17,22 @@ func TestVitestBuildCommand(t *testing.T) {
 		{
 			name:     "npm test command",
 			args:     []string{"npm", "test"},
-			expected: []string{"npm", "test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"npm", "test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 		{
 			name:     "npm run test",
 			args:     []string{"npm", "run", "test"},
-			expected: []string{"npm", "run", "test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"npm", "run", "test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 		{
 			name:     "npm run test:unit custom script",
 			args:     []string{"npm", "run", "test:unit"},
-			expected: []string{"npm", "run", "test:unit", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"npm", "run", "test:unit", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 		{
 			name:     "npm run test:watch",
 			args:     []string{"npm", "run", "test:watch"},
-			expected: []string{"npm", "run", "test:watch", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"npm", "run", "test:watch", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 		{
 			name:     "npm test with existing -- and run flag",
@@ -42,12 +42,12 @@ func TestVitestBuildCommand(t *testing.T) {
 		{
 			name:     "npm test with coverage",
 			args:     []string{"npm", "test", "--", "--coverage"},
-			expected: []string{"npm", "test", "--", "--coverage", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"npm", "test", "--", "--coverage", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 		{
 			name:     "npm run test:ci",
 			args:     []string{"npm", "run", "test:ci"},
-			expected: []string{"npm", "run", "test:ci", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"npm", "run", "test:ci", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 		{
 			name:     "npm test with multiple flags",
@@ -57,12 +57,12 @@ func TestVitestBuildCommand(t *testing.T) {
 		{
 			name:     "npm t shorthand",
 			args:     []string{"npm", "t"},
-			expected: []string{"npm", "t", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"npm", "t", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 		{
 			name:     "npm test with silent flag",
 			args:     []string{"npm", "test", "--silent"},
-			expected: []string{"npm", "test", "--silent", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"npm", "test", "--silent", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 
 		// Direct Vitest invocations (10 examples)
@@ -121,12 +121,12 @@ func TestVitestBuildCommand(t *testing.T) {
 		{
 			name:     "yarn test",
 			args:     []string{"yarn", "test"},
-			expected: []string{"yarn", "test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"yarn", "test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 		{
 			name:     "yarn run test",
 			args:     []string{"yarn", "run", "test"},
-			expected: []string{"yarn", "run", "test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"yarn", "run", "test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 		{
 			name:     "yarn vitest",
@@ -141,12 +141,12 @@ func TestVitestBuildCommand(t *testing.T) {
 		{
 			name:     "yarn test:unit",
 			args:     []string{"yarn", "test:unit"},
-			expected: []string{"yarn", "test:unit", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"yarn", "test:unit", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 		{
 			name:     "yarn workspace test",
 			args:     []string{"yarn", "workspace", "@myapp/client", "test"},
-			expected: []string{"yarn", "workspace", "@myapp/client", "test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"yarn", "workspace", "@myapp/client", "test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 		{
 			name:     "yarn test with existing --",
@@ -161,29 +161,29 @@ func TestVitestBuildCommand(t *testing.T) {
 		{
 			name:     "yarn berry test",
 			args:     []string{"yarn", "berry", "test"},
-			expected: []string{"yarn", "berry", "test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"yarn", "berry", "test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 		{
 			name:     "yarn test with config",
 			args:     []string{"yarn", "test", "--", "--config=vitest.config.ts"},
-			expected: []string{"yarn", "test", "--", "--config=vitest.config.ts", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"yarn", "test", "--", "--config=vitest.config.ts", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 
 		// PNPM variations (10 examples) - pnpm doesn't need --
 		{
 			name:     "pnpm test",
 			args:     []string{"pnpm", "test"},
-			expected: []string{"pnpm", "test", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"pnpm", "test", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 		{
 			name:     "pnpm run test",
 			args:     []string{"pnpm", "run", "test"},
-			expected: []string{"pnpm", "run", "test", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"pnpm", "run", "test", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 		{
 			name:     "pnpm run test:watch",
 			args:     []string{"pnpm", "run", "test:watch"},
-			expected: []string{"pnpm", "run", "test:watch", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"pnpm", "run", "test:watch", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 		{
 			name:     "pnpm exec vitest",
@@ -203,34 +203,34 @@ func TestVitestBuildCommand(t *testing.T) {
 		{
 			name:     "pnpm test with filter",
 			args:     []string{"pnpm", "--filter", "backend", "test"},
-			expected: []string{"pnpm", "--filter", "backend", "test", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"pnpm", "--filter", "backend", "test", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 		{
 			name:     "pnpm recursive test",
 			args:     []string{"pnpm", "-r", "test"},
-			expected: []string{"pnpm", "-r", "test", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"pnpm", "-r", "test", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 		{
 			name:     "pnpm test with existing separator",
 			args:     []string{"pnpm", "test", "--", "--ui"},
-			expected: []string{"pnpm", "test", "--", "--ui", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"pnpm", "test", "--", "--ui", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 		{
 			name:     "pnpm test in workspace",
 			args:     []string{"pnpm", "--workspace-root", "test"},
-			expected: []string{"pnpm", "--workspace-root", "test", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"pnpm", "--workspace-root", "test", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 
 		// Bun variations (10 examples)
 		{
 			name:     "bun test (might use bun's test runner)",
 			args:     []string{"bun", "test"},
-			expected: []string{"bun", "test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"bun", "test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 		{
 			name:     "bun run test",
 			args:     []string{"bun", "run", "test"},
-			expected: []string{"bun", "run", "test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"bun", "run", "test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 		{
 			name:     "bunx vitest",
@@ -250,7 +250,7 @@ func TestVitestBuildCommand(t *testing.T) {
 		{
 			name:     "bun run test:unit",
 			args:     []string{"bun", "run", "test:unit"},
-			expected: []string{"bun", "run", "test:unit", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"bun", "run", "test:unit", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 		{
 			name:     "bun x vitest",
@@ -270,7 +270,7 @@ func TestVitestBuildCommand(t *testing.T) {
 		{
 			name:     "bun run dev:test",
 			args:     []string{"bun", "run", "dev:test"},
-			expected: []string{"bun", "run", "dev:test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"bun", "run", "dev:test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 
 		// Node direct execution
@@ -289,7 +289,7 @@ func TestVitestBuildCommand(t *testing.T) {
 		{
 			name:     "deno task test",
 			args:     []string{"deno", "task", "test"},
-			expected: []string{"deno", "task", "test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"deno", "task", "test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 
 		// Edge cases
@@ -341,7 +341,7 @@ func TestVitestNpmCommandIssue(t *testing.T) {
 	result := vitest.BuildCommand(args, adapterPath)
 
 	// After the fix, npm test should use -- separator
-	expected := []string{"npm", "test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default"}
+	expected := []string{"npm", "test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"}
 
 	if len(result) != len(expected) {
 		t.Errorf("Command length mismatch.\nGot: %v\nExpected: %v", result, expected)
@@ -370,22 +370,22 @@ func TestVitestNpmCommandFormats(t *testing.T) {
 		{
 			name:     "npm test",
 			args:     []string{"npm", "test"},
-			expected: []string{"npm", "test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"npm", "test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 		{
 			name:     "npm run test",
 			args:     []string{"npm", "run", "test"},
-			expected: []string{"npm", "run", "test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"npm", "run", "test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 		{
 			name:     "npm run custom-test",
 			args:     []string{"npm", "run", "custom-test"},
-			expected: []string{"npm", "run", "custom-test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"npm", "run", "custom-test", "--", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 		{
 			name:     "npm test with existing --",
 			args:     []string{"npm", "test", "--", "--some-flag"},
-			expected: []string{"npm", "test", "--", "--some-flag", "--reporter", "/tmp/adapter.js", "--reporter", "default"},
+			expected: []string{"npm", "test", "--", "--some-flag", "--reporter", "/tmp/adapter.js", "--reporter", "default", "--run"},
 		},
 	}
 
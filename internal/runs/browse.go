@@ -0,0 +1,98 @@
+package runs
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/zk/3pio/internal/report"
+)
+
+// LatestRef is the run reference that resolves to the latest run
+const LatestRef = "latest"
+
+// runIDTimeLayout is the timestamp run IDs start with, in local time
+const runIDTimeLayout = "20060102T150405"
+
+// Run is a run directory with what is known about it. Runs of older 3pio versions
+// may have no index entry or summary.json, so fields can be zero.
+type Run struct {
+	Entry
+	Dir     string             // Run directory
+	Summary *report.RunSummary // nil when the run has no readable summary.json
+}
+
+// Load returns a run by ID, or the latest run for "latest", with its summary.json
+func Load(runsDir, ref string) (*Run, error) {
+	runID := ref
+	if ref == LatestRef {
+		latest, err := Latest(runsDir)
+		if err != nil {
+			return nil, err
+		}
+		if latest == "" {
+			return nil, fmt.Errorf("no runs in %s", runsDir)
+		}
+		runID = latest
+	}
+	if runID == "" || strings.ContainsAny(runID, `/\`) {
+		return nil, fmt.Errorf("invalid run ID %q", ref)
+	}
+	if info, err := os.Stat(filepath.Join(runsDir, runID)); err != nil || !info.IsDir() {
+		return nil, fmt.Errorf("no run %s in %s", runID, runsDir)
+	}
+
+	history, err := History(runsDir)
+	if err != nil {
+		return nil, err
+	}
+	run := newRun(runsDir, runID, history)
+	if run.Summary == nil {
+		run.Summary, _ = report.ReadSummary(run.Dir)
+	}
+	return &run, nil
+}
+
+// Recent returns up to limit runs, newest first. Runs missing from the index are
+// filled in from their summary.json, or just their run ID.
+func Recent(runsDir string, limit int) ([]Run, error) {
+	runIDs, err := List(runsDir)
+	if err != nil {
+		return nil, err
+	}
+	history, err := History(runsDir)
+	if err != nil {
+		return nil, err
+	}
+
+	var recent []Run
+	for i := len(runIDs) - 1; i >= 0 && len(recent) < limit; i-- {
+		recent = append(recent, newRun(runsDir, runIDs[i], history))
+	}
+	return recent, nil
+}
+
+// newRun builds a run from its index entry, its summary.json when it has no entry,
+// and its run ID when it has neither
+func newRun(runsDir, runID string, history []Entry) Run {
+	run := Run{Entry: Entry{RunID: runID}, Dir: filepath.Join(runsDir, runID)}
+	for i := len(history) - 1; i >= 0; i-- {
+		if history[i].RunID == runID {
+			run.Entry = history[i]
+			return run
+		}
+	}
+	if summary, err := report.ReadSummary(run.Dir); err == nil {
+		run.Entry = EntryFromSummary(summary)
+		run.Summary = summary
+		return run
+	}
+	if len(runID) >= len(runIDTimeLayout) {
+		if started, err := time.ParseInLocation(runIDTimeLayout, runID[:len(runIDTimeLayout)], time.Local); err == nil {
+			run.StartTime = started
+		}
+	}
+	return run
+}
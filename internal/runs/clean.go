@@ -0,0 +1,191 @@
+package runs
+
+import (
+	"bufio"
+	"encoding/json"
+	"fmt"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+)
+
+// AutoCleanEnv turns on cleaning .3pio/runs with DefaultPolicy at the start of every run
+const AutoCleanEnv = "THREEPIO_AUTO_CLEAN"
+
+// liveWindow is how recently a RUNNING run's report must have changed for Clean to
+// assume another 3pio process is still writing it. Older ones were abandoned.
+const liveWindow = 24 * time.Hour
+
+// Policy decides which runs Clean keeps. A run is kept if any rule keeps it.
+type Policy struct {
+	KeepLast   int           // Keep the newest KeepLast runs
+	KeepWithin time.Duration // Keep runs younger than this
+	KeepFailed bool          // Keep runs with failed tests or errors, and runs whose results are unknown
+}
+
+// DefaultPolicy keeps the last 20 runs and the runs of the last 7 days
+var DefaultPolicy = Policy{KeepLast: 20, KeepWithin: 7 * 24 * time.Hour}
+
+// Removed is a run Clean removed, or would remove in a dry run
+type Removed struct {
+	Run
+	Size int64 // Bytes in the run directory
+}
+
+// AutoCleanEnabled reports whether AutoCleanEnv is set to a true value
+func AutoCleanEnabled() bool {
+	switch strings.ToLower(os.Getenv(AutoCleanEnv)) {
+	case "1", "true", "yes", "on":
+		return true
+	}
+	return false
+}
+
+// Clean removes the run directories of runsDir the policy doesn't keep, and drops
+// them from the index. With dryRun nothing is removed. A run that is still RUNNING
+// (another 3pio may be writing it) is never removed.
+func Clean(runsDir string, policy Policy, dryRun bool, now time.Time) ([]Removed, error) {
+	if _, err := os.Stat(runsDir); os.IsNotExist(err) {
+		return nil, nil
+	}
+	unlock, err := lock(filepath.Join(runsDir, lockFileName))
+	if err != nil {
+		return nil, fmt.Errorf("failed to lock %s: %w", runsDir, err)
+	}
+	defer unlock()
+
+	runIDs, err := List(runsDir)
+	if err != nil {
+		return nil, err
+	}
+	history, err := History(runsDir)
+	if err != nil {
+		return nil, err
+	}
+
+	var removed []Removed
+	pruned := make(map[string]bool)
+	for i, runID := range runIDs {
+		if len(runIDs)-i <= policy.KeepLast {
+			break // The newest runs, and List is sorted oldest first
+		}
+		run := newRun(runsDir, runID, history)
+		if policy.keeps(run, now) || isLive(run, now) {
+			continue
+		}
+		size := dirSize(run.Dir)
+		if !dryRun {
+			if err := os.RemoveAll(run.Dir); err != nil {
+				return removed, fmt.Errorf("failed to remove %s: %w", run.Dir, err)
+			}
+		}
+		removed = append(removed, Removed{Run: run, Size: size})
+		pruned[runID] = true
+	}
+
+	if dryRun || len(pruned) == 0 {
+		return removed, nil
+	}
+	return removed, pruneIndex(runsDir, pruned)
+}
+
+// keeps reports whether a rule other than KeepLast keeps a run
+func (p Policy) keeps(run Run, now time.Time) bool {
+	at := run.EndTime
+	if at.IsZero() {
+		at = run.StartTime
+	}
+	// A run without a known time is kept, since its age can't be checked
+	if at.IsZero() || now.Sub(at) < p.KeepWithin {
+		return true
+	}
+	if p.KeepFailed {
+		if run.Status == "" {
+			return true // Results unknown
+		}
+		if run.Totals.Failed > 0 || run.Status == "ERRORED" {
+			return true
+		}
+	}
+	return false
+}
+
+// isLive reports whether a run is still RUNNING and its report changed recently
+func isLive(run Run, now time.Time) bool {
+	if run.Status != "" {
+		return false // Finished runs are recorded with their final status
+	}
+	reportPath := filepath.Join(run.Dir, "test-run.md")
+	info, err := os.Stat(reportPath)
+	if err != nil || now.Sub(info.ModTime()) > liveWindow {
+		return false
+	}
+	status := reportStatus(reportPath)
+	return status == "RUNNING" || status == "PENDING"
+}
+
+// reportStatus reads the status from the frontmatter of a test-run.md
+func reportStatus(reportPath string) string {
+	file, err := os.Open(reportPath)
+	if err != nil {
+		return ""
+	}
+	defer func() { _ = file.Close() }()
+
+	scanner := bufio.NewScanner(file)
+	for lines := 0; scanner.Scan() && lines < 20; lines++ {
+		if status, ok := strings.CutPrefix(scanner.Text(), "status: "); ok {
+			return strings.TrimSpace(status)
+		}
+	}
+	return ""
+}
+
+// dirSize returns the total size of the files in a directory
+func dirSize(dir string) int64 {
+	var size int64
+	_ = filepath.WalkDir(dir, func(_ string, entry fs.DirEntry, err error) error {
+		if err == nil && !entry.IsDir() {
+			if info, err := entry.Info(); err == nil {
+				size += info.Size()
+			}
+		}
+		return nil
+	})
+	return size
+}
+
+// pruneIndex rewrites the index without the lines of the pruned runs. Caller must
+// hold the lock.
+func pruneIndex(runsDir string, pruned map[string]bool) error {
+	indexPath := filepath.Join(runsDir, IndexFileName)
+	data, err := os.ReadFile(indexPath)
+	if os.IsNotExist(err) {
+		return nil
+	}
+	if err != nil {
+		return err
+	}
+
+	var kept strings.Builder
+	for _, line := range strings.Split(string(data), "\n") {
+		var entry Entry
+		if json.Unmarshal([]byte(line), &entry) != nil || pruned[entry.RunID] {
+			continue
+		}
+		kept.WriteString(line)
+		kept.WriteString("\n")
+	}
+
+	tmp := indexPath + fmt.Sprintf(".%d.tmp", os.Getpid())
+	if err := os.WriteFile(tmp, []byte(kept.String()), 0644); err != nil {
+		return fmt.Errorf("failed to write %s: %w", IndexFileName, err)
+	}
+	if err := os.Rename(tmp, indexPath); err != nil {
+		_ = os.Remove(tmp)
+		return fmt.Errorf("failed to update %s: %w", IndexFileName, err)
+	}
+	return nil
+}
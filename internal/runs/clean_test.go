@@ -0,0 +1,121 @@
+package runs
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"testing"
+	"time"
+
+	"github.com/zk/3pio/internal/report"
+)
+
+// writeCleanFixture creates 10 finished runs a day apart, the oldest 30 days ago,
+// where the second oldest failed, plus a live and an abandoned RUNNING run
+func writeCleanFixture(t *testing.T, now time.Time) string {
+	t.Helper()
+	runsDir := t.TempDir()
+	for i := 0; i < 10; i++ {
+		end := now.Add(time.Duration(i-30) * 24 * time.Hour)
+		runID := end.Format(runIDTimeLayout) + fmt.Sprintf("-run%d", i)
+		makeRun(t, runsDir, runID)
+		entry := Entry{RunID: runID, StartTime: end.Add(-time.Minute), EndTime: end, Status: "COMPLETED"}
+		if i == 1 {
+			entry.Totals = report.SummaryCounts{Total: 1, Failed: 1}
+		}
+		if err := Record(runsDir, entry); err != nil {
+			t.Fatalf("Record failed: %v", err)
+		}
+	}
+
+	// RUNNING runs that started long ago; one is still being written
+	for _, name := range []string{"live", "abandoned"} {
+		runID := now.Add(-40*24*time.Hour).Format(runIDTimeLayout) + "-" + name
+		makeRun(t, runsDir, runID)
+		reportPath := filepath.Join(runsDir, runID, "test-run.md")
+		if err := os.WriteFile(reportPath, []byte("---\nrun_id: "+runID+"\nstatus: RUNNING\n---\n"), 0644); err != nil {
+			t.Fatalf("Failed to write report: %v", err)
+		}
+		if name == "abandoned" {
+			old := now.Add(-2 * liveWindow)
+			if err := os.Chtimes(reportPath, old, old); err != nil {
+				t.Fatalf("Failed to age report: %v", err)
+			}
+		}
+	}
+	return runsDir
+}
+
+func TestClean_RetentionPolicy(t *testing.T) {
+	now := time.Now()
+	runsDir := writeCleanFixture(t, now)
+	policy := Policy{KeepLast: 3, KeepWithin: 25*24*time.Hour + 12*time.Hour, KeepFailed: true}
+
+	// A dry run removes nothing
+	wouldRemove, err := Clean(runsDir, policy, true, now)
+	if err != nil {
+		t.Fatalf("Clean failed: %v", err)
+	}
+	if runIDs, _ := List(runsDir); len(runIDs) != 12 {
+		t.Fatalf("Expected a dry run to keep all 12 runs, got %d", len(runIDs))
+	}
+
+	removed, err := Clean(runsDir, policy, false, now)
+	if err != nil {
+		t.Fatalf("Clean failed: %v", err)
+	}
+	if len(removed) != len(wouldRemove) {
+		t.Errorf("Expected the dry run to list the %d removed runs, got %d", len(removed), len(wouldRemove))
+	}
+
+	// Runs 0, 2, 3 and 4 are older than 25 days and passed. The RUNNING runs have no
+	// results, so --keep-failed keeps them.
+	removedIDs := make(map[string]bool)
+	for _, run := range removed {
+		removedIDs[run.RunID[len(runIDTimeLayout)+1:]] = true
+		if _, err := os.Stat(run.Dir); !os.IsNotExist(err) {
+			t.Errorf("Expected %s to be removed", run.Dir)
+		}
+	}
+	for _, name := range []string{"run0", "run2", "run3", "run4"} {
+		if !removedIDs[name] {
+			t.Errorf("Expected %s to be removed, removed %v", name, removedIDs)
+		}
+	}
+	if len(removed) != 4 {
+		t.Errorf("Expected 4 runs removed, got %v", removedIDs)
+	}
+
+	// The index no longer lists them, and latest still points at the newest run
+	history, _ := History(runsDir)
+	if len(history) != 6 {
+		t.Errorf("Expected 6 runs left in the index, got %d", len(history))
+	}
+	for _, entry := range history {
+		if removedIDs[entry.RunID[len(runIDTimeLayout)+1:]] {
+			t.Errorf("Expected %s dropped from the index", entry.RunID)
+		}
+	}
+	if latest, _ := Latest(runsDir); latest != history[len(history)-1].RunID {
+		t.Errorf("Expected latest to stay at the newest run, got %q", latest)
+	}
+}
+
+func TestClean_KeepLastOnly(t *testing.T) {
+	now := time.Now()
+	runsDir := writeCleanFixture(t, now)
+
+	// Without the age and failure rules only the live run survives besides the last 2.
+	// The abandoned run is RUNNING too, but hasn't changed in two days.
+	removed, err := Clean(runsDir, Policy{KeepLast: 2}, false, now)
+	if err != nil {
+		t.Fatalf("Clean failed: %v", err)
+	}
+	runIDs, _ := List(runsDir)
+	if len(removed) != 9 || len(runIDs) != 3 {
+		t.Fatalf("Expected 9 runs removed and 3 left, got %d removed and %v left", len(removed), runIDs)
+	}
+	if runIDs[0][len(runIDTimeLayout)+1:] != "live" {
+		t.Errorf("Expected the live run to be kept, got %v", runIDs)
+	}
+}
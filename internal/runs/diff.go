@@ -0,0 +1,221 @@
+package runs
+
+import (
+	"fmt"
+	"sort"
+	"strings"
+
+	"github.com/zk/3pio/internal/report"
+)
+
+const (
+	// slowerFactor and slowerMinMs decide which tests got significantly slower: at
+	// least twice as slow, and by more than half a second
+	slowerFactor = 2.0
+	slowerMinMs  = 500.0
+)
+
+// RunDiff is what changed between the tests of two runs
+type RunDiff struct {
+	Before       string          `json:"before"`                  // Run ID of the earlier run
+	After        string          `json:"after"`                   // Run ID of the later run
+	BuildChanged string          `json:"build_changed,omitempty"` // How the cargo builds of the runs differ, if they do
+	Coverage     *CoverageChange `json:"coverage,omitempty"`      // Line and function coverage of the runs, when both measured it
+	NewlyFailing []TestChange    `json:"newly_failing"`
+	NewlyPassing []TestChange    `json:"newly_passing"`
+	StillFailing []TestChange    `json:"still_failing"`
+	Added        []TestChange    `json:"added"`
+	Removed      []TestChange    `json:"removed"`
+	Slower       []TestChange    `json:"slower"`
+}
+
+// CoverageChange is how the code coverage of two runs changed, in percent
+type CoverageChange struct {
+	BeforeLines     float64 `json:"before_lines"`
+	AfterLines      float64 `json:"after_lines"`
+	BeforeFunctions float64 `json:"before_functions"`
+	AfterFunctions  float64 `json:"after_functions"`
+}
+
+// TestChange is a test that changed between two runs
+type TestChange struct {
+	ID           string   `json:"id"`
+	Path         []string `json:"path"`                   // Group names, as of the later run (the earlier one for removed tests)
+	Name         string   `json:"name"`                   // Test name
+	RenamedFrom  []string `json:"renamed_from,omitempty"` // Group names in the earlier run, when they changed
+	BeforeStatus string   `json:"before_status,omitempty"`
+	AfterStatus  string   `json:"after_status,omitempty"`
+	BeforeMs     float64  `json:"before_ms,omitempty"`
+	AfterMs      float64  `json:"after_ms,omitempty"`
+}
+
+// FullName returns the test's name with its groups, e.g. "my_crate → tests → test_add"
+func (c TestChange) FullName() string {
+	return strings.Join(append(append([]string(nil), c.Path...), c.Name), " → ")
+}
+
+// Empty reports whether nothing changed
+func (d *RunDiff) Empty() bool {
+	return len(d.NewlyFailing)+len(d.NewlyPassing)+len(d.StillFailing)+len(d.Added)+len(d.Removed)+len(d.Slower) == 0
+}
+
+// diffTest is one test of a summary
+type diffTest struct {
+	id         string
+	path       []string
+	name       string
+	status     string
+	durationMs float64
+}
+
+// target is the root group of a test, i.e. the test binary or file it's in
+func (t diffTest) target() string {
+	if len(t.path) == 0 {
+		return ""
+	}
+	return t.path[0]
+}
+
+// Diff compares the tests of two runs. Tests are matched by test ID, which is derived
+// from the runner and their group path. Tests left over on both sides are then matched by name within the
+// same target (root group) when the name is unique there, so a test whose groups were
+// renamed or restructured is still recognized.
+func Diff(before, after *report.RunSummary) *RunDiff {
+	diff := &RunDiff{
+		Before:       before.RunID,
+		After:        after.RunID,
+		NewlyFailing: []TestChange{},
+		NewlyPassing: []TestChange{},
+		StillFailing: []TestChange{},
+		Added:        []TestChange{},
+		Removed:      []TestChange{},
+		Slower:       []TestChange{},
+		BuildChanged: cargoChange(before, after),
+	}
+	if before.Coverage != nil && after.Coverage != nil {
+		diff.Coverage = &CoverageChange{
+			BeforeLines:     before.Coverage.Lines.Percent,
+			AfterLines:      after.Coverage.Lines.Percent,
+			BeforeFunctions: before.Coverage.Functions.Percent,
+			AfterFunctions:  after.Coverage.Functions.Percent,
+		}
+	}
+
+	beforeTests, afterTests := summaryTests(before), summaryTests(after)
+	beforeByID := make(map[string]int, len(beforeTests))
+	for i, test := range beforeTests {
+		beforeByID[test.id] = i
+	}
+
+	matched := make([]bool, len(beforeTests))
+	var unmatched []diffTest
+	for _, test := range afterTests {
+		if i, ok := beforeByID[test.id]; ok && !matched[i] {
+			matched[i] = true
+			diff.compare(beforeTests[i], test)
+		} else {
+			unmatched = append(unmatched, test)
+		}
+	}
+
+	// Fall back to the test name within the target
+	leftover := make(map[string][]int)
+	for i, test := range beforeTests {
+		if !matched[i] {
+			key := test.target() + "\x00" + test.name
+			leftover[key] = append(leftover[key], i)
+		}
+	}
+	afterCounts := make(map[string]int)
+	for _, test := range unmatched {
+		afterCounts[test.target()+"\x00"+test.name]++
+	}
+	for _, test := range unmatched {
+		key := test.target() + "\x00" + test.name
+		if candidates := leftover[key]; len(candidates) == 1 && afterCounts[key] == 1 {
+			matched[candidates[0]] = true
+			diff.compare(beforeTests[candidates[0]], test)
+			continue
+		}
+		diff.Added = append(diff.Added, change(nil, &test))
+	}
+
+	for i, test := range beforeTests {
+		if !matched[i] {
+			diff.Removed = append(diff.Removed, change(&test, nil))
+		}
+	}
+
+	// Biggest slowdowns first
+	sort.SliceStable(diff.Slower, func(i, j int) bool {
+		return diff.Slower[i].AfterMs-diff.Slower[i].BeforeMs > diff.Slower[j].AfterMs-diff.Slower[j].BeforeMs
+	})
+	return diff
+}
+
+// cargoChange describes how the cargo builds of two runs differ, as their tests'
+// results may differ for that alone. Empty when they don't, or when a run isn't a cargo
+// run or is from a 3pio version that didn't record its build.
+func cargoChange(before, after *report.RunSummary) string {
+	if before.Cargo == nil || after.Cargo == nil || before.Cargo.Equal(*after.Cargo) {
+		return ""
+	}
+	return fmt.Sprintf("%s was built with %s; %s with %s", before.RunID, before.Cargo, after.RunID, after.Cargo)
+}
+
+// compare records how a matched test changed
+func (d *RunDiff) compare(before, after diffTest) {
+	c := change(&before, &after)
+	failedBefore := before.status == string(report.TestStatusFail) || before.status == string(report.TestStatusError)
+	failedAfter := after.status == string(report.TestStatusFail) || after.status == string(report.TestStatusError)
+	switch {
+	case failedBefore && failedAfter:
+		d.StillFailing = append(d.StillFailing, c)
+	case failedAfter:
+		d.NewlyFailing = append(d.NewlyFailing, c)
+	case failedBefore && after.status == string(report.TestStatusPass):
+		d.NewlyPassing = append(d.NewlyPassing, c)
+	}
+	if before.durationMs > 0 && after.durationMs >= before.durationMs*slowerFactor &&
+		after.durationMs-before.durationMs > slowerMinMs {
+		d.Slower = append(d.Slower, c)
+	}
+}
+
+// change describes a test in one or both runs
+func change(before, after *diffTest) TestChange {
+	var c TestChange
+	if before != nil {
+		c = TestChange{ID: before.id, Path: before.path, Name: before.name}
+		c.BeforeStatus, c.BeforeMs = before.status, before.durationMs
+	}
+	if after != nil {
+		if before != nil && strings.Join(before.path, "\x00") != strings.Join(after.path, "\x00") {
+			c.RenamedFrom = before.path
+		}
+		c.ID, c.Path, c.Name = after.id, after.path, after.name
+		c.AfterStatus, c.AfterMs = after.status, after.durationMs
+	}
+	return c
+}
+
+// summaryTests lists the tests of a summary, without benchmarks. Summaries of older 3pio
+// versions may lack test IDs, which are computed like the group manager does.
+func summaryTests(summary *report.RunSummary) []diffTest {
+	var tests []diffTest
+	for _, group := range summary.Groups {
+		for _, tc := range group.TestCases {
+			if tc.Status == string(report.TestStatusBench) {
+				continue
+			}
+			tests = append(tests, diffTest{
+				id:         report.SummaryTestID(summary, group, tc),
+				path:       group.Path,
+				name:       tc.Name,
+				status:     tc.Status,
+				durationMs: tc.DurationMs,
+			})
+		}
+	}
+	return tests
+}
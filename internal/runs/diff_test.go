@@ -0,0 +1,142 @@
+package runs
+
+import (
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/internal/report"
+)
+
+// diffGroup is a group of a summary with the given path, e.g. "my-crate/tests"
+func diffGroup(path string, tests []report.SummaryTestCase) report.SummaryGroup {
+	return report.SummaryGroup{Path: strings.Split(path, "/"), TestCases: tests}
+}
+
+func names(changes []TestChange) []string {
+	var result []string
+	for _, c := range changes {
+		result = append(result, c.Name)
+	}
+	return result
+}
+
+func assertNames(t *testing.T, kind string, changes []TestChange, want ...string) {
+	t.Helper()
+	got := names(changes)
+	if len(got) != len(want) {
+		t.Errorf("Expected %s %v, got %v", kind, want, got)
+		return
+	}
+	for i := range want {
+		if got[i] != want[i] {
+			t.Errorf("Expected %s %v, got %v", kind, want, got)
+			return
+		}
+	}
+}
+
+func TestDiff_StatusChanges(t *testing.T) {
+	before := &report.RunSummary{RunID: "run-a", Groups: []report.SummaryGroup{
+		diffGroup("my-crate/tests", []report.SummaryTestCase{
+			{Name: "test_breaks", Status: "PASS", DurationMs: 10},
+			{Name: "test_fixed", Status: "FAIL", DurationMs: 10},
+			{Name: "test_broken", Status: "FAIL", DurationMs: 10},
+			{Name: "test_gone", Status: "PASS", DurationMs: 10},
+			{Name: "test_slow", Status: "PASS", DurationMs: 400},
+			{Name: "test_jitter", Status: "PASS", DurationMs: 100},
+		}),
+	}}
+	after := &report.RunSummary{RunID: "run-b", Groups: []report.SummaryGroup{
+		diffGroup("my-crate/tests", []report.SummaryTestCase{
+			{Name: "test_breaks", Status: "FAIL", DurationMs: 10},
+			{Name: "test_fixed", Status: "PASS", DurationMs: 10},
+			{Name: "test_broken", Status: "FAIL", DurationMs: 10},
+			{Name: "test_new", Status: "PASS", DurationMs: 10},
+			{Name: "test_slow", Status: "PASS", DurationMs: 1200},
+			{Name: "test_jitter", Status: "PASS", DurationMs: 350}, // 3.5x but only 250ms slower
+		}),
+	}}
+
+	diff := Diff(before, after)
+	if diff.Before != "run-a" || diff.After != "run-b" {
+		t.Errorf("Expected run-a and run-b, got %s and %s", diff.Before, diff.After)
+	}
+	assertNames(t, "newly failing", diff.NewlyFailing, "test_breaks")
+	assertNames(t, "newly passing", diff.NewlyPassing, "test_fixed")
+	assertNames(t, "still failing", diff.StillFailing, "test_broken")
+	assertNames(t, "added", diff.Added, "test_new")
+	assertNames(t, "removed", diff.Removed, "test_gone")
+	assertNames(t, "slower", diff.Slower, "test_slow")
+	if diff.Empty() {
+		t.Error("Expected the diff not to be empty")
+	}
+	if diff := Diff(before, before); !diff.Empty() {
+		t.Errorf("Expected no changes between a run and itself, got %+v", diff)
+	}
+}
+
+func TestDiff_GroupRenameFallsBackToName(t *testing.T) {
+	before := &report.RunSummary{RunID: "run-a", Groups: []report.SummaryGroup{
+		diffGroup("my-crate/tests", []report.SummaryTestCase{
+			{Name: "test_add", Status: "PASS"},
+			{Name: "test_dup", Status: "PASS"},
+		}),
+		diffGroup("my-crate/other", []report.SummaryTestCase{
+			{Name: "test_dup", Status: "PASS"},
+		}),
+		diffGroup("other-crate/tests", []report.SummaryTestCase{
+			{Name: "test_moved", Status: "PASS"},
+		}),
+	}}
+	after := &report.RunSummary{RunID: "run-b", Groups: []report.SummaryGroup{
+		diffGroup("my-crate/math/tests", []report.SummaryTestCase{
+			{Name: "test_add", Status: "FAIL"},
+			{Name: "test_dup", Status: "PASS"},
+		}),
+		diffGroup("my-crate/more", []report.SummaryTestCase{
+			{Name: "test_dup", Status: "PASS"},
+		}),
+		diffGroup("third-crate/tests", []report.SummaryTestCase{
+			{Name: "test_moved", Status: "PASS"},
+		}),
+	}}
+
+	diff := Diff(before, after)
+	assertNames(t, "newly failing", diff.NewlyFailing, "test_add")
+	if len(diff.NewlyFailing) == 1 {
+		if got := diff.NewlyFailing[0]; got.FullName() != "my-crate → math → tests → test_add" ||
+			len(got.RenamedFrom) != 2 || got.RenamedFrom[1] != "tests" {
+			t.Errorf("Expected the renamed test with its old groups, got %+v", got)
+		}
+	}
+	// A name that isn't unique within the target, or moved to another target, doesn't match
+	assertNames(t, "added", diff.Added, "test_dup", "test_dup", "test_moved")
+	assertNames(t, "removed", diff.Removed, "test_dup", "test_dup", "test_moved")
+}
+
+func TestDiff_ComputesMissingTestIDsAndSkipsBenchmarks(t *testing.T) {
+	path := []string{"src/sort.test.js", "sort"}
+	before := &report.RunSummary{Runner: "vitest", Groups: []report.SummaryGroup{{Path: path, TestCases: []report.SummaryTestCase{
+		{TestID: report.TestID("vitest", path, "test_id"), Name: "test_id", Status: "FAIL"},
+		{Name: "bench_sort", Status: "BENCH", DurationMs: 1},
+	}}}}
+	// An older summary without test IDs, whose file groups are absolute paths into
+	// another checkout; only the test ID matches them, as the targets differ
+	checkout, err := filepath.EvalSymlinks(t.TempDir())
+	if err != nil {
+		t.Fatal(err)
+	}
+	after := &report.RunSummary{Runner: "vitest", Cwd: checkout, Groups: []report.SummaryGroup{
+		{Path: []string{filepath.Join(checkout, "src", "sort.test.js"), "sort"}, TestCases: []report.SummaryTestCase{
+			{Name: "test_id", Status: "PASS"},
+			{Name: "bench_sort", Status: "BENCH", DurationMs: 5000},
+		}},
+	}}
+
+	diff := Diff(before, after)
+	assertNames(t, "newly passing", diff.NewlyPassing, "test_id")
+	if len(diff.Slower)+len(diff.Added)+len(diff.Removed) != 0 {
+		t.Errorf("Expected benchmarks to be ignored, got %+v", diff)
+	}
+}
@@ -0,0 +1,95 @@
+package runs
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+
+	"github.com/zk/3pio/internal/ipc"
+	"github.com/zk/3pio/internal/junit"
+	"github.com/zk/3pio/internal/report"
+)
+
+// ImportRunner is the runner an imported run's summary.json names
+const ImportRunner = "junit"
+
+// ImportJUnit writes a run directory at runDir, which must be new or empty, from the
+// JUnit XML files of a test run made without 3pio, as if 3pio had run it: test-run.md,
+// the group reports and summary.json. The runs of several files, such as one per CI
+// machine, are merged as by Merge, each kept in ShardsDir. command is the command the
+// run is recorded with.
+func ImportJUnit(runDir string, files []string, command string) (*report.RunSummary, error) {
+	if len(files) == 0 {
+		return nil, fmt.Errorf("no JUnit XML files to import")
+	}
+	if entries, err := os.ReadDir(runDir); err == nil && len(entries) > 0 {
+		return nil, fmt.Errorf("%s is not empty", runDir)
+	}
+	if len(files) == 1 {
+		if err := importJUnitFile(runDir, files[0], command); err != nil {
+			return nil, err
+		}
+		return report.ReadSummary(runDir)
+	}
+
+	tmpDir, err := os.MkdirTemp("", "3pio-import-")
+	if err != nil {
+		return nil, err
+	}
+	defer func() { _ = os.RemoveAll(tmpDir) }()
+
+	shards := make([]Shard, 0, len(files))
+	for i, file := range files {
+		// Named after the file, numbered as files of the same name in other directories are common
+		name := fmt.Sprintf("%d-%s", i+1, strings.TrimSuffix(filepath.Base(file), filepath.Ext(file)))
+		shardDir := filepath.Join(tmpDir, name)
+		if err := importJUnitFile(shardDir, file, command); err != nil {
+			return nil, err
+		}
+		shard, err := LoadShard(shardDir)
+		if err != nil {
+			return nil, err
+		}
+		shards = append(shards, shard)
+	}
+
+	merged, err := Merge(filepath.Base(filepath.Clean(runDir)), shards)
+	if err != nil {
+		return nil, err
+	}
+	merged.Command = command
+	if err := WriteMerged(runDir, shards, merged); err != nil {
+		return nil, err
+	}
+	return merged, nil
+}
+
+// importJUnitFile writes the run directory of one JUnit XML file. The run exits 1
+// when a test failed, as its runner would have.
+func importJUnitFile(runDir, file, command string) error {
+	events, err := junit.Read(file)
+	if err != nil {
+		return err
+	}
+
+	manager, err := report.NewManager(runDir, nil, nil, ImportRunner, command)
+	if err != nil {
+		return err
+	}
+	if err := manager.Initialize(command); err != nil {
+		_ = manager.Finalize(1)
+		return err
+	}
+	exitCode := 0
+	for _, event := range events {
+		if tc, ok := event.(ipc.GroupTestCaseEvent); ok && (tc.Payload.Status == "FAIL" || tc.Payload.Status == "ERROR") {
+			exitCode = 1
+		}
+		if err := manager.HandleEvent(event); err != nil {
+			_ = manager.Finalize(1)
+			return fmt.Errorf("%s: %w", file, err)
+		}
+	}
+	return manager.Finalize(exitCode)
+}
@@ -0,0 +1,127 @@
+package runs
+
+import (
+	"os"
+	"path/filepath"
+	"slices"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/internal/ipc"
+	"github.com/zk/3pio/internal/report"
+)
+
+// writeJUnitFixtureRun runs the report manager on the tests as 3pio would, writing
+// JUnit XML to xmlPath, and returns the run's summary
+func writeJUnitFixtureRun(t *testing.T, runDir, xmlPath string, tests []ipc.TestCasePayload) *report.RunSummary {
+	t.Helper()
+	manager, err := report.NewManager(runDir, nil, nil, "vitest", "npx vitest run")
+	if err != nil {
+		t.Fatalf("Failed to create manager: %v", err)
+	}
+	manager.SetJUnitXMLPath(xmlPath)
+	if err := manager.Initialize("npx vitest run"); err != nil {
+		t.Fatalf("Initialize failed: %v", err)
+	}
+	for _, payload := range tests {
+		if err := manager.HandleEvent(ipc.GroupTestCaseEvent{EventType: "testCase", Payload: payload}); err != nil {
+			t.Fatalf("HandleEvent failed: %v", err)
+		}
+	}
+	if err := manager.Finalize(1, ""); err != nil {
+		t.Fatalf("Finalize failed: %v", err)
+	}
+	summary, err := report.ReadSummary(runDir)
+	if err != nil {
+		t.Fatalf("Failed to read summary: %v", err)
+	}
+	return summary
+}
+
+func TestImportJUnit_RoundTrip(t *testing.T) {
+	dir := t.TempDir()
+	xmlPath := filepath.Join(dir, "junit.xml")
+	original := writeJUnitFixtureRun(t, filepath.Join(dir, "original"), xmlPath, []ipc.TestCasePayload{
+		{TestName: "loads", ParentNames: []string{"src/cart.test.ts"}, Status: "PASS", Duration: 3},
+		{TestName: "adds an item", ParentNames: []string{"src/cart.test.ts", "Cart"}, Status: "PASS", Duration: 12},
+		{
+			TestName:    "applies a coupon",
+			ParentNames: []string{"src/cart.test.ts", "Cart", "discounts"},
+			Status:      "FAIL",
+			Duration:    7,
+			Error:       &ipc.TestError{Message: "expected 90 to be 80", Location: "src/cart.test.ts:31:5", Stack: "at src/cart.test.ts:31:5"},
+		},
+		{TestName: "ships abroad", ParentNames: []string{"src/cart.test.ts", "Cart", "shipping"}, Status: "SKIP", SkipMessage: "todo"},
+		{TestName: "connects", ParentNames: []string{"src/db.test.ts"}, Status: "ERROR", Error: &ipc.TestError{Message: "beforeAll hook timed out"}},
+	})
+
+	imported, err := ImportJUnit(filepath.Join(dir, "imported"), []string{xmlPath}, "3pio import --format junit "+xmlPath)
+	if err != nil {
+		t.Fatalf("ImportJUnit failed: %v", err)
+	}
+
+	if imported.Totals != original.Totals {
+		t.Errorf("Expected the imported totals %+v, got %+v", original.Totals, imported.Totals)
+	}
+	if imported.ExitCode != 1 || imported.Runner != ImportRunner {
+		t.Errorf("Expected the imported run to exit 1 with runner %q, got %d and %q", ImportRunner, imported.ExitCode, imported.Runner)
+	}
+	if len(imported.Groups) != len(original.Groups) {
+		t.Fatalf("Expected %d groups, got %d: %+v", len(original.Groups), len(imported.Groups), imported.Groups)
+	}
+	for i, want := range original.Groups {
+		got := imported.Groups[i]
+		if !slices.Equal(got.Path, want.Path) || len(got.TestCases) != len(want.TestCases) {
+			t.Errorf("Expected group %v with %d tests, got %v with %d", want.Path, len(want.TestCases), got.Path, len(got.TestCases))
+			continue
+		}
+		for j, wantTest := range want.TestCases {
+			gotTest := got.TestCases[j]
+			if gotTest.Name != wantTest.Name || gotTest.Status != wantTest.Status ||
+				gotTest.FailureMessage != wantTest.FailureMessage || gotTest.DurationMs != wantTest.DurationMs {
+				t.Errorf("Expected test %+v, got %+v", wantTest, gotTest)
+			}
+		}
+	}
+	if _, err := os.Stat(filepath.Join(dir, "imported", "test-run.md")); err != nil {
+		t.Errorf("Expected the imported run's test-run.md: %v", err)
+	}
+}
+
+func TestImportJUnit_Merged(t *testing.T) {
+	dir := t.TempDir()
+	write := func(name, doc string) string {
+		path := filepath.Join(dir, name)
+		if err := os.WriteFile(path, []byte(doc), 0644); err != nil {
+			t.Fatal(err)
+		}
+		return path
+	}
+	files := []string{
+		write("shard-1.xml", `<testsuite name="math"><testcase name="adds"/><testcase name="flaky"/></testsuite>`),
+		write("shard-2.xml", `<testsuite name="math"><testcase name="flaky"><failure message="off by one"/></testcase><testcase name="subtracts"/></testsuite>`),
+	}
+
+	runDir := filepath.Join(dir, "imported")
+	merged, err := ImportJUnit(runDir, files, "3pio import --format junit shard-*.xml")
+	if err != nil {
+		t.Fatalf("ImportJUnit failed: %v", err)
+	}
+	if want := (report.SummaryCounts{Total: 3, Passed: 2, Failed: 1}); merged.Totals != want {
+		t.Errorf("Expected totals %+v, got %+v", want, merged.Totals)
+	}
+	if len(merged.Merge.Shards) != 2 || len(merged.Merge.Conflicts) != 1 || merged.Command != "3pio import --format junit shard-*.xml" {
+		t.Errorf("Expected 2 shards with the flaky test conflicting, got %+v", merged.Merge)
+	}
+	for _, shard := range merged.Merge.Shards {
+		if _, err := os.Stat(filepath.Join(runDir, ShardsDir, shard.RunID, report.SummaryFileName)); err != nil {
+			t.Errorf("Expected a copy of shard %s: %v", shard.RunID, err)
+		}
+	}
+
+	// A malformed file fails the import with its name and line
+	files = append(files, write("broken.xml", "<testsuite name=\"math\">\n<testcase name=\"adds\">\n</testsuite>\n"))
+	if _, err := ImportJUnit(filepath.Join(dir, "broken"), files, "3pio import"); err == nil || !strings.Contains(err.Error(), "broken.xml:3: malformed XML") {
+		t.Errorf("Expected the malformed file's line, got %v", err)
+	}
+}
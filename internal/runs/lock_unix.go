@@ -0,0 +1,25 @@
+//go:build !windows
+
+package runs
+
+import (
+	"os"
+	"syscall"
+)
+
+// lock takes an exclusive advisory lock on path, creating the file if needed, and
+// returns the function that releases it
+func lock(path string) (func(), error) {
+	file, err := os.OpenFile(path, os.O_CREATE|os.O_RDWR, 0644)
+	if err != nil {
+		return nil, err
+	}
+	if err := syscall.Flock(int(file.Fd()), syscall.LOCK_EX); err != nil {
+		_ = file.Close()
+		return nil, err
+	}
+	return func() {
+		_ = syscall.Flock(int(file.Fd()), syscall.LOCK_UN)
+		_ = file.Close()
+	}, nil
+}
@@ -0,0 +1,623 @@
+package runs
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"slices"
+	"sort"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/zk/3pio/internal/report"
+)
+
+// ShardsDir is where a merged run keeps a copy of the run directory of each shard
+const ShardsDir = "shards"
+
+// statusRank orders results from worst to best. When shards disagree the merged run
+// keeps the worst; the results of tests that didn't run come last, so any shard that
+// ran a test decides its result.
+var statusRank = []report.TestStatus{
+	report.TestStatusError,
+	report.TestStatusFail,
+	report.TestStatusInterrupted,
+	report.TestStatusXPass,
+	report.TestStatusQuarantinedFail,
+	report.TestStatusFlaky,
+	report.TestStatusWarn,
+	report.TestStatusPass,
+	report.TestStatusXFail,
+	report.TestStatusBench,
+	report.TestStatusSkip,
+	report.TestStatusNoTests,
+	report.TestStatusRunning,
+	report.TestStatusPending,
+}
+
+// Shard is a run directory to merge, such as one CI machine's part of the test suite
+type Shard struct {
+	Dir     string // Run directory
+	Summary *report.RunSummary
+}
+
+// LoadShard reads the summary.json of a run directory
+func LoadShard(dir string) (Shard, error) {
+	summary, err := report.ReadSummary(dir)
+	if err != nil {
+		return Shard{}, fmt.Errorf("%s is not a run directory with a %s: %w", dir, report.SummaryFileName, err)
+	}
+	return Shard{Dir: dir, Summary: summary}, nil
+}
+
+// merger builds a merged summary one shard at a time
+type merger struct {
+	summary   *report.RunSummary
+	groups    map[string]*report.SummaryGroup // By group ID
+	order     []*report.SummaryGroup          // In the order they were first seen
+	tests     map[string]map[string]int       // Index of each test in its group's test cases, by group and test ID
+	results   map[string]map[string]string    // Status of each test by shard run ID, by test ID
+	conflicts []string                        // IDs of the tests the shards disagree on
+}
+
+// Merge combines the summaries of the shards of a test run into one, with the run ID
+// runID. Groups that more than one shard ran are joined and their test cases unioned
+// by ID. A test that ran on several shards is counted once: as a duplicate when the
+// results agree, and as a conflict with its worst result when they don't. A shard that
+// skipped a test another shard ran, as partitioned runs do, leaves the result to that
+// shard. Report paths point into ShardsDir, where WriteMerged copies the shards.
+func Merge(runID string, shards []Shard) (*report.RunSummary, error) {
+	if len(shards) == 0 {
+		return nil, fmt.Errorf("no runs to merge")
+	}
+	m := &merger{
+		summary: &report.RunSummary{
+			SchemaVersion: report.SummarySchemaVersion,
+			RunID:         runID,
+			Cwd:           shards[0].Summary.Cwd,
+			Status:        "COMPLETED",
+			Groups:        []report.SummaryGroup{},
+			Merge:         &report.SummaryMerge{Shards: []report.SummaryShard{}, Conflicts: []report.SummaryConflict{}},
+		},
+		groups:  make(map[string]*report.SummaryGroup),
+		tests:   make(map[string]map[string]int),
+		results: make(map[string]map[string]string),
+	}
+
+	var commands, runners []string
+	for _, shard := range shards {
+		s := shard.Summary
+		for _, seen := range m.summary.Merge.Shards {
+			if seen.RunID == s.RunID {
+				return nil, fmt.Errorf("run %s is given twice", s.RunID)
+			}
+		}
+		m.addShard(s)
+		commands = appendUnique(commands, s.Command)
+		runners = appendUnique(runners, s.Runner)
+		for _, group := range s.Groups {
+			m.addGroup(s, group)
+		}
+	}
+	m.summary.Command = strings.Join(commands, "; ")
+	m.summary.Runner = strings.Join(runners, ", ")
+	if err := m.checkShards(); err != nil {
+		return nil, err
+	}
+	m.finish()
+	return m.summary, nil
+}
+
+// checkShards lists the shards of a sharded suite that no run covered. Runs that don't
+// say which shard they ran are left out; those that do must split the suite the same way.
+func (m *merger) checkShards() error {
+	var first *report.SummaryShard
+	covered := make(map[int]bool)
+	for i := range m.summary.Merge.Shards {
+		shard := &m.summary.Merge.Shards[i]
+		if shard.Shard == nil {
+			continue
+		}
+		if first == nil {
+			first = shard
+		} else if shard.Shard.Total != first.Shard.Total {
+			return fmt.Errorf("run %s is shard %s, but run %s is shard %s", first.RunID, first.Shard, shard.RunID, shard.Shard)
+		}
+		covered[shard.Shard.Index] = true
+	}
+	if first == nil {
+		return nil
+	}
+	for index := 1; index <= first.Shard.Total; index++ {
+		if !covered[index] {
+			m.summary.Merge.MissingShards = append(m.summary.Merge.MissingShards, index)
+		}
+	}
+	return nil
+}
+
+// addShard records a shard's run and its times
+func (m *merger) addShard(s *report.RunSummary) {
+	m.summary.Merge.Shards = append(m.summary.Merge.Shards, report.SummaryShard{
+		RunID:      s.RunID,
+		Command:    s.Command,
+		StartTime:  s.StartTime,
+		EndTime:    s.EndTime,
+		ExitCode:   s.ExitCode,
+		ExitReason: s.ExitReason,
+		Totals:     s.Totals,
+		Shard:      s.Shard,
+	})
+	// Shards of a pytest run usually collect the same files, so their collection
+	// errors are the same ones
+	m.summary.Totals.CollectionErrors = max(m.summary.Totals.CollectionErrors, s.Totals.CollectionErrors)
+	if !s.StartTime.IsZero() && !s.EndTime.IsZero() {
+		wallMs := float64(s.EndTime.Sub(s.StartTime)) / float64(time.Millisecond)
+		m.summary.Merge.WallMs = max(m.summary.Merge.WallMs, wallMs)
+		m.summary.Merge.ShardMs += wallMs
+	}
+	if !s.StartTime.IsZero() && (m.summary.StartTime.IsZero() || s.StartTime.Before(m.summary.StartTime)) {
+		m.summary.StartTime = s.StartTime
+	}
+	if s.EndTime.After(m.summary.EndTime) {
+		m.summary.EndTime = s.EndTime
+	}
+	if s.Status != "COMPLETED" && m.summary.Status == "COMPLETED" {
+		m.summary.Status = s.Status
+	}
+}
+
+// addGroup joins a shard's group with the same group of the shards before it
+func (m *merger) addGroup(s *report.RunSummary, group report.SummaryGroup) {
+	runID := s.RunID
+	key := group.ID
+	if key == "" {
+		key = report.GenerateGroupIDFromPath(group.Path)
+	}
+	merged, exists := m.groups[key]
+	if !exists {
+		merged = &report.SummaryGroup{
+			ID:          group.ID,
+			Name:        group.Name,
+			Path:        group.Path,
+			Status:      group.Status,
+			DurationMs:  group.DurationMs,
+			Report:      shardPath(runID, group.Report),
+			Error:       group.Error,
+			FailureKind: group.FailureKind,
+			SkipReason:  group.SkipReason,
+			TestCases:   []report.SummaryTestCase{},
+		}
+		m.groups[key] = merged
+		m.order = append(m.order, merged)
+		m.tests[key] = make(map[string]int)
+	} else {
+		merged.DurationMs += group.DurationMs
+		if worse(group.Status, merged.Status) {
+			merged.Status = group.Status
+			merged.Error, merged.FailureKind, merged.SkipReason = group.Error, group.FailureKind, group.SkipReason
+			merged.Report = shardPath(runID, group.Report)
+		}
+	}
+
+	for _, tc := range group.TestCases {
+		tc.Report = shardPath(runID, tc.Report)
+		tc.TestID = report.SummaryTestID(s, group, tc)
+		id := tc.ID
+		if id == "" {
+			id = report.GenerateTestCaseID(tc.Name, append([]string(nil), group.Path...))
+		}
+		m.addTestCase(key, merged, id, runID, tc)
+	}
+}
+
+// addTestCase adds a shard's result of a test to its merged group
+func (m *merger) addTestCase(key string, group *report.SummaryGroup, id, runID string, tc report.SummaryTestCase) {
+	if m.results[id] == nil {
+		m.results[id] = make(map[string]string)
+	}
+	m.results[id][runID] = tc.Status
+
+	i, exists := m.tests[key][id]
+	if !exists {
+		m.tests[key][id] = len(group.TestCases)
+		group.TestCases = append(group.TestCases, tc)
+		return
+	}
+	current := &group.TestCases[i]
+	switch {
+	case !ran(tc.Status):
+		// Another shard ran it, or none did
+	case !ran(current.Status):
+		*current = tc
+	case tc.Status == current.Status:
+		m.summary.Merge.Duplicates++
+	default:
+		if !slices.Contains(m.conflicts, id) {
+			m.conflicts = append(m.conflicts, id)
+		}
+		if worse(tc.Status, current.Status) {
+			*current = tc
+		}
+	}
+}
+
+// finish orders the groups depth-first, lists the conflicts and recomputes the totals
+// and the exit code
+func (m *merger) finish() {
+	sort.SliceStable(m.order, func(i, j int) bool {
+		return slices.Compare(m.order[i].Path, m.order[j].Path) < 0
+	})
+	errored := false
+	conflictPaths := make(map[string]report.SummaryConflict)
+	for _, group := range m.order {
+		m.summary.Groups = append(m.summary.Groups, *group)
+		if group.Status == string(report.TestStatusError) {
+			errored = true
+		}
+		for _, tc := range group.TestCases {
+			m.countTestCase(tc)
+			id := tc.ID
+			if id == "" {
+				id = report.GenerateTestCaseID(tc.Name, append([]string(nil), group.Path...))
+			}
+			conflictPaths[id] = report.SummaryConflict{ID: id, Path: group.Path, Name: tc.Name}
+		}
+	}
+	for _, id := range m.conflicts {
+		conflict := conflictPaths[id]
+		conflict.Results = m.results[id]
+		m.summary.Merge.Conflicts = append(m.summary.Merge.Conflicts, conflict)
+	}
+
+	// Missing shards' tests aren't in the merged run, whatever the results of the others
+	if len(m.summary.Merge.MissingShards) > 0 && m.summary.Status == "COMPLETED" {
+		m.summary.Status = "INCOMPLETE"
+	}
+
+	// A shard that couldn't build or run its tests decides the exit code, then failures,
+	// then missing shards
+	for _, shard := range m.summary.Merge.Shards {
+		switch shard.ExitReason {
+		case report.ExitReasonOK, report.ExitReasonTestsFailed, report.ExitReasonNoTestsFound, "":
+		default:
+			if m.summary.ExitReason == "" {
+				m.summary.ExitCode, m.summary.ExitReason = shard.ExitCode, shard.ExitReason
+			}
+		}
+	}
+	if m.summary.ExitReason != "" {
+		return
+	}
+	totals := m.summary.Totals
+	switch {
+	case totals.Failed > 0 || errored:
+		m.summary.ExitCode, m.summary.ExitReason = 1, report.ExitReasonTestsFailed
+	case len(m.summary.Merge.MissingShards) > 0:
+		m.summary.ExitCode, m.summary.ExitReason = 1, report.ExitReasonIncomplete
+	case totals.Total == 0 && totals.Benchmarks == 0:
+		// As when every shard's filter matched no test
+		m.summary.ExitCode, m.summary.ExitReason = m.summary.Merge.Shards[0].ExitCode, m.summary.Merge.Shards[0].ExitReason
+	default:
+		m.summary.ExitCode, m.summary.ExitReason = 0, report.ExitReasonOK
+	}
+}
+
+// countTestCase adds a merged test to the totals, as for the summary of a run
+func (m *merger) countTestCase(tc report.SummaryTestCase) {
+	totals := &m.summary.Totals
+	if tc.Status != string(report.TestStatusBench) {
+		totals.Total++
+	}
+	switch report.TestStatus(tc.Status) {
+	case report.TestStatusPass:
+		totals.Passed++
+	case report.TestStatusFail:
+		totals.Failed++
+	case report.TestStatusSkip:
+		totals.Skipped++
+	case report.TestStatusQuarantinedFail:
+		totals.Quarantined++
+	case report.TestStatusFlaky:
+		totals.Flaky++
+	case report.TestStatusError:
+		totals.Errors++
+	case report.TestStatusBench:
+		totals.Benchmarks++
+	}
+}
+
+// ran reports whether a status is the result of running the test
+func ran(status string) bool {
+	switch report.TestStatus(status) {
+	case report.TestStatusSkip, report.TestStatusNoTests, report.TestStatusRunning, report.TestStatusPending, "":
+		return false
+	}
+	return true
+}
+
+// worse reports whether status a is a worse result than b
+func worse(a, b string) bool {
+	return rank(a) < rank(b)
+}
+
+// rank returns the position of a status in statusRank, unknown ones after the rest
+func rank(status string) int {
+	if i := slices.Index(statusRank, report.TestStatus(status)); i >= 0 {
+		return i
+	}
+	return len(statusRank)
+}
+
+// shardPath returns the path of a shard's file in the merged run, for a path relative
+// to the shard's run directory
+func shardPath(runID, path string) string {
+	if path == "" {
+		return ""
+	}
+	return "./" + ShardsDir + "/" + runID + "/" + strings.TrimPrefix(filepath.ToSlash(path), "./")
+}
+
+// MissingShardsText describes the shards a merged run is missing, e.g. "shards 3
+// and 4 of 4", or "" when it has them all
+func MissingShardsText(summary *report.RunSummary) string {
+	if summary.Merge == nil || len(summary.Merge.MissingShards) == 0 {
+		return ""
+	}
+	missing := summary.Merge.MissingShards
+	total := 0
+	for _, shard := range summary.Merge.Shards {
+		if shard.Shard != nil {
+			total = shard.Shard.Total
+		}
+	}
+	if len(missing) == 1 {
+		return fmt.Sprintf("shard %d of %d", missing[0], total)
+	}
+	indexes := make([]string, len(missing))
+	for i, index := range missing {
+		indexes[i] = strconv.Itoa(index)
+	}
+	return fmt.Sprintf("shards %s and %s of %d", strings.Join(indexes[:len(indexes)-1], ", "), indexes[len(indexes)-1], total)
+}
+
+// appendUnique appends s to values unless it is empty or already there
+func appendUnique(values []string, s string) []string {
+	if s == "" || slices.Contains(values, s) {
+		return values
+	}
+	return append(values, s)
+}
+
+// WriteMerged writes a merged run to outDir, which must be new or empty: summary.json,
+// test-run.md, and a copy of every shard's run directory in ShardsDir
+func WriteMerged(outDir string, shards []Shard, summary *report.RunSummary) error {
+	if entries, err := os.ReadDir(outDir); err == nil && len(entries) > 0 {
+		return fmt.Errorf("%s is not empty", outDir)
+	}
+	absOut, err := filepath.Abs(outDir)
+	if err != nil {
+		return err
+	}
+	for _, shard := range shards {
+		absShard, err := filepath.Abs(shard.Dir)
+		if err != nil {
+			return err
+		}
+		if absOut == absShard || strings.HasPrefix(absOut, absShard+string(filepath.Separator)) {
+			return fmt.Errorf("%s is inside the run directory %s", outDir, shard.Dir)
+		}
+	}
+
+	for _, shard := range shards {
+		if err := copyDir(shard.Dir, filepath.Join(outDir, ShardsDir, shard.Summary.RunID)); err != nil {
+			return fmt.Errorf("failed to copy run %s: %w", shard.Summary.RunID, err)
+		}
+	}
+	data, err := json.MarshalIndent(summary, "", "  ")
+	if err != nil {
+		return err
+	}
+	if err := os.WriteFile(filepath.Join(outDir, report.SummaryFileName), append(data, '\n'), 0644); err != nil {
+		return err
+	}
+	return os.WriteFile(filepath.Join(outDir, "test-run.md"), []byte(mergedReport(summary)), 0644)
+}
+
+// copyDir copies the directories and regular files under src to dst
+func copyDir(src, dst string) error {
+	return filepath.WalkDir(src, func(path string, d os.DirEntry, err error) error {
+		if err != nil {
+			return err
+		}
+		rel, err := filepath.Rel(src, path)
+		if err != nil {
+			return err
+		}
+		target := filepath.Join(dst, rel)
+		if d.IsDir() {
+			return os.MkdirAll(target, 0755)
+		}
+		if !d.Type().IsRegular() {
+			return nil
+		}
+		return copyFile(path, target)
+	})
+}
+
+// copyFile copies a regular file
+func copyFile(src, dst string) error {
+	in, err := os.Open(src)
+	if err != nil {
+		return err
+	}
+	defer func() { _ = in.Close() }()
+	out, err := os.Create(dst)
+	if err != nil {
+		return err
+	}
+	if _, err := io.Copy(out, in); err != nil {
+		_ = out.Close()
+		return err
+	}
+	return out.Close()
+}
+
+// mergedReport renders the test-run.md of a merged run
+func mergedReport(summary *report.RunSummary) string {
+	sb := &strings.Builder{}
+	var runIDs []string
+	for _, shard := range summary.Merge.Shards {
+		runIDs = append(runIDs, shard.RunID)
+	}
+
+	sb.WriteString("---\n")
+	fmt.Fprintf(sb, "run_id: %s\n", summary.RunID)
+	fmt.Fprintf(sb, "detected_runner: %s\n", summary.Runner)
+	fmt.Fprintf(sb, "status: %s\n", summary.Status)
+	fmt.Fprintf(sb, "exit_reason: %s\n", summary.ExitReason)
+	fmt.Fprintf(sb, "merged_from: %s\n", strings.Join(runIDs, ", "))
+	sb.WriteString("---\n\n")
+
+	sb.WriteString("# 3pio Merged Test Run\n\n")
+	fmt.Fprintf(sb, "- Test command: `%s`\n", summary.Command)
+	fmt.Fprintf(sb, "- Merged from %d runs, copied to `./%s`\n", len(runIDs), ShardsDir)
+	if len(summary.Merge.MissingShards) > 0 {
+		fmt.Fprintf(sb, "- **Incomplete: %s missing**; their tests aren't in this run\n", MissingShardsText(summary))
+	}
+	if n := len(summary.Merge.Conflicts); n > 0 {
+		fmt.Fprintf(sb, "- **%d tests had different results on different shards**; each has its worst result\n", n)
+	}
+	sb.WriteString("\n")
+
+	if len(summary.Merge.Conflicts) > 0 {
+		sb.WriteString("## Conflicting results\n\n")
+		for _, conflict := range summary.Merge.Conflicts {
+			shardIDs := make([]string, 0, len(conflict.Results))
+			for runID := range conflict.Results {
+				shardIDs = append(shardIDs, runID)
+			}
+			sort.Strings(shardIDs)
+			results := make([]string, 0, len(shardIDs))
+			for _, runID := range shardIDs {
+				results = append(results, fmt.Sprintf("%s on %s", conflict.Results[runID], runID))
+			}
+			path := append(append([]string(nil), conflict.Path...), conflict.Name)
+			fmt.Fprintf(sb, "- `%s`: %s\n", report.BuildHierarchicalPathFromSlice(path), strings.Join(results, ", "))
+		}
+		sb.WriteString("\n")
+	}
+
+	var failures []string
+	for _, group := range summary.Groups {
+		failedTests := 0
+		for _, tc := range group.TestCases {
+			if tc.Status != string(report.TestStatusFail) && tc.Status != string(report.TestStatusError) {
+				continue
+			}
+			failedTests++
+			path := append(append([]string(nil), group.Path...), tc.Name)
+			failures = append(failures, failureLine(path, tc.FailureMessage, tc.Report))
+		}
+		if failedTests == 0 && group.Status == string(report.TestStatusError) {
+			failures = append(failures, failureLine(group.Path, group.Error, group.Report))
+		}
+	}
+	if len(failures) > 0 {
+		sb.WriteString("## Failures\n\n")
+		for _, line := range failures {
+			sb.WriteString(line)
+		}
+		sb.WriteString("\n")
+	}
+
+	totals := summary.Totals
+	sb.WriteString("## Summary\n\n")
+	fmt.Fprintf(sb, "- Total test cases: %d\n", totals.Total)
+	fmt.Fprintf(sb, "- Test cases passed: %d\n", totals.Passed)
+	fmt.Fprintf(sb, "- Test cases failed: %d\n", totals.Failed)
+	if totals.Errors > 0 {
+		fmt.Fprintf(sb, "- Test cases errored: %d\n", totals.Errors)
+	}
+	fmt.Fprintf(sb, "- Test cases skipped: %d\n", totals.Skipped)
+	if totals.Quarantined > 0 {
+		fmt.Fprintf(sb, "- Test cases failed while quarantined: %d\n", totals.Quarantined)
+	}
+	if totals.Flaky > 0 {
+		fmt.Fprintf(sb, "- Test cases flaky: %d\n", totals.Flaky)
+	}
+	if totals.CollectionErrors > 0 {
+		fmt.Fprintf(sb, "- Test files that failed to collect: %d\n", totals.CollectionErrors)
+	}
+	if totals.Benchmarks > 0 {
+		fmt.Fprintf(sb, "- Benchmarks: %d\n", totals.Benchmarks)
+	}
+	if summary.Merge.Duplicates > 0 {
+		fmt.Fprintf(sb, "- Tests run on more than one shard with the same result: %d\n", summary.Merge.Duplicates)
+	}
+	fmt.Fprintf(sb, "- Total duration: %.2fs (longest shard), %.2fs across shards\n\n", summary.Merge.WallMs/1000, summary.Merge.ShardMs/1000)
+
+	sb.WriteString("## Shards\n\n")
+	sb.WriteString("| Run | Shard | Exit reason | Passed | Failed | Skipped | Duration | Report |\n")
+	sb.WriteString("|-----|-------|-------------|--------|--------|---------|----------|--------|\n")
+	for _, shard := range summary.Merge.Shards {
+		duration := "-"
+		if !shard.StartTime.IsZero() && !shard.EndTime.IsZero() {
+			duration = fmt.Sprintf("%.2fs", shard.EndTime.Sub(shard.StartTime).Seconds())
+		}
+		index := "-"
+		if shard.Shard != nil {
+			index = shard.Shard.String()
+		}
+		fmt.Fprintf(sb, "| %s | %s | %s | %d | %d | %d | %s | [test-run.md](%s) |\n", shard.RunID, index, shard.ExitReason,
+			shard.Totals.Passed, shard.Totals.Failed, shard.Totals.Skipped, duration, shardPath(shard.RunID, "test-run.md"))
+	}
+	sb.WriteString("\n")
+
+	sb.WriteString("## Test group results\n\n")
+	sb.WriteString("| Status | Name | Tests | Duration | Report |\n")
+	sb.WriteString("|--------|------|-------|----------|--------|\n")
+	for _, group := range summary.Groups {
+		if len(group.Path) != 1 {
+			continue
+		}
+		passed, failed, skipped := 0, 0, 0
+		for _, g := range summary.Groups {
+			if !slices.Equal(g.Path[:min(len(g.Path), 1)], group.Path) {
+				continue
+			}
+			for _, tc := range g.TestCases {
+				switch report.TestStatus(tc.Status) {
+				case report.TestStatusPass:
+					passed++
+				case report.TestStatusFail:
+					failed++
+				case report.TestStatusSkip:
+					skipped++
+				}
+			}
+		}
+		reportLink := "-"
+		if group.Report != "" {
+			reportLink = fmt.Sprintf("[report](%s)", report.NormalizeFilePath(group.Report))
+		}
+		fmt.Fprintf(sb, "| %s | %s | %d passed, %d failed, %d skipped | %.2fs | %s |\n", group.Status,
+			filepath.Base(group.Name), passed, failed, skipped, group.DurationMs/1000, reportLink)
+	}
+	return sb.String()
+}
+
+// failureLine is a line of the failures of a merged test-run.md
+func failureLine(path []string, message, reportPath string) string {
+	line := fmt.Sprintf("- `%s`", report.BuildHierarchicalPathFromSlice(path))
+	if message = strings.TrimSpace(message); message != "" {
+		line += ": " + strings.SplitN(message, "\n", 2)[0]
+	}
+	if reportPath != "" {
+		line += fmt.Sprintf(" ([report](%s))", report.NormalizeFilePath(reportPath))
+	}
+	return line + "\n"
+}
@@ -0,0 +1,184 @@
+package runs
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/zk/3pio/internal/report"
+	"github.com/zk/3pio/internal/runinfo"
+)
+
+// shard returns a shard whose run took the given time and ran the given groups
+func shard(runID string, took time.Duration, groups ...report.SummaryGroup) Shard {
+	start := time.Date(2026, 10, 14, 9, 0, 0, 0, time.UTC)
+	return Shard{Summary: &report.RunSummary{
+		RunID:      runID,
+		Command:    "cargo nextest run",
+		Runner:     "cargo nextest",
+		StartTime:  start,
+		EndTime:    start.Add(took),
+		Status:     "COMPLETED",
+		ExitReason: report.ExitReasonOK,
+		Groups:     groups,
+	}}
+}
+
+func TestMerge(t *testing.T) {
+	shards := []Shard{
+		shard("shard-1", 40*time.Second,
+			diffGroup("my-crate", nil),
+			diffGroup("my-crate/tests", []report.SummaryTestCase{
+				{Name: "test_add", Status: "PASS", Report: "./reports/my_crate/tests/index.md"},
+				{Name: "test_flaky", Status: "PASS"},
+				{Name: "test_shared", Status: "PASS"},
+				{Name: "test_sub", Status: "SKIP"},
+			})),
+		shard("shard-2", 60*time.Second,
+			diffGroup("my-crate/tests", []report.SummaryTestCase{
+				{Name: "test_flaky", Status: "FAIL", FailureMessage: "timed out", Report: "./reports/my_crate/tests/index.md"},
+				{Name: "test_shared", Status: "PASS"},
+				{Name: "test_sub", Status: "PASS"},
+			}),
+			diffGroup("other-crate", []report.SummaryTestCase{
+				{Name: "test_ignored", Status: "SKIP"},
+			})),
+	}
+
+	merged, err := Merge("merged", shards)
+	if err != nil {
+		t.Fatalf("Merge failed: %v", err)
+	}
+
+	// test_sub was skipped where it didn't run, so it isn't a conflict
+	if want := (report.SummaryCounts{Total: 5, Passed: 3, Failed: 1, Skipped: 1}); merged.Totals != want {
+		t.Errorf("Expected totals %+v, got %+v", want, merged.Totals)
+	}
+	if merged.Merge.Duplicates != 1 {
+		t.Errorf("Expected test_shared to be a duplicate, got %d duplicates", merged.Merge.Duplicates)
+	}
+	if len(merged.Merge.Conflicts) != 1 {
+		t.Fatalf("Expected test_flaky to be the only conflict, got %+v", merged.Merge.Conflicts)
+	}
+	conflict := merged.Merge.Conflicts[0]
+	if conflict.Name != "test_flaky" || conflict.Results["shard-1"] != "PASS" || conflict.Results["shard-2"] != "FAIL" {
+		t.Errorf("Unexpected conflict %+v", conflict)
+	}
+	if merged.ExitCode != 1 || merged.ExitReason != report.ExitReasonTestsFailed {
+		t.Errorf("Expected exit code 1 and tests_failed, got %d and %s", merged.ExitCode, merged.ExitReason)
+	}
+	if merged.Merge.WallMs != 60000 || merged.Merge.ShardMs != 100000 {
+		t.Errorf("Expected 60s of wall time and 100s across shards, got %.0fms and %.0fms", merged.Merge.WallMs, merged.Merge.ShardMs)
+	}
+
+	// Groups are depth-first, and keep the worst result with its shard's report
+	var paths []string
+	for _, group := range merged.Groups {
+		paths = append(paths, strings.Join(group.Path, "/"))
+	}
+	if strings.Join(paths, " ") != "my-crate my-crate/tests other-crate" {
+		t.Errorf("Expected groups in depth-first order, got %v", paths)
+	}
+	for _, tc := range merged.Groups[1].TestCases {
+		if tc.Name == "test_flaky" && (tc.Status != "FAIL" || tc.Report != "./shards/shard-2/reports/my_crate/tests/index.md") {
+			t.Errorf("Expected test_flaky to fail with shard-2's report, got %+v", tc)
+		}
+	}
+
+	if _, err := Merge("merged", []Shard{shards[0], shards[0]}); err == nil {
+		t.Error("Expected an error for a run given twice")
+	}
+}
+
+func TestMerge_ExitCode(t *testing.T) {
+	passing := shard("shard-1", time.Second, diffGroup("my-crate", []report.SummaryTestCase{{Name: "test_add", Status: "PASS"}}))
+	merged, err := Merge("merged", []Shard{passing})
+	if err != nil || merged.ExitCode != 0 || merged.ExitReason != report.ExitReasonOK {
+		t.Errorf("Expected a passing merge to exit 0, got %+v (%v)", merged, err)
+	}
+
+	// A shard that didn't build decides the exit code, even when the others passed
+	broken := shard("shard-2", time.Second)
+	broken.Summary.ExitCode, broken.Summary.ExitReason = 101, report.ExitReasonBuildFailed
+	merged, err = Merge("merged", []Shard{passing, broken})
+	if err != nil || merged.ExitCode != 101 || merged.ExitReason != report.ExitReasonBuildFailed {
+		t.Errorf("Expected build_failed with exit code 101, got %d and %s (%v)", merged.ExitCode, merged.ExitReason, err)
+	}
+}
+
+func TestMerge_Shards(t *testing.T) {
+	sharded := func(runID string, index, total int) Shard {
+		s := shard(runID, time.Second, diffGroup(runID, []report.SummaryTestCase{{Name: "test_" + runID, Status: "PASS"}}))
+		s.Summary.Shard = &runinfo.Shard{Index: index, Total: total}
+		return s
+	}
+
+	merged, err := Merge("merged", []Shard{sharded("shard-1", 1, 3), sharded("shard-3", 3, 3)})
+	if err != nil {
+		t.Fatalf("Merge failed: %v", err)
+	}
+	if len(merged.Merge.MissingShards) != 1 || merged.Merge.MissingShards[0] != 2 {
+		t.Errorf("Expected shard 2 to be missing, got %v", merged.Merge.MissingShards)
+	}
+	if merged.Status != "INCOMPLETE" || merged.ExitCode != 1 || merged.ExitReason != report.ExitReasonIncomplete {
+		t.Errorf("Expected an incomplete run exiting 1, got %s with %d and %s", merged.Status, merged.ExitCode, merged.ExitReason)
+	}
+	if text := MissingShardsText(merged); text != "shard 2 of 3" {
+		t.Errorf("Expected shard 2 of 3 to be missing, got %q", text)
+	}
+	if content := mergedReport(merged); !strings.Contains(content, "**Incomplete: shard 2 of 3 missing**") || !strings.Contains(content, "| shard-3 | 3/3 |") {
+		t.Errorf("Expected the missing shard and the shard of each run in test-run.md, got:\n%s", content)
+	}
+
+	merged, err = Merge("merged", []Shard{sharded("shard-1", 1, 3), sharded("shard-2", 2, 3), sharded("shard-3", 3, 3)})
+	if err != nil || len(merged.Merge.MissingShards) != 0 || merged.Status != "COMPLETED" || merged.ExitReason != report.ExitReasonOK {
+		t.Errorf("Expected every shard to complete the run, got %+v (%v)", merged, err)
+	}
+
+	if _, err := Merge("merged", []Shard{sharded("shard-1", 1, 3), sharded("shard-2", 2, 4)}); err == nil {
+		t.Error("Expected an error for shards of different splits")
+	}
+}
+
+func TestWriteMerged(t *testing.T) {
+	shardDir := t.TempDir()
+	if err := os.MkdirAll(filepath.Join(shardDir, "reports"), 0755); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(filepath.Join(shardDir, "reports", "index.md"), []byte("# Group\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	s := shard("shard-1", time.Second, diffGroup("my-crate", []report.SummaryTestCase{
+		{Name: "test_sub", Status: "FAIL", FailureMessage: "assertion failed", Report: "./reports/index.md"},
+	}))
+	s.Dir = shardDir
+	merged, err := Merge("merged", []Shard{s})
+	if err != nil {
+		t.Fatalf("Merge failed: %v", err)
+	}
+
+	outDir := filepath.Join(t.TempDir(), "merged")
+	if err := WriteMerged(outDir, []Shard{s}, merged); err != nil {
+		t.Fatalf("WriteMerged failed: %v", err)
+	}
+	if _, err := os.Stat(filepath.Join(outDir, "shards", "shard-1", "reports", "index.md")); err != nil {
+		t.Errorf("Expected the shard's reports to be copied: %v", err)
+	}
+	summary, err := report.ReadSummary(outDir)
+	if err != nil || summary.Merge == nil || summary.Totals.Failed != 1 {
+		t.Errorf("Expected the merged summary.json, got %+v (%v)", summary, err)
+	}
+	markdown, _ := os.ReadFile(filepath.Join(outDir, "test-run.md"))
+	if !strings.Contains(string(markdown), "- `my-crate → test_sub`: assertion failed ([report](shards/shard-1/reports/index.md))") {
+		t.Errorf("Expected the failure with its shard's report in test-run.md:\n%s", markdown)
+	}
+
+	if err := WriteMerged(outDir, []Shard{s}, merged); err == nil {
+		t.Error("Expected an error for an output directory that isn't empty")
+	}
+	if err := WriteMerged(filepath.Join(shardDir, "merged"), []Shard{s}, merged); err == nil {
+		t.Error("Expected an error for an output directory inside a run")
+	}
+}
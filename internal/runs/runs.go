@@ -0,0 +1,177 @@
+// Package runs keeps the history of the 3pio runs of a project in .3pio/runs: a
+// pointer to the latest finished run and an index with one line per finished run.
+package runs
+
+import (
+	"bufio"
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+	"runtime"
+	"strings"
+	"time"
+
+	"github.com/zk/3pio/internal/report"
+	"github.com/zk/3pio/internal/runinfo"
+)
+
+const (
+	// IndexFileName is the index of finished runs in the runs directory, one JSON
+	// object per line, oldest first
+	IndexFileName = "index.jsonl"
+
+	// LatestName is the pointer to the latest finished run in the runs directory. It is
+	// a symlink to the run directory, or on Windows, where symlinks need privileges, a
+	// text file holding the run ID.
+	LatestName = "latest"
+
+	// lockFileName is locked while the index and the pointer are updated
+	lockFileName = ".lock"
+)
+
+// Entry is one line of index.jsonl
+type Entry struct {
+	RunID     string               `json:"run_id"`
+	Command   string               `json:"command"`         // Test command as given to 3pio
+	StartTime time.Time            `json:"start_time"`      // RFC 3339, UTC
+	EndTime   time.Time            `json:"end_time"`        // RFC 3339, UTC
+	Status    string               `json:"status"`          // COMPLETED, ERRORED or INTERRUPTED, as in test-run.md
+	ExitCode  int                  `json:"exit_code"`       // Exit code 3pio exited with
+	Cargo     *runinfo.Cargo       `json:"cargo,omitempty"` // Profile, features and target of a cargo run
+	Totals    report.SummaryCounts `json:"totals"`
+}
+
+// EntryFromSummary builds the index entry of a run from its summary.json
+func EntryFromSummary(summary *report.RunSummary) Entry {
+	return Entry{
+		RunID:     summary.RunID,
+		Command:   summary.Command,
+		StartTime: summary.StartTime,
+		EndTime:   summary.EndTime,
+		Status:    summary.Status,
+		ExitCode:  summary.ExitCode,
+		Cargo:     summary.Cargo,
+		Totals:    summary.Totals,
+	}
+}
+
+// Record appends a finished run to the index and points latest at it. Other 3pio
+// processes in the same project wait for it, so concurrent runs don't interleave
+// their lines or leave latest pointing at a half-written file.
+func Record(runsDir string, entry Entry) error {
+	unlock, err := lock(filepath.Join(runsDir, lockFileName))
+	if err != nil {
+		return fmt.Errorf("failed to lock %s: %w", runsDir, err)
+	}
+	defer unlock()
+
+	line, err := json.Marshal(entry)
+	if err != nil {
+		return err
+	}
+	index, err := os.OpenFile(filepath.Join(runsDir, IndexFileName), os.O_CREATE|os.O_APPEND|os.O_WRONLY, 0644)
+	if err != nil {
+		return fmt.Errorf("failed to open %s: %w", IndexFileName, err)
+	}
+	if _, err := index.Write(append(line, '\n')); err != nil {
+		_ = index.Close()
+		return fmt.Errorf("failed to write %s: %w", IndexFileName, err)
+	}
+	if err := index.Close(); err != nil {
+		return fmt.Errorf("failed to write %s: %w", IndexFileName, err)
+	}
+
+	return setLatest(runsDir, entry.RunID)
+}
+
+// setLatest points latest at a run. The pointer is made under a temporary name and
+// renamed over the old one, so readers always find a whole pointer.
+func setLatest(runsDir, runID string) error {
+	latest := filepath.Join(runsDir, LatestName)
+	tmp := filepath.Join(runsDir, fmt.Sprintf(".%s.%d.tmp", LatestName, os.Getpid()))
+	_ = os.Remove(tmp)
+
+	if runtime.GOOS == "windows" || os.Symlink(runID, tmp) != nil {
+		// Fall back to a text file where symlinks aren't available
+		if err := os.WriteFile(tmp, []byte(runID+"\n"), 0644); err != nil {
+			return fmt.Errorf("failed to write %s: %w", LatestName, err)
+		}
+	}
+	if err := os.Rename(tmp, latest); err != nil {
+		_ = os.Remove(tmp)
+		return fmt.Errorf("failed to update %s: %w", LatestName, err)
+	}
+	return nil
+}
+
+// Latest returns the ID of the latest run in runsDir, or "" if there is none. It
+// follows the latest pointer, and falls back to the newest run directory when the
+// pointer is missing or its run was deleted, e.g. for runs of older 3pio versions.
+func Latest(runsDir string) (string, error) {
+	latest := filepath.Join(runsDir, LatestName)
+	var runID string
+	if target, err := os.Readlink(latest); err == nil {
+		runID = filepath.Base(target)
+	} else if data, err := os.ReadFile(latest); err == nil {
+		runID = strings.TrimSpace(string(data))
+	}
+	if runID != "" {
+		if info, err := os.Stat(filepath.Join(runsDir, runID)); err == nil && info.IsDir() {
+			return runID, nil
+		}
+	}
+
+	runIDs, err := List(runsDir)
+	if err != nil || len(runIDs) == 0 {
+		return "", err
+	}
+	return runIDs[len(runIDs)-1], nil
+}
+
+// List returns the IDs of the run directories in runsDir, oldest first. Run IDs start
+// with a timestamp, so they sort by age.
+func List(runsDir string) ([]string, error) {
+	entries, err := os.ReadDir(runsDir)
+	if os.IsNotExist(err) {
+		return nil, nil
+	}
+	if err != nil {
+		return nil, err
+	}
+	var runIDs []string
+	for _, entry := range entries {
+		if entry.IsDir() {
+			runIDs = append(runIDs, entry.Name())
+		}
+	}
+	return runIDs, nil // ReadDir sorts by name
+}
+
+// History returns the entries of index.jsonl, oldest first. A line left incomplete by
+// a crash is skipped.
+func History(runsDir string) ([]Entry, error) {
+	file, err := os.Open(filepath.Join(runsDir, IndexFileName))
+	if os.IsNotExist(err) {
+		return nil, nil
+	}
+	if err != nil {
+		return nil, err
+	}
+	defer func() { _ = file.Close() }()
+
+	var history []Entry
+	scanner := bufio.NewScanner(file)
+	scanner.Buffer(make([]byte, 0, 64*1024), 1024*1024)
+	for scanner.Scan() {
+		var entry Entry
+		if err := json.Unmarshal(scanner.Bytes(), &entry); err != nil {
+			continue
+		}
+		history = append(history, entry)
+	}
+	if err := scanner.Err(); err != nil {
+		return nil, fmt.Errorf("failed to read %s: %w", IndexFileName, err)
+	}
+	return history, nil
+}
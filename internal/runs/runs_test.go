@@ -0,0 +1,121 @@
+package runs
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"sync"
+	"testing"
+	"time"
+
+	"github.com/zk/3pio/internal/report"
+)
+
+func makeRun(t *testing.T, runsDir, runID string) {
+	t.Helper()
+	if err := os.MkdirAll(filepath.Join(runsDir, runID), 0755); err != nil {
+		t.Fatalf("Failed to create run directory: %v", err)
+	}
+}
+
+func TestRecord_LatestAndHistory(t *testing.T) {
+	runsDir := t.TempDir()
+
+	// Without any runs there is no latest one
+	if latest, err := Latest(runsDir); err != nil || latest != "" {
+		t.Fatalf("Expected no latest run, got %q (%v)", latest, err)
+	}
+
+	start := time.Date(2026, 10, 14, 9, 0, 0, 0, time.UTC)
+	for i, runID := range []string{"20261014T090000-kirk", "20261014T100000-spock"} {
+		makeRun(t, runsDir, runID)
+		entry := Entry{
+			RunID:     runID,
+			Command:   "cargo test",
+			StartTime: start.Add(time.Duration(i) * time.Hour),
+			EndTime:   start.Add(time.Duration(i)*time.Hour + time.Minute),
+			Status:    "COMPLETED",
+			ExitCode:  i,
+			Totals:    report.SummaryCounts{Total: 3, Passed: 3 - i, Failed: i},
+		}
+		if err := Record(runsDir, entry); err != nil {
+			t.Fatalf("Record failed: %v", err)
+		}
+		if latest, err := Latest(runsDir); err != nil || latest != runID {
+			t.Errorf("Expected latest to be %s, got %q (%v)", runID, latest, err)
+		}
+	}
+
+	history, err := History(runsDir)
+	if err != nil {
+		t.Fatalf("History failed: %v", err)
+	}
+	if len(history) != 2 || history[1].RunID != "20261014T100000-spock" || history[1].Totals.Failed != 1 ||
+		history[1].ExitCode != 1 || !history[1].StartTime.Equal(start.Add(time.Hour)) {
+		t.Errorf("Unexpected history %+v", history)
+	}
+
+	// The pointer and index aren't listed as runs
+	runIDs, err := List(runsDir)
+	if err != nil || len(runIDs) != 2 || runIDs[0] != "20261014T090000-kirk" {
+		t.Errorf("Expected the two run directories, got %v (%v)", runIDs, err)
+	}
+}
+
+func TestLatest_FallsBackToNewestRunDirectory(t *testing.T) {
+	runsDir := t.TempDir()
+	makeRun(t, runsDir, "20261014T090000-kirk")
+	makeRun(t, runsDir, "20261014T100000-spock")
+
+	// No pointer, e.g. runs of an older 3pio version
+	if latest, _ := Latest(runsDir); latest != "20261014T100000-spock" {
+		t.Errorf("Expected the newest run directory, got %q", latest)
+	}
+
+	// A pointer to a deleted run is ignored
+	if err := setLatest(runsDir, "20261014T080000-deleted"); err != nil {
+		t.Fatalf("setLatest failed: %v", err)
+	}
+	if latest, _ := Latest(runsDir); latest != "20261014T100000-spock" {
+		t.Errorf("Expected the newest run directory, got %q", latest)
+	}
+}
+
+func TestRecord_Concurrent(t *testing.T) {
+	runsDir := t.TempDir()
+
+	const runCount = 20
+	var wg sync.WaitGroup
+	for i := 0; i < runCount; i++ {
+		runID := fmt.Sprintf("20261014T1000%02d-run", i)
+		makeRun(t, runsDir, runID)
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			if err := Record(runsDir, Entry{RunID: runID, Command: "cargo test"}); err != nil {
+				t.Errorf("Record failed: %v", err)
+			}
+		}()
+	}
+	wg.Wait()
+
+	// Every run has a whole line, and latest points at one of them
+	history, err := History(runsDir)
+	if err != nil || len(history) != runCount {
+		t.Fatalf("Expected %d entries, got %d (%v)", runCount, len(history), err)
+	}
+	seen := make(map[string]bool)
+	for _, entry := range history {
+		seen[entry.RunID] = true
+	}
+	latest, err := Latest(runsDir)
+	if err != nil || !seen[latest] || len(seen) != runCount {
+		t.Errorf("Expected latest among the %d recorded runs, got %q (%v)", runCount, latest, err)
+	}
+
+	// Only the runs, the index, the pointer and the lock are left
+	entries, _ := os.ReadDir(runsDir)
+	if len(entries) != runCount+3 {
+		t.Errorf("Expected %d entries in the runs directory, got %d", runCount+3, len(entries))
+	}
+}
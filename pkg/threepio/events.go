@@ -0,0 +1,125 @@
+package threepio
+
+import (
+	"time"
+
+	"github.com/zk/3pio/internal/ipc"
+)
+
+// Reporter renders a run in place of 3pio's console output. Run calls its methods
+// from one goroutine at a time, in order: Start, Event for each event, then Finish.
+type Reporter interface {
+	Start(runID, runDir string) // Before the test command starts; runDir is relative to RunConfig.Dir
+	Event(event Event)          // As each event arrives, once the reports have it
+	Finish(result *RunResult)   // Once the reports are written
+}
+
+// EventKind is what an Event reports
+type EventKind string
+
+const (
+	EventGroupDiscovered EventKind = "groupDiscovered" // A group the run reports on: a file, package, test binary or describe block
+	EventGroupStart      EventKind = "groupStart"      // A group started running
+	EventTestCase        EventKind = "testCase"        // A test finished, or is listed as PENDING before the tests start
+	EventGroupResult     EventKind = "groupResult"     // A group finished
+	EventGroupError      EventKind = "groupError"      // A group failed outside its tests, e.g. in setup
+	EventOutput          EventKind = "output"          // A group printed output
+	EventBuildError      EventKind = "buildError"      // A compiler error stopped the tests from building
+	EventCrash           EventKind = "crash"           // A test binary died before finishing
+)
+
+// Event is something that happened during a run
+type Event struct {
+	Kind     EventKind
+	Group    []string      // Path of the group, e.g. {"my-crate", "tests/api.rs"}; for a test, of its group
+	Test     string        // Test name (EventTestCase), or the test running when a binary crashed (EventCrash)
+	Status   string        // PASS, FAIL, SKIP, PENDING, XFAIL, XPASS, BENCH or FLAKY (EventTestCase, EventGroupResult)
+	Duration time.Duration // Of the test or group, when the runner reports it
+	Message  string        // Failure, error or compiler message
+	Output   string        // Output of the test (EventTestCase) or the group (EventOutput)
+	Stderr   bool          // The output is from stderr (EventOutput)
+}
+
+// newEvent converts an IPC event. It returns false for the events that only steer
+// the orchestrator, such as the adapter handshake or collection progress.
+func newEvent(event ipc.Event) (Event, bool) {
+	switch e := event.(type) {
+	case ipc.GroupDiscoveredEvent:
+		return Event{Kind: EventGroupDiscovered, Group: groupPath(e.Payload.ParentNames, e.Payload.GroupName)}, true
+	case ipc.GroupStartEvent:
+		return Event{Kind: EventGroupStart, Group: groupPath(e.Payload.ParentNames, e.Payload.GroupName)}, true
+	case ipc.GroupTestCaseEvent:
+		ev := Event{
+			Kind:     EventTestCase,
+			Group:    e.Payload.ParentNames,
+			Test:     e.Payload.TestName,
+			Status:   e.Payload.Status,
+			Duration: milliseconds(e.Payload.Duration),
+			Output:   e.Payload.Stdout + e.Payload.Stderr,
+		}
+		if e.Payload.Error != nil {
+			ev.Message = e.Payload.Error.Message
+		}
+		return ev, true
+	case ipc.GroupResultEvent:
+		return Event{
+			Kind:     EventGroupResult,
+			Group:    groupPath(e.Payload.ParentNames, e.Payload.GroupName),
+			Status:   e.Payload.Status,
+			Duration: milliseconds(e.Payload.Duration),
+		}, true
+	case ipc.GroupErrorEvent:
+		ev := Event{
+			Kind:     EventGroupError,
+			Group:    groupPath(e.Payload.ParentNames, e.Payload.GroupName),
+			Duration: milliseconds(e.Payload.Duration),
+			Message:  e.Payload.ErrorType,
+		}
+		if e.Payload.Error != nil {
+			ev.Message = e.Payload.Error.Message
+		}
+		return ev, true
+	case ipc.GroupStdoutChunkEvent:
+		return Event{Kind: EventOutput, Group: groupPath(e.Payload.ParentNames, e.Payload.GroupName), Output: e.Payload.Chunk}, true
+	case ipc.GroupStderrChunkEvent:
+		return Event{Kind: EventOutput, Group: groupPath(e.Payload.ParentNames, e.Payload.GroupName), Output: e.Payload.Chunk, Stderr: true}, true
+	case ipc.BuildErrorEvent:
+		ev := Event{Kind: EventBuildError, Message: e.Payload.Message}
+		if e.Payload.Target != "" {
+			ev.Group = []string{e.Payload.Target}
+		}
+		return ev, true
+	case ipc.BinaryCrashEvent:
+		return Event{Kind: EventCrash, Group: []string{e.Payload.Target}, Test: e.Payload.TestName, Message: e.Payload.Message}, true
+	}
+	return Event{}, false
+}
+
+// groupPath returns the full path of a group from its parents and name
+func groupPath(parentNames []string, name string) []string {
+	path := append([]string(nil), parentNames...)
+	if name != "" {
+		path = append(path, name)
+	}
+	return path
+}
+
+// milliseconds converts a duration of the IPC events
+func milliseconds(ms float64) time.Duration {
+	return time.Duration(ms * float64(time.Millisecond))
+}
+
+// reporterObserver passes the orchestrator's events on to a Reporter
+type reporterObserver struct {
+	reporter Reporter
+}
+
+func (r *reporterObserver) RunStarted(runID, runDir string) {
+	r.reporter.Start(runID, runDir)
+}
+
+func (r *reporterObserver) Event(event ipc.Event) {
+	if ev, ok := newEvent(event); ok {
+		r.reporter.Event(ev)
+	}
+}
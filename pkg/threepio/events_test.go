@@ -0,0 +1,66 @@
+package threepio
+
+import (
+	"reflect"
+	"testing"
+	"time"
+
+	"github.com/zk/3pio/internal/ipc"
+)
+
+func TestNewEvent(t *testing.T) {
+	testCases := []struct {
+		event    ipc.Event
+		expected Event
+	}{
+		{
+			ipc.NewGroupDiscoveredEvent("tests/api.rs", []string{"my-crate"}),
+			Event{Kind: EventGroupDiscovered, Group: []string{"my-crate", "tests/api.rs"}},
+		},
+		{
+			ipc.GroupTestCaseEvent{Payload: ipc.TestCasePayload{
+				TestName:    "test_login",
+				ParentNames: []string{"my-crate", "tests/api.rs"},
+				Status:      "FAIL",
+				Duration:    12.5,
+				Error:       &ipc.TestError{Message: "assertion failed: ok"},
+				Stdout:      "connecting\n",
+			}},
+			Event{
+				Kind:     EventTestCase,
+				Group:    []string{"my-crate", "tests/api.rs"},
+				Test:     "test_login",
+				Status:   "FAIL",
+				Duration: 12500 * time.Microsecond,
+				Message:  "assertion failed: ok",
+				Output:   "connecting\n",
+			},
+		},
+		{
+			ipc.GroupResultEvent{Payload: ipc.GroupResultPayload{GroupName: "tests/api.rs", ParentNames: []string{"my-crate"}, Status: "FAIL", Duration: 40}},
+			Event{Kind: EventGroupResult, Group: []string{"my-crate", "tests/api.rs"}, Status: "FAIL", Duration: 40 * time.Millisecond},
+		},
+		{
+			ipc.GroupStderrChunkEvent{Payload: ipc.OutputChunkPayload{GroupName: "pkg/api", Chunk: "warning\n"}},
+			Event{Kind: EventOutput, Group: []string{"pkg/api"}, Output: "warning\n", Stderr: true},
+		},
+		{
+			ipc.BinaryCrashEvent{Payload: ipc.BinaryCrashPayload{Target: "my-crate", TestName: "tests::test_abort", Message: "signal: 6, SIGABRT"}},
+			Event{Kind: EventCrash, Group: []string{"my-crate"}, Test: "tests::test_abort", Message: "signal: 6, SIGABRT"},
+		},
+	}
+	for _, tc := range testCases {
+		ev, ok := newEvent(tc.event)
+		if !ok {
+			t.Errorf("Expected %T to be converted", tc.event)
+			continue
+		}
+		if !reflect.DeepEqual(ev, tc.expected) {
+			t.Errorf("Expected %+v for %T, got %+v", tc.expected, tc.event, ev)
+		}
+	}
+
+	if _, ok := newEvent(ipc.RunCompleteEvent{}); ok {
+		t.Error("Expected runComplete to be left out")
+	}
+}
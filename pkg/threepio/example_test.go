@@ -0,0 +1,51 @@
+package threepio_test
+
+import (
+	"fmt"
+	"log"
+	"strings"
+	"time"
+
+	"github.com/zk/3pio/pkg/threepio"
+)
+
+// Runs go test with 3pio's console output and reads the outcome
+func ExampleRun() {
+	cfg := threepio.DefaultRunConfig("go", "test", "./...")
+	cfg.Timeout = 10 * time.Minute
+
+	result, err := threepio.Run(cfg)
+	if err != nil {
+		log.Fatal(err)
+	}
+	fmt.Printf("%d of %d tests failed, see %s\n", result.Counts.Failed, result.Counts.Total, result.RunDir)
+}
+
+// failureReporter prints one line per failed test instead of 3pio's console output
+type failureReporter struct{}
+
+func (failureReporter) Start(runID, runDir string) {
+	fmt.Println("Run", runID)
+}
+
+func (failureReporter) Event(event threepio.Event) {
+	if event.Kind == threepio.EventTestCase && event.Status == "FAIL" {
+		fmt.Printf("FAIL %s > %s: %s\n", strings.Join(event.Group, " > "), event.Test, event.Message)
+	}
+}
+
+func (failureReporter) Finish(result *threepio.RunResult) {
+	fmt.Printf("%s, exit code %d\n", result.ExitReason, result.ExitCode)
+}
+
+// Renders the run with a Reporter, in a project directory with extra environment
+func ExampleReporter() {
+	cfg := threepio.DefaultRunConfig("cargo", "test")
+	cfg.Dir = "path/to/project"
+	cfg.Env = map[string]string{"RUST_LOG": "debug"}
+	cfg.Reporter = failureReporter{}
+
+	if _, err := threepio.Run(cfg); err != nil {
+		log.Fatal(err)
+	}
+}
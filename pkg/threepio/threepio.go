@@ -0,0 +1,225 @@
+// Package threepio runs a test command the way the 3pio binary does, for programs that
+// embed 3pio instead of running it and reading its exit code. It detects the test
+// runner, writes the run directory with test-run.md, summary.json and the group
+// reports, and tells a Reporter about the run as it happens.
+//
+// The 3pio binary is a command line over this package: its flags are the fields of
+// RunConfig.
+package threepio
+
+import (
+	"fmt"
+	"io"
+	"path/filepath"
+	"sort"
+	"sync"
+	"time"
+
+	"github.com/zk/3pio/internal/console"
+	"github.com/zk/3pio/internal/ipc"
+	"github.com/zk/3pio/internal/logger"
+	"github.com/zk/3pio/internal/orchestrator"
+)
+
+// Verbosity is how much 3pio's console output shows. It never changes the reports.
+type Verbosity = console.Verbosity
+
+const (
+	Normal  = console.Normal  // A block for each group with failures, then the summary
+	Quiet   = console.Quiet   // Only the failed tests and the summary
+	Verbose = console.Verbose // Also every test case as it completes
+)
+
+// ColorMode is when 3pio's console output uses colors
+type ColorMode = console.ColorMode
+
+const (
+	ColorAuto   = console.ColorAuto   // In a terminal, unless NO_COLOR is set
+	ColorAlways = console.ColorAlways // Even when the output isn't a terminal
+	ColorNever  = console.ColorNever  // No colors
+)
+
+// IPCTransport is how the Jest, Vitest, Mocha, Cypress and pytest adapters send their events
+type IPCTransport = ipc.Transport
+
+const (
+	TransportFile  = ipc.TransportFile  // Append to a file in the run directory
+	TransportStdio = ipc.TransportStdio // Write to fd 3, or stderr on Windows
+)
+
+// RunConfig describes a test run. Zero values leave a feature off; DefaultRunConfig
+// returns the defaults of the 3pio command line.
+type RunConfig struct {
+	Command           []string          // The test command, e.g. {"cargo", "test"}
+	Dir               string            // Directory the command runs in, which holds .3pio; empty for the current one
+	Env               map[string]string // Variables for the test command, on top of the process's environment
+	Timeout           time.Duration     // Stop the run after this long in total; 0 waits forever
+	InactivityTimeout time.Duration     // Stop the run after this long without output; 0 waits forever
+	OutputDir         string            // Directory for the runs and the quarantine and redact files; empty for .3pio
+	Reporter          Reporter          // Renders the run in place of 3pio's console output; nil for 3pio's
+	Console           io.Writer         // Where 3pio's console output goes without a Reporter; nil for stdout
+	EventStream       io.Writer         // Also write the events as JSON lines here, as 3pio --output json does; nil for none
+
+	// The other options of the command line, see 3pio --help
+	Runner            string        // --runner: runner to use instead of detecting it, e.g. "criterion"
+	NoBacktrace       bool          // --no-backtrace
+	RerunFailed       bool          // --rerun-failed
+	JUnitXMLPath      string        // --junit-xml
+	SummaryJSON       string        // --summary-json
+	SlowThreshold     time.Duration // --slow-threshold; 0 for the default
+	GHAAnnotations    bool          // --gha-annotations
+	NoSnippets        bool          // --no-snippets
+	AllowNoTests      bool          // --allow-no-tests
+	QuarantinePath    string        // --quarantine
+	Retries           int           // --retries
+	FailOnFlaky       bool          // --fail-on-flaky
+	FailOnXPass       bool          // --fail-on-xpass
+	FailOnWarnings    bool          // --fail-on-warnings
+	StrictArgs        bool          // --strict-args
+	SnippetLines      int           // --snippet-lines
+	HeartbeatInterval time.Duration // --heartbeat; 0 never
+	MaxLineLength     int           // --max-line-length; 0 for the default
+	Progress          bool          // --progress
+	PreflightList     bool          // --preflight-list
+	Redact            bool          // --redact
+	RedactRaw         bool          // --redact-raw
+	Verbosity         Verbosity     // -q/--quiet or -v/--verbose
+	Color             ColorMode     // --color
+	IPCTransport      IPCTransport  // --ipc-transport
+}
+
+// DefaultRunConfig returns the config of "3pio <command>" outside a terminal and GitHub Actions
+func DefaultRunConfig(command ...string) RunConfig {
+	return RunConfig{
+		Command:           command,
+		SnippetLines:      orchestrator.DefaultSnippetLines,
+		HeartbeatInterval: orchestrator.DefaultHeartbeatInterval,
+		PreflightList:     true,
+		Color:             ColorAuto,
+		IPCTransport:      TransportFile,
+	}
+}
+
+// RunResult is the outcome of a run
+type RunResult struct {
+	RunID      string // e.g. "20250917T120000-brave-anakin"
+	RunDir     string // Absolute path of the run directory; empty when the run never started
+	ExitCode   int    // What the 3pio binary exits with
+	ExitReason string // Why the run ended, as in summary.json: "ok", "tests_failed", "build_failed", ...
+	Counts     Counts
+}
+
+// Counts are the test cases of a run by outcome
+type Counts struct {
+	Passed  int
+	Failed  int
+	Skipped int
+	Flaky   int // Failed, then passed on a retry; not counted as failed
+	Errored int // Setup or teardown failed (pytest fixtures); not counted as failed
+	Total   int
+}
+
+// runMu makes runs take turns: the orchestrator handles the process's signals, and
+// hands the terminal to the test command while it runs
+var runMu sync.Mutex
+
+// Run runs the test command and returns once its reports are written. The result is
+// there even when err isn't nil, with the exit code the 3pio binary would use; err is
+// nil when the tests ran, whether or not they passed. Runs in one process take turns.
+func Run(cfg RunConfig) (*RunResult, error) {
+	runMu.Lock()
+	defer runMu.Unlock()
+
+	fileLogger, err := logger.NewFileLoggerIn(cfg.Dir)
+	if err != nil {
+		return &RunResult{ExitCode: 1}, fmt.Errorf("failed to create debug logger: %w", err)
+	}
+	defer func() { _ = fileLogger.Close() }()
+
+	config := orchestrator.Config{
+		Command:           cfg.Command,
+		Runner:            cfg.Runner,
+		NoBacktrace:       cfg.NoBacktrace,
+		Timeout:           cfg.Timeout,
+		InactivityTimeout: cfg.InactivityTimeout,
+		RerunFailed:       cfg.RerunFailed,
+		JUnitXMLPath:      cfg.JUnitXMLPath,
+		SummaryJSON:       cfg.SummaryJSON,
+		SlowThreshold:     cfg.SlowThreshold,
+		GHAAnnotations:    cfg.GHAAnnotations,
+		NoSnippets:        cfg.NoSnippets,
+		AllowNoTests:      cfg.AllowNoTests,
+		QuarantinePath:    cfg.QuarantinePath,
+		Retries:           cfg.Retries,
+		FailOnFlaky:       cfg.FailOnFlaky,
+		FailOnXPass:       cfg.FailOnXPass,
+		FailOnWarnings:    cfg.FailOnWarnings,
+		StrictArgs:        cfg.StrictArgs,
+		SnippetLines:      cfg.SnippetLines,
+		MaxLineLength:     cfg.MaxLineLength,
+		HeartbeatInterval: cfg.HeartbeatInterval,
+		Progress:          cfg.Progress,
+		PreflightList:     cfg.PreflightList,
+		Redact:            cfg.Redact,
+		RedactRaw:         cfg.RedactRaw,
+		Env:               envList(cfg.Env),
+		Dir:               cfg.Dir,
+		OutputDir:         cfg.OutputDir,
+		Logger:            fileLogger,
+		Verbosity:         cfg.Verbosity,
+		Color:             cfg.Color,
+		IPCTransport:      cfg.IPCTransport,
+		Console:           cfg.Console,
+		EventStream:       cfg.EventStream,
+	}
+	if cfg.Reporter != nil {
+		config.Console = io.Discard
+		config.Progress = false
+		config.Observer = &reporterObserver{reporter: cfg.Reporter}
+	}
+
+	orch, err := orchestrator.New(config)
+	if err != nil {
+		return &RunResult{ExitCode: 1}, fmt.Errorf("failed to create orchestrator: %w", err)
+	}
+	runErr := orch.Run()
+
+	result := newRunResult(orch.Result())
+	if cfg.Reporter != nil {
+		cfg.Reporter.Finish(result)
+	}
+	return result, runErr
+}
+
+// newRunResult converts the orchestrator's result
+func newRunResult(r orchestrator.Result) *RunResult {
+	result := &RunResult{
+		RunID:      r.RunID,
+		ExitCode:   r.ExitCode,
+		ExitReason: string(r.ExitReason),
+		Counts: Counts{
+			Passed:  r.Passed,
+			Failed:  r.Failed,
+			Skipped: r.Skipped,
+			Flaky:   r.Flaky,
+			Errored: r.Errored,
+			Total:   r.Total,
+		},
+	}
+	if r.RunDir != "" {
+		if dir, err := filepath.Abs(r.RunDir); err == nil {
+			result.RunDir = dir
+		}
+	}
+	return result
+}
+
+// envList turns variables into "NAME=value" entries, sorted by name
+func envList(env map[string]string) []string {
+	list := make([]string, 0, len(env))
+	for name, value := range env {
+		list = append(list, name+"="+value)
+	}
+	sort.Strings(list)
+	return list
+}
@@ -0,0 +1,10 @@
+// The specs don't open pages, so the projects need no installed browsers
+module.exports = {
+  testDir: './tests',
+  retries: 1,
+  timeout: 1000,
+  projects: [
+    { name: 'chromium', use: { browserName: 'chromium' } },
+    { name: 'firefox', use: { browserName: 'firefox' } },
+  ],
+};
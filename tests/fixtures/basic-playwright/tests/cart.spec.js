@@ -0,0 +1,23 @@
+const { test, expect } = require('@playwright/test');
+
+test.describe('cart', () => {
+  test('adds an item', () => {
+    console.log('adding widget to cart');
+    expect([1].length).toBe(1);
+  });
+
+  test('applies a discount', () => {
+    expect(90).toBe(80);
+  });
+
+  test('reloads the total', (_, testInfo) => {
+    // Fails the first time and passes when retried
+    expect(testInfo.retry).toBe(1);
+  });
+
+  test('waits for the payment', async () => {
+    await new Promise((resolve) => setTimeout(resolve, 5000));
+  });
+
+  test.skip('ships abroad', () => {});
+});
@@ -0,0 +1,14 @@
+import { describe, expect, test } from "bun:test";
+
+describe("cart", () => {
+  test("adds an item", () => {
+    console.log("adding widget to cart");
+    expect([1].length).toBe(1);
+  });
+
+  test("applies a discount", () => {
+    expect(90).toBe(80);
+  });
+
+  test.skip("ships abroad", () => {});
+});
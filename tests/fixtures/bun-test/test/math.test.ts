@@ -0,0 +1,7 @@
+import { expect, test } from "bun:test";
+
+test("adds numbers", () => {
+  expect(1 + 2).toBe(3);
+});
+
+test.todo("divides by zero");
@@ -0,0 +1,44 @@
+using Xunit;
+using Xunit.Abstractions;
+
+namespace Calc.Tests;
+
+public class CalculatorTests
+{
+    private readonly ITestOutputHelper output;
+
+    public CalculatorTests(ITestOutputHelper output)
+    {
+        this.output = output;
+    }
+
+    [Fact]
+    public void Adds()
+    {
+        output.WriteLine("adding 2 and 2");
+        Assert.Equal(4, 2 + 2);
+    }
+
+    [Fact]
+    public void Divides()
+    {
+        Assert.Equal(2, 7 / 2 + 1);
+    }
+
+    [Fact(Skip = "needs a 128-bit machine")]
+    public void Overflows()
+    {
+    }
+}
+
+public class ParserTests
+{
+    [Theory]
+    [InlineData("1+1", 2)]
+    [InlineData("2+3", 5)]
+    public void Parses(string input, int expected)
+    {
+        var parts = input.Split('+');
+        Assert.Equal(expected, int.Parse(parts[0]) + int.Parse(parts[1]));
+    }
+}
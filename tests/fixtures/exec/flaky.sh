@@ -0,0 +1,9 @@
+#!/bin/sh
+# Fails the first time it runs, leaving $FLAKY_MARKER behind, and passes after that
+if [ -f "$FLAKY_MARKER" ]; then
+	echo "reached the service"
+	exit 0
+fi
+touch "$FLAKY_MARKER"
+echo "connection refused"
+exit 2
@@ -0,0 +1,11 @@
+#!/bin/sh
+# Fails the first time it runs, leaving $FLAKY_MARKER behind, and hangs without
+# output after that
+if [ -f "$FLAKY_MARKER" ]; then
+	echo "waiting for the service"
+	sleep 300
+	exit 0
+fi
+touch "$FLAKY_MARKER"
+echo "connection refused"
+exit 2
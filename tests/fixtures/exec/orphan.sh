@@ -0,0 +1,9 @@
+#!/bin/sh
+# Starts a long-lived helper, records its process group and the terminal's
+# foreground group, then waits on stdin until it is killed
+sleep 300 &
+echo $! > "$ORPHAN_DIR/grandchild.pid"
+cat /proc/$$/stat > "$ORPHAN_DIR/stat"
+echo "waiting on stdin"
+read -r line
+echo "read $line"
@@ -0,0 +1,6 @@
+#!/bin/sh
+# A smoke test with no parseable output: checks a few things, then exits with $1
+echo "checking the config"
+echo "checking the database" >&2
+echo "checking the queue"
+exit "${1:-0}"
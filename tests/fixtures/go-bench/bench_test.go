@@ -0,0 +1,36 @@
+package bench
+
+import (
+	"strconv"
+	"strings"
+	"testing"
+)
+
+func TestJoin(t *testing.T) {
+	if got := strings.Join([]string{"a", "b"}, ","); got != "a,b" {
+		t.Errorf("Join() = %q, want %q", got, "a,b")
+	}
+}
+
+func TestItoa(t *testing.T) {
+	if got := strconv.Itoa(42); got != "42" {
+		t.Errorf("Itoa() = %q, want %q", got, "42")
+	}
+}
+
+func BenchmarkJoin(b *testing.B) {
+	parts := []string{"a", "b", "c"}
+	for i := 0; i < b.N; i++ {
+		_ = strings.Join(parts, ",")
+	}
+}
+
+func BenchmarkItoa(b *testing.B) {
+	for _, n := range []int{7, 123456789} {
+		b.Run(strconv.Itoa(n), func(b *testing.B) {
+			for i := 0; i < b.N; i++ {
+				_ = strconv.Itoa(n)
+			}
+		})
+	}
+}
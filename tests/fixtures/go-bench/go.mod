@@ -0,0 +1,3 @@
+module github.com/zk/3pio/tests/fixtures/go-bench
+
+go 1.21
@@ -0,0 +1,7 @@
+// Package broken doesn't compile, on purpose
+package broken
+
+// Double returns twice n
+func Double(n int) int {
+	return n * factor
+}
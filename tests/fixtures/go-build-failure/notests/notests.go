@@ -0,0 +1,7 @@
+// Package notests has no test files
+package notests
+
+// Greeting returns a greeting for name
+func Greeting(name string) string {
+	return "hello " + name
+}
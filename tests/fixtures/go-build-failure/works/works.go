@@ -0,0 +1,7 @@
+// Package works builds and passes its tests
+package works
+
+// Add returns the sum of a and b
+func Add(a, b int) int {
+	return a + b
+}
@@ -0,0 +1,25 @@
+package cache
+
+import (
+	"testing"
+	"time"
+)
+
+// TestLookup runs its subtests in parallel: each one pauses until TestLookup
+// returns, then continues, so the run, pause and cont actions of the three
+// interleave with their output
+func TestLookup(t *testing.T) {
+	for _, key := range []string{"first", "second", "third"} {
+		key := key
+		t.Run(key, func(t *testing.T) {
+			t.Parallel()
+			time.Sleep(20 * time.Millisecond)
+			t.Logf("looked up %s", key)
+		})
+	}
+}
+
+func TestEvict(t *testing.T) {
+	t.Parallel()
+	t.Log("evicted")
+}
@@ -0,0 +1,25 @@
+package store
+
+import (
+	"testing"
+	"time"
+)
+
+// TestWrite fails one of its parallel subtests, failing the package; the other
+// subtest's output must stay with it
+func TestWrite(t *testing.T) {
+	t.Run("small", func(t *testing.T) {
+		t.Parallel()
+		time.Sleep(10 * time.Millisecond)
+		t.Log("wrote small")
+	})
+	t.Run("large", func(t *testing.T) {
+		t.Parallel()
+		time.Sleep(20 * time.Millisecond)
+		t.Errorf("write large: disk full")
+	})
+}
+
+func TestRead(t *testing.T) {
+	t.Log("read")
+}
@@ -0,0 +1,48 @@
+package race
+
+import (
+	"os"
+	"sync"
+	"testing"
+)
+
+// TestCounter increments a counter from two goroutines without a lock. It only runs
+// with THREEPIO_RACE_FIXTURE=1, so a plain go test ./... over the repo stays green.
+func TestCounter(t *testing.T) {
+	if os.Getenv("THREEPIO_RACE_FIXTURE") != "1" {
+		t.Skip("set THREEPIO_RACE_FIXTURE=1 to run the race")
+	}
+	counter := 0
+	var wg sync.WaitGroup
+	for i := 0; i < 2; i++ {
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			counter++
+		}()
+	}
+	wg.Wait()
+	if counter == 0 {
+		t.Error("counter was never incremented")
+	}
+}
+
+// TestSafe doesn't race
+func TestSafe(t *testing.T) {
+	var mu sync.Mutex
+	counter := 0
+	var wg sync.WaitGroup
+	for i := 0; i < 2; i++ {
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			mu.Lock()
+			counter++
+			mu.Unlock()
+		}()
+	}
+	wg.Wait()
+	if counter != 2 {
+		t.Errorf("counter = %d, want 2", counter)
+	}
+}
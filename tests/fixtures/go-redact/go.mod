@@ -0,0 +1,3 @@
+module testmodule
+
+go 1.21
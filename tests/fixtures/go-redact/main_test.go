@@ -0,0 +1,18 @@
+package main
+
+import (
+	"encoding/json"
+	"net/url"
+	"os"
+	"testing"
+)
+
+// TestLeaksToken fails while printing the token from its environment as is,
+// URL-encoded and inside a JSON string
+func TestLeaksToken(t *testing.T) {
+	token := os.Getenv("FAKE_SERVICE_TOKEN")
+	body, _ := json.Marshal(map[string]string{"authorization": "Bearer " + token})
+	t.Logf("GET https://api.example.com/items?token=%s", url.QueryEscape(token))
+	t.Logf("request body: %s", body)
+	t.Fatalf("unauthorized: token %s was rejected", token)
+}
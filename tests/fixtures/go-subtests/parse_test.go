@@ -0,0 +1,65 @@
+package subtests
+
+import (
+	"strconv"
+	"testing"
+)
+
+// TestParse nests table-driven subtests three levels deep: base, sign, case
+func TestParse(t *testing.T) {
+	bases := []struct {
+		name string
+		base int
+	}{
+		{"decimal", 10},
+		{"hex", 16},
+	}
+	signs := []struct {
+		name   string
+		prefix string
+		sign   int64
+	}{
+		{"positive", "", 1},
+		{"negative", "-", -1},
+	}
+	cases := []struct {
+		name  string
+		input string
+		want  int64
+	}{
+		{"zero", "0", 0},
+		{"ten", "10", 10},
+	}
+
+	for _, b := range bases {
+		t.Run(b.name, func(t *testing.T) {
+			for _, s := range signs {
+				t.Run(s.name, func(t *testing.T) {
+					for _, c := range cases {
+						t.Run(c.name, func(t *testing.T) {
+							got, err := strconv.ParseInt(s.prefix+c.input, b.base, 64)
+							if err != nil {
+								t.Fatal(err)
+							}
+							// Deliberately wrong for "ten" outside base 10
+							if want := s.sign * c.want; got != want {
+								t.Errorf("ParseInt(%q, %d) = %d, want %d", s.prefix+c.input, b.base, got, want)
+							}
+						})
+					}
+				})
+			}
+		})
+	}
+}
+
+// TestPaths has subtests whose names contain slashes, which Go keeps as they are
+func TestPaths(t *testing.T) {
+	for _, path := range []string{"usr/bin", "usr/local/bin"} {
+		t.Run(path, func(t *testing.T) {
+			if path == "" {
+				t.Fatal("empty path")
+			}
+		})
+	}
+}
@@ -0,0 +1,27 @@
+package hang
+
+import (
+	"testing"
+	"time"
+)
+
+func TestQuick(t *testing.T) {
+	if 1+1 != 2 {
+		t.Error("math is broken")
+	}
+}
+
+// TestHang outlives go test -timeout 1s, and passes without it
+func TestHang(t *testing.T) {
+	t.Run("slow", func(t *testing.T) {
+		time.Sleep(3 * time.Second)
+	})
+}
+
+// TestAfterHang is parallel, so it waits for TestHang and never gets to run
+func TestAfterHang(t *testing.T) {
+	t.Parallel()
+	if 2*2 != 4 {
+		t.Error("math is broken")
+	}
+}
@@ -0,0 +1,30 @@
+const assert = require('assert');
+
+describe('Cart', function () {
+  it('adds an item', function () {
+    assert.strictEqual([1].length, 1);
+  });
+
+  describe('discounts', function () {
+    it('applies a percentage', function () {
+      assert.strictEqual(100 * 0.9, 90);
+    });
+
+    it('applies a coupon', function () {
+      assert.strictEqual(100 - 15, 80);
+    });
+  });
+
+  describe('shipping', function () {
+    it('ships abroad');
+
+    it.skip('ships overnight', function () {
+      assert.fail('not offered yet');
+    });
+
+    it('quotes the carrier', function (done) {
+      // Never calls done, so it outlives its timeout
+      this.timeout(50);
+    });
+  });
+});
@@ -0,0 +1,21 @@
+const test = require('node:test');
+const assert = require('node:assert');
+
+test('cart', async (t) => {
+  await t.test('adds an item', () => {
+    console.log('adding widget to cart');
+    assert.strictEqual(1 + 1, 2);
+  });
+
+  await t.test('checkout', async (t) => {
+    await t.test('charges the total', () => {
+      assert.strictEqual(45 * 2, 90);
+    });
+
+    await t.test('applies a discount', () => {
+      assert.strictEqual(100 - 10, 80);
+    });
+  });
+
+  await t.test('ships abroad', { skip: 'no carrier yet' }, () => {});
+});
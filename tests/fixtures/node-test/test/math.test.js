@@ -0,0 +1,8 @@
+const test = require('node:test');
+const assert = require('node:assert');
+
+test('adds numbers', () => {
+  assert.strictEqual(2 + 3, 5);
+});
+
+test('divides by zero', { todo: 'decide what it returns' });
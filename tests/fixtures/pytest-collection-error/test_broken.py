@@ -0,0 +1,5 @@
+import module_that_does_not_exist
+
+
+def test_never_collected():
+    assert module_that_does_not_exist.answer() == 42
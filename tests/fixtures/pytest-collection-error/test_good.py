@@ -0,0 +1,6 @@
+def test_add():
+    assert 1 + 1 == 2
+
+
+def test_upper():
+    assert "abc".upper() == "ABC"
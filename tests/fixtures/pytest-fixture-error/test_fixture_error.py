@@ -0,0 +1,18 @@
+import pytest
+
+
+@pytest.fixture
+def database():
+    raise ConnectionError("database is not running")
+
+
+def test_query(database):
+    assert database.query("select 1") == 1
+
+
+def test_insert(database):
+    assert database.insert({"id": 1})
+
+
+def test_without_database():
+    assert 1 + 1 == 2
@@ -0,0 +1,2 @@
+def test_failed():
+    assert "a" * 2 == "aaa"
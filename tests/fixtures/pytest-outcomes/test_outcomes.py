@@ -0,0 +1,20 @@
+import pytest
+
+
+def test_passed():
+    assert 1 + 1 == 2
+
+
+@pytest.mark.skip(reason="needs a GPU")
+def test_skipped():
+    assert False
+
+
+@pytest.mark.xfail(reason="rounding bug, issue 12")
+def test_xfailed():
+    assert round(2.675, 2) == 2.68
+
+
+@pytest.mark.xfail(reason="flaky on old parsers")
+def test_xpassed():
+    assert int("42") == 42
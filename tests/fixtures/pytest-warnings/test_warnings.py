@@ -0,0 +1,14 @@
+import warnings
+
+
+def parse_legacy(value):
+    warnings.warn("parse_legacy() is deprecated, use int()", DeprecationWarning)
+    return int(value)
+
+
+def test_parse_legacy():
+    assert parse_legacy("42") == 42
+
+
+def test_parse_legacy_again():
+    assert parse_legacy("7") == 7
@@ -0,0 +1,11 @@
+import time
+
+import pytest
+
+
+@pytest.mark.parametrize("n", range(200))
+def test_value(n):
+    print(f"checking value {n}")
+    # Long enough for the workers' tests to overlap
+    time.sleep(0.005)
+    assert n % 50 != 7, f"value {n} is unlucky"
@@ -0,0 +1,30 @@
+use std::io::Write;
+
+/// Writes bytes that aren't valid UTF-8 to stdout, bypassing libtest's output capture
+pub fn dump_header() {
+    let mut stdout = std::io::stdout();
+    stdout.write_all(b"header: \xff\xfe\x00\x01 end of header\n").unwrap();
+    stdout.flush().unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binary_dump() {
+        dump_header();
+    }
+
+    #[test]
+    fn test_valid_text() {
+        println!("plain text");
+        assert_eq!(1 + 1, 2);
+    }
+
+    #[test]
+    fn test_binary_failure() {
+        dump_header();
+        panic!("decoded {}", String::from_utf8_lossy(b"\xff\xfe"));
+    }
+}
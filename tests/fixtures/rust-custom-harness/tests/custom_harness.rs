@@ -0,0 +1,87 @@
+//! A libtest-mimic harness that reports its tests to 3pio through threepio-reporter
+
+use std::sync::Arc;
+
+use libtest_mimic::{Arguments, Failed, Trial};
+use rust_custom_harness::{add, reverse};
+use threepio_reporter::{Outcome, ThreePioReporter};
+
+type Test = fn() -> Result<(), Failed>;
+
+const TESTS: &[(&str, &str, Test, bool)] = &[
+    ("math", "adds", adds, false),
+    ("math", "adds_negative", adds_negative, false),
+    ("math", "fails_on_purpose", fails_on_purpose, false),
+    ("strings", "reverses", reverses, false),
+    ("strings", "reverses_unicode", reverses_unicode, true),
+];
+
+fn adds() -> Result<(), Failed> {
+    check(add(2, 3) == 5, "expected 2 + 3 to be 5")
+}
+
+fn adds_negative() -> Result<(), Failed> {
+    check(add(-2, -3) == -5, "expected -2 + -3 to be -5")
+}
+
+fn fails_on_purpose() -> Result<(), Failed> {
+    let sum = add(3, 3);
+    check(sum == 7, &format!("expected 7, got {}", sum))
+}
+
+fn reverses() -> Result<(), Failed> {
+    check(reverse("abc") == "cba", "expected cba")
+}
+
+fn reverses_unicode() -> Result<(), Failed> {
+    check(reverse("añb") == "bña", "expected bña")
+}
+
+fn check(ok: bool, message: &str) -> Result<(), Failed> {
+    if ok {
+        Ok(())
+    } else {
+        Err(message.into())
+    }
+}
+
+fn main() {
+    let args = Arguments::from_iter(threepio_reporter::args());
+    let reporter = Arc::new(ThreePioReporter::from_env(env!("CARGO_CRATE_NAME")));
+
+    let runs_ignored = args.ignored || args.include_ignored;
+    let mut trials = Vec::new();
+    for &(group, name, test, ignored) in TESTS {
+        if ignored && !runs_ignored && args.filter.is_none() {
+            // libtest-mimic doesn't call ignored tests, so report them up front
+            if let Some(reporter) = &*reporter {
+                reporter.test_finished(&[group], name, Outcome::Ignored).ok();
+            }
+        }
+
+        let reporter = Arc::clone(&reporter);
+        let trial = Trial::test(format!("{}::{}", group, name), move || {
+            let Some(reporter) = &*reporter else {
+                return test();
+            };
+            reporter.test_started(&[group], name).ok();
+            let result = test();
+            let outcome = match &result {
+                Ok(()) => Outcome::Passed,
+                Err(failed) => Outcome::Failed {
+                    message: failed.message().unwrap_or("test failed").to_string(),
+                },
+            };
+            reporter.test_finished(&[group], name, outcome).ok();
+            result
+        });
+        trials.push(trial.with_ignored_flag(ignored));
+    }
+
+    let conclusion = libtest_mimic::run(&args, trials);
+    // exit() skips the reporter's drop, which would finish the groups
+    if let Some(reporter) = &*reporter {
+        reporter.finish().ok();
+    }
+    conclusion.exit();
+}
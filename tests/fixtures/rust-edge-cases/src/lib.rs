@@ -1,8 +1,17 @@
 /// Edge case testing module
+///
+/// ```should_panic
+/// rust_edge_cases::will_panic();
+/// ```
 pub fn will_panic() {
     panic!("This function always panics!");
 }
 
+/// Always panics, which makes this doctest fail on purpose
+///
+/// ```
+/// assert_eq!(rust_edge_cases::divide_by_zero(), 0);
+/// ```
 pub fn divide_by_zero() -> i32 {
     let x = 10;
     let y = 0;
@@ -25,6 +34,9 @@ mod tests {
 
     #[test]
     fn test_assertion_failure() {
+        println!("computing 2 + 2");
+        println!();
+        println!("expecting 5");
         assert_eq!(2 + 2, 5, "Math is broken!");
     }
 
@@ -34,6 +46,12 @@ mod tests {
         will_panic();
     }
 
+    #[test]
+    #[should_panic(expected = "This message does not match")]
+    fn test_expected_panic_mismatch() {
+        will_panic();
+    }
+
     #[test]
     fn test_unexpected_panic() {
         panic!("Unexpected panic occurred!");
@@ -0,0 +1,29 @@
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add() {
+        assert_eq!(add(2, 2), 4);
+    }
+
+    #[test]
+    fn test_always_fails() {
+        assert_eq!(add(2, 2), 5);
+    }
+
+    // Fails only while TOGGLE_FAILURE is set, so a test can flip it between runs
+    #[test]
+    fn test_toggled() {
+        let expected = if std::env::var_os("TOGGLE_FAILURE").is_some() {
+            5
+        } else {
+            4
+        };
+        assert_eq!(add(2, 2), expected);
+    }
+}
@@ -0,0 +1,31 @@
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add() {
+        assert_eq!(add(2, 2), 4);
+    }
+
+    #[test]
+    fn test_always_fails() {
+        assert_eq!(add(2, 2), 5);
+    }
+
+    // Fails on the first attempt only: it leaves the file named by FLAKY_MARKER
+    // behind, and passes once the file exists
+    #[test]
+    fn test_flaky() {
+        let marker = std::env::var_os("FLAKY_MARKER").expect("FLAKY_MARKER is not set");
+        let retried = std::path::Path::new(&marker).exists();
+        if !retried {
+            std::fs::write(&marker, "").unwrap();
+        }
+        println!("attempt ran, retried: {}", retried);
+        assert!(retried, "fails on the first attempt");
+    }
+}
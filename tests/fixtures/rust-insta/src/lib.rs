@@ -0,0 +1,23 @@
+pub fn greet(name: &str) -> String {
+    format!("Hello, {}!", name)
+}
+
+pub fn farewell(name: &str) -> String {
+    format!("Goodbye, {}.", name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The stored snapshot is stale: it predates the exclamation mark
+    #[test]
+    fn greeting() {
+        insta::assert_snapshot!(greet("world"));
+    }
+
+    #[test]
+    fn parting() {
+        insta::assert_snapshot!(farewell("world"));
+    }
+}
@@ -0,0 +1,33 @@
+pub fn double(x: i32) -> i32 {
+    x * 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::process::Command;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_double() {
+        assert_eq!(double(2), 4);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_spawns_grandchild() {
+        // A helper that outlives the test binary unless its whole process group is killed
+        let child = Command::new("sleep")
+            .arg("300")
+            .spawn()
+            .expect("failed to spawn sleep");
+        let pid_file = concat!(env!("CARGO_MANIFEST_DIR"), "/grandchild.pid");
+        fs::write(pid_file, child.id().to_string()).expect("failed to write grandchild.pid");
+
+        // Far longer than the --timeout the integration test uses
+        thread::sleep(Duration::from_secs(120));
+        assert_eq!(double(3), 6);
+    }
+}
@@ -0,0 +1,8 @@
+//! A harness = false target that knows nothing of 3pio. Its output looks like
+//! libtest's, but only the exit code says how it went.
+
+fn main() {
+    println!("test phantom_check ... ok");
+    println!("checked 3 inputs, 1 failed");
+    std::process::exit(1);
+}
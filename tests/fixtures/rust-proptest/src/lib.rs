@@ -0,0 +1,17 @@
+/// Converts to unsigned, wrongly for negative numbers
+pub fn to_unsigned(n: i32) -> u32 {
+    n as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn round_trips(n in any::<i32>()) {
+            prop_assert_eq!(to_unsigned(n) as i64, n as i64);
+        }
+    }
+}
@@ -0,0 +1,22 @@
+pub fn triple(x: i32) -> i32 {
+    x * 3
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_triple() {
+        assert_eq!(triple(2), 6);
+    }
+
+    #[test]
+    fn test_sleeps_past_timeout() {
+        // Far longer than the --timeout the integration test uses
+        thread::sleep(Duration::from_secs(120));
+        assert_eq!(triple(3), 9);
+    }
+}
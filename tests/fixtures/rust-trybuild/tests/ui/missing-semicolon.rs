@@ -0,0 +1,4 @@
+fn main() {
+    let answer = rust_trybuild::answer()
+    let doubled = answer * 2;
+}
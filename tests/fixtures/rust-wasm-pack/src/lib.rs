@@ -0,0 +1,32 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[wasm_bindgen]
+pub fn divide(a: i32, b: i32) -> i32 {
+    a.checked_div(b).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn adds() {
+        assert_eq!(add(2, 2), 4);
+    }
+
+    // Fails on purpose: dividing by zero gives 0
+    #[wasm_bindgen_test]
+    fn divides() {
+        assert_eq!(divide(4, 0), 2);
+    }
+
+    #[wasm_bindgen_test]
+    #[ignore = "needs network"]
+    fn fetches() {}
+}
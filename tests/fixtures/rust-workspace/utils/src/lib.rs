@@ -65,6 +65,13 @@ mod tests {
         assert_eq!(count_words("one"), 1);
     }
 
+    // Fails only while TOGGLE_FAILURE is set, so the workspace passes by default
+    #[test]
+    fn test_toggled_failure() {
+        let expected = if std::env::var_os("TOGGLE_FAILURE").is_some() { "hello" } else { "olleh" };
+        assert_eq!(reverse("hello"), expected);
+    }
+
     mod math_tests {
         use super::math::*;
 
@@ -0,0 +1,10 @@
+const test = require('node:test');
+const assert = require('node:assert');
+
+test('adds numbers', () => {
+  assert.strictEqual(2 + 3, 5);
+});
+
+test('multiplies numbers', () => {
+  assert.strictEqual(2 * 3, 6);
+});
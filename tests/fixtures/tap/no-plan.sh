@@ -0,0 +1,4 @@
+#!/bin/sh
+# Stops short of its plan line, yet exits 0
+echo "ok 1 - starts"
+echo "ok 2 - stops"
@@ -0,0 +1,12 @@
+#!/bin/sh
+# Prints TAP the way a hand-rolled shell test harness would
+echo "1..4"
+echo "ok 1 - parses the config"
+echo "not ok 2 - writes the lockfile"
+echo "# expected /tmp/app.lock to exist"
+echo "ok 3 - reloads on SIGHUP # SKIP needs a tty"
+echo "    ok 1 - reads the header"
+echo "    ok 2 - reads the body"
+echo "    1..2"
+echo "ok 4 - parses a request"
+exit 1
@@ -0,0 +1,25 @@
+import { describe, it, expect } from 'vitest';
+
+describe('Cart', () => {
+  it('adds an item', () => {
+    console.log('adding widget to cart');
+    expect([].concat('widget')).toHaveLength(1);
+  });
+
+  it('applies a discount', () => {
+    expect(100 * 0.9).toBe(80);
+  });
+
+  it.skip('ships abroad', () => {
+    expect(true).toBe(false);
+  });
+
+  it.todo('saves the cart for later');
+
+  describe('checkout', () => {
+    it('charges the total', () => {
+      console.error('charging 90');
+      expect(90).toBe(90);
+    });
+  });
+});
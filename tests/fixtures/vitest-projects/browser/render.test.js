@@ -0,0 +1,5 @@
+import { it, expect } from 'vitest';
+
+it('renders a title', () => {
+  expect('<h1>Cart</h1>').toContain('Cart');
+});
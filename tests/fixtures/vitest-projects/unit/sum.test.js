@@ -0,0 +1,5 @@
+import { it, expect } from 'vitest';
+
+it('adds numbers', () => {
+  expect(1 + 2).toBe(3);
+});
@@ -0,0 +1,11 @@
+import { defineConfig } from 'vitest/config';
+
+export default defineConfig({
+  test: {
+    reporters: [],
+    projects: [
+      { test: { name: 'unit', include: ['unit/**/*.test.js'] } },
+      { test: { name: 'browser', include: ['browser/**/*.test.js'] } }
+    ]
+  }
+});
@@ -1,6 +1,8 @@
 package integration_test
 
 import (
+	"bufio"
+	"encoding/json"
 	"os"
 	"path/filepath"
 	"strings"
@@ -156,6 +158,103 @@ func testFullFlowWithRunner(t *testing.T, fixtureDir string, command []string) {
 	} else {
 		t.Logf("output.log contains %d bytes of output", len(outputLogContent))
 	}
+
+	if expectedRunner == "jest" {
+		checkJestLocations(t, runDir)
+		checkJestConsole(t, runDir)
+	}
+}
+
+// checkJestLocations checks the Jest adapter sent where each test of math.test.js is
+// defined, and the line of the failed assertion
+func checkJestLocations(t *testing.T, runDir string) {
+	t.Helper()
+	file, err := os.Open(filepath.Join(runDir, "ipc.jsonl"))
+	if err != nil {
+		t.Fatalf("Failed to open ipc.jsonl: %v", err)
+	}
+	defer func() { _ = file.Close() }()
+
+	type location struct {
+		File   string `json:"file"`
+		Line   int    `json:"line"`
+		Column int    `json:"column"`
+	}
+	type testCase struct {
+		TestName string `json:"testName"`
+		location
+		Error *location `json:"error"`
+	}
+	tests := map[string]testCase{}
+	scanner := bufio.NewScanner(file)
+	scanner.Buffer(make([]byte, 0, 64*1024), 10*1024*1024)
+	for scanner.Scan() {
+		var event struct {
+			EventType string   `json:"eventType"`
+			Payload   testCase `json:"payload"`
+		}
+		if json.Unmarshal(scanner.Bytes(), &event) != nil || event.EventType != "testCase" {
+			continue
+		}
+		tests[event.Payload.TestName] = event.Payload
+	}
+
+	for name, line := range map[string]int{
+		"should add numbers correctly":      2,
+		"should multiply numbers correctly": 9,
+		"should handle division":            16,
+	} {
+		tc, ok := tests[name]
+		if !ok {
+			t.Errorf("No testCase event for %q", name)
+			continue
+		}
+		if tc.File != "math.test.js" || tc.Line != line || tc.Column != 3 {
+			t.Errorf("Expected %q at math.test.js:%d:3, got %+v", name, line, tc.location)
+		}
+	}
+	if failed := tests["should add numbers correctly"].Error; failed == nil || failed.File != "math.test.js" || failed.Line != 4 {
+		t.Errorf("Expected the failure at line 4 of math.test.js, got %+v", failed)
+	}
+}
+
+// checkJestConsole checks the Jest adapter attributed the console.log calls of two
+// tests in math.test.js to the test that made them
+func checkJestConsole(t *testing.T, runDir string) {
+	t.Helper()
+	file, err := os.Open(filepath.Join(runDir, "ipc.jsonl"))
+	if err != nil {
+		t.Fatalf("Failed to open ipc.jsonl: %v", err)
+	}
+	defer func() { _ = file.Close() }()
+
+	loggedBy := map[string]string{}
+	scanner := bufio.NewScanner(file)
+	scanner.Buffer(make([]byte, 0, 64*1024), 10*1024*1024)
+	for scanner.Scan() {
+		var event struct {
+			EventType string `json:"eventType"`
+			Payload   struct {
+				GroupName string `json:"groupName"`
+				TestName  string `json:"testName"`
+				Level     string `json:"level"`
+				Chunk     string `json:"chunk"`
+			} `json:"payload"`
+		}
+		if json.Unmarshal(scanner.Bytes(), &event) != nil || event.EventType != "groupStdout" || event.Payload.Level != "log" {
+			continue
+		}
+		loggedBy[strings.TrimSpace(event.Payload.Chunk)] = event.Payload.GroupName + " > " + event.Payload.TestName
+	}
+
+	for message, test := range map[string]string{
+		"Testing addition...":       "Math operations > should add numbers correctly",
+		"Testing multiplication...": "Math operations > should multiply numbers correctly",
+	} {
+		if loggedBy[message] != test {
+			t.Errorf("Expected %q logged by %q, got %q", message, test, loggedBy[message])
+		}
+	}
 }
 
 func TestEmptyTestSuiteHandling(t *testing.T) {
@@ -2,10 +2,12 @@ package integration_test
 
 import (
 	"os"
+	"os/exec"
 	"path/filepath"
 	"regexp"
 	"strings"
 	"testing"
+	"time"
 
 	"github.com/zk/3pio/tests/testutil"
 )
@@ -29,6 +31,7 @@ func TestCargoTestBasicProject(t *testing.T) {
 		expectPass  bool
 		minTests    int
 		checkOutput []string
+		failures    []string // Tests the failures section must list
 	}{
 		{
 			name:       "rust-basic all tests",
@@ -61,6 +64,24 @@ func TestCargoTestBasicProject(t *testing.T) {
 				"test_assertion_failure",
 				"test_unexpected_panic",
 				"FAIL",
+				"## Captured output",
+				"computing 2 + 2",
+				"Math is broken!",
+				"[PANICKED AS EXPECTED]",
+				"- expected panic: \"This message does not match\"",
+			},
+			failures: []string{
+				"test_assertion_failure",
+				"test_expected_panic_mismatch",
+				"test_unexpected_panic",
+				"test_assert_with_custom_message",
+				"test_multiple_assertions_first_fails",
+				"test_overflow_panic",
+				"test_index_out_of_bounds",
+				"test_unwrap_none",
+				"test_expect_with_message",
+				"nested_test_fail",
+				"very_nested_panic",
 			},
 		},
 	}
@@ -133,6 +154,11 @@ func TestCargoTestBasicProject(t *testing.T) {
 			if !strings.Contains(string(mainReport), "detected_runner: cargo test") {
 				t.Error("Should detect cargo test runner")
 			}
+			if tc.failures != nil {
+				checkFailuresSection(t, runDir, string(mainReport), tc.failures)
+			} else if strings.Contains(string(mainReport), "## Failures") {
+				t.Error("Expected no failures section for a passing run")
+			}
 
 			// Flexible content verification - warn instead of fail for CI compatibility
 			for _, expected := range tc.checkOutput {
@@ -182,6 +208,107 @@ func TestCargoTestBasicProject(t *testing.T) {
 	}
 }
 
+var failureLinePattern = regexp.MustCompile("^- `([^`]+)`(?:: (.*))? \\(\\[report\\]\\(([^)]+)\\)\\)$")
+
+// checkFailuresSection checks that the failures section of test-run.md lists each of
+// the expected tests with an excerpt and a link to a report that exists
+func checkFailuresSection(t *testing.T, runDir, report string, expected []string) {
+	t.Helper()
+
+	start := strings.Index(report, "## Failures\n\n")
+	if start == -1 {
+		t.Fatalf("Expected a failures section in test-run.md:\n%s", report)
+	}
+	if summary := strings.Index(report, "## Summary"); summary != -1 && summary < start {
+		t.Errorf("Expected the failures section before the summary")
+	}
+	section := report[start+len("## Failures\n\n"):]
+	if end := strings.Index(section, "\n\n"); end != -1 {
+		section = section[:end]
+	}
+
+	excerpts := make(map[string]string)
+	for _, line := range strings.Split(section, "\n") {
+		match := failureLinePattern.FindStringSubmatch(line)
+		if match == nil {
+			t.Errorf("Malformed failures line: %q", line)
+			continue
+		}
+		path := strings.Split(match[1], " → ")
+		if len(path) < 2 {
+			t.Errorf("Expected the full group path in %q", line)
+		}
+		excerpts[path[len(path)-1]] = match[2]
+		if _, err := os.Stat(filepath.Join(runDir, filepath.FromSlash(match[3]))); err != nil {
+			t.Errorf("Broken report link in %q: %v", line, err)
+		}
+	}
+	for _, name := range expected {
+		if excerpt, ok := excerpts[name]; !ok {
+			t.Errorf("Expected %s in the failures section:\n%s", name, section)
+		} else if excerpt == "" {
+			t.Errorf("Expected a failure excerpt for %s", name)
+		}
+	}
+	for _, passing := range []string{"test_normal_pass", "test_expected_panic", "nested_test_pass"} {
+		if _, ok := excerpts[passing]; ok {
+			t.Errorf("Passing test %s listed in the failures section", passing)
+		}
+	}
+}
+
+func TestCargoTestSlowThreshold(t *testing.T) {
+	if _, err := testutil.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+
+	fixtureDir := filepath.Join("..", "fixtures", "rust-edge-cases")
+	if _, err := os.Stat(fixtureDir); os.IsNotExist(err) {
+		t.Skip("rust-edge-cases fixture not found")
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	// test_long_running sleeps for 100ms, well over a 50ms threshold
+	result := testutil.RunThreepio(t, fixtureDir, "--slow-threshold", "0.05", "cargo", "test")
+	runDir := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID)
+
+	mainReport, err := os.ReadFile(filepath.Join(runDir, "test-run.md"))
+	if err != nil {
+		t.Fatalf("Failed to read main report: %v", err)
+	}
+	start := strings.Index(string(mainReport), "## Slowest tests\n\n")
+	if start == -1 {
+		t.Fatalf("Expected a slowest tests section:\n%s", mainReport)
+	}
+	section := string(mainReport[start:])
+	if end := strings.Index(section, "\n\n## "); end != -1 {
+		section = section[:end]
+	}
+	// Doctests can take longer than the sleep, since their time includes compiling them
+	rows := strings.Split(section, "\n")[4:]
+	if len(rows) == 0 || len(rows) > 10 || !strings.Contains(section, "| test_long_running |") {
+		t.Errorf("Expected test_long_running among at most 10 slowest tests:\n%s", section)
+	}
+
+	var groupReport []byte
+	_ = filepath.Walk(runDir, func(path string, info os.FileInfo, err error) error {
+		if err == nil && strings.HasSuffix(path, "index.md") {
+			if content, _ := os.ReadFile(path); strings.Contains(string(content), "test_long_running") {
+				groupReport = content
+			}
+		}
+		return nil
+	})
+	if !strings.Contains(string(groupReport), "Sorted by duration") {
+		t.Fatalf("Expected test_long_running's group report sorted by duration:\n%s", groupReport)
+	}
+	results := string(groupReport[strings.Index(string(groupReport), "## Test case results"):])
+	firstTest := results[strings.Index(results, "\n- ")+3:]
+	if !strings.Contains(firstTest[:strings.Index(firstTest, "\n")], "test_long_running") {
+		t.Errorf("Expected test_long_running listed first:\n%s", results)
+	}
+}
+
 func TestCargoTestWithFlags(t *testing.T) {
 	if _, err := testutil.LookPath("cargo"); err != nil {
 		t.Skip("cargo not found in PATH")
@@ -200,7 +327,17 @@ func TestCargoTestWithFlags(t *testing.T) {
 		{
 			name:     "lib tests only",
 			args:     []string{"cargo", "test", "--lib"},
-			contains: []string{"unit_tests"},
+			contains: []string{"unit_tests", "○ test_ignored [IGNORED]"},
+		},
+		{
+			name:     "include ignored tests",
+			args:     []string{"cargo", "test", "--lib", "--", "--include-ignored"},
+			contains: []string{"✓ test_ignored"},
+		},
+		{
+			name:     "ignored tests only",
+			args:     []string{"cargo", "test", "--lib", "--", "--ignored"},
+			contains: []string{"✓ test_ignored"},
 		},
 		{
 			name:     "doc tests only",
@@ -259,6 +396,175 @@ func TestCargoTestWithFlags(t *testing.T) {
 	}
 }
 
+func TestCargoBench(t *testing.T) {
+	if _, err := testutil.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+
+	fixtureDir := filepath.Join("..", "fixtures", "rust-benchmarks")
+	if _, err := os.Stat(fixtureDir); os.IsNotExist(err) {
+		t.Skip("rust-benchmarks fixture not found")
+	}
+
+	// Only the libtest #[bench] functions; the criterion target has its own harness
+	result := testutil.RunThreepio(t, fixtureDir, "cargo", "bench", "--lib")
+
+	runDir := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID)
+	allReports := ""
+	err := filepath.Walk(runDir, func(path string, info os.FileInfo, err error) error {
+		if err != nil {
+			return err
+		}
+		if strings.HasSuffix(path, ".md") {
+			content, err := os.ReadFile(path)
+			if err != nil {
+				return err
+			}
+			allReports += string(content) + "\n"
+		}
+		return nil
+	})
+	if err != nil {
+		t.Fatalf("Failed to read reports: %v", err)
+	}
+
+	// Toolchains that can't build #![feature(test)] report a build failure instead
+	if strings.Contains(allReports, "exit_reason: build_failed") {
+		t.Log("Benchmarks could not be built on this toolchain; build errors were reported")
+		return
+	}
+
+	for _, expected := range []string{"## Benchmarks", "| bench_fibonacci_10 |", "| bench_factorial_10 |", "- Benchmarks: 2"} {
+		if !strings.Contains(allReports, expected) {
+			t.Logf("Warning: Expected content '%s' not found in reports (may vary between environments)", expected)
+		} else {
+			t.Logf("Found expected content: %s", expected)
+		}
+	}
+}
+
+func TestCargoBenchCriterion(t *testing.T) {
+	if _, err := testutil.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+
+	fixtureDir := filepath.Join("..", "fixtures", "rust-benchmarks")
+	if _, err := os.Stat(fixtureDir); os.IsNotExist(err) {
+		t.Skip("rust-benchmarks fixture not found")
+	}
+
+	// Keep the criterion run short; the fixture benches fib 10 and fact 10
+	result := testutil.RunThreepio(t, fixtureDir, "--runner", "criterion", "cargo", "bench", "--bench", "simple_bench",
+		"--", "--sample-size", "10", "--warm-up-time", "0.1", "--measurement-time", "0.1")
+
+	runDir := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID)
+	allReports := ""
+	err := filepath.Walk(runDir, func(path string, info os.FileInfo, err error) error {
+		if err != nil {
+			return err
+		}
+		if strings.HasSuffix(path, ".md") {
+			content, err := os.ReadFile(path)
+			if err != nil {
+				return err
+			}
+			allReports += string(content) + "\n"
+		}
+		return nil
+	})
+	if err != nil {
+		t.Fatalf("Failed to read reports: %v", err)
+	}
+
+	if !strings.Contains(allReports, "detected_runner: cargo bench (criterion)") {
+		t.Logf("Warning: criterion runner not reported as detected runner")
+	}
+
+	for _, expected := range []string{"# Test Report: simple-bench > fib 10", "# Test Report: simple-bench > fact 10",
+		"| Benchmark | Mean (ns) | Median (ns) | Std. dev. (ns) | Change |", "- Benchmarks: 2"} {
+		if !strings.Contains(allReports, expected) {
+			t.Logf("Warning: Expected content '%s' not found in reports (may vary between environments)", expected)
+		} else {
+			t.Logf("Found expected content: %s", expected)
+		}
+	}
+}
+
+func TestCargoTestBinaryCrash(t *testing.T) {
+	if _, err := testutil.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+
+	fixtureDir := filepath.Join("..", "fixtures", "rust-crash")
+	if _, err := os.Stat(fixtureDir); os.IsNotExist(err) {
+		t.Skip("rust-crash fixture not found")
+	}
+
+	// test_abort calls std::process::abort(), killing the test binary mid-run.
+	// One test thread makes test_abort the only test running when it happens.
+	result := testutil.RunThreepio(t, fixtureDir, "cargo", "test", "--", "--test-threads=1")
+	if result.ExitCode == 0 {
+		t.Errorf("Expected non-zero exit code after the test binary crashed")
+	}
+
+	reportPath := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID, "test-run.md")
+	content, err := os.ReadFile(reportPath)
+	if err != nil {
+		t.Fatalf("Failed to read test-run.md: %v", err)
+	}
+	report := string(content)
+
+	for _, expected := range []string{"exit_reason: runner_crashed", "## Test binary crashed", "while running `tests::test_abort`"} {
+		if !strings.Contains(report, expected) {
+			t.Errorf("Expected %q in test-run.md, got:\n%s", expected, report)
+		}
+	}
+}
+
+func TestCargoTestRunTimeout(t *testing.T) {
+	if _, err := testutil.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+
+	fixtureDir := filepath.Join("..", "fixtures", "rust-timeout")
+	if _, err := os.Stat(fixtureDir); os.IsNotExist(err) {
+		t.Skip("rust-timeout fixture not found")
+	}
+
+	// Build the tests first, so the timeout fires while test_sleeps_past_timeout,
+	// which sleeps for two minutes, is running rather than during the compile
+	build := exec.Command("cargo", "test", "--no-run")
+	build.Dir = fixtureDir
+	if output, err := build.CombinedOutput(); err != nil {
+		t.Fatalf("Failed to build the rust-timeout tests: %v\n%s", err, output)
+	}
+
+	start := time.Now()
+	result := testutil.RunThreepio(t, fixtureDir, "--timeout", "5", "cargo", "test")
+	if elapsed := time.Since(start); elapsed > time.Minute {
+		t.Errorf("Expected the run to stop after the timeout, took %s", elapsed)
+	}
+	if result.ExitCode != 124 {
+		t.Errorf("Expected exit code 124 after the timeout, got %d", result.ExitCode)
+	}
+
+	reportPath := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID, "test-run.md")
+	content, err := os.ReadFile(reportPath)
+	if err != nil {
+		t.Fatalf("Failed to read test-run.md: %v", err)
+	}
+	report := string(content)
+
+	for _, expected := range []string{"exit_reason: timeout", "Run timed out after 5s"} {
+		if !strings.Contains(report, expected) {
+			t.Errorf("Expected %q in test-run.md, got:\n%s", expected, report)
+		}
+	}
+	if !strings.Contains(report, "INTERRUPTED") {
+		t.Errorf("Expected the running group to be INTERRUPTED in test-run.md, got:\n%s", report)
+	}
+}
+
 func TestCargoNextest(t *testing.T) {
 	// Check if cargo-nextest is installed
 	if _, err := testutil.LookPath("cargo"); err != nil {
@@ -307,6 +613,16 @@ func TestCargoNextest(t *testing.T) {
 				"rust-basic",
 			},
 		},
+		{
+			name:       "nextest failures with retries",
+			fixture:    "rust-edge-cases",
+			args:       []string{"cargo", "nextest", "run", "--retries", "1", "--no-fail-fast"},
+			expectPass: false,
+			checkOutput: []string{
+				"test_assertion_failure",
+				"Attempts: 2 (FAIL, FAIL)",
+			},
+		},
 	}
 
 	for _, tc := range testCases {
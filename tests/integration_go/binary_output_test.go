@@ -0,0 +1,69 @@
+package integration_test
+
+import (
+	"bytes"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+	"unicode/utf8"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestBinaryOutput runs tests that write bytes that aren't valid UTF-8 to stdout, and
+// checks the run completes with readable reports while output.log keeps the bytes
+func TestBinaryOutput(t *testing.T) {
+	if _, err := testutil.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+
+	fixtureDir := filepath.Join(fixturesDir, "rust-binary-output")
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	result := testutil.RunThreepio(t, fixtureDir, "cargo", "test")
+	if result.ExitCode != 1 {
+		t.Fatalf("Expected exit code 1 for the failing test, got %d\nstdout:\n%s\nstderr:\n%s", result.ExitCode, result.Stdout, result.Stderr)
+	}
+	if !utf8.ValidString(result.Stdout) {
+		t.Errorf("Expected the console output to be valid UTF-8, got:\n%q", result.Stdout)
+	}
+	runDir := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID)
+
+	output, err := os.ReadFile(filepath.Join(runDir, "output.log"))
+	if err != nil {
+		t.Fatalf("Failed to read output.log: %v", err)
+	}
+	if !bytes.Contains(output, []byte("header: \xff\xfe\x00\x01 end of header")) {
+		t.Errorf("Expected output.log to keep the raw bytes, got:\n%q", output)
+	}
+
+	testRun, err := os.ReadFile(filepath.Join(runDir, "test-run.md"))
+	if err != nil {
+		t.Fatalf("Failed to read test-run.md: %v", err)
+	}
+	if !strings.Contains(string(testRun), "test_binary_failure") {
+		t.Errorf("Expected test-run.md to list the failed test, got:\n%s", testRun)
+	}
+
+	// Every report is valid UTF-8, and all three tests are in the group's report
+	var reports strings.Builder
+	for _, file := range listRunFiles(t, runDir) {
+		if !strings.HasSuffix(file, ".md") {
+			continue
+		}
+		data, err := os.ReadFile(filepath.Join(runDir, filepath.FromSlash(file)))
+		if err != nil {
+			t.Fatalf("Failed to read %s: %v", file, err)
+		}
+		if !utf8.Valid(data) {
+			t.Errorf("Expected %s to be valid UTF-8, got:\n%q", file, data)
+		}
+		reports.Write(data)
+	}
+	for _, name := range []string{"test_binary_dump", "test_valid_text", "test_binary_failure", "decoded \uFFFD\uFFFD"} {
+		if !strings.Contains(reports.String(), name) {
+			t.Errorf("Expected the reports to mention %q", name)
+		}
+	}
+}
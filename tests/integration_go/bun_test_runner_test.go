@@ -0,0 +1,61 @@
+package integration_test
+
+import (
+	"path/filepath"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestBunTestRunner runs bun test and checks its text output lands in file groups, with
+// the describe block as a group and every outcome of the tests
+func TestBunTestRunner(t *testing.T) {
+	if _, err := testutil.LookPath("bun"); err != nil {
+		t.Skip("bun not found in PATH")
+	}
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "bun-test"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	result := testutil.RunThreepio(t, fixtureDir, "bun", "test")
+	if result.ExitCode != 1 {
+		t.Fatalf("Expected exit code 1 for the failing test, got %d:\n%s", result.ExitCode, result.Stdout)
+	}
+	summary := readSummaryJSON(t, fixtureDir, result.RunID)
+	if summary.DetectedRunner != "bun test" {
+		t.Errorf("Expected the bun test runner, got %q", summary.DetectedRunner)
+	}
+	if summary.Totals.Total != 5 || summary.Totals.Passed != 2 || summary.Totals.Failed != 1 || summary.Totals.Skipped != 2 {
+		t.Errorf("Expected 5 tests: 2 passed, 1 failed and 2 skipped, got %+v", summary.Totals)
+	}
+
+	statuses := map[string]string{}
+	files := map[string]string{}
+	runDir := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID)
+	reports := map[string]string{}
+	for _, group := range summary.Groups {
+		reports[group.Name] = filepath.Join(runDir, group.Report)
+		for _, tc := range group.TestCases {
+			statuses[tc.Name] = tc.Status
+			files[tc.Name] = filepath.Base(group.Path[0])
+		}
+	}
+	for name, want := range map[string]string{
+		"adds an item":       "PASS",
+		"applies a discount": "FAIL",
+		"ships abroad":       "SKIP",
+		"adds numbers":       "PASS",
+		"divides by zero":    "SKIP",
+	} {
+		if statuses[name] != want {
+			t.Errorf("Expected %q to be %s, got %q", name, want, statuses[name])
+		}
+	}
+	if files["applies a discount"] != "cart.test.ts" || files["adds numbers"] != "math.test.ts" {
+		t.Errorf("Expected the tests under their files, got %v", files)
+	}
+
+	testutil.AssertFileContains(t, reports["cart"], "applies a discount", "Received: 90")
+}
@@ -0,0 +1,37 @@
+package integration_test
+
+import (
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestCargoBuildDiff runs rust-basic with and without a feature and checks 3pio diff
+// won't compare the runs unless forced, as their results may differ for that alone
+func TestCargoBuildDiff(t *testing.T) {
+	if _, err := testutil.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+	fixtureDir := filepath.Join(fixturesDir, "rust-basic")
+
+	before := testutil.RunThreepio(t, fixtureDir, "cargo", "test")
+	after := testutil.RunThreepio(t, fixtureDir, "cargo", "test", "--features", "extra")
+	if before.ExitCode != 0 || after.ExitCode != 0 {
+		t.Fatalf("Expected both runs to pass, got %d and %d. Stdout: %s", before.ExitCode, after.ExitCode, after.Stdout)
+	}
+	runDir := filepath.Join(fixtureDir, ".3pio", "runs", after.RunID)
+	testutil.AssertFileContains(t, filepath.Join(runDir, "test-run.md"), "- Cargo build: profile dev, features extra")
+	testutil.AssertFileContains(t, filepath.Join(runDir, "summary.json"), `"features": [`, `"extra"`)
+
+	diff := testutil.RunThreepio(t, fixtureDir, "diff", before.RunID, after.RunID)
+	if diff.ExitCode != 1 || !strings.Contains(diff.Stderr, "built differently") {
+		t.Errorf("Expected diff to refuse runs built with other features, got exit code %d. Stderr: %s", diff.ExitCode, diff.Stderr)
+	}
+
+	forced := testutil.RunThreepio(t, fixtureDir, "diff", before.RunID, after.RunID, "--force")
+	if forced.ExitCode != 0 || !strings.Contains(forced.Stdout, "Warning: the runs were built differently") {
+		t.Errorf("Expected diff --force to compare the runs with a warning, got exit code %d. Stdout: %s", forced.ExitCode, forced.Stdout)
+	}
+}
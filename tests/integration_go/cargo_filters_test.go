@@ -0,0 +1,50 @@
+package integration_test
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestCargoFilters checks the report names the filters a cargo test ran with, and that
+// filters which leave no test to run end the run with no_tests_found
+func TestCargoFilters(t *testing.T) {
+	if _, err := testutil.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+	fixtureDir := filepath.Join(fixturesDir, "rust-basic")
+
+	testCases := []struct {
+		name     string
+		args     []string
+		exitCode int
+		reason   string
+		filters  string
+	}{
+		{"matching filter", []string{"cargo", "test", "tests::test_add", "--", "--exact"}, 0, "ok", "- Filters: tests::test_add (exact)"},
+		{"misspelled filter", []string{"cargo", "test", "tset_add"}, 5, "no_tests_found", "- Filters: tset_add"},
+		{"skip excludes everything", []string{"cargo", "test", "--", "--skip", "test_"}, 5, "no_tests_found", "- Filters: --skip test_"},
+	}
+	for _, tc := range testCases {
+		t.Run(tc.name, func(t *testing.T) {
+			testutil.CleanupTestRuns(t, fixtureDir)
+
+			result := testutil.RunThreepio(t, fixtureDir, tc.args...)
+			if result.ExitCode != tc.exitCode {
+				t.Errorf("Expected exit code %d, got %d. Stdout: %s", tc.exitCode, result.ExitCode, result.Stdout)
+			}
+			report, err := os.ReadFile(filepath.Join(fixtureDir, ".3pio", "runs", result.RunID, "test-run.md"))
+			if err != nil {
+				t.Fatalf("Failed to read test-run.md: %v", err)
+			}
+			for _, want := range []string{tc.filters + "\n", "exit_reason: " + tc.reason + "\n"} {
+				if !strings.Contains(string(report), want) {
+					t.Errorf("Expected test-run.md to contain %q, got:\n%s", want, report)
+				}
+			}
+		})
+	}
+}
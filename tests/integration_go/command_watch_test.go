@@ -1,111 +1,161 @@
 package integration_test
 
 import (
+	"context"
+	"errors"
+	"os"
 	"os/exec"
 	"path/filepath"
 	"strings"
 	"testing"
-)
+	"time"
 
-// TestJestWatchModeRejection verifies that jest --watch is rejected
-func TestJestWatchModeRejection(t *testing.T) {
-	testDir := filepath.Join(fixturesDir, "basic-jest")
-	cleanTestDir(t, testDir)
+	"github.com/zk/3pio/tests/testutil"
+)
 
-	// Try to run jest with --watch
-	cmd := exec.Command(getBinaryPath(), "npx", "jest", "--watch")
-	cmd.Dir = testDir
+// runWatchCommand runs 3pio with args in dir, killing it after two minutes so a watcher
+// that wasn't stopped fails the test instead of hanging it
+func runWatchCommand(t *testing.T, dir string, args ...string) (string, int) {
+	t.Helper()
+	ctx, cancel := context.WithTimeout(context.Background(), 2*time.Minute)
+	defer cancel()
 
+	cmd := exec.CommandContext(ctx, getBinaryPath(), args...)
+	cmd.Dir = dir
 	output, err := cmd.CombinedOutput()
-
-	// Should fail with non-zero exit code
-	if err == nil {
-		t.Error("Expected jest --watch to be rejected, but it succeeded")
+	if ctx.Err() != nil {
+		t.Fatalf("3pio %s was still running after 2 minutes, watching for changes:\n%s", strings.Join(args, " "), output)
 	}
-
-	// Check for clear error message
-	outputStr := string(output)
-	if !strings.Contains(outputStr, "watch") || !strings.Contains(outputStr, "not supported") {
-		t.Errorf("Expected clear error message about watch mode not being supported, got: %s", outputStr)
+	exitCode := 0
+	var exitErr *exec.ExitError
+	if errors.As(err, &exitErr) {
+		exitCode = exitErr.ExitCode()
+	} else if err != nil {
+		t.Fatalf("Failed to run 3pio: %v", err)
 	}
+	return string(output), exitCode
+}
 
-	// Verify exit code is non-zero
-	if exitErr, ok := err.(*exec.ExitError); ok {
-		if exitErr.ExitCode() == 0 {
-			t.Error("Expected non-zero exit code for watch mode rejection")
-		}
+// assertRunCompleted checks that a run finished and wrote its report
+func assertRunCompleted(t *testing.T, dir, output string) {
+	t.Helper()
+	entries, err := os.ReadDir(filepath.Join(dir, ".3pio", "runs"))
+	if err != nil || len(entries) != 1 {
+		t.Fatalf("Expected one run in %s, got %v (%v):\n%s", dir, entries, err, output)
+	}
+	assertFileExists(t, filepath.Join(dir, ".3pio", "runs", entries[0].Name(), "test-run.md"))
+	if strings.Contains(output, "not supported") {
+		t.Errorf("Expected the watch flag to be removed, not refused:\n%s", output)
 	}
+}
 
-	// Verify no reports were created
-	assertNoFile(t, filepath.Join(testDir, ".3pio", "runs"))
+// TestJestWatchFlagsRemoved checks that jest's watch flags are dropped with a warning
+// and the tests run once
+func TestJestWatchFlagsRemoved(t *testing.T) {
+	testDir := filepath.Join(fixturesDir, "basic-jest")
+	for _, tc := range []struct {
+		args    []string
+		warning string
+	}{
+		{[]string{"npx", "jest", "--watch"}, "Warning: removed --watch, which would watch for changes and never exit"},
+		{[]string{"npx", "jest", "--watchAll"}, "Warning: removed --watchAll"},
+		{[]string{"npx", "jest", "--watchAll=true"}, "Warning: removed --watchAll=true"},
+		{[]string{"npm", "test", "--", "--watch"}, "Warning: removed --watch"},
+		{[]string{"yarn", "test", "--watch"}, "Warning: removed --watch"},
+		// nodemon never runs, so it needn't be installed
+		{[]string{"nodemon", "--watch", "src", "--exec", "npx jest"}, "Warning: running npx jest instead of nodemon"},
+	} {
+		t.Run(strings.Join(tc.args, " "), func(t *testing.T) {
+			cleanTestDir(t, testDir)
+			output, _ := runWatchCommand(t, testDir, tc.args...)
+			if !strings.Contains(output, tc.warning) {
+				t.Errorf("Expected %q, got:\n%s", tc.warning, output)
+			}
+			assertRunCompleted(t, testDir, output)
+		})
+	}
 }
 
-// TestJestWatchAllModeRejection verifies that jest --watchAll is rejected
-func TestJestWatchAllModeRejection(t *testing.T) {
+// TestWatchModeWarningOnStderr checks that the warning is still shown with --output
+// json, which keeps stdout for the events
+func TestWatchModeWarningOnStderr(t *testing.T) {
 	testDir := filepath.Join(fixturesDir, "basic-jest")
 	cleanTestDir(t, testDir)
 
-	// Try to run jest with --watchAll
-	cmd := exec.Command(getBinaryPath(), "npx", "jest", "--watchAll")
-	cmd.Dir = testDir
-
-	output, err := cmd.CombinedOutput()
-
-	// Should fail with non-zero exit code
-	if err == nil {
-		t.Error("Expected jest --watchAll to be rejected, but it succeeded")
+	result := testutil.RunThreepio(t, testDir, "--output", "json", "npx", "jest", "--watch")
+	if !strings.Contains(result.Stderr, "Warning: removed --watch") {
+		t.Errorf("Expected the warning on stderr, got:\n%s", result.Stderr)
 	}
-
-	// Check for clear error message
-	outputStr := string(output)
-	if !strings.Contains(outputStr, "watch") || !strings.Contains(outputStr, "not supported") {
-		t.Errorf("Expected clear error message about watch mode not being supported, got: %s", outputStr)
+	if strings.Contains(result.Stdout, "Warning:") {
+		t.Errorf("Expected only events on stdout, got:\n%s", result.Stdout)
 	}
 }
 
-// TestVitestWatchModeRejection verifies that vitest --watch is rejected
-func TestVitestWatchModeRejection(t *testing.T) {
-	testDir := filepath.Join(fixturesDir, "basic-vitest")
+// TestJestWatchAllFalseKept checks that --watchAll=false, which turns watching off, is
+// passed on without a warning
+func TestJestWatchAllFalseKept(t *testing.T) {
+	testDir := filepath.Join(fixturesDir, "basic-jest")
 	cleanTestDir(t, testDir)
 
-	// Try to run vitest with --watch
-	cmd := exec.Command(getBinaryPath(), "npx", "vitest", "--watch")
-	cmd.Dir = testDir
-
-	output, err := cmd.CombinedOutput()
-
-	// Should fail with non-zero exit code
-	if err == nil {
-		t.Error("Expected vitest --watch to be rejected, but it succeeded")
-	}
-
-	// Check for clear error message
-	outputStr := string(output)
-	if !strings.Contains(outputStr, "watch") || !strings.Contains(outputStr, "not supported") {
-		t.Errorf("Expected clear error message about watch mode not being supported, got: %s", outputStr)
+	output, _ := runWatchCommand(t, testDir, "npx", "jest", "--watchAll=false")
+	if strings.Contains(output, "Warning: removed") {
+		t.Errorf("Expected no warning for --watchAll=false, got:\n%s", output)
 	}
+	assertRunCompleted(t, testDir, output)
 }
 
-// TestVitestDefaultWatchModeRejection verifies that plain 'vitest' (which defaults to watch) is rejected
-func TestVitestDefaultWatchModeRejection(t *testing.T) {
+// TestVitestWatchModeStopped checks that vitest runs once whether it is given --watch, the
+// watch subcommand or no subcommand at all
+func TestVitestWatchModeStopped(t *testing.T) {
 	testDir := filepath.Join(fixturesDir, "basic-vitest")
-	cleanTestDir(t, testDir)
-
-	// Try to run vitest without 'run' (defaults to watch mode)
-	cmd := exec.Command(getBinaryPath(), "npx", "vitest")
-	cmd.Dir = testDir
-
-	output, err := cmd.CombinedOutput()
-
-	// Should fail with non-zero exit code
-	if err == nil {
-		t.Error("Expected plain vitest (watch mode default) to be rejected, but it succeeded")
+	for _, tc := range []struct {
+		args    []string
+		warning string
+	}{
+		{[]string{"npx", "vitest", "--watch"}, "Warning: removed --watch"},
+		{[]string{"npx", "vitest", "-w"}, "Warning: removed -w"},
+		{[]string{"npx", "vitest", "watch"}, "Warning: running vitest run instead of vitest watch"},
+		{[]string{"npx", "vitest"}, "Warning: running vitest run instead of vitest, which would watch for changes and never exit"},
+	} {
+		t.Run(strings.Join(tc.args, " "), func(t *testing.T) {
+			cleanTestDir(t, testDir)
+			output, _ := runWatchCommand(t, testDir, tc.args...)
+			if !strings.Contains(output, tc.warning) {
+				t.Errorf("Expected %q, got:\n%s", tc.warning, output)
+			}
+			assertRunCompleted(t, testDir, output)
+		})
 	}
+}
 
-	// Check for clear error message
-	outputStr := string(output)
-	if !strings.Contains(outputStr, "watch") || !strings.Contains(outputStr, "not supported") {
-		t.Errorf("Expected clear error message about watch mode not being supported, got: %s", outputStr)
+// TestStrictArgsRefusesWatchMode checks that --strict-args refuses watch flags before
+// the run starts
+func TestStrictArgsRefusesWatchMode(t *testing.T) {
+	for _, tc := range []struct {
+		fixture string
+		args    []string
+	}{
+		{"basic-jest", []string{"npx", "jest", "--watch"}},
+		{"basic-jest", []string{"npx", "jest", "--watchAll"}},
+		{"basic-jest", []string{"npm", "test", "--", "--watch"}},
+		{"basic-jest", []string{"yarn", "test", "--watch"}},
+		{"basic-vitest", []string{"npx", "vitest", "--watch"}},
+		{"basic-vitest", []string{"npx", "vitest"}},
+		{"basic-jest", []string{"nodemon", "--exec", "npx jest"}},
+	} {
+		t.Run(strings.Join(tc.args, " "), func(t *testing.T) {
+			testDir := filepath.Join(fixturesDir, tc.fixture)
+			cleanTestDir(t, testDir)
+
+			output, exitCode := runWatchCommand(t, testDir, append([]string{"--strict-args"}, tc.args...)...)
+			if exitCode == 0 {
+				t.Errorf("Expected a non-zero exit code, got 0:\n%s", output)
+			}
+			if !strings.Contains(output, "watch mode is not supported with --strict-args") {
+				t.Errorf("Expected a clear error about watch mode, got:\n%s", output)
+			}
+			assertNoFile(t, filepath.Join(testDir, ".3pio", "runs"))
+		})
 	}
 }
 
@@ -132,66 +182,36 @@ func TestPytestWatchModeRejection(t *testing.T) {
 	}
 }
 
-// TestPtestWatchModeRejection verifies that ptw (pytest-watch alias) is rejected
-func TestPtestWatchAliasRejection(t *testing.T) {
-	testDir := filepath.Join(fixturesDir, "basic-pytest")
-	cleanTestDir(t, testDir)
-
-	// Try to run ptw (pytest-watch alias)
-	cmd := exec.Command(getBinaryPath(), "ptw")
-	cmd.Dir = testDir
-
-	output, err := cmd.CombinedOutput()
-
-	// Should fail with non-zero exit code
-	if err == nil {
-		t.Error("Expected ptw to be rejected, but it succeeded")
-	}
-
-	// Check for clear error message
-	outputStr := string(output)
-	if !strings.Contains(outputStr, "watch") || !strings.Contains(outputStr, "not supported") {
-		t.Errorf("Expected clear error message about watch mode not being supported, got: %s", outputStr)
-	}
-}
-
-// TestNpmTestWatchModeRejection verifies that npm test -- --watch is rejected
-func TestNpmTestWatchModeRejection(t *testing.T) {
+// TestWatchWrapperRejection checks that a watch wrapper running a command 3pio can't
+// pick out of its arguments is refused before the run starts
+func TestWatchWrapperRejection(t *testing.T) {
 	testDir := filepath.Join(fixturesDir, "basic-jest")
 	cleanTestDir(t, testDir)
 
-	// Try to run npm test with watch flag
-	cmd := exec.Command(getBinaryPath(), "npm", "test", "--", "--watch")
-	cmd.Dir = testDir
-
-	output, err := cmd.CombinedOutput()
-
-	// Should fail with non-zero exit code
-	if err == nil {
-		t.Error("Expected npm test -- --watch to be rejected, but it succeeded")
+	output, exitCode := runWatchCommand(t, testDir, "npx", "watchexec", "--", "npx", "jest")
+	if exitCode == 0 {
+		t.Errorf("Expected a non-zero exit code, got 0:\n%s", output)
 	}
-
-	// Check for clear error message
-	outputStr := string(output)
-	if !strings.Contains(outputStr, "watch") || !strings.Contains(outputStr, "not supported") {
-		t.Errorf("Expected clear error message about watch mode not being supported, got: %s", outputStr)
+	if !strings.Contains(output, "watch mode is not supported: watchexec would watch for changes") {
+		t.Errorf("Expected a clear error about watch mode, got:\n%s", output)
 	}
+	assertNoFile(t, filepath.Join(testDir, ".3pio", "runs"))
 }
 
-// TestYarnTestWatchModeRejection verifies that yarn test --watch is rejected
-func TestYarnTestWatchModeRejection(t *testing.T) {
-	testDir := filepath.Join(fixturesDir, "basic-jest")
+// TestPtestWatchModeRejection verifies that ptw (pytest-watch alias) is rejected
+func TestPtestWatchAliasRejection(t *testing.T) {
+	testDir := filepath.Join(fixturesDir, "basic-pytest")
 	cleanTestDir(t, testDir)
 
-	// Try to run yarn test with watch flag
-	cmd := exec.Command(getBinaryPath(), "yarn", "test", "--watch")
+	// Try to run ptw (pytest-watch alias)
+	cmd := exec.Command(getBinaryPath(), "ptw")
 	cmd.Dir = testDir
 
 	output, err := cmd.CombinedOutput()
 
 	// Should fail with non-zero exit code
 	if err == nil {
-		t.Error("Expected yarn test --watch to be rejected, but it succeeded")
+		t.Error("Expected ptw to be rejected, but it succeeded")
 	}
 
 	// Check for clear error message
@@ -200,38 +220,3 @@ func TestYarnTestWatchModeRejection(t *testing.T) {
 		t.Errorf("Expected clear error message about watch mode not being supported, got: %s", outputStr)
 	}
 }
-
-// TestWatchModeErrorMessage verifies the error message is clear and helpful
-func TestWatchModeErrorMessage(t *testing.T) {
-	testDir := filepath.Join(fixturesDir, "basic-jest")
-	cleanTestDir(t, testDir)
-
-	// Try to run jest with --watch
-	cmd := exec.Command(getBinaryPath(), "npx", "jest", "--watch")
-	cmd.Dir = testDir
-
-	output, err := cmd.CombinedOutput()
-
-	if err == nil {
-		t.Fatal("Expected watch mode to be rejected")
-	}
-
-	outputStr := string(output)
-
-	// Check for helpful error message components
-	requiredPhrases := []string{
-		"watch",
-		"not supported",
-	}
-
-	for _, phrase := range requiredPhrases {
-		if !strings.Contains(strings.ToLower(outputStr), phrase) {
-			t.Errorf("Error message missing required phrase '%s'. Got: %s", phrase, outputStr)
-		}
-	}
-
-	// Verify it suggests using run mode
-	if strings.Contains(outputStr, "vitest") && !strings.Contains(outputStr, "vitest run") {
-		t.Error("Error message for vitest should suggest using 'vitest run'")
-	}
-}
@@ -0,0 +1,127 @@
+package integration_test
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestCargoTestCustomHarness runs a harness = false target built on libtest-mimic,
+// which reports its tests through the threepio-reporter crate
+func TestCargoTestCustomHarness(t *testing.T) {
+	if _, err := testutil.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+
+	fixtureDir := filepath.Join(fixturesDir, "rust-custom-harness")
+	if _, err := os.Stat(fixtureDir); os.IsNotExist(err) {
+		t.Skip("rust-custom-harness fixture not found")
+	}
+
+	result := testutil.RunThreepio(t, fixtureDir, "cargo", "test", "--test", "custom_harness")
+	runDir := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID)
+	if output, err := os.ReadFile(filepath.Join(runDir, "output.log")); err == nil &&
+		strings.Contains(string(output), "failed to get `libtest-mimic`") {
+		t.Skip("libtest-mimic can't be downloaded")
+	}
+	content, err := os.ReadFile(filepath.Join(runDir, "test-run.md"))
+	if err != nil {
+		t.Fatalf("Failed to read test-run.md: %v", err)
+	}
+	report := string(content)
+	if result.ExitCode == 0 {
+		t.Errorf("Expected non-zero exit code, fails_on_purpose fails")
+	}
+
+	// A failed custom harness is not a crashed test binary
+	if strings.Contains(report, "## Test binary crashed") {
+		t.Errorf("Expected no crash section, got:\n%s", report)
+	}
+
+	allReports := ""
+	err = filepath.Walk(runDir, func(path string, info os.FileInfo, err error) error {
+		if err != nil {
+			return err
+		}
+		if strings.HasSuffix(path, ".md") {
+			content, err := os.ReadFile(path)
+			if err != nil {
+				return err
+			}
+			allReports += string(content) + "\n"
+		}
+		return nil
+	})
+	if err != nil {
+		t.Fatalf("Failed to read reports: %v", err)
+	}
+
+	for _, expected := range []string{"custom-harness", "math", "strings", "adds_negative", "reverses", "expected 7, got 6"} {
+		if !strings.Contains(allReports, expected) {
+			t.Errorf("Expected %q in the reports, got:\n%s", expected, allReports)
+		}
+	}
+	if strings.Contains(report, "malformed IPC") {
+		t.Errorf("The reporter wrote malformed events:\n%s", report)
+	}
+}
+
+// TestCargoTestPlainHarness runs a harness = false target that doesn't report to 3pio:
+// it becomes one group, failing by its exit code, with its output kept as is
+func TestCargoTestPlainHarness(t *testing.T) {
+	if _, err := testutil.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+
+	fixtureDir := filepath.Join(fixturesDir, "rust-plain-harness")
+	if _, err := os.Stat(fixtureDir); os.IsNotExist(err) {
+		t.Skip("rust-plain-harness fixture not found")
+	}
+
+	result := testutil.RunThreepio(t, fixtureDir, "cargo", "test")
+	if result.ExitCode == 0 {
+		t.Errorf("Expected non-zero exit code, plain_harness exits with 1")
+	}
+	runDir := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID)
+	content, err := os.ReadFile(filepath.Join(runDir, "test-run.md"))
+	if err != nil {
+		t.Fatalf("Failed to read test-run.md: %v", err)
+	}
+	report := string(content)
+	for _, expected := range []string{"exit_reason: tests_failed", "rust-plain-harness", "plain-harness"} {
+		if !strings.Contains(report, expected) {
+			t.Errorf("Expected %q in test-run.md, got:\n%s", expected, report)
+		}
+	}
+	if strings.Contains(report, "phantom_check") || strings.Contains(report, "## Test binary crashed") {
+		t.Errorf("Expected the harness output not to be parsed as tests, got:\n%s", report)
+	}
+
+	var groupReport string
+	err = filepath.Walk(filepath.Join(runDir, "reports"), func(path string, info os.FileInfo, err error) error {
+		if err != nil {
+			return err
+		}
+		if strings.HasSuffix(path, ".md") {
+			content, err := os.ReadFile(path)
+			if err != nil {
+				return err
+			}
+			if strings.Contains(string(content), "group_name: plain-harness") {
+				groupReport = string(content)
+			}
+		}
+		return nil
+	})
+	if err != nil {
+		t.Fatalf("Failed to read group reports: %v", err)
+	}
+	for _, expected := range []string{"status: FAIL", "Custom harness — individual tests not available", "test phantom_check ... ok"} {
+		if !strings.Contains(groupReport, expected) {
+			t.Errorf("Expected %q in the plain-harness report, got:\n%s", expected, groupReport)
+		}
+	}
+}
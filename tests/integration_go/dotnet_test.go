@@ -0,0 +1,94 @@
+package integration_test
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// skipWithoutDotnet skips the dotnet test tests unless THREEPIO_DOTNET_TESTS=1, as the
+// .NET SDK is too heavy to install for every CI run
+func skipWithoutDotnet(t *testing.T) {
+	t.Helper()
+	if os.Getenv("THREEPIO_DOTNET_TESTS") != "1" {
+		t.Skip("set THREEPIO_DOTNET_TESTS=1 to run the dotnet test tests")
+	}
+	if _, err := testutil.LookPath("dotnet"); err != nil {
+		t.Skip("dotnet not found in PATH")
+	}
+}
+
+// TestDotnetTest runs an xUnit project and checks its tests land under their classes,
+// read from the TRX file
+func TestDotnetTest(t *testing.T) {
+	skipWithoutDotnet(t)
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "dotnet-xunit"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	result := testutil.RunThreepio(t, fixtureDir, "dotnet", "test")
+	if result.ExitCode != 1 {
+		t.Fatalf("Expected exit code 1 for the failing test, got %d:\n%s", result.ExitCode, result.Stdout)
+	}
+	summary := readSummaryJSON(t, fixtureDir, result.RunID)
+	if summary.DetectedRunner != "dotnet test" {
+		t.Errorf("Expected the dotnet test runner, got %q", summary.DetectedRunner)
+	}
+	if summary.Totals.Total != 5 || summary.Totals.Passed != 3 || summary.Totals.Failed != 1 || summary.Totals.Skipped != 1 {
+		t.Errorf("Expected 5 tests: 3 passed, 1 failed and 1 skipped, got %+v", summary.Totals)
+	}
+
+	statuses := map[string]string{}
+	for _, group := range summary.Groups {
+		for _, tc := range group.TestCases {
+			statuses[group.Name+" > "+tc.Name] = tc.Status
+			if tc.Name == "Divides" && !strings.Contains(tc.FailureMessage, "Assert.Equal() Failure") {
+				t.Errorf("Expected Divides to fail with xUnit's message, got %q", tc.FailureMessage)
+			}
+		}
+	}
+	for name, want := range map[string]string{
+		"Calc.Tests.CalculatorTests > Adds":      "PASS",
+		"Calc.Tests.CalculatorTests > Divides":   "FAIL",
+		"Calc.Tests.CalculatorTests > Overflows": "SKIP",
+	} {
+		if statuses[name] != want {
+			t.Errorf("Expected %q to be %s, got %q", name, want, statuses[name])
+		}
+	}
+}
+
+// TestDotnetTestBuildError checks a compile error fails the build, not the tests
+func TestDotnetTestBuildError(t *testing.T) {
+	skipWithoutDotnet(t)
+	fixtureDir := t.TempDir()
+	for _, name := range []string{"Calc.Tests.csproj", "CalculatorTests.cs"} {
+		data, err := os.ReadFile(filepath.Join(fixturesDir, "dotnet-xunit", name))
+		if err != nil {
+			t.Fatal(err)
+		}
+		if err := os.WriteFile(filepath.Join(fixtureDir, name), data, 0644); err != nil {
+			t.Fatal(err)
+		}
+	}
+	if err := os.WriteFile(filepath.Join(fixtureDir, "Broken.cs"), []byte("namespace Calc.Tests;\n\npublic class Broken { int x = }\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	result := testutil.RunThreepio(t, fixtureDir, "dotnet", "test")
+	if result.ExitCode == 0 {
+		t.Fatalf("Expected the build error to fail the run:\n%s", result.Stdout)
+	}
+	summary := readSummaryJSON(t, fixtureDir, result.RunID)
+	if summary.ExitReason != "build_failed" {
+		t.Errorf("Expected exit reason build_failed, got %q", summary.ExitReason)
+	}
+	if !strings.Contains(result.Stdout, "Broken.cs") {
+		t.Errorf("Expected the compile error on the console, got:\n%s", result.Stdout)
+	}
+}
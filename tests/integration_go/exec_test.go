@@ -0,0 +1,106 @@
+package integration_test
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestExec runs a script with no parseable output through 3pio exec and checks the run
+// is one group named after the script, failing with the script's exit code, with the
+// script's output in the group report
+func TestExec(t *testing.T) {
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "exec"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	result := testutil.RunThreepio(t, fixtureDir, "exec", "--", "sh", "smoke.sh", "3")
+	if result.ExitCode != 3 {
+		t.Fatalf("Expected the script's exit code 3, got %d:\n%s", result.ExitCode, result.Stdout)
+	}
+	summary := readSummaryJSON(t, fixtureDir, result.RunID)
+	if summary.DetectedRunner != "exec" || summary.Status != "COMPLETED" {
+		t.Errorf("Expected a COMPLETED exec run, got %q with runner %q", summary.Status, summary.DetectedRunner)
+	}
+	if summary.Totals.Total != 1 || summary.Totals.Failed != 1 || len(summary.Groups) != 1 {
+		t.Fatalf("Expected one group with one failed test, got %+v", summary)
+	}
+	group := summary.Groups[0]
+	if filepath.Base(group.Name) != "smoke.sh" || group.TestCases[0].FailureMessage != "exited with code 3" {
+		t.Errorf("Expected smoke.sh to fail with its exit code, got %q: %+v", group.Name, group.TestCases[0])
+	}
+	runDir := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID)
+	testutil.AssertFileContains(t, filepath.Join(runDir, filepath.FromSlash(group.Report)),
+		"checking the config", "checking the database", "checking the queue")
+
+	// A --watch of the script's own isn't taken for a watch mode
+	result = testutil.RunThreepio(t, fixtureDir, "exec", "--", "sh", "smoke.sh", "0", "--watch")
+	if result.ExitCode != 0 || strings.Contains(result.Stdout, "removed --watch") {
+		t.Errorf("Expected the script to pass with its arguments as given, got %d:\n%s", result.ExitCode, result.Stdout)
+	}
+}
+
+// TestExecRetries checks --retries re-runs a failed script, which is flaky when it
+// passes on the retry
+func TestExecRetries(t *testing.T) {
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "exec"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	t.Setenv("FLAKY_MARKER", filepath.Join(t.TempDir(), "marker"))
+	result := testutil.RunThreepio(t, fixtureDir, "--retries", "1", "exec", "--", "sh", "flaky.sh")
+	if result.ExitCode != 0 {
+		t.Errorf("Expected exit code 0 when the script passed on a retry, got %d:\n%s", result.ExitCode, result.Stdout)
+	}
+	if !strings.Contains(result.Stdout, "(passed on a retry)") {
+		t.Errorf("Expected the script to be reported flaky, got:\n%s", result.Stdout)
+	}
+	output, err := os.ReadFile(filepath.Join(fixtureDir, ".3pio", "runs", result.RunID, "output.log"))
+	if err != nil || !strings.Contains(string(output), "connection refused") || !strings.Contains(string(output), "reached the service") {
+		t.Errorf("Expected both attempts in output.log, got %q (%v)", output, err)
+	}
+}
+
+// TestExecRetryStopsAtTimeout runs a script that fails, then hangs when retried, and
+// checks --timeout and --inactivity-timeout stop the retry like they stop the first run
+func TestExecRetryStopsAtTimeout(t *testing.T) {
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "exec"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	for _, tc := range []struct {
+		name   string
+		flag   string
+		reason string
+	}{
+		{"timeout", "--timeout", "exit_reason: timeout"},
+		{"inactivity timeout", "--inactivity-timeout", "exit_reason: inactivity_timeout"},
+	} {
+		t.Run(tc.name, func(t *testing.T) {
+			// flaky_hang.sh sleeps for five minutes on the retry
+			t.Setenv("FLAKY_MARKER", filepath.Join(t.TempDir(), "marker"))
+			start := time.Now()
+			result := testutil.RunThreepio(t, fixtureDir, "--retries", "1", tc.flag, "5", "exec", "--", "sh", "flaky_hang.sh")
+			if elapsed := time.Since(start); elapsed > time.Minute {
+				t.Errorf("Expected the retry to stop after the timeout, took %s", elapsed)
+			}
+			if result.ExitCode != 124 {
+				t.Errorf("Expected exit code 124 after the timeout, got %d:\n%s", result.ExitCode, result.Stdout)
+			}
+			if !strings.Contains(result.Stdout, "Retrying 1 failed tests (attempt 2 of 2)") {
+				t.Errorf("Expected the script to be retried, got:\n%s", result.Stdout)
+			}
+			testutil.AssertFileContains(t, filepath.Join(fixtureDir, ".3pio", "runs", result.RunID, "test-run.md"), tc.reason)
+		})
+	}
+}
@@ -0,0 +1,74 @@
+package integration_test
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestCargoExitReasons checks the summary line and the report tell a build failure, a
+// filter that matched nothing and failing tests apart
+func TestCargoExitReasons(t *testing.T) {
+	if _, err := testutil.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+
+	// A crate that doesn't compile
+	brokenDir := t.TempDir()
+	for name, content := range map[string]string{
+		"Cargo.toml": "[package]\nname = \"broken\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+		"src/lib.rs": "pub fn answer() -> u32 {\n    \"forty-two\"\n}\n",
+	} {
+		path := filepath.Join(brokenDir, name)
+		if err := os.MkdirAll(filepath.Dir(path), 0755); err != nil {
+			t.Fatalf("Failed to create %s: %v", filepath.Dir(path), err)
+		}
+		if err := os.WriteFile(path, []byte(content), 0644); err != nil {
+			t.Fatalf("Failed to write %s: %v", path, err)
+		}
+	}
+
+	testCases := []struct {
+		name     string
+		dir      string
+		args     []string
+		exitCode int // -1 for any non-zero code
+		reason   string
+		message  string // Headline above the results, empty to not check it
+	}{
+		{"passing tests", filepath.Join(fixturesDir, "rust-basic"), []string{"cargo", "test"}, 0, "ok", ""},
+		{"failing tests", filepath.Join(fixturesDir, "rust-edge-cases"), []string{"cargo", "test"}, -1, "tests_failed", "Test failures!"},
+		{"build failure", brokenDir, []string{"cargo", "test"}, -1, "build_failed", "Build failed!"},
+		{"no tests matched", filepath.Join(fixturesDir, "rust-basic"), []string{"cargo", "test", "no_such_test"}, 5, "no_tests_found", "No tests matched the filter"},
+		{"no tests matched, allowed", filepath.Join(fixturesDir, "rust-basic"), []string{"--allow-no-tests", "cargo", "test", "no_such_test"}, 0, "no_tests_found", "No tests matched the filter"},
+	}
+	for _, tc := range testCases {
+		t.Run(tc.name, func(t *testing.T) {
+			if _, err := os.Stat(tc.dir); os.IsNotExist(err) {
+				t.Skipf("fixture %s not found", tc.dir)
+			}
+			testutil.CleanupTestRuns(t, tc.dir)
+
+			result := testutil.RunThreepio(t, tc.dir, tc.args...)
+			if (tc.exitCode < 0 && result.ExitCode == 0) || (tc.exitCode >= 0 && result.ExitCode != tc.exitCode) {
+				t.Errorf("Expected exit code %d, got %d", tc.exitCode, result.ExitCode)
+			}
+			for _, want := range []string{tc.message, "Exit reason: " + tc.reason} {
+				if want != "" && !strings.Contains(result.Stdout, want) {
+					t.Errorf("Expected console output to contain %q, got:\n%s", want, result.Stdout)
+				}
+			}
+
+			report, err := os.ReadFile(filepath.Join(tc.dir, ".3pio", "runs", result.RunID, "test-run.md"))
+			if err != nil {
+				t.Fatalf("Failed to read test-run.md: %v", err)
+			}
+			if !strings.Contains(string(report), "exit_reason: "+tc.reason+"\n") {
+				t.Errorf("Expected exit_reason %s in test-run.md, got:\n%s", tc.reason, report)
+			}
+		})
+	}
+}
@@ -0,0 +1,76 @@
+package integration_test
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestFailuresMarkedAgainstPreviousRun runs a fixture whose test_toggled fails only
+// while TOGGLE_FAILURE is set, and checks each failure is marked new or known
+func TestFailuresMarkedAgainstPreviousRun(t *testing.T) {
+	if _, err := testutil.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+
+	fixtureDir := filepath.Join(fixturesDir, "rust-failure-toggle")
+	if _, err := os.Stat(fixtureDir); os.IsNotExist(err) {
+		t.Skip("rust-failure-toggle fixture not found")
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	readReport := func(runID string) string {
+		t.Helper()
+		content, err := os.ReadFile(filepath.Join(fixtureDir, ".3pio", "runs", runID, "test-run.md"))
+		if err != nil {
+			t.Fatalf("Failed to read test-run.md: %v", err)
+		}
+		return string(content)
+	}
+
+	// No previous run, so nothing to mark
+	first := testutil.RunThreepio(t, fixtureDir, "cargo", "test")
+	if report := readReport(first.RunID); strings.Contains(report, "new failure") || strings.Contains(report, "still failing") {
+		t.Errorf("Expected no annotations on the first run, got:\n%s", report)
+	}
+	if strings.Contains(first.Stdout, "[NEW]") || strings.Contains(first.Stdout, "[KNOWN]") {
+		t.Errorf("Expected no tags on the first run, got:\n%s", first.Stdout)
+	}
+
+	t.Setenv("TOGGLE_FAILURE", "1")
+	second := testutil.RunThreepio(t, fixtureDir, "cargo", "test")
+	report := readReport(second.RunID)
+	for _, want := range []string{
+		"test_always_fails` (still failing since " + first.RunID + ")",
+		"test_toggled` (new failure)",
+	} {
+		if !strings.Contains(report, want) {
+			t.Errorf("Expected report to contain %q, got:\n%s", want, report)
+		}
+	}
+	for _, want := range []string{"test_always_fails [KNOWN]", "test_toggled [NEW]"} {
+		if !strings.Contains(second.Stdout, want) {
+			t.Errorf("Expected console output to contain %q, got:\n%s", want, second.Stdout)
+		}
+	}
+
+	// Passing again drops test_toggled from the failures, so failing after that is new again
+	_ = os.Unsetenv("TOGGLE_FAILURE")
+	third := testutil.RunThreepio(t, fixtureDir, "cargo", "test")
+	if report := readReport(third.RunID); strings.Contains(report, "test_toggled`") ||
+		!strings.Contains(report, "test_always_fails` (still failing since "+second.RunID+")") {
+		t.Errorf("Expected only test_always_fails to be listed and known, got:\n%s", report)
+	}
+
+	t.Setenv("TOGGLE_FAILURE", "1")
+	fourth := testutil.RunThreepio(t, fixtureDir, "cargo", "test")
+	if report := readReport(fourth.RunID); !strings.Contains(report, "test_toggled` (new failure)") {
+		t.Errorf("Expected test_toggled to be a new failure again, got:\n%s", report)
+	}
+	if !strings.Contains(fourth.Stdout, "test_toggled [NEW]") {
+		t.Errorf("Expected test_toggled tagged NEW again, got:\n%s", fourth.Stdout)
+	}
+}
@@ -0,0 +1,66 @@
+package integration_test
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestGoBenchmarks runs tests and benchmarks together, with and without -benchmem
+func TestGoBenchmarks(t *testing.T) {
+	if _, err := testutil.LookPath("go"); err != nil {
+		t.Skip("go not found in PATH")
+	}
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "go-bench"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	for _, tc := range []struct {
+		name   string
+		args   []string
+		header string
+	}{
+		{"benchmem", []string{"-benchmem"}, "| Benchmark | Iterations | ns/op | B/op | allocs/op |"},
+		{"no benchmem", nil, "| Benchmark | Iterations | ns/op |\n"},
+	} {
+		t.Run(tc.name, func(t *testing.T) {
+			args := append([]string{"go", "test", "-count=1", "-bench=.", "-benchtime=100x"}, tc.args...)
+			result := testutil.RunThreepio(t, fixtureDir, append(args, "./...")...)
+			if result.ExitCode != 0 {
+				t.Fatalf("Expected exit code 0, got %d:\n%s", result.ExitCode, result.Stdout)
+			}
+			runDir := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID)
+			data, err := os.ReadFile(filepath.Join(runDir, "summary.json"))
+			if err != nil {
+				t.Fatalf("Failed to read summary.json: %v", err)
+			}
+			var summary summaryJSON
+			if err := json.Unmarshal(data, &summary); err != nil {
+				t.Fatalf("Failed to parse summary.json: %v", err)
+			}
+
+			// The benchmarks don't count as tests, sub-benchmarks included
+			if summary.Totals.Total != 2 || summary.Totals.Passed != 2 || summary.Totals.Benchmarks != 3 {
+				t.Fatalf("Expected 2 passed tests and 3 benchmarks, got %+v", summary.Totals)
+			}
+			if len(summary.Groups) == 0 {
+				t.Fatal("Expected the package group in summary.json")
+			}
+			report := filepath.Join(runDir, summary.Groups[0].Report)
+			testutil.AssertFileContains(t, report, "## Benchmarks", tc.header, "| BenchmarkJoin", "| BenchmarkItoa/123456789")
+			content, err := os.ReadFile(report)
+			if err != nil {
+				t.Fatalf("Failed to read %s: %v", report, err)
+			}
+			if strings.Contains(string(content), "| BenchmarkItoa |") {
+				t.Errorf("Expected the parent of the sub-benchmarks not to be listed, got:\n%s", content)
+			}
+		})
+	}
+}
@@ -0,0 +1,66 @@
+package integration_test
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestGoBuildFailure runs a module with a package that doesn't compile, one without
+// test files and one that passes
+func TestGoBuildFailure(t *testing.T) {
+	if _, err := testutil.LookPath("go"); err != nil {
+		t.Skip("go not found in PATH")
+	}
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "go-build-failure"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	result := testutil.RunThreepio(t, fixtureDir, "go", "test", "-count=1", "./...")
+	if result.ExitCode == 0 {
+		t.Fatalf("Expected a failed run, got exit code 0:\n%s", result.Stdout)
+	}
+	data, err := os.ReadFile(filepath.Join(fixtureDir, ".3pio", "runs", result.RunID, "summary.json"))
+	if err != nil {
+		t.Fatalf("Failed to read summary.json: %v", err)
+	}
+	var summary summaryJSON
+	if err := json.Unmarshal(data, &summary); err != nil {
+		t.Fatalf("Failed to parse summary.json: %v", err)
+	}
+
+	// Every failure was a build failure
+	if summary.ExitReason != "build_failed" {
+		t.Errorf("Expected exit_reason build_failed, got %q", summary.ExitReason)
+	}
+	const module = "github.com/zk/3pio/tests/fixtures/go-build-failure/"
+	found := make(map[string]bool)
+	for _, group := range summary.Groups {
+		switch group.Name {
+		case module + "broken":
+			found[group.Name] = true
+			if group.Status != "ERROR" || group.FailureKind != "BUILD_ERROR" || group.Error == "" {
+				t.Errorf("Expected the broken package to be ERROR with a BUILD_ERROR, got %+v", group)
+			}
+		case module + "notests":
+			found[group.Name] = true
+			if group.Status != "SKIP" || group.SkipReason != "no_test_files" {
+				t.Errorf("Expected the package without tests to be skipped with no_test_files, got %+v", group)
+			}
+		case module + "works":
+			found[group.Name] = true
+			if group.Status != "PASS" {
+				t.Errorf("Expected the passing package to be PASS, got %+v", group)
+			}
+		}
+	}
+	if len(found) != 3 {
+		t.Errorf("Expected all three packages in summary.json, got %v", found)
+	}
+	testutil.AssertFileContains(t, filepath.Join(fixtureDir, ".3pio", "runs", result.RunID, "test-run.md"), "## Build errors")
+}
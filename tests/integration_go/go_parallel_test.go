@@ -0,0 +1,90 @@
+package integration_test
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestGoParallelSubtests checks that parallel subtests, which pause and continue, are
+// reported once each with their own output, and that a failing package fails the run
+func TestGoParallelSubtests(t *testing.T) {
+	if _, err := testutil.LookPath("go"); err != nil {
+		t.Skip("go not found in PATH")
+	}
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "go-parallel"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	result := testutil.RunThreepio(t, fixtureDir, "go", "test", "-count=1", "./...")
+	if result.ExitCode != 1 {
+		t.Fatalf("Expected exit code 1, got %d:\n%s", result.ExitCode, result.Stdout)
+	}
+	data, err := os.ReadFile(filepath.Join(fixtureDir, ".3pio", "runs", result.RunID, "summary.json"))
+	if err != nil {
+		t.Fatalf("Failed to read summary.json: %v", err)
+	}
+	var summary summaryJSON
+	if err := json.Unmarshal(data, &summary); err != nil {
+		t.Fatalf("Failed to parse summary.json: %v", err)
+	}
+
+	const module = "github.com/zk/3pio/tests/fixtures/go-parallel"
+	statuses := make(map[string]string)
+	tests := make(map[string]int)
+	testStatuses := make(map[string]string)
+	failures := make(map[string]string)
+	cases := 0
+	for _, group := range summary.Groups {
+		path := strings.Join(group.Path, " > ")
+		statuses[path] = group.Status
+		for _, tc := range group.TestCases {
+			name := path + " > " + tc.Name
+			tests[name]++
+			testStatuses[name] = tc.Status
+			failures[name] = tc.FailureMessage
+			cases++
+		}
+	}
+
+	for path, status := range map[string]string{
+		module + "/cache":              "PASS",
+		module + "/cache > TestLookup": "PASS",
+		module + "/store":              "FAIL",
+		module + "/store > TestWrite":  "FAIL",
+	} {
+		if statuses[path] != status {
+			t.Errorf("Expected group %s to be %s, got %q", path, status, statuses[path])
+		}
+	}
+
+	// Continuing a paused subtest doesn't report it a second time
+	for name, status := range map[string]string{
+		module + "/cache > TestLookup > first":  "PASS",
+		module + "/cache > TestLookup > second": "PASS",
+		module + "/cache > TestLookup > third":  "PASS",
+		module + "/cache > TestEvict":           "PASS",
+		module + "/store > TestWrite > small":   "PASS",
+		module + "/store > TestWrite > large":   "FAIL",
+		module + "/store > TestRead":            "PASS",
+	} {
+		if tests[name] != 1 || testStatuses[name] != status {
+			t.Errorf("Expected test %s to be reported once as %s, got %d times as %q", name, status, tests[name], testStatuses[name])
+		}
+	}
+	if summary.Totals.Total != cases || summary.Totals.Failed != 1 {
+		t.Errorf("Expected %d tests with 1 failure in the totals, got %d with %d", cases, summary.Totals.Total, summary.Totals.Failed)
+	}
+
+	// The interleaved output of the parallel subtests stays with the test that wrote it
+	large := failures[module+"/store > TestWrite > large"]
+	if !strings.Contains(large, "write large: disk full") || strings.Contains(large, "wrote small") {
+		t.Errorf("Expected only the large subtest's output in its failure, got %q", large)
+	}
+}
@@ -0,0 +1,58 @@
+package integration_test
+
+import (
+	"encoding/json"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestGoDataRace runs a test with a data race under the race detector
+func TestGoDataRace(t *testing.T) {
+	if _, err := testutil.LookPath("go"); err != nil {
+		t.Skip("go not found in PATH")
+	}
+	// The race detector needs cgo
+	if out, err := exec.Command("go", "env", "CGO_ENABLED").Output(); err != nil || strings.TrimSpace(string(out)) != "1" {
+		t.Skip("go test -race needs cgo")
+	}
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "go-race"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+	t.Setenv("THREEPIO_RACE_FIXTURE", "1")
+
+	result := testutil.RunThreepio(t, fixtureDir, "go", "test", "-race", "-count=1", "./...")
+	if result.ExitCode == 0 {
+		t.Fatalf("Expected the race to fail the run, got exit code 0:\n%s", result.Stdout)
+	}
+	runDir := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID)
+	data, err := os.ReadFile(filepath.Join(runDir, "summary.json"))
+	if err != nil {
+		t.Fatalf("Failed to read summary.json: %v", err)
+	}
+	var summary summaryJSON
+	if err := json.Unmarshal(data, &summary); err != nil {
+		t.Fatalf("Failed to parse summary.json: %v", err)
+	}
+
+	kinds := make(map[string]string)
+	report := ""
+	for _, group := range summary.Groups {
+		for _, tc := range group.TestCases {
+			kinds[tc.Name] = tc.Status + " " + tc.FailureKind
+			if tc.Name == "TestCounter" {
+				report = filepath.Join(runDir, group.Report)
+			}
+		}
+	}
+	if kinds["TestCounter"] != "FAIL DATA_RACE" || kinds["TestSafe"] != "PASS " {
+		t.Fatalf("Expected TestCounter to fail with DATA_RACE and TestSafe to pass, got %v", kinds)
+	}
+	testutil.AssertFileContains(t, report, "[DATA_RACE]", "Data race:", "Previous ")
+}
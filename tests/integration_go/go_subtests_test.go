@@ -0,0 +1,75 @@
+package integration_test
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestGoNestedSubtests checks that subtests become nested groups under their package
+func TestGoNestedSubtests(t *testing.T) {
+	if _, err := testutil.LookPath("go"); err != nil {
+		t.Skip("go not found in PATH")
+	}
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "go-subtests"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	result := testutil.RunThreepio(t, fixtureDir, "go", "test", "-count=1", "./...")
+	if result.ExitCode != 1 {
+		t.Fatalf("Expected exit code 1, got %d:\n%s", result.ExitCode, result.Stdout)
+	}
+	data, err := os.ReadFile(filepath.Join(fixtureDir, ".3pio", "runs", result.RunID, "summary.json"))
+	if err != nil {
+		t.Fatalf("Failed to read summary.json: %v", err)
+	}
+	var summary summaryJSON
+	if err := json.Unmarshal(data, &summary); err != nil {
+		t.Fatalf("Failed to parse summary.json: %v", err)
+	}
+
+	const pkg = "github.com/zk/3pio/tests/fixtures/go-subtests"
+	statuses := make(map[string]string)
+	tests := make(map[string]string)
+	for _, group := range summary.Groups {
+		path := strings.Join(group.Path, " > ")
+		statuses[path] = group.Status
+		for _, tc := range group.TestCases {
+			tests[path+" > "+tc.Name] = tc.Status
+		}
+	}
+
+	// Three levels of groups, with the failures rolled up to the ones above them
+	for path, status := range map[string]string{
+		pkg + " > TestParse":                      "FAIL",
+		pkg + " > TestParse > decimal":            "PASS",
+		pkg + " > TestParse > hex":                "FAIL",
+		pkg + " > TestParse > hex > negative":     "FAIL",
+		pkg + " > TestParse > decimal > positive": "PASS",
+	} {
+		if statuses[path] != status {
+			t.Errorf("Expected group %s to be %s, got %q", path, status, statuses[path])
+		}
+	}
+	// Tests sit in their innermost group; slashes within a subtest name don't split it
+	for name, status := range map[string]string{
+		pkg + " > TestParse > hex > negative > zero":    "PASS",
+		pkg + " > TestParse > hex > negative > ten":     "FAIL",
+		pkg + " > TestParse > decimal > positive > ten": "PASS",
+		pkg + " > TestPaths > usr/bin":                  "PASS",
+		pkg + " > TestPaths > usr/local/bin":            "PASS",
+	} {
+		if tests[name] != status {
+			t.Errorf("Expected test %s to be %s, got %q", name, status, tests[name])
+		}
+	}
+	if _, split := statuses[pkg+" > TestPaths > usr"]; split {
+		t.Errorf("Expected no group for the first part of a subtest name with a slash")
+	}
+}
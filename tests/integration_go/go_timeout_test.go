@@ -0,0 +1,54 @@
+package integration_test
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestGoTestTimeout runs a test that outlives go test -timeout
+func TestGoTestTimeout(t *testing.T) {
+	if _, err := testutil.LookPath("go"); err != nil {
+		t.Skip("go not found in PATH")
+	}
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "go-timeout"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	result := testutil.RunThreepio(t, fixtureDir, "go", "test", "-timeout", "1s", "-count=1", "./...")
+	if result.ExitCode == 0 {
+		t.Fatalf("Expected the timeout to fail the run, got exit code 0:\n%s", result.Stdout)
+	}
+	runDir := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID)
+	data, err := os.ReadFile(filepath.Join(runDir, "summary.json"))
+	if err != nil {
+		t.Fatalf("Failed to read summary.json: %v", err)
+	}
+	var summary summaryJSON
+	if err := json.Unmarshal(data, &summary); err != nil {
+		t.Fatalf("Failed to parse summary.json: %v", err)
+	}
+	if summary.ExitReason != "test_timeout" {
+		t.Errorf("Expected exit reason test_timeout, got %q", summary.ExitReason)
+	}
+
+	kinds := make(map[string]string)
+	report := ""
+	for _, group := range summary.Groups {
+		for _, tc := range group.TestCases {
+			kinds[tc.Name] = tc.Status + " " + tc.FailureKind
+			if tc.Name == "slow" {
+				report = filepath.Join(runDir, group.Report)
+			}
+		}
+	}
+	if kinds["TestQuick"] != "PASS " || kinds["slow"] != "FAIL TIMED_OUT" || kinds["TestAfterHang"] != "SKIP " {
+		t.Fatalf("Expected TestQuick to pass, slow to time out and TestAfterHang to be skipped, got %v", kinds)
+	}
+	testutil.AssertFileContains(t, report, "panic: test timed out after 1s", "hang.TestHang.func1")
+}
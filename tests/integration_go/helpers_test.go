@@ -77,14 +77,35 @@ func getLatestRunDir(t *testing.T, baseDir string) string {
 		return ""
 	}
 
-	if len(entries) == 0 {
+	// Get the last run directory (most recent by timestamp), skipping index.jsonl and latest
+	latest := ""
+	for _, entry := range entries {
+		if entry.IsDir() {
+			latest = entry.Name()
+		}
+	}
+	if latest == "" {
 		t.Fatal("No run directories found")
 		return ""
 	}
+	return filepath.Join(runsDir, latest)
+}
 
-	// Get the last entry (most recent by timestamp)
-	latestEntry := entries[len(entries)-1]
-	return filepath.Join(runsDir, latestEntry.Name())
+// listRunDirs returns the names of the run directories in runsDir, oldest first
+func listRunDirs(t *testing.T, runsDir string) []string {
+	t.Helper()
+
+	entries, err := os.ReadDir(runsDir)
+	if err != nil {
+		t.Fatalf("Failed to read runs directory: %v", err)
+	}
+	var runDirs []string
+	for _, entry := range entries {
+		if entry.IsDir() {
+			runDirs = append(runDirs, entry.Name())
+		}
+	}
+	return runDirs
 }
 
 // assertReportExists verifies that the main test report exists in the run directory
@@ -0,0 +1,36 @@
+package integration_test
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestInstaSnapshots runs rust-insta, whose greeting snapshot is stale, and checks the
+// mismatch is reported as one with the snapshot insta wrote for review
+func TestInstaSnapshots(t *testing.T) {
+	if _, err := testutil.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+	fixtureDir := filepath.Join(fixturesDir, "rust-insta")
+	newFile := filepath.Join(fixtureDir, "src", "snapshots", "rust_insta__tests__greeting.snap.new")
+	t.Cleanup(func() { _ = os.Remove(newFile) })
+
+	// insta only writes .snap.new files under CI when told to
+	t.Setenv("INSTA_UPDATE", "new")
+	testutil.CleanupTestRuns(t, fixtureDir)
+	result := testutil.RunThreepio(t, fixtureDir, "cargo", "test")
+	if result.ExitCode == 0 {
+		t.Fatalf("Expected the stale snapshot to fail the run. Stdout: %s", result.Stdout)
+	}
+	if !strings.Contains(result.Stdout, "Snapshots:   1 pending, run `cargo insta review`") {
+		t.Errorf("Expected the console summary to point at cargo insta review, got:\n%s", result.Stdout)
+	}
+
+	runDir := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID)
+	testutil.AssertFileContains(t, filepath.Join(runDir, "test-run.md"), "## Pending snapshots", "- `src/snapshots/rust_insta__tests__greeting.snap.new`")
+	testutil.AssertFileContains(t, filepath.Join(runDir, "summary.json"), `"failure_kind": "SNAPSHOT_MISMATCH"`)
+}
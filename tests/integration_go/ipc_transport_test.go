@@ -0,0 +1,67 @@
+package integration_test
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestStdioIPCTransport verifies that adapters sending their events over fd 3 (stderr
+// on Windows) give the same report as the IPC file, with no events left in output.log
+func TestStdioIPCTransport(t *testing.T) {
+	t.Run("Jest", func(t *testing.T) {
+		if _, err := testutil.LookPath("npm"); err != nil {
+			t.Skip("npm not found in PATH")
+		}
+		if err := testutil.CommandAvailable("npx", "jest", "--version"); err != nil {
+			t.Skipf("jest command failed: %v", err)
+		}
+		checkStdioTransport(t, "basic-jest", []string{"npx", "jest", "math.test.js", "string.test.js"}, "math.test.js")
+	})
+
+	t.Run("pytest", func(t *testing.T) {
+		if err := testutil.CommandAvailable("python3", "-m", "pytest", "--version"); err != nil {
+			t.Skip("pytest not available")
+		}
+		checkStdioTransport(t, "basic-pytest", []string{"pytest"}, ".py")
+	})
+}
+
+func checkStdioTransport(t *testing.T, fixture string, command []string, wantInReport string) {
+	t.Helper()
+	fixtureDir := filepath.Join(fixturesDir, fixture)
+	if _, err := os.Stat(fixtureDir); os.IsNotExist(err) {
+		t.Skipf("fixture %s not found", fixture)
+	}
+
+	args := append([]string{"--ipc-transport", "stdio"}, command...)
+	result := testutil.RunThreepio(t, fixtureDir, args...)
+	if result.RunID == "" {
+		t.Fatalf("No run ID. Stdout: %s, Stderr: %s", result.Stdout, result.Stderr)
+	}
+	runDir := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID)
+
+	report, err := os.ReadFile(filepath.Join(runDir, "test-run.md"))
+	if err != nil {
+		t.Fatalf("Failed to read test-run.md: %v", err)
+	}
+	for _, want := range []string{wantInReport, "Total test cases:"} {
+		if !strings.Contains(string(report), want) {
+			t.Errorf("test-run.md should contain %q:\n%s", want, report)
+		}
+	}
+	if strings.Contains(string(report), "malformed IPC") {
+		t.Errorf("Events were corrupted on the way:\n%s", report)
+	}
+
+	output, err := os.ReadFile(filepath.Join(runDir, "output.log"))
+	if err != nil {
+		t.Fatalf("Failed to read output.log: %v", err)
+	}
+	if strings.Contains(string(output), "3PIO_EVT:") {
+		t.Errorf("output.log should not contain events sent over stderr:\n%s", output)
+	}
+}
@@ -0,0 +1,91 @@
+package integration_test
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/pkg/threepio"
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// recordingReporter keeps what a run reports, in order
+type recordingReporter struct {
+	calls  []string
+	events []threepio.Event
+	result *threepio.RunResult
+}
+
+func (r *recordingReporter) Start(runID, runDir string) {
+	r.calls = append(r.calls, "start")
+}
+
+func (r *recordingReporter) Event(event threepio.Event) {
+	r.calls = append(r.calls, "event")
+	r.events = append(r.events, event)
+}
+
+func (r *recordingReporter) Finish(result *threepio.RunResult) {
+	r.calls = append(r.calls, "finish")
+	r.result = result
+}
+
+// TestLibraryRun drives a go test run through the library API instead of the binary
+func TestLibraryRun(t *testing.T) {
+	if _, err := testutil.LookPath("go"); err != nil {
+		t.Skip("go not found in PATH")
+	}
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "go-redact"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	wd, _ := os.Getwd()
+	outputDir := t.TempDir()
+	reporter := &recordingReporter{}
+
+	cfg := threepio.DefaultRunConfig("go", "test", "-count=1", "./...")
+	cfg.Dir = fixtureDir
+	cfg.OutputDir = outputDir
+	cfg.Env = map[string]string{"FAKE_SERVICE_TOKEN": "library-token"}
+	cfg.Reporter = reporter
+	result, err := threepio.Run(cfg)
+	if err != nil {
+		t.Fatalf("Run failed: %v", err)
+	}
+
+	if result.ExitCode != 1 || result.ExitReason != "tests_failed" {
+		t.Errorf("Expected exit code 1 and reason tests_failed, got %d and %q", result.ExitCode, result.ExitReason)
+	}
+	if result.Counts.Failed != 1 {
+		t.Errorf("Expected 1 failed test, got %+v", result.Counts)
+	}
+	if filepath.Dir(result.RunDir) != filepath.Join(outputDir, "runs") {
+		t.Errorf("Expected the run directory in %s, got %s", filepath.Join(outputDir, "runs"), result.RunDir)
+	}
+	assertFileExists(t, filepath.Join(result.RunDir, "test-run.md"))
+	if after, _ := os.Getwd(); after != wd {
+		t.Errorf("Expected the working directory to stay %s, got %s", wd, after)
+	}
+	assertFileExists(t, filepath.Join(fixtureDir, ".3pio", "debug.log"))
+
+	// Events arrive between Start and Finish, the failure with the variable from Env
+	if len(reporter.calls) < 3 || reporter.calls[0] != "start" || reporter.calls[len(reporter.calls)-1] != "finish" {
+		t.Errorf("Expected start, events, then finish, got %v", reporter.calls)
+	}
+	if reporter.result != result {
+		t.Errorf("Expected Finish to get the result Run returned")
+	}
+	var failure *threepio.Event
+	for i, event := range reporter.events {
+		if event.Kind == threepio.EventTestCase && event.Test == "TestLeaksToken" {
+			failure = &reporter.events[i]
+		}
+	}
+	if failure == nil {
+		t.Fatalf("Expected a testCase event for TestLeaksToken, got %+v", reporter.events)
+	}
+	if failure.Status != "FAIL" || !strings.Contains(failure.Output+failure.Message, "token library-token was rejected") {
+		t.Errorf("Expected TestLeaksToken to fail with the token from Env, got %+v", *failure)
+	}
+}
@@ -0,0 +1,66 @@
+package integration_test
+
+import (
+	"os"
+	"path/filepath"
+	"slices"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestMochaOutcomes runs nested describe blocks with a pending and a timed-out test,
+// choosing the spec reporter, and checks 3pio's reporter replaced it and each outcome
+// lands in its describe block's group
+func TestMochaOutcomes(t *testing.T) {
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "mocha-outcomes"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	if _, err := os.Stat(filepath.Join(fixtureDir, "node_modules", "mocha")); err != nil {
+		t.Skip("mocha not installed in the fixture (npm install)")
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	result := testutil.RunThreepio(t, fixtureDir, "npx", "mocha", "--reporter", "spec", "test/")
+	if result.ExitCode != 1 {
+		t.Fatalf("Expected exit code 1 for the failing tests, got %d:\n%s", result.ExitCode, result.Stdout)
+	}
+	if !strings.Contains(result.Stdout, "in place of --reporter spec") {
+		t.Errorf("Expected a note that the spec reporter was replaced, got:\n%s", result.Stdout)
+	}
+	summary := readSummaryJSON(t, fixtureDir, result.RunID)
+	if summary.Totals.Total != 6 || summary.Totals.Passed != 2 || summary.Totals.Failed != 2 || summary.Totals.Skipped != 2 {
+		t.Errorf("Expected 6 tests: 2 passed, 2 failed and 2 skipped, got %+v", summary.Totals)
+	}
+
+	statuses := map[string]string{}
+	kinds := map[string]string{}
+	paths := map[string][]string{}
+	for _, group := range summary.Groups {
+		for _, tc := range group.TestCases {
+			statuses[tc.Name] = tc.Status
+			kinds[tc.Name] = tc.FailureKind
+			paths[tc.Name] = group.Path[1:]
+		}
+	}
+	for name, want := range map[string]string{
+		"adds an item":         "PASS",
+		"applies a percentage": "PASS",
+		"applies a coupon":     "FAIL",
+		"ships abroad":         "SKIP",
+		"ships overnight":      "SKIP",
+		"quotes the carrier":   "FAIL",
+	} {
+		if statuses[name] != want {
+			t.Errorf("Expected %q to be %s, got %q", name, want, statuses[name])
+		}
+	}
+	if kinds["quotes the carrier"] != "TIMED_OUT" {
+		t.Errorf("Expected quotes the carrier to have TIMED_OUT, got %q", kinds["quotes the carrier"])
+	}
+	if !slices.Equal(paths["applies a coupon"], []string{"Cart", "discounts"}) || !slices.Equal(paths["adds an item"], []string{"Cart"}) {
+		t.Errorf("Expected the tests under their describe blocks, got %v", paths)
+	}
+}
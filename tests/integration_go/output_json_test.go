@@ -0,0 +1,104 @@
+package integration_test
+
+import (
+	"bufio"
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// streamLine is the part of an --output json line the test reads
+type streamLine struct {
+	Type    string `json:"type"`
+	RunID   string `json:"run_id"`
+	GroupID string `json:"group_id"`
+	Test    *struct {
+		ID     string `json:"id"`
+		Status string `json:"status"`
+	} `json:"test"`
+	Summary *summaryJSON `json:"summary"`
+}
+
+func TestOutputJSON_CargoTest(t *testing.T) {
+	if _, err := testutil.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+
+	fixtureDir := filepath.Join("..", "fixtures", "rust-basic")
+	if _, err := os.Stat(fixtureDir); os.IsNotExist(err) {
+		t.Skip("rust-basic fixture not found")
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	result := testutil.RunThreepio(t, fixtureDir, "--output", "json", "cargo", "test")
+
+	// Every line of stdout is an event: no console output and no runner output
+	var lines []streamLine
+	scanner := bufio.NewScanner(strings.NewReader(result.Stdout))
+	scanner.Buffer(make([]byte, 0, 64*1024), 16*1024*1024)
+	for scanner.Scan() {
+		var line streamLine
+		if err := json.Unmarshal(scanner.Bytes(), &line); err != nil {
+			t.Fatalf("Expected only JSON lines on stdout, got %q: %v", scanner.Text(), err)
+		}
+		lines = append(lines, line)
+	}
+	if len(lines) < 2 || lines[0].Type != "run_started" || lines[len(lines)-1].Type != "run_finished" {
+		t.Fatalf("Expected run_started first and run_finished last, got %d lines:\n%s", len(lines), result.Stdout)
+	}
+
+	runDir := filepath.Join(fixtureDir, ".3pio", "runs", lines[0].RunID)
+	data, err := os.ReadFile(filepath.Join(runDir, "summary.json"))
+	if err != nil {
+		t.Fatalf("Failed to read summary.json of run %q: %v", lines[0].RunID, err)
+	}
+	var summary summaryJSON
+	if err := json.Unmarshal(data, &summary); err != nil {
+		t.Fatalf("Failed to parse summary.json: %v", err)
+	}
+
+	// The test_finished events give the same counts as summary.json
+	statuses := map[string]string{}
+	for _, line := range lines {
+		if line.Type == "test_finished" && line.Test != nil {
+			statuses[line.GroupID+"/"+line.Test.ID] = line.Test.Status
+		}
+	}
+	passed, failed, skipped := 0, 0, 0
+	for _, status := range statuses {
+		switch status {
+		case "PASS":
+			passed++
+		case "FAIL":
+			failed++
+		case "SKIP":
+			skipped++
+		}
+	}
+	if passed != summary.Totals.Passed || failed != summary.Totals.Failed || skipped != summary.Totals.Skipped {
+		t.Errorf("Expected %d passed, %d failed and %d skipped as in summary.json, the stream gave %d, %d and %d",
+			summary.Totals.Passed, summary.Totals.Failed, summary.Totals.Skipped, passed, failed, skipped)
+	}
+	if summary.Totals.Total == 0 {
+		t.Errorf("Expected tests in summary.json, got %+v", summary.Totals)
+	}
+
+	finished := lines[len(lines)-1].Summary
+	if finished == nil || finished.Totals != summary.Totals || finished.RunID != summary.RunID {
+		t.Errorf("Expected run_finished to carry summary.json, got %+v", finished)
+	}
+}
+
+func TestOutputJSON_RejectsSummaryOnStdout(t *testing.T) {
+	result := testutil.RunThreepio(t, ".", "--output", "json", "--summary-json", "-", "cargo", "test")
+	if result.ExitCode != 1 || !strings.Contains(result.Stderr, "--output json") {
+		t.Errorf("Expected exit code 1 with an error on stderr, got %d:\n%s", result.ExitCode, result.Stderr)
+	}
+	if result.Stdout != "" {
+		t.Errorf("Expected nothing on stdout, got %q", result.Stdout)
+	}
+}
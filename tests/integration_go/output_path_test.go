@@ -82,16 +82,12 @@ func TestConsoleOutputMatchesActualDirectoryStructure(t *testing.T) {
 
 	// Find the actual reports directory to verify the directory name
 	reportsPath := filepath.Join(fixtureDir, ".3pio/runs")
-	runDirs, err := os.ReadDir(reportsPath)
-	if err != nil {
-		t.Fatalf("Failed to read runs directory: %v", err)
-	}
-
+	runDirs := listRunDirs(t, reportsPath)
 	if len(runDirs) != 1 {
 		t.Fatalf("Expected exactly 1 run directory, found %d", len(runDirs))
 	}
 
-	reportsDirPath := filepath.Join(reportsPath, runDirs[0].Name(), "reports")
+	reportsDirPath := filepath.Join(reportsPath, runDirs[0], "reports")
 	reportEntries, err := os.ReadDir(reportsDirPath)
 	if err != nil {
 		t.Fatalf("Failed to read reports directory: %v", err)
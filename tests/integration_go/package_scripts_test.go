@@ -0,0 +1,68 @@
+package integration_test
+
+import (
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestPackageScripts runs a test script that runs node --test through another script,
+// with each package manager, and checks 3pio found node --test and read its TAP
+func TestPackageScripts(t *testing.T) {
+	tests := []struct {
+		fixture string
+		command []string
+	}{
+		{"scripts-npm", []string{"npm", "test"}},
+		{"scripts-yarn-classic", []string{"yarn", "test"}},
+		{"scripts-pnpm", []string{"pnpm", "test"}},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.fixture, func(t *testing.T) {
+			if _, err := testutil.LookPath(tt.command[0]); err != nil {
+				t.Skipf("%s not found in PATH", tt.command[0])
+			}
+			fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, tt.fixture))
+			if err != nil {
+				t.Fatalf("Failed to get absolute fixture path: %v", err)
+			}
+			testutil.CleanupTestRuns(t, fixtureDir)
+
+			result := testutil.RunThreepio(t, fixtureDir, tt.command...)
+			if result.ExitCode != 0 {
+				t.Fatalf("Expected exit code 0, got %d:\n%s%s", result.ExitCode, result.Stdout, result.Stderr)
+			}
+			summary := readSummaryJSON(t, fixtureDir, result.RunID)
+			if summary.DetectedRunner != "node --test" {
+				t.Errorf("Expected the node --test runner, got %q", summary.DetectedRunner)
+			}
+			if summary.Totals.Total != 2 || summary.Totals.Passed != 2 {
+				t.Errorf("Expected 2 passed tests, got %+v", summary.Totals)
+			}
+		})
+	}
+}
+
+// TestPackageScriptRunnerNotLast checks that a script running a command after its
+// tests is refused, as 3pio's reporter options would go to that command
+func TestPackageScriptRunnerNotLast(t *testing.T) {
+	if _, err := testutil.LookPath("npm"); err != nil {
+		t.Skip("npm not found in PATH")
+	}
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "scripts-npm"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	result := testutil.RunThreepio(t, fixtureDir, "npm", "run", "test:lint-after")
+	if result.ExitCode == 0 {
+		t.Fatalf("Expected the script to be refused, got exit code 0:\n%s", result.Stdout)
+	}
+	if output := result.Stdout + result.Stderr; !strings.Contains(output, `runs "node -e console.log('linted')" after "node --test"`) {
+		t.Errorf("Expected the command after the tests to be named, got:\n%s", output)
+	}
+}
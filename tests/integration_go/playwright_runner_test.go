@@ -0,0 +1,64 @@
+package integration_test
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestPlaywrightRunner runs a spec in two projects with a retry and checks each
+// project is a root group, the retried test is flaky and the timeout is reported
+func TestPlaywrightRunner(t *testing.T) {
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "basic-playwright"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	if _, err := os.Stat(filepath.Join(fixtureDir, "node_modules", "@playwright", "test")); err != nil {
+		t.Skip("@playwright/test not installed in the fixture (npm install)")
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	result := testutil.RunThreepio(t, fixtureDir, "npx", "playwright", "test")
+	if result.ExitCode != 1 {
+		t.Fatalf("Expected exit code 1 for the failing tests, got %d:\n%s", result.ExitCode, result.Stdout)
+	}
+	summary := readSummaryJSON(t, fixtureDir, result.RunID)
+	if summary.DetectedRunner != "playwright" {
+		t.Errorf("Expected the playwright runner, got %q", summary.DetectedRunner)
+	}
+
+	projects := map[string]bool{}
+	statuses := map[string]string{}
+	kinds := map[string]string{}
+	for _, group := range summary.Groups {
+		if len(group.Path) == 0 {
+			continue
+		}
+		projects[group.Path[0]] = true
+		for _, tc := range group.TestCases {
+			statuses[group.Path[0]+" > "+tc.Name] = tc.Status
+			kinds[group.Path[0]+" > "+tc.Name] = tc.FailureKind
+		}
+	}
+	if !projects["chromium"] || !projects["firefox"] {
+		t.Errorf("Expected groups under chromium and firefox, got %v", projects)
+	}
+	for _, project := range []string{"chromium", "firefox"} {
+		for name, want := range map[string]string{
+			"adds an item":          "PASS",
+			"applies a discount":    "FAIL",
+			"reloads the total":     "FLAKY",
+			"waits for the payment": "FAIL",
+			"ships abroad":          "SKIP",
+		} {
+			if got := statuses[project+" > "+name]; got != want {
+				t.Errorf("Expected %s > %s to be %s, got %q", project, name, want, got)
+			}
+		}
+	}
+	if kind := kinds["chromium > waits for the payment"]; kind != "TIMED_OUT" {
+		t.Errorf("Expected waits for the payment to time out, got failure kind %q", kind)
+	}
+}
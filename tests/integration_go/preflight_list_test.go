@@ -0,0 +1,72 @@
+package integration_test
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestCargoTestPreflightList lists the tests before running them, and the run reuses
+// the test binaries the list built
+func TestCargoTestPreflightList(t *testing.T) {
+	if _, err := testutil.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+
+	fixtureDir := filepath.Join(fixturesDir, "rust-performance")
+	if _, err := os.Stat(fixtureDir); os.IsNotExist(err) {
+		t.Skip("rust-performance fixture not found")
+	}
+
+	// A changed source file makes cargo compile the crate again
+	now := time.Now()
+	if err := os.Chtimes(filepath.Join(fixtureDir, "src", "lib.rs"), now, now); err != nil {
+		t.Fatalf("Failed to touch src/lib.rs: %v", err)
+	}
+
+	result := testutil.RunThreepio(t, fixtureDir, "cargo", "test")
+	if result.ExitCode != 0 {
+		t.Fatalf("Expected exit code 0, got %d:\n%s", result.ExitCode, result.Stdout)
+	}
+	runDir := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID)
+
+	list, err := os.ReadFile(filepath.Join(runDir, "preflight-list.log"))
+	if err != nil {
+		t.Fatalf("Failed to read preflight-list.log: %v", err)
+	}
+	if !strings.Contains(string(list), "test_add_001: test") {
+		t.Errorf("Expected the listed tests in preflight-list.log, got:\n%s", list)
+	}
+	output, err := os.ReadFile(filepath.Join(runDir, "output.log"))
+	if err != nil {
+		t.Fatalf("Failed to read output.log: %v", err)
+	}
+	compiles := strings.Count(string(list), "Compiling rust-performance") + strings.Count(string(output), "Compiling rust-performance")
+	if compiles != 1 || strings.Contains(string(output), "Compiling rust-performance") {
+		t.Errorf("Expected the crate compiled once, by the list, got %d times:\n%s", compiles, output)
+	}
+
+	// Every listed test ran, so none is left pending or skipped
+	report, err := os.ReadFile(filepath.Join(runDir, "test-run.md"))
+	if err != nil {
+		t.Fatalf("Failed to read test-run.md: %v", err)
+	}
+	for _, unexpected := range []string{"PENDING", "SKIP"} {
+		if strings.Contains(string(report), unexpected) {
+			t.Errorf("Expected no %s in test-run.md, got:\n%s", unexpected, report)
+		}
+	}
+
+	// --no-preflight-list runs the tests without listing them first
+	result = testutil.RunThreepio(t, fixtureDir, "--no-preflight-list", "cargo", "test")
+	if result.ExitCode != 0 {
+		t.Fatalf("Expected exit code 0 with --no-preflight-list, got %d", result.ExitCode)
+	}
+	if _, err := os.Stat(filepath.Join(fixtureDir, ".3pio", "runs", result.RunID, "preflight-list.log")); !os.IsNotExist(err) {
+		t.Errorf("Expected no preflight-list.log with --no-preflight-list, got %v", err)
+	}
+}
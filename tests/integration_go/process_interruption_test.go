@@ -2,9 +2,11 @@ package integration_test
 
 import (
 	"io"
+	"os"
 	"os/exec"
 	"path/filepath"
 	"runtime"
+	"strings"
 	"syscall"
 	"testing"
 	"time"
@@ -146,6 +148,84 @@ func TestGracefulShutdownOnSIGINT(t *testing.T) {
 	}
 }
 
+func TestCargoTestSIGINTFinalizesReport(t *testing.T) {
+	// Skip on Windows as SIGINT is not supported the same way
+	if runtime.GOOS == "windows" {
+		t.Skip("Skipping signal test on Windows")
+	}
+	if _, err := exec.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+
+	projectDir := filepath.Join(fixturesDir, "rust-performance")
+
+	// Clean output directory
+	if err := cleanProjectOutput(projectDir); err != nil {
+		t.Fatalf("Failed to clean project output: %v", err)
+	}
+
+	// Get absolute path to binary
+	binaryPath, err := filepath.Abs(threePioBinary)
+	if err != nil {
+		t.Fatalf("Failed to get absolute path to binary: %v", err)
+	}
+
+	cmd := exec.Command(binaryPath, "cargo", "test")
+	cmd.Dir = projectDir
+	cmd.Stdout = io.Discard
+	cmd.Stderr = io.Discard
+
+	if err := cmd.Start(); err != nil {
+		t.Fatalf("Failed to start command: %v", err)
+	}
+
+	// Wait for the run directory so the report exists before the interrupt
+	runsDir := filepath.Join(projectDir, ".3pio", "runs")
+	deadline := time.Now().Add(10 * time.Second)
+	for time.Now().Before(deadline) {
+		if entries, err := os.ReadDir(runsDir); err == nil && len(entries) > 0 {
+			break
+		}
+		time.Sleep(50 * time.Millisecond)
+	}
+	time.Sleep(500 * time.Millisecond)
+
+	if err := cmd.Process.Signal(syscall.SIGINT); err != nil {
+		t.Fatalf("Failed to send SIGINT: %v", err)
+	}
+
+	// The grace period is 5s, so 3pio must be done well before this
+	done := make(chan error, 1)
+	go func() {
+		done <- cmd.Wait()
+	}()
+	select {
+	case <-done:
+	case <-time.After(30 * time.Second):
+		_ = cmd.Process.Kill()
+		t.Fatal("3pio did not exit after SIGINT")
+	}
+
+	if exitCode := cmd.ProcessState.ExitCode(); exitCode != 130 {
+		t.Errorf("Expected exit code 130 after SIGINT, got %d", exitCode)
+	}
+
+	content, err := os.ReadFile(filepath.Join(getLatestRunDir(t, projectDir), "test-run.md"))
+	if err != nil {
+		t.Fatalf("Failed to read test-run.md: %v", err)
+	}
+	report := string(content)
+
+	for _, expected := range []string{"status: INTERRUPTED", "exit_reason: interrupted", "# 3pio Test Run"} {
+		if !strings.Contains(report, expected) {
+			t.Errorf("Expected %q in test-run.md, got:\n%s", expected, report)
+		}
+	}
+	if strings.Contains(report, "status: RUNNING") {
+		t.Errorf("Report was left RUNNING after SIGINT:\n%s", report)
+	}
+}
+
 func TestQuickProcessTermination(t *testing.T) {
 	projectDir := filepath.Join(fixturesDir, "basic-jest")
 
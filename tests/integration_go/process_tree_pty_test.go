@@ -0,0 +1,109 @@
+//go:build linux
+// +build linux
+
+package integration_test
+
+import (
+	"bytes"
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strconv"
+	"strings"
+	"syscall"
+	"testing"
+	"time"
+
+	"golang.org/x/sys/unix"
+)
+
+// TestTimeoutKillsGroupUnderTerminal runs 3pio with a pseudo-terminal as its
+// controlling terminal and stdin, and checks the test command gets the terminal's
+// foreground group of its own, so it can read stdin, and that the timeout still
+// kills the helpers it starts
+func TestTimeoutKillsGroupUnderTerminal(t *testing.T) {
+	master, slave := openPTY(t)
+	defer func() { _ = master.Close() }()
+	defer func() { _ = slave.Close() }()
+
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "exec"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	cleanTestDir(t, fixtureDir)
+	orphanDir := t.TempDir()
+
+	// orphan.sh starts `sleep 300`, then waits on stdin until the timeout
+	var output bytes.Buffer
+	cmd := exec.Command(getBinaryPath(), "--timeout", "3", "exec", "--", "sh", "orphan.sh")
+	cmd.Dir = fixtureDir
+	cmd.Env = append(os.Environ(), "ORPHAN_DIR="+orphanDir)
+	cmd.Stdin = slave
+	cmd.Stdout = &output
+	cmd.Stderr = &output
+	cmd.SysProcAttr = &syscall.SysProcAttr{Setsid: true, Setctty: true, Ctty: 0}
+	err = cmd.Run()
+	if exitErr, ok := err.(*exec.ExitError); !ok || exitErr.ExitCode() != 124 {
+		t.Fatalf("Expected exit code 124 after the timeout, got %v:\n%s", err, output.String())
+	}
+
+	// /proc/<pid>/stat: pid (comm) state ppid pgrp session tty_nr tpgid ...
+	stat, err := os.ReadFile(filepath.Join(orphanDir, "stat"))
+	if err != nil {
+		t.Fatalf("orphan.sh never started: %v\n%s", err, output.String())
+	}
+	fields := strings.Fields(string(stat[bytes.LastIndexByte(stat, ')')+1:]))
+	if len(fields) < 6 {
+		t.Fatalf("Unexpected /proc stat line %q", stat)
+	}
+	pgrp, tpgid := fields[2], fields[5]
+	if pgrp == strconv.Itoa(cmd.Process.Pid) {
+		t.Errorf("Expected the test command in a process group of its own, got 3pio's")
+	}
+	if tpgid != pgrp {
+		t.Errorf("Expected the test command's group %s to have the terminal, got group %s", pgrp, tpgid)
+	}
+
+	content, err := os.ReadFile(filepath.Join(orphanDir, "grandchild.pid"))
+	if err != nil {
+		t.Fatalf("Grandchild never started: %v", err)
+	}
+	pid, err := strconv.Atoi(strings.TrimSpace(string(content)))
+	if err != nil {
+		t.Fatalf("Invalid grandchild.pid %q: %v", content, err)
+	}
+	deadline := time.Now().Add(5 * time.Second)
+	for syscall.Kill(pid, 0) == nil {
+		if time.Now().After(deadline) {
+			_ = syscall.Kill(pid, syscall.SIGKILL)
+			t.Fatalf("Grandchild process %d still running after 3pio timed the run out", pid)
+		}
+		time.Sleep(100 * time.Millisecond)
+	}
+}
+
+// openPTY opens a pseudo-terminal pair, skipping the test where there are none
+func openPTY(t *testing.T) (master, slave *os.File) {
+	t.Helper()
+	master, err := os.OpenFile("/dev/ptmx", os.O_RDWR|syscall.O_NOCTTY, 0)
+	if err != nil {
+		t.Skipf("No pseudo-terminals: %v", err)
+	}
+	fd := int(master.Fd())
+	if err := unix.IoctlSetPointerInt(fd, unix.TIOCSPTLCK, 0); err != nil {
+		_ = master.Close()
+		t.Fatalf("Failed to unlock the pseudo-terminal: %v", err)
+	}
+	n, err := unix.IoctlGetInt(fd, unix.TIOCGPTN)
+	if err != nil {
+		_ = master.Close()
+		t.Fatalf("Failed to get the pseudo-terminal number: %v", err)
+	}
+	slave, err = os.OpenFile(fmt.Sprintf("/dev/pts/%d", n), os.O_RDWR|syscall.O_NOCTTY, 0)
+	if err != nil {
+		_ = master.Close()
+		t.Skipf("Failed to open the pseudo-terminal: %v", err)
+	}
+	return master, slave
+}
@@ -0,0 +1,56 @@
+//go:build !windows
+// +build !windows
+
+package integration_test
+
+import (
+	"os"
+	"path/filepath"
+	"strconv"
+	"strings"
+	"syscall"
+	"testing"
+	"time"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestTimeoutKillsGrandchildren verifies that a timeout kills the helpers test
+// binaries spawn, not just cargo
+func TestTimeoutKillsGrandchildren(t *testing.T) {
+	if _, err := testutil.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+
+	fixtureDir := filepath.Join(fixturesDir, "rust-orphan")
+	if _, err := os.Stat(fixtureDir); os.IsNotExist(err) {
+		t.Skip("rust-orphan fixture not found")
+	}
+	pidFile := filepath.Join(fixtureDir, "grandchild.pid")
+	_ = os.Remove(pidFile)
+
+	// test_spawns_grandchild starts `sleep 300` and then sleeps for two minutes
+	result := testutil.RunThreepio(t, fixtureDir, "--timeout", "20", "cargo", "test")
+	if result.ExitCode != 124 {
+		t.Errorf("Expected exit code 124 after the timeout, got %d", result.ExitCode)
+	}
+
+	content, err := os.ReadFile(pidFile)
+	if err != nil {
+		t.Skipf("Grandchild never started (slow compile?): %v", err)
+	}
+	pid, err := strconv.Atoi(strings.TrimSpace(string(content)))
+	if err != nil {
+		t.Fatalf("Invalid grandchild.pid %q: %v", content, err)
+	}
+
+	// The orphaned sleep is reaped by init shortly after it's killed
+	deadline := time.Now().Add(5 * time.Second)
+	for syscall.Kill(pid, 0) == nil {
+		if time.Now().After(deadline) {
+			_ = syscall.Kill(pid, syscall.SIGKILL)
+			t.Fatalf("Grandchild process %d still running after 3pio timed the run out", pid)
+		}
+		time.Sleep(100 * time.Millisecond)
+	}
+}
@@ -0,0 +1,54 @@
+package integration_test
+
+import (
+	"os"
+	"path/filepath"
+	"regexp"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// progressFrameRegex matches a frame of the progress line, drawn after "\r\x1b[K"
+var progressFrameRegex = regexp.MustCompile(`\r\x1b\[K(\[T\+ [0-9hms]+\]  (\d+)/(\d+) groups[^\r\n]*)`)
+
+// TestProgressLine forces the progress line on with --progress, as in a terminal, and
+// checks its final frame shows every group finished with the run's tally
+func TestProgressLine(t *testing.T) {
+	if _, err := testutil.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+
+	fixtureDir := filepath.Join(fixturesDir, "rust-failure-toggle")
+	if _, err := os.Stat(fixtureDir); os.IsNotExist(err) {
+		t.Skip("rust-failure-toggle fixture not found")
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+	t.Setenv("NO_COLOR", "1")
+
+	result := testutil.RunThreepio(t, fixtureDir, "--progress", "cargo", "test")
+	frames := progressFrameRegex.FindAllStringSubmatch(result.Stdout, -1)
+	if len(frames) == 0 {
+		t.Fatalf("Expected progress frames in the output, got:\n%q", result.Stdout)
+	}
+	final := frames[len(frames)-1]
+	if final[2] != final[3] {
+		t.Errorf("Expected the final frame to show every group finished, got %q", final[1])
+	}
+	if !strings.HasSuffix(final[1], "2 passed  1 failed  0 skipped") {
+		t.Errorf("Expected the final frame to end with the tally, got %q", final[1])
+	}
+
+	// The line is erased before the summary, which prints as without it
+	summary := result.Stdout[strings.LastIndex(result.Stdout, final[0])+len(final[0]):]
+	if !strings.HasPrefix(summary, "\r\x1b[K") || strings.Contains(summary[len("\r\x1b[K"):], "\x1b[") {
+		t.Errorf("Expected the progress line to be erased before the summary, got:\n%q", summary)
+	}
+
+	// Without a terminal (the default here) there is no progress line
+	result = testutil.RunThreepio(t, fixtureDir, "cargo", "test")
+	if strings.Contains(result.Stdout, "\x1b[K") {
+		t.Errorf("Expected no progress line when stdout isn't a terminal, got:\n%q", result.Stdout)
+	}
+}
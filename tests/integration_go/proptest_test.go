@@ -0,0 +1,28 @@
+package integration_test
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestProptestMinimalInput runs rust-proptest, whose property fails for negative
+// numbers, and checks the input proptest shrank the failure to is reported
+func TestProptestMinimalInput(t *testing.T) {
+	if _, err := testutil.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+	fixtureDir := filepath.Join(fixturesDir, "rust-proptest")
+	t.Cleanup(func() { _ = os.RemoveAll(filepath.Join(fixtureDir, "proptest-regressions")) })
+
+	testutil.CleanupTestRuns(t, fixtureDir)
+	result := testutil.RunThreepio(t, fixtureDir, "cargo", "test")
+	if result.ExitCode == 0 {
+		t.Fatalf("Expected the property to fail the run. Stdout: %s", result.Stdout)
+	}
+
+	runDir := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID)
+	testutil.AssertFileContains(t, filepath.Join(runDir, "summary.json"), `"minimal_input": {`, `"framework": "proptest"`, `"input": "n = -1"`)
+}
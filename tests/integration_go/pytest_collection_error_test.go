@@ -0,0 +1,61 @@
+package integration_test
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestPytestCollectionError runs a test file that can't be imported next to one whose
+// tests all pass
+func TestPytestCollectionError(t *testing.T) {
+	if _, err := testutil.LookPath("pytest"); err != nil {
+		t.Skip("pytest not found in PATH")
+	}
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "pytest-collection-error"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	// pytest stops before running anything on a collection error, unless told to go on
+	for _, args := range [][]string{{"pytest"}, {"pytest", "--continue-on-collection-errors"}} {
+		t.Run(strings.Join(args, " "), func(t *testing.T) {
+			result := testutil.RunThreepio(t, fixtureDir, args...)
+			if result.ExitCode == 0 {
+				t.Fatalf("Expected the broken file to fail the run, got exit code 0:\n%s", result.Stdout)
+			}
+			runDir := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID)
+			data, err := os.ReadFile(filepath.Join(runDir, "summary.json"))
+			if err != nil {
+				t.Fatalf("Failed to read summary.json: %v", err)
+			}
+			var summary summaryJSON
+			if err := json.Unmarshal(data, &summary); err != nil {
+				t.Fatalf("Failed to parse summary.json: %v", err)
+			}
+			if summary.Totals.CollectionErrors != 1 || summary.ExitReason != "build_failed" {
+				t.Errorf("Expected 1 collection error and exit reason build_failed, got %+v and %q", summary.Totals, summary.ExitReason)
+			}
+			found := false
+			for _, group := range summary.Groups {
+				if !strings.HasSuffix(group.Name, "test_broken.py") {
+					continue
+				}
+				found = true
+				if group.Status != "ERROR" || !strings.Contains(group.Error, "module_that_does_not_exist") {
+					t.Errorf("Expected test_broken.py to be ERROR with the import traceback, got %s: %q", group.Status, group.Error)
+				}
+				testutil.AssertFileContains(t, filepath.Join(runDir, group.Report), "module_that_does_not_exist")
+			}
+			if !found {
+				t.Errorf("Expected a group for test_broken.py in summary.json")
+			}
+			testutil.AssertFileContains(t, filepath.Join(runDir, "test-run.md"), "Test files that failed to collect: 1")
+		})
+	}
+}
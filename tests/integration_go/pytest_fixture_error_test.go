@@ -0,0 +1,68 @@
+package integration_test
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestPytestSetupErrors checks that tests whose shared fixture raises are ERROR rather
+// than FAIL, counted apart from failures, each with the fixture's traceback
+func TestPytestSetupErrors(t *testing.T) {
+	if _, err := testutil.LookPath("pytest"); err != nil {
+		t.Skip("pytest not found in PATH")
+	}
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "pytest-fixture-error"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	result := testutil.RunThreepio(t, fixtureDir, "pytest")
+	if result.ExitCode == 0 {
+		t.Fatalf("Expected the setup errors to fail the run, got exit code 0:\n%s", result.Stdout)
+	}
+	runDir := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID)
+	data, err := os.ReadFile(filepath.Join(runDir, "summary.json"))
+	if err != nil {
+		t.Fatalf("Failed to read summary.json: %v", err)
+	}
+	var summary summaryJSON
+	if err := json.Unmarshal(data, &summary); err != nil {
+		t.Fatalf("Failed to parse summary.json: %v", err)
+	}
+	if summary.Totals.Errors != 2 || summary.Totals.Failed != 0 || summary.Totals.Passed != 1 {
+		t.Errorf("Expected 2 errors, 0 failures and 1 pass, got %+v", summary.Totals)
+	}
+
+	report := ""
+	for _, group := range summary.Groups {
+		for _, tc := range group.TestCases {
+			if tc.Name == "test_query" || tc.Name == "test_insert" {
+				if tc.Status != "ERROR" {
+					t.Errorf("Expected %s to be ERROR, got %s", tc.Name, tc.Status)
+				}
+				report = filepath.Join(runDir, group.Report)
+			}
+		}
+	}
+	if report == "" {
+		t.Fatalf("Expected test_query and test_insert in summary.json, got %+v", summary.Groups)
+	}
+	content, err := os.ReadFile(report)
+	if err != nil {
+		t.Fatalf("Failed to read %s: %v", report, err)
+	}
+	// Both tests show the traceback of the fixture they share
+	if got := strings.Count(string(content), "> Setup error:"); got != 2 {
+		t.Errorf("Expected a setup error under each test, got %d:\n%s", got, content)
+	}
+	if got := strings.Count(string(content), "ConnectionError: database is not running"); got < 2 {
+		t.Errorf("Expected the fixture's error under each test, got %d:\n%s", got, content)
+	}
+	testutil.AssertFileContains(t, filepath.Join(runDir, "test-run.md"), "- Test cases failed: 0", "- Test cases errored: 2")
+}
@@ -0,0 +1,64 @@
+package integration_test
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestPytestOutcomes runs one test of each pytest outcome, with and without --fail-on-xpass
+func TestPytestOutcomes(t *testing.T) {
+	if _, err := testutil.LookPath("pytest"); err != nil {
+		t.Skip("pytest not found in PATH")
+	}
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "pytest-outcomes"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	result := testutil.RunThreepio(t, fixtureDir, "pytest")
+	if result.ExitCode == 0 {
+		t.Fatalf("Expected test_failed to fail the run, got exit code 0:\n%s", result.Stdout)
+	}
+	runDir := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID)
+	data, err := os.ReadFile(filepath.Join(runDir, "summary.json"))
+	if err != nil {
+		t.Fatalf("Failed to read summary.json: %v", err)
+	}
+	var summary summaryJSON
+	if err := json.Unmarshal(data, &summary); err != nil {
+		t.Fatalf("Failed to parse summary.json: %v", err)
+	}
+	statuses := make(map[string]string)
+	report := ""
+	for _, group := range summary.Groups {
+		for _, tc := range group.TestCases {
+			statuses[tc.Name] = tc.Status
+			if tc.Name == "test_xpassed" {
+				report = filepath.Join(runDir, group.Report)
+			}
+		}
+	}
+	want := map[string]string{"test_passed": "PASS", "test_failed": "FAIL", "test_skipped": "SKIP", "test_xfailed": "XFAIL", "test_xpassed": "XPASS"}
+	for name, status := range want {
+		if statuses[name] != status {
+			t.Errorf("Expected %s to be %s, got %v", name, status, statuses)
+		}
+	}
+	testutil.AssertFileContains(t, report, "Skipped: needs a GPU", "Expected failure: rounding bug, issue 12", "Passed, but expected to fail: flaky on old parsers")
+	testutil.AssertFileContains(t, filepath.Join(runDir, "test-run.md"), "## Unexpected passes")
+
+	// pytest passes a run whose only surprise is an unexpected pass, unless asked not to
+	result = testutil.RunThreepio(t, fixtureDir, "pytest", "test_outcomes.py")
+	if result.ExitCode != 0 {
+		t.Errorf("Expected exit code 0 without --fail-on-xpass, got %d:\n%s", result.ExitCode, result.Stdout)
+	}
+	result = testutil.RunThreepio(t, fixtureDir, "--fail-on-xpass", "pytest", "test_outcomes.py")
+	if result.ExitCode == 0 {
+		t.Errorf("Expected a non-zero exit code with --fail-on-xpass, got 0:\n%s", result.Stdout)
+	}
+}
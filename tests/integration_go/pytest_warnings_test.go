@@ -0,0 +1,35 @@
+package integration_test
+
+import (
+	"path/filepath"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestPytestWarnings checks that pytest's warnings make it into the report, and fail the
+// run only with --fail-on-warnings
+func TestPytestWarnings(t *testing.T) {
+	if _, err := testutil.LookPath("pytest"); err != nil {
+		t.Skip("pytest not found in PATH")
+	}
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "pytest-warnings"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	result := testutil.RunThreepio(t, fixtureDir, "pytest")
+	if result.ExitCode != 0 {
+		t.Errorf("Expected warnings not to fail the run, got exit code %d:\n%s", result.ExitCode, result.Stdout)
+	}
+	// Both tests warn from the same line of parse_legacy, so the warning is listed once
+	testutil.AssertFileContains(t, filepath.Join(fixtureDir, ".3pio", "runs", result.RunID, "test-run.md"),
+		"- Warnings: 1, see Warnings below",
+		"- **DeprecationWarning** at `./test_warnings.py:5`: parse_legacy() is deprecated, use int()")
+
+	result = testutil.RunThreepio(t, fixtureDir, "--fail-on-warnings", "pytest")
+	if result.ExitCode == 0 {
+		t.Errorf("Expected a non-zero exit code with --fail-on-warnings, got 0:\n%s", result.Stdout)
+	}
+}
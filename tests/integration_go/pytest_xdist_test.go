@@ -0,0 +1,72 @@
+package integration_test
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestPytestXdist runs 200 tests on 4 pytest-xdist workers and checks the results
+// match a serial run, with each failure keeping the output of its own test
+func TestPytestXdist(t *testing.T) {
+	if _, err := testutil.LookPath("pytest"); err != nil {
+		t.Skip("pytest not found in PATH")
+	}
+	if err := exec.Command("python3", "-c", "import xdist").Run(); err != nil {
+		t.Skip("pytest-xdist not installed")
+	}
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "pytest-xdist"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	readSummary := func(runID string) summaryJSON {
+		t.Helper()
+		data, err := os.ReadFile(filepath.Join(fixtureDir, ".3pio", "runs", runID, "summary.json"))
+		if err != nil {
+			t.Fatalf("Failed to read summary.json: %v", err)
+		}
+		var summary summaryJSON
+		if err := json.Unmarshal(data, &summary); err != nil {
+			t.Fatalf("Failed to parse summary.json: %v", err)
+		}
+		return summary
+	}
+
+	serial := readSummary(testutil.RunThreepio(t, fixtureDir, "pytest").RunID)
+	result := testutil.RunThreepio(t, fixtureDir, "pytest", "-n", "4")
+	parallel := readSummary(result.RunID)
+
+	if parallel.Totals != serial.Totals || parallel.Totals.Total != 200 || parallel.Totals.Failed != 4 {
+		t.Errorf("Expected the totals of the serial run with 200 tests and 4 failures, got %+v (serial %+v)", parallel.Totals, serial.Totals)
+	}
+	runDir := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID)
+	testutil.AssertFileContains(t, filepath.Join(runDir, "test-run.md"), "workers: 4")
+
+	for _, group := range parallel.Groups {
+		if !strings.HasSuffix(group.Name, "test_many.py") {
+			continue
+		}
+		content, err := os.ReadFile(filepath.Join(runDir, group.Report))
+		if err != nil {
+			t.Fatalf("Failed to read the group report: %v", err)
+		}
+		// Each failure's output is its own test's, not that of a test on another worker
+		for _, n := range []int{7, 57, 107, 157} {
+			want := fmt.Sprintf("checking value %d\n", n)
+			if !strings.Contains(string(content), want) {
+				t.Errorf("Expected the output %q of test_value[%d] in the report", want, n)
+			}
+		}
+		if strings.Count(string(content), "checking value ") != 4 {
+			t.Errorf("Expected only the 4 failures' output in the report, got:\n%s", content)
+		}
+	}
+}
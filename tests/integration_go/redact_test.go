@@ -0,0 +1,67 @@
+package integration_test
+
+import (
+	"io/fs"
+	"net/url"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// fakeToken has a quote, a slash and a plus, so it reads differently URL-encoded
+// and inside a JSON string
+const fakeToken = `ghp_fake/Token+"0123456789`
+
+// TestRedactSecretsFromReports runs a test that prints a token from its environment
+func TestRedactSecretsFromReports(t *testing.T) {
+	if _, err := testutil.LookPath("go"); err != nil {
+		t.Skip("go not found in PATH")
+	}
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "go-redact"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	t.Setenv("FAKE_SERVICE_TOKEN", fakeToken)
+	leaks := []string{fakeToken, url.QueryEscape(fakeToken), `ghp_fake/Token+\"0123456789`}
+
+	result := testutil.RunThreepio(t, fixtureDir, "--redact", "go", "test", "-count=1", "./...")
+	if result.ExitCode != 1 {
+		t.Fatalf("Expected exit code 1, got %d:\n%s", result.ExitCode, result.Stdout)
+	}
+	runDir := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID)
+
+	// Every report has the token replaced; output.log keeps it without --redact-raw
+	err = filepath.WalkDir(runDir, func(path string, d fs.DirEntry, err error) error {
+		if err != nil || d.IsDir() || d.Name() == "output.log" {
+			return err
+		}
+		content, err := os.ReadFile(path)
+		if err != nil {
+			return err
+		}
+		for _, leak := range leaks {
+			if strings.Contains(string(content), leak) {
+				t.Errorf("Expected %q to be redacted from %s, got:\n%s", leak, path, content)
+			}
+		}
+		return nil
+	})
+	if err != nil {
+		t.Fatalf("Failed to read the run directory: %v", err)
+	}
+	testutil.AssertFileContains(t, filepath.Join(runDir, "test-run.md"), "«redacted:FAKE_SERVICE_TOKEN»")
+	// go test -json writes the output as JSON strings, where the URL-encoded form reads the same
+	testutil.AssertFileContains(t, filepath.Join(runDir, "output.log"), url.QueryEscape(fakeToken))
+
+	// --redact-raw also rewrites output.log
+	result = testutil.RunThreepio(t, fixtureDir, "--redact-raw", "go", "test", "-count=1", "./...")
+	if result.ExitCode != 1 {
+		t.Fatalf("Expected exit code 1 with --redact-raw, got %d", result.ExitCode)
+	}
+	outputLog := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID, "output.log")
+	testutil.AssertFileNotContains(t, outputLog, leaks...)
+	testutil.AssertFileContains(t, outputLog, "«redacted:FAKE_SERVICE_TOKEN»")
+}
@@ -0,0 +1,102 @@
+package integration_test
+
+import (
+	"io"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"regexp"
+	"strings"
+	"testing"
+	"time"
+)
+
+// reportStatusPattern matches the status line of test-run.md's frontmatter
+var reportStatusPattern = regexp.MustCompile(`(?m)^status: (PENDING|RUNNING|COMPLETED|ERRORED|INTERRUPTED)$`)
+
+// checkCompleteReport returns why content isn't a complete test-run.md, or ""
+func checkCompleteReport(content string) string {
+	if !strings.HasPrefix(content, "---\n") {
+		return "missing frontmatter"
+	}
+	end := strings.Index(content, "\n---\n\n# 3pio Test Run\n")
+	if end < 0 {
+		return "missing end of frontmatter or header"
+	}
+	if !reportStatusPattern.MatchString(content[:end]) {
+		return "missing status"
+	}
+	if !strings.Contains(content[end:], "\n## ") || !strings.HasSuffix(content, "\n") {
+		return "truncated body"
+	}
+	return ""
+}
+
+func TestReportNeverTornWhileTailing(t *testing.T) {
+	if _, err := exec.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+
+	projectDir := filepath.Join(fixturesDir, "rust-performance")
+	if err := cleanProjectOutput(projectDir); err != nil {
+		t.Fatalf("Failed to clean project output: %v", err)
+	}
+
+	binaryPath, err := filepath.Abs(threePioBinary)
+	if err != nil {
+		t.Fatalf("Failed to get absolute path to binary: %v", err)
+	}
+
+	cmd := exec.Command(binaryPath, "cargo", "test")
+	cmd.Dir = projectDir
+	cmd.Stdout = io.Discard
+	cmd.Stderr = io.Discard
+	if err := cmd.Start(); err != nil {
+		t.Fatalf("Failed to start command: %v", err)
+	}
+	done := make(chan error, 1)
+	go func() {
+		done <- cmd.Wait()
+	}()
+
+	// Read test-run.md in a tight loop until 3pio exits
+	runsDir := filepath.Join(projectDir, ".3pio", "runs")
+	var reportPath string
+	reads, statuses := 0, make(map[string]bool)
+	timeout := time.After(3 * time.Minute)
+	for running := true; running; {
+		select {
+		case <-done:
+			running = false
+		case <-timeout:
+			_ = cmd.Process.Kill()
+			t.Fatal("3pio did not finish within 3 minutes")
+		default:
+		}
+
+		if reportPath == "" {
+			entries, err := os.ReadDir(runsDir)
+			if err != nil || len(entries) == 0 {
+				continue
+			}
+			reportPath = filepath.Join(runsDir, entries[0].Name(), "test-run.md")
+		}
+		data, err := os.ReadFile(reportPath)
+		if err != nil {
+			continue // Not written yet
+		}
+		reads++
+		if problem := checkCompleteReport(string(data)); problem != "" {
+			t.Fatalf("Read %d: torn test-run.md (%s), %d bytes:\n%s", reads, problem, len(data), data)
+		}
+		statuses[reportStatusPattern.FindStringSubmatch(string(data))[1]] = true
+	}
+
+	if reads == 0 {
+		t.Fatal("Never read test-run.md during the run")
+	}
+	if !statuses["COMPLETED"] && !statuses["ERRORED"] {
+		t.Errorf("Expected to read the final report, saw statuses %v", statuses)
+	}
+	t.Logf("Read test-run.md %d times, statuses %v", reads, statuses)
+}
@@ -191,16 +191,12 @@ func TestTestResultFormattingInLogFiles(t *testing.T) {
 
 			// Find the run directory (should be only one)
 			runsDir := filepath.Join(threepioDir, "runs")
-			runDirs, err := os.ReadDir(runsDir)
-			if err != nil {
-				t.Fatalf("Failed to read runs directory: %v", err)
-			}
-
+			runDirs := listRunDirs(t, runsDir)
 			if len(runDirs) != 1 {
 				t.Fatalf("Expected exactly one run directory, found %d", len(runDirs))
 			}
 
-			runDir := filepath.Join(runsDir, runDirs[0].Name())
+			runDir := filepath.Join(runsDir, runDirs[0])
 			reportsDir := filepath.Join(runDir, "reports")
 
 			// Check that reports directory exists
@@ -212,7 +208,7 @@ func TestTestResultFormattingInLogFiles(t *testing.T) {
 			var reportFiles []string
 			var allReportContent strings.Builder
 
-			err = filepath.Walk(reportsDir, func(path string, info os.FileInfo, err error) error {
+			err := filepath.Walk(reportsDir, func(path string, info os.FileInfo, err error) error {
 				if err != nil {
 					return err
 				}
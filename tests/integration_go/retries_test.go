@@ -0,0 +1,56 @@
+package integration_test
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestRetriesReportFlakyTests runs a fixture whose test_flaky fails on the first
+// attempt only, and checks --retries reports it as flaky without failing the run
+func TestRetriesReportFlakyTests(t *testing.T) {
+	if _, err := testutil.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+
+	fixtureDir := filepath.Join(fixturesDir, "rust-flaky")
+	if _, err := os.Stat(fixtureDir); os.IsNotExist(err) {
+		t.Skip("rust-flaky fixture not found")
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	// test_always_fails keeps failing, so the run still fails
+	t.Setenv("FLAKY_MARKER", filepath.Join(t.TempDir(), "marker"))
+	result := testutil.RunThreepio(t, fixtureDir, "--retries", "2", "cargo", "test", "--no-fail-fast")
+	if result.ExitCode == 0 {
+		t.Errorf("Expected a non-zero exit code while test_always_fails fails, got 0")
+	}
+	for _, want := range []string{
+		"Retrying 2 failed tests (attempt 2 of 3)",
+		"Retrying 1 failed tests (attempt 3 of 3)",
+		"test_flaky (passed on a retry)",
+		"1 flaky",
+	} {
+		if !strings.Contains(result.Stdout, want) {
+			t.Errorf("Expected console output to contain %q, got:\n%s", want, result.Stdout)
+		}
+	}
+	testutil.AssertFileContains(t, filepath.Join(fixtureDir, ".3pio", "runs", result.RunID, "test-run.md"),
+		"## Flaky tests", "test_flaky`: passed on attempt 2", "- Test cases flaky: 1")
+
+	// Only the flaky test fails, so the run passes unless --fail-on-flaky is given
+	t.Setenv("FLAKY_MARKER", filepath.Join(t.TempDir(), "marker"))
+	result = testutil.RunThreepio(t, fixtureDir, "--retries", "1", "cargo", "test", "--no-fail-fast", "test_flaky")
+	if result.ExitCode != 0 {
+		t.Errorf("Expected exit code 0 when the only failure passed on a retry, got %d:\n%s", result.ExitCode, result.Stdout)
+	}
+
+	t.Setenv("FLAKY_MARKER", filepath.Join(t.TempDir(), "marker"))
+	result = testutil.RunThreepio(t, fixtureDir, "--retries", "1", "--fail-on-flaky", "cargo", "test", "--no-fail-fast", "test_flaky")
+	if result.ExitCode == 0 {
+		t.Errorf("Expected a non-zero exit code with --fail-on-flaky, got 0:\n%s", result.Stdout)
+	}
+}
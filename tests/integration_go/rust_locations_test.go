@@ -0,0 +1,58 @@
+package integration_test
+
+import (
+	"io/fs"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestRustPanicLocationsRelativeToWorkspace fails a test of the utils crate and checks
+// its location is reported relative to the workspace root, whether cargo runs from the
+// root or from inside the crate
+func TestRustPanicLocationsRelativeToWorkspace(t *testing.T) {
+	if _, err := testutil.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+
+	workspaceDir := filepath.Join(fixturesDir, "rust-workspace")
+	if _, err := os.Stat(workspaceDir); os.IsNotExist(err) {
+		t.Skip("rust-workspace fixture not found")
+	}
+	t.Setenv("TOGGLE_FAILURE", "1")
+
+	const want = "Location: utils/src/lib.rs:72:9"
+	for _, dir := range []string{workspaceDir, filepath.Join(workspaceDir, "utils")} {
+		t.Run(filepath.Base(dir), func(t *testing.T) {
+			testutil.CleanupTestRuns(t, dir)
+
+			result := testutil.RunThreepio(t, dir, "cargo", "test")
+			if result.ExitCode == 0 {
+				t.Fatalf("Expected the toggled failure to fail the run, output:\n%s", result.Stdout)
+			}
+
+			found := false
+			runDir := filepath.Join(dir, ".3pio", "runs", result.RunID)
+			err := filepath.WalkDir(runDir, func(path string, d fs.DirEntry, err error) error {
+				if err != nil || d.IsDir() || filepath.Ext(path) != ".md" {
+					return err
+				}
+				content, err := os.ReadFile(path)
+				if err != nil {
+					return err
+				}
+				found = found || strings.Contains(string(content), want)
+				return nil
+			})
+			if err != nil {
+				t.Fatalf("Failed to read reports: %v", err)
+			}
+			if !found {
+				t.Errorf("Expected a report of run %s to contain %q", result.RunID, want)
+			}
+		})
+	}
+}
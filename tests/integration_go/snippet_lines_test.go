@@ -0,0 +1,79 @@
+package integration_test
+
+import (
+	"path/filepath"
+	"regexp"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// failLineRegex matches a failed group's line on the console and its report path
+var failLineRegex = regexp.MustCompile(`^FAIL\(\d+\)(?: [A-Z]+\(\d+\))* (\$trun_dir/\S+)$`)
+
+// TestFailureSnippetLines snapshots the shape of the console output of rust-edge-cases
+// with --snippet-lines 5: each failed group line is followed by at most 5 lines of
+// its first failure and a trailer pointing at its report. The panic text itself
+// depends on the Rust version, so only the fixture's messages are looked for in it.
+func TestFailureSnippetLines(t *testing.T) {
+	if _, err := testutil.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+
+	fixtureDir := filepath.Join(fixturesDir, "rust-edge-cases")
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	// One test thread keeps the order of the failures, and so the snippets, stable
+	result := testutil.RunThreepio(t, fixtureDir, "--snippet-lines", "5", "cargo", "test", "--no-fail-fast", "--", "--test-threads=1")
+	if result.ExitCode == 0 {
+		t.Fatalf("Expected rust-edge-cases to fail, got exit code 0:\n%s", result.Stdout)
+	}
+	if strings.Contains(result.Stdout, "\x1b[") {
+		t.Errorf("Expected no escape codes when stdout isn't a terminal, got:\n%q", result.Stdout)
+	}
+
+	lines := strings.Split(result.Stdout, "\n")
+	blocks := 0
+	var snippets []string
+	for i, line := range lines {
+		match := failLineRegex.FindStringSubmatch(line)
+		if match == nil {
+			continue
+		}
+		blocks++
+		trailer := "  … see " + match[1] + " for full output"
+		end := i + 1
+		for end < len(lines) && lines[end] != trailer {
+			if !strings.HasPrefix(lines[end], "  ") || strings.HasPrefix(lines[end], "  ✕ ") {
+				t.Fatalf("Expected the snippet of %q to end with %q, got:\n%s", line, trailer, strings.Join(lines[i:end+1], "\n"))
+			}
+			end++
+		}
+		if end == len(lines) {
+			t.Fatalf("No trailer after %q", line)
+		}
+		if n := end - i - 1; n < 1 || n > 5 {
+			t.Errorf("Expected 1 to 5 snippet lines under %q, got %d:\n%s", line, n, strings.Join(lines[i:end+1], "\n"))
+		}
+		snippets = append(snippets, lines[i+1:end]...)
+	}
+	if blocks == 0 {
+		t.Fatalf("Expected failed groups on the console, got:\n%s", result.Stdout)
+	}
+	found := false
+	for _, message := range []string{"Math is broken!", "Nested test failure", "Deep panic!", "Unexpected panic occurred!", "Division by zero!"} {
+		if strings.Contains(strings.Join(snippets, "\n"), message) {
+			found = true
+		}
+	}
+	if !found {
+		t.Errorf("Expected a snippet to quote one of the fixture's failures, got:\n%s", strings.Join(snippets, "\n"))
+	}
+
+	// --snippet-lines 0 leaves only the failed group lines and their assertion diffs
+	result = testutil.RunThreepio(t, fixtureDir, "--snippet-lines", "0", "cargo", "test", "--no-fail-fast")
+	if strings.Contains(result.Stdout, "for full output") {
+		t.Errorf("Expected no snippets with --snippet-lines 0, got:\n%s", result.Stdout)
+	}
+}
@@ -0,0 +1,163 @@
+package integration_test
+
+import (
+	"bytes"
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"reflect"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// Mirrors report.RunSummary; decoding with DisallowUnknownFields pins the schema so
+// a field can't be renamed or dropped without this test noticing
+type summaryJSON struct {
+	SchemaVersion  int       `json:"schema_version"`
+	RunID          string    `json:"run_id"`
+	Command        string    `json:"command"`
+	DetectedRunner string    `json:"detected_runner"`
+	Cwd            string    `json:"cwd"`
+	StartTime      time.Time `json:"start_time"`
+	EndTime        time.Time `json:"end_time"`
+	ExitCode       int       `json:"exit_code"`
+	Status         string    `json:"status"`
+	ExitReason     string    `json:"exit_reason"`
+	RerunOf        string    `json:"rerun_of,omitempty"`
+	Totals         struct {
+		Total            int `json:"total"`
+		Passed           int `json:"passed"`
+		Failed           int `json:"failed"`
+		Skipped          int `json:"skipped"`
+		Errors           int `json:"errors"`
+		Benchmarks       int `json:"benchmarks"`
+		CollectionErrors int `json:"collection_errors"`
+	} `json:"totals"`
+	Groups []struct {
+		ID          string   `json:"id"`
+		Name        string   `json:"name"`
+		Path        []string `json:"path"`
+		Status      string   `json:"status"`
+		DurationMs  float64  `json:"duration_ms"`
+		Report      string   `json:"report"`
+		Error       string   `json:"error,omitempty"`
+		FailureKind string   `json:"failure_kind,omitempty"`
+		SkipReason  string   `json:"skip_reason,omitempty"`
+		TestCases   []struct {
+			ID             string  `json:"id"`
+			TestID         string  `json:"test_id,omitempty"`
+			Name           string  `json:"name"`
+			Status         string  `json:"status"`
+			DurationMs     float64 `json:"duration_ms"`
+			FailureMessage string  `json:"failure_message,omitempty"`
+			FailureKind    string  `json:"failure_kind,omitempty"`
+			Report         string  `json:"report"`
+		} `json:"test_cases"`
+	} `json:"groups"`
+}
+
+func TestCargoTestSummaryJSON(t *testing.T) {
+	if _, err := testutil.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+
+	fixtureDir := filepath.Join("..", "fixtures", "rust-comprehensive")
+	if _, err := os.Stat(fixtureDir); os.IsNotExist(err) {
+		t.Skip("rust-comprehensive fixture not found")
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	result := testutil.RunThreepio(t, fixtureDir, "--summary-json", "-", "cargo", "test")
+	runDir := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID)
+
+	data, err := os.ReadFile(filepath.Join(runDir, "summary.json"))
+	if err != nil {
+		t.Fatalf("Failed to read summary.json: %v", err)
+	}
+
+	decoder := json.NewDecoder(bytes.NewReader(data))
+	decoder.DisallowUnknownFields()
+	var summary summaryJSON
+	if err := decoder.Decode(&summary); err != nil {
+		t.Fatalf("summary.json doesn't match the schema: %v\n%s", err, data)
+	}
+
+	// Round trip: re-encoding the decoded summary gives back the same document
+	var original, roundTripped interface{}
+	encoded, err := json.Marshal(summary)
+	if err != nil {
+		t.Fatalf("Failed to encode summary: %v", err)
+	}
+	_ = json.Unmarshal(data, &original)
+	_ = json.Unmarshal(encoded, &roundTripped)
+	if !reflect.DeepEqual(original, roundTripped) {
+		t.Errorf("summary.json didn't survive a round trip:\n%s\n%s", data, encoded)
+	}
+
+	if summary.SchemaVersion != 2 {
+		t.Errorf("Expected schema_version 2, got %d", summary.SchemaVersion)
+	}
+	if summary.RunID != result.RunID || summary.Command != "cargo test" || summary.DetectedRunner != "cargo test" {
+		t.Errorf("Unexpected run metadata: %s, %q, %q", summary.RunID, summary.Command, summary.DetectedRunner)
+	}
+	if summary.ExitCode != result.ExitCode {
+		t.Errorf("Expected exit_code %d, got %d", result.ExitCode, summary.ExitCode)
+	}
+	if summary.Status != "COMPLETED" {
+		t.Errorf("Expected status COMPLETED, got %s", summary.Status)
+	}
+	if expected := map[bool]string{true: "ok", false: "tests_failed"}[result.ExitCode == 0]; summary.ExitReason != expected {
+		t.Errorf("Expected exit_reason %s for exit code %d, got %q", expected, result.ExitCode, summary.ExitReason)
+	}
+	if absFixture, _ := filepath.Abs(fixtureDir); summary.Cwd != absFixture {
+		t.Errorf("Expected cwd %s, got %s", absFixture, summary.Cwd)
+	}
+	if summary.EndTime.Before(summary.StartTime) {
+		t.Errorf("Expected end_time after start_time, got %v and %v", summary.StartTime, summary.EndTime)
+	}
+
+	// Every test case is listed once under its group, and the counts add up
+	statuses := make(map[string]string)
+	total := 0
+	for _, group := range summary.Groups {
+		if group.ID == "" || len(group.Path) == 0 || group.Path[len(group.Path)-1] != group.Name {
+			t.Errorf("Malformed group: %+v", group)
+		}
+		if _, err := os.Stat(filepath.Join(runDir, group.Report)); err != nil {
+			t.Errorf("Missing report %s for group %s: %v", group.Report, group.Name, err)
+		}
+		for _, tc := range group.TestCases {
+			if tc.ID == "" || !strings.HasPrefix(tc.TestID, "t1-") || tc.Report != group.Report {
+				t.Errorf("Malformed test case in %s: %+v", group.Name, tc)
+			}
+			if tc.Status != "BENCH" {
+				total++
+			}
+			statuses[tc.Name] = tc.Status
+		}
+	}
+	if total != summary.Totals.Total {
+		t.Errorf("Expected totals.total %d to match the listed test cases (%d)", summary.Totals.Total, total)
+	}
+	for name, status := range map[string]string{"test_addition": "PASS", "test_ignored": "SKIP"} {
+		if statuses[name] != status {
+			t.Errorf("Expected %s to be %s, got %q", name, status, statuses[name])
+		}
+	}
+
+	// --summary-json - prints the same document after the console summary
+	totalTime := strings.Index(result.Stdout, "Total time:")
+	if totalTime == -1 {
+		t.Fatalf("Console summary missing:\n%s", result.Stdout)
+	}
+	printed := result.Stdout[totalTime:]
+	if start := strings.Index(printed, "{"); start != -1 {
+		printed = printed[start:]
+	}
+	if strings.TrimSpace(printed) != strings.TrimSpace(string(data)) {
+		t.Errorf("Expected summary.json on stdout after the console summary, got:\n%s", printed)
+	}
+}
@@ -0,0 +1,75 @@
+package integration_test
+
+import (
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestTAPRunner runs a shell script that prints TAP with --runner tap and checks its
+// tests land under the script, with the indented subtests as a group
+func TestTAPRunner(t *testing.T) {
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "tap"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	result := testutil.RunThreepio(t, fixtureDir, "--runner", "tap", "sh", "suite.sh")
+	if result.ExitCode != 1 {
+		t.Fatalf("Expected exit code 1 for the failing test, got %d:\n%s", result.ExitCode, result.Stdout)
+	}
+	summary := readSummaryJSON(t, fixtureDir, result.RunID)
+	if summary.DetectedRunner != "tap" {
+		t.Errorf("Expected the tap runner, got %q", summary.DetectedRunner)
+	}
+	if summary.Totals.Total != 5 || summary.Totals.Passed != 3 || summary.Totals.Failed != 1 || summary.Totals.Skipped != 1 {
+		t.Errorf("Expected 5 tests: 3 passed, 1 failed and 1 skipped, got %+v", summary.Totals)
+	}
+
+	statuses := map[string]string{}
+	parents := map[string]string{}
+	for _, group := range summary.Groups {
+		for _, tc := range group.TestCases {
+			statuses[tc.Name] = tc.Status
+			parents[tc.Name] = filepath.Base(group.Name)
+		}
+	}
+	for name, want := range map[string]string{
+		"parses the config":   "PASS",
+		"writes the lockfile": "FAIL",
+		"reloads on SIGHUP":   "SKIP",
+		"reads the header":    "PASS",
+		"reads the body":      "PASS",
+	} {
+		if statuses[name] != want {
+			t.Errorf("Expected %q to be %s, got %q", name, want, statuses[name])
+		}
+	}
+	if parents["parses the config"] != "suite.sh" || parents["reads the body"] != "parses a request" {
+		t.Errorf("Expected the tests under suite.sh and the subtests under parses a request, got %v", parents)
+	}
+}
+
+// TestTAPRunnerNoPlan checks that TAP without a plan errors the run, though the
+// script exited 0 and its tests passed
+func TestTAPRunnerNoPlan(t *testing.T) {
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "tap"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	result := testutil.RunThreepio(t, fixtureDir, "--runner", "tap", "sh", "no-plan.sh")
+	if result.ExitCode != 1 {
+		t.Errorf("Expected exit code 1 for the missing plan, got %d:\n%s", result.ExitCode, result.Stdout)
+	}
+	if !strings.Contains(result.Stdout, "No plan") {
+		t.Errorf("Expected the missing plan to be reported, got:\n%s", result.Stdout)
+	}
+	if summary := readSummaryJSON(t, fixtureDir, result.RunID); summary.Status != "ERRORED" || summary.Totals.Passed != 2 {
+		t.Errorf("Expected an ERRORED run with 2 passed tests, got %s with %+v", summary.Status, summary.Totals)
+	}
+}
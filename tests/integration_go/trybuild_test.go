@@ -0,0 +1,46 @@
+package integration_test
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// TestTrybuildMismatch runs rust-trybuild, whose UI test's .stderr file is stale, and
+// checks the case is reported with its expected and actual compiler output
+func TestTrybuildMismatch(t *testing.T) {
+	if _, err := testutil.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+	fixtureDir := filepath.Join(fixturesDir, "rust-trybuild")
+	t.Cleanup(func() { _ = os.RemoveAll(filepath.Join(fixtureDir, "wip")) })
+
+	testutil.CleanupTestRuns(t, fixtureDir)
+	result := testutil.RunThreepio(t, fixtureDir, "cargo", "test")
+	if result.ExitCode == 0 {
+		t.Fatalf("Expected the stale .stderr file to fail the run. Stdout: %s", result.Stdout)
+	}
+
+	runDir := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID)
+	testutil.AssertFileContains(t, filepath.Join(runDir, "test-run.md"), "trybuild `tests/ui/missing-semicolon.rs`: mismatch")
+
+	var reports strings.Builder
+	err := filepath.Walk(filepath.Join(runDir, "reports"), func(path string, info os.FileInfo, err error) error {
+		if err != nil || !strings.HasSuffix(path, "index.md") {
+			return err
+		}
+		content, err := os.ReadFile(path)
+		reports.Write(content)
+		return err
+	})
+	if err != nil {
+		t.Fatalf("Failed to read reports: %v", err)
+	}
+	if !strings.Contains(reports.String(), "with [tests/ui/missing-semicolon.stderr](") ||
+		!strings.Contains(reports.String(), "-error: expected one of `.`, `;`, `?`, or an operator, found `let`") {
+		t.Errorf("Expected the case's report to link its .stderr file and diff the output, got:\n%s", reports.String())
+	}
+}
@@ -0,0 +1,161 @@
+package integration_test
+
+import (
+	"io/fs"
+	"os"
+	"path/filepath"
+	"reflect"
+	"regexp"
+	"sort"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// verboseTestLineRegex matches a test case printed by --verbose
+var verboseTestLineRegex = regexp.MustCompile(`^(PASS|FAIL|IGNORED|SKIP) .*→ (test_\w+)( \(\d+\.\d{2}s\))?$`)
+
+// TestVerbosityLevels runs rust-basic at each console verbosity and compares the
+// transcripts, and checks the run directories are the same at every level
+func TestVerbosityLevels(t *testing.T) {
+	if _, err := testutil.LookPath("cargo"); err != nil {
+		t.Skip("cargo not found in PATH")
+	}
+
+	fixtureDir := filepath.Join(fixturesDir, "rust-basic")
+	testutil.CleanupTestRuns(t, fixtureDir)
+	runsDir := filepath.Join(fixtureDir, ".3pio", "runs")
+	if err := os.MkdirAll(runsDir, 0755); err != nil {
+		t.Fatalf("Failed to create runs directory: %v", err)
+	}
+
+	transcripts := make(map[string][]string)
+	runFiles := make(map[string][]string)
+	for _, level := range []string{"--quiet", "", "--verbose"} {
+		args := []string{"cargo", "test"}
+		if level != "" {
+			args = append([]string{level}, args...)
+		}
+		before := make(map[string]bool)
+		for _, run := range listRunDirs(t, runsDir) {
+			before[run] = true
+		}
+		result := testutil.RunThreepio(t, fixtureDir, args...)
+		if result.ExitCode != 0 {
+			t.Fatalf("%q: expected exit code 0, got %d\nstdout:\n%s", level, result.ExitCode, result.Stdout)
+		}
+		transcripts[level] = stableLines(result.Stdout)
+
+		var runID string
+		for _, run := range listRunDirs(t, runsDir) {
+			if !before[run] {
+				runID = run
+			}
+		}
+		if runID == "" {
+			t.Fatalf("%q: no new run directory", level)
+		}
+		runFiles[level] = listRunFiles(t, filepath.Join(runsDir, runID))
+	}
+
+	normal := transcripts[""]
+	if !containsLine(normal, "Splendid! All tests passed successfully") {
+		t.Fatalf("Expected the default summary, got:\n%s", strings.Join(normal, "\n"))
+	}
+
+	// --quiet prints only the results line for a passing run
+	var results string
+	for _, line := range normal {
+		if strings.HasPrefix(line, "Results:") {
+			results = line
+		}
+	}
+	if quiet := transcripts["--quiet"]; !reflect.DeepEqual(quiet, []string{results}) {
+		t.Errorf("Expected --quiet to print only %q, got:\n%s", results, strings.Join(quiet, "\n"))
+	}
+
+	// --verbose adds a line per test case to the default transcript
+	var tests, rest []string
+	for _, line := range transcripts["--verbose"] {
+		if match := verboseTestLineRegex.FindStringSubmatch(line); match != nil {
+			tests = append(tests, match[2])
+		} else {
+			rest = append(rest, line)
+		}
+	}
+	if !reflect.DeepEqual(rest, normal) {
+		t.Errorf("Expected --verbose without its test lines to match the default transcript\ngot:\n%s\nwant:\n%s",
+			strings.Join(rest, "\n"), strings.Join(normal, "\n"))
+	}
+	sort.Strings(tests)
+	for _, name := range []string{"test_add", "test_combined_operations", "test_divide", "test_ignored", "test_that_fails"} {
+		if i := sort.SearchStrings(tests, name); i == len(tests) || tests[i] != name {
+			t.Errorf("Expected a --verbose line for %s, got %v", name, tests)
+		}
+	}
+	if !containsMatch(transcripts["--verbose"], `^IGNORED .*→ test_ignored$`) {
+		t.Errorf("Expected test_ignored shown as IGNORED, got:\n%s", strings.Join(transcripts["--verbose"], "\n"))
+	}
+
+	// The run directory doesn't depend on the console
+	for _, level := range []string{"--quiet", "--verbose"} {
+		if !reflect.DeepEqual(runFiles[level], runFiles[""]) {
+			t.Errorf("%s wrote %v, want %v", level, runFiles[level], runFiles[""])
+		}
+	}
+}
+
+// stableLines returns the lines of a transcript without those that change between
+// runs: the time, the run directory and the run's duration
+func stableLines(stdout string) []string {
+	var lines []string
+	for _, line := range strings.Split(strings.TrimSpace(stdout), "\n") {
+		if strings.HasPrefix(line, "current_time:") || strings.HasPrefix(line, "trun_dir:") || strings.HasPrefix(line, "Total time:") {
+			continue
+		}
+		lines = append(lines, line)
+	}
+	return lines
+}
+
+func containsLine(lines []string, want string) bool {
+	for _, line := range lines {
+		if line == want {
+			return true
+		}
+	}
+	return false
+}
+
+func containsMatch(lines []string, pattern string) bool {
+	re := regexp.MustCompile(pattern)
+	for _, line := range lines {
+		if re.MatchString(line) {
+			return true
+		}
+	}
+	return false
+}
+
+// listRunFiles returns the files of a run directory, relative to it
+func listRunFiles(t *testing.T, runDir string) []string {
+	t.Helper()
+	var files []string
+	err := filepath.WalkDir(runDir, func(path string, d fs.DirEntry, err error) error {
+		if err != nil || d.IsDir() {
+			return err
+		}
+		rel, err := filepath.Rel(runDir, path)
+		if err != nil {
+			return err
+		}
+		files = append(files, filepath.ToSlash(rel))
+		return nil
+	})
+	if err != nil {
+		t.Fatalf("Failed to list %s: %v", runDir, err)
+	}
+	sort.Strings(files)
+	return files
+}
@@ -0,0 +1,102 @@
+package integration_test
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"slices"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// readSummaryJSON reads the summary.json of a run in a fixture
+func readSummaryJSON(t *testing.T, fixtureDir, runID string) summaryJSON {
+	t.Helper()
+	data, err := os.ReadFile(filepath.Join(fixtureDir, ".3pio", "runs", runID, "summary.json"))
+	if err != nil {
+		t.Fatalf("Failed to read summary.json: %v", err)
+	}
+	var summary summaryJSON
+	if err := json.Unmarshal(data, &summary); err != nil {
+		t.Fatalf("Failed to parse summary.json: %v", err)
+	}
+	return summary
+}
+
+// TestVitestOutcomes runs a script that starts Vitest in watch mode, so 3pio has to force
+// --run, and checks every outcome lands in the describe block's group with its output
+func TestVitestOutcomes(t *testing.T) {
+	if _, err := testutil.LookPath("npx"); err != nil {
+		t.Skip("npx not found in PATH")
+	}
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "vitest-outcomes"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	result := testutil.RunThreepio(t, fixtureDir, "npm", "test")
+	if result.ExitCode != 1 {
+		t.Fatalf("Expected exit code 1 for the failing test, got %d:\n%s", result.ExitCode, result.Stdout)
+	}
+	summary := readSummaryJSON(t, fixtureDir, result.RunID)
+	if summary.Totals.Total != 5 || summary.Totals.Passed != 2 || summary.Totals.Failed != 1 || summary.Totals.Skipped != 2 {
+		t.Errorf("Expected 5 tests: 2 passed, 1 failed and 2 skipped, got %+v", summary.Totals)
+	}
+
+	runDir := filepath.Join(fixtureDir, ".3pio", "runs", result.RunID)
+	statuses := map[string]string{}
+	reports := map[string]string{}
+	for _, group := range summary.Groups {
+		reports[group.Name] = filepath.Join(runDir, group.Report)
+		for _, tc := range group.TestCases {
+			statuses[tc.Name] = tc.Status
+			if tc.Name == "adds an item" && !slices.Equal(group.Path[1:], []string{"Cart"}) {
+				t.Errorf("Expected %q under the Cart group, got %v", tc.Name, group.Path)
+			}
+		}
+	}
+	for name, want := range map[string]string{
+		"adds an item":             "PASS",
+		"applies a discount":       "FAIL",
+		"ships abroad":             "SKIP",
+		"saves the cart for later": "SKIP",
+		"charges the total":        "PASS",
+	} {
+		if statuses[name] != want {
+			t.Errorf("Expected %q to be %s, got %q", name, want, statuses[name])
+		}
+	}
+
+	// Console output goes to the group of the test that logged it
+	testutil.AssertFileContains(t, reports["Cart"], "[TODO]", "adding widget to cart")
+	testutil.AssertFileContains(t, reports["checkout"], "charging 90")
+}
+
+// TestVitestProjects checks that groups of a Vitest workspace sit under their project
+func TestVitestProjects(t *testing.T) {
+	if _, err := testutil.LookPath("npx"); err != nil {
+		t.Skip("npx not found in PATH")
+	}
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "vitest-projects"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	result := testutil.RunThreepio(t, fixtureDir, "npx", "vitest", "run")
+	if result.ExitCode != 0 {
+		t.Fatalf("Expected exit code 0, got %d:\n%s", result.ExitCode, result.Stdout)
+	}
+	summary := readSummaryJSON(t, fixtureDir, result.RunID)
+	projects := map[string]string{}
+	for _, group := range summary.Groups {
+		for _, tc := range group.TestCases {
+			projects[tc.Name] = group.Path[0]
+		}
+	}
+	if projects["adds numbers"] != "unit" || projects["renders a title"] != "browser" {
+		t.Errorf("Expected the tests under the unit and browser projects, got %v", projects)
+	}
+}
@@ -0,0 +1,94 @@
+package integration_test
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/zk/3pio/tests/testutil"
+)
+
+// skipWithoutWasmPack skips the wasm-pack test tests unless THREEPIO_WASM_TESTS=1, as
+// they need the wasm32 target, wasm-pack and, for the browsers, a WebDriver
+func skipWithoutWasmPack(t *testing.T) {
+	t.Helper()
+	if os.Getenv("THREEPIO_WASM_TESTS") != "1" {
+		t.Skip("set THREEPIO_WASM_TESTS=1 to run the wasm-pack test tests")
+	}
+	if _, err := testutil.LookPath("wasm-pack"); err != nil {
+		t.Skip("wasm-pack not found in PATH")
+	}
+}
+
+// TestWasmPackTest runs the fixture's tests in Node.js, which needs no WebDriver, and
+// checks they land under the crate's tests module
+func TestWasmPackTest(t *testing.T) {
+	skipWithoutWasmPack(t)
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "rust-wasm-pack"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	result := testutil.RunThreepio(t, fixtureDir, "wasm-pack", "test", "--node")
+	if result.ExitCode != 1 {
+		t.Fatalf("Expected exit code 1 for the failing test, got %d:\n%s", result.ExitCode, result.Stdout)
+	}
+	summary := readSummaryJSON(t, fixtureDir, result.RunID)
+	if summary.DetectedRunner != "wasm-pack test" {
+		t.Errorf("Expected the wasm-pack test runner, got %q", summary.DetectedRunner)
+	}
+	if summary.ExitReason != "tests_failed" {
+		t.Errorf("Expected exit reason tests_failed, got %q", summary.ExitReason)
+	}
+	if summary.Totals.Total != 3 || summary.Totals.Passed != 1 || summary.Totals.Failed != 1 || summary.Totals.Skipped != 1 {
+		t.Errorf("Expected 3 tests: 1 passed, 1 failed and 1 skipped, got %+v", summary.Totals)
+	}
+
+	statuses := map[string]string{}
+	for _, group := range summary.Groups {
+		for _, tc := range group.TestCases {
+			statuses[strings.Join(group.Path, " > ")+" > "+tc.Name] = tc.Status
+			if tc.Name == "divides" && !strings.Contains(tc.FailureMessage, "assertion `left == right` failed") {
+				t.Errorf("Expected divides to fail with its assertion, got %q", tc.FailureMessage)
+			}
+		}
+	}
+	for name, want := range map[string]string{
+		"rust-wasm-pack > tests > adds":    "PASS",
+		"rust-wasm-pack > tests > divides": "FAIL",
+		"rust-wasm-pack > tests > fetches": "SKIP",
+	} {
+		if statuses[name] != want {
+			t.Errorf("Expected %q to be %s, got %q", name, want, statuses[name])
+		}
+	}
+}
+
+// TestWasmPackTestMissingWebDriver checks a WebDriver that can't be started ends the
+// run as an environment error that says what to install, not as failed tests
+func TestWasmPackTestMissingWebDriver(t *testing.T) {
+	skipWithoutWasmPack(t)
+	fixtureDir, err := filepath.Abs(filepath.Join(fixturesDir, "rust-wasm-pack"))
+	if err != nil {
+		t.Fatalf("Failed to get absolute fixture path: %v", err)
+	}
+	testutil.CleanupTestRuns(t, fixtureDir)
+
+	missing := filepath.Join(t.TempDir(), "geckodriver")
+	result := testutil.RunThreepio(t, fixtureDir, "wasm-pack", "test", "--headless", "--firefox", "--geckodriver", missing)
+	if result.ExitCode == 0 {
+		t.Fatalf("Expected the missing WebDriver to fail the run:\n%s", result.Stdout)
+	}
+	summary := readSummaryJSON(t, fixtureDir, result.RunID)
+	if summary.ExitReason != "environment_error" {
+		t.Errorf("Expected exit reason environment_error, got %q", summary.ExitReason)
+	}
+	if summary.Totals.Failed != 0 {
+		t.Errorf("Expected no failed tests, got %+v", summary.Totals)
+	}
+	if !strings.Contains(result.Stdout, "geckodriver couldn't start headless Firefox") || !strings.Contains(result.Stdout, "GECKODRIVER") {
+		t.Errorf("Expected the console to say how to set up geckodriver, got:\n%s", result.Stdout)
+	}
+}
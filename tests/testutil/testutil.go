@@ -75,9 +75,13 @@ func RunThreepio(t *testing.T, dir string, args ...string) TestResult {
 	if runID == "" {
 		// Try to find the most recent run directory
 		runsDir := filepath.Join(dir, ".3pio", "runs")
-		if entries, err := os.ReadDir(runsDir); err == nil && len(entries) > 0 {
-			// Get the last entry (most recent)
-			runID = entries[len(entries)-1].Name()
+		if entries, err := os.ReadDir(runsDir); err == nil {
+			// Get the last run directory (most recent)
+			for _, entry := range entries {
+				if entry.IsDir() {
+					runID = entry.Name()
+				}
+			}
 		}
 	}
 